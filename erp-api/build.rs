@@ -0,0 +1,31 @@
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Embeds every file in ../migrations so the server and erp-admin carry the full, ordered set.
+fn main() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../migrations").canonicalize().expect("migrations directory");
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut ups: Vec<(String, PathBuf)> = fs::read_dir(&dir)
+        .expect("read migrations directory")
+        .map(|entry| entry.expect("migration entry").path())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let version = name.strip_suffix(".sql")?;
+            (!version.ends_with(".down")).then(|| (version.to_string(), path.clone()))
+        })
+        .collect();
+    ups.sort();
+
+    let mut out = String::from("static EMBEDDED: &[(&str, &str, Option<&str>)] = &[\n");
+    for (version, path) in &ups {
+        let down = dir.join(format!("{version}.down.sql"));
+        let down = if down.exists() { format!("Some(include_str!({:?}))", down.display().to_string()) } else { "None".to_string() };
+        writeln!(out, "    ({version:?}, include_str!({:?}), {down}),", path.display().to_string()).unwrap();
+    }
+    out.push_str("];\n");
+
+    fs::write(PathBuf::from(env::var("OUT_DIR").unwrap()).join("migrations.rs"), out).expect("write embedded migrations");
+}
@@ -0,0 +1,130 @@
+use anyhow::{anyhow, bail, Result};
+use erp_api::migrate::{MigrationState, Migrator};
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: erp-admin [--database-url <url>] <group> <command>
+
+Commands:
+  migrate status              List every migration and whether it has been applied
+  migrate up [--to <version>] Apply pending migrations, optionally stopping after <version>
+  migrate down [--steps <n>]  Revert the last <n> applied migrations (default 1)
+  migrate verify              Fail if an applied migration was edited or removed
+  search rebuild              Reindex every searchable record for global search
+
+The database defaults to $DATABASE_URL, then sqlite:erp.db?mode=rwc.";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    tracing_subscriber::fmt().with_env_filter(
+        tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "warn".into()),
+    ).init();
+
+    match run(std::env::args().skip(1).collect()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(mut args: Vec<String>) -> Result<()> {
+    let mut database_url = std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:erp.db?mode=rwc".to_string());
+    if let Some(url) = take_option(&mut args, "--database-url")? {
+        database_url = url;
+    }
+
+    let (group, command) = match args.as_slice() {
+        [group, command, ..] => (group.clone(), command.clone()),
+        _ => bail!("{USAGE}"),
+    };
+    if group != "migrate" && group != "search" {
+        bail!("{USAGE}");
+    }
+    args.drain(..2);
+
+    let pool = SqlitePoolOptions::new().max_connections(1).connect(&database_url).await?;
+    match group.as_str() {
+        "migrate" => migrate(&pool, &command, args).await,
+        "search" => search(&pool, &command, args).await,
+        _ => bail!("{USAGE}"),
+    }
+}
+
+async fn migrate(pool: &SqlitePool, command: &str, mut args: Vec<String>) -> Result<()> {
+    let migrator = Migrator::embedded();
+
+    match command {
+        "status" => {
+            no_extra(&args)?;
+            let statuses = migrator.status(pool).await?;
+            for status in &statuses {
+                println!(
+                    "{:<9} {:<35} {}{}",
+                    status.state.to_string(),
+                    status.applied_at.as_deref().unwrap_or("-"),
+                    status.version,
+                    if status.reversible { "" } else { " (irreversible)" },
+                );
+            }
+            let pending = statuses.iter().filter(|s| s.state == MigrationState::Pending).count();
+            println!("{} migrations, {} pending", statuses.len(), pending);
+        }
+        "up" => {
+            let target = take_option(&mut args, "--to")?;
+            no_extra(&args)?;
+            let applied = migrator.up(pool, target.as_deref()).await?;
+            for version in &applied {
+                println!("applied {version}");
+            }
+            println!("{} migrations applied", applied.len());
+        }
+        "down" => {
+            let steps = match take_option(&mut args, "--steps")? {
+                Some(steps) => steps.parse().map_err(|_| anyhow!("--steps must be a positive number"))?,
+                None => 1,
+            };
+            no_extra(&args)?;
+            for version in migrator.down(pool, steps).await? {
+                println!("reverted {version}");
+            }
+        }
+        "verify" => {
+            no_extra(&args)?;
+            migrator.verify(pool).await?;
+            println!("All applied migrations match their files");
+        }
+        _ => bail!("{USAGE}"),
+    }
+    Ok(())
+}
+
+async fn search(pool: &SqlitePool, command: &str, args: Vec<String>) -> Result<()> {
+    if command != "rebuild" {
+        bail!("{USAGE}");
+    }
+    no_extra(&args)?;
+    let indexed = erp_enterprise::SearchService::new().rebuild_index(pool).await?;
+    println!("{indexed} records indexed");
+    Ok(())
+}
+
+fn no_extra(args: &[String]) -> Result<()> {
+    match args.first() {
+        Some(extra) => bail!("Unexpected argument {extra}\n\n{USAGE}"),
+        None => Ok(()),
+    }
+}
+
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>> {
+    let Some(index) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        bail!("{name} needs a value");
+    }
+    let value = args.remove(index + 1);
+    args.remove(index);
+    Ok(Some(value))
+}
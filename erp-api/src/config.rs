@@ -28,10 +28,17 @@ pub struct Config {
     pub server_host: String,
     pub server_port: u16,
     pub jwt_secret: String,
-    pub jwt_expiration: i64,
+    pub access_token_minutes: i64,
+    pub refresh_token_days: i64,
     pub cors_allowed_origins: Vec<String>,
     pub trust_proxy: bool,
     pub stripe: Option<erp_payments::StripeConfig>,
+    pub master_key: Option<erp_keys::MasterKey>,
+    pub job_worker_enabled: bool,
+    /// Registers the in-process simulator gateway. For development and testing only.
+    pub payment_simulator_enabled: bool,
+    /// Where files are kept when no storage is configured in the database.
+    pub storage_dir: String,
 }
 
 #[derive(Debug)]
@@ -54,13 +61,18 @@ impl Default for Config {
             server_host: "127.0.0.1".to_string(),
             server_port: 3000,
             jwt_secret: secret,
-            jwt_expiration: 24,
+            access_token_minutes: 15,
+            refresh_token_days: 30,
             cors_allowed_origins: vec![
                 "http://localhost:5173".to_string(),
                 "http://localhost:3000".to_string(),
             ],
             trust_proxy: false,
             stripe: None,
+            master_key: None,
+            job_worker_enabled: true,
+            payment_simulator_enabled: false,
+            storage_dir: "storage".to_string(),
         }
     }
 }
@@ -109,6 +121,11 @@ impl Config {
             );
         }
 
+        let master_key = erp_keys::MasterKey::from_env_if_set().unwrap_or_else(|e| {
+            eprintln!("ERROR: Cannot load the master key: {}", e);
+            std::process::exit(1);
+        });
+
         let cors_origins = env::var("CORS_ALLOWED_ORIGINS")
             .unwrap_or_else(|_| "http://localhost:5173,http://localhost:3000".to_string());
         let cors_allowed_origins: Vec<String> = cors_origins
@@ -126,16 +143,30 @@ impl Config {
                 .and_then(|p| p.parse().ok())
                 .unwrap_or(3000),
             jwt_secret,
-            jwt_expiration: env::var("JWT_EXPIRATION")
+            access_token_minutes: env::var("ACCESS_TOKEN_MINUTES")
                 .ok()
                 .and_then(|p| p.parse().ok())
-                .unwrap_or(24),
+                .unwrap_or(15),
+            refresh_token_days: env::var("REFRESH_TOKEN_DAYS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(30),
             cors_allowed_origins,
             trust_proxy: env::var("TRUST_PROXY")
                 .ok()
                 .and_then(|p| p.parse().ok())
                 .unwrap_or(false),
             stripe: erp_payments::StripeConfig::from_env().ok(),
+            master_key,
+            job_worker_enabled: env::var("JOB_WORKER_ENABLED")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(true),
+            payment_simulator_enabled: env::var("PAYMENT_SIMULATOR_ENABLED")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(false),
+            storage_dir: env::var("STORAGE_DIR").unwrap_or_else(|_| "storage".to_string()),
         }
     }
 }
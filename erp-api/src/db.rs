@@ -2,7 +2,7 @@ use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
 use std::sync::Arc;
 use crate::Config;
 use crate::handlers::websocket::WebSocketManager;
-use erp_auth::AuthService;
+use erp_auth::{AuthService, Authorizer};
 
 #[derive(Clone)]
 pub struct AppState {
@@ -10,12 +10,15 @@ pub struct AppState {
     pub config: Arc<Config>,
     pub ws_manager: WebSocketManager,
     pub auth_svc: Arc<AuthService>,
+    pub authz: Arc<Authorizer>,
     pub project_svc: Arc<erp_projects::ProjectService>,
     pub timesheet_svc: Arc<erp_projects::TimesheetService>,
     pub payment_svc: Arc<erp_payments::PaymentService>,
     pub gateway_svc: Arc<erp_payments::GatewayService>,
     pub stripe_svc: Arc<Option<erp_payments::StripeService>>,
     pub backup_svc: Arc<erp_backup::BackupService>,
+    pub graphql: erp_graphql::GraphQLSchema,
+    pub blobs: Arc<erp_core::BlobService>,
 }
 
 impl AppState {
@@ -34,46 +37,59 @@ impl AppState {
             None
         };
 
+        let mut gateway_svc = erp_payments::GatewayService::new(pool.clone());
+        if config.payment_simulator_enabled {
+            tracing::warn!("Payment simulator enabled. Card payments through Simulator gateways are not real.");
+            gateway_svc = gateway_svc.with_simulator(erp_payments::SimulatorGateway::new());
+        }
+        if let Some(stripe_config) = config.stripe.clone() {
+            gateway_svc = gateway_svc.with_gateway(Arc::new(erp_payments::StripeGateway::new(stripe_config)?));
+        }
+
+        let ws_manager: WebSocketManager = Arc::new(crate::handlers::websocket::WebSocketManagerInner::new());
+        ws_manager.forward_realtime_events();
+
+        let auth_svc = AuthService::new(pool.clone()).with_token_lifetimes(
+            chrono::Duration::minutes(config.access_token_minutes),
+            chrono::Duration::days(config.refresh_token_days),
+        );
+
+        let blobs = blob_service(&pool, &config).await?;
+
         Ok(Self {
             pool: pool.clone(),
             config: Arc::new(config),
-            ws_manager: Arc::new(crate::handlers::websocket::WebSocketManagerInner::new()),
-            auth_svc: Arc::new(AuthService::new(pool.clone())),
+            ws_manager,
+            auth_svc: Arc::new(auth_svc),
+            authz: Arc::new(Authorizer::new(pool.clone())),
             project_svc: Arc::new(erp_projects::ProjectService::new(pool.clone())),
             timesheet_svc: Arc::new(erp_projects::TimesheetService::new(pool.clone())),
             payment_svc: Arc::new(erp_payments::PaymentService::new(pool.clone())),
-            gateway_svc: Arc::new(erp_payments::GatewayService::new(pool.clone())),
+            gateway_svc: Arc::new(gateway_svc),
             stripe_svc: Arc::new(stripe_svc),
-            backup_svc: Arc::new(erp_backup::BackupService::new(pool)),
+            backup_svc: Arc::new(erp_backup::BackupService::new(pool.clone())),
+            graphql: erp_graphql::build_schema(pool),
+            blobs: Arc::new(blobs),
         })
     }
 }
 
-async fn run_migrations(pool: &SqlitePool) -> anyhow::Result<()> {
-    let migration_queries: Vec<&str> = vec![
-        include_str!("../../migrations/20240101000000_finance.sql"),
-        include_str!("../../migrations/20240101000001_inventory.sql"),
-        include_str!("../../migrations/20240101000002_sales.sql"),
-        include_str!("../../migrations/20240101000003_purchasing.sql"),
-        include_str!("../../migrations/20240101000005_hr.sql"),
-        include_str!("../../migrations/20240101000006_auth.sql"),
-        include_str!("../../migrations/20240101000013_enterprise_features.sql"),
-        include_str!("../../migrations/20240101200500_enterprise_wms_demand_edi_tenant_revrec_intercompany_lms.sql"),
-        include_str!("../../migrations/20240304000000_stripe_payments.sql"),
-    ];
-    
-    for query in migration_queries {
-        for statement in query.split(';') {
-            let statement = statement.trim();
-            if !statement.is_empty() {
-                if let Err(e) = sqlx::query(statement).execute(pool).await {
-                    if !e.to_string().contains("already exists") {
-                        return Err(anyhow::anyhow!(e));
-                    }
-                }
-            }
-        }
-    }
-    
+/// Stores files where the default storage configuration says, else under `config.storage_dir`.
+async fn blob_service(pool: &SqlitePool, config: &Config) -> anyhow::Result<erp_core::BlobService> {
+    let staging_dir = std::path::Path::new(&config.storage_dir).join("staging");
+    let blobs = match erp_config::ConfigService::new().default_storage_config(pool).await? {
+        Some(storage) => erp_core::BlobService::new(storage.blob_store()?, staging_dir)
+            .with_default_policy(storage.upload_policy()),
+        None => erp_core::BlobService::new(
+            Arc::new(erp_core::blob::LocalBlobStore::new(std::path::Path::new(&config.storage_dir).join("blobs"))),
+            staging_dir,
+        ),
+    };
+    Ok(blobs.with_signing_key(config.jwt_secret.as_bytes()))
+}
+
+/// Brings the schema up to date; see [`crate::migrate`].
+pub async fn run_migrations(pool: &SqlitePool) -> anyhow::Result<()> {
+    crate::migrate::Migrator::embedded().up(pool, None).await?;
     Ok(())
 }
@@ -19,6 +19,7 @@ impl IntoResponse for ApiError {
             Error::BusinessRule(_) => (StatusCode::UNPROCESSABLE_ENTITY, self.0.to_string()),
             Error::Conflict(_) => (StatusCode::CONFLICT, self.0.to_string()),
             Error::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized".to_string()),
+            Error::Forbidden(_) => (StatusCode::FORBIDDEN, self.0.to_string()),
             Error::Database(e) => {
                 tracing::error!("Database error: {}", e);
                 (
@@ -53,7 +54,10 @@ impl From<sqlx::Error> for ApiError {
 
 impl From<anyhow::Error> for ApiError {
     fn from(err: anyhow::Error) -> Self {
-        ApiError(Error::Internal(err))
+        match err.downcast::<Error>() {
+            Ok(err) => ApiError(err),
+            Err(err) => ApiError(Error::Internal(err)),
+        }
     }
 }
 
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use erp_core::Pagination;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 use erp_ai::{AIModelService, PredictionService, AnomalyService, ForecastService, RecommendationService, CustomerInsightService};
 use erp_ai::{AIModel, ModelType, ModelStatus};
 
@@ -312,14 +313,14 @@ pub async fn get_customer_insights(
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/models", axum::routing::get(list_models).post(create_model))
-        .route("/models/:id", axum::routing::get(get_model))
-        .route("/models/:id/deploy", axum::routing::post(deploy_model))
-        .route("/predict", axum::routing::post(predict))
-        .route("/forecast/demand", axum::routing::post(forecast_demand))
-        .route("/anomalies", axum::routing::get(list_anomalies))
-        .route("/anomalies/:id/acknowledge", axum::routing::post(acknowledge_anomaly))
-        .route("/anomalies/:id/resolve", axum::routing::post(resolve_anomaly))
-        .route("/recommendations/:customer_id", axum::routing::get(get_recommendations))
-        .route("/insights/:customer_id", axum::routing::get(get_customer_insights))
+        .route("/models", axum::routing::get(list_models).post(create_model).require("ai:models"))
+        .route("/models/:id", axum::routing::get(get_model).require("ai:models:read"))
+        .route("/models/:id/deploy", axum::routing::post(deploy_model).require("ai:models:write"))
+        .route("/predict", axum::routing::post(predict).require("ai:predict:write"))
+        .route("/forecast/demand", axum::routing::post(forecast_demand).require("ai:forecast:write"))
+        .route("/anomalies", axum::routing::get(list_anomalies).require("ai:anomalies:read"))
+        .route("/anomalies/:id/acknowledge", axum::routing::post(acknowledge_anomaly).require("ai:anomalies:write"))
+        .route("/anomalies/:id/resolve", axum::routing::post(resolve_anomaly).require("ai:anomalies:write"))
+        .route("/recommendations/:customer_id", axum::routing::get(get_recommendations).require("ai:recommendations:read"))
+        .route("/insights/:customer_id", axum::routing::get(get_customer_insights).require("ai:insights:read"))
 }
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateMpsRequest {
@@ -34,19 +35,19 @@ pub struct CreateScheduleRequest {
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/mps", get(list_mps).post(create_mps))
-        .route("/mps/:id", get(get_mps))
-        .route("/mps/:id/release", post(release_mps))
-        .route("/mrp", get(list_mrp).post(run_mrp))
-        .route("/mrp/:id", get(get_mrp))
-        .route("/mrp/:id/suggestions", get(get_mrp_suggestions))
-        .route("/schedules", get(list_schedules).post(create_schedule))
-        .route("/schedules/:id", get(get_schedule))
-        .route("/schedules/:id/optimize", post(optimize_schedule))
-        .route("/capacity", get(analyze_capacity))
-        .route("/resources", get(list_resources))
-        .route("/exceptions", get(list_exceptions))
-        .route("/scenarios", post(create_scenario))
+        .route("/mps", get(list_mps).post(create_mps).require("aps:mps"))
+        .route("/mps/:id", get(get_mps).require("aps:mps:read"))
+        .route("/mps/:id/release", post(release_mps).require("aps:mps:write"))
+        .route("/mrp", get(list_mrp).post(run_mrp).require("aps:mrp"))
+        .route("/mrp/:id", get(get_mrp).require("aps:mrp:read"))
+        .route("/mrp/:id/suggestions", get(get_mrp_suggestions).require("aps:mrp:read"))
+        .route("/schedules", get(list_schedules).post(create_schedule).require("aps:schedules"))
+        .route("/schedules/:id", get(get_schedule).require("aps:schedules:read"))
+        .route("/schedules/:id/optimize", post(optimize_schedule).require("aps:schedules:write"))
+        .route("/capacity", get(analyze_capacity).require("aps:capacity:read"))
+        .route("/resources", get(list_resources).require("aps:resources:read"))
+        .route("/exceptions", get(list_exceptions).require("aps:exceptions:read"))
+        .route("/scenarios", post(create_scenario).require("aps:scenarios:write"))
 }
 
 async fn list_mps(State(_state): State<AppState>) -> Json<serde_json::Value> {
@@ -7,21 +7,22 @@ use serde::Deserialize;
 use uuid::Uuid;
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_assistant::{AssistantService, CreateConversationRequest, SendMessageRequest, MessageFeedbackRequest};
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/conversations", axum::routing::get(list_conversations).post(create_conversation))
-        .route("/conversations/:id", axum::routing::get(get_conversation).delete(archive_conversation))
-        .route("/conversations/:id/messages", axum::routing::post(send_message).get(list_messages))
-        .route("/messages/:id/feedback", axum::routing::post(provide_feedback))
-        .route("/intents", axum::routing::get(list_intents).post(create_intent))
-        .route("/intents/:id", axum::routing::get(get_intent).delete(delete_intent))
-        .route("/skills", axum::routing::get(list_skills).post(create_skill))
-        .route("/skills/:id", axum::routing::get(get_skill).delete(delete_skill))
-        .route("/quick-actions", axum::routing::get(list_quick_actions).post(create_quick_action))
-        .route("/quick-actions/:id", axum::routing::delete(delete_quick_action))
-        .route("/parse", axum::routing::post(parse_query))
+        .route("/conversations", axum::routing::get(list_conversations).post(create_conversation).require("assistant:conversations"))
+        .route("/conversations/:id", axum::routing::get(get_conversation).delete(archive_conversation).require("assistant:conversations"))
+        .route("/conversations/:id/messages", axum::routing::post(send_message).get(list_messages).require("assistant:conversations"))
+        .route("/messages/:id/feedback", axum::routing::post(provide_feedback).require("assistant:messages:write"))
+        .route("/intents", axum::routing::get(list_intents).post(create_intent).require("assistant:intents"))
+        .route("/intents/:id", axum::routing::get(get_intent).delete(delete_intent).require("assistant:intents"))
+        .route("/skills", axum::routing::get(list_skills).post(create_skill).require("assistant:skills"))
+        .route("/skills/:id", axum::routing::get(get_skill).delete(delete_skill).require("assistant:skills"))
+        .route("/quick-actions", axum::routing::get(list_quick_actions).post(create_quick_action).require("assistant:quick-actions"))
+        .route("/quick-actions/:id", axum::routing::delete(delete_quick_action).require("assistant:quick-actions:delete"))
+        .route("/parse", axum::routing::post(parse_query).require("assistant:parse:write"))
 }
 
 #[derive(Deserialize)]
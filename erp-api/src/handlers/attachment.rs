@@ -1,11 +1,14 @@
 use axum::{
     extract::{Multipart, Path, Query, State},
-    Json,
+    response::Response,
+    Extension, Json,
 };
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::handlers::auth::AuthUser;
+use crate::handlers::files;
 use erp_core::{Attachment, AttachmentService};
 
 const ALLOWED_ENTITY_TYPES: &[&str] = &[
@@ -13,20 +16,6 @@ const ALLOWED_ENTITY_TYPES: &[&str] = &[
     "accounts", "journal_entries", "employees"
 ];
 
-const MAX_FILE_SIZE: usize = 10 * 1024 * 1024;
-
-fn sanitize_filename(filename: &str) -> String {
-    let sanitized: String = filename
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '.' || *c == '-' || *c == '_')
-        .collect();
-    if sanitized.is_empty() {
-        "file".to_string()
-    } else {
-        sanitized
-    }
-}
-
 fn validate_entity_type(entity_type: &str) -> Result<(), erp_core::Error> {
     if entity_type.contains("..") || entity_type.contains('/') || entity_type.contains('\\') {
         return Err(erp_core::Error::validation("Invalid entity_type: path traversal detected"));
@@ -66,6 +55,7 @@ pub struct AttachmentResponse {
     pub original_filename: String,
     pub mime_type: String,
     pub file_size: i64,
+    pub checksum: Option<String>,
     pub created_at: String,
 }
 
@@ -79,6 +69,7 @@ impl From<Attachment> for AttachmentResponse {
             original_filename: a.original_filename,
             mime_type: a.mime_type,
             file_size: a.file_size,
+            checksum: a.checksum,
             created_at: a.created_at.to_rfc3339(),
         }
     }
@@ -102,56 +93,32 @@ pub async fn list_attachments(
 
 pub async fn upload_attachment(
     State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
     Query(query): Query<AttachmentQuery>,
-    mut multipart: Multipart,
+    multipart: Multipart,
 ) -> ApiResult<Json<AttachmentResponse>> {
     validate_entity_type(&query.entity_type)?;
     validate_entity_id(&query.entity_id)?;
-    
-    #[allow(clippy::never_loop)]
-    while let Some(field) = multipart.next_field().await.map_err(|e| {
-        erp_core::Error::validation(format!("Failed to read multipart: {}", e))
-    })? {
-        let original_filename = sanitize_filename(field.file_name().unwrap_or("unknown"));
-        let mime_type = field.content_type().unwrap_or("application/octet-stream").to_string();
-        let data = field.bytes().await.map_err(|e| {
-            erp_core::Error::validation(format!("Failed to read file data: {}", e))
-        })?;
-        
-        if data.len() > MAX_FILE_SIZE {
-            return Err(erp_core::Error::validation(format!(
-                "File too large. Maximum size is {} bytes",
-                MAX_FILE_SIZE
-            )).into());
-        }
-        
-        let file_size = data.len() as i64;
-        
-        let upload_dir = std::path::PathBuf::from("uploads").join(&query.entity_type);
-        std::fs::create_dir_all(&upload_dir).map_err(|e| {
-            erp_core::Error::validation(format!("Failed to create upload directory: {}", e))
-        })?;
-        
-        let attachment = Attachment::new(
-            &query.entity_type,
-            &query.entity_id,
-            &original_filename,
-            &mime_type,
-            file_size,
-            None,
-        );
-        
-        let file_path = std::path::PathBuf::from(&attachment.file_path);
-        std::fs::write(&file_path, &data).map_err(|e| {
-            erp_core::Error::validation(format!("Failed to write file: {}", e))
-        })?;
-        
-        let created = AttachmentService::create(&state.pool, attachment).await?;
-        
-        return Ok(Json(AttachmentResponse::from(created)));
-    }
-    
-    Err(erp_core::Error::validation("No file provided").into())
+
+    let upload = files::read_upload(&state.blobs, multipart, state.blobs.default_policy().max_size).await?;
+    let attachment = Attachment::new(
+        &query.entity_type,
+        &query.entity_id,
+        &upload.file_name,
+        &upload.content_type,
+        upload.file.size as i64,
+        Some(user.user_id()),
+    );
+    let expected_sha256 = upload.expected_sha256().map(str::to_string);
+    let created = AttachmentService::create(
+        &state.pool,
+        &state.blobs,
+        attachment,
+        upload.file,
+        expected_sha256.as_deref(),
+    ).await?;
+
+    Ok(Json(AttachmentResponse::from(created)))
 }
 
 pub async fn get_attachment(
@@ -162,15 +129,36 @@ pub async fn get_attachment(
     Ok(Json(AttachmentResponse::from(attachment)))
 }
 
+fn stored_checksum(attachment: &Attachment) -> ApiResult<&str> {
+    attachment.checksum.as_deref().ok_or_else(|| {
+        erp_core::Error::not_found("Attachment content", &attachment.id.to_string()).into()
+    })
+}
+
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Response> {
+    let attachment = AttachmentService::get(&state.pool, id).await?;
+    let (blob, stream) = state.blobs.open(&state.pool, stored_checksum(&attachment)?).await?;
+    Ok(files::blob_response(&blob, stream, &attachment.original_filename))
+}
+
+pub async fn attachment_download_url(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<files::DownloadUrlQuery>,
+) -> ApiResult<Json<files::DownloadUrlResponse>> {
+    let attachment = AttachmentService::get(&state.pool, id).await?;
+    let url = files::download_url(&state.blobs, stored_checksum(&attachment)?, &attachment.original_filename, &query)?;
+    Ok(Json(url))
+}
+
 pub async fn delete_attachment(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<serde_json::Value>> {
-    let attachment = AttachmentService::get(&state.pool, id).await?;
-    
-    std::fs::remove_file(&attachment.file_path).ok();
-    
-    AttachmentService::delete(&state.pool, id).await?;
+    AttachmentService::delete(&state.pool, &state.blobs, id).await?;
     
     Ok(Json(serde_json::json!({ "status": "deleted" })))
 }
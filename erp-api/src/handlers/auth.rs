@@ -1,46 +1,120 @@
 use axum::{
-    extract::State,
+    extract::{ConnectInfo, State},
     Json,
-    http::{Request, StatusCode, header::AUTHORIZATION},
+    http::{HeaderMap, Request, StatusCode, header::{AUTHORIZATION, USER_AGENT}},
     response::Response,
     body::Body,
 };
+use std::net::SocketAddr;
 use crate::db::AppState;
 use crate::error::ApiResult;
-use erp_auth::{LoginRequest, RegisterRequest, AuthResponse, UserInfo};
+use erp_auth::{
+    AuthResponse, ClientInfo, LoginRequest, LoginResponse, PasswordResetConfirm, PasswordResetRequest,
+    RefreshRequest, RegisterRequest, TwoFactorLoginRequest, UserInfo,
+};
 use validator::Validate;
 
+fn client_info(headers: &HeaderMap, addr: Option<ConnectInfo<SocketAddr>>) -> ClientInfo {
+    ClientInfo {
+        ip_address: addr.map(|ConnectInfo(addr)| addr.ip().to_string()),
+        user_agent: headers.get(USER_AGENT).and_then(|ua| ua.to_str().ok()).map(|ua| ua.to_string()),
+    }
+}
+
 pub async fn register(
     State(state): State<AppState>,
+    addr: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     Json(req): Json<RegisterRequest>,
 ) -> ApiResult<Json<AuthResponse>> {
     req.validate().map_err(|e| erp_core::Error::Validation(e.to_string()))?;
-    let res = state.auth_svc.register(req).await?;
+    let res = state.auth_svc.register(req, client_info(&headers, addr)).await?;
     Ok(Json(res))
 }
 
 pub async fn login(
     State(state): State<AppState>,
+    addr: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     Json(req): Json<LoginRequest>,
+) -> ApiResult<Json<LoginResponse>> {
+    req.validate().map_err(|e| erp_core::Error::Validation(e.to_string()))?;
+    let res = state.auth_svc.login(req, client_info(&headers, addr)).await?;
+    Ok(Json(res))
+}
+
+pub async fn login_two_factor(
+    State(state): State<AppState>,
+    addr: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(req): Json<TwoFactorLoginRequest>,
+) -> ApiResult<Json<AuthResponse>> {
+    req.validate().map_err(|e| erp_core::Error::Validation(e.to_string()))?;
+    let res = state.auth_svc.login_two_factor(req, client_info(&headers, addr)).await?;
+    Ok(Json(res))
+}
+
+pub async fn refresh(
+    State(state): State<AppState>,
+    Json(req): Json<RefreshRequest>,
 ) -> ApiResult<Json<AuthResponse>> {
     req.validate().map_err(|e| erp_core::Error::Validation(e.to_string()))?;
-    let res = state.auth_svc.login(req).await?;
+    let res = state.auth_svc.refresh(req).await?;
     Ok(Json(res))
 }
 
+pub async fn logout(
+    State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
+) -> ApiResult<Json<serde_json::Value>> {
+    state.auth_svc.logout(&user).await?;
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Emails a reset token to the account's address. The response is the same whether or not the
+/// address is registered.
+pub async fn request_password_reset(
+    State(state): State<AppState>,
+    Json(req): Json<PasswordResetRequest>,
+) -> ApiResult<Json<serde_json::Value>> {
+    req.validate().map_err(|e| erp_core::Error::Validation(e.to_string()))?;
+    if let Some(reset) = state.auth_svc.request_password_reset(req).await? {
+        let email = erp_enterprise::QueueEmailRequest {
+            template_name: None,
+            to_address: reset.email,
+            subject: Some("Reset your password".to_string()),
+            body: Some(format!(
+                "Hello {},\n\nUse this token to choose a new password: {}\n\nIt expires at {}. If you did not ask to reset your password, you can ignore this email.",
+                reset.full_name,
+                reset.token,
+                reset.expires_at.to_rfc3339(),
+            )),
+            variables: None,
+        };
+        erp_enterprise::EmailTemplateService::new().queue_email(&state.pool, email).await?;
+    }
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "If that address belongs to an account, a password reset email is on its way",
+    })))
+}
+
+pub async fn confirm_password_reset(
+    State(state): State<AppState>,
+    Json(req): Json<PasswordResetConfirm>,
+) -> ApiResult<Json<serde_json::Value>> {
+    req.validate().map_err(|e| erp_core::Error::Validation(e.to_string()))?;
+    state.auth_svc.confirm_password_reset(req).await?;
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
 pub async fn me(
     State(state): State<AppState>,
     axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
 ) -> ApiResult<Json<UserInfo>> {
     let id = uuid::Uuid::parse_str(&user.user_id).map_err(|_| erp_core::Error::Unauthorized)?;
     let user = state.auth_svc.get_user(id).await?;
-    Ok(Json(UserInfo {
-        id: user.base.id,
-        username: user.username,
-        email: user.email,
-        full_name: user.full_name,
-        role: user.role.as_str().to_string(),
-    }))
+    Ok(Json(UserInfo::from(&user)))
 }
 
 #[derive(Clone, Debug)]
@@ -64,10 +138,11 @@ pub async fn auth_middleware(
 ) -> Result<Response, StatusCode> {
     let token = extract_token(&req).ok_or(StatusCode::UNAUTHORIZED)?;
     
-    let token_data = state.auth_svc.validate_token(&token).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let token_data = state.auth_svc.validate_token(&token).await.map_err(|_| StatusCode::UNAUTHORIZED)?;
     
     let mut req = req;
     req.extensions_mut().insert(AuthUser(token_data));
+    req.extensions_mut().insert(state.authz.clone());
     Ok(next.run(req).await)
 }
 
@@ -77,7 +152,7 @@ pub async fn optional_auth_middleware(
     next: axum::middleware::Next,
 ) -> Response {
     if let Some(token) = extract_token(&req) {
-        if let Ok(token_data) = state.auth_svc.validate_token(&token) {
+        if let Ok(token_data) = state.auth_svc.validate_token(&token).await {
             req.extensions_mut().insert(AuthUser(token_data));
         }
     }
@@ -8,6 +8,7 @@ use uuid::Uuid;
 use chrono::Utc;
 use erp_core::Pagination;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 use erp_automation::{WorkflowService, WorkflowExecutionService, ScheduledJobService, WebhookService, ActionTemplateService};
 use erp_automation::{AutomationWorkflow, AutomationType, AutomationStatus, WorkflowExecution, TriggerType};
 
@@ -374,15 +375,15 @@ pub async fn list_action_templates(
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/workflows", axum::routing::get(list_workflows).post(create_workflow))
-        .route("/workflows/:id", axum::routing::get(get_workflow))
-        .route("/workflows/:id/publish", axum::routing::post(publish_workflow))
-        .route("/workflows/:id/pause", axum::routing::post(pause_workflow))
-        .route("/executions", axum::routing::post(start_execution))
-        .route("/executions/:id", axum::routing::get(get_execution))
-        .route("/executions/:id/cancel", axum::routing::post(cancel_execution))
-        .route("/workflows/:workflow_id/executions", axum::routing::get(list_executions))
-        .route("/scheduled-jobs", axum::routing::get(list_scheduled_jobs).post(create_scheduled_job))
-        .route("/webhooks", axum::routing::get(list_webhooks).post(create_webhook))
-        .route("/action-templates", axum::routing::get(list_action_templates))
+        .route("/workflows", axum::routing::get(list_workflows).post(create_workflow).require("automation:workflows"))
+        .route("/workflows/:id", axum::routing::get(get_workflow).require("automation:workflows:read"))
+        .route("/workflows/:id/publish", axum::routing::post(publish_workflow).require("automation:workflows:write"))
+        .route("/workflows/:id/pause", axum::routing::post(pause_workflow).require("automation:workflows:write"))
+        .route("/executions", axum::routing::post(start_execution).require("automation:executions:write"))
+        .route("/executions/:id", axum::routing::get(get_execution).require("automation:executions:read"))
+        .route("/executions/:id/cancel", axum::routing::post(cancel_execution).require("automation:executions:write"))
+        .route("/workflows/:workflow_id/executions", axum::routing::get(list_executions).require("automation:workflows:read"))
+        .route("/scheduled-jobs", axum::routing::get(list_scheduled_jobs).post(create_scheduled_job).require("automation:scheduled-jobs"))
+        .route("/webhooks", axum::routing::get(list_webhooks).post(create_webhook).require("automation:webhooks"))
+        .route("/action-templates", axum::routing::get(list_action_templates).require("automation:action-templates:read"))
 }
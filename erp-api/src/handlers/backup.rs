@@ -7,8 +7,9 @@ use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::BaseEntity;
-use erp_backup::{BackupSchedule, BackupType, BackupStorageType, BackupRecord};
+use erp_backup::{BackupSchedule, BackupType, BackupStorageType, BackupRecord, RestoreOperation};
 
 #[derive(Serialize)]
 pub struct BackupScheduleResponse {
@@ -43,6 +44,9 @@ pub struct CreateScheduleRequest {
     pub schedule_cron: String,
     pub retention_days: Option<i32>,
     pub max_backups: Option<i32>,
+    pub backup_type: Option<BackupType>,
+    pub storage_path: Option<String>,
+    pub compression: Option<bool>,
 }
 
 pub async fn list_schedules(State(state): State<AppState>) -> ApiResult<Json<Vec<BackupScheduleResponse>>> {
@@ -57,15 +61,15 @@ pub async fn create_schedule(
     let schedule = BackupSchedule {
         base: BaseEntity::new(),
         name: req.name,
-        backup_type: BackupType::Full,
+        backup_type: req.backup_type.unwrap_or(BackupType::Full),
         schedule_cron: req.schedule_cron,
         retention_days: req.retention_days.unwrap_or(30),
         max_backups: req.max_backups.unwrap_or(10),
-        compression: true,
+        compression: req.compression.unwrap_or(true),
         encryption_enabled: false,
         encryption_key_id: None,
         storage_type: BackupStorageType::Local,
-        storage_path: "./backups".to_string(),
+        storage_path: req.storage_path.unwrap_or_else(|| "./backups".to_string()),
         include_attachments: true,
         is_active: true,
         last_run: None,
@@ -82,6 +86,9 @@ pub struct BackupRecordResponse {
     pub status: String,
     pub file_path: String,
     pub file_size_bytes: i64,
+    pub compressed_size_bytes: Option<i64>,
+    pub checksum: Option<String>,
+    pub parent_backup_id: Option<Uuid>,
     pub started_at: String,
     pub completed_at: Option<String>,
     pub duration_seconds: Option<i64>,
@@ -96,6 +103,9 @@ impl From<BackupRecord> for BackupRecordResponse {
             status: format!("{:?}", b.status),
             file_path: b.file_path,
             file_size_bytes: b.file_size_bytes,
+            compressed_size_bytes: b.compressed_size_bytes,
+            checksum: b.checksum,
+            parent_backup_id: b.parent_backup_id,
             started_at: b.started_at.to_rfc3339(),
             completed_at: b.completed_at.map(|d| d.to_rfc3339()),
             duration_seconds: b.duration_seconds,
@@ -117,8 +127,16 @@ pub async fn list_backups(
     Ok(Json(backups.into_iter().map(BackupRecordResponse::from).collect()))
 }
 
-pub async fn execute_backup(State(state): State<AppState>) -> ApiResult<Json<BackupRecordResponse>> {
-    let backup = state.backup_svc.execute_backup(None).await?;
+#[derive(Deserialize)]
+pub struct ExecuteBackupQuery {
+    pub schedule_id: Option<Uuid>,
+}
+
+pub async fn execute_backup(
+    State(state): State<AppState>,
+    Query(query): Query<ExecuteBackupQuery>,
+) -> ApiResult<Json<BackupRecordResponse>> {
+    let backup = state.backup_svc.execute_backup(query.schedule_id).await?;
     Ok(Json(BackupRecordResponse::from(backup)))
 }
 
@@ -139,15 +157,37 @@ pub async fn delete_backup(
     Ok(Json(serde_json::json!({ "status": "deleted" })))
 }
 
+fn restore_response(restore: RestoreOperation) -> serde_json::Value {
+    serde_json::json!({
+        "id": restore.base.id,
+        "backup_id": restore.backup_id,
+        "status": format!("{:?}", restore.status),
+        "records_restored": restore.records_restored,
+        "backup_before_restore": restore.backup_before_restore,
+        "error_message": restore.error_message
+    })
+}
+
 pub async fn restore_backup(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<serde_json::Value>> {
     let restore = state.backup_svc.restore_backup(id, None).await?;
-    Ok(Json(serde_json::json!({
-        "status": "restored",
-        "records_restored": restore.records_restored
-    })))
+    Ok(Json(restore_response(restore)))
+}
+
+#[derive(Deserialize)]
+pub struct RestoreAsOfRequest {
+    pub as_of: chrono::DateTime<chrono::Utc>,
+}
+
+/// Restores the latest backup completed by `as_of`; later changes are not replayed.
+pub async fn restore_latest_before(
+    State(state): State<AppState>,
+    Json(req): Json<RestoreAsOfRequest>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let restore = state.backup_svc.restore_latest_backup_before(req.as_of, None).await?;
+    Ok(Json(restore_response(restore)))
 }
 
 pub async fn verify_backup(
@@ -158,7 +198,10 @@ pub async fn verify_backup(
     Ok(Json(serde_json::json!({
         "status": format!("{:?}", verification.status),
         "file_readable": verification.file_readable,
-        "schema_valid": verification.schema_valid
+        "checksum_valid": verification.checksum_valid,
+        "schema_valid": verification.schema_valid,
+        "sample_data_valid": verification.sample_data_valid,
+        "error_details": verification.error_details
     })))
 }
 
@@ -178,10 +221,11 @@ pub async fn storage_stats(State(state): State<AppState>) -> ApiResult<Json<Stor
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/schedules", get(list_schedules).post(create_schedule))
-        .route("/", get(list_backups).post(execute_backup))
-        .route("/:id", get(get_backup).delete(delete_backup))
-        .route("/:id/restore", post(restore_backup))
-        .route("/:id/verify", post(verify_backup))
-        .route("/stats", get(storage_stats))
+        .route("/schedules", get(list_schedules).post(create_schedule).require("backup:schedules"))
+        .route("/", get(list_backups).post(execute_backup).require("backup:backup"))
+        .route("/:id", get(get_backup).delete(delete_backup).require("backup:backup"))
+        .route("/restore/point-in-time", post(restore_latest_before).require("backup:restore:write"))
+        .route("/:id/restore", post(restore_backup).require("backup:backup:write"))
+        .route("/:id/verify", post(verify_backup).require("backup:backup:write"))
+        .route("/stats", get(storage_stats).require("backup:stats:read"))
 }
@@ -1,6 +1,6 @@
 use axum::{
     extract::{Path, Query, State},
-    routing::{get, post},
+    routing::{get, post, put},
     Json, Router,
 };
 use chrono::Utc;
@@ -8,27 +8,74 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::Pagination;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/accounts", post(create_bank_account).get(list_bank_accounts))
-        .route("/accounts/:id", get(get_bank_account))
-        .route("/statements", post(import_statement).get(list_statements))
-        .route("/statements/:id", get(get_statement))
-        .route("/statements/:id/transactions", get(list_statement_transactions))
-        .route("/transactions", get(list_transactions))
-        .route("/transactions/:id/reconcile", post(reconcile_transaction))
-        .route("/transactions/:id/unreconcile", post(unreconcile_transaction))
-        .route("/reconciliations", post(start_reconciliation).get(list_reconciliations))
-        .route("/reconciliations/:id", get(get_reconciliation))
-        .route("/reconciliations/:id/complete", post(complete_reconciliation))
-        .route("/reconciliations/:id/matches", get(list_reconciliation_matches))
-        .route("/reconciliations/:id/auto-match", post(auto_match_transactions))
-        .route("/rules", post(create_reconciliation_rule).get(list_reconciliation_rules))
-        .route("/rules/:id", get(get_reconciliation_rule).put(update_reconciliation_rule))
-        .route("/unreconciled", get(list_unreconciled_transactions))
-        .route("/summary/:account_id", get(get_reconciliation_summary))
+        .route("/accounts", post(create_bank_account).get(list_bank_accounts).require("bank:accounts"))
+        .route("/accounts/:id", get(get_bank_account).require("bank:accounts:read"))
+        .route("/accounts/:id/originator", put(set_originator).require("bank:accounts:write"))
+        .route("/vendors/:vendor_id/bank-account", put(set_vendor_bank_account).require("bank:vendors:write"))
+        .route("/payment-files", post(generate_payment_file).require("bank:payment-files:write"))
+        .route("/statements", post(import_statement).get(list_statements).require("bank:statements"))
+        .route("/statements/import", post(import_statement_file).require("bank:statements:write"))
+        .route("/statements/:id", get(get_statement).require("bank:statements:read"))
+        .route("/statements/:id/transactions", get(list_statement_transactions).require("bank:statements:read"))
+        .route("/transactions", get(list_transactions).require("bank:transactions:read"))
+        .route("/transactions/:id/reconcile", post(reconcile_transaction).require("bank:transactions:write"))
+        .route("/transactions/:id/unreconcile", post(unreconcile_transaction).require("bank:transactions:write"))
+        .route("/reconciliations", post(start_reconciliation).get(list_reconciliations).require("bank:reconciliations"))
+        .route("/reconciliations/:id", get(get_reconciliation).require("bank:reconciliations:read"))
+        .route("/reconciliations/:id/complete", post(complete_reconciliation).require("bank:reconciliations:write"))
+        .route("/reconciliations/:id/matches", get(list_reconciliation_matches).require("bank:reconciliations:read"))
+        .route("/reconciliations/:id/auto-match", post(auto_match_transactions).require("bank:reconciliations:write"))
+        .route("/rules", post(create_reconciliation_rule).get(list_reconciliation_rules).require("bank:rules"))
+        .route("/rules/:id", get(get_reconciliation_rule).put(update_reconciliation_rule).require("bank:rules"))
+        .route("/unreconciled", get(list_unreconciled_transactions).require("bank:unreconciled:read"))
+        .route("/summary/:account_id", get(get_reconciliation_summary).require("bank:summary:read"))
+}
+
+pub async fn import_statement_file(
+    State(state): State<AppState>,
+    Json(req): Json<erp_bank::ImportStatementFileRequest>,
+) -> ApiResult<Json<Vec<erp_bank::ImportedStatement>>> {
+    let imported = erp_bank::BankService::new(state.pool.clone())
+        .import_statement_file(&state.pool, req)
+        .await?;
+    Ok(Json(imported))
+}
+
+pub async fn set_originator(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<erp_bank::SetOriginatorRequest>,
+) -> ApiResult<Json<erp_bank::Originator>> {
+    let originator = erp_bank::BankService::new(state.pool.clone())
+        .set_originator(&state.pool, id, req)
+        .await?;
+    Ok(Json(originator))
+}
+
+pub async fn set_vendor_bank_account(
+    State(state): State<AppState>,
+    Path(vendor_id): Path<Uuid>,
+    Json(req): Json<erp_bank::SetVendorBankAccountRequest>,
+) -> ApiResult<Json<erp_bank::VendorBankAccount>> {
+    let account = erp_bank::BankService::new(state.pool.clone())
+        .set_vendor_bank_account(&state.pool, vendor_id, req)
+        .await?;
+    Ok(Json(account))
+}
+
+pub async fn generate_payment_file(
+    State(state): State<AppState>,
+    Json(req): Json<erp_bank::GeneratePaymentFileRequest>,
+) -> ApiResult<Json<erp_bank::GeneratedPaymentFile>> {
+    let file = erp_bank::BankService::new(state.pool.clone())
+        .generate_payment_file(&state.pool, req)
+        .await?;
+    Ok(Json(file))
 }
 
 #[derive(Debug, Serialize)]
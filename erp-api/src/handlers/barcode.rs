@@ -4,6 +4,7 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::{BaseEntity, Status};
 use erp_barcode::{
     Barcode, BarcodeType, BarcodeEntityType,
@@ -275,10 +276,10 @@ pub async fn validate_barcode(
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/:code", get(get_barcode))
-        .route("/", post(create_barcode))
-        .route("/print-jobs", post(create_print_job))
-        .route("/print-jobs/:id/complete", post(complete_print_job))
-        .route("/scan", post(scan))
-        .route("/validate/:code/:type", get(validate_barcode))
+        .route("/:code", get(get_barcode).require("barcodes:barcodes:read"))
+        .route("/", post(create_barcode).require("barcodes:barcodes:write"))
+        .route("/print-jobs", post(create_print_job).require("barcodes:print-jobs:write"))
+        .route("/print-jobs/:id/complete", post(complete_print_job).require("barcodes:print-jobs:write"))
+        .route("/scan", post(scan).require("barcodes:scan:write"))
+        .route("/validate/:code/:type", get(validate_barcode).require("barcodes:validate:read"))
 }
@@ -7,17 +7,18 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::db::AppState;
 use crate::ApiResult;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/kpis", get(list_kpis).post(create_kpi))
-        .route("/kpis/:id", get(get_kpi))
-        .route("/kpis/:id/values", post(record_kpi_value))
-        .route("/dashboards", get(list_dashboards).post(create_dashboard))
-        .route("/dashboards/:id", get(get_dashboard))
-        .route("/dashboards/:id/widgets", post(add_widget))
-        .route("/reports", get(list_reports).post(create_report))
-        .route("/reports/:id/execute", post(execute_report))
+        .route("/kpis", get(list_kpis).post(create_kpi).require("bi:kpis"))
+        .route("/kpis/:id", get(get_kpi).require("bi:kpis:read"))
+        .route("/kpis/:id/values", post(record_kpi_value).require("bi:kpis:write"))
+        .route("/dashboards", get(list_dashboards).post(create_dashboard).require("bi:dashboards"))
+        .route("/dashboards/:id", get(get_dashboard).require("bi:dashboards:read"))
+        .route("/dashboards/:id/widgets", post(add_widget).require("bi:dashboards:write"))
+        .route("/reports", get(list_reports).post(create_report).require("bi:reports"))
+        .route("/reports/:id/execute", post(execute_report).require("bi:reports:write"))
 }
 
 #[derive(Deserialize)]
@@ -7,19 +7,20 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::db::AppState;
 use crate::ApiResult;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/processes", get(list_processes).post(create_process))
-        .route("/processes/:id", get(get_process))
-        .route("/processes/:id/publish", post(publish_process))
-        .route("/processes/:id/nodes", post(add_node))
-        .route("/processes/:id/flows", post(add_flow))
-        .route("/instances", get(list_instances).post(start_instance))
-        .route("/instances/:id", get(get_instance))
-        .route("/tasks", get(list_user_tasks))
-        .route("/tasks/:id/claim", post(claim_task))
-        .route("/tasks/:id/complete", post(complete_task))
+        .route("/processes", get(list_processes).post(create_process).require("bpm:processes"))
+        .route("/processes/:id", get(get_process).require("bpm:processes:read"))
+        .route("/processes/:id/publish", post(publish_process).require("bpm:processes:write"))
+        .route("/processes/:id/nodes", post(add_node).require("bpm:processes:write"))
+        .route("/processes/:id/flows", post(add_flow).require("bpm:processes:write"))
+        .route("/instances", get(list_instances).post(start_instance).require("bpm:instances"))
+        .route("/instances/:id", get(get_instance).require("bpm:instances:read"))
+        .route("/tasks", get(list_user_tasks).require("bpm:tasks:read"))
+        .route("/tasks/:id/claim", post(claim_task).require("bpm:tasks:write"))
+        .route("/tasks/:id/complete", post(complete_task).require("bpm:tasks:write"))
 }
 
 #[derive(Deserialize)]
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateContractRequest {
@@ -35,15 +36,15 @@ pub struct ApproveRequest {
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/", get(list_contracts).post(create_contract))
-        .route("/:id", get(get_contract))
-        .route("/:id/submit", post(submit_for_approval))
-        .route("/:id/approve", post(approve_contract))
-        .route("/:id/activate", post(activate_contract))
-        .route("/:id/terminate", post(terminate_contract))
-        .route("/expiring", get(list_expiring))
-        .route("/types", get(list_contract_types))
-        .route("/:id/risk", get(get_risk_assessment))
+        .route("/", get(list_contracts).post(create_contract).require("clm:clm"))
+        .route("/:id", get(get_contract).require("clm:clm:read"))
+        .route("/:id/submit", post(submit_for_approval).require("clm:clm:write"))
+        .route("/:id/approve", post(approve_contract).require("clm:clm:approve"))
+        .route("/:id/activate", post(activate_contract).require("clm:clm:write"))
+        .route("/:id/terminate", post(terminate_contract).require("clm:clm:write"))
+        .route("/expiring", get(list_expiring).require("clm:expiring:read"))
+        .route("/types", get(list_contract_types).require("clm:types:read"))
+        .route("/:id/risk", get(get_risk_assessment).require("clm:clm:read"))
 }
 
 async fn list_contracts(State(_state): State<AppState>) -> Json<serde_json::Value> {
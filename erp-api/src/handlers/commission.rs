@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreatePlanRequest {
@@ -38,16 +39,16 @@ pub struct CreateQuotaRequest {
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/plans", get(list_plans).post(create_plan))
-        .route("/plans/:id", get(get_plan))
-        .route("/calculations", get(list_calculations).post(calculate_commission))
-        .route("/calculations/:id", get(get_calculation))
-        .route("/calculations/:id/approve", post(approve_calculation))
-        .route("/quotas", get(list_quotas).post(create_quota))
-        .route("/quotas/:id/progress", get(get_quota_progress))
-        .route("/teams", get(list_teams))
-        .route("/reports", get(get_reports))
-        .route("/forecasts", get(get_forecasts))
+        .route("/plans", get(list_plans).post(create_plan).require("commission:plans"))
+        .route("/plans/:id", get(get_plan).require("commission:plans:read"))
+        .route("/calculations", get(list_calculations).post(calculate_commission).require("commission:calculations"))
+        .route("/calculations/:id", get(get_calculation).require("commission:calculations:read"))
+        .route("/calculations/:id/approve", post(approve_calculation).require("commission:calculations:approve"))
+        .route("/quotas", get(list_quotas).post(create_quota).require("commission:quotas"))
+        .route("/quotas/:id/progress", get(get_quota_progress).require("commission:quotas:read"))
+        .route("/teams", get(list_teams).require("commission:teams:read"))
+        .route("/reports", get(get_reports).require("commission:reports:read"))
+        .route("/forecasts", get(get_forecasts).require("commission:forecasts:read"))
 }
 
 async fn list_plans(State(_state): State<AppState>) -> Json<serde_json::Value> {
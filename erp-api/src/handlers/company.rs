@@ -8,18 +8,19 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 use erp_company::{CompanyService, IntercompanyService, ConsolidationService, CreateCompanyRequest, CreateIntercompanyRequest, CompanyType, ConsolidationMethod};
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/companies", get(list_companies).post(create_company))
-        .route("/companies/:id", get(get_company))
-        .route("/companies/:id/children", get(get_company_children))
-        .route("/companies/:id/tree", get(get_company_tree))
-        .route("/intercompany", get(list_intercompany).post(create_intercompany))
-        .route("/intercompany/pending-eliminations", get(get_pending_eliminations))
-        .route("/consolidations", post(create_consolidation))
-        .route("/consolidations/:id/run-eliminations", post(run_eliminations))
+        .route("/companies", get(list_companies).post(create_company).require("company:companies"))
+        .route("/companies/:id", get(get_company).require("company:companies:read"))
+        .route("/companies/:id/children", get(get_company_children).require("company:companies:read"))
+        .route("/companies/:id/tree", get(get_company_tree).require("company:companies:read"))
+        .route("/intercompany", get(list_intercompany).post(create_intercompany).require("company:intercompany"))
+        .route("/intercompany/pending-eliminations", get(get_pending_eliminations).require("company:intercompany:read"))
+        .route("/consolidations", post(create_consolidation).require("company:consolidations:write"))
+        .route("/consolidations/:id/run-eliminations", post(run_eliminations).require("company:consolidations:write"))
 }
 
 #[derive(Deserialize)]
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreatePlanRequest {
@@ -53,20 +54,20 @@ pub struct CreateReviewRequest {
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/plans", get(list_plans).post(create_plan))
-        .route("/plans/:id", get(get_plan))
-        .route("/compensations", post(set_compensation))
-        .route("/compensations/:employee_id", get(get_compensation))
-        .route("/adjustments", get(list_adjustments).post(create_adjustment))
-        .route("/adjustments/:id/approve", post(approve_adjustment))
-        .route("/reviews", get(list_reviews).post(create_review))
-        .route("/reviews/:id", get(get_review))
-        .route("/bonuses/:employee_id", post(calculate_bonus))
-        .route("/statements/:employee_id", get(get_total_rewards))
-        .route("/pay-equity", post(analyze_pay_equity))
-        .route("/benchmarks/:position_id", get(get_benchmark))
-        .route("/grades", get(list_grades))
-        .route("/ranges", get(list_salary_ranges))
+        .route("/plans", get(list_plans).post(create_plan).require("compensation:plans"))
+        .route("/plans/:id", get(get_plan).require("compensation:plans:read"))
+        .route("/compensations", post(set_compensation).require("compensation:compensations:write"))
+        .route("/compensations/:employee_id", get(get_compensation).require("compensation:compensations:read"))
+        .route("/adjustments", get(list_adjustments).post(create_adjustment).require("compensation:adjustments"))
+        .route("/adjustments/:id/approve", post(approve_adjustment).require("compensation:adjustments:approve"))
+        .route("/reviews", get(list_reviews).post(create_review).require("compensation:reviews"))
+        .route("/reviews/:id", get(get_review).require("compensation:reviews:read"))
+        .route("/bonuses/:employee_id", post(calculate_bonus).require("compensation:bonuses:write"))
+        .route("/statements/:employee_id", get(get_total_rewards).require("compensation:statements:read"))
+        .route("/pay-equity", post(analyze_pay_equity).require("compensation:pay-equity:write"))
+        .route("/benchmarks/:position_id", get(get_benchmark).require("compensation:benchmarks:read"))
+        .route("/grades", get(list_grades).require("compensation:grades:read"))
+        .route("/ranges", get(list_salary_ranges).require("compensation:ranges:read"))
 }
 
 async fn list_plans(State(_state): State<AppState>) -> Json<serde_json::Value> {
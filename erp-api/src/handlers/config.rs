@@ -6,6 +6,7 @@ use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::BaseEntity;
 use erp_config::{
     ConfigService, CompanySetting, AuditSetting,
@@ -252,12 +253,12 @@ pub async fn list_integrations(
 
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/configs", axum::routing::get(list_configs).post(set_config))
-        .route("/configs/:category/:key", axum::routing::get(get_config))
-        .route("/configs/:id", axum::routing::delete(delete_config))
-        .route("/company", axum::routing::get(get_company_settings).post(update_company_settings))
-        .route("/sequences", axum::routing::post(create_sequence))
-        .route("/sequences/:code/next", axum::routing::get(get_next_number))
-        .route("/audit", axum::routing::get(get_audit_settings).post(update_audit_settings))
-        .route("/integrations", axum::routing::get(list_integrations))
+        .route("/configs", axum::routing::get(list_configs).post(set_config).require("config:configs"))
+        .route("/configs/:category/:key", axum::routing::get(get_config).require("config:configs:read"))
+        .route("/configs/:id", axum::routing::delete(delete_config).require("config:configs:delete"))
+        .route("/company", axum::routing::get(get_company_settings).post(update_company_settings).require("config:company"))
+        .route("/sequences", axum::routing::post(create_sequence).require("config:sequences:write"))
+        .route("/sequences/:code/next", axum::routing::get(get_next_number).require("config:sequences:read"))
+        .route("/audit", axum::routing::get(get_audit_settings).post(update_audit_settings).require("config:audit"))
+        .route("/integrations", axum::routing::get(list_integrations).require("config:integrations:read"))
 }
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateTemplateRequest {
@@ -37,14 +38,14 @@ pub struct CreateQuoteRequest {
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/templates", get(list_templates).post(create_template))
-        .route("/templates/:id", get(get_template))
-        .route("/configurations", get(list_configurations).post(create_configuration))
-        .route("/configurations/:id", get(get_configuration))
-        .route("/configurations/:id/price", post(calculate_price))
-        .route("/quotes", get(list_quotes).post(create_quote))
-        .route("/quotes/:id", get(get_quote))
-        .route("/quotes/:id/approve", post(approve_quote))
+        .route("/templates", get(list_templates).post(create_template).require("cpq:templates"))
+        .route("/templates/:id", get(get_template).require("cpq:templates:read"))
+        .route("/configurations", get(list_configurations).post(create_configuration).require("cpq:configurations"))
+        .route("/configurations/:id", get(get_configuration).require("cpq:configurations:read"))
+        .route("/configurations/:id/price", post(calculate_price).require("cpq:configurations:write"))
+        .route("/quotes", get(list_quotes).post(create_quote).require("cpq:quotes"))
+        .route("/quotes/:id", get(get_quote).require("cpq:quotes:read"))
+        .route("/quotes/:id/approve", post(approve_quote).require("cpq:quotes:approve"))
 }
 
 async fn list_templates(State(_state): State<AppState>) -> Json<serde_json::Value> {
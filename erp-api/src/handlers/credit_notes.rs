@@ -10,6 +10,7 @@ use uuid::Uuid;
 
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_credit_notes::{
     CreditNoteService, CreditNote, CreditNoteLine, CreditNoteReason,
     CreateCreditNoteRequest, CreateCreditNoteLineRequest, ApplyCreditNoteRequest,
@@ -99,12 +100,12 @@ pub struct CreditNoteApplicationResponse {
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/", get(list_credit_notes).post(create_credit_note))
-        .route("/:id", get(get_credit_note))
-        .route("/:id/issue", post(issue_credit_note))
-        .route("/:id/void", post(void_credit_note))
-        .route("/:id/apply", post(apply_credit_note))
-        .route("/customer/:customer_id", get(list_customer_credit_notes))
+        .route("/", get(list_credit_notes).post(create_credit_note).require("credit-notes:credit-notes"))
+        .route("/:id", get(get_credit_note).require("credit-notes:credit-notes:read"))
+        .route("/:id/issue", post(issue_credit_note).require("credit-notes:credit-notes:write"))
+        .route("/:id/void", post(void_credit_note).require("credit-notes:credit-notes:void"))
+        .route("/:id/apply", post(apply_credit_note).require("credit-notes:credit-notes:write"))
+        .route("/customer/:customer_id", get(list_customer_credit_notes).require("credit-notes:customer:read"))
 }
 
 impl From<CreditNote> for CreditNoteResponse {
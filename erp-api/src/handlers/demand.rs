@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::error::ApiResult;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
@@ -89,9 +90,9 @@ pub async fn add_signal(
 
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/run", axum::routing::post(run_forecast))
-        .route("/plans", axum::routing::post(create_plan))
-        .route("/safety-stock", axum::routing::post(calculate_safety_stock))
-        .route("/accuracy", axum::routing::post(get_accuracy))
-        .route("/signals", axum::routing::post(add_signal))
+        .route("/run", axum::routing::post(run_forecast).require("demand:run:write"))
+        .route("/plans", axum::routing::post(create_plan).require("demand:plans:write"))
+        .route("/safety-stock", axum::routing::post(calculate_safety_stock).require("demand:safety-stock:write"))
+        .route("/accuracy", axum::routing::post(get_accuracy).require("demand:accuracy:write"))
+        .route("/signals", axum::routing::post(add_signal).require("demand:signals:write"))
 }
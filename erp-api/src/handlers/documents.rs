@@ -1,14 +1,18 @@
 use axum::{
-    extract::{Path, Query, State},
-    Json,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    response::Response,
+    Extension, Json,
 };
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::handlers::auth::AuthUser;
+use crate::handlers::files;
+use crate::middleware::RequirePermission;
 use erp_core::BaseEntity;
 use erp_documents::{
-    DocumentService, Document,
+    DocumentService, Document, DocumentFolder, DocumentVersion, NewVersion,
     DocumentType, DocumentStatus, AccessLevel, DispositionType,
 };
 
@@ -17,6 +21,8 @@ pub struct CreateFolderRequest {
     pub name: String,
     pub parent_id: Option<Uuid>,
     pub description: Option<String>,
+    pub max_file_size: Option<i64>,
+    pub allowed_mime_types: Option<Vec<String>>,
 }
 
 #[derive(Serialize)]
@@ -26,6 +32,22 @@ pub struct FolderResponse {
     pub parent_id: Option<Uuid>,
     pub path: String,
     pub status: String,
+    pub max_file_size: Option<i64>,
+    pub allowed_mime_types: Vec<String>,
+}
+
+impl From<DocumentFolder> for FolderResponse {
+    fn from(f: DocumentFolder) -> Self {
+        Self {
+            id: f.base.id,
+            allowed_mime_types: erp_core::UploadPolicy::parse_mime_types(f.allowed_mime_types.as_deref()),
+            name: f.name,
+            parent_id: f.parent_id,
+            path: f.path,
+            status: format!("{:?}", f.status),
+            max_file_size: f.max_file_size,
+        }
+    }
 }
 
 pub async fn list_folders(
@@ -34,13 +56,7 @@ pub async fn list_folders(
 ) -> ApiResult<Json<Vec<FolderResponse>>> {
     let service = DocumentService::new();
     let folders = service.list_folders(&state.pool, query.parent_id).await?;
-    Ok(Json(folders.into_iter().map(|f| FolderResponse {
-        id: f.base.id,
-        name: f.name,
-        parent_id: f.parent_id,
-        path: f.path,
-        status: format!("{:?}", f.status),
-    }).collect()))
+    Ok(Json(folders.into_iter().map(FolderResponse::from).collect()))
 }
 
 #[derive(Deserialize)]
@@ -53,14 +69,27 @@ pub async fn create_folder(
     Json(req): Json<CreateFolderRequest>,
 ) -> ApiResult<Json<FolderResponse>> {
     let service = DocumentService::new();
-    let folder = service.create_folder(&state.pool, req.name, req.parent_id, req.description).await?;
-    Ok(Json(FolderResponse {
-        id: folder.base.id,
-        name: folder.name,
-        parent_id: folder.parent_id,
-        path: folder.path,
-        status: format!("{:?}", folder.status),
-    }))
+    let mut folder = service.create_folder(&state.pool, req.name, req.parent_id, req.description).await?;
+    if req.max_file_size.is_some() || req.allowed_mime_types.is_some() {
+        folder = service.set_upload_policy(&state.pool, folder.base.id, req.max_file_size, req.allowed_mime_types).await?;
+    }
+    Ok(Json(FolderResponse::from(folder)))
+}
+
+#[derive(Deserialize)]
+pub struct UploadPolicyRequest {
+    pub max_file_size: Option<i64>,
+    pub allowed_mime_types: Option<Vec<String>>,
+}
+
+pub async fn set_upload_policy(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UploadPolicyRequest>,
+) -> ApiResult<Json<FolderResponse>> {
+    let service = DocumentService::new();
+    let folder = service.set_upload_policy(&state.pool, id, req.max_file_size, req.allowed_mime_types).await?;
+    Ok(Json(FolderResponse::from(folder)))
 }
 
 #[derive(Serialize)]
@@ -232,13 +261,118 @@ pub async fn create_retention_policy(
     Ok(Json(serde_json::json!({ "status": "created" })))
 }
 
+#[derive(Serialize)]
+pub struct VersionResponse {
+    pub id: Uuid,
+    pub document_id: Uuid,
+    pub version: i32,
+    pub revision: String,
+    pub file_size: i64,
+    pub checksum: String,
+    pub change_summary: Option<String>,
+    pub changed_by: Option<Uuid>,
+    pub created_at: String,
+}
+
+impl From<DocumentVersion> for VersionResponse {
+    fn from(v: DocumentVersion) -> Self {
+        Self {
+            id: v.base.id,
+            document_id: v.document_id,
+            version: v.version,
+            revision: v.revision,
+            file_size: v.file_size,
+            checksum: v.checksum,
+            change_summary: v.change_summary,
+            changed_by: v.changed_by,
+            created_at: v.base.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Uploads a new version as multipart `file`, with optional `change_summary` and `sha256` fields.
+pub async fn upload_version(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+    multipart: Multipart,
+) -> ApiResult<Json<VersionResponse>> {
+    let service = DocumentService::new();
+    let doc = service.get_document(&state.pool, id).await?;
+    let max_size = match doc.folder_id {
+        Some(folder_id) => service.get_folder(&state.pool, folder_id).await?
+            .upload_policy(state.blobs.default_policy())
+            .max_size,
+        None => state.blobs.default_policy().max_size,
+    };
+    let mut upload = files::read_upload(&state.blobs, multipart, max_size).await?;
+    let new = NewVersion {
+        file_name: Some(upload.file_name.clone()),
+        mime_type: upload.content_type.clone(),
+        change_summary: upload.fields.remove("change_summary"),
+        changed_by: Some(user.user_id()),
+        expected_sha256: upload.fields.remove("sha256"),
+    };
+    let version = service.create_version(&state.pool, &state.blobs, id, upload.file, new).await?;
+    Ok(Json(VersionResponse::from(version)))
+}
+
+pub async fn list_versions(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<Vec<VersionResponse>>> {
+    let service = DocumentService::new();
+    let versions = service.list_versions(&state.pool, id).await?;
+    Ok(Json(versions.into_iter().map(VersionResponse::from).collect()))
+}
+
+pub async fn download_document(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Response> {
+    let service = DocumentService::new();
+    let (file_name, blob, stream) = service.open_content(&state.pool, &state.blobs, id, None).await?;
+    Ok(files::blob_response(&blob, stream, &file_name))
+}
+
+pub async fn download_version(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(Uuid, i32)>,
+) -> ApiResult<Response> {
+    let service = DocumentService::new();
+    let (file_name, blob, stream) = service.open_content(&state.pool, &state.blobs, id, Some(version)).await?;
+    Ok(files::blob_response(&blob, stream, &file_name))
+}
+
+pub async fn document_download_url(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<files::DownloadUrlQuery>,
+) -> ApiResult<Json<files::DownloadUrlResponse>> {
+    let service = DocumentService::new();
+    let doc = service.get_document(&state.pool, id).await?;
+    state.blobs.get(&state.pool, &doc.checksum).await?;
+    Ok(Json(files::download_url(&state.blobs, &doc.checksum, &doc.file_name, &query)?))
+}
+
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/folders", axum::routing::get(list_folders).post(create_folder))
-        .route("/documents", axum::routing::get(list_documents).post(create_document))
-        .route("/documents/:id", axum::routing::get(get_document))
-        .route("/documents/:id/checkout", axum::routing::post(checkout_document))
-        .route("/documents/checkin", axum::routing::post(checkin_document))
-        .route("/documents/review", axum::routing::post(request_review))
-        .route("/retention-policies", axum::routing::post(create_retention_policy))
+        .route("/folders", axum::routing::get(list_folders).post(create_folder).require("documents:folders"))
+        .route("/folders/:id/upload-policy", axum::routing::put(set_upload_policy).require("documents:folders:write"))
+        .route("/documents", axum::routing::get(list_documents).post(create_document).require("documents:documents"))
+        .route("/documents/:id", axum::routing::get(get_document).require("documents:documents:read"))
+        .route("/documents/:id/checkout", axum::routing::post(checkout_document).require("documents:documents:write"))
+        .route(
+            "/documents/:id/versions",
+            axum::routing::get(list_versions)
+                .post(upload_version)
+                .layer(DefaultBodyLimit::disable())
+                    .require("documents:documents"),
+        )
+        .route("/documents/:id/versions/:version/content", axum::routing::get(download_version).require("documents:documents:read"))
+        .route("/documents/:id/content", axum::routing::get(download_document).require("documents:documents:read"))
+        .route("/documents/:id/download-url", axum::routing::get(document_download_url).require("documents:documents:read"))
+        .route("/documents/checkin", axum::routing::post(checkin_document).require("documents:documents:write"))
+        .route("/documents/review", axum::routing::post(request_review).require("documents:documents:write"))
+        .route("/retention-policies", axum::routing::post(create_retention_policy).require("documents:retention-policies:write"))
 }
@@ -3,34 +3,35 @@ use chrono::Datelike;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/equipment", post(create_equipment).get(list_equipment))
-        .route("/equipment/:id", get(get_equipment).put(update_equipment))
-        .route("/equipment/:id/meter-readings", post(record_meter_reading).get(list_meter_readings))
-        .route("/equipment/:id/failures", get(list_failures))
-        .route("/equipment/:id/down", post(record_downtime))
-        .route("/work-orders", post(create_work_order).get(list_work_orders))
-        .route("/work-orders/:id", get(get_work_order))
-        .route("/work-orders/:id/assign", post(assign_work_order))
-        .route("/work-orders/:id/start", post(start_work_order))
-        .route("/work-orders/:id/complete", post(complete_work_order))
-        .route("/work-orders/:id/close", post(close_work_order))
-        .route("/work-orders/:id/tasks", post(add_task).get(list_tasks))
-        .route("/work-orders/:id/labor", post(add_labor).get(list_labor))
-        .route("/work-orders/:id/parts", post(add_part).get(list_parts))
-        .route("/pm-schedules", post(create_pm_schedule).get(list_pm_schedules))
-        .route("/pm-schedules/:id", get(get_pm_schedule))
-        .route("/pm-schedules/:id/tasks", post(add_pm_task).get(list_pm_tasks))
-        .route("/pm-schedules/:id/generate", post(generate_pm_work_order))
-        .route("/failure-codes", post(create_failure_code).get(list_failure_codes))
-        .route("/locations", post(create_location).get(list_locations))
-        .route("/spare-parts", post(create_spare_part).get(list_spare_parts))
-        .route("/spare-parts/:id", get(get_spare_part))
-        .route("/budgets", post(create_budget).get(list_budgets))
-        .route("/kpis", get(list_kpis))
-        .route("/service-contracts", post(create_contract).get(list_contracts))
+        .route("/equipment", post(create_equipment).get(list_equipment).require("eam:equipment"))
+        .route("/equipment/:id", get(get_equipment).put(update_equipment).require("eam:equipment"))
+        .route("/equipment/:id/meter-readings", post(record_meter_reading).get(list_meter_readings).require("eam:equipment"))
+        .route("/equipment/:id/failures", get(list_failures).require("eam:equipment:read"))
+        .route("/equipment/:id/down", post(record_downtime).require("eam:equipment:write"))
+        .route("/work-orders", post(create_work_order).get(list_work_orders).require("eam:work-orders"))
+        .route("/work-orders/:id", get(get_work_order).require("eam:work-orders:read"))
+        .route("/work-orders/:id/assign", post(assign_work_order).require("eam:work-orders:write"))
+        .route("/work-orders/:id/start", post(start_work_order).require("eam:work-orders:write"))
+        .route("/work-orders/:id/complete", post(complete_work_order).require("eam:work-orders:write"))
+        .route("/work-orders/:id/close", post(close_work_order).require("eam:work-orders:write"))
+        .route("/work-orders/:id/tasks", post(add_task).get(list_tasks).require("eam:work-orders"))
+        .route("/work-orders/:id/labor", post(add_labor).get(list_labor).require("eam:work-orders"))
+        .route("/work-orders/:id/parts", post(add_part).get(list_parts).require("eam:work-orders"))
+        .route("/pm-schedules", post(create_pm_schedule).get(list_pm_schedules).require("eam:pm-schedules"))
+        .route("/pm-schedules/:id", get(get_pm_schedule).require("eam:pm-schedules:read"))
+        .route("/pm-schedules/:id/tasks", post(add_pm_task).get(list_pm_tasks).require("eam:pm-schedules"))
+        .route("/pm-schedules/:id/generate", post(generate_pm_work_order).require("eam:pm-schedules:write"))
+        .route("/failure-codes", post(create_failure_code).get(list_failure_codes).require("eam:failure-codes"))
+        .route("/locations", post(create_location).get(list_locations).require("eam:locations"))
+        .route("/spare-parts", post(create_spare_part).get(list_spare_parts).require("eam:spare-parts"))
+        .route("/spare-parts/:id", get(get_spare_part).require("eam:spare-parts:read"))
+        .route("/budgets", post(create_budget).get(list_budgets).require("eam:budgets"))
+        .route("/kpis", get(list_kpis).require("eam:kpis:read"))
+        .route("/service-contracts", post(create_contract).get(list_contracts).require("eam:service-contracts"))
 }
 
 #[derive(Serialize)]
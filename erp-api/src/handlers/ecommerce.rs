@@ -4,6 +4,7 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::{Pagination, BaseEntity, Money, Currency, Status};
 use erp_ecommerce::{
     EcommercePlatform, PlatformType, SyncDirection, SyncStatus,
@@ -344,10 +345,10 @@ pub async fn publish_listing(
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/platforms", get(list_platforms).post(create_platform))
-        .route("/orders", get(list_orders).post(import_order))
-        .route("/orders/:id/link", post(link_sales_order))
-        .route("/orders/:id/fulfillment", post(update_fulfillment))
-        .route("/listings", post(create_listing))
-        .route("/listings/:id/publish", post(publish_listing))
+        .route("/platforms", get(list_platforms).post(create_platform).require("ecommerce:platforms"))
+        .route("/orders", get(list_orders).post(import_order).require("ecommerce:orders"))
+        .route("/orders/:id/link", post(link_sales_order).require("ecommerce:orders:write"))
+        .route("/orders/:id/fulfillment", post(update_fulfillment).require("ecommerce:orders:write"))
+        .route("/listings", post(create_listing).require("ecommerce:listings:write"))
+        .route("/listings/:id/publish", post(publish_listing).require("ecommerce:listings:write"))
 }
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{State, Query},
+    extract::{Path, State, Query},
     Json,
 };
 use serde::Deserialize;
@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Deserialize)]
 pub struct ListQuery {
@@ -44,11 +45,11 @@ pub async fn create_partner(
 pub async fn process_inbound(
     State(state): State<AppState>,
     Json(req): Json<erp_edi::ProcessEdiRequest>,
-) -> ApiResult<Json<erp_edi::EdiTransaction>> {
-    let txn = erp_edi::EdiService::new(erp_edi::SqliteEdiRepository::new(state.pool.clone()))
+) -> ApiResult<Json<erp_edi::InboundResult>> {
+    let result = erp_edi::EdiService::new(erp_edi::SqliteEdiRepository::new(state.pool.clone()))
         .process_inbound(req)
         .await?;
-    Ok(Json(txn))
+    Ok(Json(result))
 }
 
 pub async fn generate_outbound(
@@ -74,7 +75,9 @@ pub async fn list_transactions(
     let txn_type = query.transaction_type.and_then(|s| match s.as_str() {
         "X12_850" => Some(erp_edi::EdiTransactionType::X12_850),
         "X12_810" => Some(erp_edi::EdiTransactionType::X12_810),
+        "X12_855" => Some(erp_edi::EdiTransactionType::X12_855),
         "X12_856" => Some(erp_edi::EdiTransactionType::X12_856),
+        "X12_997" => Some(erp_edi::EdiTransactionType::X12_997),
         _ => None,
     });
     let txns = erp_edi::EdiService::new(erp_edi::SqliteEdiRepository::new(state.pool.clone()))
@@ -83,10 +86,21 @@ pub async fn list_transactions(
     Ok(Json(txns))
 }
 
+pub async fn get_transaction(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<erp_edi::EdiTransaction>> {
+    let txn = erp_edi::EdiService::new(erp_edi::SqliteEdiRepository::new(state.pool.clone()))
+        .get_transaction(id)
+        .await?;
+    Ok(Json(txn))
+}
+
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/partners", axum::routing::get(list_partners).post(create_partner))
-        .route("/inbound", axum::routing::post(process_inbound))
-        .route("/outbound", axum::routing::post(generate_outbound))
-        .route("/transactions", axum::routing::get(list_transactions))
+        .route("/partners", axum::routing::get(list_partners).post(create_partner).require("edi:partners"))
+        .route("/inbound", axum::routing::post(process_inbound).require("edi:inbound:write"))
+        .route("/outbound", axum::routing::post(generate_outbound).require("edi:outbound:write"))
+        .route("/transactions", axum::routing::get(list_transactions).require("edi:transactions:read"))
+        .route("/transactions/:id", axum::routing::get(get_transaction).require("edi:transactions:read"))
 }
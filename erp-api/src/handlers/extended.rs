@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
 use crate::handlers::auth::AuthUser;
+use crate::policy::{self, AccessPolicy};
 use erp_finance::{CurrencyDef, ExchangeRate, BudgetWithVariance, CurrencyService, BudgetService};
 use erp_inventory::{Lot, LotService};
 use erp_hr::{LeaveTypeDef, LeaveRequestExtended, ExpenseReport, ExpenseCategory, LeaveService, ExpenseService};
@@ -448,10 +449,23 @@ pub async fn create_expense_report(
 
 pub async fn list_expense_reports(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Query(query): Query<ExpenseReportsQuery>,
-) -> ApiResult<Json<Vec<ExpenseReportResponse>>> {
-    let reports = ExpenseService::list_expense_reports(&state.pool, query.employee_id).await?;
-    Ok(Json(reports.into_iter().map(ExpenseReportResponse::from).collect()))
+) -> ApiResult<Json<serde_json::Value>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
+    let reports = ExpenseService::list_expense_reports_scoped(&state.pool, query.employee_id, &policy.row_scope(policy::EXPENSE_REPORTS)).await?;
+    let reports: Vec<ExpenseReportResponse> = reports.into_iter().map(ExpenseReportResponse::from).collect();
+    Ok(Json(policy.redact(policy::EXPENSE_REPORTS, &reports)?))
+}
+
+pub async fn get_expense_report(
+    State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
+    let report = ExpenseService::get_expense_report_scoped(&state.pool, id, &policy.row_scope(policy::EXPENSE_REPORTS)).await?;
+    Ok(Json(policy.redact(policy::EXPENSE_REPORTS, &ExpenseReportResponse::from(report))?))
 }
 
 #[derive(Debug, Deserialize)]
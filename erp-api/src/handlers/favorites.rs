@@ -8,6 +8,7 @@ use uuid::Uuid;
 use crate::db::AppState;
 use crate::error::ApiResult;
 use crate::handlers::auth::AuthUser;
+use crate::middleware::RequirePermission;
 use erp_favorites::{CreateFavoriteRequest, Favorite, FavoriteService, FavoriteType};
 
 #[derive(Debug, Deserialize)]
@@ -196,9 +197,9 @@ pub async fn favorite_count(
 
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/", axum::routing::get(list_favorites).post(create_favorite))
-        .route("/count", axum::routing::get(favorite_count))
-        .route("/toggle", axum::routing::post(toggle_favorite))
-        .route("/:id", axum::routing::get(get_favorite).delete(delete_favorite))
-        .route("/check/:favorite_type/:entity_id", axum::routing::get(is_favorite))
+        .route("/", axum::routing::get(list_favorites).post(create_favorite).require("favorites:favorites"))
+        .route("/count", axum::routing::get(favorite_count).require("favorites:count:read"))
+        .route("/toggle", axum::routing::post(toggle_favorite).require("favorites:toggle:write"))
+        .route("/:id", axum::routing::get(get_favorite).delete(delete_favorite).require("favorites:favorites"))
+        .route("/check/:favorite_type/:entity_id", axum::routing::get(is_favorite).require("favorites:check:read"))
 }
@@ -7,6 +7,7 @@ use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::BaseEntity;
 use erp_features::{FeatureFlagService, FeatureFlag, FeatureFlagOverride, FlagTargetType, FlagEvaluationContext};
 
@@ -157,10 +158,10 @@ pub async fn create_override(
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/", get(list_flags).post(create_flag))
-        .route("/:key", get(get_flag))
-        .route("/id/:id", delete(delete_flag))
-        .route("/:id/toggle", post(toggle_flag))
-        .route("/:key/evaluate", post(evaluate_flag))
-        .route("/:flag_id/overrides", post(create_override))
+        .route("/", get(list_flags).post(create_flag).require("features:features"))
+        .route("/:key", get(get_flag).require("features:features:read"))
+        .route("/id/:id", delete(delete_flag).require("features:id:delete"))
+        .route("/:id/toggle", post(toggle_flag).require("features:features:write"))
+        .route("/:key/evaluate", post(evaluate_flag).require("features:features:write"))
+        .route("/:flag_id/overrides", post(create_override).require("features:features:write"))
 }
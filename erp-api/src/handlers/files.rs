@@ -0,0 +1,125 @@
+use axum::{
+    body::Body,
+    extract::{Multipart, Path, Query, State},
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::db::AppState;
+use crate::error::ApiResult;
+use erp_core::blob::{Blob, ByteStream, SignedDownload, StagedBlob};
+use erp_core::BlobService;
+
+const DEFAULT_LINK_MINUTES: i64 = 15;
+const MAX_LINK_MINUTES: i64 = 7 * 24 * 60;
+
+/// A multipart upload: the staged `file` part and any plain form fields sent with it.
+pub struct Upload {
+    pub file: StagedBlob,
+    pub file_name: String,
+    pub content_type: String,
+    pub fields: HashMap<String, String>,
+}
+
+impl Upload {
+    /// The SHA-256 the client sent to have the upload checked against.
+    pub fn expected_sha256(&self) -> Option<&str> {
+        self.fields.get("sha256").map(String::as_str)
+    }
+}
+
+/// Streams the `file` part of a multipart body to staging, stopping once it passes `max_size`.
+/// Other parts are read as text fields whatever order they come in.
+pub async fn read_upload(blobs: &BlobService, mut multipart: Multipart, max_size: u64) -> ApiResult<Upload> {
+    let mut file = None;
+    let mut fields = HashMap::new();
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        erp_core::Error::validation(format!("Failed to read multipart: {}", e))
+    })? {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "file" || (file.is_none() && field.file_name().is_some()) {
+            let file_name = sanitize_filename(field.file_name().unwrap_or("file"));
+            let content_type = field.content_type().unwrap_or("application/octet-stream").to_string();
+            let staged = blobs.stage(field, max_size).await?;
+            file = Some((staged, file_name, content_type));
+        } else {
+            let value = field.text().await.map_err(|e| {
+                erp_core::Error::validation(format!("Failed to read field {}: {}", name, e))
+            })?;
+            fields.insert(name, value);
+        }
+    }
+    let (file, file_name, content_type) = file.ok_or_else(|| erp_core::Error::validation("No file provided"))?;
+    Ok(Upload { file, file_name, content_type, fields })
+}
+
+pub fn sanitize_filename(filename: &str) -> String {
+    let sanitized: String = filename
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '.' || *c == '-' || *c == '_')
+        .collect();
+    if sanitized.is_empty() {
+        "file".to_string()
+    } else {
+        sanitized
+    }
+}
+
+/// Sends a stored file as a download named `file_name`.
+pub fn blob_response(blob: &Blob, stream: ByteStream, file_name: &str) -> Response {
+    let disposition = format!("attachment; filename=\"{}\"", sanitize_filename(file_name));
+    let mut response = Body::from_stream(stream).into_response();
+    let headers = response.headers_mut();
+    for (name, value) in [
+        (header::CONTENT_TYPE, blob.mime_type.clone()),
+        (header::CONTENT_LENGTH, blob.size.to_string()),
+        (header::CONTENT_DISPOSITION, disposition),
+        (header::ETAG, format!("\"{}\"", blob.sha256)),
+    ] {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(name, value);
+        }
+    }
+    response
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct DownloadUrlQuery {
+    pub expires_in_minutes: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DownloadUrlResponse {
+    pub url: String,
+    pub expires_at: String,
+}
+
+/// A link anyone holding it can use to download a file until it expires.
+pub fn download_url(blobs: &BlobService, sha256: &str, file_name: &str, query: &DownloadUrlQuery) -> ApiResult<DownloadUrlResponse> {
+    let minutes = query.expires_in_minutes.unwrap_or(DEFAULT_LINK_MINUTES);
+    if !(1..=MAX_LINK_MINUTES).contains(&minutes) {
+        return Err(erp_core::Error::validation(format!(
+            "expires_in_minutes must be between 1 and {}", MAX_LINK_MINUTES
+        )).into());
+    }
+    let expires_at = Utc::now() + Duration::minutes(minutes);
+    // The sanitised name, timestamp and hex signature need no percent-encoding.
+    let signed = blobs.sign_download(sha256, &sanitize_filename(file_name), expires_at);
+    Ok(DownloadUrlResponse {
+        url: format!("/files/{}?name={}&expires={}&signature={}", sha256, signed.name, signed.expires, signed.signature),
+        expires_at: expires_at.to_rfc3339(),
+    })
+}
+
+/// Serves a file to the holder of a signed link, without further authentication.
+pub async fn signed_download(
+    State(state): State<AppState>,
+    Path(sha256): Path<String>,
+    Query(signed): Query<SignedDownload>,
+) -> ApiResult<Response> {
+    state.blobs.verify_download(&sha256, &signed)?;
+    let (blob, stream) = state.blobs.open(&state.pool, &sha256).await?;
+    Ok(blob_response(&blob, stream, &signed.name))
+}
@@ -7,8 +7,10 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::handlers::auth::AuthUser;
 use erp_core::{BaseEntity, Status, Currency, Money, Pagination};
-use erp_finance::{Account, AccountType, JournalEntry, JournalLine, FiscalYear,
+use erp_core::events::publish_change;
+use erp_finance::{Account, AccountType, JournalEntry, JournalLine, JournalEntryReversal, FiscalYear,
                  AccountService, JournalEntryService, FiscalYearService, FinancialReportingService,
                  BalanceSheet, ProfitAndLoss, TrialBalance,
                  DunningService, PeriodManagementService, RecurringJournalService,
@@ -81,6 +83,7 @@ pub async fn get_account(
 
 pub async fn create_account(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Json(req): Json<CreateAccountRequest>,
 ) -> ApiResult<Json<AccountResponse>> {
     let service = AccountService::new();
@@ -102,11 +105,13 @@ pub async fn create_account(
     };
     
     let created = service.create_account(&state.pool, account).await?;
+    publish_change("account", created.base.id, "created", &user.user_id);
     Ok(Json(AccountResponse::from(created)))
 }
 
 pub async fn update_account(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Path(id): Path<Uuid>,
     Json(req): Json<CreateAccountRequest>,
 ) -> ApiResult<Json<AccountResponse>> {
@@ -126,15 +131,18 @@ pub async fn update_account(
     account.description = req.description;
     
     let updated = service.update_account(&state.pool, account).await?;
+    publish_change("account", updated.base.id, "updated", &user.user_id);
     Ok(Json(AccountResponse::from(updated)))
 }
 
 pub async fn delete_account(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<()>> {
     let service = AccountService::new();
     service.delete_account(&state.pool, id).await?;
+    publish_change("account", id, "deleted", &user.user_id);
     Ok(Json(()))
 }
 
@@ -225,6 +233,7 @@ pub async fn get_journal_entry(
 
 pub async fn create_journal_entry(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Json(req): Json<CreateJournalEntryRequest>,
 ) -> ApiResult<Json<JournalEntryResponse>> {
     let service = JournalEntryService::new();
@@ -246,18 +255,102 @@ pub async fn create_journal_entry(
     };
     
     let created = service.create_entry(&state.pool, entry).await?;
+    publish_change("journal_entry", created.base.id, "created", &user.user_id);
     Ok(Json(JournalEntryResponse::from(created)))
 }
 
 pub async fn post_journal_entry(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<serde_json::Value>> {
     let service = JournalEntryService::new();
     service.post_entry(&state.pool, id).await?;
+    publish_change("journal_entry", id, "posted", &user.user_id);
     Ok(Json(serde_json::json!({ "status": "posted" })))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ReverseJournalEntryRequest {
+    pub reversal_date: Option<String>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JournalEntryReversalResponse {
+    pub id: Uuid,
+    pub original_entry_id: Uuid,
+    pub reversal_entry_id: Option<Uuid>,
+    pub reversal_date: String,
+    pub reason: Option<String>,
+    pub status: String,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+impl From<JournalEntryReversal> for JournalEntryReversalResponse {
+    fn from(r: JournalEntryReversal) -> Self {
+        Self {
+            id: r.id,
+            original_entry_id: r.original_entry_id,
+            reversal_entry_id: r.reversal_entry_id,
+            reversal_date: r.reversal_date.to_rfc3339(),
+            reason: r.reason,
+            status: format!("{:?}", r.status),
+            created_at: r.created_at.to_rfc3339(),
+            completed_at: r.completed_at.map(|d| d.to_rfc3339()),
+            last_error: r.last_error,
+        }
+    }
+}
+
+pub async fn reverse_journal_entry(
+    State(state): State<AppState>,
+    axum::Extension(auth_user): axum::Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<ReverseJournalEntryRequest>,
+) -> ApiResult<Json<JournalEntryResponse>> {
+    let reversal_date = match req.reversal_date {
+        Some(d) => Some(chrono::DateTime::parse_from_rfc3339(&d)
+            .map(|d| d.with_timezone(&Utc))
+            .map_err(|_| erp_core::Error::validation("Invalid reversal date format"))?),
+        None => None,
+    };
+    
+    let service = JournalEntryService::new();
+    let reversal = service.reverse_entry(&state.pool, id, reversal_date, req.reason, Some(auth_user.user_id())).await?;
+    Ok(Json(JournalEntryResponse::from(reversal)))
+}
+
+pub async fn schedule_journal_entry_reversal(
+    State(state): State<AppState>,
+    axum::Extension(auth_user): axum::Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<ReverseJournalEntryRequest>,
+) -> ApiResult<Json<JournalEntryReversalResponse>> {
+    let service = JournalEntryService::new();
+    let scheduled = service.schedule_reversal(&state.pool, id, req.reason, Some(auth_user.user_id())).await?;
+    Ok(Json(JournalEntryReversalResponse::from(scheduled)))
+}
+
+pub async fn get_journal_entry_reversal(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<Option<JournalEntryReversalResponse>>> {
+    let service = JournalEntryService::new();
+    let reversal = service.get_reversal(&state.pool, id).await?;
+    Ok(Json(reversal.map(JournalEntryReversalResponse::from)))
+}
+
+pub async fn process_scheduled_reversals(
+    State(state): State<AppState>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let service = JournalEntryService::new();
+    let run = service.process_scheduled_reversals(&state.pool, Utc::now()).await?;
+    Ok(Json(serde_json::json!({ "processed": run.reversed.len(), "failed": run.failed })))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateFiscalYearRequest {
     pub name: String,
@@ -8,6 +8,7 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_fraud::{FraudService, ReviewAlertRequest, CaseResolution, Evidence, EvidenceType};
 
 #[derive(Deserialize)]
@@ -28,21 +29,21 @@ pub struct AnalyticsQuery {
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/alerts", axum::routing::get(list_alerts).post(create_alert))
-        .route("/alerts/:id", axum::routing::get(get_alert))
-        .route("/alerts/:id/review", axum::routing::post(review_alert))
-        .route("/alerts/:id/assign", axum::routing::post(assign_alert))
-        .route("/alerts/:id/escalate", axum::routing::post(escalate_alert))
-        .route("/rules", axum::routing::get(list_rules).post(create_rule))
-        .route("/rules/:id", axum::routing::get(get_rule).delete(delete_rule))
-        .route("/evaluate", axum::routing::post(evaluate_transaction))
-        .route("/cases", axum::routing::get(list_cases).post(create_case))
-        .route("/cases/:id", axum::routing::get(get_case))
-        .route("/cases/:id/evidence", axum::routing::post(add_evidence))
-        .route("/cases/:id/resolve", axum::routing::post(resolve_case))
-        .route("/risk/vendor/:vendor_id", axum::routing::get(get_vendor_risk).post(calculate_vendor_risk))
-        .route("/risk/employee/:employee_id", axum::routing::get(get_employee_risk).post(calculate_employee_risk))
-        .route("/analytics", axum::routing::get(get_analytics))
+        .route("/alerts", axum::routing::get(list_alerts).post(create_alert).require("fraud:alerts"))
+        .route("/alerts/:id", axum::routing::get(get_alert).require("fraud:alerts:read"))
+        .route("/alerts/:id/review", axum::routing::post(review_alert).require("fraud:alerts:write"))
+        .route("/alerts/:id/assign", axum::routing::post(assign_alert).require("fraud:alerts:write"))
+        .route("/alerts/:id/escalate", axum::routing::post(escalate_alert).require("fraud:alerts:write"))
+        .route("/rules", axum::routing::get(list_rules).post(create_rule).require("fraud:rules"))
+        .route("/rules/:id", axum::routing::get(get_rule).delete(delete_rule).require("fraud:rules"))
+        .route("/evaluate", axum::routing::post(evaluate_transaction).require("fraud:evaluate:write"))
+        .route("/cases", axum::routing::get(list_cases).post(create_case).require("fraud:cases"))
+        .route("/cases/:id", axum::routing::get(get_case).require("fraud:cases:read"))
+        .route("/cases/:id/evidence", axum::routing::post(add_evidence).require("fraud:cases:write"))
+        .route("/cases/:id/resolve", axum::routing::post(resolve_case).require("fraud:cases:write"))
+        .route("/risk/vendor/:vendor_id", axum::routing::get(get_vendor_risk).post(calculate_vendor_risk).require("fraud:risk"))
+        .route("/risk/employee/:employee_id", axum::routing::get(get_employee_risk).post(calculate_employee_risk).require("fraud:risk"))
+        .route("/analytics", axum::routing::get(get_analytics).require("fraud:analytics:read"))
 }
 
 async fn create_alert(
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 use erp_giftcards::{
     AdjustGiftCardRequest, CreateGiftCardRequest, GiftCardResponse, GiftCardTransactionResponse,
     GiftCardType, RedeemGiftCardRequest, ReloadGiftCardRequest,
@@ -14,14 +15,14 @@ use erp_giftcards::{
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/", post(create_gift_card).get(list_gift_cards))
-        .route("/:id", get(get_gift_card))
-        .route("/:id/redeem", post(redeem_gift_card))
-        .route("/:id/reload", post(reload_gift_card))
-        .route("/:id/adjust", post(adjust_gift_card))
-        .route("/:id/cancel", post(cancel_gift_card))
-        .route("/:id/transactions", get(list_transactions))
-        .route("/check-balance", post(check_balance))
+        .route("/", post(create_gift_card).get(list_gift_cards).require("giftcards:giftcards"))
+        .route("/:id", get(get_gift_card).require("giftcards:giftcards:read"))
+        .route("/:id/redeem", post(redeem_gift_card).require("giftcards:giftcards:write"))
+        .route("/:id/reload", post(reload_gift_card).require("giftcards:giftcards:write"))
+        .route("/:id/adjust", post(adjust_gift_card).require("giftcards:giftcards:write"))
+        .route("/:id/cancel", post(cancel_gift_card).require("giftcards:giftcards:write"))
+        .route("/:id/transactions", get(list_transactions).require("giftcards:giftcards:read"))
+        .route("/check-balance", post(check_balance).require("giftcards:check-balance:write"))
 }
 
 #[derive(Deserialize)]
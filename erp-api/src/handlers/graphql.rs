@@ -1,25 +1,97 @@
+use async_graphql::http::{WebSocket as GraphQLWebSocket, WebSocketProtocols as Protocols, WsMessage};
 use axum::{
-    extract::State,
-    routing::{get, post},
-    Router,
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
 };
+use erp_auth::jwt::TokenData;
+use erp_graphql::Viewer;
+use futures::{SinkExt, StreamExt};
 use crate::db::AppState;
+use crate::handlers::auth::AuthUser;
+use crate::handlers::websocket::WebSocketQuery;
+use crate::policy::{self, AccessPolicy};
 use crate::ApiResult;
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route("/", post(graphql_handler))
-        .route("/playground", get(graphql_playground))
+/// The caller as the GraphQL resolvers see them, with the row scopes and hidden fields the
+/// REST handlers apply to the same resources.
+async fn viewer(state: &AppState, user: &TokenData) -> ApiResult<Viewer> {
+    let policy = AccessPolicy::load(&state.pool, user).await?;
+    let mut viewer = Viewer::new(user.user_id.clone(), state.authz.clone());
+    for resource in [policy::CUSTOMERS, policy::SALES_ORDERS, policy::EMPLOYEES] {
+        viewer = viewer
+            .with_scope(resource, policy.row_scope(resource))
+            .with_hidden_fields(resource, policy.hidden_fields(resource));
+    }
+    Ok(viewer)
 }
 
 pub async fn graphql_handler(
     State(state): State<AppState>,
-    body: String,
-) -> ApiResult<String> {
-    let schema = erp_graphql::build_schema(state.pool);
-    let request = async_graphql::Request::new(body);
-    let response = schema.execute(request).await;
-    Ok(serde_json::to_string(&response)?)
+    Extension(AuthUser(user)): Extension<AuthUser>,
+    Json(mut request): Json<async_graphql::Request>,
+) -> ApiResult<Json<async_graphql::Response>> {
+    request.data = erp_graphql::session_data(&state.pool, viewer(&state, &user).await?);
+    Ok(Json(state.graphql.execute(request).await))
+}
+
+/// GraphQL subscriptions over `graphql-transport-ws` (or the older `graphql-ws`) for the
+/// user identified by `?token=<jwt>`.
+pub async fn graphql_ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    Query(query): Query<WebSocketQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let protocol = headers
+        .get(header::SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').find_map(|p| p.trim().parse::<Protocols>().ok()))
+        .unwrap_or(Protocols::GraphQLWS);
+
+    let user = match query.token {
+        Some(token) => state.auth_svc.validate_token(&token).await.ok(),
+        None => None,
+    };
+    let Some(user) = user else {
+        return (StatusCode::UNAUTHORIZED, "Missing or invalid token").into_response();
+    };
+    let viewer = match viewer(&state, &user).await {
+        Ok(viewer) => viewer,
+        Err(e) => return e.into_response(),
+    };
+
+    ws.protocols(["graphql-transport-ws", "graphql-ws"])
+        .on_upgrade(move |socket| serve_subscriptions(socket, state, viewer, protocol))
+}
+
+async fn serve_subscriptions(socket: WebSocket, state: AppState, viewer: Viewer, protocol: Protocols) {
+    let (mut sink, stream) = socket.split();
+    let incoming = stream
+        .take_while(|message| futures::future::ready(message.is_ok()))
+        .filter_map(|message| async move {
+            match message {
+                Ok(Message::Text(text)) => Some(text.into_bytes()),
+                Ok(Message::Binary(bytes)) => Some(bytes),
+                _ => None,
+            }
+        });
+
+    let mut outgoing = GraphQLWebSocket::new(state.graphql.clone(), Box::pin(incoming), protocol)
+        .connection_data(erp_graphql::session_data(&state.pool, viewer));
+    while let Some(message) = outgoing.next().await {
+        let message = match message {
+            WsMessage::Text(text) => Message::Text(text),
+            WsMessage::Close(code, reason) => Message::Close(Some(CloseFrame { code, reason: reason.into() })),
+        };
+        if sink.send(message).await.is_err() {
+            break;
+        }
+    }
 }
 
 pub async fn graphql_playground() -> ApiResult<String> {
@@ -5,7 +5,10 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::handlers::auth::AuthUser;
+use crate::policy::{self, AccessPolicy};
 use erp_core::{BaseEntity, Status, Pagination, ContactInfo, Address};
+use erp_core::events::publish_change;
 use erp_hr::{Employee, Payroll, PayrollRun, EmployeeService, AttendanceService, FullPayrollService};
 
 type PayrollRunRow = (
@@ -61,26 +64,33 @@ impl From<Employee> for EmployeeResponse {
 }
 pub async fn list_employees(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Query(pagination): Query<Pagination>,
-) -> ApiResult<Json<erp_core::Paginated<EmployeeResponse>>> {
+) -> ApiResult<Json<serde_json::Value>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
     let svc = EmployeeService::new();
-    let res = svc.list(&state.pool, pagination).await?;
-    Ok(Json(erp_core::Paginated::new(
-        res.items.into_iter().map(EmployeeResponse::from).collect(),
+    let res = svc.list_scoped(&state.pool, pagination, &policy.row_scope(policy::EMPLOYEES)).await?;
+    let page = erp_core::Paginated::new(
+        res.items.into_iter().map(EmployeeResponse::from).collect::<Vec<_>>(),
         res.total,
         Pagination { page: res.page, per_page: res.per_page },
-    )))
+    );
+    Ok(Json(policy.redact(policy::EMPLOYEES, &page)?))
 }
 pub async fn get_employee(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Path(id): Path<Uuid>,
-) -> ApiResult<Json<EmployeeResponse>> {
-    Ok(Json(EmployeeResponse::from(
-        EmployeeService::new().get(&state.pool, id).await?,
-    )))
+) -> ApiResult<Json<serde_json::Value>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
+    let employee = EmployeeService::new()
+        .get_scoped(&state.pool, id, &policy.row_scope(policy::EMPLOYEES))
+        .await?;
+    Ok(Json(policy.redact(policy::EMPLOYEES, &EmployeeResponse::from(employee))?))
 }
 pub async fn create_employee(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Json(req): Json<CreateEmployeeRequest>,
 ) -> ApiResult<Json<EmployeeResponse>> {
     let svc = EmployeeService::new();
@@ -113,7 +123,9 @@ pub async fn create_employee(
         manager_id: None,
         status: Status::Active,
     };
-    Ok(Json(EmployeeResponse::from(svc.create(&state.pool, e).await?)))
+    let created = svc.create(&state.pool, e).await?;
+    publish_change("employee", created.base.id, "created", &user.user_id);
+    Ok(Json(EmployeeResponse::from(created)))
 }
 #[derive(Deserialize)]
 pub struct AttendanceRequest {
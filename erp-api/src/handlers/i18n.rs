@@ -7,15 +7,16 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::db::AppState;
 use crate::ApiResult;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/locales", get(list_locales).post(create_locale))
-        .route("/locales/:code", get(get_locale))
-        .route("/translations", post(set_translation))
-        .route("/translations/:locale/:namespace", get(get_translations))
-        .route("/user-preferences", post(set_user_preference).get(get_user_preference))
-        .route("/missing", get(get_missing_translations))
+        .route("/locales", get(list_locales).post(create_locale).require("i18n:locales"))
+        .route("/locales/:code", get(get_locale).require("i18n:locales:read"))
+        .route("/translations", post(set_translation).require("i18n:translations:write"))
+        .route("/translations/:locale/:namespace", get(get_translations).require("i18n:translations:read"))
+        .route("/user-preferences", post(set_user_preference).get(get_user_preference).require("i18n:user-preferences"))
+        .route("/missing", get(get_missing_translations).require("i18n:missing:read"))
 }
 
 #[derive(Deserialize)]
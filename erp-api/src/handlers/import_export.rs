@@ -1,11 +1,16 @@
 use axum::{
-    extract::{Query, State},
+    extract::{Multipart, Path, Query, State},
     body::Body,
-    http::{header, Response},
+    http::{header, Response, StatusCode},
+    Extension, Json,
 };
+use uuid::Uuid;
 use crate::db::AppState;
 use crate::error::ApiResult;
-use tracing::warn;
+use crate::handlers::auth::AuthUser;
+use crate::handlers::files;
+use erp_mdm::import::{ImportEntity, ImportService, MAX_FILE_SIZE};
+use erp_mdm::{ColumnMapping, CreateImportMappingRequest, CreateImportRequest, DataImportJob, ImportFormat, ImportMapping, ImportRowError};
 
 #[derive(Debug, serde::Deserialize)]
 pub struct ExportQuery {
@@ -115,294 +120,149 @@ fn escape_csv(s: &str) -> String {
     }
 }
 
-const MAX_CSV_SIZE: usize = 10 * 1024 * 1024;
-const MAX_CSV_ROWS: usize = 10000;
-const MAX_FIELD_LENGTH: usize = 500;
+pub async fn list_import_entities() -> Json<&'static [ImportEntity]> {
+    Json(ImportService::new().entities())
+}
 
-fn validate_csv_field(value: &str, field_name: &str) -> Result<(), erp_core::Error> {
-    if value.len() > MAX_FIELD_LENGTH {
-        return Err(erp_core::Error::validation(format!(
-            "Field '{}' exceeds maximum length of {} characters",
-            field_name, MAX_FIELD_LENGTH
-        )));
-    }
-    if value.contains('\0') {
-        return Err(erp_core::Error::validation(format!(
-            "Field '{}' contains null bytes",
-            field_name
-        )));
-    }
-    Ok(())
+#[derive(Debug, serde::Deserialize)]
+pub struct ListImportsQuery {
+    pub entity: Option<String>,
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
 }
 
-fn validate_sku(sku: &str) -> Result<(), erp_core::Error> {
-    if sku.is_empty() {
-        return Err(erp_core::Error::validation("SKU cannot be empty"));
-    }
-    if sku.len() > 50 {
-        return Err(erp_core::Error::validation("SKU exceeds maximum length of 50 characters"));
-    }
-    if !sku.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.') {
-        return Err(erp_core::Error::validation("SKU contains invalid characters"));
-    }
-    Ok(())
+pub async fn list_imports(
+    State(state): State<AppState>,
+    Query(query): Query<ListImportsQuery>,
+) -> ApiResult<Json<Vec<DataImportJob>>> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let jobs = ImportService::new()
+        .list_imports(&state.pool, query.entity.as_deref(), limit, query.offset.unwrap_or(0).max(0))
+        .await?;
+    Ok(Json(jobs))
 }
 
-fn validate_email(email: &str) -> Result<(), erp_core::Error> {
-    if email.is_empty() {
-        return Ok(());
-    }
-    if !email.contains('@') || email.len() > 255 {
-        return Err(erp_core::Error::validation("Invalid email format"));
+/// Starts an import from a multipart upload. Besides `file` it takes `entity` and, optionally,
+/// `mapping_id` or `mapping` (a JSON list of column mappings), `format`, `sheet`, `dry_run`,
+/// `upsert` and `sha256`. Small files are imported before responding; larger ones are queued.
+pub async fn create_import(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    multipart: Multipart,
+) -> ApiResult<(StatusCode, Json<DataImportJob>)> {
+    let mut upload = files::read_upload(&state.blobs, multipart, MAX_FILE_SIZE).await?;
+    let service = ImportService::new();
+    let entity_type = upload.fields.remove("entity")
+        .ok_or_else(|| erp_core::Error::validation("entity is required"))?;
+    let entity = service.entity(&entity_type)?;
+    if !state.authz.check(&user.0.user_id, entity.permission).await? {
+        return Err(erp_core::Error::forbidden(format!("missing permission {}", entity.permission)).into());
     }
-    Ok(())
+
+    let mapping_id = match upload.fields.remove("mapping_id").filter(|v| !v.trim().is_empty()) {
+        Some(id) => Some(Uuid::parse_str(id.trim())
+            .map_err(|_| erp_core::Error::validation("mapping_id must be a UUID"))?),
+        None => None,
+    };
+    let columns = match upload.fields.remove("mapping").filter(|v| !v.trim().is_empty()) {
+        Some(json) => Some(serde_json::from_str::<Vec<ColumnMapping>>(&json)
+            .map_err(|e| erp_core::Error::validation(format!("Invalid mapping: {}", e)))?),
+        None => None,
+    };
+    let format = match upload.fields.remove("format").map(|f| f.trim().to_lowercase()) {
+        Some(f) if f == "csv" => Some(ImportFormat::Csv),
+        Some(f) if f == "xlsx" => Some(ImportFormat::Xlsx),
+        Some(f) if f.is_empty() => None,
+        Some(f) => return Err(erp_core::Error::validation(format!("Unsupported format {}", f)).into()),
+        None => None,
+    };
+    let request = CreateImportRequest {
+        entity_type,
+        file_name: upload.file_name.clone(),
+        expected_sha256: upload.expected_sha256().map(str::to_string),
+        format,
+        sheet: upload.fields.remove("sheet").filter(|s| !s.trim().is_empty()),
+        mapping_id,
+        columns,
+        dry_run: flag(upload.fields.get("dry_run"))?,
+        upsert: flag(upload.fields.get("upsert"))?,
+        created_by: Some(user.user_id()),
+    };
+    let job = service.create_import(&state.pool, &state.blobs, upload.file, request).await?;
+    Ok((StatusCode::CREATED, Json(job)))
 }
 
-fn parse_csv_line(line: &str) -> Vec<String> {
-    let mut fields = Vec::new();
-    let mut current = String::new();
-    let mut in_quotes = false;
-    let mut chars = line.chars().peekable();
-    
-    while let Some(c) = chars.next() {
-        match c {
-            '"' if !in_quotes => {
-                in_quotes = true;
-            }
-            '"' if in_quotes => {
-                if chars.peek() == Some(&'"') {
-                    current.push('"');
-                    chars.next();
-                } else {
-                    in_quotes = false;
-                }
-            }
-            ',' if !in_quotes => {
-                fields.push(current);
-                current = String::new();
-            }
-            _ => {
-                current.push(c);
-            }
-        }
+fn flag(value: Option<&String>) -> Result<bool, erp_core::Error> {
+    match value.map(|v| v.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("false") | Some("0") | Some("no") | Some("off") => Ok(false),
+        Some("true") | Some("1") | Some("yes") | Some("on") => Ok(true),
+        Some(other) => Err(erp_core::Error::validation(format!("{} is not true or false", other))),
     }
-    fields.push(current);
-    fields
+}
+
+pub async fn get_import(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<DataImportJob>> {
+    Ok(Json(ImportService::new().get_import(&state.pool, id).await?))
 }
 
 #[derive(Debug, serde::Deserialize)]
-pub struct ImportQuery {
-    pub entity: String,
+pub struct ImportErrorsQuery {
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
 }
 
-pub async fn import_csv(
+pub async fn list_import_errors(
     State(state): State<AppState>,
-    Query(query): Query<ImportQuery>,
-    body: String,
-) -> ApiResult<axum::Json<serde_json::Value>> {
-    if body.len() > MAX_CSV_SIZE {
-        return Err(erp_core::Error::validation(format!(
-            "CSV file exceeds maximum size of {} bytes",
-            MAX_CSV_SIZE
-        )).into());
-    }
-    
-    let lines: Vec<&str> = body.lines().collect();
-    if lines.is_empty() {
-        return Err(erp_core::Error::validation("Empty CSV").into());
-    }
-    
-    if lines.len() > MAX_CSV_ROWS {
-        return Err(erp_core::Error::validation(format!(
-            "CSV exceeds maximum of {} rows",
-            MAX_CSV_ROWS
-        )).into());
-    }
-    
-    let count = match query.entity.as_str() {
-        "products" => import_products(&state.pool, &lines).await?,
-        "customers" => import_customers(&state.pool, &lines).await?,
-        "vendors" => import_vendors(&state.pool, &lines).await?,
-        "accounts" => import_accounts(&state.pool, &lines).await?,
-        "employees" => import_employees(&state.pool, &lines).await?,
-        _ => return Err(erp_core::Error::validation("Unknown entity type").into()),
-    };
-    
-    Ok(axum::Json(serde_json::json!({ "imported": count })))
+    Path(id): Path<Uuid>,
+    Query(query): Query<ImportErrorsQuery>,
+) -> ApiResult<Json<Vec<ImportRowError>>> {
+    let service = ImportService::new();
+    service.get_import(&state.pool, id).await?;
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+    let errors = service.errors(&state.pool, id, limit, query.offset.unwrap_or(0).max(0)).await?;
+    Ok(Json(errors))
 }
 
-async fn import_products(pool: &sqlx::SqlitePool, lines: &[&str]) -> crate::error::ApiResult<usize> {
-    let mut count = 0;
-    for line in lines.iter().skip(1) {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let parts = parse_csv_line(line);
-        if parts.len() >= 4 {
-            let sku = &parts[1];
-            let name = &parts[2];
-            let uom = &parts[3];
-            
-            validate_sku(sku)?;
-            validate_csv_field(name, "name")?;
-            validate_csv_field(uom, "unit_of_measure")?;
-            
-            let id = uuid::Uuid::new_v4().to_string();
-            let now = chrono::Utc::now().to_rfc3339();
-            if let Err(e) = sqlx::query(
-                "INSERT OR IGNORE INTO products (id, sku, name, unit_of_measure, status, created_at, updated_at)
-                 VALUES (?, ?, ?, ?, 'Active', ?, ?)"
-            )
-            .bind(&id)
-            .bind(sku)
-            .bind(name)
-            .bind(uom)
-            .bind(&now)
-            .bind(&now)
-            .execute(pool)
-            .await
-            {
-                warn!(sku = %sku, error = %e, "Failed to import product");
-            } else {
-                count += 1;
-            }
-        }
-    }
-    Ok(count)
+/// The rows that failed, as uploaded, with an Errors column to fix and re-import from.
+pub async fn download_import_errors(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Response<Body>> {
+    let csv = ImportService::new().error_file(&state.pool, id).await?;
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/csv")
+        .header(header::CONTENT_DISPOSITION, format!("attachment; filename=\"import-{}-errors.csv\"", id))
+        .body(Body::from(csv))
+        .map_err(|e| erp_core::Error::Internal(anyhow::anyhow!("Failed to build response: {}", e)).into())
 }
 
-async fn import_customers(pool: &sqlx::SqlitePool, lines: &[&str]) -> crate::error::ApiResult<usize> {
-    let mut count = 0;
-    for line in lines.iter().skip(1) {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let parts = parse_csv_line(line);
-        if parts.len() >= 3 {
-            let code = &parts[1];
-            let name = &parts[2];
-            let email = parts.get(3).map(|s| s.as_str()).unwrap_or("");
-            
-            validate_csv_field(code, "code")?;
-            validate_csv_field(name, "name")?;
-            validate_email(email)?;
-            
-            let id = uuid::Uuid::new_v4().to_string();
-            let now = chrono::Utc::now().to_rfc3339();
-            if let Err(e) = sqlx::query(
-                "INSERT OR IGNORE INTO customers (id, code, name, email, status, created_at, updated_at, payment_terms)
-                 VALUES (?, ?, ?, ?, 'Active', ?, ?, 30)"
-            )
-            .bind(&id)
-            .bind(code)
-            .bind(name)
-            .bind(email)
-            .bind(&now)
-            .bind(&now)
-            .execute(pool)
-            .await
-            {
-                warn!(code = %code, error = %e, "Failed to import customer");
-            } else {
-                count += 1;
-            }
-        }
-    }
-    Ok(count)
+#[derive(Debug, serde::Deserialize)]
+pub struct ListMappingsQuery {
+    pub entity: Option<String>,
 }
 
-async fn import_vendors(pool: &sqlx::SqlitePool, lines: &[&str]) -> crate::error::ApiResult<usize> {
-    let mut count = 0;
-    for line in lines.iter().skip(1) {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let parts = parse_csv_line(line);
-        if parts.len() >= 3 {
-            let id = uuid::Uuid::new_v4().to_string();
-            let now = chrono::Utc::now().to_rfc3339();
-            if let Err(e) = sqlx::query(
-                "INSERT OR IGNORE INTO vendors (id, code, name, email, status, created_at, updated_at, payment_terms)
-                 VALUES (?, ?, ?, ?, 'Active', ?, ?, 30)"
-            )
-            .bind(&id)
-            .bind(&parts[1])
-            .bind(&parts[2])
-            .bind(parts.get(3).unwrap_or(&"".to_string()))
-            .bind(&now)
-            .bind(&now)
-            .execute(pool)
-            .await
-            {
-                warn!(code = %parts[1], error = %e, "Failed to import vendor");
-            } else {
-                count += 1;
-            }
-        }
-    }
-    Ok(count)
+pub async fn list_import_mappings(
+    State(state): State<AppState>,
+    Query(query): Query<ListMappingsQuery>,
+) -> ApiResult<Json<Vec<ImportMapping>>> {
+    Ok(Json(ImportService::new().list_mappings(&state.pool, query.entity.as_deref()).await?))
 }
 
-async fn import_accounts(pool: &sqlx::SqlitePool, lines: &[&str]) -> crate::error::ApiResult<usize> {
-    let mut count = 0;
-    for line in lines.iter().skip(1) {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let parts = parse_csv_line(line);
-        if parts.len() >= 4 {
-            let id = uuid::Uuid::new_v4().to_string();
-            let now = chrono::Utc::now().to_rfc3339();
-            if let Err(e) = sqlx::query(
-                "INSERT OR IGNORE INTO accounts (id, code, name, account_type, status, created_at, updated_at)
-                 VALUES (?, ?, ?, ?, 'Active', ?, ?)"
-            )
-            .bind(&id)
-            .bind(&parts[1])
-            .bind(&parts[2])
-            .bind(&parts[3])
-            .bind(&now)
-            .bind(&now)
-            .execute(pool)
-            .await
-            {
-                warn!(code = %parts[1], error = %e, "Failed to import account");
-            } else {
-                count += 1;
-            }
-        }
-    }
-    Ok(count)
+pub async fn create_import_mapping(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Json(req): Json<CreateImportMappingRequest>,
+) -> ApiResult<(StatusCode, Json<ImportMapping>)> {
+    let mapping = ImportService::new().create_mapping(&state.pool, req, Some(user.user_id())).await?;
+    Ok((StatusCode::CREATED, Json(mapping)))
 }
 
-async fn import_employees(pool: &sqlx::SqlitePool, lines: &[&str]) -> crate::error::ApiResult<usize> {
-    let mut count = 0;
-    for line in lines.iter().skip(1) {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let parts = parse_csv_line(line);
-        if parts.len() >= 4 {
-            let id = uuid::Uuid::new_v4().to_string();
-            let now = chrono::Utc::now().to_rfc3339();
-            if let Err(e) = sqlx::query(
-                "INSERT OR IGNORE INTO employees (id, employee_number, first_name, last_name, email, status, created_at, updated_at, hire_date, birth_date)
-                 VALUES (?, ?, ?, ?, '', 'Active', ?, ?, date('now'), date('now'))"
-            )
-            .bind(&id)
-            .bind(&parts[1])
-            .bind(&parts[2])
-            .bind(&parts[3])
-            .bind(&now)
-            .bind(&now)
-            .execute(pool)
-            .await
-            {
-                warn!(employee_number = %parts[1], error = %e, "Failed to import employee");
-            } else {
-                count += 1;
-            }
-        }
-    }
-    Ok(count)
+pub async fn delete_import_mapping(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    ImportService::new().delete_mapping(&state.pool, id).await?;
+    Ok(StatusCode::NO_CONTENT)
 }
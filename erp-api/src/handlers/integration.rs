@@ -10,6 +10,7 @@ use uuid::Uuid;
 use crate::db::AppState;
 use crate::error::ApiResult;
 use crate::handlers::auth::AuthUser;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateAPIKeyRequest {
@@ -248,10 +249,10 @@ fn parse_auth_type(s: &str) -> anyhow::Result<erp_integration::AuthType> {
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/api-keys", axum::routing::post(create_api_key).get(list_api_keys))
-        .route("/api-keys/:id/revoke", axum::routing::post(revoke_api_key))
-        .route("/api-keys/:id", axum::routing::delete(delete_api_key))
-        .route("/connections", axum::routing::post(create_connection).get(list_connections))
-        .route("/connections/:id/test", axum::routing::post(test_connection))
-        .route("/connections/:id", axum::routing::delete(delete_connection))
+        .route("/api-keys", axum::routing::post(create_api_key).get(list_api_keys).require("integration:api-keys"))
+        .route("/api-keys/:id/revoke", axum::routing::post(revoke_api_key).require("integration:api-keys:write"))
+        .route("/api-keys/:id", axum::routing::delete(delete_api_key).require("integration:api-keys:delete"))
+        .route("/connections", axum::routing::post(create_connection).get(list_connections).require("integration:connections"))
+        .route("/connections/:id/test", axum::routing::post(test_connection).require("integration:connections:write"))
+        .route("/connections/:id", axum::routing::delete(delete_connection).require("integration:connections:delete"))
 }
@@ -7,9 +7,13 @@ use serde::{Deserialize, Serialize};
 use validator::Validate;
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::handlers::auth::AuthUser;
 use erp_core::{BaseEntity, Status, Pagination, Address};
+use erp_core::events::publish_change;
 use erp_inventory::{Product, ProductType, Warehouse, StockMovement, StockLevel, MovementType, 
-                    ProductService, WarehouseService, StockService};
+                    ProductService, WarehouseService, StockService, AtpService, AvailableToPromise,
+                    CostingService, ProductCostSettings, ValuationMethod, CostAdjustment, CostVariance,
+                    PerpetualValuationReport};
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateProductRequest {
@@ -81,6 +85,7 @@ pub async fn get_product(
 
 pub async fn create_product(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Json(req): Json<CreateProductRequest>,
 ) -> ApiResult<Json<ProductResponse>> {
     req.validate()?;
@@ -103,11 +108,13 @@ pub async fn create_product(
     };
     
     let created = service.create_product(&state.pool, product).await?;
+    publish_change("product", created.base.id, "created", &user.user_id);
     Ok(Json(ProductResponse::from(created)))
 }
 
 pub async fn update_product(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Path(id): Path<Uuid>,
     Json(req): Json<CreateProductRequest>,
 ) -> ApiResult<Json<ProductResponse>> {
@@ -128,15 +135,18 @@ pub async fn update_product(
     product.unit_of_measure = req.unit_of_measure;
     
     let updated = service.update_product(&state.pool, product).await?;
+    publish_change("product", updated.base.id, "updated", &user.user_id);
     Ok(Json(ProductResponse::from(updated)))
 }
 
 pub async fn delete_product(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<()>> {
     let service = ProductService::new();
     service.delete_product(&state.pool, id).await?;
+    publish_change("product", id, "deleted", &user.user_id);
     Ok(Json(()))
 }
 
@@ -213,6 +223,7 @@ pub async fn get_warehouse(
 
 pub async fn create_warehouse(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Json(req): Json<CreateWarehouseRequest>,
 ) -> ApiResult<Json<WarehouseResponse>> {
     req.validate()?;
@@ -234,6 +245,7 @@ pub async fn create_warehouse(
     };
     
     let created = service.create_warehouse(&state.pool, warehouse).await?;
+    publish_change("warehouse", created.base.id, "created", &user.user_id);
     Ok(Json(WarehouseResponse::from(created)))
 }
 
@@ -248,6 +260,8 @@ pub struct CreateStockMovementRequest {
     pub movement_type: String,
     #[validate(length(max = 100, message = "Reference must not exceed 100 characters"))]
     pub reference: Option<String>,
+    #[validate(range(min = 0, message = "Unit cost cannot be negative"))]
+    pub unit_cost: Option<i64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -261,10 +275,13 @@ pub struct StockMovementResponse {
     pub quantity: i64,
     pub reference: Option<String>,
     pub date: String,
+    pub unit_cost: Option<i64>,
+    pub total_cost: Option<i64>,
 }
 
 pub async fn create_stock_movement(
     State(state): State<AppState>,
+    axum::Extension(AuthUser(user)): axum::Extension<AuthUser>,
     Json(req): Json<CreateStockMovementRequest>,
 ) -> ApiResult<Json<StockMovementResponse>> {
     req.validate()?;
@@ -286,9 +303,12 @@ pub async fn create_stock_movement(
         quantity: req.quantity,
         reference: req.reference,
         date: chrono::Utc::now(),
+        unit_cost: req.unit_cost,
+        total_cost: None,
     };
     
     let created = service.record_movement(&state.pool, movement).await?;
+    publish_change("product", created.product_id, "stock_moved", &user.user_id);
     
     Ok(Json(StockMovementResponse {
         id: created.base.id,
@@ -300,6 +320,8 @@ pub async fn create_stock_movement(
         quantity: created.quantity,
         reference: created.reference,
         date: created.date.to_rfc3339(),
+        unit_cost: created.unit_cost,
+        total_cost: created.total_cost,
     }))
 }
 
@@ -332,3 +354,84 @@ pub async fn get_stock(
     let levels = service.get_product_stock(&state.pool, product_id).await?;
     Ok(Json(levels.into_iter().map(StockLevelResponse::from).collect()))
 }
+
+#[derive(Debug, Deserialize)]
+pub struct AtpQuery {
+    pub product_id: Uuid,
+    pub quantity: i64,
+    pub date: Option<chrono::DateTime<chrono::Utc>>,
+    pub warehouse_id: Option<Uuid>,
+}
+
+pub async fn check_atp(
+    State(state): State<AppState>,
+    Query(query): Query<AtpQuery>,
+) -> ApiResult<Json<AvailableToPromise>> {
+    let date = query.date.unwrap_or_else(chrono::Utc::now);
+    Ok(Json(AtpService::check(&state.pool, query.product_id, query.quantity, date, query.warehouse_id).await?))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigureCostingRequest {
+    pub valuation_method: ValuationMethod,
+    #[serde(default)]
+    pub standard_cost: i64,
+}
+
+pub async fn get_costing(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<ProductCostSettings>> {
+    Ok(Json(CostingService::get_settings(&state.pool, id).await?))
+}
+
+pub async fn configure_costing(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<ConfigureCostingRequest>,
+) -> ApiResult<Json<ProductCostSettings>> {
+    Ok(Json(CostingService::configure(&state.pool, id, req.valuation_method, req.standard_cost).await?))
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RevalueStandardCostRequest {
+    #[validate(range(min = 1, message = "Standard cost must be positive"))]
+    pub standard_cost: i64,
+    #[validate(length(min = 1, max = 500, message = "Reason must be between 1 and 500 characters"))]
+    pub reason: String,
+}
+
+pub async fn revalue_standard_cost(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<RevalueStandardCostRequest>,
+) -> ApiResult<Json<CostAdjustment>> {
+    req.validate()?;
+    Ok(Json(CostingService::revalue_standard_cost(&state.pool, id, req.standard_cost, &req.reason).await?))
+}
+
+pub async fn list_cost_variances(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<Vec<CostVariance>>> {
+    Ok(Json(CostingService::list_variances(&state.pool, id).await?))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ValuationReportQuery {
+    pub warehouse_id: Option<Uuid>,
+    /// Defaults to the debit account of the goods receipt posting rule.
+    pub account_id: Option<Uuid>,
+}
+
+pub async fn valuation_report(
+    State(state): State<AppState>,
+    Query(query): Query<ValuationReportQuery>,
+) -> ApiResult<Json<PerpetualValuationReport>> {
+    let account_id = match query.account_id {
+        Some(id) => Some(id),
+        None => erp_finance::PostingService::get_rule(&state.pool, erp_finance::PostingEvent::GoodsReceipt).await?
+            .map(|rule| rule.debit_account_id),
+    };
+    Ok(Json(CostingService::valuation_report(&state.pool, query.warehouse_id, account_id).await?))
+}
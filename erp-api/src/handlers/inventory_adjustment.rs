@@ -8,6 +8,7 @@ use uuid::Uuid;
 use crate::error::ApiResult;
 use crate::db::AppState;
 use crate::handlers::auth::AuthUser;
+use crate::middleware::RequirePermission;
 use erp_inventory_adjustment::{
     AdjustmentService,
     InventoryAdjustment, InventoryAdjustmentLine, InventoryAdjustmentWithLines, AdjustmentAnalytics,
@@ -152,15 +153,15 @@ pub struct RejectRequestDto {
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/", axum::routing::get(list_adjustments).post(create_adjustment))
-        .route("/analytics", axum::routing::get(get_analytics))
-        .route("/:id", axum::routing::get(get_adjustment).delete(delete_adjustment))
-        .route("/:id/submit", axum::routing::post(submit_adjustment))
-        .route("/:id/approve", axum::routing::post(approve_adjustment))
-        .route("/:id/reject", axum::routing::post(reject_adjustment))
-        .route("/:id/complete", axum::routing::post(complete_adjustment))
-        .route("/:id/cancel", axum::routing::post(cancel_adjustment))
-        .route("/:id/lines", axum::routing::get(list_lines).post(add_line))
+        .route("/", axum::routing::get(list_adjustments).post(create_adjustment).require("inventory-adjustments:inventory-adjustments"))
+        .route("/analytics", axum::routing::get(get_analytics).require("inventory-adjustments:analytics:read"))
+        .route("/:id", axum::routing::get(get_adjustment).delete(delete_adjustment).require("inventory-adjustments:inventory-adjustments"))
+        .route("/:id/submit", axum::routing::post(submit_adjustment).require("inventory-adjustments:inventory-adjustments:write"))
+        .route("/:id/approve", axum::routing::post(approve_adjustment).require("inventory-adjustments:inventory-adjustments:approve"))
+        .route("/:id/reject", axum::routing::post(reject_adjustment).require("inventory-adjustments:inventory-adjustments:reject"))
+        .route("/:id/complete", axum::routing::post(complete_adjustment).require("inventory-adjustments:inventory-adjustments:write"))
+        .route("/:id/cancel", axum::routing::post(cancel_adjustment).require("inventory-adjustments:inventory-adjustments:write"))
+        .route("/:id/lines", axum::routing::get(list_lines).post(add_line).require("inventory-adjustments:inventory-adjustments"))
 }
 
 fn parse_adjustment_type(s: &str) -> AdjustmentType {
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use erp_core::Pagination;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 use erp_iot::{IoTDeviceService, TelemetryService, IoTAlertService, DeviceCommandService};
 use erp_iot::{IoTDevice, DeviceType, DeviceStatus, ConnectivityType, MetricType};
 
@@ -287,14 +288,14 @@ pub async fn send_command(
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/devices", axum::routing::get(list_devices).post(register_device))
-        .route("/devices/:id", axum::routing::get(get_device))
-        .route("/devices/:device_id/heartbeat", axum::routing::post(device_heartbeat))
-        .route("/telemetry", axum::routing::post(ingest_telemetry))
-        .route("/telemetry/batch", axum::routing::post(ingest_batch))
-        .route("/telemetry/:device_id", axum::routing::get(get_telemetry))
-        .route("/alerts", axum::routing::get(list_alerts))
-        .route("/alerts/:id/acknowledge", axum::routing::post(acknowledge_alert))
-        .route("/alerts/:id/resolve", axum::routing::post(resolve_alert))
-        .route("/devices/:device_id/command", axum::routing::post(send_command))
+        .route("/devices", axum::routing::get(list_devices).post(register_device).require("iot:devices"))
+        .route("/devices/:id", axum::routing::get(get_device).require("iot:devices:read"))
+        .route("/devices/:device_id/heartbeat", axum::routing::post(device_heartbeat).require("iot:devices:write"))
+        .route("/telemetry", axum::routing::post(ingest_telemetry).require("iot:telemetry:write"))
+        .route("/telemetry/batch", axum::routing::post(ingest_batch).require("iot:telemetry:write"))
+        .route("/telemetry/:device_id", axum::routing::get(get_telemetry).require("iot:telemetry:read"))
+        .route("/alerts", axum::routing::get(list_alerts).require("iot:alerts:read"))
+        .route("/alerts/:id/acknowledge", axum::routing::post(acknowledge_alert).require("iot:alerts:write"))
+        .route("/alerts/:id/resolve", axum::routing::post(resolve_alert).require("iot:alerts:write"))
+        .route("/devices/:device_id/command", axum::routing::post(send_command).require("iot:devices:write"))
 }
@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateJobRequest {
@@ -279,11 +280,11 @@ fn parse_schedule_type(s: &str) -> anyhow::Result<erp_jobs::ScheduleType> {
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/", axum::routing::post(submit_job).get(list_jobs))
-        .route("/:id", axum::routing::get(get_job))
-        .route("/:id/cancel", axum::routing::post(cancel_job))
-        .route("/:id/retry", axum::routing::post(retry_job))
-        .route("/schedules", axum::routing::post(create_schedule).get(list_schedules))
-        .route("/schedules/:id/enable", axum::routing::post(enable_schedule))
-        .route("/schedules/:id/disable", axum::routing::post(disable_schedule))
+        .route("/", axum::routing::post(submit_job).get(list_jobs).require("jobs:jobs"))
+        .route("/:id", axum::routing::get(get_job).require("jobs:jobs:read"))
+        .route("/:id/cancel", axum::routing::post(cancel_job).require("jobs:jobs:write"))
+        .route("/:id/retry", axum::routing::post(retry_job).require("jobs:jobs:write"))
+        .route("/schedules", axum::routing::post(create_schedule).get(list_schedules).require("jobs:schedules"))
+        .route("/schedules/:id/enable", axum::routing::post(enable_schedule).require("jobs:schedules:write"))
+        .route("/schedules/:id/disable", axum::routing::post(disable_schedule).require("jobs:schedules:write"))
 }
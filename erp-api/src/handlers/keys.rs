@@ -7,8 +7,16 @@ use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_keys::{KeyService, EncryptionKey, KeyType};
 
+fn key_service(state: &AppState) -> KeyService {
+    match &state.config.master_key {
+        Some(master_key) => KeyService::with_master_key(master_key.clone()),
+        None => KeyService::new(),
+    }
+}
+
 #[derive(Serialize)]
 pub struct KeyResponse {
     pub id: Uuid,
@@ -43,7 +51,7 @@ pub struct GenerateKeyRequest {
 }
 
 pub async fn list_keys(State(state): State<AppState>) -> ApiResult<Json<Vec<KeyResponse>>> {
-    let service = KeyService::new();
+    let service = key_service(&state);
     let keys = service.list_keys(&state.pool).await?;
     Ok(Json(keys.into_iter().map(KeyResponse::from).collect()))
 }
@@ -52,7 +60,7 @@ pub async fn generate_key(
     State(state): State<AppState>,
     Json(req): Json<GenerateKeyRequest>,
 ) -> ApiResult<Json<KeyResponse>> {
-    let service = KeyService::new();
+    let service = key_service(&state);
     let key_type = match req.key_type.as_str() {
         "Aes256Gcm" => KeyType::Aes256Gcm,
         "Aes256Cbc" => KeyType::Aes256Cbc,
@@ -68,7 +76,7 @@ pub async fn get_key(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<KeyResponse>> {
-    let service = KeyService::new();
+    let service = key_service(&state);
     let key = service.get_key(&state.pool, id).await?
         .ok_or_else(|| anyhow::anyhow!("Key not found"))?;
     Ok(Json(KeyResponse::from(key)))
@@ -78,7 +86,7 @@ pub async fn set_primary_key(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<serde_json::Value>> {
-    let service = KeyService::new();
+    let service = key_service(&state);
     service.set_primary_key(&state.pool, id).await?;
     Ok(Json(serde_json::json!({ "status": "updated" })))
 }
@@ -87,17 +95,18 @@ pub async fn rotate_key(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<serde_json::Value>> {
-    let service = KeyService::new();
+    let service = key_service(&state);
     let rotation = service.rotate_key(&state.pool, id, None).await?;
     Ok(Json(serde_json::json!({
         "status": "rotated",
         "from_version": rotation.from_version,
-        "to_version": rotation.to_version
+        "to_version": rotation.to_version,
+        "re_encrypted_count": rotation.re_encrypted_count
     })))
 }
 
 pub async fn keys_needing_rotation(State(state): State<AppState>) -> ApiResult<Json<Vec<KeyResponse>>> {
-    let service = KeyService::new();
+    let service = key_service(&state);
     let keys = service.get_keys_needing_rotation(&state.pool).await?;
     Ok(Json(keys.into_iter().map(KeyResponse::from).collect()))
 }
@@ -119,7 +128,7 @@ pub async fn encrypt_data(
     State(state): State<AppState>,
     Json(req): Json<EncryptRequest>,
 ) -> ApiResult<Json<EncryptResponse>> {
-    let service = KeyService::new();
+    let service = key_service(&state);
     service.encrypt_data(&state.pool, &req.entity_type, &req.entity_id, &req.field_name, &req.plaintext).await?;
     Ok(Json(EncryptResponse { encrypted: true }))
 }
@@ -140,18 +149,18 @@ pub async fn decrypt_data(
     State(state): State<AppState>,
     Json(req): Json<DecryptRequest>,
 ) -> ApiResult<Json<DecryptResponse>> {
-    let service = KeyService::new();
+    let service = key_service(&state);
     let plaintext = service.decrypt_data(&state.pool, &req.entity_type, &req.entity_id, &req.field_name).await?;
     Ok(Json(DecryptResponse { plaintext }))
 }
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/", get(list_keys).post(generate_key))
-        .route("/:id", get(get_key))
-        .route("/:id/primary", post(set_primary_key))
-        .route("/:id/rotate", post(rotate_key))
-        .route("/needing-rotation", get(keys_needing_rotation))
-        .route("/encrypt", post(encrypt_data))
-        .route("/decrypt", post(decrypt_data))
+        .route("/", get(list_keys).post(generate_key).require("keys:keys"))
+        .route("/:id", get(get_key).require("keys:keys:read"))
+        .route("/:id/primary", post(set_primary_key).require("keys:keys:write"))
+        .route("/:id/rotate", post(rotate_key).require("keys:keys:write"))
+        .route("/needing-rotation", get(keys_needing_rotation).require("keys:needing-rotation:read"))
+        .route("/encrypt", post(encrypt_data).require("keys:encrypt:write"))
+        .route("/decrypt", post(decrypt_data).require("keys:decrypt:write"))
 }
@@ -6,17 +6,18 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/", post(create_lease).get(list_leases))
-        .route("/:id", get(get_lease))
-        .route("/:id/amortization", post(calculate_amortization))
-        .route("/:id/rou-asset", post(create_rou_asset))
-        .route("/:id/liability", post(create_liability))
-        .route("/:id/payment", post(record_lease_payment))
-        .route("/:id/modification", post(create_modification))
-        .route("/disclosures", post(generate_disclosure))
+        .route("/", post(create_lease).get(list_leases).require("lease:lease"))
+        .route("/:id", get(get_lease).require("lease:lease:read"))
+        .route("/:id/amortization", post(calculate_amortization).require("lease:lease:write"))
+        .route("/:id/rou-asset", post(create_rou_asset).require("lease:lease:write"))
+        .route("/:id/liability", post(create_liability).require("lease:lease:write"))
+        .route("/:id/payment", post(record_lease_payment).require("lease:lease:write"))
+        .route("/:id/modification", post(create_modification).require("lease:lease:write"))
+        .route("/disclosures", post(generate_disclosure).require("lease:disclosures:write"))
 }
 
 #[derive(Deserialize)]
@@ -2,17 +2,18 @@ use axum::{extract::State, routing::{get, post}, Json, Router};
 use serde::Serialize;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/programs", post(create_program).get(list_programs))
-        .route("/programs/:id", get(get_program))
-        .route("/members", post(enroll_member).get(list_members))
-        .route("/members/:id", get(get_member))
-        .route("/members/:id/earn", post(earn_points))
-        .route("/members/:id/redeem", post(redeem_points))
-        .route("/rewards", post(create_reward).get(list_rewards))
-        .route("/promotions", post(create_promotion).get(list_promotions))
+        .route("/programs", post(create_program).get(list_programs).require("loyalty:programs"))
+        .route("/programs/:id", get(get_program).require("loyalty:programs:read"))
+        .route("/members", post(enroll_member).get(list_members).require("loyalty:members"))
+        .route("/members/:id", get(get_member).require("loyalty:members:read"))
+        .route("/members/:id/earn", post(earn_points).require("loyalty:members:write"))
+        .route("/members/:id/redeem", post(redeem_points).require("loyalty:members:write"))
+        .route("/rewards", post(create_reward).get(list_rewards).require("loyalty:rewards"))
+        .route("/promotions", post(create_promotion).get(list_promotions).require("loyalty:promotions"))
 }
 
 #[derive(Serialize)]
@@ -4,7 +4,9 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::handlers::auth::AuthUser;
 use erp_core::{BaseEntity, Status, Pagination};
+use erp_core::events::publish_change;
 use erp_manufacturing::{BillOfMaterial, BomComponent, WorkOrder, BillOfMaterialService, WorkOrderService};
 
 #[derive(Deserialize)] pub struct CreateBomRequest { pub product_id: Uuid, pub name: String, pub quantity: i64, pub components: Vec<BomComponentRequest> }
@@ -29,14 +31,16 @@ pub async fn get_bom(State(state): State<AppState>, Path(id): Path<Uuid>) -> Api
     Ok(Json(BomResponse::from(BillOfMaterialService::new().get(&state.pool, id).await?)))
 }
 
-pub async fn create_bom(State(state): State<AppState>, Json(req): Json<CreateBomRequest>) -> ApiResult<Json<BomResponse>> {
+pub async fn create_bom(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Json(req): Json<CreateBomRequest>) -> ApiResult<Json<BomResponse>> {
     let svc = BillOfMaterialService::new();
     let bom = BillOfMaterial {
         base: BaseEntity::new(), product_id: req.product_id, name: req.name, version: "1.0".to_string(), quantity: req.quantity,
         components: req.components.into_iter().map(|c| BomComponent { id: Uuid::nil(), product_id: c.product_id, quantity: c.quantity, unit: c.unit, scrap_percent: 0.0 }).collect(),
         operations: vec![], status: Status::Draft,
     };
-    Ok(Json(BomResponse::from(svc.create(&state.pool, bom).await?)))
+    let created = svc.create(&state.pool, bom).await?;
+    publish_change("bom", created.base.id, "created", &user.user_id);
+    Ok(Json(BomResponse::from(created)))
 }
 
 #[derive(Deserialize)] pub struct CreateWORequest { pub product_id: Uuid, pub bom_id: Uuid, pub quantity: i64, pub planned_start: String, pub planned_end: String }
@@ -52,7 +56,7 @@ pub async fn list_work_orders(State(state): State<AppState>, Query(pagination):
     Ok(Json(erp_core::Paginated::new(res.items.into_iter().map(WOResponse::from).collect(), res.total, Pagination { page: res.page, per_page: res.per_page })))
 }
 
-pub async fn create_work_order(State(state): State<AppState>, Json(req): Json<CreateWORequest>) -> ApiResult<Json<WOResponse>> {
+pub async fn create_work_order(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Json(req): Json<CreateWORequest>) -> ApiResult<Json<WOResponse>> {
     let svc = WorkOrderService::new();
     let wo = WorkOrder {
         base: BaseEntity::new(), order_number: String::new(), product_id: req.product_id, bom_id: req.bom_id, quantity: req.quantity,
@@ -60,15 +64,19 @@ pub async fn create_work_order(State(state): State<AppState>, Json(req): Json<Cr
         planned_end: chrono::DateTime::parse_from_rfc3339(&req.planned_end).map(|d| d.with_timezone(&Utc)).unwrap_or_else(|_| Utc::now()),
         actual_start: None, actual_end: None, status: Status::Draft,
     };
-    Ok(Json(WOResponse::from(svc.create(&state.pool, wo).await?)))
+    let created = svc.create(&state.pool, wo).await?;
+    publish_change("work_order", created.base.id, "created", &user.user_id);
+    Ok(Json(WOResponse::from(created)))
 }
 
-pub async fn start_work_order(State(state): State<AppState>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
+pub async fn start_work_order(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
     WorkOrderService::new().start(&state.pool, id).await?;
+    publish_change("work_order", id, "started", &user.user_id);
     Ok(Json(serde_json::json!({ "status": "in_progress" })))
 }
 
-pub async fn complete_work_order(State(state): State<AppState>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
+pub async fn complete_work_order(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
     WorkOrderService::new().complete(&state.pool, id).await?;
+    publish_change("work_order", id, "completed", &user.user_id);
     Ok(Json(serde_json::json!({ "status": "completed" })))
 }
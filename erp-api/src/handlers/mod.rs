@@ -35,6 +35,7 @@ pub mod email;
 pub mod email_templates;
 pub mod extended;
 pub mod features;
+pub mod files;
 pub mod finance;
 pub mod fraud;
 pub mod fsm;
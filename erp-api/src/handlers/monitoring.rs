@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
 use crate::handlers::auth::AuthUser;
+use crate::middleware::RequirePermission;
 use erp_core::BaseEntity;
 use erp_monitoring::{
     MonitoringService, HealthCheck, HealthCheckType, AlertRule,
@@ -262,14 +263,14 @@ pub async fn database_stats(State(state): State<AppState>) -> ApiResult<Json<Dat
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/status", get(system_status))
-        .route("/metrics/collect", post(collect_metrics))
-        .route("/health-checks", get(list_health_checks).post(create_health_check))
-        .route("/health-checks/:id/run", post(run_health_check))
-        .route("/health-checks/run-all", post(run_all_health_checks))
-        .route("/alert-rules", get(list_alert_rules).post(create_alert_rule))
-        .route("/alerts", get(list_alerts))
-        .route("/alerts/:id/acknowledge", post(acknowledge_alert))
-        .route("/alerts/:id/resolve", post(resolve_alert))
-        .route("/database-stats", get(database_stats))
+        .route("/status", get(system_status).require("monitoring:status:read"))
+        .route("/metrics/collect", post(collect_metrics).require("monitoring:metrics:write"))
+        .route("/health-checks", get(list_health_checks).post(create_health_check).require("monitoring:health-checks"))
+        .route("/health-checks/:id/run", post(run_health_check).require("monitoring:health-checks:write"))
+        .route("/health-checks/run-all", post(run_all_health_checks).require("monitoring:health-checks:write"))
+        .route("/alert-rules", get(list_alert_rules).post(create_alert_rule).require("monitoring:alert-rules"))
+        .route("/alerts", get(list_alerts).require("monitoring:alerts:read"))
+        .route("/alerts/:id/acknowledge", post(acknowledge_alert).require("monitoring:alerts:write"))
+        .route("/alerts/:id/resolve", post(resolve_alert).require("monitoring:alerts:write"))
+        .route("/database-stats", get(database_stats).require("monitoring:database-stats:read"))
 }
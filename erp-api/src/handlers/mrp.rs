@@ -2,24 +2,25 @@ use axum::{extract::State, routing::{get, post}, Json, Router};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/runs", post(create_run).get(list_runs))
-        .route("/runs/:id", get(get_run))
-        .route("/runs/:id/execute", post(execute_run))
-        .route("/runs/:id/suggestions", get(list_suggestions))
-        .route("/parameters", post(create_parameter).get(list_parameters))
-        .route("/parameters/:id", get(get_parameter).put(update_parameter))
-        .route("/forecasts", post(create_forecast).get(list_forecasts))
-        .route("/forecasts/:id", get(get_forecast))
-        .route("/forecasts/:id/lines", post(add_forecast_line).get(list_forecast_lines))
-        .route("/planned-orders", post(create_planned_order).get(list_planned_orders))
-        .route("/planned-orders/:id", get(get_planned_order))
-        .route("/planned-orders/:id/firm", post(firm_planned_order))
-        .route("/planned-orders/:id/convert", post(convert_planned_order))
-        .route("/exceptions", get(list_exceptions))
-        .route("/exceptions/:id/acknowledge", post(acknowledge_exception))
+        .route("/runs", post(create_run).get(list_runs).require("mrp:runs"))
+        .route("/runs/:id", get(get_run).require("mrp:runs:read"))
+        .route("/runs/:id/execute", post(execute_run).require("mrp:runs:write"))
+        .route("/runs/:id/suggestions", get(list_suggestions).require("mrp:runs:read"))
+        .route("/parameters", post(create_parameter).get(list_parameters).require("mrp:parameters"))
+        .route("/parameters/:id", get(get_parameter).put(update_parameter).require("mrp:parameters"))
+        .route("/forecasts", post(create_forecast).get(list_forecasts).require("mrp:forecasts"))
+        .route("/forecasts/:id", get(get_forecast).require("mrp:forecasts:read"))
+        .route("/forecasts/:id/lines", post(add_forecast_line).get(list_forecast_lines).require("mrp:forecasts"))
+        .route("/planned-orders", post(create_planned_order).get(list_planned_orders).require("mrp:planned-orders"))
+        .route("/planned-orders/:id", get(get_planned_order).require("mrp:planned-orders:read"))
+        .route("/planned-orders/:id/firm", post(firm_planned_order).require("mrp:planned-orders:write"))
+        .route("/planned-orders/:id/convert", post(convert_planned_order).require("mrp:planned-orders:write"))
+        .route("/exceptions", get(list_exceptions).require("mrp:exceptions:read"))
+        .route("/exceptions/:id/acknowledge", post(acknowledge_exception).require("mrp:exceptions:write"))
 }
 
 #[derive(Serialize)]
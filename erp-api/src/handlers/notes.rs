@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_notes::{CreateNoteRequest, Note, NoteService, UpdateNoteRequest};
 
 const ALLOWED_ENTITY_TYPES: &[&str] = &[
@@ -248,8 +249,8 @@ pub async fn unpin_note(
 
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/", axum::routing::get(list_notes).post(create_note))
-        .route("/:id", axum::routing::get(get_note).put(update_note).delete(delete_note))
-        .route("/:id/pin", axum::routing::post(pin_note))
-        .route("/:id/unpin", axum::routing::post(unpin_note))
+        .route("/", axum::routing::get(list_notes).post(create_note).require("notes:notes"))
+        .route("/:id", axum::routing::get(get_note).put(update_note).delete(delete_note).require("notes:notes"))
+        .route("/:id/pin", axum::routing::post(pin_note).require("notes:notes:write"))
+        .route("/:id/unpin", axum::routing::post(unpin_note).require("notes:notes:write"))
 }
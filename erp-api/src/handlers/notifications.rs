@@ -10,6 +10,7 @@ use uuid::Uuid;
 use crate::db::AppState;
 use crate::error::ApiResult;
 use crate::handlers::auth::AuthUser;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateNotificationRequest {
@@ -186,8 +187,8 @@ fn parse_notification_priority(s: &str) -> anyhow::Result<erp_notifications::Not
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/", axum::routing::post(send_notification).get(list_notifications))
-        .route("/:id/read", axum::routing::post(mark_notification_read))
-        .route("/read-all", axum::routing::post(mark_all_notifications_read))
-        .route("/unread-count", axum::routing::get(unread_count))
+        .route("/", axum::routing::post(send_notification).get(list_notifications).require("notifications:notifications"))
+        .route("/:id/read", axum::routing::post(mark_notification_read).require("notifications:notifications:write"))
+        .route("/read-all", axum::routing::post(mark_all_notifications_read).require("notifications:read-all:write"))
+        .route("/unread-count", axum::routing::get(unread_count).require("notifications:unread-count:read"))
 }
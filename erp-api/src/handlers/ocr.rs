@@ -1,13 +1,17 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    response::Response,
+    Extension, Json,
 };
 use serde::Deserialize;
 use uuid::Uuid;
 use crate::db::AppState;
 use crate::error::ApiResult;
-use erp_ocr::{OcrService, UploadDocumentRequest, DocumentType, OcrSettings};
+use crate::handlers::auth::AuthUser;
+use crate::handlers::files;
+use crate::middleware::RequirePermission;
+use erp_ocr::{OcrService, UploadDocumentRequest, CreateTemplateRequest, DocumentType, OcrSettings};
 
 #[derive(Deserialize)]
 pub struct ListDocumentsQuery {
@@ -22,15 +26,17 @@ fn default_limit() -> i64 { 50 }
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/documents", axum::routing::get(list_documents).post(upload_document))
-        .route("/documents/:id", axum::routing::get(get_document).delete(delete_document))
-        .route("/documents/:id/process", axum::routing::post(process_document))
-        .route("/documents/:id/review", axum::routing::post(review_document))
-        .route("/templates", axum::routing::get(list_templates).post(create_template))
-        .route("/templates/:id", axum::routing::get(get_template).delete(delete_template))
-        .route("/batch-jobs", axum::routing::post(create_batch_job))
-        .route("/batch-jobs/:id", axum::routing::get(get_batch_job))
-        .route("/settings", axum::routing::get(get_settings).put(update_settings))
+        .route("/documents", axum::routing::get(list_documents).post(upload_document).require("ocr:documents"))
+        .route("/documents/:id", axum::routing::get(get_document).delete(delete_document).require("ocr:documents"))
+        .route("/documents/:id/content", axum::routing::get(download_document).require("ocr:documents:read"))
+        .route("/documents/:id/download-url", axum::routing::get(document_download_url).require("ocr:documents:read"))
+        .route("/documents/:id/process", axum::routing::post(process_document).require("ocr:documents:write"))
+        .route("/documents/:id/review", axum::routing::post(review_document).require("ocr:documents:write"))
+        .route("/templates", axum::routing::get(list_templates).post(create_template).require("ocr:templates"))
+        .route("/templates/:id", axum::routing::get(get_template).delete(delete_template).require("ocr:templates"))
+        .route("/batch-jobs", axum::routing::post(create_batch_job).require("ocr:batch-jobs:write"))
+        .route("/batch-jobs/:id", axum::routing::get(get_batch_job).require("ocr:batch-jobs:read"))
+        .route("/settings", axum::routing::get(get_settings).put(update_settings).require("ocr:settings"))
 }
 
 #[derive(Deserialize)]
@@ -43,12 +49,8 @@ struct UploadDocumentBody {
     auto_process: bool,
 }
 
-async fn upload_document(
-    State(_state): State<AppState>,
-    Json(body): Json<UploadDocumentBody>,
-) -> ApiResult<Json<serde_json::Value>> {
-    let service = OcrService::new();
-    let doc_type = match body.document_type.as_str() {
+fn document_type(name: &str) -> DocumentType {
+    match name {
         "invoice" => DocumentType::Invoice,
         "receipt" => DocumentType::Receipt,
         "purchase_order" => DocumentType::PurchaseOrder,
@@ -61,34 +63,41 @@ async fn upload_document(
         "business_card" => DocumentType::BusinessCard,
         "form" => DocumentType::Form,
         _ => DocumentType::Other,
-    };
-    
-    let doc = service.upload_document(UploadDocumentRequest {
-        document_type: doc_type,
+    }
+}
+
+async fn upload_document(
+    State(state): State<AppState>,
+    Json(body): Json<UploadDocumentBody>,
+) -> ApiResult<(StatusCode, Json<serde_json::Value>)> {
+    let service = OcrService::new();
+    let doc = service.upload_document(&state.pool, &state.blobs, UploadDocumentRequest {
+        document_type: document_type(&body.document_type),
         filename: body.filename,
         content: body.content,
         template_id: body.template_id,
         auto_process: body.auto_process,
     }).await?;
-    Ok(Json(serde_json::to_value(doc)?))
+    Ok((StatusCode::CREATED, Json(serde_json::to_value(doc)?)))
 }
 
 async fn get_document(
     Path(id): Path<Uuid>,
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> ApiResult<Json<serde_json::Value>> {
     let service = OcrService::new();
-    let doc = service.get_document(id).await?;
+    let doc = service.get_document(&state.pool, id).await?;
     Ok(Json(serde_json::to_value(doc)?))
 }
 
 async fn list_documents(
     Query(query): Query<ListDocumentsQuery>,
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> ApiResult<Json<Vec<serde_json::Value>>> {
     let service = OcrService::new();
     let status = query.status.and_then(|s| match s.as_str() {
         "pending" => Some(erp_ocr::OcrStatus::Pending),
+        "queued" => Some(erp_ocr::OcrStatus::Queued),
         "processing" => Some(erp_ocr::OcrStatus::Processing),
         "completed" => Some(erp_ocr::OcrStatus::Completed),
         "failed" => Some(erp_ocr::OcrStatus::Failed),
@@ -96,23 +105,44 @@ async fn list_documents(
         "validated" => Some(erp_ocr::OcrStatus::Validated),
         _ => None,
     });
-    let docs = service.list_documents(status, query.limit, query.offset).await?;
+    let docs = service.list_documents(&state.pool, status, query.limit, query.offset).await?;
     Ok(Json(docs.into_iter().map(|d| serde_json::to_value(d).unwrap_or_default()).collect()))
 }
 
 async fn delete_document(
-    Path(_id): Path<Uuid>,
-    State(_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
 ) -> ApiResult<StatusCode> {
+    OcrService::new().delete_document(&state.pool, id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+async fn download_document(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> ApiResult<Response> {
+    let doc = OcrService::new().get_document(&state.pool, id).await?;
+    let checksum = doc.checksum.ok_or_else(|| erp_core::Error::not_found("OCR document file", &id.to_string()))?;
+    let (blob, stream) = state.blobs.open(&state.pool, &checksum).await?;
+    Ok(files::blob_response(&blob, stream, &doc.original_filename))
+}
+
+async fn document_download_url(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    Query(query): Query<files::DownloadUrlQuery>,
+) -> ApiResult<Json<files::DownloadUrlResponse>> {
+    let doc = OcrService::new().get_document(&state.pool, id).await?;
+    let checksum = doc.checksum.ok_or_else(|| erp_core::Error::not_found("OCR document file", &id.to_string()))?;
+    Ok(Json(files::download_url(&state.blobs, &checksum, &doc.original_filename, &query)?))
+}
+
 async fn process_document(
     Path(id): Path<Uuid>,
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> ApiResult<Json<serde_json::Value>> {
     let service = OcrService::new();
-    let result = service.process_document(id).await?;
+    let result = service.process_document(&state.pool, &state.blobs, id).await?;
     Ok(Json(serde_json::to_value(result)?))
 }
 
@@ -123,41 +153,51 @@ struct ReviewDocumentBody {
 
 async fn review_document(
     Path(id): Path<Uuid>,
-    State(_state): State<AppState>,
-    axum::Extension(user_id): axum::Extension<Uuid>,
+    State(state): State<AppState>,
+    Extension(AuthUser(user)): Extension<AuthUser>,
     Json(body): Json<ReviewDocumentBody>,
 ) -> ApiResult<Json<serde_json::Value>> {
+    let reviewer_id = Uuid::parse_str(&user.user_id).map_err(|_| erp_core::Error::Unauthorized)?;
     let service = OcrService::new();
-    let doc = service.review_document(id, user_id, body.corrections).await?;
+    let doc = service.review_document(&state.pool, id, reviewer_id, body.corrections).await?;
     Ok(Json(serde_json::to_value(doc)?))
 }
 
 async fn create_template(
-    State(_state): State<AppState>,
-    Json(_template): Json<serde_json::Value>,
-) -> ApiResult<Json<serde_json::Value>> {
-    Ok(Json(_template))
+    State(state): State<AppState>,
+    Json(request): Json<CreateTemplateRequest>,
+) -> ApiResult<(StatusCode, Json<serde_json::Value>)> {
+    let template = OcrService::new().create_template(&state.pool, request).await?;
+    Ok((StatusCode::CREATED, Json(serde_json::to_value(template)?)))
 }
 
 async fn get_template(
-    Path(_id): Path<Uuid>,
-    State(_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
 ) -> ApiResult<Json<serde_json::Value>> {
-    Ok(Json(serde_json::json!({})))
+    let template = OcrService::new().get_template(&state.pool, id).await?;
+    Ok(Json(serde_json::to_value(template)?))
+}
+
+#[derive(Deserialize)]
+struct ListTemplatesQuery {
+    document_type: Option<String>,
 }
 
 async fn list_templates(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    Query(query): Query<ListTemplatesQuery>,
 ) -> ApiResult<Json<Vec<serde_json::Value>>> {
     let service = OcrService::new();
-    let templates = service.list_templates(None).await?;
+    let templates = service.list_templates(&state.pool, query.document_type.as_deref().map(document_type)).await?;
     Ok(Json(templates.into_iter().map(|t| serde_json::to_value(t).unwrap_or_default()).collect()))
 }
 
 async fn delete_template(
-    Path(_id): Path<Uuid>,
-    State(_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
 ) -> ApiResult<StatusCode> {
+    OcrService::new().delete_template(&state.pool, id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -169,34 +209,35 @@ struct CreateBatchJobBody {
 }
 
 async fn create_batch_job(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(body): Json<CreateBatchJobBody>,
-) -> ApiResult<Json<serde_json::Value>> {
+) -> ApiResult<(StatusCode, Json<serde_json::Value>)> {
     let service = OcrService::new();
-    let job = service.create_batch_job(body.name, body.document_ids, body.template_id).await?;
-    Ok(Json(serde_json::to_value(job)?))
+    let job = service.create_batch_job(&state.pool, body.name, body.document_ids, body.template_id).await?;
+    Ok((StatusCode::CREATED, Json(serde_json::to_value(job)?)))
 }
 
 async fn get_batch_job(
-    Path(_id): Path<Uuid>,
-    State(_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
 ) -> ApiResult<Json<serde_json::Value>> {
-    Ok(Json(serde_json::json!({})))
+    let job = OcrService::new().get_batch_job(&state.pool, id).await?;
+    Ok(Json(serde_json::to_value(job)?))
 }
 
 async fn get_settings(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> ApiResult<Json<serde_json::Value>> {
     let service = OcrService::new();
-    let settings = service.get_settings().await?;
+    let settings = service.get_settings(&state.pool).await?;
     Ok(Json(serde_json::to_value(settings)?))
 }
 
 async fn update_settings(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(settings): Json<OcrSettings>,
 ) -> ApiResult<StatusCode> {
     let service = OcrService::new();
-    service.update_settings(settings).await?;
+    service.update_settings(&state.pool, settings).await?;
     Ok(StatusCode::NO_CONTENT)
 }
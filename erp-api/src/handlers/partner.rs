@@ -2,17 +2,18 @@ use axum::{extract::State, routing::{get, post}, Json, Router};
 use serde::Serialize;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/", post(create_partner).get(list_partners))
-        .route("/:id", get(get_partner))
-        .route("/:id/contacts", post(create_contact).get(list_contacts))
-        .route("/:id/agreements", post(create_agreement).get(list_agreements))
-        .route("/:id/deals", post(create_deal).get(list_deals))
-        .route("/deals/:id/register", post(register_deal))
-        .route("/:id/commissions", post(create_commission).get(list_commissions))
-        .route("/:id/performance", get(get_performance))
+        .route("/", post(create_partner).get(list_partners).require("partner:partner"))
+        .route("/:id", get(get_partner).require("partner:partner:read"))
+        .route("/:id/contacts", post(create_contact).get(list_contacts).require("partner:partner"))
+        .route("/:id/agreements", post(create_agreement).get(list_agreements).require("partner:partner"))
+        .route("/:id/deals", post(create_deal).get(list_deals).require("partner:partner"))
+        .route("/deals/:id/register", post(register_deal).require("partner:deals:write"))
+        .route("/:id/commissions", post(create_commission).get(list_commissions).require("partner:partner"))
+        .route("/:id/performance", get(get_performance).require("partner:partner:read"))
 }
 
 #[derive(Serialize)]
@@ -6,6 +6,7 @@ use uuid::Uuid;
 
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_payment_terms::{PaymentTerm, PaymentTermService};
 
 #[derive(Deserialize)]
@@ -211,15 +212,16 @@ pub async fn calculate_payment_term(
 
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/", axum::routing::get(list_payment_terms).post(create_payment_term))
-        .route("/default", axum::routing::get(get_default_payment_term))
-        .route("/code/:code", axum::routing::get(get_payment_term_by_code))
+        .route("/", axum::routing::get(list_payment_terms).post(create_payment_term).require("payment-terms:payment-terms"))
+        .route("/default", axum::routing::get(get_default_payment_term).require("payment-terms:default:read"))
+        .route("/code/:code", axum::routing::get(get_payment_term_by_code).require("payment-terms:code:read"))
         .route(
             "/:id",
             axum::routing::get(get_payment_term)
                 .put(update_payment_term)
-                .delete(delete_payment_term),
+                .delete(delete_payment_term)
+                    .require("payment-terms:payment-terms"),
         )
-        .route("/:id/set-default", axum::routing::post(set_default_payment_term))
-        .route("/:id/calculate", axum::routing::post(calculate_payment_term))
+        .route("/:id/set-default", axum::routing::post(set_default_payment_term).require("payment-terms:payment-terms:write"))
+        .route("/:id/calculate", axum::routing::post(calculate_payment_term).require("payment-terms:payment-terms:write"))
 }
@@ -10,26 +10,31 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::db::AppState;
+use crate::error::{ApiError, ApiResult};
 use crate::handlers::auth::AuthUser;
-use erp_payments::{CreatePaymentRequest, ProcessPaymentRequest, CreateRefundRequest, PaymentMethod};
+use crate::middleware::RequirePermission;
+use erp_payments::{CardDetails, CreatePaymentRequest, ProcessPaymentRequest, ProcessedPayment, CreateRefundRequest, PaymentMethod};
 use erp_payments::{CreatePaymentIntentRequest, CreateCheckoutSessionRequest};
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/gateways", get(list_gateways).post(create_gateway))
-        .route("/payments", post(create_payment))
-        .route("/payments/:id", get(get_payment))
-        .route("/payments/customer/:customer_id", get(list_customer_payments))
-        .route("/payments/:id/refund", post(refund_payment))
-        .route("/process", post(process_payment))
-        .route("/stripe/intents", post(create_stripe_intent))
-        .route("/stripe/intents/:id", get(get_stripe_intent))
-        .route("/stripe/intents/:id/cancel", post(cancel_stripe_intent))
-        .route("/stripe/checkout", post(create_stripe_checkout))
-        .route("/stripe/checkout/:id", get(get_stripe_checkout))
-        .route("/stripe/refund", post(create_stripe_refund))
-        .route("/stripe/config", get(get_stripe_config))
-        .route("/stripe/webhook", post(stripe_webhook))
+        .route("/gateways", get(list_gateways).post(create_gateway).require("payments:gateways"))
+        .route("/gateways/:id/tokenize", post(tokenize_card).require("payments:gateways:write"))
+        .route("/payments", post(create_payment).require("payments:payments:write"))
+        .route("/payments/:id", get(get_payment).require("payments:payments:read"))
+        .route("/payments/customer/:customer_id", get(list_customer_payments).require("payments:payments:read"))
+        .route("/payments/:id/refund", post(refund_payment).require("payments:payments:write"))
+        .route("/payments/:id/capture", post(capture_payment).require("payments:payments:write"))
+        .route("/payments/:id/void", post(void_payment).require("payments:payments:void"))
+        .route("/process", post(process_payment).require("payments:process:write"))
+        .route("/stripe/intents", post(create_stripe_intent).require("payments:stripe:write"))
+        .route("/stripe/intents/:id", get(get_stripe_intent).require("payments:stripe:read"))
+        .route("/stripe/intents/:id/cancel", post(cancel_stripe_intent).require("payments:stripe:write"))
+        .route("/stripe/checkout", post(create_stripe_checkout).require("payments:stripe:write"))
+        .route("/stripe/checkout/:id", get(get_stripe_checkout).require("payments:stripe:read"))
+        .route("/stripe/refund", post(create_stripe_refund).require("payments:stripe:write"))
+        .route("/stripe/config", get(get_stripe_config).require("payments:stripe:read"))
+        .route("/stripe/webhook", post(stripe_webhook).require("payments:stripe:write"))
 }
 
 #[derive(Deserialize)]
@@ -39,6 +44,8 @@ pub struct CreateGatewayBody {
     pub gateway_type: String,
     #[serde(default)]
     pub supported_methods: Vec<String>,
+    /// Signs the gateway's webhooks. Without one, webhooks from the gateway are refused.
+    pub webhook_secret: Option<String>,
 }
 
 async fn create_gateway(
@@ -46,7 +53,7 @@ async fn create_gateway(
     axum::Extension(_auth_user): axum::Extension<AuthUser>,
     Json(body): Json<CreateGatewayBody>,
 ) -> Json<serde_json::Value> {
-    match state.gateway_svc.create(body.code, body.name, body.gateway_type, body.supported_methods).await {
+    match state.gateway_svc.create(body.code, body.name, body.gateway_type, body.supported_methods, body.webhook_secret).await {
         Ok(gateway) => Json(json!({
             "id": gateway.base.id,
             "code": gateway.code,
@@ -66,7 +73,8 @@ async fn list_gateways(State(state): State<AppState>) -> Json<serde_json::Value>
                 "code": g.code,
                 "name": g.name,
                 "gateway_type": g.gateway_type,
-                "is_live": g.is_live
+                "is_live": g.is_live,
+                "is_default": g.is_default
             })).collect::<Vec<_>>()
         })),
         Err(e) => Json(json!({ "error": e.to_string() })),
@@ -194,7 +202,16 @@ async fn refund_payment(
         amount: body.amount,
         reason: body.reason,
     };
-    match state.payment_svc.refund(req, Some(user.user_id())).await {
+    let through_gateway = match state.payment_svc.get(id).await {
+        Ok(payment) => payment.is_some_and(|p| p.gateway_transaction_id.is_some()),
+        Err(e) => return Json(json!({ "error": e.to_string() })),
+    };
+    let refund = if through_gateway {
+        state.gateway_svc.refund_payment(req, Some(user.user_id())).await
+    } else {
+        state.payment_svc.refund(req, Some(user.user_id())).await
+    };
+    match refund {
         Ok(refund) => Json(json!({
             "id": refund.base.id,
             "refund_number": refund.refund_number,
@@ -216,6 +233,21 @@ pub struct ProcessPaymentBody {
     pub invoice_id: Option<Uuid>,
     pub description: Option<String>,
     pub metadata: Option<String>,
+    #[serde(default)]
+    pub authorize_only: bool,
+}
+
+fn processed_json(processed: ProcessedPayment) -> Json<serde_json::Value> {
+    Json(json!({
+        "id": processed.payment.base.id,
+        "payment_number": processed.payment.payment_number,
+        "amount": processed.payment.amount,
+        "status": processed.payment.status,
+        "processing_fee": processed.payment.processing_fee,
+        "gateway_transaction_id": processed.payment.gateway_transaction_id,
+        "decline_code": processed.gateway.decline_code,
+        "next_action_url": processed.gateway.next_action_url
+    }))
 }
 
 async fn process_payment(
@@ -235,19 +267,67 @@ async fn process_payment(
         invoice_id: body.invoice_id,
         description: body.description,
         metadata: body.metadata,
+        capture: !body.authorize_only,
     };
     match state.gateway_svc.process_payment(req).await {
-        Ok(payment) => Json(json!({
-            "id": payment.base.id,
-            "payment_number": payment.payment_number,
-            "amount": payment.amount,
-            "status": payment.status,
-            "processing_fee": payment.processing_fee
-        })),
+        Ok(processed) => processed_json(processed),
+        Err(e) => Json(json!({ "error": e.to_string() })),
+    }
+}
+
+async fn tokenize_card(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(card): Json<CardDetails>,
+) -> Json<serde_json::Value> {
+    match state.gateway_svc.tokenize(id, card).await {
+        Ok(token) => Json(json!(token)),
         Err(e) => Json(json!({ "error": e.to_string() })),
     }
 }
 
+#[derive(Deserialize)]
+pub struct CapturePaymentBody {
+    pub amount: Option<i64>,
+}
+
+async fn capture_payment(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    body: Option<Json<CapturePaymentBody>>,
+) -> Json<serde_json::Value> {
+    let amount = body.and_then(|Json(body)| body.amount);
+    match state.gateway_svc.capture_payment(id, amount).await {
+        Ok(processed) => processed_json(processed),
+        Err(e) => Json(json!({ "error": e.to_string() })),
+    }
+}
+
+async fn void_payment(State(state): State<AppState>, Path(id): Path<Uuid>) -> Json<serde_json::Value> {
+    match state.gateway_svc.void_payment(id).await {
+        Ok(processed) => processed_json(processed),
+        Err(e) => Json(json!({ "error": e.to_string() })),
+    }
+}
+
+/// Public: authenticated by the gateway's signature rather than a user token.
+pub async fn gateway_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Json<serde_json::Value>> {
+    let header = state.gateway_svc.signature_header(id).await?;
+    let signature = headers.get(header).and_then(|v| v.to_str().ok())
+        .ok_or_else(|| ApiError(erp_core::Error::validation(format!("Missing {} header", header))))?;
+    let payment = state.gateway_svc.handle_webhook(id, &body, signature).await?;
+    Ok(Json(json!({
+        "received": true,
+        "payment_id": payment.as_ref().map(|p| p.base.id),
+        "status": payment.map(|p| p.status)
+    })))
+}
+
 #[derive(Deserialize)]
 pub struct CreateStripeIntentBody {
     pub customer_id: Uuid,
@@ -2,16 +2,17 @@ use axum::{extract::State, routing::{get, post}, Json, Router};
 use serde::Serialize;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/cards", post(issue_card).get(list_cards))
-        .route("/cards/:id", get(get_card))
-        .route("/cards/:id/transactions", post(record_transaction).get(list_transactions))
-        .route("/cards/:id/statements", post(create_statement).get(list_statements))
-        .route("/virtual-cards", post(create_virtual_card))
-        .route("/policies", post(create_policy).get(list_policies))
-        .route("/disputes", post(file_dispute).get(list_disputes))
+        .route("/cards", post(issue_card).get(list_cards).require("pcard:cards"))
+        .route("/cards/:id", get(get_card).require("pcard:cards:read"))
+        .route("/cards/:id/transactions", post(record_transaction).get(list_transactions).require("pcard:cards"))
+        .route("/cards/:id/statements", post(create_statement).get(list_statements).require("pcard:cards"))
+        .route("/virtual-cards", post(create_virtual_card).require("pcard:virtual-cards:write"))
+        .route("/policies", post(create_policy).get(list_policies).require("pcard:policies"))
+        .route("/disputes", post(file_dispute).get(list_disputes).require("pcard:disputes"))
 }
 
 #[derive(Serialize)]
@@ -8,6 +8,7 @@ use uuid::Uuid;
 use erp_core::Pagination;
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_portals::{PortalUserService, PortalOrderService, PortalPaymentService};
 use erp_portals::{PortalUser, PortalType, PaymentMethodType, PortalOrderLine};
 
@@ -65,6 +66,7 @@ pub struct PaymentRequest {
     pub invoice_ids: Vec<Uuid>,
     pub amount_cents: i64,
     pub payment_method: String,
+    pub payment_token: Option<String>,
 }
 
 impl From<PortalUser> for UserResponse {
@@ -190,7 +192,12 @@ pub async fn process_payment(
     State(state): State<AppState>,
     Json(req): Json<PaymentRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    let service = PortalPaymentService::new();
+    let gateway = state.gateway_svc.default_gateway().await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let service = match gateway {
+        Some(gateway) => PortalPaymentService::new().with_gateway(gateway),
+        None => PortalPaymentService::new(),
+    };
     let user_id = Uuid::nil();
     
     let method = match req.payment_method.as_str() {
@@ -202,14 +209,16 @@ pub async fn process_payment(
         _ => PaymentMethodType::Other,
     };
     
-    let payment = service.process_payment(&state.pool, user_id, req.invoice_ids, req.amount_cents, method).await
+    let payment = service.process_payment(&state.pool, user_id, req.invoice_ids, req.amount_cents, method, req.payment_token).await
         .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
     
     Ok(Json(serde_json::json!({
         "id": payment.base.id,
         "payment_reference": payment.payment_reference,
         "status": format!("{:?}", payment.status),
-        "amount_cents": payment.amount_cents
+        "amount_cents": payment.amount_cents,
+        "provider_transaction_id": payment.provider_transaction_id,
+        "failure_reason": payment.failure_reason
     })))
 }
 
@@ -226,10 +235,10 @@ pub async fn submit_supplier_quote(
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/register", axum::routing::post(register))
-        .route("/login", axum::routing::post(login))
-        .route("/orders", axum::routing::get(list_orders).post(create_order))
-        .route("/orders/:id/submit", axum::routing::post(submit_order))
-        .route("/payments", axum::routing::post(process_payment))
-        .route("/supplier/quotes", axum::routing::post(submit_supplier_quote))
+        .route("/register", axum::routing::post(register).require("portals:register:write"))
+        .route("/login", axum::routing::post(login).require("portals:login:write"))
+        .route("/orders", axum::routing::get(list_orders).post(create_order).require("portals:orders"))
+        .route("/orders/:id/submit", axum::routing::post(submit_order).require("portals:orders:write"))
+        .route("/payments", axum::routing::post(process_payment).require("portals:payments:write"))
+        .route("/supplier/quotes", axum::routing::post(submit_supplier_quote).require("portals:supplier:write"))
 }
@@ -4,6 +4,7 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::{Pagination, BaseEntity, Money, Currency};
 use erp_pos::{
     POSStore, POSStatus, POSTransaction, POSTransactionLine, POSTransactionPayment,
@@ -166,7 +167,10 @@ pub async fn create_transaction(
     State(state): State<AppState>,
     Json(req): Json<CreateTransactionRequest>,
 ) -> ApiResult<Json<POSTransactionResponse>> {
-    let svc = POSTransactionService::new();
+    let svc = match state.gateway_svc.default_gateway().await? {
+        Some(gateway) => POSTransactionService::new().with_gateway(gateway),
+        None => POSTransactionService::new(),
+    };
     
     let lines: Vec<POSTransactionLine> = req.lines.into_iter().enumerate().map(|(i, l)| {
         let line_total = l.quantity * l.unit_price;
@@ -316,11 +320,11 @@ pub async fn redeem_gift_card(
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/stores", get(list_stores).post(create_store))
-        .route("/stores/:id", get(get_store))
-        .route("/transactions", get(list_transactions).post(create_transaction))
-        .route("/transactions/:id/void", post(void_transaction))
-        .route("/gift-cards", post(create_gift_card))
-        .route("/gift-cards/:number", get(get_gift_card))
-        .route("/gift-cards/:number/redeem", post(redeem_gift_card))
+        .route("/stores", get(list_stores).post(create_store).require("pos:stores"))
+        .route("/stores/:id", get(get_store).require("pos:stores:read"))
+        .route("/transactions", get(list_transactions).post(create_transaction).require("pos:transactions"))
+        .route("/transactions/:id/void", post(void_transaction).require("pos:transactions:void"))
+        .route("/gift-cards", post(create_gift_card).require("pos:gift-cards:write"))
+        .route("/gift-cards/:number", get(get_gift_card).require("pos:gift-cards:read"))
+        .route("/gift-cards/:number/redeem", post(redeem_gift_card).require("pos:gift-cards:write"))
 }
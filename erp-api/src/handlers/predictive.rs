@@ -2,19 +2,20 @@ use axum::{extract::State, routing::{get, post}, Json, Router};
 use serde::Serialize;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/sensors", post(register_sensor).get(list_sensors))
-        .route("/sensors/:id", get(get_sensor))
-        .route("/sensors/:id/readings", post(record_reading).get(list_readings))
-        .route("/models", post(create_model).get(list_models))
-        .route("/models/:id", get(get_model))
-        .route("/assets/:id/health", get(get_health_score).post(calculate_health_score))
-        .route("/predictions", post(predict_failure).get(list_predictions))
-        .route("/predictions/:id", get(get_prediction))
-        .route("/schedules", post(schedule_maintenance).get(list_schedules))
-        .route("/anomalies", post(detect_anomaly).get(list_anomalies))
+        .route("/sensors", post(register_sensor).get(list_sensors).require("predictive:sensors"))
+        .route("/sensors/:id", get(get_sensor).require("predictive:sensors:read"))
+        .route("/sensors/:id/readings", post(record_reading).get(list_readings).require("predictive:sensors"))
+        .route("/models", post(create_model).get(list_models).require("predictive:models"))
+        .route("/models/:id", get(get_model).require("predictive:models:read"))
+        .route("/assets/:id/health", get(get_health_score).post(calculate_health_score).require("predictive:assets"))
+        .route("/predictions", post(predict_failure).get(list_predictions).require("predictive:predictions"))
+        .route("/predictions/:id", get(get_prediction).require("predictive:predictions:read"))
+        .route("/schedules", post(schedule_maintenance).get(list_schedules).require("predictive:schedules"))
+        .route("/anomalies", post(detect_anomaly).get(list_anomalies).require("predictive:anomalies"))
 }
 
 #[derive(Serialize)]
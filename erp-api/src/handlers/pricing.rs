@@ -6,6 +6,7 @@ use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::BaseEntity;
 use erp_pricing::{
     PricingService, Discount, Promotion,
@@ -310,12 +311,12 @@ pub async fn create_price_tier(
 
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/price-books", axum::routing::get(list_price_books).post(create_price_book))
-        .route("/prices", axum::routing::post(set_product_price))
-        .route("/prices/calculate", axum::routing::post(calculate_price))
-        .route("/discounts", axum::routing::get(list_discounts).post(create_discount))
-        .route("/discounts/validate", axum::routing::post(validate_discount))
-        .route("/coupons", axum::routing::post(create_coupon))
-        .route("/promotions", axum::routing::get(list_promotions).post(create_promotion))
-        .route("/price-tiers", axum::routing::post(create_price_tier))
+        .route("/price-books", axum::routing::get(list_price_books).post(create_price_book).require("pricing:price-books"))
+        .route("/prices", axum::routing::post(set_product_price).require("pricing:prices:write"))
+        .route("/prices/calculate", axum::routing::post(calculate_price).require("pricing:prices:write"))
+        .route("/discounts", axum::routing::get(list_discounts).post(create_discount).require("pricing:discounts"))
+        .route("/discounts/validate", axum::routing::post(validate_discount).require("pricing:discounts:write"))
+        .route("/coupons", axum::routing::post(create_coupon).require("pricing:coupons:write"))
+        .route("/promotions", axum::routing::get(list_promotions).post(create_promotion).require("pricing:promotions"))
+        .route("/price-tiers", axum::routing::post(create_price_tier).require("pricing:price-tiers:write"))
 }
@@ -8,6 +8,7 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_processmining::{ProcessMiningService, ProcessCategory, ProcessStatus, ImportEventsRequest, ProcessEventImport, SimulationScenario};
 
 #[derive(Deserialize)]
@@ -24,16 +25,16 @@ pub struct DashboardQuery {
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/processes", axum::routing::get(list_processes).post(create_process))
-        .route("/processes/:id", axum::routing::get(get_process).delete(delete_process))
-        .route("/processes/:id/discovery", axum::routing::post(run_discovery))
-        .route("/processes/:id/bottlenecks", axum::routing::post(analyze_bottlenecks))
-        .route("/processes/:id/conformance", axum::routing::post(check_conformance))
-        .route("/processes/:id/dashboard", axum::routing::get(get_dashboard))
-        .route("/events/import", axum::routing::post(import_events))
-        .route("/simulations", axum::routing::post(create_simulation))
-        .route("/simulations/:id", axum::routing::get(get_simulation))
-        .route("/simulations/:id/run", axum::routing::post(run_simulation))
+        .route("/processes", axum::routing::get(list_processes).post(create_process).require("processmining:processes"))
+        .route("/processes/:id", axum::routing::get(get_process).delete(delete_process).require("processmining:processes"))
+        .route("/processes/:id/discovery", axum::routing::post(run_discovery).require("processmining:processes:write"))
+        .route("/processes/:id/bottlenecks", axum::routing::post(analyze_bottlenecks).require("processmining:processes:write"))
+        .route("/processes/:id/conformance", axum::routing::post(check_conformance).require("processmining:processes:write"))
+        .route("/processes/:id/dashboard", axum::routing::get(get_dashboard).require("processmining:processes:read"))
+        .route("/events/import", axum::routing::post(import_events).require("processmining:events:write"))
+        .route("/simulations", axum::routing::post(create_simulation).require("processmining:simulations:write"))
+        .route("/simulations/:id", axum::routing::get(get_simulation).require("processmining:simulations:read"))
+        .route("/simulations/:id/run", axum::routing::post(run_simulation).require("processmining:simulations:write"))
 }
 
 async fn create_process(
@@ -4,7 +4,9 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::handlers::auth::AuthUser;
 use erp_core::{BaseEntity, Status, Pagination, Money, Currency, ContactInfo, Address};
+use erp_core::events::publish_change;
 use erp_purchasing::{Vendor, PurchaseOrder, PurchaseOrderLine, VendorService, PurchaseOrderService};
 
 #[derive(Deserialize)] pub struct CreateVendorRequest { pub code: String, pub name: String, pub email: Option<String>, pub phone: Option<String>, pub payment_terms: Option<u32> }
@@ -24,7 +26,7 @@ pub async fn get_vendor(State(state): State<AppState>, Path(id): Path<Uuid>) ->
     Ok(Json(VendorResponse::from(VendorService::new().get(&state.pool, id).await?)))
 }
 
-pub async fn create_vendor(State(state): State<AppState>, Json(req): Json<CreateVendorRequest>) -> ApiResult<Json<VendorResponse>> {
+pub async fn create_vendor(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Json(req): Json<CreateVendorRequest>) -> ApiResult<Json<VendorResponse>> {
     let svc = VendorService::new();
     let v = Vendor {
         base: BaseEntity::new(), code: req.code, name: req.name,
@@ -32,7 +34,9 @@ pub async fn create_vendor(State(state): State<AppState>, Json(req): Json<Create
         address: Address { street: String::new(), city: String::new(), state: None, postal_code: String::new(), country: String::new() },
         payment_terms: req.payment_terms.unwrap_or(30), status: Status::Active,
     };
-    Ok(Json(VendorResponse::from(svc.create(&state.pool, v).await?)))
+    let created = svc.create(&state.pool, v).await?;
+    publish_change("vendor", created.base.id, "created", &user.user_id);
+    Ok(Json(VendorResponse::from(created)))
 }
 
 #[derive(Deserialize)] pub struct CreatePORequest { pub vendor_id: Uuid, pub lines: Vec<POLineRequest> }
@@ -53,7 +57,7 @@ pub async fn get_order(State(state): State<AppState>, Path(id): Path<Uuid>) -> A
     Ok(Json(POResponse::from(PurchaseOrderService::new().get(&state.pool, id).await?)))
 }
 
-pub async fn create_order(State(state): State<AppState>, Json(req): Json<CreatePORequest>) -> ApiResult<Json<POResponse>> {
+pub async fn create_order(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Json(req): Json<CreatePORequest>) -> ApiResult<Json<POResponse>> {
     let svc = PurchaseOrderService::new();
     let po = PurchaseOrder {
         base: BaseEntity::new(), po_number: String::new(), vendor_id: req.vendor_id, order_date: Utc::now(), expected_date: None,
@@ -63,10 +67,13 @@ pub async fn create_order(State(state): State<AppState>, Json(req): Json<CreateP
         }).collect(),
         subtotal: Money::zero(Currency::USD), tax_amount: Money::zero(Currency::USD), total: Money::zero(Currency::USD), status: Status::Draft,
     };
-    Ok(Json(POResponse::from(svc.create(&state.pool, po).await?)))
+    let created = svc.create(&state.pool, po).await?;
+    publish_change("purchase_order", created.base.id, "created", &user.user_id);
+    Ok(Json(POResponse::from(created)))
 }
 
-pub async fn approve_order(State(state): State<AppState>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
+pub async fn approve_order(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
     PurchaseOrderService::new().approve(&state.pool, id).await?;
+    publish_change("purchase_order", id, "approved", &user.user_id);
     Ok(Json(serde_json::json!({ "status": "approved" })))
 }
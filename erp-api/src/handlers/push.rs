@@ -7,14 +7,15 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::db::AppState;
 use crate::ApiResult;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/devices", post(register_device).get(get_user_devices))
-        .route("/send", post(send_notification))
-        .route("/broadcast", post(send_broadcast))
-        .route("/templates", post(create_template).get(list_templates))
-        .route("/preferences", post(set_preference))
+        .route("/devices", post(register_device).get(get_user_devices).require("push:devices"))
+        .route("/send", post(send_notification).require("push:send:write"))
+        .route("/broadcast", post(send_broadcast).require("push:broadcast:write"))
+        .route("/templates", post(create_template).get(list_templates).require("push:templates"))
+        .route("/preferences", post(set_preference).require("push:preferences:write"))
 }
 
 #[derive(Deserialize)]
@@ -8,6 +8,7 @@ use uuid::Uuid;
 use crate::error::ApiResult;
 use crate::db::AppState;
 use crate::handlers::auth::AuthUser;
+use crate::middleware::RequirePermission;
 use erp_quality::{
     QualityService,
     QualityInspection, InspectionItem, QualityInspectionWithItems, QualityAnalytics,
@@ -257,17 +258,17 @@ fn parse_ncr_status(s: &str) -> Option<NCRStatus> {
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/inspections", axum::routing::get(list_inspections).post(create_inspection))
-        .route("/inspections/analytics", axum::routing::get(get_analytics))
-        .route("/inspections/:id", axum::routing::get(get_inspection).delete(delete_inspection))
-        .route("/inspections/:id/start", axum::routing::post(start_inspection))
-        .route("/inspections/:id/complete", axum::routing::post(complete_inspection))
-        .route("/inspections/:id/cancel", axum::routing::post(cancel_inspection))
-        .route("/inspections/:id/items", axum::routing::get(list_items).post(add_item))
-        .route("/inspections/:id/items/:item_id", axum::routing::put(update_item))
-        .route("/ncrs", axum::routing::get(list_ncrs).post(create_ncr))
-        .route("/ncrs/:id", axum::routing::get(get_ncr).put(update_ncr).delete(delete_ncr))
-        .route("/ncrs/:id/close", axum::routing::post(close_ncr))
+        .route("/inspections", axum::routing::get(list_inspections).post(create_inspection).require("quality:inspections"))
+        .route("/inspections/analytics", axum::routing::get(get_analytics).require("quality:inspections:read"))
+        .route("/inspections/:id", axum::routing::get(get_inspection).delete(delete_inspection).require("quality:inspections"))
+        .route("/inspections/:id/start", axum::routing::post(start_inspection).require("quality:inspections:write"))
+        .route("/inspections/:id/complete", axum::routing::post(complete_inspection).require("quality:inspections:write"))
+        .route("/inspections/:id/cancel", axum::routing::post(cancel_inspection).require("quality:inspections:write"))
+        .route("/inspections/:id/items", axum::routing::get(list_items).post(add_item).require("quality:inspections"))
+        .route("/inspections/:id/items/:item_id", axum::routing::put(update_item).require("quality:inspections:write"))
+        .route("/ncrs", axum::routing::get(list_ncrs).post(create_ncr).require("quality:ncrs"))
+        .route("/ncrs/:id", axum::routing::get(get_ncr).put(update_ncr).delete(delete_ncr).require("quality:ncrs"))
+        .route("/ncrs/:id/close", axum::routing::post(close_ncr).require("quality:ncrs:write"))
 }
 
 pub async fn create_inspection(
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     routing::{delete, get, post},
     Json, Router,
 };
@@ -8,7 +8,8 @@ use serde::{Deserialize, Serialize};
 use sqlx::Row;
 use crate::db::AppState;
 use crate::error::{ApiError, ApiResult};
-use erp_auth::get_default_permissions;
+use crate::middleware::RequirePermission;
+use erp_auth::{get_default_permissions, Decision};
 
 fn parse_uuid(s: &str) -> ApiResult<Uuid> {
     Uuid::parse_str(s).map_err(|e| ApiError(erp_core::Error::Validation(format!("Invalid UUID: {}", e))))
@@ -87,6 +88,7 @@ pub async fn delete_role(
     sqlx::query("UPDATE custom_roles SET is_active = 0 WHERE id = ? AND is_system = 0")
         .bind(id.to_string())
         .execute(&state.pool).await?;
+    state.authz.invalidate_all();
     Ok(Json(serde_json::json!({ "status": "deleted" })))
 }
 
@@ -177,6 +179,7 @@ pub async fn assign_permission(
     .bind(req.permission_id.to_string())
     .bind(&now)
     .execute(&state.pool).await?;
+    state.authz.invalidate_all();
     
     Ok(Json(serde_json::json!({ "status": "assigned" })))
 }
@@ -189,6 +192,7 @@ pub async fn revoke_permission(
         .bind(role_id.to_string())
         .bind(permission_id.to_string())
         .execute(&state.pool).await?;
+    state.authz.invalidate_all();
     
     Ok(Json(serde_json::json!({ "status": "revoked" })))
 }
@@ -235,6 +239,9 @@ pub async fn assign_role_to_user(
     Path(role_id): Path<Uuid>,
     Json(req): Json<AssignRoleRequest>,
 ) -> ApiResult<Json<serde_json::Value>> {
+    if let Some(expires_at) = &req.expires_at {
+        erp_core::parse_datetime(expires_at, "expires_at")?;
+    }
     let id = Uuid::new_v4();
     let now = chrono::Utc::now().to_rfc3339();
     
@@ -247,6 +254,7 @@ pub async fn assign_role_to_user(
     .bind(&now)
     .bind(&req.expires_at)
     .execute(&state.pool).await?;
+    state.authz.invalidate_user(&req.user_id.to_string());
     
     Ok(Json(serde_json::json!({ "status": "assigned" })))
 }
@@ -259,6 +267,7 @@ pub async fn revoke_role_from_user(
         .bind(user_id.to_string())
         .bind(role_id.to_string())
         .execute(&state.pool).await?;
+    state.authz.invalidate_user(&user_id.to_string());
     
     Ok(Json(serde_json::json!({ "status": "revoked" })))
 }
@@ -304,22 +313,26 @@ pub async fn get_user_effective_permissions(
     State(state): State<AppState>,
     Path(user_id): Path<Uuid>,
 ) -> ApiResult<Json<UserEffectivePermissionsResponse>> {
-    let rows = sqlx::query(
-        r#"SELECT DISTINCT p.code 
-           FROM user_role_assignments ura 
-           JOIN role_permissions rp ON ura.role_id = rp.role_id 
-           JOIN permissions p ON rp.permission_id = p.id 
-           WHERE ura.user_id = ? AND (ura.expires_at IS NULL OR ura.expires_at > datetime('now'))"#
-    )
-    .bind(user_id.to_string())
-    .fetch_all(&state.pool).await?;
-    
+    let effective = state.authz.permissions(&user_id.to_string()).await?;
     Ok(Json(UserEffectivePermissionsResponse {
         user_id,
-        permissions: rows.into_iter().map(|r| r.get::<String, _>("code")).collect(),
+        permissions: effective.permissions(),
     }))
 }
 
+#[derive(Deserialize)]
+pub struct ExplainQuery {
+    pub permission: String,
+}
+
+pub async fn explain_user_permission(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<ExplainQuery>,
+) -> ApiResult<Json<Decision>> {
+    Ok(Json(state.authz.explain(&user_id.to_string(), &query.permission).await?))
+}
+
 #[derive(Deserialize)]
 pub struct SetDataPermissionRequest {
     pub resource: String,
@@ -384,15 +397,16 @@ pub async fn set_field_permission(
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/roles", get(list_roles).post(create_role))
-        .route("/roles/:id", delete(delete_role))
-        .route("/permissions", get(list_permissions))
-        .route("/roles/:role_id/permissions", get(list_role_permissions).post(assign_permission))
-        .route("/roles/:role_id/permissions/:permission_id", delete(revoke_permission))
-        .route("/roles/:role_id/users", post(assign_role_to_user))
-        .route("/users/:user_id/roles", get(list_user_roles))
-        .route("/users/:user_id/roles/:role_id", delete(revoke_role_from_user))
-        .route("/users/:user_id/effective-permissions", get(get_user_effective_permissions))
-        .route("/roles/:role_id/data-permissions", post(set_data_permission))
-        .route("/roles/:role_id/field-permissions", post(set_field_permission))
+        .route("/roles", get(list_roles).post(create_role).require("admin:roles"))
+        .route("/roles/:id", delete(delete_role).require("admin:roles:delete"))
+        .route("/permissions", get(list_permissions).require("admin:permissions:read"))
+        .route("/roles/:role_id/permissions", get(list_role_permissions).post(assign_permission).require("admin:roles"))
+        .route("/roles/:role_id/permissions/:permission_id", delete(revoke_permission).require("admin:roles:delete"))
+        .route("/roles/:role_id/users", post(assign_role_to_user).require("admin:roles:write"))
+        .route("/users/:user_id/roles", get(list_user_roles).require("admin:users:read"))
+        .route("/users/:user_id/roles/:role_id", delete(revoke_role_from_user).require("admin:users:delete"))
+        .route("/users/:user_id/effective-permissions", get(get_user_effective_permissions).require("admin:users:read"))
+        .route("/users/:user_id/explain", get(explain_user_permission).require("admin:users:read"))
+        .route("/roles/:role_id/data-permissions", post(set_data_permission).require("admin:roles:write"))
+        .route("/roles/:role_id/field-permissions", post(set_field_permission).require("admin:roles:write"))
 }
@@ -1,17 +1,76 @@
-use axum::{extract::{Query, State}, Json, routing::{get, post}};
+use axum::{extract::{Path, Query, State}, response::Response, Extension, Json, routing::{get, post}};
+use std::sync::Arc;
+use async_trait::async_trait;
 use uuid::Uuid;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::handlers::auth::AuthUser;
+use crate::handlers::files;
+use crate::policy::AccessPolicy;
+use crate::middleware::RequirePermission;
+use erp_auth::Authorizer;
 use erp_core::{Pagination, BaseEntity, Status};
+use erp_reports::semantic::{Entity, ENTITIES};
 use erp_reports::{
-    ReportDefinition, ReportCategory, ReportFormat,
+    ReportDefinition, ReportCategory, ReportFormat, ReportParameter, ReportQuery,
     ReportSchedule, ScheduleFrequency, DeliveryMethod,
-    ReportExecution, ReportDashboard,
+    ReportExecution, ReportDashboard, DataAccess, AccessResolver, RunReport,
     ReportDefinitionService, ReportScheduleService, ReportExecutionService,
 };
 
+/// Lets a user see and download report output run by anyone.
+const MANAGE_EXECUTIONS: &str = "reports:executions:manage";
+
+/// What `user_id` may report on: entities their permissions allow, limited to the rows and
+/// fields their data and field permissions allow.
+async fn data_access(authz: &Authorizer, policy: &AccessPolicy, user_id: &str) -> ApiResult<DataAccess> {
+    let permissions = authz.permissions(user_id).await?;
+    let mut access = DataAccess::unrestricted();
+    for entity in &ENTITIES {
+        if !permissions.allows(entity.permission) {
+            access = access.deny(entity.name);
+            continue;
+        }
+        access = access.scope(entity.name, policy.row_scope(entity.name));
+        for field in policy.hidden_fields(entity.name) {
+            access = access.hide(entity.name, field);
+        }
+    }
+    Ok(access)
+}
+
+/// Resolves access for reports run without a request, such as scheduled ones.
+pub struct PolicyAccessResolver {
+    pub authz: Arc<Authorizer>,
+}
+
+#[async_trait]
+impl AccessResolver for PolicyAccessResolver {
+    async fn access_for(&self, pool: &SqlitePool, user_id: Uuid) -> erp_core::Result<DataAccess> {
+        let user_id = user_id.to_string();
+        let policy = AccessPolicy::for_user(pool, &user_id).await.map_err(|e| e.0)?;
+        data_access(&self.authz, &policy, &user_id).await.map_err(|e| e.0)
+    }
+}
+
+fn parse_format(format: &str) -> ReportFormat {
+    match format {
+        "Excel" => ReportFormat::Excel,
+        "CSV" => ReportFormat::CSV,
+        "HTML" => ReportFormat::HTML,
+        "JSON" => ReportFormat::JSON,
+        "Word" => ReportFormat::Word,
+        _ => ReportFormat::PDF,
+    }
+}
+
+pub async fn list_entities() -> Json<&'static [Entity]> {
+    Json(&ENTITIES)
+}
+
 #[derive(Serialize)]
 pub struct ReportDefinitionResponse {
     pub id: Uuid,
@@ -19,6 +78,9 @@ pub struct ReportDefinitionResponse {
     pub code: String,
     pub category: String,
     pub status: String,
+    pub query: ReportQuery,
+    pub parameters: Vec<ReportParameter>,
+    pub columns: Vec<erp_reports::ReportColumn>,
 }
 
 impl From<ReportDefinition> for ReportDefinitionResponse {
@@ -29,6 +91,9 @@ impl From<ReportDefinition> for ReportDefinitionResponse {
             code: r.code,
             category: format!("{:?}", r.category),
             status: format!("{:?}", r.status),
+            query: r.query,
+            parameters: r.parameters,
+            columns: r.columns,
         }
     }
 }
@@ -39,8 +104,10 @@ pub struct CreateReportRequest {
     pub code: String,
     pub category: String,
     pub description: Option<String>,
-    pub data_source: String,
-    pub query_template: String,
+    pub query: ReportQuery,
+    #[serde(default)]
+    pub parameters: Vec<ReportParameter>,
+    pub default_format: Option<String>,
 }
 
 pub async fn list_reports(
@@ -58,6 +125,7 @@ pub async fn list_reports(
 
 pub async fn create_report(
     State(state): State<AppState>,
+    Extension(AuthUser(user)): Extension<AuthUser>,
     Json(req): Json<CreateReportRequest>,
 ) -> ApiResult<Json<ReportDefinitionResponse>> {
     let svc = ReportDefinitionService::new();
@@ -79,15 +147,15 @@ pub async fn create_report(
             _ => ReportCategory::Financial,
         },
         description: req.description,
-        data_source: req.data_source,
-        query_template: req.query_template,
-        parameters: vec![],
+        data_source: req.query.entity.clone(),
+        query: req.query,
+        parameters: req.parameters,
         columns: vec![],
-        default_format: ReportFormat::PDF,
-        allowed_formats: vec![ReportFormat::PDF, ReportFormat::Excel, ReportFormat::CSV],
+        default_format: req.default_format.as_deref().map(parse_format).unwrap_or(ReportFormat::PDF),
+        allowed_formats: vec![ReportFormat::PDF, ReportFormat::Excel, ReportFormat::CSV, ReportFormat::JSON],
         is_scheduled: false,
         status: Status::Active,
-        created_by: None,
+        created_by: Some(Uuid::parse_str(&user.user_id)?),
         version: 1,
     };
     Ok(Json(ReportDefinitionResponse::from(svc.create(&state.pool, report).await?)))
@@ -122,12 +190,14 @@ pub struct CreateScheduleRequest {
     pub name: String,
     pub frequency: String,
     pub output_format: String,
+    pub parameters: Option<serde_json::Map<String, serde_json::Value>>,
     pub recipients: Vec<String>,
     pub email_subject: Option<String>,
 }
 
 pub async fn create_schedule(
     State(state): State<AppState>,
+    Extension(AuthUser(user)): Extension<AuthUser>,
     Json(req): Json<CreateScheduleRequest>,
 ) -> ApiResult<Json<ReportScheduleResponse>> {
     let svc = ReportScheduleService::new();
@@ -150,15 +220,8 @@ pub async fn create_schedule(
         end_date: None,
         next_run_at: None,
         last_run_at: None,
-        parameters: "{}".to_string(),
-        output_format: match req.output_format.as_str() {
-            "Excel" => ReportFormat::Excel,
-            "CSV" => ReportFormat::CSV,
-            "HTML" => ReportFormat::HTML,
-            "JSON" => ReportFormat::JSON,
-            "Word" => ReportFormat::Word,
-            _ => ReportFormat::PDF,
-        },
+        parameters: serde_json::Value::Object(req.parameters.unwrap_or_default()).to_string(),
+        output_format: parse_format(&req.output_format),
         delivery_methods: vec![DeliveryMethod::Email],
         recipients: req.recipients,
         email_subject: req.email_subject,
@@ -169,7 +232,7 @@ pub async fn create_schedule(
         webhook_url: None,
         is_active: true,
         status: Status::Active,
-        created_by: None,
+        created_by: Some(Uuid::parse_str(&user.user_id)?),
     };
     Ok(Json(ReportScheduleResponse::from(svc.create(&state.pool, schedule).await?)))
 }
@@ -181,8 +244,13 @@ pub struct ReportExecutionResponse {
     pub format: String,
     pub status: String,
     pub row_count: i64,
-    pub file_path: Option<String>,
+    pub file_size_bytes: Option<i64>,
+    pub checksum: Option<String>,
+    pub duration_ms: Option<i64>,
     pub error_message: Option<String>,
+    pub executed_by: Option<Uuid>,
+    pub schedule_id: Option<Uuid>,
+    pub completed_at: Option<String>,
 }
 
 impl From<ReportExecution> for ReportExecutionResponse {
@@ -193,8 +261,13 @@ impl From<ReportExecution> for ReportExecutionResponse {
             format: format!("{:?}", e.format),
             status: format!("{:?}", e.status),
             row_count: e.row_count,
-            file_path: e.file_path,
+            file_size_bytes: e.file_size_bytes,
+            checksum: e.checksum,
+            duration_ms: e.duration_ms,
             error_message: e.error_message,
+            executed_by: e.executed_by,
+            schedule_id: e.schedule_id,
+            completed_at: e.completed_at.map(|d| d.to_rfc3339()),
         }
     }
 }
@@ -202,45 +275,94 @@ impl From<ReportExecution> for ReportExecutionResponse {
 #[derive(Deserialize)]
 pub struct RunReportRequest {
     pub report_definition_id: Uuid,
-    pub format: String,
-    pub parameters: Option<String>,
+    pub format: Option<String>,
+    pub parameters: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 pub async fn run_report(
     State(state): State<AppState>,
+    Extension(AuthUser(user)): Extension<AuthUser>,
     Json(req): Json<RunReportRequest>,
 ) -> ApiResult<Json<ReportExecutionResponse>> {
-    let svc = ReportExecutionService::new();
-    let format = match req.format.as_str() {
-        "Excel" => ReportFormat::Excel,
-        "CSV" => ReportFormat::CSV,
-        "HTML" => ReportFormat::HTML,
-        "JSON" => ReportFormat::JSON,
-        "Word" => ReportFormat::Word,
-        _ => ReportFormat::PDF,
-    };
-    
-    let execution = svc.start(
-        &state.pool,
-        req.report_definition_id,
-        None,
-        req.parameters.as_deref().unwrap_or("{}"),
-        format,
-    ).await?;
-    
-    let def_svc = ReportDefinitionService::new();
-    let report = def_svc.get(&state.pool, req.report_definition_id).await?;
-    
-    let (_csv, row_count) = erp_reports::ReportGeneratorService::generate_csv(
-        &state.pool,
-        &report.query_template,
-        &report.columns,
-    ).await?;
-    
-    let file_path = format!("/tmp/report_{}.csv", execution.base.id);
-    svc.complete(&state.pool, execution.base.id, &file_path, row_count).await?;
-    
-    Ok(Json(ReportExecutionResponse::from(svc.get(&state.pool, execution.base.id).await?)))
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
+    let access = data_access(&state.authz, &policy, &user.user_id).await?;
+    let execution = ReportExecutionService::new().run(&state.pool, &state.blobs, &access, RunReport {
+        report_definition_id: req.report_definition_id,
+        format: req.format.as_deref().map(parse_format),
+        parameters: req.parameters.unwrap_or_default(),
+        schedule_id: None,
+        executed_by: Some(Uuid::parse_str(&user.user_id)?),
+    }).await?;
+    Ok(Json(ReportExecutionResponse::from(execution)))
+}
+
+#[derive(Deserialize)]
+pub struct ListExecutionsQuery {
+    pub limit: Option<i64>,
+}
+
+pub async fn list_executions(
+    State(state): State<AppState>,
+    Extension(AuthUser(user)): Extension<AuthUser>,
+    Query(query): Query<ListExecutionsQuery>,
+) -> ApiResult<Json<Vec<ReportExecutionResponse>>> {
+    let user_id = Uuid::parse_str(&user.user_id)?;
+    let executions = ReportExecutionService::new()
+        .list_for_user(&state.pool, user_id, query.limit.unwrap_or(50).clamp(1, 200))
+        .await?;
+    Ok(Json(executions.into_iter().map(ReportExecutionResponse::from).collect()))
+}
+
+/// An execution the caller ran, or any execution for those allowed to manage them. Others'
+/// executions are reported as missing rather than forbidden.
+async fn visible_execution(state: &AppState, user_id: &str, id: Uuid) -> ApiResult<ReportExecution> {
+    let execution = ReportExecutionService::new().get(&state.pool, id).await?;
+    let own = execution.executed_by.map(|e| e.to_string()).as_deref() == Some(user_id);
+    if !own && !state.authz.permissions(user_id).await?.allows(MANAGE_EXECUTIONS) {
+        return Err(erp_core::Error::not_found("ReportExecution", &id.to_string()).into());
+    }
+    Ok(execution)
+}
+
+/// The stored output of a completed execution and the name to download it as.
+async fn execution_output(state: &AppState, execution: &ReportExecution) -> ApiResult<(String, String)> {
+    let checksum = execution.checksum.clone().ok_or_else(|| {
+        erp_core::Error::not_found("Report output", &execution.base.id.to_string())
+    })?;
+    let report = ReportDefinitionService::new().get(&state.pool, execution.report_definition_id).await?;
+    let timestamp = execution.completed_at.unwrap_or(execution.base.created_at).format("%Y%m%d-%H%M%S");
+    Ok((checksum, format!("{}-{}.{}", report.code, timestamp, execution.format.extension())))
+}
+
+pub async fn get_execution(
+    State(state): State<AppState>,
+    Extension(AuthUser(user)): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<ReportExecutionResponse>> {
+    let execution = visible_execution(&state, &user.user_id, id).await?;
+    Ok(Json(ReportExecutionResponse::from(execution)))
+}
+
+pub async fn download_execution(
+    State(state): State<AppState>,
+    Extension(AuthUser(user)): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Response> {
+    let execution = visible_execution(&state, &user.user_id, id).await?;
+    let (checksum, file_name) = execution_output(&state, &execution).await?;
+    let (blob, stream) = state.blobs.open(&state.pool, &checksum).await?;
+    Ok(files::blob_response(&blob, stream, &file_name))
+}
+
+pub async fn execution_download_url(
+    State(state): State<AppState>,
+    Extension(AuthUser(user)): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<files::DownloadUrlQuery>,
+) -> ApiResult<Json<files::DownloadUrlResponse>> {
+    let execution = visible_execution(&state, &user.user_id, id).await?;
+    let (checksum, file_name) = execution_output(&state, &execution).await?;
+    Ok(Json(files::download_url(&state.blobs, &checksum, &file_name, &query)?))
 }
 
 #[derive(Serialize)]
@@ -291,8 +413,13 @@ pub async fn create_dashboard(
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/definitions", get(list_reports).post(create_report))
-        .route("/schedules", post(create_schedule))
-        .route("/run", post(run_report))
-        .route("/dashboards", post(create_dashboard))
+        .route("/definitions", get(list_reports).post(create_report).require("reports:definitions"))
+        .route("/schedules", post(create_schedule).require("reports:schedules:write"))
+        .route("/entities", get(list_entities).require("reports:entities:read"))
+        .route("/run", post(run_report).require("reports:run:write"))
+        .route("/executions", get(list_executions).require("reports:executions:read"))
+        .route("/executions/:id", get(get_execution).require("reports:executions:read"))
+        .route("/executions/:id/content", get(download_execution).require("reports:executions:read"))
+        .route("/executions/:id/download-url", get(execution_download_url).require("reports:executions:read"))
+        .route("/dashboards", post(create_dashboard).require("reports:dashboards:write"))
 }
@@ -4,6 +4,7 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::{Pagination, BaseEntity, Money, Currency};
 use erp_returns::{
     ReturnOrder, ReturnLine, ReturnType, ReturnReason, ReturnStatus, ReturnDisposition, ItemCondition,
@@ -333,13 +334,13 @@ pub async fn process_refund(
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/", get(list_returns).post(create_return))
-        .route("/:id", get(get_return))
-        .route("/:id/approve", post(approve_return))
-        .route("/:id/receive", post(receive_return))
-        .route("/:id/process", post(process_return))
-        .route("/credit-memos", get(list_credit_memos).post(create_credit_memo))
-        .route("/credit-memos/:id/issue", post(issue_credit_memo))
-        .route("/refunds", get(list_refunds).post(create_refund))
-        .route("/refunds/:id/process", post(process_refund))
+        .route("/", get(list_returns).post(create_return).require("returns:returns"))
+        .route("/:id", get(get_return).require("returns:returns:read"))
+        .route("/:id/approve", post(approve_return).require("returns:returns:approve"))
+        .route("/:id/receive", post(receive_return).require("returns:returns:write"))
+        .route("/:id/process", post(process_return).require("returns:returns:write"))
+        .route("/credit-memos", get(list_credit_memos).post(create_credit_memo).require("returns:credit-memos"))
+        .route("/credit-memos/:id/issue", post(issue_credit_memo).require("returns:credit-memos:write"))
+        .route("/refunds", get(list_refunds).post(create_refund).require("returns:refunds"))
+        .route("/refunds/:id/process", post(process_refund).require("returns:refunds:write"))
 }
@@ -8,20 +8,21 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 use erp_risk::{RiskService, MitigationService, RiskControlService, CreateRiskRequest, CreateMitigationRequest, RiskCategory, RiskLevel};
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/risks", get(list_risks).post(create_risk))
-        .route("/risks/:id", get(get_risk))
-        .route("/risks/:id/status", post(update_risk_status))
-        .route("/risks/:id/assess", post(assess_risk))
-        .route("/risks/dashboard", get(get_dashboard))
-        .route("/mitigations", post(create_mitigation))
-        .route("/mitigations/:plan_id/tasks", post(add_mitigation_task))
-        .route("/mitigations/tasks/:task_id/complete", post(complete_task))
-        .route("/controls", post(create_control))
-        .route("/controls/:control_id/link/:risk_id", post(link_control))
+        .route("/risks", get(list_risks).post(create_risk).require("risk:risks"))
+        .route("/risks/:id", get(get_risk).require("risk:risks:read"))
+        .route("/risks/:id/status", post(update_risk_status).require("risk:risks:write"))
+        .route("/risks/:id/assess", post(assess_risk).require("risk:risks:write"))
+        .route("/risks/dashboard", get(get_dashboard).require("risk:risks:read"))
+        .route("/mitigations", post(create_mitigation).require("risk:mitigations:write"))
+        .route("/mitigations/:plan_id/tasks", post(add_mitigation_task).require("risk:mitigations:write"))
+        .route("/mitigations/tasks/:task_id/complete", post(complete_task).require("risk:mitigations:write"))
+        .route("/controls", post(create_control).require("risk:controls:write"))
+        .route("/controls/:control_id/link/:risk_id", post(link_control).require("risk:controls:write"))
 }
 
 #[derive(Deserialize)]
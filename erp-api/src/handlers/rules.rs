@@ -6,6 +6,7 @@ use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::BaseEntity;
 use erp_rules::{
     RulesService, BusinessRule, RuleSet, DecisionTable,
@@ -304,12 +305,12 @@ pub async fn evaluate_table(
 
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/rules", axum::routing::get(list_rules).post(create_rule))
-        .route("/rules/:id", axum::routing::get(get_rule).delete(delete_rule))
-        .route("/rules/execute", axum::routing::post(execute_rules))
-        .route("/rulesets", axum::routing::get(list_rulesets).post(create_ruleset))
-        .route("/rulesets/add-rule", axum::routing::post(add_rule_to_ruleset))
-        .route("/decision-tables", axum::routing::post(create_decision_table))
-        .route("/decision-tables/rows", axum::routing::post(add_decision_row))
-        .route("/decision-tables/evaluate", axum::routing::post(evaluate_table))
+        .route("/rules", axum::routing::get(list_rules).post(create_rule).require("rules:rules"))
+        .route("/rules/:id", axum::routing::get(get_rule).delete(delete_rule).require("rules:rules"))
+        .route("/rules/execute", axum::routing::post(execute_rules).require("rules:rules:write"))
+        .route("/rulesets", axum::routing::get(list_rulesets).post(create_ruleset).require("rules:rulesets"))
+        .route("/rulesets/add-rule", axum::routing::post(add_rule_to_ruleset).require("rules:rulesets:write"))
+        .route("/decision-tables", axum::routing::post(create_decision_table).require("rules:decision-tables:write"))
+        .route("/decision-tables/rows", axum::routing::post(add_decision_row).require("rules:decision-tables:write"))
+        .route("/decision-tables/evaluate", axum::routing::post(evaluate_table).require("rules:decision-tables:write"))
 }
@@ -4,8 +4,12 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::handlers::auth::AuthUser;
+use crate::policy::{self, AccessPolicy};
 use erp_core::{BaseEntity, Status, Pagination, Money, Currency, ContactInfo, Address};
-use erp_sales::{Customer, SalesOrder, SalesOrderLine, SalesQuote, SalesQuoteLine, CustomerService, SalesOrderService, QuotationService};
+use erp_core::events::publish_change;
+use erp_sales::{Customer, SalesOrder, SalesOrderLine, SalesQuote, SalesQuoteLine, CustomerService, SalesOrderService, QuotationService,
+    CashApplication, CustomerStatement, Invoice, InvoiceLineRequest, OpenItem, Payment, PaymentAllocationRequest, PaymentMethod, ReceivablesService};
 
 #[derive(Deserialize)] pub struct CreateCustomerRequest { pub code: String, pub name: String, pub email: Option<String>, pub phone: Option<String>, pub credit_limit: Option<i64>, pub payment_terms: Option<u32> }
 #[derive(Serialize)] pub struct CustomerResponse { pub id: Uuid, pub code: String, pub name: String, pub email: Option<String>, pub phone: Option<String>, pub status: String }
@@ -14,56 +18,66 @@ impl From<Customer> for CustomerResponse {
     fn from(c: Customer) -> Self { Self { id: c.base.id, code: c.code, name: c.name, email: c.contact.email, phone: c.contact.phone, status: format!("{:?}", c.status) } }
 }
 
-pub async fn list_customers(State(state): State<AppState>, Query(pagination): Query<Pagination>) -> ApiResult<Json<erp_core::Paginated<CustomerResponse>>> {
+pub async fn list_customers(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Query(pagination): Query<Pagination>) -> ApiResult<Json<serde_json::Value>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
     let svc = CustomerService::new();
-    let res = svc.list(&state.pool, pagination).await?;
-    Ok(Json(erp_core::Paginated::new(res.items.into_iter().map(CustomerResponse::from).collect(), res.total, Pagination { page: res.page, per_page: res.per_page })))
+    let res = svc.list_scoped(&state.pool, pagination, &policy.row_scope(policy::CUSTOMERS)).await?;
+    let page = erp_core::Paginated::new(res.items.into_iter().map(CustomerResponse::from).collect::<Vec<_>>(), res.total, Pagination { page: res.page, per_page: res.per_page });
+    Ok(Json(policy.redact(policy::CUSTOMERS, &page)?))
 }
 
-pub async fn get_customer(State(state): State<AppState>, Path(id): Path<Uuid>) -> ApiResult<Json<CustomerResponse>> {
+pub async fn get_customer(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
     let svc = CustomerService::new();
-    Ok(Json(CustomerResponse::from(svc.get(&state.pool, id).await?)))
+    let customer = svc.get_scoped(&state.pool, id, &policy.row_scope(policy::CUSTOMERS)).await?;
+    Ok(Json(policy.redact(policy::CUSTOMERS, &CustomerResponse::from(customer))?))
 }
 
-pub async fn create_customer(State(state): State<AppState>, Json(req): Json<CreateCustomerRequest>) -> ApiResult<Json<CustomerResponse>> {
+pub async fn create_customer(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Json(req): Json<CreateCustomerRequest>) -> ApiResult<Json<CustomerResponse>> {
     let svc = CustomerService::new();
     let c = Customer {
-        base: BaseEntity::new(), code: req.code, name: req.name,
+        base: BaseEntity { created_by: Uuid::parse_str(&user.user_id).ok(), ..BaseEntity::new() }, code: req.code, name: req.name,
         contact: ContactInfo { email: req.email, phone: req.phone, fax: None, website: None },
         billing_address: Address { street: String::new(), city: String::new(), state: None, postal_code: String::new(), country: String::new() },
         shipping_address: None, credit_limit: req.credit_limit.map(|v| Money::new(v, Currency::USD)),
         payment_terms: req.payment_terms.unwrap_or(30), status: Status::Active,
     };
-    Ok(Json(CustomerResponse::from(svc.create(&state.pool, c).await?)))
+    let created = svc.create(&state.pool, c).await?;
+    publish_change("customer", created.base.id, "created", &user.user_id);
+    Ok(Json(CustomerResponse::from(created)))
 }
 
 #[derive(Deserialize)] pub struct CreateOrderRequest { pub customer_id: Uuid, pub lines: Vec<OrderLineRequest> }
 #[derive(Deserialize)] pub struct OrderLineRequest { pub product_id: Uuid, pub description: String, pub quantity: i64, pub unit_price: i64 }
 
 #[derive(Serialize)] pub struct OrderResponse { pub id: Uuid, pub order_number: String, pub customer_id: Uuid, pub status: String, pub total: f64, pub lines: Vec<OrderLineResponse> }
-#[derive(Serialize)] pub struct OrderLineResponse { pub product_id: Uuid, pub description: String, pub quantity: i64, pub unit_price: f64, pub line_total: f64 }
+#[derive(Serialize)] pub struct OrderLineResponse { pub id: Uuid, pub product_id: Uuid, pub description: String, pub quantity: i64, pub unit_price: f64, pub line_total: f64 }
 
 impl From<SalesOrder> for OrderResponse {
     fn from(o: SalesOrder) -> Self { Self { id: o.base.id, order_number: o.order_number, customer_id: o.customer_id, status: format!("{:?}", o.status), total: o.total.to_decimal(),
-        lines: o.lines.into_iter().map(|l| OrderLineResponse { product_id: l.product_id, description: l.description, quantity: l.quantity, unit_price: l.unit_price.to_decimal(), line_total: l.line_total.to_decimal() }).collect() }
+        lines: o.lines.into_iter().map(|l| OrderLineResponse { id: l.id, product_id: l.product_id, description: l.description, quantity: l.quantity, unit_price: l.unit_price.to_decimal(), line_total: l.line_total.to_decimal() }).collect() }
     }
 }
 
-pub async fn list_orders(State(state): State<AppState>, Query(pagination): Query<Pagination>) -> ApiResult<Json<erp_core::Paginated<OrderResponse>>> {
+pub async fn list_orders(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Query(pagination): Query<Pagination>) -> ApiResult<Json<serde_json::Value>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
     let svc = SalesOrderService::new();
-    let res = svc.list(&state.pool, pagination).await?;
-    Ok(Json(erp_core::Paginated::new(res.items.into_iter().map(OrderResponse::from).collect(), res.total, Pagination { page: res.page, per_page: res.per_page })))
+    let res = svc.list_scoped(&state.pool, pagination, &policy.row_scope(policy::SALES_ORDERS)).await?;
+    let page = erp_core::Paginated::new(res.items.into_iter().map(OrderResponse::from).collect::<Vec<_>>(), res.total, Pagination { page: res.page, per_page: res.per_page });
+    Ok(Json(policy.redact(policy::SALES_ORDERS, &page)?))
 }
 
-pub async fn get_order(State(state): State<AppState>, Path(id): Path<Uuid>) -> ApiResult<Json<OrderResponse>> {
+pub async fn get_order(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
     let svc = SalesOrderService::new();
-    Ok(Json(OrderResponse::from(svc.get(&state.pool, id).await?)))
+    let order = svc.get_scoped(&state.pool, id, &policy.row_scope(policy::SALES_ORDERS)).await?;
+    Ok(Json(policy.redact(policy::SALES_ORDERS, &OrderResponse::from(order))?))
 }
 
-pub async fn create_order(State(state): State<AppState>, Json(req): Json<CreateOrderRequest>) -> ApiResult<Json<OrderResponse>> {
+pub async fn create_order(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Json(req): Json<CreateOrderRequest>) -> ApiResult<Json<OrderResponse>> {
     let svc = SalesOrderService::new();
     let order = SalesOrder {
-        base: BaseEntity::new(), order_number: String::new(), customer_id: req.customer_id, order_date: Utc::now(), required_date: None,
+        base: BaseEntity { created_by: Uuid::parse_str(&user.user_id).ok(), ..BaseEntity::new() }, order_number: String::new(), customer_id: req.customer_id, order_date: Utc::now(), required_date: None,
         lines: req.lines.into_iter().map(|l| SalesOrderLine {
             id: Uuid::nil(), product_id: l.product_id, description: l.description, quantity: l.quantity,
             unit_price: Money::new(l.unit_price, Currency::USD), discount_percent: 0.0, tax_rate: 0.0,
@@ -71,16 +85,108 @@ pub async fn create_order(State(state): State<AppState>, Json(req): Json<CreateO
         }).collect(),
         subtotal: Money::zero(Currency::USD), tax_amount: Money::zero(Currency::USD), total: Money::zero(Currency::USD), status: Status::Draft,
     };
-    Ok(Json(OrderResponse::from(svc.create(&state.pool, order).await?)))
+    let created = svc.create(&state.pool, order).await?;
+    publish_change("sales_order", created.base.id, "created", &user.user_id);
+    Ok(Json(OrderResponse::from(created)))
 }
 
-pub async fn confirm_order(State(state): State<AppState>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
-    SalesOrderService::new().confirm(&state.pool, id).await?;
+#[derive(Deserialize)] pub struct ConfirmOrderRequest { pub warehouse_id: Option<Uuid> }
+
+pub async fn confirm_order(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>, req: Option<Json<ConfirmOrderRequest>>) -> ApiResult<Json<serde_json::Value>> {
+    let warehouse_id = req.and_then(|Json(r)| r.warehouse_id);
+    SalesOrderService::new().confirm_from_warehouse(&state.pool, id, warehouse_id).await?;
+    publish_change("sales_order", id, "confirmed", &user.user_id);
     Ok(Json(serde_json::json!({ "status": "confirmed" })))
 }
 
-pub async fn list_invoices(State(_state): State<AppState>, Query(_pagination): Query<Pagination>) -> ApiResult<Json<Vec<serde_json::Value>>> { Ok(Json(vec![])) }
-pub async fn create_invoice(State(_state): State<AppState>, Json(_req): Json<serde_json::Value>) -> ApiResult<Json<serde_json::Value>> { Ok(Json(serde_json::json!({"id": Uuid::new_v4()}))) }
+pub async fn ship_order(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
+    SalesOrderService::new().ship(&state.pool, id).await?;
+    publish_change("sales_order", id, "shipped", &user.user_id);
+    Ok(Json(serde_json::json!({ "status": "shipped" })))
+}
+
+pub async fn cancel_order(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>) -> ApiResult<Json<serde_json::Value>> {
+    SalesOrderService::new().cancel(&state.pool, id).await?;
+    publish_change("sales_order", id, "cancelled", &user.user_id);
+    Ok(Json(serde_json::json!({ "status": "cancelled" })))
+}
+
+pub async fn get_order_reservations(State(state): State<AppState>, Path(id): Path<Uuid>) -> ApiResult<Json<Vec<erp_inventory::StockReservation>>> {
+    Ok(Json(SalesOrderService::new().reservations(&state.pool, id).await?))
+}
+
+#[derive(Deserialize)] pub struct InvoiceFilter { pub customer_id: Option<Uuid> }
+
+pub async fn list_invoices(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Query(pagination): Query<Pagination>, Query(filter): Query<InvoiceFilter>) -> ApiResult<Json<erp_core::Paginated<Invoice>>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
+    Ok(Json(ReceivablesService::list_invoices_scoped(&state.pool, filter.customer_id, pagination, &policy.invoice_scope()).await?))
+}
+
+#[derive(Deserialize)] pub struct CreateInvoiceRequest { pub sales_order_id: Uuid, pub lines: Option<Vec<InvoiceLineRequest>>, pub payment_term_id: Option<Uuid> }
+
+pub async fn create_invoice(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Json(req): Json<CreateInvoiceRequest>) -> ApiResult<Json<Invoice>> {
+    let invoice = ReceivablesService::invoice_order(
+        &state.pool, req.sales_order_id, req.lines.as_deref(), req.payment_term_id, Uuid::parse_str(&user.user_id).ok(),
+    ).await?;
+    Ok(Json(invoice))
+}
+
+pub async fn get_invoice(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>) -> ApiResult<Json<Invoice>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
+    Ok(Json(ReceivablesService::get_invoice_scoped(&state.pool, id, &policy.invoice_scope()).await?))
+}
+
+#[derive(Deserialize)]
+pub struct ReceivePaymentRequest {
+    pub customer_id: Uuid,
+    pub amount: i64,
+    pub payment_date: Option<chrono::DateTime<Utc>>,
+    pub payment_method: Option<PaymentMethod>,
+    pub reference: Option<String>,
+    #[serde(default)]
+    pub allocations: Vec<PaymentAllocationRequest>,
+}
+
+pub async fn receive_payment(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Json(req): Json<ReceivePaymentRequest>) -> ApiResult<Json<CashApplication>> {
+    let payment = Payment {
+        base: BaseEntity { created_by: Uuid::parse_str(&user.user_id).ok(), ..BaseEntity::new() },
+        payment_number: String::new(),
+        customer_id: req.customer_id,
+        invoice_id: None,
+        payment_date: req.payment_date.unwrap_or_else(Utc::now),
+        amount: Money::new(req.amount, Currency::USD),
+        payment_method: req.payment_method.unwrap_or(PaymentMethod::BankTransfer),
+        reference: req.reference,
+    };
+    Ok(Json(ReceivablesService::apply_payment(&state.pool, payment, &req.allocations).await?))
+}
+
+pub async fn get_payment(State(state): State<AppState>, Path(id): Path<Uuid>) -> ApiResult<Json<CashApplication>> {
+    Ok(Json(ReceivablesService::get_cash_application(&state.pool, id).await?))
+}
+
+#[derive(Deserialize)] pub struct ApplyPaymentRequest { #[serde(default)] pub allocations: Vec<PaymentAllocationRequest> }
+
+pub async fn apply_payment(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>, req: Option<Json<ApplyPaymentRequest>>) -> ApiResult<Json<CashApplication>> {
+    let allocations = req.map(|Json(r)| r.allocations).unwrap_or_default();
+    Ok(Json(ReceivablesService::apply_unapplied(&state.pool, id, &allocations, Uuid::parse_str(&user.user_id).ok()).await?))
+}
+
+pub async fn get_open_items(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>) -> ApiResult<Json<Vec<OpenItem>>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
+    CustomerService::new().get_scoped(&state.pool, id, &policy.row_scope(policy::CUSTOMERS)).await?;
+    Ok(Json(ReceivablesService::open_items(&state.pool, id).await?))
+}
+
+#[derive(Deserialize)] pub struct StatementQuery { pub from: Option<chrono::DateTime<Utc>>, pub to: Option<chrono::DateTime<Utc>> }
+
+pub async fn get_statement(State(state): State<AppState>, axum::Extension(AuthUser(user)): axum::Extension<AuthUser>, Path(id): Path<Uuid>, Query(query): Query<StatementQuery>) -> ApiResult<Json<CustomerStatement>> {
+    let policy = AccessPolicy::load(&state.pool, &user).await?;
+    CustomerService::new().get_scoped(&state.pool, id, &policy.row_scope(policy::CUSTOMERS)).await?;
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query.from.unwrap_or_else(|| to - chrono::Duration::days(30));
+    Ok(Json(ReceivablesService::statement(&state.pool, id, from, to).await?))
+}
 
 #[derive(Deserialize)]
 pub struct CreateQuotationRequest {
@@ -1,11 +1,14 @@
 use axum::{
     extract::{Path, Query, State},
-    Json,
+    Extension, Json,
 };
+use erp_auth::jwt::TokenData;
 use serde::Deserialize;
 use crate::db::AppState;
 use crate::error::ApiResult;
-use erp_enterprise::{SearchService, SearchRequest, SearchResponse, IndexRequest, SearchStats};
+use crate::handlers::auth::AuthUser;
+use crate::policy::{self, AccessPolicy};
+use erp_enterprise::{SearchAccess, SearchService, SearchRequest, SearchResponse, IndexRequest, SearchStats, SEARCHABLE};
 
 #[derive(Debug, Deserialize)]
 pub struct SearchQuery {
@@ -15,8 +18,31 @@ pub struct SearchQuery {
     pub offset: Option<i32>,
 }
 
+/// Hides the entity types the caller may not read, and applies their row scopes to customers,
+/// sales orders and employees. Types with hidden fields show only their title.
+async fn search_access(state: &AppState, user: &TokenData) -> ApiResult<SearchAccess> {
+    let policy = AccessPolicy::load(&state.pool, user).await?;
+    let permissions = state.authz.permissions(&user.user_id).await?;
+    let mut access = SearchAccess::unrestricted();
+    for searchable in &SEARCHABLE {
+        if !permissions.allows(searchable.permission) {
+            access = access.deny(searchable.entity_type);
+            continue;
+        }
+        let resource = searchable.table;
+        if [policy::CUSTOMERS, policy::SALES_ORDERS, policy::EMPLOYEES].contains(&resource) {
+            access = access.scope(searchable.entity_type, policy.row_scope(resource));
+            if !policy.hidden_fields(resource).is_empty() {
+                access = access.redact(searchable.entity_type);
+            }
+        }
+    }
+    Ok(access)
+}
+
 pub async fn search(
     State(state): State<AppState>,
+    Extension(AuthUser(user)): Extension<AuthUser>,
     Query(query): Query<SearchQuery>,
 ) -> ApiResult<Json<SearchResponse>> {
     let svc = SearchService::new();
@@ -31,7 +57,8 @@ pub async fn search(
         offset: query.offset,
     };
     
-    let results = svc.search(&state.pool, request).await?;
+    let access = search_access(&state, &user).await?;
+    let results = svc.search(&state.pool, request, &access).await?;
     Ok(Json(results))
 }
 
@@ -64,7 +91,7 @@ pub async fn rebuild_index(
 ) -> ApiResult<Json<serde_json::Value>> {
     let svc = SearchService::new();
     let count = svc.rebuild_index(&state.pool).await?;
-    Ok(Json(serde_json::json!({ "deleted_count": count })))
+    Ok(Json(serde_json::json!({ "indexed_count": count })))
 }
 
 pub async fn search_stats(
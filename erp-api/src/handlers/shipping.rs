@@ -8,17 +8,18 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 use erp_shipping::{CarrierService, ShipmentService, CarrierType, CreateShipmentRequest, GetRatesRequest, ShipmentItemRequest, ShipmentStatus};
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/carriers", get(list_carriers).post(create_carrier))
-        .route("/shipments", get(list_pending_shipments).post(create_shipment))
-        .route("/shipments/:id", get(get_shipment))
-        .route("/shipments/:id/status", post(update_shipment_status))
-        .route("/shipments/:id/label", post(generate_label))
-        .route("/shipments/:id/tracking", post(add_tracking_event))
-        .route("/rates", post(get_rates))
+        .route("/carriers", get(list_carriers).post(create_carrier).require("shipping:carriers"))
+        .route("/shipments", get(list_pending_shipments).post(create_shipment).require("shipping:shipments"))
+        .route("/shipments/:id", get(get_shipment).require("shipping:shipments:read"))
+        .route("/shipments/:id/status", post(update_shipment_status).require("shipping:shipments:write"))
+        .route("/shipments/:id/label", post(generate_label).require("shipping:shipments:write"))
+        .route("/shipments/:id/tracking", post(add_tracking_event).require("shipping:shipments:write"))
+        .route("/rates", post(get_rates).require("shipping:rates:write"))
 }
 
 #[derive(Deserialize)]
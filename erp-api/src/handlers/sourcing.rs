@@ -6,6 +6,7 @@ use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_core::BaseEntity;
 use erp_sourcing::{
     SourcingService, SourcingEvent, SourcingItem, Bid,
@@ -262,13 +263,13 @@ pub async fn invite_supplier(
 
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/events", axum::routing::get(list_events).post(create_event))
-        .route("/events/:id", axum::routing::get(get_event))
-        .route("/events/:id/publish", axum::routing::post(publish_event))
-        .route("/items", axum::routing::post(add_item))
-        .route("/bids", axum::routing::post(submit_bid))
-        .route("/bids/:event_id", axum::routing::get(list_bids))
-        .route("/bids/:id/accept", axum::routing::post(accept_bid))
-        .route("/award", axum::routing::post(award_bid))
-        .route("/invite", axum::routing::post(invite_supplier))
+        .route("/events", axum::routing::get(list_events).post(create_event).require("sourcing:events"))
+        .route("/events/:id", axum::routing::get(get_event).require("sourcing:events:read"))
+        .route("/events/:id/publish", axum::routing::post(publish_event).require("sourcing:events:write"))
+        .route("/items", axum::routing::post(add_item).require("sourcing:items:write"))
+        .route("/bids", axum::routing::post(submit_bid).require("sourcing:bids:write"))
+        .route("/bids/:event_id", axum::routing::get(list_bids).require("sourcing:bids:read"))
+        .route("/bids/:id/accept", axum::routing::post(accept_bid).require("sourcing:bids:write"))
+        .route("/award", axum::routing::post(award_bid).require("sourcing:award:write"))
+        .route("/invite", axum::routing::post(invite_supplier).require("sourcing:invite:write"))
 }
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RecordSpendRequest {
@@ -41,20 +42,20 @@ pub struct CreateOpportunityRequest {
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/transactions", get(list_transactions).post(record_spend))
-        .route("/analyze", post(analyze_spend))
-        .route("/vendors/:id/analysis", get(analyze_vendor))
-        .route("/categories/:id/analysis", get(analyze_category))
-        .route("/maverick", get(identify_maverick))
-        .route("/duplicates", get(identify_duplicates))
-        .route("/opportunities", get(list_opportunities).post(create_opportunity))
-        .route("/trends", get(get_trends))
-        .route("/forecast", post(forecast_spend))
-        .route("/tail-spend", get(analyze_tail_spend))
-        .route("/risk-scores", get(get_risk_scores))
-        .route("/compliance/:id", get(analyze_compliance))
-        .route("/kpis", get(get_kpis))
-        .route("/dashboards", get(list_dashboards))
+        .route("/transactions", get(list_transactions).post(record_spend).require("spend-analytics:transactions"))
+        .route("/analyze", post(analyze_spend).require("spend-analytics:analyze:write"))
+        .route("/vendors/:id/analysis", get(analyze_vendor).require("spend-analytics:vendors:read"))
+        .route("/categories/:id/analysis", get(analyze_category).require("spend-analytics:categories:read"))
+        .route("/maverick", get(identify_maverick).require("spend-analytics:maverick:read"))
+        .route("/duplicates", get(identify_duplicates).require("spend-analytics:duplicates:read"))
+        .route("/opportunities", get(list_opportunities).post(create_opportunity).require("spend-analytics:opportunities"))
+        .route("/trends", get(get_trends).require("spend-analytics:trends:read"))
+        .route("/forecast", post(forecast_spend).require("spend-analytics:forecast:write"))
+        .route("/tail-spend", get(analyze_tail_spend).require("spend-analytics:tail-spend:read"))
+        .route("/risk-scores", get(get_risk_scores).require("spend-analytics:risk-scores:read"))
+        .route("/compliance/:id", get(analyze_compliance).require("spend-analytics:compliance:read"))
+        .route("/kpis", get(get_kpis).require("spend-analytics:kpis:read"))
+        .route("/dashboards", get(list_dashboards).require("spend-analytics:dashboards:read"))
 }
 
 async fn list_transactions(State(_state): State<AppState>) -> Json<serde_json::Value> {
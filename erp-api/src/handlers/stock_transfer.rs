@@ -8,6 +8,7 @@ use uuid::Uuid;
 use crate::error::ApiResult;
 use crate::db::AppState;
 use crate::handlers::auth::AuthUser;
+use crate::middleware::RequirePermission;
 use erp_stock_transfer::{
     TransferService, StockTransfer, StockTransferLine, StockTransferWithLines, TransferAnalytics,
     CreateTransferRequest, CreateTransferLineRequest, ShipTransferRequest, ReceiveTransferRequest,
@@ -165,16 +166,16 @@ pub struct RejectRequestDto {
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/", axum::routing::get(list_transfers).post(create_transfer))
-        .route("/analytics", axum::routing::get(get_analytics))
-        .route("/:id", axum::routing::get(get_transfer).delete(delete_transfer))
-        .route("/:id/submit", axum::routing::post(submit_transfer))
-        .route("/:id/approve", axum::routing::post(approve_transfer))
-        .route("/:id/reject", axum::routing::post(reject_transfer))
-        .route("/:id/ship", axum::routing::post(ship_transfer))
-        .route("/:id/receive", axum::routing::post(receive_transfer))
-        .route("/:id/cancel", axum::routing::post(cancel_transfer))
-        .route("/:id/lines", axum::routing::get(list_lines).post(add_line))
+        .route("/", axum::routing::get(list_transfers).post(create_transfer).require("stock-transfers:stock-transfers"))
+        .route("/analytics", axum::routing::get(get_analytics).require("stock-transfers:analytics:read"))
+        .route("/:id", axum::routing::get(get_transfer).delete(delete_transfer).require("stock-transfers:stock-transfers"))
+        .route("/:id/submit", axum::routing::post(submit_transfer).require("stock-transfers:stock-transfers:write"))
+        .route("/:id/approve", axum::routing::post(approve_transfer).require("stock-transfers:stock-transfers:approve"))
+        .route("/:id/reject", axum::routing::post(reject_transfer).require("stock-transfers:stock-transfers:reject"))
+        .route("/:id/ship", axum::routing::post(ship_transfer).require("stock-transfers:stock-transfers:write"))
+        .route("/:id/receive", axum::routing::post(receive_transfer).require("stock-transfers:stock-transfers:write"))
+        .route("/:id/cancel", axum::routing::post(cancel_transfer).require("stock-transfers:stock-transfers:write"))
+        .route("/:id/lines", axum::routing::get(list_lines).post(add_line).require("stock-transfers:stock-transfers"))
 }
 
 fn parse_priority(s: &str) -> TransferPriority {
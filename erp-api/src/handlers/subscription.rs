@@ -8,18 +8,19 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 use erp_subscription::{SubscriptionPlanService, SubscriptionService, CreatePlanRequest, CreateSubscriptionRequest, BillingInterval};
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/plans", get(list_plans).post(create_plan))
-        .route("/subscriptions", post(create_subscription))
-        .route("/subscriptions/:id", get(get_subscription))
-        .route("/subscriptions/customer/:customer_id", get(list_customer_subscriptions))
-        .route("/subscriptions/:id/cancel", post(cancel_subscription))
-        .route("/subscriptions/:id/renew", post(renew_subscription))
-        .route("/subscriptions/expiring/:days", get(get_expiring))
-        .route("/subscriptions/:id/usage", post(record_usage))
+        .route("/plans", get(list_plans).post(create_plan).require("subscription:plans"))
+        .route("/subscriptions", post(create_subscription).require("subscription:subscriptions:write"))
+        .route("/subscriptions/:id", get(get_subscription).require("subscription:subscriptions:read"))
+        .route("/subscriptions/customer/:customer_id", get(list_customer_subscriptions).require("subscription:subscriptions:read"))
+        .route("/subscriptions/:id/cancel", post(cancel_subscription).require("subscription:subscriptions:write"))
+        .route("/subscriptions/:id/renew", post(renew_subscription).require("subscription:subscriptions:write"))
+        .route("/subscriptions/expiring/:days", get(get_expiring).require("subscription:subscriptions:read"))
+        .route("/subscriptions/:id/usage", post(record_usage).require("subscription:subscriptions:write"))
 }
 
 #[derive(Deserialize)]
@@ -1,14 +1,17 @@
-use axum::{extract::{Query, State}, Json, routing::{get, post}};
+use axum::{extract::{Path, Query, State}, Json, routing::{get, post, put}};
 use uuid::Uuid;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use crate::db::AppState;
 use crate::error::ApiResult;
-use erp_core::{Pagination, BaseEntity, Status};
+use crate::middleware::RequirePermission;
+use erp_core::{Pagination, BaseEntity, Currency, Money, Status};
 use erp_tax::{
     TaxJurisdiction, TaxRate, TaxType, TaxCalculationMethod,
-    TaxExemption, ExemptionType,
-    TaxJurisdictionService, TaxRateService, TaxCalculationService, TaxExemptionService,
+    TaxExemption, ExemptionType, TaxClass, TaxClassType, TaxTransaction, TaxTransactionSource,
+    TaxCalculationRequest, TaxCalculationResult,
+    TaxJurisdictionService, TaxRateService, TaxCalculationService, TaxExemptionService, TaxClassService,
+    TaxTransactionService,
 };
 
 #[derive(Serialize)]
@@ -40,6 +43,9 @@ pub struct CreateJurisdictionRequest {
     pub state_code: Option<String>,
     pub county: Option<String>,
     pub city: Option<String>,
+    pub postal_code_from: Option<String>,
+    pub postal_code_to: Option<String>,
+    pub parent_jurisdiction_id: Option<Uuid>,
 }
 
 pub async fn list_jurisdictions(
@@ -68,9 +74,9 @@ pub async fn create_jurisdiction(
         state_code: req.state_code,
         county: req.county,
         city: req.city,
-        postal_code_from: None,
-        postal_code_to: None,
-        parent_jurisdiction_id: None,
+        postal_code_from: req.postal_code_from,
+        postal_code_to: req.postal_code_to,
+        parent_jurisdiction_id: req.parent_jurisdiction_id,
         status: Status::Active,
         effective_from: Utc::now(),
         effective_to: None,
@@ -86,6 +92,9 @@ pub struct TaxRateResponse {
     pub code: String,
     pub rate: f64,
     pub tax_type: String,
+    pub tax_class_id: Option<Uuid>,
+    pub is_compound: bool,
+    pub calculation_method: String,
     pub status: String,
 }
 
@@ -98,6 +107,9 @@ impl From<TaxRate> for TaxRateResponse {
             code: r.code,
             rate: r.rate,
             tax_type: format!("{:?}", r.tax_type),
+            tax_class_id: r.tax_class_id,
+            is_compound: r.is_compound,
+            calculation_method: format!("{:?}", r.calculation_method),
             status: format!("{:?}", r.status),
         }
     }
@@ -112,6 +124,13 @@ pub struct CreateTaxRateRequest {
     pub tax_type: Option<String>,
     pub is_compound: Option<bool>,
     pub is_recoverable: Option<bool>,
+    pub tax_class_id: Option<Uuid>,
+    pub calculation_method: Option<String>,
+    pub priority: Option<i32>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    /// The currency of `min_amount` and `max_amount`, required with either.
+    pub currency: Option<Currency>,
 }
 
 pub async fn list_tax_rates(
@@ -133,6 +152,15 @@ pub async fn create_tax_rate(
     Json(req): Json<CreateTaxRateRequest>,
 ) -> ApiResult<Json<TaxRateResponse>> {
     let svc = TaxRateService::new();
+    let threshold = |amount: Option<i64>| -> Result<Option<Money>, erp_core::Error> {
+        match (amount, &req.currency) {
+            (Some(amount), Some(currency)) => Ok(Some(Money::new(amount, currency.clone()))),
+            (Some(_), None) => Err(erp_core::Error::validation("A currency is required with min_amount or max_amount")),
+            (None, _) => Ok(None),
+        }
+    };
+    let min_amount = threshold(req.min_amount)?;
+    let max_amount = threshold(req.max_amount)?;
     let rate = TaxRate {
         base: BaseEntity::new(),
         jurisdiction_id: req.jurisdiction_id,
@@ -151,48 +179,176 @@ pub async fn create_tax_rate(
         rate: req.rate,
         is_compound: req.is_compound.unwrap_or(false),
         is_recoverable: req.is_recoverable.unwrap_or(false),
-        calculation_method: TaxCalculationMethod::Exclusive,
+        calculation_method: match req.calculation_method.as_deref() {
+            Some("Inclusive") => TaxCalculationMethod::Inclusive,
+            Some("Mixed") => TaxCalculationMethod::Mixed,
+            _ => TaxCalculationMethod::Exclusive,
+        },
         status: Status::Active,
         effective_from: Utc::now(),
         effective_to: None,
-        priority: 1,
-        min_amount: None,
-        max_amount: None,
+        priority: req.priority.unwrap_or(1),
+        min_amount,
+        max_amount,
+        tax_class_id: req.tax_class_id,
     };
     Ok(Json(TaxRateResponse::from(svc.create(&state.pool, rate).await?)))
 }
 
+pub async fn calculate_tax(
+    State(state): State<AppState>,
+    Json(req): Json<TaxCalculationRequest>,
+) -> ApiResult<Json<TaxCalculationResult>> {
+    Ok(Json(TaxCalculationService::calculate(&state.pool, &req).await?))
+}
+
 #[derive(Deserialize)]
-pub struct CalculateTaxRequest {
+pub struct RecordTaxRequest {
+    pub transaction_type: String,
+    pub transaction_id: Uuid,
+    pub source: Option<TaxTransactionSource>,
+    #[serde(flatten)]
+    pub calculation: TaxCalculationRequest,
+}
+
+#[derive(Serialize)]
+pub struct TaxTransactionResponse {
+    pub id: Uuid,
+    pub transaction_type: String,
+    pub transaction_id: Uuid,
+    pub transaction_date: chrono::DateTime<Utc>,
     pub jurisdiction_id: Uuid,
-    pub amount: i64,
-    pub customer_id: Option<Uuid>,
+    pub tax_rate_id: Uuid,
+    pub tax_class_id: Option<Uuid>,
+    pub tax_type: String,
+    pub tax_rate: f64,
+    pub taxable_amount: erp_core::Money,
+    pub tax_amount: erp_core::Money,
+    pub exempt_amount: erp_core::Money,
+    pub exemption_id: Option<Uuid>,
+    pub reverse_charge: bool,
+    pub source: String,
+}
+
+impl From<TaxTransaction> for TaxTransactionResponse {
+    fn from(t: TaxTransaction) -> Self {
+        Self {
+            id: t.base.id,
+            transaction_type: t.transaction_type,
+            transaction_id: t.transaction_id,
+            transaction_date: t.transaction_date,
+            jurisdiction_id: t.jurisdiction_id,
+            tax_rate_id: t.tax_rate_id,
+            tax_class_id: t.tax_class_id,
+            tax_type: format!("{:?}", t.tax_type),
+            tax_rate: t.tax_rate,
+            taxable_amount: t.taxable_amount,
+            tax_amount: t.tax_amount,
+            exempt_amount: t.exempt_amount,
+            exemption_id: t.exemption_id,
+            reverse_charge: t.reverse_charge,
+            source: format!("{:?}", t.source),
+        }
+    }
 }
 
 #[derive(Serialize)]
-pub struct CalculateTaxResponse {
-    pub taxable_amount: f64,
-    pub total_tax: f64,
+pub struct RecordTaxResponse {
+    pub result: TaxCalculationResult,
+    pub transactions: Vec<TaxTransactionResponse>,
 }
 
-pub async fn calculate_tax(
+pub async fn record_tax(
     State(state): State<AppState>,
-    Json(req): Json<CalculateTaxRequest>,
-) -> ApiResult<Json<CalculateTaxResponse>> {
-    let result = TaxCalculationService::calculate(
+    Json(req): Json<RecordTaxRequest>,
+) -> ApiResult<Json<RecordTaxResponse>> {
+    let (result, transactions) = TaxCalculationService::commit(
         &state.pool,
-        req.jurisdiction_id,
-        req.amount,
-        req.customer_id,
-        None,
+        &req.calculation,
+        &req.transaction_type,
+        req.transaction_id,
+        req.source.unwrap_or(TaxTransactionSource::Manual),
     ).await?;
-    
-    Ok(Json(CalculateTaxResponse {
-        taxable_amount: result.taxable_amount.to_decimal(),
-        total_tax: result.total_tax.to_decimal(),
+    Ok(Json(RecordTaxResponse {
+        result,
+        transactions: transactions.into_iter().map(TaxTransactionResponse::from).collect(),
     }))
 }
 
+pub async fn list_transactions(
+    State(state): State<AppState>,
+    Path((transaction_type, transaction_id)): Path<(String, Uuid)>,
+) -> ApiResult<Json<Vec<TaxTransactionResponse>>> {
+    let svc = TaxTransactionService::new();
+    let transactions = svc.get_by_reference(&state.pool, &transaction_type, transaction_id).await?;
+    Ok(Json(transactions.into_iter().map(TaxTransactionResponse::from).collect()))
+}
+
+#[derive(Serialize)]
+pub struct TaxClassResponse {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub class_type: String,
+    pub status: String,
+}
+
+impl From<TaxClass> for TaxClassResponse {
+    fn from(c: TaxClass) -> Self {
+        Self {
+            id: c.base.id,
+            code: c.code,
+            name: c.name,
+            description: c.description,
+            class_type: format!("{:?}", c.class_type),
+            status: format!("{:?}", c.status),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CreateTaxClassRequest {
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub class_type: Option<TaxClassType>,
+}
+
+pub async fn list_tax_classes(State(state): State<AppState>) -> ApiResult<Json<Vec<TaxClassResponse>>> {
+    let classes = TaxClassService::new().list(&state.pool).await?;
+    Ok(Json(classes.into_iter().map(TaxClassResponse::from).collect()))
+}
+
+pub async fn create_tax_class(
+    State(state): State<AppState>,
+    Json(req): Json<CreateTaxClassRequest>,
+) -> ApiResult<Json<TaxClassResponse>> {
+    let class = TaxClass {
+        base: BaseEntity::new(),
+        name: req.name,
+        code: req.code,
+        description: req.description,
+        class_type: req.class_type.unwrap_or(TaxClassType::Product),
+        status: Status::Active,
+    };
+    Ok(Json(TaxClassResponse::from(TaxClassService::new().create(&state.pool, class).await?)))
+}
+
+#[derive(Deserialize)]
+pub struct AssignTaxClassRequest {
+    pub tax_class_id: Option<Uuid>,
+}
+
+pub async fn assign_product_tax_class(
+    State(state): State<AppState>,
+    Path(product_id): Path<Uuid>,
+    Json(req): Json<AssignTaxClassRequest>,
+) -> ApiResult<Json<serde_json::Value>> {
+    TaxClassService::new().assign_to_product(&state.pool, product_id, req.tax_class_id).await?;
+    Ok(Json(serde_json::json!({ "product_id": product_id, "tax_class_id": req.tax_class_id })))
+}
+
 #[derive(Serialize)]
 pub struct TaxExemptionResponse {
     pub id: Uuid,
@@ -258,8 +414,12 @@ pub async fn create_exemption(
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/jurisdictions", get(list_jurisdictions).post(create_jurisdiction))
-        .route("/rates", get(list_tax_rates).post(create_tax_rate))
-        .route("/calculate", post(calculate_tax))
-        .route("/exemptions", post(create_exemption))
+        .route("/jurisdictions", get(list_jurisdictions).post(create_jurisdiction).require("tax:jurisdictions"))
+        .route("/rates", get(list_tax_rates).post(create_tax_rate).require("tax:rates"))
+        .route("/calculate", post(calculate_tax).require("tax:calculate:write"))
+        .route("/transactions", post(record_tax).require("tax:transactions:write"))
+        .route("/transactions/:transaction_type/:transaction_id", get(list_transactions).require("tax:transactions:read"))
+        .route("/classes", get(list_tax_classes).post(create_tax_class).require("tax:classes"))
+        .route("/products/:id/class", put(assign_product_tax_class).require("tax:products:write"))
+        .route("/exemptions", post(create_exemption).require("tax:exemptions:write"))
 }
@@ -1,13 +1,17 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    Json,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::handlers::auth::AuthUser;
+use crate::handlers::files;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateTemplateRequest {
@@ -251,6 +255,139 @@ pub async fn render_email_template(
     })))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct GenerateDocumentRequest {
+    pub template_id: Option<Uuid>,
+    pub template_code: Option<String>,
+    pub name: String,
+    pub variables: serde_json::Value,
+    pub locale: Option<String>,
+    pub related_entity_type: Option<String>,
+    pub related_entity_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeneratedDocumentResponse {
+    pub id: Uuid,
+    pub template_id: Uuid,
+    pub template_version: i32,
+    pub name: String,
+    pub output_format: String,
+    pub content: Option<String>,
+    pub file_size: Option<i64>,
+    pub related_entity_type: Option<String>,
+    pub related_entity_id: Option<Uuid>,
+    pub generated_by: Uuid,
+    pub generated_at: String,
+}
+
+impl From<erp_templates::GeneratedDocument> for GeneratedDocumentResponse {
+    fn from(d: erp_templates::GeneratedDocument) -> Self {
+        Self {
+            id: d.base.id,
+            template_id: d.template_id,
+            template_version: d.template_version,
+            name: d.name,
+            output_format: format!("{:?}", d.output_format),
+            content: d.content,
+            file_size: d.file_size,
+            related_entity_type: d.related_entity_type,
+            related_entity_id: d.related_entity_id,
+            generated_by: d.generated_by,
+            generated_at: d.generated_at.to_rfc3339(),
+        }
+    }
+}
+
+pub async fn generate_document(
+    State(state): State<AppState>,
+    Extension(AuthUser(user)): Extension<AuthUser>,
+    Json(req): Json<GenerateDocumentRequest>,
+) -> ApiResult<(StatusCode, Json<GeneratedDocumentResponse>)> {
+    let generated_by = Uuid::parse_str(&user.user_id).map_err(|_| erp_core::Error::Unauthorized)?;
+    let service = erp_templates::TemplateService::new();
+    let template_id = match (req.template_id, req.template_code.as_ref()) {
+        (Some(id), _) => id,
+        (None, Some(code)) => service.get_by_code(&state.pool, code).await?
+            .ok_or_else(|| erp_core::Error::not_found("Template", code))?
+            .base.id,
+        (None, None) => return Err(erp_core::Error::validation("Either template_id or template_code is required").into()),
+    };
+    let document = service.generate_document(&state.pool, &state.blobs, erp_templates::GenerateDocument {
+        template_id,
+        name: req.name,
+        variables: req.variables,
+        locale: req.locale,
+        related_entity_type: req.related_entity_type,
+        related_entity_id: req.related_entity_id,
+        generated_by,
+    }).await?;
+    Ok((StatusCode::CREATED, Json(document.into())))
+}
+
+pub async fn get_document(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<GeneratedDocumentResponse>> {
+    Ok(Json(find_document(&state, id).await?.into()))
+}
+
+/// The rendered document: the stored file for PDFs, the kept text for other formats.
+pub async fn download_document(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Response> {
+    let document = find_document(&state, id).await?;
+    let file_name = document_file_name(&document);
+    if let Some(checksum) = &document.file_path {
+        let (blob, stream) = state.blobs.open(&state.pool, checksum).await?;
+        return Ok(files::blob_response(&blob, stream, &file_name));
+    }
+    let mime_type = match document.output_format {
+        erp_templates::TemplateFormat::HTML => "text/html; charset=utf-8",
+        erp_templates::TemplateFormat::JSON => "application/json",
+        erp_templates::TemplateFormat::XML => "application/xml",
+        erp_templates::TemplateFormat::CSV => "text/csv; charset=utf-8",
+        erp_templates::TemplateFormat::Markdown => "text/markdown; charset=utf-8",
+        _ => "text/plain; charset=utf-8",
+    };
+    Ok(([(header::CONTENT_TYPE, mime_type)], document.content.unwrap_or_default()).into_response())
+}
+
+pub async fn document_download_url(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<files::DownloadUrlQuery>,
+) -> ApiResult<Json<files::DownloadUrlResponse>> {
+    let document = find_document(&state, id).await?;
+    let checksum = document.file_path.as_deref().ok_or_else(|| {
+        erp_core::Error::validation(format!("Document {} is kept as text, not as a file", id))
+    })?;
+    Ok(Json(files::download_url(&state.blobs, checksum, &document_file_name(&document), &query)?))
+}
+
+async fn find_document(state: &AppState, id: Uuid) -> ApiResult<erp_templates::GeneratedDocument> {
+    Ok(erp_templates::TemplateService::new().get_document(&state.pool, id).await?
+        .ok_or_else(|| erp_core::Error::not_found("GeneratedDocument", &id.to_string()))?)
+}
+
+fn document_file_name(document: &erp_templates::GeneratedDocument) -> String {
+    let extension = match document.output_format {
+        erp_templates::TemplateFormat::PDF => "pdf",
+        erp_templates::TemplateFormat::HTML => "html",
+        erp_templates::TemplateFormat::JSON => "json",
+        erp_templates::TemplateFormat::XML => "xml",
+        erp_templates::TemplateFormat::CSV => "csv",
+        erp_templates::TemplateFormat::Markdown => "md",
+        erp_templates::TemplateFormat::PlainText => "txt",
+    };
+    if document.name.to_lowercase().ends_with(&format!(".{}", extension)) {
+        document.name.clone()
+    } else {
+        format!("{}.{}", document.name, extension)
+    }
+}
+
 fn parse_template_type(s: &str) -> anyhow::Result<erp_templates::TemplateType> {
     match s {
         "Email" => Ok(erp_templates::TemplateType::Email),
@@ -261,6 +398,9 @@ fn parse_template_type(s: &str) -> anyhow::Result<erp_templates::TemplateType> {
         "Quote" => Ok(erp_templates::TemplateType::Quote),
         "PurchaseOrder" => Ok(erp_templates::TemplateType::PurchaseOrder),
         "PackingSlip" => Ok(erp_templates::TemplateType::PackingSlip),
+        "PickList" => Ok(erp_templates::TemplateType::PickList),
+        "CreditNote" => Ok(erp_templates::TemplateType::CreditNote),
+        "Payslip" => Ok(erp_templates::TemplateType::Payslip),
         "Contract" => Ok(erp_templates::TemplateType::Contract),
         "Letter" => Ok(erp_templates::TemplateType::Letter),
         "SMS" => Ok(erp_templates::TemplateType::SMS),
@@ -285,9 +425,13 @@ fn parse_template_format(s: &str) -> anyhow::Result<erp_templates::TemplateForma
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/", axum::routing::post(create_template).get(list_templates))
-        .route("/:id", axum::routing::get(get_template).delete(delete_template))
-        .route("/render", axum::routing::post(render_template))
-        .route("/email", axum::routing::post(create_email_template).get(list_email_templates))
-        .route("/email/:id/render", axum::routing::post(render_email_template))
+        .route("/", axum::routing::post(create_template).get(list_templates).require("templates:templates"))
+        .route("/:id", axum::routing::get(get_template).delete(delete_template).require("templates:templates"))
+        .route("/render", axum::routing::post(render_template).require("templates:render:write"))
+        .route("/documents", axum::routing::post(generate_document).require("templates:documents:write"))
+        .route("/documents/:id", axum::routing::get(get_document).require("templates:documents:read"))
+        .route("/documents/:id/content", axum::routing::get(download_document).require("templates:documents:read"))
+        .route("/documents/:id/download-url", axum::routing::get(document_download_url).require("templates:documents:read"))
+        .route("/email", axum::routing::post(create_email_template).get(list_email_templates).require("templates:email"))
+        .route("/email/:id/render", axum::routing::post(render_email_template).require("templates:email:write"))
 }
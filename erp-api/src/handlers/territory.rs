@@ -2,16 +2,17 @@ use axum::{extract::State, routing::{get, post}, Json, Router};
 use serde::Serialize;
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/territories", post(create_territory).get(list_territories))
-        .route("/territories/:id", get(get_territory))
-        .route("/territories/:id/assign", post(assign_rep))
-        .route("/quotas", post(create_quota).get(list_quotas))
-        .route("/quotas/:id", get(get_quota))
-        .route("/quotas/:id/attainment", post(record_attainment).get(get_attainment))
-        .route("/performance/:territitory_id", get(get_territory_performance))
+        .route("/territories", post(create_territory).get(list_territories).require("territory:territories"))
+        .route("/territories/:id", get(get_territory).require("territory:territories:read"))
+        .route("/territories/:id/assign", post(assign_rep).require("territory:territories:write"))
+        .route("/quotas", post(create_quota).get(list_quotas).require("territory:quotas"))
+        .route("/quotas/:id", get(get_quota).require("territory:quotas:read"))
+        .route("/quotas/:id/attainment", post(record_attainment).get(get_attainment).require("territory:quotas"))
+        .route("/performance/:territitory_id", get(get_territory_performance).require("territory:performance:read"))
 }
 
 #[derive(Serialize)]
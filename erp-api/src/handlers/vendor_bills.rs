@@ -6,6 +6,7 @@ use uuid::Uuid;
 
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 use erp_vendor_bills::{ThreeWayMatchResult, VendorBill, VendorBillLineCreateRequest, VendorBillService};
 
 #[derive(Deserialize)]
@@ -280,15 +281,16 @@ pub async fn delete_vendor_bill(
 
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/", axum::routing::get(list_vendor_bills).post(create_vendor_bill))
-        .route("/vendor/:vendor_id", axum::routing::get(list_vendor_bills_by_vendor))
+        .route("/", axum::routing::get(list_vendor_bills).post(create_vendor_bill).require("vendor-bills:vendor-bills"))
+        .route("/vendor/:vendor_id", axum::routing::get(list_vendor_bills_by_vendor).require("vendor-bills:vendor:read"))
         .route(
             "/:id",
-            axum::routing::get(get_vendor_bill).delete(delete_vendor_bill),
+            axum::routing::get(get_vendor_bill).delete(delete_vendor_bill)
+                .require("vendor-bills:vendor-bills"),
         )
-        .route("/:id/submit", axum::routing::post(submit_vendor_bill))
-        .route("/:id/approve", axum::routing::post(approve_vendor_bill))
-        .route("/:id/void", axum::routing::post(void_vendor_bill))
-        .route("/:id/payment", axum::routing::post(record_vendor_bill_payment))
-        .route("/:id/match", axum::routing::post(perform_three_way_match))
+        .route("/:id/submit", axum::routing::post(submit_vendor_bill).require("vendor-bills:vendor-bills:write"))
+        .route("/:id/approve", axum::routing::post(approve_vendor_bill).require("vendor-bills:vendor-bills:approve"))
+        .route("/:id/void", axum::routing::post(void_vendor_bill).require("vendor-bills:vendor-bills:void"))
+        .route("/:id/payment", axum::routing::post(record_vendor_bill_payment).require("vendor-bills:vendor-bills:write"))
+        .route("/:id/match", axum::routing::post(perform_three_way_match).require("vendor-bills:vendor-bills:write"))
 }
@@ -8,6 +8,7 @@ use uuid::Uuid;
 use crate::error::ApiResult;
 use crate::db::AppState;
 use crate::handlers::auth::AuthUser;
+use crate::middleware::RequirePermission;
 use erp_warranty::{
     WarrantyService, SqliteWarrantyRepository,
     WarrantyPolicy, ProductWarranty, WarrantyClaim, WarrantyClaimLine, WarrantyClaimLabor,
@@ -397,25 +398,25 @@ pub struct AssignClaimRequestDto {
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/policies", axum::routing::get(list_policies).post(create_policy))
-        .route("/policies/:id", axum::routing::get(get_policy).put(update_policy).delete(delete_policy))
-        .route("/warranties", axum::routing::get(list_warranties).post(create_warranty))
-        .route("/warranties/expiring", axum::routing::get(list_expiring_warranties))
-        .route("/warranties/:id", axum::routing::get(get_warranty))
-        .route("/warranties/:id/transfer", axum::routing::post(transfer_warranty))
-        .route("/warranties/:id/void", axum::routing::post(void_warranty))
-        .route("/warranties/:id/extend", axum::routing::post(extend_warranty))
-        .route("/warranties/:id/extensions", axum::routing::get(list_warranty_extensions))
-        .route("/claims", axum::routing::get(list_claims).post(create_claim))
-        .route("/claims/:id", axum::routing::get(get_claim))
-        .route("/claims/:id/assign", axum::routing::post(assign_claim))
-        .route("/claims/:id/approve", axum::routing::post(approve_claim))
-        .route("/claims/:id/reject", axum::routing::post(reject_claim))
-        .route("/claims/:id/start", axum::routing::post(start_claim_work))
-        .route("/claims/:id/resolve", axum::routing::post(resolve_claim))
-        .route("/claims/:id/lines", axum::routing::get(list_claim_lines).post(add_claim_line))
-        .route("/claims/:id/labor", axum::routing::get(list_claim_labor).post(add_claim_labor))
-        .route("/analytics", axum::routing::get(get_analytics))
+        .route("/policies", axum::routing::get(list_policies).post(create_policy).require("warranty:policies"))
+        .route("/policies/:id", axum::routing::get(get_policy).put(update_policy).delete(delete_policy).require("warranty:policies"))
+        .route("/warranties", axum::routing::get(list_warranties).post(create_warranty).require("warranty:warranties"))
+        .route("/warranties/expiring", axum::routing::get(list_expiring_warranties).require("warranty:warranties:read"))
+        .route("/warranties/:id", axum::routing::get(get_warranty).require("warranty:warranties:read"))
+        .route("/warranties/:id/transfer", axum::routing::post(transfer_warranty).require("warranty:warranties:write"))
+        .route("/warranties/:id/void", axum::routing::post(void_warranty).require("warranty:warranties:void"))
+        .route("/warranties/:id/extend", axum::routing::post(extend_warranty).require("warranty:warranties:write"))
+        .route("/warranties/:id/extensions", axum::routing::get(list_warranty_extensions).require("warranty:warranties:read"))
+        .route("/claims", axum::routing::get(list_claims).post(create_claim).require("warranty:claims"))
+        .route("/claims/:id", axum::routing::get(get_claim).require("warranty:claims:read"))
+        .route("/claims/:id/assign", axum::routing::post(assign_claim).require("warranty:claims:write"))
+        .route("/claims/:id/approve", axum::routing::post(approve_claim).require("warranty:claims:approve"))
+        .route("/claims/:id/reject", axum::routing::post(reject_claim).require("warranty:claims:reject"))
+        .route("/claims/:id/start", axum::routing::post(start_claim_work).require("warranty:claims:write"))
+        .route("/claims/:id/resolve", axum::routing::post(resolve_claim).require("warranty:claims:write"))
+        .route("/claims/:id/lines", axum::routing::get(list_claim_lines).post(add_claim_line).require("warranty:claims"))
+        .route("/claims/:id/labor", axum::routing::get(list_claim_labor).post(add_claim_labor).require("warranty:claims"))
+        .route("/analytics", axum::routing::get(get_analytics).require("warranty:analytics:read"))
 }
 
 fn parse_warranty_type(s: &str) -> WarrantyType {
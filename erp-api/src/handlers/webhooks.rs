@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 use crate::db::AppState;
 use crate::error::ApiResult;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateWebhookRequest {
@@ -198,9 +199,9 @@ fn parse_event_type(s: &str) -> anyhow::Result<erp_webhooks::WebhookEventType> {
 
 pub fn routes() -> axum::Router<AppState> {
     axum::Router::new()
-        .route("/", axum::routing::post(create_webhook).get(list_webhooks))
-        .route("/:id", axum::routing::get(get_webhook).delete(delete_webhook))
-        .route("/:id/ping", axum::routing::post(ping_webhook))
-        .route("/:id/rotate-secret", axum::routing::post(rotate_webhook_secret))
-        .route("/trigger", axum::routing::post(trigger_webhook))
+        .route("/", axum::routing::post(create_webhook).get(list_webhooks).require("webhooks:webhooks"))
+        .route("/:id", axum::routing::get(get_webhook).delete(delete_webhook).require("webhooks:webhooks"))
+        .route("/:id/ping", axum::routing::post(ping_webhook).require("webhooks:webhooks:write"))
+        .route("/:id/rotate-secret", axum::routing::post(rotate_webhook_secret).require("webhooks:webhooks:write"))
+        .route("/trigger", axum::routing::post(trigger_webhook).require("webhooks:trigger:write"))
 }
@@ -1,14 +1,22 @@
 use axum::{
-    extract::{State, Query},
-    response::Response,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State, Query,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
     Json,
 };
+use erp_core::events::RealtimeEvent;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
 use uuid::Uuid;
 use crate::db::AppState;
+use crate::policy::AccessPolicy;
+
+const MAX_TOPICS_PER_CLIENT: usize = 100;
 
 pub type WebSocketManager = Arc<WebSocketManagerInner>;
 
@@ -22,6 +30,30 @@ pub struct WebSocketMessage {
     pub event_type: String,
     pub payload: serde_json::Value,
     pub target_users: Option<Vec<Uuid>>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
+impl WebSocketMessage {
+    /// Whether a client of `user_id` subscribed to `subscriptions` should receive this message.
+    /// A message addressed to users reaches only those users, whatever its topics.
+    pub fn is_for(&self, user_id: Uuid, subscriptions: &HashSet<String>) -> bool {
+        match &self.target_users {
+            Some(users) if !users.is_empty() => users.contains(&user_id),
+            _ => self.topics.is_empty() || self.topics.iter().any(|topic| subscriptions.contains(topic)),
+        }
+    }
+}
+
+impl From<RealtimeEvent> for WebSocketMessage {
+    fn from(event: RealtimeEvent) -> Self {
+        Self {
+            event_type: event.event_type.clone(),
+            target_users: event.target_users.clone(),
+            topics: event.topics.clone(),
+            payload: serde_json::to_value(&event).unwrap_or(serde_json::json!({})),
+        }
+    }
 }
 
 impl WebSocketManagerInner {
@@ -67,6 +99,23 @@ impl WebSocketManagerInner {
     pub fn subscribe(&self) -> broadcast::Receiver<WebSocketMessage> {
         self.tx.subscribe()
     }
+
+    /// Relays events that services publish on `erp_core::events` to connected clients.
+    pub fn forward_realtime_events(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let manager = Arc::clone(self);
+        let mut events = erp_core::events::subscribe();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => manager.broadcast(event.into()),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Websocket relay dropped {} realtime events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
 }
 
 impl Default for WebSocketManagerInner {
@@ -80,19 +129,136 @@ pub struct WebSocketQuery {
     pub token: Option<String>,
 }
 
+/// Upgrades to a WebSocket for the user identified by `?token=<jwt>`.
+///
+/// Clients receive events addressed to them, broadcasts, and events on topics they subscribe
+/// to by sending `{"action": "subscribe", "topic": "sales_order:<id>"}` (or `"unsubscribe"`).
+/// Subscribing needs the same permission and row scope as reading the document.
+/// `{"action": "ping"}` is answered with a `pong`.
 pub async fn websocket_handler(
-    State(_state): State<AppState>,
-    Query(_query): Query<WebSocketQuery>,
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    Query(query): Query<WebSocketQuery>,
 ) -> Response {
-    Response::builder()
-        .status(501)
-        .body(axum::body::Body::from("WebSocket not implemented"))
-        .unwrap_or_else(|_| {
-            axum::response::IntoResponse::into_response((
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to build response",
-            ))
-        })
+    let token_data = match query.token {
+        Some(token) => state.auth_svc.validate_token(&token).await.ok(),
+        None => None,
+    };
+    let user_id = token_data.and_then(|token_data| Uuid::parse_str(&token_data.user_id).ok());
+
+    match user_id {
+        Some(user_id) => ws.on_upgrade(move |socket| handle_socket(socket, state, user_id)),
+        None => (StatusCode::UNAUTHORIZED, "Missing or invalid token").into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ClientCommand {
+    Subscribe { topic: String },
+    Unsubscribe { topic: String },
+    Ping,
+}
+
+async fn handle_socket(mut socket: WebSocket, state: AppState, user_id: Uuid) {
+    let client_id = Uuid::new_v4();
+    let mut messages = state.ws_manager.subscribe();
+    state.ws_manager.add_client(user_id, client_id).await;
+
+    let mut topics: HashSet<String> = HashSet::new();
+    let mut connected = send_json(&mut socket, serde_json::json!({ "type": "connected", "client_id": client_id })).await;
+
+    while connected {
+        tokio::select! {
+            incoming = socket.recv() => {
+                connected = match incoming {
+                    Some(Ok(Message::Text(text))) => {
+                        let reply = handle_command(&state, user_id, &text, &mut topics).await;
+                        send_json(&mut socket, reply).await
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => false,
+                    Some(Ok(_)) => true,
+                };
+            }
+            outgoing = messages.recv() => {
+                connected = match outgoing {
+                    Ok(message) if message.is_for(user_id, &topics) => {
+                        send_json(&mut socket, serde_json::json!({
+                            "type": "event",
+                            "event_type": message.event_type,
+                            "topics": message.topics,
+                            "payload": message.payload,
+                        })).await
+                    }
+                    Ok(_) => true,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        send_json(&mut socket, serde_json::json!({ "type": "lagged", "skipped": skipped })).await
+                    }
+                    Err(broadcast::error::RecvError::Closed) => false,
+                };
+            }
+        }
+    }
+
+    state.ws_manager.remove_client(user_id, client_id).await;
+}
+
+async fn handle_command(state: &AppState, user_id: Uuid, text: &str, topics: &mut HashSet<String>) -> serde_json::Value {
+    match serde_json::from_str::<ClientCommand>(text) {
+        Ok(ClientCommand::Subscribe { topic }) => {
+            if topics.len() >= MAX_TOPICS_PER_CLIENT && !topics.contains(&topic) {
+                return serde_json::json!({ "type": "error", "message": "Too many subscriptions" });
+            }
+            match can_subscribe(state, user_id, &topic).await {
+                Ok(true) => {}
+                Ok(false) => return serde_json::json!({ "type": "error", "topic": topic, "message": "Not allowed to subscribe to this topic" }),
+                Err(e) => {
+                    tracing::error!("Failed to authorize websocket subscription: {}", e);
+                    return serde_json::json!({ "type": "error", "topic": topic, "message": "Subscription could not be authorized" });
+                }
+            }
+            topics.insert(topic.clone());
+            serde_json::json!({ "type": "subscribed", "topic": topic })
+        }
+        Ok(ClientCommand::Unsubscribe { topic }) => {
+            topics.remove(&topic);
+            serde_json::json!({ "type": "unsubscribed", "topic": topic })
+        }
+        Ok(ClientCommand::Ping) => serde_json::json!({ "type": "pong" }),
+        Err(e) => serde_json::json!({ "type": "error", "message": format!("Invalid command: {}", e) }),
+    }
+}
+
+/// Document topics (`<entity>:<id>`) need the entity's read permission and the row to be in the
+/// caller's row scope; `approvals` needs access to approval requests. Anything else is refused.
+async fn can_subscribe(state: &AppState, user_id: Uuid, topic: &str) -> erp_core::Result<bool> {
+    let user = user_id.to_string();
+    if topic == "approvals" {
+        return state.authz.check(&user, "approvals:requests:read").await;
+    }
+    let Some((name, id)) = topic.split_once(':') else {
+        return Ok(false);
+    };
+    let Some(entity) = erp_graphql::subscription::entity(name) else {
+        return Ok(false);
+    };
+    if !state.authz.check(&user, entity.permission).await? {
+        return Ok(false);
+    }
+    let Some(resource) = entity.resource else {
+        return Ok(true);
+    };
+    let scope = AccessPolicy::for_user(&state.pool, &user).await.map_err(|e| e.0)?.row_scope(resource);
+    if scope.is_unrestricted() {
+        return Ok(true);
+    }
+    let sql = format!("SELECT EXISTS (SELECT 1 FROM {} WHERE id = ?{})", entity.table, scope.sql());
+    let (visible,): (bool,) = scope.bind_query_as(sqlx::query_as(&sql).bind(id)).fetch_one(&state.pool).await?;
+    Ok(visible)
+}
+
+async fn send_json(socket: &mut WebSocket, value: serde_json::Value) -> bool {
+    socket.send(Message::Text(value.to_string())).await.is_ok()
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -127,6 +293,7 @@ impl NotificationEvent {
             event_type: self.event_type.clone(),
             payload: serde_json::to_value(self).unwrap_or(serde_json::json!({})),
             target_users: Some(target_users),
+            topics: Vec::new(),
         }
     }
 }
@@ -141,6 +308,17 @@ pub fn notify_all(ws_manager: &WebSocketManager, event: NotificationEvent) {
         event_type: event.event_type.clone(),
         payload: serde_json::to_value(event).unwrap_or(serde_json::json!({})),
         target_users: None,
+        topics: Vec::new(),
+    };
+    ws_manager.broadcast(msg);
+}
+
+pub fn notify_topic(ws_manager: &WebSocketManager, topic: &str, event: NotificationEvent) {
+    let msg = WebSocketMessage {
+        event_type: event.event_type.clone(),
+        payload: serde_json::to_value(event).unwrap_or(serde_json::json!({})),
+        target_users: None,
+        topics: vec![topic.to_string()],
     };
     ws_manager.broadcast(msg);
 }
@@ -153,3 +331,4 @@ pub async fn get_ws_stats(
         "total_connections": total
     })))
 }
+
@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::error::ApiResult;
 use crate::db::AppState;
+use crate::middleware::RequirePermission;
 
 #[derive(Debug, Deserialize)]
 pub struct ListQuery {
@@ -146,13 +147,13 @@ pub async fn optimize_wave(
 
 pub fn routes() -> axum::Router<crate::db::AppState> {
     axum::Router::new()
-        .route("/locations", axum::routing::get(list_locations).post(create_location))
-        .route("/locations/:id", axum::routing::get(get_location))
-        .route("/waves", axum::routing::post(create_wave))
-        .route("/waves/:id/release", axum::routing::post(release_wave))
-        .route("/pick-tasks", axum::routing::post(create_pick_task))
-        .route("/pick-tasks/:id/complete", axum::routing::post(complete_pick))
-        .route("/cycle-counts", axum::routing::post(create_cycle_count))
-        .route("/receipts", axum::routing::post(create_receipt))
-        .route("/optimize-wave", axum::routing::post(optimize_wave))
+        .route("/locations", axum::routing::get(list_locations).post(create_location).require("wms:locations"))
+        .route("/locations/:id", axum::routing::get(get_location).require("wms:locations:read"))
+        .route("/waves", axum::routing::post(create_wave).require("wms:waves:write"))
+        .route("/waves/:id/release", axum::routing::post(release_wave).require("wms:waves:write"))
+        .route("/pick-tasks", axum::routing::post(create_pick_task).require("wms:pick-tasks:write"))
+        .route("/pick-tasks/:id/complete", axum::routing::post(complete_pick).require("wms:pick-tasks:write"))
+        .route("/cycle-counts", axum::routing::post(create_cycle_count).require("wms:cycle-counts:write"))
+        .route("/receipts", axum::routing::post(create_receipt).require("wms:receipts:write"))
+        .route("/optimize-wave", axum::routing::post(optimize_wave).require("wms:optimize-wave:write"))
 }
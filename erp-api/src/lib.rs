@@ -1,9 +1,12 @@
 pub mod config;
 pub mod db;
+pub mod migrate;
 pub mod routes;
 pub mod handlers;
 pub mod error;
 pub mod middleware;
+pub mod policy;
+pub mod worker;
 
 pub use config::Config;
 pub use db::AppState;
@@ -13,6 +13,9 @@ async fn main() -> Result<()> {
     erp_auth::init_jwt_secret(&config.jwt_secret)?;
     
     let state = erp_api::AppState::new(config.clone()).await?;
+    if config.job_worker_enabled {
+        erp_api::worker::start(&state).await?;
+    }
     let app = erp_api::routes::create_router(state);
 
     let addr = format!("{}:{}", config.server_host, config.server_port);
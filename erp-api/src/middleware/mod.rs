@@ -1,3 +1,5 @@
+pub mod permission;
 pub mod rate_limit;
 
+pub use permission::RequirePermission;
 pub use rate_limit::{RateLimiter, rate_limit_middleware};
@@ -0,0 +1,66 @@
+use axum::{
+    body::Body,
+    extract::MatchedPath,
+    http::{Method, Request},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::MethodRouter,
+};
+use erp_auth::Authorizer;
+use erp_core::Error;
+use std::sync::Arc;
+use crate::error::ApiError;
+use crate::handlers::auth::AuthUser;
+
+/// Path verbs that are their own action rather than a plain write.
+const VERB_ACTIONS: &[&str] = &["approve", "reject", "post", "reverse", "void"];
+
+pub trait RequirePermission {
+    /// Requires the caller to hold `permission` on every method registered so far. A two-segment
+    /// `module:resource` takes its action from the request: `read` for GET, `delete` for DELETE,
+    /// the path's verb for approve/reject/post/reverse/void, and `write` otherwise.
+    fn require(self, permission: &'static str) -> Self;
+}
+
+impl<S: Clone + Send + Sync + 'static> RequirePermission for MethodRouter<S> {
+    fn require(self, permission: &'static str) -> Self {
+        self.route_layer(middleware::from_fn(move |req, next| authorize(permission, req, next)))
+    }
+}
+
+async fn authorize(required: &'static str, req: Request<Body>, next: Next) -> Response {
+    let Some(AuthUser(user)) = req.extensions().get::<AuthUser>().cloned() else {
+        return ApiError(Error::Unauthorized).into_response();
+    };
+    let Some(authorizer) = req.extensions().get::<Arc<Authorizer>>().cloned() else {
+        return ApiError(Error::internal("Authorizer missing from request")).into_response();
+    };
+
+    let permission = required_permission(required, &req);
+    match authorizer.check(&user.user_id, &permission).await {
+        Ok(true) => next.run(req).await,
+        Ok(false) => ApiError(Error::forbidden(format!("missing permission {}", permission))).into_response(),
+        Err(Error::NotFound(_)) => ApiError(Error::Unauthorized).into_response(),
+        Err(e) => ApiError(e).into_response(),
+    }
+}
+
+fn required_permission(required: &'static str, req: &Request<Body>) -> String {
+    if required.matches(':').count() >= 2 {
+        return required.to_string();
+    }
+    let last = req.extensions().get::<MatchedPath>().and_then(|m| m.as_str().rsplit('/').next().map(str::to_string));
+    format!("{}:{}", required, default_action(req.method(), last.as_deref()))
+}
+
+/// The action for a request to a route whose path ends in `last`.
+fn default_action(method: &Method, last: Option<&str>) -> &'static str {
+    if let Some(verb) = last.and_then(|last| VERB_ACTIONS.iter().find(|v| **v == last)) {
+        return verb;
+    }
+    match *method {
+        Method::GET | Method::HEAD => "read",
+        Method::DELETE => "delete",
+        _ => "write",
+    }
+}
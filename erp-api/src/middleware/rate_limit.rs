@@ -119,7 +119,10 @@ impl RateLimiter {
 }
 
 fn is_auth_endpoint(path: &str) -> bool {
-    path == "/auth/login" || path == "/auth/register"
+    matches!(
+        path,
+        "/auth/login" | "/auth/login/2fa" | "/auth/register" | "/auth/password-reset/request" | "/auth/password-reset/confirm"
+    )
 }
 
 pub async fn rate_limit_middleware(
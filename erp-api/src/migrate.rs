@@ -0,0 +1,386 @@
+//! Versioned schema migrations.
+//!
+//! Every `migrations/<version>.sql` file is embedded at build time and applied in file-name order,
+//! each in its own transaction. `schema_migrations` records the SHA-256 of every applied file, so a
+//! migration edited or deleted after it ran is reported instead of silently skipped. A sibling
+//! `<version>.down.sql`, where one exists, reverts the migration. The files that predate
+//! `schema_migrations` form a baseline that is loaded from `migrations/baseline/schema.sql` instead.
+
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use sqlx::{Connection, Executor, SqliteConnection, SqlitePool};
+use std::time::Instant;
+
+include!(concat!(env!("OUT_DIR"), "/migrations.rs"));
+
+/// Every file shipped before `schema_migrations` existed. They do not apply cleanly as a set, so
+/// they are never executed: a database with no tracked migrations loads `BASELINE_SCHEMA`, the
+/// schema and seed rows they produce, and records them as applied.
+const LEGACY_BASELINE: &[&str] = &[
+    "20240101000000_finance",
+    "20240101000001_inventory",
+    "20240101000002_sales",
+    "20240101000003_purchasing",
+    "20240101000004_manufacturing",
+    "20240101000005_hr",
+    "20240101000006_auth",
+    "20240101000007_audit",
+    "20240101000008_quotations",
+    "20240101000009_workflows",
+    "20240101000010_attachments",
+    "20240101000011_extended_features",
+    "20240101000012_advanced_features",
+    "20240101000013_enterprise_features",
+    "20240101000014_indexes",
+    "20240101000015_service_management",
+    "20240101000016_it_assets",
+    "20240101000017_compliance",
+    "20240101000018_enterprise_additions",
+    "20240101000019_new_modules",
+    "20240101000020_ai_portals_iot_automation",
+    "20240101000021_new_enterprise_modules",
+    "20240101000022_enterprise_integration_features",
+    "20240101000023_new_enterprise_features",
+    "20240101000024_enterprise_features_expansion",
+    "20240101000025_enterprise_security_features",
+    "20240101000026_enterprise_infrastructure_features",
+    "20240101000027_enterprise_cpq_clm_commission_aps_spend_compensation",
+    "20240101000030_credit_notes",
+    "20240101200000_tms",
+    "20240101200100_plm",
+    "20240101200200_mdm",
+    "20240101200300_fsm",
+    "20240101200400_tpm",
+    "20240101200500_enterprise_wms_demand_edi_tenant_revrec_intercompany_lms",
+    "20240101200600_reportscheduling_chat_calendar_signing_email",
+    "20240101200700_assistant_ocr_fraud_processmining",
+    "20240101200700_lease_bank_loyalty_partner_pcard_territory_predictive",
+    "20240101200800_mrp_eam_valuation_cash_supplier",
+    "202401012009_budgeting_grant_abc_dataquality_knowledge",
+    "20240101201000_promotions",
+    "20240202000000_bi_i18n_push_bpm_graphql",
+    "20240227000000_kanban",
+    "20240301000000_currency_revaluation",
+    "20240302000000_approval_workflow",
+    "20240303000000_credit_management",
+    "20240304000000_stripe_payments",
+    "20240305000000_dropship",
+    "20240306000000_bundles",
+    "20240307000000_warranty",
+    "20240308000000_eam",
+    "20240309000000_vendor_bills",
+    "20250228160000_fix_work_orders",
+    "20260302000000_inventory_adjustments",
+    "20260302100000_stock_transfers",
+    "20260303100000_payment_terms",
+    "20260305000000_shift_scheduling",
+    "20260306100000_quality_enhancements",
+    "20260306110000_notes",
+    "20260307000000_product_variants",
+    "20260307120000_gift_cards",
+    "20260307150000_favorites",
+    "20260307160000_cycle_counting",
+    "20260307170000_landed_costs",
+    "20260307180000_subcontracting",
+    "20260307200000_quality_capa_calibration",
+];
+
+const BASELINE_SCHEMA: &str = include_str!("../../migrations/baseline/schema.sql");
+
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub version: &'static str,
+    pub checksum: String,
+    up: &'static str,
+    down: Option<&'static str>,
+}
+
+impl Migration {
+    pub fn new(version: &'static str, up: &'static str, down: Option<&'static str>) -> Self {
+        Self { version, checksum: checksum(up), up, down }
+    }
+
+    pub fn reversible(&self) -> bool {
+        self.down.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    /// Applied, but the file has changed since.
+    Modified,
+    /// Recorded as applied, but no longer shipped.
+    Missing,
+}
+
+impl std::fmt::Display for MigrationState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            MigrationState::Applied => "applied",
+            MigrationState::Pending => "pending",
+            MigrationState::Modified => "MODIFIED",
+            MigrationState::Missing => "MISSING",
+        };
+        f.write_str(label)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: String,
+    pub state: MigrationState,
+    pub applied_at: Option<String>,
+    pub reversible: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct AppliedRow {
+    version: String,
+    checksum: String,
+    applied_at: String,
+}
+
+pub struct Migrator {
+    migrations: Vec<Migration>,
+    baseline: Option<(&'static [&'static str], &'static str)>,
+}
+
+impl Migrator {
+    pub fn new(mut migrations: Vec<Migration>) -> Self {
+        migrations.sort_by(|a, b| a.version.cmp(b.version));
+        Self { migrations, baseline: None }
+    }
+
+    /// The migrations compiled into this binary.
+    pub fn embedded() -> Self {
+        Self::new(EMBEDDED.iter().map(|(version, up, down)| Migration::new(version, up, *down)).collect())
+            .with_baseline(LEGACY_BASELINE, BASELINE_SCHEMA)
+    }
+
+    /// Treats `versions` as already applied on an untracked database once `schema` has been loaded
+    /// into it. `schema` must only create what is missing, since the database may already hold it.
+    pub fn with_baseline(mut self, versions: &'static [&'static str], schema: &'static str) -> Self {
+        self.baseline = Some((versions, schema));
+        self
+    }
+
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    pub async fn status(&self, pool: &SqlitePool) -> Result<Vec<MigrationStatus>> {
+        ensure_table(pool).await?;
+        let applied: Vec<AppliedRow> = sqlx::query_as("SELECT version, checksum, applied_at FROM schema_migrations ORDER BY version")
+            .fetch_all(pool)
+            .await?;
+
+        let mut statuses: Vec<MigrationStatus> = self.migrations.iter().map(|migration| {
+            let row = applied.iter().find(|row| row.version == migration.version);
+            let state = match row {
+                None => MigrationState::Pending,
+                Some(row) if row.checksum == migration.checksum => MigrationState::Applied,
+                Some(_) => MigrationState::Modified,
+            };
+            MigrationStatus {
+                version: migration.version.to_string(),
+                state,
+                applied_at: row.map(|row| row.applied_at.clone()),
+                reversible: migration.reversible(),
+            }
+        }).collect();
+
+        for row in applied {
+            if !self.migrations.iter().any(|migration| migration.version == row.version) {
+                statuses.push(MigrationStatus {
+                    version: row.version,
+                    state: MigrationState::Missing,
+                    applied_at: Some(row.applied_at),
+                    reversible: false,
+                });
+            }
+        }
+        statuses.sort_by(|a, b| a.version.cmp(&b.version));
+        Ok(statuses)
+    }
+
+    /// Fails if any applied migration was edited or removed.
+    pub async fn verify(&self, pool: &SqlitePool) -> Result<()> {
+        let problems: Vec<String> = self.status(pool).await?.into_iter().filter_map(|status| match status.state {
+            MigrationState::Modified => Some(format!("{} was edited after it was applied", status.version)),
+            MigrationState::Missing => Some(format!("{} was applied but its file is missing", status.version)),
+            _ => None,
+        }).collect();
+
+        if !problems.is_empty() {
+            bail!("schema_migrations does not match the migration files: {}", problems.join("; "));
+        }
+        Ok(())
+    }
+
+    /// Applies pending migrations in order, stopping after `target` when given. Returns the
+    /// versions applied.
+    pub async fn up(&self, pool: &SqlitePool, target: Option<&str>) -> Result<Vec<String>> {
+        if let Some(target) = target {
+            if !self.migrations.iter().any(|migration| migration.version == target) {
+                bail!("Unknown migration {}", target);
+            }
+        }
+
+        ensure_table(pool).await?;
+        self.apply_baseline(pool).await?;
+        self.verify(pool).await?;
+
+        let pending: Vec<String> = self.status(pool).await?.into_iter()
+            .filter(|status| status.state == MigrationState::Pending)
+            .map(|status| status.version)
+            .collect();
+
+        let mut applied = Vec::new();
+        for migration in &self.migrations {
+            if target.is_some_and(|target| migration.version > target) {
+                break;
+            }
+            if !pending.iter().any(|version| version == migration.version) {
+                continue;
+            }
+
+            let started = Instant::now();
+            let mut tx = pool.begin().await?;
+            if let Err(e) = tx.execute(migration.up).await {
+                tx.rollback().await?;
+                bail!("Migration {} failed: {}", migration.version, e);
+            }
+            record(&mut tx, migration, started.elapsed().as_millis() as i64, false).await?;
+            tx.commit().await?;
+            tracing::info!("Applied migration {}", migration.version);
+            applied.push(migration.version.to_string());
+        }
+        Ok(applied)
+    }
+
+    /// Reverts the `steps` most recently applied migrations, newest first. Every one of them needs a
+    /// down script; nothing is reverted otherwise.
+    pub async fn down(&self, pool: &SqlitePool, steps: usize) -> Result<Vec<String>> {
+        self.verify(pool).await?;
+        let versions: Vec<(String,)> = sqlx::query_as("SELECT version FROM schema_migrations ORDER BY version DESC LIMIT ?")
+            .bind(steps as i64)
+            .fetch_all(pool)
+            .await?;
+
+        let mut plan = Vec::new();
+        for (version,) in &versions {
+            let migration = self.migrations.iter().find(|migration| migration.version == version)
+                .ok_or_else(|| anyhow!("Migration {} is not known to this build", version))?;
+            let down = migration.down.ok_or_else(|| anyhow!("Migration {} has no down script", version))?;
+            plan.push((migration.version, down));
+        }
+
+        let mut reverted = Vec::new();
+        for (version, down) in plan {
+            let mut tx = pool.begin().await?;
+            if let Err(e) = tx.execute(down).await {
+                tx.rollback().await?;
+                bail!("Reverting {} failed: {}", version, e);
+            }
+            sqlx::query("DELETE FROM schema_migrations WHERE version = ?")
+                .bind(version)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            tracing::info!("Reverted migration {}", version);
+            reverted.push(version.to_string());
+        }
+        Ok(reverted)
+    }
+
+    /// Loads the baseline schema into a database with no tracked migrations, whether new or built by
+    /// the untracked runner, and records the baseline files as applied without running them.
+    async fn apply_baseline(&self, pool: &SqlitePool) -> Result<()> {
+        let Some((versions, schema)) = self.baseline else {
+            return Ok(());
+        };
+        let (tracked,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM schema_migrations").fetch_one(pool).await?;
+        if tracked > 0 {
+            return Ok(());
+        }
+
+        let mut tx = pool.begin().await?;
+        if let Err(e) = tx.execute(schema).await {
+            tx.rollback().await?;
+            bail!("Loading the baseline schema failed: {}", e);
+        }
+        add_missing_columns(&mut tx, schema).await?;
+        for migration in self.migrations.iter().filter(|migration| versions.contains(&migration.version)) {
+            record(&mut tx, migration, 0, true).await?;
+        }
+        tx.commit().await?;
+        tracing::info!("Recorded {} baseline migrations", versions.len());
+        Ok(())
+    }
+}
+
+/// Adds the columns `schema` defines that an existing table lacks. The untracked runner applied only
+/// some of the baseline files, so a table it created can predate columns another of them defines.
+async fn add_missing_columns(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>, schema: &str) -> Result<()> {
+    let mut reference = SqliteConnection::connect("sqlite::memory:").await?;
+    reference.execute(schema).await?;
+    let tables: Vec<String> = sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table' AND sql LIKE 'CREATE TABLE%'")
+        .fetch_all(&mut reference)
+        .await?;
+
+    for table in tables {
+        let existing: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+            .bind(&table)
+            .fetch_all(&mut **tx)
+            .await?;
+        let columns: Vec<(String, String, bool, Option<String>)> = sqlx::query_as("SELECT name, type, \"notnull\", dflt_value FROM pragma_table_info(?)")
+            .bind(&table)
+            .fetch_all(&mut reference)
+            .await?;
+        for (name, column_type, not_null, default) in columns.into_iter().filter(|c| !existing.contains(&c.0)) {
+            // Rows already in the table have no value for it, so NOT NULL only holds with a default.
+            let constraint = match default {
+                Some(default) if not_null => format!(" NOT NULL DEFAULT {}", default),
+                Some(default) => format!(" DEFAULT {}", default),
+                None => String::new(),
+            };
+            tx.execute(format!("ALTER TABLE \"{}\" ADD COLUMN \"{}\" {}{}", table, name, column_type, constraint).as_str()).await?;
+        }
+    }
+    reference.close().await?;
+    Ok(())
+}
+
+fn checksum(sql: &str) -> String {
+    format!("{:x}", Sha256::digest(sql.as_bytes()))
+}
+
+async fn ensure_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version TEXT PRIMARY KEY,
+            checksum TEXT NOT NULL,
+            applied_at TEXT NOT NULL,
+            execution_ms INTEGER NOT NULL DEFAULT 0,
+            baseline INTEGER NOT NULL DEFAULT 0
+        )",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn record(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>, migration: &Migration, execution_ms: i64, baseline: bool) -> Result<()> {
+    sqlx::query("INSERT INTO schema_migrations (version, checksum, applied_at, execution_ms, baseline) VALUES (?, ?, ?, ?, ?)")
+        .bind(migration.version)
+        .bind(&migration.checksum)
+        .bind(Utc::now().to_rfc3339())
+        .bind(execution_ms)
+        .bind(baseline)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,344 @@
+use std::collections::HashSet;
+
+use erp_auth::jwt::TokenData;
+use erp_auth::DataFilterType;
+use erp_core::RowScope;
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use crate::error::ApiResult;
+
+pub const CUSTOMERS: &str = "customers";
+pub const SALES_ORDERS: &str = "sales_orders";
+pub const EMPLOYEES: &str = "employees";
+pub const EXPENSE_REPORTS: &str = "expense_reports";
+
+/// How rows of a resource relate to the people that Own/Department/Team filters are about.
+enum Owner {
+    /// `created_by` holds the id of the user that owns the row.
+    CreatedBy,
+    /// The named column holds the id of the employee the row belongs to.
+    Employee(&'static str),
+}
+
+struct ResourceRule {
+    resource: &'static str,
+    table: &'static str,
+    owner: Owner,
+    /// Columns a `Custom` filter may compare against.
+    custom_columns: &'static [&'static str],
+    /// Built-in roles that see every row when no data permission covers the resource; other
+    /// roles see only their own rows.
+    full_access_roles: &'static [&'static str],
+}
+
+const RULES: [ResourceRule; 4] = [
+    ResourceRule { resource: CUSTOMERS, table: "customers", owner: Owner::CreatedBy, custom_columns: &["code", "status", "billing_country", "billing_state"], full_access_roles: &["Sales", "Finance"] },
+    ResourceRule { resource: SALES_ORDERS, table: "sales_orders", owner: Owner::CreatedBy, custom_columns: &["customer_id", "status"], full_access_roles: &["Sales", "Finance", "Warehouse"] },
+    ResourceRule { resource: EMPLOYEES, table: "employees", owner: Owner::Employee("id"), custom_columns: &["department_id", "position_id", "manager_id", "status"], full_access_roles: &["HR"] },
+    ResourceRule { resource: EXPENSE_REPORTS, table: "expense_reports", owner: Owner::Employee("employee_id"), custom_columns: &["employee_id", "status"], full_access_roles: &["HR", "Finance"] },
+];
+
+/// Keys of the list envelopes responses wrap records in; see [`strip_fields`].
+const ENVELOPE_KEYS: [&str; 10] = ["items", "data", "results", "records", "total", "page", "per_page", "total_pages", "next_cursor", "has_more"];
+
+struct DataRule {
+    resource: String,
+    filter_type: Option<DataFilterType>,
+    filter_value: String,
+}
+
+struct FieldRule {
+    resource: String,
+    field_name: String,
+    can_read: bool,
+}
+
+/// The caller's data and field permissions, gathered from every role they hold: assigned roles,
+/// the built-in role and the parents of both. A user with several roles sees a row if any role
+/// allows it, and a field unless a role hides it and none grants it. Admins are unrestricted;
+/// without a data permission for a resource, the user's built-in role decides between every row
+/// and only their own.
+pub struct AccessPolicy {
+    user_id: String,
+    role: String,
+    bypass: bool,
+    employee_id: Option<String>,
+    department_id: Option<String>,
+    data_rules: Vec<DataRule>,
+    field_rules: Vec<FieldRule>,
+}
+
+impl AccessPolicy {
+    /// The caller's policy. The role is read from the database rather than the token, so a role
+    /// change applies to tokens issued before it.
+    pub async fn load(pool: &SqlitePool, user: &TokenData) -> ApiResult<Self> {
+        Self::for_user(pool, &user.user_id).await
+    }
+
+    /// The policy of a user outside a request, such as the owner of a scheduled job.
+    pub async fn for_user(pool: &SqlitePool, user_id: &str) -> ApiResult<Self> {
+        let role: Option<String> = sqlx::query_scalar("SELECT role FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?;
+        let role = role.ok_or_else(|| erp_core::Error::not_found("User", user_id))?;
+        Self::build(pool, user_id, &role).await
+    }
+
+    async fn build(pool: &SqlitePool, user_id: &str, role: &str) -> ApiResult<Self> {
+        let bypass = role == erp_auth::UserRole::Admin.as_str();
+        let (employee_id, department_id): (Option<String>, Option<String>) = sqlx::query_as(
+            "SELECT u.employee_id, e.department_id FROM users u LEFT JOIN employees e ON e.id = u.employee_id WHERE u.id = ?"
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+        .unwrap_or((None, None));
+
+        let mut policy = Self {
+            user_id: user_id.to_string(),
+            role: role.to_string(),
+            bypass,
+            employee_id,
+            department_id,
+            data_rules: Vec::new(),
+            field_rules: Vec::new(),
+        };
+        if bypass {
+            return Ok(policy);
+        }
+
+        // The same roles the permission checks see: assigned, the built-in one and their parents.
+        let (held, _) = erp_auth::resolve_permissions(pool, user_id).await?;
+        if held.roles.is_empty() {
+            return Ok(policy);
+        }
+        let placeholders = vec!["?"; held.roles.len()].join(", ");
+
+        let sql = format!("SELECT resource, filter_type, filter_value FROM data_permissions WHERE role_id IN ({})", placeholders);
+        let mut query = sqlx::query_as(&sql);
+        for role in &held.roles {
+            query = query.bind(&role.role_id);
+        }
+        let data_rows: Vec<(String, String, String)> = query.fetch_all(pool).await?;
+        policy.data_rules = data_rows.into_iter()
+            .map(|(resource, filter_type, filter_value)| DataRule {
+                resource,
+                filter_type: parse_filter_type(&filter_type),
+                filter_value,
+            })
+            .collect();
+
+        let sql = format!("SELECT resource, field_name, can_read FROM field_permissions WHERE role_id IN ({})", placeholders);
+        let mut query = sqlx::query_as(&sql);
+        for role in &held.roles {
+            query = query.bind(&role.role_id);
+        }
+        let field_rows: Vec<(String, String, bool)> = query.fetch_all(pool).await?;
+        policy.field_rules = field_rows.into_iter()
+            .map(|(resource, field_name, can_read)| FieldRule { resource, field_name, can_read })
+            .collect();
+
+        Ok(policy)
+    }
+
+    /// The rows of `resource` the caller may read, as predicates on the resource's table.
+    pub fn row_scope(&self, resource: &str) -> RowScope {
+        let Some(rule) = RULES.iter().find(|r| r.resource == resource) else {
+            return RowScope::unrestricted();
+        };
+        if self.bypass {
+            return RowScope::unrestricted();
+        }
+        let baseline;
+        let mut rules: Vec<&DataRule> = self.data_rules.iter().filter(|r| r.resource == resource).collect();
+        if rules.is_empty() {
+            let filter_type = if rule.full_access_roles.contains(&self.role.as_str()) { DataFilterType::All } else { DataFilterType::Own };
+            baseline = DataRule { resource: resource.to_string(), filter_type: Some(filter_type), filter_value: String::new() };
+            rules.push(&baseline);
+        }
+
+        let mut clauses = Vec::new();
+        let mut binds = Vec::new();
+        for data_rule in rules {
+            match self.predicate(rule, data_rule) {
+                Predicate::All => return RowScope::unrestricted(),
+                Predicate::None => {}
+                Predicate::Where(clause, values) => {
+                    clauses.push(format!("({})", clause));
+                    binds.extend(values);
+                }
+            }
+        }
+        if clauses.is_empty() {
+            return RowScope::deny();
+        }
+        RowScope::unrestricted().and(clauses.join(" OR "), binds)
+    }
+
+    fn predicate(&self, rule: &ResourceRule, data_rule: &DataRule) -> Predicate {
+        let table = rule.table;
+        let value = data_rule.filter_value.trim();
+        match data_rule.filter_type {
+            Some(DataFilterType::All) => Predicate::All,
+            Some(DataFilterType::Own) => match &rule.owner {
+                Owner::CreatedBy => Predicate::Where(format!("{}.created_by = ?", table), vec![self.user_id.clone()]),
+                Owner::Employee(column) => match &self.employee_id {
+                    Some(employee_id) => Predicate::Where(format!("{}.{} = ?", table, column), vec![employee_id.clone()]),
+                    None => Predicate::None,
+                },
+            },
+            Some(DataFilterType::Department) => {
+                let departments: Vec<String> = if value.is_empty() {
+                    self.department_id.iter().cloned().collect()
+                } else {
+                    value.split(',').map(|d| d.trim().to_string()).filter(|d| !d.is_empty()).collect()
+                };
+                if departments.is_empty() {
+                    return Predicate::None;
+                }
+                let placeholders = vec!["?"; departments.len()].join(", ");
+                owned_by_employees(rule, &format!("e.department_id IN ({})", placeholders), departments)
+            }
+            Some(DataFilterType::Team) => {
+                let manager = if value.is_empty() { self.employee_id.clone() } else { Some(value.to_string()) };
+                match manager {
+                    Some(manager) => owned_by_employees(rule, "e.id = ? OR e.manager_id = ?", vec![manager.clone(), manager]),
+                    None => Predicate::None,
+                }
+            }
+            Some(DataFilterType::Custom) => custom_predicate(rule, value),
+            None => Predicate::None,
+        }
+    }
+
+    /// Invoices are visible when their customer is.
+    pub fn invoice_scope(&self) -> RowScope {
+        let customers = self.row_scope(CUSTOMERS);
+        if customers.is_unrestricted() {
+            return customers;
+        }
+        RowScope::unrestricted().and(
+            format!("invoices.customer_id IN (SELECT customers.id FROM customers WHERE 1 = 1{})", customers.sql()),
+            customers.binds().to_vec(),
+        )
+    }
+
+    /// Fields of `resource` the caller may not read.
+    pub fn hidden_fields(&self, resource: &str) -> HashSet<&str> {
+        if self.bypass {
+            return HashSet::new();
+        }
+        let rules = self.field_rules.iter().filter(|r| r.resource == resource);
+        let granted: HashSet<&str> = rules.clone().filter(|r| r.can_read).map(|r| r.field_name.as_str()).collect();
+        rules.filter(|r| !r.can_read && !granted.contains(r.field_name.as_str()))
+            .map(|r| r.field_name.as_str())
+            .collect()
+    }
+
+    /// Serializes a response for `resource` with hidden fields removed. Works on single records,
+    /// arrays of records and list envelopes, and reaches into nested objects and arrays.
+    pub fn redact<T: Serialize>(&self, resource: &str, value: &T) -> ApiResult<serde_json::Value> {
+        let mut json = serde_json::to_value(value)?;
+        let hidden = self.hidden_fields(resource);
+        if !hidden.is_empty() {
+            strip_fields(&mut json, &hidden);
+        }
+        Ok(json)
+    }
+}
+
+enum Predicate {
+    All,
+    /// The filter cannot match anything for this caller.
+    None,
+    Where(String, Vec<String>),
+}
+
+fn parse_filter_type(value: &str) -> Option<DataFilterType> {
+    match value {
+        "All" => Some(DataFilterType::All),
+        "Own" => Some(DataFilterType::Own),
+        "Department" => Some(DataFilterType::Department),
+        "Team" => Some(DataFilterType::Team),
+        "Custom" => Some(DataFilterType::Custom),
+        _ => None,
+    }
+}
+
+/// Rows owned by the employees matching `employee_filter` (a predicate on `employees e`), or
+/// created by users linked to those employees.
+fn owned_by_employees(rule: &ResourceRule, employee_filter: &str, binds: Vec<String>) -> Predicate {
+    let clause = match &rule.owner {
+        Owner::Employee(column) => format!(
+            "{}.{} IN (SELECT e.id FROM employees e WHERE {})", rule.table, column, employee_filter
+        ),
+        Owner::CreatedBy => format!(
+            "{}.created_by IN (SELECT u.id FROM users u JOIN employees e ON e.id = u.employee_id WHERE {})",
+            rule.table, employee_filter
+        ),
+    };
+    Predicate::Where(clause, binds)
+}
+
+/// Parses `column=value` conditions separated by `;`. Unknown columns make the filter match
+/// nothing rather than being ignored.
+fn custom_predicate(rule: &ResourceRule, value: &str) -> Predicate {
+    let mut clauses = Vec::new();
+    let mut binds = Vec::new();
+    for condition in value.split(';').map(str::trim).filter(|c| !c.is_empty()) {
+        let Some((column, expected)) = condition.split_once('=') else {
+            return Predicate::None;
+        };
+        let column = column.trim();
+        if !rule.custom_columns.contains(&column) {
+            tracing::warn!(resource = rule.resource, column, "Denying rows for data permission on unsupported column");
+            return Predicate::None;
+        }
+        clauses.push(format!("{}.{} = ?", rule.table, column));
+        binds.push(expected.trim().to_string());
+    }
+    if clauses.is_empty() {
+        return Predicate::None;
+    }
+    Predicate::Where(clauses.join(" AND "), binds)
+}
+
+/// Removes hidden fields from every record in `value`. An object made only of envelope keys
+/// around at least one array is a list envelope and keeps its own keys; any other object is a
+/// record, and fields nested inside it are stripped too.
+fn strip_fields(value: &mut serde_json::Value, hidden: &HashSet<&str>) {
+    match value {
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|item| strip_fields(item, hidden)),
+        serde_json::Value::Object(map) => {
+            let envelope = map.values().any(|v| v.is_array()) && map.keys().all(|key| ENVELOPE_KEYS.contains(&key.as_str()));
+            if !envelope {
+                map.retain(|key, _| !hidden.contains(key.as_str()));
+            }
+            map.values_mut().for_each(|nested| strip_fields(nested, hidden));
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_strip_fields_reaches_envelopes_and_nested_records() {
+        let hidden: HashSet<&str> = ["email", "total"].into_iter().collect();
+        let mut page = json!({
+            "data": [{ "name": "Acme", "email": "a@example.com", "contact": { "email": "b@example.com" } }],
+            "total": 1,
+        });
+        strip_fields(&mut page, &hidden);
+        assert_eq!(page, json!({ "data": [{ "name": "Acme", "contact": {} }], "total": 1 }));
+
+        let mut order = json!({ "id": "1", "total": 10, "lines": [{ "total": 10 }] });
+        strip_fields(&mut order, &hidden);
+        assert_eq!(order, json!({ "id": "1", "lines": [{}] }));
+    }
+}
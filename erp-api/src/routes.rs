@@ -1,8 +1,9 @@
 use crate::db::AppState;
 use crate::handlers;
-use crate::middleware::{rate_limit_middleware, RateLimiter};
+use crate::middleware::{rate_limit_middleware, RateLimiter, RequirePermission};
 use axum::http::{header, HeaderValue, Method};
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
     routing::{delete, get, post, put},
     Extension, Router,
@@ -36,7 +37,14 @@ pub fn create_router(state: AppState) -> Router {
         .route("/health", get(handlers::health))
         .route("/auth/register", post(handlers::auth::register))
         .route("/auth/login", post(handlers::auth::login))
+        .route("/auth/login/2fa", post(handlers::auth::login_two_factor))
+        .route("/auth/refresh", post(handlers::auth::refresh))
+        .route("/auth/password-reset/request", post(handlers::auth::request_password_reset))
+        .route("/auth/password-reset/confirm", post(handlers::auth::confirm_password_reset))
+        .route("/payments/gateways/:id/webhook", post(handlers::payments::gateway_webhook))
         .route("/ws", get(handlers::websocket::websocket_handler))
+        .route("/graphql/ws", get(handlers::graphql::graphql_ws_handler))
+        .route("/files/:sha256", get(handlers::files::signed_download))
         .route(
             "/oauth/authorize",
             get(handlers::security::get_oauth_authorize_url),
@@ -51,6 +59,7 @@ pub fn create_router(state: AppState) -> Router {
 
     let protected_routes = Router::new()
         .route("/auth/me", get(handlers::auth::me))
+        .route("/auth/logout", post(handlers::auth::logout))
         .nest("/api/v1", api_routes(state.clone()))
         .layer(middleware::from_fn(rate_limit_middleware))
         .layer(Extension(rate_limiter.clone()))
@@ -87,12 +96,12 @@ fn api_routes(state: AppState) -> Router<AppState> {
         .nest("/automation", handlers::automation::routes())
         .nest("/bundles", bundles_routes())
         .nest("/quality", handlers::quality::routes())
-        .route("/audit-logs", get(handlers::audit::list_audit_logs))
+        .route("/audit-logs", get(handlers::audit::list_audit_logs).require("admin:audit-logs:read"))
         .merge(workflow_routes())
         .merge(attachment_routes())
         .merge(extended_routes())
-        .route("/export", get(handlers::import_export::export_csv))
-        .route("/import", post(handlers::import_export::import_csv))
+        .route("/export", get(handlers::import_export::export_csv).require("data:export:read"))
+        .merge(import_routes())
         .nest("/compliance", compliance_routes(state.clone()))
         .nest("/projects", projects_routes(state.clone()))
         .nest("/notifications", handlers::notifications::routes())
@@ -134,6 +143,7 @@ fn api_routes(state: AppState) -> Router<AppState> {
         .nest("/tpm", tpm_routes())
         .nest("/wms", handlers::wms::routes())
         .nest("/demand", handlers::demand::routes())
+        .nest("/edi", handlers::edi::routes())
         .nest("/lease", handlers::lease::routes())
         .nest("/bank", handlers::bank::routes())
         .nest("/loyalty", handlers::loyalty::routes())
@@ -148,7 +158,7 @@ fn api_routes(state: AppState) -> Router<AppState> {
         .nest("/i18n", handlers::i18n::routes())
         .nest("/push", handlers::push::routes())
         .nest("/bpm", handlers::bpm::routes())
-        .nest("/graphql", handlers::graphql::routes())
+        .nest("/graphql", graphql_routes())
         .nest("/assistant", handlers::assistant::routes())
         .nest("/ocr", handlers::ocr::routes())
         .nest("/fraud", handlers::fraud::routes())
@@ -168,39 +178,46 @@ fn api_routes(state: AppState) -> Router<AppState> {
         .nest("/notes", handlers::notes::routes())
         .nest("/favorites", handlers::favorites::routes())
         .nest("/credit-notes", handlers::credit_notes::routes())
-        .route("/ws-stats", get(handlers::websocket::get_ws_stats))
+        .route("/ws-stats", get(handlers::websocket::get_ws_stats).require("monitoring:websockets:read"))
 }
 
 fn compliance_routes(state: AppState) -> Router<AppState> {
     Router::new()
-        .route("/stats", get(handlers::compliance::stats))
+        .route("/stats", get(handlers::compliance::stats).require("compliance:stats:read"))
         .route(
             "/data-subjects",
             get(handlers::compliance::list_data_subjects)
-                .post(handlers::compliance::create_data_subject),
+                .post(handlers::compliance::create_data_subject)
+                .require("compliance:data-subjects"),
         )
         .route(
             "/consents",
-            get(handlers::compliance::list_consents).post(handlers::compliance::create_consent),
+            get(handlers::compliance::list_consents)
+                .post(handlers::compliance::create_consent)
+                .require("compliance:consents"),
         )
         .route(
             "/consents/:id/withdraw",
-            post(handlers::compliance::withdraw_consent),
+            post(handlers::compliance::withdraw_consent).require("compliance:consents:write"),
         )
         .route(
             "/dsars",
-            get(handlers::compliance::list_dsars).post(handlers::compliance::create_dsar),
+            get(handlers::compliance::list_dsars)
+                .post(handlers::compliance::create_dsar)
+                .require("compliance:dsars"),
         )
         .route(
             "/dsars/:id/complete",
-            post(handlers::compliance::complete_dsar),
+            post(handlers::compliance::complete_dsar).require("compliance:dsars:write"),
         )
         .route(
             "/breaches",
-            get(handlers::compliance::list_breaches).post(handlers::compliance::create_breach),
+            get(handlers::compliance::list_breaches)
+                .post(handlers::compliance::create_breach)
+                .require("compliance:breaches"),
         )
-        .route("/policies", get(handlers::compliance::list_policies))
-        .route("/processors", get(handlers::compliance::list_processors))
+        .route("/policies", get(handlers::compliance::list_policies).require("compliance:policies:read"))
+        .route("/processors", get(handlers::compliance::list_processors).require("compliance:processors:read"))
         .with_state(state)
 }
 
@@ -208,33 +225,37 @@ fn projects_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route(
             "/",
-            get(handlers::projects::list_projects).post(handlers::projects::create_project),
+            get(handlers::projects::list_projects)
+                .post(handlers::projects::create_project)
+                .require("projects:projects"),
         )
-        .route("/:id", get(handlers::projects::get_project))
-        .route("/:id/status", post(handlers::projects::update_status))
-        .route("/:id/tasks", get(handlers::projects::list_tasks))
-        .route("/tasks", post(handlers::projects::create_task))
+        .route("/:id", get(handlers::projects::get_project).require("projects:projects:read"))
+        .route("/:id/status", post(handlers::projects::update_status).require("projects:projects:write"))
+        .route("/:id/tasks", get(handlers::projects::list_tasks).require("projects:projects:read"))
+        .route("/tasks", post(handlers::projects::create_task).require("projects:tasks:write"))
         .route(
             "/tasks/:id/complete",
-            post(handlers::projects::complete_task),
+            post(handlers::projects::complete_task).require("projects:tasks:write"),
         )
-        .route("/:id/milestones", get(handlers::projects::list_milestones))
-        .route("/milestones", post(handlers::projects::create_milestone))
+        .route("/:id/milestones", get(handlers::projects::list_milestones).require("projects:projects:read"))
+        .route("/milestones", post(handlers::projects::create_milestone).require("projects:milestones:write"))
         .route(
             "/milestones/:id/complete",
-            post(handlers::projects::complete_milestone),
+            post(handlers::projects::complete_milestone).require("projects:milestones:write"),
         )
         .route(
             "/timesheets",
-            get(handlers::projects::list_timesheets).post(handlers::projects::create_timesheet),
+            get(handlers::projects::list_timesheets)
+                .post(handlers::projects::create_timesheet)
+                .require("projects:timesheets"),
         )
         .route(
             "/timesheets/:id/submit",
-            post(handlers::projects::submit_timesheet),
+            post(handlers::projects::submit_timesheet).require("projects:timesheets:write"),
         )
         .route(
             "/timesheets/:id/approve",
-            post(handlers::projects::approve_timesheet),
+            post(handlers::projects::approve_timesheet).require("projects:timesheets:approve"),
         )
         .with_state(state)
 }
@@ -243,120 +264,144 @@ fn finance_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route(
             "/accounts",
-            get(handlers::finance::list_accounts).post(handlers::finance::create_account),
+            get(handlers::finance::list_accounts)
+                .post(handlers::finance::create_account)
+                .require("finance:accounts"),
         )
         .route(
             "/accounts/:id",
             get(handlers::finance::get_account)
                 .put(handlers::finance::update_account)
-                .delete(handlers::finance::delete_account),
+                .delete(handlers::finance::delete_account)
+                .require("finance:accounts"),
         )
         .route(
             "/journal-entries",
             get(handlers::finance::list_journal_entries)
-                .post(handlers::finance::create_journal_entry),
+                .post(handlers::finance::create_journal_entry)
+                .require("finance:journals"),
         )
         .route(
             "/journal-entries/:id",
-            get(handlers::finance::get_journal_entry),
+            get(handlers::finance::get_journal_entry).require("finance:journals:read"),
         )
         .route(
             "/journal-entries/:id/post",
-            post(handlers::finance::post_journal_entry),
+            post(handlers::finance::post_journal_entry).require("finance:journals:post"),
+        )
+        .route(
+            "/journal-entries/:id/reverse",
+            post(handlers::finance::reverse_journal_entry).require("finance:journals:reverse"),
+        )
+        .route(
+            "/journal-entries/:id/schedule-reversal",
+            post(handlers::finance::schedule_journal_entry_reversal).require("finance:journals:write"),
+        )
+        .route(
+            "/journal-entries/:id/reversal",
+            get(handlers::finance::get_journal_entry_reversal).require("finance:journals:read"),
+        )
+        .route(
+            "/journal-entries/reversals/process",
+            post(handlers::finance::process_scheduled_reversals).require("finance:journals:write"),
         )
         .route(
             "/fiscal-years",
-            get(handlers::finance::list_fiscal_years).post(handlers::finance::create_fiscal_year),
+            get(handlers::finance::list_fiscal_years)
+                .post(handlers::finance::create_fiscal_year)
+                .require("finance:fiscal-years"),
         )
         .route(
             "/reports/balance-sheet",
-            get(handlers::finance::get_balance_sheet),
+            get(handlers::finance::get_balance_sheet).require("finance:reports:read"),
         )
         .route(
             "/reports/profit-and-loss",
-            get(handlers::finance::get_profit_and_loss),
+            get(handlers::finance::get_profit_and_loss).require("finance:reports:read"),
         )
         .route(
             "/reports/trial-balance",
-            get(handlers::finance::get_trial_balance),
+            get(handlers::finance::get_trial_balance).require("finance:reports:read"),
         )
         .route(
             "/dunning/policies",
-            post(handlers::finance::create_dunning_policy),
+            post(handlers::finance::create_dunning_policy).require("finance:dunning:write"),
         )
         .route(
             "/dunning/policies/:policy_id/levels",
-            post(handlers::finance::add_dunning_level),
+            post(handlers::finance::add_dunning_level).require("finance:dunning:write"),
         )
-        .route("/dunning/runs", post(handlers::finance::create_dunning_run))
+        .route("/dunning/runs", post(handlers::finance::create_dunning_run).require("finance:dunning:write"))
         .route(
             "/dunning/runs/:id/execute",
-            post(handlers::finance::execute_dunning_run),
+            post(handlers::finance::execute_dunning_run).require("finance:dunning:write"),
         )
-        .route("/dunning/aging", get(handlers::finance::get_aging_report))
+        .route("/dunning/aging", get(handlers::finance::get_aging_report).require("finance:dunning:read"))
         .route(
             "/collections",
-            post(handlers::finance::create_collection_case),
+            post(handlers::finance::create_collection_case).require("finance:collections:write"),
         )
         .route(
             "/collections/:id/activities",
-            post(handlers::finance::add_collection_activity),
+            post(handlers::finance::add_collection_activity).require("finance:collections:write"),
         )
-        .route("/periods", get(handlers::finance::list_periods))
+        .route("/periods", get(handlers::finance::list_periods).require("finance:periods:read"))
         .route(
             "/periods/create/:fiscal_year_id",
-            post(handlers::finance::create_periods),
+            post(handlers::finance::create_periods).require("finance:periods:write"),
         )
-        .route("/periods/:id/lock", post(handlers::finance::lock_period))
+        .route("/periods/:id/lock", post(handlers::finance::lock_period).require("finance:periods:write"))
         .route(
             "/periods/:id/unlock",
-            post(handlers::finance::unlock_period),
+            post(handlers::finance::unlock_period).require("finance:periods:write"),
         )
         .route(
             "/periods/:id/checklist",
-            post(handlers::finance::create_close_checklist),
+            post(handlers::finance::create_close_checklist).require("finance:periods:write"),
         )
         .route(
             "/periods/checklist/:task_id/complete",
-            post(handlers::finance::complete_checklist_task),
+            post(handlers::finance::complete_checklist_task).require("finance:periods:write"),
         )
         .route(
             "/recurring-journals",
             get(handlers::finance::list_recurring_journals)
-                .post(handlers::finance::create_recurring_journal),
+                .post(handlers::finance::create_recurring_journal)
+                .require("finance:recurring-journals"),
         )
         .route(
             "/recurring-journals/process",
-            post(handlers::finance::process_recurring_journals),
+            post(handlers::finance::process_recurring_journals).require("finance:recurring-journals:write"),
         )
         .route(
             "/recurring-journals/:id/deactivate",
-            post(handlers::finance::deactivate_recurring_journal),
+            post(handlers::finance::deactivate_recurring_journal).require("finance:recurring-journals:write"),
         )
         .route(
             "/currency-revaluations",
             get(handlers::finance::list_currency_revaluations)
-                .post(handlers::finance::create_currency_revaluation),
+                .post(handlers::finance::create_currency_revaluation)
+                .require("finance:currency-revaluations"),
         )
         .route(
             "/currency-revaluations/preview",
-            post(handlers::finance::preview_currency_revaluation),
+            post(handlers::finance::preview_currency_revaluation).require("finance:currency-revaluations:read"),
         )
         .route(
             "/currency-revaluations/:id",
-            get(handlers::finance::get_currency_revaluation),
+            get(handlers::finance::get_currency_revaluation).require("finance:currency-revaluations:read"),
         )
         .route(
             "/currency-revaluations/:id/lines",
-            get(handlers::finance::get_currency_revaluation_lines),
+            get(handlers::finance::get_currency_revaluation_lines).require("finance:currency-revaluations:read"),
         )
         .route(
             "/currency-revaluations/:id/post",
-            post(handlers::finance::post_currency_revaluation),
+            post(handlers::finance::post_currency_revaluation).require("finance:currency-revaluations:post"),
         )
         .route(
             "/currency-revaluations/:id/reverse",
-            post(handlers::finance::reverse_currency_revaluation),
+            post(handlers::finance::reverse_currency_revaluation).require("finance:currency-revaluations:reverse"),
         )
         .with_state(state)
 }
@@ -365,24 +410,39 @@ fn inventory_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route(
             "/products",
-            get(handlers::inventory::list_products).post(handlers::inventory::create_product),
+            get(handlers::inventory::list_products)
+                .post(handlers::inventory::create_product)
+                .require("inventory:products"),
         )
         .route(
             "/products/:id",
             get(handlers::inventory::get_product)
                 .put(handlers::inventory::update_product)
-                .delete(handlers::inventory::delete_product),
+                .delete(handlers::inventory::delete_product)
+                .require("inventory:products"),
         )
         .route(
             "/warehouses",
-            get(handlers::inventory::list_warehouses).post(handlers::inventory::create_warehouse),
+            get(handlers::inventory::list_warehouses)
+                .post(handlers::inventory::create_warehouse)
+                .require("inventory:warehouses"),
         )
-        .route("/warehouses/:id", get(handlers::inventory::get_warehouse))
+        .route("/warehouses/:id", get(handlers::inventory::get_warehouse).require("inventory:warehouses:read"))
         .route(
             "/stock-movements",
-            post(handlers::inventory::create_stock_movement),
+            post(handlers::inventory::create_stock_movement).require("inventory:stock:adjust"),
         )
-        .route("/stock/:product_id", get(handlers::inventory::get_stock))
+        .route("/stock/:product_id", get(handlers::inventory::get_stock).require("inventory:stock:read"))
+        .route("/atp", get(handlers::inventory::check_atp).require("inventory:atp:read"))
+        .route(
+            "/products/:id/costing",
+            get(handlers::inventory::get_costing)
+                .put(handlers::inventory::configure_costing)
+                .require("inventory:products"),
+        )
+        .route("/products/:id/revalue", post(handlers::inventory::revalue_standard_cost).require("inventory:products:write"))
+        .route("/products/:id/cost-variances", get(handlers::inventory::list_cost_variances).require("inventory:products:read"))
+        .route("/valuation", get(handlers::inventory::valuation_report).require("inventory:valuation:read"))
         .with_state(state)
 }
 
@@ -390,39 +450,56 @@ fn sales_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route(
             "/customers",
-            get(handlers::sales::list_customers).post(handlers::sales::create_customer),
+            get(handlers::sales::list_customers)
+                .post(handlers::sales::create_customer)
+                .require("sales:customers"),
         )
-        .route("/customers/:id", get(handlers::sales::get_customer))
+        .route("/customers/:id", get(handlers::sales::get_customer).require("sales:customers:read"))
+        .route("/customers/:id/open-items", get(handlers::sales::get_open_items).require("sales:customers:read"))
+        .route("/customers/:id/statement", get(handlers::sales::get_statement).require("sales:customers:read"))
         .route(
             "/orders",
-            get(handlers::sales::list_orders).post(handlers::sales::create_order),
+            get(handlers::sales::list_orders)
+                .post(handlers::sales::create_order)
+                .require("sales:orders"),
         )
-        .route("/orders/:id", get(handlers::sales::get_order))
-        .route("/orders/:id/confirm", post(handlers::sales::confirm_order))
+        .route("/orders/:id", get(handlers::sales::get_order).require("sales:orders:read"))
+        .route("/orders/:id/confirm", post(handlers::sales::confirm_order).require("sales:orders:approve"))
+        .route("/orders/:id/ship", post(handlers::sales::ship_order).require("sales:orders:write"))
+        .route("/orders/:id/cancel", post(handlers::sales::cancel_order).require("sales:orders:write"))
+        .route("/orders/:id/reservations", get(handlers::sales::get_order_reservations).require("sales:orders:read"))
         .route(
             "/invoices",
-            get(handlers::sales::list_invoices).post(handlers::sales::create_invoice),
+            get(handlers::sales::list_invoices)
+                .post(handlers::sales::create_invoice)
+                .require("sales:invoices"),
         )
+        .route("/invoices/:id", get(handlers::sales::get_invoice).require("sales:invoices:read"))
+        .route("/payments", post(handlers::sales::receive_payment).require("sales:payments:write"))
+        .route("/payments/:id", get(handlers::sales::get_payment).require("sales:payments:read"))
+        .route("/payments/:id/apply", post(handlers::sales::apply_payment).require("sales:payments:write"))
         .route(
             "/quotations",
-            get(handlers::sales::list_quotations).post(handlers::sales::create_quotation),
+            get(handlers::sales::list_quotations)
+                .post(handlers::sales::create_quotation)
+                .require("sales:quotations"),
         )
-        .route("/quotations/:id", get(handlers::sales::get_quotation))
+        .route("/quotations/:id", get(handlers::sales::get_quotation).require("sales:quotations:read"))
         .route(
             "/quotations/:id/send",
-            post(handlers::sales::send_quotation),
+            post(handlers::sales::send_quotation).require("sales:quotations:write"),
         )
         .route(
             "/quotations/:id/accept",
-            post(handlers::sales::accept_quotation),
+            post(handlers::sales::accept_quotation).require("sales:quotations:write"),
         )
         .route(
             "/quotations/:id/reject",
-            post(handlers::sales::reject_quotation),
+            post(handlers::sales::reject_quotation).require("sales:quotations:reject"),
         )
         .route(
             "/quotations/:id/convert",
-            post(handlers::sales::convert_quotation),
+            post(handlers::sales::convert_quotation).require("sales:quotations:write"),
         )
         .with_state(state)
 }
@@ -431,17 +508,21 @@ fn purchasing_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route(
             "/vendors",
-            get(handlers::purchasing::list_vendors).post(handlers::purchasing::create_vendor),
+            get(handlers::purchasing::list_vendors)
+                .post(handlers::purchasing::create_vendor)
+                .require("purchasing:vendors"),
         )
-        .route("/vendors/:id", get(handlers::purchasing::get_vendor))
+        .route("/vendors/:id", get(handlers::purchasing::get_vendor).require("purchasing:vendors:read"))
         .route(
             "/orders",
-            get(handlers::purchasing::list_orders).post(handlers::purchasing::create_order),
+            get(handlers::purchasing::list_orders)
+                .post(handlers::purchasing::create_order)
+                .require("purchasing:orders"),
         )
-        .route("/orders/:id", get(handlers::purchasing::get_order))
+        .route("/orders/:id", get(handlers::purchasing::get_order).require("purchasing:orders:read"))
         .route(
             "/orders/:id/approve",
-            post(handlers::purchasing::approve_order),
+            post(handlers::purchasing::approve_order).require("purchasing:orders:approve"),
         )
         .with_state(state)
 }
@@ -450,21 +531,24 @@ fn manufacturing_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route(
             "/boms",
-            get(handlers::manufacturing::list_boms).post(handlers::manufacturing::create_bom),
+            get(handlers::manufacturing::list_boms)
+                .post(handlers::manufacturing::create_bom)
+                .require("manufacturing:boms"),
         )
-        .route("/boms/:id", get(handlers::manufacturing::get_bom))
+        .route("/boms/:id", get(handlers::manufacturing::get_bom).require("manufacturing:boms:read"))
         .route(
             "/work-orders",
             get(handlers::manufacturing::list_work_orders)
-                .post(handlers::manufacturing::create_work_order),
+                .post(handlers::manufacturing::create_work_order)
+                .require("manufacturing:workorders"),
         )
         .route(
             "/work-orders/:id/start",
-            post(handlers::manufacturing::start_work_order),
+            post(handlers::manufacturing::start_work_order).require("manufacturing:workorders:write"),
         )
         .route(
             "/work-orders/:id/complete",
-            post(handlers::manufacturing::complete_work_order),
+            post(handlers::manufacturing::complete_work_order).require("manufacturing:workorders:write"),
         )
         .with_state(state)
 }
@@ -473,57 +557,66 @@ fn hr_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route(
             "/employees",
-            get(handlers::hr::list_employees).post(handlers::hr::create_employee),
+            get(handlers::hr::list_employees)
+                .post(handlers::hr::create_employee)
+                .require("hr:employees"),
         )
-        .route("/employees/:id", get(handlers::hr::get_employee))
-        .route("/attendance/check-in", post(handlers::hr::check_in))
-        .route("/attendance/check-out", post(handlers::hr::check_out))
+        .route("/employees/:id", get(handlers::hr::get_employee).require("hr:employees:read"))
+        .route("/attendance/check-in", post(handlers::hr::check_in).require("hr:attendance:write"))
+        .route("/attendance/check-out", post(handlers::hr::check_out).require("hr:attendance:write"))
         .route(
             "/payroll-runs",
-            get(handlers::hr::list_payroll_runs).post(handlers::hr::create_payroll_run),
+            get(handlers::hr::list_payroll_runs)
+                .post(handlers::hr::create_payroll_run)
+                .require("hr:payroll"),
         )
-        .route("/payroll-runs/:id", get(handlers::hr::get_payroll_run))
+        .route("/payroll-runs/:id", get(handlers::hr::get_payroll_run).require("hr:payroll:read"))
         .route(
             "/payroll-runs/:id/process",
-            post(handlers::hr::process_payroll_run),
+            post(handlers::hr::process_payroll_run).require("hr:payroll:write"),
         )
         .route(
             "/payroll-runs/:id/approve",
-            post(handlers::hr::approve_payroll_run),
+            post(handlers::hr::approve_payroll_run).require("hr:payroll:approve"),
         )
-        .route("/payroll-runs/:id/pay", post(handlers::hr::pay_payroll_run))
+        .route("/payroll-runs/:id/pay", post(handlers::hr::pay_payroll_run).require("hr:payroll:write"))
         .route(
             "/payroll-runs/:id/entries",
-            get(handlers::hr::list_payroll_entries),
+            get(handlers::hr::list_payroll_entries).require("hr:payroll:read"),
         )
         .route(
             "/performance-cycles",
-            get(handlers::hr::list_performance_cycles).post(handlers::hr::create_performance_cycle),
+            get(handlers::hr::list_performance_cycles)
+                .post(handlers::hr::create_performance_cycle)
+                .require("hr:performance-cycles"),
         )
         .route(
             "/performance-cycles/:id/activate",
-            post(handlers::hr::activate_performance_cycle),
+            post(handlers::hr::activate_performance_cycle).require("hr:performance-cycles:write"),
         )
         .route(
             "/performance-cycles/:id/close",
-            post(handlers::hr::close_performance_cycle),
+            post(handlers::hr::close_performance_cycle).require("hr:performance-cycles:write"),
         )
         .route(
             "/performance-goals",
-            get(handlers::hr::list_performance_goals).post(handlers::hr::create_performance_goal),
+            get(handlers::hr::list_performance_goals)
+                .post(handlers::hr::create_performance_goal)
+                .require("hr:performance-goals"),
         )
         .route(
             "/performance-goals/:id/rating",
-            post(handlers::hr::update_goal_rating),
+            post(handlers::hr::update_goal_rating).require("hr:performance-goals:write"),
         )
         .route(
             "/performance-reviews",
             get(handlers::hr::list_performance_reviews)
-                .post(handlers::hr::create_performance_review),
+                .post(handlers::hr::create_performance_review)
+                .require("hr:performance-reviews"),
         )
         .route(
             "/performance-reviews/:id/submit",
-            post(handlers::hr::submit_performance_review),
+            post(handlers::hr::submit_performance_review).require("hr:performance-reviews:write"),
         )
         .with_state(state)
 }
@@ -532,43 +625,49 @@ fn service_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route(
             "/tickets",
-            get(handlers::service::list_tickets).post(handlers::service::create_ticket),
+            get(handlers::service::list_tickets)
+                .post(handlers::service::create_ticket)
+                .require("service:tickets"),
         )
-        .route("/tickets/:id", get(handlers::service::get_ticket))
+        .route("/tickets/:id", get(handlers::service::get_ticket).require("service:tickets:read"))
         .route(
             "/tickets/:id/assign",
-            post(handlers::service::assign_ticket),
+            post(handlers::service::assign_ticket).require("service:tickets:write"),
         )
         .route(
             "/tickets/:id/status",
-            post(handlers::service::update_ticket_status),
+            post(handlers::service::update_ticket_status).require("service:tickets:write"),
         )
         .route(
             "/tickets/:id/satisfaction",
-            post(handlers::service::set_satisfaction),
+            post(handlers::service::set_satisfaction).require("service:tickets:write"),
         )
-        .route("/tickets/stats", get(handlers::service::ticket_stats))
+        .route("/tickets/stats", get(handlers::service::ticket_stats).require("service:tickets:read"))
         .route(
             "/articles",
-            get(handlers::service::list_articles).post(handlers::service::create_article),
+            get(handlers::service::list_articles)
+                .post(handlers::service::create_article)
+                .require("service:articles"),
         )
-        .route("/articles/search", get(handlers::service::search_articles))
-        .route("/articles/:id", get(handlers::service::get_article))
+        .route("/articles/search", get(handlers::service::search_articles).require("service:articles:read"))
+        .route("/articles/:id", get(handlers::service::get_article).require("service:articles:read"))
         .route(
             "/articles/:id/publish",
-            post(handlers::service::publish_article),
+            post(handlers::service::publish_article).require("service:articles:write"),
         )
         .route(
             "/articles/:id/archive",
-            post(handlers::service::archive_article),
+            post(handlers::service::archive_article).require("service:articles:write"),
         )
         .route(
             "/articles/:id/feedback",
-            post(handlers::service::article_feedback),
+            post(handlers::service::article_feedback).require("service:articles:write"),
         )
         .route(
             "/slas",
-            get(handlers::service::list_slas).post(handlers::service::create_sla),
+            get(handlers::service::list_slas)
+                .post(handlers::service::create_sla)
+                .require("service:slas"),
         )
         .with_state(state)
 }
@@ -577,36 +676,41 @@ fn assets_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route(
             "/assets",
-            get(handlers::assets::list_assets).post(handlers::assets::create_asset),
+            get(handlers::assets::list_assets)
+                .post(handlers::assets::create_asset)
+                .require("assets:assets"),
         )
-        .route("/assets/:id", get(handlers::assets::get_asset))
-        .route("/assets/:id/assign", post(handlers::assets::assign_asset))
-        .route("/assets/:id/return", post(handlers::assets::return_asset))
+        .route("/assets/:id", get(handlers::assets::get_asset).require("assets:assets:read"))
+        .route("/assets/:id/assign", post(handlers::assets::assign_asset).require("assets:assets:write"))
+        .route("/assets/:id/return", post(handlers::assets::return_asset).require("assets:assets:write"))
         .route(
             "/assets/:id/status",
-            post(handlers::assets::update_asset_status),
+            post(handlers::assets::update_asset_status).require("assets:assets:write"),
         )
-        .route("/assets/stats", get(handlers::assets::asset_stats))
+        .route("/assets/stats", get(handlers::assets::asset_stats).require("assets:assets:read"))
         .route(
             "/licenses",
-            get(handlers::assets::list_licenses).post(handlers::assets::create_license),
+            get(handlers::assets::list_licenses)
+                .post(handlers::assets::create_license)
+                .require("assets:licenses"),
         )
-        .route("/licenses/:id", get(handlers::assets::get_license))
+        .route("/licenses/:id", get(handlers::assets::get_license).require("assets:licenses:read"))
         .route(
             "/licenses/:id/use",
-            post(handlers::assets::use_license_seat),
+            post(handlers::assets::use_license_seat).require("assets:licenses:write"),
         )
         .route(
             "/licenses/:id/release",
-            post(handlers::assets::release_license_seat),
+            post(handlers::assets::release_license_seat).require("assets:licenses:write"),
         )
         .route(
             "/licenses/expiring",
-            get(handlers::assets::expiring_licenses),
+            get(handlers::assets::expiring_licenses).require("assets:licenses:read"),
         )
         .with_state(state)
 }
 
+// Every route here acts on the caller's own account, so none requires a permission.
 fn security_routes() -> Router<AppState> {
     Router::new()
         .route("/2fa/setup", post(handlers::security::setup_two_factor))
@@ -639,14 +743,14 @@ fn security_routes() -> Router<AppState> {
 
 fn search_routes() -> Router<AppState> {
     Router::new()
-        .route("/", get(handlers::search::search))
-        .route("/index", post(handlers::search::index_entity))
+        .route("/", get(handlers::search::search).require("search:index:read"))
+        .route("/index", post(handlers::search::index_entity).require("search:index:write"))
         .route(
             "/index/:entity_type/:entity_id",
-            delete(handlers::search::remove_from_index),
+            delete(handlers::search::remove_from_index).require("search:index:delete"),
         )
-        .route("/rebuild", post(handlers::search::rebuild_index))
-        .route("/stats", get(handlers::search::search_stats))
+        .route("/rebuild", post(handlers::search::rebuild_index).require("search:rebuild:write"))
+        .route("/stats", get(handlers::search::search_stats).require("search:stats:read"))
 }
 
 fn email_routes() -> Router<AppState> {
@@ -654,22 +758,24 @@ fn email_routes() -> Router<AppState> {
         .route(
             "/templates",
             get(handlers::email_templates::list_templates)
-                .post(handlers::email_templates::create_template),
+                .post(handlers::email_templates::create_template)
+                .require("email:templates"),
         )
         .route(
             "/templates/:name",
             get(handlers::email_templates::get_template)
                 .put(handlers::email_templates::update_template)
-                .delete(handlers::email_templates::delete_template),
+                .delete(handlers::email_templates::delete_template)
+                .require("email:templates"),
         )
-        .route("/queue", post(handlers::email_templates::queue_email))
+        .route("/queue", post(handlers::email_templates::queue_email).require("email:queue:write"))
         .route(
             "/queue/pending",
-            get(handlers::email_templates::get_pending_emails),
+            get(handlers::email_templates::get_pending_emails).require("email:queue:read"),
         )
         .route(
             "/queue/stats",
-            get(handlers::email_templates::get_email_queue_stats),
+            get(handlers::email_templates::get_email_queue_stats).require("email:queue:read"),
         )
 }
 
@@ -678,16 +784,18 @@ fn bulk_routes() -> Router<AppState> {
         .route(
             "/",
             get(handlers::bulk_operations::list_operations)
-                .post(handlers::bulk_operations::create_operation),
+                .post(handlers::bulk_operations::create_operation)
+                .require("bulk:operations"),
         )
         .route(
             "/:id",
             get(handlers::bulk_operations::get_operation)
-                .delete(handlers::bulk_operations::cancel_operation),
+                .delete(handlers::bulk_operations::cancel_operation)
+                .require("bulk:operations"),
         )
         .route(
             "/cleanup",
-            delete(handlers::bulk_operations::cleanup_operations),
+            delete(handlers::bulk_operations::cleanup_operations).require("bulk:cleanup:delete"),
         )
 }
 
@@ -696,53 +804,63 @@ fn archival_routes() -> Router<AppState> {
         .route(
             "/policies",
             get(handlers::archival::list_retention_policies)
-                .post(handlers::archival::create_retention_policy),
+                .post(handlers::archival::create_retention_policy)
+                .require("archival:policies"),
         )
         .route(
             "/policies/:entity_type",
-            get(handlers::archival::get_retention_policy),
+            get(handlers::archival::get_retention_policy).require("archival:policies:read"),
         )
         .route(
             "/records",
-            get(handlers::archival::list_archived_records).post(handlers::archival::archive_record),
+            get(handlers::archival::list_archived_records)
+                .post(handlers::archival::archive_record)
+                .require("archival:records"),
         )
         .route(
             "/records/:id",
             get(handlers::archival::get_archived_record)
-                .delete(handlers::archival::delete_archived_record),
+                .delete(handlers::archival::delete_archived_record)
+                .require("archival:records"),
         )
         .route(
             "/records/:id/restore",
-            post(handlers::archival::restore_record),
+            post(handlers::archival::restore_record).require("archival:records:write"),
         )
-        .route("/purge", post(handlers::archival::purge_expired))
-        .route("/stats", get(handlers::archival::archival_stats))
+        .route("/purge", post(handlers::archival::purge_expired).require("archival:purge:write"))
+        .route("/stats", get(handlers::archival::archival_stats).require("archival:stats:read"))
 }
 
 fn tms_routes() -> Router<AppState> {
     Router::new()
         .route(
             "/vehicles",
-            get(handlers::tms::list_vehicles).post(handlers::tms::create_vehicle),
+            get(handlers::tms::list_vehicles)
+                .post(handlers::tms::create_vehicle)
+                .require("tms:vehicles"),
         )
-        .route("/vehicles/:id", get(handlers::tms::get_vehicle))
+        .route("/vehicles/:id", get(handlers::tms::get_vehicle).require("tms:vehicles:read"))
         .route(
             "/drivers",
-            get(handlers::tms::list_drivers).post(handlers::tms::create_driver),
+            get(handlers::tms::list_drivers)
+                .post(handlers::tms::create_driver)
+                .require("tms:drivers"),
         )
-        .route("/drivers/:id", get(handlers::tms::get_driver))
+        .route("/drivers/:id", get(handlers::tms::get_driver).require("tms:drivers:read"))
         .route(
             "/loads",
-            get(handlers::tms::list_loads).post(handlers::tms::create_load),
+            get(handlers::tms::list_loads)
+                .post(handlers::tms::create_load)
+                .require("tms:loads"),
         )
-        .route("/loads/:id", get(handlers::tms::get_load))
-        .route("/loads/:id/assign", post(handlers::tms::assign_load))
-        .route("/loads/:id/dispatch", post(handlers::tms::dispatch_load))
-        .route("/loads/:id/deliver", post(handlers::tms::deliver_load))
-        .route("/routes/optimize", post(handlers::tms::optimize_route))
+        .route("/loads/:id", get(handlers::tms::get_load).require("tms:loads:read"))
+        .route("/loads/:id/assign", post(handlers::tms::assign_load).require("tms:loads:write"))
+        .route("/loads/:id/dispatch", post(handlers::tms::dispatch_load).require("tms:loads:write"))
+        .route("/loads/:id/deliver", post(handlers::tms::deliver_load).require("tms:loads:write"))
+        .route("/routes/optimize", post(handlers::tms::optimize_route).require("tms:routes:write"))
         .route(
             "/freight-invoices/:id/audit",
-            post(handlers::tms::audit_freight_invoice),
+            post(handlers::tms::audit_freight_invoice).require("tms:freight-invoices:write"),
         )
 }
 
@@ -750,39 +868,41 @@ fn plm_routes() -> Router<AppState> {
     Router::new()
         .route(
             "/items",
-            get(handlers::plm::list_items).post(handlers::plm::create_item),
-        )
-        .route("/items/:id", get(handlers::plm::get_item))
-        .route("/items/:id/release", post(handlers::plm::release_item))
-        .route("/ecrs", post(handlers::plm::create_ecr))
-        .route("/ecrs/:id/submit", post(handlers::plm::submit_ecr))
-        .route("/ecrs/:id/approve", post(handlers::plm::approve_ecr))
-        .route("/ecrs/:id/reject", post(handlers::plm::reject_ecr))
-        .route("/boms", post(handlers::plm::create_bom))
-        .route("/specifications", post(handlers::plm::create_specification))
-        .route("/design-reviews", post(handlers::plm::create_design_review))
+            get(handlers::plm::list_items)
+                .post(handlers::plm::create_item)
+                .require("plm:items"),
+        )
+        .route("/items/:id", get(handlers::plm::get_item).require("plm:items:read"))
+        .route("/items/:id/release", post(handlers::plm::release_item).require("plm:items:write"))
+        .route("/ecrs", post(handlers::plm::create_ecr).require("plm:ecrs:write"))
+        .route("/ecrs/:id/submit", post(handlers::plm::submit_ecr).require("plm:ecrs:write"))
+        .route("/ecrs/:id/approve", post(handlers::plm::approve_ecr).require("plm:ecrs:approve"))
+        .route("/ecrs/:id/reject", post(handlers::plm::reject_ecr).require("plm:ecrs:reject"))
+        .route("/boms", post(handlers::plm::create_bom).require("plm:boms:write"))
+        .route("/specifications", post(handlers::plm::create_specification).require("plm:specifications:write"))
+        .route("/design-reviews", post(handlers::plm::create_design_review).require("plm:design-reviews:write"))
 }
 
 fn mdm_routes() -> Router<AppState> {
     Router::new()
-        .route("/golden-records", post(handlers::mdm::create_golden_record))
-        .route("/golden-records/:id", get(handlers::mdm::get_golden_record))
-        .route("/quality-rules", post(handlers::mdm::create_quality_rule))
-        .route("/quality-check/:id", post(handlers::mdm::run_quality_check))
+        .route("/golden-records", post(handlers::mdm::create_golden_record).require("mdm:golden-records:write"))
+        .route("/golden-records/:id", get(handlers::mdm::get_golden_record).require("mdm:golden-records:read"))
+        .route("/quality-rules", post(handlers::mdm::create_quality_rule).require("mdm:quality-rules:write"))
+        .route("/quality-check/:id", post(handlers::mdm::run_quality_check).require("mdm:quality-check:write"))
         .route(
             "/violations/:id/resolve",
-            post(handlers::mdm::resolve_violation),
+            post(handlers::mdm::resolve_violation).require("mdm:violations:write"),
         )
-        .route("/duplicates/find", post(handlers::mdm::find_duplicates))
-        .route("/merge", post(handlers::mdm::merge_records))
+        .route("/duplicates/find", post(handlers::mdm::find_duplicates).require("mdm:duplicates:read"))
+        .route("/merge", post(handlers::mdm::merge_records).require("mdm:merge:write"))
         .route(
             "/dashboard/:entity_type",
-            get(handlers::mdm::get_quality_dashboard),
+            get(handlers::mdm::get_quality_dashboard).require("mdm:dashboard:read"),
         )
-        .route("/import-jobs", post(handlers::mdm::create_import_job))
+        .route("/import-jobs", post(handlers::mdm::create_import_job).require("mdm:import-jobs:write"))
         .route(
             "/import-jobs/:id/start",
-            post(handlers::mdm::start_import_job),
+            post(handlers::mdm::start_import_job).require("mdm:import-jobs:write"),
         )
 }
 
@@ -790,107 +910,125 @@ fn fsm_routes() -> Router<AppState> {
     Router::new()
         .route(
             "/orders",
-            get(handlers::fsm::list_service_orders).post(handlers::fsm::create_service_order),
+            get(handlers::fsm::list_service_orders)
+                .post(handlers::fsm::create_service_order)
+                .require("fsm:orders"),
         )
-        .route("/orders/:id", get(handlers::fsm::get_service_order))
-        .route("/orders/dispatch", post(handlers::fsm::dispatch_order))
-        .route("/orders/:id/start", post(handlers::fsm::start_service))
+        .route("/orders/:id", get(handlers::fsm::get_service_order).require("fsm:orders:read"))
+        .route("/orders/dispatch", post(handlers::fsm::dispatch_order).require("fsm:orders:write"))
+        .route("/orders/:id/start", post(handlers::fsm::start_service).require("fsm:orders:write"))
         .route(
             "/orders/:id/complete",
-            post(handlers::fsm::complete_service),
+            post(handlers::fsm::complete_service).require("fsm:orders:write"),
         )
-        .route("/orders/:id/feedback", post(handlers::fsm::record_feedback))
+        .route("/orders/:id/feedback", post(handlers::fsm::record_feedback).require("fsm:orders:write"))
         .route(
             "/technicians",
-            get(handlers::fsm::list_technicians).post(handlers::fsm::create_technician),
+            get(handlers::fsm::list_technicians)
+                .post(handlers::fsm::create_technician)
+                .require("fsm:technicians"),
         )
-        .route("/routes/optimize", post(handlers::fsm::optimize_route))
+        .route("/routes/optimize", post(handlers::fsm::optimize_route).require("fsm:routes:write"))
         .route(
             "/technicians/find",
-            post(handlers::fsm::find_available_technician),
+            post(handlers::fsm::find_available_technician).require("fsm:technicians:read"),
         )
 }
 
+/// Resolvers check the domain permissions themselves, so any user who may read the API can
+/// send operations.
+fn graphql_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(handlers::graphql::graphql_handler).require("graphql:api:read"))
+        .route("/playground", get(handlers::graphql::graphql_playground).require("graphql:playground:read"))
+}
+
 fn tpm_routes() -> Router<AppState> {
     Router::new()
         .route(
             "/promotions",
-            get(handlers::tpm::list_promotions).post(handlers::tpm::create_promotion),
+            get(handlers::tpm::list_promotions)
+                .post(handlers::tpm::create_promotion)
+                .require("tpm:promotions"),
         )
-        .route("/promotions/:id", get(handlers::tpm::get_promotion))
+        .route("/promotions/:id", get(handlers::tpm::get_promotion).require("tpm:promotions:read"))
         .route(
             "/promotions/:id/activate",
-            post(handlers::tpm::activate_promotion),
+            post(handlers::tpm::activate_promotion).require("tpm:promotions:write"),
         )
         .route(
             "/promotions/:id/performance",
-            post(handlers::tpm::calculate_promotion_performance),
+            post(handlers::tpm::calculate_promotion_performance).require("tpm:promotions:write"),
         )
         .route(
             "/rebate-agreements",
-            post(handlers::tpm::create_rebate_agreement),
+            post(handlers::tpm::create_rebate_agreement).require("tpm:rebate-agreements:write"),
         )
         .route(
             "/rebate-agreements/:id",
-            get(handlers::tpm::get_rebate_agreement),
+            get(handlers::tpm::get_rebate_agreement).require("tpm:rebate-agreements:read"),
         )
         .route(
             "/rebate-agreements/:id/calculate",
-            post(handlers::tpm::calculate_rebate),
+            post(handlers::tpm::calculate_rebate).require("tpm:rebate-agreements:write"),
         )
         .route(
             "/rebate-agreements/:id/payment",
-            post(handlers::tpm::process_rebate_payment),
+            post(handlers::tpm::process_rebate_payment).require("tpm:rebate-agreements:write"),
         )
-        .route("/chargebacks", post(handlers::tpm::submit_chargeback))
+        .route("/chargebacks", post(handlers::tpm::submit_chargeback).require("tpm:chargebacks:write"))
         .route(
             "/chargebacks/:id/review",
-            post(handlers::tpm::review_chargeback),
+            post(handlers::tpm::review_chargeback).require("tpm:chargebacks:write"),
         )
-        .route("/funds", post(handlers::tpm::create_trade_fund))
-        .route("/funds/:id/commit", post(handlers::tpm::commit_fund))
+        .route("/funds", post(handlers::tpm::create_trade_fund).require("tpm:funds:write"))
+        .route("/funds/:id/commit", post(handlers::tpm::commit_fund).require("tpm:funds:write"))
 }
 
 fn promotions_routes() -> Router<AppState> {
     Router::new()
         .route(
             "/campaigns",
-            get(handlers::promotions::list_promotions).post(handlers::promotions::create_promotion),
+            get(handlers::promotions::list_promotions)
+                .post(handlers::promotions::create_promotion)
+                .require("promotions:campaigns"),
         )
-        .route("/campaigns/:id", get(handlers::promotions::get_promotion))
+        .route("/campaigns/:id", get(handlers::promotions::get_promotion).require("promotions:campaigns:read"))
         .route(
             "/campaigns/:id",
-            put(handlers::promotions::update_promotion),
+            put(handlers::promotions::update_promotion).require("promotions:campaigns:write"),
         )
         .route(
             "/campaigns/:id/activate",
-            post(handlers::promotions::activate_promotion),
+            post(handlers::promotions::activate_promotion).require("promotions:campaigns:write"),
         )
         .route(
             "/campaigns/:id/deactivate",
-            post(handlers::promotions::deactivate_promotion),
+            post(handlers::promotions::deactivate_promotion).require("promotions:campaigns:write"),
         )
         .route(
             "/campaigns/:id/calculate",
-            post(handlers::promotions::calculate_promotion_discount),
+            post(handlers::promotions::calculate_promotion_discount).require("promotions:campaigns:write"),
         )
         .route(
             "/campaigns/:id/report",
-            get(handlers::promotions::get_promotion_report),
+            get(handlers::promotions::get_promotion_report).require("promotions:campaigns:read"),
         )
         .route(
             "/coupons",
-            get(handlers::promotions::list_coupons).post(handlers::promotions::create_coupon),
+            get(handlers::promotions::list_coupons)
+                .post(handlers::promotions::create_coupon)
+                .require("promotions:coupons"),
         )
-        .route("/coupons/:id", get(handlers::promotions::get_coupon))
+        .route("/coupons/:id", get(handlers::promotions::get_coupon).require("promotions:coupons:read"))
         .route(
             "/coupons/validate",
-            post(handlers::promotions::validate_coupon),
+            post(handlers::promotions::validate_coupon).require("promotions:coupons:read"),
         )
-        .route("/coupons/apply", post(handlers::promotions::apply_coupon))
+        .route("/coupons/apply", post(handlers::promotions::apply_coupon).require("promotions:coupons:write"))
         .route(
             "/coupons/generate-batch",
-            post(handlers::promotions::generate_coupon_batch_handler),
+            post(handlers::promotions::generate_coupon_batch_handler).require("promotions:coupons:write"),
         )
 }
 
@@ -899,77 +1037,80 @@ fn approval_workflow_routes() -> Router<AppState> {
         .route(
             "/workflows",
             get(handlers::approval_workflow::list_workflows)
-                .post(handlers::approval_workflow::create_workflow),
+                .post(handlers::approval_workflow::create_workflow)
+                .require("approvals:workflows"),
         )
         .route(
             "/workflows/:id",
             get(handlers::approval_workflow::get_workflow)
                 .put(handlers::approval_workflow::update_workflow)
-                .delete(handlers::approval_workflow::delete_workflow),
+                .delete(handlers::approval_workflow::delete_workflow)
+                .require("approvals:workflows"),
         )
         .route(
             "/requests",
             get(handlers::approval_workflow::list_requests)
-                .post(handlers::approval_workflow::submit_for_approval),
+                .post(handlers::approval_workflow::submit_for_approval)
+                .require("approvals:requests"),
         )
         .route(
             "/requests/:id",
-            get(handlers::approval_workflow::get_request),
+            get(handlers::approval_workflow::get_request).require("approvals:requests:read"),
         )
         .route(
             "/requests/:id/approve",
-            post(handlers::approval_workflow::approve_request),
+            post(handlers::approval_workflow::approve_request).require("approvals:requests:approve"),
         )
         .route(
             "/requests/:id/reject",
-            post(handlers::approval_workflow::reject_request),
+            post(handlers::approval_workflow::reject_request).require("approvals:requests:reject"),
         )
         .route(
             "/requests/:id/cancel",
-            post(handlers::approval_workflow::cancel_request),
+            post(handlers::approval_workflow::cancel_request).require("approvals:requests:write"),
         )
         .route(
             "/pending/:user_id",
-            get(handlers::approval_workflow::get_pending_approvals),
+            get(handlers::approval_workflow::get_pending_approvals).require("approvals:pending:read"),
         )
         .route(
             "/pending/:user_id/summary",
-            get(handlers::approval_workflow::get_pending_summary),
+            get(handlers::approval_workflow::get_pending_summary).require("approvals:pending:read"),
         )
 }
 
 fn credit_routes() -> Router<AppState> {
     Router::new()
-        .route("/check", post(handlers::credit::check_credit))
-        .route("/summary", get(handlers::credit::get_summary))
-        .route("/profiles", get(handlers::credit::list_profiles))
-        .route("/on-hold", get(handlers::credit::list_on_hold))
-        .route("/high-risk", get(handlers::credit::list_high_risk))
-        .route("/alerts", get(handlers::credit::list_alerts))
+        .route("/check", post(handlers::credit::check_credit).require("credit:check:read"))
+        .route("/summary", get(handlers::credit::get_summary).require("credit:summary:read"))
+        .route("/profiles", get(handlers::credit::list_profiles).require("credit:profiles:read"))
+        .route("/on-hold", get(handlers::credit::list_on_hold).require("credit:on-hold:read"))
+        .route("/high-risk", get(handlers::credit::list_high_risk).require("credit:high-risk:read"))
+        .route("/alerts", get(handlers::credit::list_alerts).require("credit:alerts:read"))
         .route(
             "/alerts/:id/acknowledge",
-            post(handlers::credit::acknowledge_alert),
+            post(handlers::credit::acknowledge_alert).require("credit:alerts:write"),
         )
-        .route("/invoice", post(handlers::credit::record_invoice))
-        .route("/payment", post(handlers::credit::record_payment))
-        .route("/:customer_id", get(handlers::credit::get_profile))
+        .route("/invoice", post(handlers::credit::record_invoice).require("credit:invoice:write"))
+        .route("/payment", post(handlers::credit::record_payment).require("credit:payment:write"))
+        .route("/:customer_id", get(handlers::credit::get_profile).require("credit:profiles:read"))
         .route(
             "/:customer_id/limit",
-            post(handlers::credit::update_credit_limit),
+            post(handlers::credit::update_credit_limit).require("credit:profiles:write"),
         )
-        .route("/:customer_id/hold", post(handlers::credit::place_hold))
+        .route("/:customer_id/hold", post(handlers::credit::place_hold).require("credit:profiles:write"))
         .route(
             "/:customer_id/release",
-            post(handlers::credit::release_hold),
+            post(handlers::credit::release_hold).require("credit:profiles:write"),
         )
         .route(
             "/:customer_id/transactions",
-            get(handlers::credit::list_transactions),
+            get(handlers::credit::list_transactions).require("credit:profiles:read"),
         )
-        .route("/:customer_id/holds", get(handlers::credit::list_holds))
+        .route("/:customer_id/holds", get(handlers::credit::list_holds).require("credit:profiles:read"))
         .route(
             "/:customer_id/limit-changes",
-            get(handlers::credit::list_limit_changes),
+            get(handlers::credit::list_limit_changes).require("credit:profiles:read"),
         )
 }
 
@@ -977,36 +1118,46 @@ fn kanban_routes() -> Router<AppState> {
     Router::new()
         .route(
             "/boards",
-            get(handlers::kanban::list_boards).post(handlers::kanban::create_board),
+            get(handlers::kanban::list_boards)
+                .post(handlers::kanban::create_board)
+                .require("kanban:boards"),
         )
         .route(
             "/boards/:id",
-            get(handlers::kanban::get_board).delete(handlers::kanban::delete_board),
+            get(handlers::kanban::get_board)
+                .delete(handlers::kanban::delete_board)
+                .require("kanban:boards"),
         )
         .route(
             "/boards/:id/summary",
-            get(handlers::kanban::get_board_summary),
+            get(handlers::kanban::get_board_summary).require("kanban:boards:read"),
         )
         .route(
             "/boards/:id/activities",
-            get(handlers::kanban::list_activities),
+            get(handlers::kanban::list_activities).require("kanban:boards:read"),
         )
-        .route("/boards/:board_id/cards", get(handlers::kanban::list_cards))
-        .route("/cards", post(handlers::kanban::create_card))
+        .route("/boards/:board_id/cards", get(handlers::kanban::list_cards).require("kanban:boards:read"))
+        .route("/cards", post(handlers::kanban::create_card).require("kanban:cards:write"))
         .route(
             "/cards/:id",
-            get(handlers::kanban::get_card).delete(handlers::kanban::delete_card),
+            get(handlers::kanban::get_card)
+                .delete(handlers::kanban::delete_card)
+                .require("kanban:cards"),
         )
-        .route("/cards/move", post(handlers::kanban::move_card))
-        .route("/cards/:id/block", post(handlers::kanban::block_card))
-        .route("/cards/:id/unblock", post(handlers::kanban::unblock_card))
+        .route("/cards/move", post(handlers::kanban::move_card).require("kanban:cards:write"))
+        .route("/cards/:id/block", post(handlers::kanban::block_card).require("kanban:cards:write"))
+        .route("/cards/:id/unblock", post(handlers::kanban::unblock_card).require("kanban:cards:write"))
         .route(
             "/cards/:card_id/comments",
-            get(handlers::kanban::list_comments).post(handlers::kanban::add_comment),
+            get(handlers::kanban::list_comments)
+                .post(handlers::kanban::add_comment)
+                .require("kanban:cards"),
         )
         .route(
             "/cards/:card_id/checklists",
-            get(handlers::kanban::list_checklists).post(handlers::kanban::add_checklist),
+            get(handlers::kanban::list_checklists)
+                .post(handlers::kanban::add_checklist)
+                .require("kanban:cards"),
         )
 }
 
@@ -1014,32 +1165,37 @@ fn bundles_routes() -> Router<AppState> {
     Router::new()
         .route(
             "/",
-            get(handlers::bundles::list_bundles).post(handlers::bundles::create_bundle),
+            get(handlers::bundles::list_bundles)
+                .post(handlers::bundles::create_bundle)
+                .require("bundles:bundles"),
         )
         .route(
             "/:id",
             get(handlers::bundles::get_bundle)
                 .put(handlers::bundles::update_bundle)
-                .delete(handlers::bundles::delete_bundle),
+                .delete(handlers::bundles::delete_bundle)
+                .require("bundles:bundles"),
         )
-        .route("/:id/components", post(handlers::bundles::add_component))
+        .route("/:id/components", post(handlers::bundles::add_component).require("bundles:bundles:write"))
         .route(
             "/:id/components/:component_id",
-            delete(handlers::bundles::remove_component),
+            delete(handlers::bundles::remove_component).require("bundles:bundles:delete"),
         )
         .route(
             "/:id/availability",
-            get(handlers::bundles::get_availability),
+            get(handlers::bundles::get_availability).require("bundles:bundles:read"),
         )
         .route(
             "/:id/price-rules",
-            get(handlers::bundles::get_price_rules).post(handlers::bundles::add_price_rule),
+            get(handlers::bundles::get_price_rules)
+                .post(handlers::bundles::add_price_rule)
+                .require("bundles:bundles"),
         )
         .route(
             "/:id/calculate-price",
-            get(handlers::bundles::calculate_price),
+            get(handlers::bundles::calculate_price).require("bundles:bundles:read"),
         )
-        .route("/:id/analytics", get(handlers::bundles::get_analytics))
+        .route("/:id/analytics", get(handlers::bundles::get_analytics).require("bundles:bundles:read"))
 }
 
 fn shift_scheduling_routes() -> Router<AppState> {
@@ -1047,59 +1203,65 @@ fn shift_scheduling_routes() -> Router<AppState> {
         .route(
             "/shifts",
             get(handlers::shift_scheduling::list_shifts)
-                .post(handlers::shift_scheduling::create_shift),
+                .post(handlers::shift_scheduling::create_shift)
+                .require("scheduling:shifts"),
         )
         .route(
             "/shifts/active",
-            get(handlers::shift_scheduling::list_active_shifts),
+            get(handlers::shift_scheduling::list_active_shifts).require("scheduling:shifts:read"),
         )
         .route(
             "/shifts/:id",
             get(handlers::shift_scheduling::get_shift)
                 .put(handlers::shift_scheduling::update_shift)
-                .delete(handlers::shift_scheduling::delete_shift),
+                .delete(handlers::shift_scheduling::delete_shift)
+                .require("scheduling:shifts"),
         )
         .route(
             "/schedules",
             get(handlers::shift_scheduling::list_schedules)
-                .post(handlers::shift_scheduling::create_schedule),
+                .post(handlers::shift_scheduling::create_schedule)
+                .require("scheduling:schedules"),
         )
         .route(
             "/schedules/:id",
             get(handlers::shift_scheduling::get_schedule)
                 .put(handlers::shift_scheduling::update_schedule)
-                .delete(handlers::shift_scheduling::delete_schedule),
+                .delete(handlers::shift_scheduling::delete_schedule)
+                .require("scheduling:schedules"),
         )
         .route(
             "/schedules/:id/publish",
-            post(handlers::shift_scheduling::publish_schedule),
+            post(handlers::shift_scheduling::publish_schedule).require("scheduling:schedules:write"),
         )
         .route(
             "/schedules/:id/assignments",
-            get(handlers::shift_scheduling::list_assignments),
+            get(handlers::shift_scheduling::list_assignments).require("scheduling:schedules:read"),
         )
         .route(
             "/schedules/:id/daily",
-            get(handlers::shift_scheduling::get_daily_schedule),
+            get(handlers::shift_scheduling::get_daily_schedule).require("scheduling:schedules:read"),
         )
         .route(
             "/assignments",
             get(handlers::shift_scheduling::list_employee_assignments)
-                .post(handlers::shift_scheduling::create_assignment),
+                .post(handlers::shift_scheduling::create_assignment)
+                .require("scheduling:assignments"),
         )
         .route(
             "/assignments/:id",
             get(handlers::shift_scheduling::get_assignment)
                 .put(handlers::shift_scheduling::update_assignment)
-                .delete(handlers::shift_scheduling::delete_assignment),
+                .delete(handlers::shift_scheduling::delete_assignment)
+                .require("scheduling:assignments"),
         )
         .route(
             "/assignments/:id/clock-in",
-            post(handlers::shift_scheduling::clock_in),
+            post(handlers::shift_scheduling::clock_in).require("scheduling:assignments:write"),
         )
         .route(
             "/assignments/:id/clock-out",
-            post(handlers::shift_scheduling::clock_out),
+            post(handlers::shift_scheduling::clock_out).require("scheduling:assignments:write"),
         )
 }
 
@@ -1107,19 +1269,49 @@ fn workflow_routes() -> Router<AppState> {
     Router::new()
         .route(
             "/workflows",
-            get(handlers::workflow::list_workflows).post(handlers::workflow::create_workflow),
+            get(handlers::workflow::list_workflows)
+                .post(handlers::workflow::create_workflow)
+                .require("workflow:workflows"),
         )
         .route(
             "/approvals",
-            get(handlers::workflow::list_pending_approvals),
+            get(handlers::workflow::list_pending_approvals).require("workflow:approvals:read"),
         )
         .route(
             "/approvals/:id/approve",
-            post(handlers::workflow::approve_request),
+            post(handlers::workflow::approve_request).require("workflow:approvals:approve"),
         )
         .route(
             "/approvals/:id/reject",
-            post(handlers::workflow::reject_request),
+            post(handlers::workflow::reject_request).require("workflow:approvals:reject"),
+        )
+}
+
+fn import_routes() -> Router<AppState> {
+    Router::new()
+        .route("/imports/entities", get(handlers::import_export::list_import_entities).require("data:import:read"))
+        .route(
+            "/imports",
+            get(handlers::import_export::list_imports)
+                .post(handlers::import_export::create_import)
+                .layer(DefaultBodyLimit::disable())
+                .require("data:import"),
+        )
+        .route("/imports/:id", get(handlers::import_export::get_import).require("data:import:read"))
+        .route("/imports/:id/errors", get(handlers::import_export::list_import_errors).require("data:import:read"))
+        .route(
+            "/imports/:id/error-file",
+            get(handlers::import_export::download_import_errors).require("data:import:read"),
+        )
+        .route(
+            "/import-mappings",
+            get(handlers::import_export::list_import_mappings)
+                .post(handlers::import_export::create_import_mapping)
+                .require("data:import"),
+        )
+        .route(
+            "/import-mappings/:id",
+            delete(handlers::import_export::delete_import_mapping).require("data:import:delete"),
         )
 }
 
@@ -1128,111 +1320,139 @@ fn attachment_routes() -> Router<AppState> {
         .route(
             "/attachments",
             get(handlers::attachment::list_attachments)
-                .post(handlers::attachment::upload_attachment),
+                .post(handlers::attachment::upload_attachment)
+                .layer(DefaultBodyLimit::disable())
+                .require("documents:attachments"),
         )
         .route(
             "/attachments/:id",
             get(handlers::attachment::get_attachment)
-                .delete(handlers::attachment::delete_attachment),
+                .delete(handlers::attachment::delete_attachment)
+                .require("documents:attachments"),
+        )
+        .route(
+            "/attachments/:id/content",
+            get(handlers::attachment::download_attachment).require("documents:attachments"),
+        )
+        .route(
+            "/attachments/:id/download-url",
+            get(handlers::attachment::attachment_download_url).require("documents:attachments"),
         )
 }
 
 fn extended_routes() -> Router<AppState> {
     Router::new()
-        .route("/currencies", get(handlers::extended::list_currencies))
+        .route("/currencies", get(handlers::extended::list_currencies).require("finance:currencies:read"))
         .route(
             "/exchange-rates",
-            post(handlers::extended::set_exchange_rate),
+            post(handlers::extended::set_exchange_rate).require("finance:exchange-rates:write"),
         )
-        .route("/convert", get(handlers::extended::convert_currency))
+        .route("/convert", get(handlers::extended::convert_currency).require("finance:currencies:read"))
         .route(
             "/budgets",
-            get(handlers::extended::list_budgets).post(handlers::extended::create_budget),
+            get(handlers::extended::list_budgets)
+                .post(handlers::extended::create_budget)
+                .require("finance:budgets"),
         )
         .route(
             "/lots",
-            get(handlers::extended::list_lots).post(handlers::extended::create_lot),
+            get(handlers::extended::list_lots)
+                .post(handlers::extended::create_lot)
+                .require("inventory:lots"),
         )
-        .route("/leave-types", get(handlers::extended::list_leave_types))
+        .route("/leave-types", get(handlers::extended::list_leave_types).require("hr:leave:read"))
         .route(
             "/leave-requests",
             get(handlers::extended::list_pending_leave)
-                .post(handlers::extended::create_leave_request),
+                .post(handlers::extended::create_leave_request)
+                .require("hr:leave"),
         )
         .route(
             "/leave-requests/:id/approve",
-            post(handlers::extended::approve_leave),
+            post(handlers::extended::approve_leave).require("hr:leave:approve"),
         )
         .route(
             "/leave-requests/:id/reject",
-            post(handlers::extended::reject_leave),
+            post(handlers::extended::reject_leave).require("hr:leave:reject"),
         )
         .route(
             "/expense-categories",
             get(handlers::extended::list_expense_categories)
-                .post(handlers::extended::create_expense_category),
+                .post(handlers::extended::create_expense_category)
+                .require("hr:expenses"),
         )
         .route(
             "/expense-reports",
             get(handlers::extended::list_expense_reports)
-                .post(handlers::extended::create_expense_report),
+                .post(handlers::extended::create_expense_report)
+                .require("hr:expenses"),
+        )
+        .route(
+            "/expense-reports/:id",
+            get(handlers::extended::get_expense_report).require("hr:expenses:read"),
         )
         .route(
             "/expense-reports/:id/submit",
-            post(handlers::extended::submit_expense),
+            post(handlers::extended::submit_expense).require("hr:expenses:write"),
         )
         .route(
             "/expense-reports/:id/approve",
-            post(handlers::extended::approve_expense),
+            post(handlers::extended::approve_expense).require("hr:expenses:approve"),
         )
         .route(
             "/expense-reports/:id/reject",
-            post(handlers::extended::reject_expense),
+            post(handlers::extended::reject_expense).require("hr:expenses:reject"),
         )
         .route(
             "/fixed-assets",
-            get(handlers::extended::list_fixed_assets).post(handlers::extended::create_fixed_asset),
+            get(handlers::extended::list_fixed_assets)
+                .post(handlers::extended::create_fixed_asset)
+                .require("finance:fixed-assets"),
         )
         .route(
             "/fixed-assets/:id/depreciate",
-            post(handlers::extended::depreciate_asset),
+            post(handlers::extended::depreciate_asset).require("finance:fixed-assets:write"),
         )
-        .route("/inspections", post(handlers::extended::create_inspection))
+        .route("/inspections", post(handlers::extended::create_inspection).require("quality:inspections:write"))
         .route(
             "/inspections/:id/complete",
-            post(handlers::extended::complete_inspection),
+            post(handlers::extended::complete_inspection).require("quality:inspections:write"),
         )
-        .route("/ncrs", post(handlers::extended::create_ncr))
+        .route("/ncrs", post(handlers::extended::create_ncr).require("quality:ncrs:write"))
         .route(
             "/leads",
-            get(handlers::extended::list_leads).post(handlers::extended::create_lead),
+            get(handlers::extended::list_leads)
+                .post(handlers::extended::create_lead)
+                .require("sales:leads"),
         )
         .route(
             "/opportunities",
             get(handlers::extended::list_opportunities)
-                .post(handlers::extended::create_opportunity),
+                .post(handlers::extended::create_opportunity)
+                .require("sales:opportunities"),
         )
         .route(
             "/opportunities/:id/stage",
-            post(handlers::extended::update_opportunity_stage),
+            post(handlers::extended::update_opportunity_stage).require("sales:opportunities:write"),
         )
         .route(
             "/schedules",
             get(handlers::extended::list_schedules)
-                .post(handlers::extended::create_production_schedule),
+                .post(handlers::extended::create_production_schedule)
+                .require("manufacturing:schedules"),
         )
-        .route("/scorecards", post(handlers::extended::create_scorecard))
+        .route("/scorecards", post(handlers::extended::create_scorecard).require("purchasing:scorecards:write"))
         .route(
             "/scorecards/:vendor_id",
-            get(handlers::extended::list_scorecards),
+            get(handlers::extended::list_scorecards).require("purchasing:scorecards:read"),
         )
         .route(
             "/custom-fields",
-            post(handlers::extended::create_custom_field),
+            post(handlers::extended::create_custom_field).require("config:custom-fields:write"),
         )
         .route(
             "/custom-fields/:entity_type",
-            get(handlers::extended::list_custom_fields),
+            get(handlers::extended::list_custom_fields).require("config:custom-fields:read"),
         )
-        .route("/custom-values", post(handlers::extended::set_custom_value))
+        .route("/custom-values", post(handlers::extended::set_custom_value).require("config:custom-fields:write"))
 }
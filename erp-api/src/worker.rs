@@ -0,0 +1,114 @@
+use erp_jobs::{JobRunner, JobService, ScheduledJob};
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+use crate::db::AppState;
+use crate::handlers::reports::PolicyAccessResolver;
+
+pub const REPORT_SCHEDULES_HANDLER: &str = "reports.process_due_schedules";
+pub const WEBHOOK_DELIVERIES_HANDLER: &str = "webhooks.deliver_pending";
+pub const RECURRING_JOURNALS_HANDLER: &str = "finance.post_recurring_journals";
+pub const EMAIL_QUEUE_HANDLER: &str = "email.process_queue";
+pub const OCR_QUEUE_HANDLER: &str = "ocr.process_queued";
+pub const IMPORT_JOBS_HANDLER: &str = "mdm.process_imports";
+
+const DEFAULT_BATCH_SIZE: i32 = 50;
+
+/// Recurring housekeeping jobs created on startup: (name, handler, interval in seconds).
+const BUILTIN_JOBS: [(&str, &str, i64); 6] = [
+    ("Run due report schedules", REPORT_SCHEDULES_HANDLER, 60),
+    ("Deliver pending webhooks", WEBHOOK_DELIVERIES_HANDLER, 30),
+    ("Post recurring journals", RECURRING_JOURNALS_HANDLER, 3600),
+    ("Send queued email", EMAIL_QUEUE_HANDLER, 30),
+    ("Read queued OCR documents", OCR_QUEUE_HANDLER, 30),
+    ("Run queued imports", IMPORT_JOBS_HANDLER, 10),
+];
+
+fn batch_size(job: &ScheduledJob) -> i32 {
+    job.payload.as_ref()
+        .and_then(|p| p.get("batch_size"))
+        .and_then(|v| v.as_i64())
+        .map(|v| v as i32)
+        .unwrap_or(DEFAULT_BATCH_SIZE)
+}
+
+/// A runner with handlers for the workspace's periodic processes.
+pub fn job_runner(worker_id: impl Into<String>, state: &AppState) -> JobRunner {
+    let blobs = state.blobs.clone();
+    let ocr_blobs = state.blobs.clone();
+    let import_blobs = state.blobs.clone();
+    let resolver = Arc::new(PolicyAccessResolver { authz: state.authz.clone() });
+    JobRunner::new(worker_id)
+        .register_fn(REPORT_SCHEDULES_HANDLER, move |pool, _job| {
+            let blobs = blobs.clone();
+            let resolver = resolver.clone();
+            async move {
+                let executions = erp_reports::ReportScheduleService::new()
+                    .run_due(&pool, &blobs, resolver.as_ref())
+                    .await?;
+                Ok(Some(json!({ "executions": executions.len() })))
+            }
+        })
+        .register_fn(WEBHOOK_DELIVERIES_HANDLER, |pool, job| async move {
+            let service = erp_webhooks::WebhookService::new();
+            let pending = service.get_pending_deliveries(&pool, batch_size(&job)).await?;
+            let mut delivered = 0;
+            let mut failed = 0;
+            for delivery in &pending {
+                match service.process_delivery(&pool, delivery.base.id).await {
+                    Ok(d) if matches!(d.status, erp_webhooks::DeliveryStatus::Delivered) => delivered += 1,
+                    Ok(_) => failed += 1,
+                    Err(e) => {
+                        tracing::warn!(delivery_id = %delivery.base.id, "Webhook delivery failed: {}", e);
+                        failed += 1;
+                    }
+                }
+            }
+            Ok(Some(json!({ "attempted": pending.len(), "delivered": delivered, "failed": failed })))
+        })
+        .register_fn(RECURRING_JOURNALS_HANDLER, |pool, _job| async move {
+            let posted = erp_finance::RecurringJournalService::process_due(&pool).await?;
+            Ok(Some(json!({ "journal_entries": posted.iter().map(|(_, entry)| entry).collect::<Vec<_>>() })))
+        })
+        .register_fn(EMAIL_QUEUE_HANDLER, |pool, job| async move {
+            let sent = erp_core::EmailService::process_queue(&pool, batch_size(&job)).await?;
+            Ok(Some(json!({ "sent": sent.len() })))
+        })
+        .register_fn(OCR_QUEUE_HANDLER, move |pool, job| {
+            let blobs = ocr_blobs.clone();
+            async move {
+                let results = erp_ocr::OcrService::new()
+                    .process_queued(&pool, &blobs, batch_size(&job) as i64)
+                    .await?;
+                let billed = results.iter().filter(|r| r.vendor_bill_id.is_some()).count();
+                Ok(Some(json!({ "processed": results.len(), "vendor_bills": billed })))
+            }
+        })
+        .register_fn(IMPORT_JOBS_HANDLER, move |pool, job| {
+            let blobs = import_blobs.clone();
+            async move {
+                let jobs = erp_mdm::import::ImportService::new()
+                    .process_queued(&pool, &blobs, batch_size(&job).max(1) as usize)
+                    .await?;
+                let completed = jobs.iter().filter(|j| j.status == "Completed").count();
+                Ok(Some(json!({ "jobs": jobs.len(), "completed": completed })))
+            }
+        })
+}
+
+pub async fn ensure_builtin_jobs(pool: &SqlitePool) -> anyhow::Result<()> {
+    let service = JobService::new();
+    for (name, handler, interval_seconds) in BUILTIN_JOBS {
+        service.ensure_interval_job(pool, name.to_string(), handler.to_string(), interval_seconds, None).await?;
+    }
+    Ok(())
+}
+
+/// Seeds the built-in jobs and starts a runner for this process.
+pub async fn start(state: &AppState) -> anyhow::Result<JoinHandle<()>> {
+    ensure_builtin_jobs(&state.pool).await?;
+    let worker_id = format!("erp-api-{}-{}", std::process::id(), uuid::Uuid::new_v4().simple());
+    Ok(job_runner(worker_id, state).spawn(state.pool.clone()))
+}
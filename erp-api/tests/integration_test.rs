@@ -21,38 +21,20 @@ fn init_test_env() {
 }
 
 async fn setup_test_db() -> SqlitePool {
-    let pool = SqlitePool::connect(":memory:").await.unwrap();
-    run_migrations(&pool).await;
+    let pool = setup_unprivileged_db().await;
+    // Registered users get the read-only User role; most tests exercise writes, so everyone who
+    // registers here starts out as an Admin instead. The User role keeps its shipped grants.
+    sqlx::query(
+        "CREATE TRIGGER test_users_are_admins AFTER INSERT ON users
+         BEGIN UPDATE users SET role = 'Admin' WHERE id = NEW.id; END"
+    ).execute(&pool).await.unwrap();
     pool
 }
 
-async fn run_migrations(pool: &SqlitePool) {
-    let migration_queries = vec![
-        include_str!("../../migrations/20240101000000_finance.sql"),
-        include_str!("../../migrations/20240101000001_inventory.sql"),
-        include_str!("../../migrations/20240101000002_sales.sql"),
-        include_str!("../../migrations/20240101000003_purchasing.sql"),
-        include_str!("../../migrations/20240101000004_manufacturing.sql"),
-        include_str!("../../migrations/20240101000005_hr.sql"),
-        include_str!("../../migrations/20240101000006_auth.sql"),
-        include_str!("../../migrations/20240101000011_extended_features.sql"),
-        include_str!("../../migrations/20240101000012_advanced_features.sql"),
-        include_str!("../../migrations/20240101000013_enterprise_features.sql"),
-        include_str!("../../migrations/20260302000000_inventory_adjustments.sql"),
-        include_str!("../../migrations/20260302100000_stock_transfers.sql"),
-        include_str!("../../migrations/20240309000000_vendor_bills.sql"),
-    ];
-    
-    for migration in migration_queries {
-        for statement in migration.split(';') {
-            let statement = statement.trim();
-            if !statement.is_empty() {
-                if let Err(e) = sqlx::query(statement).execute(pool).await {
-                    eprintln!("Migration error: {}", e);
-                }
-            }
-        }
-    }
+async fn setup_unprivileged_db() -> SqlitePool {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    erp_api::db::run_migrations(&pool).await.unwrap();
+    pool
 }
 
 fn create_test_app(pool: SqlitePool) -> AppState {
@@ -61,10 +43,15 @@ fn create_test_app(pool: SqlitePool) -> AppState {
         server_host: "127.0.0.1".to_string(),
         server_port: 3000,
         jwt_secret: "test-secret-at-least-32-characters-long-for-security".to_string(),
-        jwt_expiration: 24,
+        access_token_minutes: 15,
+        refresh_token_days: 30,
         cors_allowed_origins: vec!["http://localhost:5173".to_string()],
         trust_proxy: false,
         stripe: None,
+        master_key: None,
+        job_worker_enabled: false,
+        payment_simulator_enabled: true,
+        storage_dir: std::env::temp_dir().join(format!("erp-test-{}", uuid::Uuid::new_v4())).display().to_string(),
     };
     let config = std::sync::Arc::new(config);
     let ws_manager = std::sync::Arc::new(erp_api::handlers::websocket::WebSocketManagerInner::new());
@@ -74,12 +61,21 @@ fn create_test_app(pool: SqlitePool) -> AppState {
         config: config.clone(),
         ws_manager,
         auth_svc: std::sync::Arc::new(erp_auth::AuthService::new(pool.clone())),
+        authz: std::sync::Arc::new(erp_auth::Authorizer::new(pool.clone())),
         project_svc: std::sync::Arc::new(erp_projects::ProjectService::new(pool.clone())),
         timesheet_svc: std::sync::Arc::new(erp_projects::TimesheetService::new(pool.clone())),
         payment_svc: std::sync::Arc::new(erp_payments::PaymentService::new(pool.clone())),
-        gateway_svc: std::sync::Arc::new(erp_payments::GatewayService::new(pool.clone())),
+        gateway_svc: std::sync::Arc::new(erp_payments::GatewayService::new(pool.clone()).with_simulator(erp_payments::SimulatorGateway::new())),
         stripe_svc: std::sync::Arc::new(None),
-        backup_svc: std::sync::Arc::new(erp_backup::BackupService::new(pool)),
+        backup_svc: std::sync::Arc::new(erp_backup::BackupService::new(pool.clone())),
+        graphql: erp_graphql::build_schema(pool),
+        blobs: std::sync::Arc::new(
+            erp_core::BlobService::new(
+                std::sync::Arc::new(erp_core::blob::LocalBlobStore::new(std::path::Path::new(&config.storage_dir).join("blobs"))),
+                std::path::Path::new(&config.storage_dir).join("staging"),
+            )
+            .with_signing_key(config.jwt_secret.as_bytes()),
+        ),
     }
 }
 
@@ -1281,3 +1277,1616 @@ async fn test_performance_management() {
     let closed_cycle: serde_json::Value = serde_json::from_slice(&close_body_bytes).unwrap();
     assert_eq!(closed_cycle["status"], "Closed");
 }
+
+type TestSocket = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+async fn next_ws_json(socket: &mut TestSocket) -> serde_json::Value {
+    use futures::StreamExt;
+    use tokio_tungstenite::tungstenite::Message;
+
+    loop {
+        match tokio::time::timeout(std::time::Duration::from_secs(5), socket.next()).await.unwrap() {
+            Some(Ok(Message::Text(text))) => return serde_json::from_str(&text).unwrap(),
+            Some(Ok(_)) => continue,
+            other => panic!("websocket closed: {:?}", other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_websocket_streams_targeted_and_topic_events() {
+    use erp_core::events::{publish, RealtimeEvent};
+    use futures::SinkExt;
+    use tokio_tungstenite::tungstenite::Message;
+
+    init_test_env();
+    let pool = setup_unprivileged_db().await;
+    let state = create_test_app(pool.clone());
+    state.ws_manager.forward_realtime_events();
+    let ws_manager = state.ws_manager.clone();
+    let app = create_router(state);
+    let (token, user_id) = register_user(&app, "wsuser").await;
+    grant_role(&pool, "ws_rep", &[&user_id], &[("sales_orders", "Own", "")], &[]).await;
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query("INSERT INTO customers (id, code, name, created_at, updated_at) VALUES ('ws-cust', 'WS', 'WS', ?, ?)")
+        .bind(&now).bind(&now).execute(&pool).await.unwrap();
+    for (id, owner) in [("ws-mine", user_id.as_str()), ("ws-theirs", "someone-else")] {
+        sqlx::query("INSERT INTO sales_orders (id, order_number, customer_id, order_date, created_at, updated_at, created_by) VALUES (?, ?, 'ws-cust', ?, ?, ?, ?)")
+            .bind(id).bind(id).bind(&now).bind(&now).bind(&now).bind(owner)
+            .execute(&pool).await.unwrap();
+    }
+    let user_id = uuid::Uuid::parse_str(&user_id).unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>()).await.unwrap();
+    });
+
+    assert!(tokio_tungstenite::connect_async(format!("ws://{}/ws?token=bogus", addr)).await.is_err());
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws?token={}", addr, token)).await.unwrap();
+    assert_eq!(next_ws_json(&mut socket).await["type"], "connected");
+
+    for (topic, reply) in [("payroll_run:1", "error"), ("sales_order:ws-theirs", "error"), ("sales_order:ws-mine", "subscribed")] {
+        socket.send(Message::Text(json!({ "action": "subscribe", "topic": topic }).to_string())).await.unwrap();
+        assert_eq!(next_ws_json(&mut socket).await["type"], reply, "{}", topic);
+    }
+    socket.send(Message::Text(json!({ "action": "subscribe", "topic": "product:42" }).to_string())).await.unwrap();
+    assert_eq!(next_ws_json(&mut socket).await["type"], "subscribed");
+    assert_eq!(ws_manager.get_client_count(&user_id).await, 1);
+
+    publish(RealtimeEvent::new("notification", "Not yours", "ignored").for_users([uuid::Uuid::new_v4()]));
+    publish(RealtimeEvent::new("product.updated", "Someone else's", "ignored").with_topic("product:42").for_users([uuid::Uuid::new_v4()]));
+    publish(RealtimeEvent::new("product.updated", "Updated", "P-42").with_topic("product:42"));
+    publish(RealtimeEvent::new("notification", "Hello", "direct").for_users([user_id]));
+
+    let updated = next_ws_json(&mut socket).await;
+    assert_eq!(updated["type"], "event");
+    assert_eq!(updated["payload"]["title"], "Updated");
+    assert_eq!(next_ws_json(&mut socket).await["payload"]["title"], "Hello");
+}
+
+async fn authed_request(app: &axum::Router, method: Method, uri: &str, token: &str, body: Option<serde_json::Value>) -> (StatusCode, serde_json::Value) {
+    // Each request gets its own client address so the per-client rate limit doesn't cut long tests short.
+    // The limiter keys on the IP alone, so vary that rather than the port.
+    static NEXT_CLIENT: std::sync::atomic::AtomicU16 = std::sync::atomic::AtomicU16::new(1);
+    let n = NEXT_CLIENT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let client = std::net::SocketAddr::from(([127, 0, (n >> 8) as u8, n as u8], 40000));
+    let builder = Request::builder()
+        .extension(axum::extract::ConnectInfo(client))
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", token));
+    let body = body.map(|b| Body::from(serde_json::to_string(&b).unwrap())).unwrap_or_else(Body::empty);
+    let response = app.clone().oneshot(builder.body(body).unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap_or(json!({})))
+}
+
+async fn register_user(app: &axum::Router, username: &str) -> (String, String) {
+    let (status, body) = make_request(app, Method::POST, "/auth/register", Some(json!({
+        "username": username,
+        "email": format!("{}@example.com", username),
+        "password": "password123",
+        "full_name": username
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    (body["token"].as_str().unwrap().to_string(), body["user"]["id"].as_str().unwrap().to_string())
+}
+
+async fn grant_role(pool: &SqlitePool, role: &str, user_ids: &[&str], data: &[(&str, &str, &str)], hidden_fields: &[(&str, &str)]) {
+    let now = chrono::Utc::now().to_rfc3339();
+    let role_id = uuid::Uuid::new_v4().to_string();
+    sqlx::query("INSERT INTO custom_roles (id, name, code, created_at, updated_at) VALUES (?, ?, ?, ?, ?)")
+        .bind(&role_id).bind(role).bind(role).bind(&now).bind(&now)
+        .execute(pool).await.unwrap();
+    for user_id in user_ids {
+        sqlx::query("INSERT INTO user_role_assignments (id, user_id, role_id, assigned_at) VALUES (?, ?, ?, ?)")
+            .bind(uuid::Uuid::new_v4().to_string()).bind(user_id).bind(&role_id).bind(&now)
+            .execute(pool).await.unwrap();
+    }
+    for (resource, filter_type, filter_value) in data {
+        sqlx::query("INSERT INTO data_permissions (id, role_id, resource, filter_type, filter_value, created_at) VALUES (?, ?, ?, ?, ?, ?)")
+            .bind(uuid::Uuid::new_v4().to_string()).bind(&role_id).bind(resource).bind(filter_type).bind(filter_value).bind(&now)
+            .execute(pool).await.unwrap();
+    }
+    for (resource, field_name) in hidden_fields {
+        sqlx::query("INSERT INTO field_permissions (id, role_id, resource, field_name, can_read, created_at) VALUES (?, ?, ?, ?, 0, ?)")
+            .bind(uuid::Uuid::new_v4().to_string()).bind(&role_id).bind(resource).bind(field_name).bind(&now)
+            .execute(pool).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_own_scope_limits_customers_and_orders_to_creator() {
+    init_test_env();
+    let pool = setup_unprivileged_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+
+    let (alice, alice_id) = register_user(&app, "alice").await;
+    let (bob, bob_id) = register_user(&app, "bob").await;
+    let (manager, _) = register_user(&app, "salesmanager").await;
+    grant_role(&pool, "sales_rep", &[&alice_id, &bob_id], &[("customers", "Own", ""), ("sales_orders", "Own", "")], &[("customers", "email")]).await;
+    sqlx::query("INSERT OR IGNORE INTO permissions (id, code, name, module, resource, action, created_at) VALUES ('sales:*:*', 'sales:*:*', 'sales:*:*', 'sales', '*', '*', datetime('now'))")
+        .execute(&pool).await.unwrap();
+    sqlx::query("INSERT INTO role_permissions (id, role_id, permission_id, granted_at)
+                 SELECT 'sales-rep-write', r.id, p.id, datetime('now') FROM custom_roles r, permissions p WHERE r.code = 'sales_rep' AND p.code = 'sales:*:*'")
+        .execute(&pool).await.unwrap();
+
+    let mut customers = Vec::new();
+    for (token, code) in [(&alice, "CUST-A"), (&bob, "CUST-B")] {
+        let (status, body) = authed_request(&app, Method::POST, "/api/v1/sales/customers", token, Some(json!({
+            "code": code, "name": code, "email": format!("{}@example.com", code)
+        }))).await;
+        assert_eq!(status, StatusCode::OK);
+        customers.push(body["id"].as_str().unwrap().to_string());
+    }
+    let product_id = uuid::Uuid::new_v4().to_string();
+    sqlx::query("INSERT INTO products (id, sku, name, unit_of_measure, created_at, updated_at) VALUES (?, 'SKU-OWN', 'Widget', 'PCS', ?, ?)")
+        .bind(&product_id).bind(chrono::Utc::now().to_rfc3339()).bind(chrono::Utc::now().to_rfc3339())
+        .execute(&pool).await.unwrap();
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/sales/orders", &bob, Some(json!({
+        "customer_id": customers[1],
+        "lines": [{ "product_id": product_id, "description": "Widget", "quantity": 1, "unit_price": 1000 }]
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, page) = authed_request(&app, Method::GET, "/api/v1/sales/customers?page=1&per_page=50", &alice, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], 1);
+    let items = page["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["code"], "CUST-A");
+    assert!(items[0].get("email").is_none());
+    assert!(items[0].get("name").is_some());
+
+    let (status, _) = authed_request(&app, Method::GET, &format!("/api/v1/sales/customers/{}", customers[1]), &alice, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, customer) = authed_request(&app, Method::GET, &format!("/api/v1/sales/customers/{}", customers[0]), &alice, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(customer.get("email").is_none());
+
+    let (_, orders) = authed_request(&app, Method::GET, "/api/v1/sales/orders?page=1&per_page=50", &alice, None).await;
+    assert_eq!(orders["total"], 0);
+    let (_, orders) = authed_request(&app, Method::GET, "/api/v1/sales/orders?page=1&per_page=50", &bob, None).await;
+    assert_eq!(orders["total"], 1);
+
+    // Invoices follow their customer's scope.
+    let invoice_id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query("INSERT INTO invoices (id, invoice_number, customer_id, invoice_date, due_date, created_at, updated_at) VALUES (?, 'INV-OWN', ?, ?, ?, ?, ?)")
+        .bind(&invoice_id).bind(&customers[1]).bind(&now).bind(&now).bind(&now).bind(&now)
+        .execute(&pool).await.unwrap();
+    let (_, invoices) = authed_request(&app, Method::GET, "/api/v1/sales/invoices?page=1&per_page=50", &alice, None).await;
+    assert_eq!(invoices["total"], 0);
+    let (status, _) = authed_request(&app, Method::GET, &format!("/api/v1/sales/invoices/{}", invoice_id), &alice, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = authed_request(&app, Method::GET, &format!("/api/v1/sales/invoices/{}", invoice_id), &bob, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Without data permissions for a resource, the built-in role decides: User sees only its own
+    // rows, Sales sees every row.
+    let (_, page) = authed_request(&app, Method::GET, "/api/v1/sales/customers?page=1&per_page=50", &manager, None).await;
+    assert_eq!(page["total"], 0);
+    sqlx::query("UPDATE users SET role = 'Sales' WHERE username = 'salesmanager'").execute(&pool).await.unwrap();
+    let (_, page) = authed_request(&app, Method::GET, "/api/v1/sales/customers?page=1&per_page=50", &manager, None).await;
+    assert_eq!(page["total"], 2);
+    assert_eq!(page["items"][0]["email"].as_str().map(|e| e.ends_with("@example.com")), Some(true));
+}
+
+#[tokio::test]
+async fn test_built_in_and_inherited_roles_carry_data_and_field_permissions() {
+    init_test_env();
+    let pool = setup_unprivileged_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+
+    let (carol, carol_id) = register_user(&app, "carol").await;
+    let (dave, dave_id) = register_user(&app, "dave").await;
+    for user_id in [&carol_id, &dave_id] {
+        sqlx::query("UPDATE users SET role = 'Sales' WHERE id = ?").bind(user_id).execute(&pool).await.unwrap();
+    }
+    for (token, code) in [(&carol, "CUST-C"), (&dave, "CUST-D")] {
+        let (status, _) = authed_request(&app, Method::POST, "/api/v1/sales/customers", token, Some(json!({
+            "code": code, "name": code, "email": format!("{}@example.com", code)
+        }))).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // Sales sees every customer until its built-in role is limited to the user's own.
+    let (_, page) = authed_request(&app, Method::GET, "/api/v1/sales/customers?page=1&per_page=50", &carol, None).await;
+    assert_eq!(page["total"], 2);
+    sqlx::query("INSERT INTO data_permissions (id, role_id, resource, filter_type, filter_value, created_at)
+                 SELECT 'sales-own-customers', id, 'customers', 'Own', '', datetime('now') FROM custom_roles WHERE code = 'Sales' AND is_system = 1")
+        .execute(&pool).await.unwrap();
+    let (_, page) = authed_request(&app, Method::GET, "/api/v1/sales/customers?page=1&per_page=50", &carol, None).await;
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["items"][0]["code"], "CUST-C");
+    assert!(page["items"][0].get("email").is_some());
+
+    // A field hidden by a parent role is hidden from holders of the child role.
+    grant_role(&pool, "masked", &[], &[], &[("customers", "email")]).await;
+    grant_role(&pool, "rep", &[&carol_id], &[], &[]).await;
+    sqlx::query("UPDATE custom_roles SET parent_role_id = (SELECT id FROM custom_roles WHERE code = 'masked') WHERE code = 'rep'")
+        .execute(&pool).await.unwrap();
+    let (_, page) = authed_request(&app, Method::GET, "/api/v1/sales/customers?page=1&per_page=50", &carol, None).await;
+    assert!(page["items"][0].get("email").is_none());
+    let (_, page) = authed_request(&app, Method::GET, "/api/v1/sales/customers?page=1&per_page=50", &dave, None).await;
+    assert_eq!(page["items"][0]["code"], "CUST-D");
+    assert!(page["items"][0].get("email").is_some());
+}
+
+#[tokio::test]
+async fn test_department_scope_limits_employees_and_own_expense_reports() {
+    init_test_env();
+    let pool = setup_unprivileged_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+
+    let (hr_admin, hr_admin_id) = register_user(&app, "hradmin").await;
+    sqlx::query("UPDATE users SET role = 'HR' WHERE id = ?").bind(&hr_admin_id).execute(&pool).await.unwrap();
+    let (carol, carol_id) = register_user(&app, "carol").await;
+    let (dave, dave_id) = register_user(&app, "dave").await;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    for (id, code) in [("dept-eng", "ENG"), ("dept-ops", "OPS")] {
+        sqlx::query("INSERT INTO departments (id, code, name, created_at, updated_at) VALUES (?, ?, ?, ?, ?)")
+            .bind(id).bind(code).bind(code).bind(&now).bind(&now)
+            .execute(&pool).await.unwrap();
+    }
+    let mut employees = Vec::new();
+    for (number, department) in [("EMP-1", "dept-eng"), ("EMP-2", "dept-eng"), ("EMP-3", "dept-ops")] {
+        let (status, body) = authed_request(&app, Method::POST, "/api/v1/hr/employees", &hr_admin, Some(json!({
+            "employee_number": number, "first_name": number, "last_name": "Test",
+            "email": format!("{}@example.com", number), "hire_date": "2024-01-01"
+        }))).await;
+        assert_eq!(status, StatusCode::OK);
+        let id = body["id"].as_str().unwrap().to_string();
+        sqlx::query("UPDATE employees SET department_id = ? WHERE id = ?").bind(department).bind(&id).execute(&pool).await.unwrap();
+        employees.push(id);
+    }
+    for (user_id, employee_id) in [(&carol_id, &employees[0]), (&dave_id, &employees[2])] {
+        sqlx::query("UPDATE users SET employee_id = ? WHERE id = ?").bind(employee_id).bind(user_id).execute(&pool).await.unwrap();
+    }
+    grant_role(&pool, "staff", &[&carol_id, &dave_id], &[("employees", "Department", ""), ("expense_reports", "Own", "")], &[]).await;
+
+    let (_, page) = authed_request(&app, Method::GET, "/api/v1/hr/employees?page=1&per_page=50", &carol, None).await;
+    assert_eq!(page["total"], 2);
+    let mut numbers: Vec<&str> = page["items"].as_array().unwrap().iter().map(|e| e["employee_number"].as_str().unwrap()).collect();
+    numbers.sort();
+    assert_eq!(numbers, vec!["EMP-1", "EMP-2"]);
+    let (status, _) = authed_request(&app, Method::GET, &format!("/api/v1/hr/employees/{}", employees[2]), &carol, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, page) = authed_request(&app, Method::GET, "/api/v1/hr/employees?page=1&per_page=50", &dave, None).await;
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["items"][0]["employee_number"], "EMP-3");
+
+    for (i, employee_id) in employees.iter().enumerate() {
+        sqlx::query("INSERT INTO expense_reports (id, report_number, employee_id, title, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)")
+            .bind(uuid::Uuid::new_v4().to_string()).bind(format!("EXP-{}", i)).bind(employee_id).bind("Travel").bind(&now).bind(&now)
+            .execute(&pool).await.unwrap();
+    }
+    let (status, reports) = authed_request(&app, Method::GET, "/api/v1/expense-reports", &carol, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let reports = reports.as_array().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0]["employee_id"].as_str(), Some(employees[0].as_str()));
+    let (status, _) = authed_request(&app, Method::GET, &format!("/api/v1/expense-reports/{}", reports[0]["id"].as_str().unwrap()), &dave, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, reports) = authed_request(&app, Method::GET, "/api/v1/expense-reports", &hr_admin, None).await;
+    assert_eq!(reports.as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_invoice_shipped_order_and_apply_receipt() {
+    init_test_env();
+    let pool = setup_test_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+    let (token, _) = register_user(&app, "arclerk").await;
+
+    let (_, customer) = authed_request(&app, Method::POST, "/api/v1/sales/customers", &token, Some(json!({ "code": "AR-1", "name": "Receivable Co" }))).await;
+    let customer_id = customer["id"].as_str().unwrap().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    let (product_id, warehouse_id, location_id) = (uuid::Uuid::new_v4().to_string(), uuid::Uuid::new_v4().to_string(), uuid::Uuid::new_v4().to_string());
+    for (sql, binds) in [
+        ("INSERT INTO products (id, sku, name, unit_of_measure, created_at, updated_at) VALUES (?, 'SKU-AR', 'Widget', 'PCS', ?, ?)", [&product_id, &now, &now]),
+        ("INSERT INTO warehouses (id, code, name, created_at, updated_at) VALUES (?, 'WH-AR', 'Main', ?, ?)", [&warehouse_id, &now, &now]),
+    ] {
+        sqlx::query(sql).bind(binds[0]).bind(binds[1]).bind(binds[2]).execute(&pool).await.unwrap();
+    }
+    sqlx::query("INSERT INTO stock_locations (id, warehouse_id, code, name, created_at, updated_at) VALUES (?, ?, 'A1', 'Aisle 1', ?, ?)")
+        .bind(&location_id).bind(&warehouse_id).bind(&now).bind(&now).execute(&pool).await.unwrap();
+    sqlx::query("INSERT INTO stock_levels (id, product_id, location_id, quantity, reserved_quantity, available_quantity) VALUES (?, ?, ?, 10, 0, 10)")
+        .bind(uuid::Uuid::new_v4().to_string()).bind(&product_id).bind(&location_id).execute(&pool).await.unwrap();
+
+    let (_, order) = authed_request(&app, Method::POST, "/api/v1/sales/orders", &token, Some(json!({
+        "customer_id": customer_id,
+        "lines": [{ "product_id": product_id, "description": "Widget", "quantity": 4, "unit_price": 2500 }]
+    }))).await;
+    let order_id = order["id"].as_str().unwrap().to_string();
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/sales/invoices", &token, Some(json!({ "sales_order_id": order_id }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    for step in ["confirm", "ship"] {
+        let (status, _) = authed_request(&app, Method::POST, &format!("/api/v1/sales/orders/{}/{}", order_id, step), &token, None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (_, order) = authed_request(&app, Method::GET, &format!("/api/v1/sales/orders/{}", order_id), &token, None).await;
+    let (status, invoice) = authed_request(&app, Method::POST, "/api/v1/sales/invoices", &token, Some(json!({
+        "sales_order_id": order_id,
+        "lines": [{ "sales_order_line_id": order["lines"][0]["id"], "quantity": 3 }]
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(invoice["invoice_number"], "INV-000001");
+    assert_eq!(invoice["total"]["amount"], 7500);
+
+    let (status, receipt) = authed_request(&app, Method::POST, "/api/v1/sales/payments", &token, Some(json!({
+        "customer_id": customer_id, "amount": 5000, "payment_method": "Check", "reference": "CHK-1"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(receipt["unapplied_amount"], 0);
+    let (_, open) = authed_request(&app, Method::GET, &format!("/api/v1/sales/customers/{}/open-items", customer_id), &token, None).await;
+    assert_eq!(open[0]["balance"], 2500);
+    let (status, statement) = authed_request(&app, Method::GET, &format!("/api/v1/sales/customers/{}/statement", customer_id), &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(statement["closing_balance"], 2500);
+    assert_eq!(statement["lines"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_permissions_resolve_from_roles_with_inheritance_and_expiry() {
+    init_test_env();
+    let pool = setup_unprivileged_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+
+    let (admin, admin_id) = register_user(&app, "rbacadmin").await;
+    sqlx::query("UPDATE users SET role = 'Admin' WHERE id = ?").bind(&admin_id).execute(&pool).await.unwrap();
+    let (clerk, clerk_id) = register_user(&app, "clerk").await;
+    let account = json!({ "code": "1000", "name": "Cash", "account_type": "Asset" });
+
+    // The built-in User role reads everything and writes nothing.
+    let (status, _) = authed_request(&app, Method::GET, "/api/v1/finance/accounts?page=1&per_page=50", &clerk, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = authed_request(&app, Method::POST, "/api/v1/finance/accounts", &clerk, Some(account.clone())).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(body["error"].as_str().unwrap().contains("finance:accounts:write"));
+    let (status, body) = authed_request(&app, Method::POST, "/api/v1/tax/jurisdictions", &clerk, Some(json!({}))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(body["error"].as_str().unwrap().contains("tax:jurisdictions:write"));
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/rbac/roles", &clerk, Some(json!({ "name": "x", "code": "x" }))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // A role granting account writes, held only through a child role's parent link.
+    let (status, ledger) = authed_request(&app, Method::POST, "/api/v1/rbac/roles", &admin, Some(json!({ "name": "Ledger", "code": "ledger" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, senior) = authed_request(&app, Method::POST, "/api/v1/rbac/roles", &admin, Some(json!({
+        "name": "Senior Ledger", "code": "senior_ledger", "parent_role_id": ledger["id"]
+    }))).await;
+    let (_, permissions) = authed_request(&app, Method::GET, "/api/v1/rbac/permissions", &admin, None).await;
+    let write = permissions.as_array().unwrap().iter().find(|p| p["code"] == "finance:accounts:write").unwrap();
+    let (status, _) = authed_request(&app, Method::POST, &format!("/api/v1/rbac/roles/{}/permissions", ledger["id"].as_str().unwrap()), &admin, Some(json!({ "permission_id": write["id"] }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let assign_uri = format!("/api/v1/rbac/roles/{}/users", senior["id"].as_str().unwrap());
+    let (status, _) = authed_request(&app, Method::POST, &assign_uri, &admin, Some(json!({ "user_id": clerk_id }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The clerk's cached permissions were dropped when the role was assigned.
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/finance/accounts", &clerk, Some(account.clone())).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let explain_uri = |permission: &str| format!("/api/v1/rbac/users/{}/explain?permission={}", clerk_id, permission);
+    let (status, decision) = authed_request(&app, Method::GET, &explain_uri("finance:accounts:write"), &admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(decision["allowed"], true);
+    assert_eq!(decision["granted_by"][0]["role_code"], "ledger");
+    assert_eq!(decision["granted_by"][0]["source"], "inherited");
+    assert_eq!(decision["granted_by"][0]["inherited_by"], "senior_ledger");
+    let (_, decision) = authed_request(&app, Method::GET, &explain_uri("finance:accounts:delete"), &admin, None).await;
+    assert_eq!(decision["allowed"], false);
+    assert!(decision["reason"].as_str().unwrap().contains("None of the user's roles"));
+    let (status, _) = authed_request(&app, Method::GET, &explain_uri("finance:accounts"), &admin, None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Once the assignment lapses the grant is gone, and the explanation says why.
+    let (status, _) = authed_request(&app, Method::POST, &assign_uri, &admin, Some(json!({ "user_id": clerk_id, "expires_at": "2020-01-01T00:00:00Z" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/finance/accounts", &clerk, Some(json!({ "code": "1001", "name": "Bank", "account_type": "Asset" }))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (_, decision) = authed_request(&app, Method::GET, &explain_uri("finance:accounts:write"), &admin, None).await;
+    assert_eq!(decision["allowed"], false);
+    assert!(decision["reason"].as_str().unwrap().contains("senior_ledger that would grant it expired"));
+    let (_, decision) = authed_request(&app, Method::GET, &explain_uri("finance:accounts:read"), &admin, None).await;
+    assert_eq!(decision["allowed"], true);
+    assert_eq!(decision["granted_by"][0]["source"], "built_in");
+    let (_, effective) = authed_request(&app, Method::GET, &format!("/api/v1/rbac/users/{}/effective-permissions", clerk_id), &admin, None).await;
+    assert_eq!(effective["permissions"], json!(["*:*:read"]));
+}
+
+#[tokio::test]
+async fn test_login_sessions_refresh_revocation_lockout_two_factor_and_password_reset() {
+    use erp_security::totp::TOTP;
+
+    init_test_env();
+    let pool = setup_test_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+    register_user(&app, "hardened").await;
+    let login = |password: &'static str| {
+        let app = app.clone();
+        async move { authed_request(&app, Method::POST, "/auth/login", "", Some(json!({ "username": "hardened", "password": password }))).await }
+    };
+
+    // Refresh tokens rotate; replaying a rotated-out one ends the whole session.
+    let (status, session) = login("password123").await;
+    assert_eq!(status, StatusCode::OK);
+    let access = session["token"].as_str().unwrap().to_string();
+    let first_refresh = session["refresh_token"].as_str().unwrap().to_string();
+    let (status, rotated) = authed_request(&app, Method::POST, "/auth/refresh", "", Some(json!({ "refresh_token": first_refresh }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(rotated["refresh_token"], session["refresh_token"]);
+    let (status, _) = authed_request(&app, Method::GET, "/auth/me", rotated["token"].as_str().unwrap(), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = authed_request(&app, Method::POST, "/auth/refresh", "", Some(json!({ "refresh_token": first_refresh }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = authed_request(&app, Method::POST, "/auth/refresh", "", Some(json!({ "refresh_token": rotated["refresh_token"] }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = authed_request(&app, Method::GET, "/auth/me", &access, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Logging out revokes the access token straight away.
+    let (_, session) = login("password123").await;
+    let access = session["token"].as_str().unwrap();
+    let (status, _) = authed_request(&app, Method::POST, "/auth/logout", access, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = authed_request(&app, Method::GET, "/auth/me", access, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (revoked,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM revoked_tokens").fetch_one(&pool).await.unwrap();
+    assert_eq!(revoked, 1);
+
+    // Five wrong passwords lock the account, even against the right one.
+    for _ in 0..4 {
+        assert_eq!(login("wrongpass1").await.0, StatusCode::UNAUTHORIZED);
+    }
+    assert_eq!(login("wrongpass1").await.0, StatusCode::FORBIDDEN);
+    let (status, body) = login("password123").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(body["error"].as_str().unwrap().contains("locked"));
+
+    // A password reset sets the new password, spends the token and lifts the lock.
+    let (status, unknown) = authed_request(&app, Method::POST, "/auth/password-reset/request", "", Some(json!({ "email": "nobody@example.com" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, known) = authed_request(&app, Method::POST, "/auth/password-reset/request", "", Some(json!({ "email": "hardened@example.com" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(unknown, known);
+    let (email,): (String,) = sqlx::query_as("SELECT body FROM email_queue WHERE to_address = 'hardened@example.com'").fetch_one(&pool).await.unwrap();
+    let reset_token = email.split("new password: ").nth(1).unwrap().split_whitespace().next().unwrap();
+    let (status, _) = authed_request(&app, Method::POST, "/auth/password-reset/confirm", "", Some(json!({ "token": reset_token, "new_password": "short" }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = authed_request(&app, Method::POST, "/auth/password-reset/confirm", "", Some(json!({ "token": reset_token, "new_password": "newpassword456" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = authed_request(&app, Method::POST, "/auth/password-reset/confirm", "", Some(json!({ "token": reset_token, "new_password": "otherpassword789" }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(login("password123").await.0, StatusCode::UNAUTHORIZED);
+    let (status, session) = login("newpassword456").await;
+    assert_eq!(status, StatusCode::OK);
+    let access = session["token"].as_str().unwrap();
+
+    // With two-factor enabled the password only earns a challenge, answered by a TOTP or backup code.
+    let (status, setup) = authed_request(&app, Method::POST, "/api/v1/security/2fa/setup", access, Some(json!({ "email": "hardened@example.com" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let totp = TOTP::new(&TOTP::base32_to_secret(setup["secret"].as_str().unwrap()).unwrap());
+    let (_, verified) = authed_request(&app, Method::POST, "/api/v1/security/2fa/verify", access, Some(json!({ "code": totp.generate_code() }))).await;
+    assert_eq!(verified["valid"], true);
+
+    let (status, challenge) = login("newpassword456").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(challenge["mfa_required"], true);
+    assert!(challenge.get("token").is_none());
+    let mfa_token = challenge["mfa_token"].as_str().unwrap();
+    let (status, _) = authed_request(&app, Method::GET, "/auth/me", mfa_token, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = authed_request(&app, Method::POST, "/auth/login/2fa", "", Some(json!({ "mfa_token": mfa_token, "code": "not-a-code" }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let code = totp.generate_code();
+    let (status, session) = authed_request(&app, Method::POST, "/auth/login/2fa", "", Some(json!({ "mfa_token": mfa_token, "code": code }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = authed_request(&app, Method::GET, "/auth/me", session["token"].as_str().unwrap(), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, challenge) = login("newpassword456").await;
+    let (status, _) = authed_request(&app, Method::POST, "/auth/login/2fa", "", Some(json!({ "mfa_token": challenge["mfa_token"], "code": code }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let backup_code = setup["backup_codes"][0].as_str().unwrap();
+    let (_, challenge) = login("newpassword456").await;
+    let (status, _) = authed_request(&app, Method::POST, "/auth/login/2fa", "", Some(json!({ "mfa_token": challenge["mfa_token"], "code": backup_code }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = authed_request(&app, Method::POST, "/auth/login/2fa", "", Some(json!({ "mfa_token": challenge["mfa_token"], "code": backup_code }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_simulator_gateway_checkout_portal_and_pos_flows() {
+    init_test_env();
+    let pool = setup_test_db().await;
+    let state = create_test_app(pool.clone());
+    let simulator = state.gateway_svc.simulator().unwrap();
+    let app = create_router(state);
+    let (token, _) = register_user(&app, "cashier").await;
+
+    let (_, customer) = authed_request(&app, Method::POST, "/api/v1/sales/customers", &token, Some(json!({ "code": "PAY-1", "name": "Card Co" }))).await;
+    let customer_id = customer["id"].as_str().unwrap().to_string();
+    let (status, gateway) = authed_request(&app, Method::POST, "/api/v1/payments/gateways", &token, Some(json!({
+        "code": "SIM", "name": "Simulator", "gateway_type": "Simulator", "webhook_secret": "whsec_test"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let gateway_id = gateway["id"].as_str().unwrap().to_string();
+
+    let tokenize = |card: &'static str| {
+        let (app, token, uri) = (app.clone(), token.clone(), format!("/api/v1/payments/gateways/{}/tokenize", gateway_id));
+        async move {
+            let (_, body) = authed_request(&app, Method::POST, &uri, &token, Some(json!({
+                "number": card, "exp_month": 12, "exp_year": 2099, "cvc": "123", "holder_name": null
+            }))).await;
+            body["token"].as_str().unwrap().to_string()
+        }
+    };
+    let checkout = |card_token: String| authed_request(&app, Method::POST, "/api/v1/payments/process", &token, Some(json!({
+        "gateway_id": gateway_id, "customer_id": customer_id, "amount": 10000, "payment_method_token": card_token
+    })));
+
+    let (_, paid) = checkout(tokenize(erp_payments::SIMULATOR_CARD_APPROVED).await).await;
+    assert_eq!(paid["status"], "Completed");
+    assert_eq!(paid["processing_fee"], 320);
+
+    let (_, declined) = checkout(tokenize(erp_payments::SIMULATOR_CARD_DECLINED).await).await;
+    assert_eq!(declined["status"], "Failed");
+    assert_eq!(declined["decline_code"], "card_declined");
+
+    let (_, challenged) = checkout(tokenize(erp_payments::SIMULATOR_CARD_REQUIRES_ACTION).await).await;
+    assert_eq!(challenged["status"], "RequiresAction");
+    assert!(challenged["next_action_url"].is_string());
+    let webhook = simulator.complete_challenge(challenged["gateway_transaction_id"].as_str().unwrap(), true, "whsec_test").unwrap();
+    let deliver = |signature: String| {
+        let request = Request::builder()
+            .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from(([127, 0, 0, 1], 40000))))
+            .method(Method::POST)
+            .uri(format!("/payments/gateways/{}/webhook", gateway_id))
+            .header("x-simulator-signature", signature)
+            .body(Body::from(webhook.payload.clone()))
+            .unwrap();
+        app.clone().oneshot(request)
+    };
+    assert_eq!(deliver("forged".to_string()).await.unwrap().status(), StatusCode::BAD_REQUEST);
+    assert_eq!(deliver(webhook.signature.clone()).await.unwrap().status(), StatusCode::OK);
+    let (_, settled) = authed_request(&app, Method::GET, &format!("/api/v1/payments/payments/{}", challenged["id"].as_str().unwrap()), &token, None).await;
+    assert_eq!(settled["status"], "Completed");
+
+    let (_, refund) = authed_request(&app, Method::POST, &format!("/api/v1/payments/payments/{}/refund", paid["id"].as_str().unwrap()), &token, Some(json!({
+        "amount": 2500, "reason": "Damaged"
+    }))).await;
+    assert_eq!(refund["amount"], 2500);
+    let (_, refunded) = authed_request(&app, Method::GET, &format!("/api/v1/payments/payments/{}", paid["id"].as_str().unwrap()), &token, None).await;
+    assert_eq!(refunded["status"], "PartiallyRefunded");
+
+    let portal_payment = |card_token: String| authed_request(&app, Method::POST, "/api/v1/portals/payments", &token, Some(json!({
+        "invoice_ids": [], "amount_cents": 5000, "payment_method": "CreditCard", "payment_token": card_token
+    })));
+    let (_, portal_paid) = portal_payment(tokenize(erp_payments::SIMULATOR_CARD_APPROVED).await).await;
+    assert_eq!(portal_paid["status"], "Completed");
+    let (_, portal_declined) = portal_payment(tokenize(erp_payments::SIMULATOR_CARD_INSUFFICIENT_FUNDS).await).await;
+    assert_eq!(portal_declined["status"], "Failed");
+    assert_eq!(portal_declined["failure_reason"], "insufficient_funds");
+
+    let sale = |card_token: String| authed_request(&app, Method::POST, "/api/v1/pos/transactions", &token, Some(json!({
+        "store_id": uuid::Uuid::new_v4(), "terminal_id": uuid::Uuid::new_v4(), "register_id": uuid::Uuid::new_v4(),
+        "lines": [{ "product_id": uuid::Uuid::new_v4(), "description": "Widget", "quantity": 1, "unit_price": 1500 }],
+        "payments": [{ "payment_method": "CreditCard", "amount": 1500, "reference": card_token }]
+    })));
+    let (status, body) = sale(tokenize(erp_payments::SIMULATOR_CARD_DECLINED).await).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+    let (status, body) = sale(tokenize(erp_payments::SIMULATOR_CARD_REQUIRES_ACTION).await).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+}
+
+#[tokio::test]
+async fn test_edi_850_books_sales_order_and_exchanges_acknowledgments() {
+    init_test_env();
+    let pool = setup_test_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+    let (token, edi_id) = register_user(&app, "edi").await;
+    sqlx::query("UPDATE users SET role = 'Admin' WHERE id = ?").bind(&edi_id).execute(&pool).await.unwrap();
+
+    let (_, customer) = authed_request(&app, Method::POST, "/api/v1/sales/customers", &token, Some(json!({ "code": "EDI-1", "name": "Buyer Co" }))).await;
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/inventory/products", &token, Some(json!({
+        "sku": "WIDGET-1", "name": "Widget", "product_type": "Goods", "unit_of_measure": "EA"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, partner) = authed_request(&app, Method::POST, "/api/v1/edi/partners", &token, Some(json!({
+        "partner_code": "BUYER", "partner_name": "Buyer Co", "partner_type": "Customer",
+        "qualifier": "ZZ", "interchange_id": "BUYER", "local_qualifier": "ZZ", "local_interchange_id": "SELLER",
+        "customer_id": customer["id"], "communication_type": "AS2", "endpoint": "https://buyer.example.com/as2", "encryption": null
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", partner);
+
+    let envelope = |control: u32, functional_id: &str, body: &str| format!(
+        "ISA*00*          *00*          *ZZ*BUYER          *ZZ*SELLER         *261018*1200*U*00401*{0:09}*0*P*>~GS*{1}*BUYER*SELLER*20261018*1200*{0}*X*004010~{2}GE*1*{0}~IEA*1*{0:09}~",
+        control, functional_id, body
+    );
+    let order = envelope(42, "PO", "ST*850*0001~BEG*00*SA*PO-1001**20261018~PO1*1*5*EA*12.50**VP*WIDGET-1~CTT*1~SE*5*0001~");
+    let (status, inbound) = authed_request(&app, Method::POST, "/api/v1/edi/inbound", &token, Some(json!({ "raw_content": order }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", inbound);
+    assert_eq!(inbound["transactions"][0]["status"], "Processed");
+    let sales_order_id = inbound["transactions"][0]["parsed_data"]["sales_order_id"].as_str().unwrap().to_string();
+    let ack = inbound["acknowledgment"]["raw_content"].as_str().unwrap();
+    assert!(ack.contains("*ZZ*SELLER         *ZZ*BUYER          *"));
+    assert!(ack.contains("*000000001*0*P*>~GS*FA*SELLER*BUYER*"));
+    assert!(ack.contains("~AK1*PO*42~AK2*850*0001~AK5*A~AK9*A*1*1*1~"));
+
+    let (_, sales_order) = authed_request(&app, Method::GET, &format!("/api/v1/sales/orders/{}", sales_order_id), &token, None).await;
+    assert_eq!(sales_order["status"], "Draft");
+    assert_eq!(sales_order["total"], 62.5);
+
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/edi/inbound", &token, Some(json!({ "raw_content": order }))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, sent) = authed_request(&app, Method::POST, "/api/v1/edi/outbound", &token, Some(json!({
+        "transaction_type": "X12_855", "partner_id": partner["id"], "reference_id": sales_order_id
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", sent);
+    assert_eq!(sent["control_number"], "OUT:BUYER:000000002:2:0002");
+    assert!(sent["raw_content"].as_str().unwrap().contains("~BAK*00*AD*PO-1001*20261018"));
+
+    let reply = envelope(43, "FA", "ST*997*0001~AK1*PR*2~AK2*855*0002~AK5*A~AK9*A*1*1*1~SE*6*0001~");
+    let (status, inbound) = authed_request(&app, Method::POST, "/api/v1/edi/inbound", &token, Some(json!({ "raw_content": reply }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", inbound);
+    assert!(inbound["acknowledgment"].is_null());
+    let (_, acknowledged) = authed_request(&app, Method::GET, &format!("/api/v1/edi/transactions/{}", sent["transaction_id"].as_str().unwrap()), &token, None).await;
+    assert_eq!(acknowledged["status"], "Acknowledged");
+
+    let unknown_sku = envelope(44, "PO", "ST*850*0002~BEG*00*SA*PO-1002**20261018~PO1*1*1*EA*3**VP*NOPE~SE*4*0002~");
+    let (_, inbound) = authed_request(&app, Method::POST, "/api/v1/edi/inbound", &token, Some(json!({ "raw_content": unknown_sku }))).await;
+    assert_eq!(inbound["transactions"][0]["status"], "Error");
+    assert!(inbound["transactions"][0]["error_message"].as_str().unwrap().contains("NOPE"));
+}
+
+#[tokio::test]
+async fn test_bank_payment_files_and_statement_import() {
+    init_test_env();
+    let pool = setup_test_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+    let (token, _) = register_user(&app, "treasury").await;
+
+    let (_, cash) = authed_request(&app, Method::POST, "/api/v1/finance/accounts", &token, Some(json!({
+        "code": "1010", "name": "Operating Cash", "account_type": "Asset"
+    }))).await;
+    let bank_account = erp_finance::BankReconciliationService::create_bank_account(
+        &pool,
+        uuid::Uuid::parse_str(cash["id"].as_str().unwrap()).unwrap(),
+        "First Bank",
+        "123456789",
+        erp_finance::BankAccountType::Checking,
+        "USD",
+        None,
+    ).await.unwrap();
+    let (status, originator) = authed_request(&app, Method::PUT, &format!("/api/v1/bank/accounts/{}/originator", bank_account.id), &token, Some(json!({
+        "company_name": "Acme Manufacturing", "routing_number": "021000021", "ach_company_id": "1234567890"
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", originator);
+
+    let (_, vendor) = authed_request(&app, Method::POST, "/api/v1/purchasing/vendors", &token, Some(json!({ "code": "V001", "name": "Widget Supply" }))).await;
+    let vendor_id = vendor["id"].as_str().unwrap();
+    let (status, _) = authed_request(&app, Method::PUT, &format!("/api/v1/bank/vendors/{}/bank-account", vendor_id), &token, Some(json!({
+        "account_name": "Widget Supply", "routing_number": "011000016", "account_number": "000123456"
+    }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = authed_request(&app, Method::PUT, &format!("/api/v1/bank/vendors/{}/bank-account", vendor_id), &token, Some(json!({
+        "account_name": "Widget Supply", "routing_number": "011000015", "account_number": "000123456"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let now = chrono::Utc::now();
+    let (_, bill) = authed_request(&app, Method::POST, "/api/v1/vendor-bills", &token, Some(json!({
+        "vendor_invoice_number": "INV-77", "vendor_id": vendor_id,
+        "bill_date": now.to_rfc3339(), "due_date": (now + chrono::Duration::days(30)).to_rfc3339(),
+        "lines": [{ "description": "Widgets", "quantity": 10, "unit_price": 2500, "tax_rate": 0.0 }]
+    }))).await;
+    let bill_id = bill["id"].as_str().unwrap();
+    let value_date = (now + chrono::Duration::days(1)).date_naive().to_string();
+    let pay = |amount: Option<i64>| json!({
+        "bank_account_id": bank_account.id, "format": "NACHA", "value_date": value_date,
+        "bills": [{ "bill_id": bill_id, "amount": amount }]
+    });
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/bank/payment-files", &token, Some(pay(Some(20000)))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    authed_request(&app, Method::POST, &format!("/api/v1/vendor-bills/{}/submit", bill_id), &token, None).await;
+    authed_request(&app, Method::POST, &format!("/api/v1/vendor-bills/{}/approve", bill_id), &token, None).await;
+    let euro_account = erp_finance::BankReconciliationService::create_bank_account(
+        &pool,
+        uuid::Uuid::parse_str(cash["id"].as_str().unwrap()).unwrap(),
+        "First Bank",
+        "987654321",
+        erp_finance::BankAccountType::Checking,
+        "EUR",
+        None,
+    ).await.unwrap();
+    let (status, _) = authed_request(&app, Method::PUT, &format!("/api/v1/bank/accounts/{}/originator", euro_account.id), &token, Some(json!({
+        "company_name": "Acme Manufacturing", "iban": "DE89370400440532013000", "bic": "COBADEFFXXX"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let mut in_euros = pay(Some(20000));
+    in_euros["bank_account_id"] = json!(euro_account.id);
+    let (status, mismatch) = authed_request(&app, Method::POST, "/api/v1/bank/payment-files", &token, Some(in_euros)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(mismatch.to_string().contains("pays in EUR"), "{}", mismatch);
+    let (status, generated) = authed_request(&app, Method::POST, "/api/v1/bank/payment-files", &token, Some(pay(Some(20000)))).await;
+    assert_eq!(status, StatusCode::OK, "{}", generated);
+    assert_eq!(generated["file"]["total_amount"], 20000);
+    let content = generated["file"]["file_content"].as_str().unwrap();
+    assert!(content.lines().all(|record| record.len() == 94));
+    assert_eq!(content.lines().count() % 10, 0);
+    assert!(content.lines().nth(3).unwrap().starts_with("705INV-77"));
+    let (_, partly_paid) = authed_request(&app, Method::GET, &format!("/api/v1/vendor-bills/{}", bill_id), &token, None).await;
+    assert_eq!(partly_paid["status"], "PartiallyPaid");
+
+    let (status, rest) = authed_request(&app, Method::POST, "/api/v1/bank/payment-files", &token, Some(pay(None))).await;
+    assert_eq!(status, StatusCode::OK, "{}", rest);
+    assert_eq!(rest["payments"][0]["amount"], 5000);
+    assert!(rest["file"]["file_content"].as_str().unwrap().starts_with("101 021000021 021000021"));
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/bank/payment-files", &token, Some(pay(None))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let statement = "01,BANK,ACME,261018,0800,1,,,2/\n\
+        02,ACME,BANK,1,261018,2400,USD,2/\n\
+        03,123456789,USD,010,500000,,,015,480000,,/\n\
+        16,455,20000,Z,ACH9001,PAY-1,VENDOR PAYMENT WIDGET SUPPLY\n\
+        49,1000000,3/\n\
+        98,1000000,1,5/\n\
+        99,1000000,1,7/\n";
+    let import = json!({ "format": "BAI2", "content": statement });
+    let (status, imported) = authed_request(&app, Method::POST, "/api/v1/bank/statements/import", &token, Some(import.clone())).await;
+    assert_eq!(status, StatusCode::OK, "{}", imported);
+    assert_eq!(imported[0]["transaction_count"], 1);
+    assert_eq!(imported[0]["total_debits"], 20000);
+    assert_eq!(imported[0]["statement"]["closing_balance"], 480000);
+    let (debit, balance): (i64, i64) = sqlx::query_as("SELECT debit, balance FROM bank_transactions WHERE bank_account_id = ?")
+        .bind(bank_account.id.to_string())
+        .fetch_one(&pool).await.unwrap();
+    assert_eq!((debit, balance), (20000, 480000));
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/bank/statements/import", &token, Some(import)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_tax_engine_calculates_and_records_by_address() {
+    init_test_env();
+    let pool = setup_test_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+    let (token, _) = register_user(&app, "taxadmin").await;
+
+    let (status, us) = authed_request(&app, Method::POST, "/api/v1/tax/jurisdictions", &token, Some(json!({
+        "code": "US", "name": "United States", "country_code": "US"
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", us);
+    let (_, texas) = authed_request(&app, Method::POST, "/api/v1/tax/jurisdictions", &token, Some(json!({
+        "code": "US-TX", "name": "Texas", "country_code": "US", "state_code": "TX", "parent_jurisdiction_id": us["id"]
+    }))).await;
+    let (_, austin) = authed_request(&app, Method::POST, "/api/v1/tax/jurisdictions", &token, Some(json!({
+        "code": "US-TX-AUS", "name": "Austin", "country_code": "US", "state_code": "TX", "city": "Austin", "parent_jurisdiction_id": texas["id"]
+    }))).await;
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/tax/jurisdictions", &token, Some(json!({
+        "code": "US-TX-TRAVIS", "name": "Travis County", "country_code": "US", "state_code": "TX", "county": "Travis"
+    }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, class) = authed_request(&app, Method::POST, "/api/v1/tax/classes", &token, Some(json!({
+        "code": "FOOD", "name": "Groceries"
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", class);
+    for (jurisdiction, code, rate, class_id) in [
+        (&texas, "TX-STATE", 6.25, None),
+        (&texas, "TX-FOOD", 0.0, class["id"].as_str()),
+        (&austin, "AUS-CITY", 2.0, None),
+    ] {
+        let (status, body) = authed_request(&app, Method::POST, "/api/v1/tax/rates", &token, Some(json!({
+            "jurisdiction_id": jurisdiction["id"], "name": code, "code": code, "rate": rate, "tax_class_id": class_id
+        }))).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+    }
+
+    let (_, product) = authed_request(&app, Method::POST, "/api/v1/inventory/products", &token, Some(json!({
+        "sku": "FOOD-1", "name": "Bread", "product_type": "Goods", "unit_of_measure": "EA"
+    }))).await;
+    let (status, _) = authed_request(&app, Method::PUT, &format!("/api/v1/tax/products/{}/class", product["id"].as_str().unwrap()), &token, Some(json!({
+        "tax_class_id": class["id"]
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let order_id = uuid::Uuid::new_v4();
+    let (status, recorded) = authed_request(&app, Method::POST, "/api/v1/tax/transactions", &token, Some(json!({
+        "transaction_type": "SalesOrder", "transaction_id": order_id, "source": "SalesOrder",
+        "ship_to": { "street": "1 Congress Ave", "city": "Austin", "state": "TX", "postal_code": "78701", "country": "US" },
+        "lines": [{ "amount": 10000 }, { "amount": 5000, "product_id": product["id"] }]
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", recorded);
+    let result = &recorded["result"];
+    assert_eq!(result["jurisdictions"].as_array().unwrap().len(), 3);
+    assert_eq!(result["lines"][0]["tax_amount"]["amount"], 825);
+    assert_eq!(result["lines"][1]["tax_amount"]["amount"], 100);
+    assert_eq!(result["total_tax"]["amount"], 925);
+    assert_eq!(result["total"]["amount"], 15925);
+    assert_eq!(recorded["transactions"].as_array().unwrap().len(), 4);
+
+    let (_, again) = authed_request(&app, Method::POST, "/api/v1/tax/transactions", &token, Some(json!({
+        "transaction_type": "SalesOrder", "transaction_id": order_id,
+        "ship_to": { "street": "1 Main St", "city": "Houston", "state": "TX", "postal_code": "77002", "country": "US" },
+        "lines": [{ "amount": 10000 }]
+    }))).await;
+    assert_eq!(again["result"]["total_tax"]["amount"], 625);
+    let (status, stored) = authed_request(&app, Method::GET, &format!("/api/v1/tax/transactions/SalesOrder/{}", order_id), &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let stored = stored.as_array().unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0]["tax_amount"]["amount"], 625);
+}
+
+async fn graphql(app: &axum::Router, token: &str, query: &str, variables: serde_json::Value) -> serde_json::Value {
+    let (status, body) = authed_request(app, Method::POST, "/api/v1/graphql", token, Some(json!({
+        "query": query, "variables": variables
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    body
+}
+
+#[tokio::test]
+async fn test_graphql_mutations_nested_lookups_pagination_and_permissions() {
+    use futures::StreamExt;
+
+    init_test_env();
+    let pool = setup_unprivileged_db().await;
+    let state = create_test_app(pool.clone());
+    let app = create_router(state.clone());
+
+    let (admin, admin_id) = register_user(&app, "gqladmin").await;
+    sqlx::query("UPDATE users SET role = 'Admin' WHERE id = ?").bind(&admin_id).execute(&pool).await.unwrap();
+    let (clerk, clerk_id) = register_user(&app, "gqlclerk").await;
+
+    let body = graphql(&app, &admin, "mutation($input: ProductInput!) { createProduct(input: $input) { id sku } }", json!({
+        "input": { "sku": "GQL-1", "name": "Widget", "unitOfMeasure": "PCS" }
+    })).await;
+    let product_id = body["data"]["createProduct"]["id"].as_str().unwrap().to_string();
+    let body = graphql(&app, &admin, "mutation($input: CustomerInput!) { createCustomer(input: $input) { id email } }", json!({
+        "input": { "code": "GQL-C", "name": "Acme", "email": "buyer@acme.test" }
+    })).await;
+    assert_eq!(body["data"]["createCustomer"]["email"], "buyer@acme.test");
+    let customer_id = body["data"]["createCustomer"]["id"].as_str().unwrap().to_string();
+
+    let mut order_ids = Vec::new();
+    for quantity in [2, 5] {
+        let body = graphql(&app, &admin, "mutation($input: SalesOrderInput!) { createSalesOrder(input: $input) { id status } }", json!({
+            "input": { "customerId": customer_id, "lines": [
+                { "productId": product_id, "description": "Widget", "quantity": quantity, "unitPrice": 1000 }
+            ] }
+        })).await;
+        assert!(body["errors"].is_null(), "{}", body);
+        order_ids.push(body["data"]["createSalesOrder"]["id"].as_str().unwrap().to_string());
+    }
+
+    let page_query = "query($after: String) { salesOrders(first: 1, after: $after) {
+        totalCount pageInfo { hasNextPage endCursor }
+        edges { node { id customer { name } lines { quantity product { sku } } } }
+    } }";
+    let body = graphql(&app, &admin, page_query, json!({})).await;
+    let first = &body["data"]["salesOrders"];
+    assert_eq!(first["totalCount"], 2);
+    assert_eq!(first["pageInfo"]["hasNextPage"], true);
+    let node = &first["edges"][0]["node"];
+    assert_eq!(node["customer"]["name"], "Acme");
+    assert_eq!(node["lines"][0]["product"]["sku"], "GQL-1");
+    assert_eq!(node["lines"][0]["quantity"], 5);
+
+    let body = graphql(&app, &admin, page_query, json!({ "after": first["pageInfo"]["endCursor"] })).await;
+    let second = &body["data"]["salesOrders"];
+    assert_eq!(second["edges"].as_array().unwrap().len(), 1);
+    assert_eq!(second["pageInfo"]["hasNextPage"], false);
+    assert_ne!(second["edges"][0]["node"]["id"], node["id"]);
+
+    // Read-only users can query but not mutate.
+    let body = graphql(&app, &clerk, "mutation { createCustomer(input: { code: \"X\", name: \"X\" }) { id } }", json!({})).await;
+    assert_eq!(body["errors"][0]["extensions"]["code"], "FORBIDDEN");
+
+    // Row scopes and hidden fields follow the access policy.
+    grant_role(&pool, "gql_rep", &[&clerk_id], &[("sales_orders", "Own", ""), ("customers", "All", "")], &[("customers", "email")]).await;
+    let body = graphql(&app, &clerk, "{ salesOrders { totalCount } customers { edges { node { name email } } } }", json!({})).await;
+    assert_eq!(body["data"]["salesOrders"]["totalCount"], 0);
+    assert_eq!(body["data"]["customers"]["edges"][0]["node"]["name"], "Acme");
+    assert!(body["data"]["customers"]["edges"][0]["node"]["email"].is_null());
+
+    // Subscribers hear about mutations on the entity they watch.
+    let viewer = erp_graphql::Viewer::new(admin_id.clone(), state.authz.clone());
+    let mut request = async_graphql::Request::new("subscription { entityChanged(entity: \"sales_order\") { id action } }");
+    request.data = erp_graphql::session_data(&pool, viewer);
+    let mut stream = state.graphql.execute_stream(request);
+    loop {
+        erp_graphql::subscription::publish_change("sales_order", &order_ids[0], "warmup", &admin_id);
+        if tokio::time::timeout(std::time::Duration::from_millis(50), stream.next()).await.is_ok() {
+            break;
+        }
+    }
+    let body = graphql(&app, &admin, "mutation($id: String!) { cancelSalesOrder(id: $id) { status } }", json!({ "id": order_ids[1] })).await;
+    assert!(body["errors"].is_null(), "{}", body);
+    let change = loop {
+        let response = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next()).await.unwrap().unwrap();
+        let change = response.data.into_json().unwrap()["entityChanged"].clone();
+        if change["action"] != "warmup" {
+            break change;
+        }
+    };
+    assert_eq!(change["action"], "cancelled");
+    assert_eq!(change["id"], order_ids[1].as_str());
+
+    // Changes made through REST reach the same subscribers.
+    let (status, _) = authed_request(&app, Method::POST, &format!("/api/v1/sales/orders/{}/cancel", order_ids[0]), &admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let response = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next()).await.unwrap().unwrap();
+    let change = response.data.into_json().unwrap()["entityChanged"].clone();
+    assert_eq!(change["action"], "cancelled");
+    assert_eq!(change["id"], order_ids[0].as_str());
+}
+
+#[tokio::test]
+async fn test_global_search_indexes_automatically_and_filters_by_permission() {
+    init_test_env();
+    let pool = setup_unprivileged_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+
+    let (admin, admin_id) = register_user(&app, "searchadmin").await;
+    sqlx::query("UPDATE users SET role = 'Admin' WHERE id = ?").bind(&admin_id).execute(&pool).await.unwrap();
+    let (rep, rep_id) = register_user(&app, "searchrep").await;
+    sqlx::query("UPDATE users SET role = 'Sales' WHERE id = ?").bind(&rep_id).execute(&pool).await.unwrap();
+    grant_role(&pool, "searcher", &[&rep_id], &[("customers", "Own", "")], &[("customers", "email")]).await;
+    sqlx::query(
+        "INSERT INTO permissions (id, code, name, module, resource, action, created_at) VALUES ('search-read', 'search:*:read', 'Search', 'search', '*', 'read', datetime('now'))"
+    ).execute(&pool).await.unwrap();
+    sqlx::query(
+        "INSERT INTO role_permissions (id, role_id, permission_id, granted_at)
+         SELECT 'searcher-search', id, 'search-read', datetime('now') FROM custom_roles WHERE code = 'searcher'"
+    ).execute(&pool).await.unwrap();
+
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/sales/customers", &admin, Some(json!({
+        "code": "ACME", "name": "Acme Widgets", "email": "buyer@acme.test"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, product) = authed_request(&app, Method::POST, "/api/v1/inventory/products", &admin, Some(json!({
+        "sku": "WID-1", "name": "Industrial Widget", "description": "Heavy duty <b>gear</b> for assembly lines", "unit_of_measure": "PCS"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let product_id = product["id"].as_str().unwrap().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        "INSERT INTO employees (id, employee_number, first_name, last_name, email, birth_date, hire_date, created_at, updated_at)
+         VALUES ('emp-search', 'E-100', 'Wilma', 'Widgeon', 'wilma@example.com', '1990-01-01', '2020-01-01', ?, ?)"
+    ).bind(&now).bind(&now).execute(&pool).await.unwrap();
+
+    // Words match as prefixes, titles outrank other fields, and matches are marked.
+    let (status, body) = authed_request(&app, Method::GET, "/api/v1/search?q=widg", &admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 3);
+    let results = body["results"].as_array().unwrap();
+    let types: Vec<&str> = results.iter().map(|r| r["entity_type"].as_str().unwrap()).collect();
+    assert!(types.contains(&"customer") && types.contains(&"product") && types.contains(&"employee"));
+    let product_hit = results.iter().find(|r| r["entity_type"] == "product").unwrap();
+    assert_eq!(product_hit["highlighted_title"], "Industrial <mark>Widget</mark>");
+
+    let (_, body) = authed_request(&app, Method::GET, "/api/v1/search?q=assembly", &admin, None).await;
+    assert_eq!(body["results"][0]["snippet"], "Heavy duty &lt;b&gt;gear&lt;/b&gt; for <mark>assembly</mark> lines");
+
+    // Typos are corrected when nothing matches as typed.
+    let (_, body) = authed_request(&app, Method::GET, "/api/v1/search?q=industrail", &admin, None).await;
+    assert_eq!(body["corrected_query"], "industrial");
+    assert_eq!(body["results"][0]["entity_id"], product_id.as_str());
+
+    // Updates and deletes reach the index without any call to it.
+    let (status, _) = authed_request(&app, Method::PUT, &format!("/api/v1/inventory/products/{}", product_id), &admin, Some(json!({
+        "sku": "WID-1", "name": "Industrial Sprocket", "unit_of_measure": "PCS"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = authed_request(&app, Method::GET, "/api/v1/search?q=sprocket", &admin, None).await;
+    assert_eq!(body["total"], 1);
+    let (status, _) = authed_request(&app, Method::DELETE, &format!("/api/v1/inventory/products/{}", product_id), &admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = authed_request(&app, Method::GET, "/api/v1/search?q=sprocket", &admin, None).await;
+    assert_eq!(body["total"], 0);
+
+    // The rep may not read employees, and only sees their own customers.
+    let (status, body) = authed_request(&app, Method::GET, "/api/v1/search?q=widg", &rep, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 0, "{}", body);
+    let (status, _) = authed_request(&app, Method::POST, "/api/v1/sales/customers", &rep, Some(json!({
+        "code": "WIDGCO", "name": "Widget Co", "email": "orders@gizmo.test"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = authed_request(&app, Method::GET, "/api/v1/search?q=widg", &rep, None).await;
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["results"][0]["title"], "Widget Co");
+    // Fields hidden from the rep cannot be searched on either.
+    let (_, body) = authed_request(&app, Method::GET, "/api/v1/search?q=gizmo", &rep, None).await;
+    assert_eq!(body["total"], 0, "{}", body);
+    let (_, body) = authed_request(&app, Method::GET, "/api/v1/search?q=gizmo", &admin, None).await;
+    assert_eq!(body["total"], 1);
+
+    // Rebuilding restores entries for existing records.
+    sqlx::query("DELETE FROM search_index").execute(&pool).await.unwrap();
+    let (status, body) = authed_request(&app, Method::POST, "/api/v1/search/rebuild", &admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["indexed_count"], 3);
+    let (_, body) = authed_request(&app, Method::GET, "/api/v1/search?q=widg", &admin, None).await;
+    assert_eq!(body["total"], 3);
+}
+
+#[tokio::test]
+async fn test_restore_rebuilds_search_index() {
+    init_test_env();
+    // Backups copy the database file, so this one cannot live in memory.
+    let dir = std::env::temp_dir().join(format!("erp-restore-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let options = sqlx::sqlite::SqliteConnectOptions::new()
+        .filename(dir.join("erp.db"))
+        .create_if_missing(true)
+        .foreign_keys(true);
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(4).connect_with(options).await.unwrap();
+    erp_api::db::run_migrations(&pool).await.unwrap();
+    let app = create_router(create_test_app(pool.clone()));
+    let (admin, admin_id) = register_user(&app, "restoreadmin").await;
+    sqlx::query("UPDATE users SET role = 'Admin' WHERE id = ?").bind(&admin_id).execute(&pool).await.unwrap();
+
+    let (status, customer) = authed_request(&app, Method::POST, "/api/v1/sales/customers", &admin, Some(json!({
+        "code": "ACME", "name": "Acme Widgets", "email": "buyer@acme.test"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, product) = authed_request(&app, Method::POST, "/api/v1/inventory/products", &admin, Some(json!({
+        "sku": "WID-1", "name": "Industrial Widget", "unit_of_measure": "PCS"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    // Entries for other records are indexed by hand and only exist in search_index.
+    sqlx::query(
+        "INSERT INTO search_index (id, entity_type, entity_id, title, created_at, updated_at)
+         VALUES ('memo:1', 'memo', '1', 'Widget recall memo', datetime('now'), datetime('now'))"
+    ).execute(&pool).await.unwrap();
+
+    let service = erp_backup::BackupService::new(pool.clone()).with_backup_dir(dir.join("backups"));
+    let backup = service.execute_backup(None).await.unwrap();
+    assert_eq!(backup.status, erp_backup::BackupStatus::Completed, "{:?}", backup.error_message);
+
+    sqlx::query("UPDATE customers SET name = 'Acme Sprockets' WHERE id = ?")
+        .bind(customer["id"].as_str().unwrap()).execute(&pool).await.unwrap();
+    let (status, _) = authed_request(&app, Method::DELETE, &format!("/api/v1/inventory/products/{}", product["id"].as_str().unwrap()), &admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let restore = service.restore_backup(backup.base.id, None).await.unwrap();
+    assert_eq!(restore.status, erp_backup::RestoreStatus::Completed, "{:?}", restore.error_message);
+
+    let (status, body) = authed_request(&app, Method::GET, "/api/v1/search?q=widg", &admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let mut titles: Vec<&str> = body["results"].as_array().unwrap().iter().map(|r| r["title"].as_str().unwrap()).collect();
+    titles.sort();
+    assert_eq!(titles, vec!["Acme Widgets", "Industrial Widget", "Widget recall memo"]);
+    let (_, body) = authed_request(&app, Method::GET, "/api/v1/search?q=sprocket", &admin, None).await;
+    assert_eq!(body["total"], 0);
+    sqlx::query("INSERT INTO search_index_fts (search_index_fts) VALUES ('integrity-check')").execute(&pool).await.unwrap();
+
+    pool.close().await;
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+async fn upload_file(app: &axum::Router, uri: &str, token: &str, file_name: &str, content: &[u8], fields: &[(&str, &str)]) -> (StatusCode, serde_json::Value) {
+    let boundary = "erp-test-boundary";
+    let mut body = Vec::new();
+    for (name, value) in fields {
+        body.extend_from_slice(format!("--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n", boundary, name, value).as_bytes());
+    }
+    body.extend_from_slice(format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        boundary, file_name
+    ).as_bytes());
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    let request = Request::builder()
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from(([127, 0, 9, 9], 40000))))
+        .method(Method::POST)
+        .uri(uri)
+        .header("Content-Type", format!("multipart/form-data; boundary={}", boundary))
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap_or(json!({})))
+}
+
+async fn download_file(app: &axum::Router, uri: &str, token: Option<&str>) -> (StatusCode, axum::http::HeaderMap, Vec<u8>) {
+    let mut builder = Request::builder()
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from(([127, 0, 9, 10], 40000))))
+        .uri(uri);
+    if let Some(token) = token {
+        builder = builder.header("Authorization", format!("Bearer {}", token));
+    }
+    let response = app.clone().oneshot(builder.body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, headers, bytes.to_vec())
+}
+
+#[tokio::test]
+async fn test_blob_storage_dedupes_uploads_signs_downloads_and_enforces_folder_policy() {
+    use sha2::Digest;
+    init_test_env();
+    let pool = setup_test_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+    let (token, _) = register_user(&app, "filer").await;
+
+    let pdf = b"%PDF-1.4\n1 0 obj << /Type /Catalog >> endobj\n%%EOF\n".to_vec();
+    let pdf_sha: String = sha2::Sha256::digest(&pdf).iter().map(|b| format!("{:02x}", b)).collect();
+    let entity_id = uuid::Uuid::new_v4();
+    let uri = format!("/api/v1/attachments?entity_type=orders&entity_id={}", entity_id);
+
+    // Declared as octet-stream, stored as a PDF, and the same bytes are kept once.
+    let (status, first) = upload_file(&app, &uri, &token, "scan.pdf", &pdf, &[("sha256", &pdf_sha)]).await;
+    assert_eq!(status, StatusCode::OK, "{}", first);
+    assert_eq!(first["mime_type"], "application/pdf");
+    assert_eq!(first["checksum"], pdf_sha.as_str());
+    let (status, second) = upload_file(&app, &uri, &token, "copy.pdf", &pdf, &[]).await;
+    assert_eq!(status, StatusCode::OK);
+    let refs: i64 = sqlx::query_scalar("SELECT ref_count FROM blobs WHERE sha256 = ?").bind(&pdf_sha).fetch_one(&pool).await.unwrap();
+    assert_eq!(refs, 2);
+
+    let (status, body) = upload_file(&app, &uri, &token, "scan.pdf", &pdf, &[("sha256", &"0".repeat(64))]).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("Checksum mismatch"));
+
+    let id = first["id"].as_str().unwrap();
+    let (status, headers, bytes) = download_file(&app, &format!("/api/v1/attachments/{}/content", id), Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(bytes, pdf);
+    assert_eq!(headers["content-type"], "application/pdf");
+    assert!(headers["content-disposition"].to_str().unwrap().contains("scan.pdf"));
+
+    // A signed link works without a token, but not once altered.
+    let (status, link) = authed_request(&app, Method::GET, &format!("/api/v1/attachments/{}/download-url?expires_in_minutes=5", id), &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let url = link["url"].as_str().unwrap();
+    let (status, _, bytes) = download_file(&app, url, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(bytes, pdf);
+    let (status, _, _) = download_file(&app, &url.replace("name=scan.pdf", "name=other.pdf"), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = authed_request(&app, Method::DELETE, &format!("/api/v1/attachments/{}", second["id"].as_str().unwrap()), &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let refs: i64 = sqlx::query_scalar("SELECT ref_count FROM blobs WHERE sha256 = ?").bind(&pdf_sha).fetch_one(&pool).await.unwrap();
+    assert_eq!(refs, 1);
+
+    // Folder limits apply to document versions.
+    let (status, folder) = authed_request(&app, Method::POST, "/api/v1/documents/folders", &token, Some(json!({
+        "name": "Scans", "max_file_size": 100, "allowed_mime_types": ["image/*"]
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", folder);
+    assert_eq!(folder["allowed_mime_types"], json!(["image/*"]));
+    let folder_id = folder["id"].as_str().unwrap();
+    let (status, doc) = authed_request(&app, Method::POST, "/api/v1/documents/documents", &token, Some(json!({
+        "title": "Delivery note", "folder_id": folder_id, "file_name": "note.pdf", "file_path": "",
+        "file_size": 0, "mime_type": "application/pdf", "checksum": ""
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", doc);
+    let versions_uri = format!("/api/v1/documents/documents/{}/versions", doc["id"].as_str().unwrap());
+
+    let (status, body) = upload_file(&app, &versions_uri, &token, "note.pdf", &pdf, &[]).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("not allowed"), "{}", body);
+
+    let (status, _) = authed_request(&app, Method::PUT, &format!("/api/v1/documents/folders/{}/upload-policy", folder_id), &token, Some(json!({
+        "max_file_size": 100, "allowed_mime_types": ["application/pdf"]
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, v1) = upload_file(&app, &versions_uri, &token, "note.pdf", &pdf, &[("change_summary", "Signed copy")]).await;
+    assert_eq!(status, StatusCode::OK, "{}", v1);
+    assert_eq!(v1["version"], 1);
+    assert_eq!(v1["checksum"], pdf_sha.as_str());
+
+    let mut revised = pdf.clone();
+    revised.extend_from_slice(b"% revised\n");
+    let (status, v2) = upload_file(&app, &versions_uri, &token, "note-v2.pdf", &revised, &[]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v2["version"], 2);
+    let (status, _) = upload_file(&app, &versions_uri, &token, "big.pdf", &[b'%'; 200], &[]).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let doc_uri = format!("/api/v1/documents/documents/{}", doc["id"].as_str().unwrap());
+    let (_, headers, bytes) = download_file(&app, &format!("{}/content", doc_uri), Some(&token)).await;
+    assert_eq!(bytes, revised);
+    assert!(headers["content-disposition"].to_str().unwrap().contains("note-v2.pdf"));
+    let (_, _, bytes) = download_file(&app, &format!("{}/versions/1/content", doc_uri), Some(&token)).await;
+    assert_eq!(bytes, pdf);
+    let (_, body) = authed_request(&app, Method::GET, &doc_uri, &token, None).await;
+    assert_eq!(body["version"], 2);
+    let refs: i64 = sqlx::query_scalar("SELECT ref_count FROM blobs WHERE sha256 = ?").bind(&pdf_sha).fetch_one(&pool).await.unwrap();
+    assert_eq!(refs, 2, "the first version shares the attachment's blob");
+}
+
+#[tokio::test]
+async fn test_reports_run_within_the_callers_row_scope_and_download_their_output() {
+    init_test_env();
+    let pool = setup_unprivileged_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+
+    let (alice, alice_id) = register_user(&app, "alice").await;
+    let (bob, bob_id) = register_user(&app, "bob").await;
+    let (manager, manager_id) = register_user(&app, "salesmanager").await;
+    sqlx::query("UPDATE users SET role = 'Admin' WHERE id = ?").bind(&manager_id).execute(&pool).await.unwrap();
+    grant_role(&pool, "sales_rep", &[&alice_id, &bob_id], &[("customers", "Own", ""), ("sales_orders", "Own", "")], &[]).await;
+    for code in ["sales:*:*", "reports:*:read", "reports:run:write"] {
+        sqlx::query("INSERT OR IGNORE INTO permissions (id, code, name, module, resource, action, created_at) VALUES (?, ?, ?, '*', '*', '*', datetime('now'))")
+            .bind(code).bind(code).bind(code)
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO role_permissions (id, role_id, permission_id, granted_at)
+                     SELECT ?, r.id, p.id, datetime('now') FROM custom_roles r, permissions p WHERE r.code = 'sales_rep' AND p.code = ?")
+            .bind(format!("sales-rep-{}", code)).bind(code)
+            .execute(&pool).await.unwrap();
+    }
+
+    let product_id = uuid::Uuid::new_v4().to_string();
+    sqlx::query("INSERT INTO products (id, sku, name, unit_of_measure, created_at, updated_at) VALUES (?, 'SKU-RPT', 'Widget', 'PCS', ?, ?)")
+        .bind(&product_id).bind(chrono::Utc::now().to_rfc3339()).bind(chrono::Utc::now().to_rfc3339())
+        .execute(&pool).await.unwrap();
+    for (token, code, price) in [(&alice, "CUST-A", 1250), (&bob, "CUST-B", 4000)] {
+        let (status, customer) = authed_request(&app, Method::POST, "/api/v1/sales/customers", token, Some(json!({ "code": code, "name": code }))).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = authed_request(&app, Method::POST, "/api/v1/sales/orders", token, Some(json!({
+            "customer_id": customer["id"],
+            "lines": [{ "product_id": product_id, "description": "Widget", "quantity": 2, "unit_price": price }]
+        }))).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, body) = authed_request(&app, Method::POST, "/api/v1/reports/definitions", &manager, Some(json!({
+        "name": "Orders by Customer", "code": "ORDERS-BY-CUSTOMER", "category": "Sales",
+        "query": { "entity": "sales_orders", "columns": [{ "field": "password_hash" }] }
+    }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    let (status, report) = authed_request(&app, Method::POST, "/api/v1/reports/definitions", &manager, Some(json!({
+        "name": "Orders by Customer", "code": "ORDERS-BY-CUSTOMER", "category": "Sales", "default_format": "CSV",
+        "query": {
+            "entity": "sales_orders",
+            "columns": [{ "field": "customer.code" }, { "field": "subtotal", "aggregate": "Sum", "total": true }],
+            "group_by": ["customer.code"],
+            "sort": [{ "field": "customer_code" }]
+        }
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", report);
+    let report_id = report["id"].as_str().unwrap();
+
+    let run = |format: &str| json!({ "report_definition_id": report_id, "format": format });
+    let (status, everything) = authed_request(&app, Method::POST, "/api/v1/reports/run", &manager, Some(run("CSV"))).await;
+    assert_eq!(status, StatusCode::OK, "{}", everything);
+    assert_eq!(everything["status"], "Completed");
+    assert_eq!(everything["row_count"], 2);
+    let (status, headers, bytes) = download_file(&app, &format!("/api/v1/reports/executions/{}/content", everything["id"].as_str().unwrap()), Some(&manager)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers["content-type"].to_str().unwrap().starts_with("text/csv"));
+    assert_eq!(String::from_utf8(bytes).unwrap(), "Customer Code,Sum of Subtotal\r\nCUST-A,25.00\r\nCUST-B,80.00\r\n");
+
+    let (status, own) = authed_request(&app, Method::POST, "/api/v1/reports/run", &bob, Some(run("JSON"))).await;
+    assert_eq!(status, StatusCode::OK, "{}", own);
+    let own_id = own["id"].as_str().unwrap();
+    let (_, _, bytes) = download_file(&app, &format!("/api/v1/reports/executions/{}/content", own_id), Some(&bob)).await;
+    let output: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(output["rows"], json!([{ "customer_code": "CUST-B", "sum_subtotal": 8000 }]));
+    assert_eq!(output["totals"], json!({ "sum_subtotal": 8000 }));
+
+    // Executions and their output belong to whoever ran them.
+    let (status, _) = authed_request(&app, Method::GET, &format!("/api/v1/reports/executions/{}", own_id), &alice, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _, _) = download_file(&app, &format!("/api/v1/reports/executions/{}/content", own_id), Some(&alice)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, listed) = authed_request(&app, Method::GET, "/api/v1/reports/executions", &bob, None).await;
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert_eq!(listed[0]["checksum"], own["checksum"]);
+
+    let (status, link) = authed_request(&app, Method::GET, &format!("/api/v1/reports/executions/{}/download-url", own_id), &bob, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, headers, _) = download_file(&app, link["url"].as_str().unwrap(), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers["content-disposition"].to_str().unwrap().contains("ORDERS-BY-CUSTOMER-"));
+
+    let (status, xlsx) = authed_request(&app, Method::POST, "/api/v1/reports/run", &alice, Some(run("Excel"))).await;
+    assert_eq!(status, StatusCode::OK, "{}", xlsx);
+    assert_eq!(xlsx["row_count"], 1);
+    let (_, headers, bytes) = download_file(&app, &format!("/api/v1/reports/executions/{}/content", xlsx["id"].as_str().unwrap()), Some(&alice)).await;
+    assert_eq!(headers["content-type"], "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet");
+    assert!(bytes.starts_with(b"PK\x03\x04"));
+}
+
+#[tokio::test]
+async fn test_standard_templates_generate_pdfs_and_serve_them() {
+    init_test_env();
+    let pool = setup_test_db().await;
+    let app = create_router(create_test_app(pool.clone()));
+    let (token, user_id) = register_user(&app, "printer").await;
+
+    let company = json!({ "name": "Acme Ltd", "address": "1 Main Street, Springfield", "tax_id": "GB123456789" });
+    let party = json!({ "name": "Globex", "address": "9 Harbour Road" });
+    let lines: Vec<_> = (1..=60).map(|i| json!({
+        "sku": format!("SKU-{:03}", i), "description": format!("Widget {}", i), "location": "A-01-01", "lot": "L1",
+        "quantity": 2, "ordered": 2, "shipped": 2, "unit_price": 1250, "amount": 2500
+    })).collect();
+    let documents = [
+        ("STD_SALES_INVOICE", json!({ "company": company, "customer": party, "invoice_number": "INV-1001", "invoice_date": "2026-03-01",
+            "due_date": "2026-03-31", "currency": "EUR", "lines": lines, "subtotal": 150000, "tax": 30000, "total": 180000 })),
+        ("STD_PURCHASE_ORDER", json!({ "company": company, "supplier": party, "ship_to": party, "po_number": "PO-7", "order_date": "2026-03-01",
+            "expected_date": "2026-03-10", "currency": "USD", "lines": lines, "total": 150000 })),
+        ("STD_PACKING_SLIP", json!({ "company": company, "ship_to": party, "shipment_number": "SHP-5", "order_number": "SO-5",
+            "ship_date": "2026-03-02", "carrier": "DHL", "tracking_number": "1Z999", "lines": lines })),
+        ("STD_PICK_LIST", json!({ "company": company, "pick_list_number": "PL-3", "warehouse": "Main", "pick_date": "2026-03-02", "lines": lines })),
+        ("STD_CREDIT_NOTE", json!({ "company": company, "customer": party, "credit_note_number": "CN-2", "issue_date": "2026-03-05",
+            "invoice_number": "INV-1001", "reason": "Damaged", "currency": "GBP", "lines": lines, "subtotal": 2500, "tax": 500, "total": 3000 })),
+        ("STD_PAYSLIP", json!({ "company": company, "employee": { "name": "Jo Bloggs", "number": "E-17", "department": "Finance" },
+            "period_start": "2026-03-01", "period_end": "2026-03-31", "pay_date": "2026-03-31", "currency": "USD",
+            "earnings": [{ "description": "Salary", "amount": 500000 }], "gross_pay": 500000,
+            "deductions": [{ "description": "Income tax", "amount": 100000 }], "total_deductions": 100000, "net_pay": 400000 })),
+    ];
+    for (code, variables) in documents {
+        let (status, document) = authed_request(&app, Method::POST, "/api/v1/templates/documents", &token, Some(json!({
+            "template_code": code, "name": code, "variables": variables, "locale": "en-US",
+            "related_entity_type": "test", "related_entity_id": uuid::Uuid::new_v4()
+        }))).await;
+        assert_eq!(status, StatusCode::CREATED, "{}: {}", code, document);
+        assert_eq!(document["output_format"], "PDF");
+        assert_eq!(document["generated_by"], user_id.as_str());
+        assert!(document["content"].is_null());
+
+        let (status, headers, bytes) = download_file(&app, &format!("/api/v1/templates/documents/{}/content", document["id"].as_str().unwrap()), Some(&token)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers["content-type"], "application/pdf");
+        assert!(headers["content-disposition"].to_str().unwrap().contains(&format!("{}.pdf", code)));
+        assert!(bytes.starts_with(b"%PDF-"));
+        assert_eq!(document["file_size"], bytes.len());
+        let pages = bytes.windows(b"/Type /Page ".len()).filter(|w| w == b"/Type /Page ").count();
+        assert!(pages >= if code == "STD_PAYSLIP" { 1 } else { 2 }, "{} has {} pages", code, pages);
+    }
+
+    let (status, body) = authed_request(&app, Method::POST, "/api/v1/templates/documents", &token, Some(json!({
+        "template_code": "STD_PAYSLIP", "name": "payslip", "variables": {}, "locale": "xx-XX"
+    }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+
+    // Text formats keep their output in the document, escaped for the format.
+    let (status, template) = authed_request(&app, Method::POST, "/api/v1/templates", &token, Some(json!({
+        "name": "Note", "code": "NOTE_HTML", "template_type": "Letter", "format": "HTML", "body": "<p>{{note}}</p>"
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", template);
+    let (status, document) = authed_request(&app, Method::POST, "/api/v1/templates/documents", &token, Some(json!({
+        "template_id": template["id"], "name": "note", "variables": { "note": "<script>" }
+    }))).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", document);
+    assert_eq!(document["content"], "<p>&lt;script&gt;</p>");
+    let id = document["id"].as_str().unwrap();
+    let (status, headers, bytes) = download_file(&app, &format!("/api/v1/templates/documents/{}/content", id), Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers["content-type"].to_str().unwrap().starts_with("text/html"));
+    assert_eq!(bytes, b"<p>&lt;script&gt;</p>");
+    let (status, _) = authed_request(&app, Method::GET, &format!("/api/v1/templates/documents/{}/download-url", id), &token, None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// A one-page invoice PDF with a text layer, as accounting systems send them.
+fn invoice_pdf(number: &str, total: &str) -> Vec<u8> {
+    use erp_core::pdf::{text_width, Font, PdfWriter};
+    let mut pdf = PdfWriter::new((595.0, 842.0), "Invoice");
+    pdf.begin_page();
+    let mut text = |x: f32, y: f32, size: f32, text: &str| {
+        let x = if x < 0.0 { -x - text_width(text, Font::Regular, size) } else { x };
+        pdf.text(x, 842.0 - y, Font::Regular, size, text);
+    };
+    text(50.0, 60.0, 18.0, "Initech Office Supplies");
+    text(50.0, 76.0, 9.0, "Remit to accounts@initech-supplies.com");
+    text(360.0, 110.0, 10.0, "Invoice #");
+    text(460.0, 110.0, 10.0, number);
+    text(360.0, 125.0, 10.0, "Date:");
+    text(460.0, 125.0, 10.0, "14/03/2026");
+    for (y, row) in [(200.0, ["Item", "Qty", "Price", "Total"]), (216.0, ["Copier paper A4", "10", "4.50", "45.00"]), (230.0, ["Toner cartridge", "2", "27.50", "55.00"])] {
+        text(50.0, y, 10.0, row[0]);
+        text(-330.0, y, 10.0, row[1]);
+        text(-430.0, y, 10.0, row[2]);
+        text(-545.0, y, 10.0, row[3]);
+    }
+    text(360.0, 280.0, 10.0, "Subtotal");
+    text(-545.0, 280.0, 10.0, "100.00");
+    text(360.0, 295.0, 10.0, "VAT");
+    text(-545.0, 295.0, 10.0, "20.00");
+    text(360.0, 310.0, 10.0, "Amount Due");
+    text(-545.0, 310.0, 10.0, total);
+    pdf.finish();
+    pdf.take_output()
+}
+
+#[tokio::test]
+async fn test_captured_invoices_become_vendor_bills_or_wait_for_review() {
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+    init_test_env();
+    let pool = setup_test_db().await;
+    let state = create_test_app(pool.clone());
+    let blobs = state.blobs.clone();
+    let app = create_router(state);
+    let (token, user_id) = register_user(&app, "capture").await;
+
+    let (status, vendor) = authed_request(&app, Method::POST, "/api/v1/purchasing/vendors", &token, Some(json!({
+        "code": "INITECH", "name": "Initech Office Supplies", "email": "accounts@initech-supplies.com"
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", vendor);
+
+    let (status, doc) = authed_request(&app, Method::POST, "/api/v1/ocr/documents", &token, Some(json!({
+        "document_type": "invoice", "filename": "initech-8812.pdf", "content": BASE64.encode(invoice_pdf("8812", "$120.00"))
+    }))).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", doc);
+    assert_eq!(doc["status"], "Pending");
+    assert_eq!(doc["mime_type"], "application/pdf");
+    let doc_id = doc["base"]["id"].as_str().unwrap().to_string();
+
+    let (status, result) = authed_request(&app, Method::POST, &format!("/api/v1/ocr/documents/{}/process", doc_id), &token, None).await;
+    assert_eq!(status, StatusCode::OK, "{}", result);
+    assert_eq!(result["status"], "Completed", "{}", result);
+    let data = &result["extracted_data"];
+    assert_eq!(data["invoice_number"], "8812");
+    assert_eq!(data["invoice_date"], "2026-03-14");
+    assert_eq!(data["total_amount"], 12_000);
+    assert_eq!(data["currency"], "USD");
+    assert_eq!(data["vendor_id"], vendor["id"]);
+    assert_eq!(data["line_items"].as_array().unwrap().len(), 2);
+
+    let (status, bill) = authed_request(&app, Method::GET, &format!("/api/v1/vendor-bills/{}", result["vendor_bill_id"].as_str().unwrap()), &token, None).await;
+    assert_eq!(status, StatusCode::OK, "{}", bill);
+    assert_eq!(bill["status"], "Draft");
+    assert_eq!(bill["vendor_invoice_number"], "8812");
+    assert_eq!(bill["total"], 12_000);
+    assert_eq!(bill["lines"][1]["description"], "Toner cartridge");
+
+    let (status, headers, bytes) = download_file(&app, &format!("/api/v1/ocr/documents/{}/content", doc_id), Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-type"], "application/pdf");
+    assert!(bytes.starts_with(b"%PDF-"));
+
+    // A misread total holds the next invoice back until someone corrects it.
+    let (_, doc) = authed_request(&app, Method::POST, "/api/v1/ocr/documents", &token, Some(json!({
+        "document_type": "invoice", "filename": "initech-8813.pdf", "content": BASE64.encode(invoice_pdf("8813", "$170.00")), "auto_process": true
+    }))).await;
+    assert_eq!(doc["status"], "Queued");
+    let doc_id = doc["base"]["id"].as_str().unwrap().to_string();
+    let runs = erp_ocr::OcrService::new().process_queued(&pool, &blobs, 10).await.unwrap();
+    assert_eq!(runs.len(), 1);
+
+    let (status, queue) = authed_request(&app, Method::GET, "/api/v1/ocr/documents?status=requires_review", &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(queue.as_array().unwrap().len(), 1);
+    assert!(queue[0]["validation_errors"].to_string().contains("does not equal the total"), "{}", queue[0]);
+    assert!(queue[0]["vendor_bill_id"].is_null());
+
+    let (status, reviewed) = authed_request(&app, Method::POST, &format!("/api/v1/ocr/documents/{}/review", doc_id), &token, Some(json!({
+        "corrections": { "total_amount": 12_000 }
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", reviewed);
+    assert_eq!(reviewed["status"], "Validated");
+    assert_eq!(reviewed["reviewed_by"], user_id.as_str());
+    assert!(reviewed["vendor_bill_id"].is_string());
+
+    let (status, body) = authed_request(&app, Method::POST, &format!("/api/v1/ocr/documents/{}/review", doc_id), &token, Some(json!({}))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+
+    let (status, template) = authed_request(&app, Method::POST, "/api/v1/ocr/templates", &token, Some(json!({
+        "name": "Initech", "document_type": "Invoice", "vendor_id": vendor["id"],
+        "field_mappings": [{ "source_field": "Remit to", "target_field": "remit_email", "data_type": "Email" }]
+    }))).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", template);
+    let (status, templates) = authed_request(&app, Method::GET, "/api/v1/ocr/templates?document_type=invoice", &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(templates.as_array().unwrap().len(), 1);
+    let (status, _) = authed_request(&app, Method::GET, &format!("/api/v1/ocr/templates/{}", uuid::Uuid::new_v4()), &token, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_bulk_imports_map_columns_report_row_errors_and_queue_large_files() {
+    init_test_env();
+    let pool = setup_unprivileged_db().await;
+    let state = create_test_app(pool.clone());
+    let blobs = state.blobs.clone();
+    let app = create_router(state);
+    let (admin, admin_id) = register_user(&app, "importadmin").await;
+    sqlx::query("UPDATE users SET role = 'Admin' WHERE id = ?").bind(&admin_id).execute(&pool).await.unwrap();
+    let (clerk, _) = register_user(&app, "importclerk").await;
+    let csv = "Item;Title;Unit\nw-1;Widget;EA\nw-2;;BOX\n";
+
+    let (status, entities) = authed_request(&app, Method::GET, "/api/v1/imports/entities", &clerk, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(entities.as_array().unwrap().iter().any(|e| e["name"] == "opening_balances"));
+    let (status, body) = upload_file(&app, "/api/v1/imports", &clerk, "items.csv", csv.as_bytes(), &[("entity", "products")]).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(body["error"].as_str().unwrap().contains("data:import:write"), "{}", body);
+
+    // Import rights alone do not let a user write products.
+    let (clerk, clerk_id) = register_user(&app, "importer").await;
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query("INSERT INTO permissions (id, code, name, module, resource, action, created_at) VALUES ('perm-import', 'data:import:write', 'Import data', 'data', 'import', 'write', ?)")
+        .bind(&now).execute(&pool).await.unwrap();
+    grant_role(&pool, "importer", &[&clerk_id], &[], &[]).await;
+    sqlx::query("INSERT INTO role_permissions (id, role_id, permission_id, granted_at) SELECT 'importer-write', id, 'perm-import', ? FROM custom_roles WHERE code = 'importer'")
+        .bind(&now).execute(&pool).await.unwrap();
+    let (status, body) = upload_file(&app, "/api/v1/imports", &clerk, "items.csv", csv.as_bytes(), &[("entity", "products")]).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(body["error"].as_str().unwrap().contains("inventory:products:write"), "{}", body);
+
+    let (status, mapping) = authed_request(&app, Method::POST, "/api/v1/import-mappings", &admin, Some(json!({
+        "name": "Supplier list", "entity_type": "products",
+        "columns": [
+            { "source": "Item", "target": "sku", "transforms": [{ "type": "uppercase" }] },
+            { "source": "Title", "target": "name" },
+            { "source": "Unit", "target": "unit_of_measure" }
+        ]
+    }))).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", mapping);
+    let mapping_id = mapping["id"].as_str().unwrap();
+
+    let fields = [("entity", "products"), ("mapping_id", mapping_id), ("dry_run", "true")];
+    let (status, job) = upload_file(&app, "/api/v1/imports", &admin, "items.csv", csv.as_bytes(), &fields).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", job);
+    assert_eq!(job["status"], "Completed");
+    assert_eq!(job["dry_run"], true);
+    assert_eq!((job["success_records"].as_i64(), job["failed_records"].as_i64()), (Some(1), Some(1)));
+    let job_id = job["id"].as_str().unwrap();
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM products").fetch_one(&pool).await.unwrap();
+    assert_eq!(count, 0);
+
+    let (status, errors) = authed_request(&app, Method::GET, &format!("/api/v1/imports/{}/errors", job_id), &admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(errors[0]["row_number"], 3);
+    assert_eq!(errors[0]["field"], "name");
+    let (status, headers, bytes) = download_file(&app, &format!("/api/v1/imports/{}/error-file", job_id), Some(&admin)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-type"], "text/csv");
+    assert_eq!(String::from_utf8(bytes).unwrap(), "Row,Item,Title,Unit,Errors\r\n3,w-2,,BOX,Name: Required\r\n");
+
+    let fields = [("entity", "products"), ("mapping_id", mapping_id)];
+    let (status, job) = upload_file(&app, "/api/v1/imports", &admin, "items.csv", csv.as_bytes(), &fields).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", job);
+    let (sku,): (String,) = sqlx::query_as("SELECT sku FROM products").fetch_one(&pool).await.unwrap();
+    assert_eq!(sku, "W-1");
+
+    // Large files are queued and worked through in chunks by the background worker.
+    let mut large = String::from("sku,name,unit_of_measure,status\n");
+    for i in 0..1200 {
+        large.push_str(&format!("BULK-{:04},Bulk item {},EA,{}\n", i, i, if i == 700 { "Retired" } else { "Active" }));
+    }
+    let (status, job) = upload_file(&app, "/api/v1/imports", &admin, "bulk.csv", large.as_bytes(), &[("entity", "products"), ("upsert", "yes")]).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", job);
+    assert_eq!(job["status"], "Queued");
+    assert_eq!(job["total_records"], 1200);
+    let job_id = job["id"].as_str().unwrap();
+    let runs = erp_mdm::import::ImportService::new().process_queued(&pool, &blobs, 10).await.unwrap();
+    assert_eq!(runs.len(), 1);
+    let (status, job) = authed_request(&app, Method::GET, &format!("/api/v1/imports/{}", job_id), &admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(job["status"], "Completed", "{}", job);
+    assert_eq!((job["processed_records"].as_i64(), job["created_records"].as_i64(), job["failed_records"].as_i64()), (Some(1200), Some(1199), Some(1)));
+    let (status, headers, bytes) = download_file(&app, &format!("/api/v1/imports/{}/error-file", job_id), Some(&admin)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers["content-disposition"].to_str().unwrap().contains("errors.csv"));
+    assert!(String::from_utf8(bytes).unwrap().contains("702,BULK-0700,Bulk item 700,EA,Retired,"));
+
+    let (status, jobs) = authed_request(&app, Method::GET, "/api/v1/imports?entity=products", &admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(jobs.as_array().unwrap().len(), 3);
+    let (status, _) = authed_request(&app, Method::DELETE, &format!("/api/v1/import-mappings/{}", mapping_id), &admin, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
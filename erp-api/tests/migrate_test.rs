@@ -0,0 +1,132 @@
+use erp_api::migrate::{Migration, MigrationState, Migrator};
+use sqlx::SqlitePool;
+
+async fn versions(pool: &SqlitePool) -> Vec<String> {
+    sqlx::query_scalar("SELECT version FROM schema_migrations ORDER BY version").fetch_all(pool).await.unwrap()
+}
+
+async fn baseline_count(pool: &SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations WHERE baseline = 1").fetch_one(pool).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_embedded_migrations_apply_in_order_and_revert() {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    let migrator = Migrator::embedded();
+    let total = migrator.migrations().len();
+    assert!(total > 70);
+
+    // The files before the tracked runner are recorded from the baseline schema, the rest are run.
+    let applied = migrator.up(&pool, None).await.unwrap();
+    assert_eq!(applied.first().map(String::as_str), Some("20260308000000_mrp_netting"));
+    assert_eq!(baseline_count(&pool).await as usize + applied.len(), total);
+    assert!(migrator.up(&pool, None).await.unwrap().is_empty());
+    assert!(migrator.status(&pool).await.unwrap().iter().all(|s| s.state == MigrationState::Applied));
+    migrator.verify(&pool).await.unwrap();
+
+    // Every migration from receivables on ships a down script; reverting them drops its columns.
+    let reversible: Vec<String> = migrator.migrations().iter()
+        .skip_while(|m| m.version != "20260316000000_receivables")
+        .map(|m| m.version.to_string())
+        .collect();
+    let newest_first: Vec<String> = reversible.iter().rev().cloned().collect();
+    assert_eq!(migrator.down(&pool, reversible.len()).await.unwrap(), newest_first);
+    assert!(sqlx::query("SELECT unapplied_amount FROM payments").fetch_all(&pool).await.is_err());
+    assert!(migrator.down(&pool, 1).await.unwrap_err().to_string().contains("no down script"));
+    assert_eq!(migrator.up(&pool, None).await.unwrap(), reversible);
+    sqlx::query("SELECT unapplied_amount FROM payments").fetch_all(&pool).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_edited_and_missing_migrations_are_detected() {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    let original = Migrator::new(vec![
+        Migration::new("001_widgets", "CREATE TABLE widgets (id TEXT PRIMARY KEY, name TEXT NOT NULL)", None),
+        Migration::new("002_gadgets", "CREATE TABLE gadgets (id TEXT PRIMARY KEY, label TEXT DEFAULT 'a;b'); CREATE TRIGGER gadgets_label AFTER INSERT ON gadgets BEGIN UPDATE gadgets SET label = upper(label) WHERE id = NEW.id; END;", None),
+    ]);
+    assert_eq!(original.up(&pool, Some("001_widgets")).await.unwrap(), vec!["001_widgets"]);
+    assert_eq!(original.up(&pool, None).await.unwrap(), vec!["002_gadgets"]);
+    sqlx::query("INSERT INTO gadgets (id) VALUES ('g1')").execute(&pool).await.unwrap();
+    let label: String = sqlx::query_scalar("SELECT label FROM gadgets").fetch_one(&pool).await.unwrap();
+    assert_eq!(label, "A;B");
+
+    let edited = Migrator::new(vec![
+        Migration::new("001_widgets", "CREATE TABLE widgets (id TEXT PRIMARY KEY, name TEXT)", None),
+        original.migrations()[1].clone(),
+    ]);
+    let status = edited.status(&pool).await.unwrap();
+    assert_eq!(status[0].state, MigrationState::Modified);
+    let err = edited.up(&pool, None).await.unwrap_err().to_string();
+    assert!(err.contains("001_widgets was edited"));
+
+    let missing = Migrator::new(vec![original.migrations()[0].clone()]);
+    let err = missing.verify(&pool).await.unwrap_err().to_string();
+    assert!(err.contains("002_gadgets was applied but its file is missing"));
+
+    // A failing migration leaves nothing behind.
+    let broken = Migrator::new(vec![
+        original.migrations()[0].clone(),
+        original.migrations()[1].clone(),
+        Migration::new("003_broken", "CREATE TABLE sprockets (id TEXT PRIMARY KEY); INSERT INTO nowhere VALUES (1)", None),
+    ]);
+    assert!(broken.up(&pool, None).await.is_err());
+    assert_eq!(versions(&pool).await, vec!["001_widgets", "002_gadgets"]);
+    assert!(sqlx::query("SELECT * FROM sprockets").fetch_all(&pool).await.is_err());
+}
+
+#[tokio::test]
+async fn test_untracked_database_is_adopted() {
+    let pool = SqlitePool::connect(":memory:").await.unwrap();
+    // What the old runner left behind: its files applied statement by statement, untracked, until
+    // a statement other than a repeated CREATE failed.
+    let legacy = [
+        include_str!("../../migrations/20240101000000_finance.sql"),
+        include_str!("../../migrations/20240101000001_inventory.sql"),
+        include_str!("../../migrations/20240101000002_sales.sql"),
+        include_str!("../../migrations/20240101000003_purchasing.sql"),
+        include_str!("../../migrations/20240101000005_hr.sql"),
+        include_str!("../../migrations/20240101000006_auth.sql"),
+        include_str!("../../migrations/20240101000013_enterprise_features.sql"),
+        include_str!("../../migrations/20240101200500_enterprise_wms_demand_edi_tenant_revrec_intercompany_lms.sql"),
+        include_str!("../../migrations/20240304000000_stripe_payments.sql"),
+    ];
+    'legacy: for sql in legacy {
+        for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            if let Err(e) = sqlx::query(statement).execute(&pool).await {
+                if !e.to_string().contains("already exists") {
+                    break 'legacy;
+                }
+            }
+        }
+    }
+    sqlx::query("INSERT INTO customers (id, code, name, created_at, updated_at) VALUES ('c1', 'ACME', 'Acme', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let migrator = Migrator::embedded();
+    let applied = migrator.up(&pool, None).await.unwrap();
+    migrator.verify(&pool).await.unwrap();
+    assert_eq!(applied.first().map(String::as_str), Some("20260308000000_mrp_netting"));
+    assert_eq!(baseline_count(&pool).await as usize + applied.len(), migrator.migrations().len());
+    assert_eq!(versions(&pool).await.len(), migrator.migrations().len());
+
+    // Existing rows are kept, the tables the old runner never created are there now, and so are
+    // the columns later migrations add to the tables it did create.
+    let name: String = sqlx::query_scalar("SELECT name FROM customers WHERE id = 'c1'").fetch_one(&pool).await.unwrap();
+    assert_eq!(name, "Acme");
+    for query in [
+        "SELECT * FROM vendor_bills",
+        "SELECT employee_id FROM users",
+        "SELECT unit_cost, total_cost FROM stock_movements",
+        "SELECT payment_term_id, created_by FROM invoices",
+        "SELECT sales_order_line_id FROM invoice_lines",
+        "SELECT unapplied_amount, gateway_id FROM payments",
+        "SELECT parent_backup_id, database_checksum FROM backup_records",
+        "SELECT endpoint_url, webhook_secret FROM payment_gateways",
+        "SELECT customer_id, payment_id FROM refunds",
+    ] {
+        sqlx::query(query).fetch_all(&pool).await.unwrap_or_else(|e| panic!("{}: {}", query, e));
+    }
+    assert!(migrator.up(&pool, None).await.unwrap().is_empty());
+}
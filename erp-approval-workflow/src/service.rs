@@ -1,5 +1,6 @@
 use chrono::Utc;
 use erp_core::{BaseEntity, Error, Paginated, Pagination, Result};
+use erp_core::events::{document_topic, RealtimeEvent};
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 use uuid::Uuid;
@@ -189,7 +190,9 @@ impl ApprovalRequestService {
                     approvals: vec![],
                 };
                 
-                return self.request_repo.create(pool, request).await;
+                let request = self.request_repo.create(pool, request).await?;
+                publish_approval_event("approval.approved", "Request approved", &request, vec![request.requested_by]);
+                return Ok(request);
             }
         }
 
@@ -222,7 +225,9 @@ impl ApprovalRequestService {
             approvals: vec![],
         };
 
-        self.request_repo.create(pool, request).await
+        let request = self.request_repo.create(pool, request).await?;
+        publish_approval_event("approval.requested", "Approval needed", &request, level_users(first_level));
+        Ok(request)
     }
 
     pub async fn approve(
@@ -268,6 +273,7 @@ impl ApprovalRequestService {
             ApprovalType::Sequential => !approvals_at_level.is_empty(),
         };
 
+        let mut notify = None;
         if level_complete {
             let next_level = workflow.levels.iter()
                 .find(|l| l.level_number > current_level);
@@ -276,17 +282,23 @@ impl ApprovalRequestService {
                 Some(next) => {
                     request.current_level = Some(next.level_number);
                     request.due_date = next.due_hours.map(|h| Utc::now() + chrono::Duration::hours(h as i64));
+                    notify = Some(("approval.requested", "Approval needed", level_users(next)));
                 }
                 None => {
                     request.status = ApprovalRequestStatus::Approved;
                     request.approved_at = Some(Utc::now());
                     request.approved_by = Some(approver_id);
                     request.current_level = None;
+                    notify = Some(("approval.approved", "Request approved", vec![request.requested_by]));
                 }
             }
         }
 
-        self.request_repo.update(pool, request).await
+        let request = self.request_repo.update(pool, request).await?;
+        if let Some((event_type, title, users)) = notify {
+            publish_approval_event(event_type, title, &request, users);
+        }
+        Ok(request)
     }
 
     pub async fn reject(
@@ -321,7 +333,9 @@ impl ApprovalRequestService {
         request.rejected_by = Some(approver_id);
         request.rejection_reason = Some(reason);
 
-        self.request_repo.update(pool, request).await
+        let request = self.request_repo.update(pool, request).await?;
+        publish_approval_event("approval.rejected", "Request rejected", &request, vec![request.requested_by]);
+        Ok(request)
     }
 
     pub async fn delegate(
@@ -352,7 +366,9 @@ impl ApprovalRequestService {
         };
         self.request_repo.add_approval(pool, record).await?;
 
-        self.request_repo.update(pool, request).await
+        let request = self.request_repo.update(pool, request).await?;
+        publish_approval_event("approval.delegated", "Approval delegated to you", &request, vec![to_approver_id]);
+        Ok(request)
     }
 
     pub async fn get_request(&self, pool: &SqlitePool, id: Uuid) -> Result<ApprovalRequest> {
@@ -379,7 +395,9 @@ impl ApprovalRequestService {
         }
 
         request.status = ApprovalRequestStatus::Cancelled;
-        self.request_repo.update(pool, request).await
+        let request = self.request_repo.update(pool, request).await?;
+        publish_approval_event("approval.cancelled", "Request cancelled", &request, Vec::new());
+        Ok(request)
     }
 
     pub async fn get_pending_summary(&self, pool: &SqlitePool, user_id: Uuid) -> Result<PendingApprovalSummary> {
@@ -497,3 +515,28 @@ pub struct DelegateRequest {
     pub to_approver_id: Uuid,
     pub reason: Option<String>,
 }
+
+/// Approvers that can be notified directly; role and department levels are only reachable
+/// through the `approvals` topic.
+fn level_users(level: &ApprovalLevel) -> Vec<Uuid> {
+    match level.approver_type {
+        ApproverType::SpecificUser => level.approver_ids.clone(),
+        _ => Vec::new(),
+    }
+}
+
+fn publish_approval_event(event_type: &str, title: &str, request: &ApprovalRequest, users: Vec<Uuid>) {
+    let message = format!("{} {} ({})", request.document_type, request.document_number, request.request_number);
+    let event = RealtimeEvent::new(event_type, title, &message)
+        .with_topic("approvals")
+        .with_topic(document_topic(&request.document_type, request.document_id))
+        .for_users(users)
+        .with_data(serde_json::json!({
+            "request_id": request.id,
+            "status": format!("{:?}", request.status),
+            "current_level": request.current_level,
+            "amount": request.amount,
+            "currency": request.currency,
+        }));
+    erp_core::events::publish(event);
+}
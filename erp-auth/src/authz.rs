@@ -0,0 +1,367 @@
+//! Authorization against the RBAC tables.
+//!
+//! A user holds the system role whose code matches `users.role`, every role assigned to them in
+//! `user_role_assignments` that has not expired, and the `parent_role_id` chain of each. Their
+//! permissions are the union of those roles' grants. Grants are `module:resource:action` codes in
+//! which any segment may be `*`.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+
+use erp_core::{Error, Result};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Whether the grant `granted` covers the permission `required`. Both are
+/// `module:resource:action` codes; a `*` segment in the grant matches anything.
+pub fn permission_matches(granted: &str, required: &str) -> bool {
+    let granted: Vec<&str> = granted.split(':').collect();
+    let required: Vec<&str> = required.split(':').collect();
+    granted.len() == required.len()
+        && granted.iter().zip(&required).all(|(g, r)| *g == "*" || g == r)
+}
+
+pub fn validate_permission_code(code: &str) -> Result<()> {
+    let segments: Vec<&str> = code.split(':').collect();
+    if segments.len() != 3 || segments.iter().any(|s| s.is_empty()) {
+        return Err(Error::validation(format!(
+            "Permission '{}' must have the form module:resource:action",
+            code
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoleSource {
+    /// The system role named by `users.role`.
+    BuiltIn,
+    Assigned,
+    /// Reached through another held role's `parent_role_id`.
+    Inherited,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HeldRole {
+    pub role_id: String,
+    pub code: String,
+    pub name: String,
+    pub source: RoleSource,
+    /// For inherited roles, the code of the role that inherits from this one.
+    pub inherited_by: Option<String>,
+    pub expires_at: Option<String>,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpiredAssignment {
+    pub role_id: String,
+    pub code: String,
+    pub name: String,
+    pub expires_at: String,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectivePermissions {
+    pub user_id: String,
+    pub roles: Vec<HeldRole>,
+    pub expired: Vec<ExpiredAssignment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Grant {
+    pub role_code: String,
+    pub role_name: String,
+    pub source: RoleSource,
+    pub inherited_by: Option<String>,
+    pub permission: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Decision {
+    pub user_id: String,
+    pub permission: String,
+    pub allowed: bool,
+    pub reason: String,
+    pub granted_by: Vec<Grant>,
+    pub roles: Vec<String>,
+    pub expired: Vec<ExpiredAssignment>,
+}
+
+impl EffectivePermissions {
+    pub fn permissions(&self) -> Vec<String> {
+        let mut codes: Vec<String> = self.roles.iter().flat_map(|r| r.permissions.iter().cloned()).collect();
+        codes.sort();
+        codes.dedup();
+        codes
+    }
+
+    pub fn allows(&self, required: &str) -> bool {
+        self.roles.iter().any(|r| r.permissions.iter().any(|p| permission_matches(p, required)))
+    }
+
+    pub fn explain(&self, required: &str) -> Decision {
+        let granted_by: Vec<Grant> = self.roles.iter()
+            .flat_map(|role| {
+                role.permissions.iter()
+                    .filter(|p| permission_matches(p, required))
+                    .map(move |p| Grant {
+                        role_code: role.code.clone(),
+                        role_name: role.name.clone(),
+                        source: role.source,
+                        inherited_by: role.inherited_by.clone(),
+                        permission: p.clone(),
+                    })
+            })
+            .collect();
+        let expired: Vec<ExpiredAssignment> = self.expired.iter()
+            .filter(|e| e.permissions.iter().any(|p| permission_matches(p, required)))
+            .cloned()
+            .collect();
+
+        let reason = if let Some(grant) = granted_by.first() {
+            let source = match (&grant.source, &grant.inherited_by) {
+                (RoleSource::BuiltIn, _) => "built-in role".to_string(),
+                (RoleSource::Inherited, Some(child)) => format!("role inherited through {}", child),
+                _ => "assigned role".to_string(),
+            };
+            format!("Granted by {} {} ({})", source, grant.role_code, grant.permission)
+        } else if let Some(lapsed) = expired.first() {
+            format!(
+                "No active role grants {}; the assignment of role {} that would grant it expired at {}",
+                required, lapsed.code, lapsed.expires_at
+            )
+        } else if self.roles.is_empty() {
+            format!("The user holds no active roles, so nothing grants {}", required)
+        } else {
+            format!(
+                "None of the user's roles ({}) grants {}",
+                self.roles.iter().map(|r| r.code.as_str()).collect::<Vec<_>>().join(", "),
+                required
+            )
+        };
+
+        Decision {
+            user_id: self.user_id.clone(),
+            permission: required.to_string(),
+            allowed: !granted_by.is_empty(),
+            reason,
+            granted_by,
+            roles: self.roles.iter().map(|r| r.code.clone()).collect(),
+            expired,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RoleRow {
+    id: String,
+    code: String,
+    name: String,
+    parent_role_id: Option<String>,
+    is_system: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct AssignmentRow {
+    role_id: String,
+    expires_at: Option<String>,
+    /// Seconds until `expires_at`; `None` for assignments that never expire.
+    expires_in: Option<i64>,
+}
+
+/// Loads a user's roles and grants straight from the database. Also returns how long the result
+/// stays valid before the next assignment expires, if any does.
+pub async fn resolve_permissions(pool: &SqlitePool, user_id: &str) -> Result<(EffectivePermissions, Option<Duration>)> {
+    let user_role: String = sqlx::query_scalar("SELECT role FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| Error::not_found("User", user_id))?;
+
+    let roles: HashMap<String, RoleRow> = sqlx::query_as::<_, RoleRow>(
+        "SELECT id, code, name, parent_role_id, is_system FROM custom_roles WHERE is_active = 1",
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| (r.id.clone(), r))
+    .collect();
+
+    let assignments: Vec<AssignmentRow> = sqlx::query_as(
+        r#"SELECT role_id, expires_at,
+                  CAST((julianday(expires_at) - julianday('now')) * 86400 AS INTEGER) AS expires_in
+           FROM user_role_assignments WHERE user_id = ? ORDER BY assigned_at"#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut grants: HashMap<String, Vec<String>> = HashMap::new();
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT rp.role_id, p.code FROM role_permissions rp JOIN permissions p ON p.id = rp.permission_id ORDER BY p.code",
+    )
+    .fetch_all(pool)
+    .await?;
+    for (role_id, code) in rows {
+        grants.entry(role_id).or_default().push(code);
+    }
+
+    let mut queue: VecDeque<(String, RoleSource, Option<String>, Option<String>)> = VecDeque::new();
+    if let Some(builtin) = roles.values().find(|r| r.is_system && r.code == user_role) {
+        queue.push_back((builtin.id.clone(), RoleSource::BuiltIn, None, None));
+    }
+
+    let mut expired = Vec::new();
+    let mut valid_for: Option<Duration> = None;
+    for assignment in assignments {
+        let Some(role) = roles.get(&assignment.role_id) else { continue };
+        match assignment.expires_in {
+            Some(seconds) if seconds <= 0 => expired.push(ExpiredAssignment {
+                role_id: role.id.clone(),
+                code: role.code.clone(),
+                name: role.name.clone(),
+                expires_at: assignment.expires_at.unwrap_or_default(),
+                permissions: lineage(&roles, &role.id)
+                    .flat_map(|id| grants.get(id).cloned().unwrap_or_default())
+                    .collect(),
+            }),
+            _ => {
+                if let Some(seconds) = assignment.expires_in {
+                    let remaining = Duration::from_secs(seconds as u64);
+                    valid_for = Some(valid_for.map_or(remaining, |v| v.min(remaining)));
+                }
+                queue.push_back((role.id.clone(), RoleSource::Assigned, None, assignment.expires_at));
+            }
+        }
+    }
+
+    let mut held = Vec::new();
+    let mut seen = HashSet::new();
+    while let Some((role_id, source, inherited_by, expires_at)) = queue.pop_front() {
+        if !seen.insert(role_id.clone()) {
+            continue;
+        }
+        let role = &roles[&role_id];
+        if let Some(parent) = role.parent_role_id.as_ref().filter(|p| roles.contains_key(*p)) {
+            queue.push_back((parent.clone(), RoleSource::Inherited, Some(role.code.clone()), None));
+        }
+        held.push(HeldRole {
+            role_id: role.id.clone(),
+            code: role.code.clone(),
+            name: role.name.clone(),
+            source,
+            inherited_by,
+            expires_at,
+            permissions: grants.get(&role.id).cloned().unwrap_or_default(),
+        });
+    }
+
+    Ok((EffectivePermissions { user_id: user_id.to_string(), roles: held, expired }, valid_for))
+}
+
+/// A role followed by its ancestors, stopping at a cycle or an inactive parent.
+fn lineage<'a>(roles: &'a HashMap<String, RoleRow>, role_id: &'a str) -> impl Iterator<Item = &'a String> + 'a {
+    let mut seen = HashSet::new();
+    let mut next = roles.get(role_id);
+    std::iter::from_fn(move || {
+        let role = next.filter(|r| seen.insert(r.id.as_str()))?;
+        next = role.parent_role_id.as_ref().and_then(|p| roles.get(p));
+        Some(&role.id)
+    })
+}
+
+type CacheEntry = (Instant, String, Arc<EffectivePermissions>);
+
+/// Resolves and caches effective permissions per user. Entries live for the cache TTL or until the
+/// user's next assignment expires, whichever is sooner, and are dropped when `users.role` no longer
+/// matches the role they were resolved for. Anything that changes roles, grants or assignments must
+/// call [`Authorizer::invalidate_user`] or [`Authorizer::invalidate_all`].
+pub struct Authorizer {
+    pool: SqlitePool,
+    ttl: Duration,
+    cache: RwLock<HashMap<String, CacheEntry>>,
+}
+
+impl Authorizer {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self::with_ttl(pool, DEFAULT_CACHE_TTL)
+    }
+
+    pub fn with_ttl(pool: SqlitePool, ttl: Duration) -> Self {
+        Self { pool, ttl, cache: RwLock::new(HashMap::new()) }
+    }
+
+    pub async fn permissions(&self, user_id: &str) -> Result<Arc<EffectivePermissions>> {
+        let role: String = sqlx::query_scalar("SELECT role FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| Error::not_found("User", user_id))?;
+        if let Some((valid_until, cached_role, permissions)) = self.entries().get(user_id) {
+            if Instant::now() < *valid_until && *cached_role == role {
+                return Ok(permissions.clone());
+            }
+        }
+
+        let (permissions, valid_for) = resolve_permissions(&self.pool, user_id).await?;
+        let permissions = Arc::new(permissions);
+        let valid_until = Instant::now() + valid_for.map_or(self.ttl, |v| v.min(self.ttl));
+        self.entries_mut().insert(user_id.to_string(), (valid_until, role, permissions.clone()));
+        Ok(permissions)
+    }
+
+    pub async fn check(&self, user_id: &str, permission: &str) -> Result<bool> {
+        Ok(self.permissions(user_id).await?.allows(permission))
+    }
+
+    pub async fn explain(&self, user_id: &str, permission: &str) -> Result<Decision> {
+        validate_permission_code(permission)?;
+        Ok(self.permissions(user_id).await?.explain(permission))
+    }
+
+    pub fn invalidate_user(&self, user_id: &str) {
+        self.entries_mut().remove(user_id);
+    }
+
+    pub fn invalidate_all(&self) {
+        self.entries_mut().clear();
+    }
+
+    // The map is left consistent by every writer, so a poisoned lock is still safe to use.
+    fn entries(&self) -> RwLockReadGuard<'_, HashMap<String, CacheEntry>> {
+        self.cache.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn entries_mut(&self) -> RwLockWriteGuard<'_, HashMap<String, CacheEntry>> {
+        self.cache.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_matches_wildcards_per_segment() {
+        assert!(permission_matches("*:*:*", "finance:accounts:write"));
+        assert!(permission_matches("finance:*:*", "finance:accounts:write"));
+        assert!(permission_matches("*:*:read", "hr:employees:read"));
+        assert!(permission_matches("sales:orders:approve", "sales:orders:approve"));
+        assert!(!permission_matches("*:*:read", "hr:employees:write"));
+        assert!(!permission_matches("finance:*:*", "financial:accounts:read"));
+        assert!(!permission_matches("finance:*", "finance:accounts:read"));
+    }
+
+    #[test]
+    fn test_validate_permission_code() {
+        assert!(validate_permission_code("finance:accounts:read").is_ok());
+        assert!(validate_permission_code("finance:accounts").is_err());
+        assert!(validate_permission_code("finance::read").is_err());
+    }
+}
@@ -42,19 +42,27 @@ pub fn decode_token<T: DeserializeOwned>(token: &str) -> Result<T> {
     Ok(data.claims)
 }
 
+/// Claim marking a token that only proves the password step of a two-factor login.
+const MFA_PURPOSE: &str = "mfa";
+
+/// Issues an access token for `session_id`. Every token gets its own `jti` so it can be revoked
+/// on its own.
 pub fn generate_token(
     user_id: &str,
     username: &str,
     role: &str,
-    expiry_hours: i64,
+    session_id: &str,
+    expires_in: Duration,
 ) -> Result<(String, chrono::DateTime<Utc>)> {
     let now = Utc::now();
-    let exp = now + Duration::hours(expiry_hours);
+    let exp = now + expires_in;
 
     let claims = serde_json::json!({
         "sub": user_id,
         "username": username,
         "role": role,
+        "sid": session_id,
+        "jti": uuid::Uuid::new_v4().to_string(),
         "exp": exp.timestamp(),
         "iat": now.timestamp(),
     });
@@ -66,6 +74,9 @@ pub fn generate_token(
 pub fn validate_token(token: &str) -> Result<TokenData> {
     let claims: serde_json::Value = decode_token(token)?;
 
+    if !claims["purpose"].is_null() {
+        return Err(anyhow::anyhow!("Not an access token"));
+    }
     let exp = claims["exp"]
         .as_i64()
         .ok_or_else(|| anyhow::anyhow!("Missing or invalid exp"))?;
@@ -74,26 +85,58 @@ pub fn validate_token(token: &str) -> Result<TokenData> {
     }
 
     Ok(TokenData {
-        user_id: claims["sub"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing sub"))?
-            .to_string(),
-        username: claims["username"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing username"))?
-            .to_string(),
-        role: claims["role"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing role"))?
-            .to_string(),
+        user_id: claim(&claims, "sub")?,
+        username: claim(&claims, "username")?,
+        role: claim(&claims, "role")?,
+        session_id: claim(&claims, "sid")?,
+        jti: claim(&claims, "jti")?,
+        expires_at: chrono::DateTime::from_timestamp(exp, 0)
+            .ok_or_else(|| anyhow::anyhow!("Missing or invalid exp"))?,
     })
 }
 
+/// Issues the short-lived token a client exchanges, together with a TOTP or backup code, for a
+/// session once the password has checked out.
+pub fn generate_mfa_token(user_id: &str, expires_in: Duration) -> Result<(String, chrono::DateTime<Utc>)> {
+    let now = Utc::now();
+    let exp = now + expires_in;
+
+    let claims = serde_json::json!({
+        "sub": user_id,
+        "purpose": MFA_PURPOSE,
+        "jti": uuid::Uuid::new_v4().to_string(),
+        "exp": exp.timestamp(),
+        "iat": now.timestamp(),
+    });
+
+    let token = encode_token(&claims)?;
+    Ok((token, exp))
+}
+
+/// Returns the user id an MFA token was issued to.
+pub fn validate_mfa_token(token: &str) -> Result<String> {
+    let claims: serde_json::Value = decode_token(token)?;
+    if claims["purpose"].as_str() != Some(MFA_PURPOSE) {
+        return Err(anyhow::anyhow!("Not an MFA token"));
+    }
+    claim(&claims, "sub")
+}
+
+fn claim(claims: &serde_json::Value, name: &str) -> Result<String> {
+    claims[name]
+        .as_str()
+        .map(|value| value.to_string())
+        .ok_or_else(|| anyhow::anyhow!("Missing {}", name))
+}
+
 #[derive(Debug, Clone)]
 pub struct TokenData {
     pub user_id: String,
     pub username: String,
     pub role: String,
+    pub session_id: String,
+    pub jti: String,
+    pub expires_at: chrono::DateTime<Utc>,
 }
 
 #[cfg(test)]
@@ -108,7 +151,7 @@ mod tests {
     fn test_generate_and_validate_token() {
         init_test();
 
-        let (token, expires_at) = generate_token("user-123", "testuser", "Admin", 24).unwrap();
+        let (token, expires_at) = generate_token("user-123", "testuser", "Admin", "session-1", Duration::minutes(15)).unwrap();
 
         assert!(!token.is_empty());
         assert!(expires_at > Utc::now());
@@ -117,6 +160,20 @@ mod tests {
         assert_eq!(data.user_id, "user-123");
         assert_eq!(data.username, "testuser");
         assert_eq!(data.role, "Admin");
+        assert_eq!(data.session_id, "session-1");
+        assert!(!data.jti.is_empty());
+    }
+
+    #[test]
+    fn test_mfa_and_access_tokens_are_not_interchangeable() {
+        init_test();
+
+        let (mfa_token, _) = generate_mfa_token("user-123", Duration::minutes(5)).unwrap();
+        assert_eq!(validate_mfa_token(&mfa_token).unwrap(), "user-123");
+        assert!(validate_token(&mfa_token).is_err());
+
+        let (access_token, _) = generate_token("user-123", "testuser", "Admin", "session-1", Duration::minutes(15)).unwrap();
+        assert!(validate_mfa_token(&access_token).is_err());
     }
 
     #[test]
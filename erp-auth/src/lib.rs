@@ -3,8 +3,10 @@ pub mod repository;
 pub mod service;
 pub mod jwt;
 pub mod rbac;
+pub mod authz;
 
 pub use models::*;
 pub use service::*;
 pub use jwt::*;
 pub use rbac::*;
+pub use authz::*;
@@ -16,6 +16,7 @@ pub struct User {
     pub role: UserRole,
     pub status: UserStatus,
     pub last_login: Option<DateTime<Utc>>,
+    pub locked_until: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
@@ -141,9 +142,70 @@ pub struct RegisterRequest {
 pub struct AuthResponse {
     pub token: String,
     pub expires_at: DateTime<Utc>,
+    pub refresh_token: String,
+    pub refresh_expires_at: DateTime<Utc>,
     pub user: UserInfo,
 }
 
+/// A login either completes or, for users with two-factor authentication enabled, asks for a code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LoginResponse {
+    Authenticated(AuthResponse),
+    TwoFactorRequired(TwoFactorChallenge),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoFactorChallenge {
+    pub mfa_required: bool,
+    pub mfa_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct TwoFactorLoginRequest {
+    #[validate(length(min = 1, message = "MFA token is required"))]
+    pub mfa_token: String,
+    #[validate(length(min = 1, message = "Code is required"))]
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct RefreshRequest {
+    #[validate(length(min = 1, message = "Refresh token is required"))]
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct PasswordResetRequest {
+    #[validate(email(message = "Invalid email address"))]
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct PasswordResetConfirm {
+    #[validate(length(min = 1, message = "Reset token is required"))]
+    pub token: String,
+    pub new_password: String,
+}
+
+/// A reset token issued to a user. Only its hash is stored, so this is the one chance to deliver it.
+#[derive(Debug, Clone)]
+pub struct PasswordReset {
+    pub user_id: Uuid,
+    pub email: String,
+    pub full_name: String,
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Where a login came from, recorded on the session it opens.
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserInfo {
     pub id: Uuid,
@@ -152,3 +214,15 @@ pub struct UserInfo {
     pub full_name: String,
     pub role: String,
 }
+
+impl From<&User> for UserInfo {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.base.id,
+            username: user.username.clone(),
+            email: user.email.clone(),
+            full_name: user.full_name.clone(),
+            role: user.role.as_str().to_string(),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use sqlx::SqlitePool;
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use erp_core::Result;
 use crate::models::*;
 
@@ -13,6 +13,17 @@ pub trait UserRepository: Send + Sync {
     async fn create(&self, user: User) -> Result<User>;
     async fn update_last_login(&self, id: Uuid) -> Result<()>;
     async fn list(&self) -> Result<Vec<User>>;
+    async fn update_password(&self, id: Uuid, password_hash: &str) -> Result<()>;
+    /// Counts a failed login, locking the account until `lock_until` once `max_attempts` is reached.
+    /// Returns whether this failure locked it.
+    async fn record_failed_login(&self, id: Uuid, max_attempts: i64, lock_until: DateTime<Utc>) -> Result<bool>;
+    async fn clear_failed_logins(&self, id: Uuid) -> Result<()>;
+    async fn revoke_token(&self, jti: &str, user_id: Uuid, expires_at: DateTime<Utc>) -> Result<()>;
+    async fn is_token_revoked(&self, jti: &str) -> Result<bool>;
+    /// Stores a password-reset token hash, replacing any earlier unused one for the user.
+    async fn create_password_reset(&self, user_id: Uuid, token_hash: &str, expires_at: DateTime<Utc>) -> Result<()>;
+    /// Marks an unused reset token used, returning its user and expiry.
+    async fn consume_password_reset(&self, token_hash: &str) -> Result<Option<(Uuid, DateTime<Utc>)>>;
 }
 
 pub struct SqliteUserRepository {
@@ -29,7 +40,7 @@ impl SqliteUserRepository {
 impl UserRepository for SqliteUserRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<User> {
         let row = sqlx::query(
-            "SELECT id, username, email, password_hash, full_name, role, status, last_login, created_at, updated_at, created_by, updated_by FROM users WHERE id = ?"
+            "SELECT id, username, email, password_hash, full_name, role, status, last_login, locked_until, created_at, updated_at, created_by, updated_by FROM users WHERE id = ?"
         ).bind(id.to_string()).fetch_optional(&self.pool).await?
         .ok_or_else(|| erp_core::Error::not_found("User", &id.to_string()))?;
         
@@ -38,7 +49,7 @@ impl UserRepository for SqliteUserRepository {
 
     async fn find_by_username(&self, username: &str) -> Result<User> {
         let row = sqlx::query(
-            "SELECT id, username, email, password_hash, full_name, role, status, last_login, created_at, updated_at, created_by, updated_by FROM users WHERE username = ?"
+            "SELECT id, username, email, password_hash, full_name, role, status, last_login, locked_until, created_at, updated_at, created_by, updated_by FROM users WHERE username = ?"
         ).bind(username).fetch_optional(&self.pool).await?
         .ok_or_else(|| erp_core::Error::not_found("User", username))?;
         
@@ -47,7 +58,7 @@ impl UserRepository for SqliteUserRepository {
 
     async fn find_by_email(&self, email: &str) -> Result<User> {
         let row = sqlx::query(
-            "SELECT id, username, email, password_hash, full_name, role, status, last_login, created_at, updated_at, created_by, updated_by FROM users WHERE email = ?"
+            "SELECT id, username, email, password_hash, full_name, role, status, last_login, locked_until, created_at, updated_at, created_by, updated_by FROM users WHERE email = ?"
         ).bind(email).fetch_optional(&self.pool).await?
         .ok_or_else(|| erp_core::Error::not_found("User", email))?;
         
@@ -85,11 +96,79 @@ impl UserRepository for SqliteUserRepository {
 
     async fn list(&self) -> Result<Vec<User>> {
         let rows = sqlx::query(
-            "SELECT id, username, email, password_hash, full_name, role, status, last_login, created_at, updated_at, created_by, updated_by FROM users ORDER BY username"
+            "SELECT id, username, email, password_hash, full_name, role, status, last_login, locked_until, created_at, updated_at, created_by, updated_by FROM users ORDER BY username"
         ).fetch_all(&self.pool).await?;
         
         rows.into_iter().map(|r| self.map_user_row(r)).collect()
     }
+
+    async fn update_password(&self, id: Uuid, password_hash: &str) -> Result<()> {
+        let rows = sqlx::query("UPDATE users SET password_hash = ?, updated_at = ? WHERE id = ?")
+            .bind(password_hash).bind(Utc::now()).bind(id.to_string()).execute(&self.pool).await?;
+        if rows.rows_affected() == 0 {
+            return Err(erp_core::Error::not_found("User", &id.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn record_failed_login(&self, id: Uuid, max_attempts: i64, lock_until: DateTime<Utc>) -> Result<bool> {
+        // The counter restarts once it locks the account, so the next lock takes another full run of failures.
+        let locked: Option<(i64,)> = sqlx::query_as(
+            "UPDATE users SET
+                locked_until = CASE WHEN failed_login_attempts + 1 >= ?1 THEN ?2 ELSE locked_until END,
+                failed_login_attempts = CASE WHEN failed_login_attempts + 1 >= ?1 THEN 0 ELSE failed_login_attempts + 1 END
+             WHERE id = ?3
+             RETURNING failed_login_attempts = 0"
+        ).bind(max_attempts).bind(lock_until).bind(id.to_string()).fetch_optional(&self.pool).await?;
+        Ok(locked.is_some_and(|(locked,)| locked != 0))
+    }
+
+    async fn clear_failed_logins(&self, id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE users SET failed_login_attempts = 0, locked_until = NULL WHERE id = ?")
+            .bind(id.to_string()).execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn revoke_token(&self, jti: &str, user_id: Uuid, expires_at: DateTime<Utc>) -> Result<()> {
+        let now = Utc::now();
+        sqlx::query("DELETE FROM revoked_tokens WHERE expires_at < ?")
+            .bind(now.to_rfc3339()).execute(&self.pool).await?;
+        sqlx::query("INSERT OR IGNORE INTO revoked_tokens (jti, user_id, expires_at, revoked_at) VALUES (?, ?, ?, ?)")
+            .bind(jti).bind(user_id.to_string()).bind(expires_at.to_rfc3339()).bind(now.to_rfc3339())
+            .execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn is_token_revoked(&self, jti: &str) -> Result<bool> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT jti FROM revoked_tokens WHERE jti = ?")
+            .bind(jti).fetch_optional(&self.pool).await?;
+        Ok(row.is_some())
+    }
+
+    async fn create_password_reset(&self, user_id: Uuid, token_hash: &str, expires_at: DateTime<Utc>) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM password_reset_tokens WHERE user_id = ? AND used_at IS NULL")
+            .bind(user_id.to_string()).execute(&mut *tx).await?;
+        sqlx::query("INSERT INTO password_reset_tokens (id, user_id, token_hash, expires_at, created_at) VALUES (?, ?, ?, ?, ?)")
+            .bind(Uuid::new_v4().to_string()).bind(user_id.to_string()).bind(token_hash)
+            .bind(expires_at.to_rfc3339()).bind(Utc::now().to_rfc3339())
+            .execute(&mut *tx).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn consume_password_reset(&self, token_hash: &str) -> Result<Option<(Uuid, DateTime<Utc>)>> {
+        let row: Option<(String, String)> = sqlx::query_as(
+            "UPDATE password_reset_tokens SET used_at = ? WHERE token_hash = ? AND used_at IS NULL RETURNING user_id, expires_at"
+        ).bind(Utc::now().to_rfc3339()).bind(token_hash).fetch_optional(&self.pool).await?;
+
+        let Some((user_id, expires_at)) = row else {
+            return Ok(None);
+        };
+        let user_id = Uuid::parse_str(&user_id).map_err(|_| erp_core::Error::validation("Invalid UUID in database"))?;
+        let expires_at = erp_core::parse_datetime(&expires_at, "expires_at")?;
+        Ok(Some((user_id, expires_at)))
+    }
 }
 
 impl SqliteUserRepository {
@@ -115,6 +194,7 @@ impl SqliteUserRepository {
             role: row.try_get("role")?,
             status: row.try_get("status")?,
             last_login: row.try_get("last_login")?,
+            locked_until: row.try_get("locked_until")?,
         })
     }
 }
@@ -5,6 +5,9 @@ use erp_core::{BaseEntity, Error, Result};
 use crate::models::*;
 use crate::repository::*;
 use crate::jwt;
+use chrono::{DateTime, Duration, Utc};
+use erp_security::SecurityService;
+use sha2::{Digest, Sha256};
 
 const MIN_PASSWORD_LENGTH: usize = 8;
 
@@ -26,18 +29,39 @@ fn validate_password_strength(password: &str) -> Result<()> {
     Ok(())
 }
 
+/// Consecutive failed logins (passwords or two-factor codes) that lock an account.
+const MAX_FAILED_LOGINS: i64 = 5;
+const LOCKOUT_MINUTES: i64 = 15;
+const MFA_TOKEN_MINUTES: i64 = 5;
+const PASSWORD_RESET_MINUTES: i64 = 60;
+
 pub struct AuthService {
+    pool: SqlitePool,
     repo: SqliteUserRepository,
+    security: SecurityService,
+    access_token_ttl: Duration,
+    refresh_token_ttl: Duration,
 }
 
 impl AuthService {
     pub fn new(pool: SqlitePool) -> Self {
         Self { 
-            repo: SqliteUserRepository::new(pool),
+            repo: SqliteUserRepository::new(pool.clone()),
+            pool,
+            security: SecurityService::new(),
+            access_token_ttl: Duration::minutes(15),
+            refresh_token_ttl: Duration::days(30),
         }
     }
 
-    pub async fn register(&self, req: RegisterRequest) -> Result<AuthResponse> {
+    /// Sets how long access tokens and the sessions their refresh tokens belong to stay valid.
+    pub fn with_token_lifetimes(mut self, access: Duration, refresh: Duration) -> Self {
+        self.access_token_ttl = access;
+        self.refresh_token_ttl = refresh;
+        self
+    }
+
+    pub async fn register(&self, req: RegisterRequest, client: ClientInfo) -> Result<AuthResponse> {
         if req.username.is_empty() || req.email.is_empty() || req.password.is_empty() {
             return Err(Error::validation("Username, email, and password are required"));
         }
@@ -55,6 +79,7 @@ impl AuthService {
             role: UserRole::User,
             status: UserStatus::Active,
             last_login: None,
+            locked_until: None,
         };
         
         let user = self.repo.create(user).await.map_err(|e| {
@@ -64,22 +89,13 @@ impl AuthService {
                 e
             }
         })?;
-        let (token, expires_at) = jwt::generate_token(&user.base.id.to_string(), &user.username, user.role.as_str(), 24)?;
-        
-        Ok(AuthResponse {
-            token,
-            expires_at,
-            user: UserInfo {
-                id: user.base.id,
-                username: user.username,
-                email: user.email,
-                full_name: user.full_name,
-                role: user.role.as_str().to_string(),
-            },
-        })
+
+        self.start_session(&user, &client).await
     }
 
-    pub async fn login(&self, req: LoginRequest) -> Result<AuthResponse> {
+    /// Checks the password and either opens a session or, when the user has two-factor
+    /// authentication enabled, returns a challenge to complete with [`Self::login_two_factor`].
+    pub async fn login(&self, req: LoginRequest, client: ClientInfo) -> Result<LoginResponse> {
         let user = self.repo.find_by_username(&req.username).await.map_err(|e| {
             match e {
                 Error::NotFound(_) => Error::Unauthorized,
@@ -90,28 +106,134 @@ impl AuthService {
         if user.status != UserStatus::Active {
             return Err(Error::Unauthorized);
         }
+        ensure_not_locked(&user)?;
         
         if !self.verify_password(&req.password, &user.password_hash)? {
-            return Err(Error::Unauthorized);
+            return self.reject_login(&user).await;
+        }
+
+        let two_factor = self.security.get_two_factor_status(&self.pool, user.base.id).await?;
+        if two_factor.is_some_and(|setup| setup.enabled) {
+            let (mfa_token, expires_at) = jwt::generate_mfa_token(&user.base.id.to_string(), Duration::minutes(MFA_TOKEN_MINUTES))?;
+            return Ok(LoginResponse::TwoFactorRequired(TwoFactorChallenge {
+                mfa_required: true,
+                mfa_token,
+                expires_at,
+            }));
         }
         
-        self.repo.update_last_login(user.base.id).await?;
-        
-        let (token, expires_at) = jwt::generate_token(&user.base.id.to_string(), &user.username, user.role.as_str(), 24)?;
-        
+        self.complete_login(&user, &client).await.map(LoginResponse::Authenticated)
+    }
+
+    /// Finishes a login that [`Self::login`] answered with a two-factor challenge, accepting a TOTP
+    /// code for a later time step than the last one accepted, or an unused backup code.
+    pub async fn login_two_factor(&self, req: TwoFactorLoginRequest, client: ClientInfo) -> Result<AuthResponse> {
+        let user_id = jwt::validate_mfa_token(&req.mfa_token)
+            .ok()
+            .and_then(|id| Uuid::parse_str(&id).ok())
+            .ok_or(Error::Unauthorized)?;
+        let user = self.find_active_user(user_id).await?;
+        ensure_not_locked(&user)?;
+
+        let accepted = match self.security.validate_two_factor_code(&self.pool, user.base.id, &req.code).await {
+            Ok(accepted) => accepted,
+            Err(Error::NotFound(_)) => return Err(Error::Unauthorized),
+            Err(e) => return Err(e),
+        };
+        if !accepted {
+            return self.reject_login(&user).await;
+        }
+
+        self.complete_login(&user, &client).await
+    }
+
+    /// Exchanges a refresh token for a new access token and a new refresh token. Presenting a refresh
+    /// token that has already been rotated out ends its session, since one of its holders stole it.
+    pub async fn refresh(&self, req: RefreshRequest) -> Result<AuthResponse> {
+        let Some(session) = self.security.find_session_by_token(&self.pool, &req.refresh_token).await? else {
+            if let Some(session) = self.security.find_session_by_previous_token(&self.pool, &req.refresh_token).await? {
+                self.security.revoke_session(&self.pool, session.user_id, session.id).await?;
+            }
+            return Err(Error::Unauthorized);
+        };
+
+        if session.expires_at <= Utc::now() {
+            self.security.revoke_session(&self.pool, session.user_id, session.id).await?;
+            return Err(Error::Unauthorized);
+        }
+        let user = self.find_active_user(session.user_id).await?;
+
+        let refresh_token = generate_secret_token();
+        if !self.security.rotate_session_token(&self.pool, session.id, &req.refresh_token, &refresh_token).await? {
+            return Err(Error::Unauthorized);
+        }
+        let (token, expires_at) = jwt::generate_token(
+            &user.base.id.to_string(), &user.username, user.role.as_str(), &session.id.to_string(), self.access_token_ttl,
+        )?;
+
         Ok(AuthResponse {
             token,
             expires_at,
-            user: UserInfo {
-                id: user.base.id,
-                username: user.username,
-                email: user.email,
-                full_name: user.full_name,
-                role: user.role.as_str().to_string(),
-            },
+            refresh_token,
+            refresh_expires_at: session.expires_at,
+            user: UserInfo::from(&user),
         })
     }
 
+    /// Revokes the presented access token and ends the session it belongs to.
+    pub async fn logout(&self, token: &jwt::TokenData) -> Result<()> {
+        let user_id = Uuid::parse_str(&token.user_id).map_err(|_| Error::Unauthorized)?;
+        self.repo.revoke_token(&token.jti, user_id, token.expires_at).await?;
+
+        if let Ok(session_id) = Uuid::parse_str(&token.session_id) {
+            match self.security.revoke_session(&self.pool, user_id, session_id).await {
+                Ok(()) | Err(Error::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Issues a password-reset token for the account with this email, if there is an active one.
+    /// Callers should answer the same way either way so the endpoint does not reveal who is registered.
+    pub async fn request_password_reset(&self, req: PasswordResetRequest) -> Result<Option<PasswordReset>> {
+        let user = match self.repo.find_by_email(&req.email).await {
+            Ok(user) if user.status == UserStatus::Active => user,
+            Ok(_) | Err(Error::NotFound(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let token = generate_secret_token();
+        let expires_at = Utc::now() + Duration::minutes(PASSWORD_RESET_MINUTES);
+        self.repo.create_password_reset(user.base.id, &hash_secret_token(&token), expires_at).await?;
+
+        Ok(Some(PasswordReset {
+            user_id: user.base.id,
+            email: user.email,
+            full_name: user.full_name,
+            token,
+            expires_at,
+        }))
+    }
+
+    /// Sets a new password with a reset token, which also lifts any lockout and signs the user out
+    /// everywhere.
+    pub async fn confirm_password_reset(&self, req: PasswordResetConfirm) -> Result<()> {
+        validate_password_strength(&req.new_password)?;
+
+        let invalid = || Error::validation("Password reset token is invalid or has expired");
+        let (user_id, expires_at) = self.repo.consume_password_reset(&hash_secret_token(&req.token)).await?
+            .ok_or_else(invalid)?;
+        if expires_at <= Utc::now() {
+            return Err(invalid());
+        }
+
+        let password_hash = self.hash_password(&req.new_password)?;
+        self.repo.update_password(user_id, &password_hash).await?;
+        self.repo.clear_failed_logins(user_id).await?;
+        self.security.revoke_all_sessions(&self.pool, user_id).await
+    }
+
     pub async fn get_user(&self, id: Uuid) -> Result<User> {
         self.repo.find_by_id(id).await
     }
@@ -120,8 +242,67 @@ impl AuthService {
         self.repo.list().await
     }
 
-    pub fn validate_token(&self, token: &str) -> Result<jwt::TokenData> {
-        jwt::validate_token(token).map_err(|_| Error::Unauthorized)
+    /// Accepts an access token only while it is unrevoked and its session is still open.
+    pub async fn validate_token(&self, token: &str) -> Result<jwt::TokenData> {
+        let data = jwt::validate_token(token).map_err(|_| Error::Unauthorized)?;
+        if self.repo.is_token_revoked(&data.jti).await? {
+            return Err(Error::Unauthorized);
+        }
+
+        let session_id = Uuid::parse_str(&data.session_id).map_err(|_| Error::Unauthorized)?;
+        let session = self.security.get_session(&self.pool, session_id).await?.ok_or(Error::Unauthorized)?;
+        if session.user_id.to_string() != data.user_id || session.expires_at <= Utc::now() {
+            return Err(Error::Unauthorized);
+        }
+        Ok(data)
+    }
+
+    async fn find_active_user(&self, id: Uuid) -> Result<User> {
+        let user = self.repo.find_by_id(id).await.map_err(|e| match e {
+            Error::NotFound(_) => Error::Unauthorized,
+            _ => e,
+        })?;
+        if user.status != UserStatus::Active {
+            return Err(Error::Unauthorized);
+        }
+        Ok(user)
+    }
+
+    async fn reject_login<T>(&self, user: &User) -> Result<T> {
+        let lock_until = Utc::now() + Duration::minutes(LOCKOUT_MINUTES);
+        if self.repo.record_failed_login(user.base.id, MAX_FAILED_LOGINS, lock_until).await? {
+            return Err(locked(lock_until));
+        }
+        Err(Error::Unauthorized)
+    }
+
+    async fn complete_login(&self, user: &User, client: &ClientInfo) -> Result<AuthResponse> {
+        self.repo.clear_failed_logins(user.base.id).await?;
+        self.repo.update_last_login(user.base.id).await?;
+        self.start_session(user, client).await
+    }
+
+    async fn start_session(&self, user: &User, client: &ClientInfo) -> Result<AuthResponse> {
+        let refresh_token = generate_secret_token();
+        let session = self.security.create_session(
+            &self.pool,
+            user.base.id,
+            &refresh_token,
+            client.ip_address.as_deref(),
+            client.user_agent.as_deref(),
+            self.refresh_token_ttl.num_hours(),
+        ).await?;
+        let (token, expires_at) = jwt::generate_token(
+            &user.base.id.to_string(), &user.username, user.role.as_str(), &session.id.to_string(), self.access_token_ttl,
+        )?;
+
+        Ok(AuthResponse {
+            token,
+            expires_at,
+            refresh_token,
+            refresh_expires_at: session.expires_at,
+            user: UserInfo::from(user),
+        })
     }
 
     fn hash_password(&self, password: &str) -> Result<String> {
@@ -139,30 +320,25 @@ impl AuthService {
     }
 }
 
-pub fn has_permission(role: &str, permission: &str) -> bool {
-    let role_perms: Vec<&str> = match role {
-        "Admin" => vec!["*"],
-        "Finance" => vec!["finance:*", "sales:read", "purchasing:read"],
-        "Warehouse" => vec!["inventory:*", "purchasing:*", "manufacturing:read"],
-        "Sales" => vec!["sales:*", "inventory:read", "customers:*"],
-        "HR" => vec!["hr:*", "employees:*"],
-        _ => vec!["*:read"],
-    };
-    
-    role_perms.iter().any(|p| {
-        if *p == "*" || *p == permission {
-            return true;
-        }
-        if p.ends_with(":*") {
-            let prefix = &p[..p.len()-1];
-            return permission.starts_with(prefix);
-        }
-        if p.starts_with("*:") {
-            let suffix = &p[1..];
-            return permission.ends_with(suffix);
-        }
-        false
-    })
+fn ensure_not_locked(user: &User) -> Result<()> {
+    match user.locked_until {
+        Some(until) if until > Utc::now() => Err(locked(until)),
+        _ => Ok(()),
+    }
+}
+
+fn locked(until: DateTime<Utc>) -> Error {
+    Error::forbidden(format!("Account is locked after repeated failed logins until {}", until.to_rfc3339()))
+}
+
+/// A random 256-bit token for refresh and password-reset links, hex encoded.
+fn generate_secret_token() -> String {
+    let bytes: [u8; 32] = rand::random();
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hash_secret_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
 }
 
 #[cfg(test)]
@@ -200,28 +376,6 @@ mod tests {
         assert!(!svc.verify_password("wrongpassword", &hash).unwrap());
     }
     
-    #[test]
-    fn test_has_permission_admin() {
-        assert!(has_permission("Admin", "finance:write"));
-        assert!(has_permission("Admin", "anything:anything"));
-    }
-    
-    #[test]
-    fn test_has_permission_finance() {
-        assert!(has_permission("Finance", "finance:write"));
-        assert!(has_permission("Finance", "finance:read"));
-        assert!(has_permission("Finance", "sales:read"));
-        assert!(!has_permission("Finance", "sales:write"));
-        assert!(!has_permission("Finance", "hr:read"));
-    }
-    
-    #[test]
-    fn test_has_permission_user() {
-        assert!(has_permission("User", "finance:read"));
-        assert!(has_permission("User", "inventory:read"));
-        assert!(!has_permission("User", "finance:write"));
-    }
-    
     #[test]
     fn test_password_strength_too_short() {
         assert!(validate_password_strength("abc1").is_err());
@@ -23,7 +23,7 @@ pub struct BackupSchedule {
     pub next_run: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum BackupType {
     Full,
@@ -31,7 +31,7 @@ pub enum BackupType {
     Differential,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum BackupStorageType {
     Local,
@@ -62,9 +62,13 @@ pub struct BackupRecord {
     pub verification_status: Option<VerificationStatus>,
     pub verified_at: Option<DateTime<Utc>>,
     pub is_restorable: bool,
+    /// Backup this incremental/differential was diffed against; None for full backups.
+    pub parent_backup_id: Option<Uuid>,
+    /// SHA-256 of the uncompressed database image, used to validate a rebuilt chain.
+    pub database_checksum: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum BackupStatus {
     Pending,
@@ -74,7 +78,7 @@ pub enum BackupStatus {
     Cancelled,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum VerificationStatus {
     Pending,
@@ -99,7 +103,7 @@ pub struct RestoreOperation {
     pub backup_before_restore: Option<Uuid>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum RestoreStatus {
     Pending,
@@ -109,7 +113,7 @@ pub enum RestoreStatus {
     RolledBack,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum RestoreType {
     Full,
@@ -14,6 +14,7 @@ pub trait BackupRepository: Send + Sync {
     async fn create_backup(&self, backup: BackupRecord) -> Result<BackupRecord>;
     async fn get_backup(&self, id: Uuid) -> Result<Option<BackupRecord>>;
     async fn list_backups(&self, limit: i32) -> Result<Vec<BackupRecord>>;
+    async fn list_restorable_backups(&self) -> Result<Vec<BackupRecord>>;
     async fn update_backup(&self, backup: BackupRecord) -> Result<BackupRecord>;
     async fn delete_backup(&self, id: Uuid) -> Result<()>;
     async fn create_restore(&self, restore: RestoreOperation) -> Result<RestoreOperation>;
@@ -128,8 +129,8 @@ impl BackupRepository for SqliteBackupRepository {
             r#"INSERT INTO backup_records (id, schedule_id, backup_type, status, file_path, file_size_bytes,
                compressed_size_bytes, checksum, checksum_algorithm, started_at, completed_at, duration_seconds,
                tables_included, records_count, error_message, verification_status, verified_at, is_restorable,
-               created_at, updated_at, created_by, updated_by)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+               parent_backup_id, database_checksum, created_at, updated_at, created_by, updated_by)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         )
         .bind(backup.base.id)
         .bind(backup.schedule_id)
@@ -149,6 +150,8 @@ impl BackupRepository for SqliteBackupRepository {
         .bind(&backup.verification_status)
         .bind(backup.verified_at)
         .bind(backup.is_restorable)
+        .bind(backup.parent_backup_id)
+        .bind(&backup.database_checksum)
         .bind(backup.base.created_at)
         .bind(backup.base.updated_at)
         .bind(backup.base.created_by)
@@ -161,7 +164,8 @@ impl BackupRepository for SqliteBackupRepository {
         sqlx::query_as::<_, BackupRecord>(
             r#"SELECT id, schedule_id, backup_type, status, file_path, file_size_bytes, compressed_size_bytes,
                checksum, checksum_algorithm, started_at, completed_at, duration_seconds, tables_included,
-               records_count, error_message, verification_status, verified_at, is_restorable, created_at, updated_at, created_by, updated_by
+               records_count, error_message, verification_status, verified_at, is_restorable, parent_backup_id,
+               database_checksum, created_at, updated_at, created_by, updated_by
                FROM backup_records WHERE id = ?"#,
         )
         .bind(id)
@@ -173,7 +177,8 @@ impl BackupRepository for SqliteBackupRepository {
         sqlx::query_as::<_, BackupRecord>(
             r#"SELECT id, schedule_id, backup_type, status, file_path, file_size_bytes, compressed_size_bytes,
                checksum, checksum_algorithm, started_at, completed_at, duration_seconds, tables_included,
-               records_count, error_message, verification_status, verified_at, is_restorable, created_at, updated_at, created_by, updated_by
+               records_count, error_message, verification_status, verified_at, is_restorable, parent_backup_id,
+               database_checksum, created_at, updated_at, created_by, updated_by
                FROM backup_records ORDER BY started_at DESC LIMIT ?"#,
         )
         .bind(limit)
@@ -181,20 +186,42 @@ impl BackupRepository for SqliteBackupRepository {
         .map_err(Into::into)
     }
 
+    async fn list_restorable_backups(&self) -> Result<Vec<BackupRecord>> {
+        sqlx::query_as::<_, BackupRecord>(
+            r#"SELECT id, schedule_id, backup_type, status, file_path, file_size_bytes, compressed_size_bytes,
+               checksum, checksum_algorithm, started_at, completed_at, duration_seconds, tables_included,
+               records_count, error_message, verification_status, verified_at, is_restorable, parent_backup_id,
+               database_checksum, created_at, updated_at, created_by, updated_by
+               FROM backup_records WHERE status = 'Completed' AND is_restorable = 1 ORDER BY started_at DESC"#,
+        )
+        .fetch_all(&self.pool).await
+        .map_err(Into::into)
+    }
+
     async fn update_backup(&self, backup: BackupRecord) -> Result<BackupRecord> {
         sqlx::query(
-            r#"UPDATE backup_records SET status = ?, completed_at = ?, duration_seconds = ?, records_count = ?,
-               error_message = ?, verification_status = ?, verified_at = ?, is_restorable = ?, updated_at = ?, updated_by = ?
+            r#"UPDATE backup_records SET backup_type = ?, status = ?, file_path = ?, file_size_bytes = ?,
+               compressed_size_bytes = ?, checksum = ?, completed_at = ?, duration_seconds = ?, tables_included = ?,
+               records_count = ?, error_message = ?, verification_status = ?, verified_at = ?, is_restorable = ?,
+               parent_backup_id = ?, database_checksum = ?, updated_at = ?, updated_by = ?
                WHERE id = ?"#,
         )
+        .bind(&backup.backup_type)
         .bind(&backup.status)
+        .bind(&backup.file_path)
+        .bind(backup.file_size_bytes)
+        .bind(backup.compressed_size_bytes)
+        .bind(&backup.checksum)
         .bind(backup.completed_at)
         .bind(backup.duration_seconds)
+        .bind(&backup.tables_included)
         .bind(backup.records_count)
         .bind(&backup.error_message)
         .bind(&backup.verification_status)
         .bind(backup.verified_at)
         .bind(backup.is_restorable)
+        .bind(backup.parent_backup_id)
+        .bind(&backup.database_checksum)
         .bind(backup.base.updated_at)
         .bind(backup.base.updated_by)
         .bind(backup.base.id)
@@ -203,6 +230,9 @@ impl BackupRepository for SqliteBackupRepository {
     }
 
     async fn delete_backup(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM restore_operations WHERE backup_id = ?")
+            .bind(id)
+            .execute(&self.pool).await?;
         sqlx::query("DELETE FROM backup_records WHERE id = ?")
             .bind(id)
             .execute(&self.pool).await?;
@@ -239,13 +269,14 @@ impl BackupRepository for SqliteBackupRepository {
     async fn update_restore(&self, restore: RestoreOperation) -> Result<RestoreOperation> {
         sqlx::query(
             r#"UPDATE restore_operations SET status = ?, completed_at = ?, duration_seconds = ?,
-               records_restored = ?, error_message = ?, updated_at = ?, updated_by = ? WHERE id = ?"#,
+               records_restored = ?, error_message = ?, backup_before_restore = ?, updated_at = ?, updated_by = ? WHERE id = ?"#,
         )
         .bind(&restore.status)
         .bind(restore.completed_at)
         .bind(restore.duration_seconds)
         .bind(restore.records_restored)
         .bind(&restore.error_message)
+        .bind(restore.backup_before_restore)
         .bind(restore.base.updated_at)
         .bind(restore.base.updated_by)
         .bind(restore.base.id)
@@ -303,7 +334,9 @@ impl BackupRepository for SqliteBackupRepository {
         let count: i64 = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM backup_records")
             .fetch_one(&self.pool).await?;
         
-        let total_size: i64 = sqlx::query_scalar::<_, i64>("SELECT COALESCE(SUM(file_size_bytes), 0) FROM backup_records")
+        let total_size: i64 = sqlx::query_scalar::<_, i64>(
+            "SELECT COALESCE(SUM(COALESCE(compressed_size_bytes, file_size_bytes)), 0) FROM backup_records"
+        )
             .fetch_one(&self.pool).await?;
         
         Ok(BackupStorageStats {
@@ -1,14 +1,24 @@
-use sqlx::SqlitePool;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{Connection, Sqlite, SqliteConnection, SqlitePool, Transaction};
 use uuid::Uuid;
-use chrono::Utc;
-use std::path::PathBuf;
-use erp_core::{BaseEntity, Result};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use erp_core::{BaseEntity, Error, Result};
 use crate::models::*;
 use crate::repository::{BackupRepository, SqliteBackupRepository};
+use crate::snapshot;
+
+const DEFAULT_BACKUP_DIR: &str = "./backups";
+
+/// Tables that describe the backups themselves; a restore leaves them as they are so the
+/// catalog (including the restore being run) survives rolling the data back.
+const CATALOG_TABLES: [&str; 4] = ["backup_schedules", "backup_records", "restore_operations", "backup_verifications"];
 
 pub struct BackupService<R: BackupRepository = SqliteBackupRepository> {
     repo: R,
     pool: SqlitePool,
+    backup_dir: PathBuf,
 }
 
 impl BackupService<SqliteBackupRepository> {
@@ -16,13 +26,20 @@ impl BackupService<SqliteBackupRepository> {
         Self {
             repo: SqliteBackupRepository::new(pool.clone()),
             pool,
+            backup_dir: PathBuf::from(DEFAULT_BACKUP_DIR),
         }
     }
 }
 
 impl<R: BackupRepository> BackupService<R> {
     pub fn with_repo(repo: R, pool: SqlitePool) -> Self {
-        Self { repo, pool }
+        Self { repo, pool, backup_dir: PathBuf::from(DEFAULT_BACKUP_DIR) }
+    }
+
+    /// Directory used for backups that are not taken from a schedule with its own storage path.
+    pub fn with_backup_dir(mut self, backup_dir: impl Into<PathBuf>) -> Self {
+        self.backup_dir = backup_dir.into();
+        self
     }
 
     pub async fn create_schedule(&self, schedule: BackupSchedule) -> Result<BackupSchedule> {
@@ -45,13 +62,29 @@ impl<R: BackupRepository> BackupService<R> {
         self.repo.delete_schedule(id).await
     }
 
+    /// Takes a backup of the live database. With a schedule, the schedule's backup type,
+    /// storage path, compression and retention settings apply; incremental backups are diffed
+    /// against the schedule's latest backup and differential ones against its latest full backup.
     pub async fn execute_backup(&self, schedule_id: Option<Uuid>) -> Result<BackupRecord> {
+        let schedule = match schedule_id {
+            Some(id) => Some(self.repo.get_schedule(id).await?
+                .ok_or_else(|| Error::not_found("BackupSchedule", &id.to_string()))?),
+            None => None,
+        };
+        if let Some(schedule) = &schedule {
+            if schedule.storage_type != BackupStorageType::Local {
+                return Err(Error::business_rule(format!("{:?} backup storage is not supported", schedule.storage_type)));
+            }
+        }
+
+        let requested_type = schedule.as_ref().map(|s| s.backup_type.clone()).unwrap_or(BackupType::Full);
+        let parent = self.find_parent(schedule_id, &requested_type).await?;
         let started_at = Utc::now();
-        
+
         let backup = BackupRecord {
             base: BaseEntity::new(),
             schedule_id,
-            backup_type: BackupType::Full,
+            backup_type: if parent.is_some() { requested_type } else { BackupType::Full },
             status: BackupStatus::InProgress,
             file_path: String::new(),
             file_size_bytes: 0,
@@ -67,60 +100,157 @@ impl<R: BackupRepository> BackupService<R> {
             verification_status: None,
             verified_at: None,
             is_restorable: false,
+            parent_backup_id: parent.as_ref().map(|p| p.base.id),
+            database_checksum: None,
         };
-        
+
         let mut backup = self.repo.create_backup(backup).await?;
-        
-        match self.do_backup(&mut backup).await {
+
+        match self.do_backup(&mut backup, schedule.as_ref(), parent.as_ref()).await {
             Ok(_) => {
                 backup.status = BackupStatus::Completed;
                 backup.completed_at = Some(Utc::now());
                 backup.duration_seconds = Some((Utc::now() - started_at).num_seconds());
                 backup.is_restorable = true;
-                self.repo.update_backup(backup.clone()).await
+                let backup = self.repo.update_backup(backup).await?;
+
+                if let Some(mut schedule) = schedule {
+                    schedule.last_run = Some(started_at);
+                    schedule.base.updated_at = Utc::now();
+                    let schedule = self.repo.update_schedule(schedule).await?;
+                    self.apply_retention(&schedule).await?;
+                }
+                Ok(backup)
             }
             Err(e) => {
                 backup.status = BackupStatus::Failed;
                 backup.error_message = Some(e.to_string());
                 backup.completed_at = Some(Utc::now());
+                backup.parent_backup_id = None;
                 self.repo.update_backup(backup.clone()).await
             }
         }
     }
 
-    async fn do_backup(&self, backup: &mut BackupRecord) -> Result<()> {
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("backup_{}.db", timestamp);
-        let backup_dir = PathBuf::from("./backups");
-        
+    async fn find_parent(&self, schedule_id: Option<Uuid>, backup_type: &BackupType) -> Result<Option<BackupRecord>> {
+        if *backup_type == BackupType::Full {
+            return Ok(None);
+        }
+        let parent = self.repo.list_restorable_backups().await?
+            .into_iter()
+            .filter(|b| b.schedule_id == schedule_id)
+            .find(|b| *backup_type == BackupType::Incremental || b.backup_type == BackupType::Full);
+        Ok(parent)
+    }
+
+    async fn do_backup(&self, backup: &mut BackupRecord, schedule: Option<&BackupSchedule>, parent: Option<&BackupRecord>) -> Result<()> {
+        let backup_dir = schedule
+            .map(|s| PathBuf::from(&s.storage_path))
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| self.backup_dir.clone());
         tokio::fs::create_dir_all(&backup_dir).await
-            .map_err(|e| anyhow::anyhow!("Failed to create backup directory: {}", e))?;
-        
-        let backup_path = backup_dir.join(&filename);
-        backup.file_path = backup_path.to_string_lossy().to_string();
-        
-        let db_path = "./erp.db";
-        
-        tokio::fs::copy(db_path, &backup_path).await
-            .map_err(|e| anyhow::anyhow!("Failed to copy database: {}", e))?;
-        
-        let metadata = tokio::fs::metadata(&backup_path).await
-            .map_err(|e| anyhow::anyhow!("Failed to get backup file metadata: {}", e))?;
-        backup.file_size_bytes = metadata.len() as i64;
-        
-        let tables: Vec<String> = sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type='table'")
-            .fetch_all(&self.pool).await?;
+            .map_err(|e| Error::internal(format!("Failed to create backup directory: {}", e)))?;
+
+        // VACUUM INTO writes a transactionally consistent copy of the live database, even while
+        // other connections in the pool keep writing.
+        let snapshot_path = backup_dir.join(format!(".snapshot-{}.db", backup.base.id));
+        sqlx::query("VACUUM INTO ?")
+            .bind(snapshot_path.to_string_lossy().to_string())
+            .execute(&self.pool).await?;
+
+        let result = self.write_backup(backup, &backup_dir, &snapshot_path, schedule, parent).await;
+        let _ = tokio::fs::remove_file(&snapshot_path).await;
+        result
+    }
+
+    /// Writes the snapshot out as the backup's artifact, a page-level delta against the parent's
+    /// rebuilt image when there is one. Images are streamed between files and never held in memory.
+    async fn write_backup(&self, backup: &mut BackupRecord, backup_dir: &Path, snapshot_path: &Path, schedule: Option<&BackupSchedule>, parent: Option<&BackupRecord>) -> Result<()> {
+        let (tables, records) = inspect_database(snapshot_path).await?;
         backup.tables_included = Some(tables.join(","));
-        
-        // Use COALESCE for safer record count calculation
-        let total_records: i64 = sqlx::query_scalar(
-            "SELECT COALESCE(SUM(cnt), 0) FROM (SELECT COUNT(*) as cnt FROM users UNION ALL SELECT COUNT(*) FROM products UNION ALL SELECT COUNT(*) FROM customers)"
-        ).fetch_one(&self.pool).await?;
-        backup.records_count = Some(total_records);
-        
+        backup.records_count = Some(records);
+        let snapshot_file = snapshot_path.to_path_buf();
+        backup.database_checksum = Some(blocking(move || snapshot::sha256_file(&snapshot_file)).await?);
+
+        let base_path = match parent {
+            Some(parent) => {
+                let base_path = backup_dir.join(format!(".base-{}.db", backup.base.id));
+                if let Err(e) = self.materialize(parent, &base_path).await {
+                    let _ = tokio::fs::remove_file(&base_path).await;
+                    return Err(e);
+                }
+                Some(base_path)
+            }
+            None => None,
+        };
+        let compress = schedule.map(|s| s.compression).unwrap_or(true);
+        let filename = format!(
+            "backup_{}_{}.{}{}",
+            backup.started_at.format("%Y%m%d_%H%M%S"),
+            &backup.base.id.simple().to_string()[..8],
+            if base_path.is_some() { "delta" } else { "db" },
+            if compress { ".gz" } else { "" },
+        );
+        let backup_path = backup_dir.join(filename);
+        backup.file_path = backup_path.to_string_lossy().to_string();
+
+        let snapshot_file = snapshot_path.to_path_buf();
+        let base = base_path.clone();
+        let written = blocking(move || snapshot::write_artifact(&backup_path, compress, |out| match &base {
+            Some(base) => snapshot::write_delta(base, &snapshot_file, out),
+            None => {
+                let mut file = std::fs::File::open(&snapshot_file)
+                    .map_err(|e| Error::internal(format!("Failed to read database snapshot: {}", e)))?;
+                std::io::copy(&mut file, out)
+                    .map_err(|e| Error::internal(format!("Failed to write backup: {}", e)))?;
+                Ok(())
+            }
+        }))
+        .await;
+        if let Some(base_path) = base_path {
+            let _ = tokio::fs::remove_file(base_path).await;
+        }
+        let artifact = written?;
+
+        backup.file_size_bytes = artifact.contents_len as i64;
+        backup.compressed_size_bytes = compress.then_some(artifact.written_len as i64);
+        backup.checksum = Some(artifact.checksum);
         Ok(())
     }
 
+    /// Rebuilds the full database image for a backup into `dest` by replaying its chain from
+    /// the last full backup, checking every file's checksum along the way.
+    async fn materialize(&self, backup: &BackupRecord, dest: &Path) -> Result<()> {
+        let mut chain = vec![backup.clone()];
+        while let Some(parent_id) = chain.last().and_then(|b| b.parent_backup_id) {
+            if chain.iter().any(|b| b.base.id == parent_id) {
+                return Err(Error::business_rule("Backup chain contains a cycle"));
+            }
+            let parent = self.repo.get_backup(parent_id).await?
+                .ok_or_else(|| Error::not_found("BackupRecord", &parent_id.to_string()))?;
+            chain.push(parent);
+        }
+
+        let expected = backup.database_checksum.clone();
+        let dest = dest.to_path_buf();
+        blocking(move || {
+            let mut links = chain.iter().rev();
+            let full = links.next().expect("chain always contains the requested backup");
+            snapshot::extract_artifact(Path::new(&full.file_path), full.checksum.as_deref(), &dest)?;
+            for link in links {
+                let mut delta = snapshot::open_artifact(Path::new(&link.file_path), link.checksum.as_deref())?;
+                snapshot::apply_delta(&dest, &mut delta)?;
+            }
+            if let Some(expected) = expected {
+                if snapshot::sha256_file(&dest)? != expected {
+                    return Err(Error::business_rule("Rebuilt database does not match the backup checksum"));
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
     pub async fn list_backups(&self, limit: i32) -> Result<Vec<BackupRecord>> {
         self.repo.list_backups(limit).await
     }
@@ -130,6 +260,13 @@ impl<R: BackupRepository> BackupService<R> {
     }
 
     pub async fn delete_backup(&self, id: Uuid) -> Result<()> {
+        let dependents = self.repo.list_restorable_backups().await?
+            .into_iter()
+            .filter(|b| b.parent_backup_id == Some(id))
+            .count();
+        if dependents > 0 {
+            return Err(Error::business_rule(format!("Backup {} is the base of {} newer backup(s)", id, dependents)));
+        }
         if let Some(backup) = self.repo.get_backup(id).await? {
             if !backup.file_path.is_empty() {
                 let _ = tokio::fs::remove_file(&backup.file_path).await;
@@ -140,17 +277,32 @@ impl<R: BackupRepository> BackupService<R> {
 
     pub async fn restore_backup(&self, backup_id: Uuid, initiated_by: Option<Uuid>) -> Result<RestoreOperation> {
         let backup = self.repo.get_backup(backup_id).await?
-            .ok_or_else(|| anyhow::anyhow!("Backup not found"))?;
-        
-        if !backup.is_restorable {
-            return Err(erp_core::Error::NotFound("Backup is not restorable".to_string()));
+            .ok_or_else(|| Error::not_found("BackupRecord", &backup_id.to_string()))?;
+        self.run_restore(backup, RestoreType::Full, initiated_by).await
+    }
+
+    /// Restores the most recent backup that had completed at `as_of`. Backups are snapshots and
+    /// no change log is kept between them, so changes made after that backup but before `as_of`
+    /// are not replayed; the database returns to the backup's state, not to `as_of` itself.
+    pub async fn restore_latest_backup_before(&self, as_of: DateTime<Utc>, initiated_by: Option<Uuid>) -> Result<RestoreOperation> {
+        let backup = self.repo.list_restorable_backups().await?
+            .into_iter()
+            .filter(|b| b.completed_at.is_some_and(|completed| completed <= as_of))
+            .max_by_key(|b| b.completed_at)
+            .ok_or_else(|| Error::NotFound(format!("No restorable backup completed before {}", as_of.to_rfc3339())))?;
+        self.run_restore(backup, RestoreType::PointInTime, initiated_by).await
+    }
+
+    async fn run_restore(&self, backup: BackupRecord, restore_type: RestoreType, initiated_by: Option<Uuid>) -> Result<RestoreOperation> {
+        if !backup.is_restorable || backup.status != BackupStatus::Completed {
+            return Err(Error::business_rule("Backup is not restorable"));
         }
-        
+
         let restore = RestoreOperation {
             base: BaseEntity::new(),
-            backup_id,
+            backup_id: backup.base.id,
             status: RestoreStatus::InProgress,
-            restore_type: RestoreType::Full,
+            restore_type,
             target_tables: None,
             started_at: Utc::now(),
             completed_at: None,
@@ -160,10 +312,10 @@ impl<R: BackupRepository> BackupService<R> {
             initiated_by,
             backup_before_restore: None,
         };
-        
+
         let mut restore = self.repo.create_restore(restore).await?;
-        
-        match self.do_restore(&backup).await {
+
+        match self.do_restore(&backup, &mut restore).await {
             Ok(records) => {
                 restore.status = RestoreStatus::Completed;
                 restore.completed_at = Some(Utc::now());
@@ -180,53 +332,107 @@ impl<R: BackupRepository> BackupService<R> {
         }
     }
 
-    async fn do_restore(&self, backup: &BackupRecord) -> Result<i64> {
-        let backup_path = PathBuf::from(&backup.file_path);
-        if !backup_path.exists() {
-            return Err(erp_core::Error::NotFound(format!("Backup file not found: {}", backup.file_path)));
+    /// Rebuilds the backup into a staging file, refuses it unless SQLite's integrity check
+    /// passes, takes a safety backup, then replaces the live data in a single transaction.
+    /// Copying through the pool rather than renaming files keeps connections other requests
+    /// already hold from writing to an unlinked database.
+    async fn do_restore(&self, backup: &BackupRecord, restore: &mut RestoreOperation) -> Result<i64> {
+        let staging_dir = Path::new(&backup.file_path).parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| self.backup_dir.clone());
+        let staged = staging_dir.join(format!(".restore-{}.db", restore.base.id));
+
+        let result = async {
+            self.materialize(backup, &staged).await?;
+            inspect_database(&staged).await?;
+
+            let safety = self.execute_backup(None).await?;
+            if safety.status != BackupStatus::Completed {
+                return Err(Error::business_rule(format!(
+                    "Safety backup before restore failed: {}",
+                    safety.error_message.unwrap_or_default()
+                )));
+            }
+            restore.backup_before_restore = Some(safety.base.id);
+
+            self.replace_live_data(&staged).await
         }
-        
-        tokio::fs::copy(&backup_path, "./erp.db").await
-            .map_err(|e| anyhow::anyhow!("Failed to restore database: {}", e))?;
-        
-        Ok(backup.records_count.unwrap_or(0))
+        .await;
+
+        let _ = tokio::fs::remove_file(&staged).await;
+        result
+    }
+
+    async fn replace_live_data(&self, staged: &Path) -> Result<i64> {
+        let mut conn = self.pool.acquire().await?;
+        // Foreign keys can only be toggled outside a transaction; rows are reloaded in table
+        // order, so parents may arrive after their children.
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+        let attached = sqlx::query("ATTACH DATABASE ? AS restore_src")
+            .bind(staged.to_string_lossy().to_string())
+            .execute(&mut *conn).await;
+
+        let result = match attached {
+            Ok(_) => copy_attached_tables(&mut conn).await,
+            Err(e) => Err(e.into()),
+        };
+
+        let _ = sqlx::query("DETACH DATABASE restore_src").execute(&mut *conn).await;
+        sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await?;
+        result
     }
 
     pub async fn verify_backup(&self, backup_id: Uuid) -> Result<BackupVerification> {
         let backup = self.repo.get_backup(backup_id).await?
-            .ok_or_else(|| anyhow::anyhow!("Backup not found"))?;
-        
+            .ok_or_else(|| Error::not_found("BackupRecord", &backup_id.to_string()))?;
+
         let mut verification = BackupVerification {
             base: BaseEntity::new(),
             backup_id,
             status: VerificationStatus::Pending,
             checked_at: Utc::now(),
-            checksum_valid: true,
-            file_readable: false,
+            checksum_valid: false,
+            file_readable: Path::new(&backup.file_path).is_file(),
             schema_valid: false,
             sample_data_valid: false,
             error_details: None,
         };
-        
-        let backup_path = PathBuf::from(&backup.file_path);
-        verification.file_readable = backup_path.exists();
-        
-        if verification.file_readable {
+
+        let staged = self.backup_dir.join(format!(".verify-{}.db", verification.base.id));
+        let outcome = async {
+            tokio::fs::create_dir_all(&self.backup_dir).await
+                .map_err(|e| Error::internal(format!("Failed to create backup directory: {}", e)))?;
+            self.materialize(&backup, &staged).await?;
+            verification.checksum_valid = true;
+
+            let (tables, records) = inspect_database(&staged).await?;
             verification.schema_valid = true;
-            verification.sample_data_valid = true;
-            verification.status = VerificationStatus::Verified;
-        } else {
-            verification.status = VerificationStatus::Failed;
-            verification.error_details = Some("Backup file not found".to_string());
+
+            verification.sample_data_valid = backup.tables_included.as_deref().is_none_or(|t| t == tables.join(","))
+                && backup.records_count.is_none_or(|count| count == records);
+            if !verification.sample_data_valid {
+                return Err(Error::business_rule("Backup contents differ from what was recorded at backup time"));
+            }
+            Ok(())
+        }
+        .await;
+        let _ = tokio::fs::remove_file(&staged).await;
+
+        match outcome {
+            Ok(()) => verification.status = VerificationStatus::Verified,
+            Err(e) => {
+                verification.status = VerificationStatus::Failed;
+                verification.error_details = Some(e.to_string());
+            }
         }
-        
+
         self.repo.create_verification(verification.clone()).await?;
-        
+
         let mut backup = backup;
         backup.verification_status = Some(verification.status.clone());
         backup.verified_at = Some(Utc::now());
         self.repo.update_backup(backup).await?;
-        
+
         Ok(verification)
     }
 
@@ -238,18 +444,195 @@ impl<R: BackupRepository> BackupService<R> {
         self.repo.get_storage_stats().await
     }
 
+    /// Keeps the schedule's newest `max_backups` backups that are younger than `retention_days`
+    /// (always at least the latest one) plus any backups they are built on, and deletes the rest.
+    pub async fn apply_retention(&self, schedule: &BackupSchedule) -> Result<i32> {
+        let cutoff = Utc::now() - chrono::Duration::days(schedule.retention_days as i64);
+        let backups: Vec<BackupRecord> = self.repo.list_restorable_backups().await?
+            .into_iter()
+            .filter(|b| b.schedule_id == Some(schedule.base.id))
+            .collect();
+
+        let keep = backups.iter()
+            .enumerate()
+            .filter(|(i, b)| *i == 0 || (*i < schedule.max_backups.max(1) as usize && b.started_at >= cutoff))
+            .map(|(_, b)| b.base.id)
+            .collect();
+        self.prune(backups, keep).await
+    }
+
     pub async fn cleanup_old_backups(&self, retention_days: i32) -> Result<i32> {
         let cutoff = Utc::now() - chrono::Duration::days(retention_days as i64);
         let backups = self.repo.list_backups(1000).await?;
-        let mut deleted = 0;
-        
-        for backup in backups {
-            if backup.started_at < cutoff {
-                self.delete_backup(backup.base.id).await?;
-                deleted += 1;
+        let keep = backups.iter()
+            .filter(|b| b.started_at >= cutoff)
+            .map(|b| b.base.id)
+            .collect();
+        self.prune(backups, keep).await
+    }
+
+    /// Deletes every backup outside `keep`, except those a kept backup's chain still needs.
+    async fn prune(&self, backups: Vec<BackupRecord>, mut keep: HashSet<Uuid>) -> Result<i32> {
+        let parents: HashMap<Uuid, Uuid> = backups.iter()
+            .filter_map(|b| b.parent_backup_id.map(|parent| (b.base.id, parent)))
+            .collect();
+        let mut pending: Vec<Uuid> = keep.iter().copied().collect();
+        while let Some(id) = pending.pop() {
+            if let Some(parent) = parents.get(&id) {
+                if keep.insert(*parent) {
+                    pending.push(*parent);
+                }
             }
         }
-        
+
+        // Newest first, so incrementals go before the backups they were diffed against.
+        let mut expired: Vec<&BackupRecord> = backups.iter().filter(|b| !keep.contains(&b.base.id)).collect();
+        expired.sort_by_key(|b| std::cmp::Reverse(b.started_at));
+
+        let mut deleted = 0;
+        for backup in expired {
+            self.delete_backup(backup.base.id).await?;
+            deleted += 1;
+        }
         Ok(deleted)
     }
 }
+
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Error::internal(format!("Backup file task failed: {}", e)))?
+}
+
+/// Opens a staged copy of a database, runs SQLite's integrity check and returns its tables
+/// and total row count. The copy is not opened read-only: FTS5 checks its index by running
+/// an INSERT command, which fails on a read-only connection.
+async fn inspect_database(path: &Path) -> Result<(Vec<String>, i64)> {
+    let options = SqliteConnectOptions::new().filename(path);
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+
+    let integrity: String = sqlx::query_scalar("PRAGMA integrity_check").fetch_one(&mut conn).await?;
+    if integrity != "ok" {
+        return Err(Error::business_rule(format!("Backup failed integrity check: {}", integrity)));
+    }
+
+    let tables: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name"
+    )
+    .fetch_all(&mut conn).await?;
+
+    let mut records = 0;
+    for table in &tables {
+        let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", quote_ident(table)))
+            .fetch_one(&mut conn).await?;
+        records += count;
+    }
+
+    conn.close().await?;
+    Ok((tables, records))
+}
+
+/// Replaces the rows of every non-catalog table in `main` with those in `restore_src`.
+/// Tables that only exist in the backup are recreated; columns added since the backup
+/// was taken fall back to their defaults. The search index is rebuilt afterwards.
+async fn copy_attached_tables(conn: &mut SqliteConnection) -> Result<i64> {
+    let mut tx = conn.begin().await?;
+
+    // Virtual tables are skipped, and the search index is left to the rebuild.
+    let list_tables = |schema: &str| format!(
+        "SELECT name, sql FROM {}.sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' \
+         AND sql NOT LIKE 'CREATE VIRTUAL TABLE%' ORDER BY name",
+        schema
+    );
+    let live: Vec<(String, String)> = sqlx::query_as(&list_tables("main")).fetch_all(&mut *tx).await?;
+    let source: Vec<(String, String)> = sqlx::query_as(&list_tables("restore_src")).fetch_all(&mut *tx).await?;
+    let live: HashSet<String> = live.into_iter().map(|(name, _)| name).collect();
+
+    for table in live.iter().filter(|t| !CATALOG_TABLES.contains(&t.as_str()) && !is_search_index(t)) {
+        sqlx::query(&format!("DELETE FROM main.{}", quote_ident(table))).execute(&mut *tx).await?;
+    }
+
+    let mut restored = 0;
+    for (table, create_sql) in source.iter().filter(|(t, _)| !CATALOG_TABLES.contains(&t.as_str()) && !is_search_index(t)) {
+        if !live.contains(table) {
+            sqlx::query(create_sql).execute(&mut *tx).await?;
+            let indexes: Vec<String> = sqlx::query_scalar(
+                "SELECT sql FROM restore_src.sqlite_master WHERE type = 'index' AND tbl_name = ? AND sql IS NOT NULL"
+            )
+            .bind(table)
+            .fetch_all(&mut *tx).await?;
+            for index_sql in indexes {
+                sqlx::query(&index_sql).execute(&mut *tx).await?;
+            }
+        }
+
+        let column_query = "SELECT name FROM pragma_table_info(?, ?)";
+        let target: HashSet<String> = sqlx::query_scalar(column_query).bind(table).bind("main")
+            .fetch_all(&mut *tx).await?
+            .into_iter()
+            .collect();
+        let columns: Vec<String> = sqlx::query_scalar::<_, String>(column_query).bind(table).bind("restore_src")
+            .fetch_all(&mut *tx).await?
+            .into_iter()
+            .filter(|c| target.contains(c))
+            .map(|c| quote_ident(&c))
+            .collect();
+        if columns.is_empty() {
+            continue;
+        }
+
+        let columns = columns.join(", ");
+        let result = sqlx::query(&format!(
+            "INSERT INTO main.{table} ({columns}) SELECT {columns} FROM restore_src.{table}",
+            table = quote_ident(table),
+            columns = columns,
+        ))
+        .execute(&mut *tx).await?;
+        restored += result.rows_affected() as i64;
+    }
+
+    rebuild_search_index(&mut tx, source.iter().any(|(t, _)| t == "search_index")).await?;
+
+    tx.commit().await?;
+    Ok(restored)
+}
+
+/// Refills `search_index` from the restored records, keeps the backup's entries for anything
+/// else that was indexed, and rebuilds the full-text index from the result.
+async fn rebuild_search_index(tx: &mut Transaction<'_, Sqlite>, backed_up: bool) -> Result<()> {
+    let fts: Option<String> = sqlx::query_scalar("SELECT name FROM main.sqlite_master WHERE name = 'search_index_fts'")
+        .fetch_optional(&mut **tx).await?;
+    if fts.is_none() {
+        return Ok(());
+    }
+
+    // The triggers indexed rows as they were copied, possibly before the rows they join to.
+    sqlx::query("DELETE FROM main.search_index").execute(&mut **tx).await?;
+    sqlx::query(
+        "INSERT INTO main.search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+         SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+         FROM main.search_sources"
+    )
+    .execute(&mut **tx).await?;
+    if backed_up {
+        sqlx::query(
+            "INSERT INTO main.search_index (id, entity_type, entity_id, title, content, keywords, tenant_id, created_at, updated_at)
+             SELECT id, entity_type, entity_id, title, content, keywords, tenant_id, created_at, updated_at
+             FROM restore_src.search_index WHERE true
+             ON CONFLICT DO NOTHING"
+        )
+        .execute(&mut **tx).await?;
+    }
+    sqlx::query("INSERT INTO main.search_index_fts (search_index_fts) VALUES ('rebuild')").execute(&mut **tx).await?;
+    Ok(())
+}
+
+/// The search index is filled by triggers on the tables it covers, so a restore rebuilds it
+/// rather than copying it.
+fn is_search_index(table: &str) -> bool {
+    table == "search_index" || table.starts_with("search_index_fts")
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
@@ -0,0 +1,270 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use erp_core::{Error, Result};
+
+const DELTA_MAGIC: &[u8; 8] = b"ERPDLTA1";
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const SQLITE_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex(Sha256::digest(bytes).as_slice())
+}
+
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut tally = Tally::new(std::io::sink());
+    std::io::copy(&mut open(path)?, &mut tally).map_err(io_error)?;
+    Ok(tally.finish().1)
+}
+
+/// Counts and hashes everything written through it.
+struct Tally<W> {
+    inner: W,
+    len: u64,
+    hasher: Sha256,
+}
+
+impl<W: Write> Tally<W> {
+    fn new(inner: W) -> Self {
+        Self { inner, len: 0, hasher: Sha256::new() }
+    }
+
+    fn finish(self) -> (u64, String, W) {
+        (self.len, hex(self.hasher.finalize().as_slice()), self.inner)
+    }
+}
+
+impl<W: Write> Write for Tally<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.len += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Sizes and checksum of a written artifact.
+pub struct Artifact {
+    pub contents_len: u64,
+    pub written_len: u64,
+    pub checksum: String,
+}
+
+/// Writes a backup artifact from whatever `contents` writes, gzip-compressing it when requested.
+/// The checksum is the SHA-256 of the file as written.
+pub fn write_artifact(path: &Path, compress: bool, contents: impl FnOnce(&mut dyn Write) -> Result<()>) -> Result<Artifact> {
+    let file = File::create(path)
+        .map_err(|e| Error::internal(format!("Failed to write backup file {}: {}", path.display(), e)))?;
+    let mut on_disk = Tally::new(BufWriter::new(file));
+    let contents_len = if compress {
+        let mut raw = Tally::new(GzEncoder::new(&mut on_disk, Compression::default()));
+        contents(&mut raw)?;
+        let (len, _, encoder) = raw.finish();
+        encoder.finish().map_err(io_error)?;
+        len
+    } else {
+        let mut raw = Tally::new(&mut on_disk);
+        contents(&mut raw)?;
+        raw.finish().0
+    };
+    let (written_len, checksum, mut file) = on_disk.finish();
+    file.flush().map_err(io_error)?;
+    Ok(Artifact { contents_len, written_len, checksum })
+}
+
+/// Opens a backup artifact for reading, checking its SHA-256 against the recorded checksum
+/// first. Compressed artifacts are decompressed as they are read.
+pub fn open_artifact(path: &Path, expected_checksum: Option<&str>) -> Result<Box<dyn Read>> {
+    if let Some(expected) = expected_checksum {
+        if sha256_file(path)? != expected {
+            return Err(Error::business_rule(format!("Checksum mismatch for backup file {}", path.display())));
+        }
+    }
+    let mut reader = BufReader::new(open(path)?);
+    if reader.fill_buf().map_err(io_error)?.starts_with(&GZIP_MAGIC) {
+        Ok(Box::new(GzDecoder::new(reader)))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+/// Copies an artifact's contents into `dest`.
+pub fn extract_artifact(path: &Path, expected_checksum: Option<&str>, dest: &Path) -> Result<()> {
+    let mut reader = open_artifact(path, expected_checksum)?;
+    let mut out = BufWriter::new(File::create(dest).map_err(io_error)?);
+    std::io::copy(&mut reader, &mut out).map_err(io_error)?;
+    out.flush().map_err(io_error)
+}
+
+pub fn page_size(header: &[u8]) -> Result<usize> {
+    if header.len() < 100 || &header[..16] != SQLITE_MAGIC {
+        return Err(Error::validation("Not a SQLite database image"));
+    }
+    // The header stores 65536 as 1 because it does not fit in two bytes.
+    match u16::from_be_bytes([header[16], header[17]]) {
+        1 => Ok(65536),
+        size => Ok(size as usize),
+    }
+}
+
+/// Writes the pages of the database at `target` that differ from the one at `base`.
+/// Both files are read a page at a time, twice: once to count the changes for the header,
+/// once to write them.
+pub fn write_delta(base: &Path, target: &Path, out: &mut dyn Write) -> Result<()> {
+    let mut header = [0u8; 100];
+    open(target)?.read_exact(&mut header).map_err(|_| Error::validation("Not a SQLite database image"))?;
+    let page_size = page_size(&header)?;
+    let target_len = std::fs::metadata(target).map_err(io_error)?.len();
+
+    let changed = changed_pages(base, target, page_size, |_, _| Ok(()))?;
+
+    out.write_all(DELTA_MAGIC).map_err(io_error)?;
+    out.write_all(&(page_size as u32).to_be_bytes()).map_err(io_error)?;
+    out.write_all(&target_len.to_be_bytes()).map_err(io_error)?;
+    out.write_all(&changed.to_be_bytes()).map_err(io_error)?;
+    let written = changed_pages(base, target, page_size, |index, page| {
+        out.write_all(&index.to_be_bytes()).map_err(io_error)?;
+        out.write_all(page).map_err(io_error)
+    })?;
+    if written != changed {
+        return Err(Error::internal("Database snapshot changed while its delta was written"));
+    }
+    Ok(())
+}
+
+fn changed_pages(base: &Path, target: &Path, page_size: usize, mut visit: impl FnMut(u32, &[u8]) -> Result<()>) -> Result<u32> {
+    let mut base = BufReader::new(open(base)?);
+    let mut target = BufReader::new(open(target)?);
+    let (mut base_page, mut target_page) = (vec![0u8; page_size], vec![0u8; page_size]);
+    let mut changed = 0;
+    for index in 0u32.. {
+        let len = read_full(&mut target, &mut target_page)?;
+        if len == 0 {
+            break;
+        }
+        let base_len = read_full(&mut base, &mut base_page)?;
+        if base_len < len || base_page[..len] != target_page[..len] {
+            visit(index, &target_page[..len])?;
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+/// Applies a delta written by [`write_delta`] to the database file at `image` in place.
+pub fn apply_delta(image: &Path, delta: &mut dyn Read) -> Result<()> {
+    let invalid = || Error::validation("Corrupt incremental backup");
+    let mut header = [0u8; 24];
+    delta.read_exact(&mut header).map_err(|_| invalid())?;
+    if &header[..8] != DELTA_MAGIC {
+        return Err(invalid());
+    }
+    let page_size = u32::from_be_bytes(header[8..12].try_into().unwrap()) as u64;
+    let image_len = u64::from_be_bytes(header[12..20].try_into().unwrap());
+    let count = u32::from_be_bytes(header[20..24].try_into().unwrap());
+
+    let mut file = OpenOptions::new().write(true).open(image).map_err(io_error)?;
+    file.set_len(image_len).map_err(io_error)?;
+    let mut page = vec![0u8; page_size as usize];
+    for _ in 0..count {
+        let mut index = [0u8; 4];
+        delta.read_exact(&mut index).map_err(|_| invalid())?;
+        let start = u32::from_be_bytes(index) as u64 * page_size;
+        let len = page_size.min(image_len.saturating_sub(start)) as usize;
+        if len == 0 {
+            return Err(invalid());
+        }
+        delta.read_exact(&mut page[..len]).map_err(|_| invalid())?;
+        file.seek(SeekFrom::Start(start)).map_err(io_error)?;
+        file.write_all(&page[..len]).map_err(io_error)?;
+    }
+    file.sync_all().map_err(io_error)
+}
+
+/// Reads until `buf` is full or the reader is exhausted, returning the bytes read.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]).map_err(io_error)? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+fn open(path: &Path) -> Result<File> {
+    File::open(path).map_err(|e| Error::NotFound(format!("Backup file {} is not readable: {}", path.display(), e)))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn io_error(e: std::io::Error) -> Error {
+    Error::internal(format!("Backup I/O failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn image(pages: &[u8]) -> Vec<u8> {
+        let mut image = vec![0u8; 512 * pages.len()];
+        for (i, fill) in pages.iter().enumerate() {
+            image[i * 512..(i + 1) * 512].fill(*fill);
+        }
+        image[..16].copy_from_slice(SQLITE_MAGIC);
+        image[16..18].copy_from_slice(&512u16.to_be_bytes());
+        image
+    }
+
+    fn temp_file(contents: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("erp-snapshot-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn round_trip(base: &[u8], target: &[u8]) -> (Vec<u8>, u32) {
+        let (base_path, target_path) = (temp_file(base), temp_file(target));
+        let mut delta = Vec::new();
+        write_delta(&base_path, &target_path, &mut delta).unwrap();
+        apply_delta(&base_path, &mut delta.as_slice()).unwrap();
+        let rebuilt = std::fs::read(&base_path).unwrap();
+        std::fs::remove_file(base_path).unwrap();
+        std::fs::remove_file(target_path).unwrap();
+        (rebuilt, u32::from_be_bytes(delta[20..24].try_into().unwrap()))
+    }
+
+    #[test]
+    fn test_delta_round_trip() {
+        let (base, target) = (image(&[1, 2, 3]), image(&[1, 9, 3, 4]));
+        assert_eq!(round_trip(&base, &target), (target.clone(), 2));
+
+        let shrunk = image(&[1, 2]);
+        assert_eq!(round_trip(&target, &shrunk).0, shrunk);
+    }
+
+    #[test]
+    fn test_artifact_checksum_is_enforced() {
+        let path = std::env::temp_dir().join(format!("erp-artifact-{}.gz", uuid::Uuid::new_v4()));
+        let artifact = write_artifact(&path, true, |out| out.write_all(b"payload").map_err(io_error)).unwrap();
+        assert_eq!(artifact.contents_len, 7);
+        assert_eq!(artifact.checksum, sha256_hex(&std::fs::read(&path).unwrap()));
+
+        let mut contents = Vec::new();
+        open_artifact(&path, Some(&artifact.checksum)).unwrap().read_to_end(&mut contents).unwrap();
+        assert_eq!(contents, b"payload");
+        assert!(open_artifact(&path, Some("0000")).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
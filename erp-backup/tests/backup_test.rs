@@ -0,0 +1,139 @@
+use erp_backup::*;
+use erp_core::BaseEntity;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+
+async fn setup() -> (SqlitePool, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("erp-backup-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let options = SqliteConnectOptions::new()
+        .filename(dir.join("erp.db"))
+        .create_if_missing(true)
+        .foreign_keys(true);
+    let pool = SqlitePoolOptions::new().max_connections(4).connect_with(options).await.unwrap();
+
+    let migrations = [
+        include_str!("../../migrations/20240101000026_enterprise_infrastructure_features.sql"),
+        include_str!("../../migrations/20260311000000_backup_chains.sql"),
+        "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
+    ];
+    for migration in migrations {
+        for statement in migration.split(';') {
+            let statement = statement.trim();
+            if !statement.is_empty() {
+                let _ = sqlx::query(statement).execute(&pool).await;
+            }
+        }
+    }
+    (pool, dir)
+}
+
+fn schedule(dir: &std::path::Path, backup_type: BackupType, max_backups: i32) -> BackupSchedule {
+    BackupSchedule {
+        base: BaseEntity::new(),
+        name: "Nightly".to_string(),
+        backup_type,
+        schedule_cron: "0 2 * * *".to_string(),
+        retention_days: 30,
+        max_backups,
+        compression: true,
+        encryption_enabled: false,
+        encryption_key_id: None,
+        storage_type: BackupStorageType::Local,
+        storage_path: dir.join("backups").to_string_lossy().to_string(),
+        include_attachments: false,
+        is_active: true,
+        last_run: None,
+        next_run: None,
+    }
+}
+
+async fn item_names(pool: &SqlitePool) -> Vec<String> {
+    sqlx::query_scalar("SELECT name FROM items ORDER BY id").fetch_all(pool).await.unwrap()
+}
+
+async fn insert_item(pool: &SqlitePool, name: &str) {
+    sqlx::query("INSERT INTO items (name) VALUES (?)").bind(name).execute(pool).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_incremental_backup_verify_and_restore() {
+    let (pool, dir) = setup().await;
+    let service = BackupService::new(pool.clone()).with_backup_dir(dir.join("backups"));
+    let schedule = service.create_schedule(schedule(&dir, BackupType::Incremental, 10)).await.unwrap();
+
+    insert_item(&pool, "alpha").await;
+    let full = service.execute_backup(Some(schedule.base.id)).await.unwrap();
+    assert_eq!(full.status, BackupStatus::Completed, "{:?}", full.error_message);
+    assert_eq!(full.backup_type, BackupType::Full);
+    assert!(full.compressed_size_bytes.is_some());
+    assert_eq!(full.checksum.as_ref().unwrap().len(), 64);
+
+    insert_item(&pool, "beta").await;
+    let incremental = service.execute_backup(Some(schedule.base.id)).await.unwrap();
+    assert_eq!(incremental.backup_type, BackupType::Incremental);
+    assert_eq!(incremental.parent_backup_id, Some(full.base.id));
+    assert!(incremental.tables_included.as_deref().unwrap().split(',').any(|t| t == "items"));
+
+    let verification = service.verify_backup(incremental.base.id).await.unwrap();
+    assert_eq!(verification.status, VerificationStatus::Verified, "{:?}", verification.error_details);
+
+    insert_item(&pool, "gamma").await;
+    sqlx::query("DELETE FROM items WHERE name = 'alpha'").execute(&pool).await.unwrap();
+
+    let restore = service.restore_backup(incremental.base.id, None).await.unwrap();
+    assert_eq!(restore.status, RestoreStatus::Completed, "{:?}", restore.error_message);
+    assert_eq!(restore.records_restored, Some(2));
+    assert!(restore.backup_before_restore.is_some());
+    assert_eq!(item_names(&pool).await, vec!["alpha", "beta"]);
+
+    let restore = service.restore_latest_backup_before(full.completed_at.unwrap(), None).await.unwrap();
+    assert_eq!(restore.restore_type, RestoreType::PointInTime);
+    assert_eq!(restore.backup_id, full.base.id);
+    assert_eq!(item_names(&pool).await, vec!["alpha"]);
+
+    // The incremental backup depends on the full one, so corrupting it breaks both.
+    std::fs::write(&full.file_path, b"corrupted").unwrap();
+    let verification = service.verify_backup(incremental.base.id).await.unwrap();
+    assert_eq!(verification.status, VerificationStatus::Failed);
+    assert!(!verification.checksum_valid);
+    let restore = service.restore_backup(incremental.base.id, None).await.unwrap();
+    assert_eq!(restore.status, RestoreStatus::Failed);
+    assert_eq!(item_names(&pool).await, vec!["alpha"]);
+
+    assert!(service.delete_backup(full.base.id).await.is_err());
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn test_retention_keeps_backups_needed_by_chain() {
+    let (pool, dir) = setup().await;
+    let service = BackupService::new(pool.clone()).with_backup_dir(dir.join("backups"));
+
+    let full_schedule = service.create_schedule(schedule(&dir, BackupType::Full, 2)).await.unwrap();
+    let mut taken = Vec::new();
+    for name in ["one", "two", "three"] {
+        insert_item(&pool, name).await;
+        taken.push(service.execute_backup(Some(full_schedule.base.id)).await.unwrap());
+    }
+    let remaining: Vec<_> = service.list_backups(100).await.unwrap()
+        .into_iter()
+        .filter(|b| b.schedule_id == Some(full_schedule.base.id))
+        .collect();
+    assert_eq!(remaining.len(), 2);
+    assert!(!std::path::Path::new(&taken[0].file_path).exists());
+
+    let incremental_schedule = service.create_schedule(schedule(&dir, BackupType::Incremental, 1)).await.unwrap();
+    for name in ["four", "five", "six"] {
+        insert_item(&pool, name).await;
+        service.execute_backup(Some(incremental_schedule.base.id)).await.unwrap();
+    }
+    let chain: Vec<_> = service.list_backups(100).await.unwrap()
+        .into_iter()
+        .filter(|b| b.schedule_id == Some(incremental_schedule.base.id))
+        .collect();
+    assert_eq!(chain.len(), 3);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,276 @@
+use chrono::NaiveDate;
+use erp_core::{Error, Result};
+
+use crate::models::{StatementFormat, TransactionType};
+use crate::statement::{parse_yymmdd, ParsedStatement, StatementEntry};
+
+/// A logical BAI2 record: one physical record plus any `88` continuations.
+struct Record {
+    line: usize,
+    code: String,
+    body: String,
+    physical: i64,
+}
+
+/// Reads a BAI2 file into one statement per `03` account, checking the `49`, `98` and `99`
+/// control totals and record counts on the way.
+pub fn parse(content: &str) -> Result<Vec<ParsedStatement>> {
+    let records = records(content)?;
+    let mut statements = Vec::new();
+    let mut iter = records.iter().peekable();
+
+    let header = iter.next().filter(|r| r.code == "01").ok_or_else(|| Error::validation("BAI2 file must start with an 01 record"))?;
+    let mut file_records = header.physical;
+    let mut file_total = 0i64;
+    let mut groups = 0i64;
+
+    loop {
+        let record = iter.next().ok_or_else(|| Error::validation("BAI2 file is missing its 99 trailer"))?;
+        match record.code.as_str() {
+            "02" => {
+                let header = fields(record);
+                let currency = field(&header, 5).unwrap_or("USD").to_string();
+                let as_of = parse_yymmdd(field(&header, 3).unwrap_or_default())
+                    .map_err(|e| at(record, e))?;
+                let mut group_records = record.physical;
+                let mut group_total = 0i64;
+                let mut accounts = 0i64;
+                loop {
+                    let record = iter.next().ok_or_else(|| Error::validation("BAI2 group is missing its 98 trailer"))?;
+                    match record.code.as_str() {
+                        "03" => {
+                            let (statement, total, physical) = account(record, &mut iter, as_of, &currency)?;
+                            statements.push(statement);
+                            group_total += total;
+                            group_records += physical;
+                            accounts += 1;
+                        }
+                        "98" => {
+                            group_records += record.physical;
+                            let fields = fields(record);
+                            check(record, "group control total", field(&fields, 0), group_total)?;
+                            check(record, "number of accounts", field(&fields, 1), accounts)?;
+                            check(record, "number of records", field(&fields, 2), group_records)?;
+                            file_total += group_total;
+                            file_records += group_records;
+                            groups += 1;
+                            break;
+                        }
+                        other => return Err(at(record, Error::validation(format!("Unexpected {} record in a group", other)))),
+                    }
+                }
+            }
+            "99" => {
+                file_records += record.physical;
+                let fields = fields(record);
+                check(record, "file control total", field(&fields, 0), file_total)?;
+                check(record, "number of groups", field(&fields, 1), groups)?;
+                check(record, "number of records", field(&fields, 2), file_records)?;
+                break;
+            }
+            other => return Err(at(record, Error::validation(format!("Unexpected {} record outside a group", other)))),
+        }
+    }
+    if let Some(extra) = iter.next() {
+        return Err(at(extra, Error::validation("Records follow the 99 trailer")));
+    }
+    Ok(statements)
+}
+
+/// Reads an `03` record and its `16`s up to the `49`, returning the statement, the account
+/// control total and the number of physical records.
+fn account<'a>(
+    header: &Record,
+    iter: &mut std::iter::Peekable<impl Iterator<Item = &'a Record>>,
+    as_of: NaiveDate,
+    group_currency: &str,
+) -> Result<(ParsedStatement, i64, i64)> {
+    let summary = fields(header);
+    let account_number = field(&summary, 0).unwrap_or_default().to_string();
+    if account_number.is_empty() {
+        return Err(at(header, Error::validation("03 record has no account number")));
+    }
+    let currency = field(&summary, 1).unwrap_or(group_currency).to_string();
+
+    let mut total = 0i64;
+    let mut balances = std::collections::HashMap::new();
+    let mut tokens = summary.iter().skip(2).copied();
+    while let Some(code) = tokens.next() {
+        if code.is_empty() {
+            break;
+        }
+        let amount = tokens.next().unwrap_or_default();
+        let _item_count = tokens.next();
+        let funds = tokens.next().unwrap_or_default();
+        funds_detail(funds, &mut tokens).map_err(|e| at(header, e))?;
+        if !amount.is_empty() {
+            let amount = parse_amount(amount).map_err(|e| at(header, e))?;
+            total += amount;
+            balances.insert(code.to_string(), amount);
+        }
+    }
+    let opening = balances.get("010").or_else(|| balances.get("040")).copied().ok_or_else(|| {
+        at(header, Error::validation(format!("Account {} has no opening ledger (010) balance", account_number)))
+    })?;
+
+    let mut physical = header.physical;
+    let mut entries = Vec::new();
+    loop {
+        let record = iter.next().ok_or_else(|| Error::validation("BAI2 account is missing its 49 trailer"))?;
+        physical += record.physical;
+        match record.code.as_str() {
+            "16" => {
+                let entry = detail(record, as_of).map_err(|e| at(record, e))?;
+                total += entry.amount.abs();
+                entries.push(entry);
+            }
+            "49" => {
+                let fields = fields(record);
+                check(record, "account control total", field(&fields, 0), total)?;
+                check(record, "number of records", field(&fields, 1), physical)?;
+                break;
+            }
+            other => return Err(at(record, Error::validation(format!("Unexpected {} record in account {}", other, account_number)))),
+        }
+    }
+
+    let sum: i64 = entries.iter().map(|e| e.amount).sum();
+    let closing = balances.get("015").or_else(|| balances.get("045")).copied().unwrap_or(opening + sum);
+    let statement = ParsedStatement {
+        format: StatementFormat::BAI2,
+        account_number,
+        currency,
+        statement_date: as_of,
+        opening_balance: opening,
+        closing_balance: closing,
+        entries,
+    };
+    Ok((statement, total, physical))
+}
+
+fn detail(record: &Record, as_of: NaiveDate) -> Result<StatementEntry> {
+    let mut tokens = record.body.split(',');
+    let code = tokens.next().unwrap_or_default();
+    let amount = parse_amount(tokens.next().unwrap_or_default())?;
+    let funds = tokens.next().unwrap_or_default();
+    let value_date = funds_detail(funds, &mut tokens)?;
+    let bank_reference = tokens.next().filter(|s| !s.is_empty()).map(str::to_string);
+    let customer_reference = tokens.next().filter(|s| !s.is_empty()).map(str::to_string);
+    let text = tokens.collect::<Vec<_>>().join(",");
+
+    let (credit, transaction_type) = classify(code)?;
+    Ok(StatementEntry {
+        booking_date: as_of,
+        value_date,
+        amount: if credit { amount } else { -amount },
+        transaction_type,
+        type_code: code.to_string(),
+        bank_reference,
+        customer_reference,
+        description: text.trim().to_string(),
+    })
+}
+
+/// Consumes the availability fields that follow a funds type, returning the value date of a
+/// `V` funds type.
+fn funds_detail<'a>(funds: &str, tokens: &mut impl Iterator<Item = &'a str>) -> Result<Option<NaiveDate>> {
+    match funds {
+        "" | "Z" | "0" | "1" | "2" => Ok(None),
+        "S" => {
+            tokens.by_ref().take(3).for_each(drop);
+            Ok(None)
+        }
+        "V" => {
+            let date = tokens.next().unwrap_or_default();
+            let _time = tokens.next();
+            Ok(Some(parse_yymmdd(date)?))
+        }
+        "D" => {
+            let count: usize = tokens
+                .next()
+                .unwrap_or_default()
+                .parse()
+                .map_err(|_| Error::validation("Funds type D needs a distribution count"))?;
+            tokens.by_ref().take(count * 2).for_each(drop);
+            Ok(None)
+        }
+        other => Err(Error::validation(format!("Unknown funds type '{}'", other))),
+    }
+}
+
+/// Credit or debit, and the kind of entry, from a BAI2 detail type code.
+fn classify(code: &str) -> Result<(bool, TransactionType)> {
+    let number: u16 = code.parse().map_err(|_| Error::validation(format!("Invalid type code '{}'", code)))?;
+    let credit = match number {
+        100..=399 => true,
+        400..=699 => false,
+        _ => return Err(Error::validation(format!("Type code {} is not a transaction detail code", code))),
+    };
+    let kind = match number {
+        142 | 145 | 165..=169 | 451 | 455 | 466 | 469 => TransactionType::ACH,
+        195 | 196 | 206 | 208 | 495 | 496 | 506 | 508 => TransactionType::WireTransfer,
+        474 | 475 => TransactionType::Check,
+        354 => TransactionType::Interest,
+        555 | 556 => TransactionType::Return,
+        698 => TransactionType::Fee,
+        _ if credit => TransactionType::Credit,
+        _ => TransactionType::Debit,
+    };
+    Ok((credit, kind))
+}
+
+fn records(content: &str) -> Result<Vec<Record>> {
+    let mut records: Vec<Record> = Vec::new();
+    let mut continues_field = false;
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (code, rest) = line.split_once(',').unwrap_or((line, ""));
+        let ends_field = rest.ends_with('/');
+        let rest = rest.strip_suffix('/').unwrap_or(rest);
+        if code == "88" {
+            let previous = records
+                .last_mut()
+                .ok_or_else(|| Error::validation(format!("Line {}: 88 continuation with nothing to continue", index + 1)))?;
+            // A 16 record's free text may run on into the continuation.
+            let separator = if previous.code == "16" && !continues_field { " " } else { "," };
+            previous.body.push_str(separator);
+            previous.body.push_str(rest);
+            previous.physical += 1;
+        } else {
+            records.push(Record { line: index + 1, code: code.to_string(), body: rest.to_string(), physical: 1 });
+        }
+        continues_field = ends_field;
+    }
+    Ok(records)
+}
+
+fn fields(record: &Record) -> Vec<&str> {
+    record.body.split(',').collect()
+}
+
+fn field<'a>(fields: &[&'a str], index: usize) -> Option<&'a str> {
+    fields.get(index).copied().filter(|s| !s.is_empty())
+}
+
+fn parse_amount(value: &str) -> Result<i64> {
+    let digits = value.strip_prefix('+').unwrap_or(value);
+    digits.parse().map_err(|_| Error::validation(format!("Invalid amount '{}'", value)))
+}
+
+fn check(record: &Record, what: &str, declared: Option<&str>, actual: i64) -> Result<()> {
+    let declared = declared.unwrap_or_default();
+    if parse_amount(declared).ok() != Some(actual) {
+        return Err(at(record, Error::validation(format!("{} is {} but the file adds up to {}", what, declared, actual))));
+    }
+    Ok(())
+}
+
+fn at(record: &Record, error: Error) -> Error {
+    match error {
+        Error::Validation(message) => Error::validation(format!("Line {} ({} record): {}", record.line, record.code, message)),
+        other => other,
+    }
+}
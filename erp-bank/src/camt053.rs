@@ -0,0 +1,253 @@
+use chrono::NaiveDate;
+use erp_core::{Error, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::models::{StatementFormat, TransactionType};
+use crate::statement::{parse_decimal, ParsedStatement, StatementEntry};
+
+/// Reads an ISO 20022 camt.053 bank-to-customer statement. Only booked entries are imported.
+pub fn parse(content: &str) -> Result<Vec<ParsedStatement>> {
+    let document = Element::read(content)?;
+    let statement_list = match document.name.as_str() {
+        "Document" => document.child("BkToCstmrStmt"),
+        "BkToCstmrStmt" => Some(&document),
+        _ => None,
+    }
+    .ok_or_else(|| Error::validation("Not a camt.053 document: BkToCstmrStmt is missing"))?;
+
+    let statements = statement_list.children("Stmt").map(statement).collect::<Result<Vec<_>>>()?;
+    if statements.is_empty() {
+        return Err(Error::validation("camt.053 document has no Stmt"));
+    }
+    Ok(statements)
+}
+
+fn statement(stmt: &Element) -> Result<ParsedStatement> {
+    let id = stmt.text(&["Id"]).unwrap_or_default();
+    let context = |e: Error| match e {
+        Error::Validation(message) => Error::validation(format!("camt.053 statement {}: {}", id, message)),
+        other => other,
+    };
+    let account_number = stmt
+        .text(&["Acct", "Id", "IBAN"])
+        .or_else(|| stmt.text(&["Acct", "Id", "Othr", "Id"]))
+        .ok_or_else(|| context(Error::validation("account Id is missing")))?
+        .to_string();
+
+    let mut opening = None;
+    let mut previous_closing = None;
+    let mut closing = None;
+    for bal in stmt.children("Bal") {
+        let amount = signed_amount(bal).map_err(context)?;
+        let date = date(bal.child("Dt")).map_err(context)?;
+        match bal.text(&["Tp", "CdOrPrtry", "Cd"]) {
+            Some("OPBD") => opening = Some(amount),
+            Some("PRCD") => previous_closing = Some(amount),
+            Some("CLBD") => closing = Some((amount, date)),
+            _ => {}
+        }
+    }
+    let opening = opening.or(previous_closing).ok_or_else(|| context(Error::validation("no OPBD or PRCD balance")))?;
+    let (closing, closing_date) = closing.ok_or_else(|| context(Error::validation("no CLBD balance")))?;
+    let currency = stmt
+        .text(&["Acct", "Ccy"])
+        .or_else(|| stmt.children("Bal").find_map(|b| b.child("Amt")?.attribute("Ccy")))
+        .unwrap_or("EUR")
+        .to_string();
+    let statement_date = closing_date
+        .or_else(|| stmt.text(&["CreDtTm"]).and_then(|d| NaiveDate::parse_from_str(d.get(..10)?, "%Y-%m-%d").ok()))
+        .ok_or_else(|| context(Error::validation("statement date is missing")))?;
+
+    let mut entries = Vec::new();
+    for ntry in stmt.children("Ntry") {
+        let status = ntry.text(&["Sts", "Cd"]).or_else(|| ntry.text(&["Sts"])).unwrap_or("BOOK");
+        if status != "BOOK" {
+            continue;
+        }
+        let entry = entry(ntry, statement_date, &currency).map_err(context)?;
+        entries.push(entry);
+    }
+
+    Ok(ParsedStatement {
+        format: StatementFormat::CAMT053,
+        account_number,
+        currency,
+        statement_date,
+        opening_balance: opening,
+        closing_balance: closing,
+        entries,
+    })
+}
+
+fn entry(ntry: &Element, statement_date: NaiveDate, currency: &str) -> Result<StatementEntry> {
+    let entry_currency = ntry.child("Amt").and_then(|a| a.attribute("Ccy")).unwrap_or(currency);
+    if entry_currency != currency {
+        return Err(Error::validation(format!("entry in {} on a {} statement", entry_currency, currency)));
+    }
+    let amount = signed_amount(ntry)?;
+    let reversal = ntry.text(&["RvslInd"]) == Some("true");
+    let details = ntry.find(&["NtryDtls", "TxDtls"]);
+
+    let domain = ntry.text(&["BkTxCd", "Domn", "Cd"]);
+    let family = ntry.text(&["BkTxCd", "Domn", "Fmly", "Cd"]);
+    let sub_family = ntry.text(&["BkTxCd", "Domn", "Fmly", "SubFmlyCd"]);
+    let type_code = match (domain, family, sub_family) {
+        (Some(d), Some(f), Some(s)) => format!("{}/{}/{}", d, f, s),
+        _ => ntry.text(&["BkTxCd", "Prtry", "Cd"]).unwrap_or_default().to_string(),
+    };
+    let transaction_type = if reversal {
+        TransactionType::Reversal
+    } else {
+        match (family.unwrap_or_default(), sub_family.unwrap_or_default()) {
+            (_, "CHRG") | (_, "COMM") => TransactionType::Fee,
+            (_, "INTR") => TransactionType::Interest,
+            (_, "RRTN") | (_, "ARET") => TransactionType::Return,
+            ("ICDT", _) | ("RCDT", _) => TransactionType::WireTransfer,
+            ("IDDT", _) | ("RDDT", _) => TransactionType::ACH,
+            ("ICHQ", _) | ("RCHQ", _) => TransactionType::Check,
+            ("CCRD", _) => TransactionType::CardPayment,
+            _ if amount > 0 => TransactionType::Credit,
+            _ => TransactionType::Debit,
+        }
+    };
+
+    let counterparty = details.and_then(|tx| {
+        let role = if amount > 0 { "Dbtr" } else { "Cdtr" };
+        tx.text(&["RltdPties", role, "Nm"]).or_else(|| tx.text(&["RltdPties", role, "Pty", "Nm"]))
+    });
+    let remittance = details
+        .and_then(|tx| tx.child("RmtInf"))
+        .map(|rmt| rmt.children("Ustrd").map(|u| u.text.trim()).collect::<Vec<_>>().join(" "));
+    let description = [counterparty.map(str::to_string), remittance, ntry.text(&["AddtlNtryInf"]).map(str::to_string)]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" - ");
+
+    Ok(StatementEntry {
+        booking_date: date(ntry.child("BookgDt"))?.unwrap_or(statement_date),
+        value_date: date(ntry.child("ValDt"))?,
+        amount,
+        transaction_type,
+        type_code,
+        bank_reference: ntry.text(&["AcctSvcrRef"]).map(str::to_string),
+        customer_reference: details
+            .and_then(|tx| tx.text(&["Refs", "EndToEndId"]))
+            .filter(|r| *r != "NOTPROVIDED")
+            .map(str::to_string),
+        description,
+    })
+}
+
+/// `Amt` with its `CdtDbtInd`, as signed cents.
+fn signed_amount(element: &Element) -> Result<i64> {
+    let amount = element.text(&["Amt"]).ok_or_else(|| Error::validation(format!("{} has no Amt", element.name)))?;
+    let amount = parse_decimal(amount, '.')?;
+    match element.text(&["CdtDbtInd"]) {
+        Some("CRDT") => Ok(amount),
+        Some("DBIT") => Ok(-amount),
+        other => Err(Error::validation(format!("Invalid CdtDbtInd '{}'", other.unwrap_or_default()))),
+    }
+}
+
+/// A `Dt` or `DtTm` choice.
+fn date(element: Option<&Element>) -> Result<Option<NaiveDate>> {
+    let Some(element) = element else {
+        return Ok(None);
+    };
+    let Some(value) = element.text(&["Dt"]).or_else(|| element.text(&["DtTm"])) else {
+        return Ok(None);
+    };
+    value
+        .get(..10)
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .map(Some)
+        .ok_or_else(|| Error::validation(format!("Invalid date '{}'", value)))
+}
+
+/// Just enough of an XML tree for reading statements: local names, attributes and text.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn read(content: &str) -> Result<Self> {
+        let invalid = |e: quick_xml::Error| Error::validation(format!("Invalid XML: {}", e));
+        let mut reader = Reader::from_str(content);
+        reader.trim_text(true);
+        let mut stack: Vec<Element> = Vec::new();
+        loop {
+            match reader.read_event().map_err(invalid)? {
+                Event::Start(start) => stack.push(Self::open(&start)?),
+                Event::Empty(start) => {
+                    let element = Self::open(&start)?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some(current) = stack.last_mut() {
+                        current.text.push_str(&text.unescape().map_err(invalid)?);
+                    }
+                }
+                Event::CData(data) => {
+                    if let Some(current) = stack.last_mut() {
+                        current.text.push_str(&String::from_utf8_lossy(&data));
+                    }
+                }
+                Event::End(_) => {
+                    let element = stack.pop().ok_or_else(|| Error::validation("Invalid XML: unbalanced end tag"))?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                Event::Eof => return Err(Error::validation("Invalid XML: document ends before its root element closes")),
+                _ => {}
+            }
+        }
+    }
+
+    fn open(start: &BytesStart) -> Result<Self> {
+        let mut attributes = Vec::new();
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(|e| Error::validation(format!("Invalid XML attribute: {}", e)))?;
+            let value = attribute
+                .unescape_value()
+                .map_err(|e| Error::validation(format!("Invalid XML attribute: {}", e)))?;
+            attributes.push((String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned(), value.into_owned()));
+        }
+        Ok(Self {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    fn find(&self, path: &[&str]) -> Option<&Element> {
+        path.iter().try_fold(self, |element, name| element.child(name))
+    }
+
+    fn text(&self, path: &[&str]) -> Option<&str> {
+        self.find(path).map(|e| e.text.trim()).filter(|t| !t.is_empty())
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
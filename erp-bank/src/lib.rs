@@ -1,7 +1,14 @@
+pub mod bai2;
+pub mod camt053;
 pub mod models;
+pub mod mt940;
+pub mod nacha;
+pub mod pain001;
 pub mod repository;
 pub mod service;
+pub mod statement;
 
 pub use models::*;
 pub use repository::*;
 pub use service::*;
+pub use statement::{ParsedStatement, StatementEntry};
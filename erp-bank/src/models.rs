@@ -13,7 +13,7 @@ pub enum BankConnectionStatus {
     Expired,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum StatementFormat {
     BAI2,
@@ -24,7 +24,7 @@ pub enum StatementFormat {
     Custom,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum TransactionType {
     Credit,
@@ -106,7 +106,7 @@ pub struct BankAccount {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum BankAccountType {
     Checking,
@@ -296,6 +296,75 @@ pub struct PaymentFileGeneration {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaymentFileFormat {
+    /// NACHA ACH credits (CCD with remittance addenda).
+    NACHA,
+    /// ISO 20022 pain.001.001.03 credit transfers.
+    Pain001,
+}
+
+/// The paying company and account, as it appears in payment files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Originator {
+    pub bank_account_id: Uuid,
+    pub company_name: String,
+    pub bank_name: String,
+    pub account_number: String,
+    pub currency: String,
+    pub routing_number: Option<String>,
+    /// The ten-character ACH company identification the bank assigned.
+    pub ach_company_id: Option<String>,
+    pub iban: Option<String>,
+    pub bic: Option<String>,
+}
+
+/// Where a vendor is paid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VendorBankAccount {
+    pub vendor_id: Uuid,
+    pub account_name: String,
+    pub account_type: BankAccountType,
+    pub routing_number: Option<String>,
+    pub account_number: Option<String>,
+    pub iban: Option<String>,
+    pub bic: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One credit to one vendor, covering one or more bills.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentInstruction {
+    pub vendor_id: Uuid,
+    pub vendor_code: String,
+    pub payee: VendorBankAccount,
+    pub amount: i64,
+    pub end_to_end_id: String,
+    /// The bills paid, e.g. `INV-1001,INV-1002`.
+    pub remittance: String,
+    pub bill_ids: Vec<Uuid>,
+}
+
+/// A generated payment file with the payments it carries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedPaymentFile {
+    pub file: PaymentFileGeneration,
+    pub format: PaymentFileFormat,
+    pub payments: Vec<PaymentInstruction>,
+}
+
+/// A statement read from a bank file and stored through the finance bank ledger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedStatement {
+    pub statement: erp_finance::BankStatement,
+    pub format: StatementFormat,
+    pub account_number: String,
+    pub currency: String,
+    pub transaction_count: usize,
+    pub total_credits: i64,
+    pub total_debits: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum PaymentFileStatus {
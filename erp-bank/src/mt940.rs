@@ -0,0 +1,219 @@
+use chrono::{Datelike, NaiveDate};
+use erp_core::{Error, Result};
+
+use crate::models::{StatementFormat, TransactionType};
+use crate::statement::{parse_decimal, parse_yymmdd, ParsedStatement, StatementEntry};
+
+struct Balance {
+    date: NaiveDate,
+    currency: String,
+    amount: i64,
+}
+
+/// Reads SWIFT MT940 customer statements, with or without the `{1:}`...`{4:` envelope. A file
+/// may carry several messages and a message several statements, each opening with `:20:`.
+pub fn parse(content: &str) -> Result<Vec<ParsedStatement>> {
+    let mut statements = Vec::new();
+    for body in message_bodies(content) {
+        let mut fields: Vec<(String, String)> = Vec::new();
+        for line in body.lines() {
+            let line = line.trim_end();
+            if line.trim() == "-" {
+                continue;
+            }
+            match tag(line) {
+                Some((tag, value)) => {
+                    if tag == "20" && !fields.is_empty() {
+                        statements.push(statement(&fields)?);
+                        fields.clear();
+                    }
+                    fields.push((tag.to_string(), value.to_string()));
+                }
+                None => match fields.last_mut() {
+                    Some((_, value)) => {
+                        value.push('\n');
+                        value.push_str(line);
+                    }
+                    None if line.trim().is_empty() => {}
+                    None => return Err(Error::validation(format!("MT940 text before the first field: '{}'", line))),
+                },
+            }
+        }
+        if !fields.is_empty() {
+            statements.push(statement(&fields)?);
+        }
+    }
+    if statements.is_empty() {
+        return Err(Error::validation("No MT940 statements found"));
+    }
+    Ok(statements)
+}
+
+fn message_bodies(content: &str) -> Vec<&str> {
+    if !content.contains("{4:") {
+        return vec![content];
+    }
+    content
+        .split("{4:")
+        .skip(1)
+        .map(|block| block.split("-}").next().unwrap_or(block))
+        .collect()
+}
+
+/// Splits `:61:value` into its tag and value.
+fn tag(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix(':')?;
+    let (tag, value) = rest.split_once(':')?;
+    let valid = (2..=3).contains(&tag.len())
+        && tag.chars().take(2).all(|c| c.is_ascii_digit())
+        && tag.chars().skip(2).all(|c| c.is_ascii_uppercase());
+    valid.then_some((tag, value))
+}
+
+fn statement(fields: &[(String, String)]) -> Result<ParsedStatement> {
+    let reference = fields.iter().find(|(t, _)| t == "20").map(|(_, v)| v.as_str()).unwrap_or_default();
+    let context = |e: Error| match e {
+        Error::Validation(message) => Error::validation(format!("MT940 statement {}: {}", reference, message)),
+        other => other,
+    };
+    let mut account_number = None;
+    let mut opening = None;
+    let mut closing = None;
+    let mut entries: Vec<StatementEntry> = Vec::new();
+    let mut last_was_entry = false;
+    for (tag, value) in fields {
+        match tag.as_str() {
+            "25" => account_number = Some(value.trim().to_string()),
+            "60F" | "60M" => opening = Some(balance(value).map_err(context)?),
+            "62F" | "62M" => closing = Some(balance(value).map_err(context)?),
+            "61" => {
+                entries.push(entry(value).map_err(context)?);
+                last_was_entry = true;
+                continue;
+            }
+            "86" if last_was_entry => {
+                if let Some(entry) = entries.last_mut() {
+                    entry.description = value.lines().map(str::trim).collect::<Vec<_>>().join(" ").trim().to_string();
+                }
+            }
+            _ => {}
+        }
+        last_was_entry = false;
+    }
+    let account_number = account_number.ok_or_else(|| context(Error::validation("missing :25: account")))?;
+    let opening = opening.ok_or_else(|| context(Error::validation("missing :60F: opening balance")))?;
+    let closing = closing.ok_or_else(|| context(Error::validation("missing :62F: closing balance")))?;
+    if opening.currency != closing.currency {
+        return Err(context(Error::validation(format!(
+            "opening balance is in {} but closing balance is in {}",
+            opening.currency, closing.currency
+        ))));
+    }
+    Ok(ParsedStatement {
+        format: StatementFormat::MT940,
+        account_number,
+        currency: closing.currency,
+        statement_date: closing.date,
+        opening_balance: opening.amount,
+        closing_balance: closing.amount,
+        entries,
+    })
+}
+
+/// `C260930EUR1234,56`: mark, date, currency, amount.
+fn balance(value: &str) -> Result<Balance> {
+    let value = value.trim();
+    if value.len() < 11 || !value.is_ascii() {
+        return Err(Error::validation(format!("Invalid balance '{}'", value)));
+    }
+    let sign = match &value[..1] {
+        "C" => 1,
+        "D" => -1,
+        other => return Err(Error::validation(format!("Invalid debit/credit mark '{}'", other))),
+    };
+    Ok(Balance {
+        date: parse_yymmdd(&value[1..7])?,
+        currency: value[7..10].to_string(),
+        amount: sign * parse_decimal(&value[10..], ',')?,
+    })
+}
+
+/// `:61:` value date, optional entry date, mark, optional funds code, amount, type code,
+/// owner's reference, optional `//` bank reference, and supplementary details on a second line.
+fn entry(value: &str) -> Result<StatementEntry> {
+    let invalid = || Error::validation(format!("Invalid statement line '{}'", value.lines().next().unwrap_or_default()));
+    let mut lines = value.lines();
+    let first = lines.next().unwrap_or_default().trim();
+    if !first.is_ascii() || first.len() < 6 {
+        return Err(invalid());
+    }
+    let value_date = parse_yymmdd(&first[..6])?;
+    let mut rest = &first[6..];
+
+    let mut booking_date = value_date;
+    if rest.len() >= 4 && rest[..4].chars().all(|c| c.is_ascii_digit()) {
+        let month: u32 = rest[..2].parse().map_err(|_| invalid())?;
+        let day: u32 = rest[2..4].parse().map_err(|_| invalid())?;
+        let year = match (month, value_date.month()) {
+            (12, 1) => value_date.year() - 1,
+            (1, 12) => value_date.year() + 1,
+            _ => value_date.year(),
+        };
+        booking_date = NaiveDate::from_ymd_opt(year, month, day).ok_or_else(invalid)?;
+        rest = &rest[4..];
+    }
+
+    let (sign, reversal, after_mark) = if let Some(r) = rest.strip_prefix("RC") {
+        (-1, true, r)
+    } else if let Some(r) = rest.strip_prefix("RD") {
+        (1, true, r)
+    } else if let Some(r) = rest.strip_prefix('C') {
+        (1, false, r)
+    } else if let Some(r) = rest.strip_prefix('D') {
+        (-1, false, r)
+    } else {
+        return Err(invalid());
+    };
+    rest = after_mark;
+    if rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        rest = &rest[1..];
+    }
+
+    let amount_len = rest.find(|c: char| !(c.is_ascii_digit() || c == ',')).ok_or_else(invalid)?;
+    let amount = parse_decimal(&rest[..amount_len], ',')?;
+    rest = &rest[amount_len..];
+    if rest.len() < 4 {
+        return Err(invalid());
+    }
+    let type_code = &rest[..4];
+    let (customer_reference, bank_reference) = match rest[4..].split_once("//") {
+        Some((owner, bank)) => (owner, Some(bank)),
+        None => (&rest[4..], None),
+    };
+    let nonempty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty() && s != "NONREF");
+
+    let transaction_type = if reversal {
+        TransactionType::Reversal
+    } else {
+        match &type_code[1..] {
+            "TRF" => TransactionType::WireTransfer,
+            "CHK" => TransactionType::Check,
+            "CHG" | "COM" => TransactionType::Fee,
+            "INT" => TransactionType::Interest,
+            "DDT" => TransactionType::ACH,
+            "RTI" => TransactionType::Return,
+            _ if sign > 0 => TransactionType::Credit,
+            _ => TransactionType::Debit,
+        }
+    };
+    Ok(StatementEntry {
+        booking_date,
+        value_date: Some(value_date),
+        amount: sign * amount,
+        transaction_type,
+        type_code: type_code.to_string(),
+        bank_reference: bank_reference.and_then(nonempty),
+        customer_reference: nonempty(customer_reference),
+        description: lines.map(str::trim).collect::<Vec<_>>().join(" "),
+    })
+}
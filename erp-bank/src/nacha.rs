@@ -0,0 +1,167 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use erp_core::{Error, Result};
+
+use crate::models::{BankAccountType, Originator, PaymentInstruction};
+
+const RECORD_LENGTH: usize = 94;
+const BLOCKING_FACTOR: usize = 10;
+/// ACH entry amounts are ten digits of cents.
+const MAX_AMOUNT: i64 = 9_999_999_999;
+
+/// Writes a NACHA file with one CCD batch of credits, each carrying its remittance in an
+/// addenda record.
+pub fn write(
+    originator: &Originator,
+    payments: &[PaymentInstruction],
+    effective_date: NaiveDate,
+    created_at: DateTime<Utc>,
+    file_id_modifier: char,
+) -> Result<String> {
+    let origin_routing = originator
+        .routing_number
+        .as_deref()
+        .ok_or_else(|| Error::business_rule(format!("Bank account {} has no routing number", originator.account_number)))?;
+    check_routing(origin_routing)?;
+    let company_id = originator
+        .ach_company_id
+        .as_deref()
+        .filter(|id| !id.trim().is_empty() && id.len() <= 10)
+        .ok_or_else(|| Error::business_rule(format!("Bank account {} has no ACH company ID", originator.account_number)))?;
+    if originator.currency != "USD" {
+        return Err(Error::business_rule(format!("ACH payments must be in USD, not {}", originator.currency)));
+    }
+    if payments.is_empty() {
+        return Err(Error::validation("A payment file needs at least one payment"));
+    }
+    let odfi = &origin_routing[..8];
+
+    let mut records = Vec::new();
+    records.push(format!(
+        "101 {} {}{}{}{}094{}1{}{}{}",
+        origin_routing,
+        origin_routing,
+        created_at.format("%y%m%d"),
+        created_at.format("%H%M"),
+        file_id_modifier.to_ascii_uppercase(),
+        BLOCKING_FACTOR,
+        text(&originator.bank_name, 23),
+        text(&originator.company_name, 23),
+        text("", 8),
+    ));
+    records.push(format!(
+        "5220{}{}{}CCD{}{}{}   1{}{:07}",
+        text(&originator.company_name, 16),
+        text("", 20),
+        text(company_id, 10),
+        text("VENDOR PAY", 10),
+        effective_date.format("%y%m%d"),
+        effective_date.format("%y%m%d"),
+        odfi,
+        1,
+    ));
+
+    let mut entry_hash: u64 = 0;
+    let mut total_credit: i64 = 0;
+    for (index, payment) in payments.iter().enumerate() {
+        let payee = &payment.payee;
+        let routing = payee
+            .routing_number
+            .as_deref()
+            .ok_or_else(|| Error::business_rule(format!("Vendor {} has no routing number", payment.vendor_code)))?;
+        check_routing(routing).map_err(|e| vendor_error(payment, e))?;
+        let account = payee
+            .account_number
+            .as_deref()
+            .filter(|a| !a.is_empty() && a.len() <= 17 && a.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+            .ok_or_else(|| Error::business_rule(format!("Vendor {} has no valid ACH account number", payment.vendor_code)))?;
+        let transaction_code = match payee.account_type {
+            BankAccountType::Savings => "32",
+            _ => "22",
+        };
+        if payment.amount <= 0 || payment.amount > MAX_AMOUNT {
+            return Err(Error::validation(format!("Payment to vendor {} of {} cents is out of range", payment.vendor_code, payment.amount)));
+        }
+        let sequence = index + 1;
+        records.push(format!(
+            "6{}{}{}{:010}{}{}  1{}{:07}",
+            transaction_code,
+            routing,
+            text(account, 17),
+            payment.amount,
+            text(&payment.vendor_code, 15),
+            text(&payee.account_name, 22),
+            odfi,
+            sequence,
+        ));
+        records.push(format!("705{}0001{:07}", text(&payment.remittance, 80), sequence));
+        entry_hash += routing[..8].parse::<u64>().unwrap_or_default();
+        total_credit += payment.amount;
+    }
+    let entry_hash = entry_hash % 10_000_000_000;
+    let entries_and_addenda = payments.len() * 2;
+
+    records.push(format!(
+        "8220{:06}{:010}{:012}{:012}{}{}{}{}{:07}",
+        entries_and_addenda,
+        entry_hash,
+        0,
+        total_credit,
+        text(company_id, 10),
+        text("", 19),
+        text("", 6),
+        odfi,
+        1,
+    ));
+    let blocks = (records.len() + 1).div_ceil(BLOCKING_FACTOR);
+    records.push(format!(
+        "9{:06}{:06}{:08}{:010}{:012}{:012}{}",
+        1,
+        blocks,
+        entries_and_addenda,
+        entry_hash,
+        0,
+        total_credit,
+        text("", 39),
+    ));
+    while !records.len().is_multiple_of(BLOCKING_FACTOR) {
+        records.push("9".repeat(RECORD_LENGTH));
+    }
+    if let Some(bad) = records.iter().find(|r| r.len() != RECORD_LENGTH) {
+        return Err(Error::internal(format!("NACHA record is {} characters: {}", bad.len(), bad)));
+    }
+
+    let mut file = records.join("\n");
+    file.push('\n');
+    Ok(file)
+}
+
+/// ABA routing numbers are nine digits with a weighted 3-7-1 check digit.
+pub fn check_routing(routing: &str) -> Result<()> {
+    let digits: Vec<u32> = routing.chars().filter_map(|c| c.to_digit(10)).collect();
+    if routing.len() != 9 || digits.len() != 9 {
+        return Err(Error::validation(format!("Routing number '{}' must be nine digits", routing)));
+    }
+    let sum: u32 = digits.iter().zip([3, 7, 1].iter().cycle()).map(|(d, w)| d * w).sum();
+    if !sum.is_multiple_of(10) {
+        return Err(Error::validation(format!("Routing number '{}' fails its check digit", routing)));
+    }
+    Ok(())
+}
+
+fn vendor_error(payment: &PaymentInstruction, error: Error) -> Error {
+    match error {
+        Error::Validation(message) => Error::business_rule(format!("Vendor {}: {}", payment.vendor_code, message)),
+        other => other,
+    }
+}
+
+/// Upper-cases, drops characters ACH doesn't carry, and pads or truncates to `width`.
+fn text(value: &str, width: usize) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| c.to_ascii_uppercase())
+        .filter(|c| c.is_ascii() && !c.is_ascii_control() && *c != '*' && *c != '~')
+        .take(width)
+        .collect();
+    format!("{:<width$}", cleaned, width = width)
+}
@@ -0,0 +1,181 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use erp_core::{Error, Result};
+
+use crate::models::{Originator, PaymentInstruction};
+
+/// Writes an ISO 20022 pain.001.001.03 customer credit transfer initiation with a single
+/// payment information block. EUR files are marked as SEPA.
+pub fn write(
+    message_id: &str,
+    originator: &Originator,
+    payments: &[PaymentInstruction],
+    execution_date: NaiveDate,
+    created_at: DateTime<Utc>,
+) -> Result<String> {
+    let debtor_iban = originator
+        .iban
+        .as_deref()
+        .ok_or_else(|| Error::business_rule(format!("Bank account {} has no IBAN", originator.account_number)))?;
+    check_iban(debtor_iban)?;
+    let debtor_bic = originator
+        .bic
+        .as_deref()
+        .ok_or_else(|| Error::business_rule(format!("Bank account {} has no BIC", originator.account_number)))?;
+    check_bic(debtor_bic)?;
+    if payments.is_empty() {
+        return Err(Error::validation("A payment file needs at least one payment"));
+    }
+    if message_id.is_empty() || message_id.len() > 35 {
+        return Err(Error::validation("Message ID must be 1 to 35 characters"));
+    }
+    let sepa = originator.currency == "EUR";
+
+    let mut control_sum = 0i64;
+    let mut transactions = String::new();
+    for payment in payments {
+        if payment.amount <= 0 {
+            return Err(Error::validation(format!("Payment to vendor {} must be positive", payment.vendor_code)));
+        }
+        if payment.end_to_end_id.is_empty() || payment.end_to_end_id.len() > 35 {
+            return Err(Error::validation(format!("End-to-end ID for vendor {} must be 1 to 35 characters", payment.vendor_code)));
+        }
+        let payee = &payment.payee;
+        let account = match (&payee.iban, &payee.account_number) {
+            (Some(iban), _) => {
+                check_iban(iban).map_err(|e| vendor_error(payment, e))?;
+                format!("<IBAN>{}</IBAN>", compact(iban))
+            }
+            (None, Some(number)) if !sepa => format!("<Othr><Id>{}</Id></Othr>", escape(number)),
+            _ => return Err(Error::business_rule(format!("Vendor {} has no IBAN", payment.vendor_code))),
+        };
+        let agent = match &payee.bic {
+            Some(bic) => {
+                check_bic(bic).map_err(|e| vendor_error(payment, e))?;
+                format!("<CdtrAgt><FinInstnId><BIC>{}</BIC></FinInstnId></CdtrAgt>", bic)
+            }
+            None if sepa => String::new(),
+            None => return Err(Error::business_rule(format!("Vendor {} has no BIC", payment.vendor_code))),
+        };
+        control_sum += payment.amount;
+        transactions.push_str(&format!(
+            concat!(
+                "<CdtTrfTxInf>",
+                "<PmtId><EndToEndId>{}</EndToEndId></PmtId>",
+                "<Amt><InstdAmt Ccy=\"{}\">{}</InstdAmt></Amt>",
+                "{}",
+                "<Cdtr><Nm>{}</Nm></Cdtr>",
+                "<CdtrAcct><Id>{}</Id></CdtrAcct>",
+                "<RmtInf><Ustrd>{}</Ustrd></RmtInf>",
+                "</CdtTrfTxInf>"
+            ),
+            escape(&payment.end_to_end_id),
+            originator.currency,
+            decimal(payment.amount),
+            agent,
+            escape(&truncate(&payee.account_name, 70)),
+            account,
+            escape(&truncate(&payment.remittance, 140)),
+        ));
+    }
+
+    let service_level = if sepa { "<PmtTpInf><SvcLvl><Cd>SEPA</Cd></SvcLvl></PmtTpInf>" } else { "" };
+    let charges = if sepa { "SLEV" } else { "SHAR" };
+    Ok(format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<Document xmlns=\"urn:iso:std:iso:20022:tech:xsd:pain.001.001.03\">",
+            "<CstmrCdtTrfInitn>",
+            "<GrpHdr><MsgId>{id}</MsgId><CreDtTm>{created}</CreDtTm><NbOfTxs>{count}</NbOfTxs>",
+            "<CtrlSum>{sum}</CtrlSum><InitgPty><Nm>{company}</Nm></InitgPty></GrpHdr>",
+            "<PmtInf><PmtInfId>{id}</PmtInfId><PmtMtd>TRF</PmtMtd><BtchBookg>true</BtchBookg>",
+            "<NbOfTxs>{count}</NbOfTxs><CtrlSum>{sum}</CtrlSum>{service_level}",
+            "<ReqdExctnDt>{date}</ReqdExctnDt>",
+            "<Dbtr><Nm>{company}</Nm></Dbtr>",
+            "<DbtrAcct><Id><IBAN>{iban}</IBAN></Id><Ccy>{currency}</Ccy></DbtrAcct>",
+            "<DbtrAgt><FinInstnId><BIC>{bic}</BIC></FinInstnId></DbtrAgt>",
+            "<ChrgBr>{charges}</ChrgBr>",
+            "{transactions}",
+            "</PmtInf></CstmrCdtTrfInitn></Document>\n"
+        ),
+        id = escape(message_id),
+        created = created_at.format("%Y-%m-%dT%H:%M:%S"),
+        count = payments.len(),
+        sum = decimal(control_sum),
+        company = escape(&truncate(&originator.company_name, 70)),
+        service_level = service_level,
+        date = execution_date.format("%Y-%m-%d"),
+        iban = compact(debtor_iban),
+        currency = originator.currency,
+        bic = debtor_bic,
+        charges = charges,
+        transactions = transactions,
+    ))
+}
+
+/// IBANs are a country code, two check digits and up to 30 alphanumerics, valid when the
+/// rearranged number is 1 mod 97.
+pub fn check_iban(iban: &str) -> Result<()> {
+    let iban = compact(iban);
+    let invalid = || Error::validation(format!("IBAN '{}' is not valid", iban));
+    if !(15..=34).contains(&iban.len()) || !iban.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(invalid());
+    }
+    if !iban[..2].chars().all(|c| c.is_ascii_uppercase()) || !iban[2..4].chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let mut remainder = 0u32;
+    for c in iban[4..].chars().chain(iban[..4].chars()) {
+        let value = c.to_digit(36).ok_or_else(invalid)?;
+        remainder = if value < 10 { (remainder * 10 + value) % 97 } else { (remainder * 100 + value) % 97 };
+    }
+    if remainder != 1 {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+/// BICs are eight or eleven characters: bank, country, location and optional branch.
+pub fn check_bic(bic: &str) -> Result<()> {
+    let valid = (bic.len() == 8 || bic.len() == 11)
+        && bic.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        && bic[..6].chars().all(|c| c.is_ascii_uppercase());
+    if !valid {
+        return Err(Error::validation(format!("BIC '{}' is not valid", bic)));
+    }
+    Ok(())
+}
+
+fn vendor_error(payment: &PaymentInstruction, error: Error) -> Error {
+    match error {
+        Error::Validation(message) => Error::business_rule(format!("Vendor {}: {}", payment.vendor_code, message)),
+        other => other,
+    }
+}
+
+fn compact(iban: &str) -> String {
+    iban.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_ascii_uppercase()
+}
+
+fn decimal(cents: i64) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+fn truncate(value: &str, max: usize) -> String {
+    value.chars().take(max).collect()
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if c.is_control() => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
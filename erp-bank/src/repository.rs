@@ -1,7 +1,8 @@
 use crate::models::*;
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 use erp_core::Result;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 
 #[async_trait]
@@ -25,6 +26,15 @@ pub trait BankRepository: Send + Sync {
     async fn create_match(&self, match_rec: &ReconciliationMatch) -> Result<ReconciliationMatch>;
     async fn create_payment_file(&self, file: &PaymentFileGeneration) -> Result<PaymentFileGeneration>;
     async fn create_fee(&self, fee: &BankFee) -> Result<BankFee>;
+    async fn get_originator(&self, bank_account_id: Uuid) -> Result<Option<Originator>>;
+    async fn find_originator_by_number(&self, account_number: &str) -> Result<Option<Originator>>;
+    async fn update_originator(&self, originator: &Originator) -> Result<Originator>;
+    async fn statement_exists(&self, bank_account_id: Uuid, statement_date: DateTime<Utc>) -> Result<bool>;
+    async fn get_vendor_bank_account(&self, vendor_id: Uuid) -> Result<Option<VendorBankAccount>>;
+    async fn upsert_vendor_bank_account(&self, account: &VendorBankAccount) -> Result<VendorBankAccount>;
+    async fn get_vendor_code(&self, vendor_id: Uuid) -> Result<Option<String>>;
+    async fn create_payment_file_item(&self, payment_file_id: Uuid, vendor_id: Uuid, bill_id: Uuid, amount: i64, end_to_end_id: &str) -> Result<()>;
+    async fn count_payment_files(&self, bank_account_id: Uuid, file_date: NaiveDate) -> Result<i64>;
 }
 
 pub struct SqliteBankRepository {
@@ -35,6 +45,69 @@ impl SqliteBankRepository {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
     }
+
+    pub async fn create_payment_file_in(conn: &mut SqliteConnection, file: &PaymentFileGeneration) -> Result<PaymentFileGeneration> {
+        sqlx::query(
+            r#"INSERT INTO payment_file_generations (id, file_number, bank_account_id, file_type,
+               file_date, value_date, currency, total_amount, payment_count, file_content,
+               file_path, status, generated_at, transmitted_at, acknowledged_at, created_at)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
+        )
+        .bind(file.base.id.to_string())
+        .bind(&file.file_number)
+        .bind(file.bank_account_id.to_string())
+        .bind(&file.file_type)
+        .bind(file.file_date)
+        .bind(file.value_date)
+        .bind(&file.currency)
+        .bind(file.total_amount)
+        .bind(file.payment_count)
+        .bind(&file.file_content)
+        .bind(&file.file_path)
+        .bind(file.status.clone())
+        .bind(file.generated_at)
+        .bind(file.transmitted_at)
+        .bind(file.acknowledged_at)
+        .bind(file.created_at)
+        .execute(&mut *conn).await?;
+        Ok(file.clone())
+    }
+
+    pub async fn statement_exists_in(conn: &mut SqliteConnection, bank_account_id: Uuid, statement_date: DateTime<Utc>) -> Result<bool> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM bank_statements WHERE bank_account_id = ? AND statement_date = ?"
+        )
+        .bind(bank_account_id.to_string())
+        .bind(statement_date.to_rfc3339())
+        .fetch_one(&mut *conn).await?;
+        Ok(count > 0)
+    }
+
+    pub async fn create_payment_file_item_in(conn: &mut SqliteConnection, payment_file_id: Uuid, vendor_id: Uuid, bill_id: Uuid, amount: i64, end_to_end_id: &str) -> Result<()> {
+        sqlx::query(
+            r#"INSERT INTO payment_file_items (id, payment_file_id, vendor_id, bill_id, amount,
+               end_to_end_id, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"#
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(payment_file_id.to_string())
+        .bind(vendor_id.to_string())
+        .bind(bill_id.to_string())
+        .bind(amount)
+        .bind(end_to_end_id)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *conn).await?;
+        Ok(())
+    }
+
+    pub async fn count_payment_files_in(conn: &mut SqliteConnection, bank_account_id: Uuid, file_date: NaiveDate) -> Result<i64> {
+        let count = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM payment_file_generations WHERE bank_account_id = ? AND file_date = ?"
+        )
+        .bind(bank_account_id.to_string())
+        .bind(file_date)
+        .fetch_one(&mut *conn).await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
@@ -57,7 +130,7 @@ impl BankRepository for SqliteBankRepository {
         .bind(&conn.api_secret)
         .bind(&conn.certificate_path)
         .bind(conn.authentication_type.clone())
-        .bind(conn.statement_format.clone())
+        .bind(conn.statement_format)
         .bind(conn.polling_enabled)
         .bind(conn.polling_interval_minutes)
         .bind(conn.last_poll_at)
@@ -90,7 +163,7 @@ impl BankRepository for SqliteBankRepository {
         .bind(&account.account_number)
         .bind(&account.masked_account_number)
         .bind(&account.account_name)
-        .bind(account.account_type.clone())
+        .bind(account.account_type)
         .bind(&account.currency)
         .bind(account.gl_account_id.map(|id| id.to_string()))
         .bind(account.company_id.to_string())
@@ -133,7 +206,7 @@ impl BankRepository for SqliteBankRepository {
         .bind(stmt.total_debits)
         .bind(stmt.credit_count)
         .bind(stmt.debit_count)
-        .bind(stmt.statement_format.clone())
+        .bind(stmt.statement_format)
         .bind(&stmt.raw_file_path)
         .bind(stmt.imported_at)
         .bind(stmt.imported_by.map(|id| id.to_string()))
@@ -165,7 +238,7 @@ impl BankRepository for SqliteBankRepository {
         .bind(tx.bank_account_id.to_string())
         .bind(tx.transaction_date)
         .bind(tx.value_date)
-        .bind(tx.transaction_type.clone())
+        .bind(tx.transaction_type)
         .bind(tx.amount)
         .bind(&tx.currency)
         .bind(&tx.reference_number)
@@ -293,30 +366,7 @@ impl BankRepository for SqliteBankRepository {
     }
 
     async fn create_payment_file(&self, file: &PaymentFileGeneration) -> Result<PaymentFileGeneration> {
-        sqlx::query(
-            r#"INSERT INTO payment_file_generations (id, file_number, bank_account_id, file_type,
-               file_date, value_date, currency, total_amount, payment_count, file_content,
-               file_path, status, generated_at, transmitted_at, acknowledged_at, created_at)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
-        )
-        .bind(file.base.id.to_string())
-        .bind(&file.file_number)
-        .bind(file.bank_account_id.to_string())
-        .bind(&file.file_type)
-        .bind(file.file_date)
-        .bind(file.value_date)
-        .bind(&file.currency)
-        .bind(file.total_amount)
-        .bind(file.payment_count)
-        .bind(&file.file_content)
-        .bind(&file.file_path)
-        .bind(file.status.clone())
-        .bind(file.generated_at)
-        .bind(file.transmitted_at)
-        .bind(file.acknowledged_at)
-        .bind(file.created_at)
-        .execute(&self.pool).await?;
-        Ok(file.clone())
+        Self::create_payment_file_in(&mut *self.pool.acquire().await?, file).await
     }
 
     async fn create_fee(&self, fee: &BankFee) -> Result<BankFee> {
@@ -339,4 +389,154 @@ impl BankRepository for SqliteBankRepository {
         .execute(&self.pool).await?;
         Ok(fee.clone())
     }
+
+    async fn get_originator(&self, bank_account_id: Uuid) -> Result<Option<Originator>> {
+        let row = sqlx::query_as::<_, OriginatorRow>(
+            r#"SELECT id, bank_name, account_number, currency, company_name, routing_number,
+               ach_company_id, iban, bic FROM bank_accounts WHERE id = ?"#
+        )
+        .bind(bank_account_id.to_string())
+        .fetch_optional(&self.pool).await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn find_originator_by_number(&self, account_number: &str) -> Result<Option<Originator>> {
+        let compact: String = account_number.chars().filter(|c| !c.is_whitespace()).collect();
+        let row = sqlx::query_as::<_, OriginatorRow>(
+            r#"SELECT id, bank_name, account_number, currency, company_name, routing_number,
+               ach_company_id, iban, bic FROM bank_accounts
+               WHERE status = 'Active' AND (account_number = ? OR REPLACE(account_number, ' ', '') = ?
+                  OR REPLACE(iban, ' ', '') = ?)
+               ORDER BY created_at LIMIT 1"#
+        )
+        .bind(account_number)
+        .bind(&compact)
+        .bind(compact.to_uppercase())
+        .fetch_optional(&self.pool).await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn update_originator(&self, originator: &Originator) -> Result<Originator> {
+        sqlx::query(
+            r#"UPDATE bank_accounts SET company_name = ?, routing_number = ?, ach_company_id = ?,
+               iban = ?, bic = ? WHERE id = ?"#
+        )
+        .bind(&originator.company_name)
+        .bind(&originator.routing_number)
+        .bind(&originator.ach_company_id)
+        .bind(&originator.iban)
+        .bind(&originator.bic)
+        .bind(originator.bank_account_id.to_string())
+        .execute(&self.pool).await?;
+        Ok(originator.clone())
+    }
+
+    async fn statement_exists(&self, bank_account_id: Uuid, statement_date: DateTime<Utc>) -> Result<bool> {
+        Self::statement_exists_in(&mut *self.pool.acquire().await?, bank_account_id, statement_date).await
+    }
+
+    async fn get_vendor_bank_account(&self, vendor_id: Uuid) -> Result<Option<VendorBankAccount>> {
+        let row = sqlx::query_as::<_, VendorBankAccountRow>(
+            r#"SELECT vendor_id, account_name, account_type, routing_number, account_number, iban, bic,
+               updated_at FROM vendor_bank_accounts WHERE vendor_id = ?"#
+        )
+        .bind(vendor_id.to_string())
+        .fetch_optional(&self.pool).await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn upsert_vendor_bank_account(&self, account: &VendorBankAccount) -> Result<VendorBankAccount> {
+        sqlx::query(
+            r#"INSERT INTO vendor_bank_accounts (vendor_id, account_name, account_type, routing_number,
+               account_number, iban, bic, updated_at)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+               ON CONFLICT(vendor_id) DO UPDATE SET account_name = excluded.account_name,
+               account_type = excluded.account_type, routing_number = excluded.routing_number,
+               account_number = excluded.account_number, iban = excluded.iban, bic = excluded.bic,
+               updated_at = excluded.updated_at"#
+        )
+        .bind(account.vendor_id.to_string())
+        .bind(&account.account_name)
+        .bind(account.account_type)
+        .bind(&account.routing_number)
+        .bind(&account.account_number)
+        .bind(&account.iban)
+        .bind(&account.bic)
+        .bind(account.updated_at.to_rfc3339())
+        .execute(&self.pool).await?;
+        Ok(account.clone())
+    }
+
+    async fn get_vendor_code(&self, vendor_id: Uuid) -> Result<Option<String>> {
+        let code = sqlx::query_scalar("SELECT code FROM vendors WHERE id = ?")
+            .bind(vendor_id.to_string())
+            .fetch_optional(&self.pool).await?;
+        Ok(code)
+    }
+
+    async fn create_payment_file_item(&self, payment_file_id: Uuid, vendor_id: Uuid, bill_id: Uuid, amount: i64, end_to_end_id: &str) -> Result<()> {
+        Self::create_payment_file_item_in(&mut *self.pool.acquire().await?, payment_file_id, vendor_id, bill_id, amount, end_to_end_id).await
+    }
+
+    async fn count_payment_files(&self, bank_account_id: Uuid, file_date: NaiveDate) -> Result<i64> {
+        Self::count_payment_files_in(&mut *self.pool.acquire().await?, bank_account_id, file_date).await
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct OriginatorRow {
+    id: String,
+    bank_name: String,
+    account_number: String,
+    currency: String,
+    company_name: Option<String>,
+    routing_number: Option<String>,
+    ach_company_id: Option<String>,
+    iban: Option<String>,
+    bic: Option<String>,
+}
+
+impl From<OriginatorRow> for Originator {
+    fn from(r: OriginatorRow) -> Self {
+        Self {
+            bank_account_id: Uuid::parse_str(&r.id).unwrap_or_default(),
+            company_name: r.company_name.unwrap_or_default(),
+            bank_name: r.bank_name,
+            account_number: r.account_number,
+            currency: r.currency,
+            routing_number: r.routing_number,
+            ach_company_id: r.ach_company_id,
+            iban: r.iban,
+            bic: r.bic,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct VendorBankAccountRow {
+    vendor_id: String,
+    account_name: String,
+    account_type: BankAccountType,
+    routing_number: Option<String>,
+    account_number: Option<String>,
+    iban: Option<String>,
+    bic: Option<String>,
+    updated_at: String,
+}
+
+impl From<VendorBankAccountRow> for VendorBankAccount {
+    fn from(r: VendorBankAccountRow) -> Self {
+        Self {
+            vendor_id: Uuid::parse_str(&r.vendor_id).unwrap_or_default(),
+            account_name: r.account_name,
+            account_type: r.account_type,
+            routing_number: r.routing_number,
+            account_number: r.account_number,
+            iban: r.iban,
+            bic: r.bic,
+            updated_at: DateTime::parse_from_rfc3339(&r.updated_at)
+                .map(|d| d.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        }
+    }
 }
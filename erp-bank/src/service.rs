@@ -1,7 +1,11 @@
 use crate::models::*;
 use crate::repository::{BankRepository, SqliteBankRepository};
+use crate::statement::midnight;
+use crate::{bai2, camt053, mt940, nacha, pain001};
 use chrono::{NaiveDate, Utc};
-use erp_core::{BaseEntity, Result};
+use erp_core::{BaseEntity, Error, Result};
+use erp_finance::BankReconciliationService;
+use erp_vendor_bills::{VendorBill, VendorBillService, VendorBillStatus};
 use serde::Deserialize;
 use sqlx::SqlitePool;
 use uuid::Uuid;
@@ -167,66 +171,255 @@ impl BankService {
         self.repo.create_match(&match_rec).await
     }
 
-    pub async fn generate_payment_file(&self, _pool: &SqlitePool, req: GeneratePaymentFileRequest) -> Result<PaymentFileGeneration> {
+    /// Reads a BAI2, MT940 or camt.053 file and stores each account's statement in the bank
+    /// ledger. The statements are stored in one transaction, and none are unless every one
+    /// parses, balances and is new.
+    pub async fn import_statement_file(&self, pool: &SqlitePool, req: ImportStatementFileRequest) -> Result<Vec<ImportedStatement>> {
+        let statements = match req.format {
+            StatementFormat::BAI2 => bai2::parse(&req.content)?,
+            StatementFormat::MT940 => mt940::parse(&req.content)?,
+            StatementFormat::CAMT053 => camt053::parse(&req.content)?,
+            other => return Err(Error::validation(format!("{:?} statements cannot be imported", other))),
+        };
+
+        let mut accounts = Vec::with_capacity(statements.len());
+        for statement in &statements {
+            statement.check_balances()?;
+            let account = self
+                .repo
+                .find_originator_by_number(&statement.account_number)
+                .await?
+                .ok_or_else(|| Error::not_found("BankAccount", &statement.account_number))?;
+            if account.currency != statement.currency {
+                return Err(Error::business_rule(format!(
+                    "Statement for account {} is in {} but the account is in {}",
+                    statement.account_number, statement.currency, account.currency
+                )));
+            }
+            accounts.push(account.bank_account_id);
+        }
+
+        let mut tx = pool.begin().await?;
+        let mut imported = Vec::with_capacity(statements.len());
+        for (statement, bank_account_id) in statements.into_iter().zip(accounts) {
+            if SqliteBankRepository::statement_exists_in(&mut tx, bank_account_id, midnight(statement.statement_date)).await? {
+                return Err(Error::Conflict(format!(
+                    "A statement for account {} on {} has already been imported",
+                    statement.account_number, statement.statement_date
+                )));
+            }
+            let stored = BankReconciliationService::import_statement_in(
+                &mut tx,
+                bank_account_id,
+                midnight(statement.statement_date),
+                statement.opening_balance,
+                statement.closing_balance,
+                statement.to_imports(),
+            )
+            .await?;
+            imported.push(ImportedStatement {
+                statement: stored,
+                format: statement.format,
+                transaction_count: statement.entries.len(),
+                total_credits: statement.total_credits(),
+                total_debits: statement.total_debits(),
+                account_number: statement.account_number,
+                currency: statement.currency,
+            });
+        }
+        tx.commit().await?;
+        Ok(imported)
+    }
+
+    pub async fn set_originator(&self, _pool: &SqlitePool, bank_account_id: Uuid, req: SetOriginatorRequest) -> Result<Originator> {
+        let mut originator = self
+            .repo
+            .get_originator(bank_account_id)
+            .await?
+            .ok_or_else(|| Error::not_found("BankAccount", &bank_account_id.to_string()))?;
+        if req.company_name.trim().is_empty() {
+            return Err(Error::validation("Company name is required"));
+        }
+        if let Some(routing) = &req.routing_number {
+            nacha::check_routing(routing)?;
+        }
+        if let Some(iban) = &req.iban {
+            pain001::check_iban(iban)?;
+        }
+        if let Some(bic) = &req.bic {
+            pain001::check_bic(bic)?;
+        }
+        originator.company_name = req.company_name;
+        originator.routing_number = req.routing_number;
+        originator.ach_company_id = req.ach_company_id;
+        originator.iban = req.iban;
+        originator.bic = req.bic;
+        self.repo.update_originator(&originator).await
+    }
+
+    pub async fn set_vendor_bank_account(&self, _pool: &SqlitePool, vendor_id: Uuid, req: SetVendorBankAccountRequest) -> Result<VendorBankAccount> {
+        if self.repo.get_vendor_code(vendor_id).await?.is_none() {
+            return Err(Error::not_found("Vendor", &vendor_id.to_string()));
+        }
+        if req.account_name.trim().is_empty() {
+            return Err(Error::validation("Account name is required"));
+        }
+        if req.iban.is_none() && (req.routing_number.is_none() || req.account_number.is_none()) {
+            return Err(Error::validation("Either an IBAN or a routing and account number is required"));
+        }
+        if let Some(routing) = &req.routing_number {
+            nacha::check_routing(routing)?;
+        }
+        if let Some(iban) = &req.iban {
+            pain001::check_iban(iban)?;
+        }
+        if let Some(bic) = &req.bic {
+            pain001::check_bic(bic)?;
+        }
+        let account = VendorBankAccount {
+            vendor_id,
+            account_name: req.account_name,
+            account_type: req.account_type.unwrap_or(BankAccountType::Checking),
+            routing_number: req.routing_number,
+            account_number: req.account_number,
+            iban: req.iban,
+            bic: req.bic,
+            updated_at: Utc::now(),
+        };
+        self.repo.upsert_vendor_bank_account(&account).await
+    }
+
+    /// Builds a NACHA or pain.001 file paying approved vendor bills, one credit per vendor, and
+    /// records each bill's payment against the file. The payments, the file and its number
+    /// commit together.
+    pub async fn generate_payment_file(&self, pool: &SqlitePool, req: GeneratePaymentFileRequest) -> Result<GeneratedPaymentFile> {
+        let originator = self
+            .repo
+            .get_originator(req.bank_account_id)
+            .await?
+            .ok_or_else(|| Error::not_found("BankAccount", &req.bank_account_id.to_string()))?;
+        if originator.company_name.trim().is_empty() {
+            return Err(Error::business_rule(format!("Bank account {} has no originator details", originator.account_number)));
+        }
+        if req.bills.is_empty() {
+            return Err(Error::validation("Select at least one bill to pay"));
+        }
+        let today = Utc::now().date_naive();
+        if req.value_date < today {
+            return Err(Error::validation("Value date cannot be in the past"));
+        }
+
+        let mut payments: Vec<PaymentInstruction> = Vec::new();
+        let mut applications: Vec<(VendorBill, i64)> = Vec::with_capacity(req.bills.len());
+        for selection in &req.bills {
+            if applications.iter().any(|(bill, _)| bill.base.id == selection.bill_id) {
+                return Err(Error::validation(format!("Bill {} is selected twice", selection.bill_id)));
+            }
+            let bill = VendorBillService::get(pool, selection.bill_id).await?;
+            if bill.status != VendorBillStatus::Approved && bill.status != VendorBillStatus::PartiallyPaid {
+                return Err(Error::business_rule(format!("Bill {} is not approved for payment", bill.bill_number)));
+            }
+            if bill.total.currency.to_string() != originator.currency {
+                return Err(Error::business_rule(format!(
+                    "Bill {} is in {} but bank account {} pays in {}",
+                    bill.bill_number, bill.total.currency, originator.account_number, originator.currency
+                )));
+            }
+            let outstanding = bill.total.amount - bill.amount_paid.amount;
+            let amount = selection.amount.unwrap_or(outstanding);
+            if amount <= 0 || amount > outstanding {
+                return Err(Error::validation(format!(
+                    "Payment of {} on bill {} must be between 1 and the outstanding {}",
+                    amount, bill.bill_number, outstanding
+                )));
+            }
+            match payments.iter_mut().find(|p| p.vendor_id == bill.vendor_id) {
+                Some(payment) => {
+                    payment.amount += amount;
+                    payment.remittance.push(',');
+                    payment.remittance.push_str(&bill.vendor_invoice_number);
+                    payment.bill_ids.push(bill.base.id);
+                }
+                None => {
+                    let vendor_code = self
+                        .repo
+                        .get_vendor_code(bill.vendor_id)
+                        .await?
+                        .ok_or_else(|| Error::not_found("Vendor", &bill.vendor_id.to_string()))?;
+                    let payee = self
+                        .repo
+                        .get_vendor_bank_account(bill.vendor_id)
+                        .await?
+                        .ok_or_else(|| Error::business_rule(format!("Vendor {} has no bank account on file", vendor_code)))?;
+                    payments.push(PaymentInstruction {
+                        vendor_id: bill.vendor_id,
+                        vendor_code,
+                        payee,
+                        amount,
+                        end_to_end_id: String::new(),
+                        remittance: bill.vendor_invoice_number.clone(),
+                        bill_ids: vec![bill.base.id],
+                    });
+                }
+            }
+            applications.push((bill, amount));
+        }
+
+        // Claiming the bills first takes the database's write lock, so the count that numbers
+        // the file cannot be read by a concurrent run until this one commits.
+        let now = Utc::now();
+        let base = BaseEntity::new();
+        let mut tx = pool.begin().await?;
+        for (bill, amount) in &applications {
+            VendorBillService::record_payment_in(&mut tx, bill, base.id, *amount).await?;
+        }
+        let sequence = SqliteBankRepository::count_payment_files_in(&mut tx, req.bank_account_id, today).await?;
+        let file_number = format!("PAY-{}-{:03}", today.format("%Y%m%d"), sequence + 1);
+        for (index, payment) in payments.iter_mut().enumerate() {
+            payment.end_to_end_id = format!("{}-{:04}", file_number, index + 1);
+        }
+        let (file_type, content) = match req.format {
+            PaymentFileFormat::NACHA => {
+                let modifier = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"
+                    .get(sequence as usize)
+                    .map(|b| *b as char)
+                    .ok_or_else(|| Error::business_rule("No NACHA file ID modifiers are left for today"))?;
+                ("NACHA", nacha::write(&originator, &payments, req.value_date, now, modifier)?)
+            }
+            PaymentFileFormat::Pain001 => ("pain.001", pain001::write(&file_number, &originator, &payments, req.value_date, now)?),
+        };
+
         let file = PaymentFileGeneration {
-            base: BaseEntity::new(),
-            file_number: format!("PAY-{}", Uuid::new_v4()),
+            base,
+            file_number,
             bank_account_id: req.bank_account_id,
-            file_type: req.file_type,
-            file_date: Utc::now().date_naive(),
+            file_type: file_type.to_string(),
+            file_date: today,
             value_date: req.value_date,
-            currency: req.currency,
-            total_amount: req.total_amount,
-            payment_count: req.payment_count,
-            file_content: req.file_content,
+            currency: originator.currency.clone(),
+            total_amount: payments.iter().map(|p| p.amount).sum(),
+            payment_count: payments.len() as i32,
+            file_content: Some(content),
             file_path: None,
             status: PaymentFileStatus::Generated,
-            generated_at: Utc::now(),
+            generated_at: now,
             transmitted_at: None,
             acknowledged_at: None,
-            created_at: Utc::now(),
+            created_at: now,
         };
-        self.repo.create_payment_file(&file).await
-    }
+        let file = SqliteBankRepository::create_payment_file_in(&mut tx, &file).await?;
 
-    pub async fn parse_bai2(&self, _pool: &SqlitePool, content: &str) -> Result<Vec<BankTransaction>> {
-        let mut transactions = Vec::new();
-        for line in content.lines() {
-            if line.starts_with("16") {
-                let parts: Vec<&str> = line.split(',').collect();
-                if parts.len() >= 5 {
-                    let tx = BankTransaction {
-                        base: BaseEntity::new(),
-                        statement_id: Uuid::nil(),
-                        bank_account_id: Uuid::nil(),
-                        transaction_date: chrono::Utc::now().date_naive(),
-                        value_date: None,
-                        transaction_type: TransactionType::Credit,
-                        amount: parts.get(2).and_then(|s| s.parse().ok()).unwrap_or(0),
-                        currency: "USD".to_string(),
-                        reference_number: parts.get(3).map(|s| s.to_string()),
-                        bank_reference: None,
-                        customer_reference: None,
-                        description: parts.get(4).unwrap_or(&"").to_string(),
-                        payee_name: None,
-                        payee_account: None,
-                        check_number: None,
-                        additional_info: None,
-                        reconciliation_status: ReconciliationStatus::Unmatched,
-                        matched_entity_type: None,
-                        matched_entity_id: None,
-                        matched_amount: None,
-                        match_confidence: None,
-                        match_rule: None,
-                        journal_entry_id: None,
-                        notes: None,
-                        created_at: Utc::now(),
-                    };
-                    transactions.push(tx);
-                }
-            }
+        for (bill, amount) in &applications {
+            let end_to_end_id = payments
+                .iter()
+                .find(|p| p.vendor_id == bill.vendor_id)
+                .map(|p| p.end_to_end_id.as_str())
+                .unwrap_or_default();
+            SqliteBankRepository::create_payment_file_item_in(&mut tx, file.base.id, bill.vendor_id, bill.base.id, *amount, end_to_end_id).await?;
         }
-        Ok(transactions)
+        tx.commit().await?;
+
+        Ok(GeneratedPaymentFile { file, format: req.format, payments })
     }
 }
 
@@ -320,13 +513,42 @@ pub struct CreateMatchRequest {
     pub notes: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ImportStatementFileRequest {
+    pub format: StatementFormat,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetOriginatorRequest {
+    pub company_name: String,
+    pub routing_number: Option<String>,
+    pub ach_company_id: Option<String>,
+    pub iban: Option<String>,
+    pub bic: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetVendorBankAccountRequest {
+    pub account_name: String,
+    pub account_type: Option<BankAccountType>,
+    pub routing_number: Option<String>,
+    pub account_number: Option<String>,
+    pub iban: Option<String>,
+    pub bic: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GeneratePaymentFileRequest {
     pub bank_account_id: Uuid,
-    pub file_type: String,
+    pub format: PaymentFileFormat,
     pub value_date: NaiveDate,
-    pub currency: String,
-    pub total_amount: i64,
-    pub payment_count: i32,
-    pub file_content: Option<String>,
+    pub bills: Vec<BillPaymentSelection>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BillPaymentSelection {
+    pub bill_id: Uuid,
+    /// Defaults to the bill's outstanding balance.
+    pub amount: Option<i64>,
 }
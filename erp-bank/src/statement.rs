@@ -0,0 +1,102 @@
+use chrono::NaiveDate;
+use erp_core::{Error, Result};
+use erp_finance::BankTransactionImport;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{StatementFormat, TransactionType};
+
+/// One account's statement as read from a bank file, before it is stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedStatement {
+    pub format: StatementFormat,
+    /// The account number or IBAN as the bank reports it.
+    pub account_number: String,
+    pub currency: String,
+    pub statement_date: NaiveDate,
+    pub opening_balance: i64,
+    pub closing_balance: i64,
+    pub entries: Vec<StatementEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatementEntry {
+    pub booking_date: NaiveDate,
+    pub value_date: Option<NaiveDate>,
+    /// Credits are positive, debits negative.
+    pub amount: i64,
+    pub transaction_type: TransactionType,
+    /// The bank's own code for the entry: a BAI2 type code, an MT940 `N` code or a camt
+    /// domain/family/sub-family.
+    pub type_code: String,
+    pub bank_reference: Option<String>,
+    pub customer_reference: Option<String>,
+    pub description: String,
+}
+
+impl ParsedStatement {
+    pub fn total_credits(&self) -> i64 {
+        self.entries.iter().map(|e| e.amount).filter(|a| *a > 0).sum()
+    }
+
+    pub fn total_debits(&self) -> i64 {
+        -self.entries.iter().map(|e| e.amount).filter(|a| *a < 0).sum::<i64>()
+    }
+
+    /// Opening balance plus entries must give the closing balance.
+    pub fn check_balances(&self) -> Result<()> {
+        let computed = self.opening_balance + self.entries.iter().map(|e| e.amount).sum::<i64>();
+        if computed != self.closing_balance {
+            return Err(Error::validation(format!(
+                "Statement for account {} on {} does not balance: opening {} plus entries gives {}, closing is {}",
+                self.account_number, self.statement_date, self.opening_balance, computed, self.closing_balance
+            )));
+        }
+        Ok(())
+    }
+
+    /// The entries in the shape `BankReconciliationService::import_statement` takes, each
+    /// carrying the running balance after it.
+    pub fn to_imports(&self) -> Vec<BankTransactionImport> {
+        let mut balance = self.opening_balance;
+        self.entries
+            .iter()
+            .map(|entry| {
+                balance += entry.amount;
+                BankTransactionImport {
+                    transaction_date: midnight(entry.booking_date),
+                    value_date: entry.value_date.map(midnight),
+                    description: Some(entry.description.clone()).filter(|d| !d.is_empty()),
+                    reference: entry.bank_reference.clone().or_else(|| entry.customer_reference.clone()),
+                    debit: if entry.amount < 0 { -entry.amount } else { 0 },
+                    credit: if entry.amount > 0 { entry.amount } else { 0 },
+                    balance,
+                }
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn midnight(date: NaiveDate) -> chrono::DateTime<chrono::Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+/// Parses a decimal amount such as `1234,56` or `1234.5` into cents.
+pub(crate) fn parse_decimal(value: &str, separator: char) -> Result<i64> {
+    let invalid = || Error::validation(format!("Invalid amount '{}'", value));
+    let (whole, fraction) = value.trim().split_once(separator).unwrap_or((value.trim(), ""));
+    if whole.is_empty() && fraction.is_empty() || fraction.len() > 2 {
+        return Err(invalid());
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let whole: i64 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| invalid())? };
+    let cents: i64 = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+    whole.checked_mul(100).and_then(|w| w.checked_add(cents)).ok_or_else(invalid)
+}
+
+/// Parses a six-digit `YYMMDD` date.
+pub(crate) fn parse_yymmdd(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("20{}", value.trim()), "%Y%m%d")
+        .map_err(|_| Error::validation(format!("Invalid date '{}', expected YYMMDD", value)))
+}
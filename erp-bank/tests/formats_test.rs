@@ -0,0 +1,221 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use erp_bank::{bai2, camt053, mt940, nacha, pain001};
+use erp_bank::{BankAccountType, Originator, PaymentInstruction, StatementFormat, TransactionType, VendorBankAccount};
+use uuid::Uuid;
+
+const BAI2: &str = "01,SENDER,RECEIVER,261018,0800,1,,,2/
+02,RECEIVER,BANKID,1,261017,2400,USD,2/
+03,123456789,USD,010,100000,,,015,125000,,/
+16,165,50000,V,261018,,BREF1,CREF1,ACH CREDIT FROM
+88,CUSTOMER ONE
+16,475,25000,Z,CHK2,,CHECK 1001
+49,300000,5/
+98,300000,1,7/
+99,300000,1,9/
+";
+
+#[test]
+fn reads_bai2_with_continuations_and_control_totals() {
+    let statements = bai2::parse(BAI2).unwrap();
+    assert_eq!(statements.len(), 1);
+    let statement = &statements[0];
+    assert_eq!(statement.format, StatementFormat::BAI2);
+    assert_eq!((statement.account_number.as_str(), statement.currency.as_str()), ("123456789", "USD"));
+    assert_eq!(statement.statement_date, NaiveDate::from_ymd_opt(2026, 10, 17).unwrap());
+    assert_eq!((statement.opening_balance, statement.closing_balance), (100000, 125000));
+    statement.check_balances().unwrap();
+
+    let ach = &statement.entries[0];
+    assert_eq!((ach.amount, ach.transaction_type), (50000, TransactionType::ACH));
+    assert_eq!(ach.value_date, NaiveDate::from_ymd_opt(2026, 10, 18));
+    assert_eq!(ach.description, "ACH CREDIT FROM CUSTOMER ONE");
+    assert_eq!(ach.customer_reference.as_deref(), Some("CREF1"));
+    let check = &statement.entries[1];
+    assert_eq!((check.amount, check.transaction_type), (-25000, TransactionType::Check));
+
+    let imports = statement.to_imports();
+    assert_eq!((imports[1].debit, imports[1].balance), (25000, 125000));
+
+    let tampered = BAI2.replace("49,300000,5/", "49,300001,5/");
+    let error = bai2::parse(&tampered).unwrap_err().to_string();
+    assert!(error.contains("Line 7 (49 record)"), "{}", error);
+}
+
+#[test]
+fn reads_mt940_with_envelope_reversals_and_narrative() {
+    let content = "{1:F01BANKBEBBAXXX0000000000}{2:O9401200261018BANKBEBBAXXX00000000002610181200N}{4:
+:20:STMT261018
+:25:BE68539007547034
+:28C:1/1
+:60F:C261017EUR1000,00
+:61:2610181018C250,50NTRFINV-1001//BREF77
+:86:Payment from Customer
+ Two lines
+:61:261018D20,00NCHGNONREF
+:86:Bank fees
+:61:261018RC5,00NTRFNONREF
+:62F:C261018EUR1225,50
+-}";
+    let statements = mt940::parse(content).unwrap();
+    let statement = &statements[0];
+    assert_eq!((statement.account_number.as_str(), statement.currency.as_str()), ("BE68539007547034", "EUR"));
+    assert_eq!((statement.opening_balance, statement.closing_balance), (100000, 122550));
+    statement.check_balances().unwrap();
+
+    let transfer = &statement.entries[0];
+    assert_eq!((transfer.amount, transfer.transaction_type), (25050, TransactionType::WireTransfer));
+    assert_eq!(transfer.customer_reference.as_deref(), Some("INV-1001"));
+    assert_eq!(transfer.bank_reference.as_deref(), Some("BREF77"));
+    assert_eq!(transfer.description, "Payment from Customer Two lines");
+    assert_eq!(statement.entries[1].transaction_type, TransactionType::Fee);
+    assert_eq!(statement.entries[1].customer_reference, None);
+    assert_eq!((statement.entries[2].amount, statement.entries[2].transaction_type), (-500, TransactionType::Reversal));
+}
+
+#[test]
+fn reads_camt053_booked_entries_only() {
+    let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+  <BkToCstmrStmt>
+    <GrpHdr><MsgId>MSG1</MsgId><CreDtTm>2026-10-18T06:00:00</CreDtTm></GrpHdr>
+    <Stmt>
+      <Id>STMT-1</Id>
+      <Acct><Id><IBAN>DE89370400440532013000</IBAN></Id><Ccy>EUR</Ccy></Acct>
+      <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">1000.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2026-10-17</Dt></Dt></Bal>
+      <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">1150.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2026-10-18</Dt></Dt></Bal>
+      <Ntry>
+        <Amt Ccy="EUR">200.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Sts>BOOK</Sts>
+        <BookgDt><Dt>2026-10-18</Dt></BookgDt><ValDt><Dt>2026-10-19</Dt></ValDt>
+        <AcctSvcrRef>SVC1</AcctSvcrRef>
+        <BkTxCd><Domn><Cd>PMNT</Cd><Fmly><Cd>RCDT</Cd><SubFmlyCd>ESCT</SubFmlyCd></Fmly></Domn></BkTxCd>
+        <NtryDtls><TxDtls>
+          <Refs><EndToEndId>E2E-1</EndToEndId></Refs>
+          <RltdPties><Dbtr><Nm>Customer &amp; Co</Nm></Dbtr></RltdPties>
+          <RmtInf><Ustrd>INV 7</Ustrd></RmtInf>
+        </TxDtls></NtryDtls>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">50.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts>BOOK</Sts>
+        <BookgDt><Dt>2026-10-18</Dt></BookgDt>
+        <BkTxCd><Domn><Cd>ACMT</Cd><Fmly><Cd>MDOP</Cd><SubFmlyCd>CHRG</SubFmlyCd></Fmly></Domn></BkTxCd>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">999.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Sts>PDNG</Sts>
+      </Ntry>
+    </Stmt>
+  </BkToCstmrStmt>
+</Document>"#;
+    let statements = camt053::parse(content).unwrap();
+    let statement = &statements[0];
+    assert_eq!(statement.account_number, "DE89370400440532013000");
+    assert_eq!(statement.statement_date, NaiveDate::from_ymd_opt(2026, 10, 18).unwrap());
+    assert_eq!(statement.entries.len(), 2);
+    statement.check_balances().unwrap();
+
+    let credit = &statement.entries[0];
+    assert_eq!((credit.amount, credit.transaction_type), (20000, TransactionType::WireTransfer));
+    assert_eq!(credit.type_code, "PMNT/RCDT/ESCT");
+    assert_eq!(credit.description, "Customer & Co - INV 7");
+    assert_eq!(credit.customer_reference.as_deref(), Some("E2E-1"));
+    assert_eq!(credit.bank_reference.as_deref(), Some("SVC1"));
+    assert_eq!(statement.entries[1].transaction_type, TransactionType::Fee);
+    assert_eq!((statement.total_credits(), statement.total_debits()), (20000, 5000));
+}
+
+fn originator() -> Originator {
+    Originator {
+        bank_account_id: Uuid::new_v4(),
+        company_name: "Acme Manufacturing".to_string(),
+        bank_name: "First Bank".to_string(),
+        account_number: "987654321".to_string(),
+        currency: "USD".to_string(),
+        routing_number: Some("021000021".to_string()),
+        ach_company_id: Some("1234567890".to_string()),
+        iban: None,
+        bic: None,
+    }
+}
+
+fn payment(code: &str, routing: &str, account_type: BankAccountType, amount: i64) -> PaymentInstruction {
+    PaymentInstruction {
+        vendor_id: Uuid::new_v4(),
+        vendor_code: code.to_string(),
+        payee: VendorBankAccount {
+            vendor_id: Uuid::new_v4(),
+            account_name: format!("{} Supplies", code),
+            account_type,
+            routing_number: Some(routing.to_string()),
+            account_number: Some("000123456".to_string()),
+            iban: None,
+            bic: None,
+            updated_at: Utc::now(),
+        },
+        amount,
+        end_to_end_id: format!("PAY-20261018-001-{}", code),
+        remittance: "INV-1,INV-2".to_string(),
+        bill_ids: vec![Uuid::new_v4()],
+    }
+}
+
+#[test]
+fn writes_balanced_nacha_files() {
+    let payments = vec![
+        payment("V001", "011000015", BankAccountType::Checking, 150000),
+        payment("V002", "121000358", BankAccountType::Savings, 2550),
+    ];
+    let created = Utc.with_ymd_and_hms(2026, 10, 18, 9, 30, 0).unwrap();
+    let file = nacha::write(&originator(), &payments, NaiveDate::from_ymd_opt(2026, 10, 20).unwrap(), created, 'a').unwrap();
+    let records: Vec<&str> = file.lines().collect();
+
+    assert_eq!(records.len(), 10);
+    assert!(records.iter().all(|r| r.len() == 94));
+    assert!(records[0].starts_with("101 021000021 0210000212610180930A094101"));
+    assert!(records[1].starts_with("5220ACME MANUFACTURI"));
+    assert_eq!(&records[1][50..53], "CCD");
+    assert_eq!(&records[2][..12], "622011000015");
+    assert_eq!(&records[2][29..39], "0000150000");
+    assert_eq!(&records[3][..17], "705INV-1,INV-2   ");
+    assert_eq!(&records[4][..3], "632");
+    assert_eq!(&records[6][..54], "822000000400132000360000000000000000001525501234567890");
+    assert_eq!(&records[7][..55], "9000001000001000000040013200036000000000000000000152550");
+    assert_eq!(records[8], "9".repeat(94));
+
+    let mut bad = payments.clone();
+    bad[1].payee.routing_number = Some("121000359".to_string());
+    let error = nacha::write(&originator(), &bad, NaiveDate::from_ymd_opt(2026, 10, 20).unwrap(), created, 'A').unwrap_err();
+    assert!(error.to_string().contains("check digit"), "{}", error);
+}
+
+#[test]
+fn writes_sepa_credit_transfers() {
+    let debtor = Originator {
+        currency: "EUR".to_string(),
+        iban: Some("BE68 5390 0754 7034".to_string()),
+        bic: Some("GEBABEBB".to_string()),
+        ..originator()
+    };
+    let mut vendor = payment("V001", "011000015", BankAccountType::Checking, 123456);
+    vendor.payee.account_name = "Müller & Söhne".to_string();
+    vendor.payee.iban = Some("DE89370400440532013000".to_string());
+    vendor.payee.bic = Some("COBADEFFXXX".to_string());
+    let created = Utc.with_ymd_and_hms(2026, 10, 18, 9, 30, 0).unwrap();
+    let xml = pain001::write("PAY-20261018-001", &debtor, &[vendor.clone()], NaiveDate::from_ymd_opt(2026, 10, 20).unwrap(), created).unwrap();
+
+    let mut reader = quick_xml::Reader::from_str(&xml);
+    loop {
+        match reader.read_event().unwrap() {
+            quick_xml::events::Event::Eof => break,
+            _ => continue,
+        }
+    }
+    assert!(xml.contains("<NbOfTxs>1</NbOfTxs><CtrlSum>1234.56</CtrlSum>"));
+    assert!(xml.contains("<SvcLvl><Cd>SEPA</Cd></SvcLvl>"));
+    assert!(xml.contains("<DbtrAcct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></DbtrAcct>"));
+    assert!(xml.contains("<InstdAmt Ccy=\"EUR\">1234.56</InstdAmt>"));
+    assert!(xml.contains("<Cdtr><Nm>Müller &amp; Söhne</Nm></Cdtr>"));
+    assert!(xml.contains("<ChrgBr>SLEV</ChrgBr>"));
+
+    vendor.payee.iban = Some("DE89370400440532013001".to_string());
+    assert!(pain001::write("PAY-1", &debtor, &[vendor], NaiveDate::from_ymd_opt(2026, 10, 20).unwrap(), created).is_err());
+    assert!(pain001::check_bic("GEBA1EBB").is_err());
+}
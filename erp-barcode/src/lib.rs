@@ -1,6 +1,7 @@
 pub mod models;
 pub mod repository;
 pub mod service;
+pub mod symbology;
 pub use models::*;
 pub use repository::*;
 pub use service::*;
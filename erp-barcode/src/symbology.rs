@@ -0,0 +1,226 @@
+//! Bar and module patterns for printing barcodes. Quiet zones are left to whoever draws them.
+
+use erp_core::{Error, Result};
+use qrcode::{EcLevel, QrCode};
+
+use crate::models::BarcodeType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Symbol {
+    /// Dark and light modules from left to right, with the text to print beneath them.
+    Linear { modules: Vec<bool>, text: String },
+    /// A square of `size` by `size` modules, row by row.
+    Matrix { size: usize, modules: Vec<bool> },
+}
+
+impl Symbol {
+    /// Modules of blank margin the symbology needs around it.
+    pub fn quiet_zone(&self) -> usize {
+        match self {
+            Symbol::Linear { .. } => 10,
+            Symbol::Matrix { .. } => 4,
+        }
+    }
+}
+
+pub fn encode(barcode_type: &BarcodeType, value: &str) -> Result<Symbol> {
+    match barcode_type {
+        BarcodeType::Code128 => code128(value),
+        BarcodeType::Code39 => code39(value),
+        BarcodeType::EAN13 => ean(value, 13),
+        BarcodeType::EAN8 => ean(value, 8),
+        BarcodeType::UpcA => {
+            let upc = ean(&format!("0{}", value), 13)?;
+            match upc {
+                Symbol::Linear { modules, text } => Ok(Symbol::Linear { modules, text: text[1..].to_string() }),
+                matrix => Ok(matrix),
+            }
+        }
+        BarcodeType::ITF14 => itf14(value),
+        BarcodeType::QRCode => {
+            let code = QrCode::with_error_correction_level(value.as_bytes(), EcLevel::M)
+                .map_err(|e| Error::validation(format!("Cannot encode QR code: {}", e)))?;
+            Ok(Symbol::Matrix {
+                size: code.width(),
+                modules: code.to_colors().into_iter().map(|c| c == qrcode::Color::Dark).collect(),
+            })
+        }
+        other => Err(Error::validation(format!("{:?} barcodes cannot be printed", other))),
+    }
+}
+
+/// Bar and space widths of every Code 128 symbol value, then the stop pattern.
+#[rustfmt::skip]
+const CODE128: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212", "221213",
+    "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221", "223211", "221132",
+    "221231", "213212", "223112", "312131", "311222", "321122", "321221", "312212", "322112", "322211",
+    "212123", "212321", "232121", "111323", "131123", "131321", "112313", "132113", "132311", "211313",
+    "231113", "231311", "112133", "112331", "132131", "113123", "113321", "133121", "313121", "211331",
+    "231131", "213113", "213311", "213131", "311123", "311321", "331121", "312113", "312311", "332111",
+    "314111", "221411", "431111", "111224", "111422", "121124", "121421", "141122", "141221", "112214",
+    "112412", "122114", "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111",
+    "111242", "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311", "113141",
+    "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+
+const START_B: usize = 104;
+const START_C: usize = 105;
+const CODE_B: usize = 100;
+
+/// Code set C for values that are all digits, two to a symbol, else code set B.
+fn code128(value: &str) -> Result<Symbol> {
+    if value.is_empty() {
+        return Err(Error::validation("A Code 128 barcode needs a value"));
+    }
+    let digits = value.bytes().all(|b| b.is_ascii_digit());
+    let mut values = Vec::new();
+    if digits && value.len() >= 4 {
+        values.push(START_C);
+        let pairs = value.len() / 2;
+        for pair in value.as_bytes()[..pairs * 2].chunks(2) {
+            values.push(((pair[0] - b'0') * 10 + (pair[1] - b'0')) as usize);
+        }
+        if value.len() % 2 == 1 {
+            values.push(CODE_B);
+            values.push((value.as_bytes()[value.len() - 1] - b' ') as usize);
+        }
+    } else {
+        values.push(START_B);
+        for c in value.chars() {
+            if !(' '..='~').contains(&c) {
+                return Err(Error::validation(format!("Code 128 cannot encode {:?}", c)));
+            }
+            values.push(c as usize - ' ' as usize);
+        }
+    }
+    let checksum = values.iter().enumerate().map(|(i, v)| i.max(1) * v).sum::<usize>() % 103;
+    values.push(checksum);
+    values.push(CODE128.len() - 1);
+
+    let mut modules = Vec::new();
+    for value in values {
+        for (i, width) in CODE128[value].bytes().enumerate() {
+            modules.extend(std::iter::repeat_n(i % 2 == 0, (width - b'0') as usize));
+        }
+    }
+    Ok(Symbol::Linear { modules, text: value.to_string() })
+}
+
+/// Narrow and wide elements of each Code 39 character, bars and spaces alternating.
+const CODE39: [(char, &str); 44] = [
+    ('0', "nnnwwnwnn"), ('1', "wnnwnnnnw"), ('2', "nnwwnnnnw"), ('3', "wnwwnnnnn"), ('4', "nnnwwnnnw"),
+    ('5', "wnnwwnnnn"), ('6', "nnwwwnnnn"), ('7', "nnnwnnwnw"), ('8', "wnnwnnwnn"), ('9', "nnwwnnwnn"),
+    ('A', "wnnnnwnnw"), ('B', "nnwnnwnnw"), ('C', "wnwnnwnnn"), ('D', "nnnnwwnnw"), ('E', "wnnnwwnnn"),
+    ('F', "nnwnwwnnn"), ('G', "nnnnnwwnw"), ('H', "wnnnnwwnn"), ('I', "nnwnnwwnn"), ('J', "nnnnwwwnn"),
+    ('K', "wnnnnnnww"), ('L', "nnwnnnnww"), ('M', "wnwnnnnwn"), ('N', "nnnnwnnww"), ('O', "wnnnwnnwn"),
+    ('P', "nnwnwnnwn"), ('Q', "nnnnnnwww"), ('R', "wnnnnnwwn"), ('S', "nnwnnnwwn"), ('T', "nnnnwnwwn"),
+    ('U', "wwnnnnnnw"), ('V', "nwwnnnnnw"), ('W', "wwwnnnnnn"), ('X', "nwnnwnnnw"), ('Y', "wwnnwnnnn"),
+    ('Z', "nwwnwnnnn"), ('-', "nwnnnnwnw"), ('.', "wwnnnnwnn"), (' ', "nwwnnnwnn"), ('*', "nwnnwnwnn"),
+    ('$', "nwnwnwnnn"), ('/', "nwnwnnnwn"), ('+', "nwnnnwnwn"), ('%', "nnnwnwnwn"),
+];
+
+fn code39(value: &str) -> Result<Symbol> {
+    let value = value.to_uppercase();
+    if value.is_empty() || value.contains('*') {
+        return Err(Error::validation("A Code 39 barcode needs a value without '*'"));
+    }
+    let mut modules = Vec::new();
+    for c in format!("*{}*", value).chars() {
+        let (_, pattern) = CODE39.iter().find(|(k, _)| *k == c)
+            .ok_or_else(|| Error::validation(format!("Code 39 cannot encode {:?}", c)))?;
+        push_elements(&mut modules, pattern);
+        // Characters are separated by a narrow space.
+        modules.push(false);
+    }
+    modules.pop();
+    Ok(Symbol::Linear { modules, text: value })
+}
+
+/// Bars and spaces from narrow and wide elements, a wide one three modules across.
+fn push_elements(modules: &mut Vec<bool>, pattern: &str) {
+    for (i, element) in pattern.chars().enumerate() {
+        let width = if element == 'w' { 3 } else { 1 };
+        modules.extend(std::iter::repeat_n(i % 2 == 0, width));
+    }
+}
+
+/// Left-hand odd parity patterns. Right-hand patterns are their complement and even parity
+/// ones the complement reversed.
+const EAN_L: [&str; 10] = [
+    "0001101", "0011001", "0010011", "0111101", "0100011", "0110001", "0101111", "0111011", "0110111", "0001011",
+];
+
+/// Which left-hand digits of an EAN-13 take even parity, by the leading digit.
+const EAN_PARITY: [&str; 10] = [
+    "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL", "LGGLGL",
+];
+
+/// EAN-13 or EAN-8 from its digits, with or without the check digit.
+fn ean(value: &str, length: usize) -> Result<Symbol> {
+    if !value.bytes().all(|b| b.is_ascii_digit()) || !(length - 1..=length).contains(&value.len()) {
+        return Err(Error::validation(format!("An EAN-{} barcode needs {} or {} digits", length, length - 1, length)));
+    }
+    let mut digits: Vec<usize> = value.bytes().map(|b| (b - b'0') as usize).collect();
+    let check = check_digit(&digits[..length - 1]);
+    match digits.get(length - 1) {
+        Some(&given) if given != check => return Err(Error::validation(format!("Invalid check digit, expected {}", check))),
+        Some(_) => {}
+        None => digits.push(check),
+    }
+
+    let (parity, left, right) = if length == 13 {
+        (EAN_PARITY[digits[0]], &digits[1..7], &digits[7..])
+    } else {
+        ("LLLL", &digits[..4], &digits[4..])
+    };
+    let mut modules = Vec::new();
+    let bits = |modules: &mut Vec<bool>, pattern: &str| modules.extend(pattern.chars().map(|c| c == '1'));
+    bits(&mut modules, "101");
+    for (digit, set) in left.iter().zip(parity.chars()) {
+        let pattern: String = match set {
+            'L' => EAN_L[*digit].to_string(),
+            _ => EAN_L[*digit].chars().rev().map(|c| if c == '1' { '0' } else { '1' }).collect(),
+        };
+        bits(&mut modules, &pattern);
+    }
+    bits(&mut modules, "01010");
+    for digit in right {
+        let pattern: String = EAN_L[*digit].chars().map(|c| if c == '1' { '0' } else { '1' }).collect();
+        bits(&mut modules, &pattern);
+    }
+    bits(&mut modules, "101");
+    Ok(Symbol::Linear { modules, text: digits.iter().map(|d| d.to_string()).collect() })
+}
+
+/// GS1 mod-10 check digit: weights of 3 and 1 alternate from the rightmost digit.
+fn check_digit(digits: &[usize]) -> usize {
+    let sum: usize = digits.iter().rev().enumerate().map(|(i, d)| if i % 2 == 0 { d * 3 } else { *d }).sum();
+    (10 - sum % 10) % 10
+}
+
+/// Interleaved 2 of 5 patterns, the first digit of each pair in the bars and the second in
+/// the spaces.
+const ITF: [&str; 10] = ["nnwwn", "wnnnw", "nwnnw", "wwnnn", "nnwnw", "wnwnn", "nwwnn", "nnnww", "wnnwn", "nwnwn"];
+
+fn itf14(value: &str) -> Result<Symbol> {
+    if !value.bytes().all(|b| b.is_ascii_digit()) || !(13..=14).contains(&value.len()) {
+        return Err(Error::validation("An ITF-14 barcode needs 13 or 14 digits"));
+    }
+    let mut digits: Vec<usize> = value.bytes().map(|b| (b - b'0') as usize).collect();
+    let check = check_digit(&digits[..13]);
+    match digits.get(13) {
+        Some(&given) if given != check => return Err(Error::validation(format!("Invalid check digit, expected {}", check))),
+        Some(_) => {}
+        None => digits.push(check),
+    }
+    let mut modules = Vec::new();
+    push_elements(&mut modules, "nnnn");
+    for pair in digits.chunks(2) {
+        let interleaved: String = ITF[pair[0]].chars().zip(ITF[pair[1]].chars()).flat_map(|(b, s)| [b, s]).collect();
+        push_elements(&mut modules, &interleaved);
+    }
+    push_elements(&mut modules, "wnn");
+    Ok(Symbol::Linear { modules, text: digits.iter().map(|d| d.to_string()).collect() })
+}
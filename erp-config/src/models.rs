@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
-use erp_core::BaseEntity;
+use erp_core::blob::{BlobStore, LocalBlobStore, S3BlobStore, S3Config, UploadPolicy};
+use erp_core::{BaseEntity, Error, Result};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -137,6 +139,43 @@ pub struct StorageConfig {
     pub is_active: bool,
 }
 
+impl StorageConfig {
+    /// Connects to the configured storage. Only local and S3-compatible storage are supported.
+    pub fn blob_store(&self) -> Result<Arc<dyn BlobStore>> {
+        match self.storage_type {
+            StorageType::Local => Ok(Arc::new(LocalBlobStore::new(
+                self.base_path.clone().unwrap_or_else(|| "storage".to_string()),
+            ))),
+            StorageType::S3 => {
+                let region = self.region.clone().unwrap_or_else(|| "us-east-1".to_string());
+                let required = |value: &Option<String>, field: &str| {
+                    value.clone().filter(|v| !v.is_empty()).ok_or_else(|| {
+                        Error::validation(format!("Storage '{}' needs {} for S3", self.name, field))
+                    })
+                };
+                Ok(Arc::new(S3BlobStore::new(S3Config {
+                    endpoint: self.endpoint.clone()
+                        .unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", region)),
+                    bucket: required(&self.bucket, "bucket")?,
+                    access_key: required(&self.access_key, "access_key")?,
+                    secret_key: required(&self.secret_key, "secret_key")?,
+                    region,
+                })))
+            }
+            _ => Err(Error::validation(format!(
+                "Storage type {:?} is not supported", self.storage_type
+            ))),
+        }
+    }
+
+    pub fn upload_policy(&self) -> UploadPolicy {
+        UploadPolicy {
+            max_size: self.max_file_size.max(0) as u64,
+            allowed_mime_types: UploadPolicy::parse_mime_types(self.allowed_types.as_deref()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum StorageType {
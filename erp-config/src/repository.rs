@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use sqlx::{SqlitePool, Row};
+use sqlx::{SqliteConnection, SqlitePool, Row};
 use uuid::Uuid;
 use erp_core::Result;
 use crate::models::*;
@@ -32,6 +32,40 @@ pub trait ConfigRepository: Send + Sync {
 
 pub struct SqliteConfigRepository;
 
+impl SqliteConfigRepository {
+    /// Draws the next number on the caller's connection, so a document that rolls back gives its
+    /// number back instead of leaving a gap.
+    pub async fn get_next_number_in(&self, conn: &mut SqliteConnection, code: &str) -> Result<String> {
+        // Advancing and reading the counter in one statement keeps concurrent callers from
+        // drawing the same number.
+        let row = sqlx::query(
+            r#"UPDATE number_sequences SET current_value = COALESCE(current_value, 0) + COALESCE(increment, 1), updated_at = ?
+               WHERE code = ? AND is_active = 1
+               RETURNING prefix, suffix, current_value, padding, format"#
+        )
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(code)
+        .fetch_optional(&mut *conn).await?
+        .ok_or_else(|| erp_core::Error::not_found("NumberSequence", code))?;
+        
+        let next_value: i64 = row.get("current_value");
+        let padding: Option<i32> = row.get("padding");
+        let number_str = format!("{:0>width$}", next_value, width = padding.unwrap_or(0).max(0) as usize);
+        let prefix: Option<String> = row.get("prefix");
+        let suffix: Option<String> = row.get("suffix");
+        let format: Option<String> = row.get("format");
+        
+        let result = match format.filter(|f| !f.is_empty()) {
+            Some(fmt) => fmt.replace("{number}", &number_str)
+                .replace("{prefix}", prefix.as_deref().unwrap_or(""))
+                .replace("{suffix}", suffix.as_deref().unwrap_or("")),
+            None => format!("{}{}{}", prefix.unwrap_or_default(), number_str, suffix.unwrap_or_default()),
+        };
+        
+        Ok(result)
+    }
+}
+
 #[async_trait]
 impl ConfigRepository for SqliteConfigRepository {
     async fn get_config(&self, pool: &SqlitePool, category: &str, key: &str) -> Result<Option<SystemConfig>> {
@@ -262,35 +296,7 @@ impl ConfigRepository for SqliteConfigRepository {
     }
 
     async fn get_next_number(&self, pool: &SqlitePool, code: &str) -> Result<String> {
-        let row = sqlx::query(
-            r#"SELECT id, name, code, prefix, suffix, current_value, increment, padding, format
-               FROM number_sequences WHERE code = ? AND is_active = 1"#
-        )
-        .bind(code)
-        .fetch_one(pool).await?;
-        
-        let current_value: i64 = row.get("current_value");
-        let increment: i32 = row.get("increment");
-        let next_value = current_value + increment as i64;
-        let padding: i32 = row.get("padding");
-        let number_str = format!("{:0>width$}", next_value, width = padding as usize);
-        
-        let result = if let Ok(fmt) = row.try_get::<&str, _>("format") {
-            fmt.replace("{number}", &number_str)
-                .replace("{prefix}", row.get::<Option<&str>, _>("prefix").unwrap_or(""))
-                .replace("{suffix}", row.get::<Option<&str>, _>("suffix").unwrap_or(""))
-        } else {
-            format!("{}{}{}", row.get::<Option<&str>, _>("prefix").unwrap_or_default(), number_str, row.get::<Option<&str>, _>("suffix").unwrap_or_default())
-        };
-        
-        sqlx::query(
-            r#"UPDATE number_sequences SET current_value = ? WHERE code = ?"#
-        )
-        .bind(next_value)
-        .bind(code)
-        .execute(pool).await?;
-        
-        Ok(result)
+        self.get_next_number_in(&mut *pool.acquire().await?, code).await
     }
 
     async fn create_email_config(&self, pool: &SqlitePool, config: EmailConfig) -> Result<EmailConfig> {
@@ -391,7 +397,14 @@ impl ConfigRepository for SqliteConfigRepository {
                 created_by: None,
                 updated_by: None,
             },
-            storage_type: StorageType::Local,
+            storage_type: match r.get::<&str, _>("storage_type") {
+                "S3" => StorageType::S3,
+                "Azure" => StorageType::Azure,
+                "GCS" => StorageType::GCS,
+                "FTP" => StorageType::FTP,
+                "SFTP" => StorageType::SFTP,
+                _ => StorageType::Local,
+            },
             name: r.get("name"),
             endpoint: r.get("endpoint"),
             bucket: r.get("bucket"),
@@ -1,4 +1,4 @@
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use erp_core::{Result, BaseEntity};
 use crate::models::*;
@@ -81,6 +81,10 @@ impl ConfigService {
         self.repo.get_next_number(pool, code).await
     }
 
+    pub async fn get_next_number_in(&self, conn: &mut SqliteConnection, code: &str) -> Result<String> {
+        self.repo.get_next_number_in(conn, code).await
+    }
+
     pub async fn create_email_config(&self, pool: &SqlitePool, config: EmailConfig) -> Result<EmailConfig> {
         self.repo.create_email_config(pool, config).await
     }
@@ -97,6 +101,13 @@ impl ConfigService {
         self.repo.list_storage_configs(pool).await
     }
 
+    /// The active storage marked as default, else the first active one.
+    pub async fn default_storage_config(&self, pool: &SqlitePool) -> Result<Option<StorageConfig>> {
+        let mut configs = self.repo.list_storage_configs(pool).await?;
+        let index = configs.iter().position(|c| c.is_default).unwrap_or(0);
+        Ok((index < configs.len()).then(|| configs.swap_remove(index)))
+    }
+
     pub async fn create_payment_gateway(&self, pool: &SqlitePool, gateway: PaymentGateway) -> Result<PaymentGateway> {
         self.repo.create_payment_gateway(pool, gateway).await
     }
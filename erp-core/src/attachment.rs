@@ -1,3 +1,4 @@
+use crate::blob::{BlobService, StagedBlob};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -12,6 +13,7 @@ pub struct Attachment {
     pub mime_type: String,
     pub file_size: i64,
     pub file_path: String,
+    pub checksum: Option<String>,
     pub uploaded_by: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
@@ -45,6 +47,7 @@ impl Attachment {
             mime_type: mime_type.to_string(),
             file_size,
             file_path: format!("uploads/{}/{}", entity_type, filename),
+            checksum: None,
             uploaded_by,
             created_at: Utc::now(),
         }
@@ -60,7 +63,7 @@ impl AttachmentService {
         entity_id: &str,
     ) -> crate::Result<Vec<Attachment>> {
         let rows = sqlx::query_as::<_, AttachmentRow>(
-            "SELECT id, entity_type, entity_id, filename, original_filename, mime_type, file_size, file_path, uploaded_by, created_at
+            "SELECT id, entity_type, entity_id, filename, original_filename, mime_type, file_size, file_path, checksum, uploaded_by, created_at
              FROM attachments WHERE entity_type = ? AND entity_id = ? ORDER BY created_at DESC"
         )
         .bind(entity_type)
@@ -72,13 +75,24 @@ impl AttachmentService {
         Ok(rows.into_iter().map(|r| r.into()).collect())
     }
 
+    /// Stores `upload` and records it as `attachment`, taking its size, type and location
+    /// from the stored blob.
     pub async fn create(
         pool: &sqlx::SqlitePool,
-        attachment: Attachment,
+        blobs: &BlobService,
+        mut attachment: Attachment,
+        upload: StagedBlob,
+        expected_sha256: Option<&str>,
     ) -> crate::Result<Attachment> {
-        sqlx::query(
-            "INSERT INTO attachments (id, entity_type, entity_id, filename, original_filename, mime_type, file_size, file_path, uploaded_by, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        let blob = blobs.save(pool, upload, &attachment.mime_type, blobs.default_policy(), expected_sha256).await?;
+        attachment.mime_type = blob.mime_type.clone();
+        attachment.file_size = blob.size;
+        attachment.file_path = blob.key();
+        attachment.checksum = Some(blob.sha256.clone());
+
+        let inserted = sqlx::query(
+            "INSERT INTO attachments (id, entity_type, entity_id, filename, original_filename, mime_type, file_size, file_path, checksum, uploaded_by, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(attachment.id.to_string())
         .bind(&attachment.entity_type)
@@ -88,12 +102,17 @@ impl AttachmentService {
         .bind(&attachment.mime_type)
         .bind(attachment.file_size)
         .bind(&attachment.file_path)
+        .bind(&attachment.checksum)
         .bind(attachment.uploaded_by.map(|id| id.to_string()))
         .bind(attachment.created_at.to_rfc3339())
         .execute(pool)
-        .await
-        .map_err(crate::Error::Database)?;
-        
+        .await;
+
+        if let Err(e) = inserted {
+            blobs.release(pool, &blob.sha256).await?;
+            return Err(crate::Error::Database(e));
+        }
+
         Ok(attachment)
     }
 
@@ -102,7 +121,7 @@ impl AttachmentService {
         id: Uuid,
     ) -> crate::Result<Attachment> {
         let row = sqlx::query_as::<_, AttachmentRow>(
-            "SELECT id, entity_type, entity_id, filename, original_filename, mime_type, file_size, file_path, uploaded_by, created_at
+            "SELECT id, entity_type, entity_id, filename, original_filename, mime_type, file_size, file_path, checksum, uploaded_by, created_at
              FROM attachments WHERE id = ?"
         )
         .bind(id.to_string())
@@ -116,19 +135,20 @@ impl AttachmentService {
 
     pub async fn delete(
         pool: &sqlx::SqlitePool,
+        blobs: &BlobService,
         id: Uuid,
     ) -> crate::Result<()> {
-        let rows = sqlx::query("DELETE FROM attachments WHERE id = ?")
+        let checksum: Option<Option<String>> = sqlx::query_scalar("DELETE FROM attachments WHERE id = ? RETURNING checksum")
             .bind(id.to_string())
-            .execute(pool)
+            .fetch_optional(pool)
             .await
             .map_err(crate::Error::Database)?;
-        
-        if rows.rows_affected() == 0 {
-            return Err(crate::Error::not_found("Attachment", &id.to_string()));
+
+        match checksum {
+            None => Err(crate::Error::not_found("Attachment", &id.to_string())),
+            Some(Some(sha256)) => blobs.release(pool, &sha256).await,
+            Some(None) => Ok(()),
         }
-        
-        Ok(())
     }
 }
 
@@ -142,6 +162,7 @@ struct AttachmentRow {
     mime_type: String,
     file_size: i64,
     file_path: String,
+    checksum: Option<String>,
     uploaded_by: Option<String>,
     created_at: String,
 }
@@ -157,6 +178,7 @@ impl From<AttachmentRow> for Attachment {
             mime_type: r.mime_type,
             file_size: r.file_size,
             file_path: r.file_path,
+            checksum: r.checksum,
             uploaded_by: r.uploaded_by.and_then(|id| Uuid::parse_str(&id).ok()),
             created_at: chrono::DateTime::parse_from_rfc3339(&r.created_at)
                 .map(|d| d.with_timezone(&Utc))
@@ -0,0 +1,67 @@
+use super::{BlobStore, ByteStream, StagedBlob};
+use crate::{Error, Result};
+use async_trait::async_trait;
+use std::path::{Component, Path, PathBuf};
+use tokio_util::io::ReaderStream;
+
+/// Keeps blobs as files under a directory on the server.
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+        if relative.components().any(|c| !matches!(c, Component::Normal(_))) {
+            return Err(Error::validation(format!("Invalid blob key: {}", key)));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    fn backend(&self) -> &'static str {
+        "local"
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        Ok(tokio::fs::try_exists(self.path(key)?).await.unwrap_or(false))
+    }
+
+    async fn put(&self, key: &str, blob: &StagedBlob) -> Result<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await
+                .map_err(|e| Error::internal(format!("Failed to create directory: {}", e)))?;
+        }
+        // Copy to a sibling first so a reader never sees a half-written blob.
+        let partial = path.with_extension("part");
+        tokio::fs::copy(&blob.path, &partial).await
+            .map_err(|e| Error::internal(format!("Failed to store file: {}", e)))?;
+        tokio::fs::rename(&partial, &path).await
+            .map_err(|e| Error::internal(format!("Failed to store file: {}", e)))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<ByteStream> {
+        let file = tokio::fs::File::open(self.path(key)?).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Error::not_found("Blob", key),
+            _ => Error::internal(format!("Failed to open file: {}", e)),
+        })?;
+        Ok(Box::pin(ReaderStream::new(file)))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(Error::internal(format!("Failed to delete file: {}", e)))
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! Storage for uploaded and generated files.
+//!
+//! Bytes live in a [`BlobStore`] under the SHA-256 of their content, so identical files are
+//! stored once. The `blobs` table counts the records referring to each one, and
+//! [`BlobService`] deletes the bytes when the last reference goes.
+
+mod local;
+mod s3;
+
+pub use local::LocalBlobStore;
+pub use s3::{sign_v4, S3BlobStore, S3Config};
+
+use crate::{Error, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+pub type ByteStream = Pin<Box<dyn Stream<Item = std::io::Result<Bytes>> + Send>>;
+
+/// Bytes to look at when recognising a file's type.
+const SNIFF_LEN: usize = 512;
+
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Recorded against each blob, e.g. `local` or `s3`.
+    fn backend(&self) -> &'static str;
+
+    async fn exists(&self, key: &str) -> Result<bool>;
+
+    /// Stores a staged upload under `key`, replacing anything already there.
+    async fn put(&self, key: &str, blob: &StagedBlob) -> Result<()>;
+
+    /// Streams the bytes stored under `key`, or fails with `NotFound`.
+    async fn get(&self, key: &str) -> Result<ByteStream>;
+
+    /// Removes `key`. Removing a missing key is not an error.
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// An upload written to local scratch space, with its size and checksum, waiting to be saved.
+/// The scratch file is removed when this is dropped.
+#[derive(Debug)]
+pub struct StagedBlob {
+    pub path: PathBuf,
+    pub size: u64,
+    pub sha256: String,
+    head: Vec<u8>,
+}
+
+impl StagedBlob {
+    /// The content type recognised from the file's first bytes, if it is a common format.
+    pub fn sniffed_mime_type(&self) -> Option<&'static str> {
+        sniff_mime_type(&self.head)
+    }
+}
+
+impl Drop for StagedBlob {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// A file being written to scratch space. Finishing it gives a [`StagedBlob`] to save, and
+/// dropping it unfinished removes what was written.
+pub struct BlobWriter {
+    staged: StagedBlob,
+    file: tokio::fs::File,
+    hasher: Sha256,
+    max_size: u64,
+}
+
+impl BlobWriter {
+    pub async fn write(&mut self, chunk: &[u8]) -> Result<()> {
+        self.staged.size += chunk.len() as u64;
+        if self.staged.size > self.max_size {
+            return Err(Error::validation(format!("File too large. Maximum size is {} bytes", self.max_size)));
+        }
+        if self.staged.head.len() < SNIFF_LEN {
+            let take = (SNIFF_LEN - self.staged.head.len()).min(chunk.len());
+            self.staged.head.extend_from_slice(&chunk[..take]);
+        }
+        self.hasher.update(chunk);
+        self.file.write_all(chunk).await
+            .map_err(|e| Error::internal(format!("Failed to stage upload: {}", e)))
+    }
+
+    pub fn size(&self) -> u64 {
+        self.staged.size
+    }
+
+    pub async fn finish(mut self) -> Result<StagedBlob> {
+        self.file.flush().await.map_err(|e| Error::internal(format!("Failed to stage upload: {}", e)))?;
+        self.staged.sha256 = hex::encode(self.hasher.finalize());
+        Ok(self.staged)
+    }
+}
+
+/// Limits on what may be uploaded to a folder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadPolicy {
+    pub max_size: u64,
+    /// Content types such as `application/pdf` or `image/*`. Empty allows any type.
+    pub allowed_mime_types: Vec<String>,
+}
+
+impl Default for UploadPolicy {
+    fn default() -> Self {
+        Self { max_size: 10 * 1024 * 1024, allowed_mime_types: Vec::new() }
+    }
+}
+
+impl UploadPolicy {
+    /// Parses a comma-separated allow-list, as stored in the database.
+    pub fn parse_mime_types(list: Option<&str>) -> Vec<String> {
+        list.unwrap_or("")
+            .split(',')
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+
+    pub fn allows(&self, mime_type: &str) -> bool {
+        let mime_type = mime_type.to_lowercase();
+        self.allowed_mime_types.is_empty()
+            || self.allowed_mime_types.iter().any(|allowed| match allowed.strip_suffix("/*") {
+                Some(family) => mime_type.split('/').next() == Some(family),
+                None => *allowed == mime_type,
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Blob {
+    pub sha256: String,
+    pub size: i64,
+    pub mime_type: String,
+    pub backend: String,
+    pub ref_count: i64,
+    pub created_at: String,
+}
+
+impl Blob {
+    /// Where the bytes live in the store.
+    pub fn key(&self) -> String {
+        blob_key(&self.sha256)
+    }
+}
+
+pub fn blob_key(sha256: &str) -> String {
+    format!("{}/{}/{}", &sha256[..2], &sha256[2..4], sha256)
+}
+
+/// Query parameters of a signed download link.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedDownload {
+    pub name: String,
+    pub expires: i64,
+    pub signature: String,
+}
+
+pub struct BlobService {
+    store: Arc<dyn BlobStore>,
+    staging_dir: PathBuf,
+    signing_key: Vec<u8>,
+    default_policy: UploadPolicy,
+}
+
+impl BlobService {
+    /// Uploads are staged under `staging_dir` before they reach the store.
+    pub fn new(store: Arc<dyn BlobStore>, staging_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            store,
+            staging_dir: staging_dir.into(),
+            signing_key: Uuid::new_v4().as_bytes().to_vec(),
+            default_policy: UploadPolicy::default(),
+        }
+    }
+
+    /// Key for signing download links. Without one, links stop working on restart.
+    pub fn with_signing_key(mut self, secret: &[u8]) -> Self {
+        self.signing_key = Sha256::new().chain_update(b"blob-download:").chain_update(secret).finalize().to_vec();
+        self
+    }
+
+    /// Applies where a folder sets no limits of its own.
+    pub fn with_default_policy(mut self, policy: UploadPolicy) -> Self {
+        self.default_policy = policy;
+        self
+    }
+
+    pub fn default_policy(&self) -> &UploadPolicy {
+        &self.default_policy
+    }
+
+    pub fn backend(&self) -> &'static str {
+        self.store.backend()
+    }
+
+    /// Writes `stream` to scratch space, hashing it on the way, and stops as soon as it grows
+    /// past `max_size`.
+    pub async fn stage<S, E>(&self, stream: S, max_size: u64) -> Result<StagedBlob>
+    where
+        S: Stream<Item = std::result::Result<Bytes, E>>,
+        E: std::fmt::Display,
+    {
+        let mut stream = std::pin::pin!(stream);
+        let mut writer = self.writer(max_size).await?;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| Error::validation(format!("Failed to read upload: {}", e)))?;
+            writer.write(&chunk).await?;
+        }
+        writer.finish().await
+    }
+
+    /// Starts staging a file that is written piece by piece, such as a generated report.
+    pub async fn writer(&self, max_size: u64) -> Result<BlobWriter> {
+        tokio::fs::create_dir_all(&self.staging_dir).await
+            .map_err(|e| Error::internal(format!("Failed to create staging directory: {}", e)))?;
+        let staged = StagedBlob {
+            path: self.staging_dir.join(format!("{}.part", Uuid::new_v4())),
+            size: 0,
+            sha256: String::new(),
+            head: Vec::new(),
+        };
+        let file = tokio::fs::File::create(&staged.path).await
+            .map_err(|e| Error::internal(format!("Failed to stage upload: {}", e)))?;
+        Ok(BlobWriter { staged, file, hasher: Sha256::new(), max_size })
+    }
+
+    /// Stages bytes already in memory, such as a generated file.
+    pub async fn stage_bytes(&self, bytes: impl Into<Bytes>, max_size: u64) -> Result<StagedBlob> {
+        self.stage(futures::stream::iter([Ok::<_, std::convert::Infallible>(bytes.into())]), max_size).await
+    }
+
+    /// Checks a staged upload against `policy` and the checksum the client sent, if any, and
+    /// adds a reference to its blob, storing the bytes unless an identical file already is.
+    /// The content type is the one recognised from the bytes, else `declared_mime_type`.
+    pub async fn save(
+        &self,
+        pool: &SqlitePool,
+        staged: StagedBlob,
+        declared_mime_type: &str,
+        policy: &UploadPolicy,
+        expected_sha256: Option<&str>,
+    ) -> Result<Blob> {
+        if let Some(expected) = expected_sha256.map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()) {
+            if expected != staged.sha256 {
+                return Err(Error::validation(format!(
+                    "Checksum mismatch: expected {}, received {}", expected, staged.sha256
+                )));
+            }
+        }
+        if staged.size > policy.max_size {
+            return Err(Error::validation(format!("File too large. Maximum size is {} bytes", policy.max_size)));
+        }
+        let declared_mime_type = declared_mime_type.to_lowercase();
+        let mime_type = match staged.sniffed_mime_type() {
+            // Office documents are zip archives, so the declared type says more than the signature.
+            Some("application/zip") if is_zip_container(&declared_mime_type) => declared_mime_type,
+            Some(sniffed) => sniffed.to_string(),
+            None => declared_mime_type,
+        };
+        if !policy.allows(&mime_type) {
+            return Err(Error::validation(format!("Files of type {} are not allowed here", mime_type)));
+        }
+
+        let existing = sqlx::query("UPDATE blobs SET ref_count = ref_count + 1 WHERE sha256 = ?")
+            .bind(&staged.sha256)
+            .execute(pool)
+            .await?;
+        if existing.rows_affected() == 0 {
+            self.store.put(&blob_key(&staged.sha256), &staged).await?;
+            sqlx::query(
+                "INSERT INTO blobs (sha256, size, mime_type, backend, ref_count, created_at) VALUES (?, ?, ?, ?, 1, ?)
+                 ON CONFLICT (sha256) DO UPDATE SET ref_count = ref_count + 1"
+            )
+            .bind(&staged.sha256)
+            .bind(staged.size as i64)
+            .bind(&mime_type)
+            .bind(self.store.backend())
+            .bind(Utc::now().to_rfc3339())
+            .execute(pool)
+            .await?;
+        }
+        self.get(pool, &staged.sha256).await
+    }
+
+    pub async fn get(&self, pool: &SqlitePool, sha256: &str) -> Result<Blob> {
+        sqlx::query_as::<_, Blob>("SELECT sha256, size, mime_type, backend, ref_count, created_at FROM blobs WHERE sha256 = ?")
+            .bind(sha256)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| Error::not_found("Blob", sha256))
+    }
+
+    /// Streams a blob's bytes, checking them against its checksum as they go. A blob that no
+    /// longer matches ends the stream with an error instead of its last chunk.
+    pub async fn open(&self, pool: &SqlitePool, sha256: &str) -> Result<(Blob, ByteStream)> {
+        let blob = self.get(pool, sha256).await?;
+        let inner = self.store.get(&blob.key()).await?;
+        let expected = blob.sha256.clone();
+        let verified = futures::stream::unfold(
+            (inner, Some(Sha256::new()), expected),
+            |(mut inner, hasher, expected)| async move {
+                let mut hasher = hasher?;
+                match inner.next().await {
+                    Some(Ok(chunk)) => {
+                        hasher.update(&chunk);
+                        Some((Ok(chunk), (inner, Some(hasher), expected)))
+                    }
+                    Some(Err(e)) => Some((Err(e), (inner, None, expected))),
+                    None => {
+                        let actual = hex::encode(hasher.finalize());
+                        if actual == expected {
+                            None
+                        } else {
+                            tracing::error!("Blob {} is corrupt: its content hashes to {}", expected, actual);
+                            let error = std::io::Error::new(std::io::ErrorKind::InvalidData, "Stored file failed its checksum");
+                            Some((Err(error), (inner, None, expected)))
+                        }
+                    }
+                }
+            },
+        );
+        Ok((blob, Box::pin(verified)))
+    }
+
+    /// Drops a reference to a blob, deleting its bytes with the last one.
+    pub async fn release(&self, pool: &SqlitePool, sha256: &str) -> Result<()> {
+        let mut tx = pool.begin().await?;
+        let remaining: Option<i64> = sqlx::query_scalar(
+            "UPDATE blobs SET ref_count = ref_count - 1 WHERE sha256 = ? RETURNING ref_count"
+        )
+        .bind(sha256)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if remaining.is_some_and(|n| n <= 0) {
+            let deleted = sqlx::query("DELETE FROM blobs WHERE sha256 = ? AND ref_count <= 0")
+                .bind(sha256)
+                .execute(&mut *tx)
+                .await?;
+            // The bytes go while the transaction still holds the write lock, so an upload of the
+            // same content cannot store them again in between.
+            if deleted.rows_affected() == 1 {
+                self.store.delete(&blob_key(sha256)).await?;
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Signs a link to download a blob as `name` until `expires_at`.
+    pub fn sign_download(&self, sha256: &str, name: &str, expires_at: DateTime<Utc>) -> SignedDownload {
+        let expires = expires_at.timestamp();
+        SignedDownload {
+            name: name.to_string(),
+            expires,
+            signature: self.download_signature(sha256, name, expires),
+        }
+    }
+
+    pub fn verify_download(&self, sha256: &str, download: &SignedDownload) -> Result<()> {
+        if download.expires < Utc::now().timestamp() {
+            return Err(Error::forbidden("Download link has expired"));
+        }
+        let matches = hex::decode(&download.signature).ok()
+            .is_some_and(|given| {
+                self.download_mac(sha256, &download.name, download.expires).verify_slice(&given).is_ok()
+            });
+        if !matches {
+            return Err(Error::forbidden("Invalid download signature"));
+        }
+        Ok(())
+    }
+
+    fn download_mac(&self, sha256: &str, name: &str, expires: i64) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.signing_key).expect("HMAC accepts any key length");
+        mac.update(format!("{}\n{}\n{}", sha256, name, expires).as_bytes());
+        mac
+    }
+
+    fn download_signature(&self, sha256: &str, name: &str, expires: i64) -> String {
+        hex::encode(self.download_mac(sha256, name, expires).finalize().into_bytes())
+    }
+}
+
+fn is_zip_container(mime_type: &str) -> bool {
+    mime_type.starts_with("application/vnd.openxmlformats-officedocument.")
+        || mime_type.starts_with("application/vnd.oasis.opendocument.")
+        || mime_type.ends_with("+zip")
+}
+
+/// Recognises common document and image formats by their signature bytes.
+pub fn sniff_mime_type(head: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"%PDF-", "application/pdf"),
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"II*\x00", "image/tiff"),
+        (b"MM\x00*", "image/tiff"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+    ];
+    if head.len() >= 12 && &head[..4] == b"RIFF" && &head[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    SIGNATURES.iter().find(|(signature, _)| head.starts_with(signature)).map(|(_, mime)| *mime)
+}
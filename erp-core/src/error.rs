@@ -24,6 +24,9 @@ pub enum Error {
     #[error("Unauthorized")]
     Unauthorized,
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Internal error: {0}")]
     Internal(#[from] anyhow::Error),
 }
@@ -46,6 +49,10 @@ impl Error {
         Error::Unauthorized
     }
 
+    pub fn forbidden(msg: impl Into<String>) -> Self {
+        Error::Forbidden(msg.into())
+    }
+
     pub fn internal(msg: impl Into<String> + std::fmt::Display) -> Self {
         Error::Internal(anyhow::anyhow!("{}", msg))
     }
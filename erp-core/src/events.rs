@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+const CHANNEL_CAPACITY: usize = 1024;
+
+static EVENT_BUS: OnceLock<broadcast::Sender<RealtimeEvent>> = OnceLock::new();
+
+/// A change pushed to connected clients. An event reaches the users listed in `target_users`
+/// and anyone subscribed to one of its `topics`; with neither set it goes to every client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealtimeEvent {
+    pub id: Uuid,
+    pub event_type: String,
+    pub title: String,
+    pub message: String,
+    pub topics: Vec<String>,
+    pub target_users: Option<Vec<Uuid>>,
+    pub data: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RealtimeEvent {
+    pub fn new(event_type: &str, title: &str, message: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type: event_type.to_string(),
+            title: title.to_string(),
+            message: message.to_string(),
+            topics: Vec::new(),
+            target_users: None,
+            data: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_topic(mut self, topic: impl Into<String>) -> Self {
+        self.topics.push(topic.into());
+        self
+    }
+
+    pub fn for_users(mut self, user_ids: impl IntoIterator<Item = Uuid>) -> Self {
+        self.target_users.get_or_insert_with(Vec::new).extend(user_ids);
+        self
+    }
+
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    pub fn is_broadcast(&self) -> bool {
+        self.target_users.is_none() && self.topics.is_empty()
+    }
+}
+
+fn bus() -> &'static broadcast::Sender<RealtimeEvent> {
+    EVENT_BUS.get_or_init(|| broadcast::channel(CHANNEL_CAPACITY).0)
+}
+
+/// Publishes an event to every subscriber in this process. Events published while nobody
+/// is listening are dropped.
+pub fn publish(event: RealtimeEvent) {
+    if bus().send(event).is_err() {
+        tracing::debug!("No subscribers for realtime event");
+    }
+}
+
+pub fn subscribe() -> broadcast::Receiver<RealtimeEvent> {
+    bus().subscribe()
+}
+
+/// Topic for changes to a single document, e.g. `sales_order:{id}`.
+pub fn document_topic(document_type: &str, id: impl std::fmt::Display) -> String {
+    format!("{}:{}", document_type, id)
+}
+
+/// Announces a change to a record, as `<entity>.<action>` on the entity's document topic, so
+/// GraphQL `entityChanged` subscribers and `/ws` clients watching the document both see it.
+/// The REST handlers and GraphQL mutations that change a subscribable entity call this.
+pub fn publish_change(entity: &str, id: impl ToString, action: &str, actor: &str) {
+    let id = id.to_string();
+    let event = RealtimeEvent::new(&format!("{}.{}", entity, action), &format!("{} {}", entity, action), &id)
+        .with_topic(document_topic(entity, &id))
+        .with_data(serde_json::json!({ "entity": entity, "id": id, "action": action, "actor": actor }));
+    publish(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_reaches_subscribers() {
+        let mut rx = subscribe();
+        let user = Uuid::new_v4();
+        publish(RealtimeEvent::new("approval.requested", "Approval needed", "PO-1").for_users([user]).with_topic("approvals"));
+
+        let event = loop {
+            let event = rx.recv().await.unwrap();
+            if event.event_type == "approval.requested" {
+                break event;
+            }
+        };
+        assert_eq!(event.target_users, Some(vec![user]));
+        assert_eq!(event.topics, vec!["approvals"]);
+        assert!(!event.is_broadcast());
+        assert_eq!(document_topic("sales_order", 7), "sales_order:7");
+    }
+}
@@ -1,21 +1,28 @@
 pub mod analytics;
 pub mod audit;
+pub mod blob;
 pub mod attachment;
 pub mod compliance;
 pub mod compliance_service;
 pub mod custom_field;
 pub mod db;
 pub mod error;
+pub mod events;
 pub mod models;
 pub mod pagination;
+pub mod pdf;
 pub mod platform;
 pub mod repository;
+pub mod scope;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod workflow_models;
 pub mod workflow_service;
 
 pub use analytics::{DashboardService, KPIService, AlertService, PredictiveService};
 pub use audit::{AuditLog, AuditAction, log_audit, get_audit_logs};
 pub use attachment::{Attachment, AttachmentService};
+pub use blob::{BlobService, BlobStore, UploadPolicy};
 pub use compliance::*;
 pub use compliance_service::ComplianceService;
 pub use custom_field::CustomFieldService;
@@ -23,6 +30,7 @@ pub use db::Database;
 pub use error::{Error, Result, parse_uuid, parse_uuid_opt, parse_datetime, parse_datetime_opt};
 pub use models::{Address, BaseEntity, ContactInfo, Currency, Money, Status, CustomFieldDefinition, CustomFieldType, CustomFieldValue};
 pub use pagination::{Pagination, Paginated};
-pub use platform::{TenantService, AutomationService, EmailService, ReportService, MobileService, APIService, TenantLimits, APIUsageStats};
+pub use scope::RowScope;
+pub use platform::{TenantService, AutomationService, EmailService, MobileService, APIService, TenantLimits, APIUsageStats};
 pub use workflow_models::*;
 pub use workflow_service::{WorkflowService, ApprovalService, NotificationService};
@@ -3,7 +3,6 @@ pub mod common;
 pub mod tenant;
 pub mod automation;
 pub mod email;
-pub mod report;
 pub mod mobile;
 pub mod api;
 pub mod analytics;
@@ -13,7 +12,6 @@ pub use common::*;
 pub use tenant::*;
 pub use automation::*;
 pub use email::*;
-pub use report::*;
 pub use mobile::*;
 pub use api::*;
 pub use analytics::*;
@@ -0,0 +1,197 @@
+//! Raster images to place on a page. JPEG data is embedded as it is. PNG is decoded, its
+//! alpha channel split off into a soft mask, and the pixels compressed again.
+
+use crate::{Error, Result};
+use flate2::read::ZlibDecoder;
+use flate2::{write::ZlibEncoder, Compression};
+use std::io::{Read, Write};
+
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub(super) color_space: &'static str,
+    pub(super) filter: &'static str,
+    pub(super) data: Vec<u8>,
+    /// Flate-compressed 8-bit alpha, one byte per pixel.
+    pub(super) alpha: Option<Vec<u8>>,
+}
+
+impl Image {
+    /// Reads a JPEG or PNG file.
+    pub fn decode(bytes: &[u8]) -> Result<Image> {
+        if bytes.starts_with(&[0xFF, 0xD8]) {
+            jpeg(bytes)
+        } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            png(bytes)
+        } else {
+            Err(Error::validation("Images must be JPEG or PNG"))
+        }
+    }
+}
+
+fn jpeg(bytes: &[u8]) -> Result<Image> {
+    let truncated = || Error::validation("Truncated JPEG image");
+    let mut i = 2;
+    loop {
+        while bytes.get(i) == Some(&0xFF) {
+            i += 1;
+        }
+        let marker = *bytes.get(i).ok_or_else(truncated)?;
+        i += 1;
+        if matches!(marker, 0x01 | 0xD0..=0xD7) {
+            continue;
+        }
+        let length = u16::from_be_bytes([*bytes.get(i).ok_or_else(truncated)?, *bytes.get(i + 1).ok_or_else(truncated)?]) as usize;
+        // Start-of-frame markers, less the ones reused for Huffman and arithmetic tables.
+        if matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            let frame = bytes.get(i + 2..i + 8).ok_or_else(truncated)?;
+            let height = u16::from_be_bytes([frame[1], frame[2]]) as u32;
+            let width = u16::from_be_bytes([frame[3], frame[4]]) as u32;
+            let color_space = match frame[5] {
+                1 => "DeviceGray",
+                3 => "DeviceRGB",
+                4 => "DeviceCMYK",
+                n => return Err(Error::validation(format!("JPEG images with {} components are not supported", n))),
+            };
+            return Ok(Image { width, height, color_space, filter: "DCTDecode", data: bytes.to_vec(), alpha: None });
+        }
+        i += length;
+    }
+}
+
+fn png(bytes: &[u8]) -> Result<Image> {
+    let truncated = || Error::validation("Truncated PNG image");
+    let mut header = None;
+    let mut palette: &[u8] = &[];
+    let mut transparency: &[u8] = &[];
+    let mut compressed = Vec::new();
+    let mut i = 8;
+    while i + 8 <= bytes.len() {
+        let length = u32::from_be_bytes(bytes[i..i + 4].try_into().unwrap_or_default()) as usize;
+        let kind = &bytes[i + 4..i + 8];
+        let data = bytes.get(i + 8..i + 8 + length).ok_or_else(truncated)?;
+        match kind {
+            b"IHDR" if data.len() >= 13 => header = Some(data),
+            b"PLTE" => palette = data,
+            b"tRNS" => transparency = data,
+            b"IDAT" => compressed.extend_from_slice(data),
+            b"IEND" => break,
+            _ => {}
+        }
+        i += 12 + length;
+    }
+    let header = header.ok_or_else(|| Error::validation("PNG image has no header"))?;
+    let width = u32::from_be_bytes(header[0..4].try_into().unwrap_or_default());
+    let height = u32::from_be_bytes(header[4..8].try_into().unwrap_or_default());
+    let (depth, color_type) = (header[8] as usize, header[9]);
+    if header[12] != 0 {
+        return Err(Error::validation("Interlaced PNG images are not supported"));
+    }
+    let channels = match color_type {
+        0 | 3 => 1,
+        2 => 3,
+        4 => 2,
+        6 => 4,
+        _ => return Err(Error::validation("Unknown PNG color type")),
+    };
+    if !matches!(depth, 1 | 2 | 4 | 8 | 16) || (channels > 1 && depth < 8) {
+        return Err(Error::validation("Unsupported PNG bit depth"));
+    }
+
+    let mut raw = Vec::new();
+    ZlibDecoder::new(compressed.as_slice())
+        .read_to_end(&mut raw)
+        .map_err(|_| Error::validation("Corrupt PNG image data"))?;
+    let stride = (width as usize * channels * depth).div_ceil(8);
+    let pixels = unfilter(&raw, stride, height as usize, (channels * depth).div_ceil(8))?;
+
+    // Every sample as 8 bits, whatever the depth.
+    let sample = |row: &[u8], index: usize| -> u8 {
+        match depth {
+            16 => row[index * 2],
+            8 => row[index],
+            _ => {
+                let bit = index * depth;
+                let value = (row[bit / 8] >> (8 - depth - bit % 8)) & ((1 << depth) - 1);
+                if color_type == 3 { value } else { (value as u32 * 255 / ((1 << depth) - 1)) as u8 }
+            }
+        }
+    };
+    let color_channels = if matches!(color_type, 2 | 3 | 6) { 3 } else { 1 };
+    let has_alpha = matches!(color_type, 4 | 6) || (color_type == 3 && !transparency.is_empty());
+    let mut color = Vec::with_capacity(width as usize * height as usize * color_channels);
+    let mut alpha = Vec::with_capacity(if has_alpha { width as usize * height as usize } else { 0 });
+    for row in pixels.chunks(stride) {
+        for x in 0..width as usize {
+            match color_type {
+                3 => {
+                    let index = sample(row, x) as usize;
+                    let rgb = palette.get(index * 3..index * 3 + 3).ok_or_else(|| Error::validation("PNG palette index out of range"))?;
+                    color.extend_from_slice(rgb);
+                    if has_alpha {
+                        alpha.push(transparency.get(index).copied().unwrap_or(255));
+                    }
+                }
+                _ => {
+                    for c in 0..color_channels {
+                        color.push(sample(row, x * channels + c));
+                    }
+                    if has_alpha {
+                        alpha.push(sample(row, x * channels + channels - 1));
+                    }
+                }
+            }
+        }
+    }
+    Ok(Image {
+        width,
+        height,
+        color_space: if color_channels == 3 { "DeviceRGB" } else { "DeviceGray" },
+        filter: "FlateDecode",
+        data: deflate(&color),
+        alpha: (has_alpha && alpha.iter().any(|&a| a != 255)).then(|| deflate(&alpha)),
+    })
+}
+
+/// Reverses PNG's per-row prediction filters.
+fn unfilter(raw: &[u8], stride: usize, height: usize, bpp: usize) -> Result<Vec<u8>> {
+    if raw.len() < (stride + 1) * height {
+        return Err(Error::validation("Truncated PNG image data"));
+    }
+    let mut out = vec![0u8; stride * height];
+    for y in 0..height {
+        let filter = raw[y * (stride + 1)];
+        let line = &raw[y * (stride + 1) + 1..(y + 1) * (stride + 1)];
+        let (done, rest) = out.split_at_mut(y * stride);
+        let previous = if y == 0 { None } else { Some(&done[(y - 1) * stride..]) };
+        let current = &mut rest[..stride];
+        for x in 0..stride {
+            let left = if x >= bpp { current[x - bpp] } else { 0 };
+            let up = previous.map_or(0, |p| p[x]);
+            let up_left = if x >= bpp { previous.map_or(0, |p| p[x - bpp]) } else { 0 };
+            let predicted = match filter {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((left as u16 + up as u16) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => return Err(Error::validation("Corrupt PNG image data")),
+            };
+            current[x] = line[x].wrapping_add(predicted);
+        }
+    }
+    Ok(out)
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = ((p - a as i16).abs(), (p - b as i16).abs(), (p - c as i16).abs());
+    if pa <= pb && pa <= pc { a } else if pb <= pc { b } else { c }
+}
+
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).expect("compressing into memory cannot fail");
+    encoder.finish().expect("compressing into memory cannot fail")
+}
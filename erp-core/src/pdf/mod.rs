@@ -0,0 +1,336 @@
+//! A small PDF writer for generated documents.
+//!
+//! Text is set in the standard Helvetica fonts every viewer ships with, so no fonts are
+//! embedded. Each page is written out when it is finished, and [`PdfWriter::take_output`]
+//! hands over what has been written so far, so long documents never sit in memory whole.
+
+mod image;
+
+pub use image::Image;
+
+use chrono::Utc;
+use flate2::{write::ZlibEncoder, Compression};
+use std::io::Write;
+
+/// Portrait A4, in points.
+pub const A4: (f32, f32) = (595.0, 842.0);
+
+/// An image added to a document with [`PdfWriter::add_image`], to draw on any of its pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageId(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+
+    /// Advance widths of the printable ASCII characters, in thousandths of the font size.
+    fn widths(self) -> &'static [u16; 95] {
+        match self {
+            Font::Regular => &HELVETICA_WIDTHS,
+            Font::Bold => &HELVETICA_BOLD_WIDTHS,
+        }
+    }
+}
+
+const CATALOG: usize = 1;
+const PAGES: usize = 2;
+const FONT_REGULAR: usize = 3;
+const FONT_BOLD: usize = 4;
+
+pub struct PdfWriter {
+    out: Vec<u8>,
+    /// Bytes already handed over by `take_output`, so object offsets stay absolute.
+    written: usize,
+    /// Byte offset of each object, indexed by object number less one.
+    offsets: Vec<Option<usize>>,
+    pages: Vec<usize>,
+    /// Object number of each added image.
+    images: Vec<usize>,
+    size: (f32, f32),
+    content: Option<Vec<u8>>,
+    title: String,
+}
+
+impl PdfWriter {
+    /// Starts a document whose pages are `size` points wide and high.
+    pub fn new(size: (f32, f32), title: &str) -> Self {
+        let mut pdf = Self {
+            out: Vec::new(),
+            written: 0,
+            offsets: vec![None; FONT_BOLD],
+            pages: Vec::new(),
+            images: Vec::new(),
+            size,
+            content: None,
+            title: title.to_string(),
+        };
+        pdf.out.extend_from_slice(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n");
+        pdf.object(CATALOG, b"<< /Type /Catalog /Pages 2 0 R >>");
+        pdf.object(FONT_REGULAR, b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>");
+        pdf.object(FONT_BOLD, b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>");
+        pdf
+    }
+
+    pub fn page_size(&self) -> (f32, f32) {
+        self.size
+    }
+
+    /// Pages finished so far.
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Finishes the current page, if any, and starts another.
+    pub fn begin_page(&mut self) {
+        self.end_page();
+        self.content = Some(Vec::new());
+    }
+
+    /// Draws `text` with its baseline starting at (`x`, `y`), measured from the bottom left.
+    pub fn text(&mut self, x: f32, y: f32, font: Font, size: f32, text: &str) {
+        let content = self.content();
+        let _ = write!(content, "BT /{} {} Tf {} {} Td (", font.resource(), num(size), num(x), num(y));
+        content.extend(escape(&encode(text)));
+        content.extend_from_slice(b") Tj ET\n");
+    }
+
+    pub fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32) {
+        let content = self.content();
+        let _ = writeln!(content, "{} w {} {} m {} {} l S", num(width), num(from.0), num(from.1), num(to.0), num(to.1));
+    }
+
+    /// Fills a rectangle in a shade of grey, 0 being black and 1 white.
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, gray: f32) {
+        let content = self.content();
+        let _ = writeln!(content, "{} g {} {} {} {} re f 0 g", num(gray), num(x), num(y), num(width), num(height));
+    }
+
+    /// Embeds `image` in the document. It is written once however often it is drawn.
+    pub fn add_image(&mut self, image: &Image) -> ImageId {
+        let mask = image.alpha.as_ref().map(|alpha| {
+            let mask = self.reserve();
+            let dictionary = format!(
+                "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceGray /BitsPerComponent 8 /Filter /FlateDecode /Length {} >>",
+                image.width, image.height, alpha.len()
+            );
+            self.object(mask, &stream(&dictionary, alpha));
+            mask
+        });
+        let id = self.reserve();
+        let dictionary = format!(
+            "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /{} /BitsPerComponent 8 /Filter /{}{} /Length {} >>",
+            image.width,
+            image.height,
+            image.color_space,
+            image.filter,
+            mask.map(|m| format!(" /SMask {} 0 R", m)).unwrap_or_default(),
+            image.data.len()
+        );
+        self.object(id, &stream(&dictionary, &image.data));
+        self.images.push(id);
+        ImageId(self.images.len())
+    }
+
+    /// Draws an added image scaled into the box whose bottom left corner is (`x`, `y`).
+    pub fn image(&mut self, image: ImageId, x: f32, y: f32, width: f32, height: f32) {
+        let content = self.content();
+        let _ = writeln!(content, "q {} 0 0 {} {} {} cm /Im{} Do Q", num(width), num(height), num(x), num(y), image.0);
+    }
+
+    /// Writes out the current page, if one was started.
+    pub fn end_page(&mut self) {
+        let Some(content) = self.content.take() else { return };
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content).expect("compressing into memory cannot fail");
+        let data = encoder.finish().expect("compressing into memory cannot fail");
+
+        let contents = self.reserve();
+        self.object(contents, &stream(&format!("<< /Length {} /Filter /FlateDecode >>", data.len()), &data));
+
+        let images: Vec<String> = self.images.iter().enumerate().map(|(i, id)| format!("/Im{} {} 0 R", i + 1, id)).collect();
+        let x_objects = if images.is_empty() { String::new() } else { format!(" /XObject << {} >>", images.join(" ")) };
+        let page = self.reserve();
+        let page_body = format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 {} 0 R /F2 {} 0 R >>{} >> /Contents {} 0 R >>",
+            PAGES, num(self.size.0), num(self.size.1), FONT_REGULAR, FONT_BOLD, x_objects, contents
+        );
+        self.object(page, page_body.as_bytes());
+        self.pages.push(page);
+    }
+
+    /// The bytes written since the last call, to pass on while the document is being built.
+    pub fn take_output(&mut self) -> Vec<u8> {
+        let out = std::mem::take(&mut self.out);
+        self.written += out.len();
+        out
+    }
+
+    /// Ends the document. Its remaining bytes are left for [`PdfWriter::take_output`].
+    pub fn finish(&mut self) {
+        self.end_page();
+        if self.pages.is_empty() {
+            self.begin_page();
+            self.end_page();
+        }
+        let kids: Vec<String> = self.pages.iter().map(|p| format!("{} 0 R", p)).collect();
+        let pages = format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), self.pages.len());
+        self.object(PAGES, pages.as_bytes());
+
+        let info = self.reserve();
+        let mut info_body = b"<< /Title (".to_vec();
+        info_body.extend(escape(&encode(&self.title)));
+        info_body.extend(format!(") /Producer (ERP) /CreationDate (D:{}Z) >>", Utc::now().format("%Y%m%d%H%M%S")).bytes());
+        self.object(info, &info_body);
+
+        let xref = self.written + self.out.len();
+        let _ = write!(self.out, "xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            let _ = writeln!(self.out, "{:010} 00000 n ", offset.expect("every object is written"));
+        }
+        let _ = write!(
+            self.out,
+            "trailer\n<< /Size {} /Root {} 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            self.offsets.len() + 1, CATALOG, info, xref
+        );
+    }
+
+    fn content(&mut self) -> &mut Vec<u8> {
+        self.content.get_or_insert_with(Vec::new)
+    }
+
+    fn reserve(&mut self) -> usize {
+        self.offsets.push(None);
+        self.offsets.len()
+    }
+
+    fn object(&mut self, id: usize, body: &[u8]) {
+        self.offsets[id - 1] = Some(self.written + self.out.len());
+        let _ = writeln!(self.out, "{} 0 obj", id);
+        self.out.extend_from_slice(body);
+        self.out.extend_from_slice(b"\nendobj\n");
+    }
+}
+
+/// Width of `text` set in `font` at `size` points.
+pub fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    let widths = font.widths();
+    let units: u32 = encode(text).iter()
+        .map(|&b| match b {
+            0x20..=0x7E => widths[(b - 0x20) as usize] as u32,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+/// `text`, shortened with an ellipsis if it would be wider than `width`.
+pub fn fit_text(text: &str, font: Font, size: f32, width: f32) -> String {
+    if text_width(text, font, size) <= width {
+        return text.to_string();
+    }
+    let mut fitted: String = text.to_string();
+    while !fitted.is_empty() && text_width(&format!("{}...", fitted), font, size) > width {
+        fitted.pop();
+    }
+    if fitted.is_empty() { String::new() } else { format!("{}...", fitted.trim_end()) }
+}
+
+/// Breaks `text` into lines no wider than `width`, at spaces where possible. Explicit line
+/// breaks are kept.
+pub fn wrap_text(text: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split(' ').filter(|w| !w.is_empty()) {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if text_width(&candidate, font, size) <= width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            // A word wider than the line is broken wherever it has to be.
+            for c in word.chars() {
+                if !line.is_empty() && text_width(&format!("{}{}", line, c), font, size) > width {
+                    lines.push(std::mem::take(&mut line));
+                }
+                line.push(c);
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn stream(dictionary: &str, data: &[u8]) -> Vec<u8> {
+    let mut body = format!("{}\nstream\n", dictionary).into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(b"\nendstream");
+    body
+}
+
+fn num(value: f32) -> String {
+    let formatted = format!("{:.2}", value);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Maps text to WinAnsiEncoding, the encoding of the standard fonts. Characters it lacks
+/// become `?`.
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x20..=0x7E | 0xA1..=0xFF => c as u8,
+            _ if c.is_whitespace() => b' ',
+            _ => match c {
+                '€' => 0x80, '‚' => 0x82, 'ƒ' => 0x83, '„' => 0x84, '…' => 0x85, '†' => 0x86,
+                '‡' => 0x87, 'ˆ' => 0x88, '‰' => 0x89, 'Š' => 0x8A, '‹' => 0x8B, 'Œ' => 0x8C,
+                'Ž' => 0x8E, '‘' => 0x91, '’' => 0x92, '“' => 0x93, '”' => 0x94, '•' => 0x95,
+                '–' => 0x96, '—' => 0x97, '˜' => 0x98, '™' => 0x99, 'š' => 0x9A, '›' => 0x9B,
+                'œ' => 0x9C, 'ž' => 0x9E, 'Ÿ' => 0x9F,
+                _ => b'?',
+            },
+        })
+        .collect()
+}
+
+fn escape(bytes: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(bytes.len());
+    for &b in bytes {
+        if matches!(b, b'(' | b')' | b'\\') {
+            escaped.push(b'\\');
+        }
+        escaped.push(b);
+    }
+    escaped
+}
+
+#[rustfmt::skip]
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+#[rustfmt::skip]
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
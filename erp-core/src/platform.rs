@@ -643,216 +643,6 @@ impl EmailService {
     }
 }
 
-pub struct ReportService;
-
-impl ReportService {
-    #[allow(clippy::too_many_arguments)]
-    pub async fn create_report(
-        pool: &SqlitePool,
-        report_code: &str,
-        name: &str,
-        description: Option<&str>,
-        category: Option<&str>,
-        data_source: &str,
-        query_text: &str,
-        parameters: Option<&str>,
-        columns: Option<&str>,
-        filters: Option<&str>,
-        sorting: Option<&str>,
-        grouping: Option<&str>,
-        chart_type: Option<&str>,
-        created_by: Option<Uuid>,
-    ) -> Result<ReportDefinition> {
-        let now = Utc::now();
-        let report = ReportDefinition {
-            id: Uuid::new_v4(),
-            report_code: report_code.to_string(),
-            name: name.to_string(),
-            description: description.map(|s| s.to_string()),
-            category: category.map(|s| s.to_string()),
-            data_source: data_source.to_string(),
-            query_text: query_text.to_string(),
-            parameters: parameters.map(|s| s.to_string()),
-            columns: columns.map(|s| s.to_string()),
-            filters: filters.map(|s| s.to_string()),
-            sorting: sorting.map(|s| s.to_string()),
-            grouping: grouping.map(|s| s.to_string()),
-            chart_type: chart_type.map(|s| s.to_string()),
-            is_scheduled: false,
-            schedule_cron: None,
-            created_by,
-            created_at: now,
-        };
-
-        sqlx::query(
-            "INSERT INTO report_definitions (id, report_code, name, description, category, data_source, query_text, parameters, columns, filters, sorting, grouping, chart_type, is_scheduled, schedule_cron, created_by, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-        )
-        .bind(report.id.to_string())
-        .bind(&report.report_code)
-        .bind(&report.name)
-        .bind(&report.description)
-        .bind(&report.category)
-        .bind(&report.data_source)
-        .bind(&report.query_text)
-        .bind(&report.parameters)
-        .bind(&report.columns)
-        .bind(&report.filters)
-        .bind(&report.sorting)
-        .bind(&report.grouping)
-        .bind(&report.chart_type)
-        .bind(if report.is_scheduled { 1 } else { 0 })
-        .bind(&report.schedule_cron)
-        .bind(report.created_by.map(|id| id.to_string()))
-        .bind(report.created_at.to_rfc3339())
-        .execute(pool)
-        .await
-        .map_err(Error::Database)?;
-
-        Ok(report)
-    }
-
-    pub async fn get_report(pool: &SqlitePool, id: Uuid) -> Result<ReportDefinition> {
-        let row = sqlx::query_as::<_, ReportDefinitionRow>(
-            "SELECT id, report_code, name, description, category, data_source, query_text, parameters, columns, filters, sorting, grouping, chart_type, is_scheduled, schedule_cron, created_by, created_at
-             FROM report_definitions WHERE id = ?"
-        )
-        .bind(id.to_string())
-        .fetch_optional(pool)
-        .await
-        .map_err(Error::Database)?
-        .ok_or_else(|| Error::not_found("ReportDefinition", &id.to_string()))?;
-
-        Ok(row.into())
-    }
-
-    pub async fn list_reports(pool: &SqlitePool, category: Option<&str>) -> Result<Vec<ReportDefinition>> {
-        let rows = if let Some(cat) = category {
-            sqlx::query_as::<_, ReportDefinitionRow>(
-                "SELECT id, report_code, name, description, category, data_source, query_text, parameters, columns, filters, sorting, grouping, chart_type, is_scheduled, schedule_cron, created_by, created_at
-                 FROM report_definitions WHERE category = ? ORDER BY name"
-            )
-            .bind(cat)
-            .fetch_all(pool)
-            .await
-            .map_err(Error::Database)?
-        } else {
-            sqlx::query_as::<_, ReportDefinitionRow>(
-                "SELECT id, report_code, name, description, category, data_source, query_text, parameters, columns, filters, sorting, grouping, chart_type, is_scheduled, schedule_cron, created_by, created_at
-                 FROM report_definitions ORDER BY name"
-            )
-            .fetch_all(pool)
-            .await
-            .map_err(Error::Database)?
-        };
-
-        Ok(rows.into_iter().map(|r| r.into()).collect())
-    }
-
-    pub async fn execute_report(
-        pool: &SqlitePool,
-        report_id: Uuid,
-        parameters: Option<&str>,
-        created_by: Option<Uuid>,
-    ) -> Result<ReportExecution> {
-        let report = Self::get_report(pool, report_id).await?;
-        let start = std::time::Instant::now();
-        let now = Utc::now();
-
-        let mut execution = ReportExecution {
-            id: Uuid::new_v4(),
-            report_id,
-            parameters: parameters.map(|s| s.to_string()),
-            row_count: None,
-            file_path: None,
-            file_format: None,
-            file_size: None,
-            execution_time_ms: Some(start.elapsed().as_millis() as i32),
-            status: ReportExecutionStatus::Running,
-            error_message: None,
-            created_by,
-            created_at: now,
-        };
-
-        sqlx::query(
-            "INSERT INTO report_executions (id, report_id, parameters, row_count, file_path, file_format, file_size, execution_time_ms, status, error_message, created_by, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-        )
-        .bind(execution.id.to_string())
-        .bind(execution.report_id.to_string())
-        .bind(&execution.parameters)
-        .bind(execution.row_count)
-        .bind(&execution.file_path)
-        .bind(&execution.file_format)
-        .bind(execution.file_size)
-        .bind(execution.execution_time_ms)
-        .bind(format!("{:?}", execution.status))
-        .bind(&execution.error_message)
-        .bind(execution.created_by.map(|id| id.to_string()))
-        .bind(execution.created_at.to_rfc3339())
-        .execute(pool)
-        .await
-        .map_err(Error::Database)?;
-
-        let count: (i64,) = sqlx::query_as(&report.query_text)
-            .fetch_one(pool)
-            .await
-            .map_err(Error::Database)?;
-
-        execution.row_count = Some(count.0 as i32);
-        execution.execution_time_ms = Some(start.elapsed().as_millis() as i32);
-        execution.status = ReportExecutionStatus::Completed;
-
-        sqlx::query(
-            "UPDATE report_executions SET row_count = ?, execution_time_ms = ?, status = ? WHERE id = ?"
-        )
-        .bind(execution.row_count)
-        .bind(execution.execution_time_ms)
-        .bind(format!("{:?}", execution.status))
-        .bind(execution.id.to_string())
-        .execute(pool)
-        .await
-        .map_err(Error::Database)?;
-
-        Ok(execution)
-    }
-
-    pub async fn get_results(pool: &SqlitePool, execution_id: Uuid) -> Result<ReportExecution> {
-        let row = sqlx::query_as::<_, ReportExecutionRow>(
-            "SELECT id, report_id, parameters, row_count, file_path, file_format, file_size, execution_time_ms, status, error_message, created_by, created_at
-             FROM report_executions WHERE id = ?"
-        )
-        .bind(execution_id.to_string())
-        .fetch_optional(pool)
-        .await
-        .map_err(Error::Database)?
-        .ok_or_else(|| Error::not_found("ReportExecution", &execution_id.to_string()))?;
-
-        Ok(row.into())
-    }
-
-    pub async fn schedule_report(
-        pool: &SqlitePool,
-        report_id: Uuid,
-        schedule_cron: &str,
-    ) -> Result<ReportDefinition> {
-        let mut report = Self::get_report(pool, report_id).await?;
-        report.is_scheduled = true;
-        report.schedule_cron = Some(schedule_cron.to_string());
-
-        sqlx::query(
-            "UPDATE report_definitions SET is_scheduled = 1, schedule_cron = ? WHERE id = ?"
-        )
-        .bind(&report.schedule_cron)
-        .bind(report_id.to_string())
-        .execute(pool)
-        .await
-        .map_err(Error::Database)?;
-
-        Ok(report)
-    }
-}
-
 pub struct MobileService;
 
 impl MobileService {
@@ -1396,91 +1186,6 @@ impl From<EmailQueueRow> for EmailQueue {
     }
 }
 
-#[derive(sqlx::FromRow)]
-struct ReportDefinitionRow {
-    id: String,
-    report_code: String,
-    name: String,
-    description: Option<String>,
-    category: Option<String>,
-    data_source: String,
-    query_text: String,
-    parameters: Option<String>,
-    columns: Option<String>,
-    filters: Option<String>,
-    sorting: Option<String>,
-    grouping: Option<String>,
-    chart_type: Option<String>,
-    is_scheduled: i64,
-    schedule_cron: Option<String>,
-    created_by: Option<String>,
-    created_at: String,
-}
-
-impl From<ReportDefinitionRow> for ReportDefinition {
-    fn from(r: ReportDefinitionRow) -> Self {
-        Self {
-            id: Uuid::parse_str(&r.id).unwrap_or_default(),
-            report_code: r.report_code,
-            name: r.name,
-            description: r.description,
-            category: r.category,
-            data_source: r.data_source,
-            query_text: r.query_text,
-            parameters: r.parameters,
-            columns: r.columns,
-            filters: r.filters,
-            sorting: r.sorting,
-            grouping: r.grouping,
-            chart_type: r.chart_type,
-            is_scheduled: r.is_scheduled != 0,
-            schedule_cron: r.schedule_cron,
-            created_by: r.created_by.and_then(|id| Uuid::parse_str(&id).ok()),
-            created_at: parse_datetime(&r.created_at),
-        }
-    }
-}
-
-#[derive(sqlx::FromRow)]
-struct ReportExecutionRow {
-    id: String,
-    report_id: String,
-    parameters: Option<String>,
-    row_count: Option<i64>,
-    file_path: Option<String>,
-    file_format: Option<String>,
-    file_size: Option<i64>,
-    execution_time_ms: Option<i64>,
-    status: String,
-    error_message: Option<String>,
-    created_by: Option<String>,
-    created_at: String,
-}
-
-impl From<ReportExecutionRow> for ReportExecution {
-    fn from(r: ReportExecutionRow) -> Self {
-        Self {
-            id: Uuid::parse_str(&r.id).unwrap_or_default(),
-            report_id: Uuid::parse_str(&r.report_id).unwrap_or_default(),
-            parameters: r.parameters,
-            row_count: r.row_count.map(|v| v as i32),
-            file_path: r.file_path,
-            file_format: r.file_format,
-            file_size: r.file_size.map(|v| v as i32),
-            execution_time_ms: r.execution_time_ms.map(|v| v as i32),
-            status: match r.status.as_str() {
-                "Completed" => ReportExecutionStatus::Completed,
-                "Failed" => ReportExecutionStatus::Failed,
-                "Cancelled" => ReportExecutionStatus::Cancelled,
-                _ => ReportExecutionStatus::Running,
-            },
-            error_message: r.error_message,
-            created_by: r.created_by.and_then(|id| Uuid::parse_str(&id).ok()),
-            created_at: parse_datetime(&r.created_at),
-        }
-    }
-}
-
 #[derive(sqlx::FromRow)]
 struct PushNotificationRow {
     id: String,
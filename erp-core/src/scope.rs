@@ -0,0 +1,57 @@
+use sqlx::query::{Query, QueryAs};
+use sqlx::sqlite::SqliteArguments;
+use sqlx::Sqlite;
+
+/// Extra SQL predicates limiting which rows of a table a caller may read. Repositories append
+/// [`RowScope::sql`] to their WHERE clause and bind [`RowScope::binds`] after their own parameters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowScope {
+    predicates: Vec<String>,
+    binds: Vec<String>,
+}
+
+impl RowScope {
+    pub fn unrestricted() -> Self {
+        Self::default()
+    }
+
+    /// A scope that matches no rows.
+    pub fn deny() -> Self {
+        Self::unrestricted().and("0 = 1", Vec::new())
+    }
+
+    /// Adds a predicate that every row must satisfy. Placeholders in `predicate` are bound,
+    /// in order, to `binds`.
+    pub fn and(mut self, predicate: impl Into<String>, binds: Vec<String>) -> Self {
+        self.predicates.push(predicate.into());
+        self.binds.extend(binds);
+        self
+    }
+
+    pub fn is_unrestricted(&self) -> bool {
+        self.predicates.is_empty()
+    }
+
+    /// The predicates as ` AND (...)` fragments, or an empty string when unrestricted.
+    pub fn sql(&self) -> String {
+        self.predicates.iter().map(|p| format!(" AND ({})", p)).collect()
+    }
+
+    pub fn binds(&self) -> &[String] {
+        &self.binds
+    }
+
+    pub fn bind_query<'q>(&'q self, mut query: Query<'q, Sqlite, SqliteArguments<'q>>) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+        for value in &self.binds {
+            query = query.bind(value.as_str());
+        }
+        query
+    }
+
+    pub fn bind_query_as<'q, O>(&'q self, mut query: QueryAs<'q, Sqlite, O, SqliteArguments<'q>>) -> QueryAs<'q, Sqlite, O, SqliteArguments<'q>> {
+        for value in &self.binds {
+            query = query.bind(value.as_str());
+        }
+        query
+    }
+}
@@ -0,0 +1,23 @@
+//! Helpers for crate tests that run against an in-memory database.
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Executor, SqlitePool};
+use std::str::FromStr;
+
+/// A single-connection in-memory database with `migrations` applied in order.
+/// Panics when a migration fails, so a broken schema fails the test that needs it.
+pub async fn memory_pool(migrations: &[&str]) -> SqlitePool {
+    let options = SqliteConnectOptions::from_str("sqlite::memory:").unwrap().foreign_keys(false);
+    let pool = SqlitePoolOptions::new().max_connections(1).connect_with(options).await.unwrap();
+    for migration in migrations {
+        pool.execute(*migration).await.unwrap();
+    }
+    pool
+}
+
+pub async fn insert(pool: &SqlitePool, sql: &str, binds: &[&str]) {
+    let mut query = sqlx::query(sql);
+    for value in binds {
+        query = query.bind(*value);
+    }
+    query.execute(pool).await.unwrap();
+}
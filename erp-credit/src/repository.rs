@@ -2,7 +2,7 @@ use crate::models::*;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use erp_core::BaseEntity;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use anyhow::Result;
 
@@ -37,6 +37,190 @@ pub trait CreditRepository: Send + Sync {
 pub struct SqliteCreditRepository;
 
 impl SqliteCreditRepository {
+    pub async fn get_profile_in(&self, conn: &mut SqliteConnection, customer_id: Uuid) -> Result<Option<CustomerCreditProfile>> {
+        let row = sqlx::query(
+            "SELECT * FROM customer_credit_profiles WHERE customer_id = ?"
+        )
+        .bind(customer_id.to_string())
+        .fetch_optional(&mut *conn)
+        .await?;
+        
+        match row {
+            Some(r) => Ok(Some(Self::row_to_profile(&r)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn create_profile_in(&self, conn: &mut SqliteConnection, profile: &CustomerCreditProfile) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO customer_credit_profiles 
+             (id, customer_id, credit_limit, credit_used, available_credit, outstanding_invoices, 
+              pending_orders, overdue_amount, overdue_days_avg, credit_score, risk_level, 
+              payment_history_score, last_credit_review, next_review_date, auto_hold_enabled, 
+              hold_threshold_percent, status, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(profile.base.id.to_string())
+        .bind(profile.customer_id.to_string())
+        .bind(profile.credit_limit)
+        .bind(profile.credit_used)
+        .bind(profile.available_credit)
+        .bind(profile.outstanding_invoices)
+        .bind(profile.pending_orders)
+        .bind(profile.overdue_amount)
+        .bind(profile.overdue_days_avg)
+        .bind(profile.credit_score)
+        .bind(serde_json::to_string(&profile.risk_level)?)
+        .bind(profile.payment_history_score)
+        .bind(profile.last_credit_review.map(|d| d.to_rfc3339()))
+        .bind(profile.next_review_date.map(|d| d.to_rfc3339()))
+        .bind(profile.auto_hold_enabled)
+        .bind(profile.hold_threshold_percent)
+        .bind(serde_json::to_string(&profile.status)?)
+        .bind(profile.created_at.to_rfc3339())
+        .bind(profile.updated_at.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_profile_in(&self, conn: &mut SqliteConnection, profile: &CustomerCreditProfile) -> Result<()> {
+        sqlx::query(
+            "UPDATE customer_credit_profiles SET 
+             credit_limit = ?, credit_used = ?, available_credit = ?, outstanding_invoices = ?,
+             pending_orders = ?, overdue_amount = ?, overdue_days_avg = ?, credit_score = ?,
+             risk_level = ?, payment_history_score = ?, last_credit_review = ?, next_review_date = ?,
+             auto_hold_enabled = ?, hold_threshold_percent = ?, status = ?, updated_at = ?
+             WHERE id = ?"
+        )
+        .bind(profile.credit_limit)
+        .bind(profile.credit_used)
+        .bind(profile.available_credit)
+        .bind(profile.outstanding_invoices)
+        .bind(profile.pending_orders)
+        .bind(profile.overdue_amount)
+        .bind(profile.overdue_days_avg)
+        .bind(profile.credit_score)
+        .bind(serde_json::to_string(&profile.risk_level)?)
+        .bind(profile.payment_history_score)
+        .bind(profile.last_credit_review.map(|d| d.to_rfc3339()))
+        .bind(profile.next_review_date.map(|d| d.to_rfc3339()))
+        .bind(profile.auto_hold_enabled)
+        .bind(profile.hold_threshold_percent)
+        .bind(serde_json::to_string(&profile.status)?)
+        .bind(Utc::now().to_rfc3339())
+        .bind(profile.base.id.to_string())
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn create_transaction_in(&self, conn: &mut SqliteConnection, txn: &CreditTransaction) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO credit_transactions 
+             (id, profile_id, customer_id, transaction_type, amount, previous_credit_used, 
+              new_credit_used, reference_type, reference_id, reference_number, description, created_by, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(txn.id.to_string())
+        .bind(txn.profile_id.to_string())
+        .bind(txn.customer_id.to_string())
+        .bind(serde_json::to_string(&txn.transaction_type)?)
+        .bind(txn.amount)
+        .bind(txn.previous_credit_used)
+        .bind(txn.new_credit_used)
+        .bind(&txn.reference_type)
+        .bind(txn.reference_id.map(|id| id.to_string()))
+        .bind(&txn.reference_number)
+        .bind(&txn.description)
+        .bind(txn.created_by.map(|id| id.to_string()))
+        .bind(txn.created_at.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn create_hold_in(&self, conn: &mut SqliteConnection, hold: &CreditHold) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO credit_holds 
+             (id, profile_id, customer_id, hold_type, reason, amount_over_limit, 
+              related_order_id, related_invoice_id, status, placed_by, placed_at, 
+              released_by, released_at, override_reason, notes, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(hold.id.to_string())
+        .bind(hold.profile_id.to_string())
+        .bind(hold.customer_id.to_string())
+        .bind(serde_json::to_string(&hold.hold_type)?)
+        .bind(&hold.reason)
+        .bind(hold.amount_over_limit)
+        .bind(hold.related_order_id.map(|id| id.to_string()))
+        .bind(hold.related_invoice_id.map(|id| id.to_string()))
+        .bind(serde_json::to_string(&hold.status)?)
+        .bind(hold.placed_by.map(|id| id.to_string()))
+        .bind(hold.placed_at.to_rfc3339())
+        .bind(hold.released_by.map(|id| id.to_string()))
+        .bind(hold.released_at.map(|d| d.to_rfc3339()))
+        .bind(&hold.override_reason)
+        .bind(&hold.notes)
+        .bind(hold.created_at.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_active_hold_in(&self, conn: &mut SqliteConnection, customer_id: Uuid) -> Result<Option<CreditHold>> {
+        let row = sqlx::query(
+            "SELECT * FROM credit_holds WHERE customer_id = ? AND status = '\"Active\"'"
+        )
+        .bind(customer_id.to_string())
+        .fetch_optional(&mut *conn)
+        .await?;
+        
+        match row {
+            Some(r) => Ok(Some(Self::row_to_hold(&r)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn release_hold_in(&self, conn: &mut SqliteConnection, hold_id: Uuid, released_by: Option<Uuid>, override_reason: Option<String>) -> Result<()> {
+        let now = Utc::now();
+        sqlx::query(
+            "UPDATE credit_holds SET status = '\"Released\"', released_by = ?, released_at = ?, override_reason = ? WHERE id = ?"
+        )
+        .bind(released_by.map(|id| id.to_string()))
+        .bind(now.to_rfc3339())
+        .bind(&override_reason)
+        .bind(hold_id.to_string())
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn create_alert_in(&self, conn: &mut SqliteConnection, alert: &CreditAlert) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO credit_alerts 
+             (id, profile_id, customer_id, alert_type, severity, message, 
+              threshold_value, actual_value, is_read, acknowledged_by, acknowledged_at, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(alert.id.to_string())
+        .bind(alert.profile_id.to_string())
+        .bind(alert.customer_id.to_string())
+        .bind(serde_json::to_string(&alert.alert_type)?)
+        .bind(serde_json::to_string(&alert.severity)?)
+        .bind(&alert.message)
+        .bind(alert.threshold_value)
+        .bind(alert.actual_value)
+        .bind(alert.is_read)
+        .bind(alert.acknowledged_by.map(|id| id.to_string()))
+        .bind(alert.acknowledged_at.map(|d| d.to_rfc3339()))
+        .bind(alert.created_at.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
     fn row_to_profile(row: &sqlx::sqlite::SqliteRow) -> Result<CustomerCreditProfile> {
         use sqlx::Row;
         Ok(CustomerCreditProfile {
@@ -152,17 +336,7 @@ impl SqliteCreditRepository {
 #[async_trait]
 impl CreditRepository for SqliteCreditRepository {
     async fn get_profile(&self, pool: &SqlitePool, customer_id: Uuid) -> Result<Option<CustomerCreditProfile>> {
-        let row = sqlx::query(
-            "SELECT * FROM customer_credit_profiles WHERE customer_id = ?"
-        )
-        .bind(customer_id.to_string())
-        .fetch_optional(pool)
-        .await?;
-        
-        match row {
-            Some(r) => Ok(Some(Self::row_to_profile(&r)?)),
-            None => Ok(None),
-        }
+        self.get_profile_in(&mut *pool.acquire().await?, customer_id).await
     }
     
     async fn get_profile_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<Option<CustomerCreditProfile>> {
@@ -180,67 +354,11 @@ impl CreditRepository for SqliteCreditRepository {
     }
     
     async fn create_profile(&self, pool: &SqlitePool, profile: &CustomerCreditProfile) -> Result<()> {
-        sqlx::query(
-            "INSERT INTO customer_credit_profiles 
-             (id, customer_id, credit_limit, credit_used, available_credit, outstanding_invoices, 
-              pending_orders, overdue_amount, overdue_days_avg, credit_score, risk_level, 
-              payment_history_score, last_credit_review, next_review_date, auto_hold_enabled, 
-              hold_threshold_percent, status, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-        )
-        .bind(profile.base.id.to_string())
-        .bind(profile.customer_id.to_string())
-        .bind(profile.credit_limit)
-        .bind(profile.credit_used)
-        .bind(profile.available_credit)
-        .bind(profile.outstanding_invoices)
-        .bind(profile.pending_orders)
-        .bind(profile.overdue_amount)
-        .bind(profile.overdue_days_avg)
-        .bind(profile.credit_score)
-        .bind(serde_json::to_string(&profile.risk_level)?)
-        .bind(profile.payment_history_score)
-        .bind(profile.last_credit_review.map(|d| d.to_rfc3339()))
-        .bind(profile.next_review_date.map(|d| d.to_rfc3339()))
-        .bind(profile.auto_hold_enabled)
-        .bind(profile.hold_threshold_percent)
-        .bind(serde_json::to_string(&profile.status)?)
-        .bind(profile.created_at.to_rfc3339())
-        .bind(profile.updated_at.to_rfc3339())
-        .execute(pool)
-        .await?;
-        Ok(())
+        self.create_profile_in(&mut *pool.acquire().await?, profile).await
     }
     
     async fn update_profile(&self, pool: &SqlitePool, profile: &CustomerCreditProfile) -> Result<()> {
-        sqlx::query(
-            "UPDATE customer_credit_profiles SET 
-             credit_limit = ?, credit_used = ?, available_credit = ?, outstanding_invoices = ?,
-             pending_orders = ?, overdue_amount = ?, overdue_days_avg = ?, credit_score = ?,
-             risk_level = ?, payment_history_score = ?, last_credit_review = ?, next_review_date = ?,
-             auto_hold_enabled = ?, hold_threshold_percent = ?, status = ?, updated_at = ?
-             WHERE id = ?"
-        )
-        .bind(profile.credit_limit)
-        .bind(profile.credit_used)
-        .bind(profile.available_credit)
-        .bind(profile.outstanding_invoices)
-        .bind(profile.pending_orders)
-        .bind(profile.overdue_amount)
-        .bind(profile.overdue_days_avg)
-        .bind(profile.credit_score)
-        .bind(serde_json::to_string(&profile.risk_level)?)
-        .bind(profile.payment_history_score)
-        .bind(profile.last_credit_review.map(|d| d.to_rfc3339()))
-        .bind(profile.next_review_date.map(|d| d.to_rfc3339()))
-        .bind(profile.auto_hold_enabled)
-        .bind(profile.hold_threshold_percent)
-        .bind(serde_json::to_string(&profile.status)?)
-        .bind(Utc::now().to_rfc3339())
-        .bind(profile.base.id.to_string())
-        .execute(pool)
-        .await?;
-        Ok(())
+        self.update_profile_in(&mut *pool.acquire().await?, profile).await
     }
     
     async fn list_profiles(&self, pool: &SqlitePool, page: i64, limit: i64) -> Result<Vec<CustomerCreditProfile>> {
@@ -281,28 +399,7 @@ impl CreditRepository for SqliteCreditRepository {
     }
     
     async fn create_transaction(&self, pool: &SqlitePool, txn: &CreditTransaction) -> Result<()> {
-        sqlx::query(
-            "INSERT INTO credit_transactions 
-             (id, profile_id, customer_id, transaction_type, amount, previous_credit_used, 
-              new_credit_used, reference_type, reference_id, reference_number, description, created_by, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-        )
-        .bind(txn.id.to_string())
-        .bind(txn.profile_id.to_string())
-        .bind(txn.customer_id.to_string())
-        .bind(serde_json::to_string(&txn.transaction_type)?)
-        .bind(txn.amount)
-        .bind(txn.previous_credit_used)
-        .bind(txn.new_credit_used)
-        .bind(&txn.reference_type)
-        .bind(txn.reference_id.map(|id| id.to_string()))
-        .bind(&txn.reference_number)
-        .bind(&txn.description)
-        .bind(txn.created_by.map(|id| id.to_string()))
-        .bind(txn.created_at.to_rfc3339())
-        .execute(pool)
-        .await?;
-        Ok(())
+        self.create_transaction_in(&mut *pool.acquire().await?, txn).await
     }
     
     async fn list_transactions(&self, pool: &SqlitePool, customer_id: Uuid, limit: i64) -> Result<Vec<CreditTransaction>> {
@@ -318,60 +415,15 @@ impl CreditRepository for SqliteCreditRepository {
     }
     
     async fn create_hold(&self, pool: &SqlitePool, hold: &CreditHold) -> Result<()> {
-        sqlx::query(
-            "INSERT INTO credit_holds 
-             (id, profile_id, customer_id, hold_type, reason, amount_over_limit, 
-              related_order_id, related_invoice_id, status, placed_by, placed_at, 
-              released_by, released_at, override_reason, notes, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-        )
-        .bind(hold.id.to_string())
-        .bind(hold.profile_id.to_string())
-        .bind(hold.customer_id.to_string())
-        .bind(serde_json::to_string(&hold.hold_type)?)
-        .bind(&hold.reason)
-        .bind(hold.amount_over_limit)
-        .bind(hold.related_order_id.map(|id| id.to_string()))
-        .bind(hold.related_invoice_id.map(|id| id.to_string()))
-        .bind(serde_json::to_string(&hold.status)?)
-        .bind(hold.placed_by.map(|id| id.to_string()))
-        .bind(hold.placed_at.to_rfc3339())
-        .bind(hold.released_by.map(|id| id.to_string()))
-        .bind(hold.released_at.map(|d| d.to_rfc3339()))
-        .bind(&hold.override_reason)
-        .bind(&hold.notes)
-        .bind(hold.created_at.to_rfc3339())
-        .execute(pool)
-        .await?;
-        Ok(())
+        self.create_hold_in(&mut *pool.acquire().await?, hold).await
     }
     
     async fn get_active_hold(&self, pool: &SqlitePool, customer_id: Uuid) -> Result<Option<CreditHold>> {
-        let row = sqlx::query(
-            "SELECT * FROM credit_holds WHERE customer_id = ? AND status = '\"Active\"'"
-        )
-        .bind(customer_id.to_string())
-        .fetch_optional(pool)
-        .await?;
-        
-        match row {
-            Some(r) => Ok(Some(Self::row_to_hold(&r)?)),
-            None => Ok(None),
-        }
+        self.get_active_hold_in(&mut *pool.acquire().await?, customer_id).await
     }
     
     async fn release_hold(&self, pool: &SqlitePool, hold_id: Uuid, released_by: Option<Uuid>, override_reason: Option<String>) -> Result<()> {
-        let now = Utc::now();
-        sqlx::query(
-            "UPDATE credit_holds SET status = '\"Released\"', released_by = ?, released_at = ?, override_reason = ? WHERE id = ?"
-        )
-        .bind(released_by.map(|id| id.to_string()))
-        .bind(now.to_rfc3339())
-        .bind(&override_reason)
-        .bind(hold_id.to_string())
-        .execute(pool)
-        .await?;
-        Ok(())
+        self.release_hold_in(&mut *pool.acquire().await?, hold_id, released_by, override_reason).await
     }
     
     async fn list_holds(&self, pool: &SqlitePool, customer_id: Uuid) -> Result<Vec<CreditHold>> {
@@ -420,27 +472,7 @@ impl CreditRepository for SqliteCreditRepository {
     }
     
     async fn create_alert(&self, pool: &SqlitePool, alert: &CreditAlert) -> Result<()> {
-        sqlx::query(
-            "INSERT INTO credit_alerts 
-             (id, profile_id, customer_id, alert_type, severity, message, 
-              threshold_value, actual_value, is_read, acknowledged_by, acknowledged_at, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-        )
-        .bind(alert.id.to_string())
-        .bind(alert.profile_id.to_string())
-        .bind(alert.customer_id.to_string())
-        .bind(serde_json::to_string(&alert.alert_type)?)
-        .bind(serde_json::to_string(&alert.severity)?)
-        .bind(&alert.message)
-        .bind(alert.threshold_value)
-        .bind(alert.actual_value)
-        .bind(alert.is_read)
-        .bind(alert.acknowledged_by.map(|id| id.to_string()))
-        .bind(alert.acknowledged_at.map(|d| d.to_rfc3339()))
-        .bind(alert.created_at.to_rfc3339())
-        .execute(pool)
-        .await?;
-        Ok(())
+        self.create_alert_in(&mut *pool.acquire().await?, alert).await
     }
     
     async fn list_unread_alerts(&self, pool: &SqlitePool) -> Result<Vec<CreditAlert>> {
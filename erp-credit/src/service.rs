@@ -3,7 +3,7 @@ use crate::repository::{CreditRepository, SqliteCreditRepository};
 use anyhow::Result;
 use chrono::Utc;
 use erp_core::BaseEntity;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use tracing::info;
 use uuid::Uuid;
 
@@ -127,7 +127,14 @@ impl CreditService {
     }
     
     pub async fn get_or_create_profile(&self, pool: &SqlitePool, customer_id: Uuid, initial_limit: i64) -> Result<CustomerCreditProfile> {
-        if let Some(profile) = self.repo.get_profile(pool, customer_id).await? {
+        let mut tx = pool.begin().await?;
+        let profile = self.get_or_create_profile_in(&mut tx, customer_id, initial_limit).await?;
+        tx.commit().await?;
+        Ok(profile)
+    }
+
+    pub async fn get_or_create_profile_in(&self, conn: &mut SqliteConnection, customer_id: Uuid, initial_limit: i64) -> Result<CustomerCreditProfile> {
+        if let Some(profile) = self.repo.get_profile_in(conn, customer_id).await? {
             return Ok(profile);
         }
         
@@ -154,7 +161,7 @@ impl CreditService {
             updated_at: now,
         };
         
-        self.repo.create_profile(pool, &profile).await?;
+        self.repo.create_profile_in(conn, &profile).await?;
         Ok(profile)
     }
     
@@ -231,7 +238,14 @@ impl CreditService {
     }
     
     pub async fn record_invoice(&self, pool: &SqlitePool, customer_id: Uuid, invoice_id: Uuid, invoice_number: String, amount: i64, user_id: Option<Uuid>) -> Result<CustomerCreditProfile> {
-        let mut profile = self.get_or_create_profile(pool, customer_id, 0).await?;
+        let mut tx = pool.begin().await?;
+        let profile = self.record_invoice_in(&mut tx, customer_id, invoice_id, invoice_number, amount, user_id).await?;
+        tx.commit().await?;
+        Ok(profile)
+    }
+
+    pub async fn record_invoice_in(&self, conn: &mut SqliteConnection, customer_id: Uuid, invoice_id: Uuid, invoice_number: String, amount: i64, user_id: Option<Uuid>) -> Result<CustomerCreditProfile> {
+        let mut profile = self.get_or_create_profile_in(conn, customer_id, 0).await?;
         
         let previous_used = profile.credit_used;
         profile.credit_used += amount;
@@ -257,12 +271,12 @@ impl CreditService {
             created_by: user_id,
             created_at: Utc::now(),
         };
-        self.repo.create_transaction(pool, &txn).await?;
+        self.repo.create_transaction_in(conn, &txn).await?;
         
-        self.repo.update_profile(pool, &profile).await?;
+        self.repo.update_profile_in(conn, &profile).await?;
         
         if profile.credit_used > profile.credit_limit && profile.auto_hold_enabled {
-            let existing_hold = self.repo.get_active_hold(pool, customer_id).await?;
+            let existing_hold = self.repo.get_active_hold_in(conn, customer_id).await?;
             if existing_hold.is_none() {
                 let hold = CreditHold {
                     id: Uuid::new_v4(),
@@ -283,12 +297,12 @@ impl CreditService {
                     notes: None,
                     created_at: Utc::now(),
                 };
-                self.repo.create_hold(pool, &hold).await?;
-                self.create_alert(pool, &profile, CreditAlertType::LimitExceeded, AlertSeverity::Critical,
+                self.repo.create_hold_in(conn, &hold).await?;
+                self.create_alert_in(conn, &profile, CreditAlertType::LimitExceeded, AlertSeverity::Critical,
                     format!("Credit limit exceeded by ${:.2}", (profile.credit_used - profile.credit_limit) as f64 / 100.0)).await?;
             }
         } else if profile.available_credit < profile.credit_limit / 10 {
-            self.create_alert(pool, &profile, CreditAlertType::ApproachingLimit, AlertSeverity::Warning,
+            self.create_alert_in(conn, &profile, CreditAlertType::ApproachingLimit, AlertSeverity::Warning,
                 format!("Only ${:.2} credit available", profile.available_credit as f64 / 100.0)).await?;
         }
         
@@ -296,7 +310,14 @@ impl CreditService {
     }
     
     pub async fn record_payment(&self, pool: &SqlitePool, customer_id: Uuid, invoice_id: Option<Uuid>, amount: i64, user_id: Option<Uuid>) -> Result<CustomerCreditProfile> {
-        let mut profile = self.repo.get_profile(pool, customer_id).await?
+        let mut tx = pool.begin().await?;
+        let profile = self.record_payment_in(&mut tx, customer_id, invoice_id, amount, user_id).await?;
+        tx.commit().await?;
+        Ok(profile)
+    }
+
+    pub async fn record_payment_in(&self, conn: &mut SqliteConnection, customer_id: Uuid, invoice_id: Option<Uuid>, amount: i64, user_id: Option<Uuid>) -> Result<CustomerCreditProfile> {
+        let mut profile = self.repo.get_profile_in(conn, customer_id).await?
             .ok_or_else(|| anyhow::anyhow!("Credit profile not found"))?;
         
         let previous_used = profile.credit_used;
@@ -320,18 +341,18 @@ impl CreditService {
             created_by: user_id,
             created_at: Utc::now(),
         };
-        self.repo.create_transaction(pool, &txn).await?;
+        self.repo.create_transaction_in(conn, &txn).await?;
         
-        if let Some(hold) = self.repo.get_active_hold(pool, customer_id).await? {
+        if let Some(hold) = self.repo.get_active_hold_in(conn, customer_id).await? {
             if profile.credit_used <= profile.credit_limit {
-                self.repo.release_hold(pool, hold.id, user_id, Some("Payment received - credit within limit".to_string())).await?;
-                self.create_alert(pool, &profile, CreditAlertType::HoldReleased, AlertSeverity::Info,
+                self.repo.release_hold_in(conn, hold.id, user_id, Some("Payment received - credit within limit".to_string())).await?;
+                self.create_alert_in(conn, &profile, CreditAlertType::HoldReleased, AlertSeverity::Info,
                     "Credit hold released after payment".to_string()).await?;
             }
         }
         
         self.update_risk_level(&mut profile);
-        self.repo.update_profile(pool, &profile).await?;
+        self.repo.update_profile_in(conn, &profile).await?;
         
         Ok(profile)
     }
@@ -451,6 +472,10 @@ impl CreditService {
     pub async fn get_profile(&self, pool: &SqlitePool, customer_id: Uuid) -> Result<Option<CustomerCreditProfile>> {
         self.repo.get_profile(pool, customer_id).await
     }
+
+    pub async fn get_profile_in(&self, conn: &mut SqliteConnection, customer_id: Uuid) -> Result<Option<CustomerCreditProfile>> {
+        self.repo.get_profile_in(conn, customer_id).await
+    }
     
     pub async fn list_profiles(&self, pool: &SqlitePool, page: i64, limit: i64) -> Result<Vec<CustomerCreditProfile>> {
         self.repo.list_profiles(pool, page, limit).await
@@ -514,6 +539,11 @@ impl CreditService {
     
     async fn create_alert(&self, pool: &SqlitePool, profile: &CustomerCreditProfile, 
                           alert_type: CreditAlertType, severity: AlertSeverity, message: String) -> Result<()> {
+        self.create_alert_in(&mut *pool.acquire().await?, profile, alert_type, severity, message).await
+    }
+
+    async fn create_alert_in(&self, conn: &mut SqliteConnection, profile: &CustomerCreditProfile,
+                             alert_type: CreditAlertType, severity: AlertSeverity, message: String) -> Result<()> {
         let alert = CreditAlert {
             id: Uuid::new_v4(),
             profile_id: profile.base.id,
@@ -528,6 +558,6 @@ impl CreditService {
             acknowledged_at: None,
             created_at: Utc::now(),
         };
-        self.repo.create_alert(pool, &alert).await
+        self.repo.create_alert_in(conn, &alert).await
     }
 }
@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use erp_core::{BaseEntity, Status};
+use erp_core::{BaseEntity, Status, UploadPolicy};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -51,6 +51,20 @@ pub struct DocumentFolder {
     pub path: String,
     pub description: Option<String>,
     pub status: Status,
+    pub max_file_size: Option<i64>,
+    /// Comma-separated content types, e.g. `application/pdf,image/*`.
+    pub allowed_mime_types: Option<String>,
+}
+
+impl DocumentFolder {
+    /// The folder's upload limits, falling back to `default` for any it leaves unset.
+    pub fn upload_policy(&self, default: &UploadPolicy) -> UploadPolicy {
+        let allowed_mime_types = UploadPolicy::parse_mime_types(self.allowed_mime_types.as_deref());
+        UploadPolicy {
+            max_size: self.max_file_size.map(|n| n.max(0) as u64).unwrap_or(default.max_size),
+            allowed_mime_types: if allowed_mime_types.is_empty() { default.allowed_mime_types.clone() } else { allowed_mime_types },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,6 +110,17 @@ pub struct DocumentVersion {
     pub status: DocumentStatus,
 }
 
+/// Details of a file uploaded as a new version of a document.
+#[derive(Debug, Clone, Default)]
+pub struct NewVersion {
+    pub file_name: Option<String>,
+    pub mime_type: String,
+    pub change_summary: Option<String>,
+    pub changed_by: Option<Uuid>,
+    /// SHA-256 the client computed, checked against what arrived.
+    pub expected_sha256: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DocumentCheckout {
     pub base: BaseEntity,
@@ -16,6 +16,8 @@ pub trait DocumentRepository: Send + Sync {
     async fn list_documents(&self, pool: &SqlitePool, folder_id: Option<Uuid>, status: Option<DocumentStatus>) -> Result<Vec<Document>>;
     async fn create_version(&self, pool: &SqlitePool, version: DocumentVersion) -> Result<DocumentVersion>;
     async fn list_versions(&self, pool: &SqlitePool, document_id: Uuid) -> Result<Vec<DocumentVersion>>;
+    async fn get_version(&self, pool: &SqlitePool, document_id: Uuid, version: i32) -> Result<DocumentVersion>;
+    async fn update_folder_upload_policy(&self, pool: &SqlitePool, id: Uuid, max_file_size: Option<i64>, allowed_mime_types: Option<String>) -> Result<()>;
     async fn create_checkout(&self, pool: &SqlitePool, checkout: DocumentCheckout) -> Result<DocumentCheckout>;
     async fn checkin(&self, pool: &SqlitePool, id: Uuid) -> Result<()>;
     async fn create_review(&self, pool: &SqlitePool, review: DocumentReview) -> Result<DocumentReview>;
@@ -30,8 +32,8 @@ pub struct SqliteDocumentRepository;
 impl DocumentRepository for SqliteDocumentRepository {
     async fn create_folder(&self, pool: &SqlitePool, folder: DocumentFolder) -> Result<DocumentFolder> {
         sqlx::query(
-            r#"INSERT INTO document_folders (id, name, parent_id, path, description, status, created_at, updated_at, created_by, updated_by)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
+            r#"INSERT INTO document_folders (id, name, parent_id, path, description, status, max_file_size, allowed_mime_types, created_at, updated_at, created_by, updated_by)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
         )
         .bind(folder.base.id.to_string())
         .bind(&folder.name)
@@ -39,6 +41,8 @@ impl DocumentRepository for SqliteDocumentRepository {
         .bind(&folder.path)
         .bind(&folder.description)
         .bind(format!("{:?}", folder.status))
+        .bind(folder.max_file_size)
+        .bind(&folder.allowed_mime_types)
         .bind(folder.base.created_at.to_rfc3339())
         .bind(folder.base.updated_at.to_rfc3339())
         .bind(folder.base.created_by.map(|id| id.to_string()))
@@ -49,11 +53,12 @@ impl DocumentRepository for SqliteDocumentRepository {
 
     async fn get_folder(&self, pool: &SqlitePool, id: Uuid) -> Result<DocumentFolder> {
         let row = sqlx::query(
-            r#"SELECT id, name, parent_id, path, description, status, created_at, updated_at, created_by, updated_by
+            r#"SELECT id, name, parent_id, path, description, status, max_file_size, allowed_mime_types, created_at, updated_at, created_by, updated_by
                FROM document_folders WHERE id = ?"#
         )
         .bind(id.to_string())
-        .fetch_one(pool).await?;
+        .fetch_optional(pool).await?
+        .ok_or_else(|| erp_core::Error::not_found("DocumentFolder", &id.to_string()))?;
         
         Ok(DocumentFolder {
             base: erp_core::BaseEntity {
@@ -68,20 +73,22 @@ impl DocumentRepository for SqliteDocumentRepository {
             path: row.try_get::<&str, _>("path")?.to_string(),
             description: row.try_get::<Option<&str>, _>("description")?.map(|s| s.to_string()),
             status: erp_core::Status::Active,
+            max_file_size: row.try_get::<Option<i64>, _>("max_file_size")?,
+            allowed_mime_types: row.try_get::<Option<&str>, _>("allowed_mime_types")?.map(|s| s.to_string()),
         })
     }
 
     async fn list_folders(&self, pool: &SqlitePool, parent_id: Option<Uuid>) -> Result<Vec<DocumentFolder>> {
         let rows: Vec<sqlx::sqlite::SqliteRow> = if let Some(pid) = parent_id {
             sqlx::query(
-                r#"SELECT id, name, parent_id, path, description, status, created_at, updated_at, created_by, updated_by
+                r#"SELECT id, name, parent_id, path, description, status, max_file_size, allowed_mime_types, created_at, updated_at, created_by, updated_by
                    FROM document_folders WHERE parent_id = ?"#
             )
             .bind(pid.to_string())
             .fetch_all(pool).await?
         } else {
             sqlx::query(
-                r#"SELECT id, name, parent_id, path, description, status, created_at, updated_at, created_by, updated_by
+                r#"SELECT id, name, parent_id, path, description, status, max_file_size, allowed_mime_types, created_at, updated_at, created_by, updated_by
                    FROM document_folders WHERE parent_id IS NULL"#
             )
             .fetch_all(pool).await?
@@ -102,6 +109,8 @@ impl DocumentRepository for SqliteDocumentRepository {
                 path: row.try_get::<&str, _>("path")?.to_string(),
                 description: row.try_get::<Option<&str>, _>("description")?.map(|s| s.to_string()),
                 status: erp_core::Status::Active,
+                max_file_size: row.try_get::<Option<i64>, _>("max_file_size")?,
+                allowed_mime_types: row.try_get::<Option<&str>, _>("allowed_mime_types")?.map(|s| s.to_string()),
             });
         }
         Ok(folders)
@@ -157,7 +166,8 @@ impl DocumentRepository for SqliteDocumentRepository {
                FROM documents WHERE id = ?"#
         )
         .bind(id.to_string())
-        .fetch_one(pool).await?;
+        .fetch_optional(pool).await?
+        .ok_or_else(|| erp_core::Error::not_found("Document", &id.to_string()))?;
         
         Ok(Document {
             base: erp_core::BaseEntity {
@@ -329,28 +339,35 @@ impl DocumentRepository for SqliteDocumentRepository {
         .bind(document_id.to_string())
         .fetch_all(pool).await?;
         
-        let mut versions = Vec::new();
-        for row in rows {
-            versions.push(DocumentVersion {
-                base: erp_core::BaseEntity {
-                    id: erp_core::parse_uuid(row.try_get::<&str, _>("id")?, "id")?,
-                    created_at: erp_core::parse_datetime(row.try_get::<&str, _>("created_at")?, "created_at")?,
-                    updated_at: erp_core::parse_datetime(row.try_get::<&str, _>("updated_at")?, "updated_at")?,
-                    created_by: erp_core::parse_uuid_opt(row.try_get::<Option<&str>, _>("created_by")?, "created_by")?,
-                    updated_by: erp_core::parse_uuid_opt(row.try_get::<Option<&str>, _>("updated_by")?, "updated_by")?,
-                },
-                document_id: erp_core::parse_uuid(row.try_get::<&str, _>("document_id")?, "document_id")?,
-                version: row.try_get::<i32, _>("version")?,
-                revision: row.try_get::<&str, _>("revision")?.to_string(),
-                file_path: row.try_get::<&str, _>("file_path")?.to_string(),
-                file_size: row.try_get::<i64, _>("file_size")?,
-                checksum: row.try_get::<&str, _>("checksum")?.to_string(),
-                change_summary: row.try_get::<Option<&str>, _>("change_summary")?.map(|s| s.to_string()),
-                changed_by: erp_core::parse_uuid_opt(row.try_get::<Option<&str>, _>("changed_by")?, "changed_by")?,
-                status: DocumentStatus::Draft,
-            });
+        rows.iter().map(version_from_row).collect()
+    }
+
+    async fn get_version(&self, pool: &SqlitePool, document_id: Uuid, version: i32) -> Result<DocumentVersion> {
+        let row = sqlx::query(
+            r#"SELECT id, document_id, version, revision, file_path, file_size, checksum,
+               change_summary, changed_by, status, created_at, updated_at, created_by, updated_by
+               FROM document_versions WHERE document_id = ? AND version = ?"#
+        )
+        .bind(document_id.to_string())
+        .bind(version)
+        .fetch_optional(pool).await?
+        .ok_or_else(|| erp_core::Error::not_found("DocumentVersion", &format!("{} v{}", document_id, version)))?;
+        version_from_row(&row)
+    }
+
+    async fn update_folder_upload_policy(&self, pool: &SqlitePool, id: Uuid, max_file_size: Option<i64>, allowed_mime_types: Option<String>) -> Result<()> {
+        let result = sqlx::query(
+            "UPDATE document_folders SET max_file_size = ?, allowed_mime_types = ?, updated_at = ? WHERE id = ?"
+        )
+        .bind(max_file_size)
+        .bind(allowed_mime_types)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(id.to_string())
+        .execute(pool).await?;
+        if result.rows_affected() == 0 {
+            return Err(erp_core::Error::not_found("DocumentFolder", &id.to_string()));
         }
-        Ok(versions)
+        Ok(())
     }
 
     async fn create_checkout(&self, pool: &SqlitePool, checkout: DocumentCheckout) -> Result<DocumentCheckout> {
@@ -476,3 +493,24 @@ impl DocumentRepository for SqliteDocumentRepository {
         Ok(policy)
     }
 }
+
+fn version_from_row(row: &sqlx::sqlite::SqliteRow) -> Result<DocumentVersion> {
+    Ok(DocumentVersion {
+        base: erp_core::BaseEntity {
+            id: erp_core::parse_uuid(row.try_get::<&str, _>("id")?, "id")?,
+            created_at: erp_core::parse_datetime(row.try_get::<&str, _>("created_at")?, "created_at")?,
+            updated_at: erp_core::parse_datetime(row.try_get::<&str, _>("updated_at")?, "updated_at")?,
+            created_by: erp_core::parse_uuid_opt(row.try_get::<Option<&str>, _>("created_by")?, "created_by")?,
+            updated_by: erp_core::parse_uuid_opt(row.try_get::<Option<&str>, _>("updated_by")?, "updated_by")?,
+        },
+        document_id: erp_core::parse_uuid(row.try_get::<&str, _>("document_id")?, "document_id")?,
+        version: row.try_get::<i32, _>("version")?,
+        revision: row.try_get::<&str, _>("revision")?.to_string(),
+        file_path: row.try_get::<&str, _>("file_path")?.to_string(),
+        file_size: row.try_get::<i64, _>("file_size")?,
+        checksum: row.try_get::<&str, _>("checksum")?.to_string(),
+        change_summary: row.try_get::<Option<&str>, _>("change_summary")?.map(|s| s.to_string()),
+        changed_by: erp_core::parse_uuid_opt(row.try_get::<Option<&str>, _>("changed_by")?, "changed_by")?,
+        status: DocumentStatus::Draft,
+    })
+}
@@ -1,6 +1,7 @@
 use sqlx::SqlitePool;
 use uuid::Uuid;
-use erp_core::{Result, BaseEntity, Status};
+use erp_core::{Error, Result, BaseEntity, Status, BlobService, UploadPolicy};
+use erp_core::blob::{Blob, ByteStream, StagedBlob};
 use crate::models::*;
 use crate::repository::*;
 
@@ -34,6 +35,8 @@ impl DocumentService {
             path,
             description,
             status: Status::Active,
+            max_file_size: None,
+            allowed_mime_types: None,
         };
         
         self.repo.create_folder(pool, folder).await
@@ -47,6 +50,18 @@ impl DocumentService {
         self.repo.get_folder(pool, id).await
     }
 
+    /// Limits uploads to a folder. `None` leaves a limit to the storage default.
+    pub async fn set_upload_policy(&self, pool: &SqlitePool, folder_id: Uuid, max_file_size: Option<i64>, allowed_mime_types: Option<Vec<String>>) -> Result<DocumentFolder> {
+        if max_file_size.is_some_and(|n| n <= 0) {
+            return Err(Error::validation("max_file_size must be positive"));
+        }
+        let allowed_mime_types = allowed_mime_types
+            .map(|types| UploadPolicy::parse_mime_types(Some(&types.join(","))).join(","))
+            .filter(|types| !types.is_empty());
+        self.repo.update_folder_upload_policy(pool, folder_id, max_file_size, allowed_mime_types).await?;
+        self.repo.get_folder(pool, folder_id).await
+    }
+
     pub async fn create_document(&self, pool: &SqlitePool, doc: Document) -> Result<Document> {
         self.repo.create_document(pool, doc).await
     }
@@ -85,8 +100,73 @@ impl DocumentService {
         self.repo.checkin(pool, checkout_id).await
     }
 
-    pub async fn create_version(&self, pool: &SqlitePool, version: DocumentVersion) -> Result<DocumentVersion> {
-        self.repo.create_version(pool, version).await
+    /// Stores `upload` as the next version of a document, within the limits of its folder, and
+    /// makes it the document's current file. A checked-out document only takes new versions from
+    /// the user holding it.
+    pub async fn create_version(&self, pool: &SqlitePool, blobs: &BlobService, document_id: Uuid, upload: StagedBlob, new: NewVersion) -> Result<DocumentVersion> {
+        let mut doc = self.repo.get_document(pool, document_id).await?;
+        if let Some(holder) = doc.checked_out_by {
+            if new.changed_by != Some(holder) {
+                return Err(Error::business_rule("Document is checked out by another user"));
+            }
+        }
+        let policy = match doc.folder_id {
+            Some(folder_id) => self.repo.get_folder(pool, folder_id).await?.upload_policy(blobs.default_policy()),
+            None => blobs.default_policy().clone(),
+        };
+
+        let blob = blobs.save(pool, upload, &new.mime_type, &policy, new.expected_sha256.as_deref()).await?;
+        let stored = self.record_version(pool, &mut doc, &blob, new).await;
+        if stored.is_err() {
+            blobs.release(pool, &blob.sha256).await?;
+        }
+        stored
+    }
+
+    async fn record_version(&self, pool: &SqlitePool, doc: &mut Document, blob: &Blob, new: NewVersion) -> Result<DocumentVersion> {
+        let previous = self.repo.list_versions(pool, doc.base.id).await?;
+        let version = DocumentVersion {
+            base: BaseEntity::new(),
+            document_id: doc.base.id,
+            version: previous.first().map(|v| v.version + 1).unwrap_or(1),
+            revision: doc.revision.clone(),
+            file_path: blob.key(),
+            file_size: blob.size,
+            checksum: blob.sha256.clone(),
+            change_summary: new.change_summary,
+            changed_by: new.changed_by,
+            status: DocumentStatus::Draft,
+        };
+        let version = self.repo.create_version(pool, version).await?;
+
+        doc.version = version.version;
+        if let Some(file_name) = new.file_name {
+            doc.file_name = file_name;
+        }
+        doc.file_path = version.file_path.clone();
+        doc.file_size = version.file_size;
+        doc.mime_type = blob.mime_type.clone();
+        doc.checksum = version.checksum.clone();
+        doc.base.updated_at = chrono::Utc::now();
+        doc.base.updated_by = new.changed_by;
+        self.repo.update_document(pool, doc.clone()).await?;
+        Ok(version)
+    }
+
+    pub async fn get_version(&self, pool: &SqlitePool, document_id: Uuid, version: i32) -> Result<DocumentVersion> {
+        self.repo.get_version(pool, document_id, version).await
+    }
+
+    /// Streams the current file of a document, or of one of its versions, with the name to
+    /// download it as.
+    pub async fn open_content(&self, pool: &SqlitePool, blobs: &BlobService, document_id: Uuid, version: Option<i32>) -> Result<(String, Blob, ByteStream)> {
+        let doc = self.repo.get_document(pool, document_id).await?;
+        let checksum = match version {
+            Some(version) => self.repo.get_version(pool, document_id, version).await?.checksum,
+            None => doc.checksum,
+        };
+        let (blob, stream) = blobs.open(pool, &checksum).await?;
+        Ok((doc.file_name, blob, stream))
     }
 
     pub async fn list_versions(&self, pool: &SqlitePool, document_id: Uuid) -> Result<Vec<DocumentVersion>> {
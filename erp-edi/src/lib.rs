@@ -1,6 +1,9 @@
+pub mod mapping;
 pub mod models;
 pub mod repository;
+pub mod schema;
 pub mod service;
+pub mod x12;
 
 pub use models::*;
 pub use repository::*;
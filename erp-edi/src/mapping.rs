@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use erp_core::{Error, Result, Status};
+use erp_sales::{Invoice, SalesOrder};
+use uuid::Uuid;
+
+use crate::models::*;
+use crate::x12::{Segment, TransactionSet};
+
+/// Part-number qualifiers tried in order to find our SKU on a buyer's line item.
+const PART_QUALIFIERS: &[&str] = &["VP", "SK", "BP", "IN", "UP"];
+
+/// Reads an 850 that has already passed validation.
+pub fn read_850(set: &TransactionSet, transaction_id: Uuid, customer_id: Uuid) -> Result<Edi850PurchaseOrder> {
+    let mut order = Edi850PurchaseOrder {
+        id: Uuid::new_v4(),
+        transaction_id,
+        po_number: String::new(),
+        po_date: Utc::now().date_naive(),
+        customer_id,
+        ship_to: EdiAddress::default(),
+        bill_to: EdiAddress::default(),
+        lines: Vec::new(),
+        total_amount: 0,
+        currency: "USD".to_string(),
+        sales_order_id: None,
+    };
+    let mut party = None;
+    let mut requested_date = None;
+    for segment in &set.segments {
+        match segment.id.as_str() {
+            "BEG" => {
+                order.po_number = segment.element(3).to_string();
+                order.po_date = parse_date(segment.element(5))?;
+            }
+            "CUR" => order.currency = segment.element(2).to_string(),
+            "DTM" if segment.element(1) == "002" => {
+                let date = parse_date(segment.element(2))?;
+                match order.lines.last_mut() {
+                    Some(line) => line.requested_date = Some(date),
+                    None => requested_date = Some(date),
+                }
+            }
+            "N1" => {
+                party = Some(segment.element(1).to_string());
+                if let Some(address) = party_address(&mut order, party.as_deref()) {
+                    address.name = segment.element(2).to_string();
+                }
+            }
+            "N3" => {
+                if let Some(address) = party_address(&mut order, party.as_deref()) {
+                    address.address1 = segment.element(1).to_string();
+                    address.address2 = Some(segment.element(2)).filter(|s| !s.is_empty()).map(str::to_string);
+                }
+            }
+            "N4" => {
+                if let Some(address) = party_address(&mut order, party.as_deref()) {
+                    address.city = segment.element(1).to_string();
+                    address.state = segment.element(2).to_string();
+                    address.postal_code = segment.element(3).to_string();
+                    address.country = segment.element(4).to_string();
+                }
+            }
+            "PO1" => {
+                party = None;
+                let sku = PART_QUALIFIERS
+                    .iter()
+                    .find_map(|qualifier| {
+                        (6..segment.elements.len())
+                            .step_by(2)
+                            .find(|&i| segment.element(i) == *qualifier)
+                            .map(|i| segment.element(i + 1).to_string())
+                    })
+                    .ok_or_else(|| Error::validation(format!("PO1 line {} has no part number", segment.element(1))))?;
+                order.lines.push(Edi850Line {
+                    line_number: segment.element(1).parse().unwrap_or(order.lines.len() as i32 + 1),
+                    product_id: None,
+                    sku,
+                    description: String::new(),
+                    quantity: parse_quantity(segment.element(2))?,
+                    unit_price: parse_cents(segment.element(4))?,
+                    uom: segment.element(3).to_string(),
+                    requested_date,
+                });
+            }
+            "PID" => {
+                if let Some(line) = order.lines.last_mut().filter(|l| l.description.is_empty()) {
+                    line.description = segment.element(5).to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+    order.total_amount = order.lines.iter().map(|l| l.quantity * l.unit_price).sum();
+    Ok(order)
+}
+
+fn party_address<'a>(order: &'a mut Edi850PurchaseOrder, party: Option<&str>) -> Option<&'a mut EdiAddress> {
+    match party {
+        Some("ST") => Some(&mut order.ship_to),
+        Some("BT") => Some(&mut order.bill_to),
+        _ => None,
+    }
+}
+
+/// 855 acknowledging a purchase order line by line against the sales order booked from it.
+pub fn purchase_order_ack_855(po: &Edi850PurchaseOrder, order: &SalesOrder) -> Vec<Segment> {
+    let rejected = order.status == Status::Cancelled;
+    let mut lines = Vec::new();
+    let mut changed = false;
+    for line in &po.lines {
+        let booked = order.lines.iter().find(|l| Some(l.product_id) == line.product_id);
+        let (code, quantity) = match booked {
+            _ if rejected => ("IR", line.quantity),
+            None => ("ID", 0),
+            Some(l) if l.quantity != line.quantity => ("IQ", l.quantity),
+            Some(l) if l.unit_price.amount != line.unit_price => ("IP", l.quantity),
+            Some(l) => ("IA", l.quantity),
+        };
+        changed |= code != "IA";
+        let price = booked.map_or(line.unit_price, |l| l.unit_price.amount);
+        lines.push(Segment::new("PO1", [
+            line.line_number.to_string(),
+            line.quantity.to_string(),
+            uom(&line.uom),
+            decimal(price),
+            String::new(),
+            "VP".to_string(),
+            clip(&line.sku, 48),
+        ]));
+        lines.push(Segment::new("ACK", [code.to_string(), quantity.to_string(), uom(&line.uom)]));
+    }
+    let status = if rejected { "RJ" } else if changed { "AC" } else { "AD" };
+    let mut segments = vec![
+        Segment::new("BAK", ["00".to_string(), status.to_string(), clip(&po.po_number, 22), date8(po.po_date)]),
+        Segment::new("REF", ["CO".to_string(), clip(&order.order_number, 30)]),
+    ];
+    segments.extend(lines);
+    segments.push(Segment::new("CTT", [po.lines.len().to_string()]));
+    segments
+}
+
+/// 810 for a customer invoice. `skus` maps product ids to our part numbers.
+pub fn invoice_810(
+    invoice: &Invoice,
+    skus: &HashMap<Uuid, String>,
+    po: Option<&Edi850PurchaseOrder>,
+    bill_to: &EdiAddress,
+) -> Vec<Segment> {
+    let mut segments = vec![Segment::new("BIG", [
+        date8(invoice.invoice_date.date_naive()),
+        clip(&invoice.invoice_number, 22),
+        po.map(|p| date8(p.po_date)).unwrap_or_default(),
+        po.map(|p| clip(&p.po_number, 22)).unwrap_or_default(),
+    ])];
+    segments.push(Segment::new("CUR", ["SE".to_string(), format!("{:?}", invoice.total.currency)]));
+    segments.extend(party("BT", bill_to));
+    segments.push(Segment::new("DTM", ["011".to_string(), date8(invoice.invoice_date.date_naive())]));
+    for (index, line) in invoice.lines.iter().enumerate() {
+        let mut it1 = vec![
+            (index + 1).to_string(),
+            line.quantity.to_string(),
+            "EA".to_string(),
+            decimal(line.unit_price.amount),
+            String::new(),
+        ];
+        if let Some(sku) = skus.get(&line.product_id) {
+            it1.extend(["VP".to_string(), clip(sku, 48)]);
+        }
+        segments.push(Segment::new("IT1", it1));
+        if !line.description.is_empty() {
+            segments.push(Segment::new("PID", ["F", "", "", "", &clip(&line.description, 80)]));
+        }
+    }
+    segments.push(Segment::new("TDS", [invoice.total.amount.to_string()]));
+    if invoice.tax_amount.amount != 0 {
+        segments.push(Segment::new("TXI", ["TX".to_string(), decimal(invoice.tax_amount.amount)]));
+    }
+    segments.push(Segment::new("CTT", [invoice.lines.len().to_string()]));
+    segments
+}
+
+/// 856 in the usual shipment / order / pack / item hierarchy.
+pub fn ship_notice_856(asn: &Edi856ASN, ship_to: &EdiAddress, shipped_at: DateTime<Utc>, weight_unit: &str) -> Vec<Segment> {
+    let mut segments = vec![
+        Segment::new("BSN", [
+            "00".to_string(),
+            clip(&asn.asn_number, 30),
+            date8(asn.shipment_date),
+            shipped_at.format("%H%M").to_string(),
+            "0001".to_string(),
+        ]),
+        Segment::new("HL", ["1", "", "S"]),
+    ];
+    let weight: f64 = asn.packages.iter().map(|p| p.weight).sum();
+    let unit = if weight_unit.eq_ignore_ascii_case("kg") { "KG" } else { "LB" };
+    segments.push(Segment::new("TD1", [
+        "CTN".to_string(),
+        asn.packages.len().to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+        "G".to_string(),
+        format!("{:.2}", weight),
+        unit.to_string(),
+    ]));
+    if !asn.carrier.is_empty() {
+        segments.push(Segment::new("TD5", ["", "2", &clip(&asn.carrier, 80)]));
+    }
+    if let Some(tracking) = &asn.tracking_number {
+        segments.push(Segment::new("REF", ["CN", &clip(tracking, 30)]));
+    }
+    segments.push(Segment::new("DTM", ["011".to_string(), date8(asn.shipment_date)]));
+    segments.push(Segment::new("DTM", ["017".to_string(), date8(asn.expected_date)]));
+    segments.extend(party("ST", ship_to));
+    segments.push(Segment::new("HL", ["2", "1", "O"]));
+    segments.push(Segment::new("PRF", [clip(&asn.po_number, 22)]));
+    let mut hl = 2;
+    for package in &asn.packages {
+        hl += 1;
+        let pack = hl;
+        segments.push(Segment::new("HL", [pack.to_string(), "2".to_string(), "P".to_string()]));
+        segments.push(Segment::new("MAN", ["GM", &clip(&package.package_id, 48)]));
+        for item in &package.items {
+            hl += 1;
+            segments.push(Segment::new("HL", [hl.to_string(), pack.to_string(), "I".to_string()]));
+            segments.push(Segment::new("LIN", ["", "VP", &clip(&item.sku, 48)]));
+            segments.push(Segment::new("SN1", [String::new(), item.quantity.to_string(), "EA".to_string()]));
+        }
+    }
+    segments.push(Segment::new("CTT", [hl.to_string()]));
+    segments
+}
+
+/// N1/N3/N4 for a party, leaving out whatever the address does not have.
+pub fn party(code: &str, address: &EdiAddress) -> Vec<Segment> {
+    let mut segments = vec![Segment::new("N1", [code.to_string(), clip(&address.name, 60)])];
+    if !address.address1.is_empty() {
+        segments.push(Segment::new("N3", [
+            clip(&address.address1, 55),
+            address.address2.as_deref().map(|a| clip(a, 55)).unwrap_or_default(),
+        ]));
+    }
+    if !address.city.is_empty() {
+        let fits = |value: &str, min: usize, max: usize| {
+            if (min..=max).contains(&value.chars().count()) { value.to_string() } else { String::new() }
+        };
+        segments.push(Segment::new("N4", [
+            clip(&address.city, 30),
+            fits(&address.state, 2, 2),
+            fits(&address.postal_code, 3, 15),
+            fits(&address.country, 2, 3),
+        ]));
+    }
+    segments
+}
+
+fn uom(value: &str) -> String {
+    if value.len() == 2 { value.to_string() } else { "EA".to_string() }
+}
+
+fn clip(value: &str, max: usize) -> String {
+    value.chars().take(max).collect()
+}
+
+fn date8(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+/// Formats cents as an X12 decimal (`R`) value.
+fn decimal(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{}{}.{:02}", sign, cents.abs() / 100, cents.abs() % 100)
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| Error::validation(format!("Invalid date '{}'", value)))
+}
+
+fn parse_quantity(value: &str) -> Result<i64> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if !fraction.chars().all(|c| c == '0') {
+        return Err(Error::validation(format!("Quantity {} is not a whole number", value)));
+    }
+    whole.parse().map_err(|_| Error::validation(format!("Invalid quantity '{}'", value)))
+}
+
+/// Parses an X12 decimal into cents, rounding half away from zero.
+fn parse_cents(value: &str) -> Result<i64> {
+    let invalid = || Error::validation(format!("Invalid amount '{}'", value));
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let whole: i64 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| invalid())? };
+    let mut fraction: Vec<i64> = fraction.chars().map(|c| c.to_digit(10).map(i64::from).ok_or_else(invalid)).collect::<Result<_>>()?;
+    fraction.resize(fraction.len().max(3), 0);
+    let cents = whole * 100 + fraction[0] * 10 + fraction[1] + i64::from(fraction[2] >= 5);
+    Ok(if negative { -cents } else { cents })
+}
@@ -8,8 +8,14 @@ pub struct EdiPartner {
     pub partner_code: String,
     pub partner_name: String,
     pub partner_type: PartnerType,
+    /// The partner's ISA05/ISA06 identity.
     pub qualifier: String,
     pub interchange_id: String,
+    /// The identity we use in envelopes exchanged with this partner.
+    pub local_qualifier: String,
+    pub local_interchange_id: String,
+    /// The customer inbound purchase orders are booked against.
+    pub customer_id: Option<Uuid>,
     pub communication_type: CommunicationType,
     pub endpoint: String,
     pub encryption: Option<String>,
@@ -17,7 +23,8 @@ pub struct EdiPartner {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
 pub enum PartnerType {
     Customer,
     Vendor,
@@ -26,7 +33,8 @@ pub enum PartnerType {
     Warehouse,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
 pub enum CommunicationType {
     AS2,
     SFTP,
@@ -51,7 +59,9 @@ pub struct EdiTransaction {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
+#[allow(non_camel_case_types)]
 pub enum EdiTransactionType {
     X12_850,
     X12_810,
@@ -69,13 +79,50 @@ pub enum EdiTransactionType {
     PeppolInvoice,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl EdiTransactionType {
+    /// Maps an X12 ST01 code.
+    pub fn from_x12(transaction_set: &str) -> Option<Self> {
+        match transaction_set {
+            "850" => Some(Self::X12_850),
+            "810" => Some(Self::X12_810),
+            "856" => Some(Self::X12_856),
+            "855" => Some(Self::X12_855),
+            "860" => Some(Self::X12_860),
+            "865" => Some(Self::X12_865),
+            "940" => Some(Self::X12_940),
+            "945" => Some(Self::X12_945),
+            "997" => Some(Self::X12_997),
+            "820" => Some(Self::X12_820),
+            _ => None,
+        }
+    }
+
+    pub fn x12_code(self) -> Option<&'static str> {
+        match self {
+            Self::X12_850 => Some("850"),
+            Self::X12_810 => Some("810"),
+            Self::X12_856 => Some("856"),
+            Self::X12_855 => Some("855"),
+            Self::X12_860 => Some("860"),
+            Self::X12_865 => Some("865"),
+            Self::X12_940 => Some("940"),
+            Self::X12_945 => Some("945"),
+            Self::X12_997 => Some("997"),
+            Self::X12_820 => Some("820"),
+            Self::EdifactOrders | Self::EdifactInvoic | Self::EdifactDesadv | Self::PeppolInvoice => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
 pub enum EdiDirection {
     Inbound,
     Outbound,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
 pub enum EdiStatus {
     Received,
     Validated,
@@ -110,7 +157,8 @@ pub struct EdiAcknowledgment {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
 pub enum AckType {
     TA1,
     FA997,
@@ -122,8 +170,13 @@ pub struct SegmentError {
     pub segment_id: String,
     pub segment_position: i32,
     pub loop_id: Option<String>,
+    /// Set for element errors (AK4), whose `error_code` is then an element error code.
+    #[serde(default)]
+    pub element_position: Option<i32>,
     pub error_code: String,
     pub description: String,
+    #[serde(default)]
+    pub bad_value: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -138,6 +191,9 @@ pub struct Edi850PurchaseOrder {
     pub lines: Vec<Edi850Line>,
     pub total_amount: i64,
     pub currency: String,
+    /// The draft sales order booked from this purchase order.
+    #[serde(default)]
+    pub sales_order_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -203,7 +259,7 @@ pub struct Edi856Item {
     pub lot_number: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EdiAddress {
     pub name: String,
     pub address1: String,
@@ -221,6 +277,9 @@ pub struct CreatePartnerRequest {
     pub partner_type: PartnerType,
     pub qualifier: String,
     pub interchange_id: String,
+    pub local_qualifier: String,
+    pub local_interchange_id: String,
+    pub customer_id: Option<Uuid>,
     pub communication_type: CommunicationType,
     pub endpoint: String,
     pub encryption: Option<String>,
@@ -229,7 +288,9 @@ pub struct CreatePartnerRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessEdiRequest {
     pub raw_content: String,
-    pub partner_id: Uuid,
+    /// Identified from the ISA sender when omitted.
+    #[serde(default)]
+    pub partner_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -246,3 +307,10 @@ pub struct EdiTransmissionResult {
     pub raw_content: String,
     pub sent_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboundResult {
+    pub transactions: Vec<EdiTransaction>,
+    /// The 997 sent back, if any group called for one.
+    pub acknowledgment: Option<EdiTransmissionResult>,
+}
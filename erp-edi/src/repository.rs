@@ -1,29 +1,54 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use chrono::Utc;
+use erp_core::{parse_datetime, parse_datetime_opt, parse_uuid, parse_uuid_opt, Error, Result};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
 use uuid::Uuid;
 
 use crate::models::*;
 
+/// Control numbers reserved for one outbound interchange.
+#[derive(Debug, Clone, Copy)]
+pub struct ControlNumbers {
+    pub interchange: u32,
+    pub group: u32,
+    /// The first of the consecutive ST02 numbers reserved.
+    pub first_transaction: u32,
+}
+
 #[async_trait]
 pub trait EdiRepository: Send + Sync {
-    async fn create_partner(&self, _partner: &EdiPartner) -> anyhow::Result<()> { Ok(()) }
-    async fn get_partner(&self, _id: Uuid) -> anyhow::Result<Option<EdiPartner>> { Ok(None) }
-    async fn get_partner_by_code(&self, _code: &str) -> anyhow::Result<Option<EdiPartner>> { Ok(None) }
-    async fn list_partners(&self, _partner_type: Option<PartnerType>) -> anyhow::Result<Vec<EdiPartner>> { Ok(vec![]) }
-    async fn create_transaction(&self, _txn: &EdiTransaction) -> anyhow::Result<()> { Ok(()) }
-    async fn get_transaction(&self, _id: Uuid) -> anyhow::Result<Option<EdiTransaction>> { Ok(None) }
-    async fn list_transactions(&self, _partner_id: Option<Uuid>, _txn_type: Option<EdiTransactionType>) -> anyhow::Result<Vec<EdiTransaction>> { Ok(vec![]) }
-    async fn update_transaction_status(&self, _id: Uuid, _status: EdiStatus, _parsed_data: Option<serde_json::Value>) -> anyhow::Result<()> { Ok(()) }
-    async fn create_mapping(&self, _mapping: &EdiMapping) -> anyhow::Result<()> { Ok(()) }
-    async fn get_mapping(&self, _txn_type: EdiTransactionType) -> anyhow::Result<Option<EdiMapping>> { Ok(None) }
-    async fn create_acknowledgment(&self, _ack: &EdiAcknowledgment) -> anyhow::Result<()> { Ok(()) }
-    async fn get_acknowledgment(&self, _txn_id: Uuid) -> anyhow::Result<Option<EdiAcknowledgment>> { Ok(None) }
-    async fn create_850(&self, _order: &Edi850PurchaseOrder) -> anyhow::Result<()> { Ok(()) }
-    async fn get_850(&self, _id: Uuid) -> anyhow::Result<Option<Edi850PurchaseOrder>> { Ok(None) }
-    async fn create_810(&self, _invoice: &Edi810Invoice) -> anyhow::Result<()> { Ok(()) }
-    async fn get_810(&self, _id: Uuid) -> anyhow::Result<Option<Edi810Invoice>> { Ok(None) }
-    async fn create_856(&self, _asn: &Edi856ASN) -> anyhow::Result<()> { Ok(()) }
-    async fn get_856(&self, _id: Uuid) -> anyhow::Result<Option<Edi856ASN>> { Ok(None) }
+    async fn create_partner(&self, _partner: &EdiPartner) -> Result<()> { Ok(()) }
+    async fn get_partner(&self, _id: Uuid) -> Result<Option<EdiPartner>> { Ok(None) }
+    async fn get_partner_by_code(&self, _code: &str) -> Result<Option<EdiPartner>> { Ok(None) }
+    async fn find_partner_by_interchange(&self, _qualifier: &str, _interchange_id: &str) -> Result<Option<EdiPartner>> { Ok(None) }
+    async fn list_partners(&self, _partner_type: Option<PartnerType>) -> Result<Vec<EdiPartner>> { Ok(vec![]) }
+    /// Advances the partner's interchange and group numbers by one and its transaction set
+    /// number by `transactions`, wrapping after 999999999.
+    async fn next_control_numbers(&self, _partner_id: Uuid, _transactions: u32) -> Result<ControlNumbers> {
+        Err(Error::internal("Control numbers are not supported by this repository"))
+    }
+    async fn create_transaction(&self, _txn: &EdiTransaction) -> Result<()> { Ok(()) }
+    async fn get_transaction(&self, _id: Uuid) -> Result<Option<EdiTransaction>> { Ok(None) }
+    async fn list_transactions(&self, _partner_id: Option<Uuid>, _txn_type: Option<EdiTransactionType>) -> Result<Vec<EdiTransaction>> { Ok(vec![]) }
+    async fn update_transaction_status(&self, _id: Uuid, _status: EdiStatus, _error_message: Option<String>) -> Result<()> { Ok(()) }
+    /// Whether an interchange with this ISA13 has already been received from the partner.
+    async fn interchange_received(&self, _partner_id: Uuid, _control_number: u32) -> Result<bool> { Ok(false) }
+    /// Outbound transaction sets sent in group `group_control`, or only set `set_control` of it.
+    async fn find_outbound(&self, _partner_id: Uuid, _group_control: u32, _set_control: Option<&str>) -> Result<Vec<EdiTransaction>> { Ok(vec![]) }
+    async fn create_mapping(&self, _mapping: &EdiMapping) -> Result<()> { Ok(()) }
+    async fn get_mapping(&self, _txn_type: EdiTransactionType) -> Result<Option<EdiMapping>> { Ok(None) }
+    async fn create_acknowledgment(&self, _ack: &EdiAcknowledgment) -> Result<()> { Ok(()) }
+    async fn get_acknowledgment(&self, _txn_id: Uuid) -> Result<Option<EdiAcknowledgment>> { Ok(None) }
+    async fn create_850(&self, _order: &Edi850PurchaseOrder) -> Result<()> { Ok(()) }
+    async fn get_850(&self, _id: Uuid) -> Result<Option<Edi850PurchaseOrder>> { Ok(None) }
+    async fn find_850_by_sales_order(&self, _sales_order_id: Uuid) -> Result<Option<Edi850PurchaseOrder>> { Ok(None) }
+    async fn find_850_by_po(&self, _customer_id: Uuid, _po_number: &str) -> Result<Option<Edi850PurchaseOrder>> { Ok(None) }
+    async fn create_810(&self, _invoice: &Edi810Invoice) -> Result<()> { Ok(()) }
+    async fn get_810(&self, _id: Uuid) -> Result<Option<Edi810Invoice>> { Ok(None) }
+    async fn create_856(&self, _asn: &Edi856ASN) -> Result<()> { Ok(()) }
+    async fn get_856(&self, _id: Uuid) -> Result<Option<Edi856ASN>> { Ok(None) }
 }
 
 pub struct SqliteEdiRepository {
@@ -34,7 +59,526 @@ impl SqliteEdiRepository {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
     }
+
+    /// Product ids and names by SKU, for the SKUs that exist.
+    pub async fn products_by_sku_in(conn: &mut SqliteConnection, skus: &[String]) -> Result<HashMap<String, (Uuid, String)>> {
+        let mut products = HashMap::new();
+        for sku in skus {
+            let row = sqlx::query_as::<_, (String, String)>("SELECT id, name FROM products WHERE sku = ?")
+                .bind(sku)
+                .fetch_optional(&mut *conn)
+                .await?;
+            if let Some((id, name)) = row {
+                products.insert(sku.clone(), (parse_uuid(&id, "products.id")?, name));
+            }
+        }
+        Ok(products)
+    }
+
+    pub async fn create_transaction_in(conn: &mut SqliteConnection, txn: &EdiTransaction) -> Result<()> {
+        sqlx::query(&format!(
+            "INSERT INTO edi_transactions ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            TRANSACTION_COLUMNS
+        ))
+        .bind(txn.id.to_string())
+        .bind(txn.partner_id.to_string())
+        .bind(txn.transaction_type)
+        .bind(txn.direction)
+        .bind(&txn.control_number)
+        .bind(txn.status)
+        .bind(&txn.raw_content)
+        .bind(txn.parsed_data.as_ref().map(to_json).transpose()?)
+        .bind(&txn.error_message)
+        .bind(txn.processed_at.map(|t| t.to_rfc3339()))
+        .bind(txn.created_at.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_transaction_status_in(conn: &mut SqliteConnection, id: Uuid, status: EdiStatus, error_message: Option<String>) -> Result<()> {
+        sqlx::query("UPDATE edi_transactions SET status = ?, error_message = ?, processed_at = ? WHERE id = ?")
+            .bind(status)
+            .bind(error_message)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id.to_string())
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn interchange_received_in(conn: &mut SqliteConnection, partner_id: Uuid, control_number: u32) -> Result<bool> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM edi_transactions WHERE partner_id = ? AND direction = 'Inbound' AND control_number LIKE ?",
+        )
+        .bind(partner_id.to_string())
+        .bind(format!("IN:%:{:09}:%", control_number))
+        .fetch_one(&mut *conn)
+        .await?;
+        Ok(count > 0)
+    }
+
+    pub async fn find_outbound_in(conn: &mut SqliteConnection, partner_id: Uuid, group_control: u32, set_control: Option<&str>) -> Result<Vec<EdiTransaction>> {
+        sqlx::query_as::<_, TransactionRow>(&format!(
+            "SELECT {} FROM edi_transactions WHERE partner_id = ? AND direction = 'Outbound' AND control_number LIKE ?",
+            TRANSACTION_COLUMNS
+        ))
+        .bind(partner_id.to_string())
+        .bind(format!("OUT:%:{}:{}", group_control, set_control.unwrap_or("%")))
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(TransactionRow::into_transaction)
+        .collect()
+    }
+
+    pub async fn create_acknowledgment_in(conn: &mut SqliteConnection, ack: &EdiAcknowledgment) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO edi_acknowledgments (id, transaction_id, ack_type, accepted, error_codes, segment_errors, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(ack.id.to_string())
+        .bind(ack.transaction_id.to_string())
+        .bind(ack.ack_type)
+        .bind(ack.accepted)
+        .bind(to_json(&ack.error_codes)?)
+        .bind(to_json(&ack.segment_errors)?)
+        .bind(ack.created_at.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn create_850_in(conn: &mut SqliteConnection, order: &Edi850PurchaseOrder) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO edi_850_orders (id, transaction_id, po_number, po_date, customer_id, ship_to, bill_to, lines, total_amount, currency, sales_order_id)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(order.id.to_string())
+        .bind(order.transaction_id.to_string())
+        .bind(&order.po_number)
+        .bind(order.po_date.to_string())
+        .bind(order.customer_id.to_string())
+        .bind(to_json(&order.ship_to)?)
+        .bind(to_json(&order.bill_to)?)
+        .bind(to_json(&order.lines)?)
+        .bind(order.total_amount)
+        .bind(&order.currency)
+        .bind(order.sales_order_id.map(|id| id.to_string()))
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_850_by_po_in(conn: &mut SqliteConnection, customer_id: Uuid, po_number: &str) -> Result<Option<Edi850PurchaseOrder>> {
+        sqlx::query_as::<_, PurchaseOrderRow>("SELECT * FROM edi_850_orders WHERE customer_id = ? AND po_number = ?")
+            .bind(customer_id.to_string())
+            .bind(po_number)
+            .fetch_optional(&mut *conn)
+            .await?
+            .map(PurchaseOrderRow::into_order)
+            .transpose()
+    }
+
+    pub async fn skus_by_product(&self, product_ids: &[Uuid]) -> Result<HashMap<Uuid, String>> {
+        let mut skus = HashMap::new();
+        for id in product_ids {
+            let sku = sqlx::query_scalar::<_, String>("SELECT sku FROM products WHERE id = ?")
+                .bind(id.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+            if let Some(sku) = sku {
+                skus.insert(*id, sku);
+            }
+        }
+        Ok(skus)
+    }
+
+    pub async fn get_shipment(&self, id: Uuid) -> Result<Option<ShipmentSource>> {
+        let row = sqlx::query_as::<_, ShipmentRow>(
+            "SELECT s.shipment_number, s.order_id, COALESCE(c.code, '') AS carrier, s.tracking_number, s.weight, s.weight_unit,
+                    s.ship_to_name, s.ship_to_street, s.ship_to_city, s.ship_to_state, s.ship_to_postal_code, s.ship_to_country,
+                    s.shipped_at, s.estimated_delivery
+             FROM shipments s LEFT JOIN carriers c ON c.id = s.carrier_id WHERE s.id = ?",
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+        row.map(ShipmentRow::into_source).transpose()
+    }
+}
+
+/// The parts of a shipment an 856 describes.
+#[derive(Debug, Clone)]
+pub struct ShipmentSource {
+    pub shipment_number: String,
+    pub order_id: Option<Uuid>,
+    pub carrier: String,
+    pub tracking_number: Option<String>,
+    pub weight: f64,
+    pub weight_unit: String,
+    pub ship_to: EdiAddress,
+    pub shipped_at: Option<chrono::DateTime<Utc>>,
+    pub estimated_delivery: Option<chrono::DateTime<Utc>>,
+}
+
+#[derive(FromRow)]
+struct ShipmentRow {
+    shipment_number: String,
+    order_id: Option<String>,
+    carrier: String,
+    tracking_number: Option<String>,
+    weight: f64,
+    weight_unit: String,
+    ship_to_name: String,
+    ship_to_street: String,
+    ship_to_city: String,
+    ship_to_state: Option<String>,
+    ship_to_postal_code: String,
+    ship_to_country: String,
+    shipped_at: Option<String>,
+    estimated_delivery: Option<String>,
+}
+
+impl ShipmentRow {
+    fn into_source(self) -> Result<ShipmentSource> {
+        Ok(ShipmentSource {
+            shipment_number: self.shipment_number,
+            order_id: parse_uuid_opt(self.order_id.as_deref(), "shipments.order_id")?,
+            carrier: self.carrier,
+            tracking_number: self.tracking_number,
+            weight: self.weight,
+            weight_unit: self.weight_unit,
+            ship_to: EdiAddress {
+                name: self.ship_to_name,
+                address1: self.ship_to_street,
+                address2: None,
+                city: self.ship_to_city,
+                state: self.ship_to_state.unwrap_or_default(),
+                postal_code: self.ship_to_postal_code,
+                country: self.ship_to_country,
+            },
+            shipped_at: parse_datetime_opt(self.shipped_at.as_deref(), "shipments.shipped_at")?,
+            estimated_delivery: parse_datetime_opt(self.estimated_delivery.as_deref(), "shipments.estimated_delivery")?,
+        })
+    }
+}
+
+const PARTNER_COLUMNS: &str = "id, partner_code, partner_name, partner_type, qualifier, interchange_id, local_qualifier, local_interchange_id, customer_id, communication_method, api_endpoint, encryption, status, created_at";
+
+#[derive(FromRow)]
+struct PartnerRow {
+    id: String,
+    partner_code: String,
+    partner_name: String,
+    partner_type: PartnerType,
+    qualifier: String,
+    interchange_id: String,
+    local_qualifier: String,
+    local_interchange_id: String,
+    customer_id: Option<String>,
+    communication_method: CommunicationType,
+    api_endpoint: Option<String>,
+    encryption: Option<String>,
+    status: Option<String>,
+    created_at: String,
+}
+
+impl PartnerRow {
+    fn into_partner(self) -> Result<EdiPartner> {
+        Ok(EdiPartner {
+            id: parse_uuid(&self.id, "edi_partners.id")?,
+            partner_code: self.partner_code,
+            partner_name: self.partner_name,
+            partner_type: self.partner_type,
+            qualifier: self.qualifier,
+            interchange_id: self.interchange_id,
+            local_qualifier: self.local_qualifier,
+            local_interchange_id: self.local_interchange_id,
+            customer_id: parse_uuid_opt(self.customer_id.as_deref(), "edi_partners.customer_id")?,
+            communication_type: self.communication_method,
+            endpoint: self.api_endpoint.unwrap_or_default(),
+            encryption: self.encryption,
+            is_active: self.status.as_deref() == Some("Active"),
+            created_at: parse_datetime(&self.created_at, "edi_partners.created_at")?,
+        })
+    }
+}
+
+const TRANSACTION_COLUMNS: &str = "id, partner_id, transaction_type, direction, control_number, status, raw_content, parsed_data, error_message, processed_at, created_at";
+
+#[derive(FromRow)]
+struct TransactionRow {
+    id: String,
+    partner_id: String,
+    transaction_type: EdiTransactionType,
+    direction: EdiDirection,
+    control_number: String,
+    status: EdiStatus,
+    raw_content: Option<String>,
+    parsed_data: Option<String>,
+    error_message: Option<String>,
+    processed_at: Option<String>,
+    created_at: String,
+}
+
+impl TransactionRow {
+    fn into_transaction(self) -> Result<EdiTransaction> {
+        Ok(EdiTransaction {
+            id: parse_uuid(&self.id, "edi_transactions.id")?,
+            partner_id: parse_uuid(&self.partner_id, "edi_transactions.partner_id")?,
+            transaction_type: self.transaction_type,
+            direction: self.direction,
+            control_number: self.control_number,
+            status: self.status,
+            raw_content: self.raw_content,
+            parsed_data: self.parsed_data.as_deref().map(serde_json::from_str).transpose().map_err(|e| Error::internal(e.to_string()))?,
+            error_message: self.error_message,
+            processed_at: parse_datetime_opt(self.processed_at.as_deref(), "edi_transactions.processed_at")?,
+            created_at: parse_datetime(&self.created_at, "edi_transactions.created_at")?,
+        })
+    }
+}
+
+#[derive(FromRow)]
+struct AcknowledgmentRow {
+    id: String,
+    transaction_id: String,
+    ack_type: AckType,
+    accepted: bool,
+    error_codes: String,
+    segment_errors: String,
+    created_at: String,
+}
+
+#[derive(FromRow)]
+struct PurchaseOrderRow {
+    id: String,
+    transaction_id: String,
+    po_number: String,
+    po_date: String,
+    customer_id: String,
+    ship_to: String,
+    bill_to: String,
+    lines: String,
+    total_amount: i64,
+    currency: String,
+    sales_order_id: Option<String>,
+}
+
+impl PurchaseOrderRow {
+    fn into_order(self) -> Result<Edi850PurchaseOrder> {
+        Ok(Edi850PurchaseOrder {
+            id: parse_uuid(&self.id, "edi_850_orders.id")?,
+            transaction_id: parse_uuid(&self.transaction_id, "edi_850_orders.transaction_id")?,
+            po_number: self.po_number,
+            po_date: self.po_date.parse().map_err(|_| Error::internal(format!("Invalid edi_850_orders.po_date '{}'", self.po_date)))?,
+            customer_id: parse_uuid(&self.customer_id, "edi_850_orders.customer_id")?,
+            ship_to: from_json(&self.ship_to)?,
+            bill_to: from_json(&self.bill_to)?,
+            lines: from_json(&self.lines)?,
+            total_amount: self.total_amount,
+            currency: self.currency,
+            sales_order_id: parse_uuid_opt(self.sales_order_id.as_deref(), "edi_850_orders.sales_order_id")?,
+        })
+    }
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value).map_err(|e| Error::internal(e.to_string()))
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(value: &str) -> Result<T> {
+    serde_json::from_str(value).map_err(|e| Error::internal(e.to_string()))
 }
 
 #[async_trait]
-impl EdiRepository for SqliteEdiRepository {}
+impl EdiRepository for SqliteEdiRepository {
+    async fn create_partner(&self, partner: &EdiPartner) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO edi_partners (id, partner_code, partner_name, partner_type, edi_standard, qualifier, interchange_id, local_qualifier, local_interchange_id, customer_id, communication_method, api_endpoint, encryption, status, created_at)
+             VALUES (?, ?, ?, ?, 'X12', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(partner.id.to_string())
+        .bind(&partner.partner_code)
+        .bind(&partner.partner_name)
+        .bind(partner.partner_type)
+        .bind(&partner.qualifier)
+        .bind(&partner.interchange_id)
+        .bind(&partner.local_qualifier)
+        .bind(&partner.local_interchange_id)
+        .bind(partner.customer_id.map(|id| id.to_string()))
+        .bind(partner.communication_type)
+        .bind(&partner.endpoint)
+        .bind(&partner.encryption)
+        .bind(if partner.is_active { "Active" } else { "Inactive" })
+        .bind(partner.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                Error::Conflict(format!("EDI partner {} already exists", partner.partner_code))
+            }
+            e => Error::Database(e),
+        })?;
+        Ok(())
+    }
+
+    async fn get_partner(&self, id: Uuid) -> Result<Option<EdiPartner>> {
+        sqlx::query_as::<_, PartnerRow>(&format!("SELECT {} FROM edi_partners WHERE id = ?", PARTNER_COLUMNS))
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?
+            .map(PartnerRow::into_partner)
+            .transpose()
+    }
+
+    async fn get_partner_by_code(&self, code: &str) -> Result<Option<EdiPartner>> {
+        sqlx::query_as::<_, PartnerRow>(&format!("SELECT {} FROM edi_partners WHERE partner_code = ?", PARTNER_COLUMNS))
+            .bind(code)
+            .fetch_optional(&self.pool)
+            .await?
+            .map(PartnerRow::into_partner)
+            .transpose()
+    }
+
+    async fn find_partner_by_interchange(&self, qualifier: &str, interchange_id: &str) -> Result<Option<EdiPartner>> {
+        sqlx::query_as::<_, PartnerRow>(&format!(
+            "SELECT {} FROM edi_partners WHERE qualifier = ? AND interchange_id = ? ORDER BY status = 'Active' DESC LIMIT 1",
+            PARTNER_COLUMNS
+        ))
+        .bind(qualifier)
+        .bind(interchange_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(PartnerRow::into_partner)
+        .transpose()
+    }
+
+    async fn list_partners(&self, partner_type: Option<PartnerType>) -> Result<Vec<EdiPartner>> {
+        sqlx::query_as::<_, PartnerRow>(&format!(
+            "SELECT {} FROM edi_partners WHERE ?1 IS NULL OR partner_type = ?1 ORDER BY partner_code",
+            PARTNER_COLUMNS
+        ))
+        .bind(partner_type)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(PartnerRow::into_partner)
+        .collect()
+    }
+
+    async fn next_control_numbers(&self, partner_id: Uuid, transactions: u32) -> Result<ControlNumbers> {
+        let (interchange, group, last_transaction) = sqlx::query_as::<_, (i64, i64, i64)>(
+            "UPDATE edi_partners SET
+                interchange_control_number = interchange_control_number % 999999999 + 1,
+                group_control_number = group_control_number % 999999999 + 1,
+                transaction_control_number = (transaction_control_number + ?1 - 1) % 999999999 + 1
+             WHERE id = ?2
+             RETURNING interchange_control_number, group_control_number, transaction_control_number",
+        )
+        .bind(transactions)
+        .bind(partner_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| Error::not_found("EdiPartner", &partner_id.to_string()))?;
+        let mut first = last_transaction - i64::from(transactions) + 1;
+        if first < 1 {
+            first += 999999999;
+        }
+        Ok(ControlNumbers { interchange: interchange as u32, group: group as u32, first_transaction: first as u32 })
+    }
+
+    async fn create_transaction(&self, txn: &EdiTransaction) -> Result<()> {
+        Self::create_transaction_in(&mut *self.pool.acquire().await?, txn).await
+    }
+
+    async fn get_transaction(&self, id: Uuid) -> Result<Option<EdiTransaction>> {
+        sqlx::query_as::<_, TransactionRow>(&format!("SELECT {} FROM edi_transactions WHERE id = ?", TRANSACTION_COLUMNS))
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?
+            .map(TransactionRow::into_transaction)
+            .transpose()
+    }
+
+    async fn list_transactions(&self, partner_id: Option<Uuid>, txn_type: Option<EdiTransactionType>) -> Result<Vec<EdiTransaction>> {
+        sqlx::query_as::<_, TransactionRow>(&format!(
+            "SELECT {} FROM edi_transactions
+             WHERE (?1 IS NULL OR partner_id = ?1) AND (?2 IS NULL OR transaction_type = ?2)
+             ORDER BY created_at DESC, control_number DESC",
+            TRANSACTION_COLUMNS
+        ))
+        .bind(partner_id.map(|id| id.to_string()))
+        .bind(txn_type)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(TransactionRow::into_transaction)
+        .collect()
+    }
+
+    async fn update_transaction_status(&self, id: Uuid, status: EdiStatus, error_message: Option<String>) -> Result<()> {
+        Self::update_transaction_status_in(&mut *self.pool.acquire().await?, id, status, error_message).await
+    }
+
+    async fn interchange_received(&self, partner_id: Uuid, control_number: u32) -> Result<bool> {
+        Self::interchange_received_in(&mut *self.pool.acquire().await?, partner_id, control_number).await
+    }
+
+    async fn find_outbound(&self, partner_id: Uuid, group_control: u32, set_control: Option<&str>) -> Result<Vec<EdiTransaction>> {
+        Self::find_outbound_in(&mut *self.pool.acquire().await?, partner_id, group_control, set_control).await
+    }
+
+    async fn create_acknowledgment(&self, ack: &EdiAcknowledgment) -> Result<()> {
+        Self::create_acknowledgment_in(&mut *self.pool.acquire().await?, ack).await
+    }
+
+    async fn get_acknowledgment(&self, txn_id: Uuid) -> Result<Option<EdiAcknowledgment>> {
+        let Some(row) = sqlx::query_as::<_, AcknowledgmentRow>(
+            "SELECT id, transaction_id, ack_type, accepted, error_codes, segment_errors, created_at
+             FROM edi_acknowledgments WHERE transaction_id = ? ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(txn_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(EdiAcknowledgment {
+            id: parse_uuid(&row.id, "edi_acknowledgments.id")?,
+            transaction_id: parse_uuid(&row.transaction_id, "edi_acknowledgments.transaction_id")?,
+            ack_type: row.ack_type,
+            accepted: row.accepted,
+            error_codes: from_json(&row.error_codes)?,
+            segment_errors: from_json(&row.segment_errors)?,
+            created_at: parse_datetime(&row.created_at, "edi_acknowledgments.created_at")?,
+        }))
+    }
+
+    async fn create_850(&self, order: &Edi850PurchaseOrder) -> Result<()> {
+        Self::create_850_in(&mut *self.pool.acquire().await?, order).await
+    }
+
+    async fn get_850(&self, id: Uuid) -> Result<Option<Edi850PurchaseOrder>> {
+        sqlx::query_as::<_, PurchaseOrderRow>("SELECT * FROM edi_850_orders WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?
+            .map(PurchaseOrderRow::into_order)
+            .transpose()
+    }
+
+    async fn find_850_by_sales_order(&self, sales_order_id: Uuid) -> Result<Option<Edi850PurchaseOrder>> {
+        sqlx::query_as::<_, PurchaseOrderRow>("SELECT * FROM edi_850_orders WHERE sales_order_id = ?")
+            .bind(sales_order_id.to_string())
+            .fetch_optional(&self.pool)
+            .await?
+            .map(PurchaseOrderRow::into_order)
+            .transpose()
+    }
+
+    async fn find_850_by_po(&self, customer_id: Uuid, po_number: &str) -> Result<Option<Edi850PurchaseOrder>> {
+        Self::find_850_by_po_in(&mut *self.pool.acquire().await?, customer_id, po_number).await
+    }
+}
@@ -0,0 +1,503 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::models::SegmentError;
+use crate::x12::{FunctionalGroup, Segment, TransactionSet};
+
+#[derive(Debug, Clone, Copy)]
+pub enum ElementType {
+    /// Alphanumeric string.
+    AN,
+    /// Identifier, restricted to the listed codes when there are any.
+    ID(&'static [&'static str]),
+    /// Numeric with the given number of implied decimal places.
+    N(u8),
+    /// Decimal number.
+    R,
+    /// Date, CCYYMMDD.
+    DT,
+    /// Time, HHMM[SS[d..]].
+    TM,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ElementRule {
+    pub required: bool,
+    pub kind: ElementType,
+    pub min: usize,
+    pub max: usize,
+}
+
+const fn m(kind: ElementType, min: usize, max: usize) -> ElementRule {
+    ElementRule { required: true, kind, min, max }
+}
+
+const fn o(kind: ElementType, min: usize, max: usize) -> ElementRule {
+    ElementRule { required: false, kind, min, max }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SegmentRule {
+    pub id: &'static str,
+    pub required: bool,
+    /// Maximum occurrences in one transaction set; `None` is unbounded.
+    pub max_use: Option<usize>,
+    pub elements: &'static [ElementRule],
+}
+
+const fn seg(id: &'static str, required: bool, max_use: Option<usize>, elements: &'static [ElementRule]) -> SegmentRule {
+    SegmentRule { id, required, max_use, elements }
+}
+
+/// The segments a transaction set may contain. Loops are flattened, so the order of segments
+/// is not checked, only which ones appear, how often, and what their elements hold.
+#[derive(Debug)]
+pub struct TransactionSchema {
+    pub id: &'static str,
+    pub functional_id: &'static str,
+    pub segments: &'static [SegmentRule],
+}
+
+use ElementType::{AN, DT, ID, N, R, TM};
+
+const ENTITY_CODES: &[&str] = &["BT", "BY", "SF", "ST", "SE", "SU", "VN", "RE", "RI", "II", "CA", "MF", "OB", "Z7"];
+
+const CUR: SegmentRule = seg("CUR", false, Some(1), &[m(ID(&[]), 2, 3), m(ID(&[]), 3, 3)]);
+const REF: SegmentRule = seg("REF", false, None, &[m(ID(&[]), 2, 3), o(AN, 1, 30), o(AN, 1, 80)]);
+const PER: SegmentRule = seg("PER", false, None, &[m(ID(&[]), 2, 2), o(AN, 1, 60), o(ID(&[]), 2, 2), o(AN, 1, 80), o(ID(&[]), 2, 2), o(AN, 1, 80)]);
+const DTM: SegmentRule = seg("DTM", false, None, &[m(ID(&[]), 3, 3), o(DT, 8, 8), o(TM, 4, 8)]);
+const N1: SegmentRule = seg("N1", false, None, &[m(ID(ENTITY_CODES), 2, 3), o(AN, 1, 60), o(ID(&[]), 1, 2), o(AN, 2, 80)]);
+const N2: SegmentRule = seg("N2", false, None, &[m(AN, 1, 60), o(AN, 1, 60)]);
+const N3: SegmentRule = seg("N3", false, None, &[m(AN, 1, 55), o(AN, 1, 55)]);
+const N4: SegmentRule = seg("N4", false, None, &[o(AN, 2, 30), o(ID(&[]), 2, 2), o(ID(&[]), 3, 15), o(ID(&[]), 2, 3)]);
+const PID: SegmentRule = seg("PID", false, None, &[m(ID(&["F", "S", "X"]), 1, 1), o(ID(&[]), 2, 3), o(ID(&[]), 2, 2), o(AN, 1, 12), o(AN, 1, 80)]);
+const CTT: SegmentRule = seg("CTT", false, Some(1), &[m(N(0), 1, 6), o(R, 1, 10)]);
+const LINE_ITEM: &[ElementRule] = &[
+    o(AN, 1, 20),
+    m(R, 1, 15),
+    m(ID(&[]), 2, 2),
+    m(R, 1, 17),
+    o(ID(&[]), 2, 2),
+    o(ID(&[]), 2, 2),
+    o(AN, 1, 48),
+    o(ID(&[]), 2, 2),
+    o(AN, 1, 48),
+    o(ID(&[]), 2, 2),
+    o(AN, 1, 48),
+    o(ID(&[]), 2, 2),
+    o(AN, 1, 48),
+];
+
+pub const X12_850: TransactionSchema = TransactionSchema {
+    id: "850",
+    functional_id: "PO",
+    segments: &[
+        seg("BEG", true, Some(1), &[
+            m(ID(&["00", "01", "05", "06", "07"]), 2, 2),
+            m(ID(&["SA", "NE", "DS", "KN", "RE", "BK", "RL"]), 2, 2),
+            m(AN, 1, 22),
+            o(AN, 1, 30),
+            m(DT, 8, 8),
+        ]),
+        CUR,
+        REF,
+        PER,
+        DTM,
+        N1,
+        N2,
+        N3,
+        N4,
+        seg("PO1", true, None, LINE_ITEM),
+        PID,
+        CTT,
+    ],
+};
+
+pub const X12_855: TransactionSchema = TransactionSchema {
+    id: "855",
+    functional_id: "PR",
+    segments: &[
+        seg("BAK", true, Some(1), &[
+            m(ID(&["00", "01", "05", "06"]), 2, 2),
+            m(ID(&["AC", "AD", "AE", "AK", "AP", "AT", "RD", "RJ"]), 2, 2),
+            m(AN, 1, 22),
+            m(DT, 8, 8),
+        ]),
+        CUR,
+        REF,
+        DTM,
+        N1,
+        N3,
+        N4,
+        seg("PO1", false, None, LINE_ITEM),
+        PID,
+        seg("ACK", false, None, &[
+            m(ID(&["IA", "IB", "IC", "ID", "IP", "IQ", "IR", "IS"]), 2, 2),
+            o(R, 1, 15),
+            o(ID(&[]), 2, 2),
+            o(ID(&[]), 3, 3),
+            o(DT, 8, 8),
+        ]),
+        CTT,
+    ],
+};
+
+pub const X12_856: TransactionSchema = TransactionSchema {
+    id: "856",
+    functional_id: "SH",
+    segments: &[
+        seg("BSN", true, Some(1), &[m(ID(&["00", "01", "05", "06", "07"]), 2, 2), m(AN, 2, 30), m(DT, 8, 8), m(TM, 4, 8), o(ID(&["0001", "0002", "0004"]), 4, 4)]),
+        seg("HL", true, None, &[m(AN, 1, 12), o(AN, 1, 12), m(ID(&["S", "O", "T", "P", "I"]), 1, 2), o(ID(&["0", "1"]), 1, 1)]),
+        seg("TD1", false, None, &[o(AN, 3, 5), o(N(0), 1, 7), o(ID(&[]), 1, 1), o(ID(&[]), 2, 3), o(AN, 1, 50), o(ID(&[]), 1, 2), o(R, 1, 10), o(ID(&[]), 2, 2)]),
+        seg("TD5", false, None, &[o(ID(&[]), 1, 2), o(ID(&[]), 1, 2), o(AN, 2, 80), o(ID(&[]), 1, 2), o(AN, 1, 35)]),
+        REF,
+        DTM,
+        N1,
+        N3,
+        N4,
+        seg("PRF", false, None, &[m(AN, 1, 22), o(AN, 1, 30), o(AN, 1, 30), o(DT, 8, 8)]),
+        seg("LIN", false, None, &[o(AN, 1, 20), m(ID(&[]), 2, 2), m(AN, 1, 48), o(ID(&[]), 2, 2), o(AN, 1, 48)]),
+        seg("SN1", false, None, &[o(AN, 1, 20), m(R, 1, 10), m(ID(&[]), 2, 2)]),
+        PID,
+        seg("MAN", false, None, &[m(ID(&[]), 1, 2), m(AN, 1, 48)]),
+        CTT,
+    ],
+};
+
+pub const X12_810: TransactionSchema = TransactionSchema {
+    id: "810",
+    functional_id: "IN",
+    segments: &[
+        seg("BIG", true, Some(1), &[m(DT, 8, 8), m(AN, 1, 22), o(DT, 8, 8), o(AN, 1, 22)]),
+        CUR,
+        REF,
+        N1,
+        N3,
+        N4,
+        seg("ITD", false, None, &[o(ID(&[]), 2, 2), o(ID(&[]), 1, 2), o(R, 1, 6), o(DT, 8, 8), o(N(0), 1, 3), o(DT, 8, 8), o(N(0), 1, 3)]),
+        DTM,
+        seg("IT1", true, None, LINE_ITEM),
+        PID,
+        seg("TDS", true, Some(1), &[m(N(2), 1, 15), o(N(2), 1, 15), o(N(2), 1, 15), o(N(2), 1, 15)]),
+        seg("TXI", false, None, &[m(ID(&[]), 2, 2), o(R, 1, 18), o(R, 1, 10)]),
+        seg("SAC", false, None, &[m(ID(&["A", "C", "N"]), 1, 1), o(ID(&[]), 4, 4), o(ID(&[]), 2, 2), o(AN, 1, 10), o(N(2), 1, 15)]),
+        CTT,
+    ],
+};
+
+pub const X12_997: TransactionSchema = TransactionSchema {
+    id: "997",
+    functional_id: "FA",
+    segments: &[
+        seg("AK1", true, Some(1), &[m(ID(&[]), 2, 2), m(N(0), 1, 9)]),
+        seg("AK2", false, None, &[m(ID(&[]), 3, 3), m(AN, 4, 9)]),
+        seg("AK3", false, None, &[m(ID(&[]), 2, 3), m(N(0), 1, 6), o(AN, 1, 4), o(ID(&[]), 1, 3)]),
+        seg("AK4", false, None, &[m(N(0), 1, 2), o(N(0), 1, 4), m(ID(&[]), 1, 3), o(AN, 1, 99)]),
+        seg("AK5", false, None, &[m(ID(&["A", "E", "M", "R", "W", "X"]), 1, 1), o(ID(&[]), 1, 3), o(ID(&[]), 1, 3), o(ID(&[]), 1, 3), o(ID(&[]), 1, 3), o(ID(&[]), 1, 3)]),
+        seg("AK9", true, Some(1), &[m(ID(&["A", "E", "M", "P", "R", "W", "X"]), 1, 1), m(N(0), 1, 6), m(N(0), 1, 6), m(N(0), 1, 6), o(ID(&[]), 1, 3), o(ID(&[]), 1, 3), o(ID(&[]), 1, 3), o(ID(&[]), 1, 3), o(ID(&[]), 1, 3)]),
+    ],
+};
+
+pub fn schema_for(transaction_set: &str) -> Option<&'static TransactionSchema> {
+    match transaction_set {
+        "850" => Some(&X12_850),
+        "855" => Some(&X12_855),
+        "856" => Some(&X12_856),
+        "810" => Some(&X12_810),
+        "997" => Some(&X12_997),
+        _ => None,
+    }
+}
+
+/// The outcome of validating one transaction set, as reported in its 997 AK2 loop.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionAck {
+    pub transaction_set: String,
+    pub control_number: String,
+    pub accepted: bool,
+    /// AK5 syntax error codes.
+    pub error_codes: Vec<String>,
+    pub segment_errors: Vec<SegmentError>,
+}
+
+impl TransactionAck {
+    pub fn summary(&self) -> String {
+        let mut problems: Vec<String> = self.error_codes.iter().map(|c| format!("AK5 {}", c)).collect();
+        problems.extend(self.segment_errors.iter().map(|e| match e.element_position {
+            Some(position) => format!("{}{:02} at segment {}: {}", e.segment_id, position, e.segment_position, e.description),
+            None => format!("{} at segment {}: {}", e.segment_id, e.segment_position, e.description),
+        }));
+        problems.join("; ")
+    }
+}
+
+/// The outcome of validating a functional group, which becomes one 997.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupAck {
+    pub functional_id: String,
+    pub control_number: u32,
+    pub transactions: Vec<TransactionAck>,
+    /// GE01 as received, or the actual count when GE is missing or unreadable.
+    pub declared_count: usize,
+    /// AK9 syntax error codes.
+    pub error_codes: Vec<String>,
+}
+
+impl GroupAck {
+    pub fn accepted_count(&self) -> usize {
+        self.transactions.iter().filter(|t| t.accepted).count()
+    }
+
+    /// The body of the 997 acknowledging this group.
+    pub fn to_997(&self) -> Vec<Segment> {
+        let mut segments = vec![Segment::new("AK1", [self.functional_id.clone(), self.control_number.to_string()])];
+        for ack in &self.transactions {
+            segments.push(Segment::new("AK2", [ack.transaction_set.as_str(), &ack.control_number]));
+            let mut errors = ack.segment_errors.iter().peekable();
+            while let Some(first) = errors.next() {
+                let mut element_errors = Vec::new();
+                let mut segment_code = None;
+                for error in std::iter::once(first).chain(std::iter::from_fn(|| {
+                    errors.next_if(|e| e.segment_position == first.segment_position && e.segment_id == first.segment_id)
+                })) {
+                    match error.element_position {
+                        Some(_) => element_errors.push(error),
+                        None => segment_code = Some(error.error_code.clone()),
+                    }
+                }
+                segments.push(Segment::new("AK3", [
+                    first.segment_id.clone(),
+                    first.segment_position.to_string(),
+                    first.loop_id.clone().unwrap_or_default(),
+                    segment_code.unwrap_or_else(|| "8".to_string()),
+                ]));
+                for error in element_errors {
+                    segments.push(Segment::new("AK4", [
+                        error.element_position.unwrap_or_default().to_string(),
+                        String::new(),
+                        error.error_code.clone(),
+                        error.bad_value.clone().unwrap_or_default(),
+                    ]));
+                }
+            }
+            let mut ak5 = vec![if ack.accepted { "A" } else { "R" }.to_string()];
+            ak5.extend(ack.error_codes.iter().take(5).cloned());
+            segments.push(Segment::new("AK5", ak5));
+        }
+        let accepted = self.accepted_count();
+        let status = if !self.error_codes.is_empty() || accepted == 0 {
+            "R"
+        } else if accepted < self.transactions.len() {
+            "P"
+        } else {
+            "A"
+        };
+        let mut ak9 = vec![
+            status.to_string(),
+            self.declared_count.to_string(),
+            self.transactions.len().to_string(),
+            accepted.to_string(),
+        ];
+        ak9.extend(self.error_codes.iter().take(5).cloned());
+        segments.push(Segment::new("AK9", ak9));
+        segments
+    }
+}
+
+pub fn validate_group(group: &FunctionalGroup) -> GroupAck {
+    let mut error_codes = Vec::new();
+    let declared_count = match &group.trailer {
+        None => {
+            error_codes.push("3".to_string());
+            group.transactions.len()
+        }
+        Some(ge) => {
+            if ge.element(2).parse::<u32>().ok() != Some(group.control_number) {
+                error_codes.push("4".to_string());
+            }
+            match ge.element(1).parse::<usize>() {
+                Ok(count) if count == group.transactions.len() => count,
+                Ok(count) => {
+                    error_codes.push("5".to_string());
+                    count
+                }
+                Err(_) => {
+                    error_codes.push("5".to_string());
+                    group.transactions.len()
+                }
+            }
+        }
+    };
+    GroupAck {
+        functional_id: group.functional_id.clone(),
+        control_number: group.control_number,
+        transactions: group.transactions.iter().map(validate_transaction).collect(),
+        declared_count,
+        error_codes,
+    }
+}
+
+pub fn validate_transaction(set: &TransactionSet) -> TransactionAck {
+    let mut error_codes = Vec::new();
+    let mut segment_errors = Vec::new();
+
+    match &set.trailer {
+        None => error_codes.push("2".to_string()),
+        Some(se) => {
+            if se.element(2) != set.control_number {
+                error_codes.push("3".to_string());
+            }
+            if se.element(1).parse::<usize>().ok() != Some(set.segments.len() + 2) {
+                error_codes.push("4".to_string());
+            }
+        }
+    }
+    if !(4..=9).contains(&set.control_number.len()) {
+        error_codes.push("7".to_string());
+    }
+
+    match schema_for(&set.id) {
+        None => error_codes.insert(0, "1".to_string()),
+        Some(schema) => {
+            let mut uses = vec![0usize; schema.segments.len()];
+            for (index, segment) in set.segments.iter().enumerate() {
+                // ST is position 1.
+                let position = index as i32 + 2;
+                let Some(rule_index) = schema.segments.iter().position(|r| r.id == segment.id) else {
+                    let recognised = (2..=3).contains(&segment.id.len())
+                        && segment.id.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+                    let (code, description) = if recognised {
+                        ("2", format!("Segment {} is not used in a {}", segment.id, set.id))
+                    } else {
+                        ("1", format!("Unrecognized segment ID {}", segment.id))
+                    };
+                    segment_errors.push(segment_error(segment, position, None, code, description, None));
+                    continue;
+                };
+                let rule = &schema.segments[rule_index];
+                uses[rule_index] += 1;
+                if rule.max_use.is_some_and(|max| uses[rule_index] > max) {
+                    segment_errors.push(segment_error(segment, position, None, "5", format!("{} occurs more than {} times", rule.id, rule.max_use.unwrap_or_default()), None));
+                }
+                validate_elements(segment, position, rule, &mut segment_errors);
+            }
+            let end = set.segments.len() as i32 + 2;
+            for (rule, count) in schema.segments.iter().zip(&uses) {
+                if rule.required && *count == 0 {
+                    segment_errors.push(SegmentError {
+                        segment_id: rule.id.to_string(),
+                        segment_position: end,
+                        loop_id: None,
+                        element_position: None,
+                        error_code: "3".to_string(),
+                        description: format!("Mandatory segment {} is missing", rule.id),
+                        bad_value: None,
+                    });
+                }
+            }
+        }
+    }
+
+    if !segment_errors.is_empty() && !error_codes.contains(&"5".to_string()) {
+        error_codes.push("5".to_string());
+    }
+    TransactionAck {
+        transaction_set: set.id.clone(),
+        control_number: set.control_number.clone(),
+        accepted: error_codes.is_empty(),
+        error_codes,
+        segment_errors,
+    }
+}
+
+fn validate_elements(segment: &Segment, position: i32, rule: &SegmentRule, errors: &mut Vec<SegmentError>) {
+    for (index, element_rule) in rule.elements.iter().enumerate() {
+        let element = index + 1;
+        let value = segment.element(element);
+        if value.is_empty() {
+            if element_rule.required {
+                errors.push(segment_error(segment, position, Some(element), "1", format!("{}{:02} is mandatory", segment.id, element), None));
+            }
+            continue;
+        }
+        if let Some((code, problem)) = check_element(value, element_rule) {
+            errors.push(segment_error(segment, position, Some(element), code, format!("{}{:02} {}", segment.id, element, problem), Some(value)));
+        }
+    }
+    if segment.elements.len() > rule.elements.len() && segment.elements[rule.elements.len()..].iter().any(|e| !e.is_empty()) {
+        let element = rule.elements.len() + 1;
+        errors.push(segment_error(segment, position, Some(element), "3", format!("{} has more than {} elements", segment.id, rule.elements.len()), None));
+    }
+}
+
+fn check_element(value: &str, rule: &ElementRule) -> Option<(&'static str, String)> {
+    if value.chars().any(char::is_control) {
+        return Some(("6", "contains control characters".to_string()));
+    }
+    let length = match rule.kind {
+        ElementType::N(_) | ElementType::R => value.chars().filter(char::is_ascii_digit).count(),
+        _ => value.chars().count(),
+    };
+    match rule.kind {
+        ElementType::N(_) => {
+            let digits = value.strip_prefix('-').unwrap_or(value);
+            if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return Some(("6", "must be numeric".to_string()));
+            }
+        }
+        ElementType::R => {
+            let digits = value.strip_prefix('-').unwrap_or(value);
+            if digits.is_empty() || digits == "." || digits.matches('.').count() > 1 || !digits.chars().all(|c| c.is_ascii_digit() || c == '.') {
+                return Some(("6", "must be a decimal number".to_string()));
+            }
+        }
+        ElementType::DT => {
+            if !value.chars().all(|c| c.is_ascii_digit()) || NaiveDate::parse_from_str(value, "%Y%m%d").is_err() {
+                return Some(("8", "is not a valid CCYYMMDD date".to_string()));
+            }
+        }
+        ElementType::TM => {
+            let digits: Vec<u32> = value.chars().filter_map(|c| c.to_digit(10)).collect();
+            let valid = digits.len() == value.len()
+                && matches!(digits.len(), 4 | 6 | 7 | 8)
+                && digits[0] * 10 + digits[1] < 24
+                && digits[2] * 10 + digits[3] < 60
+                && (digits.len() < 6 || digits[4] * 10 + digits[5] < 60);
+            if !valid {
+                return Some(("9", "is not a valid HHMM time".to_string()));
+            }
+        }
+        ElementType::AN | ElementType::ID(_) => {}
+    }
+    if length < rule.min {
+        return Some(("4", format!("is shorter than {} characters", rule.min)));
+    }
+    if length > rule.max {
+        return Some(("5", format!("is longer than {} characters", rule.max)));
+    }
+    if let ElementType::ID(codes) = rule.kind {
+        if !codes.is_empty() && !codes.contains(&value) {
+            return Some(("7", format!("code '{}' is not one of {}", value, codes.join(", "))));
+        }
+    }
+    None
+}
+
+fn segment_error(
+    segment: &Segment,
+    position: i32,
+    element: Option<usize>,
+    code: &str,
+    description: String,
+    bad_value: Option<&str>,
+) -> SegmentError {
+    SegmentError {
+        segment_id: segment.id.clone(),
+        segment_position: position,
+        loop_id: None,
+        element_position: element.map(|e| e as i32),
+        error_code: code.to_string(),
+        description,
+        bad_value: bad_value.map(str::to_string),
+    }
+}
@@ -1,8 +1,19 @@
 use chrono::Utc;
+use erp_core::{Address, BaseEntity, Currency, Error, Money, Result, Status};
+use erp_sales::{CustomerService, ReceivablesService, SalesOrder, SalesOrderLine, SalesOrderService};
+use serde_json::json;
+use sqlx::{Connection, SqliteConnection};
 use uuid::Uuid;
 
+use crate::mapping;
 use crate::models::*;
 use crate::repository::*;
+use crate::schema;
+use crate::x12::{Delimiters, FunctionalGroup, Interchange, Segment, TransactionSet};
+
+/// The X12 release of everything we send.
+const X12_VERSION: &str = "00401";
+const X12_GROUP_VERSION: &str = "004010";
 
 pub struct EdiService<R: EdiRepository> {
     pub repo: R,
@@ -15,7 +26,20 @@ impl EdiService<SqliteEdiRepository> {
 }
 
 impl<R: EdiRepository> EdiService<R> {
-    pub async fn create_partner(&self, req: CreatePartnerRequest) -> anyhow::Result<EdiPartner> {
+    pub async fn create_partner(&self, req: CreatePartnerRequest) -> Result<EdiPartner> {
+        if req.partner_code.trim().is_empty() {
+            return Err(Error::validation("Partner code is required"));
+        }
+        for (field, qualifier) in [("qualifier", &req.qualifier), ("local_qualifier", &req.local_qualifier)] {
+            if qualifier.len() != 2 {
+                return Err(Error::validation(format!("{} must be a two-character ISA qualifier", field)));
+            }
+        }
+        for (field, id) in [("interchange_id", &req.interchange_id), ("local_interchange_id", &req.local_interchange_id)] {
+            if id.trim().is_empty() || id.len() > 15 {
+                return Err(Error::validation(format!("{} must be 1 to 15 characters", field)));
+            }
+        }
         let partner = EdiPartner {
             id: Uuid::new_v4(),
             partner_code: req.partner_code,
@@ -23,6 +47,9 @@ impl<R: EdiRepository> EdiService<R> {
             partner_type: req.partner_type,
             qualifier: req.qualifier,
             interchange_id: req.interchange_id,
+            local_qualifier: req.local_qualifier,
+            local_interchange_id: req.local_interchange_id,
+            customer_id: req.customer_id,
             communication_type: req.communication_type,
             endpoint: req.endpoint,
             encryption: req.encryption,
@@ -33,57 +60,461 @@ impl<R: EdiRepository> EdiService<R> {
         Ok(partner)
     }
 
-    pub async fn process_inbound(&self, req: ProcessEdiRequest) -> anyhow::Result<EdiTransaction> {
-        let control_number = format!("IC-{}", Utc::now().format("%Y%m%d%H%M%S"));
-        let txn = EdiTransaction {
-            id: Uuid::new_v4(),
-            partner_id: req.partner_id,
-            transaction_type: EdiTransactionType::X12_850,
-            direction: EdiDirection::Inbound,
-            control_number,
-            status: EdiStatus::Received,
-            raw_content: Some(req.raw_content),
-            parsed_data: None,
-            error_message: None,
-            processed_at: None,
-            created_at: Utc::now(),
-        };
-        self.repo.create_transaction(&txn).await?;
-        Ok(txn)
+    pub async fn list_partners(&self, partner_type: Option<PartnerType>) -> Result<Vec<EdiPartner>> {
+        self.repo.list_partners(partner_type).await
+    }
+
+    pub async fn list_transactions(&self, partner_id: Option<Uuid>, txn_type: Option<EdiTransactionType>) -> Result<Vec<EdiTransaction>> {
+        self.repo.list_transactions(partner_id, txn_type).await
+    }
+
+    pub async fn get_transaction(&self, id: Uuid) -> Result<EdiTransaction> {
+        self.repo
+            .get_transaction(id)
+            .await?
+            .ok_or_else(|| Error::not_found("EdiTransaction", &id.to_string()))
+    }
+
+    pub async fn get_acknowledgment(&self, transaction_id: Uuid) -> Result<Option<EdiAcknowledgment>> {
+        self.repo.get_acknowledgment(transaction_id).await
     }
 
-    pub async fn generate_outbound(&self, req: GenerateEdiRequest) -> anyhow::Result<EdiTransmissionResult> {
-        let control_number = format!("OC-{}", Utc::now().format("%Y%m%d%H%M%S"));
-        let raw_content = format!("ISA*00*          *00*          *ZZ*SENDER         *ZZ*RECEIVER       *{}*1234*U*00401*000000001*0*P*>~", Utc::now().format("%y%m%d"));
-        
+    async fn partner(&self, id: Uuid) -> Result<EdiPartner> {
+        let partner = self
+            .repo
+            .get_partner(id)
+            .await?
+            .ok_or_else(|| Error::not_found("EdiPartner", &id.to_string()))?;
+        if !partner.is_active {
+            return Err(Error::business_rule(format!("EDI partner {} is inactive", partner.partner_code)));
+        }
+        Ok(partner)
+    }
+
+    /// Wraps transaction sets of one type in a fresh interchange for the partner, checks the
+    /// result against the same schema inbound documents are held to, and records it.
+    async fn send(
+        &self,
+        partner: &EdiPartner,
+        transaction_id: Uuid,
+        txn_type: EdiTransactionType,
+        sets: Vec<Vec<Segment>>,
+        parsed_data: Option<serde_json::Value>,
+    ) -> Result<EdiTransmissionResult> {
+        let schema = txn_type
+            .x12_code()
+            .and_then(schema::schema_for)
+            .ok_or_else(|| Error::business_rule(format!("{:?} documents cannot be sent", txn_type)))?;
+        if partner.interchange_id.trim().is_empty() || partner.local_interchange_id.trim().is_empty() {
+            return Err(Error::business_rule(format!("EDI partner {} has no interchange IDs", partner.partner_code)));
+        }
+        let numbers = self.repo.next_control_numbers(partner.id, sets.len() as u32).await?;
+        let now = Utc::now();
+        let transactions: Vec<TransactionSet> = sets
+            .into_iter()
+            .enumerate()
+            .map(|(i, segments)| {
+                let number = (numbers.first_transaction as u64 + i as u64 - 1) % 999_999_999 + 1;
+                TransactionSet::new(schema.id, format!("{:04}", number), segments)
+            })
+            .collect();
+        let first_set = transactions[0].control_number.clone();
+        let interchange = Interchange {
+            delimiters: Delimiters::default(),
+            sender_qualifier: partner.local_qualifier.clone(),
+            sender_id: partner.local_interchange_id.clone(),
+            receiver_qualifier: partner.qualifier.clone(),
+            receiver_id: partner.interchange_id.clone(),
+            date: now.format("%y%m%d").to_string(),
+            time: now.format("%H%M").to_string(),
+            version: X12_VERSION.to_string(),
+            control_number: numbers.interchange,
+            test: false,
+            groups: vec![FunctionalGroup {
+                functional_id: schema.functional_id.to_string(),
+                sender_code: partner.local_interchange_id.clone(),
+                receiver_code: partner.interchange_id.clone(),
+                date: now.format("%Y%m%d").to_string(),
+                time: now.format("%H%M").to_string(),
+                control_number: numbers.group,
+                version: X12_GROUP_VERSION.to_string(),
+                transactions,
+                trailer: None,
+            }],
+        };
+        let raw_content = interchange.to_x12();
+
+        let written = Interchange::parse(&raw_content)?;
+        let check = schema::validate_group(&written.groups[0]);
+        if let Some(rejected) = check.transactions.iter().find(|t| !t.accepted) {
+            return Err(Error::business_rule(format!("Generated {} is not valid: {}", schema.id, rejected.summary())));
+        }
+
+        let control_number = format!("OUT:{}:{:09}:{}:{}", partner.partner_code, numbers.interchange, numbers.group, first_set);
         let txn = EdiTransaction {
-            id: Uuid::new_v4(),
-            partner_id: req.partner_id,
-            transaction_type: req.transaction_type,
+            id: transaction_id,
+            partner_id: partner.id,
+            transaction_type: txn_type,
             direction: EdiDirection::Outbound,
             control_number: control_number.clone(),
             status: EdiStatus::Processed,
             raw_content: Some(raw_content.clone()),
-            parsed_data: None,
+            parsed_data,
             error_message: None,
-            processed_at: Some(Utc::now()),
-            created_at: Utc::now(),
+            processed_at: Some(now),
+            created_at: now,
         };
         self.repo.create_transaction(&txn).await?;
+        Ok(EdiTransmissionResult { transaction_id: txn.id, control_number, raw_content, sent_at: now })
+    }
+}
+
+impl EdiService<SqliteEdiRepository> {
+    /// Parses and validates an inbound interchange, books what it carries, and answers every
+    /// functional group other than acknowledgments with a 997. The interchange's transactions
+    /// and everything they book commit together.
+    pub async fn process_inbound(&self, req: ProcessEdiRequest) -> Result<InboundResult> {
+        let interchange = Interchange::parse(&req.raw_content)?;
+        let partner = match req.partner_id {
+            Some(id) => self.partner(id).await?,
+            None => {
+                let partner = self
+                    .repo
+                    .find_partner_by_interchange(&interchange.sender_qualifier, &interchange.sender_id)
+                    .await?
+                    .ok_or_else(|| {
+                        Error::not_found("EdiPartner", &format!("{}:{}", interchange.sender_qualifier, interchange.sender_id))
+                    })?;
+                self.partner(partner.id).await?
+            }
+        };
+        if interchange.sender_qualifier != partner.qualifier || interchange.sender_id != partner.interchange_id {
+            return Err(Error::validation(format!(
+                "Interchange sender {}:{} is not partner {}",
+                interchange.sender_qualifier, interchange.sender_id, partner.partner_code
+            )));
+        }
+        if interchange.receiver_qualifier != partner.local_qualifier || interchange.receiver_id != partner.local_interchange_id {
+            return Err(Error::validation(format!(
+                "Interchange is addressed to {}:{}, not to {}:{}",
+                interchange.receiver_qualifier, interchange.receiver_id, partner.local_qualifier, partner.local_interchange_id
+            )));
+        }
+        let mut tx = self.repo.pool.begin().await?;
+        if SqliteEdiRepository::interchange_received_in(&mut tx, partner.id, interchange.control_number).await? {
+            return Err(Error::Conflict(format!(
+                "Interchange {:09} from {} has already been received",
+                interchange.control_number, partner.partner_code
+            )));
+        }
+
+        let mut transactions = Vec::new();
+        let mut acknowledgments = Vec::new();
+        for group in &interchange.groups {
+            let group_ack = schema::validate_group(group);
+            let acknowledge = group.functional_id != "FA";
+            for (set, ack) in group.transactions.iter().zip(&group_ack.transactions) {
+                let Some(transaction_type) = EdiTransactionType::from_x12(&set.id) else {
+                    continue;
+                };
+                let now = Utc::now();
+                let mut txn = EdiTransaction {
+                    id: Uuid::new_v4(),
+                    partner_id: partner.id,
+                    transaction_type,
+                    direction: EdiDirection::Inbound,
+                    control_number: format!(
+                        "IN:{}:{:09}:{}:{}",
+                        partner.partner_code, interchange.control_number, group.control_number, set.control_number
+                    ),
+                    status: EdiStatus::Validated,
+                    raw_content: Some(set.to_x12(&interchange.delimiters)),
+                    parsed_data: None,
+                    error_message: None,
+                    processed_at: Some(now),
+                    created_at: now,
+                };
+                if ack.accepted {
+                    Self::apply_inbound(&mut tx, &partner, set, &mut txn).await?;
+                } else {
+                    txn.status = EdiStatus::Error;
+                    txn.error_message = Some(ack.summary());
+                }
+                SqliteEdiRepository::create_transaction_in(&mut tx, &txn).await?;
+                if acknowledge {
+                    SqliteEdiRepository::create_acknowledgment_in(
+                        &mut tx,
+                        &EdiAcknowledgment {
+                            id: Uuid::new_v4(),
+                            transaction_id: txn.id,
+                            ack_type: AckType::FA997,
+                            accepted: ack.accepted,
+                            error_codes: ack.error_codes.clone(),
+                            segment_errors: ack.segment_errors.clone(),
+                            created_at: now,
+                        },
+                    )
+                    .await?;
+                }
+                transactions.push(txn);
+            }
+            if acknowledge {
+                acknowledgments.push(group_ack);
+            }
+        }
+        tx.commit().await?;
+
+        let acknowledgment = if acknowledgments.is_empty() {
+            None
+        } else {
+            let parsed = serde_json::to_value(&acknowledgments).map_err(|e| Error::internal(e.to_string()))?;
+            let sets = acknowledgments.iter().map(|a| a.to_997()).collect();
+            Some(self.send(&partner, Uuid::new_v4(), EdiTransactionType::X12_997, sets, Some(parsed)).await?)
+        };
+        Ok(InboundResult { transactions, acknowledgment })
+    }
 
-        Ok(EdiTransmissionResult {
-            transaction_id: txn.id,
-            control_number,
-            raw_content,
-            sent_at: Utc::now(),
-        })
+    async fn apply_inbound(conn: &mut SqliteConnection, partner: &EdiPartner, set: &TransactionSet, txn: &mut EdiTransaction) -> Result<()> {
+        match txn.transaction_type {
+            EdiTransactionType::X12_850 => {
+                // A rejected order is recorded as an error, so its writes are undone on their own.
+                let mut savepoint = conn.begin().await?;
+                match Self::book_850(&mut savepoint, partner, set, txn.id).await {
+                    Ok(order) => {
+                        savepoint.commit().await?;
+                        txn.status = EdiStatus::Processed;
+                        txn.parsed_data = Some(serde_json::to_value(&order).map_err(|e| Error::internal(e.to_string()))?);
+                    }
+                    Err(e @ (Error::Validation(_) | Error::BusinessRule(_) | Error::NotFound(_))) => {
+                        txn.status = EdiStatus::Error;
+                        txn.error_message = Some(e.to_string());
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            EdiTransactionType::X12_997 => {
+                Self::reconcile_997(conn, partner, set).await?;
+                txn.status = EdiStatus::Processed;
+                txn.parsed_data = Some(json!(set.segments));
+            }
+            _ => {
+                txn.status = EdiStatus::Parsed;
+                txn.parsed_data = Some(json!(set.segments));
+            }
+        }
+        Ok(())
     }
 
-    pub async fn list_partners(&self, partner_type: Option<PartnerType>) -> anyhow::Result<Vec<EdiPartner>> {
-        self.repo.list_partners(partner_type).await
+    /// Marks the outbound transactions a partner's 997 acknowledges, set by set when it has AK2
+    /// loops and by group otherwise.
+    async fn reconcile_997(conn: &mut SqliteConnection, partner: &EdiPartner, set: &TransactionSet) -> Result<()> {
+        let Some(group) = set.segments.iter().find(|s| s.id == "AK1").and_then(|s| s.element(2).parse::<u32>().ok()) else {
+            return Ok(());
+        };
+        let mut acknowledged_set = None;
+        let mut by_set = false;
+        for segment in &set.segments {
+            match segment.id.as_str() {
+                "AK2" => acknowledged_set = Some(segment.element(2).to_string()),
+                "AK5" => {
+                    if let Some(control) = acknowledged_set.take() {
+                        by_set = true;
+                        Self::mark_acknowledged(conn, partner, group, Some(&control), segment).await?;
+                    }
+                }
+                "AK9" if !by_set => Self::mark_acknowledged(conn, partner, group, None, segment).await?,
+                _ => {}
+            }
+        }
+        Ok(())
     }
 
-    pub async fn list_transactions(&self, partner_id: Option<Uuid>, txn_type: Option<EdiTransactionType>) -> anyhow::Result<Vec<EdiTransaction>> {
-        self.repo.list_transactions(partner_id, txn_type).await
+    async fn mark_acknowledged(conn: &mut SqliteConnection, partner: &EdiPartner, group: u32, set: Option<&str>, status: &Segment) -> Result<()> {
+        let codes_from = if status.id == "AK9" { 5 } else { 2 };
+        let codes: Vec<&str> = status.elements.iter().skip(codes_from - 1).map(String::as_str).filter(|c| !c.is_empty()).collect();
+        let (new_status, message) = match status.element(1) {
+            "A" | "E" => (EdiStatus::Acknowledged, None),
+            code => (EdiStatus::Error, Some(format!("Partner 997 returned {} with codes [{}]", code, codes.join(", ")))),
+        };
+        for txn in SqliteEdiRepository::find_outbound_in(conn, partner.id, group, set).await? {
+            SqliteEdiRepository::update_transaction_status_in(conn, txn.id, new_status, message.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// Books an 850 as a draft sales order for the partner's customer.
+    async fn book_850(conn: &mut SqliteConnection, partner: &EdiPartner, set: &TransactionSet, transaction_id: Uuid) -> Result<Edi850PurchaseOrder> {
+        let customer_id = partner
+            .customer_id
+            .ok_or_else(|| Error::business_rule(format!("EDI partner {} is not linked to a customer", partner.partner_code)))?;
+        let mut po = mapping::read_850(set, transaction_id, customer_id)?;
+        if po.currency != "USD" {
+            return Err(Error::business_rule(format!("Purchase order {} is in {}; sales orders are booked in USD", po.po_number, po.currency)));
+        }
+        if SqliteEdiRepository::find_850_by_po_in(conn, customer_id, &po.po_number).await?.is_some() {
+            return Err(Error::business_rule(format!("Purchase order {} has already been booked", po.po_number)));
+        }
+        let skus: Vec<String> = po.lines.iter().map(|l| l.sku.clone()).collect();
+        let products = SqliteEdiRepository::products_by_sku_in(conn, &skus).await?;
+        let unknown: Vec<&str> = skus.iter().filter(|s| !products.contains_key(*s)).map(String::as_str).collect();
+        if !unknown.is_empty() {
+            return Err(Error::business_rule(format!("Unknown SKUs on purchase order {}: {}", po.po_number, unknown.join(", "))));
+        }
+        for line in &mut po.lines {
+            let (product_id, name) = &products[&line.sku];
+            line.product_id = Some(*product_id);
+            if line.description.is_empty() {
+                line.description = name.clone();
+            }
+        }
+
+        let usd = |amount| Money::new(amount, Currency::USD);
+        let order = SalesOrder {
+            base: BaseEntity::new(),
+            order_number: String::new(),
+            customer_id,
+            order_date: po.po_date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc(),
+            required_date: po.lines.iter().filter_map(|l| l.requested_date).min().and_then(|d| d.and_hms_opt(0, 0, 0)).map(|d| d.and_utc()),
+            lines: po
+                .lines
+                .iter()
+                .map(|l| SalesOrderLine {
+                    id: Uuid::nil(),
+                    product_id: l.product_id.unwrap_or_default(),
+                    description: l.description.clone(),
+                    quantity: l.quantity,
+                    unit_price: usd(l.unit_price),
+                    discount_percent: 0.0,
+                    tax_rate: 0.0,
+                    line_total: usd(l.quantity * l.unit_price),
+                })
+                .collect(),
+            subtotal: usd(0),
+            tax_amount: usd(0),
+            total: usd(0),
+            status: Status::Draft,
+        };
+        let order = SalesOrderService::new().create_in(conn, order).await?;
+        po.sales_order_id = Some(order.base.id);
+        SqliteEdiRepository::create_850_in(conn, &po).await?;
+        Ok(po)
+    }
+
+    /// Builds and records an outbound 810 (invoice), 855 (sales order) or 856 (shipment).
+    pub async fn generate_outbound(&self, req: GenerateEdiRequest) -> Result<EdiTransmissionResult> {
+        let partner = self.partner(req.partner_id).await?;
+        let transaction_id = Uuid::new_v4();
+        let (segments, parsed) = match req.transaction_type {
+            EdiTransactionType::X12_810 => self.invoice_810(&partner, req.reference_id).await?,
+            EdiTransactionType::X12_855 => self.order_ack_855(&partner, req.reference_id).await?,
+            EdiTransactionType::X12_856 => self.ship_notice_856(&partner, req.reference_id, transaction_id).await?,
+            other => return Err(Error::business_rule(format!("{:?} documents cannot be generated", other))),
+        };
+        self.send(&partner, transaction_id, req.transaction_type, vec![segments], Some(parsed)).await
+    }
+
+    async fn invoice_810(&self, partner: &EdiPartner, invoice_id: Uuid) -> Result<(Vec<Segment>, serde_json::Value)> {
+        let pool = &self.repo.pool;
+        let invoice = ReceivablesService::get_invoice(pool, invoice_id).await?;
+        check_customer(partner, invoice.customer_id, "Invoice", &invoice.invoice_number)?;
+        let po = match invoice.sales_order_id {
+            Some(order_id) => self.repo.find_850_by_sales_order(order_id).await?,
+            None => None,
+        };
+        let product_ids: Vec<Uuid> = invoice.lines.iter().map(|l| l.product_id).collect();
+        let skus = self.repo.skus_by_product(&product_ids).await?;
+        let bill_to = match po.as_ref().map(|p| p.bill_to.clone()).filter(|a| !a.name.is_empty()) {
+            Some(address) => address,
+            None => {
+                let customer = CustomerService::new().get(pool, invoice.customer_id).await?;
+                edi_address(&customer.name, &customer.billing_address)
+            }
+        };
+        let segments = mapping::invoice_810(&invoice, &skus, po.as_ref(), &bill_to);
+        let parsed = json!({
+            "invoice_id": invoice.base.id,
+            "invoice_number": invoice.invoice_number,
+            "po_number": po.as_ref().map(|p| p.po_number.clone()),
+            "total": invoice.total.amount,
+        });
+        Ok((segments, parsed))
+    }
+
+    async fn order_ack_855(&self, partner: &EdiPartner, order_id: Uuid) -> Result<(Vec<Segment>, serde_json::Value)> {
+        let order = SalesOrderService::new().get(&self.repo.pool, order_id).await?;
+        check_customer(partner, order.customer_id, "Sales order", &order.order_number)?;
+        let po = self.repo.find_850_by_sales_order(order_id).await?.ok_or_else(|| {
+            Error::business_rule(format!("Sales order {} was not booked from an EDI purchase order", order.order_number))
+        })?;
+        let segments = mapping::purchase_order_ack_855(&po, &order);
+        let parsed = json!({ "sales_order_id": order.base.id, "order_number": order.order_number, "po_number": po.po_number });
+        Ok((segments, parsed))
+    }
+
+    async fn ship_notice_856(&self, partner: &EdiPartner, shipment_id: Uuid, transaction_id: Uuid) -> Result<(Vec<Segment>, serde_json::Value)> {
+        let shipment = self
+            .repo
+            .get_shipment(shipment_id)
+            .await?
+            .ok_or_else(|| Error::not_found("Shipment", &shipment_id.to_string()))?;
+        let order_id = shipment.order_id.ok_or_else(|| {
+            Error::business_rule(format!("Shipment {} is not linked to a sales order", shipment.shipment_number))
+        })?;
+        let order = SalesOrderService::new().get(&self.repo.pool, order_id).await?;
+        check_customer(partner, order.customer_id, "Sales order", &order.order_number)?;
+        let po_number = match self.repo.find_850_by_sales_order(order_id).await? {
+            Some(po) => po.po_number,
+            None => order.order_number.clone(),
+        };
+        let product_ids: Vec<Uuid> = order.lines.iter().map(|l| l.product_id).collect();
+        let skus = self.repo.skus_by_product(&product_ids).await?;
+        let items: Vec<Edi856Item> = order
+            .lines
+            .iter()
+            .map(|l| Edi856Item {
+                sku: skus.get(&l.product_id).cloned().unwrap_or_else(|| l.product_id.to_string()),
+                quantity: l.quantity,
+                lot_number: None,
+            })
+            .collect();
+        let shipped_at = shipment.shipped_at.unwrap_or_else(Utc::now);
+        let shipment_date = shipped_at.date_naive();
+        let asn = Edi856ASN {
+            id: Uuid::new_v4(),
+            transaction_id,
+            asn_number: shipment.shipment_number.clone(),
+            shipment_date,
+            expected_date: shipment.estimated_delivery.map_or(shipment_date, |d| d.date_naive()),
+            po_number,
+            carrier: shipment.carrier.clone(),
+            tracking_number: shipment.tracking_number.clone(),
+            total_items: items.iter().map(|i| i.quantity as i32).sum(),
+            packages: vec![Edi856Package { package_id: shipment.shipment_number.clone(), weight: shipment.weight, items }],
+        };
+        let segments = mapping::ship_notice_856(&asn, &shipment.ship_to, shipped_at, &shipment.weight_unit);
+        let parsed = serde_json::to_value(&asn).map_err(|e| Error::internal(e.to_string()))?;
+        Ok((segments, parsed))
+    }
+}
+
+fn check_customer(partner: &EdiPartner, customer_id: Uuid, document: &str, number: &str) -> Result<()> {
+    match partner.customer_id {
+        Some(expected) if expected != customer_id => Err(Error::business_rule(format!(
+            "{} {} is for another customer than EDI partner {}",
+            document, number, partner.partner_code
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn edi_address(name: &str, address: &Address) -> EdiAddress {
+    EdiAddress {
+        name: name.to_string(),
+        address1: address.street.clone(),
+        address2: None,
+        city: address.city.clone(),
+        state: address.state.clone().unwrap_or_default(),
+        postal_code: address.postal_code.clone(),
+        country: address.country.clone(),
     }
 }
@@ -0,0 +1,319 @@
+use erp_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// The ISA segment is fixed width, which is what makes delimiter detection possible.
+const ISA_LENGTH: usize = 106;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delimiters {
+    pub element: char,
+    pub component: char,
+    /// ISA11, which only separates repeated elements from version 00402 on.
+    pub repetition: Option<char>,
+    pub segment: char,
+}
+
+impl Default for Delimiters {
+    fn default() -> Self {
+        Self { element: '*', component: '>', repetition: None, segment: '~' }
+    }
+}
+
+impl Delimiters {
+    /// Reads the delimiters from the ISA segment that opens an interchange.
+    pub fn detect(raw: &str) -> Result<Self> {
+        let isa: Vec<char> = raw.trim_start().chars().take(ISA_LENGTH).collect();
+        if isa.len() < ISA_LENGTH || !isa.starts_with(&['I', 'S', 'A']) {
+            return Err(Error::validation("Interchange must start with a 106-character ISA segment"));
+        }
+        let delimiters = Self {
+            element: isa[3],
+            component: isa[104],
+            repetition: None,
+            segment: isa[105],
+        };
+        let header: String = isa[..104].iter().collect();
+        let fields: Vec<&str> = header.split(delimiters.element).collect();
+        if fields.len() != 17 {
+            return Err(Error::validation(format!(
+                "ISA has {} elements, expected 16; check the element separator '{}'",
+                fields.len() - 1,
+                delimiters.element
+            )));
+        }
+        let chars = [delimiters.element, delimiters.component, delimiters.segment];
+        if chars.iter().any(|c| c.is_ascii_alphanumeric())
+            || chars[0] == chars[1]
+            || chars[0] == chars[2]
+            || chars[1] == chars[2]
+        {
+            return Err(Error::validation("ISA delimiters must be distinct non-alphanumeric characters"));
+        }
+        let repetition = fields[11].chars().next().filter(|c| fields[12] >= "00402" && !c.is_ascii_alphanumeric());
+        Ok(Self { repetition, ..delimiters })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Segment {
+    pub id: String,
+    pub elements: Vec<String>,
+}
+
+impl Segment {
+    pub fn new<S: Into<String>>(id: &str, elements: impl IntoIterator<Item = S>) -> Self {
+        Self { id: id.to_string(), elements: elements.into_iter().map(Into::into).collect() }
+    }
+
+    /// `XX01` is `element(1)`. Absent elements read as empty.
+    pub fn element(&self, position: usize) -> &str {
+        position.checked_sub(1).and_then(|i| self.elements.get(i)).map_or("", String::as_str)
+    }
+
+    fn parse(text: &str, delimiters: &Delimiters) -> Self {
+        let mut parts = text.split(delimiters.element);
+        let id = parts.next().unwrap_or_default().to_string();
+        Self { id, elements: parts.map(str::to_string).collect() }
+    }
+
+    /// Writes the segment with its terminator, dropping trailing empty elements.
+    pub fn write(&self, delimiters: &Delimiters, out: &mut String) {
+        let used = self.elements.iter().rposition(|e| !e.is_empty()).map_or(0, |i| i + 1);
+        out.push_str(&self.id);
+        for element in &self.elements[..used] {
+            out.push(delimiters.element);
+            out.push_str(element);
+        }
+        out.push(delimiters.segment);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionSet {
+    /// ST01, e.g. `850`.
+    pub id: String,
+    /// ST02.
+    pub control_number: String,
+    /// The segments between ST and SE.
+    pub segments: Vec<Segment>,
+    /// SE as received. Ignored when writing, where it is computed.
+    pub trailer: Option<Segment>,
+}
+
+impl TransactionSet {
+    pub fn new(id: &str, control_number: String, segments: Vec<Segment>) -> Self {
+        Self { id: id.to_string(), control_number, segments, trailer: None }
+    }
+
+    pub fn write(&self, delimiters: &Delimiters, out: &mut String) {
+        Segment::new("ST", [self.id.as_str(), &self.control_number]).write(delimiters, out);
+        for segment in &self.segments {
+            segment.write(delimiters, out);
+        }
+        Segment::new("SE", [(self.segments.len() + 2).to_string(), self.control_number.clone()]).write(delimiters, out);
+    }
+
+    pub fn to_x12(&self, delimiters: &Delimiters) -> String {
+        let mut out = String::new();
+        self.write(delimiters, &mut out);
+        out
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionalGroup {
+    /// GS01, e.g. `PO` for purchase orders.
+    pub functional_id: String,
+    pub sender_code: String,
+    pub receiver_code: String,
+    /// CCYYMMDD.
+    pub date: String,
+    /// HHMM.
+    pub time: String,
+    pub control_number: u32,
+    /// GS08, e.g. `004010`.
+    pub version: String,
+    pub transactions: Vec<TransactionSet>,
+    /// GE as received. Ignored when writing, where it is computed.
+    pub trailer: Option<Segment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interchange {
+    pub delimiters: Delimiters,
+    pub sender_qualifier: String,
+    pub sender_id: String,
+    pub receiver_qualifier: String,
+    pub receiver_id: String,
+    /// YYMMDD.
+    pub date: String,
+    /// HHMM.
+    pub time: String,
+    /// ISA12, e.g. `00401`.
+    pub version: String,
+    pub control_number: u32,
+    pub test: bool,
+    pub groups: Vec<FunctionalGroup>,
+}
+
+impl Interchange {
+    /// Splits an interchange into groups and transaction sets. Envelope faults that make the
+    /// interchange unreadable are errors; faults inside a group or set are left for validation
+    /// to report in a 997.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let delimiters = Delimiters::detect(raw)?;
+        let mut segments = raw
+            .split(delimiters.segment)
+            .map(|s| s.trim_matches(|c: char| c == '\r' || c == '\n'))
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| Segment::parse(s, &delimiters));
+
+        let isa = segments.next().ok_or_else(|| Error::validation("Missing ISA segment"))?;
+        let mut interchange = Self {
+            delimiters,
+            sender_qualifier: isa.element(5).trim().to_string(),
+            sender_id: isa.element(6).trim().to_string(),
+            receiver_qualifier: isa.element(7).trim().to_string(),
+            receiver_id: isa.element(8).trim().to_string(),
+            date: isa.element(9).to_string(),
+            time: isa.element(10).to_string(),
+            version: isa.element(12).to_string(),
+            control_number: control_number(isa.element(13), "ISA13")?,
+            test: isa.element(15) == "T",
+            groups: Vec::new(),
+        };
+
+        let mut group: Option<FunctionalGroup> = None;
+        let mut set: Option<TransactionSet> = None;
+        let mut trailer = None;
+        for segment in segments {
+            if trailer.is_some() {
+                return Err(Error::validation(format!("Segment {} follows IEA", segment.id)));
+            }
+            if let Some(open) = set.as_mut() {
+                if !matches!(segment.id.as_str(), "SE" | "ST" | "GE" | "GS" | "IEA" | "ISA") {
+                    open.segments.push(segment);
+                    continue;
+                }
+                let mut closed = set.take().unwrap();
+                let is_trailer = segment.id == "SE";
+                if is_trailer {
+                    closed.trailer = Some(segment.clone());
+                }
+                group.as_mut().unwrap().transactions.push(closed);
+                if is_trailer {
+                    continue;
+                }
+            }
+            match segment.id.as_str() {
+                "GS" => {
+                    if let Some(open) = group.take() {
+                        interchange.groups.push(open);
+                    }
+                    group = Some(FunctionalGroup {
+                        functional_id: segment.element(1).to_string(),
+                        sender_code: segment.element(2).to_string(),
+                        receiver_code: segment.element(3).to_string(),
+                        date: segment.element(4).to_string(),
+                        time: segment.element(5).to_string(),
+                        control_number: control_number(segment.element(6), "GS06")?,
+                        version: segment.element(8).to_string(),
+                        transactions: Vec::new(),
+                        trailer: None,
+                    });
+                }
+                "ST" => {
+                    if group.is_none() {
+                        return Err(Error::validation("ST segment outside a functional group"));
+                    }
+                    set = Some(TransactionSet {
+                        id: segment.element(1).to_string(),
+                        control_number: segment.element(2).to_string(),
+                        segments: Vec::new(),
+                        trailer: None,
+                    });
+                }
+                "GE" => {
+                    let mut closed = group.take().ok_or_else(|| Error::validation("GE segment without a GS"))?;
+                    closed.trailer = Some(segment);
+                    interchange.groups.push(closed);
+                }
+                "IEA" => {
+                    if let Some(open) = group.take() {
+                        interchange.groups.push(open);
+                    }
+                    trailer = Some(segment);
+                }
+                other => return Err(Error::validation(format!("Segment {} outside a transaction set", other))),
+            }
+        }
+
+        let trailer = trailer.ok_or_else(|| Error::validation("Interchange is missing its IEA trailer"))?;
+        if control_number(trailer.element(2), "IEA02")? != interchange.control_number {
+            return Err(Error::validation(format!(
+                "IEA02 {} does not match ISA13 {}",
+                trailer.element(2),
+                interchange.control_number
+            )));
+        }
+        if trailer.element(1).parse::<usize>().ok() != Some(interchange.groups.len()) {
+            return Err(Error::validation(format!(
+                "IEA01 declares {} groups but the interchange has {}",
+                trailer.element(1),
+                interchange.groups.len()
+            )));
+        }
+        Ok(interchange)
+    }
+
+    pub fn to_x12(&self) -> String {
+        let d = &self.delimiters;
+        let mut out = String::new();
+        let repetition = d.repetition.map_or("U".to_string(), String::from);
+        Segment::new("ISA", [
+            "00".to_string(),
+            " ".repeat(10),
+            "00".to_string(),
+            " ".repeat(10),
+            format!("{:<2.2}", self.sender_qualifier),
+            format!("{:<15.15}", self.sender_id),
+            format!("{:<2.2}", self.receiver_qualifier),
+            format!("{:<15.15}", self.receiver_id),
+            self.date.clone(),
+            self.time.clone(),
+            repetition,
+            self.version.clone(),
+            format!("{:09}", self.control_number),
+            "0".to_string(),
+            if self.test { "T" } else { "P" }.to_string(),
+            d.component.to_string(),
+        ])
+        .write(d, &mut out);
+        for group in &self.groups {
+            Segment::new("GS", [
+                group.functional_id.clone(),
+                group.sender_code.clone(),
+                group.receiver_code.clone(),
+                group.date.clone(),
+                group.time.clone(),
+                group.control_number.to_string(),
+                "X".to_string(),
+                group.version.clone(),
+            ])
+            .write(d, &mut out);
+            for set in &group.transactions {
+                set.write(d, &mut out);
+            }
+            Segment::new("GE", [group.transactions.len().to_string(), group.control_number.to_string()]).write(d, &mut out);
+        }
+        Segment::new("IEA", [self.groups.len().to_string(), format!("{:09}", self.control_number)]).write(d, &mut out);
+        out
+    }
+}
+
+fn control_number(value: &str, field: &str) -> Result<u32> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| Error::validation(format!("{} control number '{}' is not numeric", field, value)))
+}
@@ -0,0 +1,144 @@
+use erp_edi::schema::{validate_group, validate_transaction};
+use erp_edi::x12::{Delimiters, Interchange, Segment, TransactionSet};
+use erp_edi::mapping::{read_850, ship_notice_856};
+use erp_edi::{Edi856ASN, Edi856Item, Edi856Package, EdiAddress};
+use chrono::{NaiveDate, Utc};
+use uuid::Uuid;
+
+const ISA: &str = "ISA*00*          *00*          *ZZ*BUYER          *ZZ*SELLER         *261018*1200*U*00401*000000042*0*P*>~";
+
+fn interchange(body: &str) -> String {
+    format!("{}GS*PO*BUYER*SELLER*20261018*1200*7*X*004010~{}GE*1*7~IEA*1*000000042~", ISA, body)
+}
+
+const PURCHASE_ORDER: &str = "ST*850*0001~\
+    BEG*00*SA*PO-1001**20261018~\
+    N1*ST*Acme Receiving~\
+    N3*1 Dock Road~\
+    N4*Springfield*IL*62701*US~\
+    PO1*1*5*EA*12.50**VP*WIDGET-1~\
+    PID*F****Blue widget~\
+    DTM*002*20261101~\
+    PO1*2*2*CA*40**BP*GADGET-2~\
+    CTT*2~\
+    SE*11*0001~";
+
+#[test]
+fn parses_custom_delimiters_and_line_breaks() {
+    let raw = interchange(PURCHASE_ORDER).replace('*', "|").replace('>', ":").replace('~', "\n");
+    let delimiters = Delimiters::detect(&raw).unwrap();
+    assert_eq!((delimiters.element, delimiters.component, delimiters.segment), ('|', ':', '\n'));
+
+    let parsed = Interchange::parse(&raw).unwrap();
+    assert_eq!(parsed.sender_id, "BUYER");
+    assert_eq!(parsed.control_number, 42);
+    let group = &parsed.groups[0];
+    assert_eq!((group.functional_id.as_str(), group.control_number), ("PO", 7));
+    let set = &group.transactions[0];
+    assert_eq!(set.segments.len(), 9);
+    assert!(validate_group(group).transactions[0].accepted);
+
+    let order = read_850(set, Uuid::new_v4(), Uuid::new_v4()).unwrap();
+    assert_eq!(order.po_number, "PO-1001");
+    assert_eq!(order.ship_to.city, "Springfield");
+    assert_eq!(order.lines.len(), 2);
+    assert_eq!((order.lines[0].sku.as_str(), order.lines[0].unit_price), ("WIDGET-1", 1250));
+    assert_eq!(order.lines[0].description, "Blue widget");
+    assert_eq!(order.lines[0].requested_date.map(|d| d.to_string()).as_deref(), Some("2026-11-01"));
+    assert_eq!((order.lines[1].sku.as_str(), order.lines[1].quantity), ("GADGET-2", 2));
+}
+
+#[test]
+fn rejects_envelope_faults() {
+    let missing_trailer = interchange(PURCHASE_ORDER).replace("IEA*1*000000042~", "");
+    assert!(Interchange::parse(&missing_trailer).is_err());
+    let wrong_control = interchange(PURCHASE_ORDER).replace("IEA*1*000000042", "IEA*1*000000043");
+    assert!(Interchange::parse(&wrong_control).is_err());
+    assert!(Interchange::parse("ISA*00*short~").is_err());
+}
+
+#[test]
+fn validation_errors_become_a_rejecting_997() {
+    let bad = "ST*850*0002~\
+        BEG*00*ZZ*PO-1002**20261399~\
+        PO1*1**EA*abc**VP*WIDGET-1~\
+        XYZ*1~\
+        SE*4*0003~";
+    let raw = interchange(bad);
+    let parsed = Interchange::parse(&raw).unwrap();
+    let ack = validate_group(&parsed.groups[0]);
+    let set = &ack.transactions[0];
+    assert!(!set.accepted);
+    assert_eq!(set.error_codes, vec!["3", "4", "5"]);
+
+    let codes = |segment: &str, element: i32| {
+        set.segment_errors
+            .iter()
+            .filter(|e| e.segment_id == segment && e.element_position == Some(element))
+            .map(|e| e.error_code.as_str())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(codes("BEG", 2), vec!["7"]);
+    assert_eq!(codes("BEG", 5), vec!["8"]);
+    assert_eq!(codes("PO1", 2), vec!["1"]);
+    assert_eq!(codes("PO1", 4), vec!["6"]);
+    assert!(set.segment_errors.iter().any(|e| e.segment_id == "XYZ" && e.error_code == "2"));
+
+    let segments: Vec<String> = ack.to_997().iter().map(|s| format!("{}*{}", s.id, s.elements.join("*"))).collect();
+    assert_eq!(segments[0], "AK1*PO*7");
+    assert_eq!(segments[1], "AK2*850*0002");
+    assert!(segments.contains(&"AK3*BEG*2**8".to_string()));
+    assert!(segments.contains(&"AK4*2**7*ZZ".to_string()));
+    assert!(segments.contains(&"AK3*XYZ*4**2".to_string()));
+    assert_eq!(segments[segments.len() - 2], "AK5*R*3*4*5");
+    assert_eq!(segments[segments.len() - 1], "AK9*R*1*1*0");
+}
+
+#[test]
+fn unknown_transaction_sets_are_not_supported() {
+    let set = TransactionSet {
+        id: "999".to_string(),
+        control_number: "0001".to_string(),
+        segments: vec![],
+        trailer: Some(Segment::new("SE", ["2", "0001"])),
+    };
+    assert_eq!(validate_transaction(&set).error_codes, vec!["1"]);
+}
+
+#[test]
+fn written_interchanges_parse_back() {
+    let parsed = Interchange::parse(&interchange(PURCHASE_ORDER)).unwrap();
+    let written = parsed.to_x12();
+    assert!(written.starts_with(ISA));
+    let reparsed = Interchange::parse(&written).unwrap();
+    assert_eq!(reparsed.groups[0].transactions[0].segments, parsed.groups[0].transactions[0].segments);
+    assert!(validate_group(&reparsed.groups[0]).transactions[0].accepted);
+    assert_eq!(written, reparsed.to_x12());
+}
+
+#[test]
+fn ship_notices_pass_their_own_schema() {
+    let date = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+    let asn = Edi856ASN {
+        id: Uuid::new_v4(),
+        transaction_id: Uuid::new_v4(),
+        asn_number: "SHP-0001".to_string(),
+        shipment_date: date,
+        expected_date: date,
+        po_number: "PO-1001".to_string(),
+        carrier: "UPS".to_string(),
+        tracking_number: Some("1Z999".to_string()),
+        packages: vec![Edi856Package {
+            package_id: "SHP-0001".to_string(),
+            weight: 12.5,
+            items: vec![Edi856Item { sku: "WIDGET-1".to_string(), quantity: 5, lot_number: None }],
+        }],
+        total_items: 5,
+    };
+    let ship_to = EdiAddress { name: "Acme Receiving".to_string(), city: "Springfield".to_string(), ..Default::default() };
+    let mut set = TransactionSet::new("856", "0001".to_string(), ship_notice_856(&asn, &ship_to, Utc::now(), "LB"));
+    set.trailer = Some(Segment::new("SE", [(set.segments.len() + 2).to_string(), "0001".to_string()]));
+    let ack = validate_transaction(&set);
+    assert!(ack.accepted, "{}", ack.summary());
+    assert_eq!(set.segments.iter().filter(|s| s.id == "HL").count(), 4);
+}
@@ -1,12 +1,45 @@
 use chrono::{DateTime, Utc};
-use erp_core::Result;
+use erp_core::{Error, Result, RowScope};
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
+use std::collections::HashSet;
 use uuid::Uuid;
 
+const MAX_QUERY_TERMS: usize = 10;
+const MAX_CORRECTIONS: usize = 3;
+/// Wrap matches in `snippet()` and `highlight()` output. Control characters never occur in
+/// indexed text, so the markers survive HTML escaping and are swapped for `<mark>` afterwards.
+const MATCH_START: &str = "\u{2}";
+const MATCH_END: &str = "\u{3}";
+
+/// An entity type the database keeps in the search index itself. The `search_sources` view
+/// defines what is indexed, and triggers on `table` keep the index current.
+#[derive(Debug)]
+pub struct Searchable {
+    pub entity_type: &'static str,
+    pub table: &'static str,
+    /// Permission a caller needs to see the entity in results.
+    pub permission: &'static str,
+}
+
+pub const SEARCHABLE: [Searchable; 8] = [
+    Searchable { entity_type: "customer", table: "customers", permission: "sales:customers:read" },
+    Searchable { entity_type: "product", table: "products", permission: "inventory:products:read" },
+    Searchable { entity_type: "vendor", table: "vendors", permission: "purchasing:vendors:read" },
+    Searchable { entity_type: "sales_order", table: "sales_orders", permission: "sales:orders:read" },
+    Searchable { entity_type: "purchase_order", table: "purchase_orders", permission: "purchasing:orders:read" },
+    Searchable { entity_type: "employee", table: "employees", permission: "hr:employees:read" },
+    Searchable { entity_type: "document", table: "documents", permission: "documents:documents:read" },
+    Searchable { entity_type: "knowledge_article", table: "knowledge_articles", permission: "service:articles:read" },
+];
+
+pub fn searchable(entity_type: &str) -> Option<&'static Searchable> {
+    SEARCHABLE.iter().find(|s| s.entity_type == entity_type)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchIndexEntry {
-    pub id: Uuid,
+    pub id: String,
     pub entity_type: String,
     pub entity_id: String,
     pub title: String,
@@ -30,6 +63,9 @@ pub struct SearchResult {
     pub entity_type: String,
     pub entity_id: String,
     pub title: String,
+    /// HTML-escaped title with matches wrapped in `<mark>`.
+    pub highlighted_title: String,
+    /// HTML-escaped excerpt around the best match, with matches wrapped in `<mark>`.
     pub snippet: String,
     pub relevance_score: f64,
 }
@@ -39,6 +75,8 @@ pub struct SearchResponse {
     pub results: Vec<SearchResult>,
     pub total: i64,
     pub query: String,
+    /// The query as corrected for typos, when nothing matched it as typed.
+    pub corrected_query: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +89,72 @@ pub struct IndexRequest {
     pub tenant_id: Option<String>,
 }
 
+/// Which results a caller may see.
+#[derive(Debug, Clone, Default)]
+pub struct SearchAccess {
+    denied: Vec<String>,
+    scopes: Vec<(&'static Searchable, RowScope)>,
+    redacted: HashSet<String>,
+}
+
+impl SearchAccess {
+    pub fn unrestricted() -> Self {
+        Self::default()
+    }
+
+    /// Leaves an entity type out of results entirely.
+    pub fn deny(mut self, entity_type: &str) -> Self {
+        self.denied.push(entity_type.to_string());
+        self
+    }
+
+    /// Limits an indexed entity type to the rows of its table within `scope`.
+    pub fn scope(mut self, entity_type: &str, scope: RowScope) -> Self {
+        if let Some(searchable) = searchable(entity_type).filter(|_| !scope.is_unrestricted()) {
+            self.scopes.push((searchable, scope));
+        }
+        self
+    }
+
+    /// Shows only the title of an entity type, for callers who may not read all of its fields.
+    pub fn redact(mut self, entity_type: &str) -> Self {
+        self.redacted.insert(entity_type.to_string());
+        self
+    }
+
+    /// Conditions on `s` for the visible results of `expression`. Redacted types only match on
+    /// their title and keywords, since their content may hold fields the caller cannot read.
+    fn filter(&self, entity_types: Option<&[String]>, expression: &str) -> (String, Vec<String>) {
+        let mut sql = String::new();
+        let mut binds = Vec::new();
+        if !self.redacted.is_empty() {
+            sql.push_str(&format!(
+                " AND (s.entity_type NOT IN ({}) OR s.doc_id IN (SELECT rowid FROM search_index_fts WHERE search_index_fts MATCH ?))",
+                vec!["?"; self.redacted.len()].join(", ")
+            ));
+            binds.extend(self.redacted.iter().cloned());
+            binds.push(format!("{{title keywords}} : ({})", expression));
+        }
+        if !self.denied.is_empty() {
+            sql.push_str(&format!(" AND s.entity_type NOT IN ({})", vec!["?"; self.denied.len()].join(", ")));
+            binds.extend(self.denied.iter().cloned());
+        }
+        if let Some(types) = entity_types.filter(|t| !t.is_empty()) {
+            sql.push_str(&format!(" AND s.entity_type IN ({})", vec!["?"; types.len()].join(", ")));
+            binds.extend(types.iter().cloned());
+        }
+        for (searchable, scope) in &self.scopes {
+            sql.push_str(&format!(
+                " AND (s.entity_type <> ? OR s.entity_id IN (SELECT id FROM {} WHERE 1 = 1{}))",
+                searchable.table, scope.sql()
+            ));
+            binds.push(searchable.entity_type.to_string());
+            binds.extend(scope.binds().iter().cloned());
+        }
+        (sql, binds)
+    }
+}
+
 pub struct SearchService;
 
 impl Default for SearchService {
@@ -64,17 +168,22 @@ impl SearchService {
         Self
     }
 
+    /// Adds or replaces the index entry for an entity. Entities listed in [`SEARCHABLE`] are
+    /// indexed automatically, so this is for anything else.
     pub async fn index(&self, pool: &SqlitePool, req: IndexRequest) -> Result<SearchIndexEntry> {
-        let id = Uuid::new_v4();
         let now = Utc::now();
         let keywords = req.keywords.map(|k| k.join(", "));
 
-        sqlx::query(
-            r#"INSERT OR REPLACE INTO search_index 
+        let (id, created_at): (String, String) = sqlx::query_as(
+            r#"INSERT INTO search_index
                (id, entity_type, entity_id, title, content, keywords, tenant_id, created_at, updated_at)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"#
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+               ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+                   title = excluded.title, content = excluded.content, keywords = excluded.keywords,
+                   tenant_id = excluded.tenant_id, updated_at = excluded.updated_at
+               RETURNING id, created_at"#
         )
-        .bind(id.to_string())
+        .bind(Uuid::new_v4().to_string())
         .bind(&req.entity_type)
         .bind(&req.entity_id)
         .bind(&req.title)
@@ -83,7 +192,7 @@ impl SearchService {
         .bind(&req.tenant_id)
         .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
-        .execute(pool)
+        .fetch_one(pool)
         .await?;
 
         Ok(SearchIndexEntry {
@@ -94,7 +203,7 @@ impl SearchService {
             content: req.content,
             keywords,
             tenant_id: req.tenant_id,
-            created_at: now,
+            created_at: DateTime::parse_from_rfc3339(&created_at).map(|d| d.with_timezone(&Utc)).unwrap_or(now),
             updated_at: now,
         })
     }
@@ -108,115 +217,73 @@ impl SearchService {
         Ok(())
     }
 
-    pub async fn search(&self, pool: &SqlitePool, req: SearchRequest) -> Result<SearchResponse> {
-        let limit = req.limit.unwrap_or(50).min(100);
-        let offset = req.offset.unwrap_or(0);
-        let search_term = format!("%{}%", req.query.to_lowercase());
-
-        let results = if let Some(entity_types) = &req.entity_types {
-            let placeholders: Vec<String> = entity_types.iter().map(|_| "?".to_string()).collect();
-            let query = format!(
-                r#"SELECT entity_type, entity_id, title, content 
-                   FROM search_index 
-                   WHERE (LOWER(title) LIKE ? OR LOWER(content) LIKE ? OR LOWER(keywords) LIKE ?)
-                   AND entity_type IN ({})
-                   ORDER BY 
-                     CASE WHEN LOWER(title) LIKE ? THEN 1 ELSE 2 END,
-                     created_at DESC
-                   LIMIT ? OFFSET ?"#,
-                placeholders.join(",")
-            );
-
-            let mut sql_query = sqlx::query(&query);
-            sql_query = sql_query.bind(&search_term).bind(&search_term).bind(&search_term);
-            for et in entity_types {
-                sql_query = sql_query.bind(et);
+    /// Full-text search ranked by BM25, with title matches counting most, then keywords. Every
+    /// word matches as a prefix. When nothing matches, words are retried alongside the indexed
+    /// words closest to them in spelling.
+    pub async fn search(&self, pool: &SqlitePool, req: SearchRequest, access: &SearchAccess) -> Result<SearchResponse> {
+        let terms = terms(&req.query);
+        if terms.is_empty() {
+            return Err(Error::validation("Search query must contain at least one word"));
+        }
+        let limit = req.limit.unwrap_or(50).clamp(1, 100);
+        let offset = req.offset.unwrap_or(0).max(0);
+        let mut expression = match_expression(&terms.iter().map(|t| vec![t.clone()]).collect::<Vec<_>>());
+        let (mut filter, mut binds) = access.filter(req.entity_types.as_deref(), &expression);
+        let mut total = Self::count(pool, &expression, &filter, &binds).await?;
+        let mut corrected_query = None;
+
+        if total == 0 {
+            let mut groups = Vec::with_capacity(terms.len());
+            let mut corrected = Vec::with_capacity(terms.len());
+            for term in &terms {
+                let corrections = Self::corrections(pool, term).await?;
+                corrected.push(corrections.first().cloned().unwrap_or_else(|| term.clone()));
+                groups.push(std::iter::once(term.clone()).chain(corrections).collect::<Vec<_>>());
             }
-            sql_query = sql_query.bind(&search_term).bind(limit).bind(offset);
-
-            let rows: Vec<SearchRow> = sql_query
-                .map(|row: sqlx::sqlite::SqliteRow| {
-                    use sqlx::Row;
-                    SearchRow {
-                        entity_type: row.get("entity_type"),
-                        entity_id: row.get("entity_id"),
-                        title: row.get("title"),
-                        content: row.get("content"),
-                    }
-                })
-                .fetch_all(pool)
-                .await?;
-
-            rows
-        } else {
-            let query = r#"SELECT entity_type, entity_id, title, content 
-                          FROM search_index 
-                          WHERE (LOWER(title) LIKE ? OR LOWER(content) LIKE ? OR LOWER(keywords) LIKE ?)
-                          ORDER BY 
-                            CASE WHEN LOWER(title) LIKE ? THEN 1 ELSE 2 END,
-                            created_at DESC
-                          LIMIT ? OFFSET ?"#;
-
-            let rows: Vec<SearchRow> = sqlx::query(query)
-                .bind(&search_term)
-                .bind(&search_term)
-                .bind(&search_term)
-                .bind(&search_term)
-                .bind(limit)
-                .bind(offset)
-                .map(|row: sqlx::sqlite::SqliteRow| {
-                    use sqlx::Row;
-                    SearchRow {
-                        entity_type: row.get("entity_type"),
-                        entity_id: row.get("entity_id"),
-                        title: row.get("title"),
-                        content: row.get("content"),
-                    }
-                })
-                .fetch_all(pool)
-                .await?;
-
-            rows
-        };
-
-        let total: i64 = if let Some(entity_types) = &req.entity_types {
-            let placeholders: Vec<String> = entity_types.iter().map(|_| "?".to_string()).collect();
-            let count_query = format!(
-                r#"SELECT COUNT(*) as count FROM search_index 
-                   WHERE (LOWER(title) LIKE ? OR LOWER(content) LIKE ? OR LOWER(keywords) LIKE ?)
-                   AND entity_type IN ({})"#,
-                placeholders.join(",")
-            );
-            
-            let mut sql_query = sqlx::query_scalar::<_, i64>(&count_query);
-            sql_query = sql_query.bind(&search_term).bind(&search_term).bind(&search_term);
-            for et in entity_types {
-                sql_query = sql_query.bind(et);
+            if corrected != terms {
+                expression = match_expression(&groups);
+                (filter, binds) = access.filter(req.entity_types.as_deref(), &expression);
+                total = Self::count(pool, &expression, &filter, &binds).await?;
+                corrected_query = Some(corrected.join(" "));
             }
-            sql_query.fetch_one(pool).await?
-        } else {
-            sqlx::query_scalar::<_, i64>(
-                r#"SELECT COUNT(*) FROM search_index 
-                   WHERE (LOWER(title) LIKE ? OR LOWER(content) LIKE ? OR LOWER(keywords) LIKE ?)"#
-            )
-            .bind(&search_term)
-            .bind(&search_term)
-            .bind(&search_term)
-            .fetch_one(pool)
-            .await?
-        };
+        }
+
+        let sql = format!(
+            r#"SELECT s.entity_type, s.entity_id, s.title,
+                      highlight(search_index_fts, 0, ?, ?) AS highlighted_title,
+                      snippet(search_index_fts, -1, ?, ?, '...', 16) AS snippet,
+                      bm25(search_index_fts, 10.0, 1.0, 5.0) AS rank
+               FROM search_index_fts JOIN search_index s ON s.doc_id = search_index_fts.rowid
+               WHERE search_index_fts MATCH ?{}
+               ORDER BY rank
+               LIMIT ? OFFSET ?"#,
+            filter
+        );
+        let mut query = sqlx::query_as::<_, SearchRow>(&sql)
+            .bind(MATCH_START).bind(MATCH_END)
+            .bind(MATCH_START).bind(MATCH_END)
+            .bind(&expression);
+        for value in &binds {
+            query = query.bind(value);
+        }
+        let rows = query.bind(limit).bind(offset).fetch_all(pool).await?;
 
-        let results: Vec<SearchResult> = results
+        let results = rows
             .into_iter()
-            .enumerate()
-            .map(|(i, row)| {
-                let snippet = Self::create_snippet(&row.content, &req.query);
+            .map(|row| {
+                let highlighted_title = mark_matches(&row.highlighted_title);
+                let snippet = if access.redacted.contains(&row.entity_type) {
+                    highlighted_title.clone()
+                } else {
+                    mark_matches(&row.snippet)
+                };
                 SearchResult {
                     entity_type: row.entity_type,
                     entity_id: row.entity_id,
                     title: row.title,
+                    highlighted_title,
                     snippet,
-                    relevance_score: 1.0 / (1.0 + i as f64),
+                    relevance_score: -row.rank,
                 }
             })
             .collect();
@@ -225,44 +292,79 @@ impl SearchService {
             results,
             total,
             query: req.query,
+            corrected_query,
         })
     }
 
-    fn create_snippet(content: &Option<String>, query: &str) -> String {
-        let content = match content {
-            Some(c) if !c.is_empty() => c,
-            _ => return String::new(),
-        };
+    async fn count(pool: &SqlitePool, expression: &str, filter: &str, binds: &[String]) -> Result<i64> {
+        let sql = format!(
+            "SELECT COUNT(*) FROM search_index_fts JOIN search_index s ON s.doc_id = search_index_fts.rowid
+             WHERE search_index_fts MATCH ?{}",
+            filter
+        );
+        let mut query = sqlx::query_scalar::<_, i64>(&sql).bind(expression);
+        for value in binds {
+            query = query.bind(value);
+        }
+        Ok(query.fetch_one(pool).await?)
+    }
 
-        let lower_content = content.to_lowercase();
-        let lower_query = query.to_lowercase();
+    /// Indexed words within one edit of `term` (two for words of eight letters or more),
+    /// closest and most common first.
+    async fn corrections(pool: &SqlitePool, term: &str) -> Result<Vec<String>> {
+        let len = term.chars().count();
+        let max_edits = match len {
+            0..=3 => return Ok(Vec::new()),
+            4..=7 => 1,
+            _ => 2,
+        };
+        let candidates: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT term, doc FROM search_index_vocab WHERE length(term) BETWEEN ? AND ?"
+        )
+        .bind((len - max_edits) as i64)
+        .bind((len + max_edits) as i64)
+        .fetch_all(pool)
+        .await?;
 
-        if let Some(pos) = lower_content.find(&lower_query) {
-            let start = pos.saturating_sub(50);
-            let end = (pos + query.len() + 50).min(content.len());
-            let mut snippet = content[start..end].to_string();
-            if start > 0 {
-                snippet = format!("...{}", snippet);
-            }
-            if end < content.len() {
-                snippet = format!("{}...", snippet);
-            }
-            snippet
-        } else {
-            let end = 100.min(content.len());
-            let mut snippet = content[..end].to_string();
-            if end < content.len() {
-                snippet.push_str("...");
-            }
-            snippet
-        }
+        let mut scored: Vec<(usize, i64, String)> = candidates
+            .into_iter()
+            .filter_map(|(candidate, docs)| {
+                let distance = edit_distance(term, &candidate);
+                (distance > 0 && distance <= max_edits).then_some((distance, -docs, candidate))
+            })
+            .collect();
+        scored.sort();
+        Ok(scored.into_iter().take(MAX_CORRECTIONS).map(|(_, _, term)| term).collect())
     }
 
+    /// Reindexes every entity in [`SEARCHABLE`] from its table and rebuilds the full-text index.
+    /// Entries added through [`SearchService::index`] for other entity types are kept. Returns
+    /// the number of entities indexed.
     pub async fn rebuild_index(&self, pool: &SqlitePool) -> Result<u64> {
-        let result = sqlx::query("DELETE FROM search_index")
-            .execute(pool)
+        let placeholders = vec!["?"; SEARCHABLE.len()].join(", ");
+        let mut tx = pool.begin().await?;
+
+        let sql = format!("DELETE FROM search_index WHERE entity_type IN ({})", placeholders);
+        let mut delete = sqlx::query(&sql);
+        for searchable in &SEARCHABLE {
+            delete = delete.bind(searchable.entity_type);
+        }
+        delete.execute(&mut *tx).await?;
+
+        let indexed = sqlx::query(
+            r#"INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+               SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+               FROM search_sources"#
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query("INSERT INTO search_index_fts (search_index_fts) VALUES ('rebuild')")
+            .execute(&mut *tx)
             .await?;
-        Ok(result.rows_affected())
+        tx.commit().await?;
+        Ok(indexed)
     }
 
     pub async fn get_stats(&self, pool: &SqlitePool) -> Result<SearchStats> {
@@ -283,12 +385,83 @@ impl SearchService {
     }
 }
 
+/// The lowercase words of a query, split the way the index tokenizer splits text.
+fn terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for word in query.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        let word = word.to_lowercase();
+        if !terms.contains(&word) {
+            terms.push(word);
+        }
+    }
+    terms.truncate(MAX_QUERY_TERMS);
+    terms
+}
+
+/// An FTS5 query requiring every group, where a group matches its first word as a prefix or
+/// any of its corrections exactly.
+fn match_expression(groups: &[Vec<String>]) -> String {
+    groups
+        .iter()
+        .map(|group| {
+            let alternatives: Vec<String> = group
+                .iter()
+                .enumerate()
+                .map(|(i, word)| format!("\"{}\"{}", word.replace('"', "\"\""), if i == 0 { "*" } else { "" }))
+                .collect();
+            if alternatives.len() == 1 {
+                alternatives.into_iter().next().unwrap_or_default()
+            } else {
+                format!("({})", alternatives.join(" OR "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+/// Escapes `text` for HTML and turns the match markers into `<mark>` tags.
+fn mark_matches(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace(MATCH_START, "<mark>")
+        .replace(MATCH_END, "</mark>")
+}
+
+/// Edits (insertions, deletions, substitutions and swaps of neighbouring letters) between two
+/// words.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
 #[derive(Debug, sqlx::FromRow)]
 struct SearchRow {
     entity_type: String,
     entity_id: String,
     title: String,
-    content: Option<String>,
+    highlighted_title: String,
+    snippet: String,
+    rank: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
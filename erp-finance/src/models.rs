@@ -44,6 +44,99 @@ pub struct JournalLine {
     pub description: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
+pub enum PostingEvent {
+    GoodsIssue,
+    GoodsReceipt,
+    VendorBill,
+    CustomerInvoice,
+    CustomerPayment,
+    VendorPayment,
+    ProductionCompletion,
+    InventoryAdjustment,
+    InventoryRevaluation,
+    PurchasePriceVariance,
+}
+
+impl PostingEvent {
+    pub fn tax_side(&self) -> Option<PostingSide> {
+        match self {
+            PostingEvent::CustomerInvoice => Some(PostingSide::Credit),
+            PostingEvent::VendorBill => Some(PostingSide::Debit),
+            _ => None,
+        }
+    }
+
+    /// Events whose amount may be negative: the rule's accounts are swapped to post a decrease.
+    pub fn is_signed(&self) -> bool {
+        matches!(self, PostingEvent::InventoryAdjustment | PostingEvent::InventoryRevaluation | PostingEvent::PurchasePriceVariance)
+    }
+}
+
+impl std::str::FromStr for PostingEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "GoodsIssue" => Ok(PostingEvent::GoodsIssue),
+            "GoodsReceipt" => Ok(PostingEvent::GoodsReceipt),
+            "VendorBill" => Ok(PostingEvent::VendorBill),
+            "CustomerInvoice" => Ok(PostingEvent::CustomerInvoice),
+            "CustomerPayment" => Ok(PostingEvent::CustomerPayment),
+            "VendorPayment" => Ok(PostingEvent::VendorPayment),
+            "ProductionCompletion" => Ok(PostingEvent::ProductionCompletion),
+            "InventoryAdjustment" => Ok(PostingEvent::InventoryAdjustment),
+            "InventoryRevaluation" => Ok(PostingEvent::InventoryRevaluation),
+            "PurchasePriceVariance" => Ok(PostingEvent::PurchasePriceVariance),
+            _ => Err(format!("Unknown posting event: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PostingSide {
+    Debit,
+    Credit,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostingRule {
+    pub id: Uuid,
+    pub event: PostingEvent,
+    pub debit_account_id: Uuid,
+    pub credit_account_id: Uuid,
+    pub tax_account_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostingDocument {
+    pub event: PostingEvent,
+    pub source_type: String,
+    pub source_id: Uuid,
+    pub source_number: String,
+    pub date: DateTime<Utc>,
+    pub amount: i64,
+    pub tax_amount: i64,
+    pub currency: erp_core::Currency,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntrySource {
+    pub id: Uuid,
+    pub journal_entry_id: Uuid,
+    pub event: PostingEvent,
+    pub source_type: String,
+    pub source_id: Uuid,
+    pub source_number: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FiscalYear {
     pub base: BaseEntity,
@@ -765,6 +858,51 @@ pub enum PeriodLockType {
     HardClose,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum JournalReversalStatus {
+    Scheduled,
+    Completed,
+}
+
+impl std::str::FromStr for JournalReversalStatus {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "Scheduled" => Ok(JournalReversalStatus::Scheduled),
+            "Completed" => Ok(JournalReversalStatus::Completed),
+            _ => Err(format!("Unknown reversal status: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntryReversal {
+    pub id: Uuid,
+    pub original_entry_id: Uuid,
+    pub reversal_entry_id: Option<Uuid>,
+    pub reversal_date: DateTime<Utc>,
+    pub reason: Option<String>,
+    pub status: JournalReversalStatus,
+    pub created_at: DateTime<Utc>,
+    pub created_by: Option<Uuid>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Why the last attempt to post a scheduled reversal failed.
+    pub last_error: Option<String>,
+}
+
+/// The outcome of processing the scheduled reversals that are due.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduledReversalRun {
+    pub reversed: Vec<JournalEntry>,
+    pub failed: Vec<ReversalFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReversalFailure {
+    pub original_entry_id: Uuid,
+    pub error: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PeriodCloseChecklist {
     pub id: Uuid,
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use chrono::Utc;
 use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status};
@@ -211,6 +211,7 @@ struct JournalLineRow {
     debit: i64,
     credit: i64,
     description: Option<String>,
+    currency: String,
 }
 
 pub struct SqliteJournalEntryRepository;
@@ -300,14 +301,15 @@ impl JournalEntryRepository for SqliteJournalEntryRepository {
         .await?;
         
         for line in &entry.lines {
-            sqlx::query("INSERT INTO journal_lines (id, journal_entry_id, account_id, debit, credit, description)
-                 VALUES (?, ?, ?, ?, ?, ?)")
+            sqlx::query("INSERT INTO journal_lines (id, journal_entry_id, account_id, debit, credit, description, currency)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)")
             .bind(line.id.to_string())
             .bind(entry.base.id.to_string())
             .bind(line.account_id.to_string())
             .bind(line.debit.amount)
             .bind(line.credit.amount)
             .bind(&line.description)
+            .bind(line.debit.currency.to_string())
             .execute(&mut *tx)
             .await?;
         }
@@ -341,14 +343,15 @@ impl JournalEntryRepository for SqliteJournalEntryRepository {
             .await?;
         
         for line in &entry.lines {
-            sqlx::query("INSERT INTO journal_lines (id, journal_entry_id, account_id, debit, credit, description)
-                 VALUES (?, ?, ?, ?, ?, ?)")
+            sqlx::query("INSERT INTO journal_lines (id, journal_entry_id, account_id, debit, credit, description, currency)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)")
             .bind(line.id.to_string())
             .bind(entry.base.id.to_string())
             .bind(line.account_id.to_string())
             .bind(line.debit.amount)
             .bind(line.credit.amount)
             .bind(&line.description)
+            .bind(line.debit.currency.to_string())
             .execute(&mut *tx)
             .await?;
         }
@@ -366,11 +369,15 @@ impl JournalEntryRepository for SqliteJournalEntryRepository {
         if total_debits != total_credits {
             return Err(Error::business_rule("Journal entry must balance (debits must equal credits)"));
         }
-        
+
+        let mut conn = pool.acquire().await?;
+        crate::service::PeriodManagementService::ensure_period_open_in(&mut conn, entry.date).await?;
+        self.ensure_accounts_active(&mut conn, &entry.lines).await?;
+
         let rows = sqlx::query("UPDATE journal_entries SET status = 'Posted', updated_at = ? WHERE id = ? AND status = 'Draft'")
         .bind(Utc::now().to_rfc3339())
         .bind(id.to_string())
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
         
         if rows.rows_affected() == 0 {
@@ -382,11 +389,111 @@ impl JournalEntryRepository for SqliteJournalEntryRepository {
 }
 
 impl SqliteJournalEntryRepository {
+    /// Loads an entry on the caller's connection, so it sees the caller's uncommitted writes.
+    pub async fn find_by_id_in(&self, conn: &mut SqliteConnection, id: Uuid) -> Result<JournalEntry> {
+        use erp_core::{Money, Currency};
+
+        let row = sqlx::query_as::<_, JournalEntryRow>(
+            "SELECT id, entry_number, date, description, reference, status,
+                    created_at, updated_at, created_by, updated_by
+             FROM journal_entries WHERE id = ?"
+        )
+        .bind(id.to_string())
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| Error::not_found("JournalEntry", &id.to_string()))?;
+
+        let lines = sqlx::query_as::<_, JournalLineRow>(
+            "SELECT id, journal_entry_id, account_id, debit, credit, description, currency
+             FROM journal_lines WHERE journal_entry_id = ?"
+        )
+        .bind(id.to_string())
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|r| {
+            let currency: Currency = r.currency.parse().map_err(Error::validation)?;
+            Ok(JournalLine {
+                id: Uuid::parse_str(&r.id).map_err(|_| Error::validation("Invalid journal line ID format"))?,
+                account_id: Uuid::parse_str(&r.account_id).map_err(|_| Error::validation("Invalid account ID format in journal line"))?,
+                debit: Money::new(r.debit, currency.clone()),
+                credit: Money::new(r.credit, currency),
+                description: r.description,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+        self.row_to_entry(row, lines)
+    }
+
+    /// Inserts an entry straight into Posted on the caller's connection, after the checks
+    /// [`JournalEntryRepository::post`] makes, so it commits or rolls back with the caller.
+    pub async fn create_posted_in(&self, conn: &mut SqliteConnection, entry: &JournalEntry) -> Result<()> {
+        let total_debits: i64 = entry.lines.iter().map(|l| l.debit.amount).sum();
+        let total_credits: i64 = entry.lines.iter().map(|l| l.credit.amount).sum();
+        if entry.lines.is_empty() || total_debits != total_credits {
+            return Err(Error::business_rule("Journal entry must balance (debits must equal credits)"));
+        }
+        crate::service::PeriodManagementService::ensure_period_open_in(conn, entry.date).await?;
+        self.ensure_accounts_active(conn, &entry.lines).await?;
+
+        let now = Utc::now();
+        sqlx::query("INSERT INTO journal_entries (id, entry_number, date, description, reference, status,
+             created_at, updated_at, created_by, updated_by)
+             VALUES (?, ?, ?, ?, ?, 'Posted', ?, ?, ?, ?)")
+        .bind(entry.base.id.to_string())
+        .bind(&entry.entry_number)
+        .bind(entry.date.to_rfc3339())
+        .bind(&entry.description)
+        .bind(&entry.reference)
+        .bind(entry.base.created_at.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .bind(entry.base.created_by.map(|id| id.to_string()))
+        .bind(entry.base.updated_by.map(|id| id.to_string()))
+        .execute(&mut *conn)
+        .await?;
+
+        for line in &entry.lines {
+            sqlx::query("INSERT INTO journal_lines (id, journal_entry_id, account_id, debit, credit, description, currency)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)")
+            .bind(line.id.to_string())
+            .bind(entry.base.id.to_string())
+            .bind(line.account_id.to_string())
+            .bind(line.debit.amount)
+            .bind(line.credit.amount)
+            .bind(&line.description)
+            .bind(line.debit.currency.to_string())
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn ensure_accounts_active(&self, conn: &mut SqliteConnection, lines: &[JournalLine]) -> Result<()> {
+        let mut account_ids: Vec<Uuid> = lines.iter().map(|l| l.account_id).collect();
+        account_ids.sort();
+        account_ids.dedup();
+
+        for account_id in account_ids {
+            let row: Option<(String, String)> = sqlx::query_as("SELECT code, status FROM accounts WHERE id = ?")
+                .bind(account_id.to_string())
+                .fetch_optional(&mut *conn)
+                .await?;
+            match row {
+                None => return Err(Error::not_found("Account", &account_id.to_string())),
+                Some((code, status)) if status != "Active" => {
+                    return Err(Error::business_rule(format!("Cannot post to inactive account {}", code)));
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+
     async fn load_lines(&self, pool: &SqlitePool, entry_id: Uuid) -> Result<Vec<JournalLine>> {
         use erp_core::{Money, Currency};
         
         let rows = sqlx::query_as::<_, JournalLineRow>(
-            "SELECT id, journal_entry_id, account_id, debit, credit, description
+            "SELECT id, journal_entry_id, account_id, debit, credit, description, currency
              FROM journal_lines WHERE journal_entry_id = ?"
         )
         .bind(entry_id.to_string())
@@ -398,11 +505,12 @@ impl SqliteJournalEntryRepository {
                 .map_err(|_| Error::validation("Invalid journal line ID format"))?;
             let account_id = Uuid::parse_str(&r.account_id)
                 .map_err(|_| Error::validation("Invalid account ID format in journal line"))?;
+            let currency: Currency = r.currency.parse().map_err(Error::validation)?;
             Ok(JournalLine {
                 id,
                 account_id,
-                debit: Money::new(r.debit, Currency::USD),
-                credit: Money::new(r.credit, Currency::USD),
+                debit: Money::new(r.debit, currency.clone()),
+                credit: Money::new(r.credit, currency),
                 description: r.description,
             })
         }).collect()
@@ -418,7 +526,7 @@ impl SqliteJournalEntryRepository {
         
         let placeholders = entry_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
         let query = format!(
-            "SELECT id, journal_entry_id, account_id, debit, credit, description
+            "SELECT id, journal_entry_id, account_id, debit, credit, description, currency
              FROM journal_lines WHERE journal_entry_id IN ({})",
             placeholders
         );
@@ -440,11 +548,15 @@ impl SqliteJournalEntryRepository {
                 Ok(id) => id,
                 Err(_) => continue,
             };
+            let currency: Currency = match r.currency.parse() {
+                Ok(currency) => currency,
+                Err(_) => continue,
+            };
             let line = JournalLine {
                 id,
                 account_id,
-                debit: Money::new(r.debit, Currency::USD),
-                credit: Money::new(r.credit, Currency::USD),
+                debit: Money::new(r.debit, currency.clone()),
+                credit: Money::new(r.credit, currency),
                 description: r.description,
             };
             result.entry(r.journal_entry_id).or_default().push(line);
@@ -479,7 +591,7 @@ impl SqliteJournalEntryRepository {
             reference: row.reference,
             lines,
             status: match row.status.as_str() {
-                "Posted" => Status::Approved,
+                "Posted" => Status::Posted,
                 _ => Status::Draft,
             },
         })
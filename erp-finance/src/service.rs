@@ -1,4 +1,4 @@
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status};
@@ -136,8 +136,277 @@ impl JournalEntryService {
         Ok(())
     }
 
+    /// Posts the reversing entry and links it to the original in one transaction.
+    pub async fn reverse_entry(
+        &self,
+        pool: &SqlitePool,
+        id: Uuid,
+        reversal_date: Option<DateTime<Utc>>,
+        reason: Option<String>,
+        created_by: Option<Uuid>,
+    ) -> Result<JournalEntry> {
+        let mut tx = pool.begin().await?;
+        let original = self.repo.find_by_id_in(&mut tx, id).await?;
+        Self::ensure_reversible(&mut tx, &original).await?;
+        
+        let scheduled = match Self::find_reversal(&mut tx, id).await? {
+            Some(r) if r.status == JournalReversalStatus::Completed => {
+                return Err(Error::business_rule(format!("Journal entry {} has already been reversed", original.entry_number)));
+            }
+            other => other,
+        };
+        
+        let date = reversal_date.unwrap_or_else(Utc::now);
+        if date < original.date {
+            return Err(Error::validation("Reversal date cannot be before the original entry date"));
+        }
+        
+        let reversal = self.reversal_entry(&original, date, reason.as_deref(), created_by);
+        self.repo.create_posted_in(&mut tx, &reversal).await?;
+        let now = Utc::now();
+        
+        match scheduled {
+            Some(r) => {
+                let updated = sqlx::query(
+                    "UPDATE journal_entry_reversals SET reversal_entry_id = ?, reversal_date = ?, reason = COALESCE(?, reason),
+                     status = 'Completed', completed_at = ?, last_error = NULL WHERE id = ? AND status = 'Scheduled'"
+                )
+                .bind(reversal.base.id.to_string())
+                .bind(date.to_rfc3339())
+                .bind(&reason)
+                .bind(now.to_rfc3339())
+                .bind(r.id.to_string())
+                .execute(&mut *tx)
+                .await
+                .map_err(Error::Database)?;
+                if updated.rows_affected() == 0 {
+                    return Err(Error::business_rule(format!("Journal entry {} has already been reversed", original.entry_number)));
+                }
+            }
+            None => {
+                sqlx::query(
+                    "INSERT INTO journal_entry_reversals (id, original_entry_id, reversal_entry_id, reversal_date, reason, status, created_at, created_by, completed_at)
+                     VALUES (?, ?, ?, ?, ?, 'Completed', ?, ?, ?)"
+                )
+                .bind(Uuid::new_v4().to_string())
+                .bind(id.to_string())
+                .bind(reversal.base.id.to_string())
+                .bind(date.to_rfc3339())
+                .bind(&reason)
+                .bind(now.to_rfc3339())
+                .bind(created_by.map(|u| u.to_string()))
+                .bind(now.to_rfc3339())
+                .execute(&mut *tx)
+                .await
+                .map_err(Error::Database)?;
+            }
+        }
+        tx.commit().await?;
+        
+        Ok(reversal)
+    }
+
+    pub async fn schedule_reversal(
+        &self,
+        pool: &SqlitePool,
+        id: Uuid,
+        reason: Option<String>,
+        created_by: Option<Uuid>,
+    ) -> Result<JournalEntryReversal> {
+        let original = self.repo.find_by_id(pool, id).await?;
+        let mut conn = pool.acquire().await?;
+        Self::ensure_reversible(&mut conn, &original).await?;
+        if Self::find_reversal(&mut conn, id).await?.is_some() {
+            return Err(Error::business_rule(format!("Journal entry {} already has a reversal", original.entry_number)));
+        }
+        
+        drop(conn);
+        let now = Utc::now();
+        let reversal = JournalEntryReversal {
+            id: Uuid::new_v4(),
+            original_entry_id: id,
+            reversal_entry_id: None,
+            reversal_date: PeriodManagementService::next_period_start(pool, original.date).await?,
+            reason,
+            status: JournalReversalStatus::Scheduled,
+            created_at: now,
+            created_by,
+            completed_at: None,
+            last_error: None,
+        };
+        
+        sqlx::query(
+            "INSERT INTO journal_entry_reversals (id, original_entry_id, reversal_entry_id, reversal_date, reason, status, created_at, created_by, completed_at)
+             VALUES (?, ?, NULL, ?, ?, 'Scheduled', ?, ?, NULL)"
+        )
+        .bind(reversal.id.to_string())
+        .bind(id.to_string())
+        .bind(reversal.reversal_date.to_rfc3339())
+        .bind(&reversal.reason)
+        .bind(now.to_rfc3339())
+        .bind(created_by.map(|u| u.to_string()))
+        .execute(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        Ok(reversal)
+    }
+
+    /// Reverses every scheduled entry that is due. An entry that cannot be reversed stays scheduled
+    /// with its error recorded, and the run carries on with the rest.
+    pub async fn process_scheduled_reversals(&self, pool: &SqlitePool, as_of: DateTime<Utc>) -> Result<ScheduledReversalRun> {
+        let rows = sqlx::query_as::<_, JournalEntryReversalRow>(
+            "SELECT id, original_entry_id, reversal_entry_id, reversal_date, reason, status, created_at, created_by, completed_at, last_error
+             FROM journal_entry_reversals WHERE status = 'Scheduled' AND date(reversal_date) <= date(?)
+             ORDER BY reversal_date"
+        )
+        .bind(as_of.to_rfc3339())
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        let mut run = ScheduledReversalRun::default();
+        for row in rows {
+            let scheduled = JournalEntryReversal::try_from(row)?;
+            match self.reverse_entry(
+                pool,
+                scheduled.original_entry_id,
+                Some(scheduled.reversal_date),
+                None,
+                scheduled.created_by,
+            ).await {
+                Ok(entry) => run.reversed.push(entry),
+                Err(e) => {
+                    sqlx::query("UPDATE journal_entry_reversals SET last_error = ? WHERE id = ? AND status = 'Scheduled'")
+                        .bind(e.to_string())
+                        .bind(scheduled.id.to_string())
+                        .execute(pool)
+                        .await
+                        .map_err(Error::Database)?;
+                    run.failed.push(ReversalFailure { original_entry_id: scheduled.original_entry_id, error: e.to_string() });
+                }
+            }
+        }
+        
+        Ok(run)
+    }
+
+    pub async fn get_reversal(&self, pool: &SqlitePool, entry_id: Uuid) -> Result<Option<JournalEntryReversal>> {
+        let row = sqlx::query_as::<_, JournalEntryReversalRow>(
+            "SELECT id, original_entry_id, reversal_entry_id, reversal_date, reason, status, created_at, created_by, completed_at, last_error
+             FROM journal_entry_reversals WHERE original_entry_id = ? OR reversal_entry_id = ?"
+        )
+        .bind(entry_id.to_string())
+        .bind(entry_id.to_string())
+        .fetch_optional(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        row.map(JournalEntryReversal::try_from).transpose()
+    }
+
+    async fn find_reversal(conn: &mut SqliteConnection, original_entry_id: Uuid) -> Result<Option<JournalEntryReversal>> {
+        let row = sqlx::query_as::<_, JournalEntryReversalRow>(
+            "SELECT id, original_entry_id, reversal_entry_id, reversal_date, reason, status, created_at, created_by, completed_at, last_error
+             FROM journal_entry_reversals WHERE original_entry_id = ?"
+        )
+        .bind(original_entry_id.to_string())
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(Error::Database)?;
+        
+        row.map(JournalEntryReversal::try_from).transpose()
+    }
+
+    async fn ensure_reversible(conn: &mut SqliteConnection, entry: &JournalEntry) -> Result<()> {
+        if entry.status != Status::Posted {
+            return Err(Error::business_rule("Only posted journal entries can be reversed"));
+        }
+        
+        let is_reversal: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM journal_entry_reversals WHERE reversal_entry_id = ?"
+        )
+        .bind(entry.base.id.to_string())
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(Error::Database)?;
+        
+        if is_reversal.is_some() {
+            return Err(Error::business_rule("A reversal entry cannot itself be reversed"));
+        }
+        Ok(())
+    }
+
+    fn reversal_entry(
+        &self,
+        original: &JournalEntry,
+        date: DateTime<Utc>,
+        reason: Option<&str>,
+        created_by: Option<Uuid>,
+    ) -> JournalEntry {
+        let mut base = BaseEntity::new();
+        base.created_by = created_by;
+        JournalEntry {
+            base,
+            entry_number: self.generate_entry_number(),
+            date,
+            description: match reason {
+                Some(reason) => format!("Reversal of {}: {}", original.entry_number, reason),
+                None => format!("Reversal of {}", original.entry_number),
+            },
+            reference: Some(original.entry_number.clone()),
+            lines: original.lines.iter().map(|l| JournalLine {
+                id: Uuid::new_v4(),
+                account_id: l.account_id,
+                debit: l.credit.clone(),
+                credit: l.debit.clone(),
+                description: l.description.clone(),
+            }).collect(),
+            status: Status::Posted,
+        }
+    }
+
     fn generate_entry_number(&self) -> String {
-        format!("JE-{}", chrono::Local::now().format("%Y%m%d%H%M%S"))
+        format!("JE-{}-{}", chrono::Local::now().format("%Y%m%d%H%M%S"), &Uuid::new_v4().to_string()[0..8])
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct JournalEntryReversalRow {
+    id: String,
+    original_entry_id: String,
+    reversal_entry_id: Option<String>,
+    reversal_date: String,
+    reason: Option<String>,
+    status: String,
+    created_at: String,
+    created_by: Option<String>,
+    completed_at: Option<String>,
+    last_error: Option<String>,
+}
+
+impl TryFrom<JournalEntryReversalRow> for JournalEntryReversal {
+    type Error = Error;
+    
+    fn try_from(r: JournalEntryReversalRow) -> Result<Self> {
+        let parse_date = |s: &str| chrono::DateTime::parse_from_rfc3339(s)
+            .map(|d| d.with_timezone(&Utc))
+            .map_err(|_| Error::validation("Invalid date in journal entry reversal"));
+        let parse_id = |s: &str| Uuid::parse_str(s)
+            .map_err(|_| Error::validation("Invalid ID in journal entry reversal"));
+        
+        Ok(Self {
+            id: parse_id(&r.id)?,
+            original_entry_id: parse_id(&r.original_entry_id)?,
+            reversal_entry_id: r.reversal_entry_id.as_deref().map(parse_id).transpose()?,
+            reversal_date: parse_date(&r.reversal_date)?,
+            reason: r.reason,
+            status: r.status.parse().map_err(Error::validation)?,
+            created_at: parse_date(&r.created_at)?,
+            created_by: r.created_by.as_deref().map(parse_id).transpose()?,
+            completed_at: r.completed_at.as_deref().map(parse_date).transpose()?,
+            last_error: r.last_error,
+        })
     }
 }
 
@@ -724,11 +993,11 @@ impl FinancialReportingService {
         
         let rows = sqlx::query_as::<_, AccountBalanceRow>(
             "SELECT a.id, a.code, a.name, a.account_type,
-                    COALESCE(SUM(jl.debit), 0) as total_debit,
-                    COALESCE(SUM(jl.credit), 0) as total_credit
+                    COALESCE(SUM(CASE WHEN je.status = 'Posted' THEN jl.debit ELSE 0 END), 0) as total_debit,
+                    COALESCE(SUM(CASE WHEN je.status = 'Posted' THEN jl.credit ELSE 0 END), 0) as total_credit
              FROM accounts a
              LEFT JOIN journal_lines jl ON a.id = jl.account_id
-             LEFT JOIN journal_entries je ON jl.journal_entry_id = je.id AND je.status = 'Completed'
+             LEFT JOIN journal_entries je ON jl.journal_entry_id = je.id
              WHERE a.status != 'Deleted'
              GROUP BY a.id, a.code, a.name, a.account_type"
         )
@@ -759,7 +1028,7 @@ impl FinancialReportingService {
             "SELECT COALESCE(SUM(debit), 0), COALESCE(SUM(credit), 0)
              FROM journal_lines jl
              JOIN journal_entries je ON jl.journal_entry_id = je.id
-             WHERE jl.account_id = ? AND je.status = 'Completed'"
+             WHERE jl.account_id = ? AND je.status = 'Posted'"
         )
         .bind(account_id.to_string())
         .fetch_one(pool)
@@ -945,6 +1214,20 @@ impl BankReconciliationService {
         opening_balance: i64,
         closing_balance: i64,
         transactions: Vec<BankTransactionImport>,
+    ) -> Result<BankStatement> {
+        let mut tx = pool.begin().await?;
+        let statement = Self::import_statement_in(&mut tx, bank_account_id, statement_date, opening_balance, closing_balance, transactions).await?;
+        tx.commit().await?;
+        Ok(statement)
+    }
+
+    pub async fn import_statement_in(
+        conn: &mut SqliteConnection,
+        bank_account_id: Uuid,
+        statement_date: DateTime<Utc>,
+        opening_balance: i64,
+        closing_balance: i64,
+        transactions: Vec<BankTransactionImport>,
     ) -> Result<BankStatement> {
         let now = chrono::Utc::now();
         let statement_id = Uuid::new_v4();
@@ -970,7 +1253,7 @@ impl BankReconciliationService {
         .bind(statement.opening_balance)
         .bind(statement.closing_balance)
         .bind(statement.created_at.to_rfc3339())
-        .execute(pool)
+        .execute(&mut *conn)
         .await
         .map_err(Error::Database)?;
         
@@ -991,7 +1274,7 @@ impl BankReconciliationService {
             .bind(tx.credit)
             .bind(tx.balance)
             .bind(now.to_rfc3339())
-            .execute(pool)
+            .execute(&mut *conn)
             .await
             .map_err(Error::Database)?;
         }
@@ -1075,7 +1358,7 @@ impl BankReconciliationService {
              JOIN journal_entries je ON jl.journal_entry_id = je.id
              WHERE jl.account_id = (SELECT account_id FROM bank_accounts WHERE id = ?)
              AND (jl.debit = ? OR jl.credit = ?)
-             AND je.status = 'Completed'
+             AND je.status = 'Posted'
              LIMIT 1"
         )
         .bind(tx.bank_account_id.clone())
@@ -2261,7 +2544,7 @@ impl ConsolidationService {
                 "SELECT a.id, a.code, COALESCE(SUM(jl.debit - jl.credit), 0) as balance
                  FROM accounts a
                  LEFT JOIN journal_lines jl ON jl.account_id = a.id
-                 LEFT JOIN journal_entries je ON jl.journal_entry_id = je.id AND je.status = 'Completed'
+                 LEFT JOIN journal_entries je ON jl.journal_entry_id = je.id AND je.status = 'Posted'
                  WHERE a.code LIKE ?
                  GROUP BY a.id"
             )
@@ -2342,7 +2625,7 @@ impl ConsolidationService {
                 "SELECT a.account_type, COALESCE(SUM(jl.debit - jl.credit), 0)
                  FROM accounts a
                  LEFT JOIN journal_lines jl ON jl.account_id = a.id
-                 LEFT JOIN journal_entries je ON jl.journal_entry_id = je.id AND je.status = 'Completed'
+                 LEFT JOIN journal_entries je ON jl.journal_entry_id = je.id AND je.status = 'Posted'
                  WHERE a.company_id IS NULL OR a.company_id = ?
                  GROUP BY a.account_type"
             )
@@ -2564,8 +2847,16 @@ impl DunningService {
         Ok(run)
     }
 
+    /// Generates a letter for every overdue customer invoice with an open balance, at the level
+    /// the run's policy sets for how late it is.
     pub async fn execute_run(pool: &SqlitePool, run_id: Uuid) -> Result<Vec<DunningLetter>> {
         let now = chrono::Utc::now();
+        let (policy_id,): (String,) = sqlx::query_as("SELECT policy_id FROM dunning_runs WHERE id = ?")
+            .bind(run_id.to_string())
+            .fetch_optional(pool)
+            .await
+            .map_err(Error::Database)?
+            .ok_or_else(|| Error::not_found("DunningRun", &run_id.to_string()))?;
         
         sqlx::query(
             "UPDATE dunning_runs SET status = 'Running' WHERE id = ?"
@@ -2576,10 +2867,10 @@ impl DunningService {
         .map_err(Error::Database)?;
         
         let overdue_invoices: Vec<OverdueInvoiceRow> = sqlx::query_as(
-            "SELECT i.id as invoice_id, i.customer_id, i.total, i.due_date,
+            "SELECT i.id as invoice_id, i.customer_id, i.total - i.amount_paid as balance, i.due_date,
                     CAST((julianday('now') - julianday(i.due_date)) AS INTEGER) as days_overdue
              FROM invoices i
-             WHERE i.status = 'Issued' AND i.due_date < datetime('now')
+             WHERE i.status = 'Posted' AND i.total > i.amount_paid AND julianday(i.due_date) < julianday('now')
              ORDER BY i.due_date ASC"
         )
         .fetch_all(pool)
@@ -2592,7 +2883,7 @@ impl DunningService {
         let mut customers: std::collections::HashSet<String> = std::collections::HashSet::new();
         
         for invoice in overdue_invoices {
-            let level = Self::determine_level(pool, invoice.days_overdue).await?;
+            let level = Self::determine_level(pool, &policy_id, invoice.days_overdue).await?;
             let customer_id = Uuid::parse_str(&invoice.customer_id).unwrap_or_default();
             let invoice_id = Uuid::parse_str(&invoice.invoice_id).unwrap_or_default();
             
@@ -2603,9 +2894,9 @@ impl DunningService {
                 level: level.clone(),
                 letter_date: now,
                 invoice_ids: vec![invoice_id],
-                invoice_amount: invoice.total,
+                invoice_amount: invoice.balance,
                 fee_amount: 0,
-                total_amount: invoice.total,
+                total_amount: invoice.balance,
                 sent_at: None,
                 acknowledged_at: None,
                 status: DunningLetterStatus::Generated,
@@ -2638,7 +2929,7 @@ impl DunningService {
             .await
             .ok();
             
-            total_amount += invoice.total;
+            total_amount += invoice.balance;
             customers.insert(invoice.customer_id.to_string());
             letters.push(letter);
         }
@@ -2657,10 +2948,11 @@ impl DunningService {
         Ok(letters)
     }
 
-    async fn determine_level(pool: &SqlitePool, days_overdue: i64) -> Result<DunningLevel> {
+    async fn determine_level(pool: &SqlitePool, policy_id: &str, days_overdue: i64) -> Result<DunningLevel> {
         let level_row: Option<(String,)> = sqlx::query_as(
-            "SELECT level FROM dunning_level_configs WHERE days_overdue <= ? ORDER BY days_overdue DESC LIMIT 1"
+            "SELECT level FROM dunning_level_configs WHERE policy_id = ? AND days_overdue <= ? ORDER BY days_overdue DESC LIMIT 1"
         )
+        .bind(policy_id)
         .bind(days_overdue as i32)
         .fetch_optional(pool)
         .await
@@ -2776,14 +3068,15 @@ impl DunningService {
     pub async fn get_aging_report(pool: &SqlitePool) -> Result<AgingReport> {
         let rows: Vec<AgingRow> = sqlx::query_as(
             "SELECT c.id as customer_id, c.name as customer_name,
-                    SUM(CASE WHEN julianday('now') - julianday(i.due_date) BETWEEN 0 AND 30 THEN i.total ELSE 0 END) as current,
-                    SUM(CASE WHEN julianday('now') - julianday(i.due_date) BETWEEN 31 AND 60 THEN i.total ELSE 0 END) as days_31_60,
-                    SUM(CASE WHEN julianday('now') - julianday(i.due_date) BETWEEN 61 AND 90 THEN i.total ELSE 0 END) as days_61_90,
-                    SUM(CASE WHEN julianday('now') - julianday(i.due_date) > 90 THEN i.total ELSE 0 END) as over_90
+                    SUM(CASE WHEN julianday('now') - julianday(i.due_date) BETWEEN 0 AND 30 THEN i.total - i.amount_paid ELSE 0 END) as current,
+                    SUM(CASE WHEN julianday('now') - julianday(i.due_date) BETWEEN 31 AND 60 THEN i.total - i.amount_paid ELSE 0 END) as days_31_60,
+                    SUM(CASE WHEN julianday('now') - julianday(i.due_date) BETWEEN 61 AND 90 THEN i.total - i.amount_paid ELSE 0 END) as days_61_90,
+                    SUM(CASE WHEN julianday('now') - julianday(i.due_date) > 90 THEN i.total - i.amount_paid ELSE 0 END) as over_90
              FROM customers c
-             LEFT JOIN invoices i ON c.id = i.customer_id AND i.status = 'Issued' AND i.due_date < datetime('now')
+             LEFT JOIN invoices i ON c.id = i.customer_id AND i.status = 'Posted' AND i.total > i.amount_paid
+                 AND julianday(i.due_date) < julianday('now')
              GROUP BY c.id, c.name
-             HAVING SUM(i.total) > 0"
+             HAVING SUM(i.total - i.amount_paid) > 0"
         )
         .fetch_all(pool)
         .await
@@ -2811,7 +3104,7 @@ struct OverdueInvoiceRow {
     #[allow(dead_code)]
     customer_id: String,
     #[allow(dead_code)]
-    total: i64,
+    balance: i64,
     #[allow(dead_code)]
     due_date: String,
     #[allow(dead_code)]
@@ -2982,19 +3275,59 @@ impl PeriodManagementService {
     }
 
     pub async fn is_period_locked(pool: &SqlitePool, date: DateTime<Utc>) -> Result<bool> {
-        let lock_type: Option<(String,)> = sqlx::query_as(
-            "SELECT lock_type FROM accounting_periods WHERE date(?) >= date(start_date) AND date(?) <= date(end_date)"
+        Self::is_period_locked_in(&mut *pool.acquire().await?, date).await
+    }
+
+    /// Periods run from their start up to, but not including, their end, which is the next
+    /// period's start. A date is locked when any period containing it is hard closed.
+    pub async fn is_period_locked_in(conn: &mut SqliteConnection, date: DateTime<Utc>) -> Result<bool> {
+        let (locked,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS (SELECT 1 FROM accounting_periods
+             WHERE julianday(start_date) <= julianday(?) AND julianday(?) < julianday(end_date) AND lock_type = 'HardClose')"
         )
         .bind(date.to_rfc3339())
         .bind(date.to_rfc3339())
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(Error::Database)?;
+        
+        Ok(locked)
+    }
+
+    pub async fn ensure_period_open(pool: &SqlitePool, date: DateTime<Utc>) -> Result<()> {
+        Self::ensure_period_open_in(&mut *pool.acquire().await?, date).await
+    }
+
+    pub async fn ensure_period_open_in(conn: &mut SqliteConnection, date: DateTime<Utc>) -> Result<()> {
+        if Self::is_period_locked_in(conn, date).await? {
+            return Err(Error::business_rule(format!(
+                "Accounting period for {} is closed for posting",
+                date.format("%Y-%m-%d")
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn next_period_start(pool: &SqlitePool, date: DateTime<Utc>) -> Result<DateTime<Utc>> {
+        use chrono::Datelike;
+        
+        let start: Option<(String,)> = sqlx::query_as(
+            "SELECT start_date FROM accounting_periods WHERE date(start_date) > date(?) ORDER BY start_date LIMIT 1"
+        )
+        .bind(date.to_rfc3339())
         .fetch_optional(pool)
         .await
         .map_err(Error::Database)?;
         
-        Ok(match lock_type {
-            Some((lt,)) => lt == "HardClose",
-            None => false,
-        })
+        if let Some((start,)) = start {
+            return chrono::DateTime::parse_from_rfc3339(&start)
+                .map(|d| d.with_timezone(&chrono::Utc))
+                .map_err(|_| Error::validation("Invalid period start date"));
+        }
+        
+        let month_start = date.date_naive().with_day(1).unwrap_or(date.date_naive());
+        let next = month_start + chrono::Months::new(1);
+        Ok(next.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
     }
 
     pub async fn create_close_checklist(
@@ -3327,6 +3660,9 @@ impl RecurringJournalService {
 
     async fn create_journal_entry(pool: &SqlitePool, journal: &RecurringJournal) -> Result<Uuid> {
         let now = chrono::Utc::now();
+        if journal.auto_post {
+            PeriodManagementService::ensure_period_open(pool, now).await?;
+        }
         let je_id = Uuid::new_v4();
         let entry_number = format!("JE-{}", now.format("%Y%m%d%H%M%S"));
         
@@ -3340,7 +3676,7 @@ impl RecurringJournalService {
         .bind(&entry_number)
         .bind(now.to_rfc3339())
         .bind(&description)
-        .bind(if journal.auto_post { "Completed" } else { "Draft" })
+        .bind(if journal.auto_post { "Posted" } else { "Draft" })
         .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
         .execute(pool)
@@ -3732,6 +4068,7 @@ impl CurrencyRevaluationService {
             return Err(Error::business_rule("No revaluation lines to post"));
         }
 
+        PeriodManagementService::ensure_period_open(pool, revaluation.revaluation_date).await?;
         let journal_entry_id = Self::create_revaluation_journal_entry(pool, &revaluation, &lines).await?;
 
         let now = chrono::Utc::now();
@@ -3756,13 +4093,8 @@ impl CurrencyRevaluationService {
         }
 
         if let Some(je_id) = revaluation.journal_entry_id {
-            sqlx::query(
-                "UPDATE journal_entries SET status = 'Draft' WHERE id = ?"
-            )
-            .bind(je_id.to_string())
-            .execute(pool)
-            .await
-            .ok();
+            let reason = format!("Currency revaluation {} reversed", revaluation.revaluation_number);
+            JournalEntryService::new().reverse_entry(pool, je_id, None, Some(reason), None).await?;
         }
 
         let now = chrono::Utc::now();
@@ -3790,7 +4122,7 @@ impl CurrencyRevaluationService {
 
         sqlx::query(
             "INSERT INTO journal_entries (id, entry_number, date, description, reference, status, created_at, updated_at)
-             VALUES (?, ?, ?, ?, NULL, 'Completed', ?, ?)"
+             VALUES (?, ?, ?, ?, NULL, 'Posted', ?, ?)"
         )
         .bind(je_id.to_string())
         .bind(&entry_number)
@@ -3880,7 +4212,7 @@ impl CurrencyRevaluationService {
              FROM accounts a
              JOIN bank_accounts ba ON ba.account_id = a.id
              LEFT JOIN journal_lines jl ON jl.account_id = a.id
-             LEFT JOIN journal_entries je ON jl.journal_entry_id = je.id AND je.status = 'Completed'
+             LEFT JOIN journal_entries je ON jl.journal_entry_id = je.id AND je.status = 'Posted'
              WHERE a.status = 'Active' AND ba.currency != 'USD'
              GROUP BY a.id, a.code, a.name, ba.currency
              HAVING original_balance != 0"
@@ -4024,6 +4356,291 @@ impl From<CurrencyRevaluationLineRow> for CurrencyRevaluationLine {
     }
 }
 
+pub struct PostingService;
+
+impl Default for PostingService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PostingService {
+    pub fn new() -> Self { Self }
+
+    pub async fn upsert_rule(
+        pool: &SqlitePool,
+        event: PostingEvent,
+        debit_account_id: Uuid,
+        credit_account_id: Uuid,
+        tax_account_id: Option<Uuid>,
+        description: Option<&str>,
+    ) -> Result<PostingRule> {
+        if debit_account_id == credit_account_id {
+            return Err(Error::validation("Debit and credit accounts must differ"));
+        }
+        let account_repo = SqliteAccountRepository;
+        for account_id in [Some(debit_account_id), Some(credit_account_id), tax_account_id].into_iter().flatten() {
+            let account = account_repo.find_by_id(pool, account_id).await?;
+            if account.status != Status::Active {
+                return Err(Error::business_rule(format!("Account {} is not active", account.code)));
+            }
+        }
+        
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO posting_rules (id, event, debit_account_id, credit_account_id, tax_account_id, description, is_active, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, 1, ?, ?)
+             ON CONFLICT(event) DO UPDATE SET
+             debit_account_id = excluded.debit_account_id,
+             credit_account_id = excluded.credit_account_id,
+             tax_account_id = excluded.tax_account_id,
+             description = excluded.description,
+             is_active = 1,
+             updated_at = excluded.updated_at"
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(format!("{:?}", event))
+        .bind(debit_account_id.to_string())
+        .bind(credit_account_id.to_string())
+        .bind(tax_account_id.map(|id| id.to_string()))
+        .bind(description)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        Self::get_rule(pool, event).await?
+            .ok_or_else(|| Error::not_found("PostingRule", &format!("{:?}", event)))
+    }
+
+    pub async fn deactivate_rule(pool: &SqlitePool, event: PostingEvent) -> Result<()> {
+        sqlx::query("UPDATE posting_rules SET is_active = 0, updated_at = ? WHERE event = ?")
+            .bind(Utc::now().to_rfc3339())
+            .bind(format!("{:?}", event))
+            .execute(pool)
+            .await
+            .map_err(Error::Database)?;
+        Ok(())
+    }
+
+    pub async fn get_rule(pool: &SqlitePool, event: PostingEvent) -> Result<Option<PostingRule>> {
+        Self::get_rule_in(&mut *pool.acquire().await?, event).await
+    }
+
+    pub async fn get_rule_in(conn: &mut SqliteConnection, event: PostingEvent) -> Result<Option<PostingRule>> {
+        let row = sqlx::query_as::<_, PostingRuleRow>(
+            "SELECT id, event, debit_account_id, credit_account_id, tax_account_id, description, is_active, created_at, updated_at
+             FROM posting_rules WHERE event = ? AND is_active = 1"
+        )
+        .bind(format!("{:?}", event))
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(Error::Database)?;
+        
+        row.map(PostingRule::try_from).transpose()
+    }
+
+    pub async fn list_rules(pool: &SqlitePool) -> Result<Vec<PostingRule>> {
+        let rows = sqlx::query_as::<_, PostingRuleRow>(
+            "SELECT id, event, debit_account_id, credit_account_id, tax_account_id, description, is_active, created_at, updated_at
+             FROM posting_rules ORDER BY event"
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        rows.into_iter().map(PostingRule::try_from).collect()
+    }
+
+    pub async fn is_configured(pool: &SqlitePool, event: PostingEvent) -> Result<bool> {
+        Ok(Self::get_rule(pool, event).await?.is_some())
+    }
+
+    /// Posts a balanced journal entry for an operational document using the event's posting rule.
+    /// Runs on the caller's connection, normally inside the transaction that saves the document,
+    /// so the document and its entry commit or roll back together. Returns `None` when no active
+    /// rule is configured or the document carries no value, and the existing entry when the same
+    /// document was already posted for this event. A negative amount on a signed event posts to
+    /// the rule's accounts the other way round.
+    pub async fn post_document(conn: &mut SqliteConnection, mut doc: PostingDocument) -> Result<Option<JournalEntry>> {
+        if doc.tax_amount < 0 || (doc.amount < 0 && !doc.event.is_signed()) {
+            return Err(Error::validation("Posting amounts cannot be negative"));
+        }
+        let journal_repo = SqliteJournalEntryRepository;
+        if let Some(existing) = Self::find_source(conn, doc.event, doc.source_id).await? {
+            return journal_repo.find_by_id_in(conn, existing.journal_entry_id).await.map(Some);
+        }
+        let Some(mut rule) = Self::get_rule_in(conn, doc.event).await? else {
+            return Ok(None);
+        };
+        if doc.amount + doc.tax_amount == 0 {
+            return Ok(None);
+        }
+        if doc.amount < 0 {
+            doc.amount = -doc.amount;
+            std::mem::swap(&mut rule.debit_account_id, &mut rule.credit_account_id);
+        }
+        
+        let now = Utc::now();
+        let description = doc.description.clone()
+            .or_else(|| rule.description.clone())
+            .unwrap_or_else(|| format!("{:?} {}", doc.event, doc.source_number));
+        let entry = JournalEntry {
+            base: BaseEntity::new(),
+            entry_number: format!("JE-{}-{}", now.format("%Y%m%d%H%M%S"), &Uuid::new_v4().to_string()[0..8]),
+            date: doc.date,
+            description,
+            reference: Some(doc.source_number.clone()),
+            lines: Self::build_lines(&rule, &doc),
+            status: Status::Posted,
+        };
+        journal_repo.create_posted_in(conn, &entry).await?;
+        
+        sqlx::query(
+            "INSERT INTO journal_entry_sources (id, journal_entry_id, event, source_type, source_id, source_number, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(entry.base.id.to_string())
+        .bind(format!("{:?}", doc.event))
+        .bind(&doc.source_type)
+        .bind(doc.source_id.to_string())
+        .bind(&doc.source_number)
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(Error::Database)?;
+        
+        Ok(Some(entry))
+    }
+
+    pub async fn list_sources(pool: &SqlitePool, source_type: &str, source_id: Uuid) -> Result<Vec<JournalEntrySource>> {
+        let rows = sqlx::query_as::<_, JournalEntrySourceRow>(
+            "SELECT id, journal_entry_id, event, source_type, source_id, source_number, created_at
+             FROM journal_entry_sources WHERE source_type = ? AND source_id = ? ORDER BY created_at"
+        )
+        .bind(source_type)
+        .bind(source_id.to_string())
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        rows.into_iter().map(JournalEntrySource::try_from).collect()
+    }
+
+    async fn find_source(conn: &mut SqliteConnection, event: PostingEvent, source_id: Uuid) -> Result<Option<JournalEntrySource>> {
+        let row = sqlx::query_as::<_, JournalEntrySourceRow>(
+            "SELECT id, journal_entry_id, event, source_type, source_id, source_number, created_at
+             FROM journal_entry_sources WHERE event = ? AND source_id = ?"
+        )
+        .bind(format!("{:?}", event))
+        .bind(source_id.to_string())
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(Error::Database)?;
+        
+        row.map(JournalEntrySource::try_from).transpose()
+    }
+
+    fn build_lines(rule: &PostingRule, doc: &PostingDocument) -> Vec<JournalLine> {
+        use erp_core::Money;
+        
+        let line = |account_id: Uuid, debit: i64, credit: i64| JournalLine {
+            id: Uuid::new_v4(),
+            account_id,
+            debit: Money::new(debit, doc.currency.clone()),
+            credit: Money::new(credit, doc.currency.clone()),
+            description: Some(doc.source_number.clone()),
+        };
+        let gross = doc.amount + doc.tax_amount;
+        
+        match (doc.event.tax_side(), rule.tax_account_id) {
+            (Some(PostingSide::Credit), Some(tax_account)) if doc.tax_amount > 0 => vec![
+                line(rule.debit_account_id, gross, 0),
+                line(rule.credit_account_id, 0, doc.amount),
+                line(tax_account, 0, doc.tax_amount),
+            ],
+            (Some(PostingSide::Debit), Some(tax_account)) if doc.tax_amount > 0 => vec![
+                line(rule.debit_account_id, doc.amount, 0),
+                line(tax_account, doc.tax_amount, 0),
+                line(rule.credit_account_id, 0, gross),
+            ],
+            _ => vec![
+                line(rule.debit_account_id, gross, 0),
+                line(rule.credit_account_id, 0, gross),
+            ],
+        }
+        .into_iter()
+        .filter(|l| l.debit.amount != 0 || l.credit.amount != 0)
+        .collect()
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PostingRuleRow {
+    id: String,
+    event: String,
+    debit_account_id: String,
+    credit_account_id: String,
+    tax_account_id: Option<String>,
+    description: Option<String>,
+    is_active: i64,
+    created_at: String,
+    updated_at: String,
+}
+
+impl TryFrom<PostingRuleRow> for PostingRule {
+    type Error = Error;
+
+    fn try_from(r: PostingRuleRow) -> Result<Self> {
+        Ok(Self {
+            id: Uuid::parse_str(&r.id).unwrap_or_default(),
+            event: r.event.parse().map_err(Error::validation)?,
+            debit_account_id: Uuid::parse_str(&r.debit_account_id).unwrap_or_default(),
+            credit_account_id: Uuid::parse_str(&r.credit_account_id).unwrap_or_default(),
+            tax_account_id: r.tax_account_id.and_then(|id| Uuid::parse_str(&id).ok()),
+            description: r.description,
+            is_active: r.is_active != 0,
+            created_at: chrono::DateTime::parse_from_rfc3339(&r.created_at)
+                .map(|d| d.with_timezone(&chrono::Utc))
+                .unwrap_or_else(|_| chrono::Utc::now()),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&r.updated_at)
+                .map(|d| d.with_timezone(&chrono::Utc))
+                .unwrap_or_else(|_| chrono::Utc::now()),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct JournalEntrySourceRow {
+    id: String,
+    journal_entry_id: String,
+    event: String,
+    source_type: String,
+    source_id: String,
+    source_number: String,
+    created_at: String,
+}
+
+impl TryFrom<JournalEntrySourceRow> for JournalEntrySource {
+    type Error = Error;
+
+    fn try_from(r: JournalEntrySourceRow) -> Result<Self> {
+        Ok(Self {
+            id: Uuid::parse_str(&r.id).unwrap_or_default(),
+            journal_entry_id: Uuid::parse_str(&r.journal_entry_id).unwrap_or_default(),
+            event: r.event.parse().map_err(Error::validation)?,
+            source_type: r.source_type,
+            source_id: Uuid::parse_str(&r.source_id).unwrap_or_default(),
+            source_number: r.source_number,
+            created_at: chrono::DateTime::parse_from_rfc3339(&r.created_at)
+                .map(|d| d.with_timezone(&chrono::Utc))
+                .unwrap_or_else(|_| chrono::Utc::now()),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4138,4 +4755,58 @@ mod tests {
         let result = svc.validate_account(&account);
         assert!(result.is_err());
     }
+
+    fn posting_fixture(event: PostingEvent, tax_account: Option<Uuid>) -> (PostingRule, PostingDocument) {
+        let rule = PostingRule {
+            id: Uuid::new_v4(),
+            event,
+            debit_account_id: Uuid::new_v4(),
+            credit_account_id: Uuid::new_v4(),
+            tax_account_id: tax_account,
+            description: None,
+            is_active: true,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let doc = PostingDocument {
+            event,
+            source_type: "Test".to_string(),
+            source_id: Uuid::new_v4(),
+            source_number: "DOC-001".to_string(),
+            date: chrono::Utc::now(),
+            amount: 10000,
+            tax_amount: 800,
+            currency: erp_core::Currency::EUR,
+            description: None,
+        };
+        (rule, doc)
+    }
+    
+    #[test]
+    fn test_posting_lines_split_output_tax() {
+        let tax_account = Uuid::new_v4();
+        let (rule, doc) = posting_fixture(PostingEvent::CustomerInvoice, Some(tax_account));
+        let lines = PostingService::build_lines(&rule, &doc);
+        
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].account_id, rule.debit_account_id);
+        assert_eq!(lines[0].debit.amount, 10800);
+        assert_eq!(lines[2].account_id, tax_account);
+        assert_eq!(lines[2].credit.amount, 800);
+        assert!(lines.iter().all(|l| l.debit.currency == erp_core::Currency::EUR));
+        
+        let entry = JournalEntry { lines, ..create_test_entry(vec![], vec![]) };
+        assert!(JournalEntryService::new().validate_entry(&entry).is_ok());
+    }
+    
+    #[test]
+    fn test_posting_lines_without_tax_account() {
+        let (rule, doc) = posting_fixture(PostingEvent::VendorBill, None);
+        let lines = PostingService::build_lines(&rule, &doc);
+        
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].debit.amount, 10800);
+        assert_eq!(lines[1].account_id, rule.credit_account_id);
+        assert_eq!(lines[1].credit.amount, 10800);
+    }
 }
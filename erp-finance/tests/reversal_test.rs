@@ -0,0 +1,196 @@
+use chrono::{TimeZone, Utc};
+use erp_core::{BaseEntity, Currency, Money, Status};
+use erp_finance::*;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+async fn setup() -> SqlitePool {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    let migrations = [
+        include_str!("../../migrations/20240101000000_finance.sql"),
+        include_str!("../../migrations/20240101000018_enterprise_additions.sql"),
+        include_str!("../../migrations/20260310000000_journal_reversals.sql"),
+        include_str!("../../migrations/20260329000000_journal_reversal_errors.sql"),
+        include_str!("../../migrations/20260329000500_journal_line_currency.sql"),
+    ];
+    for migration in migrations {
+        for statement in migration.split(';') {
+            let statement = statement.trim();
+            if !statement.is_empty() {
+                let _ = sqlx::query(statement).execute(&pool).await;
+            }
+        }
+    }
+    pool
+}
+
+async fn create_account(pool: &SqlitePool, code: &str, account_type: AccountType) -> Uuid {
+    let account = Account {
+        base: BaseEntity::new(),
+        code: code.to_string(),
+        name: format!("Account {}", code),
+        account_type,
+        parent_id: None,
+        description: None,
+        status: Status::Active,
+    };
+    AccountService::new().create_account(pool, account).await.unwrap().base.id
+}
+
+async fn create_periods(pool: &SqlitePool, start: chrono::DateTime<Utc>, end: chrono::DateTime<Utc>) -> Vec<AccountingPeriod> {
+    let year = FiscalYear {
+        base: BaseEntity::new(),
+        name: "FY2026".to_string(),
+        start_date: start,
+        end_date: end,
+        status: Status::Active,
+    };
+    let year = FiscalYearService::new().create_fiscal_year(pool, year).await.unwrap();
+    PeriodManagementService::create_periods_for_fiscal_year(pool, year.base.id).await.unwrap()
+}
+
+fn entry(date: chrono::DateTime<Utc>, debit_account: Uuid, credit_account: Uuid, amount: i64) -> JournalEntry {
+    let line = |account_id, debit, credit| JournalLine {
+        id: Uuid::nil(),
+        account_id,
+        debit: Money::new(debit, Currency::USD),
+        credit: Money::new(credit, Currency::USD),
+        description: None,
+    };
+    JournalEntry {
+        base: BaseEntity::new(),
+        entry_number: String::new(),
+        date,
+        description: "Accrued expense".to_string(),
+        reference: None,
+        lines: vec![line(debit_account, amount, 0), line(credit_account, 0, amount)],
+        status: Status::Draft,
+    }
+}
+
+#[tokio::test]
+async fn test_posting_rejected_in_locked_period_and_inactive_account() {
+    let pool = setup().await;
+    let expense = create_account(&pool, "6000", AccountType::Expense).await;
+    let accrued = create_account(&pool, "2100", AccountType::Liability).await;
+    let march = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+    let april = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+    let periods = create_periods(&pool, march, Utc.with_ymd_and_hms(2026, 5, 1, 0, 0, 0).unwrap()).await;
+    PeriodManagementService::lock_period(&pool, periods[0].id, PeriodLockType::HardClose, None).await.unwrap();
+
+    let service = JournalEntryService::new();
+    let locked = service.create_entry(&pool, entry(march + chrono::Duration::days(10), expense, accrued, 5000)).await.unwrap();
+    let err = service.post_entry(&pool, locked.base.id).await.unwrap_err();
+    assert!(err.to_string().contains("closed"));
+
+    // The first day of the next period is open even though it is the locked period's end date.
+    let open = service.create_entry(&pool, entry(april, expense, accrued, 5000)).await.unwrap();
+    service.post_entry(&pool, open.base.id).await.unwrap();
+
+    sqlx::query("UPDATE accounts SET status = 'Inactive' WHERE id = ?")
+        .bind(accrued.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    let inactive = service.create_entry(&pool, entry(april, expense, accrued, 100)).await.unwrap();
+    let err = service.post_entry(&pool, inactive.base.id).await.unwrap_err();
+    assert!(err.to_string().contains("inactive account"));
+}
+
+#[tokio::test]
+async fn test_reverse_and_auto_reverse_entries() {
+    let pool = setup().await;
+    let expense = create_account(&pool, "6000", AccountType::Expense).await;
+    let accrued = create_account(&pool, "2100", AccountType::Liability).await;
+    let march = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+    let april = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+    create_periods(&pool, march, Utc.with_ymd_and_hms(2026, 5, 1, 0, 0, 0).unwrap()).await;
+
+    let service = JournalEntryService::new();
+    let original = service.create_entry(&pool, entry(march + chrono::Duration::days(30), expense, accrued, 5000)).await.unwrap();
+    service.post_entry(&pool, original.base.id).await.unwrap();
+
+    let reversal = service.reverse_entry(&pool, original.base.id, None, Some("Duplicate".to_string()), None).await.unwrap();
+    assert_eq!(reversal.status, Status::Posted);
+    assert_eq!(reversal.reference.as_deref(), Some(original.entry_number.as_str()));
+    assert_eq!(reversal.lines[0].account_id, expense);
+    assert_eq!(reversal.lines[0].credit.amount, 5000);
+
+    let link = service.get_reversal(&pool, reversal.base.id).await.unwrap().unwrap();
+    assert_eq!(link.original_entry_id, original.base.id);
+    assert_eq!(link.status, JournalReversalStatus::Completed);
+    assert!(service.reverse_entry(&pool, original.base.id, None, None, None).await.is_err());
+    assert!(service.reverse_entry(&pool, reversal.base.id, None, None, None).await.is_err());
+
+    let accrual = service.create_entry(&pool, entry(march + chrono::Duration::days(30), expense, accrued, 2500)).await.unwrap();
+    service.post_entry(&pool, accrual.base.id).await.unwrap();
+    let scheduled = service.schedule_reversal(&pool, accrual.base.id, None, None).await.unwrap();
+    assert_eq!(scheduled.reversal_date, april);
+
+    assert!(service.process_scheduled_reversals(&pool, march + chrono::Duration::days(30)).await.unwrap().reversed.is_empty());
+    let processed = service.process_scheduled_reversals(&pool, april).await.unwrap().reversed;
+    assert_eq!(processed.len(), 1);
+    assert_eq!(processed[0].date, april);
+    assert_eq!(processed[0].lines[1].debit.amount, 2500);
+}
+
+#[tokio::test]
+async fn test_failed_scheduled_reversal_is_recorded_and_others_continue() {
+    let pool = setup().await;
+    let expense = create_account(&pool, "6000", AccountType::Expense).await;
+    let travel = create_account(&pool, "6100", AccountType::Expense).await;
+    let accrued = create_account(&pool, "2100", AccountType::Liability).await;
+    let march = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+    let april = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+    create_periods(&pool, march, Utc.with_ymd_and_hms(2026, 5, 1, 0, 0, 0).unwrap()).await;
+
+    let service = JournalEntryService::new();
+    let user = Uuid::new_v4();
+    let mut scheduled = Vec::new();
+    for debit_account in [travel, expense] {
+        let accrual = service.create_entry(&pool, entry(march + chrono::Duration::days(20), debit_account, accrued, 1000)).await.unwrap();
+        service.post_entry(&pool, accrual.base.id).await.unwrap();
+        scheduled.push(service.schedule_reversal(&pool, accrual.base.id, None, Some(user)).await.unwrap());
+    }
+    sqlx::query("UPDATE accounts SET status = 'Inactive' WHERE id = ?")
+        .bind(travel.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let run = service.process_scheduled_reversals(&pool, april).await.unwrap();
+    assert_eq!(run.reversed.len(), 1);
+    assert_eq!(run.reversed[0].base.created_by, Some(user));
+    assert_eq!(run.failed.len(), 1);
+    assert_eq!(run.failed[0].original_entry_id, scheduled[0].original_entry_id);
+
+    let failed = service.get_reversal(&pool, scheduled[0].original_entry_id).await.unwrap().unwrap();
+    assert_eq!(failed.status, JournalReversalStatus::Scheduled);
+    assert!(failed.reversal_entry_id.is_none());
+    assert!(failed.last_error.unwrap().contains("inactive account"));
+    let (entries,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM journal_entries").fetch_one(&pool).await.unwrap();
+    assert_eq!(entries, 3);
+}
+
+#[tokio::test]
+async fn test_entries_keep_their_currency() {
+    let pool = setup().await;
+    let expense = create_account(&pool, "6000", AccountType::Expense).await;
+    let accrued = create_account(&pool, "2100", AccountType::Liability).await;
+    let march = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+    create_periods(&pool, march, Utc.with_ymd_and_hms(2026, 5, 1, 0, 0, 0).unwrap()).await;
+
+    let mut euro = entry(march + chrono::Duration::days(10), expense, accrued, 4000);
+    for line in &mut euro.lines {
+        line.debit.currency = Currency::EUR;
+        line.credit.currency = Currency::EUR;
+    }
+    let service = JournalEntryService::new();
+    let created = service.create_entry(&pool, euro).await.unwrap();
+    service.post_entry(&pool, created.base.id).await.unwrap();
+
+    let reversal = service.reverse_entry(&pool, created.base.id, None, None, None).await.unwrap();
+    for loaded in [service.get_entry(&pool, created.base.id).await.unwrap(), reversal] {
+        assert!(loaded.lines.iter().all(|l| l.debit.currency == Currency::EUR && l.credit.currency == Currency::EUR));
+    }
+}
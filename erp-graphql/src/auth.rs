@@ -0,0 +1,92 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Guard};
+use erp_auth::Authorizer;
+use erp_core::{Error, RowScope};
+
+pub const CUSTOMERS: &str = "customers";
+pub const SALES_ORDERS: &str = "sales_orders";
+pub const EMPLOYEES: &str = "employees";
+
+/// The caller of a GraphQL operation: who they are, what they may do, and which rows and
+/// fields of the scoped resources they may see. Built by the API from the same policy the
+/// REST handlers use.
+#[derive(Clone)]
+pub struct Viewer {
+    pub user_id: String,
+    authz: Arc<Authorizer>,
+    scopes: HashMap<String, RowScope>,
+    hidden: HashMap<String, HashSet<String>>,
+}
+
+impl Viewer {
+    pub fn new(user_id: impl Into<String>, authz: Arc<Authorizer>) -> Self {
+        Self { user_id: user_id.into(), authz, scopes: HashMap::new(), hidden: HashMap::new() }
+    }
+
+    pub fn with_scope(mut self, resource: &str, scope: RowScope) -> Self {
+        self.scopes.insert(resource.to_string(), scope);
+        self
+    }
+
+    pub fn with_hidden_fields<I, S>(mut self, resource: &str, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.hidden.entry(resource.to_string()).or_default().extend(fields.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn scope(&self, resource: &str) -> RowScope {
+        self.scopes.get(resource).cloned().unwrap_or_default()
+    }
+
+    pub fn hidden_fields(&self, resource: &str) -> Option<&HashSet<String>> {
+        self.hidden.get(resource).filter(|fields| !fields.is_empty())
+    }
+
+    pub async fn require(&self, permission: &str) -> async_graphql::Result<()> {
+        match self.authz.check(&self.user_id, permission).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(to_graphql_error(Error::forbidden(format!("missing permission {}", permission)))),
+            Err(Error::NotFound(_)) => Err(to_graphql_error(Error::Unauthorized)),
+            Err(e) => Err(to_graphql_error(e)),
+        }
+    }
+}
+
+/// Field guard that requires a permission code, e.g. `Require("sales:orders:read")`.
+pub struct Require(pub &'static str);
+
+impl Guard for Require {
+    async fn check(&self, ctx: &Context<'_>) -> async_graphql::Result<()> {
+        match ctx.data_opt::<Viewer>() {
+            Some(viewer) => viewer.require(self.0).await,
+            None => Err(to_graphql_error(Error::Unauthorized)),
+        }
+    }
+}
+
+/// Converts a domain error into a GraphQL error carrying the same classification the REST
+/// API maps to a status code.
+pub fn to_graphql_error(error: Error) -> async_graphql::Error {
+    let code = match &error {
+        Error::NotFound(_) => "NOT_FOUND",
+        Error::Validation(_) => "BAD_USER_INPUT",
+        Error::BusinessRule(_) => "BUSINESS_RULE",
+        Error::Conflict(_) => "CONFLICT",
+        Error::Unauthorized => "UNAUTHENTICATED",
+        Error::Forbidden(_) => "FORBIDDEN",
+        Error::Database(_) | Error::Internal(_) => "INTERNAL",
+    };
+    let message = match &error {
+        Error::Database(_) | Error::Internal(_) => {
+            tracing::error!("GraphQL resolver failed: {}", error);
+            "Internal server error".to_string()
+        }
+        _ => error.to_string(),
+    };
+    async_graphql::Error::new(message).extend_with(|_, e| e.set("code", code))
+}
@@ -1,4 +1,11 @@
+pub mod auth;
+pub mod loaders;
+pub mod mutation;
+pub mod query;
+pub mod record;
 pub mod schema;
 pub mod service;
+pub mod subscription;
 
+pub use auth::Viewer;
 pub use service::*;
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use async_graphql::dataloader::{DataLoader, Loader};
+use sqlx::SqlitePool;
+
+use crate::auth::Viewer;
+use crate::record::{fetch_where, present, scope_for, Child, Record};
+
+/// Batches the relationship lookups of one operation into one query per record type and
+/// relationship. Rows are filtered and redacted for the viewer the same way top-level
+/// queries are.
+pub struct RecordLoader {
+    pool: SqlitePool,
+    viewer: Viewer,
+}
+
+pub type Loaders = DataLoader<RecordLoader>;
+
+pub fn loaders(pool: SqlitePool, viewer: Viewer) -> Loaders {
+    DataLoader::new(RecordLoader { pool, viewer }, tokio::spawn)
+}
+
+/// Loads a `T` by its id.
+pub struct ById<T>(pub String, PhantomData<fn() -> T>);
+
+/// Loads the `T`s that belong to a parent record, see [`Child`].
+pub struct ChildrenOf<T>(pub String, PhantomData<fn() -> T>);
+
+macro_rules! key {
+    ($name:ident) => {
+        impl<T> $name<T> {
+            pub fn new(id: impl Into<String>) -> Self {
+                Self(id.into(), PhantomData)
+            }
+        }
+
+        impl<T> Clone for $name<T> {
+            fn clone(&self) -> Self {
+                Self::new(self.0.clone())
+            }
+        }
+
+        impl<T> PartialEq for $name<T> {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl<T> Eq for $name<T> {}
+
+        impl<T> Hash for $name<T> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.hash(state)
+            }
+        }
+    };
+}
+
+key!(ById);
+key!(ChildrenOf);
+
+impl<T: Record> Loader<ById<T>> for RecordLoader {
+    type Value = T;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[ById<T>]) -> Result<HashMap<ById<T>, T>, Self::Error> {
+        let ids: Vec<String> = keys.iter().map(|k| k.0.clone()).collect();
+        let rows = fetch_where::<T>(&self.pool, &scope_for::<T>(&self.viewer), "id", &ids).await?;
+        Ok(rows.into_iter()
+            .map(|row| (ById::new(row.id()), present(&self.viewer, row)))
+            .collect())
+    }
+}
+
+impl<T: Child> Loader<ChildrenOf<T>> for RecordLoader {
+    type Value = Vec<T>;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[ChildrenOf<T>]) -> Result<HashMap<ChildrenOf<T>, Vec<T>>, Self::Error> {
+        let parents: Vec<String> = keys.iter().map(|k| k.0.clone()).collect();
+        let rows = fetch_where::<T>(&self.pool, &scope_for::<T>(&self.viewer), T::PARENT, &parents).await?;
+        let mut children: HashMap<ChildrenOf<T>, Vec<T>> = keys.iter().map(|k| (k.clone(), Vec::new())).collect();
+        for row in rows {
+            if let Some(parent) = row.parent().map(ChildrenOf::new) {
+                children.entry(parent).or_default().push(present(&self.viewer, row));
+            }
+        }
+        Ok(children)
+    }
+}
@@ -0,0 +1,607 @@
+use async_graphql::{Context, InputObject, Object};
+use chrono::{NaiveDate, Utc};
+use erp_core::{Address, BaseEntity, ContactInfo, Currency, Money, Status};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::auth::{to_graphql_error, Require, Viewer};
+use crate::record::{reload, Record};
+use crate::schema::*;
+use crate::subscription::publish_change;
+
+fn parse_id(field: &str, value: &str) -> async_graphql::Result<Uuid> {
+    Uuid::parse_str(value).map_err(|_| to_graphql_error(erp_core::Error::validation(format!("{} is not a valid id", field))))
+}
+
+fn parse_optional_id(field: &str, value: Option<&str>) -> async_graphql::Result<Option<Uuid>> {
+    value.map(|v| parse_id(field, v)).transpose()
+}
+
+fn parse_datetime(field: &str, value: &str) -> async_graphql::Result<chrono::DateTime<Utc>> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|_| to_graphql_error(erp_core::Error::validation(format!("{} must be an RFC 3339 timestamp", field))))
+}
+
+fn empty_address() -> Address {
+    Address { street: String::new(), city: String::new(), state: None, postal_code: String::new(), country: String::new() }
+}
+
+/// Runs a service call on behalf of the viewer, then publishes the change and reads the
+/// record back for the response.
+async fn write<T, F>(ctx: &Context<'_>, entity: &str, action: &str, call: F) -> async_graphql::Result<T>
+where
+    T: Record,
+    F: std::future::Future<Output = erp_core::Result<Uuid>>,
+{
+    let id = call.await.map_err(to_graphql_error)?;
+    let viewer = ctx.data::<Viewer>()?;
+    publish_change(entity, id, action, &viewer.user_id);
+    reload(ctx.data::<SqlitePool>()?, viewer, id).await
+}
+
+fn user_id(ctx: &Context<'_>) -> async_graphql::Result<Option<Uuid>> {
+    Ok(Uuid::parse_str(&ctx.data::<Viewer>()?.user_id).ok())
+}
+
+#[derive(InputObject)]
+pub struct AccountInput {
+    #[graphql(validator(min_length = 1, max_length = 20))]
+    pub code: String,
+    #[graphql(validator(min_length = 1, max_length = 200))]
+    pub name: String,
+    /// Asset (default), Liability, Equity, Revenue or Expense.
+    pub account_type: Option<String>,
+    pub parent_id: Option<String>,
+    pub description: Option<String>,
+}
+
+fn account_type(value: Option<&str>) -> erp_finance::AccountType {
+    match value {
+        Some("Liability") => erp_finance::AccountType::Liability,
+        Some("Equity") => erp_finance::AccountType::Equity,
+        Some("Revenue") => erp_finance::AccountType::Revenue,
+        Some("Expense") => erp_finance::AccountType::Expense,
+        _ => erp_finance::AccountType::Asset,
+    }
+}
+
+#[derive(InputObject)]
+pub struct JournalEntryInput {
+    pub description: String,
+    pub reference: Option<String>,
+    pub lines: Vec<JournalLineInput>,
+}
+
+#[derive(InputObject)]
+pub struct JournalLineInput {
+    pub account_id: String,
+    #[graphql(default, validator(minimum = 0))]
+    pub debit: i64,
+    #[graphql(default, validator(minimum = 0))]
+    pub credit: i64,
+    pub description: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct ProductInput {
+    #[graphql(validator(min_length = 1, max_length = 50))]
+    pub sku: String,
+    #[graphql(validator(min_length = 1, max_length = 200))]
+    pub name: String,
+    #[graphql(validator(max_length = 1000))]
+    pub description: Option<String>,
+    /// Goods (default), Service or Digital.
+    pub product_type: Option<String>,
+    pub category_id: Option<String>,
+    #[graphql(validator(min_length = 1, max_length = 20))]
+    pub unit_of_measure: String,
+}
+
+fn product_type(value: Option<&str>) -> erp_inventory::ProductType {
+    match value {
+        Some("Service") => erp_inventory::ProductType::Service,
+        Some("Digital") => erp_inventory::ProductType::Digital,
+        _ => erp_inventory::ProductType::Goods,
+    }
+}
+
+#[derive(InputObject)]
+pub struct WarehouseInput {
+    #[graphql(validator(min_length = 1, max_length = 20))]
+    pub code: String,
+    #[graphql(validator(min_length = 1, max_length = 100))]
+    pub name: String,
+    pub street: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct StockMovementInput {
+    pub product_id: String,
+    pub to_location_id: String,
+    pub from_location_id: Option<String>,
+    #[graphql(validator(minimum = 1))]
+    pub quantity: i64,
+    /// Receipt (default), Issue, Transfer or Adjustment.
+    pub movement_type: Option<String>,
+    pub reference: Option<String>,
+    #[graphql(validator(minimum = 0))]
+    pub unit_cost: Option<i64>,
+}
+
+#[derive(InputObject)]
+pub struct CustomerInput {
+    pub code: String,
+    pub name: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub credit_limit: Option<i64>,
+    pub payment_terms: Option<u32>,
+}
+
+#[derive(InputObject)]
+pub struct OrderLineInput {
+    pub product_id: String,
+    pub description: String,
+    pub quantity: i64,
+    pub unit_price: i64,
+}
+
+#[derive(InputObject)]
+pub struct SalesOrderInput {
+    pub customer_id: String,
+    pub lines: Vec<OrderLineInput>,
+}
+
+#[derive(InputObject)]
+pub struct VendorInput {
+    pub code: String,
+    pub name: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub payment_terms: Option<u32>,
+}
+
+#[derive(InputObject)]
+pub struct PurchaseOrderInput {
+    pub vendor_id: String,
+    pub lines: Vec<OrderLineInput>,
+}
+
+#[derive(InputObject)]
+pub struct BomInput {
+    pub product_id: String,
+    pub name: String,
+    pub quantity: i64,
+    pub components: Vec<BomComponentInput>,
+}
+
+#[derive(InputObject)]
+pub struct BomComponentInput {
+    pub product_id: String,
+    pub quantity: i64,
+    pub unit: String,
+}
+
+#[derive(InputObject)]
+pub struct WorkOrderInput {
+    pub product_id: String,
+    pub bom_id: String,
+    pub quantity: i64,
+    pub planned_start: String,
+    pub planned_end: String,
+}
+
+#[derive(InputObject)]
+pub struct EmployeeInput {
+    pub employee_number: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub phone: Option<String>,
+    /// `YYYY-MM-DD`.
+    pub hire_date: String,
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    #[graphql(guard = "Require(\"finance:accounts:write\")")]
+    async fn create_account(&self, ctx: &Context<'_>, input: AccountInput) -> async_graphql::Result<Account> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let account = erp_finance::Account {
+            base: BaseEntity { created_by: user_id(ctx)?, ..BaseEntity::new() },
+            code: input.code,
+            name: input.name,
+            account_type: account_type(input.account_type.as_deref()),
+            parent_id: parse_optional_id("parentId", input.parent_id.as_deref())?,
+            status: Status::Active,
+            description: input.description,
+        };
+        write(ctx, "account", "created", async {
+            Ok(erp_finance::AccountService::new().create_account(pool, account).await?.base.id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"finance:accounts:write\")")]
+    async fn update_account(&self, ctx: &Context<'_>, id: String, input: AccountInput) -> async_graphql::Result<Account> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let id = parse_id("id", &id)?;
+        let parent_id = parse_optional_id("parentId", input.parent_id.as_deref())?;
+        write(ctx, "account", "updated", async {
+            let svc = erp_finance::AccountService::new();
+            let mut account = svc.get_account(pool, id).await?;
+            account.code = input.code;
+            account.name = input.name;
+            account.account_type = account_type(input.account_type.as_deref());
+            account.parent_id = parent_id;
+            account.description = input.description;
+            Ok(svc.update_account(pool, account).await?.base.id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"finance:journals:write\")")]
+    async fn create_journal_entry(&self, ctx: &Context<'_>, input: JournalEntryInput) -> async_graphql::Result<JournalEntry> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let mut lines = Vec::with_capacity(input.lines.len());
+        for line in input.lines {
+            lines.push(erp_finance::JournalLine {
+                id: Uuid::nil(),
+                account_id: parse_id("accountId", &line.account_id)?,
+                debit: Money::new(line.debit, Currency::USD),
+                credit: Money::new(line.credit, Currency::USD),
+                description: line.description,
+            });
+        }
+        let entry = erp_finance::JournalEntry {
+            base: BaseEntity { created_by: user_id(ctx)?, ..BaseEntity::new() },
+            entry_number: String::new(),
+            date: Utc::now(),
+            description: input.description,
+            reference: input.reference,
+            lines,
+            status: Status::Draft,
+        };
+        write(ctx, "journal_entry", "created", async {
+            Ok(erp_finance::JournalEntryService::new().create_entry(pool, entry).await?.base.id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"finance:journals:post\")")]
+    async fn post_journal_entry(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<JournalEntry> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let id = parse_id("id", &id)?;
+        write(ctx, "journal_entry", "posted", async {
+            erp_finance::JournalEntryService::new().post_entry(pool, id).await.map(|_| id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"inventory:products:write\")")]
+    async fn create_product(&self, ctx: &Context<'_>, input: ProductInput) -> async_graphql::Result<Product> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let product = erp_inventory::Product {
+            base: BaseEntity { created_by: user_id(ctx)?, ..BaseEntity::new() },
+            sku: input.sku,
+            name: input.name,
+            description: input.description,
+            product_type: product_type(input.product_type.as_deref()),
+            category_id: parse_optional_id("categoryId", input.category_id.as_deref())?,
+            unit_of_measure: input.unit_of_measure,
+            status: Status::Active,
+        };
+        write(ctx, "product", "created", async {
+            Ok(erp_inventory::ProductService::new().create_product(pool, product).await?.base.id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"inventory:products:write\")")]
+    async fn update_product(&self, ctx: &Context<'_>, id: String, input: ProductInput) -> async_graphql::Result<Product> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let id = parse_id("id", &id)?;
+        let category_id = parse_optional_id("categoryId", input.category_id.as_deref())?;
+        write(ctx, "product", "updated", async {
+            let svc = erp_inventory::ProductService::new();
+            let mut product = svc.get_product(pool, id).await?;
+            product.sku = input.sku;
+            product.name = input.name;
+            product.description = input.description;
+            product.product_type = product_type(input.product_type.as_deref());
+            product.category_id = category_id;
+            product.unit_of_measure = input.unit_of_measure;
+            Ok(svc.update_product(pool, product).await?.base.id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"inventory:warehouses:write\")")]
+    async fn create_warehouse(&self, ctx: &Context<'_>, input: WarehouseInput) -> async_graphql::Result<Warehouse> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let warehouse = erp_inventory::Warehouse {
+            base: BaseEntity::new(),
+            code: input.code,
+            name: input.name,
+            address: Address {
+                street: input.street.unwrap_or_default(),
+                city: input.city.unwrap_or_default(),
+                state: input.state,
+                postal_code: input.postal_code.unwrap_or_default(),
+                country: input.country.unwrap_or_default(),
+            },
+            status: Status::Active,
+        };
+        write(ctx, "warehouse", "created", async {
+            Ok(erp_inventory::WarehouseService::new().create_warehouse(pool, warehouse).await?.base.id)
+        }).await
+    }
+
+    /// Records a stock movement and returns the product, whose `stock` reflects it.
+    #[graphql(guard = "Require(\"inventory:stock:adjust\")")]
+    async fn record_stock_movement(&self, ctx: &Context<'_>, input: StockMovementInput) -> async_graphql::Result<Product> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let movement = erp_inventory::StockMovement {
+            base: BaseEntity::new(),
+            movement_number: String::new(),
+            movement_type: match input.movement_type.as_deref() {
+                Some("Issue") => erp_inventory::MovementType::Issue,
+                Some("Transfer") => erp_inventory::MovementType::Transfer,
+                Some("Adjustment") => erp_inventory::MovementType::Adjustment,
+                _ => erp_inventory::MovementType::Receipt,
+            },
+            product_id: parse_id("productId", &input.product_id)?,
+            from_location_id: parse_optional_id("fromLocationId", input.from_location_id.as_deref())?,
+            to_location_id: parse_id("toLocationId", &input.to_location_id)?,
+            quantity: input.quantity,
+            reference: input.reference,
+            date: Utc::now(),
+            unit_cost: input.unit_cost,
+            total_cost: None,
+        };
+        write(ctx, "product", "stock_moved", async {
+            Ok(erp_inventory::StockService::new().record_movement(pool, movement).await?.product_id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"sales:customers:write\")")]
+    async fn create_customer(&self, ctx: &Context<'_>, input: CustomerInput) -> async_graphql::Result<Customer> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let customer = erp_sales::Customer {
+            base: BaseEntity { created_by: user_id(ctx)?, ..BaseEntity::new() },
+            code: input.code,
+            name: input.name,
+            contact: ContactInfo { email: input.email, phone: input.phone, fax: None, website: None },
+            billing_address: empty_address(),
+            shipping_address: None,
+            credit_limit: input.credit_limit.map(|v| Money::new(v, Currency::USD)),
+            payment_terms: input.payment_terms.unwrap_or(30),
+            status: Status::Active,
+        };
+        write(ctx, "customer", "created", async {
+            Ok(erp_sales::CustomerService::new().create(pool, customer).await?.base.id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"sales:orders:write\")")]
+    async fn create_sales_order(&self, ctx: &Context<'_>, input: SalesOrderInput) -> async_graphql::Result<SalesOrder> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let mut lines = Vec::with_capacity(input.lines.len());
+        for line in input.lines {
+            lines.push(erp_sales::SalesOrderLine {
+                id: Uuid::nil(),
+                product_id: parse_id("productId", &line.product_id)?,
+                description: line.description,
+                quantity: line.quantity,
+                unit_price: Money::new(line.unit_price, Currency::USD),
+                discount_percent: 0.0,
+                tax_rate: 0.0,
+                line_total: Money::new(line.quantity * line.unit_price, Currency::USD),
+            });
+        }
+        let order = erp_sales::SalesOrder {
+            base: BaseEntity { created_by: user_id(ctx)?, ..BaseEntity::new() },
+            order_number: String::new(),
+            customer_id: parse_id("customerId", &input.customer_id)?,
+            order_date: Utc::now(),
+            required_date: None,
+            lines,
+            subtotal: Money::zero(Currency::USD),
+            tax_amount: Money::zero(Currency::USD),
+            total: Money::zero(Currency::USD),
+            status: Status::Draft,
+        };
+        write(ctx, "sales_order", "created", async {
+            Ok(erp_sales::SalesOrderService::new().create(pool, order).await?.base.id)
+        }).await
+    }
+
+    /// Confirms the order and reserves its stock, from `warehouseId` when given.
+    #[graphql(guard = "Require(\"sales:orders:approve\")")]
+    async fn confirm_sales_order(&self, ctx: &Context<'_>, id: String, warehouse_id: Option<String>) -> async_graphql::Result<SalesOrder> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let id = parse_id("id", &id)?;
+        let warehouse_id = parse_optional_id("warehouseId", warehouse_id.as_deref())?;
+        write(ctx, "sales_order", "confirmed", async {
+            erp_sales::SalesOrderService::new().confirm_from_warehouse(pool, id, warehouse_id).await.map(|_| id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"sales:orders:write\")")]
+    async fn ship_sales_order(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<SalesOrder> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let id = parse_id("id", &id)?;
+        write(ctx, "sales_order", "shipped", async {
+            erp_sales::SalesOrderService::new().ship(pool, id).await.map(|_| id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"sales:orders:write\")")]
+    async fn cancel_sales_order(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<SalesOrder> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let id = parse_id("id", &id)?;
+        write(ctx, "sales_order", "cancelled", async {
+            erp_sales::SalesOrderService::new().cancel(pool, id).await.map(|_| id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"purchasing:vendors:write\")")]
+    async fn create_vendor(&self, ctx: &Context<'_>, input: VendorInput) -> async_graphql::Result<Vendor> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let vendor = erp_purchasing::Vendor {
+            base: BaseEntity { created_by: user_id(ctx)?, ..BaseEntity::new() },
+            code: input.code,
+            name: input.name,
+            contact: ContactInfo { email: input.email, phone: input.phone, fax: None, website: None },
+            address: empty_address(),
+            payment_terms: input.payment_terms.unwrap_or(30),
+            status: Status::Active,
+        };
+        write(ctx, "vendor", "created", async {
+            Ok(erp_purchasing::VendorService::new().create(pool, vendor).await?.base.id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"purchasing:orders:write\")")]
+    async fn create_purchase_order(&self, ctx: &Context<'_>, input: PurchaseOrderInput) -> async_graphql::Result<PurchaseOrder> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let mut lines = Vec::with_capacity(input.lines.len());
+        for line in input.lines {
+            lines.push(erp_purchasing::PurchaseOrderLine {
+                id: Uuid::nil(),
+                product_id: parse_id("productId", &line.product_id)?,
+                description: line.description,
+                quantity: line.quantity,
+                unit_price: Money::new(line.unit_price, Currency::USD),
+                tax_rate: 0.0,
+                line_total: Money::new(line.quantity * line.unit_price, Currency::USD),
+            });
+        }
+        let order = erp_purchasing::PurchaseOrder {
+            base: BaseEntity { created_by: user_id(ctx)?, ..BaseEntity::new() },
+            po_number: String::new(),
+            vendor_id: parse_id("vendorId", &input.vendor_id)?,
+            order_date: Utc::now(),
+            expected_date: None,
+            lines,
+            subtotal: Money::zero(Currency::USD),
+            tax_amount: Money::zero(Currency::USD),
+            total: Money::zero(Currency::USD),
+            status: Status::Draft,
+        };
+        write(ctx, "purchase_order", "created", async {
+            Ok(erp_purchasing::PurchaseOrderService::new().create(pool, order).await?.base.id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"purchasing:orders:write\")")]
+    async fn submit_purchase_order(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<PurchaseOrder> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let id = parse_id("id", &id)?;
+        write(ctx, "purchase_order", "submitted", async {
+            erp_purchasing::PurchaseOrderService::new().submit(pool, id).await.map(|_| id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"purchasing:orders:approve\")")]
+    async fn approve_purchase_order(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<PurchaseOrder> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let id = parse_id("id", &id)?;
+        write(ctx, "purchase_order", "approved", async {
+            erp_purchasing::PurchaseOrderService::new().approve(pool, id).await.map(|_| id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"manufacturing:boms:write\")")]
+    async fn create_bom(&self, ctx: &Context<'_>, input: BomInput) -> async_graphql::Result<BillOfMaterial> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let mut components = Vec::with_capacity(input.components.len());
+        for component in input.components {
+            components.push(erp_manufacturing::BomComponent {
+                id: Uuid::nil(),
+                product_id: parse_id("productId", &component.product_id)?,
+                quantity: component.quantity,
+                unit: component.unit,
+                scrap_percent: 0.0,
+            });
+        }
+        let bom = erp_manufacturing::BillOfMaterial {
+            base: BaseEntity { created_by: user_id(ctx)?, ..BaseEntity::new() },
+            product_id: parse_id("productId", &input.product_id)?,
+            name: input.name,
+            version: "1.0".to_string(),
+            quantity: input.quantity,
+            components,
+            operations: vec![],
+            status: Status::Draft,
+        };
+        write(ctx, "bom", "created", async {
+            Ok(erp_manufacturing::BillOfMaterialService::new().create(pool, bom).await?.base.id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"manufacturing:workorders:write\")")]
+    async fn create_work_order(&self, ctx: &Context<'_>, input: WorkOrderInput) -> async_graphql::Result<WorkOrder> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let order = erp_manufacturing::WorkOrder {
+            base: BaseEntity { created_by: user_id(ctx)?, ..BaseEntity::new() },
+            order_number: String::new(),
+            product_id: parse_id("productId", &input.product_id)?,
+            bom_id: parse_id("bomId", &input.bom_id)?,
+            quantity: input.quantity,
+            planned_start: parse_datetime("plannedStart", &input.planned_start)?,
+            planned_end: parse_datetime("plannedEnd", &input.planned_end)?,
+            actual_start: None,
+            actual_end: None,
+            status: Status::Draft,
+        };
+        write(ctx, "work_order", "created", async {
+            Ok(erp_manufacturing::WorkOrderService::new().create(pool, order).await?.base.id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"manufacturing:workorders:write\")")]
+    async fn start_work_order(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<WorkOrder> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let id = parse_id("id", &id)?;
+        write(ctx, "work_order", "started", async {
+            erp_manufacturing::WorkOrderService::new().start(pool, id).await.map(|_| id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"manufacturing:workorders:write\")")]
+    async fn complete_work_order(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<WorkOrder> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let id = parse_id("id", &id)?;
+        write(ctx, "work_order", "completed", async {
+            erp_manufacturing::WorkOrderService::new().complete(pool, id).await.map(|_| id)
+        }).await
+    }
+
+    #[graphql(guard = "Require(\"hr:employees:write\")")]
+    async fn create_employee(&self, ctx: &Context<'_>, input: EmployeeInput) -> async_graphql::Result<Employee> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let hire_date = NaiveDate::parse_from_str(&input.hire_date, "%Y-%m-%d")
+            .map_err(|_| to_graphql_error(erp_core::Error::validation("Invalid hireDate format, expected YYYY-MM-DD")))?;
+        let employee = erp_hr::Employee {
+            base: BaseEntity { created_by: user_id(ctx)?, ..BaseEntity::new() },
+            employee_number: input.employee_number,
+            first_name: input.first_name,
+            last_name: input.last_name,
+            email: input.email.clone(),
+            contact: ContactInfo { email: Some(input.email), phone: input.phone, fax: None, website: None },
+            address: empty_address(),
+            birth_date: NaiveDate::from_ymd_opt(1970, 1, 1).unwrap_or(hire_date),
+            hire_date,
+            termination_date: None,
+            department_id: None,
+            position_id: None,
+            manager_id: None,
+            status: Status::Active,
+        };
+        write(ctx, "employee", "created", async {
+            Ok(erp_hr::EmployeeService::new().create(pool, employee).await?.base.id)
+        }).await
+    }
+}
@@ -0,0 +1,166 @@
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::{Require, Viewer, CUSTOMERS, EMPLOYEES, SALES_ORDERS};
+use crate::record::{db_error, find, paginate, Page, Paged, Record};
+use crate::schema::*;
+
+pub struct QueryRoot;
+
+async fn page<T: Paged>(ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<T>> {
+    paginate(ctx.data::<SqlitePool>()?, ctx.data::<Viewer>()?, first, after).await
+}
+
+async fn one<T: Record>(ctx: &Context<'_>, id: &str) -> async_graphql::Result<Option<T>> {
+    find(ctx.data::<SqlitePool>()?, ctx.data::<Viewer>()?, id).await
+}
+
+#[Object]
+impl QueryRoot {
+    #[graphql(guard = "Require(\"finance:accounts:read\")")]
+    async fn accounts(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<Account>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"finance:accounts:read\")")]
+    async fn account(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<Account>> {
+        one(ctx, &id).await
+    }
+
+    #[graphql(guard = "Require(\"finance:journals:read\")")]
+    async fn journal_entries(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<JournalEntry>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"finance:journals:read\")")]
+    async fn journal_entry(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<JournalEntry>> {
+        one(ctx, &id).await
+    }
+
+    #[graphql(guard = "Require(\"inventory:products:read\")")]
+    async fn products(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<Product>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"inventory:products:read\")")]
+    async fn product(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<Product>> {
+        one(ctx, &id).await
+    }
+
+    #[graphql(guard = "Require(\"inventory:warehouses:read\")")]
+    async fn warehouses(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<Warehouse>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"sales:customers:read\")")]
+    async fn customers(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<Customer>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"sales:customers:read\")")]
+    async fn customer(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<Customer>> {
+        one(ctx, &id).await
+    }
+
+    #[graphql(guard = "Require(\"sales:orders:read\")")]
+    async fn sales_orders(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<SalesOrder>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"sales:orders:read\")")]
+    async fn sales_order(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<SalesOrder>> {
+        one(ctx, &id).await
+    }
+
+    #[graphql(guard = "Require(\"purchasing:vendors:read\")")]
+    async fn vendors(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<Vendor>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"purchasing:vendors:read\")")]
+    async fn vendor(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<Vendor>> {
+        one(ctx, &id).await
+    }
+
+    #[graphql(guard = "Require(\"purchasing:orders:read\")")]
+    async fn purchase_orders(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<PurchaseOrder>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"purchasing:orders:read\")")]
+    async fn purchase_order(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<PurchaseOrder>> {
+        one(ctx, &id).await
+    }
+
+    #[graphql(guard = "Require(\"manufacturing:boms:read\")")]
+    async fn boms(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<BillOfMaterial>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"manufacturing:boms:read\")")]
+    async fn bom(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<BillOfMaterial>> {
+        one(ctx, &id).await
+    }
+
+    #[graphql(guard = "Require(\"manufacturing:workorders:read\")")]
+    async fn work_orders(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<WorkOrder>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"manufacturing:workorders:read\")")]
+    async fn work_order(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<WorkOrder>> {
+        one(ctx, &id).await
+    }
+
+    #[graphql(guard = "Require(\"hr:employees:read\")")]
+    async fn employees(&self, ctx: &Context<'_>, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<Employee>> {
+        page(ctx, first, after).await
+    }
+
+    #[graphql(guard = "Require(\"hr:employees:read\")")]
+    async fn employee(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<Employee>> {
+        one(ctx, &id).await
+    }
+
+    /// Counts within the viewer's row scope for customers, sales orders and employees.
+    #[graphql(guard = "Require(\"sales:orders:read\").and(Require(\"inventory:products:read\"))")]
+    async fn dashboard_stats(&self, ctx: &Context<'_>) -> async_graphql::Result<DashboardStats> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let viewer = ctx.data::<Viewer>()?;
+        let orders = viewer.scope(SALES_ORDERS);
+        let month_start = chrono::Utc::now().format("%Y-%m-01").to_string();
+
+        let count = |table: &'static str, resource: Option<&'static str>| async move {
+            let scope = resource.map(|r| viewer.scope(r)).unwrap_or_default();
+            let sql = format!("SELECT COUNT(*) FROM {} WHERE 1 = 1{}", table, scope.sql());
+            scope.bind_query_as(sqlx::query_as::<_, (i64,)>(&sql)).fetch_one(pool).await.map(|r| r.0).map_err(db_error)
+        };
+        let total_products = count("products", None).await?;
+        let total_vendors = count("vendors", None).await?;
+        let total_customers = count("customers", Some(CUSTOMERS)).await?;
+        let total_employees = count("employees", Some(EMPLOYEES)).await?;
+
+        let sql = format!(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(CASE WHEN status IN ('Draft', 'Pending') THEN 1 ELSE 0 END), 0),
+                    COALESCE(SUM(CASE WHEN order_date >= ? AND status IN ('Approved', 'Completed') THEN total ELSE 0 END), 0),
+                    COALESCE(SUM(CASE WHEN order_date >= ? THEN 1 ELSE 0 END), 0)
+             FROM sales_orders WHERE 1 = 1{}",
+            orders.sql()
+        );
+        let (total_orders, pending_orders, revenue_this_month, orders_this_month): (i64, i64, i64, i64) =
+            orders.bind_query_as(sqlx::query_as(&sql).bind(&month_start).bind(&month_start))
+                .fetch_one(pool).await.map_err(db_error)?;
+
+        Ok(DashboardStats {
+            total_products,
+            total_customers,
+            total_orders,
+            total_vendors,
+            total_employees,
+            pending_orders,
+            revenue_this_month,
+            orders_this_month,
+        })
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+
+use async_graphql::connection::{Connection, CursorType, Edge, OpaqueCursor};
+use async_graphql::{OutputType, SimpleObject};
+use erp_core::RowScope;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, SqlitePool};
+
+use crate::auth::{to_graphql_error, Viewer};
+
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// A GraphQL object backed by one table.
+pub trait Record: for<'r> FromRow<'r, SqliteRow> + Send + Sync + Unpin + Clone + 'static {
+    const TABLE: &'static str;
+    /// Select list producing the struct's fields, e.g. `id, code, total_currency AS currency`.
+    const COLUMNS: &'static str;
+    /// Row and field permission resource, for tables the access policy covers.
+    const RESOURCE: Option<&'static str> = None;
+
+    fn id(&self) -> &str;
+
+    /// Clears the fields named in `hidden`. Only records with a `RESOURCE` have hideable fields.
+    fn redact(&mut self, _hidden: &HashSet<String>) {}
+}
+
+/// A record listed newest first with keyset pagination.
+pub trait Paged: Record + OutputType {
+    fn created_at(&self) -> &str;
+}
+
+/// A record loaded in batches by the id of the record it belongs to.
+pub trait Child: Record {
+    const PARENT: &'static str;
+
+    fn parent(&self) -> Option<&str>;
+}
+
+/// Clears the named `Option` fields of `$row` that appear in `$hidden`.
+macro_rules! redact_fields {
+    ($row:expr, $hidden:expr, $($field:ident),+ $(,)?) => {
+        $(
+            if $hidden.contains(stringify!($field)) {
+                $row.$field = None;
+            }
+        )+
+    };
+}
+pub(crate) use redact_fields;
+
+pub fn db_error(e: sqlx::Error) -> async_graphql::Error {
+    to_graphql_error(e.into())
+}
+
+/// Applies the viewer's field permissions to a fetched row.
+pub fn present<T: Record>(viewer: &Viewer, mut row: T) -> T {
+    if let Some(hidden) = T::RESOURCE.and_then(|resource| viewer.hidden_fields(resource)) {
+        row.redact(hidden);
+    }
+    row
+}
+
+pub fn scope_for<T: Record>(viewer: &Viewer) -> RowScope {
+    T::RESOURCE.map(|resource| viewer.scope(resource)).unwrap_or_default()
+}
+
+/// Rows of `T` within `scope` whose `column` is one of `values`. Fields are not redacted yet
+/// so callers can still group by them.
+pub async fn fetch_where<T: Record>(pool: &SqlitePool, scope: &RowScope, column: &str, values: &[String]) -> async_graphql::Result<Vec<T>> {
+    if values.is_empty() {
+        return Ok(Vec::new());
+    }
+    let sql = format!(
+        "SELECT {} FROM {} WHERE {} IN ({}){} ORDER BY rowid",
+        T::COLUMNS, T::TABLE, column, vec!["?"; values.len()].join(", "), scope.sql()
+    );
+    let mut query = sqlx::query_as::<_, T>(&sql);
+    for value in values {
+        query = query.bind(value);
+    }
+    scope.bind_query_as(query).fetch_all(pool).await.map_err(db_error)
+}
+
+pub async fn find<T: Record>(pool: &SqlitePool, viewer: &Viewer, id: &str) -> async_graphql::Result<Option<T>> {
+    let rows = fetch_where::<T>(pool, &scope_for::<T>(viewer), "id", &[id.to_string()]).await?;
+    Ok(rows.into_iter().next().map(|row| present(viewer, row)))
+}
+
+/// Reads back a record the viewer just wrote. Like the REST endpoints this ignores row scope,
+/// but hidden fields stay hidden.
+pub async fn reload<T: Record>(pool: &SqlitePool, viewer: &Viewer, id: impl ToString) -> async_graphql::Result<T> {
+    let id = id.to_string();
+    let rows = fetch_where::<T>(pool, &RowScope::unrestricted(), "id", std::slice::from_ref(&id)).await?;
+    rows.into_iter().next()
+        .map(|row| present(viewer, row))
+        .ok_or_else(|| to_graphql_error(erp_core::Error::not_found(T::TABLE, &id)))
+}
+
+/// Position of a record in a newest-first listing.
+#[derive(Serialize, Deserialize)]
+pub struct Cursor {
+    pub created_at: String,
+    pub id: String,
+}
+
+#[derive(SimpleObject)]
+pub struct TotalCount {
+    pub total_count: i64,
+}
+
+pub type Page<T> = Connection<OpaqueCursor<Cursor>, T, TotalCount>;
+
+/// Forward pagination over the records the viewer may see, ordered by `(created_at, id)`
+/// descending so pages stay stable while new rows are inserted.
+pub async fn paginate<T: Paged>(pool: &SqlitePool, viewer: &Viewer, first: Option<i32>, after: Option<String>) -> async_graphql::Result<Page<T>> {
+    let limit = match first {
+        Some(n) if n < 0 => return Err(to_graphql_error(erp_core::Error::validation("first must not be negative"))),
+        Some(n) => (n as usize).min(MAX_PAGE_SIZE),
+        None => DEFAULT_PAGE_SIZE,
+    };
+    let after = match after {
+        Some(cursor) => Some(
+            OpaqueCursor::<Cursor>::decode_cursor(&cursor)
+                .map_err(|_| to_graphql_error(erp_core::Error::validation("Invalid cursor")))?
+                .0,
+        ),
+        None => None,
+    };
+    let scope = scope_for::<T>(viewer);
+
+    let count_sql = format!("SELECT COUNT(*) FROM {} WHERE 1 = 1{}", T::TABLE, scope.sql());
+    let (total_count,): (i64,) = scope.bind_query_as(sqlx::query_as(&count_sql)).fetch_one(pool).await.map_err(db_error)?;
+
+    let keyset = if after.is_some() { " AND (created_at < ? OR (created_at = ? AND id < ?))" } else { "" };
+    let sql = format!(
+        "SELECT {} FROM {} WHERE 1 = 1{}{} ORDER BY created_at DESC, id DESC LIMIT ?",
+        T::COLUMNS, T::TABLE, scope.sql(), keyset
+    );
+    let mut query = scope.bind_query_as(sqlx::query_as::<_, T>(&sql));
+    if let Some(cursor) = &after {
+        query = query.bind(&cursor.created_at).bind(&cursor.created_at).bind(&cursor.id);
+    }
+    let mut rows = query.bind(limit as i64 + 1).fetch_all(pool).await.map_err(db_error)?;
+
+    let has_next_page = rows.len() > limit;
+    rows.truncate(limit);
+    let mut connection = Connection::with_additional_fields(after.is_some(), has_next_page, TotalCount { total_count });
+    connection.edges.extend(rows.into_iter().map(|row| {
+        let cursor = Cursor { created_at: row.created_at().to_string(), id: row.id().to_string() };
+        Edge::new(OpaqueCursor(cursor), present(viewer, row))
+    }));
+    Ok(connection)
+}
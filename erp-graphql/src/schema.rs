@@ -1,121 +1,608 @@
-#![allow(clippy::type_complexity)]
-use async_graphql::{Object, Context, SimpleObject, InputObject, Enum};
-use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+use async_graphql::{ComplexObject, Context, SimpleObject};
+
+use crate::auth::{Require, Viewer, CUSTOMERS, EMPLOYEES, SALES_ORDERS};
+use crate::loaders::{ById, ChildrenOf, Loaders};
+use crate::record::{redact_fields, Child, Paged, Record};
+
+macro_rules! paged {
+    ($ty:ty) => {
+        impl Paged for $ty {
+            fn created_at(&self) -> &str {
+                &self.created_at
+            }
+        }
+    };
+}
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum SortOrder {
-    Asc,
-    Desc,
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct Account {
+    pub id: String,
+    pub code: String,
+    pub name: String,
+    pub account_type: String,
+    pub parent_id: Option<String>,
+    pub status: String,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
 }
 
-#[derive(InputObject)]
-pub struct PaginationInput {
-    pub page: i32,
-    pub per_page: i32,
+impl Record for Account {
+    const TABLE: &'static str = "accounts";
+    const COLUMNS: &'static str = "id, code, name, account_type, parent_id, status, description, created_at, updated_at";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+paged!(Account);
+
+#[ComplexObject]
+impl Account {
+    async fn parent(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Account>> {
+        match &self.parent_id {
+            Some(id) => ctx.data::<Loaders>()?.load_one(ById::new(id)).await,
+            None => Ok(None),
+        }
+    }
 }
 
-#[derive(SimpleObject)]
-pub struct PageInfo {
-    pub page: i32,
-    pub per_page: i32,
-    pub total_pages: i32,
-    pub total_items: i64,
-    pub has_next: bool,
-    pub has_prev: bool,
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct JournalEntry {
+    pub id: String,
+    pub entry_number: String,
+    pub date: String,
+    pub description: String,
+    pub reference: Option<String>,
+    pub status: String,
+    pub created_at: String,
 }
 
-#[derive(SimpleObject)]
+impl Record for JournalEntry {
+    const TABLE: &'static str = "journal_entries";
+    const COLUMNS: &'static str = "id, entry_number, date, description, reference, status, created_at";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+paged!(JournalEntry);
+
+#[ComplexObject]
+impl JournalEntry {
+    async fn lines(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<JournalLine>> {
+        Ok(ctx.data::<Loaders>()?.load_one(ChildrenOf::new(&self.id)).await?.unwrap_or_default())
+    }
+}
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct JournalLine {
+    pub id: String,
+    pub journal_entry_id: String,
+    pub account_id: String,
+    pub debit: i64,
+    pub credit: i64,
+    pub description: Option<String>,
+}
+
+impl Record for JournalLine {
+    const TABLE: &'static str = "journal_lines";
+    const COLUMNS: &'static str = "id, journal_entry_id, account_id, debit, credit, description";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Child for JournalLine {
+    const PARENT: &'static str = "journal_entry_id";
+
+    fn parent(&self) -> Option<&str> {
+        Some(&self.journal_entry_id)
+    }
+}
+
+#[ComplexObject]
+impl JournalLine {
+    #[graphql(guard = "Require(\"finance:accounts:read\")")]
+    async fn account(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Account>> {
+        ctx.data::<Loaders>()?.load_one(ById::new(&self.account_id)).await
+    }
+}
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
 pub struct Product {
     pub id: String,
     pub sku: String,
     pub name: String,
     pub description: Option<String>,
+    pub product_type: String,
     pub unit_of_measure: String,
     pub status: String,
     pub created_at: String,
     pub updated_at: String,
 }
 
-#[derive(SimpleObject)]
-pub struct ProductConnection {
-    pub items: Vec<Product>,
-    pub page_info: PageInfo,
+impl Record for Product {
+    const TABLE: &'static str = "products";
+    const COLUMNS: &'static str = "id, sku, name, description, product_type, unit_of_measure, status, created_at, updated_at";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
 }
+paged!(Product);
 
-#[derive(SimpleObject)]
-pub struct Customer {
+#[ComplexObject]
+impl Product {
+    #[graphql(guard = "Require(\"inventory:stock:read\")")]
+    async fn stock(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<StockLevel>> {
+        Ok(ctx.data::<Loaders>()?.load_one(ChildrenOf::new(&self.id)).await?.unwrap_or_default())
+    }
+}
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+pub struct Warehouse {
     pub id: String,
     pub code: String,
     pub name: String,
+    pub address_city: Option<String>,
+    pub address_country: Option<String>,
+    pub status: String,
+    pub created_at: String,
+}
+
+impl Record for Warehouse {
+    const TABLE: &'static str = "warehouses";
+    const COLUMNS: &'static str = "id, code, name, address_city, address_country, status, created_at";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+paged!(Warehouse);
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+pub struct StockLevel {
+    pub id: String,
+    pub product_id: String,
+    pub location_id: String,
+    pub quantity: i64,
+    pub reserved_quantity: i64,
+    pub available_quantity: i64,
+}
+
+impl Record for StockLevel {
+    const TABLE: &'static str = "stock_levels";
+    const COLUMNS: &'static str = "id, product_id, location_id, quantity, reserved_quantity, available_quantity";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Child for StockLevel {
+    const PARENT: &'static str = "product_id";
+
+    fn parent(&self) -> Option<&str> {
+        Some(&self.product_id)
+    }
+}
+
+/// Fields other than `id` are null when the viewer's field permissions hide them.
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct Customer {
+    pub id: String,
+    pub code: Option<String>,
+    pub name: Option<String>,
     pub email: Option<String>,
     pub phone: Option<String>,
-    pub status: String,
+    pub billing_city: Option<String>,
+    pub billing_country: Option<String>,
     pub credit_limit: Option<i64>,
+    pub payment_terms: Option<i64>,
+    pub status: Option<String>,
+    #[graphql(skip)]
     pub created_at: String,
-    pub updated_at: String,
 }
 
-#[derive(SimpleObject)]
-pub struct CustomerConnection {
-    pub items: Vec<Customer>,
-    pub page_info: PageInfo,
+impl Record for Customer {
+    const TABLE: &'static str = "customers";
+    const COLUMNS: &'static str = "id, code, name, email, phone, billing_city, billing_country, credit_limit, payment_terms, status, created_at";
+    const RESOURCE: Option<&'static str> = Some(CUSTOMERS);
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn redact(&mut self, hidden: &HashSet<String>) {
+        redact_fields!(self, hidden, code, name, email, phone, billing_city, billing_country, credit_limit, payment_terms, status);
+    }
 }
+paged!(Customer);
 
-#[derive(SimpleObject)]
-pub struct Order {
+#[ComplexObject]
+impl Customer {
+    #[graphql(guard = "Require(\"sales:orders:read\")")]
+    async fn orders(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<SalesOrder>> {
+        Ok(ctx.data::<Loaders>()?.load_one(ChildrenOf::new(&self.id)).await?.unwrap_or_default())
+    }
+}
+
+/// Fields other than `id` are null when the viewer's field permissions hide them.
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct SalesOrder {
     pub id: String,
-    pub order_number: String,
-    pub customer_id: String,
-    pub status: String,
-    pub total_amount: i64,
-    pub currency: String,
-    pub order_date: String,
+    pub order_number: Option<String>,
+    pub customer_id: Option<String>,
+    pub order_date: Option<String>,
+    pub status: Option<String>,
+    pub subtotal: Option<i64>,
+    pub tax_amount: Option<i64>,
+    pub total: Option<i64>,
+    pub currency: Option<String>,
+    #[graphql(skip)]
     pub created_at: String,
 }
 
-#[derive(SimpleObject)]
-pub struct OrderConnection {
-    pub items: Vec<Order>,
-    pub page_info: PageInfo,
+impl Record for SalesOrder {
+    const TABLE: &'static str = "sales_orders";
+    const COLUMNS: &'static str =
+        "id, order_number, customer_id, order_date, status, subtotal, tax_amount, total, total_currency AS currency, created_at";
+    const RESOURCE: Option<&'static str> = Some(SALES_ORDERS);
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn redact(&mut self, hidden: &HashSet<String>) {
+        redact_fields!(self, hidden, order_number, customer_id, order_date, status, subtotal, tax_amount, total, currency);
+    }
 }
+paged!(SalesOrder);
 
-#[derive(SimpleObject)]
+impl Child for SalesOrder {
+    const PARENT: &'static str = "customer_id";
+
+    fn parent(&self) -> Option<&str> {
+        self.customer_id.as_deref()
+    }
+}
+
+#[ComplexObject]
+impl SalesOrder {
+    #[graphql(guard = "Require(\"sales:customers:read\")")]
+    async fn customer(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Customer>> {
+        match &self.customer_id {
+            Some(id) => ctx.data::<Loaders>()?.load_one(ById::new(id)).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn lines(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Vec<SalesOrderLine>>> {
+        if ctx.data::<Viewer>()?.hidden_fields(SALES_ORDERS).is_some_and(|hidden| hidden.contains("lines")) {
+            return Ok(None);
+        }
+        Ok(Some(ctx.data::<Loaders>()?.load_one(ChildrenOf::new(&self.id)).await?.unwrap_or_default()))
+    }
+}
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct SalesOrderLine {
+    pub id: String,
+    pub sales_order_id: String,
+    pub product_id: String,
+    pub description: String,
+    pub quantity: i64,
+    pub unit_price: i64,
+    pub discount_percent: f64,
+    pub tax_rate: f64,
+    pub line_total: i64,
+}
+
+impl Record for SalesOrderLine {
+    const TABLE: &'static str = "sales_order_lines";
+    const COLUMNS: &'static str = "id, sales_order_id, product_id, description, quantity, unit_price, discount_percent, tax_rate, line_total";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Child for SalesOrderLine {
+    const PARENT: &'static str = "sales_order_id";
+
+    fn parent(&self) -> Option<&str> {
+        Some(&self.sales_order_id)
+    }
+}
+
+#[ComplexObject]
+impl SalesOrderLine {
+    #[graphql(guard = "Require(\"inventory:products:read\")")]
+    async fn product(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Product>> {
+        ctx.data::<Loaders>()?.load_one(ById::new(&self.product_id)).await
+    }
+}
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
 pub struct Vendor {
     pub id: String,
     pub code: String,
     pub name: String,
     pub email: Option<String>,
     pub phone: Option<String>,
+    pub payment_terms: i64,
     pub status: String,
-    pub payment_terms: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
-#[derive(SimpleObject)]
-pub struct VendorConnection {
-    pub items: Vec<Vendor>,
-    pub page_info: PageInfo,
+impl Record for Vendor {
+    const TABLE: &'static str = "vendors";
+    const COLUMNS: &'static str = "id, code, name, email, phone, payment_terms, status, created_at, updated_at";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
 }
+paged!(Vendor);
 
-#[derive(SimpleObject)]
-pub struct Employee {
+#[ComplexObject]
+impl Vendor {
+    #[graphql(guard = "Require(\"purchasing:orders:read\")")]
+    async fn purchase_orders(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<PurchaseOrder>> {
+        Ok(ctx.data::<Loaders>()?.load_one(ChildrenOf::new(&self.id)).await?.unwrap_or_default())
+    }
+}
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct PurchaseOrder {
+    pub id: String,
+    pub po_number: String,
+    pub vendor_id: String,
+    pub order_date: String,
+    pub expected_date: Option<String>,
+    pub status: String,
+    pub subtotal: i64,
+    pub tax_amount: i64,
+    pub total: i64,
+    pub currency: Option<String>,
+    pub created_at: String,
+}
+
+impl Record for PurchaseOrder {
+    const TABLE: &'static str = "purchase_orders";
+    const COLUMNS: &'static str =
+        "id, po_number, vendor_id, order_date, expected_date, status, subtotal, tax_amount, total, currency, created_at";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+paged!(PurchaseOrder);
+
+impl Child for PurchaseOrder {
+    const PARENT: &'static str = "vendor_id";
+
+    fn parent(&self) -> Option<&str> {
+        Some(&self.vendor_id)
+    }
+}
+
+#[ComplexObject]
+impl PurchaseOrder {
+    #[graphql(guard = "Require(\"purchasing:vendors:read\")")]
+    async fn vendor(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Vendor>> {
+        ctx.data::<Loaders>()?.load_one(ById::new(&self.vendor_id)).await
+    }
+
+    async fn lines(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<PurchaseOrderLine>> {
+        Ok(ctx.data::<Loaders>()?.load_one(ChildrenOf::new(&self.id)).await?.unwrap_or_default())
+    }
+}
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct PurchaseOrderLine {
+    pub id: String,
+    pub purchase_order_id: String,
+    pub product_id: String,
+    pub description: String,
+    pub quantity: i64,
+    pub unit_price: i64,
+    pub tax_rate: f64,
+    pub line_total: i64,
+}
+
+impl Record for PurchaseOrderLine {
+    const TABLE: &'static str = "purchase_order_lines";
+    const COLUMNS: &'static str = "id, purchase_order_id, product_id, description, quantity, unit_price, tax_rate, line_total";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Child for PurchaseOrderLine {
+    const PARENT: &'static str = "purchase_order_id";
+
+    fn parent(&self) -> Option<&str> {
+        Some(&self.purchase_order_id)
+    }
+}
+
+#[ComplexObject]
+impl PurchaseOrderLine {
+    #[graphql(guard = "Require(\"inventory:products:read\")")]
+    async fn product(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Product>> {
+        ctx.data::<Loaders>()?.load_one(ById::new(&self.product_id)).await
+    }
+}
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct BillOfMaterial {
+    pub id: String,
+    pub product_id: String,
+    pub name: String,
+    pub version: String,
+    pub quantity: i64,
+    pub status: String,
+    pub created_at: String,
+}
+
+impl Record for BillOfMaterial {
+    const TABLE: &'static str = "bills_of_material";
+    const COLUMNS: &'static str = "id, product_id, name, version, quantity, status, created_at";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+paged!(BillOfMaterial);
+
+#[ComplexObject]
+impl BillOfMaterial {
+    #[graphql(guard = "Require(\"inventory:products:read\")")]
+    async fn product(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Product>> {
+        ctx.data::<Loaders>()?.load_one(ById::new(&self.product_id)).await
+    }
+
+    async fn components(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<BomComponent>> {
+        Ok(ctx.data::<Loaders>()?.load_one(ChildrenOf::new(&self.id)).await?.unwrap_or_default())
+    }
+}
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct BomComponent {
+    pub id: String,
+    pub bom_id: String,
+    pub product_id: String,
+    pub quantity: i64,
+    pub unit: String,
+    pub scrap_percent: f64,
+}
+
+impl Record for BomComponent {
+    const TABLE: &'static str = "bom_components";
+    const COLUMNS: &'static str = "id, bom_id, product_id, quantity, unit, scrap_percent";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Child for BomComponent {
+    const PARENT: &'static str = "bom_id";
+
+    fn parent(&self) -> Option<&str> {
+        Some(&self.bom_id)
+    }
+}
+
+#[ComplexObject]
+impl BomComponent {
+    #[graphql(guard = "Require(\"inventory:products:read\")")]
+    async fn product(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Product>> {
+        ctx.data::<Loaders>()?.load_one(ById::new(&self.product_id)).await
+    }
+}
+
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct WorkOrder {
     pub id: String,
-    pub employee_number: String,
-    pub first_name: String,
-    pub last_name: String,
-    pub email: String,
-    pub department: Option<String>,
-    pub position: Option<String>,
+    pub order_number: String,
+    pub product_id: String,
+    pub bom_id: String,
+    pub quantity: i64,
+    pub planned_start: String,
+    pub planned_end: String,
+    pub actual_start: Option<String>,
+    pub actual_end: Option<String>,
     pub status: String,
+    pub created_at: String,
+}
+
+impl Record for WorkOrder {
+    const TABLE: &'static str = "work_orders";
+    const COLUMNS: &'static str =
+        "id, order_number, product_id, bom_id, quantity, planned_start, planned_end, actual_start, actual_end, status, created_at";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+paged!(WorkOrder);
+
+#[ComplexObject]
+impl WorkOrder {
+    #[graphql(guard = "Require(\"inventory:products:read\")")]
+    async fn product(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Product>> {
+        ctx.data::<Loaders>()?.load_one(ById::new(&self.product_id)).await
+    }
+
+    #[graphql(guard = "Require(\"manufacturing:boms:read\")")]
+    async fn bom(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<BillOfMaterial>> {
+        ctx.data::<Loaders>()?.load_one(ById::new(&self.bom_id)).await
+    }
+}
+
+/// Fields other than `id` are null when the viewer's field permissions hide them.
+#[derive(SimpleObject, sqlx::FromRow, Clone)]
+#[graphql(complex)]
+pub struct Employee {
+    pub id: String,
+    pub employee_number: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
     pub hire_date: Option<String>,
+    pub department_id: Option<String>,
+    pub manager_id: Option<String>,
+    pub status: Option<String>,
+    #[graphql(skip)]
     pub created_at: String,
 }
 
-#[derive(SimpleObject)]
-pub struct EmployeeConnection {
-    pub items: Vec<Employee>,
-    pub page_info: PageInfo,
+impl Record for Employee {
+    const TABLE: &'static str = "employees";
+    const COLUMNS: &'static str =
+        "id, employee_number, first_name, last_name, email, phone, hire_date, department_id, manager_id, status, created_at";
+    const RESOURCE: Option<&'static str> = Some(EMPLOYEES);
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn redact(&mut self, hidden: &HashSet<String>) {
+        redact_fields!(self, hidden, employee_number, first_name, last_name, email, phone, hire_date, department_id, manager_id, status);
+    }
+}
+paged!(Employee);
+
+#[ComplexObject]
+impl Employee {
+    async fn manager(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Employee>> {
+        match &self.manager_id {
+            Some(id) => ctx.data::<Loaders>()?.load_one(ById::new(id)).await,
+            None => Ok(None),
+        }
+    }
 }
 
 #[derive(SimpleObject)]
@@ -129,323 +616,3 @@ pub struct DashboardStats {
     pub revenue_this_month: i64,
     pub orders_this_month: i64,
 }
-
-pub struct QueryRoot;
-
-#[Object]
-impl QueryRoot {
-    async fn products(
-        &self,
-        ctx: &Context<'_>,
-        pagination: Option<PaginationInput>,
-    ) -> async_graphql::Result<ProductConnection> {
-        let pool = ctx.data::<SqlitePool>()?;
-        let page = pagination.as_ref().map(|p| p.page).unwrap_or(1);
-        let per_page = pagination.as_ref().map(|p| p.per_page).unwrap_or(20);
-        let offset = (page - 1) * per_page;
-
-        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM products")
-            .fetch_one(pool)
-            .await?;
-
-        let rows: Vec<(String, String, String, Option<String>, String, String, String, String)> = 
-            sqlx::query_as(
-                "SELECT id, sku, name, description, unit_of_measure, status, created_at, updated_at 
-                 FROM products ORDER BY created_at DESC LIMIT ? OFFSET ?"
-            )
-            .bind(per_page)
-            .bind(offset)
-            .fetch_all(pool)
-            .await?;
-
-        let items = rows.into_iter().map(|row| Product {
-            id: row.0,
-            sku: row.1,
-            name: row.2,
-            description: row.3,
-            unit_of_measure: row.4,
-            status: row.5,
-            created_at: row.6,
-            updated_at: row.7,
-        }).collect();
-
-        let total_pages = ((count.0 as f64) / (per_page as f64)).ceil() as i32;
-
-        Ok(ProductConnection {
-            items,
-            page_info: PageInfo {
-                page,
-                per_page,
-                total_pages,
-                total_items: count.0,
-                has_next: page < total_pages,
-                has_prev: page > 1,
-            },
-        })
-    }
-
-    async fn product(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<Product>> {
-        let pool = ctx.data::<SqlitePool>()?;
-
-        let row: Option<(String, String, String, Option<String>, String, String, String, String)> = 
-            sqlx::query_as(
-                "SELECT id, sku, name, description, unit_of_measure, status, created_at, updated_at 
-                 FROM products WHERE id = ?"
-            )
-            .bind(&id)
-            .fetch_optional(pool)
-            .await?;
-
-        Ok(row.map(|row| Product {
-            id: row.0,
-            sku: row.1,
-            name: row.2,
-            description: row.3,
-            unit_of_measure: row.4,
-            status: row.5,
-            created_at: row.6,
-            updated_at: row.7,
-        }))
-    }
-
-    async fn customers(
-        &self,
-        ctx: &Context<'_>,
-        pagination: Option<PaginationInput>,
-    ) -> async_graphql::Result<CustomerConnection> {
-        let pool = ctx.data::<SqlitePool>()?;
-        let page = pagination.as_ref().map(|p| p.page).unwrap_or(1);
-        let per_page = pagination.as_ref().map(|p| p.per_page).unwrap_or(20);
-        let offset = (page - 1) * per_page;
-
-        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM customers")
-            .fetch_one(pool)
-            .await?;
-
-        let rows: Vec<(String, String, String, Option<String>, Option<String>, String, Option<i64>, String, String)> = 
-            sqlx::query_as(
-                "SELECT id, code, name, email, phone, status, credit_limit, created_at, updated_at 
-                 FROM customers ORDER BY created_at DESC LIMIT ? OFFSET ?"
-            )
-            .bind(per_page)
-            .bind(offset)
-            .fetch_all(pool)
-            .await?;
-
-        let items = rows.into_iter().map(|row| Customer {
-            id: row.0,
-            code: row.1,
-            name: row.2,
-            email: row.3,
-            phone: row.4,
-            status: row.5,
-            credit_limit: row.6,
-            created_at: row.7,
-            updated_at: row.8,
-        }).collect();
-
-        let total_pages = ((count.0 as f64) / (per_page as f64)).ceil() as i32;
-
-        Ok(CustomerConnection {
-            items,
-            page_info: PageInfo {
-                page,
-                per_page,
-                total_pages,
-                total_items: count.0,
-                has_next: page < total_pages,
-                has_prev: page > 1,
-            },
-        })
-    }
-
-    async fn orders(
-        &self,
-        ctx: &Context<'_>,
-        pagination: Option<PaginationInput>,
-    ) -> async_graphql::Result<OrderConnection> {
-        let pool = ctx.data::<SqlitePool>()?;
-        let page = pagination.as_ref().map(|p| p.page).unwrap_or(1);
-        let per_page = pagination.as_ref().map(|p| p.per_page).unwrap_or(20);
-        let offset = (page - 1) * per_page;
-
-        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sales_orders")
-            .fetch_one(pool)
-            .await?;
-
-        let rows: Vec<(String, String, String, String, i64, String, String, String)> = 
-            sqlx::query_as(
-                "SELECT id, order_number, customer_id, status, total_amount, currency, order_date, created_at 
-                 FROM sales_orders ORDER BY created_at DESC LIMIT ? OFFSET ?"
-            )
-            .bind(per_page)
-            .bind(offset)
-            .fetch_all(pool)
-            .await?;
-
-        let items = rows.into_iter().map(|row| Order {
-            id: row.0,
-            order_number: row.1,
-            customer_id: row.2,
-            status: row.3,
-            total_amount: row.4,
-            currency: row.5,
-            order_date: row.6,
-            created_at: row.7,
-        }).collect();
-
-        let total_pages = ((count.0 as f64) / (per_page as f64)).ceil() as i32;
-
-        Ok(OrderConnection {
-            items,
-            page_info: PageInfo {
-                page,
-                per_page,
-                total_pages,
-                total_items: count.0,
-                has_next: page < total_pages,
-                has_prev: page > 1,
-            },
-        })
-    }
-
-    async fn vendors(
-        &self,
-        ctx: &Context<'_>,
-        pagination: Option<PaginationInput>,
-    ) -> async_graphql::Result<VendorConnection> {
-        let pool = ctx.data::<SqlitePool>()?;
-        let page = pagination.as_ref().map(|p| p.page).unwrap_or(1);
-        let per_page = pagination.as_ref().map(|p| p.per_page).unwrap_or(20);
-        let offset = (page - 1) * per_page;
-
-        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM vendors")
-            .fetch_one(pool)
-            .await?;
-
-        let rows: Vec<(String, String, String, Option<String>, Option<String>, String, Option<String>, String, String)> = 
-            sqlx::query_as(
-                "SELECT id, code, name, email, phone, status, payment_terms, created_at, updated_at 
-                 FROM vendors ORDER BY created_at DESC LIMIT ? OFFSET ?"
-            )
-            .bind(per_page)
-            .bind(offset)
-            .fetch_all(pool)
-            .await?;
-
-        let items = rows.into_iter().map(|row| Vendor {
-            id: row.0,
-            code: row.1,
-            name: row.2,
-            email: row.3,
-            phone: row.4,
-            status: row.5,
-            payment_terms: row.6,
-            created_at: row.7,
-            updated_at: row.8,
-        }).collect();
-
-        let total_pages = ((count.0 as f64) / (per_page as f64)).ceil() as i32;
-
-        Ok(VendorConnection {
-            items,
-            page_info: PageInfo {
-                page,
-                per_page,
-                total_pages,
-                total_items: count.0,
-                has_next: page < total_pages,
-                has_prev: page > 1,
-            },
-        })
-    }
-
-    async fn employees(
-        &self,
-        ctx: &Context<'_>,
-        pagination: Option<PaginationInput>,
-    ) -> async_graphql::Result<EmployeeConnection> {
-        let pool = ctx.data::<SqlitePool>()?;
-        let page = pagination.as_ref().map(|p| p.page).unwrap_or(1);
-        let per_page = pagination.as_ref().map(|p| p.per_page).unwrap_or(20);
-        let offset = (page - 1) * per_page;
-
-        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM employees")
-            .fetch_one(pool)
-            .await?;
-
-        let rows: Vec<(String, String, String, String, String, Option<String>, Option<String>, String, Option<String>, String)> = 
-            sqlx::query_as(
-                "SELECT id, employee_number, first_name, last_name, email, department, position, status, hire_date, created_at 
-                 FROM employees ORDER BY created_at DESC LIMIT ? OFFSET ?"
-            )
-            .bind(per_page)
-            .bind(offset)
-            .fetch_all(pool)
-            .await?;
-
-        let items = rows.into_iter().map(|row| Employee {
-            id: row.0,
-            employee_number: row.1,
-            first_name: row.2,
-            last_name: row.3,
-            email: row.4,
-            department: row.5,
-            position: row.6,
-            status: row.7,
-            hire_date: row.8,
-            created_at: row.9,
-        }).collect();
-
-        let total_pages = ((count.0 as f64) / (per_page as f64)).ceil() as i32;
-
-        Ok(EmployeeConnection {
-            items,
-            page_info: PageInfo {
-                page,
-                per_page,
-                total_pages,
-                total_items: count.0,
-                has_next: page < total_pages,
-                has_prev: page > 1,
-            },
-        })
-    }
-
-    async fn dashboard_stats(&self, ctx: &Context<'_>) -> async_graphql::Result<DashboardStats> {
-        let pool = ctx.data::<SqlitePool>()?;
-
-        let products: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM products").fetch_one(pool).await?;
-        let customers: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM customers").fetch_one(pool).await?;
-        let orders: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sales_orders").fetch_one(pool).await?;
-        let vendors: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM vendors").fetch_one(pool).await?;
-        let employees: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM employees").fetch_one(pool).await?;
-        let pending: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sales_orders WHERE status = 'pending'").fetch_one(pool).await?;
-
-        let month_start = chrono::Utc::now().format("%Y-%m-01").to_string();
-        let revenue: (i64,) = sqlx::query_as(
-            "SELECT COALESCE(SUM(total_amount), 0) FROM sales_orders WHERE status = 'confirmed' AND order_date >= ?"
-        )
-        .bind(&month_start)
-        .fetch_one(pool)
-        .await?;
-
-        let month_orders: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM sales_orders WHERE order_date >= ?"
-        )
-        .bind(&month_start)
-        .fetch_one(pool)
-        .await?;
-
-        Ok(DashboardStats {
-            total_products: products.0,
-            total_customers: customers.0,
-            total_orders: orders.0,
-            total_vendors: vendors.0,
-            total_employees: employees.0,
-            pending_orders: pending.0,
-            revenue_this_month: revenue.0,
-            orders_this_month: month_orders.0,
-        })
-    }
-}
@@ -1,12 +1,31 @@
-use async_graphql::{EmptyMutation, EmptySubscription, Schema};
+use async_graphql::{Data, Schema};
 use sqlx::SqlitePool;
 
-pub use crate::schema::*;
+use crate::auth::Viewer;
+use crate::loaders::loaders;
+use crate::mutation::MutationRoot;
+use crate::query::QueryRoot;
+use crate::subscription::SubscriptionRoot;
 
-pub type GraphQLSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+pub type GraphQLSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+pub const MAX_QUERY_DEPTH: usize = 12;
+pub const MAX_QUERY_COMPLEXITY: usize = 2_000;
 
 pub fn build_schema(pool: SqlitePool) -> GraphQLSchema {
-    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
         .data(pool)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
         .finish()
 }
+
+/// Per-operation data: the viewer every resolver authorizes against and the dataloaders
+/// that batch relationship lookups for them. Attach it to each HTTP request, or to the
+/// connection for subscriptions.
+pub fn session_data(pool: &SqlitePool, viewer: Viewer) -> Data {
+    let mut data = Data::default();
+    data.insert(loaders(pool.clone(), viewer.clone()));
+    data.insert(viewer);
+    data
+}
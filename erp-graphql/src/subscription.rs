@@ -0,0 +1,120 @@
+use async_graphql::futures_util::stream::{self, Stream, StreamExt};
+use async_graphql::{Context, SimpleObject, Subscription};
+pub use erp_core::events::publish_change;
+use erp_core::events::RealtimeEvent;
+use sqlx::SqlitePool;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::auth::{to_graphql_error, Viewer, CUSTOMERS, EMPLOYEES, SALES_ORDERS};
+
+/// An entity the API publishes change events for, with the permission needed to hear about it.
+pub struct Entity {
+    pub name: &'static str,
+    pub table: &'static str,
+    pub permission: &'static str,
+    pub resource: Option<&'static str>,
+}
+
+pub static ENTITIES: [Entity; 11] = [
+    Entity { name: "account", table: "accounts", permission: "finance:accounts:read", resource: None },
+    Entity { name: "journal_entry", table: "journal_entries", permission: "finance:journals:read", resource: None },
+    Entity { name: "product", table: "products", permission: "inventory:products:read", resource: None },
+    Entity { name: "warehouse", table: "warehouses", permission: "inventory:warehouses:read", resource: None },
+    Entity { name: "customer", table: "customers", permission: "sales:customers:read", resource: Some(CUSTOMERS) },
+    Entity { name: "sales_order", table: "sales_orders", permission: "sales:orders:read", resource: Some(SALES_ORDERS) },
+    Entity { name: "vendor", table: "vendors", permission: "purchasing:vendors:read", resource: None },
+    Entity { name: "purchase_order", table: "purchase_orders", permission: "purchasing:orders:read", resource: None },
+    Entity { name: "bom", table: "bills_of_material", permission: "manufacturing:boms:read", resource: None },
+    Entity { name: "work_order", table: "work_orders", permission: "manufacturing:workorders:read", resource: None },
+    Entity { name: "employee", table: "employees", permission: "hr:employees:read", resource: Some(EMPLOYEES) },
+];
+
+pub fn entity(name: &str) -> Option<&'static Entity> {
+    ENTITIES.iter().find(|e| e.name == name)
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct EntityChange {
+    pub entity: String,
+    pub id: String,
+    pub action: String,
+    pub event_type: String,
+    pub actor: Option<String>,
+    pub occurred_at: String,
+}
+
+impl EntityChange {
+    fn from_event(event: &RealtimeEvent) -> Option<Self> {
+        let data = event.data.as_ref()?;
+        Some(Self {
+            entity: data.get("entity")?.as_str()?.to_string(),
+            id: data.get("id")?.as_str()?.to_string(),
+            action: data.get("action")?.as_str()?.to_string(),
+            event_type: event.event_type.clone(),
+            actor: data.get("actor").and_then(|a| a.as_str()).map(String::from),
+            occurred_at: event.created_at.to_rfc3339(),
+        })
+    }
+}
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Changes to entities of one type, or to a single entity when `id` is given. Requires
+    /// the same read permission as querying the entity, and rows outside the viewer's row
+    /// scope are skipped.
+    async fn entity_changed(&self, ctx: &Context<'_>, entity: String, id: Option<String>) -> async_graphql::Result<impl Stream<Item = EntityChange>> {
+        let target = self::entity(&entity)
+            .ok_or_else(|| to_graphql_error(erp_core::Error::validation(format!("Unknown entity '{}'", entity))))?;
+        let viewer = ctx.data::<Viewer>()?.clone();
+        viewer.require(target.permission).await?;
+        let pool = ctx.data::<SqlitePool>()?.clone();
+        let user_id = Uuid::parse_str(&viewer.user_id).ok();
+
+        let events = stream::unfold(erp_core::events::subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => return Some((event, rx)),
+                    Err(RecvError::Lagged(skipped)) => tracing::warn!("GraphQL subscription dropped {} events", skipped),
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        Ok(events.filter_map(move |event| {
+            let (viewer, pool, id) = (viewer.clone(), pool.clone(), id.clone());
+            async move {
+                if event.target_users.as_ref().is_some_and(|users| !user_id.is_some_and(|u| users.contains(&u))) {
+                    return None;
+                }
+                let change = EntityChange::from_event(&event)?;
+                if change.entity != target.name || id.as_ref().is_some_and(|id| *id != change.id) {
+                    return None;
+                }
+                visible(&pool, &viewer, target, &change.id).await.then_some(change)
+            }
+        }))
+    }
+}
+
+/// Whether the changed row is within the viewer's row scope.
+async fn visible(pool: &SqlitePool, viewer: &Viewer, entity: &Entity, id: &str) -> bool {
+    let Some(resource) = entity.resource else {
+        return true;
+    };
+    let scope = viewer.scope(resource);
+    if scope.is_unrestricted() {
+        return true;
+    }
+    let sql = format!("SELECT EXISTS (SELECT 1 FROM {} WHERE id = ?{})", entity.table, scope.sql());
+    let query = sqlx::query_as::<_, (bool,)>(&sql).bind(id);
+    match scope.bind_query_as(query).fetch_one(pool).await {
+        Ok((visible,)) => visible,
+        Err(e) => {
+            tracing::error!("Failed to check subscription row scope: {}", e);
+            false
+        }
+    }
+}
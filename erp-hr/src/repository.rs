@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 use chrono::{Utc, NaiveDate};
-use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status, Money, Currency, Address, ContactInfo};
+use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status, Money, Currency, Address, ContactInfo, RowScope};
 use crate::models::*;
 
 #[derive(sqlx::FromRow)]
@@ -39,16 +39,27 @@ pub struct SqliteEmployeeRepository;
 #[async_trait]
 impl EmployeeRepository for SqliteEmployeeRepository {
     async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<Employee> {
-        let row = sqlx::query_as::<_, EmployeeRow>(
-            "SELECT id, employee_number, first_name, last_name, email, phone, birth_date, hire_date, termination_date, department_id, position_id, manager_id, status, created_at, updated_at FROM employees WHERE id = ?")
-            .bind(id.to_string()).fetch_optional(pool).await?.ok_or_else(|| Error::not_found("Employee", &id.to_string()))?;
+        self.find_in_scope(pool, id, &RowScope::unrestricted()).await
+    }
+
+    async fn find_in_scope(&self, pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<Employee> {
+        let sql = format!(
+            "SELECT id, employee_number, first_name, last_name, email, phone, birth_date, hire_date, termination_date, department_id, position_id, manager_id, status, created_at, updated_at FROM employees WHERE id = ?{}",
+            scope.sql()
+        );
+        let row = scope.bind_query_as(sqlx::query_as::<_, EmployeeRow>(&sql).bind(id.to_string()))
+            .fetch_optional(pool).await?.ok_or_else(|| Error::not_found("Employee", &id.to_string()))?;
         Ok(row.into_employee())
     }
 
-    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<Employee>> {
-        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM employees WHERE status != 'Deleted'").fetch_one(pool).await?;
-        let rows = sqlx::query_as::<_, EmployeeRow>(
-            "SELECT id, employee_number, first_name, last_name, email, phone, birth_date, hire_date, termination_date, department_id, position_id, manager_id, status, created_at, updated_at FROM employees WHERE status != 'Deleted' ORDER BY employee_number LIMIT ? OFFSET ?")
+    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination, scope: &RowScope) -> Result<Paginated<Employee>> {
+        let count_sql = format!("SELECT COUNT(*) FROM employees WHERE status != 'Deleted'{}", scope.sql());
+        let count: (i64,) = scope.bind_query_as(sqlx::query_as(&count_sql)).fetch_one(pool).await?;
+        let sql = format!(
+            "SELECT id, employee_number, first_name, last_name, email, phone, birth_date, hire_date, termination_date, department_id, position_id, manager_id, status, created_at, updated_at FROM employees WHERE status != 'Deleted'{} ORDER BY employee_number LIMIT ? OFFSET ?",
+            scope.sql()
+        );
+        let rows = scope.bind_query_as(sqlx::query_as::<_, EmployeeRow>(&sql))
             .bind(pagination.limit() as i64).bind(pagination.offset() as i64).fetch_all(pool).await?;
         Ok(Paginated::new(rows.into_iter().map(|r| r.into_employee()).collect(), count.0 as u64, pagination))
     }
@@ -324,7 +335,8 @@ impl SuccessionRepository for SqliteSuccessionRepository {
 #[async_trait]
 pub trait EmployeeRepository: Send + Sync {
     async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<Employee>;
-    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<Employee>>;
+    async fn find_in_scope(&self, pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<Employee>;
+    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination, scope: &RowScope) -> Result<Paginated<Employee>>;
     async fn create(&self, pool: &SqlitePool, emp: Employee) -> Result<Employee>;
     async fn terminate(&self, pool: &SqlitePool, id: Uuid, date: NaiveDate) -> Result<()>;
 
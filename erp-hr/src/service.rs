@@ -1,7 +1,7 @@
 use sqlx::SqlitePool;
 use uuid::Uuid;
 use chrono::Utc;
-use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status, Money, Currency};
+use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status, Money, Currency, RowScope};
 use chrono::NaiveDate;
 use crate::models::*;
 use crate::repository::*;
@@ -23,7 +23,9 @@ impl Default for EmployeeService {
 impl EmployeeService {
     pub fn new() -> Self { Self { repo: SqliteEmployeeRepository } }
     pub async fn get(&self, pool: &SqlitePool, id: Uuid) -> Result<Employee> { self.repo.find_by_id(pool, id).await }
-    pub async fn list(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<Employee>> { self.repo.find_all(pool, pagination).await }
+    pub async fn get_scoped(&self, pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<Employee> { self.repo.find_in_scope(pool, id, scope).await }
+    pub async fn list(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<Employee>> { self.repo.find_all(pool, pagination, &RowScope::unrestricted()).await }
+    pub async fn list_scoped(&self, pool: &SqlitePool, pagination: Pagination, scope: &RowScope) -> Result<Paginated<Employee>> { self.repo.find_all(pool, pagination, scope).await }
     
     pub async fn create(&self, pool: &SqlitePool, emp: Employee) -> Result<Employee> {
         if emp.employee_number.is_empty() { return Err(Error::validation("Employee number is required")); }
@@ -413,15 +415,19 @@ impl ExpenseService {
     }
 
     pub async fn get_expense_report(pool: &SqlitePool, id: Uuid) -> Result<ExpenseReport> {
-        let row = sqlx::query_as::<_, ExpenseReportRow>(
+        Self::get_expense_report_scoped(pool, id, &RowScope::unrestricted()).await
+    }
+
+    pub async fn get_expense_report_scoped(pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<ExpenseReport> {
+        let sql = format!(
             "SELECT id, report_number, employee_id, title, description, total_amount, status, submitted_at, approved_by, approved_at, rejected_at, rejection_reason, created_at, updated_at
-             FROM expense_reports WHERE id = ?"
-        )
-        .bind(id.to_string())
-        .fetch_optional(pool)
-        .await
-        .map_err(Error::Database)?
-        .ok_or_else(|| Error::not_found("ExpenseReport", &id.to_string()))?;
+             FROM expense_reports WHERE id = ?{}", scope.sql()
+        );
+        let row = scope.bind_query_as(sqlx::query_as::<_, ExpenseReportRow>(&sql).bind(id.to_string()))
+            .fetch_optional(pool)
+            .await
+            .map_err(Error::Database)?
+            .ok_or_else(|| Error::not_found("ExpenseReport", &id.to_string()))?;
         
         Ok(row.into())
     }
@@ -477,24 +483,23 @@ impl ExpenseService {
     }
 
     pub async fn list_expense_reports(pool: &SqlitePool, employee_id: Option<Uuid>) -> Result<Vec<ExpenseReport>> {
-        let rows = if let Some(eid) = employee_id {
-            sqlx::query_as::<_, ExpenseReportRow>(
-                "SELECT id, report_number, employee_id, title, description, total_amount, status, submitted_at, approved_by, approved_at, rejected_at, rejection_reason, created_at, updated_at
-                 FROM expense_reports WHERE employee_id = ? ORDER BY created_at DESC"
-            )
-            .bind(eid.to_string())
-            .fetch_all(pool)
-            .await
-            .map_err(Error::Database)?
-        } else {
-            sqlx::query_as::<_, ExpenseReportRow>(
-                "SELECT id, report_number, employee_id, title, description, total_amount, status, submitted_at, approved_by, approved_at, rejected_at, rejection_reason, created_at, updated_at
-                 FROM expense_reports ORDER BY created_at DESC"
-            )
+        Self::list_expense_reports_scoped(pool, employee_id, &RowScope::unrestricted()).await
+    }
+
+    pub async fn list_expense_reports_scoped(pool: &SqlitePool, employee_id: Option<Uuid>, scope: &RowScope) -> Result<Vec<ExpenseReport>> {
+        let employee_filter = if employee_id.is_some() { " AND employee_id = ?" } else { "" };
+        let sql = format!(
+            "SELECT id, report_number, employee_id, title, description, total_amount, status, submitted_at, approved_by, approved_at, rejected_at, rejection_reason, created_at, updated_at
+             FROM expense_reports WHERE 1 = 1{}{} ORDER BY created_at DESC", employee_filter, scope.sql()
+        );
+        let mut query = sqlx::query_as::<_, ExpenseReportRow>(&sql);
+        if let Some(eid) = employee_id {
+            query = query.bind(eid.to_string());
+        }
+        let rows = scope.bind_query_as(query)
             .fetch_all(pool)
             .await
-            .map_err(Error::Database)?
-        };
+            .map_err(Error::Database)?;
         
         Ok(rows.into_iter().map(|r| r.into()).collect())
     }
@@ -0,0 +1,105 @@
+//! Numbers, amounts and dates written the way a locale expects them.
+
+use chrono::NaiveDate;
+
+use crate::models::Locale;
+
+/// Currencies shown without minor units. Amounts are still stored in hundredths.
+const ZERO_DECIMAL: [&str; 5] = ["JPY", "KRW", "VND", "CLP", "ISK"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocaleFormat {
+    pub decimal_separator: String,
+    pub thousand_separator: String,
+    /// `before` or `after` the amount.
+    pub currency_position: String,
+    /// In the `YYYY-MM-DD` style locales are stored with.
+    pub date_format: String,
+}
+
+impl Default for LocaleFormat {
+    fn default() -> Self {
+        Self {
+            decimal_separator: ".".to_string(),
+            thousand_separator: ",".to_string(),
+            currency_position: "before".to_string(),
+            date_format: "YYYY-MM-DD".to_string(),
+        }
+    }
+}
+
+impl From<&Locale> for LocaleFormat {
+    fn from(locale: &Locale) -> Self {
+        Self {
+            decimal_separator: locale.decimal_separator.clone(),
+            thousand_separator: locale.thousand_separator.clone(),
+            currency_position: locale.currency_position.clone(),
+            date_format: locale.date_format.clone(),
+        }
+    }
+}
+
+impl LocaleFormat {
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let (whole, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+        let negative = value < 0.0 && formatted.bytes().any(|b| matches!(b, b'1'..=b'9'));
+        self.join(negative, whole, fraction)
+    }
+
+    /// An amount in hundredths of `currency`, with its symbol.
+    pub fn money(&self, amount: i64, currency: &str) -> String {
+        let currency = currency.to_uppercase();
+        let cents = amount.unsigned_abs();
+        let (whole, fraction) = if ZERO_DECIMAL.contains(&currency.as_str()) {
+            (((cents + 50) / 100).to_string(), String::new())
+        } else {
+            ((cents / 100).to_string(), format!("{:02}", cents % 100))
+        };
+        let number = self.join(false, &whole, &fraction);
+        let sign = if amount < 0 && (whole != "0" || fraction.bytes().any(|b| b != b'0')) { "-" } else { "" };
+        let symbol = currency_symbol(&currency);
+        if self.currency_position == "after" {
+            format!("{}{} {}", sign, number, symbol)
+        } else if symbol.len() == 3 && symbol == currency {
+            format!("{}{} {}", sign, symbol, number)
+        } else {
+            format!("{}{}{}", sign, symbol, number)
+        }
+    }
+
+    pub fn date(&self, date: NaiveDate) -> String {
+        let mut pattern = self.date_format.clone();
+        for (token, code) in [("YYYY", "%Y"), ("MMMM", "%B"), ("MMM", "%b"), ("MM", "%m"), ("DD", "%d"), ("YY", "%y")] {
+            pattern = pattern.replace(token, code);
+        }
+        date.format(&pattern).to_string()
+    }
+
+    fn join(&self, negative: bool, whole: &str, fraction: &str) -> String {
+        let mut grouped = String::new();
+        for (i, digit) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i).is_multiple_of(3) {
+                grouped.push_str(&self.thousand_separator);
+            }
+            grouped.push(digit);
+        }
+        let sign = if negative { "-" } else { "" };
+        if fraction.is_empty() {
+            format!("{}{}", sign, grouped)
+        } else {
+            format!("{}{}{}{}", sign, grouped, self.decimal_separator, fraction)
+        }
+    }
+}
+
+/// The usual symbol for an ISO 4217 code, or the code itself.
+pub fn currency_symbol(currency: &str) -> &str {
+    match currency {
+        "USD" => "$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" | "CNY" => "¥",
+        _ => currency,
+    }
+}
@@ -1,6 +1,8 @@
+pub mod format;
 pub mod models;
 pub mod repository;
 pub mod service;
 
+pub use format::LocaleFormat;
 pub use models::*;
 pub use service::*;
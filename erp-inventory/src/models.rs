@@ -68,6 +68,51 @@ pub struct StockLevel {
     pub available_quantity: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ReservationStatus {
+    Active,
+    Consumed,
+    Released,
+}
+
+/// Stock at one location held for a source document, such as a sales order line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockReservation {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub location_id: Uuid,
+    pub source_type: String,
+    pub source_id: Uuid,
+    pub source_line_id: Option<Uuid>,
+    pub quantity: i64,
+    pub status: ReservationStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReservationLine {
+    pub product_id: Uuid,
+    pub quantity: i64,
+    pub source_line_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailableToPromise {
+    pub product_id: Uuid,
+    pub warehouse_id: Option<Uuid>,
+    pub date: DateTime<Utc>,
+    pub requested_quantity: i64,
+    pub on_hand: i64,
+    pub reserved: i64,
+    pub incoming_purchases: i64,
+    pub planned_production: i64,
+    pub available: i64,
+    pub is_fulfilled: bool,
+    pub suggested_date: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StockMovement {
     pub base: BaseEntity,
@@ -79,6 +124,12 @@ pub struct StockMovement {
     pub quantity: i64,
     pub reference: Option<String>,
     pub date: DateTime<Utc>,
+    /// Cost per unit of a receipt or increasing adjustment. Once recorded, the cost the movement
+    /// was valued at.
+    #[serde(default)]
+    pub unit_cost: Option<i64>,
+    #[serde(default)]
+    pub total_cost: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
@@ -773,7 +824,7 @@ pub struct RFQResponseLine {
     pub notes: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum ValuationMethod {
     FIFO,
@@ -902,6 +953,64 @@ pub struct CostAdjustmentLine {
     pub value_change: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductCostSettings {
+    pub product_id: Uuid,
+    pub valuation_method: ValuationMethod,
+    pub standard_cost: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
+pub enum CostVarianceType {
+    /// Difference between the price paid and the standard cost of a receipt.
+    PurchasePrice,
+    /// Change in the value of stock on hand when its standard cost is revised.
+    Revaluation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostVariance {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub variance_type: CostVarianceType,
+    pub quantity: i64,
+    pub amount: i64,
+    pub reference: Option<String>,
+    pub movement_id: Option<Uuid>,
+    pub adjustment_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerpetualValuationLine {
+    pub product_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub valuation_method: ValuationMethod,
+    pub quantity: i64,
+    /// Quantity on hand according to stock levels, which should match the costed quantity.
+    pub stock_quantity: i64,
+    pub unit_cost: i64,
+    pub total_value: i64,
+    /// Value of the remaining cost layers, which should match the total value.
+    pub layer_value: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerpetualValuationReport {
+    pub as_of: DateTime<Utc>,
+    pub warehouse_id: Option<Uuid>,
+    pub lines: Vec<PerpetualValuationLine>,
+    pub total_quantity: i64,
+    pub total_value: i64,
+    pub gl_account_id: Option<Uuid>,
+    pub gl_balance: Option<i64>,
+    pub difference: Option<i64>,
+    pub is_reconciled: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ABCClassification {
     pub id: Uuid,
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status};
@@ -358,15 +358,16 @@ impl StockLevelRow {
     }
 }
 
-#[async_trait]
-impl StockMovementRepository for SqliteStockMovementRepository {
-    async fn record(&self, pool: &SqlitePool, movement: StockMovement) -> Result<StockMovement> {
+impl SqliteStockMovementRepository {
+    /// Writes the movement and applies it to stock levels within the caller's transaction. Receipts
+    /// add to `to_location_id`; issues take from `from_location_id` (or `to_location_id` when no
+    /// source is given); transfers move stock between the two; adjustments take from
+    /// `from_location_id` when set and otherwise add to `to_location_id`.
+    pub async fn record_in(&self, conn: &mut SqliteConnection, movement: &StockMovement) -> Result<()> {
         let now = Utc::now();
-        let mut tx = pool.begin().await?;
-        
         sqlx::query("INSERT INTO stock_movements (id, movement_number, movement_type, product_id, 
-             from_location_id, to_location_id, quantity, reference, movement_date, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+             from_location_id, to_location_id, quantity, reference, movement_date, unit_cost, total_cost, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
         .bind(movement.base.id.to_string())
         .bind(&movement.movement_number)
         .bind(format!("{:?}", movement.movement_type))
@@ -376,60 +377,81 @@ impl StockMovementRepository for SqliteStockMovementRepository {
         .bind(movement.quantity)
         .bind(&movement.reference)
         .bind(movement.date.to_rfc3339())
+        .bind(movement.unit_cost)
+        .bind(movement.total_cost)
         .bind(movement.base.created_at.to_rfc3339())
         .bind(now.to_rfc3339())
-        .execute(&mut *tx)
+        .execute(&mut *conn)
         .await?;
         
-        let existing = sqlx::query_as::<_, StockLevelRow>(
-            "SELECT id, product_id, location_id, quantity, reserved_quantity, available_quantity
-             FROM stock_levels WHERE product_id = ? AND location_id = ?"
-        )
-        .bind(movement.product_id.to_string())
-        .bind(movement.to_location_id.to_string())
-        .fetch_optional(&mut *tx)
-        .await?;
-        
-        match existing {
-            Some(_) => {
-                sqlx::query("UPDATE stock_levels SET quantity = quantity + ?, available_quantity = available_quantity + ?, reserved_quantity = reserved_quantity
-                     WHERE product_id = ? AND location_id = ?")
-                .bind(movement.quantity)
-                .bind(movement.quantity)
-                .bind(movement.product_id.to_string())
-                .bind(movement.to_location_id.to_string())
-                .execute(&mut *tx)
-                .await?;
-            }
-            None => {
-                sqlx::query("INSERT INTO stock_levels (id, product_id, location_id, quantity, reserved_quantity, available_quantity)
-                     VALUES (?, ?, ?, ?, 0, ?)")
-                .bind(Uuid::new_v4().to_string())
-                .bind(movement.product_id.to_string())
-                .bind(movement.to_location_id.to_string())
-                .bind(movement.quantity)
-                .bind(movement.quantity)
-                .execute(&mut *tx)
-                .await?;
-            }
+        for (location_id, change) in stock_changes(movement) {
+            Self::change_level(conn, movement.product_id, location_id, change).await?;
         }
-        
-        tx.commit().await?;
-        Ok(movement)
+        Ok(())
     }
 
-    async fn get_stock_level(&self, pool: &SqlitePool, product_id: Uuid, location_id: Uuid) -> Result<StockLevel> {
+    pub async fn get_stock_level_in(&self, conn: &mut SqliteConnection, product_id: Uuid, location_id: Uuid) -> Result<StockLevel> {
         let row = sqlx::query_as::<_, StockLevelRow>(
             "SELECT id, product_id, location_id, quantity, reserved_quantity, available_quantity
              FROM stock_levels WHERE product_id = ? AND location_id = ?"
         )
         .bind(product_id.to_string())
         .bind(location_id.to_string())
-        .fetch_optional(pool)
+        .fetch_optional(&mut *conn)
         .await?
         .ok_or_else(|| Error::not_found("StockLevel", &format!("{}/{}", product_id, location_id)))?;
         
-        Ok(row.into_stock_level()?)
+        row.into_stock_level()
+    }
+
+    async fn change_level(conn: &mut SqliteConnection, product_id: Uuid, location_id: Uuid, change: i64) -> Result<()> {
+        let updated = sqlx::query("UPDATE stock_levels SET quantity = quantity + ?, available_quantity = available_quantity + ?
+             WHERE product_id = ? AND location_id = ?")
+        .bind(change)
+        .bind(change)
+        .bind(product_id.to_string())
+        .bind(location_id.to_string())
+        .execute(&mut *conn)
+        .await?;
+        
+        if updated.rows_affected() == 0 {
+            sqlx::query("INSERT INTO stock_levels (id, product_id, location_id, quantity, reserved_quantity, available_quantity)
+                 VALUES (?, ?, ?, ?, 0, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(product_id.to_string())
+            .bind(location_id.to_string())
+            .bind(change)
+            .bind(change)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+/// The quantity each location gains (positive) or loses (negative) through a movement.
+pub fn stock_changes(movement: &StockMovement) -> Vec<(Uuid, i64)> {
+    let quantity = movement.quantity;
+    match (&movement.movement_type, movement.from_location_id) {
+        (MovementType::Receipt, _) | (MovementType::Adjustment, None) => vec![(movement.to_location_id, quantity)],
+        (MovementType::Issue, from) => vec![(from.unwrap_or(movement.to_location_id), -quantity)],
+        (MovementType::Adjustment, Some(from)) => vec![(from, -quantity)],
+        (MovementType::Transfer, Some(from)) => vec![(from, -quantity), (movement.to_location_id, quantity)],
+        (MovementType::Transfer, None) => vec![(movement.to_location_id, quantity)],
+    }
+}
+
+#[async_trait]
+impl StockMovementRepository for SqliteStockMovementRepository {
+    async fn record(&self, pool: &SqlitePool, movement: StockMovement) -> Result<StockMovement> {
+        let mut tx = pool.begin().await?;
+        self.record_in(&mut tx, &movement).await?;
+        tx.commit().await?;
+        Ok(movement)
+    }
+
+    async fn get_stock_level(&self, pool: &SqlitePool, product_id: Uuid, location_id: Uuid) -> Result<StockLevel> {
+        self.get_stock_level_in(&mut *pool.acquire().await?, product_id, location_id).await
     }
 
     async fn get_product_stock(&self, pool: &SqlitePool, product_id: Uuid) -> Result<Vec<StockLevel>> {
@@ -672,9 +694,8 @@ struct CycleCountLineRow {
 
 pub struct SqliteValuationRepository;
 
-#[async_trait]
-impl ValuationRepository for SqliteValuationRepository {
-    async fn get_valuation(&self, pool: &SqlitePool, product_id: Uuid, warehouse_id: Uuid) -> Result<ProductValuation> {
+impl SqliteValuationRepository {
+    pub async fn valuation_in(conn: &mut SqliteConnection, product_id: Uuid, warehouse_id: Uuid) -> Result<Option<ProductValuation>> {
         let row = sqlx::query_as::<_, ValuationRow>(
             "SELECT id, product_id, warehouse_id, valuation_method, standard_cost, current_unit_cost, 
                     total_quantity, total_value, last_receipt_cost, last_receipt_date, 
@@ -683,14 +704,13 @@ impl ValuationRepository for SqliteValuationRepository {
         )
         .bind(product_id.to_string())
         .bind(warehouse_id.to_string())
-        .fetch_optional(pool)
-        .await?
-        .ok_or_else(|| Error::not_found("ProductValuation", &format!("{}/{}", product_id, warehouse_id)))?;
+        .fetch_optional(&mut *conn)
+        .await?;
         
-        Ok(row.into_valuation()?)
+        row.map(|r| r.into_valuation()).transpose()
     }
 
-    async fn update_valuation(&self, pool: &SqlitePool, v: ProductValuation) -> Result<()> {
+    pub async fn save_valuation_in(conn: &mut SqliteConnection, v: &ProductValuation) -> Result<()> {
         let now = Utc::now();
         sqlx::query(
             "INSERT INTO product_valuations (id, product_id, warehouse_id, valuation_method, standard_cost, 
@@ -698,11 +718,15 @@ impl ValuationRepository for SqliteValuationRepository {
                     last_issue_cost, last_issue_date, created_at, updated_at)
              VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
              ON CONFLICT(product_id, warehouse_id) DO UPDATE SET
+                valuation_method = excluded.valuation_method,
+                standard_cost = excluded.standard_cost,
                 current_unit_cost = excluded.current_unit_cost,
                 total_quantity = excluded.total_quantity,
                 total_value = excluded.total_value,
                 last_receipt_cost = excluded.last_receipt_cost,
                 last_receipt_date = excluded.last_receipt_date,
+                last_issue_cost = excluded.last_issue_cost,
+                last_issue_date = excluded.last_issue_date,
                 updated_at = excluded.updated_at"
         )
         .bind(v.id.to_string())
@@ -719,13 +743,13 @@ impl ValuationRepository for SqliteValuationRepository {
         .bind(v.last_issue_date.map(|d| d.to_rfc3339()))
         .bind(v.created_at.to_rfc3339())
         .bind(now.to_rfc3339())
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
         
         Ok(())
     }
 
-    async fn add_cost_layer(&self, pool: &SqlitePool, layer: InventoryCostLayer) -> Result<()> {
+    pub async fn add_layer_in(conn: &mut SqliteConnection, layer: &InventoryCostLayer) -> Result<()> {
         sqlx::query(
             "INSERT INTO inventory_cost_layers (id, product_id, warehouse_id, layer_date, receipt_reference, 
                     receipt_id, quantity, unit_cost, remaining_quantity, total_value, created_at)
@@ -742,34 +766,43 @@ impl ValuationRepository for SqliteValuationRepository {
         .bind(layer.remaining_quantity)
         .bind(layer.total_value)
         .bind(layer.created_at.to_rfc3339())
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
         
         Ok(())
     }
 
-    async fn get_cost_layers(&self, pool: &SqlitePool, product_id: Uuid, warehouse_id: Uuid) -> Result<Vec<InventoryCostLayer>> {
+    /// Layers with quantity remaining, oldest first.
+    pub async fn layers_in(conn: &mut SqliteConnection, product_id: Uuid, warehouse_id: Uuid) -> Result<Vec<InventoryCostLayer>> {
         let rows = sqlx::query_as::<_, CostLayerRow>(
             "SELECT id, product_id, warehouse_id, layer_date, receipt_reference, receipt_id, 
                     quantity, unit_cost, remaining_quantity, total_value, created_at
              FROM inventory_cost_layers 
              WHERE product_id = ? AND warehouse_id = ? AND remaining_quantity > 0
-             ORDER BY layer_date ASC"
+             ORDER BY layer_date ASC, created_at ASC"
         )
         .bind(product_id.to_string())
         .bind(warehouse_id.to_string())
-        .fetch_all(pool)
+        .fetch_all(&mut *conn)
         .await?;
         
-        Ok(rows.into_iter().map(|r| r.into_layer()).collect::<Result<Vec<_>>>()?)
+        rows.into_iter().map(|r| r.into_layer()).collect()
     }
 
-    async fn create_cost_adjustment(&self, pool: &SqlitePool, adj: CostAdjustment, lines: Vec<CostAdjustmentLine>) -> Result<()> {
-        let mut tx = pool.begin().await?;
-        
+    pub async fn update_layer_in(conn: &mut SqliteConnection, layer_id: Uuid, remaining_quantity: i64, unit_cost: i64) -> Result<()> {
+        sqlx::query("UPDATE inventory_cost_layers SET remaining_quantity = ?, unit_cost = ? WHERE id = ?")
+            .bind(remaining_quantity)
+            .bind(unit_cost)
+            .bind(layer_id.to_string())
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn create_adjustment_in(conn: &mut SqliteConnection, adj: &CostAdjustment, lines: &[CostAdjustmentLine]) -> Result<()> {
         sqlx::query(
-            "INSERT INTO cost_adjustments (id, adjustment_number, adjustment_type, adjustment_date, reason, status, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO cost_adjustments (id, adjustment_number, adjustment_type, adjustment_date, reason, status, journal_entry_id, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(adj.id.to_string())
         .bind(&adj.adjustment_number)
@@ -777,8 +810,9 @@ impl ValuationRepository for SqliteValuationRepository {
         .bind(adj.adjustment_date.to_rfc3339())
         .bind(&adj.reason)
         .bind(format!("{:?}", adj.status))
+        .bind(adj.journal_entry_id.map(|id| id.to_string()))
         .bind(adj.created_at.to_rfc3339())
-        .execute(&mut *tx)
+        .execute(&mut *conn)
         .await?;
         
         for line in lines {
@@ -797,10 +831,40 @@ impl ValuationRepository for SqliteValuationRepository {
             .bind(line.old_total_value)
             .bind(line.new_total_value)
             .bind(line.value_change)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         }
         
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ValuationRepository for SqliteValuationRepository {
+    async fn get_valuation(&self, pool: &SqlitePool, product_id: Uuid, warehouse_id: Uuid) -> Result<ProductValuation> {
+        let mut conn = pool.acquire().await?;
+        Self::valuation_in(&mut conn, product_id, warehouse_id).await?
+            .ok_or_else(|| Error::not_found("ProductValuation", &format!("{}/{}", product_id, warehouse_id)))
+    }
+
+    async fn update_valuation(&self, pool: &SqlitePool, v: ProductValuation) -> Result<()> {
+        let mut conn = pool.acquire().await?;
+        Self::save_valuation_in(&mut conn, &v).await
+    }
+
+    async fn add_cost_layer(&self, pool: &SqlitePool, layer: InventoryCostLayer) -> Result<()> {
+        let mut conn = pool.acquire().await?;
+        Self::add_layer_in(&mut conn, &layer).await
+    }
+
+    async fn get_cost_layers(&self, pool: &SqlitePool, product_id: Uuid, warehouse_id: Uuid) -> Result<Vec<InventoryCostLayer>> {
+        let mut conn = pool.acquire().await?;
+        Self::layers_in(&mut conn, product_id, warehouse_id).await
+    }
+
+    async fn create_cost_adjustment(&self, pool: &SqlitePool, adj: CostAdjustment, lines: Vec<CostAdjustmentLine>) -> Result<()> {
+        let mut tx = pool.begin().await?;
+        Self::create_adjustment_in(&mut tx, &adj, &lines).await?;
         tx.commit().await?;
         Ok(())
     }
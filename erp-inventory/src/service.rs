@@ -1,7 +1,8 @@
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
-use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status};
+use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status, Currency};
+use erp_finance::{PostingDocument, PostingEvent, PostingService};
 use crate::models::*;
 use crate::repository::*;
 use tracing::warn;
@@ -128,46 +129,95 @@ impl StockService {
         self.repo.get_stock_level(pool, product_id, location_id).await
     }
 
-    pub async fn record_movement(&self, pool: &SqlitePool, mut movement: StockMovement) -> Result<StockMovement> {
+    /// Records a movement, applies it to stock levels, values it with the product's costing method
+    /// and posts that value to the ledger, all in one transaction.
+    pub async fn record_movement(&self, pool: &SqlitePool, movement: StockMovement) -> Result<StockMovement> {
+        let mut tx = pool.begin().await?;
+        let movement = self.record_movement_in(&mut tx, movement).await?;
+        Self::post_movement_in(&mut tx, &movement).await?;
+        tx.commit().await?;
+        Ok(movement)
+    }
+
+    /// [`Self::record_movement`] on the caller's connection without the ledger posting, for
+    /// documents that move stock as part of their own transaction and post it themselves.
+    pub async fn record_movement_in(&self, conn: &mut SqliteConnection, mut movement: StockMovement) -> Result<StockMovement> {
         if movement.quantity <= 0 {
             return Err(Error::validation("Movement quantity must be positive"));
         }
+        if movement.unit_cost.is_some_and(|cost| cost < 0) {
+            return Err(Error::validation("Unit cost cannot be negative"));
+        }
+        if matches!(movement.movement_type, MovementType::Transfer) && movement.from_location_id.is_none() {
+            return Err(Error::validation("Transfers require a source location"));
+        }
         
-        match movement.movement_type {
-            MovementType::Issue | MovementType::Transfer => {
-                if let Some(from_location_id) = movement.from_location_id {
-                    match self.repo.get_stock_level(pool, movement.product_id, from_location_id).await {
-                        Ok(level) => {
-                            if level.available_quantity < movement.quantity {
-                                return Err(Error::business_rule(format!(
-                                    "Insufficient stock. Available: {}, Requested: {}",
-                                    level.available_quantity, movement.quantity
-                                )));
-                            }
-                        }
-                        Err(Error::NotFound(_)) => {
-                            return Err(Error::business_rule("No stock available at source location"));
-                        }
-                        Err(e) => return Err(e),
+        for (location_id, change) in stock_changes(&movement) {
+            if change >= 0 {
+                continue;
+            }
+            match self.repo.get_stock_level_in(conn, movement.product_id, location_id).await {
+                Ok(level) => {
+                    if level.available_quantity < movement.quantity {
+                        return Err(Error::business_rule(format!(
+                            "Insufficient stock. Available: {}, Requested: {}",
+                            level.available_quantity, movement.quantity
+                        )));
                     }
                 }
+                Err(Error::NotFound(_)) => {
+                    return Err(Error::business_rule("No stock available at source location"));
+                }
+                Err(e) => return Err(e),
             }
-            MovementType::Receipt | MovementType::Adjustment => {}
         }
         
         movement.base = BaseEntity::new();
-        movement.movement_number = self.generate_movement_number();
+        movement.movement_number = self.generate_movement_number(movement.base.id);
         movement.date = Utc::now();
         
-        self.repo.record(pool, movement).await
+        let total_cost = CostingService::apply_movement(conn, &movement).await?;
+        movement.unit_cost = Some(total_cost / movement.quantity);
+        movement.total_cost = Some(total_cost);
+        self.repo.record_in(conn, &movement).await?;
+        
+        Ok(movement)
+    }
+
+    /// Posts a recorded movement: receipts and issues at their cost, adjustments up or down, and
+    /// any purchase price variance captured on receipt. Transfers stay within inventory.
+    pub async fn post_movement_in(conn: &mut SqliteConnection, movement: &StockMovement) -> Result<()> {
+        let total_cost = movement.total_cost.unwrap_or(0);
+        let posting = match (&movement.movement_type, movement.from_location_id) {
+            (MovementType::Receipt, _) => Some((PostingEvent::GoodsReceipt, total_cost)),
+            (MovementType::Issue, _) => Some((PostingEvent::GoodsIssue, total_cost)),
+            (MovementType::Adjustment, None) => Some((PostingEvent::InventoryAdjustment, total_cost)),
+            (MovementType::Adjustment, Some(_)) => Some((PostingEvent::InventoryAdjustment, -total_cost)),
+            (MovementType::Transfer, _) => None,
+        };
+        let variance = CostingService::purchase_price_variance_in(conn, movement.base.id).await?;
+        for (event, amount) in posting.into_iter().chain(Some((PostingEvent::PurchasePriceVariance, variance))) {
+            PostingService::post_document(conn, PostingDocument {
+                event,
+                source_type: "StockMovement".to_string(),
+                source_id: movement.base.id,
+                source_number: movement.movement_number.clone(),
+                date: movement.date,
+                amount,
+                tax_amount: 0,
+                currency: Currency::default(),
+                description: movement.reference.clone(),
+            }).await?;
+        }
+        Ok(())
     }
 
     pub async fn get_product_stock(&self, pool: &SqlitePool, product_id: Uuid) -> Result<Vec<StockLevel>> {
         self.repo.get_product_stock(pool, product_id).await
     }
 
-    fn generate_movement_number(&self) -> String {
-        format!("SM-{}", chrono::Local::now().format("%Y%m%d%H%M%S"))
+    fn generate_movement_number(&self, id: Uuid) -> String {
+        format!("SM-{}-{}", chrono::Local::now().format("%Y%m%d%H%M%S"), &id.simple().to_string()[..8])
     }
 }
 
@@ -277,10 +327,11 @@ mod tests {
     #[test]
     fn test_movement_number_format() {
         let svc = StockService::new();
-        let number = svc.generate_movement_number();
+        let number = svc.generate_movement_number(Uuid::new_v4());
         
         assert!(number.starts_with("SM-"));
-        assert_eq!(number.len(), 17);
+        assert_eq!(number.len(), 26);
+        assert_ne!(number, svc.generate_movement_number(Uuid::new_v4()));
     }
 
     #[test]
@@ -3046,6 +3097,22 @@ impl InventoryValuationService {
 
         Ok(total_issued_cost)
     }
+
+    pub async fn get_unit_cost(&self, pool: &SqlitePool, product_id: Uuid) -> Result<i64> {
+        let row: (Option<i64>, Option<i64>, Option<i64>) = sqlx::query_as(
+            "SELECT SUM(total_quantity), SUM(total_value), MAX(current_unit_cost) FROM product_valuations WHERE product_id = ?"
+        )
+        .bind(product_id.to_string())
+        .fetch_one(pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(match row {
+            (Some(quantity), Some(value), _) if quantity > 0 => value / quantity,
+            (_, _, Some(unit_cost)) => unit_cost,
+            _ => 0,
+        })
+    }
 }
 
 pub struct StockTransferService {
@@ -3126,3 +3193,1026 @@ impl StockTransferService {
     }
 }
 
+
+/// Hard reservations against stock levels. Reserving moves quantity from available to reserved,
+/// consuming takes it out of on-hand stock and releasing makes it available again.
+pub struct ReservationService;
+
+impl Default for ReservationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReservationService {
+    pub fn new() -> Self { Self }
+
+    /// Reserves every stocked line of a source document, spreading a line over several locations
+    /// when no single one holds enough. Fails without reserving anything if any line is short.
+    pub async fn reserve(
+        pool: &SqlitePool,
+        source_type: &str,
+        source_id: Uuid,
+        lines: &[ReservationLine],
+        warehouse_id: Option<Uuid>,
+    ) -> Result<Vec<StockReservation>> {
+        let mut tx = pool.begin().await?;
+        let (existing,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM stock_reservations WHERE source_type = ? AND source_id = ? AND status = 'Active'"
+        )
+        .bind(source_type)
+        .bind(source_id.to_string())
+        .fetch_one(&mut *tx)
+        .await?;
+        if existing > 0 {
+            return Err(Error::business_rule(format!("{} {} already holds stock reservations", source_type, source_id)));
+        }
+
+        let now = Utc::now();
+        let mut reservations = Vec::new();
+        for line in lines {
+            if line.quantity <= 0 {
+                return Err(Error::validation("Reservation quantity must be positive"));
+            }
+            let product_type: Option<(String,)> = sqlx::query_as("SELECT product_type FROM products WHERE id = ?")
+                .bind(line.product_id.to_string())
+                .fetch_optional(&mut *tx)
+                .await?;
+            match product_type {
+                Some((product_type,)) if product_type == "Goods" => {}
+                Some(_) => continue,
+                None => return Err(Error::not_found("Product", &line.product_id.to_string())),
+            }
+
+            let candidates: Vec<(String, String, i64)> = sqlx::query_as(
+                "SELECT sl.location_id, loc.warehouse_id, sl.available_quantity
+                 FROM stock_levels sl JOIN stock_locations loc ON loc.id = sl.location_id
+                 WHERE sl.product_id = ? AND sl.available_quantity > 0 AND (? IS NULL OR loc.warehouse_id = ?)
+                 ORDER BY sl.available_quantity DESC"
+            )
+            .bind(line.product_id.to_string())
+            .bind(warehouse_id.map(|id| id.to_string()))
+            .bind(warehouse_id.map(|id| id.to_string()))
+            .fetch_all(&mut *tx)
+            .await?;
+
+            let mut remaining = line.quantity;
+            for (location_id, location_warehouse_id, available) in candidates {
+                if remaining == 0 {
+                    break;
+                }
+                let quantity = remaining.min(available);
+                sqlx::query(
+                    "UPDATE stock_levels SET reserved_quantity = reserved_quantity + ?, available_quantity = available_quantity - ?
+                     WHERE product_id = ? AND location_id = ?"
+                )
+                .bind(quantity)
+                .bind(quantity)
+                .bind(line.product_id.to_string())
+                .bind(&location_id)
+                .execute(&mut *tx)
+                .await?;
+
+                let reservation = StockReservation {
+                    id: Uuid::new_v4(),
+                    product_id: line.product_id,
+                    warehouse_id: parse_uuid(&location_warehouse_id),
+                    location_id: parse_uuid(&location_id),
+                    source_type: source_type.to_string(),
+                    source_id,
+                    source_line_id: line.source_line_id,
+                    quantity,
+                    status: ReservationStatus::Active,
+                    created_at: now,
+                    updated_at: now,
+                };
+                sqlx::query(
+                    "INSERT INTO stock_reservations (id, product_id, warehouse_id, location_id, source_type, source_id, source_line_id, quantity, status, created_at, updated_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, 'Active', ?, ?)"
+                )
+                .bind(reservation.id.to_string())
+                .bind(reservation.product_id.to_string())
+                .bind(&location_warehouse_id)
+                .bind(&location_id)
+                .bind(&reservation.source_type)
+                .bind(source_id.to_string())
+                .bind(reservation.source_line_id.map(|id| id.to_string()))
+                .bind(quantity)
+                .bind(now.to_rfc3339())
+                .bind(now.to_rfc3339())
+                .execute(&mut *tx)
+                .await?;
+
+                reservations.push(reservation);
+                remaining -= quantity;
+            }
+            if remaining > 0 {
+                return Err(Error::business_rule(format!(
+                    "Insufficient stock to reserve product {}. Requested: {}, Available: {}",
+                    line.product_id, line.quantity, line.quantity - remaining
+                )));
+            }
+        }
+
+        tx.commit().await?;
+        Ok(reservations)
+    }
+
+    /// Issues the reserved stock of a source document, e.g. when a sales order ships.
+    pub async fn consume(pool: &SqlitePool, source_type: &str, source_id: Uuid, reference: &str) -> Result<Vec<StockReservation>> {
+        let mut tx = pool.begin().await?;
+        let (reservations, _) = Self::consume_in(&mut tx, source_type, source_id, reference).await?;
+        tx.commit().await?;
+        Ok(reservations)
+    }
+
+    /// [`Self::consume`] within the caller's transaction, so the issue can commit together with
+    /// whatever the source document records for it. Also returns the cost of the stock issued.
+    pub async fn consume_in(conn: &mut SqliteConnection, source_type: &str, source_id: Uuid, reference: &str) -> Result<(Vec<StockReservation>, i64)> {
+        let reservations = Self::active_in(conn, source_type, source_id).await?;
+        let now = Utc::now();
+        let mut issued_cost = 0;
+        for r in &reservations {
+            sqlx::query(
+                "UPDATE stock_levels SET quantity = quantity - ?, reserved_quantity = reserved_quantity - ?
+                 WHERE product_id = ? AND location_id = ?"
+            )
+            .bind(r.quantity)
+            .bind(r.quantity)
+            .bind(r.product_id.to_string())
+            .bind(r.location_id.to_string())
+            .execute(&mut *conn)
+            .await?;
+
+            let total_cost = CostingService::issue_in(conn, r.product_id, r.warehouse_id, r.quantity).await?;
+            issued_cost += total_cost;
+            sqlx::query(
+                "INSERT INTO stock_movements (id, movement_number, movement_type, product_id, from_location_id, to_location_id, quantity, reference, movement_date, unit_cost, total_cost, created_at, updated_at)
+                 VALUES (?, ?, 'Issue', ?, ?, NULL, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(format!("SM-{}-{}", now.format("%Y%m%d%H%M%S"), &r.id.simple().to_string()[..8]))
+            .bind(r.product_id.to_string())
+            .bind(r.location_id.to_string())
+            .bind(r.quantity)
+            .bind(reference)
+            .bind(now.to_rfc3339())
+            .bind(total_cost / r.quantity)
+            .bind(total_cost)
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(&mut *conn)
+            .await?;
+        }
+        let reservations = Self::close(conn, reservations, ReservationStatus::Consumed, now).await?;
+        Ok((reservations, issued_cost))
+    }
+
+    /// Returns the reserved stock of a source document to available, e.g. when an order is cancelled.
+    pub async fn release(pool: &SqlitePool, source_type: &str, source_id: Uuid) -> Result<Vec<StockReservation>> {
+        let mut tx = pool.begin().await?;
+        let reservations = Self::active_in(&mut tx, source_type, source_id).await?;
+        let now = Utc::now();
+        for r in &reservations {
+            sqlx::query(
+                "UPDATE stock_levels SET reserved_quantity = reserved_quantity - ?, available_quantity = available_quantity + ?
+                 WHERE product_id = ? AND location_id = ?"
+            )
+            .bind(r.quantity)
+            .bind(r.quantity)
+            .bind(r.product_id.to_string())
+            .bind(r.location_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        }
+        let reservations = Self::close(&mut tx, reservations, ReservationStatus::Released, now).await?;
+        tx.commit().await?;
+        Ok(reservations)
+    }
+
+    pub async fn list_for_source(pool: &SqlitePool, source_type: &str, source_id: Uuid) -> Result<Vec<StockReservation>> {
+        let rows = sqlx::query_as::<_, StockReservationRow>(
+            "SELECT id, product_id, warehouse_id, location_id, source_type, source_id, source_line_id, quantity, status, created_at, updated_at
+             FROM stock_reservations WHERE source_type = ? AND source_id = ? ORDER BY created_at"
+        )
+        .bind(source_type)
+        .bind(source_id.to_string())
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn active_in(conn: &mut SqliteConnection, source_type: &str, source_id: Uuid) -> Result<Vec<StockReservation>> {
+        let rows = sqlx::query_as::<_, StockReservationRow>(
+            "SELECT id, product_id, warehouse_id, location_id, source_type, source_id, source_line_id, quantity, status, created_at, updated_at
+             FROM stock_reservations WHERE source_type = ? AND source_id = ? AND status = 'Active'"
+        )
+        .bind(source_type)
+        .bind(source_id.to_string())
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn close(
+        conn: &mut SqliteConnection,
+        reservations: Vec<StockReservation>,
+        status: ReservationStatus,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<StockReservation>> {
+        let mut closed = Vec::with_capacity(reservations.len());
+        for mut r in reservations {
+            sqlx::query("UPDATE stock_reservations SET status = ?, updated_at = ? WHERE id = ?")
+                .bind(format!("{:?}", status))
+                .bind(now.to_rfc3339())
+                .bind(r.id.to_string())
+                .execute(&mut *conn)
+                .await?;
+            r.status = status.clone();
+            r.updated_at = now;
+            closed.push(r);
+        }
+        Ok(closed)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StockReservationRow {
+    id: String,
+    product_id: String,
+    warehouse_id: String,
+    location_id: String,
+    source_type: String,
+    source_id: String,
+    source_line_id: Option<String>,
+    quantity: i64,
+    status: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<StockReservationRow> for StockReservation {
+    fn from(r: StockReservationRow) -> Self {
+        Self {
+            id: parse_uuid(&r.id),
+            product_id: parse_uuid(&r.product_id),
+            warehouse_id: parse_uuid(&r.warehouse_id),
+            location_id: parse_uuid(&r.location_id),
+            source_type: r.source_type,
+            source_id: parse_uuid(&r.source_id),
+            source_line_id: r.source_line_id.as_deref().map(parse_uuid),
+            quantity: r.quantity,
+            status: match r.status.as_str() {
+                "Consumed" => ReservationStatus::Consumed,
+                "Released" => ReservationStatus::Released,
+                _ => ReservationStatus::Active,
+            },
+            created_at: parse_timestamp(&r.created_at),
+            updated_at: parse_timestamp(&r.updated_at),
+        }
+    }
+}
+
+fn parse_uuid(value: &str) -> Uuid {
+    Uuid::parse_str(value).unwrap_or_else(|e| {
+        warn!("Invalid UUID '{}': {}", value, e);
+        Uuid::nil()
+    })
+}
+
+/// Accepts RFC 3339 timestamps and plain `YYYY-MM-DD` dates (taken as midnight UTC).
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|d| d.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| d.and_utc())
+        })
+        .unwrap_or_else(|| {
+            warn!("Invalid timestamp '{}'", value);
+            Utc::now()
+        })
+}
+
+/// Available-to-promise: stock on hand less reservations, plus open purchase order lines and
+/// planned work orders due by the requested date. Purchase and work orders are not tied to a
+/// warehouse, so a warehouse-level check only counts that warehouse's stock.
+pub struct AtpService;
+
+impl Default for AtpService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AtpService {
+    pub fn new() -> Self { Self }
+
+    pub async fn check(
+        pool: &SqlitePool,
+        product_id: Uuid,
+        quantity: i64,
+        date: DateTime<Utc>,
+        warehouse_id: Option<Uuid>,
+    ) -> Result<AvailableToPromise> {
+        if quantity <= 0 {
+            return Err(Error::validation("Requested quantity must be positive"));
+        }
+        let (on_hand, reserved): (i64, i64) = sqlx::query_as(
+            "SELECT COALESCE(SUM(sl.quantity), 0), COALESCE(SUM(sl.reserved_quantity), 0)
+             FROM stock_levels sl JOIN stock_locations loc ON loc.id = sl.location_id
+             WHERE sl.product_id = ? AND (? IS NULL OR loc.warehouse_id = ?)"
+        )
+        .bind(product_id.to_string())
+        .bind(warehouse_id.map(|id| id.to_string()))
+        .bind(warehouse_id.map(|id| id.to_string()))
+        .fetch_one(pool)
+        .await
+        .map_err(Error::Database)?;
+
+        let mut events = vec![erp_sales_atp::InventoryEvent {
+            id: Uuid::new_v4(),
+            product_id,
+            event_type: erp_sales_atp::SupplyDemandType::OnHand,
+            quantity: on_hand - reserved,
+            date: DateTime::<Utc>::MIN_UTC,
+        }];
+        if warehouse_id.is_none() {
+            let purchases: Vec<(String, i64)> = sqlx::query_as(
+                "SELECT COALESCE(po.expected_date, po.order_date),
+                        pol.quantity - COALESCE((SELECT SUM(grl.quantity_received) FROM goods_receipt_lines grl WHERE grl.po_line_id = pol.id), 0)
+                 FROM purchase_order_lines pol JOIN purchase_orders po ON po.id = pol.purchase_order_id
+                 WHERE pol.product_id = ? AND po.status = 'Approved'"
+            )
+            .bind(product_id.to_string())
+            .fetch_all(pool)
+            .await
+            .map_err(Error::Database)?;
+            let production: Vec<(String, i64)> = sqlx::query_as(
+                "SELECT planned_end, quantity FROM work_orders
+                 WHERE product_id = ? AND status NOT IN ('Completed', 'Cancelled', 'Deleted')"
+            )
+            .bind(product_id.to_string())
+            .fetch_all(pool)
+            .await
+            .map_err(Error::Database)?;
+
+            let supply = purchases.into_iter().map(|s| (erp_sales_atp::SupplyDemandType::PurchaseOrder, s))
+                .chain(production.into_iter().map(|s| (erp_sales_atp::SupplyDemandType::ProductionOrder, s)));
+            for (event_type, (due, outstanding)) in supply {
+                if outstanding > 0 {
+                    events.push(erp_sales_atp::InventoryEvent {
+                        id: Uuid::new_v4(),
+                        product_id,
+                        event_type,
+                        quantity: outstanding,
+                        date: parse_timestamp(&due),
+                    });
+                }
+            }
+        }
+
+        let due_by = |kind: fn(&erp_sales_atp::SupplyDemandType) -> bool| -> i64 {
+            events.iter().filter(|e| kind(&e.event_type) && e.date <= date).map(|e| e.quantity).sum()
+        };
+        let incoming_purchases = due_by(|t| matches!(t, erp_sales_atp::SupplyDemandType::PurchaseOrder));
+        let planned_production = due_by(|t| matches!(t, erp_sales_atp::SupplyDemandType::ProductionOrder));
+
+        let result = erp_sales_atp::AtpEngine::new(events).check_availability(erp_sales_atp::AtpCheckRequest {
+            product_id,
+            requested_quantity: quantity,
+            requested_date: date,
+        });
+
+        Ok(AvailableToPromise {
+            product_id,
+            warehouse_id,
+            date,
+            requested_quantity: quantity,
+            on_hand,
+            reserved,
+            incoming_purchases,
+            planned_production,
+            available: result.available_quantity,
+            is_fulfilled: result.is_fulfilled,
+            suggested_date: result.suggested_date,
+        })
+    }
+}
+
+/// Perpetual costing: keeps each product's valuation and cost layers per warehouse in step with
+/// its stock movements, using the product's configured valuation method (FIFO by default).
+///
+/// Every receipt adds a cost layer and every issue draws layers down oldest first (newest first
+/// under LIFO), so open layers always hold the quantity on hand. FIFO and LIFO value issues at
+/// the cost of the layers consumed; weighted and moving average at the running average cost;
+/// standard cost at the standard, with purchase price variances captured on receipt.
+pub struct CostingService;
+
+impl Default for CostingService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CostingService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn get_settings(pool: &SqlitePool, product_id: Uuid) -> Result<ProductCostSettings> {
+        let mut conn = pool.acquire().await?;
+        Self::settings_in(&mut conn, product_id).await
+    }
+
+    /// Sets a product's valuation method and standard cost. Neither may change while stock is on
+    /// hand: the method would no longer match the layers, and a new standard cost needs a
+    /// revaluation to keep the inventory value consistent.
+    pub async fn configure(
+        pool: &SqlitePool,
+        product_id: Uuid,
+        valuation_method: ValuationMethod,
+        standard_cost: i64,
+    ) -> Result<ProductCostSettings> {
+        if standard_cost < 0 {
+            return Err(Error::validation("Standard cost cannot be negative"));
+        }
+        if valuation_method == ValuationMethod::StandardCost && standard_cost == 0 {
+            return Err(Error::validation("Standard cost is required for standard cost valuation"));
+        }
+
+        let mut tx = pool.begin().await?;
+        let exists: Option<(String,)> = sqlx::query_as("SELECT id FROM products WHERE id = ?")
+            .bind(product_id.to_string())
+            .fetch_optional(&mut *tx)
+            .await?;
+        if exists.is_none() {
+            return Err(Error::not_found("Product", &product_id.to_string()));
+        }
+
+        let current = Self::settings_in(&mut tx, product_id).await?;
+        let (on_hand,): (i64,) = sqlx::query_as("SELECT COALESCE(SUM(total_quantity), 0) FROM product_valuations WHERE product_id = ?")
+            .bind(product_id.to_string())
+            .fetch_one(&mut *tx)
+            .await?;
+        if on_hand != 0 {
+            if current.valuation_method != valuation_method {
+                return Err(Error::business_rule("Cannot change the valuation method of a product with stock on hand"));
+            }
+            if valuation_method == ValuationMethod::StandardCost && current.standard_cost != standard_cost {
+                return Err(Error::business_rule("Revalue the stock on hand to change its standard cost"));
+            }
+        }
+
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO product_cost_settings (product_id, valuation_method, standard_cost, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(product_id) DO UPDATE SET
+             valuation_method = excluded.valuation_method,
+             standard_cost = excluded.standard_cost,
+             updated_at = excluded.updated_at"
+        )
+        .bind(product_id.to_string())
+        .bind(format!("{:?}", valuation_method))
+        .bind(standard_cost)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(ProductCostSettings { product_id, valuation_method, standard_cost, updated_at: now })
+    }
+
+    /// Values a movement and updates the affected valuations and layers. Returns the movement's
+    /// total cost. Moves between locations of one warehouse leave its value unchanged.
+    pub(crate) async fn apply_movement(conn: &mut SqliteConnection, movement: &StockMovement) -> Result<i64> {
+        let settings = Self::settings_in(conn, movement.product_id).await?;
+        let reference = movement.reference.clone().unwrap_or_else(|| movement.movement_number.clone());
+
+        let mut changes = Vec::new();
+        for (location_id, change) in stock_changes(movement) {
+            changes.push((Self::warehouse_of(conn, location_id).await?, change));
+        }
+        if let [(from, _), (to, _)] = changes[..] {
+            if from == to {
+                let valuation = Self::valuation_for(conn, &settings, from).await?;
+                return Ok(valuation.current_unit_cost * movement.quantity);
+            }
+        }
+
+        // (quantity, value) slices the movement is valued at. A transfer carries the slices
+        // issued from the source into the destination.
+        let mut slices: Vec<(i64, i64)> = Vec::new();
+        for (warehouse_id, change) in changes {
+            if change < 0 {
+                slices = Self::issue(conn, &settings, warehouse_id, -change).await?;
+            } else if slices.is_empty() {
+                let unit_cost = Self::receive(conn, &settings, warehouse_id, movement, &reference).await?;
+                slices.push((change, change * unit_cost));
+            } else {
+                let mut valuation = Self::valuation_for(conn, &settings, warehouse_id).await?;
+                for &(quantity, value) in &slices {
+                    Self::add_layer(conn, &mut valuation, quantity, value, &reference, Some(movement.base.id)).await?;
+                }
+                SqliteValuationRepository::save_valuation_in(conn, &valuation).await?;
+            }
+        }
+        Ok(slices.iter().map(|(_, value)| value).sum())
+    }
+
+    /// Values an issue made outside [`StockService::record_movement`], e.g. shipping reserved
+    /// stock. Returns the cost of the quantity issued.
+    pub(crate) async fn issue_in(conn: &mut SqliteConnection, product_id: Uuid, warehouse_id: Uuid, quantity: i64) -> Result<i64> {
+        let settings = Self::settings_in(conn, product_id).await?;
+        let slices = Self::issue(conn, &settings, warehouse_id, quantity).await?;
+        Ok(slices.iter().map(|(_, value)| value).sum())
+    }
+
+    /// Changes the standard cost of a standard-costed product and revalues its stock on hand in
+    /// every warehouse. The change in value is captured as a revaluation variance, recorded as a
+    /// posted cost adjustment and posted to the ledger in the same transaction.
+    pub async fn revalue_standard_cost(pool: &SqlitePool, product_id: Uuid, new_standard_cost: i64, reason: &str) -> Result<CostAdjustment> {
+        if new_standard_cost <= 0 {
+            return Err(Error::validation("Standard cost must be positive"));
+        }
+        let mut tx = pool.begin().await?;
+        let settings = Self::settings_in(&mut tx, product_id).await?;
+        if settings.valuation_method != ValuationMethod::StandardCost {
+            return Err(Error::business_rule("Product is not valued at standard cost"));
+        }
+        if settings.standard_cost == new_standard_cost {
+            return Err(Error::validation("New standard cost matches the current standard cost"));
+        }
+
+        let now = Utc::now();
+        let mut adjustment = CostAdjustment {
+            id: Uuid::new_v4(),
+            adjustment_number: format!("SC-ADJ-{}-{}", now.format("%Y%m%d%H%M%S"), &Uuid::new_v4().simple().to_string()[..8]),
+            adjustment_type: CostAdjustmentType::StandardCostChange,
+            adjustment_date: now,
+            reason: reason.to_string(),
+            status: CostAdjustmentStatus::Posted,
+            approved_by: None,
+            approved_at: None,
+            journal_entry_id: None,
+            created_by: None,
+            created_at: now,
+        };
+
+        let warehouses: Vec<(String,)> = sqlx::query_as("SELECT warehouse_id FROM product_valuations WHERE product_id = ?")
+            .bind(product_id.to_string())
+            .fetch_all(&mut *tx)
+            .await?;
+        let mut lines = Vec::new();
+        for (warehouse_id,) in warehouses {
+            let mut valuation = Self::valuation_for(&mut tx, &settings, parse_uuid(&warehouse_id)).await?;
+            let old_unit_cost = valuation.current_unit_cost;
+            let old_total_value = valuation.total_value;
+            let new_total_value = valuation.total_quantity * new_standard_cost;
+
+            for layer in SqliteValuationRepository::layers_in(&mut tx, product_id, valuation.warehouse_id).await? {
+                SqliteValuationRepository::update_layer_in(&mut tx, layer.id, layer.remaining_quantity, new_standard_cost).await?;
+            }
+            valuation.standard_cost = new_standard_cost;
+            valuation.current_unit_cost = new_standard_cost;
+            valuation.total_value = new_total_value;
+            SqliteValuationRepository::save_valuation_in(&mut tx, &valuation).await?;
+
+            let line = CostAdjustmentLine {
+                id: Uuid::new_v4(),
+                adjustment_id: adjustment.id,
+                product_id,
+                warehouse_id: valuation.warehouse_id,
+                quantity: valuation.total_quantity,
+                old_unit_cost,
+                new_unit_cost: new_standard_cost,
+                old_total_value,
+                new_total_value,
+                value_change: new_total_value - old_total_value,
+            };
+            if line.value_change != 0 {
+                Self::record_variance(&mut tx, &line_variance(&line, CostVarianceType::Revaluation, &adjustment)).await?;
+            }
+            lines.push(line);
+        }
+        let entry = PostingService::post_document(&mut tx, PostingDocument {
+            event: PostingEvent::InventoryRevaluation,
+            source_type: "CostAdjustment".to_string(),
+            source_id: adjustment.id,
+            source_number: adjustment.adjustment_number.clone(),
+            date: now,
+            amount: lines.iter().map(|l| l.value_change).sum(),
+            tax_amount: 0,
+            currency: Currency::default(),
+            description: Some(reason.to_string()),
+        }).await?;
+        adjustment.journal_entry_id = entry.map(|e| e.base.id);
+        SqliteValuationRepository::create_adjustment_in(&mut tx, &adjustment, &lines).await?;
+
+        sqlx::query("UPDATE product_cost_settings SET standard_cost = ?, updated_at = ? WHERE product_id = ?")
+            .bind(new_standard_cost)
+            .bind(now.to_rfc3339())
+            .bind(product_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(adjustment)
+    }
+
+    /// The purchase price variance captured when a movement was received, zero when there was none.
+    pub async fn purchase_price_variance_in(conn: &mut SqliteConnection, movement_id: Uuid) -> Result<i64> {
+        let (amount,): (i64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(amount), 0) FROM inventory_cost_variances WHERE movement_id = ? AND variance_type = 'PurchasePrice'"
+        )
+        .bind(movement_id.to_string())
+        .fetch_one(&mut *conn)
+        .await?;
+        Ok(amount)
+    }
+
+    pub async fn list_variances(pool: &SqlitePool, product_id: Uuid) -> Result<Vec<CostVariance>> {
+        let rows = sqlx::query_as::<_, CostVarianceRow>(
+            "SELECT id, product_id, warehouse_id, variance_type, quantity, amount, reference, movement_id, adjustment_id, created_at
+             FROM inventory_cost_variances WHERE product_id = ? ORDER BY created_at"
+        )
+        .bind(product_id.to_string())
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    /// The value of stock on hand per product and warehouse. Without a warehouse filter, the
+    /// total is reconciled against the posted balance of `inventory_account_id`; the report is
+    /// reconciled when the balances agree and every costed quantity matches the stock levels.
+    pub async fn valuation_report(
+        pool: &SqlitePool,
+        warehouse_id: Option<Uuid>,
+        inventory_account_id: Option<Uuid>,
+    ) -> Result<PerpetualValuationReport> {
+        let rows = sqlx::query_as::<_, PerpetualValuationRow>(
+            "SELECT v.product_id, v.warehouse_id, v.valuation_method, v.total_quantity, v.current_unit_cost, v.total_value,
+                    COALESCE((SELECT SUM(l.remaining_quantity * l.unit_cost) FROM inventory_cost_layers l
+                              WHERE l.product_id = v.product_id AND l.warehouse_id = v.warehouse_id AND l.remaining_quantity > 0), 0) AS layer_value,
+                    COALESCE((SELECT SUM(sl.quantity) FROM stock_levels sl JOIN stock_locations loc ON loc.id = sl.location_id
+                              WHERE sl.product_id = v.product_id AND loc.warehouse_id = v.warehouse_id), 0) AS stock_quantity
+             FROM product_valuations v
+             WHERE (? IS NULL OR v.warehouse_id = ?) AND (v.total_quantity != 0 OR v.total_value != 0)
+             ORDER BY v.warehouse_id, v.product_id"
+        )
+        .bind(warehouse_id.map(|id| id.to_string()))
+        .bind(warehouse_id.map(|id| id.to_string()))
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+
+        let lines: Vec<PerpetualValuationLine> = rows.into_iter().map(|r| r.into()).collect();
+        let total_quantity = lines.iter().map(|l| l.quantity).sum();
+        let total_value = lines.iter().map(|l| l.total_value).sum();
+
+        let gl_balance = match (warehouse_id, inventory_account_id) {
+            (None, Some(account_id)) => {
+                let (balance,): (i64,) = sqlx::query_as(
+                    "SELECT COALESCE(SUM(jl.debit - jl.credit), 0) FROM journal_lines jl
+                     JOIN journal_entries je ON je.id = jl.journal_entry_id
+                     WHERE jl.account_id = ? AND je.status = 'Posted'"
+                )
+                .bind(account_id.to_string())
+                .fetch_one(pool)
+                .await
+                .map_err(Error::Database)?;
+                Some(balance)
+            }
+            _ => None,
+        };
+        let difference = gl_balance.map(|balance| total_value - balance);
+        let is_reconciled = difference == Some(0) && lines.iter().all(|l| l.quantity == l.stock_quantity);
+
+        Ok(PerpetualValuationReport {
+            as_of: Utc::now(),
+            warehouse_id,
+            lines,
+            total_quantity,
+            total_value,
+            gl_account_id: inventory_account_id,
+            gl_balance,
+            difference,
+            is_reconciled,
+        })
+    }
+
+    async fn settings_in(conn: &mut SqliteConnection, product_id: Uuid) -> Result<ProductCostSettings> {
+        let row: Option<(String, i64, String)> = sqlx::query_as(
+            "SELECT valuation_method, standard_cost, updated_at FROM product_cost_settings WHERE product_id = ?"
+        )
+        .bind(product_id.to_string())
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(match row {
+            Some((method, standard_cost, updated_at)) => ProductCostSettings {
+                product_id,
+                valuation_method: parse_valuation_method(&method),
+                standard_cost,
+                updated_at: parse_timestamp(&updated_at),
+            },
+            None => ProductCostSettings {
+                product_id,
+                valuation_method: ValuationMethod::FIFO,
+                standard_cost: 0,
+                updated_at: Utc::now(),
+            },
+        })
+    }
+
+    async fn warehouse_of(conn: &mut SqliteConnection, location_id: Uuid) -> Result<Uuid> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT warehouse_id FROM stock_locations WHERE id = ?")
+            .bind(location_id.to_string())
+            .fetch_optional(&mut *conn)
+            .await?;
+        row.map(|(id,)| parse_uuid(&id))
+            .ok_or_else(|| Error::not_found("StockLocation", &location_id.to_string()))
+    }
+
+    async fn valuation_for(conn: &mut SqliteConnection, settings: &ProductCostSettings, warehouse_id: Uuid) -> Result<ProductValuation> {
+        let now = Utc::now();
+        let mut valuation = SqliteValuationRepository::valuation_in(conn, settings.product_id, warehouse_id).await?
+            .unwrap_or_else(|| ProductValuation {
+                id: Uuid::new_v4(),
+                product_id: settings.product_id,
+                warehouse_id,
+                valuation_method: settings.valuation_method,
+                standard_cost: settings.standard_cost,
+                current_unit_cost: settings.standard_cost,
+                total_quantity: 0,
+                total_value: 0,
+                last_receipt_cost: 0,
+                last_receipt_date: None,
+                last_issue_cost: 0,
+                last_issue_date: None,
+                created_at: now,
+                updated_at: now,
+            });
+        valuation.valuation_method = settings.valuation_method;
+        valuation.standard_cost = settings.standard_cost;
+        Ok(valuation)
+    }
+
+    /// Adds received stock at its cost: the movement's unit cost (or the current cost when none
+    /// is given), or the standard cost with the difference captured as a purchase price variance.
+    async fn receive(
+        conn: &mut SqliteConnection,
+        settings: &ProductCostSettings,
+        warehouse_id: Uuid,
+        movement: &StockMovement,
+        reference: &str,
+    ) -> Result<i64> {
+        let mut valuation = Self::valuation_for(conn, settings, warehouse_id).await?;
+        let unit_cost = match settings.valuation_method {
+            ValuationMethod::StandardCost => settings.standard_cost,
+            _ => movement.unit_cost.unwrap_or(valuation.current_unit_cost),
+        };
+        if let Some(actual) = movement.unit_cost.filter(|actual| *actual != unit_cost) {
+            Self::record_variance(conn, &CostVariance {
+                id: Uuid::new_v4(),
+                product_id: settings.product_id,
+                warehouse_id,
+                variance_type: CostVarianceType::PurchasePrice,
+                quantity: movement.quantity,
+                amount: (actual - unit_cost) * movement.quantity,
+                reference: Some(reference.to_string()),
+                movement_id: Some(movement.base.id),
+                adjustment_id: None,
+                created_at: Utc::now(),
+            }).await?;
+        }
+
+        Self::add_layer(conn, &mut valuation, movement.quantity, movement.quantity * unit_cost, reference, Some(movement.base.id)).await?;
+        SqliteValuationRepository::save_valuation_in(conn, &valuation).await?;
+        Ok(unit_cost)
+    }
+
+    async fn add_layer(
+        conn: &mut SqliteConnection,
+        valuation: &mut ProductValuation,
+        quantity: i64,
+        value: i64,
+        reference: &str,
+        receipt_id: Option<Uuid>,
+    ) -> Result<()> {
+        let now = Utc::now();
+        let unit_cost = value / quantity;
+        SqliteValuationRepository::add_layer_in(conn, &InventoryCostLayer {
+            id: Uuid::new_v4(),
+            product_id: valuation.product_id,
+            warehouse_id: valuation.warehouse_id,
+            layer_date: now,
+            receipt_reference: reference.to_string(),
+            receipt_id,
+            quantity,
+            unit_cost,
+            remaining_quantity: quantity,
+            total_value: value,
+            created_at: now,
+        }).await?;
+
+        valuation.total_quantity += quantity;
+        valuation.total_value += value;
+        valuation.current_unit_cost = match valuation.valuation_method {
+            ValuationMethod::StandardCost => valuation.standard_cost,
+            _ if valuation.total_quantity > 0 => valuation.total_value / valuation.total_quantity,
+            _ => unit_cost,
+        };
+        valuation.last_receipt_cost = unit_cost;
+        valuation.last_receipt_date = Some(now);
+        Self::pool_average_layers(conn, valuation).await
+    }
+
+    /// Average-cost stock is one pool: its quantity sits on the newest open layer at the
+    /// current average cost, so issues never draw down individual receipts.
+    async fn pool_average_layers(conn: &mut SqliteConnection, valuation: &ProductValuation) -> Result<()> {
+        if !matches!(valuation.valuation_method, ValuationMethod::WeightedAverage | ValuationMethod::MovingAverage) {
+            return Ok(());
+        }
+        let mut layers = SqliteValuationRepository::layers_in(conn, valuation.product_id, valuation.warehouse_id).await?;
+        let Some(newest) = layers.pop() else {
+            return Ok(());
+        };
+        for layer in layers {
+            SqliteValuationRepository::update_layer_in(conn, layer.id, 0, layer.unit_cost).await?;
+        }
+        SqliteValuationRepository::update_layer_in(conn, newest.id, valuation.total_quantity.max(0), valuation.current_unit_cost).await
+    }
+
+    /// Draws `quantity` out of a warehouse's layers and returns the (quantity, value) slices it
+    /// was valued at. Stock that predates costing has no layers and goes out at the current cost.
+    /// Average-cost issues take the pooled layer down rather than the oldest receipts.
+    async fn issue(conn: &mut SqliteConnection, settings: &ProductCostSettings, warehouse_id: Uuid, quantity: i64) -> Result<Vec<(i64, i64)>> {
+        let mut valuation = Self::valuation_for(conn, settings, warehouse_id).await?;
+        let mut layers = SqliteValuationRepository::layers_in(conn, settings.product_id, warehouse_id).await?;
+        if settings.valuation_method == ValuationMethod::LIFO {
+            layers.reverse();
+        }
+
+        let mut remaining = quantity;
+        let mut layer_slices = Vec::new();
+        let averaged = matches!(settings.valuation_method, ValuationMethod::WeightedAverage | ValuationMethod::MovingAverage);
+        for layer in layers {
+            if remaining == 0 || averaged {
+                break;
+            }
+            let taken = layer.remaining_quantity.min(remaining);
+            SqliteValuationRepository::update_layer_in(conn, layer.id, layer.remaining_quantity - taken, layer.unit_cost).await?;
+            layer_slices.push((taken, taken * layer.unit_cost));
+            remaining -= taken;
+        }
+
+        let slices = match settings.valuation_method {
+            ValuationMethod::FIFO | ValuationMethod::LIFO => {
+                if remaining > 0 {
+                    layer_slices.push((remaining, remaining * valuation.current_unit_cost));
+                }
+                layer_slices
+            }
+            ValuationMethod::StandardCost => vec![(quantity, quantity * settings.standard_cost)],
+            ValuationMethod::WeightedAverage | ValuationMethod::MovingAverage => {
+                // Issuing everything takes the whole value so rounding never leaves a residue.
+                let value = if quantity == valuation.total_quantity {
+                    valuation.total_value
+                } else {
+                    quantity * valuation.current_unit_cost
+                };
+                vec![(quantity, value)]
+            }
+        };
+
+        let value: i64 = slices.iter().map(|(_, value)| value).sum();
+        let now = Utc::now();
+        valuation.total_quantity -= quantity;
+        valuation.total_value -= value;
+        if valuation.valuation_method != ValuationMethod::StandardCost && valuation.total_quantity > 0 {
+            valuation.current_unit_cost = valuation.total_value / valuation.total_quantity;
+        }
+        valuation.last_issue_cost = value / quantity;
+        valuation.last_issue_date = Some(now);
+        SqliteValuationRepository::save_valuation_in(conn, &valuation).await?;
+        Self::pool_average_layers(conn, &valuation).await?;
+
+        Ok(slices)
+    }
+
+    async fn record_variance(conn: &mut SqliteConnection, variance: &CostVariance) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO inventory_cost_variances (id, product_id, warehouse_id, variance_type, quantity, amount, reference, movement_id, adjustment_id, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(variance.id.to_string())
+        .bind(variance.product_id.to_string())
+        .bind(variance.warehouse_id.to_string())
+        .bind(format!("{:?}", variance.variance_type))
+        .bind(variance.quantity)
+        .bind(variance.amount)
+        .bind(&variance.reference)
+        .bind(variance.movement_id.map(|id| id.to_string()))
+        .bind(variance.adjustment_id.map(|id| id.to_string()))
+        .bind(variance.created_at.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+}
+
+fn line_variance(line: &CostAdjustmentLine, variance_type: CostVarianceType, adjustment: &CostAdjustment) -> CostVariance {
+    CostVariance {
+        id: Uuid::new_v4(),
+        product_id: line.product_id,
+        warehouse_id: line.warehouse_id,
+        variance_type,
+        quantity: line.quantity,
+        amount: line.value_change,
+        reference: Some(adjustment.adjustment_number.clone()),
+        movement_id: None,
+        adjustment_id: Some(adjustment.id),
+        created_at: adjustment.created_at,
+    }
+}
+
+fn parse_valuation_method(value: &str) -> ValuationMethod {
+    match value {
+        "LIFO" => ValuationMethod::LIFO,
+        "WeightedAverage" => ValuationMethod::WeightedAverage,
+        "StandardCost" => ValuationMethod::StandardCost,
+        "MovingAverage" => ValuationMethod::MovingAverage,
+        _ => ValuationMethod::FIFO,
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CostVarianceRow {
+    id: String,
+    product_id: String,
+    warehouse_id: String,
+    variance_type: String,
+    quantity: i64,
+    amount: i64,
+    reference: Option<String>,
+    movement_id: Option<String>,
+    adjustment_id: Option<String>,
+    created_at: String,
+}
+
+impl From<CostVarianceRow> for CostVariance {
+    fn from(r: CostVarianceRow) -> Self {
+        Self {
+            id: parse_uuid(&r.id),
+            product_id: parse_uuid(&r.product_id),
+            warehouse_id: parse_uuid(&r.warehouse_id),
+            variance_type: match r.variance_type.as_str() {
+                "Revaluation" => CostVarianceType::Revaluation,
+                _ => CostVarianceType::PurchasePrice,
+            },
+            quantity: r.quantity,
+            amount: r.amount,
+            reference: r.reference,
+            movement_id: r.movement_id.as_deref().map(parse_uuid),
+            adjustment_id: r.adjustment_id.as_deref().map(parse_uuid),
+            created_at: parse_timestamp(&r.created_at),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PerpetualValuationRow {
+    product_id: String,
+    warehouse_id: String,
+    valuation_method: String,
+    total_quantity: i64,
+    current_unit_cost: i64,
+    total_value: i64,
+    layer_value: i64,
+    stock_quantity: i64,
+}
+
+impl From<PerpetualValuationRow> for PerpetualValuationLine {
+    fn from(r: PerpetualValuationRow) -> Self {
+        Self {
+            product_id: parse_uuid(&r.product_id),
+            warehouse_id: parse_uuid(&r.warehouse_id),
+            valuation_method: parse_valuation_method(&r.valuation_method),
+            quantity: r.total_quantity,
+            stock_quantity: r.stock_quantity,
+            unit_cost: r.current_unit_cost,
+            total_value: r.total_value,
+            layer_value: r.layer_value,
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use chrono::Utc;
+use erp_core::BaseEntity;
+use erp_finance::{PostingEvent, PostingService};
+use erp_inventory::{
+    CostAdjustmentType, CostVarianceType, CostingService, MovementType, StockMovement, StockService, ValuationMethod,
+};
+use erp_core::testing::{insert, memory_pool};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+async fn setup() -> SqlitePool {
+    memory_pool(&[
+        include_str!("../../migrations/20240101000000_finance.sql"),
+        include_str!("../../migrations/20240101000001_inventory.sql"),
+        include_str!("../../migrations/20240101000018_enterprise_additions.sql"),
+        include_str!("../../migrations/20260309000000_posting_rules.sql"),
+        include_str!("../../migrations/20260315000000_inventory_costing.sql"),
+        include_str!("../../migrations/20260329000500_journal_line_currency.sql"),
+    ]).await
+}
+
+async fn product(pool: &SqlitePool, sku: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    let now = Utc::now().to_rfc3339();
+    insert(pool, "INSERT INTO products (id, sku, name, product_type, unit_of_measure, created_at, updated_at) VALUES (?, ?, ?, 'Goods', 'PCS', ?, ?)", &[&id.to_string(), sku, sku, &now, &now]).await;
+    id
+}
+
+async fn location(pool: &SqlitePool, code: &str) -> (Uuid, Uuid) {
+    let (warehouse, location) = (Uuid::new_v4(), Uuid::new_v4());
+    let now = Utc::now().to_rfc3339();
+    insert(pool, "INSERT INTO warehouses (id, code, name, created_at, updated_at) VALUES (?, ?, ?, ?, ?)", &[&warehouse.to_string(), code, code, &now, &now]).await;
+    insert(pool, "INSERT INTO stock_locations (id, warehouse_id, code, name, created_at, updated_at) VALUES (?, ?, 'A1', 'Aisle 1', ?, ?)", &[&location.to_string(), &warehouse.to_string(), &now, &now]).await;
+    (warehouse, location)
+}
+
+async fn movement(
+    pool: &SqlitePool,
+    movement_type: MovementType,
+    product_id: Uuid,
+    from: Option<Uuid>,
+    to: Uuid,
+    quantity: i64,
+    unit_cost: Option<i64>,
+) -> StockMovement {
+    StockService::new().record_movement(pool, StockMovement {
+        base: BaseEntity::new(),
+        movement_number: String::new(),
+        movement_type,
+        product_id,
+        from_location_id: from,
+        to_location_id: to,
+        quantity,
+        reference: None,
+        date: Utc::now(),
+        unit_cost,
+        total_cost: None,
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_movements_update_cost_layers_by_valuation_method() {
+    let pool = setup().await;
+    let (wh1, loc1) = location(&pool, "WH1").await;
+    let (wh2, loc2) = location(&pool, "WH2").await;
+
+    // FIFO is the default: issues take the oldest layers first.
+    let fifo = product(&pool, "FIFO").await;
+    movement(&pool, MovementType::Receipt, fifo, None, loc1, 10, Some(100)).await;
+    movement(&pool, MovementType::Receipt, fifo, None, loc1, 5, Some(120)).await;
+    let issued = movement(&pool, MovementType::Issue, fifo, Some(loc1), loc1, 12, None).await;
+    assert_eq!(issued.total_cost, Some(10 * 100 + 2 * 120));
+    let stock = StockService::new().get_stock_level(&pool, fifo, loc1).await.unwrap();
+    assert_eq!(stock.quantity, 3);
+
+    // A transfer carries the cost of the layers it takes to the other warehouse.
+    let transfer = movement(&pool, MovementType::Transfer, fifo, Some(loc1), loc2, 2, None).await;
+    assert_eq!(transfer.total_cost, Some(240));
+    let report = CostingService::valuation_report(&pool, Some(wh2), None).await.unwrap();
+    assert_eq!((report.total_quantity, report.total_value), (2, 240));
+
+    let lifo = product(&pool, "LIFO").await;
+    CostingService::configure(&pool, lifo, ValuationMethod::LIFO, 0).await.unwrap();
+    movement(&pool, MovementType::Receipt, lifo, None, loc1, 5, Some(100)).await;
+    movement(&pool, MovementType::Receipt, lifo, None, loc1, 5, Some(200)).await;
+    let issued = movement(&pool, MovementType::Issue, lifo, Some(loc1), loc1, 6, None).await;
+    assert_eq!(issued.total_cost, Some(5 * 200 + 100));
+
+    let average = product(&pool, "AVG").await;
+    CostingService::configure(&pool, average, ValuationMethod::WeightedAverage, 0).await.unwrap();
+    movement(&pool, MovementType::Receipt, average, None, loc1, 10, Some(100)).await;
+    movement(&pool, MovementType::Receipt, average, None, loc1, 10, Some(130)).await;
+    let issued = movement(&pool, MovementType::Issue, average, Some(loc1), loc1, 5, None).await;
+    assert_eq!(issued.total_cost, Some(5 * 115));
+    // Average-cost receipts are pooled rather than drawn down oldest first.
+    let layers: Vec<(i64, i64)> = sqlx::query_as("SELECT remaining_quantity, unit_cost FROM inventory_cost_layers WHERE product_id = ? AND remaining_quantity > 0")
+        .bind(average.to_string())
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(layers, vec![(15, 115)]);
+    // An increasing adjustment without a cost comes in at the current average.
+    let adjusted = movement(&pool, MovementType::Adjustment, average, None, loc1, 5, None).await;
+    assert_eq!(adjusted.total_cost, Some(5 * 115));
+    let written_off = movement(&pool, MovementType::Adjustment, average, Some(loc1), loc1, 20, None).await;
+    assert_eq!(written_off.total_cost, Some(20 * 115));
+
+    // Standard cost: receipts come in at standard and the price difference is a variance.
+    let standard = product(&pool, "STD").await;
+    CostingService::configure(&pool, standard, ValuationMethod::StandardCost, 50).await.unwrap();
+    let received = movement(&pool, MovementType::Receipt, standard, None, loc1, 10, Some(55)).await;
+    assert_eq!(received.total_cost, Some(500));
+    let err = CostingService::configure(&pool, standard, ValuationMethod::StandardCost, 60).await.unwrap_err();
+    assert!(err.to_string().contains("Revalue"));
+    assert!(CostingService::configure(&pool, standard, ValuationMethod::FIFO, 0).await.is_err());
+
+    let adjustment = CostingService::revalue_standard_cost(&pool, standard, 60, "Annual cost roll").await.unwrap();
+    assert!(matches!(adjustment.adjustment_type, CostAdjustmentType::StandardCostChange));
+    let variances = CostingService::list_variances(&pool, standard).await.unwrap();
+    assert_eq!(variances.len(), 2);
+    assert_eq!((variances[0].variance_type, variances[0].amount), (CostVarianceType::PurchasePrice, 50));
+    assert_eq!((variances[1].variance_type, variances[1].amount), (CostVarianceType::Revaluation, 100));
+    assert_eq!(variances[1].adjustment_id, Some(adjustment.id));
+    let issued = movement(&pool, MovementType::Issue, standard, Some(loc1), loc1, 4, None).await;
+    assert_eq!(issued.total_cost, Some(240));
+    assert!(CostingService::revalue_standard_cost(&pool, fifo, 10, "Not standard").await.is_err());
+
+    // The perpetual valuation agrees with the layers and stock levels; it reconciles once the
+    // inventory account carries the same balance.
+    let report = CostingService::valuation_report(&pool, Some(wh1), None).await.unwrap();
+    let expected = [(fifo, 1, 120), (lifo, 4, 400), (average, 0, 0), (standard, 6, 360)];
+    for (product_id, quantity, value) in expected {
+        let line = report.lines.iter().find(|l| l.product_id == product_id);
+        match line {
+            Some(line) => {
+                assert_eq!((line.quantity, line.stock_quantity, line.total_value, line.layer_value), (quantity, quantity, value, value));
+            }
+            None => assert_eq!(quantity, 0),
+        }
+    }
+
+    let account = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+    insert(&pool, "INSERT INTO accounts (id, code, name, account_type, created_at, updated_at) VALUES (?, '1300', 'Inventory', 'Asset', ?, ?)", &[&account, &now, &now]).await;
+    let entry = Uuid::new_v4().to_string();
+    insert(&pool, "INSERT INTO journal_entries (id, entry_number, date, description, status, created_at, updated_at) VALUES (?, 'JE-1', ?, 'Stock', 'Posted', ?, ?)", &[&entry, &now, &now, &now]).await;
+    insert(&pool, "INSERT INTO journal_lines (id, journal_entry_id, account_id, debit, credit) VALUES (?, ?, ?, 1120, 0)", &[&Uuid::new_v4().to_string(), &entry, &account]).await;
+
+    let account_id = Uuid::parse_str(&account).unwrap();
+    let report = CostingService::valuation_report(&pool, None, Some(account_id)).await.unwrap();
+    assert_eq!(report.total_value, 120 + 240 + 400 + 360);
+    assert_eq!((report.gl_balance, report.difference), (Some(1120), Some(0)));
+    assert!(report.is_reconciled);
+
+    movement(&pool, MovementType::Receipt, fifo, None, loc1, 1, Some(80)).await;
+    let report = CostingService::valuation_report(&pool, None, Some(account_id)).await.unwrap();
+    assert_eq!(report.difference, Some(80));
+    assert!(!report.is_reconciled);
+}
+
+async fn account(pool: &SqlitePool, code: &str, account_type: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    let now = Utc::now().to_rfc3339();
+    insert(pool, "INSERT INTO accounts (id, code, name, account_type, status, created_at, updated_at) VALUES (?, ?, ?, ?, 'Active', ?, ?)", &[&id.to_string(), code, code, account_type, &now, &now]).await;
+    id
+}
+
+async fn balance(pool: &SqlitePool, account_id: Uuid) -> i64 {
+    let (balance,): (i64,) = sqlx::query_as("SELECT COALESCE(SUM(debit - credit), 0) FROM journal_lines WHERE account_id = ?")
+        .bind(account_id.to_string())
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    balance
+}
+
+#[tokio::test]
+async fn test_movements_and_revaluations_post_to_the_ledger() {
+    let pool = setup().await;
+    let (_, loc) = location(&pool, "WH1").await;
+    let inventory = account(&pool, "1300", "Asset").await;
+    let clearing = account(&pool, "2100", "Liability").await;
+    let cogs = account(&pool, "5000", "Expense").await;
+    let variance = account(&pool, "5100", "Expense").await;
+    let revaluation = account(&pool, "5200", "Expense").await;
+    PostingService::upsert_rule(&pool, PostingEvent::GoodsReceipt, inventory, clearing, None, None).await.unwrap();
+    PostingService::upsert_rule(&pool, PostingEvent::GoodsIssue, cogs, inventory, None, None).await.unwrap();
+    PostingService::upsert_rule(&pool, PostingEvent::PurchasePriceVariance, variance, clearing, None, None).await.unwrap();
+    PostingService::upsert_rule(&pool, PostingEvent::InventoryAdjustment, inventory, variance, None, None).await.unwrap();
+    PostingService::upsert_rule(&pool, PostingEvent::InventoryRevaluation, inventory, revaluation, None, None).await.unwrap();
+
+    let standard = product(&pool, "STD").await;
+    CostingService::configure(&pool, standard, ValuationMethod::StandardCost, 50).await.unwrap();
+    movement(&pool, MovementType::Receipt, standard, None, loc, 10, Some(45)).await;
+    assert_eq!((balance(&pool, inventory).await, balance(&pool, clearing).await, balance(&pool, variance).await), (500, -450, -50));
+
+    movement(&pool, MovementType::Issue, standard, Some(loc), loc, 4, None).await;
+    assert_eq!((balance(&pool, inventory).await, balance(&pool, cogs).await), (300, 200));
+
+    movement(&pool, MovementType::Adjustment, standard, Some(loc), loc, 1, None).await;
+    assert_eq!((balance(&pool, inventory).await, balance(&pool, variance).await), (250, 0));
+
+    let adjustment = CostingService::revalue_standard_cost(&pool, standard, 40, "Cost roll").await.unwrap();
+    assert!(adjustment.journal_entry_id.is_some());
+    assert_eq!((balance(&pool, inventory).await, balance(&pool, revaluation).await), (200, 50));
+    let report = CostingService::valuation_report(&pool, None, Some(inventory)).await.unwrap();
+    assert!(report.is_reconciled);
+}
@@ -0,0 +1,113 @@
+use chrono::{Duration, Utc};
+use erp_inventory::{AtpService, ReservationLine, ReservationService, ReservationStatus};
+use erp_core::testing::{insert, memory_pool};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+async fn setup() -> SqlitePool {
+    memory_pool(&[
+        include_str!("../../migrations/20240101000001_inventory.sql"),
+        include_str!("../../migrations/20240101000003_purchasing.sql"),
+        include_str!("../../migrations/20240101000004_manufacturing.sql"),
+        include_str!("../../migrations/20260314000000_stock_reservations.sql"),
+        include_str!("../../migrations/20260315000000_inventory_costing.sql"),
+    ]).await
+}
+
+async fn level(pool: &SqlitePool, product_id: &str, location_id: &str) -> (i64, i64, i64) {
+    sqlx::query_as("SELECT quantity, reserved_quantity, available_quantity FROM stock_levels WHERE product_id = ? AND location_id = ?")
+        .bind(product_id)
+        .bind(location_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_reservations_follow_the_order_lifecycle_and_feed_atp() {
+    let pool = setup().await;
+    let now = Utc::now().to_rfc3339();
+    let (wh1, wh2) = (Uuid::new_v4(), Uuid::new_v4());
+    let (loc1, loc2) = (Uuid::new_v4().to_string(), Uuid::new_v4().to_string());
+    let (product, service) = (Uuid::new_v4(), Uuid::new_v4());
+    let product_id = product.to_string();
+
+    for (id, code) in [(wh1, "WH1"), (wh2, "WH2")] {
+        insert(&pool, "INSERT INTO warehouses (id, code, name, created_at, updated_at) VALUES (?, ?, ?, ?, ?)", &[&id.to_string(), code, code, &now, &now]).await;
+    }
+    for (id, warehouse) in [(&loc1, wh1), (&loc2, wh2)] {
+        insert(&pool, "INSERT INTO stock_locations (id, warehouse_id, code, name, created_at, updated_at) VALUES (?, ?, 'A1', 'Aisle 1', ?, ?)", &[id, &warehouse.to_string(), &now, &now]).await;
+    }
+    for (id, sku, product_type) in [(product, "WIDGET", "Goods"), (service, "INSTALL", "Service")] {
+        insert(&pool, "INSERT INTO products (id, sku, name, product_type, unit_of_measure, created_at, updated_at) VALUES (?, ?, ?, ?, 'PCS', ?, ?)", &[&id.to_string(), sku, sku, product_type, &now, &now]).await;
+    }
+    for (location, quantity) in [(&loc1, "10"), (&loc2, "5")] {
+        insert(&pool, "INSERT INTO stock_levels (id, product_id, location_id, quantity, reserved_quantity, available_quantity) VALUES (?, ?, ?, ?, 0, ?)", &[&Uuid::new_v4().to_string(), &product_id, location, quantity, quantity]).await;
+    }
+
+    // A line larger than any one location is split across warehouses.
+    let order_a = Uuid::new_v4();
+    let reserved = ReservationService::reserve(&pool, "SalesOrder", order_a, &[ReservationLine { product_id: product, quantity: 12, source_line_id: None }], None).await.unwrap();
+    assert_eq!(reserved.len(), 2);
+    assert_eq!(reserved.iter().map(|r| r.quantity).sum::<i64>(), 12);
+    assert_eq!(level(&pool, &product_id, &loc1).await, (10, 10, 0));
+    assert_eq!(level(&pool, &product_id, &loc2).await, (5, 2, 3));
+
+    // The same units cannot be promised twice, and a failed reservation leaves nothing behind.
+    let order_b = Uuid::new_v4();
+    let err = ReservationService::reserve(&pool, "SalesOrder", order_b, &[ReservationLine { product_id: product, quantity: 4, source_line_id: None }], None).await.unwrap_err();
+    assert!(err.to_string().contains("Insufficient stock"));
+    assert_eq!(level(&pool, &product_id, &loc2).await, (5, 2, 3));
+    assert!(ReservationService::list_for_source(&pool, "SalesOrder", order_b).await.unwrap().is_empty());
+
+    let lines = [
+        ReservationLine { product_id: product, quantity: 3, source_line_id: Some(Uuid::new_v4()) },
+        ReservationLine { product_id: service, quantity: 1, source_line_id: Some(Uuid::new_v4()) },
+    ];
+    let err = ReservationService::reserve(&pool, "SalesOrder", order_b, &lines, Some(wh1)).await.unwrap_err();
+    assert!(err.to_string().contains("Insufficient stock"));
+    let reserved = ReservationService::reserve(&pool, "SalesOrder", order_b, &lines, Some(wh2)).await.unwrap();
+    assert_eq!(reserved.len(), 1);
+    assert_eq!(reserved[0].warehouse_id, wh2);
+    assert!(ReservationService::reserve(&pool, "SalesOrder", order_b, &lines, None).await.is_err());
+
+    assert!(AtpService::check(&pool, product, 0, Utc::now(), None).await.is_err());
+    let atp = AtpService::check(&pool, product, 1, Utc::now(), None).await.unwrap();
+    assert_eq!((atp.on_hand, atp.reserved, atp.available), (15, 15, 0));
+    assert!(!atp.is_fulfilled);
+
+    // Open purchase order lines (less what was received) and planned work orders add supply.
+    let (po, po_line) = (Uuid::new_v4().to_string(), Uuid::new_v4().to_string());
+    let po_due = Utc::now() + Duration::days(7);
+    let wo_due = Utc::now() + Duration::days(3);
+    insert(&pool, "INSERT INTO purchase_orders (id, po_number, vendor_id, order_date, expected_date, status, created_at, updated_at) VALUES (?, 'PO-1', ?, ?, ?, 'Approved', ?, ?)", &[&po, &Uuid::new_v4().to_string(), &now, &po_due.to_rfc3339(), &now, &now]).await;
+    insert(&pool, "INSERT INTO purchase_order_lines (id, purchase_order_id, product_id, description, quantity, unit_price, line_total) VALUES (?, ?, ?, 'Widgets', 20, 100, 2000)", &[&po_line, &po, &product_id]).await;
+    insert(&pool, "INSERT INTO goods_receipt_lines (id, goods_receipt_id, po_line_id, product_id, quantity_ordered, quantity_received) VALUES (?, ?, ?, ?, 20, 5)", &[&Uuid::new_v4().to_string(), &Uuid::new_v4().to_string(), &po_line, &product_id]).await;
+    insert(&pool, "INSERT INTO work_orders (id, order_number, product_id, bom_id, quantity, planned_start, planned_end, status, created_at, updated_at) VALUES (?, 'WO-1', ?, ?, 4, ?, ?, 'Released', ?, ?)", &[&Uuid::new_v4().to_string(), &product_id, &Uuid::new_v4().to_string(), &now, &wo_due.to_rfc3339(), &now, &now]).await;
+
+    let atp = AtpService::check(&pool, product, 10, Utc::now() + Duration::days(10), None).await.unwrap();
+    assert_eq!((atp.incoming_purchases, atp.planned_production, atp.available), (15, 4, 19));
+    assert!(atp.is_fulfilled);
+    let atp = AtpService::check(&pool, product, 10, Utc::now() + Duration::days(1), None).await.unwrap();
+    assert_eq!((atp.incoming_purchases, atp.planned_production, atp.available), (0, 0, 0));
+    assert_eq!(atp.suggested_date.map(|d| d.timestamp()), Some(po_due.timestamp()));
+    let atp = AtpService::check(&pool, product, 1, Utc::now() + Duration::days(10), Some(wh2)).await.unwrap();
+    assert_eq!((atp.on_hand, atp.reserved, atp.incoming_purchases, atp.available), (5, 5, 0, 0));
+
+    // Shipping consumes the reservations and issues the stock.
+    let consumed = ReservationService::consume(&pool, "SalesOrder", order_a, "SO-A").await.unwrap();
+    assert!(consumed.iter().all(|r| r.status == ReservationStatus::Consumed));
+    assert_eq!(level(&pool, &product_id, &loc1).await, (0, 0, 0));
+    assert_eq!(level(&pool, &product_id, &loc2).await, (3, 3, 0));
+    let (issued,): (i64,) = sqlx::query_as("SELECT COALESCE(SUM(quantity), 0) FROM stock_movements WHERE movement_type = 'Issue' AND reference = 'SO-A'")
+        .fetch_one(&pool).await.unwrap();
+    assert_eq!(issued, 12);
+
+    // Cancelling releases them; releasing again is a no-op.
+    let released = ReservationService::release(&pool, "SalesOrder", order_b).await.unwrap();
+    assert_eq!(released.len(), 1);
+    assert_eq!(level(&pool, &product_id, &loc2).await, (3, 0, 3));
+    assert!(ReservationService::release(&pool, "SalesOrder", order_b).await.unwrap().is_empty());
+    let history = ReservationService::list_for_source(&pool, "SalesOrder", order_b).await.unwrap();
+    assert_eq!(history[0].status, ReservationStatus::Released);
+}
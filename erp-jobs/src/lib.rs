@@ -1,6 +1,8 @@
 pub mod models;
 pub mod repository;
 pub mod service;
+pub mod worker;
 
 pub use models::*;
 pub use service::*;
+pub use worker::*;
@@ -13,6 +13,8 @@ pub trait JobRepository: Send + Sync {
     async fn list_pending(&self, pool: &SqlitePool, limit: i32) -> anyhow::Result<Vec<ScheduledJob>>;
     async fn list_scheduled(&self, pool: &SqlitePool, before: DateTime<Utc>) -> anyhow::Result<Vec<ScheduledJob>>;
     async fn list_recurring(&self, pool: &SqlitePool) -> anyhow::Result<Vec<ScheduledJob>>;
+    async fn list_by_handler(&self, pool: &SqlitePool, handler: &str) -> anyhow::Result<Vec<ScheduledJob>>;
+    async fn claim_due(&self, pool: &SqlitePool, worker_id: &str, now: DateTime<Utc>, lease_expired_before: DateTime<Utc>, limit: i32) -> anyhow::Result<Vec<ScheduledJob>>;
     async fn update(&self, pool: &SqlitePool, job: &ScheduledJob) -> anyhow::Result<()>;
     async fn delete(&self, pool: &SqlitePool, id: Uuid) -> anyhow::Result<()>;
     async fn acquire_lock(&self, pool: &SqlitePool, id: Uuid, worker_id: &str) -> anyhow::Result<bool>;
@@ -148,6 +150,51 @@ impl JobRepository for SqliteJobRepository {
         .map_err(Into::into)
     }
 
+    async fn list_by_handler(&self, pool: &SqlitePool, handler: &str) -> anyhow::Result<Vec<ScheduledJob>> {
+        sqlx::query_as::<_, ScheduledJob>(
+            "SELECT * FROM scheduled_jobs WHERE handler = ? ORDER BY created_at ASC"
+        )
+        .bind(handler)
+        .fetch_all(pool)
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Leases up to `limit` due jobs to `worker_id` in a single statement. SQLite serialises
+    /// writers, so two workers can never claim the same row; jobs whose lease expired
+    /// (the worker died mid-run) become claimable again.
+    async fn claim_due(&self, pool: &SqlitePool, worker_id: &str, now: DateTime<Utc>, lease_expired_before: DateTime<Utc>, limit: i32) -> anyhow::Result<Vec<ScheduledJob>> {
+        let mut jobs = sqlx::query_as::<_, ScheduledJob>(
+            r#"UPDATE scheduled_jobs SET
+                   status = 'Running', locked_by = ?, locked_at = ?, started_at = ?, updated_at = ?
+               WHERE id IN (
+                   SELECT id FROM scheduled_jobs
+                   WHERE (status IN ('Pending', 'Scheduled')
+                          AND (next_run_at IS NULL OR next_run_at <= ?)
+                          AND (locked_by IS NULL OR locked_at < ?))
+                      OR (status = 'Running' AND locked_at < ?)
+                   ORDER BY
+                     CASE priority WHEN 'Critical' THEN 1 WHEN 'High' THEN 2 WHEN 'Normal' THEN 3 ELSE 4 END,
+                     COALESCE(next_run_at, created_at) ASC
+                   LIMIT ?
+               )
+               RETURNING *"#
+        )
+        .bind(worker_id)
+        .bind(now)
+        .bind(now)
+        .bind(now)
+        .bind(now)
+        .bind(lease_expired_before)
+        .bind(lease_expired_before)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        jobs.sort_by_key(|job| priority_rank(&job.priority));
+        Ok(jobs)
+    }
+
     async fn update(&self, pool: &SqlitePool, job: &ScheduledJob) -> anyhow::Result<()> {
         let now = Utc::now();
         sqlx::query(
@@ -226,6 +273,15 @@ impl JobRepository for SqliteJobRepository {
     }
 }
 
+fn priority_rank(priority: &JobPriority) -> u8 {
+    match priority {
+        JobPriority::Critical => 1,
+        JobPriority::High => 2,
+        JobPriority::Normal => 3,
+        JobPriority::Low => 4,
+    }
+}
+
 #[async_trait]
 pub trait JobExecutionRepository: Send + Sync {
     async fn create(&self, pool: &SqlitePool, execution: &JobExecution) -> anyhow::Result<JobExecution>;
@@ -245,8 +301,8 @@ impl JobExecutionRepository for SqliteJobExecutionRepository {
             INSERT INTO job_executions (
                 id, job_id, execution_number, started_at, completed_at, duration_ms,
                 status, result, error_message, error_stack_trace, retry_of_id,
-                retry_number, worker_id, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                retry_number, worker_id, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             RETURNING *
             "#,
         )
@@ -264,6 +320,7 @@ impl JobExecutionRepository for SqliteJobExecutionRepository {
         .bind(execution.retry_number)
         .bind(&execution.worker_id)
         .bind(now)
+        .bind(now)
         .fetch_one(pool)
         .await
         .map_err(Into::into)
@@ -293,7 +350,7 @@ impl JobExecutionRepository for SqliteJobExecutionRepository {
             r#"
             UPDATE job_executions SET
                 completed_at = ?, duration_ms = ?, status = ?, result = ?,
-                error_message = ?, error_stack_trace = ?
+                error_message = ?, error_stack_trace = ?, updated_at = ?
             WHERE id = ?
             "#,
         )
@@ -303,6 +360,7 @@ impl JobExecutionRepository for SqliteJobExecutionRepository {
         .bind(&execution.result)
         .bind(&execution.error_message)
         .bind(&execution.error_stack_trace)
+        .bind(Utc::now())
         .bind(execution.base.id)
         .execute(pool)
         .await?;
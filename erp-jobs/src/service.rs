@@ -223,6 +223,8 @@ impl JobService {
         Ok(jobs)
     }
 
+    /// Records the outcome of a run and releases the worker's lease. Failed runs are retried
+    /// with exponential backoff until `max_retries` is exhausted.
     pub async fn update_after_run(&self, pool: &SqlitePool, job_id: Uuid, success: bool, error: Option<String>, duration_ms: i64) -> anyhow::Result<()> {
         let mut job = self.job_repo.get_by_id(pool, job_id).await?
             .ok_or_else(|| anyhow::anyhow!("Job not found"))?;
@@ -231,11 +233,14 @@ impl JobService {
         job.run_count += 1;
         job.last_run_at = Some(now);
         job.last_duration_ms = Some(duration_ms);
+        job.locked_by = None;
+        job.locked_at = None;
         
         if success {
             job.success_count += 1;
             job.last_success_at = Some(now);
             job.last_error = None;
+            job.retry_count = 0;
         } else {
             job.failure_count += 1;
             job.last_failure_at = Some(now);
@@ -246,11 +251,16 @@ impl JobService {
             ((job.avg_duration_ms.unwrap_or(0) * (job.run_count - 1)) + duration_ms) / job.run_count
         );
         
-        if job.job_type == JobType::OneTime {
+        if !success && job.retry_count < job.max_retries {
+            job.retry_count += 1;
+            job.status = JobStatus::Scheduled;
+            job.next_run_at = Some(now + retry_backoff(job.retry_delay_seconds, job.retry_count));
+        } else if job.job_type == JobType::OneTime {
             job.status = if success { JobStatus::Completed } else { JobStatus::Failed };
             job.completed_at = Some(now);
         } else {
             job.status = JobStatus::Scheduled;
+            job.retry_count = 0;
             
             if let Some(cron) = &job.cron_expression {
                 if let Ok(next) = calculate_next_cron_run(cron) {
@@ -264,6 +274,24 @@ impl JobService {
         self.job_repo.update(pool, &job).await
     }
 
+    /// Returns the recurring job registered for `handler`, creating it on first use.
+    pub async fn ensure_interval_job(
+        &self,
+        pool: &SqlitePool,
+        name: String,
+        handler: String,
+        interval_seconds: i64,
+        payload: Option<serde_json::Value>,
+    ) -> anyhow::Result<ScheduledJob> {
+        let existing = self.job_repo.list_by_handler(pool, &handler).await?
+            .into_iter()
+            .find(|job| job.job_type == JobType::Recurring);
+        match existing {
+            Some(job) => Ok(job),
+            None => self.schedule_interval(pool, name, handler, interval_seconds, payload).await,
+        }
+    }
+
     pub async fn delete(&self, pool: &SqlitePool, id: Uuid) -> anyhow::Result<()> {
         self.job_repo.delete(pool, id).await
     }
@@ -421,6 +449,13 @@ impl JobScheduleService {
     }
 }
 
+/// Delay before retry number `attempt` (1-based): the base delay doubled per attempt, capped at a day.
+fn retry_backoff(retry_delay_seconds: i32, attempt: i32) -> chrono::Duration {
+    let base = i64::from(retry_delay_seconds.max(1));
+    let factor = 1i64 << (attempt - 1).clamp(0, 16);
+    chrono::Duration::seconds((base * factor).min(86_400))
+}
+
 fn calculate_next_cron_run(cron_expression: &str) -> anyhow::Result<DateTime<Utc>> {
     let schedule = CronSchedule::from_str(cron_expression)
         .map_err(|e| anyhow::anyhow!("Invalid cron expression: {}", e))?;
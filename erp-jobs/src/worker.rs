@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use erp_core::BaseEntity;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+use crate::models::*;
+use crate::repository::*;
+use crate::service::{JobScheduleService, JobService};
+
+/// Work performed for jobs whose `handler` matches the name it was registered under.
+/// The returned value is stored as the execution result.
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    async fn run(&self, pool: &SqlitePool, job: &ScheduledJob) -> anyhow::Result<Option<serde_json::Value>>;
+}
+
+struct FnHandler<F>(F);
+
+#[async_trait]
+impl<F, Fut> JobHandler for FnHandler<F>
+where
+    F: Fn(SqlitePool, ScheduledJob) -> Fut + Send + Sync,
+    Fut: Future<Output = anyhow::Result<Option<serde_json::Value>>> + Send,
+{
+    async fn run(&self, pool: &SqlitePool, job: &ScheduledJob) -> anyhow::Result<Option<serde_json::Value>> {
+        (self.0)(pool.clone(), job.clone()).await
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunSummary {
+    pub claimed: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+/// Leases due jobs and dispatches them to registered handlers. Any number of runners may
+/// share a database; each job is leased to one runner at a time.
+pub struct JobRunner {
+    worker_id: String,
+    handlers: HashMap<String, Arc<dyn JobHandler>>,
+    job_service: JobService,
+    schedule_service: JobScheduleService,
+    job_repo: SqliteJobRepository,
+    execution_repo: SqliteJobExecutionRepository,
+    batch_size: i32,
+    poll_interval: Duration,
+    lease: Duration,
+}
+
+impl JobRunner {
+    pub fn new(worker_id: impl Into<String>) -> Self {
+        Self {
+            worker_id: worker_id.into(),
+            handlers: HashMap::new(),
+            job_service: JobService::new(),
+            schedule_service: JobScheduleService::new(),
+            job_repo: SqliteJobRepository,
+            execution_repo: SqliteJobExecutionRepository,
+            batch_size: 10,
+            poll_interval: Duration::from_secs(5),
+            lease: Duration::from_secs(600),
+        }
+    }
+
+    pub fn register(mut self, handler: impl Into<String>, job_handler: impl JobHandler + 'static) -> Self {
+        self.handlers.insert(handler.into(), Arc::new(job_handler));
+        self
+    }
+
+    pub fn register_fn<F, Fut>(self, handler: impl Into<String>, f: F) -> Self
+    where
+        F: Fn(SqlitePool, ScheduledJob) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<Option<serde_json::Value>>> + Send + 'static,
+    {
+        self.register(handler, FnHandler(f))
+    }
+
+    pub fn with_batch_size(mut self, batch_size: i32) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// How long a claimed job stays leased. Runs are cut off at the lease, but a job whose
+    /// lease expires anyway, for example because its worker died mid-run, is claimed again
+    /// and may run more than once.
+    pub fn with_lease(mut self, lease: Duration) -> Self {
+        self.lease = lease.max(Duration::from_secs(1));
+        self
+    }
+
+    pub fn worker_id(&self) -> &str {
+        &self.worker_id
+    }
+
+    /// Materialises due job schedules, then runs up to one batch of due jobs. Jobs are claimed
+    /// one at a time, so each lease starts when its job does rather than when the batch did.
+    pub async fn run_once(&self, pool: &SqlitePool) -> anyhow::Result<RunSummary> {
+        self.schedule_service.trigger_due(pool).await?;
+
+        let lease = chrono::Duration::from_std(self.lease)?;
+        let mut summary = RunSummary::default();
+        while summary.claimed < self.batch_size as usize {
+            let now = Utc::now();
+            let Some(job) = self.job_repo.claim_due(pool, &self.worker_id, now, now - lease, 1).await?.pop() else {
+                break;
+            };
+            summary.claimed += 1;
+            if self.execute(pool, job).await? {
+                summary.succeeded += 1;
+            } else {
+                summary.failed += 1;
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Polls until the returned task is aborted. An empty poll waits `poll_interval`;
+    /// a full batch polls again immediately.
+    pub fn spawn(self, pool: SqlitePool) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!(worker_id = %self.worker_id, handlers = self.handlers.len(), "Job runner started");
+            loop {
+                let busy = match self.run_once(&pool).await {
+                    Ok(summary) => summary.claimed as i32 >= self.batch_size,
+                    Err(e) => {
+                        tracing::error!(worker_id = %self.worker_id, "Job runner poll failed: {}", e);
+                        false
+                    }
+                };
+                if !busy {
+                    tokio::time::sleep(self.poll_interval).await;
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, pool: &SqlitePool, job: ScheduledJob) -> anyhow::Result<bool> {
+        let started = Instant::now();
+        let mut execution = self.execution_repo.create(pool, &JobExecution {
+            base: BaseEntity::new(),
+            job_id: job.base.id,
+            execution_number: job.run_count + 1,
+            started_at: Utc::now(),
+            completed_at: None,
+            duration_ms: None,
+            status: ExecutionStatus::Running,
+            result: None,
+            error_message: None,
+            error_stack_trace: None,
+            retry_of_id: None,
+            retry_number: job.retry_count,
+            worker_id: Some(self.worker_id.clone()),
+            created_at: Utc::now(),
+        }).await?;
+
+        let timeout = Duration::from_secs(job.timeout_seconds.max(1) as u64).min(self.lease);
+        let (status, result, error) = match self.handlers.get(&job.handler).cloned() {
+            None => (ExecutionStatus::Failed, None, Some(format!("No handler registered for '{}'", job.handler))),
+            Some(handler) => {
+                let task_pool = pool.clone();
+                let task_job = job.clone();
+                let mut task = tokio::spawn(async move { handler.run(&task_pool, &task_job).await });
+                match tokio::time::timeout(timeout, &mut task).await {
+                    Ok(Ok(Ok(result))) => (ExecutionStatus::Completed, result, None),
+                    Ok(Ok(Err(e))) => (ExecutionStatus::Failed, None, Some(e.to_string())),
+                    Ok(Err(e)) => (ExecutionStatus::Failed, None, Some(format!("Handler panicked: {}", e))),
+                    Err(_) => {
+                        task.abort();
+                        (ExecutionStatus::Timeout, None, Some(format!("Timed out after {}s", timeout.as_secs())))
+                    }
+                }
+            }
+        };
+
+        let success = matches!(status, ExecutionStatus::Completed);
+        let duration_ms = started.elapsed().as_millis() as i64;
+        if let Some(message) = &error {
+            tracing::warn!(job_id = %job.base.id, handler = %job.handler, "Job failed: {}", message);
+        }
+
+        execution.completed_at = Some(Utc::now());
+        execution.duration_ms = Some(duration_ms);
+        execution.status = status;
+        execution.result = result;
+        execution.error_message = error.clone();
+        self.execution_repo.update(pool, &execution).await?;
+
+        self.job_service.update_after_run(pool, job.base.id, success, error, duration_ms).await?;
+        Ok(success)
+    }
+}
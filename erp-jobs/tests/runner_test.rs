@@ -0,0 +1,182 @@
+use erp_jobs::*;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+async fn setup() -> (SqlitePool, std::path::PathBuf) {
+    let path = std::env::temp_dir().join(format!("erp-jobs-test-{}.db", uuid::Uuid::new_v4()));
+    let options = SqliteConnectOptions::new()
+        .filename(&path)
+        .create_if_missing(true)
+        .busy_timeout(Duration::from_secs(10));
+    let pool = SqlitePoolOptions::new().max_connections(4).connect_with(options).await.unwrap();
+    for statement in include_str!("../../migrations/20260312000000_job_worker.sql").split(';') {
+        let statement = statement.trim();
+        if !statement.is_empty() {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+    }
+    (pool, path)
+}
+
+async fn submit(pool: &SqlitePool, handler: &str) -> ScheduledJob {
+    JobService::new()
+        .submit(pool, handler.to_string(), handler.to_string(), None, None, None, None)
+        .await
+        .unwrap()
+}
+
+async fn job(pool: &SqlitePool, id: uuid::Uuid) -> ScheduledJob {
+    JobService::new().get(pool, id).await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn test_runner_retries_with_backoff_and_enforces_timeouts() {
+    let (pool, path) = setup().await;
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let flaky_attempts = attempts.clone();
+    let runner = JobRunner::new("worker-1")
+        .register_fn("ok", |_pool, job| async move { Ok(Some(serde_json::json!({ "name": job.name }))) })
+        .register_fn("flaky", move |_pool, _job| {
+            let attempts = flaky_attempts.clone();
+            async move {
+                if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                    anyhow::bail!("upstream unavailable");
+                }
+                Ok(None)
+            }
+        })
+        .register_fn("slow", |_pool, _job| async move {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(None)
+        });
+
+    let ok = submit(&pool, "ok").await;
+    let flaky = submit(&pool, "flaky").await;
+    let slow = submit(&pool, "slow").await;
+    let unknown = submit(&pool, "unknown").await;
+    sqlx::query("UPDATE scheduled_jobs SET timeout_seconds = 1, max_retries = 0 WHERE id = ?")
+        .bind(slow.base.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let summary = runner.run_once(&pool).await.unwrap();
+    assert_eq!(summary, RunSummary { claimed: 4, succeeded: 1, failed: 3 });
+
+    let ok = job(&pool, ok.base.id).await;
+    assert_eq!(ok.status, JobStatus::Completed);
+    assert!(ok.locked_by.is_none());
+    let executions = JobService::new().get_executions(&pool, ok.base.id, 10).await.unwrap();
+    assert_eq!(executions.len(), 1);
+    assert!(matches!(executions[0].status, ExecutionStatus::Completed));
+    assert_eq!(executions[0].result, Some(serde_json::json!({ "name": "ok" })));
+
+    let retried = job(&pool, flaky.base.id).await;
+    assert_eq!(retried.status, JobStatus::Scheduled);
+    assert_eq!(retried.retry_count, 1);
+    assert_eq!(retried.last_error.as_deref(), Some("upstream unavailable"));
+    assert!(retried.next_run_at.unwrap() > chrono::Utc::now() + chrono::Duration::seconds(50));
+
+    let slow = job(&pool, slow.base.id).await;
+    assert_eq!(slow.status, JobStatus::Failed);
+    assert_eq!(slow.last_error.as_deref(), Some("Timed out after 1s"));
+    let executions = JobService::new().get_executions(&pool, slow.base.id, 10).await.unwrap();
+    assert!(matches!(executions[0].status, ExecutionStatus::Timeout));
+
+    let unknown = job(&pool, unknown.base.id).await;
+    assert_eq!(unknown.retry_count, 1);
+    assert!(unknown.last_error.unwrap().contains("No handler registered"));
+
+    // Nothing is due until the backoff elapses.
+    assert_eq!(runner.run_once(&pool).await.unwrap().claimed, 0);
+    sqlx::query("UPDATE scheduled_jobs SET next_run_at = ? WHERE id = ?")
+        .bind(chrono::Utc::now() - chrono::Duration::seconds(1))
+        .bind(flaky.base.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let summary = runner.run_once(&pool).await.unwrap();
+    assert_eq!(summary, RunSummary { claimed: 1, succeeded: 1, failed: 0 });
+    let flaky = job(&pool, flaky.base.id).await;
+    assert_eq!(flaky.status, JobStatus::Completed);
+    assert_eq!((flaky.run_count, flaky.failure_count, flaky.retry_count), (2, 1, 0));
+
+    pool.close().await;
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_concurrent_runners_claim_each_job_once() {
+    let (pool, path) = setup().await;
+    let runs = Arc::new(AtomicUsize::new(0));
+    let runner = |id: &str| {
+        let runs = runs.clone();
+        JobRunner::new(id).with_batch_size(5).register_fn("count", move |_pool, _job| {
+            let runs = runs.clone();
+            async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ok(None)
+            }
+        })
+    };
+    let (first, second) = (runner("worker-a"), runner("worker-b"));
+
+    for _ in 0..20 {
+        submit(&pool, "count").await;
+    }
+    let mut claimed = 0;
+    loop {
+        let (a, b) = tokio::join!(first.run_once(&pool), second.run_once(&pool));
+        let batch = a.unwrap().claimed + b.unwrap().claimed;
+        if batch == 0 {
+            break;
+        }
+        claimed += batch;
+    }
+    assert_eq!(claimed, 20);
+    assert_eq!(runs.load(Ordering::SeqCst), 20);
+
+    let completed = JobService::new().list(&pool, Some(JobStatus::Completed), 100, 0).await.unwrap();
+    assert_eq!(completed.len(), 20);
+
+    pool.close().await;
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_lease_starts_when_each_job_starts() {
+    let (pool, path) = setup().await;
+    let runs = Arc::new(AtomicUsize::new(0));
+    let runner = |id: &str| {
+        let runs = runs.clone();
+        JobRunner::new(id).with_batch_size(2).with_lease(Duration::from_secs(2)).register_fn("slow", move |_pool, _job| {
+            let runs = runs.clone();
+            async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(1200)).await;
+                Ok(None)
+            }
+        })
+    };
+    let (first, second) = (runner("worker-a"), runner("worker-b"));
+    submit(&pool, "slow").await;
+    submit(&pool, "slow").await;
+
+    // The second job starts 1.2s into the first worker's run; had it been leased with the
+    // first, its lease would have run out while it was still running.
+    let late = async {
+        tokio::time::sleep(Duration::from_millis(2300)).await;
+        second.run_once(&pool).await.unwrap()
+    };
+    let (summary, late) = tokio::join!(first.run_once(&pool), late);
+    assert_eq!(summary.unwrap(), RunSummary { claimed: 2, succeeded: 2, failed: 0 });
+    assert_eq!(late.claimed, 0);
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+    pool.close().await;
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,194 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rand::RngCore;
+use erp_core::{Error, Result};
+
+pub const NONCE_LEN: usize = 12;
+pub const TAG_LEN: usize = 16;
+pub const KEY_LEN: usize = 32;
+
+const WRAPPED_KEY_PREFIX: &str = "v1";
+
+#[derive(Clone)]
+pub struct MasterKey {
+    key: [u8; KEY_LEN],
+}
+
+impl std::fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MasterKey(..)")
+    }
+}
+
+impl MasterKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let key: [u8; KEY_LEN] = bytes.try_into()
+            .map_err(|_| Error::validation(format!("Master key must be {} bytes", KEY_LEN)))?;
+        Ok(Self { key })
+    }
+
+    pub fn from_base64(encoded: &str) -> Result<Self> {
+        let bytes = STANDARD.decode(encoded.trim())
+            .map_err(|e| Error::validation(format!("Invalid master key encoding: {}", e)))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Reads a key file holding either the base64-encoded key or the raw 32 key bytes.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let contents = std::fs::read(path.as_ref())
+            .map_err(|e| Error::internal(format!("Cannot read master key file {}: {}", path.as_ref().display(), e)))?;
+        if contents.len() == KEY_LEN {
+            return Self::from_bytes(&contents);
+        }
+        let encoded = String::from_utf8(contents)
+            .map_err(|_| Error::validation("Master key file must contain base64 text or raw key bytes"))?;
+        Self::from_base64(&encoded)
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Self::from_env_if_set()?
+            .ok_or_else(|| Error::internal("ERP_MASTER_KEY or ERP_MASTER_KEY_FILE environment variable must be set"))
+    }
+
+    /// Like [`Self::from_env`], but `None` when no key is configured. A key that is configured but
+    /// cannot be read is still an error.
+    pub fn from_env_if_set() -> Result<Option<Self>> {
+        if let Ok(encoded) = std::env::var("ERP_MASTER_KEY") {
+            return Self::from_base64(&encoded).map(Some);
+        }
+        match std::env::var("ERP_MASTER_KEY_FILE") {
+            Ok(path) => Self::from_file(path).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    pub fn generate() -> Self {
+        Self { key: generate_data_key() }
+    }
+
+    pub fn wrap(&self, data_key: &[u8], key_id: &str) -> Result<String> {
+        let (nonce, body) = encrypt(&self.key, data_key, key_id.as_bytes())?;
+        Ok(format!("{}:{}:{}", WRAPPED_KEY_PREFIX, STANDARD.encode(nonce), STANDARD.encode(body)))
+    }
+
+    pub fn unwrap(&self, wrapped: &str, key_id: &str) -> Result<Vec<u8>> {
+        let mut parts = wrapped.splitn(3, ':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(WRAPPED_KEY_PREFIX), Some(iv), Some(body)) => {
+                let body = STANDARD.decode(body)
+                    .map_err(|e| Error::validation(format!("Invalid wrapped key: {}", e)))?;
+                let iv = STANDARD.decode(iv)
+                    .map_err(|e| Error::validation(format!("Invalid wrapped key nonce: {}", e)))?;
+                decrypt(&self.key, &iv, &body, key_id.as_bytes())
+            }
+            _ => Err(Error::validation("Unrecognised wrapped key format")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sealed {
+    pub iv: String,
+    pub ciphertext: String,
+    pub auth_tag: String,
+}
+
+pub fn generate_data_key() -> [u8; KEY_LEN] {
+    let mut key = [0u8; KEY_LEN];
+    rand::thread_rng().fill_bytes(&mut key);
+    key
+}
+
+pub fn seal(key: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<Sealed> {
+    let (nonce, mut output) = encrypt(key, plaintext, aad)?;
+    let tag = output.split_off(output.len() - TAG_LEN);
+
+    Ok(Sealed {
+        iv: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(output),
+        auth_tag: STANDARD.encode(tag),
+    })
+}
+
+pub fn open(key: &[u8], sealed: &Sealed, aad: &[u8]) -> Result<Vec<u8>> {
+    let decode = |field: &str, value: &str| STANDARD.decode(value)
+        .map_err(|e| Error::validation(format!("Invalid {}: {}", field, e)));
+    let iv = decode("IV", &sealed.iv)?;
+    let mut body = decode("ciphertext", &sealed.ciphertext)?;
+    let tag = decode("auth tag", &sealed.auth_tag)?;
+    if tag.len() != TAG_LEN {
+        return Err(Error::validation("Invalid auth tag length"));
+    }
+    body.extend_from_slice(&tag);
+    decrypt(key, &iv, &body, aad)
+}
+
+fn encrypt(key: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<([u8; NONCE_LEN], Vec<u8>)> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let body = cipher(key)?
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad })
+        .map_err(|_| Error::internal("Encryption failed"))?;
+    Ok((nonce, body))
+}
+
+fn decrypt(key: &[u8], iv: &[u8], body: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    if iv.len() != NONCE_LEN {
+        return Err(Error::validation("Invalid IV length"));
+    }
+    cipher(key)?
+        .decrypt(Nonce::from_slice(iv), Payload { msg: body, aad })
+        .map_err(|_| Error::validation("Decryption failed: ciphertext or authentication tag is invalid"))
+}
+
+fn cipher(key: &[u8]) -> Result<Aes256Gcm> {
+    if key.len() != KEY_LEN {
+        return Err(Error::validation(format!("Encryption key must be {} bytes", KEY_LEN)));
+    }
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open_round_trip() {
+        let key = generate_data_key();
+        let sealed = seal(&key, b"4111-1111-1111-1111", b"customer:42:card").unwrap();
+        assert_ne!(sealed.ciphertext, STANDARD.encode(b"4111-1111-1111-1111"));
+
+        let plaintext = open(&key, &sealed, b"customer:42:card").unwrap();
+        assert_eq!(plaintext, b"4111-1111-1111-1111");
+    }
+
+    #[test]
+    fn test_open_rejects_tampering_and_wrong_context() {
+        let key = generate_data_key();
+        let sealed = seal(&key, b"secret", b"customer:42:ssn").unwrap();
+
+        assert!(open(&key, &sealed, b"customer:43:ssn").is_err());
+        assert!(open(&generate_data_key(), &sealed, b"customer:42:ssn").is_err());
+
+        let mut tampered = sealed.clone();
+        tampered.auth_tag = STANDARD.encode([0u8; TAG_LEN]);
+        assert!(open(&key, &tampered, b"customer:42:ssn").is_err());
+    }
+
+    #[test]
+    fn test_wrap_and_unwrap_data_key() {
+        let master = MasterKey::generate();
+        let data_key = generate_data_key();
+        let wrapped = master.wrap(&data_key, "key_abc").unwrap();
+
+        assert!(wrapped.starts_with("v1:"));
+        assert_eq!(master.unwrap(&wrapped, "key_abc").unwrap(), data_key);
+        assert!(master.unwrap(&wrapped, "key_other").is_err());
+        assert!(MasterKey::generate().unwrap(&wrapped, "key_abc").is_err());
+
+        let unwrapped = STANDARD.encode(data_key);
+        assert!(master.unwrap(&unwrapped, "key_abc").is_err());
+    }
+}
@@ -1,6 +1,8 @@
+pub mod crypto;
 pub mod models;
 pub mod repository;
 pub mod service;
 
 pub use models::*;
 pub use service::*;
+pub use crypto::MasterKey;
@@ -20,6 +20,9 @@ pub struct EncryptionKey {
     pub expires_at: Option<DateTime<Utc>>,
     pub max_usage_count: Option<i64>,
     pub current_usage_count: i64,
+    /// Key material stored as plain base64 before envelope encryption; wrapped on first use.
+    #[serde(default)]
+    pub legacy_material: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
@@ -80,6 +83,9 @@ pub struct EncryptedData {
     pub auth_tag: Option<String>,
     pub encrypted_value: String,
     pub encryption_algorithm: String,
+    /// Written by the pre-AEAD scheme; readable only until it is re-encrypted.
+    #[serde(default)]
+    pub legacy_format: bool,
     pub created_at: DateTime<Utc>,
 }
 
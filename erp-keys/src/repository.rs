@@ -12,6 +12,7 @@ pub trait KeyRepository: Send + Sync {
     async fn get_primary_key(&self, pool: &SqlitePool, key_type: KeyType) -> Result<Option<EncryptionKey>>;
     async fn list_keys(&self, pool: &SqlitePool) -> Result<Vec<EncryptionKey>>;
     async fn update_key(&self, pool: &SqlitePool, key: EncryptionKey) -> Result<EncryptionKey>;
+    async fn upgrade_key_material(&self, pool: &SqlitePool, id: Uuid, wrapped: &str) -> Result<()>;
     async fn delete_key(&self, pool: &SqlitePool, id: Uuid) -> Result<()>;
     async fn create_rotation(&self, pool: &SqlitePool, rotation: KeyRotation) -> Result<KeyRotation>;
     async fn update_rotation(&self, pool: &SqlitePool, rotation: KeyRotation) -> Result<KeyRotation>;
@@ -19,6 +20,7 @@ pub trait KeyRepository: Send + Sync {
     async fn log_usage(&self, pool: &SqlitePool, log: KeyUsageLog) -> Result<()>;
     async fn create_encrypted_data(&self, pool: &SqlitePool, data: EncryptedData) -> Result<EncryptedData>;
     async fn list_encrypted_data(&self, pool: &SqlitePool, entity_type: &str, entity_id: &str) -> Result<Vec<EncryptedData>>;
+    async fn list_encrypted_data_by_key(&self, pool: &SqlitePool, key_id: Uuid, limit: i64) -> Result<Vec<EncryptedData>>;
     async fn update_encrypted_data(&self, pool: &SqlitePool, data: EncryptedData) -> Result<EncryptedData>;
     async fn create_policy(&self, pool: &SqlitePool, policy: KeyPolicy) -> Result<KeyPolicy>;
     async fn list_policies(&self, pool: &SqlitePool) -> Result<Vec<KeyPolicy>>;
@@ -62,7 +64,7 @@ impl KeyRepository for SqliteKeyRepository {
         let row: Option<sqlx::sqlite::SqliteRow> = sqlx::query(
             r#"SELECT id, key_id, key_type, algorithm, key_version, public_key, encrypted_private_key,
                key_derivation_info, is_active, is_primary, rotation_days, last_rotated, expires_at,
-               max_usage_count, current_usage_count, created_at, updated_at
+               max_usage_count, current_usage_count, legacy_material, created_at, updated_at
                FROM encryption_keys WHERE id = ?"#
         )
         .bind(id.to_string())
@@ -90,6 +92,7 @@ impl KeyRepository for SqliteKeyRepository {
             expires_at: r.get::<Option<&str>, _>("expires_at").and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok().map(|d| d.with_timezone(&chrono::Utc))),
             max_usage_count: r.get("max_usage_count"),
             current_usage_count: r.get("current_usage_count"),
+            legacy_material: r.get::<i32, _>("legacy_material") == 1,
         }))
     }
 
@@ -97,7 +100,7 @@ impl KeyRepository for SqliteKeyRepository {
         let row: Option<sqlx::sqlite::SqliteRow> = sqlx::query(
             r#"SELECT id, key_id, key_type, algorithm, key_version, public_key, encrypted_private_key,
                key_derivation_info, is_active, is_primary, rotation_days, last_rotated, expires_at,
-               max_usage_count, current_usage_count, created_at, updated_at
+               max_usage_count, current_usage_count, legacy_material, created_at, updated_at
                FROM encryption_keys WHERE key_id = ?"#
         )
         .bind(key_id)
@@ -125,6 +128,7 @@ impl KeyRepository for SqliteKeyRepository {
             expires_at: r.get::<Option<&str>, _>("expires_at").and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok().map(|d| d.with_timezone(&chrono::Utc))),
             max_usage_count: r.get("max_usage_count"),
             current_usage_count: r.get("current_usage_count"),
+            legacy_material: r.get::<i32, _>("legacy_material") == 1,
         }))
     }
 
@@ -132,7 +136,7 @@ impl KeyRepository for SqliteKeyRepository {
         let row: Option<sqlx::sqlite::SqliteRow> = sqlx::query(
             r#"SELECT id, key_id, key_type, algorithm, key_version, public_key, encrypted_private_key,
                key_derivation_info, is_active, is_primary, rotation_days, last_rotated, expires_at,
-               max_usage_count, current_usage_count, created_at, updated_at
+               max_usage_count, current_usage_count, legacy_material, created_at, updated_at
                FROM encryption_keys WHERE is_primary = 1 AND is_active = 1 LIMIT 1"#
         )
         .fetch_optional(pool).await?;
@@ -159,6 +163,7 @@ impl KeyRepository for SqliteKeyRepository {
             expires_at: r.get::<Option<&str>, _>("expires_at").and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok().map(|d| d.with_timezone(&chrono::Utc))),
             max_usage_count: r.get("max_usage_count"),
             current_usage_count: r.get("current_usage_count"),
+            legacy_material: r.get::<i32, _>("legacy_material") == 1,
         }))
     }
 
@@ -166,7 +171,7 @@ impl KeyRepository for SqliteKeyRepository {
         let rows: Vec<sqlx::sqlite::SqliteRow> = sqlx::query(
             r#"SELECT id, key_id, key_type, algorithm, key_version, public_key, encrypted_private_key,
                key_derivation_info, is_active, is_primary, rotation_days, last_rotated, expires_at,
-               max_usage_count, current_usage_count, created_at, updated_at
+               max_usage_count, current_usage_count, legacy_material, created_at, updated_at
                FROM encryption_keys ORDER BY created_at DESC"#
         )
         .fetch_all(pool).await?;
@@ -193,6 +198,7 @@ impl KeyRepository for SqliteKeyRepository {
             expires_at: r.get::<Option<&str>, _>("expires_at").and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok().map(|d| d.with_timezone(&chrono::Utc))),
             max_usage_count: r.get("max_usage_count"),
             current_usage_count: r.get("current_usage_count"),
+            legacy_material: r.get::<i32, _>("legacy_material") == 1,
         }).collect())
     }
 
@@ -214,6 +220,18 @@ impl KeyRepository for SqliteKeyRepository {
         Ok(key)
     }
 
+    async fn upgrade_key_material(&self, pool: &SqlitePool, id: Uuid, wrapped: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE encryption_keys SET encrypted_private_key = ?, legacy_material = 0, updated_at = ?
+             WHERE id = ? AND legacy_material = 1"
+        )
+        .bind(wrapped)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(id.to_string())
+        .execute(pool).await?;
+        Ok(())
+    }
+
     async fn delete_key(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
         sqlx::query("UPDATE encryption_keys SET is_active = 0 WHERE id = ?")
             .bind(id.to_string())
@@ -335,38 +353,33 @@ impl KeyRepository for SqliteKeyRepository {
     async fn list_encrypted_data(&self, pool: &SqlitePool, entity_type: &str, entity_id: &str) -> Result<Vec<EncryptedData>> {
         let rows: Vec<sqlx::sqlite::SqliteRow> = sqlx::query(
             r#"SELECT id, entity_type, entity_id, field_name, key_id, key_version, iv, auth_tag,
-               encrypted_value, encryption_algorithm, created_at, updated_at
+               encrypted_value, encryption_algorithm, legacy_format, created_at, updated_at
                FROM encrypted_data WHERE entity_type = ? AND entity_id = ?"#
         )
         .bind(entity_type)
         .bind(entity_id)
         .fetch_all(pool).await?;
         
-        Ok(rows.into_iter().map(|r| EncryptedData {
-            base: erp_core::BaseEntity {
-                id: Uuid::parse_str(r.get::<&str, _>("id")).unwrap(),
-                created_at: chrono::DateTime::parse_from_rfc3339(r.get::<&str, _>("created_at")).unwrap().with_timezone(&chrono::Utc),
-                updated_at: chrono::DateTime::parse_from_rfc3339(r.get::<&str, _>("updated_at")).unwrap().with_timezone(&chrono::Utc),
-                created_by: None,
-                updated_by: None,
-            },
-            entity_type: r.get("entity_type"),
-            entity_id: r.get("entity_id"),
-            field_name: r.get("field_name"),
-            key_id: Uuid::parse_str(r.get::<&str, _>("key_id")).unwrap(),
-            key_version: r.get("key_version"),
-            iv: r.get("iv"),
-            auth_tag: r.get("auth_tag"),
-            encrypted_value: r.get("encrypted_value"),
-            encryption_algorithm: r.get("encryption_algorithm"),
-            created_at: chrono::DateTime::parse_from_rfc3339(r.get::<&str, _>("created_at")).unwrap().with_timezone(&chrono::Utc),
-        }).collect())
+        Ok(rows.into_iter().map(encrypted_data_from_row).collect())
+    }
+
+    async fn list_encrypted_data_by_key(&self, pool: &SqlitePool, key_id: Uuid, limit: i64) -> Result<Vec<EncryptedData>> {
+        let rows: Vec<sqlx::sqlite::SqliteRow> = sqlx::query(
+            r#"SELECT id, entity_type, entity_id, field_name, key_id, key_version, iv, auth_tag,
+               encrypted_value, encryption_algorithm, legacy_format, created_at, updated_at
+               FROM encrypted_data WHERE key_id = ? ORDER BY created_at LIMIT ?"#
+        )
+        .bind(key_id.to_string())
+        .bind(limit)
+        .fetch_all(pool).await?;
+        
+        Ok(rows.into_iter().map(encrypted_data_from_row).collect())
     }
 
     async fn update_encrypted_data(&self, pool: &SqlitePool, data: EncryptedData) -> Result<EncryptedData> {
         sqlx::query(
             r#"UPDATE encrypted_data SET key_id = ?, key_version = ?, iv = ?, auth_tag = ?,
-               encrypted_value = ?, encryption_algorithm = ?, updated_at = ?
+               encrypted_value = ?, encryption_algorithm = ?, legacy_format = ?, updated_at = ?
                WHERE id = ?"#
         )
         .bind(data.key_id.to_string())
@@ -375,6 +388,7 @@ impl KeyRepository for SqliteKeyRepository {
         .bind(&data.auth_tag)
         .bind(&data.encrypted_value)
         .bind(&data.encryption_algorithm)
+        .bind(data.legacy_format as i32)
         .bind(data.base.updated_at.to_rfc3339())
         .bind(data.base.id.to_string())
         .execute(pool).await?;
@@ -433,3 +447,26 @@ impl KeyRepository for SqliteKeyRepository {
         }).collect())
     }
 }
+
+fn encrypted_data_from_row(r: sqlx::sqlite::SqliteRow) -> EncryptedData {
+    EncryptedData {
+        base: erp_core::BaseEntity {
+            id: Uuid::parse_str(r.get::<&str, _>("id")).unwrap(),
+            created_at: chrono::DateTime::parse_from_rfc3339(r.get::<&str, _>("created_at")).unwrap().with_timezone(&chrono::Utc),
+            updated_at: chrono::DateTime::parse_from_rfc3339(r.get::<&str, _>("updated_at")).unwrap().with_timezone(&chrono::Utc),
+            created_by: None,
+            updated_by: None,
+        },
+        entity_type: r.get("entity_type"),
+        entity_id: r.get("entity_id"),
+        field_name: r.get("field_name"),
+        key_id: Uuid::parse_str(r.get::<&str, _>("key_id")).unwrap(),
+        key_version: r.get("key_version"),
+        iv: r.get("iv"),
+        auth_tag: r.get("auth_tag"),
+        encrypted_value: r.get("encrypted_value"),
+        encryption_algorithm: r.get("encryption_algorithm"),
+        legacy_format: r.get::<i32, _>("legacy_format") == 1,
+        created_at: chrono::DateTime::parse_from_rfc3339(r.get::<&str, _>("created_at")).unwrap().with_timezone(&chrono::Utc),
+    }
+}
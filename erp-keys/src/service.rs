@@ -2,12 +2,19 @@ use sqlx::SqlitePool;
 use uuid::Uuid;
 use chrono::Utc;
 use rand::RngCore;
-use erp_core::{BaseEntity, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use erp_core::{BaseEntity, Error, Result};
+use crate::crypto::{self, MasterKey, Sealed};
 use crate::models::*;
 use crate::repository::{KeyRepository, SqliteKeyRepository};
 
+const AES_256_GCM: &str = "AES-256-GCM";
+const ROTATION_BATCH_SIZE: i64 = 500;
+
 pub struct KeyService {
     repo: SqliteKeyRepository,
+    master_key: Option<MasterKey>,
 }
 
 impl Default for KeyService {
@@ -17,39 +24,45 @@ impl Default for KeyService {
 }
 
 impl KeyService {
+    /// A service without a master key, which can list keys and policies but not use key material.
+    /// Load the key once at startup with [`MasterKey::from_env`] and use [`Self::with_master_key`].
     pub fn new() -> Self {
-        Self { repo: SqliteKeyRepository }
+        Self { repo: SqliteKeyRepository, master_key: None }
+    }
+
+    pub fn with_master_key(master_key: MasterKey) -> Self {
+        Self { repo: SqliteKeyRepository, master_key: Some(master_key) }
     }
 
     pub async fn generate_key(&self, pool: &SqlitePool, key_type: KeyType, name: &str) -> Result<EncryptionKey> {
+        let key = self.build_key(key_type, name, 1)?;
+        self.repo.create_key(pool, key).await
+    }
+
+    fn build_key(&self, key_type: KeyType, name: &str, key_version: i32) -> Result<EncryptionKey> {
         let key_id = format!("key_{}", Uuid::new_v4().simple());
         let now = Utc::now();
         
-        let (algorithm, encrypted_private_key) = match key_type {
-            KeyType::Aes256Gcm | KeyType::Symmetric | KeyType::DataEncryption => {
-                let mut key_bytes = [0u8; 32];
-                rand::thread_rng().fill_bytes(&mut key_bytes);
-                ("AES-256-GCM".to_string(), Some(base64::Engine::encode(&base64::engine::general_purpose::STANDARD, key_bytes)))
-            }
-            KeyType::Aes256Cbc => {
-                let mut key_bytes = [0u8; 32];
-                rand::thread_rng().fill_bytes(&mut key_bytes);
-                ("AES-256-CBC".to_string(), Some(base64::Engine::encode(&base64::engine::general_purpose::STANDARD, key_bytes)))
-            }
-            KeyType::Hmac => {
-                let mut key_bytes = [0u8; 64];
-                rand::thread_rng().fill_bytes(&mut key_bytes);
-                ("HMAC-SHA256".to_string(), Some(base64::Engine::encode(&base64::engine::general_purpose::STANDARD, key_bytes)))
-            }
-            _ => ("UNKNOWN".to_string(), None),
+        let (algorithm, key_len) = match key_type {
+            KeyType::Aes256Gcm | KeyType::Symmetric | KeyType::DataEncryption => (AES_256_GCM, 32),
+            KeyType::Aes256Cbc => ("AES-256-CBC", 32),
+            KeyType::Hmac => ("HMAC-SHA256", 64),
+            _ => ("UNKNOWN", 0),
+        };
+        let encrypted_private_key = if key_len > 0 {
+            let mut key_bytes = vec![0u8; key_len];
+            rand::thread_rng().fill_bytes(&mut key_bytes);
+            Some(self.master_key()?.wrap(&key_bytes, &key_id)?)
+        } else {
+            None
         };
 
-        let key = EncryptionKey {
+        Ok(EncryptionKey {
             base: BaseEntity::new(),
             key_id,
             key_type,
-            algorithm,
-            key_version: 1,
+            algorithm: algorithm.to_string(),
+            key_version,
             public_key: None,
             encrypted_private_key,
             key_derivation_info: Some(format!("Generated for: {}", name)),
@@ -60,9 +73,29 @@ impl KeyService {
             expires_at: None,
             max_usage_count: None,
             current_usage_count: 0,
-        };
+            legacy_material: false,
+        })
+    }
 
-        self.repo.create_key(pool, key).await
+    fn master_key(&self) -> Result<&MasterKey> {
+        self.master_key.as_ref()
+            .ok_or_else(|| Error::internal("Master key is not configured; set ERP_MASTER_KEY or ERP_MASTER_KEY_FILE"))
+    }
+
+    /// Unwraps a key's data key. Material flagged as legacy by migration is plain base64; it is
+    /// wrapped under the master key and saved so the plain copy does not outlive its first use.
+    async fn data_key(&self, pool: &SqlitePool, key: &EncryptionKey) -> Result<Vec<u8>> {
+        let material = key.encrypted_private_key.as_deref()
+            .ok_or_else(|| Error::validation(format!("Key {} has no key material", key.key_id)))?;
+        let master_key = self.master_key()?;
+        if !key.legacy_material {
+            return master_key.unwrap(material, &key.key_id);
+        }
+        let data_key = STANDARD.decode(material)
+            .map_err(|e| Error::validation(format!("Invalid key material: {}", e)))?;
+        let wrapped = master_key.wrap(&data_key, &key.key_id)?;
+        self.repo.upgrade_key_material(pool, key.base.id, &wrapped).await?;
+        Ok(data_key)
     }
 
     pub async fn list_keys(&self, pool: &SqlitePool) -> Result<Vec<EncryptionKey>> {
@@ -91,60 +124,110 @@ impl KeyService {
         Ok(())
     }
 
+    /// Creates the next key version, makes it primary and re-encrypts the old key's data in batches.
+    /// Rows are updated one at a time, so reads keep working against whichever key a row references.
     pub async fn rotate_key(&self, pool: &SqlitePool, id: Uuid, initiated_by: Option<Uuid>) -> Result<KeyRotation> {
         let old_key = self.repo.get_key(pool, id).await?
             .ok_or_else(|| anyhow::anyhow!("Key not found"))?;
         
-        let new_key = self.generate_key(pool, old_key.key_type.clone(), &old_key.key_id).await?;
+        let new_key = self.build_key(old_key.key_type.clone(), &old_key.key_id, old_key.key_version + 1)?;
+        let new_key = self.repo.create_key(pool, new_key).await?;
         
-        let rotation = KeyRotation {
+        let mut rotation = KeyRotation {
             base: BaseEntity::new(),
             key_id: id,
             from_version: old_key.key_version,
             to_version: new_key.key_version,
             rotation_type: RotationType::Manual,
-            status: RotationStatus::Completed,
+            status: RotationStatus::InProgress,
             started_at: Utc::now(),
-            completed_at: Some(Utc::now()),
+            completed_at: None,
             re_encrypted_count: 0,
             error_message: None,
             initiated_by,
         };
-        
         self.repo.create_rotation(pool, rotation.clone()).await?;
         
-        let mut old_key = old_key;
-        old_key.is_primary = false;
-        old_key.base.updated_at = Utc::now();
-        self.repo.update_key(pool, old_key).await?;
-        
         self.set_primary_key(pool, new_key.base.id).await?;
         
+        loop {
+            match self.reencrypt_batch(pool, &old_key, &new_key, ROTATION_BATCH_SIZE).await {
+                Ok(0) => break,
+                Ok(count) => rotation.re_encrypted_count += count,
+                Err(e) => {
+                    rotation.status = RotationStatus::Failed;
+                    rotation.error_message = Some(e.to_string());
+                    rotation.base.updated_at = Utc::now();
+                    self.repo.update_rotation(pool, rotation).await?;
+                    return Err(e);
+                }
+            }
+        }
+        
+        rotation.status = RotationStatus::Completed;
+        rotation.completed_at = Some(Utc::now());
+        rotation.base.updated_at = Utc::now();
+        self.repo.update_rotation(pool, rotation.clone()).await?;
+        
+        self.log_key_usage(pool, id, KeyOperation::Rotate, None, None, true, None).await;
+        
         Ok(rotation)
     }
 
+    /// Re-encrypts up to `batch_size` rows from `from_key` under `to_key`, returning how many were moved.
+    pub async fn reencrypt_batch(&self, pool: &SqlitePool, from_key: &EncryptionKey, to_key: &EncryptionKey, batch_size: i64) -> Result<i64> {
+        let records = self.repo.list_encrypted_data_by_key(pool, from_key.base.id, batch_size).await?;
+        if records.is_empty() {
+            return Ok(0);
+        }
+        
+        let from_data_key = self.data_key(pool, from_key).await?;
+        let to_data_key = self.data_key(pool, to_key).await?;
+        let mut count = 0;
+        
+        for mut record in records {
+            let plaintext = Self::open_record(&from_data_key, from_key, &record)?;
+            let sealed = crypto::seal(&to_data_key, plaintext.as_bytes(), Self::aad(&record).as_bytes())?;
+            
+            record.key_id = to_key.base.id;
+            record.key_version = to_key.key_version;
+            record.iv = sealed.iv;
+            record.auth_tag = Some(sealed.auth_tag);
+            record.encrypted_value = sealed.ciphertext;
+            record.encryption_algorithm = AES_256_GCM.to_string();
+            record.legacy_format = false;
+            record.base.updated_at = Utc::now();
+            self.repo.update_encrypted_data(pool, record).await?;
+            count += 1;
+        }
+        
+        Ok(count)
+    }
+
     pub async fn encrypt_data(&self, pool: &SqlitePool, entity_type: &str, entity_id: &str, field_name: &str, plaintext: &str) -> Result<EncryptedData> {
         let key = self.repo.get_primary_key(pool, KeyType::Aes256Gcm).await?
             .ok_or_else(|| anyhow::anyhow!("No primary key found"))?;
         
-        let mut iv = [0u8; 12];
-        rand::thread_rng().fill_bytes(&mut iv);
-        
-        let encrypted = self.do_encrypt(&key, plaintext, &iv)?;
+        let data_key = self.data_key(pool, &key).await?;
         
-        let data = EncryptedData {
+        let mut data = EncryptedData {
             base: BaseEntity::new(),
             entity_type: entity_type.to_string(),
             entity_id: entity_id.to_string(),
             field_name: field_name.to_string(),
             key_id: key.base.id,
             key_version: key.key_version,
-            iv: base64::Engine::encode(&base64::engine::general_purpose::STANDARD, iv),
+            iv: String::new(),
             auth_tag: None,
-            encrypted_value: encrypted,
-            encryption_algorithm: key.algorithm.clone(),
+            encrypted_value: String::new(),
+            encryption_algorithm: AES_256_GCM.to_string(),
+            legacy_format: false,
             created_at: Utc::now(),
         };
+        let sealed = crypto::seal(&data_key, plaintext.as_bytes(), Self::aad(&data).as_bytes())?;
+        data.iv = sealed.iv;
+        data.auth_tag = Some(sealed.auth_tag);
+        data.encrypted_value = sealed.ciphertext;
         
         self.repo.create_encrypted_data(pool, data.clone()).await?;
         
@@ -162,31 +245,69 @@ impl KeyService {
                 let key = self.repo.get_key(pool, data.key_id).await?
                     .ok_or_else(|| anyhow::anyhow!("Key not found"))?;
                 
-                let iv = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &data.iv)
-                    .map_err(|e| anyhow::anyhow!("Invalid IV: {}", e))?;
-                
-                let plaintext = self.do_decrypt(&key, &data.encrypted_value, &iv)?;
-                
-                self.log_key_usage(pool, key.base.id, KeyOperation::Decrypt, Some(entity_type), Some(entity_id), true, None).await;
+                let result = match self.data_key(pool, &key).await {
+                    Ok(data_key) => Self::open_record(&data_key, &key, &data).map(|plaintext| (data_key, plaintext)),
+                    Err(e) => Err(e),
+                };
+                let error = result.as_ref().err().map(|e| e.to_string());
+                self.log_key_usage(pool, key.base.id, KeyOperation::Decrypt, Some(entity_type), Some(entity_id), result.is_ok(), error.as_deref()).await;
+                let (data_key, plaintext) = result?;
                 
+                if data.legacy_format {
+                    self.upgrade_record(pool, &data_key, data, &plaintext).await?;
+                }
                 Ok(Some(plaintext))
             }
             None => Ok(None),
         }
     }
 
-    fn do_encrypt(&self, _key: &EncryptionKey, plaintext: &str, iv: &[u8]) -> Result<String> {
-        let iv_b64 = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, iv);
-        let data_b64 = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, plaintext.as_bytes());
-        Ok(format!("{}.{}", iv_b64, data_b64))
+    /// Re-encrypts a legacy row under its own key with authenticated encryption.
+    async fn upgrade_record(&self, pool: &SqlitePool, data_key: &[u8], mut record: EncryptedData, plaintext: &str) -> Result<()> {
+        let sealed = crypto::seal(data_key, plaintext.as_bytes(), Self::aad(&record).as_bytes())?;
+        record.iv = sealed.iv;
+        record.auth_tag = Some(sealed.auth_tag);
+        record.encrypted_value = sealed.ciphertext;
+        record.encryption_algorithm = AES_256_GCM.to_string();
+        record.legacy_format = false;
+        record.base.updated_at = Utc::now();
+        self.repo.update_encrypted_data(pool, record).await?;
+        Ok(())
+    }
+
+    fn aad(data: &EncryptedData) -> String {
+        format!("{}:{}:{}", data.entity_type, data.entity_id, data.field_name)
+    }
+
+    fn open_record(data_key: &[u8], key: &EncryptionKey, data: &EncryptedData) -> Result<String> {
+        if data.key_version != key.key_version {
+            return Err(Error::validation(format!(
+                "Encrypted value references version {} of key {} but version {} was found",
+                data.key_version, key.key_id, key.key_version
+            )));
+        }
+        match &data.auth_tag {
+            Some(auth_tag) => {
+                let sealed = Sealed {
+                    iv: data.iv.clone(),
+                    ciphertext: data.encrypted_value.clone(),
+                    auth_tag: auth_tag.clone(),
+                };
+                let plaintext = crypto::open(data_key, &sealed, Self::aad(data).as_bytes())?;
+                String::from_utf8(plaintext).map_err(|e| Error::Validation(format!("Invalid UTF-8: {}", e)))
+            }
+            // Only rows the migration flagged as written before authenticated encryption may lack a tag.
+            None if data.legacy_format => Self::decode_legacy(&data.encrypted_value),
+            None => Err(Error::validation("Encrypted value has no authentication tag")),
+        }
     }
 
-    fn do_decrypt(&self, _key: &EncryptionKey, ciphertext: &str, _iv: &[u8]) -> Result<String> {
+    fn decode_legacy(ciphertext: &str) -> Result<String> {
         let parts: Vec<&str> = ciphertext.split('.').collect();
         if parts.len() != 2 {
             return Err(erp_core::Error::Validation("Invalid ciphertext format".to_string()));
         }
-        let data = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, parts[1])
+        let data = STANDARD.decode(parts[1])
             .map_err(|e| erp_core::Error::Validation(format!("Invalid data: {}", e)))?;
         String::from_utf8(data).map_err(|e| erp_core::Error::Validation(format!("Invalid UTF-8: {}", e)))
     }
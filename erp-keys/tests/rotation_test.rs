@@ -0,0 +1,115 @@
+use erp_keys::*;
+use sqlx::{Row, SqlitePool};
+
+async fn insert_untagged(pool: &SqlitePool, key_id: uuid::Uuid, entity_id: &str, legacy_format: bool) {
+    sqlx::query(
+        "INSERT INTO encrypted_data (id, entity_type, entity_id, field_name, key_id, key_version, iv, auth_tag, encrypted_value, encryption_algorithm, legacy_format, created_at, updated_at)
+         VALUES (?, 'customer', ?, 'tax_id', ?, 1, 'AAAA', NULL, 'AAAA.OTg3LTY1LTQzMjE=', 'AES-256-GCM', ?, ?, ?)"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(entity_id)
+    .bind(key_id.to_string())
+    .bind(legacy_format)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn setup() -> SqlitePool {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    for migration in [
+        include_str!("../../migrations/20240101000026_enterprise_infrastructure_features.sql"),
+        include_str!("../../migrations/20260329000100_legacy_key_material.sql"),
+    ] {
+        for statement in migration.split(';') {
+            let statement = statement.trim();
+            if !statement.is_empty() {
+                let _ = sqlx::query(statement).execute(&pool).await;
+            }
+        }
+    }
+    pool
+}
+
+#[tokio::test]
+async fn test_encrypt_rotate_and_decrypt() {
+    let pool = setup().await;
+    let service = KeyService::with_master_key(MasterKey::generate());
+
+    let key = service.generate_key(&pool, KeyType::Aes256Gcm, "customer-pii").await.unwrap();
+    assert!(key.encrypted_private_key.as_deref().unwrap().starts_with("v1:"));
+    service.set_primary_key(&pool, key.base.id).await.unwrap();
+
+    let data = service.encrypt_data(&pool, "customer", "c-1", "tax_id", "123-45-6789").await.unwrap();
+    assert!(data.auth_tag.is_some());
+    assert!(!data.encrypted_value.contains("123-45-6789"));
+
+    // A row written by the old base64 placeholder scheme, flagged by the legacy migration.
+    insert_untagged(&pool, key.base.id, "c-2", true).await;
+
+    let rotation = service.rotate_key(&pool, key.base.id, None).await.unwrap();
+    assert_eq!(rotation.from_version, 1);
+    assert_eq!(rotation.to_version, 2);
+    assert_eq!(rotation.re_encrypted_count, 2);
+
+    let remaining: i64 = sqlx::query("SELECT COUNT(*) AS n FROM encrypted_data WHERE key_id = ? OR auth_tag IS NULL")
+        .bind(key.base.id.to_string())
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get("n");
+    assert_eq!(remaining, 0);
+
+    assert_eq!(service.decrypt_data(&pool, "customer", "c-1", "tax_id").await.unwrap().as_deref(), Some("123-45-6789"));
+    assert_eq!(service.decrypt_data(&pool, "customer", "c-2", "tax_id").await.unwrap().as_deref(), Some("987-65-4321"));
+
+    let other_master = KeyService::with_master_key(MasterKey::generate());
+    assert!(other_master.decrypt_data(&pool, "customer", "c-1", "tax_id").await.is_err());
+}
+
+#[tokio::test]
+async fn test_legacy_formats_are_only_read_when_flagged() {
+    use base64::Engine;
+
+    let pool = setup().await;
+    let master = MasterKey::generate();
+    let service = KeyService::with_master_key(master.clone());
+    let key = service.generate_key(&pool, KeyType::Aes256Gcm, "customer-pii").await.unwrap();
+    service.set_primary_key(&pool, key.base.id).await.unwrap();
+
+    // An untagged value the migration did not flag is rejected rather than read as plain base64.
+    insert_untagged(&pool, key.base.id, "c-3", false).await;
+    assert!(service.decrypt_data(&pool, "customer", "c-3", "tax_id").await.is_err());
+
+    // A flagged value is read once and re-encrypted in place.
+    insert_untagged(&pool, key.base.id, "c-4", true).await;
+    assert_eq!(service.decrypt_data(&pool, "customer", "c-4", "tax_id").await.unwrap().as_deref(), Some("987-65-4321"));
+    let (tagged, legacy): (Option<String>, bool) = sqlx::query_as("SELECT auth_tag, legacy_format FROM encrypted_data WHERE entity_id = 'c-4'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(tagged.is_some() && !legacy);
+
+    // Plain key material is only accepted when flagged, and is wrapped on first use.
+    let data_key = master.unwrap(key.encrypted_private_key.as_deref().unwrap(), &key.key_id).unwrap();
+    let plain = base64::engine::general_purpose::STANDARD.encode(&data_key);
+    sqlx::query("UPDATE encryption_keys SET encrypted_private_key = ? WHERE id = ?")
+        .bind(&plain)
+        .bind(key.base.id.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert!(service.decrypt_data(&pool, "customer", "c-4", "tax_id").await.is_err());
+
+    sqlx::query("UPDATE encryption_keys SET legacy_material = 1 WHERE id = ?")
+        .bind(key.base.id.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(service.decrypt_data(&pool, "customer", "c-4", "tax_id").await.unwrap().as_deref(), Some("987-65-4321"));
+    let stored = service.get_key(&pool, key.base.id).await.unwrap().unwrap();
+    assert!(!stored.legacy_material);
+    assert!(stored.encrypted_private_key.as_deref().unwrap().starts_with("v1:"));
+}
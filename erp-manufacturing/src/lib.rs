@@ -1,4 +1,4 @@
-pub mod models; pub mod repository; pub mod service;
+pub mod models; pub mod repository; pub mod service; pub mod mrp;
 #[cfg(test)]
 mod tests;
 pub use models::*; pub use repository::*; pub use service::*;
@@ -171,6 +171,37 @@ pub enum MRPPlannedOrderStatus {
     Cancelled,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
+pub enum LotSizingRule {
+    LotForLot,
+    FixedQuantity,
+    EconomicOrderQuantity,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MRPItemSettings {
+    pub product_id: Uuid,
+    pub lot_sizing_rule: LotSizingRule,
+    pub fixed_lot_size: i64,
+    pub annual_demand: i64,
+    pub ordering_cost: i64,
+    pub holding_cost_per_unit: i64,
+    pub lead_time_days: Option<i32>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MRPPegging {
+    pub id: Uuid,
+    pub mrp_run_id: Uuid,
+    pub planned_order_id: Uuid,
+    pub demand_type: String,
+    pub demand_id: Option<Uuid>,
+    pub demand_date: DateTime<Utc>,
+    pub quantity: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CapacityPlan {
     pub id: Uuid,
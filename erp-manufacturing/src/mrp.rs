@@ -0,0 +1,288 @@
+use std::collections::{HashMap, VecDeque};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use erp_core::{Error, Result};
+use crate::models::*;
+
+#[derive(Debug, Clone)]
+pub struct MrpItem {
+    pub product_id: Uuid,
+    pub on_hand: i64,
+    pub safety_stock: i64,
+    pub lead_time_days: i32,
+    pub lot_sizing_rule: LotSizingRule,
+    pub fixed_lot_size: i64,
+    pub annual_demand: i64,
+    pub ordering_cost: i64,
+    pub holding_cost_per_unit: i64,
+    pub bom: Option<BillOfMaterial>,
+}
+
+impl MrpItem {
+    pub fn new(product_id: Uuid) -> Self {
+        Self {
+            product_id,
+            on_hand: 0,
+            safety_stock: 0,
+            lead_time_days: 0,
+            lot_sizing_rule: LotSizingRule::LotForLot,
+            fixed_lot_size: 0,
+            annual_demand: 0,
+            ordering_cost: 0,
+            holding_cost_per_unit: 0,
+            bom: None,
+        }
+    }
+
+    pub fn apply_settings(&mut self, settings: &MRPItemSettings) {
+        self.lot_sizing_rule = settings.lot_sizing_rule;
+        self.fixed_lot_size = settings.fixed_lot_size;
+        self.annual_demand = settings.annual_demand;
+        self.ordering_cost = settings.ordering_cost;
+        self.holding_cost_per_unit = settings.holding_cost_per_unit;
+        if let Some(days) = settings.lead_time_days {
+            self.lead_time_days = days;
+        }
+    }
+
+    pub fn lot_size(&self, net_requirement: i64) -> i64 {
+        if net_requirement <= 0 {
+            return 0;
+        }
+        match self.lot_sizing_rule {
+            LotSizingRule::LotForLot => net_requirement,
+            LotSizingRule::FixedQuantity => {
+                if self.fixed_lot_size <= 0 {
+                    return net_requirement;
+                }
+                ceil_div(net_requirement, self.fixed_lot_size) * self.fixed_lot_size
+            }
+            LotSizingRule::EconomicOrderQuantity => {
+                if self.holding_cost_per_unit <= 0 || self.annual_demand <= 0 || self.ordering_cost <= 0 {
+                    return net_requirement;
+                }
+                let eoq = ((2.0 * self.annual_demand as f64 * self.ordering_cost as f64)
+                    / self.holding_cost_per_unit as f64).sqrt().ceil() as i64;
+                net_requirement.max(eoq)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MrpDemand {
+    pub product_id: Uuid,
+    pub quantity: i64,
+    pub due_date: DateTime<Utc>,
+    pub source_type: String,
+    pub source_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MrpSupply {
+    pub product_id: Uuid,
+    pub quantity: i64,
+    pub available_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MrpPlan {
+    pub planned_orders: Vec<MRPPlannedOrder>,
+    pub pegging: Vec<MRPPegging>,
+    pub low_level_codes: HashMap<Uuid, u32>,
+}
+
+pub fn component_requirements(bom: &BillOfMaterial, quantity: i64) -> Vec<(Uuid, i64)> {
+    let bom_quantity = bom.quantity.max(1);
+    bom.components.iter()
+        .filter(|c| c.quantity > 0)
+        .map(|c| {
+            let scrap_bp = (c.scrap_percent * 100.0).round().max(0.0) as i64;
+            let gross = quantity * c.quantity * (10_000 + scrap_bp);
+            (c.product_id, ceil_div(gross, bom_quantity * 10_000))
+        })
+        .collect()
+}
+
+pub fn low_level_codes(items: &HashMap<Uuid, MrpItem>) -> Result<HashMap<Uuid, u32>> {
+    fn visit(
+        product_id: Uuid,
+        level: u32,
+        items: &HashMap<Uuid, MrpItem>,
+        codes: &mut HashMap<Uuid, u32>,
+        path: &mut Vec<Uuid>,
+    ) -> Result<()> {
+        if path.contains(&product_id) {
+            return Err(Error::business_rule(format!("Circular bill of material detected for product {}", product_id)));
+        }
+        let code = codes.entry(product_id).or_insert(0);
+        if level <= *code && !path.is_empty() {
+            return Ok(());
+        }
+        *code = (*code).max(level);
+        if let Some(bom) = items.get(&product_id).and_then(|i| i.bom.as_ref()) {
+            path.push(product_id);
+            for component in &bom.components {
+                visit(component.product_id, level + 1, items, codes, path)?;
+            }
+            path.pop();
+        }
+        Ok(())
+    }
+
+    let mut codes = HashMap::new();
+    let mut roots: Vec<Uuid> = items.keys().copied().collect();
+    roots.sort();
+    for product_id in roots {
+        let level = codes.get(&product_id).copied().unwrap_or(0);
+        visit(product_id, level, items, &mut codes, &mut Vec::new())?;
+    }
+    Ok(codes)
+}
+
+pub struct MrpEngine {
+    run_id: Uuid,
+    now: DateTime<Utc>,
+    horizon_end: DateTime<Utc>,
+}
+
+impl MrpEngine {
+    pub fn new(run_id: Uuid, now: DateTime<Utc>, horizon_days: i32) -> Self {
+        Self { run_id, now, horizon_end: now + Duration::days(horizon_days as i64) }
+    }
+
+    pub fn plan(&self, mut items: HashMap<Uuid, MrpItem>, demands: Vec<MrpDemand>, supplies: Vec<MrpSupply>) -> Result<MrpPlan> {
+        let mut demands_by_product: HashMap<Uuid, Vec<MrpDemand>> = HashMap::new();
+        for demand in demands.into_iter().filter(|d| d.quantity > 0 && d.due_date <= self.horizon_end) {
+            items.entry(demand.product_id).or_insert_with(|| MrpItem::new(demand.product_id));
+            demands_by_product.entry(demand.product_id).or_default().push(demand);
+        }
+        let mut supplies_by_product: HashMap<Uuid, Vec<MrpSupply>> = HashMap::new();
+        for supply in supplies.into_iter().filter(|s| s.quantity > 0) {
+            supplies_by_product.entry(supply.product_id).or_default().push(supply);
+        }
+        let component_ids: Vec<Uuid> = items.values()
+            .filter_map(|i| i.bom.as_ref())
+            .flat_map(|b| b.components.iter().map(|c| c.product_id))
+            .collect();
+        for id in component_ids {
+            items.entry(id).or_insert_with(|| MrpItem::new(id));
+        }
+
+        let codes = low_level_codes(&items)?;
+        let mut sequence: Vec<Uuid> = items.keys().copied().collect();
+        sequence.sort_by_key(|id| (codes.get(id).copied().unwrap_or(0), *id));
+
+        let mut plan = MrpPlan { low_level_codes: codes, ..Default::default() };
+        for product_id in sequence {
+            let item = &items[&product_id];
+            let product_demands = demands_by_product.remove(&product_id).unwrap_or_default();
+            let product_supplies = supplies_by_product.remove(&product_id).unwrap_or_default();
+            let first_order = plan.planned_orders.len();
+            self.net_item(item, product_demands, product_supplies, &mut plan);
+
+            if let Some(bom) = &item.bom {
+                for order in &plan.planned_orders[first_order..] {
+                    let due = order.release_date.unwrap_or(order.due_date);
+                    for (component_id, quantity) in component_requirements(bom, order.quantity) {
+                        demands_by_product.entry(component_id).or_default().push(MrpDemand {
+                            product_id: component_id,
+                            quantity,
+                            due_date: due,
+                            source_type: "PlannedOrder".to_string(),
+                            source_id: Some(order.id),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(plan)
+    }
+
+    fn net_item(&self, item: &MrpItem, mut demands: Vec<MrpDemand>, mut supplies: Vec<MrpSupply>, plan: &mut MrpPlan) {
+        for demand in &mut demands {
+            if demand.due_date < self.now {
+                demand.due_date = self.now;
+            }
+        }
+        let available = item.on_hand - item.safety_stock;
+        if available < 0 {
+            demands.push(MrpDemand {
+                product_id: item.product_id,
+                quantity: -available,
+                due_date: self.now,
+                source_type: "SafetyStock".to_string(),
+                source_id: None,
+            });
+        }
+        demands.sort_by_key(|d| d.due_date);
+        supplies.sort_by_key(|s| s.available_date);
+
+        // Each entry is a quantity on hand for netting; planned lots carry the index of the order they came from.
+        let mut pool: VecDeque<(i64, Option<usize>)> = VecDeque::new();
+        if available > 0 {
+            pool.push_back((available, None));
+        }
+        let mut receipts = supplies.into_iter().peekable();
+
+        for demand in demands {
+            while let Some(receipt) = receipts.next_if(|s| s.available_date <= demand.due_date) {
+                pool.push_back((receipt.quantity, None));
+            }
+
+            let mut remaining = demand.quantity;
+            while remaining > 0 {
+                let Some((quantity, order_index)) = pool.front_mut() else { break };
+                let taken = remaining.min(*quantity);
+                *quantity -= taken;
+                remaining -= taken;
+                if let Some(index) = *order_index {
+                    self.peg(plan, index, &demand, taken);
+                }
+                if *quantity == 0 {
+                    pool.pop_front();
+                }
+            }
+            if remaining == 0 {
+                continue;
+            }
+
+            let quantity = item.lot_size(remaining);
+            let release = (demand.due_date - Duration::days(item.lead_time_days as i64)).max(self.now);
+            let order = MRPPlannedOrder {
+                id: Uuid::new_v4(),
+                mrp_run_id: self.run_id,
+                product_id: item.product_id,
+                order_type: if item.bom.is_some() { MRPOrderType::Production } else { MRPOrderType::Purchase },
+                quantity,
+                due_date: demand.due_date,
+                release_date: Some(release),
+                source_type: Some(demand.source_type.clone()),
+                source_id: demand.source_id,
+                status: MRPPlannedOrderStatus::Planned,
+            };
+            plan.planned_orders.push(order);
+            let index = plan.planned_orders.len() - 1;
+            self.peg(plan, index, &demand, remaining);
+            if quantity > remaining {
+                pool.push_back((quantity - remaining, Some(index)));
+            }
+        }
+    }
+
+    fn peg(&self, plan: &mut MrpPlan, order_index: usize, demand: &MrpDemand, quantity: i64) {
+        plan.pegging.push(MRPPegging {
+            id: Uuid::new_v4(),
+            mrp_run_id: self.run_id,
+            planned_order_id: plan.planned_orders[order_index].id,
+            demand_type: demand.source_type.clone(),
+            demand_id: demand.source_id,
+            demand_date: demand.due_date,
+            quantity,
+        });
+    }
+}
+
+fn ceil_div(numerator: i64, denominator: i64) -> i64 {
+    (numerator + denominator - 1) / denominator
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use chrono::{Utc, DateTime, NaiveDate};
 use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status};
@@ -144,8 +144,14 @@ impl WorkOrderRepository for SqliteWorkOrderRepository {
     }
 
     async fn update_status(&self, pool: &SqlitePool, id: Uuid, status: Status, actual_start: Option<String>, actual_end: Option<String>) -> Result<()> {
+        self.update_status_in(&mut *pool.acquire().await?, id, status, actual_start, actual_end).await
+    }
+}
+
+impl SqliteWorkOrderRepository {
+    pub async fn update_status_in(&self, conn: &mut SqliteConnection, id: Uuid, status: Status, actual_start: Option<String>, actual_end: Option<String>) -> Result<()> {
         let rows = sqlx::query("UPDATE work_orders SET status = ?, actual_start = COALESCE(?, actual_start), actual_end = COALESCE(?, actual_end), updated_at = ? WHERE id = ?")
-            .bind(format!("{:?}", status)).bind(actual_start).bind(actual_end).bind(Utc::now().to_rfc3339()).bind(id.to_string()).execute(pool).await?;
+            .bind(format!("{:?}", status)).bind(actual_start).bind(actual_end).bind(Utc::now().to_rfc3339()).bind(id.to_string()).execute(&mut *conn).await?;
         if rows.rows_affected() == 0 { return Err(Error::not_found("WorkOrder", &id.to_string())); }
         Ok(())
     }
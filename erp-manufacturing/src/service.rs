@@ -1,8 +1,11 @@
 use sqlx::SqlitePool;
+use std::collections::HashMap;
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status};
+use erp_finance::{PostingDocument, PostingEvent, PostingService};
 use crate::models::*;
+use crate::mrp::{MrpDemand, MrpEngine, MrpItem, MrpPlan, MrpSupply};
 use crate::repository::*;
 
 pub struct BillOfMaterialService { repo: SqliteBillOfMaterialRepository }
@@ -52,7 +55,33 @@ impl WorkOrderService {
     }
     
     pub async fn complete(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
-        self.repo.update_status(pool, id, Status::Completed, None, Some(Utc::now().to_rfc3339())).await
+        let order = self.repo.find_by_id(pool, id).await?;
+        let now = Utc::now();
+        
+        let mut cost = 0i64;
+        if PostingService::is_configured(pool, PostingEvent::ProductionCompletion).await? {
+            let bom = SqliteBillOfMaterialRepository.find_by_id(pool, order.bom_id).await?;
+            let valuation = erp_inventory::InventoryValuationService::new();
+            for (component_id, quantity) in crate::mrp::component_requirements(&bom, order.quantity) {
+                cost += quantity * valuation.get_unit_cost(pool, component_id).await?;
+            }
+        }
+        
+        let mut tx = pool.begin().await?;
+        PostingService::post_document(&mut tx, PostingDocument {
+            event: PostingEvent::ProductionCompletion,
+            source_type: "WorkOrder".to_string(),
+            source_id: order.base.id,
+            source_number: order.order_number.clone(),
+            date: now,
+            amount: cost,
+            tax_amount: 0,
+            currency: erp_core::Currency::USD,
+            description: Some(format!("Production completion for {}", order.order_number)),
+        }).await?;
+        self.repo.update_status_in(&mut tx, id, Status::Completed, None, Some(now.to_rfc3339())).await?;
+        tx.commit().await?;
+        Ok(())
     }
 }
 
@@ -259,8 +288,21 @@ impl MRPService {
         .await
         .map_err(Error::Database)?;
         
-        let planned_orders = Self::generate_planned_orders(pool, run_id, planning_horizon_days).await?;
-        for order in planned_orders {
+        let plan = match Self::generate_planned_orders(pool, run_id, planning_horizon_days).await {
+            Ok(plan) => plan,
+            Err(e) => {
+                sqlx::query("UPDATE mrp_runs SET status = 'Failed', completed_at = ? WHERE id = ?")
+                    .bind(Utc::now().to_rfc3339())
+                    .bind(run_id.to_string())
+                    .execute(pool)
+                    .await
+                    .map_err(Error::Database)?;
+                return Err(e);
+            }
+        };
+        
+        let mut tx = pool.begin().await.map_err(Error::Database)?;
+        for order in &plan.planned_orders {
             sqlx::query(
                 "INSERT INTO mrp_planned_orders (id, mrp_run_id, product_id, order_type, quantity, due_date, release_date, source_type, source_id, status)
                  VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 'Planned')"
@@ -274,7 +316,23 @@ impl MRPService {
             .bind(order.release_date.map(|d| d.to_rfc3339()))
             .bind(&order.source_type)
             .bind(order.source_id.map(|id| id.to_string()))
-            .execute(pool)
+            .execute(&mut *tx)
+            .await
+            .map_err(Error::Database)?;
+        }
+        for peg in &plan.pegging {
+            sqlx::query(
+                "INSERT INTO mrp_pegging (id, mrp_run_id, planned_order_id, demand_type, demand_id, demand_date, quantity)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(peg.id.to_string())
+            .bind(peg.mrp_run_id.to_string())
+            .bind(peg.planned_order_id.to_string())
+            .bind(&peg.demand_type)
+            .bind(peg.demand_id.map(|id| id.to_string()))
+            .bind(peg.demand_date.to_rfc3339())
+            .bind(peg.quantity)
+            .execute(&mut *tx)
             .await
             .map_err(Error::Database)?;
         }
@@ -282,16 +340,244 @@ impl MRPService {
         sqlx::query("UPDATE mrp_runs SET status = 'Completed', completed_at = ? WHERE id = ?")
             .bind(Utc::now().to_rfc3339())
             .bind(run_id.to_string())
-            .execute(pool)
+            .execute(&mut *tx)
             .await
             .map_err(Error::Database)?;
+        tx.commit().await.map_err(Error::Database)?;
         
         Self::get_mrp_run(pool, run_id).await
     }
 
-    async fn generate_planned_orders(pool: &SqlitePool, run_id: Uuid, horizon_days: i32) -> Result<Vec<MRPPlannedOrder>> {
-        let _ = (pool, run_id, horizon_days);
-        Ok(vec![])
+    async fn generate_planned_orders(pool: &SqlitePool, run_id: Uuid, horizon_days: i32) -> Result<MrpPlan> {
+        let now = Utc::now();
+        let engine = MrpEngine::new(run_id, now, horizon_days);
+        let horizon_end = now + chrono::Duration::days(horizon_days as i64);
+        
+        let mut demands: Vec<MrpDemand> = Vec::new();
+        let sales_rows = sqlx::query_as::<_, (String, String, i64, String)>(
+            "SELECT o.id, l.product_id, l.quantity, COALESCE(o.required_date, o.order_date)
+             FROM sales_order_lines l JOIN sales_orders o ON o.id = l.sales_order_id
+             WHERE o.status IN ('Pending', 'Approved')"
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+        for (order_id, product_id, quantity, due) in sales_rows {
+            demands.push(MrpDemand {
+                product_id: planning_id(&product_id)?,
+                quantity,
+                due_date: parse_planning_date(&due).unwrap_or(now),
+                source_type: "SalesOrder".to_string(),
+                source_id: Some(planning_id(&order_id)?),
+            });
+        }
+        
+        let mut supplies: Vec<MrpSupply> = Vec::new();
+        let po_rows = sqlx::query_as::<_, (String, i64, String)>(
+            "SELECT l.product_id,
+                    l.quantity - COALESCE((SELECT SUM(r.quantity_received) FROM goods_receipt_lines r WHERE r.po_line_id = l.id), 0),
+                    COALESCE(o.expected_date, o.order_date)
+             FROM purchase_order_lines l JOIN purchase_orders o ON o.id = l.purchase_order_id
+             WHERE o.status IN ('Pending', 'Approved')"
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+        // Received quantities are already on hand.
+        for (product_id, quantity, expected) in po_rows.into_iter().filter(|row| row.1 > 0) {
+            supplies.push(MrpSupply {
+                product_id: planning_id(&product_id)?,
+                quantity,
+                available_date: parse_planning_date(&expected).unwrap_or(now),
+            });
+        }
+        
+        let bom_repo = SqliteBillOfMaterialRepository;
+        let wo_rows = sqlx::query_as::<_, (String, String, String, i64, String, String, String)>(
+            "SELECT id, product_id, bom_id, quantity, planned_start, planned_end, status FROM work_orders
+             WHERE status NOT IN ('Completed', 'Cancelled', 'Closed')"
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+        for (wo_id, product_id, bom_id, quantity, planned_start, planned_end, status) in wo_rows {
+            let product_id = planning_id(&product_id)?;
+            let wo_id = planning_id(&wo_id)?;
+            supplies.push(MrpSupply {
+                product_id,
+                quantity,
+                available_date: parse_planning_date(&planned_end).unwrap_or(now),
+            });
+            if matches!(status.as_str(), "InProgress" | "Pending") {
+                continue;
+            }
+            let bom = match bom_id.trim() {
+                "" => Self::find_planning_bom(pool, product_id).await?,
+                bom_id => match planning_id(bom_id)? {
+                    id if id.is_nil() => Self::find_planning_bom(pool, product_id).await?,
+                    id => Some(bom_repo.find_by_id(pool, id).await?),
+                },
+            };
+            if let Some(bom) = bom {
+                for (component_id, required) in crate::mrp::component_requirements(&bom, quantity) {
+                    demands.push(MrpDemand {
+                        product_id: component_id,
+                        quantity: required,
+                        due_date: parse_planning_date(&planned_start).unwrap_or(now),
+                        source_type: "WorkOrder".to_string(),
+                        source_id: Some(wo_id),
+                    });
+                }
+            }
+        }
+        
+        let mut pending: Vec<Uuid> = sqlx::query_as::<_, (String,)>("SELECT id FROM products WHERE status = 'Active'")
+            .fetch_all(pool)
+            .await
+            .map_err(Error::Database)?
+            .into_iter()
+            .map(|(id,)| planning_id(&id))
+            .collect::<Result<_>>()?;
+        pending.extend(demands.iter().map(|d| d.product_id));
+        
+        let stock_svc = erp_inventory::StockService::new();
+        let mut items: HashMap<Uuid, MrpItem> = HashMap::new();
+        while let Some(product_id) = pending.pop() {
+            if items.contains_key(&product_id) {
+                continue;
+            }
+            let mut item = MrpItem::new(product_id);
+            // Reservations belong to the approved orders counted as demand, so net against the
+            // quantity on hand rather than what is left after reserving.
+            item.on_hand = stock_svc.get_product_stock(pool, product_id).await?
+                .iter()
+                .map(|l| l.quantity)
+                .sum();
+            for level in erp_inventory::SafetyStockService::get_for_product(pool, product_id).await? {
+                item.safety_stock += level.safety_stock;
+                item.lead_time_days = item.lead_time_days.max(level.lead_time_days);
+            }
+            if let Some(settings) = Self::get_item_settings(pool, product_id).await? {
+                item.apply_settings(&settings);
+            }
+            item.bom = Self::find_planning_bom(pool, product_id).await?;
+            if let Some(bom) = &item.bom {
+                pending.extend(bom.components.iter().map(|c| c.product_id));
+            }
+            
+            let sales_in_horizon: Vec<(DateTime<Utc>, i64)> = demands.iter()
+                .filter(|d| d.product_id == product_id && d.source_type == "SalesOrder")
+                .map(|d| (d.due_date, d.quantity))
+                .collect();
+            for forecast in erp_inventory::DemandForecastService::get_forecasts_for_product(pool, product_id).await? {
+                if forecast.period_end < now || forecast.period_start > horizon_end {
+                    continue;
+                }
+                let consumed: i64 = sales_in_horizon.iter()
+                    .filter(|(due, _)| *due >= forecast.period_start && *due <= forecast.period_end)
+                    .map(|(_, qty)| qty)
+                    .sum();
+                let remaining = forecast.forecast_quantity - consumed;
+                if remaining > 0 {
+                    demands.push(MrpDemand {
+                        product_id,
+                        quantity: remaining,
+                        due_date: forecast.period_start.max(now),
+                        source_type: "Forecast".to_string(),
+                        source_id: Some(forecast.id),
+                    });
+                }
+            }
+            items.insert(product_id, item);
+        }
+        
+        engine.plan(items, demands, supplies)
+    }
+
+    async fn find_planning_bom(pool: &SqlitePool, product_id: Uuid) -> Result<Option<BillOfMaterial>> {
+        let bom_id = sqlx::query_as::<_, (String,)>(
+            "SELECT id FROM bills_of_material
+             WHERE product_id = ? AND status NOT IN ('Inactive', 'Cancelled', 'Deleted')
+             ORDER BY CASE status WHEN 'Active' THEN 0 WHEN 'Approved' THEN 1 ELSE 2 END, created_at DESC
+             LIMIT 1"
+        )
+        .bind(product_id.to_string())
+        .fetch_optional(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        match bom_id {
+            Some((id,)) => Ok(Some(SqliteBillOfMaterialRepository.find_by_id(pool, planning_id(&id)?).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_item_settings(pool: &SqlitePool, mut settings: MRPItemSettings) -> Result<MRPItemSettings> {
+        if settings.fixed_lot_size < 0 || settings.annual_demand < 0 || settings.ordering_cost < 0 || settings.holding_cost_per_unit < 0 {
+            return Err(Error::validation("Lot sizing parameters cannot be negative"));
+        }
+        if settings.lot_sizing_rule == LotSizingRule::FixedQuantity && settings.fixed_lot_size == 0 {
+            return Err(Error::validation("Fixed lot size is required for the FixedQuantity rule"));
+        }
+        if settings.lot_sizing_rule == LotSizingRule::EconomicOrderQuantity && settings.holding_cost_per_unit == 0 {
+            return Err(Error::validation("Holding cost is required for the EconomicOrderQuantity rule"));
+        }
+        if settings.lead_time_days.is_some_and(|d| d < 0) {
+            return Err(Error::validation("Lead time cannot be negative"));
+        }
+        settings.updated_at = Utc::now();
+        
+        sqlx::query(
+            "INSERT INTO mrp_item_settings (product_id, lot_sizing_rule, fixed_lot_size, annual_demand, ordering_cost, holding_cost_per_unit, lead_time_days, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(product_id) DO UPDATE SET
+             lot_sizing_rule = excluded.lot_sizing_rule,
+             fixed_lot_size = excluded.fixed_lot_size,
+             annual_demand = excluded.annual_demand,
+             ordering_cost = excluded.ordering_cost,
+             holding_cost_per_unit = excluded.holding_cost_per_unit,
+             lead_time_days = excluded.lead_time_days,
+             updated_at = excluded.updated_at"
+        )
+        .bind(settings.product_id.to_string())
+        .bind(format!("{:?}", settings.lot_sizing_rule))
+        .bind(settings.fixed_lot_size)
+        .bind(settings.annual_demand)
+        .bind(settings.ordering_cost)
+        .bind(settings.holding_cost_per_unit)
+        .bind(settings.lead_time_days)
+        .bind(settings.updated_at.to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        Ok(settings)
+    }
+
+    pub async fn get_item_settings(pool: &SqlitePool, product_id: Uuid) -> Result<Option<MRPItemSettings>> {
+        let row = sqlx::query_as::<_, MRPItemSettingsRow>(
+            "SELECT product_id, lot_sizing_rule, fixed_lot_size, annual_demand, ordering_cost, holding_cost_per_unit, lead_time_days, updated_at
+             FROM mrp_item_settings WHERE product_id = ?"
+        )
+        .bind(product_id.to_string())
+        .fetch_optional(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        Ok(row.map(|r| r.into()))
+    }
+
+    pub async fn get_pegging(pool: &SqlitePool, planned_order_id: Uuid) -> Result<Vec<MRPPegging>> {
+        let rows = sqlx::query_as::<_, MRPPeggingRow>(
+            "SELECT id, mrp_run_id, planned_order_id, demand_type, demand_id, demand_date, quantity
+             FROM mrp_pegging WHERE planned_order_id = ? ORDER BY demand_date"
+        )
+        .bind(planned_order_id.to_string())
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        Ok(rows.into_iter().map(|r| r.into()).collect())
     }
 
     pub async fn get_mrp_run(pool: &SqlitePool, id: Uuid) -> Result<MRPRun> {
@@ -329,6 +615,9 @@ impl MRPService {
         let now = Utc::now();
         match order.order_type {
             MRPOrderType::Production => {
+                let bom_id = Self::find_planning_bom(pool, order.product_id).await?
+                    .map(|b| b.base.id)
+                    .ok_or_else(|| Error::business_rule("No bill of material found for planned product"))?;
                 let wo_number = format!("WO-{}", now.format("%Y%m%d%H%M%S"));
                 sqlx::query(
                     "INSERT INTO work_orders (id, order_number, product_id, bom_id, quantity, planned_start, planned_end, actual_start, actual_end, status, created_at, updated_at)
@@ -337,9 +626,9 @@ impl MRPService {
                 .bind(Uuid::new_v4().to_string())
                 .bind(&wo_number)
                 .bind(order.product_id.to_string())
-                .bind(Uuid::nil().to_string())
+                .bind(bom_id.to_string())
                 .bind(order.quantity)
-                .bind(order.due_date.to_rfc3339())
+                .bind(order.release_date.unwrap_or(order.due_date).to_rfc3339())
                 .bind(order.due_date.to_rfc3339())
                 .bind(now.to_rfc3339())
                 .bind(now.to_rfc3339())
@@ -444,6 +733,73 @@ impl From<MRPPlannedOrderRow> for MRPPlannedOrder {
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct MRPItemSettingsRow {
+    product_id: String,
+    lot_sizing_rule: String,
+    fixed_lot_size: i64,
+    annual_demand: i64,
+    ordering_cost: i64,
+    holding_cost_per_unit: i64,
+    lead_time_days: Option<i64>,
+    updated_at: String,
+}
+
+impl From<MRPItemSettingsRow> for MRPItemSettings {
+    fn from(r: MRPItemSettingsRow) -> Self {
+        Self {
+            product_id: Uuid::parse_str(&r.product_id).unwrap_or_default(),
+            lot_sizing_rule: match r.lot_sizing_rule.as_str() { "FixedQuantity" => LotSizingRule::FixedQuantity, "EconomicOrderQuantity" => LotSizingRule::EconomicOrderQuantity, _ => LotSizingRule::LotForLot },
+            fixed_lot_size: r.fixed_lot_size,
+            annual_demand: r.annual_demand,
+            ordering_cost: r.ordering_cost,
+            holding_cost_per_unit: r.holding_cost_per_unit,
+            lead_time_days: r.lead_time_days.map(|d| d as i32),
+            updated_at: chrono::DateTime::parse_from_rfc3339(&r.updated_at).map(|d| d.with_timezone(&Utc)).unwrap_or_else(|_| Utc::now()),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MRPPeggingRow {
+    id: String,
+    mrp_run_id: String,
+    planned_order_id: String,
+    demand_type: String,
+    demand_id: Option<String>,
+    demand_date: String,
+    quantity: i64,
+}
+
+impl From<MRPPeggingRow> for MRPPegging {
+    fn from(r: MRPPeggingRow) -> Self {
+        Self {
+            id: Uuid::parse_str(&r.id).unwrap_or_default(),
+            mrp_run_id: Uuid::parse_str(&r.mrp_run_id).unwrap_or_default(),
+            planned_order_id: Uuid::parse_str(&r.planned_order_id).unwrap_or_default(),
+            demand_type: r.demand_type,
+            demand_id: r.demand_id.and_then(|s| Uuid::parse_str(&s).ok()),
+            demand_date: chrono::DateTime::parse_from_rfc3339(&r.demand_date).map(|d| d.with_timezone(&Utc)).unwrap_or_else(|_| Utc::now()),
+            quantity: r.quantity,
+        }
+    }
+}
+
+/// Parses an id read while planning. A malformed id is an error rather than a nil id that would
+/// plan against the wrong record.
+fn planning_id(value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value).map_err(|_| Error::internal(format!("Invalid id {} in planning data", value)))
+}
+
+fn parse_planning_date(value: &str) -> Option<DateTime<Utc>> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|d| d.with_timezone(&Utc))
+        .ok()
+        .or_else(|| chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|d| d.and_utc()))
+}
+
 pub struct CapacityPlanningService;
 
 impl Default for CapacityPlanningService {
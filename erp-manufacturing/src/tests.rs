@@ -81,4 +81,157 @@ mod tests {
         // 0.875 * 0.95238 * 0.95 = 0.79166
         assert!((oee - 0.79166).abs() < 0.00001);
     }
+
+    fn test_bom(product_id: Uuid, components: Vec<(Uuid, i64, f64)>) -> BillOfMaterial {
+        BillOfMaterial {
+            base: erp_core::BaseEntity::new(),
+            product_id,
+            name: "Test BOM".to_string(),
+            version: "1".to_string(),
+            quantity: 1,
+            components: components.into_iter().map(|(id, qty, scrap)| BomComponent {
+                id: Uuid::new_v4(),
+                product_id: id,
+                quantity: qty,
+                unit: "PCS".to_string(),
+                scrap_percent: scrap,
+            }).collect(),
+            operations: vec![],
+            status: erp_core::Status::Active,
+        }
+    }
+
+    #[test]
+    fn test_mrp_lot_sizing_rules() {
+        let mut item = crate::mrp::MrpItem::new(Uuid::new_v4());
+        assert_eq!(item.lot_size(37), 37);
+
+        item.lot_sizing_rule = LotSizingRule::FixedQuantity;
+        item.fixed_lot_size = 25;
+        assert_eq!(item.lot_size(37), 50);
+        assert_eq!(item.lot_size(25), 25);
+
+        // EOQ = sqrt(2 * 1000 * 50 / 4) = 158.1 -> 159
+        item.lot_sizing_rule = LotSizingRule::EconomicOrderQuantity;
+        item.annual_demand = 1000;
+        item.ordering_cost = 50;
+        item.holding_cost_per_unit = 4;
+        assert_eq!(item.lot_size(37), 159);
+        assert_eq!(item.lot_size(400), 400);
+    }
+
+    #[test]
+    fn test_mrp_low_level_codes_and_cycle_detection() {
+        use crate::mrp::{low_level_codes, MrpItem};
+        use std::collections::HashMap;
+
+        let (bike, frame, wheel, spoke) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut items = HashMap::new();
+        for id in [bike, frame, wheel, spoke] {
+            items.insert(id, MrpItem::new(id));
+        }
+        items.get_mut(&bike).unwrap().bom = Some(test_bom(bike, vec![(frame, 1, 0.0), (wheel, 2, 0.0), (spoke, 4, 0.0)]));
+        items.get_mut(&wheel).unwrap().bom = Some(test_bom(wheel, vec![(spoke, 32, 0.0)]));
+
+        let codes = low_level_codes(&items).unwrap();
+        assert_eq!(codes[&bike], 0);
+        assert_eq!(codes[&frame], 1);
+        assert_eq!(codes[&wheel], 1);
+        assert_eq!(codes[&spoke], 2);
+
+        items.get_mut(&spoke).unwrap().bom = Some(test_bom(spoke, vec![(bike, 1, 0.0)]));
+        assert!(low_level_codes(&items).is_err());
+    }
+
+    #[test]
+    fn test_mrp_multi_level_explosion_with_pegging() {
+        use crate::mrp::{MrpDemand, MrpEngine, MrpItem, MrpSupply};
+        use std::collections::HashMap;
+
+        let now = Utc::now();
+        let (bike, wheel, spoke) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let order_id = Uuid::new_v4();
+
+        let mut bike_item = MrpItem::new(bike);
+        bike_item.on_hand = 3;
+        bike_item.lead_time_days = 2;
+        bike_item.bom = Some(test_bom(bike, vec![(wheel, 2, 0.0)]));
+
+        let mut wheel_item = MrpItem::new(wheel);
+        wheel_item.on_hand = 4;
+        wheel_item.safety_stock = 2;
+        wheel_item.lead_time_days = 1;
+        wheel_item.bom = Some(test_bom(wheel, vec![(spoke, 10, 10.0)]));
+
+        let mut spoke_item = MrpItem::new(spoke);
+        spoke_item.lead_time_days = 5;
+        spoke_item.lot_sizing_rule = LotSizingRule::FixedQuantity;
+        spoke_item.fixed_lot_size = 100;
+
+        let items = HashMap::from([(bike, bike_item), (wheel, wheel_item), (spoke, spoke_item)]);
+        let demands = vec![MrpDemand {
+            product_id: bike,
+            quantity: 10,
+            due_date: now + chrono::Duration::days(10),
+            source_type: "SalesOrder".to_string(),
+            source_id: Some(order_id),
+        }];
+        let supplies = vec![MrpSupply { product_id: spoke, quantity: 20, available_date: now + chrono::Duration::days(1) }];
+
+        let plan = MrpEngine::new(Uuid::new_v4(), now, 30).plan(items, demands, supplies).unwrap();
+        let order_for = |id: Uuid| plan.planned_orders.iter().find(|o| o.product_id == id).unwrap();
+
+        // 10 bikes - 3 on hand = 7 to build, released 2 days before due
+        let bike_order = order_for(bike);
+        assert_eq!(bike_order.quantity, 7);
+        assert!(matches!(bike_order.order_type, MRPOrderType::Production));
+        assert_eq!(bike_order.release_date.unwrap(), now + chrono::Duration::days(8));
+        assert_eq!(bike_order.source_id, Some(order_id));
+
+        // 14 wheels needed, 4 on hand but 2 held as safety stock
+        let wheel_order = order_for(wheel);
+        assert_eq!(wheel_order.quantity, 12);
+        assert_eq!(wheel_order.due_date, now + chrono::Duration::days(8));
+        assert_eq!(wheel_order.source_id, Some(bike_order.id));
+
+        // 12 wheels * 10 spokes * 1.1 scrap = 132, less 20 on order, rounded up to a lot of 100
+        let spoke_order = order_for(spoke);
+        assert_eq!(spoke_order.quantity, 200);
+        assert!(matches!(spoke_order.order_type, MRPOrderType::Purchase));
+
+        let spoke_pegs: Vec<_> = plan.pegging.iter().filter(|p| p.planned_order_id == spoke_order.id).collect();
+        assert_eq!(spoke_pegs.len(), 1);
+        assert_eq!(spoke_pegs[0].quantity, 112);
+        assert_eq!(spoke_pegs[0].demand_id, Some(wheel_order.id));
+        assert_eq!(plan.low_level_codes[&spoke], 2);
+    }
+
+    #[test]
+    fn test_mrp_safety_stock_shortfall_and_lot_remainder_pegging() {
+        use crate::mrp::{MrpDemand, MrpEngine, MrpItem};
+        use std::collections::HashMap;
+
+        let now = Utc::now();
+        let part = Uuid::new_v4();
+        let mut item = MrpItem::new(part);
+        item.safety_stock = 5;
+        item.lot_sizing_rule = LotSizingRule::FixedQuantity;
+        item.fixed_lot_size = 50;
+
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let demands = vec![
+            MrpDemand { product_id: part, quantity: 20, due_date: now + chrono::Duration::days(3), source_type: "SalesOrder".to_string(), source_id: Some(first) },
+            MrpDemand { product_id: part, quantity: 10, due_date: now + chrono::Duration::days(6), source_type: "SalesOrder".to_string(), source_id: Some(second) },
+            MrpDemand { product_id: part, quantity: 99, due_date: now + chrono::Duration::days(60), source_type: "SalesOrder".to_string(), source_id: None },
+        ];
+
+        let plan = MrpEngine::new(Uuid::new_v4(), now, 30).plan(HashMap::from([(part, item)]), demands, vec![]).unwrap();
+
+        // The safety stock shortfall triggers one lot of 50 that also covers both orders; the order beyond the horizon is ignored
+        assert_eq!(plan.planned_orders.len(), 1);
+        assert_eq!(plan.planned_orders[0].quantity, 50);
+        assert_eq!(plan.planned_orders[0].source_type.as_deref(), Some("SafetyStock"));
+        let pegged: Vec<(Option<Uuid>, i64)> = plan.pegging.iter().map(|p| (p.demand_id, p.quantity)).collect();
+        assert_eq!(pegged, vec![(None, 5), (Some(first), 20), (Some(second), 10)]);
+    }
 }
@@ -0,0 +1,75 @@
+use chrono::{Duration, Utc};
+use erp_core::testing::{insert, memory_pool};
+use erp_manufacturing::MRPService;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+async fn setup() -> SqlitePool {
+    memory_pool(&[
+        include_str!("../../migrations/20240101000001_inventory.sql"),
+        include_str!("../../migrations/20240101000002_sales.sql"),
+        include_str!("../../migrations/20240101000003_purchasing.sql"),
+        include_str!("../../migrations/20240101000004_manufacturing.sql"),
+        include_str!("../../migrations/20240101000012_advanced_features.sql"),
+        include_str!("../../migrations/20240101000013_enterprise_features.sql"),
+        include_str!("../../migrations/20260308000000_mrp_netting.sql"),
+    ]).await
+}
+
+#[tokio::test]
+async fn test_mrp_nets_open_supply_against_on_hand_stock() {
+    let pool = setup().await;
+    let now = Utc::now();
+    let stamp = now.to_rfc3339();
+    let product = Uuid::new_v4();
+    let product_id = product.to_string();
+    let location = Uuid::new_v4().to_string();
+
+    insert(&pool, "INSERT INTO products (id, sku, name, product_type, unit_of_measure, created_at, updated_at) VALUES (?, 'WIDGET', 'Widget', 'Goods', 'PCS', ?, ?)", &[&product_id, &stamp, &stamp]).await;
+    insert(&pool, "INSERT INTO stock_levels (id, product_id, location_id, quantity, reserved_quantity, available_quantity) VALUES (?, ?, ?, 10, 0, 10)", &[&Uuid::new_v4().to_string(), &product_id, &location]).await;
+
+    let order = Uuid::new_v4().to_string();
+    let required = (now + Duration::days(5)).to_rfc3339();
+    insert(&pool, "INSERT INTO sales_orders (id, order_number, customer_id, order_date, required_date, status, created_at, updated_at) VALUES (?, 'SO-1', ?, ?, ?, 'Pending', ?, ?)", &[&order, &Uuid::new_v4().to_string(), &stamp, &required, &stamp, &stamp]).await;
+    insert(&pool, "INSERT INTO sales_order_lines (id, sales_order_id, product_id, description, quantity, unit_price, line_total) VALUES (?, ?, ?, 'Widget', 30, 100, 3000)", &[&Uuid::new_v4().to_string(), &order, &product_id]).await;
+
+    // 15 of the 20 ordered units have arrived and are part of the 10 on hand.
+    let po = Uuid::new_v4().to_string();
+    let po_line = Uuid::new_v4().to_string();
+    let expected = (now + Duration::days(2)).to_rfc3339();
+    insert(&pool, "INSERT INTO purchase_orders (id, po_number, vendor_id, order_date, expected_date, status, created_at, updated_at) VALUES (?, 'PO-1', ?, ?, ?, 'Approved', ?, ?)", &[&po, &Uuid::new_v4().to_string(), &stamp, &expected, &stamp, &stamp]).await;
+    insert(&pool, "INSERT INTO purchase_order_lines (id, purchase_order_id, product_id, description, quantity, unit_price, line_total) VALUES (?, ?, ?, 'Widget', 20, 50, 1000)", &[&po_line, &po, &product_id]).await;
+    let receipt = Uuid::new_v4().to_string();
+    insert(&pool, "INSERT INTO goods_receipts (id, receipt_number, purchase_order_id, warehouse_id, receipt_date, status, created_at, updated_at) VALUES (?, 'GR-1', ?, ?, ?, 'Completed', ?, ?)", &[&receipt, &po, &Uuid::new_v4().to_string(), &stamp, &stamp, &stamp]).await;
+    insert(&pool, "INSERT INTO goods_receipt_lines (id, goods_receipt_id, po_line_id, product_id, quantity_ordered, quantity_received) VALUES (?, ?, ?, ?, 20, 15)", &[&Uuid::new_v4().to_string(), &receipt, &po_line, &product_id]).await;
+
+    let run = MRPService::run_mrp(&pool, 30).await.unwrap();
+    let planned = MRPService::get_planned_orders(&pool, run.base.id).await.unwrap();
+    assert_eq!(planned.len(), 1);
+    assert_eq!(planned[0].product_id, product);
+    assert_eq!(planned[0].quantity, 15);
+}
+
+#[tokio::test]
+async fn test_mrp_does_not_count_reserved_demand_twice() {
+    let pool = setup().await;
+    let now = Utc::now();
+    let stamp = now.to_rfc3339();
+    let product = Uuid::new_v4();
+    let product_id = product.to_string();
+
+    insert(&pool, "INSERT INTO products (id, sku, name, product_type, unit_of_measure, created_at, updated_at) VALUES (?, 'GADGET', 'Gadget', 'Goods', 'PCS', ?, ?)", &[&product_id, &stamp, &stamp]).await;
+    // 6 of the 10 on hand are reserved for the approved order below.
+    insert(&pool, "INSERT INTO stock_levels (id, product_id, location_id, quantity, reserved_quantity, available_quantity) VALUES (?, ?, ?, 10, 6, 4)", &[&Uuid::new_v4().to_string(), &product_id, &Uuid::new_v4().to_string()]).await;
+
+    let required = (now + Duration::days(5)).to_rfc3339();
+    for (number, status, quantity) in [("SO-1", "Approved", "6"), ("SO-2", "Pending", "8")] {
+        let order = Uuid::new_v4().to_string();
+        insert(&pool, "INSERT INTO sales_orders (id, order_number, customer_id, order_date, required_date, status, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)", &[&order, number, &Uuid::new_v4().to_string(), &stamp, &required, status, &stamp, &stamp]).await;
+        insert(&pool, "INSERT INTO sales_order_lines (id, sales_order_id, product_id, description, quantity, unit_price, line_total) VALUES (?, ?, ?, 'Gadget', ?, 100, 100)", &[&Uuid::new_v4().to_string(), &order, &product_id, quantity]).await;
+    }
+
+    let run = MRPService::run_mrp(&pool, 30).await.unwrap();
+    let planned = MRPService::get_planned_orders(&pool, run.base.id).await.unwrap();
+    assert_eq!(planned.iter().map(|o| o.quantity).sum::<i64>(), 4);
+}
@@ -0,0 +1,353 @@
+//! The entities files may be imported into and the fields a column can be mapped to. Statements
+//! only ever name tables and columns from this catalogue.
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ImportEntity {
+    pub name: &'static str,
+    pub label: &'static str,
+    /// Permission needed to import the entity.
+    pub permission: &'static str,
+    pub fields: &'static [ImportField],
+    #[serde(skip)]
+    pub target: Target,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportField {
+    pub name: &'static str,
+    pub label: &'static str,
+    #[serde(flatten)]
+    pub field_type: FieldType,
+    /// Must have a value when a record is created.
+    pub required: bool,
+    /// Part of the natural key records are matched by.
+    pub key: bool,
+    /// Belongs to a document line rather than its header.
+    pub line: bool,
+    /// Used when a new record has no value.
+    pub default: Option<&'static str>,
+    #[serde(skip)]
+    pub column: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FieldType {
+    Text,
+    Email,
+    Integer,
+    /// Money in major units, stored in cents.
+    Amount,
+    Decimal,
+    /// Stored as `YYYY-MM-DD`.
+    Date,
+    /// A date or date and time, stored as RFC 3339 in UTC.
+    DateTime,
+    Choice { options: &'static [&'static str] },
+    /// The id of the row of `table` whose `key` column holds the value.
+    Reference { table: &'static str, key: &'static str },
+}
+
+#[derive(Debug)]
+pub enum Target {
+    /// One row of `table` per file row.
+    Record { table: &'static str, tracks_user: bool },
+    /// A header row of `table` with lines in `lines`, linked by `parent`. File rows with the same
+    /// key make one document, one line per row.
+    Document {
+        table: &'static str,
+        lines: &'static str,
+        parent: &'static str,
+        kind: DocumentKind,
+        tracks_user: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocumentKind {
+    Journal,
+    SalesOrder,
+    PurchaseOrder,
+    Bom,
+    PriceList,
+}
+
+impl ImportEntity {
+    pub fn field(&self, name: &str) -> Option<&'static ImportField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    pub fn table(&self) -> &'static str {
+        match self.target {
+            Target::Record { table, .. } | Target::Document { table, .. } => table,
+        }
+    }
+
+    pub fn is_document(&self) -> bool {
+        matches!(self.target, Target::Document { .. })
+    }
+}
+
+pub fn entity(name: &str) -> Option<&'static ImportEntity> {
+    ENTITIES.iter().find(|e| e.name == name)
+}
+
+const fn field(name: &'static str, label: &'static str, field_type: FieldType) -> ImportField {
+    ImportField { name, label, field_type, required: false, key: false, line: false, default: None, column: name }
+}
+
+impl ImportField {
+    const fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    const fn key(mut self) -> Self {
+        self.key = true;
+        self.required = true;
+        self
+    }
+
+    const fn line(mut self) -> Self {
+        self.line = true;
+        self
+    }
+
+    const fn default(mut self, value: &'static str) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    const fn column(mut self, column: &'static str) -> Self {
+        self.column = column;
+        self
+    }
+}
+
+const fn reference(name: &'static str, label: &'static str, table: &'static str, key: &'static str, column: &'static str) -> ImportField {
+    field(name, label, FieldType::Reference { table, key }).column(column)
+}
+
+use FieldType::{Amount, Date, DateTime, Decimal, Email, Integer, Text};
+
+const ACTIVE: FieldType = FieldType::Choice { options: &["Active", "Inactive"] };
+const OPEN: FieldType = FieldType::Choice { options: &["Draft", "Pending"] };
+
+pub static ENTITIES: [ImportEntity; 10] = [
+    ImportEntity {
+        name: "products",
+        label: "Products",
+        permission: "inventory:products:write",
+        fields: &[
+            field("sku", "SKU", Text).key(),
+            field("name", "Name", Text).required(),
+            field("description", "Description", Text),
+            field("product_type", "Product Type", FieldType::Choice { options: &["Goods", "Service", "Digital"] }).default("Goods"),
+            field("unit_of_measure", "Unit of Measure", Text).default("EA"),
+            field("status", "Status", ACTIVE).default("Active"),
+        ],
+        target: Target::Record { table: "products", tracks_user: true },
+    },
+    ImportEntity {
+        name: "customers",
+        label: "Customers",
+        permission: "sales:customers:write",
+        fields: &[
+            field("code", "Code", Text).key(),
+            field("name", "Name", Text).required(),
+            field("email", "Email", Email),
+            field("phone", "Phone", Text),
+            field("website", "Website", Text),
+            field("billing_street", "Billing Street", Text),
+            field("billing_city", "Billing City", Text),
+            field("billing_state", "Billing State", Text),
+            field("billing_postal_code", "Billing Postal Code", Text),
+            field("billing_country", "Billing Country", Text),
+            field("shipping_street", "Shipping Street", Text),
+            field("shipping_city", "Shipping City", Text),
+            field("shipping_state", "Shipping State", Text),
+            field("shipping_postal_code", "Shipping Postal Code", Text),
+            field("shipping_country", "Shipping Country", Text),
+            field("credit_limit", "Credit Limit", Amount),
+            field("payment_terms", "Payment Terms (Days)", Integer).default("30"),
+            field("status", "Status", ACTIVE).default("Active"),
+        ],
+        target: Target::Record { table: "customers", tracks_user: true },
+    },
+    ImportEntity {
+        name: "vendors",
+        label: "Vendors",
+        permission: "purchasing:vendors:write",
+        fields: &[
+            field("code", "Code", Text).key(),
+            field("name", "Name", Text).required(),
+            field("email", "Email", Email),
+            field("phone", "Phone", Text),
+            field("website", "Website", Text),
+            field("street", "Street", Text),
+            field("city", "City", Text),
+            field("state", "State", Text),
+            field("postal_code", "Postal Code", Text),
+            field("country", "Country", Text),
+            field("payment_terms", "Payment Terms (Days)", Integer).default("30"),
+            field("status", "Status", ACTIVE).default("Active"),
+        ],
+        target: Target::Record { table: "vendors", tracks_user: true },
+    },
+    ImportEntity {
+        name: "accounts",
+        label: "Chart of Accounts",
+        permission: "finance:accounts:write",
+        fields: &[
+            field("code", "Code", Text).key(),
+            field("name", "Name", Text).required(),
+            field("account_type", "Account Type", FieldType::Choice {
+                options: &["Asset", "Liability", "Equity", "Revenue", "Expense"],
+            }).required(),
+            reference("parent_code", "Parent Account Code", "accounts", "code", "parent_id"),
+            field("description", "Description", Text),
+            field("status", "Status", ACTIVE).default("Active"),
+        ],
+        target: Target::Record { table: "accounts", tracks_user: true },
+    },
+    ImportEntity {
+        name: "employees",
+        label: "Employees",
+        permission: "hr:employees:write",
+        fields: &[
+            field("employee_number", "Employee Number", Text).key(),
+            field("first_name", "First Name", Text).required(),
+            field("last_name", "Last Name", Text).required(),
+            field("email", "Email", Email).required(),
+            field("phone", "Phone", Text),
+            field("street", "Street", Text),
+            field("city", "City", Text),
+            field("state", "State", Text),
+            field("postal_code", "Postal Code", Text),
+            field("country", "Country", Text),
+            field("birth_date", "Birth Date", Date).required(),
+            field("hire_date", "Hire Date", Date).required(),
+            reference("department_code", "Department Code", "departments", "code", "department_id"),
+            reference("manager_number", "Manager Employee Number", "employees", "employee_number", "manager_id"),
+            field("status", "Status", ACTIVE).default("Active"),
+        ],
+        target: Target::Record { table: "employees", tracks_user: true },
+    },
+    ImportEntity {
+        name: "opening_balances",
+        label: "Opening Balances",
+        permission: "finance:journals:write",
+        fields: &[
+            field("entry_number", "Entry Number", Text).key(),
+            field("date", "Date", DateTime).required(),
+            field("description", "Description", Text).default("Opening balances"),
+            field("reference", "Reference", Text),
+            reference("account_code", "Account Code", "accounts", "code", "account_id").required().line(),
+            field("debit", "Debit", Amount).line().default("0"),
+            field("credit", "Credit", Amount).line().default("0"),
+            field("line_description", "Line Description", Text).line().column("description"),
+        ],
+        target: Target::Document {
+            table: "journal_entries",
+            lines: "journal_lines",
+            parent: "journal_entry_id",
+            kind: DocumentKind::Journal,
+            tracks_user: true,
+        },
+    },
+    ImportEntity {
+        name: "sales_orders",
+        label: "Open Sales Orders",
+        permission: "sales:orders:write",
+        fields: &[
+            field("order_number", "Order Number", Text).key(),
+            reference("customer_code", "Customer Code", "customers", "code", "customer_id").required(),
+            field("order_date", "Order Date", DateTime).required(),
+            field("required_date", "Required Date", DateTime),
+            field("status", "Status", OPEN).default("Draft"),
+            reference("product_sku", "Product SKU", "products", "sku", "product_id").required().line(),
+            field("description", "Line Description", Text).line(),
+            field("quantity", "Quantity", Integer).required().line(),
+            field("unit_price", "Unit Price", Amount).required().line(),
+            field("discount_percent", "Discount %", Decimal).line().default("0"),
+            field("tax_rate", "Tax Rate %", Decimal).line().default("0"),
+        ],
+        target: Target::Document {
+            table: "sales_orders",
+            lines: "sales_order_lines",
+            parent: "sales_order_id",
+            kind: DocumentKind::SalesOrder,
+            tracks_user: true,
+        },
+    },
+    ImportEntity {
+        name: "purchase_orders",
+        label: "Open Purchase Orders",
+        permission: "purchasing:orders:write",
+        fields: &[
+            field("po_number", "PO Number", Text).key(),
+            reference("vendor_code", "Vendor Code", "vendors", "code", "vendor_id").required(),
+            field("order_date", "Order Date", DateTime).required(),
+            field("expected_date", "Expected Date", DateTime),
+            field("currency", "Currency", Text).default("USD"),
+            field("status", "Status", OPEN).default("Draft"),
+            reference("product_sku", "Product SKU", "products", "sku", "product_id").required().line(),
+            field("description", "Line Description", Text).line(),
+            field("quantity", "Quantity", Integer).required().line(),
+            field("unit_price", "Unit Price", Amount).required().line(),
+            field("tax_rate", "Tax Rate %", Decimal).line().default("0"),
+        ],
+        target: Target::Document {
+            table: "purchase_orders",
+            lines: "purchase_order_lines",
+            parent: "purchase_order_id",
+            kind: DocumentKind::PurchaseOrder,
+            tracks_user: true,
+        },
+    },
+    ImportEntity {
+        name: "boms",
+        label: "Bills of Material",
+        permission: "manufacturing:boms:write",
+        fields: &[
+            reference("product_sku", "Product SKU", "products", "sku", "product_id").key(),
+            field("version", "Version", Text).key(),
+            field("name", "Name", Text),
+            field("quantity", "Quantity", Integer).default("1"),
+            field("status", "Status", FieldType::Choice { options: &["Draft", "Active"] }).default("Draft"),
+            reference("component_sku", "Component SKU", "products", "sku", "product_id").required().line(),
+            field("component_quantity", "Component Quantity", Integer).required().line().column("quantity"),
+            field("unit", "Unit", Text).line(),
+            field("scrap_percent", "Scrap %", Decimal).line().default("0"),
+        ],
+        target: Target::Document {
+            table: "bills_of_material",
+            lines: "bom_components",
+            parent: "bom_id",
+            kind: DocumentKind::Bom,
+            tracks_user: false,
+        },
+    },
+    ImportEntity {
+        name: "price_lists",
+        label: "Price Lists",
+        permission: "inventory:products:write",
+        fields: &[
+            field("name", "Price List", Text).key(),
+            field("currency", "Currency", Text).default("USD"),
+            field("status", "Status", ACTIVE).default("Active"),
+            reference("product_sku", "Product SKU", "products", "sku", "product_id").required().line(),
+            field("price", "Price", Amount).required().line(),
+            field("min_quantity", "Minimum Quantity", Integer).line().default("0"),
+        ],
+        target: Target::Document {
+            table: "price_lists",
+            lines: "price_list_items",
+            parent: "price_list_id",
+            kind: DocumentKind::PriceList,
+            tracks_user: false,
+        },
+    },
+];
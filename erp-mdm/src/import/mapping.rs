@@ -0,0 +1,157 @@
+//! Matches file columns to entity fields and applies each column's transforms.
+
+use super::catalogue::{ImportEntity, ImportField};
+use super::source::SheetRow;
+use super::writer::{self, MappedRow, Problem};
+use crate::models::{ColumnMapping, Transform};
+use chrono::NaiveDate;
+use erp_core::{Error, Result};
+use std::collections::HashSet;
+
+/// Checks that every mapping targets a field of the entity, once.
+pub(crate) fn validate_targets(entity: &ImportEntity, columns: &[ColumnMapping]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for column in columns {
+        if entity.field(&column.target).is_none() {
+            return Err(Error::validation(format!("{} has no field {}", entity.label, column.target)));
+        }
+        if !seen.insert(column.target.as_str()) {
+            return Err(Error::validation(format!("Field {} is mapped more than once", column.target)));
+        }
+        if column.source.is_none() && column.default.is_none() {
+            return Err(Error::validation(format!("Field {} needs a source column or a default", column.target)));
+        }
+        for transform in &column.transforms {
+            if let Transform::DateFormat { format } = transform {
+                if chrono::format::StrftimeItems::new(format).any(|item| matches!(item, chrono::format::Item::Error)) {
+                    return Err(Error::validation(format!("{} is not a valid date format", format)));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Maps each header to the field with the same name or label, ignoring case and punctuation.
+pub(crate) fn auto_map(entity: &ImportEntity, headers: &[String]) -> Vec<ColumnMapping> {
+    let mut columns: Vec<ColumnMapping> = Vec::new();
+    for header in headers {
+        let normalized = normalize(header);
+        let field = entity
+            .fields
+            .iter()
+            .find(|f| normalize(f.name) == normalized || normalize(f.label) == normalized);
+        if let Some(field) = field {
+            if !columns.iter().any(|c| c.target == field.name) {
+                columns.push(ColumnMapping {
+                    source: Some(header.clone()),
+                    target: field.name.to_string(),
+                    transforms: Vec::new(),
+                    values: Default::default(),
+                    default: None,
+                });
+            }
+        }
+    }
+    columns
+}
+
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// A field with the index of its file column.
+pub(crate) struct Column {
+    pub field: &'static ImportField,
+    pub source: Option<usize>,
+    pub mapping: ColumnMapping,
+}
+
+/// Resolves mappings against a file's headers. Every key field must be mapped, and so must
+/// required fields without a default.
+pub(crate) fn resolve(entity: &'static ImportEntity, headers: &[String], columns: &[ColumnMapping]) -> Result<Vec<Column>> {
+    validate_targets(entity, columns)?;
+    let mut resolved = Vec::new();
+    for mapping in columns {
+        let source = match &mapping.source {
+            Some(source) => Some(
+                headers
+                    .iter()
+                    .position(|h| h.eq_ignore_ascii_case(source.trim()))
+                    .ok_or_else(|| Error::validation(format!("The file has no column {}", source)))?,
+            ),
+            None => None,
+        };
+        let field = entity.field(&mapping.target).expect("validated target");
+        resolved.push(Column { field, source, mapping: mapping.clone() });
+    }
+    let unmapped: Vec<&str> = entity
+        .fields
+        .iter()
+        .filter(|f| f.required && f.default.is_none() && !resolved.iter().any(|c| c.field.name == f.name))
+        .map(|f| f.label)
+        .collect();
+    if !unmapped.is_empty() {
+        return Err(Error::validation(format!("No column is mapped to {}", unmapped.join(", "))));
+    }
+    Ok(resolved)
+}
+
+/// A row's text for each mapped field, or the problems found while transforming it.
+pub(crate) fn map_row(columns: &[Column], row: &SheetRow, width: usize) -> std::result::Result<MappedRow, Vec<Problem>> {
+    let mut problems = Vec::new();
+    if let Some(extra) = row.cells.iter().skip(width).position(|c| !c.trim().is_empty()) {
+        problems.push(Problem {
+            row: row.number,
+            field: None,
+            message: format!("Has a value in column {}, past the last header", width + extra + 1),
+        });
+    }
+    let mut values = Vec::with_capacity(columns.len());
+    for column in columns {
+        let cell = column.source.map(|i| row.cell(i)).unwrap_or("");
+        match transform(&column.mapping, cell) {
+            Ok(text) => values.push((column.field, text)),
+            Err(message) => problems.push(Problem { row: row.number, field: Some(column.field.name), message }),
+        }
+    }
+    if problems.is_empty() {
+        Ok(MappedRow { number: row.number, values })
+    } else {
+        Err(problems)
+    }
+}
+
+/// Trims the cell, applies the transforms in order, then replaces the whole value from `values`.
+/// An empty result falls back to the mapping's default.
+pub(crate) fn transform(mapping: &ColumnMapping, cell: &str) -> std::result::Result<Option<String>, String> {
+    let mut text = cell.trim().to_string();
+    if !text.is_empty() {
+        for transform in &mapping.transforms {
+            text = match transform {
+                Transform::Trim => text.trim().to_string(),
+                Transform::Uppercase => text.to_uppercase(),
+                Transform::Lowercase => text.to_lowercase(),
+                Transform::Replace { from, to } if !from.is_empty() => text.replace(from.as_str(), to),
+                Transform::Replace { .. } => text,
+                Transform::Prefix { value } => format!("{}{}", value, text),
+                Transform::Suffix { value } => format!("{}{}", text, value),
+                Transform::DateFormat { format } => NaiveDate::parse_from_str(&text, format)
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .map_err(|_| format!("{} does not match the date format {}", text, format))?,
+                Transform::Multiply { factor } => {
+                    let number = writer::number(&text).ok_or_else(|| format!("{} is not a number", text))?;
+                    let product = ((number * factor) * 1e9).round() / 1e9;
+                    product.to_string()
+                }
+            };
+        }
+        if let Some((_, replacement)) = mapping.values.iter().find(|(from, _)| from.trim().eq_ignore_ascii_case(&text)) {
+            text = replacement.clone();
+        }
+    }
+    if text.is_empty() {
+        return Ok(mapping.default.clone().filter(|d| !d.is_empty()));
+    }
+    Ok(Some(text))
+}
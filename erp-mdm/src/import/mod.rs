@@ -0,0 +1,474 @@
+//! Bulk import of CSV and XLSX files into the entities of the catalogue, tracked as MDM import
+//! jobs. Rows are saved in chunks, each in one transaction, and every record in a savepoint of
+//! its own so a bad row only loses itself.
+
+pub mod catalogue;
+mod mapping;
+pub mod source;
+mod writer;
+
+pub use catalogue::{FieldType, ImportEntity, ImportField, ENTITIES};
+
+use crate::models::*;
+use crate::repository::{MDMRepository, SqliteMDMRepository};
+use crate::service::MDMService;
+use anyhow::Result;
+use chrono::Utc;
+use erp_core::blob::{StagedBlob, UploadPolicy};
+use erp_core::{BlobService, Error};
+use futures::TryStreamExt;
+use mapping::Column;
+use source::{Sheet, SheetRow};
+use sqlx::{Acquire, SqlitePool};
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Range;
+use uuid::Uuid;
+use writer::{Failure, Outcome, Problem, Writer};
+
+/// Rows saved in one transaction. Files with more are left to the background worker.
+pub const CHUNK_ROWS: usize = 500;
+
+/// Largest file that may be imported.
+pub const MAX_FILE_SIZE: u64 = 200 * 1024 * 1024;
+
+pub struct ImportService;
+
+impl Default for ImportService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Default)]
+struct Progress {
+    processed: i32,
+    success: i32,
+    failed: i32,
+    duplicates: i32,
+    created: i32,
+    updated: i32,
+    errors: Vec<(Problem, Vec<String>)>,
+}
+
+fn repo(pool: &SqlitePool) -> SqliteMDMRepository {
+    SqliteMDMRepository::new(pool.clone())
+}
+
+impl ImportService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn entities(&self) -> &'static [ImportEntity] {
+        &ENTITIES
+    }
+
+    pub fn entity(&self, name: &str) -> Result<&'static ImportEntity> {
+        Ok(catalogue::entity(name).ok_or_else(|| Error::validation(format!("Unknown import entity {}", name)))?)
+    }
+
+    pub async fn create_mapping(&self, pool: &SqlitePool, request: CreateImportMappingRequest, created_by: Option<Uuid>) -> Result<ImportMapping> {
+        let entity = self.entity(&request.entity_type)?;
+        if request.name.trim().is_empty() {
+            return Err(Error::validation("Mapping name is required").into());
+        }
+        mapping::validate_targets(entity, &request.columns)?;
+        let now = Utc::now();
+        let mapping = ImportMapping {
+            id: Uuid::new_v4(),
+            name: request.name.trim().to_string(),
+            entity_type: entity.name.to_string(),
+            columns: request.columns,
+            created_by,
+            created_at: now,
+            updated_at: now,
+        };
+        repo(pool).create_import_mapping(&mapping).await?;
+        Ok(mapping)
+    }
+
+    pub async fn list_mappings(&self, pool: &SqlitePool, entity_type: Option<&str>) -> Result<Vec<ImportMapping>> {
+        repo(pool).list_import_mappings(entity_type).await
+    }
+
+    pub async fn delete_mapping(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
+        if !repo(pool).delete_import_mapping(id).await? {
+            return Err(Error::not_found("Import mapping", &id.to_string()).into());
+        }
+        Ok(())
+    }
+
+    /// Reads the file's header, settles the column mapping, stores the file and creates the job.
+    /// Files of up to [`CHUNK_ROWS`] rows are imported at once. Larger ones are left `Queued` for
+    /// [`process_queued`](Self::process_queued).
+    pub async fn create_import(&self, pool: &SqlitePool, blobs: &BlobService, file: StagedBlob, request: CreateImportRequest) -> Result<DataImportJob> {
+        let content = tokio::fs::read(&file.path).await?;
+        let entity = self.entity(&request.entity_type)?;
+        let format = request.format.unwrap_or_else(|| source::format_of(&request.file_name));
+        let sheet = source::read(&content, format, request.sheet.as_deref())?;
+        let columns = match (request.columns, request.mapping_id) {
+            (Some(columns), _) => columns,
+            (None, Some(mapping_id)) => {
+                let saved = repo(pool)
+                    .get_import_mapping(mapping_id)
+                    .await?
+                    .ok_or_else(|| Error::not_found("Import mapping", &mapping_id.to_string()))?;
+                if saved.entity_type != entity.name {
+                    return Err(Error::validation(format!("Mapping {} is for {}, not {}", saved.name, saved.entity_type, entity.name)).into());
+                }
+                saved.columns
+            }
+            (None, None) => mapping::auto_map(entity, &sheet.headers),
+        };
+        mapping::resolve(entity, &sheet.headers, &columns)?;
+        let policy = UploadPolicy { max_size: MAX_FILE_SIZE, allowed_mime_types: Vec::new() };
+        let blob = blobs.save(pool, file, "application/octet-stream", &policy, request.expected_sha256.as_deref()).await?;
+
+        let mdm = MDMService::new(repo(pool));
+        let mut job = mdm
+            .create_import_job(format!("{} from {}", entity.label, request.file_name), entity.name.to_string(), request.file_name)
+            .await?;
+        job.total_records = sheet.rows.len() as i32;
+        job.dry_run = request.dry_run;
+        job.file_checksum = Some(blob.sha256);
+        job.created_by = request.created_by;
+        job.options = Some(ImportOptions {
+            format,
+            sheet: request.sheet,
+            headers: sheet.headers,
+            columns,
+            mapping_id: request.mapping_id,
+            upsert: request.upsert,
+        });
+        if sheet.rows.len() > CHUNK_ROWS {
+            job.status = "Queued".to_string();
+        }
+        job.updated_at = Utc::now();
+        repo(pool).update_import_job(&job).await?;
+        if job.status == "Queued" {
+            return Ok(job);
+        }
+        self.run(pool, &content, job.id, usize::MAX).await
+    }
+
+    pub async fn get_import(&self, pool: &SqlitePool, id: Uuid) -> Result<DataImportJob> {
+        Ok(repo(pool).get_import_job(id).await?.ok_or_else(|| Error::not_found("Import job", &id.to_string()))?)
+    }
+
+    pub async fn list_imports(&self, pool: &SqlitePool, entity_type: Option<&str>, limit: i32, offset: i32) -> Result<Vec<DataImportJob>> {
+        repo(pool).list_import_jobs(entity_type, limit, offset).await
+    }
+
+    /// Works through up to `max_chunks` chunks of each queued job, oldest first.
+    pub async fn process_queued(&self, pool: &SqlitePool, blobs: &BlobService, max_chunks: usize) -> Result<Vec<DataImportJob>> {
+        let ids: Vec<String> = sqlx::query_scalar(
+            "SELECT id FROM mdm_import_jobs WHERE status IN ('Queued', 'Running') AND file_checksum IS NOT NULL
+             ORDER BY created_at",
+        )
+        .fetch_all(pool)
+        .await?;
+        let mut jobs = Vec::with_capacity(ids.len());
+        for id in ids {
+            let id = erp_core::parse_uuid(&id, "id")?;
+            match self.process(pool, blobs, id, max_chunks).await {
+                Ok(job) => jobs.push(job),
+                // One broken job must not hold up the others behind it.
+                Err(e) => jobs.push(MDMService::new(repo(pool)).fail_import_job(id, e.to_string()).await?),
+            }
+        }
+        Ok(jobs)
+    }
+
+    pub async fn errors(&self, pool: &SqlitePool, job_id: Uuid, limit: i32, offset: i32) -> Result<Vec<ImportRowError>> {
+        self.get_import(pool, job_id).await?;
+        repo(pool).list_import_errors(job_id, limit, offset).await
+    }
+
+    /// The rows that were not imported as CSV: the row number, the row as it was in the file and
+    /// what is wrong with it.
+    pub async fn error_file(&self, pool: &SqlitePool, job_id: Uuid) -> Result<String> {
+        let job = self.get_import(pool, job_id).await?;
+        let entity = catalogue::entity(&job.entity_type);
+        let headers = job.options.map(|o| o.headers).unwrap_or_default();
+        let mut out = String::new();
+        let header: Vec<&str> = std::iter::once("Row").chain(headers.iter().map(String::as_str)).chain(std::iter::once("Errors")).collect();
+        push_csv_line(&mut out, &header);
+
+        let mut rows: BTreeMap<i64, (Vec<String>, Vec<String>)> = BTreeMap::new();
+        let mut offset = 0;
+        loop {
+            let page = repo(pool).list_import_errors(job_id, 1000, offset).await?;
+            if page.is_empty() {
+                break;
+            }
+            offset += page.len() as i32;
+            for error in page {
+                let label = error.field.as_deref().map(|f| entity.and_then(|e| e.field(f)).map(|f| f.label).unwrap_or(f));
+                let message = match label {
+                    Some(label) => format!("{}: {}", label, error.message),
+                    None => error.message,
+                };
+                let entry = rows.entry(error.row_number).or_insert_with(|| (error.values, Vec::new()));
+                entry.1.push(message);
+            }
+        }
+        for (number, (mut values, messages)) in rows {
+            values.resize(values.len().max(headers.len()), String::new());
+            let mut line = vec![number.to_string()];
+            line.extend(values);
+            line.push(messages.join("; "));
+            push_csv_line(&mut out, &line);
+        }
+        Ok(out)
+    }
+
+    /// Saves up to `max_chunks` more chunks of the job's rows from its stored file.
+    pub async fn process(&self, pool: &SqlitePool, blobs: &BlobService, job_id: Uuid, max_chunks: usize) -> Result<DataImportJob> {
+        let job = self.get_import(pool, job_id).await?;
+        let Some(checksum) = job.file_checksum.as_deref().filter(|_| !matches!(job.status.as_str(), "Completed" | "Failed")) else {
+            return Ok(job);
+        };
+        let (_, mut stream) = blobs.open(pool, checksum).await?;
+        let mut content = Vec::new();
+        while let Some(chunk) = stream.try_next().await? {
+            content.extend_from_slice(&chunk);
+        }
+        self.run(pool, &content, job_id, max_chunks).await
+    }
+
+    /// Saves chunks of rows and completes the job once all are done. A dry run goes through the
+    /// same checks and counts but keeps nothing but the job's progress and errors.
+    async fn run(&self, pool: &SqlitePool, content: &[u8], job_id: Uuid, max_chunks: usize) -> Result<DataImportJob> {
+        let job = self.get_import(pool, job_id).await?;
+        let mdm = MDMService::new(repo(pool));
+        let Some(options) = job.options.clone() else {
+            return mdm.fail_import_job(job_id, "The job has no import options".to_string()).await;
+        };
+        let prepared = catalogue::entity(&job.entity_type)
+            .ok_or_else(|| Error::validation(format!("Unknown import entity {}", job.entity_type)))
+            .and_then(|entity| Ok((entity, source::read(content, options.format, options.sheet.as_deref())?)))
+            .and_then(|(entity, sheet)| Ok((entity, mapping::resolve(entity, &options.headers, &options.columns)?, sheet)));
+        let (entity, columns, sheet) = match prepared {
+            Ok(prepared) => prepared,
+            Err(Error::Validation(message)) => return mdm.fail_import_job(job_id, message).await,
+            Err(e) => return Err(e.into()),
+        };
+        if sheet.rows.len() as i32 != job.total_records {
+            return mdm.fail_import_job(job_id, "The stored file no longer matches the job".to_string()).await;
+        }
+
+        let keys = record_keys(&columns, &sheet);
+        let (order, groups) = plan(entity, &keys);
+        let mut job = if job.status == "Running" { job } else { mdm.start_import_job(job_id).await? };
+        let mut chunks = 0;
+        while (job.processed_records as usize) < order.len() && chunks < max_chunks {
+            let first = groups.partition_point(|g| g.start < job.processed_records as usize);
+            let mut end = first;
+            while end < groups.len() && (end == first || groups[end].end - groups[first].start <= CHUNK_ROWS) {
+                end += 1;
+            }
+            // A dry run keeps nothing, so records from earlier chunks are told apart by their keys.
+            let earlier: HashSet<&str> = if job.dry_run {
+                order[..groups.get(first).map_or(order.len(), |g| g.start)].iter().filter_map(|&i| keys[i].as_deref()).collect()
+            } else {
+                HashSet::new()
+            };
+            let progress = self.process_chunk(pool, &job, entity, &columns, &sheet, &order, &groups[first..end], &keys, &earlier).await?;
+            if !record_progress(pool, &job, progress).await? {
+                // Another worker got to this chunk first.
+                return self.get_import(pool, job_id).await;
+            }
+            job = self.get_import(pool, job_id).await?;
+            chunks += 1;
+        }
+        if job.processed_records as usize >= order.len() {
+            job = mdm.complete_import_job(job_id, job.success_records, job.failed_records, job.duplicate_records).await?;
+        }
+        Ok(job)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn process_chunk(
+        &self,
+        pool: &SqlitePool,
+        job: &DataImportJob,
+        entity: &'static ImportEntity,
+        columns: &[Column],
+        sheet: &Sheet,
+        order: &[usize],
+        groups: &[Range<usize>],
+        keys: &[Option<String>],
+        earlier: &HashSet<&str>,
+    ) -> Result<(Progress, sqlx::Transaction<'static, sqlx::Sqlite>)> {
+        let upsert = job.options.as_ref().is_some_and(|o| o.upsert);
+        let mut writer = Writer::new(entity, upsert, job.created_by);
+        let mut progress = Progress::default();
+        let mut tx = pool.begin().await?;
+        for group in groups {
+            let rows: Vec<&SheetRow> = order[group.clone()].iter().map(|&i| &sheet.rows[i]).collect();
+            progress.processed += rows.len() as i32;
+            let mut mapped = Vec::with_capacity(rows.len());
+            let mut problems = Vec::new();
+            for row in &rows {
+                match mapping::map_row(columns, row, sheet.headers.len()) {
+                    Ok(row) => mapped.push(row),
+                    Err(row_problems) => problems.extend(row_problems),
+                }
+            }
+            let outcome = if problems.is_empty() {
+                let mut savepoint = tx.begin().await?;
+                match writer.write(&mut savepoint, &mapped).await {
+                    Ok(outcome) => {
+                        savepoint.commit().await?;
+                        Ok(outcome)
+                    }
+                    Err(failure) => {
+                        savepoint.rollback().await?;
+                        Err(failure)
+                    }
+                }
+            } else {
+                Err(Failure::Invalid(problems))
+            };
+            let count = rows.len() as i32;
+            let outcome = match outcome {
+                Ok(Outcome::Created) if keys[order[group.start]].as_deref().is_some_and(|k| earlier.contains(k)) => {
+                    Ok(if upsert { Outcome::Updated } else { Outcome::Duplicate })
+                }
+                outcome => outcome,
+            };
+            match outcome {
+                Ok(Outcome::Created) => {
+                    progress.success += count;
+                    progress.created += count;
+                }
+                Ok(Outcome::Updated) => {
+                    progress.success += count;
+                    progress.updated += count;
+                }
+                Ok(Outcome::Duplicate) => progress.duplicates += count,
+                Err(Failure::Fatal(e)) => return Err(e.into()),
+                Err(Failure::Invalid(problems)) => record_failure(&mut progress, &rows, problems),
+                Err(Failure::Rejected(message)) => {
+                    let problem = Problem { row: rows[0].number, field: None, message };
+                    record_failure(&mut progress, &rows, vec![problem]);
+                }
+            }
+        }
+        Ok((progress, tx))
+    }
+}
+
+/// The natural key of each row, or `None` where a key cell cannot be read.
+fn record_keys(columns: &[Column], sheet: &Sheet) -> Vec<Option<String>> {
+    sheet
+        .rows
+        .iter()
+        .map(|row| {
+            let mut key = String::new();
+            for column in columns.iter().filter(|c| c.field.key) {
+                let cell = column.source.map(|i| row.cell(i)).unwrap_or("");
+                key.push_str(&mapping::transform(&column.mapping, cell).ok()??);
+                key.push('\u{1f}');
+            }
+            Some(key)
+        })
+        .collect()
+}
+
+/// The order rows are saved in, and the ranges of that order making one record each. Rows of a
+/// document are brought together by key, keeping file order otherwise.
+fn plan(entity: &ImportEntity, keys: &[Option<String>]) -> (Vec<usize>, Vec<Range<usize>>) {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    if !entity.is_document() {
+        let groups = (0..order.len()).map(|i| i..i + 1).collect();
+        return (order, groups);
+    }
+    order.sort_by(|&a, &b| keys[a].cmp(&keys[b]));
+    let mut groups: Vec<Range<usize>> = Vec::new();
+    for (position, &row) in order.iter().enumerate() {
+        match groups.last_mut() {
+            Some(group) if keys[row].is_some() && keys[order[group.start]] == keys[row] => group.end = position + 1,
+            _ => groups.push(position..position + 1),
+        }
+    }
+    (order, groups)
+}
+
+/// Counts a record's rows as failed. Rows without problems of their own are blamed on the first
+/// row that has some.
+fn record_failure(progress: &mut Progress, rows: &[&SheetRow], problems: Vec<Problem>) {
+    progress.failed += rows.len() as i32;
+    let first = problems.iter().map(|p| p.row).min().unwrap_or(rows[0].number);
+    for row in rows {
+        if !problems.iter().any(|p| p.row == row.number) {
+            let message = format!("Not imported because row {} has errors", first);
+            progress.errors.push((Problem { row: row.number, field: None, message }, row.cells.clone()));
+        }
+    }
+    for problem in problems {
+        let cells = rows.iter().find(|r| r.number == problem.row).map(|r| r.cells.clone()).unwrap_or_default();
+        progress.errors.push((problem, cells));
+    }
+}
+
+/// Adds a chunk's counts and errors to the job, committing its rows unless the job is a dry run.
+/// False when the job's progress moved on meanwhile, and the chunk was not kept.
+async fn record_progress(pool: &SqlitePool, job: &DataImportJob, (progress, tx): (Progress, sqlx::Transaction<'static, sqlx::Sqlite>)) -> Result<bool> {
+    let mut tx = if job.dry_run {
+        tx.rollback().await?;
+        pool.begin().await?
+    } else {
+        tx
+    };
+    let moved = sqlx::query(
+        "UPDATE mdm_import_jobs SET processed_records = processed_records + ?, success_records = success_records + ?,
+            failed_records = failed_records + ?, duplicate_records = duplicate_records + ?,
+            created_records = created_records + ?, updated_records = updated_records + ?, updated_at = ?
+         WHERE id = ? AND processed_records = ?",
+    )
+    .bind(progress.processed)
+    .bind(progress.success)
+    .bind(progress.failed)
+    .bind(progress.duplicates)
+    .bind(progress.created)
+    .bind(progress.updated)
+    .bind(Utc::now())
+    .bind(job.id.to_string())
+    .bind(job.processed_records)
+    .execute(&mut *tx)
+    .await?;
+    if moved.rows_affected() == 0 {
+        tx.rollback().await?;
+        return Ok(false);
+    }
+    for (problem, cells) in progress.errors {
+        sqlx::query("INSERT INTO mdm_import_errors (id, job_id, row_number, field, message, row_values) VALUES (?, ?, ?, ?, ?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(job.id.to_string())
+            .bind(problem.row)
+            .bind(problem.field)
+            .bind(problem.message)
+            .bind(serde_json::to_string(&cells).map_err(|e| Error::internal(e.to_string()))?)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(true)
+}
+
+fn push_csv_line<S: AsRef<str>>(out: &mut String, cells: &[S]) {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| {
+            let cell = cell.as_ref();
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect();
+    out.push_str(&cells.join(","));
+    out.push_str("\r\n");
+}
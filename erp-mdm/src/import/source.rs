@@ -0,0 +1,527 @@
+//! Reads the header and rows of an uploaded CSV or XLSX file as text cells.
+
+use crate::models::ImportFormat;
+use chrono::{Duration, NaiveDate};
+use erp_core::{Error, Result};
+use flate2::read::DeflateDecoder;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+
+/// Files with more data rows than this are refused.
+pub const MAX_ROWS: usize = 1_000_000;
+
+/// Largest part of a workbook that will be unpacked.
+const MAX_PART_SIZE: u64 = 512 * 1024 * 1024;
+
+#[derive(Debug)]
+pub struct Sheet {
+    pub headers: Vec<String>,
+    pub rows: Vec<SheetRow>,
+}
+
+#[derive(Debug)]
+pub struct SheetRow {
+    /// The row's line in a CSV file or its row in a worksheet, counting the header as 1.
+    pub number: i64,
+    pub cells: Vec<String>,
+}
+
+impl SheetRow {
+    pub fn cell(&self, column: usize) -> &str {
+        self.cells.get(column).map(String::as_str).unwrap_or("")
+    }
+}
+
+/// The format a file name suggests, CSV unless it ends in `.xlsx`.
+pub fn format_of(file_name: &str) -> ImportFormat {
+    if file_name.to_lowercase().ends_with(".xlsx") {
+        ImportFormat::Xlsx
+    } else {
+        ImportFormat::Csv
+    }
+}
+
+pub fn read(content: &[u8], format: ImportFormat, sheet: Option<&str>) -> Result<Sheet> {
+    let rows = match format {
+        ImportFormat::Csv => read_csv(content)?,
+        ImportFormat::Xlsx => read_xlsx(content, sheet)?,
+    };
+    let mut rows = rows.into_iter();
+    let header = rows.next().ok_or_else(|| Error::validation("The file has no header row"))?;
+    let headers = trim_trailing_empty(header.cells.iter().map(|h| h.trim().to_string()).collect());
+    let rows: Vec<SheetRow> = rows.collect();
+    if rows.len() > MAX_ROWS {
+        return Err(Error::validation(format!("The file has {} rows, more than the {} allowed", rows.len(), MAX_ROWS)));
+    }
+    Ok(Sheet { headers, rows })
+}
+
+fn trim_trailing_empty(mut cells: Vec<String>) -> Vec<String> {
+    while cells.last().is_some_and(|c| c.is_empty()) {
+        cells.pop();
+    }
+    cells
+}
+
+/// Rows of a CSV file in UTF-8, or failing that Latin-1. The delimiter is whichever of comma,
+/// semicolon, tab or pipe the header line uses most. Quoted values may hold delimiters, doubled
+/// quotes and line breaks. Blank lines are skipped.
+fn read_csv(content: &[u8]) -> Result<Vec<SheetRow>> {
+    let text = match std::str::from_utf8(content) {
+        Ok(text) => text.to_string(),
+        Err(_) => content.iter().map(|&b| b as char).collect(),
+    };
+    let text = text.strip_prefix('\u{feff}').unwrap_or(&text);
+    let delimiter = detect_delimiter(text);
+
+    let mut rows = Vec::new();
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut line = 1i64;
+    let mut row_start = 1i64;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    cell.push('"');
+                    chars.next();
+                }
+                '"' => quoted = false,
+                '\n' => {
+                    line += 1;
+                    cell.push(c);
+                }
+                _ => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if cell.is_empty() => quoted = true,
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                cells.push(std::mem::take(&mut cell));
+                push_row(&mut rows, row_start, std::mem::take(&mut cells));
+                line += 1;
+                row_start = line;
+            }
+            c if c == delimiter => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    if quoted {
+        return Err(Error::validation(format!("Line {} opens a quoted value that is never closed", row_start)));
+    }
+    if !cell.is_empty() || !cells.is_empty() {
+        cells.push(cell);
+        push_row(&mut rows, row_start, cells);
+    }
+    Ok(rows)
+}
+
+fn push_row(rows: &mut Vec<SheetRow>, number: i64, cells: Vec<String>) {
+    if cells.iter().any(|c| !c.trim().is_empty()) {
+        rows.push(SheetRow { number, cells });
+    }
+}
+
+fn detect_delimiter(text: &str) -> char {
+    let header = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut quoted = false;
+    for c in header.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' | ';' | '\t' | '|' if !quoted => *counts.entry(c).or_default() += 1,
+            _ => {}
+        }
+    }
+    [',', ';', '\t', '|'].into_iter().max_by_key(|c| (counts.get(c).copied().unwrap_or(0), *c == ',')).unwrap_or(',')
+}
+
+/// Rows of one worksheet of an Office Open XML workbook. Shared and inline strings are read as
+/// text, booleans as `true` or `false`, and numbers in a date format as ISO 8601 dates.
+fn read_xlsx(content: &[u8], sheet: Option<&str>) -> Result<Vec<SheetRow>> {
+    let zip = Zip::open(content)?;
+    let workbook = zip.text("xl/workbook.xml")?.ok_or_else(|| Error::validation("The file is not an XLSX workbook"))?;
+    let (sheets, date1904) = workbook_sheets(&workbook)?;
+    let (_, relationship) = match sheet {
+        Some(name) => sheets.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).ok_or_else(|| {
+            let names: Vec<&str> = sheets.iter().map(|(n, _)| n.as_str()).collect();
+            Error::validation(format!("The workbook has no sheet named {}. Its sheets are {}", name, names.join(", ")))
+        })?,
+        None => sheets.first().ok_or_else(|| Error::validation("The workbook has no sheets"))?,
+    };
+    let rels = zip.text("xl/_rels/workbook.xml.rels")?.unwrap_or_default();
+    let target = relationship_target(&rels, relationship)?
+        .ok_or_else(|| Error::validation("The workbook does not say where its sheet is"))?;
+    let path = match target.strip_prefix('/') {
+        Some(absolute) => absolute.to_string(),
+        None => format!("xl/{}", target),
+    };
+    let shared = match zip.text("xl/sharedStrings.xml")? {
+        Some(xml) => shared_strings(&xml)?,
+        None => Vec::new(),
+    };
+    let date_styles = match zip.text("xl/styles.xml")? {
+        Some(xml) => date_styles(&xml)?,
+        None => Vec::new(),
+    };
+    let sheet_xml = zip.text(&path)?.ok_or_else(|| Error::validation(format!("The workbook is missing {}", path)))?;
+    let epoch = if date1904 {
+        NaiveDate::from_ymd_opt(1904, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(1899, 12, 30)
+    }
+    .expect("valid date");
+    worksheet_rows(&sheet_xml, &shared, &date_styles, epoch)
+}
+
+fn invalid(e: quick_xml::Error) -> Error {
+    Error::validation(format!("The workbook is not valid XML: {}", e))
+}
+
+fn attribute(start: &BytesStart, name: &str) -> Result<Option<String>> {
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(|e| Error::validation(format!("The workbook has an invalid attribute: {}", e)))?;
+        if attribute.key.local_name().as_ref() == name.as_bytes() {
+            let value = attribute.unescape_value().map_err(invalid)?;
+            return Ok(Some(value.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// Each sheet's name and relationship id, in workbook order, and whether dates count from 1904.
+fn workbook_sheets(xml: &str) -> Result<(Vec<(String, String)>, bool)> {
+    let mut reader = Reader::from_str(xml);
+    let mut sheets = Vec::new();
+    let mut date1904 = false;
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"sheet" => {
+                    if let (Some(name), Some(id)) = (attribute(&e, "name")?, attribute(&e, "id")?) {
+                        sheets.push((name, id));
+                    }
+                }
+                b"workbookPr" => {
+                    date1904 = matches!(attribute(&e, "date1904")?.as_deref(), Some("1" | "true"));
+                }
+                _ => {}
+            },
+            Event::Eof => return Ok((sheets, date1904)),
+            _ => {}
+        }
+    }
+}
+
+fn relationship_target(xml: &str, id: &str) -> Result<Option<String>> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(e) | Event::Empty(e)
+                if e.local_name().as_ref() == b"Relationship" && attribute(&e, "Id")?.as_deref() == Some(id) =>
+            {
+                return attribute(&e, "Target");
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+fn shared_strings(xml: &str) -> Result<Vec<String>> {
+    let mut reader = Reader::from_str(xml);
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    // Phonetic readings repeat text already in the string.
+    let mut in_phonetic = false;
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"si" => current.clear(),
+                b"t" => in_text = !in_phonetic,
+                b"rPh" => in_phonetic = true,
+                _ => {}
+            },
+            Event::Empty(e) if e.local_name().as_ref() == b"si" => strings.push(String::new()),
+            Event::Text(text) if in_text => current.push_str(&text.unescape().map_err(invalid)?),
+            Event::CData(data) if in_text => current.push_str(&String::from_utf8_lossy(&data)),
+            Event::End(e) => match e.local_name().as_ref() {
+                b"si" => strings.push(std::mem::take(&mut current)),
+                b"t" => in_text = false,
+                b"rPh" => in_phonetic = false,
+                _ => {}
+            },
+            Event::Eof => return Ok(strings),
+            _ => {}
+        }
+    }
+}
+
+/// For each cell format, by position, whether it shows numbers as dates.
+fn date_styles(xml: &str) -> Result<Vec<bool>> {
+    let mut reader = Reader::from_str(xml);
+    let mut custom: HashMap<String, bool> = HashMap::new();
+    let mut styles = Vec::new();
+    let mut in_cell_formats = false;
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"numFmt" => {
+                    if let (Some(id), Some(code)) = (attribute(&e, "numFmtId")?, attribute(&e, "formatCode")?) {
+                        custom.insert(id, is_date_format(&code));
+                    }
+                }
+                b"cellXfs" => in_cell_formats = true,
+                b"xf" if in_cell_formats => {
+                    let id = attribute(&e, "numFmtId")?.unwrap_or_else(|| "0".to_string());
+                    let builtin = id.parse::<u32>().is_ok_and(|n| matches!(n, 14..=22 | 45..=47));
+                    styles.push(builtin || custom.get(&id).copied().unwrap_or(false));
+                }
+                _ => {}
+            },
+            Event::End(e) if e.local_name().as_ref() == b"cellXfs" => in_cell_formats = false,
+            Event::Eof => return Ok(styles),
+            _ => {}
+        }
+    }
+}
+
+/// Whether a number format code shows a date or time: it has a day, month, year, hour or second
+/// outside quoted text and bracketed colours or locales.
+fn is_date_format(code: &str) -> bool {
+    let mut quoted = false;
+    let mut bracketed = false;
+    let mut escaped = false;
+    for c in code.chars() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '"' => quoted = !quoted,
+            '[' if !quoted => bracketed = true,
+            ']' if !quoted => bracketed = false,
+            'd' | 'D' | 'm' | 'M' | 'y' | 'Y' | 'h' | 'H' | 's' | 'S' if !quoted && !bracketed => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+#[derive(Default)]
+struct Cell {
+    column: usize,
+    kind: Option<String>,
+    style: usize,
+    value: String,
+}
+
+fn worksheet_rows(xml: &str, shared: &[String], date_styles: &[bool], epoch: NaiveDate) -> Result<Vec<SheetRow>> {
+    let mut reader = Reader::from_str(xml);
+    let mut rows: BTreeMap<i64, Vec<String>> = BTreeMap::new();
+    let mut row_number = 0i64;
+    let mut next_column = 0usize;
+    let mut cell: Option<Cell> = None;
+    let mut in_value = false;
+    loop {
+        let event = reader.read_event().map_err(invalid)?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) if e.local_name().as_ref() == b"row" => {
+                row_number = match attribute(e, "r")? {
+                    Some(r) => r.parse().map_err(|_| Error::validation(format!("The workbook has an invalid row number {}", r)))?,
+                    None => row_number + 1,
+                };
+                next_column = 0;
+            }
+            Event::Start(ref e) if e.local_name().as_ref() == b"c" => {
+                let column = match attribute(e, "r")? {
+                    Some(reference) => column_index(&reference).unwrap_or(next_column),
+                    None => next_column,
+                };
+                next_column = column + 1;
+                cell = Some(Cell {
+                    column,
+                    kind: attribute(e, "t")?,
+                    style: attribute(e, "s")?.and_then(|s| s.parse().ok()).unwrap_or(0),
+                    value: String::new(),
+                });
+            }
+            Event::Empty(ref e) if e.local_name().as_ref() == b"c" => {
+                next_column = attribute(e, "r")?.and_then(|r| column_index(&r)).unwrap_or(next_column) + 1;
+            }
+            Event::Start(ref e) if matches!(e.local_name().as_ref(), b"v" | b"t") => in_value = cell.is_some(),
+            Event::Text(ref text) if in_value => {
+                if let Some(cell) = cell.as_mut() {
+                    cell.value.push_str(&text.unescape().map_err(invalid)?);
+                }
+            }
+            Event::End(ref e) => match e.local_name().as_ref() {
+                b"v" | b"t" => in_value = false,
+                b"c" => {
+                    if let Some(cell) = cell.take() {
+                        let value = cell_text(&cell, shared, date_styles, epoch);
+                        if !value.is_empty() {
+                            let cells = rows.entry(row_number).or_default();
+                            if cells.len() <= cell.column {
+                                cells.resize(cell.column + 1, String::new());
+                            }
+                            cells[cell.column] = value;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(rows.into_iter()
+        .filter(|(_, cells)| cells.iter().any(|c| !c.trim().is_empty()))
+        .map(|(number, cells)| SheetRow { number, cells })
+        .collect())
+}
+
+fn cell_text(cell: &Cell, shared: &[String], date_styles: &[bool], epoch: NaiveDate) -> String {
+    match cell.kind.as_deref() {
+        Some("s") => cell.value.trim().parse::<usize>().ok().and_then(|i| shared.get(i)).cloned().unwrap_or_default(),
+        Some("b") => if cell.value.trim() == "1" { "true" } else { "false" }.to_string(),
+        Some("str" | "inlineStr" | "e") => cell.value.clone(),
+        _ => {
+            let Ok(number) = cell.value.trim().parse::<f64>() else {
+                return cell.value.clone();
+            };
+            if date_styles.get(cell.style).copied().unwrap_or(false) {
+                if let Some(date) = serial_date(number, epoch) {
+                    return date;
+                }
+            }
+            // Rounded so binary fractions such as 0.30000000000000004 read as written.
+            let rounded = (number * 1e9).round() / 1e9;
+            if rounded.is_finite() { rounded.to_string() } else { cell.value.clone() }
+        }
+    }
+}
+
+fn serial_date(serial: f64, epoch: NaiveDate) -> Option<String> {
+    if !(0.0..2_958_466.0).contains(&serial) {
+        return None;
+    }
+    let days = serial.trunc() as i64;
+    let seconds = ((serial - serial.trunc()) * 86_400.0).round() as i64;
+    let date = epoch.checked_add_signed(Duration::days(days))?;
+    if seconds == 0 {
+        return Some(date.format("%Y-%m-%d").to_string());
+    }
+    let time = date.and_hms_opt(0, 0, 0)? + Duration::seconds(seconds);
+    Some(time.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+/// Zero-based column of a cell reference such as `AB12`.
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: Vec<u8> = reference.bytes().take_while(|b| b.is_ascii_alphabetic()).collect();
+    if letters.is_empty() {
+        return None;
+    }
+    let index = letters.iter().fold(0usize, |acc, b| acc * 26 + (b.to_ascii_uppercase() - b'A' + 1) as usize);
+    Some(index - 1)
+}
+
+struct ZipEntry {
+    name: String,
+    method: u16,
+    compressed_size: usize,
+    offset: usize,
+}
+
+/// Just enough of a ZIP reader for workbooks: the central directory and stored or deflated
+/// entries.
+struct Zip<'a> {
+    data: &'a [u8],
+    entries: Vec<ZipEntry>,
+}
+
+impl<'a> Zip<'a> {
+    fn open(data: &'a [u8]) -> Result<Self> {
+        let not_zip = || Error::validation("The file is not an XLSX workbook");
+        let search_from = data.len().saturating_sub(22 + 65_535);
+        let end = (search_from..data.len().saturating_sub(21))
+            .rev()
+            .find(|&i| data[i..i + 4] == [0x50, 0x4b, 0x05, 0x06])
+            .ok_or_else(not_zip)?;
+        let count = u16_at(data, end + 10).ok_or_else(not_zip)? as usize;
+        let directory = u32_at(data, end + 16).ok_or_else(not_zip)?;
+        if directory == u32::MAX {
+            return Err(Error::validation("ZIP64 workbooks are not supported"));
+        }
+        let mut position = directory as usize;
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            if data.get(position..position + 4) != Some(&[0x50, 0x4b, 0x01, 0x02]) {
+                return Err(not_zip());
+            }
+            let field = |offset: usize| u16_at(data, position + offset).ok_or_else(not_zip);
+            let method = field(10)?;
+            let compressed_size = u32_at(data, position + 20).ok_or_else(not_zip)? as usize;
+            let name_length = field(28)? as usize;
+            let extra_length = field(30)? as usize;
+            let comment_length = field(32)? as usize;
+            let offset = u32_at(data, position + 42).ok_or_else(not_zip)? as usize;
+            let name = data.get(position + 46..position + 46 + name_length).ok_or_else(not_zip)?;
+            entries.push(ZipEntry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                method,
+                compressed_size,
+                offset,
+            });
+            position += 46 + name_length + extra_length + comment_length;
+        }
+        Ok(Self { data, entries })
+    }
+
+    fn read(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        let Some(entry) = self.entries.iter().find(|e| e.name == name) else {
+            return Ok(None);
+        };
+        let corrupt = || Error::validation(format!("The workbook's {} is damaged", name));
+        let header = entry.offset;
+        if self.data.get(header..header + 4) != Some(&[0x50, 0x4b, 0x03, 0x04]) {
+            return Err(corrupt());
+        }
+        let name_length = u16_at(self.data, header + 26).ok_or_else(corrupt)? as usize;
+        let extra_length = u16_at(self.data, header + 28).ok_or_else(corrupt)? as usize;
+        let start = header + 30 + name_length + extra_length;
+        let compressed = self.data.get(start..start + entry.compressed_size).ok_or_else(corrupt)?;
+        match entry.method {
+            0 => Ok(Some(compressed.to_vec())),
+            8 => {
+                let mut content = Vec::new();
+                DeflateDecoder::new(compressed)
+                    .take(MAX_PART_SIZE)
+                    .read_to_end(&mut content)
+                    .map_err(|_| corrupt())?;
+                Ok(Some(content))
+            }
+            method => Err(Error::validation(format!("The workbook uses compression method {}, which is not supported", method))),
+        }
+    }
+
+    fn text(&self, name: &str) -> Result<Option<String>> {
+        Ok(self.read(name)?.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
+
+fn u16_at(data: &[u8], at: usize) -> Option<u16> {
+    data.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn u32_at(data: &[u8], at: usize) -> Option<u32> {
+    data.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
@@ -0,0 +1,519 @@
+//! Turns mapped rows into records: converts values to their field types, resolves references and
+//! inserts or updates by natural key.
+
+use super::catalogue::{DocumentKind, FieldType, ImportEntity, ImportField, Target};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use erp_core::Error;
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Row, Sqlite, SqliteConnection};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const MAX_TEXT_LENGTH: usize = 1000;
+
+/// One file row with each mapped field's text after transforms, `None` when empty.
+pub(crate) struct MappedRow {
+    pub number: i64,
+    pub values: Vec<(&'static ImportField, Option<String>)>,
+}
+
+impl MappedRow {
+    pub fn text(&self, name: &str) -> Option<&str> {
+        self.values.iter().find(|(f, _)| f.name == name).and_then(|(_, v)| v.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Text(String),
+    Integer(i64),
+    Real(f64),
+}
+
+impl Value {
+    fn integer(&self) -> i64 {
+        match self {
+            Value::Integer(n) => *n,
+            Value::Real(n) => *n as i64,
+            Value::Text(_) => 0,
+        }
+    }
+
+    fn real(&self) -> f64 {
+        match self {
+            Value::Integer(n) => *n as f64,
+            Value::Real(n) => *n,
+            Value::Text(_) => 0.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Problem {
+    pub row: i64,
+    pub field: Option<&'static str>,
+    pub message: String,
+}
+
+pub(crate) enum Failure {
+    /// Rows with values that cannot be saved.
+    Invalid(Vec<Problem>),
+    /// The record as a whole was refused, by a rule or by the database.
+    Rejected(String),
+    Fatal(Error),
+}
+
+impl From<sqlx::Error> for Failure {
+    fn from(e: sqlx::Error) -> Self {
+        match e.as_database_error() {
+            Some(db) => Failure::Rejected(db.message().to_string()),
+            None => Failure::Fatal(Error::Database(e)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Outcome {
+    Created,
+    Updated,
+    Duplicate,
+}
+
+type Query<'q> = sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>>;
+
+fn bind<'q>(query: Query<'q>, value: Option<&Value>) -> Query<'q> {
+    match value {
+        None => query.bind(None::<String>),
+        Some(Value::Text(s)) => query.bind(s.clone()),
+        Some(Value::Integer(n)) => query.bind(*n),
+        Some(Value::Real(n)) => query.bind(*n),
+    }
+}
+
+/// Converts text to a field's type. References are resolved separately.
+pub(crate) fn parse(field: &ImportField, text: &str) -> Result<Value, String> {
+    match &field.field_type {
+        FieldType::Text | FieldType::Reference { .. } => {
+            if text.contains('\0') {
+                Err("Contains a null character".to_string())
+            } else if text.chars().count() > MAX_TEXT_LENGTH {
+                Err(format!("Longer than {} characters", MAX_TEXT_LENGTH))
+            } else {
+                Ok(Value::Text(text.to_string()))
+            }
+        }
+        FieldType::Email => {
+            let valid = text.len() <= 255
+                && !text.contains(char::is_whitespace)
+                && text.split_once('@').is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.'));
+            if valid { Ok(Value::Text(text.to_string())) } else { Err(format!("{} is not an email address", text)) }
+        }
+        FieldType::Integer => {
+            let number = number(text).ok_or_else(|| format!("{} is not a number", text))?;
+            if number.fract() != 0.0 || number.abs() > 1e15 {
+                return Err(format!("{} is not a whole number", text));
+            }
+            Ok(Value::Integer(number as i64))
+        }
+        FieldType::Amount => {
+            let number = number(text).ok_or_else(|| format!("{} is not an amount", text))?;
+            let cents = (number * 100.0).round();
+            if (number * 100.0 - cents).abs() > 1e-6 || cents.abs() > 1e15 {
+                return Err(format!("{} has more than two decimal places", text));
+            }
+            Ok(Value::Integer(cents as i64))
+        }
+        FieldType::Decimal => number(text).map(Value::Real).ok_or_else(|| format!("{} is not a number", text)),
+        FieldType::Date => date(text).map(|d| Value::Text(d.format("%Y-%m-%d").to_string())).ok_or_else(|| not_a_date(text)),
+        FieldType::DateTime => datetime(text).map(|d| Value::Text(d.to_rfc3339())).ok_or_else(|| not_a_date(text)),
+        FieldType::Choice { options } => options
+            .iter()
+            .find(|o| o.eq_ignore_ascii_case(text))
+            .map(|o| Value::Text(o.to_string()))
+            .ok_or_else(|| format!("{} is not one of {}", text, options.join(", "))),
+    }
+}
+
+fn not_a_date(text: &str) -> String {
+    format!("{} is not a YYYY-MM-DD date. Add a date_format transform to read other formats", text)
+}
+
+/// A number, ignoring thousands separators.
+pub(crate) fn number(text: &str) -> Option<f64> {
+    let text: String = text.chars().filter(|c| *c != ',' && !c.is_whitespace()).collect();
+    text.parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+pub(crate) fn date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").ok().or_else(|| datetime(text).map(|d| d.date_naive()))
+}
+
+fn datetime(text: &str) -> Option<DateTime<Utc>> {
+    if let Ok(d) = DateTime::parse_from_rfc3339(text) {
+        return Some(d.with_timezone(&Utc));
+    }
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(text, f).ok())
+        .or_else(|| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok().and_then(|d| d.and_hms_opt(0, 0, 0)))
+        .map(|d| d.and_utc())
+}
+
+/// Saves rows of one entity. References looked up once are remembered until the writer is dropped.
+pub(crate) struct Writer {
+    entity: &'static ImportEntity,
+    upsert: bool,
+    user: Option<String>,
+    references: HashMap<(&'static str, &'static str, String), String>,
+}
+
+type Values = Vec<(&'static ImportField, Value)>;
+
+fn value<'v>(values: &'v Values, name: &str) -> Option<&'v Value> {
+    values.iter().find(|(f, _)| f.name == name).map(|(_, v)| v)
+}
+
+impl Writer {
+    pub fn new(entity: &'static ImportEntity, upsert: bool, user: Option<Uuid>) -> Self {
+        Self { entity, upsert, user: user.map(|u| u.to_string()), references: HashMap::new() }
+    }
+
+    /// Saves one record, or one document from the rows sharing its key.
+    pub async fn write(&mut self, conn: &mut SqliteConnection, rows: &[MappedRow]) -> Result<Outcome, Failure> {
+        match self.entity.target {
+            Target::Record { table, tracks_user } => self.write_record(conn, &rows[0], table, tracks_user).await,
+            Target::Document { table, lines, parent, kind, tracks_user } => {
+                self.write_document(conn, rows, table, lines, parent, kind, tracks_user).await
+            }
+        }
+    }
+
+    async fn convert(&mut self, conn: &mut SqliteConnection, row: &MappedRow, problems: &mut Vec<Problem>) -> Result<Values, Failure> {
+        let mut values = Vec::new();
+        for (field, text) in &row.values {
+            let Some(text) = text else { continue };
+            match self.convert_one(conn, field, text).await? {
+                Ok(value) => values.push((*field, value)),
+                Err(message) => problems.push(Problem { row: row.number, field: Some(field.name), message }),
+            }
+        }
+        Ok(values)
+    }
+
+    async fn convert_one(&mut self, conn: &mut SqliteConnection, field: &'static ImportField, text: &str) -> Result<Result<Value, String>, Failure> {
+        let value = match parse(field, text) {
+            Ok(value) => value,
+            Err(message) => return Ok(Err(message)),
+        };
+        let FieldType::Reference { table, key } = field.field_type else {
+            return Ok(Ok(value));
+        };
+        let cache_key = (table, key, text.to_string());
+        if let Some(id) = self.references.get(&cache_key) {
+            return Ok(Ok(Value::Text(id.clone())));
+        }
+        let id: Option<String> = sqlx::query_scalar(&format!("SELECT id FROM {} WHERE {} = ? LIMIT 1", table, key))
+            .bind(text)
+            .fetch_optional(&mut *conn)
+            .await?;
+        Ok(match id {
+            Some(id) => {
+                self.references.insert(cache_key, id.clone());
+                Ok(Value::Text(id))
+            }
+            None => Err(format!("No record has {} {}", field.label, text)),
+        })
+    }
+
+    /// Fields missing from `values` get their catalogue defaults. Required fields still missing,
+    /// and not already reported, are reported against `row`.
+    fn complete(&self, values: &mut Values, fields: impl Iterator<Item = &'static ImportField>, row: i64, problems: &mut Vec<Problem>) {
+        for field in fields {
+            if value(values, field.name).is_some() || problems.iter().any(|p| p.row == row && p.field == Some(field.name)) {
+                continue;
+            }
+            match field.default.map(|d| parse(field, d)) {
+                Some(Ok(default)) => values.push((field, default)),
+                _ if field.required => problems.push(Problem { row, field: Some(field.name), message: "Required".to_string() }),
+                _ => {}
+            }
+        }
+    }
+
+    async fn find(&self, conn: &mut SqliteConnection, table: &str, values: &Values) -> Result<Option<(String, String)>, Failure> {
+        let keys: Vec<&(&'static ImportField, Value)> = values.iter().filter(|(f, _)| f.key).collect();
+        let conditions: Vec<String> = keys.iter().map(|(f, _)| format!("{} = ?", f.column)).collect();
+        let sql = format!("SELECT id, status FROM {} WHERE {} LIMIT 1", table, conditions.join(" AND "));
+        let mut query = sqlx::query(&sql);
+        for (_, v) in &keys {
+            query = bind(query, Some(v));
+        }
+        let row = query.fetch_optional(&mut *conn).await?;
+        Ok(row.map(|r| (r.get("id"), r.get("status"))))
+    }
+
+    /// Inserts a row with `values` and `extra` columns, or updates the existing one with them.
+    async fn save(
+        &self,
+        conn: &mut SqliteConnection,
+        table: &str,
+        existing: Option<&str>,
+        values: &Values,
+        extra: &[(&str, Value)],
+        tracks_user: bool,
+    ) -> Result<String, Failure> {
+        let now = Value::Text(Utc::now().to_rfc3339());
+        let user = self.user.clone().map(Value::Text);
+        let mut columns: Vec<(&str, Option<Value>)> = values.iter().map(|(f, v)| (f.column, Some(v.clone()))).collect();
+        columns.extend(extra.iter().map(|(c, v)| (*c, Some(v.clone()))));
+        columns.push(("updated_at", Some(now.clone())));
+        if tracks_user {
+            columns.push(("updated_by", user.clone()));
+        }
+        let id = match existing {
+            Some(id) => {
+                let assignments: Vec<String> = columns.iter().map(|(c, _)| format!("{} = ?", c)).collect();
+                let sql = format!("UPDATE {} SET {} WHERE id = ?", table, assignments.join(", "));
+                let mut query = sqlx::query(&sql);
+                for (_, v) in &columns {
+                    query = bind(query, v.as_ref());
+                }
+                query.bind(id).execute(&mut *conn).await?;
+                id.to_string()
+            }
+            None => {
+                let id = Uuid::new_v4().to_string();
+                columns.push(("id", Some(Value::Text(id.clone()))));
+                columns.push(("created_at", Some(now)));
+                if tracks_user {
+                    columns.push(("created_by", user));
+                }
+                let names: Vec<&str> = columns.iter().map(|(c, _)| *c).collect();
+                let sql = format!(
+                    "INSERT INTO {} ({}) VALUES ({})",
+                    table,
+                    names.join(", "),
+                    vec!["?"; names.len()].join(", ")
+                );
+                let mut query = sqlx::query(&sql);
+                for (_, v) in &columns {
+                    query = bind(query, v.as_ref());
+                }
+                query.execute(&mut *conn).await?;
+                id
+            }
+        };
+        Ok(id)
+    }
+
+    async fn write_record(&mut self, conn: &mut SqliteConnection, row: &MappedRow, table: &str, tracks_user: bool) -> Result<Outcome, Failure> {
+        let mut problems = Vec::new();
+        let mut values = self.convert(conn, row, &mut problems).await?;
+        missing_keys(self.entity, row, &mut problems);
+        if !problems.is_empty() {
+            return Err(Failure::Invalid(problems));
+        }
+        let existing = self.find(conn, table, &values).await?;
+        if existing.is_some() && !self.upsert {
+            return Ok(Outcome::Duplicate);
+        }
+        if existing.is_none() {
+            self.complete(&mut values, self.entity.fields.iter(), row.number, &mut problems);
+            if !problems.is_empty() {
+                return Err(Failure::Invalid(problems));
+            }
+        }
+        self.save(conn, table, existing.as_ref().map(|(id, _)| id.as_str()), &values, &[], tracks_user).await?;
+        Ok(if existing.is_some() { Outcome::Updated } else { Outcome::Created })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn write_document(
+        &mut self,
+        conn: &mut SqliteConnection,
+        rows: &[MappedRow],
+        table: &str,
+        lines_table: &str,
+        parent: &str,
+        kind: DocumentKind,
+        tracks_user: bool,
+    ) -> Result<Outcome, Failure> {
+        let mut problems = Vec::new();
+        let mut header: Values = Vec::new();
+        let mut header_rows: HashMap<&str, (i64, &str)> = HashMap::new();
+        let mut lines: Vec<(i64, Values)> = Vec::new();
+        for row in rows {
+            let values = self.convert(conn, row, &mut problems).await?;
+            let (head, mut line): (Values, Values) = values.into_iter().partition(|(f, _)| !f.line);
+            for (field, value) in head {
+                let text = row.text(field.name).unwrap_or_default();
+                match header_rows.get(field.name) {
+                    Some((first, first_text)) if *first_text != text => problems.push(Problem {
+                        row: row.number,
+                        field: Some(field.name),
+                        message: format!("{} differs from {} on row {} of the same document", text, first_text, first),
+                    }),
+                    Some(_) => {}
+                    None => {
+                        header_rows.insert(field.name, (row.number, text));
+                        header.push((field, value));
+                    }
+                }
+            }
+            self.complete(&mut line, self.entity.fields.iter().filter(|f| f.line), row.number, &mut problems);
+            lines.push((row.number, line));
+        }
+        missing_keys(self.entity, &rows[0], &mut problems);
+        if !problems.is_empty() {
+            return Err(Failure::Invalid(problems));
+        }
+
+        let existing = self.find(conn, table, &header).await?;
+        if existing.is_some() && !self.upsert {
+            return Ok(Outcome::Duplicate);
+        }
+        if let Some((_, status)) = &existing {
+            let editable = match kind {
+                DocumentKind::Journal => status == "Draft",
+                DocumentKind::SalesOrder | DocumentKind::PurchaseOrder => status == "Draft" || status == "Pending",
+                DocumentKind::Bom | DocumentKind::PriceList => true,
+            };
+            if !editable {
+                return Err(Failure::Rejected(format!("The existing {} is {} and cannot be replaced", self.entity.label.to_lowercase(), status)));
+            }
+        } else {
+            self.complete(&mut header, self.entity.fields.iter().filter(|f| !f.line), rows[0].number, &mut problems);
+        }
+
+        let mut extra: Vec<(&str, Value)> = Vec::new();
+        let mut line_extra: Vec<Vec<(&str, Value)>> = vec![Vec::new(); lines.len()];
+        match kind {
+            DocumentKind::Journal => {
+                let (mut debits, mut credits) = (0i64, 0i64);
+                for (number, line) in &lines {
+                    let debit = value(line, "debit").map(Value::integer).unwrap_or(0);
+                    let credit = value(line, "credit").map(Value::integer).unwrap_or(0);
+                    if debit < 0 || credit < 0 || (debit > 0) == (credit > 0) {
+                        problems.push(Problem {
+                            row: *number,
+                            field: None,
+                            message: "A line needs either a debit or a credit, not both".to_string(),
+                        });
+                    }
+                    debits += debit;
+                    credits += credit;
+                }
+                if problems.is_empty() && debits != credits {
+                    return Err(Failure::Rejected(format!(
+                        "Debits of {} and credits of {} do not balance",
+                        cents(debits),
+                        cents(credits)
+                    )));
+                }
+                extra.push(("status", Value::Text("Draft".to_string())));
+            }
+            DocumentKind::SalesOrder | DocumentKind::PurchaseOrder => {
+                let (mut subtotal, mut tax) = (0i64, 0i64);
+                for ((number, line), extra) in lines.iter().zip(line_extra.iter_mut()) {
+                    let quantity = value(line, "quantity").map(Value::integer).unwrap_or(0);
+                    if quantity <= 0 {
+                        problems.push(Problem { row: *number, field: Some("quantity"), message: "Must be more than zero".to_string() });
+                    }
+                    let gross = quantity as f64 * value(line, "unit_price").map(Value::integer).unwrap_or(0) as f64;
+                    let discount = value(line, "discount_percent").map(Value::real).unwrap_or(0.0);
+                    let line_total = (gross * (1.0 - discount / 100.0)).round() as i64;
+                    tax += (line_total as f64 * value(line, "tax_rate").map(Value::real).unwrap_or(0.0) / 100.0).round() as i64;
+                    subtotal += line_total;
+                    extra.push(("line_total", Value::Integer(line_total)));
+                    if value(line, "description").is_none() {
+                        let product = value(line, "product_sku").cloned();
+                        extra.push(("description", Value::Text(self.product(conn, product).await?.0)));
+                    }
+                }
+                extra.push(("subtotal", Value::Integer(subtotal)));
+                extra.push(("tax_amount", Value::Integer(tax)));
+                extra.push(("total", Value::Integer(subtotal + tax)));
+            }
+            DocumentKind::Bom => {
+                for ((number, line), extra) in lines.iter().zip(line_extra.iter_mut()) {
+                    if value(line, "component_quantity").map(Value::integer).unwrap_or(0) <= 0 {
+                        problems.push(Problem { row: *number, field: Some("component_quantity"), message: "Must be more than zero".to_string() });
+                    }
+                    if value(line, "unit").is_none() {
+                        let product = value(line, "component_sku").cloned();
+                        extra.push(("unit", Value::Text(self.product(conn, product).await?.1)));
+                    }
+                }
+                if value(&header, "name").is_none() && existing.is_none() {
+                    let product = value(&header, "product_sku").cloned();
+                    extra.push(("name", Value::Text(self.product(conn, product).await?.0)));
+                }
+            }
+            DocumentKind::PriceList => {
+                for (number, line) in &lines {
+                    if value(line, "price").map(Value::integer).unwrap_or(0) < 0 {
+                        problems.push(Problem { row: *number, field: Some("price"), message: "Cannot be negative".to_string() });
+                    }
+                }
+            }
+        }
+        if !problems.is_empty() {
+            return Err(Failure::Invalid(problems));
+        }
+
+        let existing_id = existing.as_ref().map(|(id, _)| id.as_str());
+        let id = self.save(conn, table, existing_id, &header, &extra, tracks_user).await?;
+        if kind != DocumentKind::PriceList {
+            sqlx::query(&format!("DELETE FROM {} WHERE {} = ?", lines_table, parent)).bind(&id).execute(&mut *conn).await?;
+        }
+        for ((_, line), extra) in lines.iter().zip(&line_extra) {
+            let mut columns: Vec<(&str, &Value)> = line.iter().map(|(f, v)| (f.column, v)).collect();
+            columns.extend(extra.iter().map(|(c, v)| (*c, v)));
+            let id_value = Value::Text(Uuid::new_v4().to_string());
+            let parent_value = Value::Text(id.clone());
+            columns.push(("id", &id_value));
+            columns.push((parent, &parent_value));
+            let names: Vec<&str> = columns.iter().map(|(c, _)| *c).collect();
+            let mut sql = format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                lines_table,
+                names.join(", "),
+                vec!["?"; names.len()].join(", ")
+            );
+            if kind == DocumentKind::PriceList {
+                sql.push_str(" ON CONFLICT (price_list_id, product_id) DO UPDATE SET price = excluded.price, min_quantity = excluded.min_quantity");
+            }
+            let mut query = sqlx::query(&sql);
+            for (_, v) in &columns {
+                query = bind(query, Some(v));
+            }
+            query.execute(&mut *conn).await?;
+        }
+        Ok(if existing.is_some() { Outcome::Updated } else { Outcome::Created })
+    }
+
+    /// The name and unit of measure of a product by id.
+    async fn product(&self, conn: &mut SqliteConnection, id: Option<Value>) -> Result<(String, String), Failure> {
+        let id = match id {
+            Some(Value::Text(id)) => id,
+            _ => return Ok((String::new(), String::new())),
+        };
+        let row: Option<(String, String)> = sqlx::query_as("SELECT name, unit_of_measure FROM products WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+        Ok(row.unwrap_or_default())
+    }
+}
+
+fn missing_keys(entity: &ImportEntity, row: &MappedRow, problems: &mut Vec<Problem>) {
+    for field in entity.fields.iter().filter(|f| f.key) {
+        if row.text(field.name).is_none() {
+            problems.push(Problem { row: row.number, field: Some(field.name), message: "Required".to_string() });
+        }
+    }
+}
+
+fn cents(amount: i64) -> String {
+    format!("{}{}.{:02}", if amount < 0 { "-" } else { "" }, amount.abs() / 100, amount.abs() % 100)
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
@@ -263,14 +264,122 @@ pub struct DataImportJob {
     pub success_records: i32,
     pub failed_records: i32,
     pub duplicate_records: i32,
+    /// Successful rows that made a new record and that changed an existing one.
+    pub created_records: i32,
+    pub updated_records: i32,
     pub status: String,
+    /// Checks and counts the rows without saving them.
+    pub dry_run: bool,
+    /// The uploaded file in blob storage.
+    pub file_checksum: Option<String>,
+    pub options: Option<ImportOptions>,
     pub started_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
     pub error_log: Option<String>,
+    pub created_by: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ImportFormat {
+    Csv,
+    Xlsx,
+}
+
+/// How a job reads its file, fixed when the job is created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportOptions {
+    pub format: ImportFormat,
+    /// Worksheet of an XLSX file, the first when not given.
+    pub sheet: Option<String>,
+    /// The file's header row.
+    pub headers: Vec<String>,
+    pub columns: Vec<ColumnMapping>,
+    pub mapping_id: Option<Uuid>,
+    /// Updates records whose natural key already exists. Otherwise their rows are skipped as
+    /// duplicates.
+    pub upsert: bool,
+}
+
+/// Where one field of the imported entity comes from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnMapping {
+    /// Header of the file column. Without one every row gets `default`.
+    #[serde(default)]
+    pub source: Option<String>,
+    pub target: String,
+    #[serde(default)]
+    pub transforms: Vec<Transform>,
+    /// Replacements for whole values, such as `{"Y": "Active"}`, matched ignoring case after
+    /// the transforms.
+    #[serde(default)]
+    pub values: HashMap<String, String>,
+    /// Used when the cell is empty.
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Transform {
+    Trim,
+    Uppercase,
+    Lowercase,
+    Replace { from: String, to: String },
+    Prefix { value: String },
+    Suffix { value: String },
+    /// Reads a date written as `format` (chrono syntax, e.g. `%d/%m/%Y`).
+    DateFormat { format: String },
+    /// Multiplies a number, e.g. by 100 to turn 0.2 into a 20 percent rate.
+    Multiply { factor: f64 },
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateImportRequest {
+    pub entity_type: String,
+    pub file_name: String,
+    /// Checked against the uploaded file when given.
+    pub expected_sha256: Option<String>,
+    /// Taken from the file name when not given.
+    pub format: Option<ImportFormat>,
+    pub sheet: Option<String>,
+    /// A saved mapping, used unless `columns` are given. Without either, headers are matched to
+    /// field names and labels.
+    pub mapping_id: Option<Uuid>,
+    pub columns: Option<Vec<ColumnMapping>>,
+    pub dry_run: bool,
+    pub upsert: bool,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportMapping {
+    pub id: Uuid,
+    pub name: String,
+    pub entity_type: String,
+    pub columns: Vec<ColumnMapping>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateImportMappingRequest {
+    pub name: String,
+    pub entity_type: String,
+    pub columns: Vec<ColumnMapping>,
+}
+
+/// Why a row of an import was not saved. `values` are the row's cells as they were in the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRowError {
+    pub row_number: i64,
+    pub field: Option<String>,
+    pub message: String,
+    pub values: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataExportJob {
     pub id: Uuid,
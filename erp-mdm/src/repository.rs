@@ -53,6 +53,13 @@ pub trait MDMRepository {
     async fn create_import_job(&self, job: &DataImportJob) -> anyhow::Result<()>;
     async fn get_import_job(&self, id: Uuid) -> anyhow::Result<Option<DataImportJob>>;
     async fn update_import_job(&self, job: &DataImportJob) -> anyhow::Result<()>;
+    async fn list_import_jobs(&self, entity_type: Option<&str>, limit: i32, offset: i32) -> anyhow::Result<Vec<DataImportJob>>;
+    async fn list_import_errors(&self, job_id: Uuid, limit: i32, offset: i32) -> anyhow::Result<Vec<ImportRowError>>;
+
+    async fn create_import_mapping(&self, mapping: &ImportMapping) -> anyhow::Result<()>;
+    async fn get_import_mapping(&self, id: Uuid) -> anyhow::Result<Option<ImportMapping>>;
+    async fn list_import_mappings(&self, entity_type: Option<&str>) -> anyhow::Result<Vec<ImportMapping>>;
+    async fn delete_import_mapping(&self, id: Uuid) -> anyhow::Result<bool>;
     
     async fn create_export_job(&self, job: &DataExportJob) -> anyhow::Result<()>;
     async fn get_export_job(&self, id: Uuid) -> anyhow::Result<Option<DataExportJob>>;
@@ -923,9 +930,10 @@ impl MDMRepository for SqliteMDMRepository {
     async fn create_import_job(&self, job: &DataImportJob) -> anyhow::Result<()> {
         sqlx::query(
             r#"INSERT INTO mdm_import_jobs (id, job_name, entity_type, source_file, total_records,
-                processed_records, success_records, failed_records, duplicate_records, status,
-                started_at, completed_at, error_log, created_at, updated_at)
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
+                processed_records, success_records, failed_records, duplicate_records, created_records,
+                updated_records, status, dry_run, file_checksum, options, started_at, completed_at,
+                error_log, created_by, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#
         )
         .bind(job.id.to_string())
         .bind(&job.job_name)
@@ -936,10 +944,16 @@ impl MDMRepository for SqliteMDMRepository {
         .bind(job.success_records)
         .bind(job.failed_records)
         .bind(job.duplicate_records)
+        .bind(job.created_records)
+        .bind(job.updated_records)
         .bind(&job.status)
+        .bind(job.dry_run)
+        .bind(&job.file_checksum)
+        .bind(job.options.as_ref().map(serde_json::to_string).transpose()?)
         .bind(job.started_at)
         .bind(job.completed_at)
         .bind(&job.error_log)
+        .bind(job.created_by.map(|id| id.to_string()))
         .bind(job.created_at)
         .bind(job.updated_at)
         .execute(&self.pool).await?;
@@ -947,59 +961,115 @@ impl MDMRepository for SqliteMDMRepository {
     }
 
     async fn get_import_job(&self, id: Uuid) -> anyhow::Result<Option<DataImportJob>> {
-        let row = sqlx::query(
-            r#"SELECT id, job_name, entity_type, source_file, total_records, processed_records,
-                success_records, failed_records, duplicate_records, status, started_at, completed_at,
-                error_log, created_at, updated_at FROM mdm_import_jobs WHERE id = ?"#
-        )
-        .bind(id.to_string())
-        .fetch_optional(&self.pool).await?;
-
-        match row {
-            Some(row) => {
-                let job = DataImportJob {
-                    id: Uuid::parse_str(row.get::<&str, _>("id"))?,
-                    job_name: row.get("job_name"),
-                    entity_type: row.get("entity_type"),
-                    source_file: row.get("source_file"),
-                    total_records: row.get("total_records"),
-                    processed_records: row.get("processed_records"),
-                    success_records: row.get("success_records"),
-                    failed_records: row.get("failed_records"),
-                    duplicate_records: row.get("duplicate_records"),
-                    status: row.get("status"),
-                    started_at: row.get("started_at"),
-                    completed_at: row.get("completed_at"),
-                    error_log: row.get("error_log"),
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
-                };
-                Ok(Some(job))
-            }
-            None => Ok(None),
-        }
+        let row = sqlx::query(&format!("SELECT {} FROM mdm_import_jobs WHERE id = ?", IMPORT_JOB_COLUMNS))
+            .bind(id.to_string())
+            .fetch_optional(&self.pool).await?;
+        row.map(|row| import_job_from_row(&row)).transpose()
     }
 
     async fn update_import_job(&self, job: &DataImportJob) -> anyhow::Result<()> {
         sqlx::query(
-            r#"UPDATE mdm_import_jobs SET processed_records = ?, success_records = ?, failed_records = ?,
-                duplicate_records = ?, status = ?, started_at = ?, completed_at = ?, error_log = ?,
-                updated_at = ? WHERE id = ?"#
+            r#"UPDATE mdm_import_jobs SET total_records = ?, processed_records = ?, success_records = ?,
+                failed_records = ?, duplicate_records = ?, created_records = ?, updated_records = ?,
+                status = ?, dry_run = ?, file_checksum = ?, options = ?, started_at = ?, completed_at = ?,
+                error_log = ?, created_by = ?, updated_at = ? WHERE id = ?"#
         )
+        .bind(job.total_records)
         .bind(job.processed_records)
         .bind(job.success_records)
         .bind(job.failed_records)
         .bind(job.duplicate_records)
+        .bind(job.created_records)
+        .bind(job.updated_records)
         .bind(&job.status)
+        .bind(job.dry_run)
+        .bind(&job.file_checksum)
+        .bind(job.options.as_ref().map(serde_json::to_string).transpose()?)
         .bind(job.started_at)
         .bind(job.completed_at)
         .bind(&job.error_log)
+        .bind(job.created_by.map(|id| id.to_string()))
         .bind(job.updated_at)
         .bind(job.id.to_string())
         .execute(&self.pool).await?;
         Ok(())
     }
 
+    async fn list_import_jobs(&self, entity_type: Option<&str>, limit: i32, offset: i32) -> anyhow::Result<Vec<DataImportJob>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM mdm_import_jobs WHERE (? IS NULL OR entity_type = ?) ORDER BY created_at DESC LIMIT ? OFFSET ?",
+            IMPORT_JOB_COLUMNS
+        ))
+        .bind(entity_type)
+        .bind(entity_type)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool).await?;
+        rows.iter().map(import_job_from_row).collect()
+    }
+
+    async fn list_import_errors(&self, job_id: Uuid, limit: i32, offset: i32) -> anyhow::Result<Vec<ImportRowError>> {
+        let rows = sqlx::query(
+            r#"SELECT row_number, field, message, row_values FROM mdm_import_errors WHERE job_id = ?
+                ORDER BY row_number, rowid LIMIT ? OFFSET ?"#
+        )
+        .bind(job_id.to_string())
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool).await?;
+        rows.iter().map(|row| {
+            Ok(ImportRowError {
+                row_number: row.get("row_number"),
+                field: row.get("field"),
+                message: row.get("message"),
+                values: serde_json::from_str(row.get::<&str, _>("row_values"))?,
+            })
+        }).collect()
+    }
+
+    async fn create_import_mapping(&self, mapping: &ImportMapping) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"INSERT INTO mdm_import_mappings (id, name, entity_type, columns, created_by, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?)"#
+        )
+        .bind(mapping.id.to_string())
+        .bind(&mapping.name)
+        .bind(&mapping.entity_type)
+        .bind(serde_json::to_string(&mapping.columns)?)
+        .bind(mapping.created_by.map(|id| id.to_string()))
+        .bind(mapping.created_at)
+        .bind(mapping.updated_at)
+        .execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn get_import_mapping(&self, id: Uuid) -> anyhow::Result<Option<ImportMapping>> {
+        let row = sqlx::query(
+            "SELECT id, name, entity_type, columns, created_by, created_at, updated_at FROM mdm_import_mappings WHERE id = ?"
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool).await?;
+        row.map(|row| import_mapping_from_row(&row)).transpose()
+    }
+
+    async fn list_import_mappings(&self, entity_type: Option<&str>) -> anyhow::Result<Vec<ImportMapping>> {
+        let rows = sqlx::query(
+            r#"SELECT id, name, entity_type, columns, created_by, created_at, updated_at FROM mdm_import_mappings
+                WHERE (? IS NULL OR entity_type = ?) ORDER BY entity_type, name"#
+        )
+        .bind(entity_type)
+        .bind(entity_type)
+        .fetch_all(&self.pool).await?;
+        rows.iter().map(import_mapping_from_row).collect()
+    }
+
+    async fn delete_import_mapping(&self, id: Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query("DELETE FROM mdm_import_mappings WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool).await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn create_export_job(&self, job: &DataExportJob) -> anyhow::Result<()> {
         sqlx::query(
             r#"INSERT INTO mdm_export_jobs (id, job_name, entity_type, filter_criteria, export_format,
@@ -1069,3 +1139,45 @@ impl MDMRepository for SqliteMDMRepository {
         Ok(())
     }
 }
+
+const IMPORT_JOB_COLUMNS: &str = "id, job_name, entity_type, source_file, total_records, processed_records, \
+    success_records, failed_records, duplicate_records, created_records, updated_records, status, dry_run, \
+    file_checksum, options, started_at, completed_at, error_log, created_by, created_at, updated_at";
+
+fn import_job_from_row(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<DataImportJob> {
+    Ok(DataImportJob {
+        id: Uuid::parse_str(row.get::<&str, _>("id"))?,
+        job_name: row.get("job_name"),
+        entity_type: row.get("entity_type"),
+        source_file: row.get("source_file"),
+        total_records: row.get("total_records"),
+        processed_records: row.get("processed_records"),
+        success_records: row.get("success_records"),
+        failed_records: row.get("failed_records"),
+        duplicate_records: row.get("duplicate_records"),
+        created_records: row.get("created_records"),
+        updated_records: row.get("updated_records"),
+        status: row.get("status"),
+        dry_run: row.get("dry_run"),
+        file_checksum: row.get("file_checksum"),
+        options: row.get::<Option<&str>, _>("options").map(serde_json::from_str).transpose()?,
+        started_at: row.get("started_at"),
+        completed_at: row.get("completed_at"),
+        error_log: row.get("error_log"),
+        created_by: row.get::<Option<&str>, _>("created_by").map(Uuid::parse_str).transpose()?,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+fn import_mapping_from_row(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<ImportMapping> {
+    Ok(ImportMapping {
+        id: Uuid::parse_str(row.get::<&str, _>("id"))?,
+        name: row.get("name"),
+        entity_type: row.get("entity_type"),
+        columns: serde_json::from_str(row.get::<&str, _>("columns"))?,
+        created_by: row.get::<Option<&str>, _>("created_by").map(Uuid::parse_str).transpose()?,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
@@ -272,10 +272,16 @@ impl<R: MDMRepository> MDMService<R> {
             success_records: 0,
             failed_records: 0,
             duplicate_records: 0,
+            created_records: 0,
+            updated_records: 0,
             status: "Pending".to_string(),
+            dry_run: false,
+            file_checksum: None,
+            options: None,
             started_at: None,
             completed_at: None,
             error_log: None,
+            created_by: None,
             created_at: now,
             updated_at: now,
         };
@@ -304,4 +310,14 @@ impl<R: MDMRepository> MDMService<R> {
         self.repo.update_import_job(&job).await?;
         Ok(job)
     }
+
+    pub async fn fail_import_job(&self, job_id: Uuid, error: String) -> anyhow::Result<DataImportJob> {
+        let mut job = self.repo.get_import_job(job_id).await?.ok_or_else(|| anyhow::anyhow!("Job not found"))?;
+        job.status = "Failed".to_string();
+        job.error_log = Some(error);
+        job.completed_at = Some(Utc::now());
+        job.updated_at = Utc::now();
+        self.repo.update_import_job(&job).await?;
+        Ok(job)
+    }
 }
@@ -0,0 +1,339 @@
+use erp_core::blob::LocalBlobStore;
+use erp_core::BlobService;
+use erp_mdm::import::source;
+use erp_mdm::import::{ImportService, CHUNK_ROWS};
+use erp_mdm::*;
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE blobs (sha256 TEXT PRIMARY KEY, size INTEGER NOT NULL, mime_type TEXT NOT NULL, backend TEXT NOT NULL, ref_count INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL)",
+    r#"CREATE TABLE mdm_import_jobs (id TEXT PRIMARY KEY, job_name TEXT NOT NULL, entity_type TEXT NOT NULL, source_file TEXT NOT NULL,
+        total_records INTEGER DEFAULT 0, processed_records INTEGER DEFAULT 0, success_records INTEGER DEFAULT 0, failed_records INTEGER DEFAULT 0,
+        duplicate_records INTEGER DEFAULT 0, status TEXT NOT NULL DEFAULT 'Pending', started_at TEXT, completed_at TEXT, error_log TEXT,
+        created_at TEXT NOT NULL, updated_at TEXT NOT NULL, created_records INTEGER NOT NULL DEFAULT 0, updated_records INTEGER NOT NULL DEFAULT 0,
+        dry_run INTEGER NOT NULL DEFAULT 0, file_checksum TEXT, options TEXT, created_by TEXT)"#,
+    r#"CREATE TABLE mdm_import_mappings (id TEXT PRIMARY KEY, name TEXT NOT NULL, entity_type TEXT NOT NULL, columns TEXT NOT NULL,
+        created_by TEXT, created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+    r#"CREATE TABLE mdm_import_errors (id TEXT PRIMARY KEY, job_id TEXT NOT NULL, row_number INTEGER NOT NULL, field TEXT,
+        message TEXT NOT NULL, row_values TEXT NOT NULL)"#,
+    r#"CREATE TABLE products (id TEXT PRIMARY KEY, sku TEXT NOT NULL UNIQUE, name TEXT NOT NULL, description TEXT,
+        product_type TEXT NOT NULL DEFAULT 'Goods', unit_of_measure TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'Active',
+        created_at TEXT NOT NULL, updated_at TEXT NOT NULL, created_by TEXT, updated_by TEXT)"#,
+    r#"CREATE TABLE customers (id TEXT PRIMARY KEY, code TEXT NOT NULL UNIQUE, name TEXT NOT NULL, email TEXT, payment_terms INTEGER NOT NULL DEFAULT 30,
+        status TEXT NOT NULL DEFAULT 'Active', created_at TEXT NOT NULL, updated_at TEXT NOT NULL, created_by TEXT, updated_by TEXT)"#,
+    r#"CREATE TABLE accounts (id TEXT PRIMARY KEY, code TEXT NOT NULL UNIQUE, name TEXT NOT NULL, account_type TEXT NOT NULL, parent_id TEXT,
+        status TEXT NOT NULL DEFAULT 'Active', description TEXT, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, created_by TEXT, updated_by TEXT)"#,
+    r#"CREATE TABLE journal_entries (id TEXT PRIMARY KEY, entry_number TEXT NOT NULL UNIQUE, date TEXT NOT NULL, description TEXT NOT NULL,
+        reference TEXT, status TEXT NOT NULL DEFAULT 'Draft', created_at TEXT NOT NULL, updated_at TEXT NOT NULL, created_by TEXT, updated_by TEXT)"#,
+    r#"CREATE TABLE journal_lines (id TEXT PRIMARY KEY, journal_entry_id TEXT NOT NULL REFERENCES journal_entries(id) ON DELETE CASCADE,
+        account_id TEXT NOT NULL, debit INTEGER NOT NULL DEFAULT 0, credit INTEGER NOT NULL DEFAULT 0, description TEXT)"#,
+    r#"CREATE TABLE sales_orders (id TEXT PRIMARY KEY, order_number TEXT NOT NULL UNIQUE, customer_id TEXT NOT NULL, order_date TEXT NOT NULL,
+        required_date TEXT, subtotal INTEGER NOT NULL DEFAULT 0, tax_amount INTEGER NOT NULL DEFAULT 0, total INTEGER NOT NULL DEFAULT 0,
+        status TEXT NOT NULL DEFAULT 'Draft', created_at TEXT NOT NULL, updated_at TEXT NOT NULL, created_by TEXT, updated_by TEXT)"#,
+    r#"CREATE TABLE sales_order_lines (id TEXT PRIMARY KEY, sales_order_id TEXT NOT NULL, product_id TEXT NOT NULL, description TEXT NOT NULL,
+        quantity INTEGER NOT NULL, unit_price INTEGER NOT NULL, discount_percent REAL NOT NULL DEFAULT 0, tax_rate REAL NOT NULL DEFAULT 0,
+        line_total INTEGER NOT NULL)"#,
+    r#"CREATE TABLE price_lists (id TEXT PRIMARY KEY, name TEXT NOT NULL, currency TEXT NOT NULL DEFAULT 'USD', status TEXT NOT NULL DEFAULT 'Active',
+        created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+    r#"CREATE TABLE price_list_items (id TEXT PRIMARY KEY, price_list_id TEXT NOT NULL, product_id TEXT NOT NULL, price INTEGER NOT NULL,
+        min_quantity INTEGER NOT NULL DEFAULT 0, UNIQUE(price_list_id, product_id))"#,
+];
+
+async fn setup() -> (SqlitePool, BlobService) {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    for statement in SCHEMA {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    let root = std::env::temp_dir().join(format!("erp-mdm-test-{}", Uuid::new_v4()));
+    let blobs = BlobService::new(Arc::new(LocalBlobStore::new(root.join("blobs"))), root.join("staging"));
+    (pool, blobs)
+}
+
+fn request(entity: &str, file_name: &str) -> CreateImportRequest {
+    CreateImportRequest {
+        entity_type: entity.to_string(),
+        file_name: file_name.to_string(),
+        expected_sha256: None,
+        format: None,
+        sheet: None,
+        mapping_id: None,
+        columns: None,
+        dry_run: false,
+        upsert: false,
+        created_by: None,
+    }
+}
+
+async fn import(pool: &SqlitePool, blobs: &BlobService, content: &str, request: CreateImportRequest) -> DataImportJob {
+    let file = blobs.stage_bytes(content.as_bytes().to_vec(), 1 << 30).await.unwrap();
+    ImportService::new().create_import(pool, blobs, file, request).await.unwrap()
+}
+
+fn cells(rows: &[source::SheetRow]) -> Vec<(i64, Vec<&str>)> {
+    rows.iter().map(|r| (r.number, r.cells.iter().map(String::as_str).collect())).collect()
+}
+
+#[test]
+fn test_csv_files_are_read_with_detected_delimiters_and_quoting() {
+    let csv = "\u{feff}SKU;Name;Notes\r\nA-1;\"Widget; large\";\"Say \"\"hi\"\"\"\r\n\r\nA-2;Gadget;\"two\nlines\"\r\nA-3;Gizmo\r\n";
+    let sheet = source::read(csv.as_bytes(), ImportFormat::Csv, None).unwrap();
+    assert_eq!(sheet.headers, vec!["SKU", "Name", "Notes"]);
+    assert_eq!(cells(&sheet.rows), vec![
+        (2, vec!["A-1", "Widget; large", "Say \"hi\""]),
+        (4, vec!["A-2", "Gadget", "two\nlines"]),
+        (6, vec!["A-3", "Gizmo"]),
+    ]);
+
+    let latin1 = b"code,name\nC-1,Caf\xe9\n";
+    let sheet = source::read(latin1, ImportFormat::Csv, None).unwrap();
+    assert_eq!(sheet.rows[0].cells, vec!["C-1", "Café"]);
+
+    let tabs = source::read(b"a\tb\n1\t2", ImportFormat::Csv, None).unwrap();
+    assert_eq!(cells(&tabs.rows), vec![(2, vec!["1", "2"])]);
+
+    let err = source::read(b"a,b\n1,\"open\n", ImportFormat::Csv, None).unwrap_err();
+    assert!(err.to_string().contains("Line 2 opens a quoted value"), "{}", err);
+    assert!(source::read(b"\n\n", ImportFormat::Csv, None).is_err());
+}
+
+/// A workbook zipped without compression.
+fn xlsx(parts: &[(&str, &str)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut directory = Vec::new();
+    for (name, content) in parts {
+        let mut crc = flate2::Crc::new();
+        crc.update(content.as_bytes());
+        let offset = out.len() as u32;
+        let mut header = vec![0x50, 0x4b, 0x03, 0x04, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        header.extend(crc.sum().to_le_bytes());
+        header.extend((content.len() as u32).to_le_bytes());
+        header.extend((content.len() as u32).to_le_bytes());
+        header.extend((name.len() as u16).to_le_bytes());
+        header.extend(0u16.to_le_bytes());
+        out.extend(&header);
+        out.extend(name.as_bytes());
+        out.extend(content.as_bytes());
+
+        let mut entry = vec![0x50, 0x4b, 0x01, 0x02, 20, 0];
+        entry.extend(&header[4..]);
+        entry.extend([0u8; 10]);
+        entry.extend(offset.to_le_bytes());
+        entry.extend(name.as_bytes());
+        directory.extend(entry);
+    }
+    let directory_offset = out.len() as u32;
+    out.extend(&directory);
+    out.extend([0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0]);
+    out.extend((parts.len() as u16).to_le_bytes());
+    out.extend((parts.len() as u16).to_le_bytes());
+    out.extend((directory.len() as u32).to_le_bytes());
+    out.extend(directory_offset.to_le_bytes());
+    out.extend(0u16.to_le_bytes());
+    out
+}
+
+#[test]
+fn test_xlsx_sheets_are_read_with_shared_strings_and_dates() {
+    let workbook = xlsx(&[
+        ("xl/workbook.xml", r#"<workbook xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets>
+            <sheet name="Notes" sheetId="1" r:id="rId1"/><sheet name="Employees" sheetId="2" r:id="rId2"/></sheets></workbook>"#),
+        ("xl/_rels/workbook.xml.rels", r#"<Relationships><Relationship Id="rId1" Target="worksheets/sheet1.xml"/>
+            <Relationship Id="rId2" Target="/xl/worksheets/sheet2.xml"/></Relationships>"#),
+        ("xl/sharedStrings.xml", r#"<sst><si><t>Number</t></si><si><t>Hired</t></si><si><r><t>Ada </t></r><r><t>Lovelace</t></r></si></sst>"#),
+        ("xl/styles.xml", r#"<styleSheet><numFmts><numFmt numFmtId="164" formatCode="dd/mm/yyyy"/><numFmt numFmtId="165" formatCode="&quot;Qty&quot; 0"/></numFmts>
+            <cellXfs><xf numFmtId="0"/><xf numFmtId="164"/><xf numFmtId="165"/></cellXfs></styleSheet>"#),
+        ("xl/worksheets/sheet1.xml", r#"<worksheet><sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>ignored</t></is></c></row></sheetData></worksheet>"#),
+        ("xl/worksheets/sheet2.xml", r#"<worksheet><sheetData>
+            <row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c><c r="D1" t="inlineStr"><is><t>Name</t></is></c><c r="E1" t="inlineStr"><is><t>Qty</t></is></c></row>
+            <row r="3"><c r="A3"><v>1001</v></c><c r="B3" s="1"><v>45292</v></c><c r="C3" t="b"><v>1</v></c><c r="D3" t="s"><v>2</v></c><c r="E3" s="2"><v>0.30000000000000004</v></c></row>
+            </sheetData></worksheet>"#),
+    ]);
+    let sheet = source::read(&workbook, ImportFormat::Xlsx, Some("employees")).unwrap();
+    assert_eq!(sheet.headers, vec!["Number", "Hired", "", "Name", "Qty"]);
+    assert_eq!(cells(&sheet.rows), vec![(3, vec!["1001", "2024-01-01", "true", "Ada Lovelace", "0.3"])]);
+
+    let first = source::read(&workbook, ImportFormat::Xlsx, None).unwrap();
+    assert_eq!(first.headers, vec!["ignored"]);
+    let err = source::read(&workbook, ImportFormat::Xlsx, Some("Payroll")).unwrap_err();
+    assert!(err.to_string().contains("Its sheets are Notes, Employees"), "{}", err);
+    assert!(source::read(b"not a zip", ImportFormat::Xlsx, None).is_err());
+}
+
+#[tokio::test]
+async fn test_mapped_products_dry_run_then_import_and_upsert() {
+    let (pool, blobs) = setup().await;
+    let service = ImportService::new();
+    let columns: Vec<ColumnMapping> = serde_json::from_value(json!([
+        {"source": "Item", "target": "sku", "transforms": [{"type": "uppercase"}, {"type": "prefix", "value": "P-"}]},
+        {"source": "Title", "target": "name"},
+        {"source": "Active?", "target": "status", "values": {"Y": "Active", "N": "Inactive"}},
+        {"source": "Kind", "target": "product_type", "default": "Goods"},
+    ])).unwrap();
+    let mapping = service.create_mapping(&pool, CreateImportMappingRequest {
+        name: "Legacy item list".to_string(),
+        entity_type: "products".to_string(),
+        columns,
+    }, None).await.unwrap();
+    let bad_mapping = service.create_mapping(&pool, CreateImportMappingRequest {
+        name: "Broken".to_string(),
+        entity_type: "products".to_string(),
+        columns: serde_json::from_value(json!([{"source": "Colour", "target": "colour"}])).unwrap(),
+    }, None).await;
+    assert!(bad_mapping.unwrap_err().to_string().contains("Products has no field colour"));
+
+    let csv = "Item,Title,Active?,Kind\nab1,Widget,Y,\ncd2,,N,Service\nef3,Gizmo,maybe,Hardware\n";
+    let job = import(&pool, &blobs, csv, CreateImportRequest { mapping_id: Some(mapping.id), dry_run: true, ..request("products", "items.csv") }).await;
+    assert_eq!(job.status, "Completed");
+    assert!(job.dry_run);
+    assert_eq!((job.total_records, job.success_records, job.failed_records, job.created_records), (3, 1, 2, 1));
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM products").fetch_one(&pool).await.unwrap();
+    assert_eq!(count, 0, "a dry run saves nothing");
+
+    let errors = service.errors(&pool, job.id, 100, 0).await.unwrap();
+    let summary: Vec<(i64, Option<&str>)> = errors.iter().map(|e| (e.row_number, e.field.as_deref())).collect();
+    assert_eq!(summary, vec![(3, Some("name")), (4, Some("status")), (4, Some("product_type"))]);
+    assert_eq!(errors[1].values, vec!["ef3", "Gizmo", "maybe", "Hardware"]);
+    let file = service.error_file(&pool, job.id).await.unwrap();
+    let lines: Vec<&str> = file.lines().collect();
+    assert_eq!(lines[0], "Row,Item,Title,Active?,Kind,Errors");
+    assert_eq!(lines[1], "3,cd2,,N,Service,Name: Required");
+    assert!(lines[2].starts_with("4,ef3,Gizmo,maybe,Hardware,\"Status: maybe is not one of Active, Inactive; Product Type:"), "{}", lines[2]);
+
+    let job = import(&pool, &blobs, csv, CreateImportRequest { mapping_id: Some(mapping.id), ..request("products", "items.csv") }).await;
+    assert_eq!((job.success_records, job.failed_records), (1, 2));
+    let product: (String, String, String, String) = sqlx::query_as("SELECT sku, name, status, unit_of_measure FROM products").fetch_one(&pool).await.unwrap();
+    assert_eq!(product, ("P-AB1".to_string(), "Widget".to_string(), "Active".to_string(), "EA".to_string()));
+
+    let again = "sku,name,unit of measure\nP-AB1,Widget Mk2,\nP-ZZ9,Sprocket,BOX\n";
+    let job = import(&pool, &blobs, again, request("products", "again.csv")).await;
+    assert_eq!((job.success_records, job.duplicate_records, job.created_records), (1, 1, 1));
+    let job = import(&pool, &blobs, again, CreateImportRequest { upsert: true, ..request("products", "again.csv") }).await;
+    assert_eq!((job.success_records, job.updated_records, job.duplicate_records), (2, 2, 0));
+    let updated: (String, String) = sqlx::query_as("SELECT name, unit_of_measure FROM products WHERE sku = 'P-AB1'").fetch_one(&pool).await.unwrap();
+    assert_eq!(updated, ("Widget Mk2".to_string(), "EA".to_string()), "empty cells keep existing values");
+
+    let unmapped = service.create_import(&pool, &blobs, blobs.stage_bytes(b"name\nX\n".to_vec(), 1024).await.unwrap(), request("products", "x.csv")).await;
+    assert!(unmapped.unwrap_err().to_string().contains("No column is mapped to SKU"));
+}
+
+#[tokio::test]
+async fn test_document_rows_are_grouped_and_fail_together() {
+    let (pool, blobs) = setup().await;
+    let now = chrono::Utc::now().to_rfc3339();
+    for (code, name) in [("C-1", "Acme")] {
+        sqlx::query("INSERT INTO customers (id, code, name, created_at, updated_at) VALUES (?, ?, ?, ?, ?)")
+            .bind(Uuid::new_v4().to_string()).bind(code).bind(name).bind(&now).bind(&now).execute(&pool).await.unwrap();
+    }
+    for (sku, name) in [("W-1", "Widget"), ("G-1", "Gadget")] {
+        sqlx::query("INSERT INTO products (id, sku, name, unit_of_measure, created_at, updated_at) VALUES (?, ?, ?, 'EA', ?, ?)")
+            .bind(Uuid::new_v4().to_string()).bind(sku).bind(name).bind(&now).bind(&now).execute(&pool).await.unwrap();
+    }
+    for (code, kind) in [("1000", "Asset"), ("3000", "Equity")] {
+        sqlx::query("INSERT INTO accounts (id, code, name, account_type, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)")
+            .bind(Uuid::new_v4().to_string()).bind(code).bind(code).bind(kind).bind(&now).bind(&now).execute(&pool).await.unwrap();
+    }
+
+    let csv = "Order Number,Customer Code,Order Date,Product SKU,Quantity,Unit Price,Discount %,Tax Rate %\n\
+        SO-1,C-1,31/01/2026,W-1,2,10.00,,10\n\
+        SO-2,C-1,01/02/2026,W-1,1,5,,\n\
+        SO-1,C-1,31/01/2026,G-1,1,\"1,000.50\",50,10\n\
+        SO-2,C-1,01/02/2026,X-9,1,5,,\n";
+    let columns: Vec<ColumnMapping> = serde_json::from_value(json!([
+        {"source": "Order Number", "target": "order_number"},
+        {"source": "Customer Code", "target": "customer_code"},
+        {"source": "Order Date", "target": "order_date", "transforms": [{"type": "date_format", "format": "%d/%m/%Y"}]},
+        {"source": "Product SKU", "target": "product_sku"},
+        {"source": "Quantity", "target": "quantity"},
+        {"source": "Unit Price", "target": "unit_price"},
+        {"source": "Discount %", "target": "discount_percent"},
+        {"source": "Tax Rate %", "target": "tax_rate"},
+    ])).unwrap();
+    let job = import(&pool, &blobs, csv, CreateImportRequest { columns: Some(columns), ..request("sales_orders", "orders.csv") }).await;
+    assert_eq!((job.success_records, job.failed_records, job.created_records), (2, 2, 2));
+
+    let order: (String, String, i64, i64, i64) = sqlx::query_as("SELECT order_date, status, subtotal, tax_amount, total FROM sales_orders WHERE order_number = 'SO-1'")
+        .fetch_one(&pool).await.unwrap();
+    assert_eq!(order, ("2026-01-31T00:00:00+00:00".to_string(), "Draft".to_string(), 2000 + 50025, 200 + 5003, 2000 + 50025 + 200 + 5003));
+    let lines: Vec<(String, i64)> = sqlx::query_as("SELECT description, line_total FROM sales_order_lines ORDER BY line_total").fetch_all(&pool).await.unwrap();
+    assert_eq!(lines, vec![("Widget".to_string(), 2000), ("Gadget".to_string(), 50025)]);
+    let (orders,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sales_orders").fetch_one(&pool).await.unwrap();
+    assert_eq!(orders, 1, "SO-2 has a bad line and is not imported at all");
+    let errors = ImportService::new().errors(&pool, job.id, 10, 0).await.unwrap();
+    let summary: Vec<(i64, &str)> = errors.iter().map(|e| (e.row_number, e.message.as_str())).collect();
+    assert_eq!(summary, vec![(3, "Not imported because row 5 has errors"), (5, "No record has Product SKU X-9")]);
+
+    let journal = "entry_number,date,account_code,debit,credit\nOB-1,2026-01-01,1000,150.00,\nOB-1,2026-01-01,3000,,140\nOB-2,2026-01-01,1000,10,10\n";
+    let job = import(&pool, &blobs, journal, request("opening_balances", "ob.csv")).await;
+    assert_eq!((job.success_records, job.failed_records), (0, 3));
+    let errors = ImportService::new().errors(&pool, job.id, 10, 0).await.unwrap();
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+    assert!(messages.contains(&"Debits of 150.00 and credits of 140.00 do not balance"), "{:?}", messages);
+    assert!(messages.contains(&"A line needs either a debit or a credit, not both"), "{:?}", messages);
+
+    let balanced = "entry_number,date,account_code,debit,credit\nOB-1,2026-01-01,1000,150.00,\nOB-1,2026-01-01,3000,,150\n";
+    let job = import(&pool, &blobs, balanced, request("opening_balances", "ob.csv")).await;
+    assert_eq!(job.success_records, 2);
+    let entry: (String, String) = sqlx::query_as("SELECT status, description FROM journal_entries").fetch_one(&pool).await.unwrap();
+    assert_eq!(entry, ("Draft".to_string(), "Opening balances".to_string()));
+    sqlx::query("UPDATE journal_entries SET status = 'Posted'").execute(&pool).await.unwrap();
+    let job = import(&pool, &blobs, balanced, CreateImportRequest { upsert: true, ..request("opening_balances", "ob.csv") }).await;
+    assert_eq!(job.failed_records, 2);
+    let errors = ImportService::new().errors(&pool, job.id, 10, 0).await.unwrap();
+    assert_eq!(errors[0].message, "The existing opening balances is Posted and cannot be replaced");
+
+    let prices = "name,product_sku,price\nRetail,W-1,12.50\nRetail,G-1,99\n";
+    import(&pool, &blobs, prices, request("price_lists", "prices.csv")).await;
+    let job = import(&pool, &blobs, "name,product_sku,price\nRetail,W-1,11\n", CreateImportRequest { upsert: true, ..request("price_lists", "prices.csv") }).await;
+    assert_eq!(job.updated_records, 1);
+    let items: Vec<(i64,)> = sqlx::query_as("SELECT price FROM price_list_items ORDER BY price").fetch_all(&pool).await.unwrap();
+    assert_eq!(items, vec![(1100,), (9900,)], "price list lines are merged by product");
+}
+
+#[tokio::test]
+async fn test_large_files_are_queued_and_imported_in_chunks() {
+    let (pool, blobs) = setup().await;
+    let service = ImportService::new();
+    let mut csv = String::from("sku,name\n");
+    for i in 0..CHUNK_ROWS * 2 + 10 {
+        csv.push_str(&format!("SKU-{:05},Product {}\n", i, i));
+    }
+    csv.push_str("SKU-00001,Again\n");
+
+    // A dry run counts the repeated SKU in the last chunk as the duplicate it will be, and a
+    // job whose file has gone missing fails without holding up the ones queued after it.
+    let dry = import(&pool, &blobs, &csv, CreateImportRequest { dry_run: true, ..request("products", "catalogue.csv") }).await;
+    sqlx::query(
+        "INSERT INTO mdm_import_jobs (id, job_name, entity_type, source_file, status, file_checksum, created_at, updated_at)
+         VALUES (?, 'Lost', 'products', 'lost.csv', 'Queued', 'missing', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z')",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .execute(&pool)
+    .await
+    .unwrap();
+    let jobs = service.process_queued(&pool, &blobs, 10).await.unwrap();
+    assert_eq!(jobs.len(), 2);
+    assert_eq!(jobs[0].status, "Failed");
+    assert!(jobs[0].error_log.is_some());
+    assert_eq!(jobs[1].id, dry.id);
+    assert_eq!(jobs[1].status, "Completed");
+    assert_eq!(jobs[1].success_records as usize, CHUNK_ROWS * 2 + 10);
+    assert_eq!(jobs[1].duplicate_records, 1);
+
+    let job = import(&pool, &blobs, &csv, request("products", "catalogue.csv")).await;
+    assert_eq!(job.status, "Queued");
+    assert_eq!(job.total_records as usize, CHUNK_ROWS * 2 + 11);
+
+    let jobs = service.process_queued(&pool, &blobs, 1).await.unwrap();
+    assert_eq!(jobs[0].status, "Running");
+    assert_eq!(jobs[0].processed_records as usize, CHUNK_ROWS);
+    let jobs = service.process_queued(&pool, &blobs, 10).await.unwrap();
+    assert_eq!(jobs[0].status, "Completed");
+    assert_eq!(jobs[0].success_records as usize, CHUNK_ROWS * 2 + 10);
+    assert_eq!(jobs[0].duplicate_records, 1);
+    assert!(service.process_queued(&pool, &blobs, 10).await.unwrap().is_empty());
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM products").fetch_one(&pool).await.unwrap();
+    assert_eq!(count as usize, CHUNK_ROWS * 2 + 10);
+}
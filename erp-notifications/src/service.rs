@@ -1,4 +1,5 @@
 use erp_core::BaseEntity;
+use erp_core::events::RealtimeEvent;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
@@ -74,7 +75,11 @@ impl NotificationService {
             updated_at: chrono::Utc::now(),
         };
         
-        self.notification_repo.create(pool, &notification).await
+        let notification = self.notification_repo.create(pool, &notification).await?;
+        if matches!(notification.channel, NotificationChannel::InApp | NotificationChannel::Push) {
+            publish_realtime(&notification);
+        }
+        Ok(notification)
     }
 
     pub async fn send_with_template(
@@ -205,6 +210,20 @@ impl NotificationService {
     }
 }
 
+fn publish_realtime(notification: &Notification) {
+    let event = RealtimeEvent::new("notification", &notification.title, &notification.body)
+        .for_users([notification.user_id])
+        .with_data(serde_json::json!({
+            "notification_id": notification.base.id,
+            "notification_type": notification.notification_type,
+            "priority": notification.priority,
+            "action_url": notification.action_url,
+            "action_text": notification.action_text,
+            "data": notification.data,
+        }));
+    erp_core::events::publish(event);
+}
+
 fn render_template(template: &str, variables: &serde_json::Value) -> anyhow::Result<String> {
     let mut result = template.to_string();
     if let serde_json::Value::Object(map) = variables {
@@ -0,0 +1,208 @@
+//! Engines that read the text of a captured document, with where each piece of text sits on
+//! its page.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+
+#[async_trait]
+pub trait OcrEngine: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn accepts(&self, mime_type: &str) -> bool;
+
+    /// The text of `content`, or `None` when the engine finds nothing it can read.
+    async fn recognize(&self, content: &[u8], mime_type: &str, language: &str) -> Result<Option<RecognizedDocument>>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecognizedDocument {
+    pub engine: String,
+    pub pages: Vec<RecognizedPage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecognizedPage {
+    pub width: f32,
+    pub height: f32,
+    pub spans: Vec<TextSpan>,
+}
+
+/// A run of text. Positions are in page units, measured from the top left corner.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextSpan {
+    pub text: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub confidence: f64,
+}
+
+/// Spans that share a baseline, merged into phrases wherever the gap between them is no
+/// wider than a space or two.
+#[derive(Debug, Clone)]
+pub struct TextLine {
+    pub page: usize,
+    pub y: f32,
+    pub height: f32,
+    pub phrases: Vec<TextSpan>,
+}
+
+impl TextLine {
+    pub fn text(&self) -> String {
+        self.phrases.iter().map(|p| p.text.as_str()).collect::<Vec<_>>().join(" ")
+    }
+
+    pub fn confidence(&self) -> f64 {
+        self.phrases.iter().map(|p| p.confidence).fold(1.0, f64::min)
+    }
+}
+
+impl RecognizedDocument {
+    pub fn text(&self) -> String {
+        self.lines().iter().map(TextLine::text).collect::<Vec<_>>().join("\n")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pages.iter().all(|p| p.spans.iter().all(|s| s.text.trim().is_empty()))
+    }
+
+    /// Every line of every page, top to bottom.
+    pub fn lines(&self) -> Vec<TextLine> {
+        let mut lines = Vec::new();
+        for (page_index, page) in self.pages.iter().enumerate() {
+            let mut spans: Vec<&TextSpan> = page.spans.iter().filter(|s| !s.text.trim().is_empty()).collect();
+            spans.sort_by(|a, b| (a.y + a.height).total_cmp(&(b.y + b.height)).then(a.x.total_cmp(&b.x)));
+            let mut page_lines: Vec<(f32, f32, Vec<&TextSpan>)> = Vec::new();
+            for span in spans {
+                let baseline = span.y + span.height;
+                match page_lines.iter_mut().find(|(b, h, _)| (baseline - *b).abs() <= h.min(span.height) * 0.5) {
+                    Some(line) => line.2.push(span),
+                    None => page_lines.push((baseline, span.height, vec![span])),
+                }
+            }
+            for (baseline, height, mut spans) in page_lines {
+                spans.sort_by(|a, b| a.x.total_cmp(&b.x));
+                let mut phrases: Vec<TextSpan> = Vec::new();
+                for span in spans {
+                    match phrases.last_mut() {
+                        Some(last) if span.x - (last.x + last.width) <= span.height.max(last.height) * 0.8 => {
+                            // Kerned pieces of one word sit almost touching; words have a space between.
+                            let gap = span.x - (last.x + last.width);
+                            let separator = if gap > span.height * 0.15 { " " } else { "" };
+                            last.text = format!("{}{}{}", last.text.trim_end(), separator, span.text.trim_start());
+                            last.width = (span.x + span.width).max(last.x + last.width) - last.x;
+                            last.confidence = last.confidence.min(span.confidence);
+                        }
+                        _ => phrases.push(TextSpan { text: span.text.trim().to_string(), ..span.clone() }),
+                    }
+                }
+                lines.push(TextLine { page: page_index, y: baseline - height, height, phrases });
+            }
+        }
+        lines
+    }
+}
+
+/// Reads images with a locally installed Tesseract.
+pub struct TesseractEngine {
+    program: PathBuf,
+}
+
+impl TesseractEngine {
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        Self { program: program.into() }
+    }
+
+    /// The program named by `OCR_TESSERACT_PATH`, else `tesseract` if it is on the `PATH`.
+    pub fn detect() -> Option<Self> {
+        if let Ok(path) = std::env::var("OCR_TESSERACT_PATH") {
+            return Some(Self::new(path));
+        }
+        std::env::split_paths(&std::env::var_os("PATH")?)
+            .map(|dir| dir.join("tesseract"))
+            .find(|path| path.is_file())
+            .map(Self::new)
+    }
+
+    /// Tesseract's three-letter code for a two-letter language.
+    fn language_code(language: &str) -> &str {
+        match language {
+            "en" => "eng",
+            "de" => "deu",
+            "fr" => "fra",
+            "es" => "spa",
+            "it" => "ita",
+            "nl" => "nld",
+            "pt" => "por",
+            other => other,
+        }
+    }
+}
+
+#[async_trait]
+impl OcrEngine for TesseractEngine {
+    fn name(&self) -> &'static str {
+        "tesseract"
+    }
+
+    fn accepts(&self, mime_type: &str) -> bool {
+        matches!(mime_type, "image/png" | "image/jpeg" | "image/tiff" | "image/bmp" | "image/gif" | "image/webp")
+    }
+
+    async fn recognize(&self, content: &[u8], _mime_type: &str, language: &str) -> Result<Option<RecognizedDocument>> {
+        let mut child = tokio::process::Command::new(&self.program)
+            .args(["stdin", "stdout", "-l", Self::language_code(language), "tsv"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let mut stdin = child.stdin.take().ok_or_else(|| anyhow::anyhow!("Tesseract has no input"))?;
+        let input = content.to_vec();
+        let writer = tokio::spawn(async move {
+            let result = stdin.write_all(&input).await;
+            drop(stdin);
+            result
+        });
+        let output = child.wait_with_output().await?;
+        writer.await??;
+        if !output.status.success() {
+            anyhow::bail!("Tesseract failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        let document = parse_tesseract_tsv(&String::from_utf8_lossy(&output.stdout));
+        Ok((!document.is_empty()).then_some(document))
+    }
+}
+
+/// Tesseract's TSV output: one row per page, block, paragraph, line and word.
+pub fn parse_tesseract_tsv(tsv: &str) -> RecognizedDocument {
+    let mut pages: Vec<RecognizedPage> = Vec::new();
+    for row in tsv.lines().skip(1) {
+        let columns: Vec<&str> = row.split('\t').collect();
+        if columns.len() < 12 {
+            continue;
+        }
+        let number = |i: usize| columns[i].trim().parse::<f32>().unwrap_or(0.0);
+        match columns[0] {
+            "1" => pages.push(RecognizedPage { width: number(8), height: number(9), spans: Vec::new() }),
+            "5" if !columns[11].trim().is_empty() => {
+                if let Some(page) = pages.last_mut() {
+                    page.spans.push(TextSpan {
+                        text: columns[11].trim().to_string(),
+                        x: number(6),
+                        y: number(7),
+                        width: number(8),
+                        height: number(9),
+                        confidence: (number(10) as f64 / 100.0).clamp(0.0, 1.0),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    RecognizedDocument { engine: "tesseract".to_string(), pages }
+}
@@ -0,0 +1,439 @@
+//! Finds field values in recognized text: next to an anchor label, inside a page region, or
+//! wherever a pattern matches.
+
+use chrono::NaiveDate;
+use erp_core::Error;
+use regex::{Regex, RegexBuilder};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+use crate::engine::{RecognizedDocument, TextLine, TextSpan};
+use crate::models::{DataType, FieldMapping, Region, TransformType, ValidationRuleType};
+
+/// Currency symbols and the codes they stand for.
+const SYMBOLS: [(&str, &str); 4] = [("$", "USD"), ("€", "EUR"), ("£", "GBP"), ("¥", "JPY")];
+
+const CODES: [&str; 20] = [
+    "USD", "EUR", "GBP", "JPY", "CHF", "CAD", "AUD", "NZD", "CNY", "INR",
+    "SEK", "NOK", "DKK", "PLN", "MXN", "BRL", "ZAR", "SGD", "HKD", "CZK",
+];
+
+#[derive(Debug, Default)]
+pub struct Extraction {
+    pub values: Map<String, Value>,
+    pub confidence: HashMap<String, f64>,
+    pub errors: Vec<String>,
+    /// The currency printed with the first amount found.
+    pub currency: Option<String>,
+}
+
+impl Extraction {
+    pub fn set(&mut self, field: &str, value: Value, confidence: f64) {
+        self.values.insert(field.to_string(), value);
+        self.confidence.insert(field.to_string(), confidence);
+    }
+
+    pub fn str(&self, field: &str) -> Option<&str> {
+        self.values.get(field).and_then(Value::as_str)
+    }
+
+    pub fn amount(&self, field: &str) -> Option<i64> {
+        self.values.get(field).and_then(Value::as_i64)
+    }
+}
+
+pub struct FieldExtractor<'a> {
+    document: &'a RecognizedDocument,
+    lines: &'a [TextLine],
+    /// Lines that belong to a line-item table, which labels such as "Total" must not match.
+    skip: &'a HashSet<usize>,
+}
+
+impl<'a> FieldExtractor<'a> {
+    pub fn new(document: &'a RecognizedDocument, lines: &'a [TextLine], skip: &'a HashSet<usize>) -> Self {
+        Self { document, lines, skip }
+    }
+
+    pub fn extract(&self, mappings: &[FieldMapping], extraction: &mut Extraction) -> erp_core::Result<()> {
+        for mapping in mappings {
+            match self.field(mapping, extraction)? {
+                Some((value, confidence)) => {
+                    for message in validate(mapping, &value) {
+                        extraction.errors.push(message);
+                    }
+                    extraction.set(&mapping.target_field, value, confidence);
+                }
+                None => {
+                    extraction.values.remove(&mapping.target_field);
+                    if mapping.required {
+                        extraction.errors.push(format!("{} was not found", mapping.target_field));
+                        extraction.confidence.insert(mapping.target_field.clone(), 0.0);
+                    } else {
+                        extraction.confidence.remove(&mapping.target_field);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn field(&self, mapping: &FieldMapping, extraction: &mut Extraction) -> erp_core::Result<Option<(Value, f64)>> {
+        let pattern = mapping.extraction_pattern.as_deref().map(compile).transpose()?;
+        let mut candidates = Vec::new();
+        if let Some(region) = &mapping.region {
+            candidates.extend(self.in_region(region));
+        }
+        let anchor = match (&mapping.anchor, &mapping.region, &pattern) {
+            (Some(anchor), _, _) => Some(compile(anchor)?),
+            (None, None, None) if !mapping.source_field.trim().is_empty() => Some(compile(&regex::escape(mapping.source_field.trim()))?),
+            _ => None,
+        };
+        match &anchor {
+            Some(anchor) => candidates.extend(self.after_anchor(anchor)),
+            None if mapping.region.is_none() => {
+                candidates.extend(self.unskipped().map(|(_, line)| (line.text(), line.confidence())));
+            }
+            None => {}
+        }
+
+        for (text, confidence) in candidates {
+            let text = match &pattern {
+                Some(pattern) => match pattern.captures(&text) {
+                    Some(captures) => captures.get(1).or_else(|| captures.get(0)).map(|m| m.as_str().to_string()).unwrap_or_default(),
+                    None => continue,
+                },
+                None => text,
+            };
+            let Some(text) = transform(mapping, text)? else { continue };
+            if let Some(value) = convert(&mapping.data_type, &text, extraction) {
+                return Ok(Some((value, confidence)));
+            }
+        }
+        Ok(mapping.default_value.as_ref().and_then(|d| convert(&mapping.data_type, d, extraction)).map(|v| (v, 1.0)))
+    }
+
+    fn unskipped(&self) -> impl Iterator<Item = (usize, &TextLine)> {
+        self.lines.iter().enumerate().filter(|(i, _)| !self.skip.contains(i))
+    }
+
+    fn in_region(&self, region: &Region) -> Option<(String, f64)> {
+        let page = self.document.pages.get(region.page.max(1) - 1)?;
+        let (left, top) = (region.x * page.width, region.y * page.height);
+        let (right, bottom) = (left + region.width * page.width, top + region.height * page.height);
+        let inside: Vec<&TextSpan> = page.spans.iter()
+            .filter(|s| {
+                let (cx, cy) = (s.x + s.width / 2.0, s.y + s.height / 2.0);
+                cx >= left && cx <= right && cy >= top && cy <= bottom
+            })
+            .collect();
+        if inside.is_empty() {
+            return None;
+        }
+        let region_document = RecognizedDocument {
+            engine: self.document.engine.clone(),
+            pages: vec![crate::engine::RecognizedPage {
+                width: page.width,
+                height: page.height,
+                spans: inside.into_iter().cloned().collect(),
+            }],
+        };
+        let lines = region_document.lines();
+        let confidence = lines.iter().map(TextLine::confidence).fold(1.0, f64::min);
+        Some((lines.iter().map(TextLine::text).collect::<Vec<_>>().join("\n"), confidence))
+    }
+
+    /// Text after each match of `anchor`: the rest of its phrase, the next phrase along the
+    /// line, and the phrase under it.
+    fn after_anchor(&self, anchor: &Regex) -> Vec<(String, f64)> {
+        let mut candidates = Vec::new();
+        for (index, line) in self.unskipped() {
+            for (p, phrase) in line.phrases.iter().enumerate() {
+                let Some(found) = anchor.find(&phrase.text) else { continue };
+                let rest = phrase.text[found.end()..].trim_start_matches([':', '#', '-', '.', ' ', '\t']).trim();
+                if !rest.is_empty() {
+                    candidates.push((rest.to_string(), phrase.confidence));
+                }
+                if let Some(next) = line.phrases.get(p + 1) {
+                    candidates.push((next.text.clone(), next.confidence));
+                }
+                if let Some(below) = self.below(index, phrase) {
+                    candidates.push((below.text.clone(), below.confidence));
+                }
+            }
+        }
+        candidates
+    }
+
+    fn below(&self, index: usize, anchor: &TextSpan) -> Option<&TextSpan> {
+        let line = &self.lines[index];
+        let next = self.lines.get(index + 1).filter(|n| {
+            n.page == line.page && !self.skip.contains(&(index + 1)) && n.y - line.y <= line.height * 3.0
+        })?;
+        next.phrases.iter().find(|p| p.x < anchor.x + anchor.width && p.x + p.width > anchor.x)
+    }
+}
+
+fn compile(pattern: &str) -> erp_core::Result<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| Error::validation(format!("Invalid pattern {:?}: {}", pattern, e)))
+}
+
+fn transform(mapping: &FieldMapping, mut text: String) -> erp_core::Result<Option<String>> {
+    for rule in &mapping.transform_rules {
+        text = match &rule.transform_type {
+            TransformType::Uppercase => text.to_uppercase(),
+            TransformType::Lowercase => text.to_lowercase(),
+            TransformType::Trim => text.trim().to_string(),
+            TransformType::Replace(from, to) => text.replace(from.as_str(), to),
+            TransformType::RegexExtract(pattern) => match compile(pattern)?.captures(&text) {
+                Some(c) => c.get(1).or_else(|| c.get(0)).map(|m| m.as_str().to_string()).unwrap_or_default(),
+                None => return Ok(None),
+            },
+            TransformType::DateFormat(format) => match NaiveDate::parse_from_str(text.trim(), format) {
+                Ok(date) => date.format("%Y-%m-%d").to_string(),
+                Err(_) => return Ok(None),
+            },
+            TransformType::NumberFormat(format) => {
+                // The last separator in the sample format, such as `1.234,56`, is the decimal one.
+                let decimal = format.chars().rev().find(|c| *c == '.' || *c == ',').unwrap_or('.');
+                text.chars()
+                    .filter(|c| c.is_ascii_digit() || *c == decimal || *c == '-')
+                    .map(|c| if c == decimal { '.' } else { c })
+                    .collect()
+            }
+            TransformType::CurrencyToCents => match parse_amount(&text) {
+                Some((cents, _)) => format!("{}.{:02}", cents / 100, (cents % 100).abs()),
+                None => return Ok(None),
+            },
+            TransformType::Custom(_) => text,
+        };
+    }
+    Ok(Some(text))
+}
+
+fn convert(data_type: &DataType, text: &str, extraction: &mut Extraction) -> Option<Value> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    match data_type {
+        DataType::String | DataType::Address => Some(Value::String(text.to_string())),
+        DataType::Number => parse_number(text).map(Value::from),
+        DataType::Percentage => parse_number(text.trim_end_matches('%').trim()).map(Value::from),
+        DataType::Currency => {
+            let (cents, currency) = parse_amount(text)?;
+            if extraction.currency.is_none() {
+                extraction.currency = currency.map(str::to_string);
+            }
+            Some(Value::from(cents))
+        }
+        DataType::Date => parse_date(text).map(|d| Value::String(d.format("%Y-%m-%d").to_string())),
+        DataType::Boolean => match text.to_lowercase().as_str() {
+            "yes" | "y" | "true" | "x" | "1" => Some(Value::Bool(true)),
+            "no" | "n" | "false" | "0" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        DataType::Email => Regex::new(r"[\w.+-]+@[\w-]+(\.[\w-]+)+").ok()?.find(text).map(|m| Value::String(m.as_str().to_lowercase())),
+        DataType::Phone => {
+            let phone = Regex::new(r"\+?\d[\d\s().-]{5,}\d").ok()?.find(text)?.as_str().to_string();
+            (phone.chars().filter(char::is_ascii_digit).count() >= 7).then_some(Value::String(phone))
+        }
+    }
+}
+
+fn validate(mapping: &FieldMapping, value: &Value) -> Vec<String> {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let number = value.as_f64();
+    let mut errors = Vec::new();
+    for rule in &mapping.validation_rules {
+        let valid = match &rule.rule_type {
+            ValidationRuleType::Required => !text.is_empty(),
+            ValidationRuleType::MinLength(n) => text.chars().count() >= *n as usize,
+            ValidationRuleType::MaxLength(n) => text.chars().count() <= *n as usize,
+            ValidationRuleType::MinValue(min) => number.is_some_and(|v| v >= *min),
+            ValidationRuleType::MaxValue(max) => number.is_some_and(|v| v <= *max),
+            ValidationRuleType::Pattern(p) => Regex::new(p).map(|r| r.is_match(&text)).unwrap_or(false),
+            ValidationRuleType::InList(options) => options.iter().any(|o| o.eq_ignore_ascii_case(&text)),
+            ValidationRuleType::Custom(_) => true,
+        };
+        if !valid {
+            errors.push(if rule.error_message.is_empty() {
+                format!("{} is not valid", mapping.target_field)
+            } else {
+                rule.error_message.clone()
+            });
+        }
+    }
+    errors
+}
+
+/// An amount as printed, in hundredths, with the currency printed beside it. Thousands may be
+/// grouped with commas, points, spaces or apostrophes. Parentheses or a minus mean negative.
+pub fn parse_amount(text: &str) -> Option<(i64, Option<&'static str>)> {
+    let mut rest = text.trim().to_string();
+    let mut currency = None;
+    for (symbol, code) in SYMBOLS {
+        if rest.contains(symbol) {
+            rest = rest.replace(symbol, " ");
+            currency = Some(code);
+        }
+    }
+    for code in CODES {
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        if words.iter().any(|w| w.eq_ignore_ascii_case(code)) {
+            rest = words.into_iter().filter(|w| !w.eq_ignore_ascii_case(code)).collect::<Vec<_>>().join(" ");
+            currency = Some(code);
+        }
+    }
+    let mut rest = rest.trim();
+    let mut negative = false;
+    if let Some(inner) = rest.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+        negative = true;
+        rest = inner.trim();
+    }
+    if let Some(unsigned) = rest.strip_prefix('-').or_else(|| rest.strip_suffix('-')) {
+        negative = true;
+        rest = unsigned.trim();
+    }
+    let shape = Regex::new(r"^(\d{1,3}(?:[ ,.'’]\d{3})+|\d+)(?:[.,](\d{1,2}))?$").ok()?;
+    let captures = shape.captures(rest)?;
+    let whole: i64 = captures[1].chars().filter(char::is_ascii_digit).collect::<String>().parse().ok()?;
+    let fraction = captures.get(2).map(|f| format!("{:0<2}", f.as_str())).unwrap_or_else(|| "00".to_string());
+    let cents = whole.checked_mul(100)? + fraction.parse::<i64>().ok()?;
+    Some((if negative { -cents } else { cents }, currency))
+}
+
+/// A quantity or rate. A single comma before exactly three digits groups thousands; any other
+/// lone separator is the decimal point.
+pub fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim().replace([' ', '\''], "");
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest.to_string()),
+        None => (false, text),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit() || c == '.' || c == ',') {
+        return None;
+    }
+    let last_point = digits.rfind('.');
+    let last_comma = digits.rfind(',');
+    let decimal = match (last_point, last_comma) {
+        (Some(p), Some(c)) => Some(if p > c { '.' } else { ',' }),
+        (None, Some(c)) if digits.matches(',').count() == 1 && digits.len() - c - 1 != 3 => Some(','),
+        (Some(_), None) if digits.matches('.').count() == 1 => Some('.'),
+        _ => None,
+    };
+    let normalized: String = digits.chars()
+        .filter_map(|c| match c {
+            '.' | ',' if Some(c) == decimal => Some('.'),
+            '.' | ',' => None,
+            d => Some(d),
+        })
+        .collect();
+    let value: f64 = normalized.parse().ok()?;
+    Some(if negative { -value } else { value })
+}
+
+/// A date written in any of the usual ways. Slashed dates are read month first unless the
+/// first number cannot be a month.
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    let iso = Regex::new(r"(\d{4})[-/.](\d{1,2})[-/.](\d{1,2})").ok()?;
+    if let Some(c) = iso.captures(text) {
+        return NaiveDate::from_ymd_opt(c[1].parse().ok()?, c[2].parse().ok()?, c[3].parse().ok()?);
+    }
+    let numeric = Regex::new(r"\b(\d{1,2})([./-])(\d{1,2})[./-](\d{2,4})\b").ok()?;
+    if let Some(c) = numeric.captures(text) {
+        let (first, second): (u32, u32) = (c[1].parse().ok()?, c[3].parse().ok()?);
+        let mut year: i32 = c[4].parse().ok()?;
+        if year < 100 {
+            year += 2000;
+        }
+        let (day, month) = if &c[2] == "/" && first <= 12 { (second, first) } else { (first, second) };
+        return NaiveDate::from_ymd_opt(year, month, day);
+    }
+    let words = text.replace([',', '.'], " ");
+    let words = words.split_whitespace().collect::<Vec<_>>().join(" ");
+    for format in ["%d %B %Y", "%d %b %Y", "%B %d %Y", "%b %d %Y"] {
+        for start in 0..words.len() {
+            if !words.is_char_boundary(start) || (start > 0 && !words[..start].ends_with(' ')) {
+                continue;
+            }
+            if let Ok((date, _)) = NaiveDate::parse_and_remainder(&words[start..], format) {
+                return Some(date);
+            }
+        }
+    }
+    None
+}
+
+/// The currency most often printed in `text`.
+pub fn detect_currency(text: &str) -> Option<&'static str> {
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+    for (symbol, code) in SYMBOLS {
+        *counts.entry(code).or_default() += text.matches(symbol).count();
+    }
+    for word in text.split(|c: char| !c.is_ascii_alphabetic()) {
+        if let Some(code) = CODES.iter().find(|c| **c == word) {
+            *counts.entry(code).or_default() += 1;
+        }
+    }
+    counts.into_iter().filter(|(_, n)| *n > 0).max_by_key(|(code, n)| (*n, std::cmp::Reverse(*code))).map(|(code, _)| code)
+}
+
+fn mapping(target: &str, anchor: &str, data_type: DataType, required: bool) -> FieldMapping {
+    FieldMapping {
+        source_field: target.replace('_', " "),
+        target_field: target.to_string(),
+        anchor: Some(anchor.to_string()),
+        region: None,
+        extraction_pattern: None,
+        data_type,
+        required,
+        validation_rules: Vec::new(),
+        default_value: None,
+        transform_rules: Vec::new(),
+    }
+}
+
+/// Labels that most invoices print their key fields under.
+pub fn invoice_fields() -> Vec<FieldMapping> {
+    vec![
+        FieldMapping {
+            extraction_pattern: Some(r"^([A-Z0-9][A-Z0-9/_.-]*\d[A-Z0-9/_.-]*)$".to_string()),
+            ..mapping("invoice_number", r"invoice\s*(number|no\b\.?|#)|^invoice\b|inv\s*#", DataType::String, true)
+        },
+        mapping("invoice_date", r"invoice\s+date|date\s+of\s+issue|issue\s+date|^date\b", DataType::Date, true),
+        mapping("due_date", r"due\s+date|payment\s+due|due\s+by", DataType::Date, false),
+        mapping("payment_terms", r"payment\s+terms|^terms\b", DataType::String, false),
+        mapping("subtotal", r"sub-?\s*total|net\s+(amount|total)", DataType::Currency, false),
+        mapping("tax_amount", r"^(sales\s+)?(tax|vat|gst)\b", DataType::Currency, false),
+        mapping("total_amount", r"total\s+due|amount\s+due|balance\s+due|grand\s+total|invoice\s+total|^total\b", DataType::Currency, true),
+        mapping("vendor_name", r"^(from|vendor|supplier|seller|sold\s+by)\b", DataType::String, false),
+    ]
+}
+
+pub fn receipt_fields() -> Vec<FieldMapping> {
+    vec![
+        mapping("merchant_name", r"^(store|merchant|shop)\b", DataType::String, false),
+        mapping("receipt_date", r"^date\b", DataType::Date, true),
+        mapping("receipt_number", r"receipt\s*(number|no\b\.?|#)", DataType::String, false),
+        mapping("tax_amount", r"^(sales\s+)?(tax|vat|gst)\b", DataType::Currency, false),
+        mapping("total_amount", r"^total\b|amount\s+paid", DataType::Currency, true),
+    ]
+}
+
+/// The most prominent text near the top of the first page that is not a document title,
+/// which is usually the issuer's name.
+pub fn letterhead(lines: &[TextLine], page_height: f32) -> Option<(String, f64)> {
+    let title = Regex::new(r"(?i)\b(invoice|receipt|bill|statement|credit\s+note|page|date)\b").ok()?;
+    lines.iter()
+        .filter(|l| l.page == 0 && l.y <= page_height / 4.0)
+        .flat_map(|l| l.phrases.iter())
+        .filter(|p| p.text.chars().any(char::is_alphabetic) && !title.is_match(&p.text))
+        .max_by(|a, b| a.height.total_cmp(&b.height).then(b.y.total_cmp(&a.y)))
+        .map(|p| (p.text.clone(), p.confidence.min(0.7)))
+}
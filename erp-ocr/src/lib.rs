@@ -1,6 +1,14 @@
+pub mod engine;
+pub mod extraction;
+pub mod matching;
 pub mod models;
+pub mod pdf;
 pub mod repository;
 pub mod service;
+pub mod tables;
 
+pub use engine::{OcrEngine, RecognizedDocument, RecognizedPage, TesseractEngine, TextLine, TextSpan};
+pub use matching::VendorMatch;
 pub use models::*;
+pub use pdf::PdfTextEngine;
 pub use service::*;
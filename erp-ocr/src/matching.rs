@@ -0,0 +1,183 @@
+//! Finds which purchasing vendor issued a document.
+
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Mail domains anyone can sign up to, which say nothing about who sent a document.
+const FREE_MAIL: [&str; 10] = [
+    "gmail.com", "googlemail.com", "yahoo.com", "hotmail.com", "outlook.com",
+    "live.com", "icloud.com", "aol.com", "proton.me", "protonmail.com",
+];
+
+const LEGAL_FORMS: [&str; 16] = [
+    "inc", "incorporated", "llc", "ltd", "limited", "corp", "corporation", "co",
+    "company", "gmbh", "ag", "plc", "sa", "sarl", "bv", "pty",
+];
+
+/// A runner-up this close to the best score makes the match ambiguous.
+const AMBIGUITY_MARGIN: f64 = 0.05;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VendorMatch {
+    pub vendor_id: Uuid,
+    pub name: String,
+    /// Days until payment is due.
+    pub payment_terms: i64,
+    pub score: f64,
+    /// What identified the vendor: `name`, `domain`, `phone` or `template`.
+    pub matched_on: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct VendorRow {
+    id: String,
+    name: String,
+    email: Option<String>,
+    phone: Option<String>,
+    website: Option<String>,
+    payment_terms: i64,
+}
+
+impl VendorRow {
+    fn into_match(self, score: f64, matched_on: &str) -> Result<VendorMatch> {
+        Ok(VendorMatch {
+            vendor_id: erp_core::parse_uuid(&self.id, "vendor_id")?,
+            name: self.name,
+            payment_terms: self.payment_terms,
+            score,
+            matched_on: matched_on.to_string(),
+        })
+    }
+}
+
+pub async fn vendor(pool: &SqlitePool, id: Uuid) -> Result<Option<VendorMatch>> {
+    vendor_in(&mut *pool.acquire().await?, id).await
+}
+
+pub async fn vendor_in(conn: &mut SqliteConnection, id: Uuid) -> Result<Option<VendorMatch>> {
+    let row = sqlx::query_as::<_, VendorRow>(
+        "SELECT id, name, email, phone, website, payment_terms FROM vendors WHERE id = ?",
+    )
+    .bind(id.to_string())
+    .fetch_optional(conn)
+    .await?;
+    row.map(|r| r.into_match(1.0, "template")).transpose()
+}
+
+/// The active vendor that `text` most likely came from. `name` is the issuer name read from
+/// the document, if any.
+pub async fn match_vendor(pool: &SqlitePool, text: &str, name: Option<&str>) -> Result<Option<VendorMatch>> {
+    let rows = sqlx::query_as::<_, VendorRow>(
+        "SELECT id, name, email, phone, website, payment_terms FROM vendors WHERE status = 'Active'",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let document = Document::new(text, name);
+    let mut scored: Vec<(f64, &'static str, VendorRow)> = rows.into_iter()
+        .filter_map(|row| document.score(&row).map(|(score, on)| (score, on, row)))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let mut scored = scored.into_iter();
+    let Some((mut score, matched_on, best)) = scored.next() else {
+        return Ok(None);
+    };
+    if scored.next().is_some_and(|(runner_up, _, _)| score - runner_up <= AMBIGUITY_MARGIN) {
+        score = score.min(0.5);
+    }
+    best.into_match(score, matched_on).map(Some)
+}
+
+struct Document {
+    text: String,
+    /// Normalized words, padded with spaces so whole names can be searched for.
+    words: String,
+    name: Option<String>,
+    phones: HashSet<String>,
+}
+
+impl Document {
+    fn new(text: &str, name: Option<&str>) -> Self {
+        let phone = Regex::new(r"\+?\d[\d\s().-]{5,}\d").expect("valid pattern");
+        Self {
+            text: text.to_lowercase(),
+            words: format!(" {} ", normalize(text)),
+            name: name.map(normalize).filter(|n| !n.is_empty()),
+            phones: phone.find_iter(text).filter_map(|m| phone_key(m.as_str())).collect(),
+        }
+    }
+
+    /// The best evidence that the document is from `vendor`, if there is any worth having.
+    fn score(&self, vendor: &VendorRow) -> Option<(f64, &'static str)> {
+        let name = normalize(&vendor.name);
+        let mut best: Option<(f64, &'static str)> = None;
+        let mut consider = |score: f64, on: &'static str| {
+            if best.is_none_or(|(b, _)| score > b) {
+                best = Some((score, on));
+            }
+        };
+        if !name.is_empty() {
+            match &self.name {
+                Some(read) if *read == name => consider(1.0, "name"),
+                Some(read) => {
+                    let similarity = dice(read, &name);
+                    if similarity >= 0.5 {
+                        consider(similarity * 0.9, "name");
+                    }
+                }
+                None => {}
+            }
+            if name.len() >= 4 && self.words.contains(&format!(" {} ", name)) {
+                consider(0.9, "name");
+            }
+        }
+        let domains = [vendor.email.as_deref().and_then(|e| e.split('@').nth(1)), vendor.website.as_deref().map(site_domain)];
+        for domain in domains.into_iter().flatten() {
+            let domain = domain.trim().to_lowercase();
+            if domain.contains('.') && !FREE_MAIL.contains(&domain.as_str()) && self.text.contains(&domain) {
+                consider(0.95, "domain");
+            }
+        }
+        if let Some(phone) = vendor.phone.as_deref().and_then(phone_key) {
+            if self.phones.contains(&phone) {
+                consider(0.9, "phone");
+            }
+        }
+        best.filter(|(score, _)| *score >= 0.5)
+    }
+}
+
+/// Lowercase words without punctuation or legal forms such as "Inc." or "GmbH".
+fn normalize(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '&')
+        .filter(|w| !w.is_empty() && !LEGAL_FORMS.contains(w))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn site_domain(website: &str) -> &str {
+    let host = website.split("://").last().unwrap_or(website);
+    let host = host.split(['/', '?', '#']).next().unwrap_or(host);
+    host.strip_prefix("www.").unwrap_or(host)
+}
+
+/// The last ten digits, which ignores how the country and area codes were written.
+fn phone_key(phone: &str) -> Option<String> {
+    let digits: String = phone.chars().filter(char::is_ascii_digit).collect();
+    (digits.len() >= 7).then(|| digits[digits.len().saturating_sub(10)..].to_string())
+}
+
+/// Dice coefficient of the two names' word sets.
+fn dice(a: &str, b: &str) -> f64 {
+    let a: HashSet<&str> = a.split_whitespace().collect();
+    let b: HashSet<&str> = b.split_whitespace().collect();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    2.0 * a.intersection(&b).count() as f64 / (a.len() + b.len()) as f64
+}
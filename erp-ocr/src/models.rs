@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use erp_core::models::BaseEntity;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,13 +12,24 @@ pub struct OcrDocument {
     pub file_path: String,
     pub file_size: i64,
     pub mime_type: String,
+    /// SHA-256 of the stored file.
+    pub checksum: Option<String>,
+    pub template_id: Option<Uuid>,
     pub status: OcrStatus,
     pub processing_started_at: Option<DateTime<Utc>>,
     pub processing_completed_at: Option<DateTime<Utc>>,
     pub confidence_score: Option<f64>,
+    /// The engine that read the text.
+    pub engine: Option<String>,
     pub raw_text: Option<String>,
     pub extracted_data: Option<serde_json::Value>,
+    /// How sure extraction is of each field in `extracted_data`, from 0 to 1.
+    #[serde(default)]
+    pub field_confidence: HashMap<String, f64>,
     pub validation_errors: Vec<String>,
+    pub vendor_id: Option<Uuid>,
+    /// The draft bill the document became.
+    pub vendor_bill_id: Option<Uuid>,
     pub reviewed_by: Option<Uuid>,
     pub reviewed_at: Option<DateTime<Utc>>,
 }
@@ -43,6 +55,8 @@ pub enum DocumentType {
 #[sqlx(type_name = "text")]
 pub enum OcrStatus {
     Pending,
+    /// Waiting for the background worker.
+    Queued,
     Processing,
     Completed,
     Failed,
@@ -64,16 +78,45 @@ pub struct OcrTemplate {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldMapping {
+    /// The label printed next to the value. Used as the anchor when no other is given.
     pub source_field: String,
     pub target_field: String,
+    /// Case-insensitive pattern for the label the value follows, either to its right or on
+    /// the line below.
+    #[serde(default)]
+    pub anchor: Option<String>,
+    /// Where on the page the value is printed.
+    #[serde(default)]
+    pub region: Option<Region>,
+    #[serde(default)]
     pub extraction_pattern: Option<String>,
     pub data_type: DataType,
+    #[serde(default)]
     pub required: bool,
+    #[serde(default)]
     pub validation_rules: Vec<ValidationRule>,
+    #[serde(default)]
     pub default_value: Option<String>,
+    #[serde(default)]
     pub transform_rules: Vec<TransformRule>,
 }
 
+/// A rectangle in fractions of the page size, measured from the top left corner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Region {
+    /// Counted from 1.
+    #[serde(default = "first_page")]
+    pub page: usize,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+fn first_page() -> usize {
+    1
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DataType {
     String,
@@ -90,6 +133,7 @@ pub enum DataType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationRule {
     pub rule_type: ValidationRuleType,
+    #[serde(default)]
     pub parameters: serde_json::Value,
     pub error_message: String,
 }
@@ -109,6 +153,7 @@ pub enum ValidationRuleType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransformRule {
     pub transform_type: TransformType,
+    #[serde(default)]
     pub parameters: serde_json::Value,
 }
 
@@ -210,6 +255,22 @@ pub struct OcrSettings {
     pub retention_days: i32,
 }
 
+impl Default for OcrSettings {
+    fn default() -> Self {
+        Self {
+            default_language: "en".to_string(),
+            auto_detect_language: true,
+            output_format: OutputFormat::Json,
+            enable_table_extraction: true,
+            enable_handwriting_recognition: false,
+            confidence_threshold: 0.8,
+            auto_validate: false,
+            auto_create_entities: true,
+            retention_days: 365,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OutputFormat {
     Json,
@@ -227,6 +288,14 @@ pub struct UploadDocumentRequest {
     pub auto_process: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTemplateRequest {
+    pub name: String,
+    pub document_type: DocumentType,
+    pub vendor_id: Option<Uuid>,
+    pub field_mappings: Vec<FieldMapping>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OcrResult {
     pub document_id: Uuid,
@@ -236,4 +305,5 @@ pub struct OcrResult {
     pub confidence: f64,
     pub validation_errors: Vec<String>,
     pub suggestions: Vec<String>,
+    pub vendor_bill_id: Option<Uuid>,
 }
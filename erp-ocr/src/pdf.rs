@@ -0,0 +1,925 @@
+//! Reads the text layer PDFs carry when they were produced by software rather than scanned.
+//!
+//! Only what finding text needs is parsed: the page tree, content streams (plain or
+//! Flate-compressed, including objects packed in object streams) and font encodings, with
+//! `ToUnicode` maps where fonts have them.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use flate2::read::ZlibDecoder;
+use regex::bytes::Regex;
+use std::collections::HashMap;
+use std::io::Read;
+
+use crate::engine::{OcrEngine, RecognizedDocument, RecognizedPage, TextSpan};
+
+/// Embedded text is exact, unlike text read from pixels.
+const TEXT_LAYER_CONFIDENCE: f64 = 0.99;
+
+pub struct PdfTextEngine;
+
+#[async_trait]
+impl OcrEngine for PdfTextEngine {
+    fn name(&self) -> &'static str {
+        "pdf-text"
+    }
+
+    fn accepts(&self, mime_type: &str) -> bool {
+        mime_type == "application/pdf"
+    }
+
+    async fn recognize(&self, content: &[u8], _mime_type: &str, _language: &str) -> Result<Option<RecognizedDocument>> {
+        let document = extract_text(content)?;
+        Ok((!document.is_empty()).then_some(document))
+    }
+}
+
+pub fn extract_text(content: &[u8]) -> Result<RecognizedDocument> {
+    if !content.starts_with(b"%PDF") {
+        anyhow::bail!("Not a PDF file");
+    }
+    let file = PdfFile::parse(content);
+    let mut pages = Vec::new();
+    for page in file.pages() {
+        pages.push(file.page_text(&page));
+    }
+    Ok(RecognizedDocument { engine: "pdf-text".to_string(), pages })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Object {
+    Null,
+    Bool(bool),
+    Number(f32),
+    Name(String),
+    String(Vec<u8>),
+    Array(Vec<Object>),
+    Dict(HashMap<String, Object>),
+    Ref(u32),
+    Operator(String),
+}
+
+impl Object {
+    fn number(&self) -> Option<f32> {
+        match self {
+            Object::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> Option<&str> {
+        match self {
+            Object::Name(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    fn dict(&self) -> Option<&HashMap<String, Object>> {
+        match self {
+            Object::Dict(d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// `1 0 R` is a reference in the file body, but `R` is never an operator in content.
+    references: bool,
+}
+
+fn is_delimiter(b: u8) -> bool {
+    matches!(b, b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%')
+}
+
+fn is_space(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r' | b'\x0c' | b'\0')
+}
+
+impl<'a> Parser<'a> {
+    fn new(data: &'a [u8], references: bool) -> Self {
+        Self { data, pos: 0, references }
+    }
+
+    fn skip_space(&mut self) {
+        while let Some(&b) = self.data.get(self.pos) {
+            if is_space(b) {
+                self.pos += 1;
+            } else if b == b'%' {
+                while self.data.get(self.pos).is_some_and(|&b| b != b'\n' && b != b'\r') {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn token(&mut self) -> &'a [u8] {
+        let start = self.pos;
+        while self.data.get(self.pos).is_some_and(|&b| !is_space(b) && !is_delimiter(b)) {
+            self.pos += 1;
+        }
+        &self.data[start..self.pos]
+    }
+
+    fn next(&mut self) -> Option<Object> {
+        self.skip_space();
+        let b = *self.data.get(self.pos)?;
+        match b {
+            b'/' => {
+                self.pos += 1;
+                Some(Object::Name(decode_name(self.token())))
+            }
+            b'(' => Some(Object::String(self.literal_string())),
+            b'<' if self.data.get(self.pos + 1) == Some(&b'<') => {
+                self.pos += 2;
+                let mut dict = HashMap::new();
+                loop {
+                    self.skip_space();
+                    if self.pos >= self.data.len() {
+                        return Some(Object::Dict(dict));
+                    }
+                    if self.data[self.pos..].starts_with(b">>") {
+                        self.pos += 2;
+                        return Some(Object::Dict(dict));
+                    }
+                    match self.next()? {
+                        Object::Name(key) => {
+                            let value = self.next()?;
+                            dict.insert(key, value);
+                        }
+                        _ => continue,
+                    }
+                }
+            }
+            b'<' => Some(Object::String(self.hex_string())),
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_space();
+                    match self.data.get(self.pos) {
+                        None => return Some(Object::Array(items)),
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Some(Object::Array(items));
+                        }
+                        _ => items.push(self.next()?),
+                    }
+                }
+            }
+            b']' | b'>' | b')' | b'{' | b'}' => {
+                self.pos += 1;
+                self.next()
+            }
+            b'0'..=b'9' | b'+' | b'-' | b'.' => {
+                let token = self.token();
+                let number = std::str::from_utf8(token).ok().and_then(|t| t.parse::<f32>().ok()).unwrap_or(0.0);
+                if self.references && token.iter().all(u8::is_ascii_digit) {
+                    let saved = self.pos;
+                    self.skip_space();
+                    let generation = self.token();
+                    self.skip_space();
+                    if !generation.is_empty() && generation.iter().all(u8::is_ascii_digit) && self.token() == b"R" {
+                        return Some(Object::Ref(number as u32));
+                    }
+                    self.pos = saved;
+                }
+                Some(Object::Number(number))
+            }
+            _ => {
+                let token = self.token();
+                if token.is_empty() {
+                    self.pos += 1;
+                    return self.next();
+                }
+                Some(match token {
+                    b"true" => Object::Bool(true),
+                    b"false" => Object::Bool(false),
+                    b"null" => Object::Null,
+                    other => Object::Operator(String::from_utf8_lossy(other).into_owned()),
+                })
+            }
+        }
+    }
+
+    fn literal_string(&mut self) -> Vec<u8> {
+        self.pos += 1;
+        let mut out = Vec::new();
+        let mut depth = 1;
+        while let Some(&b) = self.data.get(self.pos) {
+            self.pos += 1;
+            match b {
+                b'(' => {
+                    depth += 1;
+                    out.push(b);
+                }
+                b')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                    out.push(b);
+                }
+                b'\\' => {
+                    let Some(&escaped) = self.data.get(self.pos) else { break };
+                    self.pos += 1;
+                    match escaped {
+                        b'n' => out.push(b'\n'),
+                        b'r' => out.push(b'\r'),
+                        b't' => out.push(b'\t'),
+                        b'b' => out.push(8),
+                        b'f' => out.push(12),
+                        b'\r' => {
+                            if self.data.get(self.pos) == Some(&b'\n') {
+                                self.pos += 1;
+                            }
+                        }
+                        b'\n' => {}
+                        b'0'..=b'7' => {
+                            let mut value = (escaped - b'0') as u32;
+                            for _ in 0..2 {
+                                match self.data.get(self.pos) {
+                                    Some(&d @ b'0'..=b'7') => {
+                                        value = value * 8 + (d - b'0') as u32;
+                                        self.pos += 1;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                            out.push(value as u8);
+                        }
+                        other => out.push(other),
+                    }
+                }
+                _ => out.push(b),
+            }
+        }
+        out
+    }
+
+    fn hex_string(&mut self) -> Vec<u8> {
+        self.pos += 1;
+        let mut digits = Vec::new();
+        while let Some(&b) = self.data.get(self.pos) {
+            self.pos += 1;
+            match b {
+                b'>' => break,
+                b if b.is_ascii_hexdigit() => digits.push(b),
+                _ => {}
+            }
+        }
+        if digits.len() % 2 == 1 {
+            digits.push(b'0');
+        }
+        digits.chunks(2)
+            .filter_map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+            .collect()
+    }
+}
+
+fn decode_name(raw: &[u8]) -> String {
+    let mut out = Vec::with_capacity(raw.len());
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] == b'#' {
+            if let Some(b) = raw.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok()) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(raw[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+struct Stored {
+    value: Object,
+    stream: Option<Vec<u8>>,
+}
+
+struct PdfFile {
+    objects: HashMap<u32, Stored>,
+}
+
+struct Page {
+    media_box: [f32; 4],
+    resources: HashMap<String, Object>,
+    contents: Vec<u8>,
+}
+
+impl PdfFile {
+    fn parse(data: &[u8]) -> Self {
+        let header = Regex::new(r"(\d+)\s+\d+\s+obj\b").expect("valid pattern");
+        let stream_end = Regex::new(r"endstream").expect("valid pattern");
+        let mut objects = HashMap::new();
+        let mut pos = 0;
+        while let Some(found) = header.captures_at(data, pos) {
+            let whole = found.get(0).expect("match");
+            let number = std::str::from_utf8(&found[1]).ok().and_then(|n| n.parse::<u32>().ok()).unwrap_or(0);
+            let mut parser = Parser::new(data, true);
+            parser.pos = whole.end();
+            let Some(value) = parser.next() else { break };
+            parser.skip_space();
+            let mut stream = None;
+            if data[parser.pos..].starts_with(b"stream") {
+                let mut start = parser.pos + b"stream".len();
+                if data.get(start) == Some(&b'\r') {
+                    start += 1;
+                }
+                if data.get(start) == Some(&b'\n') {
+                    start += 1;
+                }
+                let declared = value.dict().and_then(|d| d.get("Length")).and_then(Object::number).map(|n| n as usize);
+                let end = match declared {
+                    Some(length) if data.get(start + length..).is_some_and(|rest| {
+                        let rest = &rest[..rest.len().min(16)];
+                        rest.windows(9).any(|w| w == b"endstream")
+                    }) => start + length,
+                    _ => stream_end.find_at(data, start).map(|m| m.start()).unwrap_or(data.len()),
+                };
+                stream = Some(data[start..end].to_vec());
+                parser.pos = end;
+            }
+            pos = parser.pos.max(whole.end());
+            objects.insert(number, Stored { value, stream });
+        }
+
+        let mut file = Self { objects };
+        file.unpack_object_streams();
+        file
+    }
+
+    /// Objects packed into `/Type /ObjStm` streams, added where the file body has no copy.
+    fn unpack_object_streams(&mut self) {
+        let mut unpacked = Vec::new();
+        for stored in self.objects.values() {
+            let Some(dict) = stored.value.dict() else { continue };
+            if dict.get("Type").and_then(Object::name) != Some("ObjStm") {
+                continue;
+            }
+            let Some(data) = self.decode(stored) else { continue };
+            let count = dict.get("N").and_then(Object::number).unwrap_or(0.0) as usize;
+            let first = dict.get("First").and_then(Object::number).unwrap_or(0.0) as usize;
+            let mut index = Parser::new(&data, false);
+            let mut entries = Vec::new();
+            for _ in 0..count {
+                let (Some(Object::Number(number)), Some(Object::Number(offset))) = (index.next(), index.next()) else { break };
+                entries.push((number as u32, first + offset as usize));
+            }
+            for (number, offset) in entries {
+                if offset >= data.len() {
+                    continue;
+                }
+                let mut parser = Parser::new(&data, true);
+                parser.pos = offset;
+                if let Some(value) = parser.next() {
+                    unpacked.push((number, value));
+                }
+            }
+        }
+        for (number, value) in unpacked {
+            self.objects.entry(number).or_insert(Stored { value, stream: None });
+        }
+    }
+
+    fn resolve<'a>(&'a self, object: &'a Object) -> &'a Object {
+        let mut current = object;
+        for _ in 0..32 {
+            match current {
+                Object::Ref(number) => match self.objects.get(number) {
+                    Some(stored) => current = &stored.value,
+                    None => return &Object::Null,
+                },
+                _ => return current,
+            }
+        }
+        &Object::Null
+    }
+
+    fn decode(&self, stored: &Stored) -> Option<Vec<u8>> {
+        let raw = stored.stream.as_ref()?;
+        let dict = stored.value.dict()?;
+        let filters = match dict.get("Filter").map(|f| self.resolve(f)) {
+            None => Vec::new(),
+            Some(Object::Name(name)) => vec![name.clone()],
+            Some(Object::Array(items)) => items.iter().filter_map(|i| i.name().map(str::to_string)).collect(),
+            Some(_) => return None,
+        };
+        let mut data = raw.clone();
+        for filter in filters {
+            match filter.as_str() {
+                "FlateDecode" | "Fl" => {
+                    let mut inflated = Vec::new();
+                    // Truncated streams still give up what was readable before the damage.
+                    let _ = ZlibDecoder::new(data.as_slice()).read_to_end(&mut inflated);
+                    if inflated.is_empty() {
+                        return None;
+                    }
+                    data = inflated;
+                }
+                _ => return None,
+            }
+        }
+        Some(data)
+    }
+
+    fn stream_of(&self, object: &Object) -> Option<Vec<u8>> {
+        match object {
+            Object::Ref(number) => self.decode(self.objects.get(number)?),
+            _ => None,
+        }
+    }
+
+    fn root_pages(&self) -> Vec<u32> {
+        let mut roots: Vec<u32> = self.objects.iter()
+            .filter(|(_, s)| {
+                s.value.dict().is_some_and(|d| d.get("Type").and_then(Object::name) == Some("Pages") && !d.contains_key("Parent"))
+            })
+            .map(|(n, _)| *n)
+            .collect();
+        roots.sort();
+        roots
+    }
+
+    /// Pages in reading order, each with what it inherits from the page tree.
+    fn pages(&self) -> Vec<Page> {
+        let mut pages = Vec::new();
+        for root in self.root_pages() {
+            self.collect_pages(&Object::Ref(root), None, None, &mut pages, 0);
+        }
+        if pages.is_empty() {
+            // No usable page tree: fall back to every page object in file order.
+            let mut numbers: Vec<u32> = self.objects.iter()
+                .filter(|(_, s)| s.value.dict().is_some_and(|d| d.get("Type").and_then(Object::name) == Some("Page")))
+                .map(|(n, _)| *n)
+                .collect();
+            numbers.sort();
+            for number in numbers {
+                self.collect_pages(&Object::Ref(number), None, None, &mut pages, 0);
+            }
+        }
+        pages
+    }
+
+    fn collect_pages(
+        &self,
+        node: &Object,
+        media_box: Option<[f32; 4]>,
+        resources: Option<HashMap<String, Object>>,
+        pages: &mut Vec<Page>,
+        depth: usize,
+    ) {
+        let Some(dict) = self.resolve(node).dict() else { return };
+        if depth > 64 {
+            return;
+        }
+        let media_box = match dict.get("MediaBox").map(|m| self.resolve(m)) {
+            Some(Object::Array(values)) if values.len() == 4 => {
+                let v: Vec<f32> = values.iter().map(|v| self.resolve(v).number().unwrap_or(0.0)).collect();
+                Some([v[0], v[1], v[2], v[3]])
+            }
+            _ => media_box,
+        };
+        let resources = dict.get("Resources").and_then(|r| self.resolve(r).dict()).cloned().or(resources);
+        match dict.get("Type").and_then(Object::name) {
+            Some("Pages") => {
+                if let Some(Object::Array(kids)) = dict.get("Kids").map(|k| self.resolve(k)) {
+                    for kid in kids {
+                        self.collect_pages(kid, media_box, resources.clone(), pages, depth + 1);
+                    }
+                }
+            }
+            _ => {
+                let mut contents = Vec::new();
+                let parts = match dict.get("Contents") {
+                    Some(Object::Ref(n)) => match self.objects.get(n).map(|s| &s.value) {
+                        Some(Object::Array(items)) => items.clone(),
+                        _ => vec![Object::Ref(*n)],
+                    },
+                    Some(Object::Array(items)) => items.clone(),
+                    _ => Vec::new(),
+                };
+                for part in parts {
+                    if let Some(data) = self.stream_of(&part) {
+                        contents.extend_from_slice(&data);
+                        contents.push(b'\n');
+                    }
+                }
+                pages.push(Page {
+                    media_box: media_box.unwrap_or([0.0, 0.0, 612.0, 792.0]),
+                    resources: resources.unwrap_or_default(),
+                    contents,
+                });
+            }
+        }
+    }
+
+    fn fonts(&self, resources: &HashMap<String, Object>) -> HashMap<String, FontEncoding> {
+        let mut fonts = HashMap::new();
+        let Some(entries) = resources.get("Font").and_then(|f| self.resolve(f).dict()) else { return fonts };
+        for (name, font) in entries {
+            let Some(dict) = self.resolve(font).dict() else { continue };
+            fonts.insert(name.clone(), self.font_encoding(dict));
+        }
+        fonts
+    }
+
+    fn font_encoding(&self, dict: &HashMap<String, Object>) -> FontEncoding {
+        let composite = dict.get("Subtype").and_then(Object::name) == Some("Type0");
+        let mut encoding = FontEncoding { code_length: if composite { 2 } else { 1 }, ..FontEncoding::default() };
+        if let Some(cmap) = dict.get("ToUnicode").and_then(|t| self.stream_of(t)) {
+            encoding.read_cmap(&cmap);
+        }
+        if let Some(Object::Dict(enc)) = dict.get("Encoding").map(|e| self.resolve(e)) {
+            if let Some(Object::Array(differences)) = enc.get("Differences").map(|d| self.resolve(d)) {
+                let mut code = 0u32;
+                for item in differences {
+                    match item {
+                        Object::Number(n) => code = *n as u32,
+                        Object::Name(glyph) => {
+                            if let Some(c) = glyph_char(glyph) {
+                                encoding.differences.insert(code, c);
+                            }
+                            code += 1;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        if !composite {
+            let first = dict.get("FirstChar").map(|f| self.resolve(f)).and_then(Object::number).unwrap_or(0.0) as u32;
+            if let Some(Object::Array(widths)) = dict.get("Widths").map(|w| self.resolve(w)) {
+                for (i, width) in widths.iter().enumerate() {
+                    if let Some(w) = self.resolve(width).number() {
+                        encoding.widths.insert(first + i as u32, w);
+                    }
+                }
+            }
+        }
+        encoding
+    }
+
+    fn page_text(&self, page: &Page) -> RecognizedPage {
+        let [x0, y0, x1, y1] = page.media_box;
+        let fonts = self.fonts(&page.resources);
+        let mut interpreter = Interpreter::new(&fonts);
+        let mut parser = Parser::new(&page.contents, false);
+        let mut operands = Vec::new();
+        while let Some(object) = parser.next() {
+            match object {
+                Object::Operator(op) => {
+                    if op == "BI" {
+                        skip_inline_image(&mut parser);
+                    } else {
+                        interpreter.apply(&op, &operands);
+                    }
+                    operands.clear();
+                }
+                other => operands.push(other),
+            }
+        }
+        let top = y0.max(y1);
+        let spans = interpreter.spans.into_iter()
+            .map(|s| TextSpan { x: s.x - x0.min(x1), y: top - s.y - s.height, ..s })
+            .collect();
+        RecognizedPage { width: (x1 - x0).abs(), height: (y1 - y0).abs(), spans }
+    }
+}
+
+fn skip_inline_image(parser: &mut Parser) {
+    let end = Regex::new(r"\sEI\b").expect("valid pattern");
+    parser.pos = end.find_at(parser.data, parser.pos).map(|m| m.end()).unwrap_or(parser.data.len());
+}
+
+#[derive(Default)]
+struct FontEncoding {
+    code_length: usize,
+    to_unicode: HashMap<u32, String>,
+    differences: HashMap<u32, char>,
+    widths: HashMap<u32, f32>,
+}
+
+impl FontEncoding {
+    fn read_cmap(&mut self, cmap: &[u8]) {
+        let mut parser = Parser::new(cmap, false);
+        let mut pending: Vec<Object> = Vec::new();
+        while let Some(object) = parser.next() {
+            match object {
+                Object::Operator(op) => {
+                    match op.as_str() {
+                        "endcodespacerange" => {
+                            if let Some(Object::String(low)) = pending.first() {
+                                self.code_length = low.len().clamp(1, 4);
+                            }
+                        }
+                        "endbfchar" => {
+                            for pair in pending.chunks(2) {
+                                if let [Object::String(source), Object::String(target)] = pair {
+                                    self.to_unicode.insert(code_of(source), utf16(target));
+                                }
+                            }
+                        }
+                        "endbfrange" => {
+                            for triple in pending.chunks(3) {
+                                let [Object::String(low), Object::String(high), target] = triple else { continue };
+                                let (low, high) = (code_of(low), code_of(high));
+                                if high < low || high - low > 0xFFFF {
+                                    continue;
+                                }
+                                for code in low..=high {
+                                    let offset = code - low;
+                                    let text = match target {
+                                        Object::String(start) => {
+                                            let mut bytes = start.clone();
+                                            if let Some(last) = bytes.last_mut() {
+                                                *last = last.wrapping_add(offset as u8);
+                                            }
+                                            utf16(&bytes)
+                                        }
+                                        Object::Array(items) => match items.get(offset as usize) {
+                                            Some(Object::String(s)) => utf16(s),
+                                            _ => continue,
+                                        },
+                                        _ => continue,
+                                    };
+                                    self.to_unicode.insert(code, text);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                    pending.clear();
+                }
+                other => pending.push(other),
+            }
+        }
+    }
+
+    /// The text of `bytes` and the width of each code, in thousandths of the font size.
+    fn decode(&self, bytes: &[u8]) -> Vec<(String, f32, bool)> {
+        let length = self.code_length.max(1);
+        bytes.chunks(length)
+            .map(|chunk| {
+                let code = code_of(chunk);
+                let text = match self.to_unicode.get(&code) {
+                    Some(text) => text.clone(),
+                    None if length == 1 => self.differences.get(&code).copied().unwrap_or_else(|| win_ansi(chunk[0])).to_string(),
+                    None => String::new(),
+                };
+                let width = self.widths.get(&code).copied().unwrap_or_else(|| {
+                    erp_core::pdf::text_width(&text, erp_core::pdf::Font::Regular, 1000.0)
+                });
+                (text, width, length == 1 && chunk[0] == b' ')
+            })
+            .collect()
+    }
+}
+
+fn code_of(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0u32, |acc, &b| (acc << 8) | b as u32)
+}
+
+fn utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes.chunks(2).map(|c| ((c[0] as u16) << 8) | *c.get(1).unwrap_or(&0) as u16).collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn win_ansi(byte: u8) -> char {
+    match byte {
+        0x80 => '€',
+        0x82 => '‚',
+        0x84 => '„',
+        0x85 => '…',
+        0x8A => 'Š',
+        0x8C => 'Œ',
+        0x8E => 'Ž',
+        0x91 => '‘',
+        0x92 => '’',
+        0x93 => '“',
+        0x94 => '”',
+        0x95 => '•',
+        0x96 => '–',
+        0x97 => '—',
+        0x99 => '™',
+        0x9A => 'š',
+        0x9C => 'œ',
+        0x9E => 'ž',
+        0x9F => 'Ÿ',
+        b if b < 0x20 => ' ',
+        b => b as char,
+    }
+}
+
+/// The character an Adobe glyph name stands for, for the names `Differences` arrays use most.
+fn glyph_char(name: &str) -> Option<char> {
+    if name.chars().count() == 1 {
+        return name.chars().next();
+    }
+    if let Some(hex) = name.strip_prefix("uni").or_else(|| name.strip_prefix('u')) {
+        if let Some(c) = u32::from_str_radix(hex.get(..4)?, 16).ok().and_then(char::from_u32) {
+            return Some(c);
+        }
+    }
+    const DIGITS: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+    if let Some(d) = DIGITS.iter().position(|d| *d == name) {
+        return char::from_digit(d as u32, 10);
+    }
+    Some(match name {
+        "space" | "nbspace" => ' ',
+        "period" => '.',
+        "comma" => ',',
+        "colon" => ':',
+        "semicolon" => ';',
+        "hyphen" | "minus" => '-',
+        "endash" => '–',
+        "emdash" => '—',
+        "slash" => '/',
+        "numbersign" => '#',
+        "dollar" => '$',
+        "Euro" => '€',
+        "sterling" => '£',
+        "yen" => '¥',
+        "percent" => '%',
+        "ampersand" => '&',
+        "parenleft" => '(',
+        "parenright" => ')',
+        "at" => '@',
+        "quotesingle" | "quoteright" => '\'',
+        "quotedbl" => '"',
+        "plus" => '+',
+        "equal" => '=',
+        "asterisk" => '*',
+        "underscore" => '_',
+        _ => return None,
+    })
+}
+
+type Matrix = [f32; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// `a` then `b`, in the row-vector convention PDF uses.
+fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    [
+        a[0] * b[0] + a[1] * b[2],
+        a[0] * b[1] + a[1] * b[3],
+        a[2] * b[0] + a[3] * b[2],
+        a[2] * b[1] + a[3] * b[3],
+        a[4] * b[0] + a[5] * b[2] + b[4],
+        a[4] * b[1] + a[5] * b[3] + b[5],
+    ]
+}
+
+#[derive(Clone)]
+struct GraphicsState {
+    ctm: Matrix,
+    font: Option<String>,
+    size: f32,
+    leading: f32,
+    char_spacing: f32,
+    word_spacing: f32,
+    scale: f32,
+}
+
+struct Interpreter<'a> {
+    fonts: &'a HashMap<String, FontEncoding>,
+    state: GraphicsState,
+    saved: Vec<GraphicsState>,
+    text_matrix: Matrix,
+    line_matrix: Matrix,
+    /// Spans with `y` at the baseline, measured up from the bottom of the page.
+    spans: Vec<TextSpan>,
+}
+
+impl<'a> Interpreter<'a> {
+    fn new(fonts: &'a HashMap<String, FontEncoding>) -> Self {
+        Self {
+            fonts,
+            state: GraphicsState {
+                ctm: IDENTITY,
+                font: None,
+                size: 12.0,
+                leading: 0.0,
+                char_spacing: 0.0,
+                word_spacing: 0.0,
+                scale: 1.0,
+            },
+            saved: Vec::new(),
+            text_matrix: IDENTITY,
+            line_matrix: IDENTITY,
+            spans: Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, op: &str, operands: &[Object]) {
+        let n = |i: usize| operands.get(i).and_then(Object::number).unwrap_or(0.0);
+        match op {
+            "q" => self.saved.push(self.state.clone()),
+            "Q" => {
+                if let Some(state) = self.saved.pop() {
+                    self.state = state;
+                }
+            }
+            "cm" if operands.len() == 6 => self.state.ctm = multiply(&[n(0), n(1), n(2), n(3), n(4), n(5)], &self.state.ctm),
+            "BT" => {
+                self.text_matrix = IDENTITY;
+                self.line_matrix = IDENTITY;
+            }
+            "Tf" => {
+                self.state.font = operands.first().and_then(Object::name).map(str::to_string);
+                self.state.size = n(1);
+            }
+            "TL" => self.state.leading = n(0),
+            "Tc" => self.state.char_spacing = n(0),
+            "Tw" => self.state.word_spacing = n(0),
+            "Tz" => self.state.scale = n(0) / 100.0,
+            "Td" => self.move_line(n(0), n(1)),
+            "TD" => {
+                self.state.leading = -n(1);
+                self.move_line(n(0), n(1));
+            }
+            "Tm" if operands.len() == 6 => {
+                self.text_matrix = [n(0), n(1), n(2), n(3), n(4), n(5)];
+                self.line_matrix = self.text_matrix;
+            }
+            "T*" => self.move_line(0.0, -self.state.leading),
+            "Tj" => {
+                if let Some(Object::String(bytes)) = operands.first() {
+                    self.show(bytes);
+                }
+            }
+            "'" => {
+                self.move_line(0.0, -self.state.leading);
+                if let Some(Object::String(bytes)) = operands.first() {
+                    self.show(bytes);
+                }
+            }
+            "\"" => {
+                self.state.word_spacing = n(0);
+                self.state.char_spacing = n(1);
+                self.move_line(0.0, -self.state.leading);
+                if let Some(Object::String(bytes)) = operands.get(2) {
+                    self.show(bytes);
+                }
+            }
+            "TJ" => {
+                if let Some(Object::Array(items)) = operands.first() {
+                    for item in items {
+                        match item {
+                            Object::String(bytes) => self.show(bytes),
+                            Object::Number(adjust) => {
+                                let tx = -adjust / 1000.0 * self.state.size * self.state.scale;
+                                self.text_matrix = multiply(&[1.0, 0.0, 0.0, 1.0, tx, 0.0], &self.text_matrix);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn move_line(&mut self, tx: f32, ty: f32) {
+        self.line_matrix = multiply(&[1.0, 0.0, 0.0, 1.0, tx, ty], &self.line_matrix);
+        self.text_matrix = self.line_matrix;
+    }
+
+    fn show(&mut self, bytes: &[u8]) {
+        let fonts = self.fonts;
+        match self.state.font.as_deref().and_then(|f| fonts.get(f)) {
+            Some(font) => self.show_with(font, bytes),
+            None => self.show_with(&FontEncoding { code_length: 1, ..FontEncoding::default() }, bytes),
+        }
+    }
+
+    fn show_with(&mut self, font: &FontEncoding, bytes: &[u8]) {
+        let start = multiply(&self.text_matrix, &self.state.ctm);
+        let mut text = String::new();
+        for (glyph, width, is_space) in font.decode(bytes) {
+            text.push_str(&glyph);
+            let spacing = self.state.char_spacing + if is_space { self.state.word_spacing } else { 0.0 };
+            let tx = (width / 1000.0 * self.state.size + spacing) * self.state.scale;
+            self.text_matrix = multiply(&[1.0, 0.0, 0.0, 1.0, tx, 0.0], &self.text_matrix);
+        }
+        let end = multiply(&self.text_matrix, &self.state.ctm);
+        let height = self.state.size * (start[2] * start[2] + start[3] * start[3]).sqrt();
+        if text.trim().is_empty() || height <= 0.0 {
+            return;
+        }
+        self.spans.push(TextSpan {
+            text,
+            x: start[4],
+            y: start[5],
+            width: ((end[4] - start[4]).powi(2) + (end[5] - start[5]).powi(2)).sqrt(),
+            height,
+            confidence: TEXT_LAYER_CONFIDENCE,
+        });
+    }
+}
@@ -1,28 +1,184 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use erp_core::models::BaseEntity;
+use erp_core::{parse_datetime, parse_datetime_opt, parse_uuid, parse_uuid_opt};
+use serde::de::DeserializeOwned;
+use sqlx::{SqliteConnection, SqlitePool};
 use crate::models::*;
 
 #[async_trait]
 pub trait OcrRepository: Send + Sync {
-    async fn create_document(&self, doc: &OcrDocument) -> Result<()>;
-    async fn get_document(&self, id: Uuid) -> Result<Option<OcrDocument>>;
-    async fn list_documents(&self, status: Option<OcrStatus>, limit: i64, offset: i64) -> Result<Vec<OcrDocument>>;
-    async fn update_document(&self, doc: &OcrDocument) -> Result<()>;
-    async fn delete_document(&self, id: Uuid) -> Result<()>;
-    
-    async fn create_template(&self, template: &OcrTemplate) -> Result<()>;
-    async fn get_template(&self, id: Uuid) -> Result<Option<OcrTemplate>>;
-    async fn list_templates(&self, document_type: Option<DocumentType>) -> Result<Vec<OcrTemplate>>;
-    async fn update_template(&self, template: &OcrTemplate) -> Result<()>;
-    async fn delete_template(&self, id: Uuid) -> Result<()>;
-    
-    async fn create_batch_job(&self, job: &OcrBatchJob) -> Result<()>;
-    async fn get_batch_job(&self, id: Uuid) -> Result<Option<OcrBatchJob>>;
-    async fn update_batch_job(&self, job: &OcrBatchJob) -> Result<()>;
-    
-    async fn get_settings(&self) -> Result<OcrSettings>;
-    async fn update_settings(&self, settings: &OcrSettings) -> Result<()>;
+    async fn create_document(&self, pool: &SqlitePool, doc: &OcrDocument) -> Result<()>;
+    async fn get_document(&self, pool: &SqlitePool, id: Uuid) -> Result<Option<OcrDocument>>;
+    async fn list_documents(&self, pool: &SqlitePool, status: Option<OcrStatus>, limit: i64, offset: i64) -> Result<Vec<OcrDocument>>;
+    async fn update_document(&self, pool: &SqlitePool, doc: &OcrDocument) -> Result<()>;
+    async fn delete_document(&self, pool: &SqlitePool, id: Uuid) -> Result<()>;
+    /// Moves a document to `Processing` unless it is being processed since `stale_before` or
+    /// later, or has been validated. False when another caller got there first.
+    async fn claim_document(&self, pool: &SqlitePool, id: Uuid, stale_before: DateTime<Utc>) -> Result<bool>;
+    async fn queued_documents(&self, pool: &SqlitePool, limit: i64) -> Result<Vec<Uuid>>;
+
+    async fn create_template(&self, pool: &SqlitePool, template: &OcrTemplate) -> Result<()>;
+    async fn get_template(&self, pool: &SqlitePool, id: Uuid) -> Result<Option<OcrTemplate>>;
+    async fn list_templates(&self, pool: &SqlitePool, document_type: Option<DocumentType>) -> Result<Vec<OcrTemplate>>;
+    /// The enabled template most recently set up for a vendor's documents of one type.
+    async fn vendor_template(&self, pool: &SqlitePool, vendor_id: Uuid, document_type: &DocumentType) -> Result<Option<OcrTemplate>>;
+    async fn update_template(&self, pool: &SqlitePool, template: &OcrTemplate) -> Result<()>;
+    async fn delete_template(&self, pool: &SqlitePool, id: Uuid) -> Result<()>;
+
+    async fn create_batch_job(&self, pool: &SqlitePool, job: &OcrBatchJob) -> Result<()>;
+    async fn get_batch_job(&self, pool: &SqlitePool, id: Uuid) -> Result<Option<OcrBatchJob>>;
+    async fn update_batch_job(&self, pool: &SqlitePool, job: &OcrBatchJob) -> Result<()>;
+
+    async fn get_settings(&self, pool: &SqlitePool) -> Result<OcrSettings>;
+    async fn update_settings(&self, pool: &SqlitePool, settings: &OcrSettings) -> Result<()>;
+}
+
+/// Enums are stored by variant name.
+fn variant<T: std::fmt::Debug>(value: &T) -> String {
+    format!("{:?}", value)
+}
+
+fn parse_variant<T: DeserializeOwned>(text: &str, field: &str) -> Result<T> {
+    serde_json::from_value(serde_json::Value::String(text.to_string()))
+        .map_err(|_| erp_core::Error::internal(format!("Unknown {} {:?}", field, text)).into())
+}
+
+fn base(id: &str, created_at: &str, updated_at: &str) -> Result<BaseEntity> {
+    Ok(BaseEntity {
+        id: parse_uuid(id, "id")?,
+        created_at: parse_datetime(created_at, "created_at")?,
+        updated_at: parse_datetime(updated_at, "updated_at")?,
+        created_by: None,
+        updated_by: None,
+    })
+}
+
+const DOCUMENT_COLUMNS: &str = "id, document_type, original_filename, file_path, file_size, mime_type, checksum, template_id,
+    status, processing_started_at, processing_completed_at, confidence_score, engine, raw_text, extracted_data,
+    field_confidence, validation_errors, vendor_id, vendor_bill_id, reviewed_by, reviewed_at, created_at, updated_at";
+
+#[derive(sqlx::FromRow)]
+struct DocumentRow {
+    id: String,
+    document_type: String,
+    original_filename: String,
+    file_path: String,
+    file_size: i64,
+    mime_type: String,
+    checksum: Option<String>,
+    template_id: Option<String>,
+    status: String,
+    processing_started_at: Option<String>,
+    processing_completed_at: Option<String>,
+    confidence_score: Option<f64>,
+    engine: Option<String>,
+    raw_text: Option<String>,
+    extracted_data: Option<String>,
+    field_confidence: Option<String>,
+    validation_errors: String,
+    vendor_id: Option<String>,
+    vendor_bill_id: Option<String>,
+    reviewed_by: Option<String>,
+    reviewed_at: Option<String>,
+    created_at: String,
+    updated_at: String,
+}
+
+impl DocumentRow {
+    fn into_document(self) -> Result<OcrDocument> {
+        Ok(OcrDocument {
+            base: base(&self.id, &self.created_at, &self.updated_at)?,
+            document_type: parse_variant(&self.document_type, "document type")?,
+            original_filename: self.original_filename,
+            file_path: self.file_path,
+            file_size: self.file_size,
+            mime_type: self.mime_type,
+            checksum: self.checksum,
+            template_id: parse_uuid_opt(self.template_id.as_deref(), "template_id")?,
+            status: parse_variant(&self.status, "OCR status")?,
+            processing_started_at: parse_datetime_opt(self.processing_started_at.as_deref(), "processing_started_at")?,
+            processing_completed_at: parse_datetime_opt(self.processing_completed_at.as_deref(), "processing_completed_at")?,
+            confidence_score: self.confidence_score,
+            engine: self.engine,
+            raw_text: self.raw_text,
+            extracted_data: self.extracted_data.as_deref().map(serde_json::from_str).transpose()?,
+            field_confidence: self.field_confidence.as_deref().map(serde_json::from_str).transpose()?.unwrap_or_default(),
+            validation_errors: serde_json::from_str(&self.validation_errors)?,
+            vendor_id: parse_uuid_opt(self.vendor_id.as_deref(), "vendor_id")?,
+            vendor_bill_id: parse_uuid_opt(self.vendor_bill_id.as_deref(), "vendor_bill_id")?,
+            reviewed_by: parse_uuid_opt(self.reviewed_by.as_deref(), "reviewed_by")?,
+            reviewed_at: parse_datetime_opt(self.reviewed_at.as_deref(), "reviewed_at")?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct TemplateRow {
+    id: String,
+    name: String,
+    document_type: String,
+    vendor_id: Option<String>,
+    field_mappings: String,
+    sample_images: String,
+    accuracy_score: f64,
+    enabled: bool,
+    created_at: String,
+    updated_at: String,
+}
+
+impl TemplateRow {
+    fn into_template(self) -> Result<OcrTemplate> {
+        Ok(OcrTemplate {
+            base: base(&self.id, &self.created_at, &self.updated_at)?,
+            name: self.name,
+            document_type: parse_variant(&self.document_type, "document type")?,
+            vendor_id: parse_uuid_opt(self.vendor_id.as_deref(), "vendor_id")?,
+            field_mappings: serde_json::from_str(&self.field_mappings)?,
+            sample_images: serde_json::from_str(&self.sample_images)?,
+            accuracy_score: self.accuracy_score,
+            enabled: self.enabled,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct BatchJobRow {
+    id: String,
+    name: String,
+    document_ids: String,
+    template_id: Option<String>,
+    status: String,
+    total_documents: i32,
+    processed_documents: i32,
+    successful_documents: i32,
+    failed_documents: i32,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+    error_details: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl BatchJobRow {
+    fn into_job(self) -> Result<OcrBatchJob> {
+        Ok(OcrBatchJob {
+            base: base(&self.id, &self.created_at, &self.updated_at)?,
+            name: self.name,
+            document_ids: serde_json::from_str(&self.document_ids)?,
+            template_id: parse_uuid_opt(self.template_id.as_deref(), "template_id")?,
+            status: parse_variant(&self.status, "batch job status")?,
+            total_documents: self.total_documents,
+            processed_documents: self.processed_documents,
+            successful_documents: self.successful_documents,
+            failed_documents: self.failed_documents,
+            started_at: parse_datetime_opt(self.started_at.as_deref(), "started_at")?,
+            completed_at: parse_datetime_opt(self.completed_at.as_deref(), "completed_at")?,
+            error_details: serde_json::from_str(&self.error_details)?,
+        })
+    }
 }
 
 pub struct SqliteOcrRepository;
@@ -37,77 +193,266 @@ impl SqliteOcrRepository {
     pub fn new() -> Self {
         Self
     }
+
+    pub async fn update_document_in(&self, conn: &mut SqliteConnection, doc: &OcrDocument) -> Result<()> {
+        sqlx::query(
+            "UPDATE ocr_documents SET template_id = ?, status = ?, processing_started_at = ?, processing_completed_at = ?,
+                confidence_score = ?, engine = ?, raw_text = ?, extracted_data = ?, field_confidence = ?,
+                validation_errors = ?, vendor_id = ?, vendor_bill_id = ?, reviewed_by = ?, reviewed_at = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(doc.template_id.map(|id| id.to_string()))
+        .bind(variant(&doc.status))
+        .bind(doc.processing_started_at.map(|t| t.to_rfc3339()))
+        .bind(doc.processing_completed_at.map(|t| t.to_rfc3339()))
+        .bind(doc.confidence_score)
+        .bind(&doc.engine)
+        .bind(&doc.raw_text)
+        .bind(doc.extracted_data.as_ref().map(serde_json::to_string).transpose()?)
+        .bind(serde_json::to_string(&doc.field_confidence)?)
+        .bind(serde_json::to_string(&doc.validation_errors)?)
+        .bind(doc.vendor_id.map(|id| id.to_string()))
+        .bind(doc.vendor_bill_id.map(|id| id.to_string()))
+        .bind(doc.reviewed_by.map(|id| id.to_string()))
+        .bind(doc.reviewed_at.map(|t| t.to_rfc3339()))
+        .bind(Utc::now().to_rfc3339())
+        .bind(doc.base.id.to_string())
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl OcrRepository for SqliteOcrRepository {
-    async fn create_document(&self, _doc: &OcrDocument) -> Result<()> {
+    async fn create_document(&self, pool: &SqlitePool, doc: &OcrDocument) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO ocr_documents (id, document_type, original_filename, file_path, file_size, mime_type, checksum,
+                template_id, status, validation_errors, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(doc.base.id.to_string())
+        .bind(variant(&doc.document_type))
+        .bind(&doc.original_filename)
+        .bind(&doc.file_path)
+        .bind(doc.file_size)
+        .bind(&doc.mime_type)
+        .bind(&doc.checksum)
+        .bind(doc.template_id.map(|id| id.to_string()))
+        .bind(variant(&doc.status))
+        .bind(serde_json::to_string(&doc.validation_errors)?)
+        .bind(doc.base.created_at.to_rfc3339())
+        .bind(doc.base.updated_at.to_rfc3339())
+        .execute(pool)
+        .await?;
         Ok(())
     }
-    
-    async fn get_document(&self, _id: Uuid) -> Result<Option<OcrDocument>> {
-        Ok(None)
+
+    async fn get_document(&self, pool: &SqlitePool, id: Uuid) -> Result<Option<OcrDocument>> {
+        let row = sqlx::query_as::<_, DocumentRow>(&format!("SELECT {} FROM ocr_documents WHERE id = ?", DOCUMENT_COLUMNS))
+            .bind(id.to_string())
+            .fetch_optional(pool)
+            .await?;
+        row.map(DocumentRow::into_document).transpose()
     }
-    
-    async fn list_documents(&self, _status: Option<OcrStatus>, _limit: i64, _offset: i64) -> Result<Vec<OcrDocument>> {
-        Ok(Vec::new())
+
+    async fn list_documents(&self, pool: &SqlitePool, status: Option<OcrStatus>, limit: i64, offset: i64) -> Result<Vec<OcrDocument>> {
+        let rows = sqlx::query_as::<_, DocumentRow>(&format!(
+            "SELECT {} FROM ocr_documents WHERE (? IS NULL OR status = ?) ORDER BY created_at DESC LIMIT ? OFFSET ?",
+            DOCUMENT_COLUMNS
+        ))
+        .bind(status.as_ref().map(variant))
+        .bind(status.as_ref().map(variant))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+        rows.into_iter().map(DocumentRow::into_document).collect()
     }
-    
-    async fn update_document(&self, _doc: &OcrDocument) -> Result<()> {
-        Ok(())
+
+    async fn update_document(&self, pool: &SqlitePool, doc: &OcrDocument) -> Result<()> {
+        self.update_document_in(&mut *pool.acquire().await?, doc).await
     }
-    
-    async fn delete_document(&self, _id: Uuid) -> Result<()> {
+
+    async fn delete_document(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM ocr_documents WHERE id = ?")
+            .bind(id.to_string())
+            .execute(pool)
+            .await?;
         Ok(())
     }
-    
-    async fn create_template(&self, _template: &OcrTemplate) -> Result<()> {
+
+    async fn claim_document(&self, pool: &SqlitePool, id: Uuid, stale_before: DateTime<Utc>) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            "UPDATE ocr_documents SET status = 'Processing', processing_started_at = ?, updated_at = ?
+             WHERE id = ? AND status != 'Validated' AND vendor_bill_id IS NULL
+               AND (status != 'Processing' OR processing_started_at IS NULL OR processing_started_at < ?)",
+        )
+        .bind(&now)
+        .bind(&now)
+        .bind(id.to_string())
+        .bind(stale_before.to_rfc3339())
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn queued_documents(&self, pool: &SqlitePool, limit: i64) -> Result<Vec<Uuid>> {
+        let ids: Vec<(String,)> = sqlx::query_as(
+            "SELECT id FROM ocr_documents WHERE status = 'Queued' ORDER BY created_at LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+        ids.iter().map(|(id,)| Ok(parse_uuid(id, "id")?)).collect()
+    }
+
+    async fn create_template(&self, pool: &SqlitePool, template: &OcrTemplate) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO ocr_templates (id, name, document_type, vendor_id, field_mappings, sample_images, accuracy_score,
+                enabled, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(template.base.id.to_string())
+        .bind(&template.name)
+        .bind(variant(&template.document_type))
+        .bind(template.vendor_id.map(|id| id.to_string()))
+        .bind(serde_json::to_string(&template.field_mappings)?)
+        .bind(serde_json::to_string(&template.sample_images)?)
+        .bind(template.accuracy_score)
+        .bind(template.enabled)
+        .bind(template.base.created_at.to_rfc3339())
+        .bind(template.base.updated_at.to_rfc3339())
+        .execute(pool)
+        .await?;
         Ok(())
     }
-    
-    async fn get_template(&self, _id: Uuid) -> Result<Option<OcrTemplate>> {
-        Ok(None)
+
+    async fn get_template(&self, pool: &SqlitePool, id: Uuid) -> Result<Option<OcrTemplate>> {
+        let row = sqlx::query_as::<_, TemplateRow>("SELECT * FROM ocr_templates WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(pool)
+            .await?;
+        row.map(TemplateRow::into_template).transpose()
     }
-    
-    async fn list_templates(&self, _document_type: Option<DocumentType>) -> Result<Vec<OcrTemplate>> {
-        Ok(Vec::new())
+
+    async fn list_templates(&self, pool: &SqlitePool, document_type: Option<DocumentType>) -> Result<Vec<OcrTemplate>> {
+        let document_type = document_type.as_ref().map(variant);
+        let rows = sqlx::query_as::<_, TemplateRow>(
+            "SELECT * FROM ocr_templates WHERE (? IS NULL OR document_type = ?) ORDER BY name",
+        )
+        .bind(&document_type)
+        .bind(&document_type)
+        .fetch_all(pool)
+        .await?;
+        rows.into_iter().map(TemplateRow::into_template).collect()
     }
-    
-    async fn update_template(&self, _template: &OcrTemplate) -> Result<()> {
+
+    async fn vendor_template(&self, pool: &SqlitePool, vendor_id: Uuid, document_type: &DocumentType) -> Result<Option<OcrTemplate>> {
+        let row = sqlx::query_as::<_, TemplateRow>(
+            "SELECT * FROM ocr_templates WHERE vendor_id = ? AND document_type = ? AND enabled = 1
+             ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(vendor_id.to_string())
+        .bind(variant(document_type))
+        .fetch_optional(pool)
+        .await?;
+        row.map(TemplateRow::into_template).transpose()
+    }
+
+    async fn update_template(&self, pool: &SqlitePool, template: &OcrTemplate) -> Result<()> {
+        sqlx::query(
+            "UPDATE ocr_templates SET name = ?, document_type = ?, vendor_id = ?, field_mappings = ?, sample_images = ?,
+                accuracy_score = ?, enabled = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(&template.name)
+        .bind(variant(&template.document_type))
+        .bind(template.vendor_id.map(|id| id.to_string()))
+        .bind(serde_json::to_string(&template.field_mappings)?)
+        .bind(serde_json::to_string(&template.sample_images)?)
+        .bind(template.accuracy_score)
+        .bind(template.enabled)
+        .bind(Utc::now().to_rfc3339())
+        .bind(template.base.id.to_string())
+        .execute(pool)
+        .await?;
         Ok(())
     }
-    
-    async fn delete_template(&self, _id: Uuid) -> Result<()> {
+
+    async fn delete_template(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM ocr_templates WHERE id = ?")
+            .bind(id.to_string())
+            .execute(pool)
+            .await?;
         Ok(())
     }
-    
-    async fn create_batch_job(&self, _job: &OcrBatchJob) -> Result<()> {
+
+    async fn create_batch_job(&self, pool: &SqlitePool, job: &OcrBatchJob) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO ocr_batch_jobs (id, name, document_ids, template_id, status, total_documents, error_details,
+                created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(job.base.id.to_string())
+        .bind(&job.name)
+        .bind(serde_json::to_string(&job.document_ids)?)
+        .bind(job.template_id.map(|id| id.to_string()))
+        .bind(variant(&job.status))
+        .bind(job.total_documents)
+        .bind(serde_json::to_string(&job.error_details)?)
+        .bind(job.base.created_at.to_rfc3339())
+        .bind(job.base.updated_at.to_rfc3339())
+        .execute(pool)
+        .await?;
         Ok(())
     }
-    
-    async fn get_batch_job(&self, _id: Uuid) -> Result<Option<OcrBatchJob>> {
-        Ok(None)
+
+    async fn get_batch_job(&self, pool: &SqlitePool, id: Uuid) -> Result<Option<OcrBatchJob>> {
+        let row = sqlx::query_as::<_, BatchJobRow>("SELECT * FROM ocr_batch_jobs WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(pool)
+            .await?;
+        row.map(BatchJobRow::into_job).transpose()
     }
-    
-    async fn update_batch_job(&self, _job: &OcrBatchJob) -> Result<()> {
+
+    async fn update_batch_job(&self, pool: &SqlitePool, job: &OcrBatchJob) -> Result<()> {
+        sqlx::query(
+            "UPDATE ocr_batch_jobs SET status = ?, processed_documents = ?, successful_documents = ?, failed_documents = ?,
+                started_at = ?, completed_at = ?, error_details = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(variant(&job.status))
+        .bind(job.processed_documents)
+        .bind(job.successful_documents)
+        .bind(job.failed_documents)
+        .bind(job.started_at.map(|t| t.to_rfc3339()))
+        .bind(job.completed_at.map(|t| t.to_rfc3339()))
+        .bind(serde_json::to_string(&job.error_details)?)
+        .bind(Utc::now().to_rfc3339())
+        .bind(job.base.id.to_string())
+        .execute(pool)
+        .await?;
         Ok(())
     }
-    
-    async fn get_settings(&self) -> Result<OcrSettings> {
-        Ok(OcrSettings {
-            default_language: "en".to_string(),
-            auto_detect_language: true,
-            output_format: OutputFormat::Json,
-            enable_table_extraction: true,
-            enable_handwriting_recognition: false,
-            confidence_threshold: 0.8,
-            auto_validate: false,
-            auto_create_entities: false,
-            retention_days: 365,
-        })
+
+    async fn get_settings(&self, pool: &SqlitePool) -> Result<OcrSettings> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT settings FROM ocr_settings WHERE id = 1")
+            .fetch_optional(pool)
+            .await?;
+        Ok(row.map(|(s,)| serde_json::from_str(&s)).transpose()?.unwrap_or_default())
     }
-    
-    async fn update_settings(&self, _settings: &OcrSettings) -> Result<()> {
+
+    async fn update_settings(&self, pool: &SqlitePool, settings: &OcrSettings) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO ocr_settings (id, settings, updated_at) VALUES (1, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET settings = excluded.settings, updated_at = excluded.updated_at",
+        )
+        .bind(serde_json::to_string(settings)?)
+        .bind(Utc::now().to_rfc3339())
+        .execute(pool)
+        .await?;
         Ok(())
     }
 }
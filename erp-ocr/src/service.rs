@@ -1,13 +1,29 @@
 use anyhow::Result;
 use uuid::Uuid;
 use erp_core::models::BaseEntity;
+use erp_core::{BlobService, Error, UploadPolicy};
+use erp_vendor_bills::{VendorBillLineCreateRequest, VendorBillService};
+use futures::TryStreamExt;
+use serde_json::{Map, Value};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::engine::{OcrEngine, RecognizedDocument, TesseractEngine};
+use crate::extraction::{self, Extraction, FieldExtractor};
+use crate::matching;
 use crate::models::*;
+use crate::pdf::PdfTextEngine;
 use crate::repository::{OcrRepository, SqliteOcrRepository};
-use chrono::Utc;
-use regex::Regex;
+use crate::tables;
+use chrono::{Duration, NaiveDate, Utc};
+
+/// A document still `Processing` after this long was left behind by a worker that stopped,
+/// and may be claimed again.
+const PROCESSING_TIMEOUT_MINUTES: i64 = 15;
 
 pub struct OcrService {
     repo: SqliteOcrRepository,
+    engines: Vec<Arc<dyn OcrEngine>>,
 }
 
 impl Default for OcrService {
@@ -17,240 +33,532 @@ impl Default for OcrService {
 }
 
 impl OcrService {
+    /// Reads PDF text layers, and images too when Tesseract is installed.
     pub fn new() -> Self {
+        let mut engines: Vec<Arc<dyn OcrEngine>> = vec![Arc::new(PdfTextEngine)];
+        if let Some(tesseract) = TesseractEngine::detect() {
+            engines.push(Arc::new(tesseract));
+        }
+        Self::with_engines(engines)
+    }
+
+    /// Tries `engines` in order until one reads some text.
+    pub fn with_engines(engines: Vec<Arc<dyn OcrEngine>>) -> Self {
         Self {
             repo: SqliteOcrRepository::new(),
+            engines,
         }
     }
-    
-    pub async fn upload_document(&self, request: UploadDocumentRequest) -> Result<OcrDocument> {
-        let content_bytes = base64::decode(request.content.replace("data:", "").split(",").last().unwrap_or(""))?;
-        
+
+    /// Stores a scanned PDF or image and queues it for recognition.
+    pub async fn upload_document(&self, pool: &SqlitePool, blobs: &BlobService, request: UploadDocumentRequest) -> Result<OcrDocument> {
+        if let Some(template_id) = request.template_id {
+            self.template(pool, template_id).await?;
+        }
+        let content_bytes = base64::decode(request.content.replace("data:", "").split(",").last().unwrap_or(""))
+            .map_err(|e| Error::validation(format!("Document content is not valid base64: {}", e)))?;
+        let policy = UploadPolicy {
+            allowed_mime_types: vec!["application/pdf".to_string(), "image/*".to_string()],
+            ..blobs.default_policy().clone()
+        };
+        let staged = blobs.stage_bytes(content_bytes, policy.max_size).await?;
+        let blob = blobs.save(pool, staged, "application/octet-stream", &policy, None).await?;
+
         let doc = OcrDocument {
             base: BaseEntity::new(),
             document_type: request.document_type,
             original_filename: request.filename,
-            file_path: format!("ocr/{}", Uuid::new_v4()),
-            file_size: content_bytes.len() as i64,
-            mime_type: "application/pdf".to_string(),
-            status: if request.auto_process { OcrStatus::Processing } else { OcrStatus::Pending },
-            processing_started_at: if request.auto_process { Some(Utc::now()) } else { None },
+            file_path: blob.key(),
+            file_size: blob.size,
+            mime_type: blob.mime_type,
+            checksum: Some(blob.sha256),
+            template_id: request.template_id,
+            status: if request.auto_process { OcrStatus::Queued } else { OcrStatus::Pending },
+            processing_started_at: None,
             processing_completed_at: None,
             confidence_score: None,
+            engine: None,
             raw_text: None,
             extracted_data: None,
+            field_confidence: HashMap::new(),
             validation_errors: Vec::new(),
+            vendor_id: None,
+            vendor_bill_id: None,
             reviewed_by: None,
             reviewed_at: None,
         };
-        
-        self.repo.create_document(&doc).await?;
-        
-        if request.auto_process {
-            let doc_id = doc.base.id;
-            tokio::spawn(async move {
-                let service = OcrService::new();
-                let _ = service.process_document(doc_id).await;
-            });
-        }
-        
+
+        self.repo.create_document(pool, &doc).await?;
         Ok(doc)
     }
-    
-    pub async fn process_document(&self, document_id: Uuid) -> Result<OcrResult> {
-        let mut doc = self.repo.get_document(document_id).await?
-            .ok_or_else(|| anyhow::anyhow!("Document not found"))?;
-        
+
+    /// Reads a document, extracts its fields and line items and matches its vendor. An invoice
+    /// read with confidence becomes a draft vendor bill; anything doubtful waits for review.
+    pub async fn process_document(&self, pool: &SqlitePool, blobs: &BlobService, document_id: Uuid) -> Result<OcrResult> {
+        let mut doc = self.document(pool, document_id).await?;
+        if matches!(doc.status, OcrStatus::Validated) || doc.vendor_bill_id.is_some() {
+            return Err(Error::business_rule("Document has already been validated").into());
+        }
+        let stale_before = Utc::now() - Duration::minutes(PROCESSING_TIMEOUT_MINUTES);
+        if !self.repo.claim_document(pool, document_id, stale_before).await? {
+            return Err(Error::Conflict("Document is already being processed".to_string()).into());
+        }
         doc.status = OcrStatus::Processing;
         doc.processing_started_at = Some(Utc::now());
-        self.repo.update_document(&doc).await?;
-        
-        let (extracted_data, raw_text, confidence) = self.perform_ocr(&doc).await?;
-        
-        let validation_errors = self.validate_extracted_data(&extracted_data, &doc.document_type)?;
-        
-        doc.raw_text = Some(raw_text);
-        doc.extracted_data = Some(extracted_data.clone());
-        doc.confidence_score = Some(confidence);
-        doc.processing_completed_at = Some(Utc::now());
-        
-        if validation_errors.is_empty() && confidence >= 0.8 {
-            doc.status = OcrStatus::Completed;
-        } else if confidence < 0.5 {
+
+        let settings = self.repo.get_settings(pool).await?;
+        if let Err(e) = self.capture(pool, blobs, &mut doc, &settings).await {
             doc.status = OcrStatus::Failed;
+            doc.validation_errors = vec![e.to_string()];
+        }
+        doc.processing_completed_at = Some(Utc::now());
+        if matches!(doc.status, OcrStatus::Completed) && doc.document_type == DocumentType::Invoice && settings.auto_create_entities {
+            if let Err(e) = self.save_with_bill(pool, &mut doc).await {
+                doc.validation_errors.push(e.to_string());
+                doc.status = OcrStatus::RequiresReview;
+                self.repo.update_document(pool, &doc).await?;
+            }
         } else {
-            doc.status = OcrStatus::RequiresReview;
+            self.repo.update_document(pool, &doc).await?;
         }
-        
-        doc.validation_errors = validation_errors.clone();
-        self.repo.update_document(&doc).await?;
-        
+
         Ok(OcrResult {
             document_id,
             status: doc.status.clone(),
-            extracted_data: Some(extracted_data),
+            extracted_data: doc.extracted_data.clone(),
             raw_text: doc.raw_text.clone(),
-            confidence,
-            validation_errors,
+            confidence: doc.confidence_score.unwrap_or(0.0),
+            validation_errors: doc.validation_errors.clone(),
             suggestions: self.generate_suggestions(&doc),
+            vendor_bill_id: doc.vendor_bill_id,
         })
     }
-    
-    async fn perform_ocr(&self, doc: &OcrDocument) -> Result<(serde_json::Value, String, f64)> {
-        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-        let simulated_text = match doc.document_type {
-            DocumentType::Invoice => {
-                &format!("Invoice Number: INV-{}-001\nDate: {}\nVendor: ABC Corp\nTotal: $1,250.00", chrono::Utc::now().format("%Y"), today)
+
+    /// Processes up to `limit` queued documents, oldest first.
+    pub async fn process_queued(&self, pool: &SqlitePool, blobs: &BlobService, limit: i64) -> Result<Vec<OcrResult>> {
+        let mut results = Vec::new();
+        for id in self.repo.queued_documents(pool, limit).await? {
+            match self.process_document(pool, blobs, id).await {
+                Ok(result) => results.push(result),
+                // Another worker claimed it first.
+                Err(e) if matches!(e.downcast_ref::<Error>(), Some(Error::Conflict(_))) => {}
+                Err(e) => return Err(e),
             }
-            DocumentType::Receipt => {
-                &format!("Store: Main Street Shop\nDate: {}\nTotal: $45.99", today)
+        }
+        Ok(results)
+    }
+
+    async fn recognize(&self, content: &[u8], mime_type: &str, language: &str) -> Result<Option<RecognizedDocument>> {
+        for engine in self.engines.iter().filter(|e| e.accepts(mime_type)) {
+            if let Some(document) = engine.recognize(content, mime_type, language).await? {
+                if !document.is_empty() {
+                    return Ok(Some(document));
+                }
             }
-            _ => "Document content extracted"
+        }
+        Ok(None)
+    }
+
+    async fn capture(&self, pool: &SqlitePool, blobs: &BlobService, doc: &mut OcrDocument, settings: &OcrSettings) -> Result<()> {
+        let checksum = doc.checksum.clone()
+            .ok_or_else(|| Error::business_rule("Document has no stored file to read"))?;
+        let (_, mut stream) = blobs.open(pool, &checksum).await?;
+        let mut content = Vec::new();
+        while let Some(chunk) = stream.try_next().await? {
+            content.extend_from_slice(&chunk);
+        }
+        let recognized = self.recognize(&content, &doc.mime_type, &settings.default_language).await?
+            .ok_or_else(|| Error::business_rule(format!("No text could be read from the {} file", doc.mime_type)))?;
+        let lines = recognized.lines();
+        let raw_text = lines.iter().map(|l| l.text()).collect::<Vec<_>>().join("\n");
+
+        let table = if settings.enable_table_extraction {
+            tables::extract_line_items(&lines)
+        } else {
+            tables::LineItems::default()
         };
-        
-        let extracted = match doc.document_type {
-            DocumentType::Invoice => {
-                self.extract_invoice_data(simulated_text)?
-            }
-            DocumentType::Receipt => {
-                self.extract_receipt_data(simulated_text)?
+        let extractor = FieldExtractor::new(&recognized, &lines, &table.lines);
+        let defaults = default_fields(&doc.document_type);
+        let mut extraction = Extraction::default();
+        extractor.extract(&defaults, &mut extraction)?;
+
+        let issuer = match &doc.document_type {
+            DocumentType::Receipt => "merchant_name",
+            _ => "vendor_name",
+        };
+        if extraction.str(issuer).is_none() && matches!(doc.document_type, DocumentType::Invoice | DocumentType::Receipt) {
+            let page_height = recognized.pages.first().map(|p| p.height).unwrap_or_default();
+            if let Some((name, confidence)) = extraction::letterhead(&lines, page_height) {
+                extraction.set(issuer, Value::String(name), confidence);
             }
-            _ => serde_json::json!({"text": simulated_text})
+        }
+
+        let mut template = match doc.template_id {
+            Some(id) => Some(self.template(pool, id).await?),
+            None => None,
         };
-        
-        Ok((extracted, simulated_text.to_string(), 0.92))
-    }
-    
-    fn extract_invoice_data(&self, text: &str) -> Result<serde_json::Value> {
-        let invoice_number = Regex::new(r"Invoice\s*(?:Number|No\.?)?\s*:?\s*([A-Z0-9-]+)")?
-            .captures(text)
-            .and_then(|c| c.get(1).map(|m| m.as_str().to_string()))
-            .unwrap_or_default();
-        
-        let date = Regex::new(r"Date\s*:?\s*(\d{4}-\d{2}-\d{2})")?
-            .captures(text)
-            .and_then(|c| c.get(1).map(|m| m.as_str().to_string()))
-            .unwrap_or_default();
-        
-        let vendor = Regex::new(r"Vendor\s*:?\s*(.+)")?
-            .captures(text)
-            .and_then(|c| c.get(1).map(|m| m.as_str().trim().to_string()))
-            .unwrap_or_default();
-        
-        let total = Regex::new(r"Total\s*:?\s*\$?([\d,]+\.?\d*)")?
-            .captures(text)
-            .and_then(|c| c.get(1).map(|m| m.as_str().replace(",", "").parse::<f64>().unwrap_or(0.0)))
-            .unwrap_or(0.0);
-        
-        Ok(serde_json::json!({
-            "invoice_number": invoice_number,
-            "invoice_date": date,
-            "vendor_name": vendor,
-            "total_amount_cents": (total * 100.0) as i64,
-            "currency": "USD",
-            "line_items": []
-        }))
-    }
-    
-    fn extract_receipt_data(&self, text: &str) -> Result<serde_json::Value> {
-        let store = Regex::new(r"Store\s*:?\s*(.+)")?
-            .captures(text)
-            .and_then(|c| c.get(1).map(|m| m.as_str().trim().to_string()))
-            .unwrap_or_default();
-        
-        let date = Regex::new(r"Date\s*:?\s*(\d{4}-\d{2}-\d{2})")?
-            .captures(text)
-            .and_then(|c| c.get(1).map(|m| m.as_str().to_string()))
-            .unwrap_or_default();
-        
-        let total = Regex::new(r"Total\s*:?\s*\$?([\d,]+\.?\d*)")?
-            .captures(text)
-            .and_then(|c| c.get(1).map(|m| m.as_str().replace(",", "").parse::<f64>().unwrap_or(0.0)))
-            .unwrap_or(0.0);
-        
-        Ok(serde_json::json!({
-            "merchant_name": store,
-            "receipt_date": date,
-            "total_amount_cents": (total * 100.0) as i64,
-            "currency": "USD"
-        }))
-    }
-    
-    fn validate_extracted_data(&self, data: &serde_json::Value, doc_type: &DocumentType) -> Result<Vec<String>> {
-        let mut errors = Vec::new();
-        
-        match doc_type {
-            DocumentType::Invoice => {
-                if data.get("invoice_number").and_then(|v| v.as_str()).map(|s| s.is_empty()).unwrap_or(true) {
-                    errors.push("Invoice number is missing".to_string());
-                }
-                if data.get("total_amount_cents").and_then(|v| v.as_i64()).unwrap_or(0) <= 0 {
-                    errors.push("Invalid total amount".to_string());
-                }
+        let vendor = match template.as_ref().and_then(|t| t.vendor_id) {
+            Some(vendor_id) => matching::vendor(pool, vendor_id).await?,
+            None => matching::match_vendor(pool, &raw_text, extraction.str(issuer)).await?,
+        };
+        if template.is_none() {
+            if let Some(vendor) = &vendor {
+                template = self.repo.vendor_template(pool, vendor.vendor_id, &doc.document_type).await?;
             }
-            DocumentType::Receipt => {
-                if data.get("merchant_name").and_then(|v| v.as_str()).map(|s| s.is_empty()).unwrap_or(true) {
-                    errors.push("Merchant name is missing".to_string());
-                }
+        }
+        if let Some(template) = &template {
+            // The template's fields replace the defaults with the same name.
+            let mut mappings: Vec<FieldMapping> = defaults.into_iter()
+                .filter(|d| !template.field_mappings.iter().any(|m| m.target_field == d.target_field))
+                .collect();
+            mappings.extend(template.field_mappings.iter().cloned());
+            let letterhead = extraction.values.get(issuer).cloned().zip(extraction.confidence.get(issuer).copied());
+            extraction = Extraction::default();
+            extractor.extract(&mappings, &mut extraction)?;
+            if let (None, Some((name, confidence))) = (extraction.values.get(issuer), letterhead) {
+                extraction.set(issuer, name, confidence);
             }
-            _ => {}
+            doc.template_id = Some(template.base.id);
+        }
+
+        match &vendor {
+            Some(vendor) => {
+                extraction.set("vendor_id", Value::String(vendor.vendor_id.to_string()), vendor.score);
+                extraction.set(issuer, Value::String(vendor.name.clone()), vendor.score);
+            }
+            None if doc.document_type == DocumentType::Invoice => {
+                extraction.errors.push("No vendor matched the document".to_string());
+                extraction.confidence.insert("vendor_id".to_string(), 0.0);
+            }
+            None => {}
+        }
+        if !table.items.is_empty() {
+            let confidence = table.items.iter().map(|i| i.confidence).fold(1.0, f64::min);
+            extraction.set("line_items", serde_json::to_value(&table.items)?, confidence);
+        }
+        if let Some(currency) = extraction.currency.clone().or_else(|| extraction::detect_currency(&raw_text).map(str::to_string)) {
+            extraction.values.insert("currency".to_string(), Value::String(currency));
+        }
+        check_totals(&mut extraction, &table.items);
+
+        for (field, confidence) in &extraction.confidence {
+            if *confidence < settings.confidence_threshold && *confidence > 0.0 {
+                extraction.errors.push(format!("{} needs review: read with {:.0}% confidence", field, confidence * 100.0));
+            }
+        }
+        extraction.errors.sort();
+        extraction.errors.dedup();
+
+        let read = lines.iter().map(|l| l.confidence()).fold(1.0, f64::min);
+        doc.confidence_score = Some(extraction.confidence.values().copied().fold(read, f64::min));
+        doc.engine = Some(recognized.engine.clone());
+        doc.raw_text = Some(raw_text);
+        doc.vendor_id = vendor.as_ref().map(|v| v.vendor_id);
+        doc.field_confidence = extraction.confidence;
+        doc.extracted_data = Some(Value::Object(extraction.values));
+        doc.validation_errors = extraction.errors;
+        doc.status = if doc.validation_errors.is_empty() { OcrStatus::Completed } else { OcrStatus::RequiresReview };
+        Ok(())
+    }
+
+    /// Saves a document together with the vendor bill created from it, so a bill never exists
+    /// without the document that links to it.
+    async fn save_with_bill(&self, pool: &SqlitePool, doc: &mut OcrDocument) -> Result<()> {
+        let mut tx = pool.begin().await?;
+        let bill_id = Self::create_bill_in(&mut tx, doc).await?;
+        let linked = OcrDocument { vendor_bill_id: Some(bill_id), ..doc.clone() };
+        self.repo.update_document_in(&mut tx, &linked).await?;
+        tx.commit().await?;
+        doc.vendor_bill_id = Some(bill_id);
+        Ok(())
+    }
+
+    /// Creates a draft vendor bill from an invoice's extracted fields. Vendor bills are kept in
+    /// USD, so an invoice in another currency is left for someone to enter by hand.
+    async fn create_bill_in(conn: &mut SqliteConnection, doc: &OcrDocument) -> Result<Uuid> {
+        let empty = Map::new();
+        let data = doc.extracted_data.as_ref().and_then(Value::as_object).unwrap_or(&empty);
+        let text = |field: &str| data.get(field).and_then(Value::as_str).map(str::trim).filter(|s| !s.is_empty());
+        let cents = |field: &str| data.get(field).and_then(Value::as_i64);
+
+        let vendor_id = match text("vendor_id") {
+            Some(id) => erp_core::parse_uuid(id, "vendor_id")?,
+            None => doc.vendor_id.ok_or_else(|| Error::validation("A vendor is needed to create the vendor bill"))?,
+        };
+        let vendor = matching::vendor_in(&mut *conn, vendor_id).await?
+            .ok_or_else(|| Error::not_found("Vendor", &vendor_id.to_string()))?;
+        if let Some(currency) = text("currency").filter(|c| !c.eq_ignore_ascii_case("USD")) {
+            return Err(Error::business_rule(format!(
+                "Vendor bills are kept in USD, so the {} invoice needs to be entered by hand", currency
+            ))
+            .into());
         }
-        
-        Ok(errors)
+        let invoice_number = text("invoice_number")
+            .ok_or_else(|| Error::validation("An invoice number is needed to create the vendor bill"))?;
+        let bill_date = text("invoice_date").and_then(extraction::parse_date)
+            .ok_or_else(|| Error::validation("An invoice date is needed to create the vendor bill"))?;
+        let due_date = text("due_date").and_then(extraction::parse_date)
+            .unwrap_or(bill_date + Duration::days(vendor.payment_terms))
+            .max(bill_date);
+
+        let existing: Option<(String,)> = sqlx::query_as(
+            "SELECT bill_number FROM vendor_bills WHERE vendor_id = ? AND vendor_invoice_number = ?",
+        )
+        .bind(vendor_id.to_string())
+        .bind(invoice_number)
+        .fetch_optional(&mut *conn)
+        .await?;
+        if let Some((bill_number,)) = existing {
+            return Err(Error::Conflict(format!(
+                "Invoice {} from {} is already vendor bill {}",
+                invoice_number, vendor.name, bill_number
+            ))
+            .into());
+        }
+
+        let items: Vec<ExtractedLineItem> = match data.get("line_items") {
+            Some(items) => serde_json::from_value(items.clone())
+                .map_err(|e| Error::validation(format!("line_items are not valid: {}", e)))?,
+            None => Vec::new(),
+        };
+        let tax = cents("tax_amount").unwrap_or(0);
+        let subtotal = cents("subtotal")
+            .or_else(|| (!items.is_empty()).then(|| items.iter().map(|i| i.amount).sum()))
+            .or_else(|| cents("total_amount").map(|total| total - tax))
+            .ok_or_else(|| Error::validation("An invoice total is needed to create the vendor bill"))?;
+        let tax_rate = if subtotal > 0 {
+            (tax as f64 / subtotal as f64 * 10_000.0).round() / 100.0
+        } else {
+            0.0
+        };
+
+        let lines = if items.is_empty() {
+            vec![VendorBillLineCreateRequest {
+                po_line_id: None,
+                product_id: None,
+                description: format!("Invoice {}", invoice_number),
+                quantity: 1,
+                unit_price: subtotal,
+                tax_rate,
+            }]
+        } else {
+            items.iter()
+                .map(|item| {
+                    // Bill lines count whole units, so a fractional quantity is billed as one line amount.
+                    let whole = item.quantity > 0.0 && item.quantity.fract() == 0.0;
+                    VendorBillLineCreateRequest {
+                        po_line_id: None,
+                        product_id: None,
+                        description: if whole {
+                            item.description.clone()
+                        } else {
+                            format!("{} ({} × {})", item.description, item.quantity, format_cents(item.unit_price))
+                        },
+                        quantity: if whole { item.quantity as i64 } else { 1 },
+                        unit_price: if whole { item.unit_price } else { item.amount },
+                        tax_rate: item.tax_rate.unwrap_or(tax_rate),
+                    }
+                })
+                .collect()
+        };
+
+        let at_midnight = |date: NaiveDate| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        let bill = VendorBillService::create_in(
+            conn,
+            vendor_id,
+            invoice_number.to_string(),
+            None,
+            at_midnight(bill_date),
+            at_midnight(due_date),
+            lines,
+            Some(format!("Captured from {}", doc.original_filename)),
+        )
+        .await?;
+        Ok(bill.base.id)
     }
-    
+
+    #[allow(clippy::collapsible_match)]
     fn generate_suggestions(&self, doc: &OcrDocument) -> Vec<String> {
         let mut suggestions = Vec::new();
-        
+
         match doc.status {
             OcrStatus::RequiresReview => {
                 suggestions.push("Manual review recommended due to low confidence".to_string());
             }
             OcrStatus::Completed => {
-                if doc.document_type == DocumentType::Invoice {
+                if doc.document_type == DocumentType::Invoice && doc.vendor_bill_id.is_none() {
                     suggestions.push("Ready to create vendor invoice".to_string());
                 }
             }
+            OcrStatus::Failed => {
+                if doc.engine.is_none() {
+                    suggestions.push("Upload a PDF with a text layer, or install Tesseract to read images".to_string());
+                }
+            }
             _ => {}
         }
-        
+
         suggestions
     }
-    
-    pub async fn get_document(&self, id: Uuid) -> Result<Option<OcrDocument>> {
-        self.repo.get_document(id).await
+
+    async fn document(&self, pool: &SqlitePool, id: Uuid) -> Result<OcrDocument> {
+        self.repo.get_document(pool, id).await?
+            .ok_or_else(|| Error::not_found("OCR document", &id.to_string()).into())
+    }
+
+    async fn template(&self, pool: &SqlitePool, id: Uuid) -> Result<OcrTemplate> {
+        self.repo.get_template(pool, id).await?
+            .ok_or_else(|| Error::not_found("OCR template", &id.to_string()).into())
+    }
+
+    pub async fn get_document(&self, pool: &SqlitePool, id: Uuid) -> Result<OcrDocument> {
+        self.document(pool, id).await
+    }
+
+    pub async fn list_documents(&self, pool: &SqlitePool, status: Option<OcrStatus>, limit: i64, offset: i64) -> Result<Vec<OcrDocument>> {
+        self.repo.list_documents(pool, status, limit, offset).await
     }
-    
-    pub async fn list_documents(&self, status: Option<OcrStatus>, limit: i64, offset: i64) -> Result<Vec<OcrDocument>> {
-        self.repo.list_documents(status, limit, offset).await
+
+    pub async fn delete_document(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
+        let doc = self.document(pool, id).await?;
+        if matches!(doc.status, OcrStatus::Processing) {
+            return Err(Error::business_rule("Document is being processed").into());
+        }
+        self.repo.delete_document(pool, id).await
     }
-    
-    pub async fn review_document(&self, id: Uuid, reviewer_id: Uuid, corrections: Option<serde_json::Value>) -> Result<OcrDocument> {
-        let mut doc = self.repo.get_document(id).await?
-            .ok_or_else(|| anyhow::anyhow!("Document not found"))?;
-        
-        if let Some(corr) = corrections {
-            doc.extracted_data = Some(corr);
-        }
-        
+
+    /// Accepts a document, applying the reviewer's corrections to its fields. A validated
+    /// invoice becomes a draft vendor bill if it has not already.
+    pub async fn review_document(&self, pool: &SqlitePool, id: Uuid, reviewer_id: Uuid, corrections: Option<Value>) -> Result<OcrDocument> {
+        let mut doc = self.document(pool, id).await?;
+        match doc.status {
+            OcrStatus::Pending | OcrStatus::Queued | OcrStatus::Processing => {
+                return Err(Error::business_rule("Document has not been processed yet").into());
+            }
+            OcrStatus::Validated => return Err(Error::business_rule("Document has already been validated").into()),
+            _ => {}
+        }
+
+        if let Some(corrections) = corrections {
+            let Value::Object(corrections) = corrections else {
+                return Err(Error::validation("Corrections must map field names to their values").into());
+            };
+            let mut data = match doc.extracted_data.take() {
+                Some(Value::Object(data)) => data,
+                _ => Map::new(),
+            };
+            for (field, value) in corrections {
+                if field == "vendor_id" {
+                    doc.vendor_id = erp_core::parse_uuid_opt(value.as_str(), "vendor_id")?;
+                }
+                doc.field_confidence.insert(field.clone(), 1.0);
+                data.insert(field, value);
+            }
+            doc.extracted_data = Some(Value::Object(data));
+        }
+
+        doc.validation_errors.clear();
+        doc.confidence_score = doc.field_confidence.values().copied().reduce(f64::min).or(doc.confidence_score);
         doc.reviewed_by = Some(reviewer_id);
         doc.reviewed_at = Some(Utc::now());
         doc.status = OcrStatus::Validated;
-        
-        self.repo.update_document(&doc).await?;
+
+        let settings = self.repo.get_settings(pool).await?;
+        if doc.document_type == DocumentType::Invoice && doc.vendor_bill_id.is_none() && settings.auto_create_entities {
+            self.save_with_bill(pool, &mut doc).await?;
+        } else {
+            self.repo.update_document(pool, &doc).await?;
+        }
         Ok(doc)
     }
-    
-    pub async fn create_template(&self, template: OcrTemplate) -> Result<OcrTemplate> {
-        self.repo.create_template(&template).await?;
+
+    /// Saves where a layout prints its fields, so documents in that layout are read from there.
+    pub async fn create_template(&self, pool: &SqlitePool, request: CreateTemplateRequest) -> Result<OcrTemplate> {
+        if request.name.trim().is_empty() {
+            return Err(Error::validation("Template name is required").into());
+        }
+        if request.field_mappings.is_empty() {
+            return Err(Error::validation("A template needs at least one field mapping").into());
+        }
+        for mapping in &request.field_mappings {
+            if mapping.target_field.trim().is_empty() {
+                return Err(Error::validation("Every field mapping needs a target_field").into());
+            }
+            for pattern in [&mapping.anchor, &mapping.extraction_pattern].into_iter().flatten() {
+                regex::Regex::new(pattern)
+                    .map_err(|e| Error::validation(format!("{}: invalid pattern {:?}: {}", mapping.target_field, pattern, e)))?;
+            }
+            if let Some(region) = &mapping.region {
+                let fraction = |v: f32| (0.0..=1.0).contains(&v);
+                if region.page == 0 || !fraction(region.x) || !fraction(region.y) || region.width <= 0.0 || region.height <= 0.0
+                    || region.x + region.width > 1.0 || region.y + region.height > 1.0
+                {
+                    return Err(Error::validation(format!(
+                        "{}: a region is a page number from 1 and fractions of the page size",
+                        mapping.target_field
+                    ))
+                    .into());
+                }
+            }
+            if mapping.anchor.is_none() && mapping.region.is_none() && mapping.extraction_pattern.is_none()
+                && mapping.source_field.trim().is_empty()
+            {
+                return Err(Error::validation(format!("{}: needs an anchor, region, pattern or source_field", mapping.target_field)).into());
+            }
+        }
+        if let Some(vendor_id) = request.vendor_id {
+            matching::vendor(pool, vendor_id).await?
+                .ok_or_else(|| Error::not_found("Vendor", &vendor_id.to_string()))?;
+        }
+
+        let template = OcrTemplate {
+            base: BaseEntity::new(),
+            name: request.name.trim().to_string(),
+            document_type: request.document_type,
+            vendor_id: request.vendor_id,
+            field_mappings: request.field_mappings,
+            sample_images: Vec::new(),
+            accuracy_score: 0.0,
+            enabled: true,
+        };
+        self.repo.create_template(pool, &template).await?;
         Ok(template)
     }
-    
-    pub async fn list_templates(&self, document_type: Option<DocumentType>) -> Result<Vec<OcrTemplate>> {
-        self.repo.list_templates(document_type).await
+
+    pub async fn get_template(&self, pool: &SqlitePool, id: Uuid) -> Result<OcrTemplate> {
+        self.template(pool, id).await
+    }
+
+    pub async fn list_templates(&self, pool: &SqlitePool, document_type: Option<DocumentType>) -> Result<Vec<OcrTemplate>> {
+        self.repo.list_templates(pool, document_type).await
     }
-    
-    pub async fn create_batch_job(&self, name: String, document_ids: Vec<Uuid>, template_id: Option<Uuid>) -> Result<OcrBatchJob> {
+
+    pub async fn delete_template(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
+        self.template(pool, id).await?;
+        self.repo.delete_template(pool, id).await
+    }
+
+    /// Queues documents for the background worker, optionally all under one template.
+    pub async fn create_batch_job(&self, pool: &SqlitePool, name: String, document_ids: Vec<Uuid>, template_id: Option<Uuid>) -> Result<OcrBatchJob> {
+        if document_ids.is_empty() {
+            return Err(Error::validation("A batch job needs at least one document").into());
+        }
+        if let Some(template_id) = template_id {
+            self.template(pool, template_id).await?;
+        }
+        let mut documents = Vec::new();
+        for id in &document_ids {
+            documents.push(self.document(pool, *id).await?);
+        }
+        for mut doc in documents {
+            if matches!(doc.status, OcrStatus::Processing | OcrStatus::Validated) || doc.vendor_bill_id.is_some() {
+                continue;
+            }
+            doc.status = OcrStatus::Queued;
+            doc.template_id = template_id.or(doc.template_id);
+            self.repo.update_document(pool, &doc).await?;
+        }
+
         let job = OcrBatchJob {
             base: BaseEntity::new(),
             name,
@@ -265,20 +573,112 @@ impl OcrService {
             completed_at: None,
             error_details: serde_json::json!({}),
         };
-        
-        self.repo.create_batch_job(&job).await?;
+
+        self.repo.create_batch_job(pool, &job).await?;
+        Ok(job)
+    }
+
+    /// A batch job with its progress brought up to date from its documents.
+    pub async fn get_batch_job(&self, pool: &SqlitePool, id: Uuid) -> Result<OcrBatchJob> {
+        let mut job = self.repo.get_batch_job(pool, id).await?
+            .ok_or_else(|| Error::not_found("OCR batch job", &id.to_string()))?;
+        if matches!(job.status, BatchJobStatus::Completed | BatchJobStatus::Cancelled) {
+            return Ok(job);
+        }
+        let (mut successful, mut failed) = (0, 0);
+        let mut errors = Map::new();
+        for document_id in &job.document_ids {
+            match self.repo.get_document(pool, *document_id).await? {
+                Some(doc) => match doc.status {
+                    OcrStatus::Pending | OcrStatus::Queued | OcrStatus::Processing => {}
+                    OcrStatus::Failed => {
+                        failed += 1;
+                        errors.insert(document_id.to_string(), serde_json::to_value(&doc.validation_errors)?);
+                    }
+                    _ => successful += 1,
+                },
+                None => {
+                    failed += 1;
+                    errors.insert(document_id.to_string(), serde_json::json!(["Document was deleted"]));
+                }
+            }
+        }
+        let processed = successful + failed;
+        if processed == job.processed_documents {
+            return Ok(job);
+        }
+        job.processed_documents = processed;
+        job.successful_documents = successful;
+        job.failed_documents = failed;
+        job.error_details = Value::Object(errors);
+        job.started_at = job.started_at.or(Some(Utc::now()));
+        if processed == job.total_documents {
+            job.status = if successful == 0 { BatchJobStatus::Failed } else { BatchJobStatus::Completed };
+            job.completed_at = Some(Utc::now());
+        } else {
+            job.status = BatchJobStatus::Running;
+        }
+        self.repo.update_batch_job(pool, &job).await?;
         Ok(job)
     }
-    
-    pub async fn get_settings(&self) -> Result<OcrSettings> {
-        self.repo.get_settings().await
+
+    pub async fn get_settings(&self, pool: &SqlitePool) -> Result<OcrSettings> {
+        self.repo.get_settings(pool).await
+    }
+
+    pub async fn update_settings(&self, pool: &SqlitePool, settings: OcrSettings) -> Result<()> {
+        if !(0.0..=1.0).contains(&settings.confidence_threshold) {
+            return Err(Error::validation("confidence_threshold must be between 0 and 1").into());
+        }
+        self.repo.update_settings(pool, &settings).await
     }
-    
-    pub async fn update_settings(&self, settings: OcrSettings) -> Result<()> {
-        self.repo.update_settings(&settings).await
+}
+
+/// The fields read from every document of a type, before any template.
+fn default_fields(document_type: &DocumentType) -> Vec<FieldMapping> {
+    match document_type {
+        DocumentType::Invoice => extraction::invoice_fields(),
+        DocumentType::Receipt => extraction::receipt_fields(),
+        _ => Vec::new(),
     }
 }
 
+/// Flags totals that do not add up, which usually means a misread digit.
+fn check_totals(extraction: &mut Extraction, items: &[ExtractedLineItem]) {
+    let (subtotal, tax, total) = (extraction.amount("subtotal"), extraction.amount("tax_amount"), extraction.amount("total_amount"));
+    let mut doubtful = Vec::new();
+    if let (Some(subtotal), Some(total)) = (subtotal, total) {
+        let tax = tax.unwrap_or(0);
+        if subtotal + tax != total {
+            extraction.errors.push(format!(
+                "Subtotal {} plus tax {} does not equal the total {}",
+                format_cents(subtotal), format_cents(tax), format_cents(total)
+            ));
+            doubtful.extend(["subtotal", "tax_amount", "total_amount"]);
+        }
+    }
+    if !items.is_empty() {
+        let sum: i64 = items.iter().map(|i| i.amount).sum();
+        let expected = subtotal.or_else(|| total.map(|t| t - tax.unwrap_or(0)));
+        if let Some(expected) = expected.filter(|e| *e != sum) {
+            extraction.errors.push(format!(
+                "Line items add up to {} but the invoice says {}",
+                format_cents(sum), format_cents(expected)
+            ));
+            doubtful.push("line_items");
+        }
+    }
+    for field in doubtful {
+        if let Some(confidence) = extraction.confidence.get_mut(field) {
+            *confidence = confidence.min(0.5);
+        }
+    }
+}
+
+fn format_cents(cents: i64) -> String {
+    format!("{}{}.{:02}", if cents < 0 { "-" } else { "" }, cents.abs() / 100, cents.abs() % 100)
+}
+
 mod base64 {
     pub fn decode(input: &str) -> Result<Vec<u8>, base64::DecodeError> {
         use base64::Engine;
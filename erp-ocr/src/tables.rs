@@ -0,0 +1,187 @@
+//! Reads line-item tables: a header row naming the columns, then one row per item until the
+//! totals.
+
+use regex::Regex;
+use std::collections::HashSet;
+
+use crate::engine::{TextLine, TextSpan};
+use crate::extraction::{parse_amount, parse_number};
+use crate::models::ExtractedLineItem;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Code,
+    Description,
+    Quantity,
+    UnitPrice,
+    TaxRate,
+    Amount,
+}
+
+#[derive(Debug, Default)]
+pub struct LineItems {
+    pub items: Vec<ExtractedLineItem>,
+    /// Indexes of the header and item lines.
+    pub lines: HashSet<usize>,
+}
+
+struct Patterns {
+    quantity: Regex,
+    unit_price: Regex,
+    tax_rate: Regex,
+    amount: Regex,
+    code: Regex,
+    description: Regex,
+    totals: Regex,
+}
+
+impl Patterns {
+    fn new() -> Self {
+        let build = |p: &str| Regex::new(&format!("(?i){}", p)).expect("valid pattern");
+        Self {
+            quantity: build(r"^(qty|quantity|units|hours|hrs)\b"),
+            unit_price: build(r"^(unit\s*(price|cost)|price|rate|each)\b"),
+            tax_rate: build(r"^(tax|vat|gst)\b"),
+            amount: build(r"^(amount|total|line\s+total|ext(\.|ended)?(\s+(price|amount))?|net)\b"),
+            code: build(r"^(sku|code|part\s*(no\b\.?|#|number)?|(item|product)\s*(no\b\.?|#|code|number))$"),
+            description: build(r"^(description|item|product|service|details|particulars|article)s?\b"),
+            totals: build(r"^(sub-?\s*total|total|tax|vat|gst|sales\s+tax|amount\s+due|balance|grand\s+total|shipping|discount|net\s+total)\b"),
+        }
+    }
+
+    fn classify(&self, header: &str) -> Option<Column> {
+        let header = header.trim().trim_end_matches(':');
+        [
+            (&self.unit_price, Column::UnitPrice),
+            (&self.quantity, Column::Quantity),
+            (&self.tax_rate, Column::TaxRate),
+            (&self.amount, Column::Amount),
+            (&self.code, Column::Code),
+            (&self.description, Column::Description),
+        ]
+        .into_iter()
+        .find(|(pattern, _)| pattern.is_match(header))
+        .map(|(_, column)| column)
+    }
+
+    /// The columns a header line names, or `None` when it is not a header.
+    fn header(&self, line: &TextLine) -> Option<Vec<(Column, f32, f32)>> {
+        let mut columns = Vec::new();
+        for phrase in &line.phrases {
+            let column = self.classify(&phrase.text)?;
+            if columns.iter().any(|(c, _, _)| *c == column) {
+                return None;
+            }
+            columns.push((column, phrase.x, phrase.x + phrase.width));
+        }
+        let has = |c: Column| columns.iter().any(|(column, _, _)| *column == c);
+        (columns.len() >= 2 && has(Column::Description) && (has(Column::Amount) || has(Column::UnitPrice))).then_some(columns)
+    }
+}
+
+/// Every line-item table in `lines`. A table continued on the next page is picked up again
+/// under its repeated header.
+pub fn extract_line_items(lines: &[TextLine]) -> LineItems {
+    let patterns = Patterns::new();
+    let mut found = LineItems::default();
+    let mut index = 0;
+    while index < lines.len() {
+        let Some(columns) = patterns.header(&lines[index]) else {
+            index += 1;
+            continue;
+        };
+        found.lines.insert(index);
+        let header = &lines[index];
+        let mut previous = header;
+        let first_item = found.items.len();
+        index += 1;
+        while let Some(line) = lines.get(index) {
+            if line.page != header.page
+                || line.y - (previous.y + previous.height) > previous.height.max(line.height) * 3.0
+                || patterns.header(line).is_some()
+                || line.phrases.first().is_some_and(|p| patterns.totals.is_match(&p.text))
+            {
+                break;
+            }
+            let cells = cells(&columns, line);
+            match item(&cells) {
+                Some(item) => found.items.push(item),
+                None => {
+                    // A wrapped description continues the item above.
+                    let continues = cells.iter().all(|(c, _)| matches!(c, Column::Description | Column::Code));
+                    match (continues && found.items.len() > first_item, found.items.last_mut()) {
+                        (true, Some(last)) => {
+                            let text = cells.iter().map(|(_, s)| s.text.as_str()).collect::<Vec<_>>().join(" ");
+                            last.description = format!("{} {}", last.description, text).trim().to_string();
+                        }
+                        _ => break,
+                    }
+                }
+            }
+            found.lines.insert(index);
+            previous = line;
+            index += 1;
+        }
+    }
+    found
+}
+
+/// Each phrase of `line` under the column it overlaps most, or else the nearest one.
+fn cells<'a>(columns: &[(Column, f32, f32)], line: &'a TextLine) -> Vec<(Column, &'a TextSpan)> {
+    line.phrases.iter()
+        .filter_map(|phrase| {
+            let (left, right) = (phrase.x, phrase.x + phrase.width);
+            let overlap = |(_, l, r): &&(Column, f32, f32)| right.min(*r) - left.max(*l);
+            let distance = |(_, l, r): &&(Column, f32, f32)| ((l + r) / 2.0 - (left + right) / 2.0).abs();
+            let column = columns.iter().filter(|c| overlap(c) > 0.0).max_by(|a, b| overlap(a).total_cmp(&overlap(b)))
+                .or_else(|| columns.iter().min_by(|a, b| distance(a).total_cmp(&distance(b))))?;
+            Some((column.0, phrase))
+        })
+        .collect()
+}
+
+fn item(cells: &[(Column, &TextSpan)]) -> Option<ExtractedLineItem> {
+    let text = |column: Column| {
+        let parts: Vec<&str> = cells.iter().filter(|(c, _)| *c == column).map(|(_, s)| s.text.as_str()).collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    };
+    let quantity = match text(Column::Quantity) {
+        Some(q) => Some(parse_number(&q)?),
+        None => None,
+    };
+    let unit_price = match text(Column::UnitPrice) {
+        Some(p) => Some(parse_amount(&p)?.0),
+        None => None,
+    };
+    let amount = match text(Column::Amount) {
+        Some(a) => Some(parse_amount(&a)?.0),
+        None => None,
+    };
+    let tax_rate = text(Column::TaxRate).and_then(|t| parse_number(t.trim_end_matches('%').trim()));
+    let code = text(Column::Code);
+    let description = text(Column::Description).or_else(|| code.clone())?;
+
+    let (quantity, unit_price, amount, consistent) = match (quantity, unit_price, amount) {
+        (_, None, None) => return None,
+        (q, Some(u), Some(a)) => {
+            let q = q.unwrap_or(1.0);
+            (q, u, a, ((q * u as f64).round() as i64 - a).abs() <= 1)
+        }
+        (q, Some(u), None) => {
+            let q = q.unwrap_or(1.0);
+            (q, u, (q * u as f64).round() as i64, true)
+        }
+        (Some(q), None, Some(a)) if q != 0.0 => (q, (a as f64 / q).round() as i64, a, true),
+        (_, None, Some(a)) => (1.0, a, a, true),
+    };
+    let read = cells.iter().map(|(_, s)| s.confidence).fold(1.0, f64::min);
+    Some(ExtractedLineItem {
+        description,
+        quantity,
+        unit_price,
+        amount,
+        tax_rate,
+        product_code: code,
+        confidence: if consistent { read } else { read.min(0.5) },
+    })
+}
@@ -0,0 +1,403 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use erp_core::blob::LocalBlobStore;
+use erp_core::pdf::{text_width, Font, PdfWriter};
+use erp_core::{BlobService, Error};
+use erp_ocr::engine::parse_tesseract_tsv;
+use erp_ocr::extraction::{parse_amount, parse_date, parse_number};
+use erp_ocr::*;
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE blobs (sha256 TEXT PRIMARY KEY, size INTEGER NOT NULL, mime_type TEXT NOT NULL, backend TEXT NOT NULL, ref_count INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL)",
+    r#"CREATE TABLE vendors (id TEXT PRIMARY KEY, code TEXT NOT NULL UNIQUE, name TEXT NOT NULL, email TEXT, phone TEXT, website TEXT,
+        payment_terms INTEGER NOT NULL DEFAULT 30, status TEXT NOT NULL DEFAULT 'Active', created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+    r#"CREATE TABLE ocr_documents (id TEXT PRIMARY KEY, document_type TEXT NOT NULL, original_filename TEXT NOT NULL, file_path TEXT NOT NULL,
+        file_size INTEGER NOT NULL, mime_type TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'pending', processing_started_at TEXT,
+        processing_completed_at TEXT, confidence_score REAL, raw_text TEXT, extracted_data TEXT, validation_errors TEXT NOT NULL DEFAULT '[]',
+        reviewed_by TEXT, reviewed_at TEXT, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, checksum TEXT, template_id TEXT, engine TEXT,
+        field_confidence TEXT NOT NULL DEFAULT '{}', vendor_id TEXT, vendor_bill_id TEXT)"#,
+    r#"CREATE TABLE ocr_templates (id TEXT PRIMARY KEY, name TEXT NOT NULL, document_type TEXT NOT NULL, vendor_id TEXT,
+        field_mappings TEXT NOT NULL DEFAULT '[]', sample_images TEXT NOT NULL DEFAULT '[]', accuracy_score REAL NOT NULL DEFAULT 0.0,
+        enabled INTEGER NOT NULL DEFAULT 1, created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+    r#"CREATE TABLE ocr_batch_jobs (id TEXT PRIMARY KEY, name TEXT NOT NULL, document_ids TEXT NOT NULL DEFAULT '[]', template_id TEXT,
+        status TEXT NOT NULL DEFAULT 'pending', total_documents INTEGER NOT NULL DEFAULT 0, processed_documents INTEGER NOT NULL DEFAULT 0,
+        successful_documents INTEGER NOT NULL DEFAULT 0, failed_documents INTEGER NOT NULL DEFAULT 0, started_at TEXT, completed_at TEXT,
+        error_details TEXT NOT NULL DEFAULT '{}', created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+    "CREATE TABLE ocr_settings (id INTEGER PRIMARY KEY CHECK (id = 1), settings TEXT NOT NULL, updated_at TEXT NOT NULL)",
+    r#"CREATE TABLE vendor_bills (id TEXT PRIMARY KEY, bill_number TEXT NOT NULL UNIQUE, vendor_invoice_number TEXT NOT NULL, vendor_id TEXT NOT NULL,
+        purchase_order_id TEXT, bill_date TEXT NOT NULL, due_date TEXT NOT NULL, subtotal INTEGER NOT NULL DEFAULT 0, tax_amount INTEGER NOT NULL DEFAULT 0,
+        total INTEGER NOT NULL DEFAULT 0, amount_paid INTEGER NOT NULL DEFAULT 0, status TEXT NOT NULL DEFAULT 'Draft',
+        match_status TEXT NOT NULL DEFAULT 'Unmatched', notes TEXT, created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+    r#"CREATE TABLE vendor_bill_lines (id TEXT PRIMARY KEY, bill_id TEXT NOT NULL, po_line_id TEXT, product_id TEXT, description TEXT NOT NULL,
+        quantity INTEGER NOT NULL, unit_price INTEGER NOT NULL, tax_rate REAL NOT NULL DEFAULT 0, line_total INTEGER NOT NULL DEFAULT 0,
+        match_quantity INTEGER NOT NULL DEFAULT 0, match_status TEXT NOT NULL DEFAULT 'Unmatched')"#,
+];
+
+async fn setup() -> (SqlitePool, BlobService) {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    for statement in SCHEMA {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    let root = std::env::temp_dir().join(format!("erp-ocr-test-{}", Uuid::new_v4()));
+    let blobs = BlobService::new(Arc::new(LocalBlobStore::new(root.join("blobs"))), root.join("staging"));
+    (pool, blobs)
+}
+
+async fn create_vendor(pool: &SqlitePool, code: &str, name: &str, email: Option<&str>) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO vendors (id, code, name, email, payment_terms, created_at, updated_at) VALUES (?, ?, ?, ?, 45, datetime('now'), datetime('now'))")
+        .bind(id.to_string()).bind(code).bind(name).bind(email)
+        .execute(pool).await.unwrap();
+    id
+}
+
+/// Text drawn on one A4 page: (x, y from the top, size, text). A negative x right-aligns the
+/// text to that distance from the left edge.
+fn pdf(items: &[(f32, f32, f32, &str)]) -> Vec<u8> {
+    let mut pdf = PdfWriter::new((595.0, 842.0), "Invoice");
+    pdf.begin_page();
+    for &(x, y, size, text) in items {
+        let x = if x < 0.0 { -x - text_width(text, Font::Regular, size) } else { x };
+        pdf.text(x, 842.0 - y, Font::Regular, size, text);
+    }
+    pdf.finish();
+    pdf.take_output()
+}
+
+fn invoice(number: &str, total: &str) -> Vec<u8> {
+    pdf(&[
+        (50.0, 60.0, 18.0, "Acme Industrial Supply"),
+        (50.0, 76.0, 9.0, "billing@acme-industrial.com"),
+        (430.0, 60.0, 16.0, "INVOICE"),
+        (360.0, 110.0, 10.0, "Invoice Number:"),
+        (460.0, 110.0, 10.0, number),
+        (360.0, 125.0, 10.0, "Invoice Date:"),
+        (460.0, 125.0, 10.0, "2026-03-02"),
+        (360.0, 140.0, 10.0, "Due Date:"),
+        (460.0, 140.0, 10.0, "April 1, 2026"),
+        (50.0, 150.0, 10.0, "Bill To:"),
+        (50.0, 164.0, 10.0, "Northwind Traders"),
+        (50.0, 220.0, 10.0, "Description"),
+        (-330.0, 220.0, 10.0, "Qty"),
+        (-430.0, 220.0, 10.0, "Unit Price"),
+        (-545.0, 220.0, 10.0, "Amount"),
+        (50.0, 236.0, 10.0, "Steel bolts M8"),
+        (-330.0, 236.0, 10.0, "100"),
+        (-430.0, 236.0, 10.0, "0.25"),
+        (-545.0, 236.0, 10.0, "25.00"),
+        (50.0, 250.0, 10.0, "Hex nuts M8"),
+        (-330.0, 250.0, 10.0, "200"),
+        (-430.0, 250.0, 10.0, "0.10"),
+        (-545.0, 250.0, 10.0, "20.00"),
+        (50.0, 264.0, 10.0, "Assembly labour"),
+        (-330.0, 264.0, 10.0, "1.5"),
+        (-430.0, 264.0, 10.0, "40.00"),
+        (-545.0, 264.0, 10.0, "60.00"),
+        (50.0, 276.0, 10.0, "on site, per hour"),
+        (360.0, 320.0, 10.0, "Subtotal"),
+        (-545.0, 320.0, 10.0, "105.00"),
+        (360.0, 335.0, 10.0, "Tax 8%"),
+        (-545.0, 335.0, 10.0, "8.40"),
+        (360.0, 350.0, 10.0, "Total Due"),
+        (-545.0, 350.0, 10.0, total),
+    ])
+}
+
+fn upload(document_type: DocumentType, content: &[u8], template_id: Option<Uuid>) -> UploadDocumentRequest {
+    UploadDocumentRequest {
+        document_type,
+        filename: "invoice.pdf".into(),
+        content: format!("data:application/pdf;base64,{}", BASE64.encode(content)),
+        template_id,
+        auto_process: true,
+    }
+}
+
+/// Reads every file as the same text, with one word read poorly.
+struct ScannedEngine {
+    words: Vec<(&'static str, f32, f32, f64)>,
+}
+
+#[async_trait]
+impl OcrEngine for ScannedEngine {
+    fn name(&self) -> &'static str {
+        "scanned"
+    }
+
+    fn accepts(&self, _mime_type: &str) -> bool {
+        true
+    }
+
+    async fn recognize(&self, _content: &[u8], _mime_type: &str, _language: &str) -> anyhow::Result<Option<RecognizedDocument>> {
+        let spans = self.words.iter()
+            .map(|&(text, x, y, confidence)| TextSpan { text: text.into(), x, y, width: text.len() as f32 * 6.0, height: 12.0, confidence })
+            .collect();
+        Ok(Some(RecognizedDocument { engine: "scanned".into(), pages: vec![RecognizedPage { width: 600.0, height: 800.0, spans }] }))
+    }
+}
+
+#[test]
+fn test_value_parsers() {
+    assert_eq!(parse_amount("$1,250.00"), Some((125_000, Some("USD"))));
+    assert_eq!(parse_amount("1.250,50 EUR"), Some((125_050, Some("EUR"))));
+    assert_eq!(parse_amount("(42.10)"), Some((-4_210, None)));
+    assert_eq!(parse_amount("12"), Some((1_200, None)));
+    assert_eq!(parse_amount("INV-2041"), None);
+    assert_eq!(parse_number("1,5"), Some(1.5));
+    assert_eq!(parse_number("1,500"), Some(1500.0));
+    let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d);
+    assert_eq!(parse_date("2026-03-02"), date(2026, 3, 2));
+    assert_eq!(parse_date("03/02/2026"), date(2026, 3, 2));
+    assert_eq!(parse_date("25/02/2026"), date(2026, 2, 25));
+    assert_eq!(parse_date("02.03.26"), date(2026, 3, 2));
+    assert_eq!(parse_date("Due April 1, 2026"), date(2026, 4, 1));
+    assert_eq!(parse_date("1 Apr 2026"), date(2026, 4, 1));
+
+    let tsv = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+               1\t1\t0\t0\t0\t0\t0\t0\t1200\t1600\t-1\t\n\
+               5\t1\t1\t1\t1\t1\t100\t200\t80\t20\t96.5\tTotal\n\
+               5\t1\t1\t1\t1\t2\t190\t200\t60\t20\t71\t$9.99\n";
+    let document = parse_tesseract_tsv(tsv);
+    assert_eq!(document.pages[0].width, 1200.0);
+    let lines = document.lines();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].text(), "Total $9.99");
+    assert!((lines[0].confidence() - 0.71).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_pdf_text_layer_is_read_with_positions() {
+    let document = pdf_text_engine_read(&invoice("INV-2041", "$113.40")).await;
+    let lines = document.lines();
+    let texts: Vec<String> = lines.iter().map(|l| l.text()).collect();
+    assert_eq!(texts[0], "Acme Industrial Supply INVOICE");
+    assert!(texts.contains(&"Invoice Number: INV-2041".to_string()), "{:?}", texts);
+    assert!(texts.contains(&"Steel bolts M8 100 0.25 25.00".to_string()), "{:?}", texts);
+    let number = lines.iter().flat_map(|l| &l.phrases).find(|p| p.text == "INV-2041").unwrap();
+    assert!((number.x - 460.0).abs() < 0.5 && (number.y - 100.0).abs() < 0.5, "{:?}", number);
+}
+
+async fn pdf_text_engine_read(content: &[u8]) -> RecognizedDocument {
+    PdfTextEngine.recognize(content, "application/pdf", "en").await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn test_confident_invoice_becomes_a_draft_vendor_bill() {
+    let (pool, blobs) = setup().await;
+    let vendor_id = create_vendor(&pool, "ACME", "Acme Industrial Supply Inc.", Some("billing@acme-industrial.com")).await;
+    create_vendor(&pool, "NW", "Northwind Traders", None).await;
+    let service = OcrService::with_engines(vec![Arc::new(PdfTextEngine)]);
+
+    let doc = service.upload_document(&pool, &blobs, upload(DocumentType::Invoice, &invoice("INV-2041", "$113.40"), None)).await.unwrap();
+    assert!(matches!(doc.status, OcrStatus::Queued));
+    assert_eq!(doc.mime_type, "application/pdf");
+
+    let results = service.process_queued(&pool, &blobs, 10).await.unwrap();
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert!(matches!(result.status, OcrStatus::Completed), "{:?}", result.validation_errors);
+    let data = result.extracted_data.as_ref().unwrap();
+    assert_eq!(data["invoice_number"], "INV-2041");
+    assert_eq!(data["invoice_date"], "2026-03-02");
+    assert_eq!(data["due_date"], "2026-04-01");
+    assert_eq!(data["subtotal"], 10_500);
+    assert_eq!(data["tax_amount"], 840);
+    assert_eq!(data["total_amount"], 11_340);
+    assert_eq!(data["currency"], "USD");
+    assert_eq!(data["vendor_id"], vendor_id.to_string());
+    assert_eq!(data["vendor_name"], "Acme Industrial Supply Inc.");
+    let items = data["line_items"].as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[2]["description"], "Assembly labour on site, per hour");
+    assert_eq!(items[2]["quantity"], 1.5);
+    assert_eq!(items[2]["amount"], 6_000);
+
+    let bill = erp_vendor_bills::VendorBillService::get(&pool, result.vendor_bill_id.unwrap()).await.unwrap();
+    assert_eq!(bill.vendor_id, vendor_id);
+    assert_eq!(bill.vendor_invoice_number, "INV-2041");
+    assert_eq!(bill.status, erp_vendor_bills::VendorBillStatus::Draft);
+    assert_eq!(bill.bill_date.date_naive().to_string(), "2026-03-02");
+    assert_eq!(bill.due_date.date_naive().to_string(), "2026-04-01");
+    assert_eq!((bill.subtotal.amount, bill.tax_amount.amount, bill.total.amount), (10_500, 840, 11_340));
+    assert_eq!(bill.lines[0].quantity, 100);
+    assert_eq!(bill.lines[0].unit_price.amount, 25);
+    assert_eq!(bill.lines[2].quantity, 1);
+    assert_eq!(bill.lines[2].unit_price.amount, 6_000);
+
+    let stored = service.get_document(&pool, doc.base.id).await.unwrap();
+    assert_eq!(stored.vendor_bill_id, result.vendor_bill_id);
+    assert_eq!(stored.engine.as_deref(), Some("pdf-text"));
+    assert!(stored.raw_text.unwrap().contains("Total Due $113.40"));
+
+    // The same invoice again is caught instead of billed twice.
+    service.upload_document(&pool, &blobs, upload(DocumentType::Invoice, &invoice("INV-2041", "$113.40"), None)).await.unwrap();
+    let again = service.process_queued(&pool, &blobs, 10).await.unwrap();
+    assert!(matches!(again[0].status, OcrStatus::RequiresReview));
+    assert!(again[0].validation_errors.iter().any(|e| e.contains("already vendor bill")), "{:?}", again[0].validation_errors);
+    assert!(again[0].vendor_bill_id.is_none());
+}
+
+#[tokio::test]
+async fn test_totals_that_do_not_add_up_go_to_review() {
+    let (pool, blobs) = setup().await;
+    create_vendor(&pool, "ACME", "Acme Industrial Supply", None).await;
+    let service = OcrService::with_engines(vec![Arc::new(PdfTextEngine)]);
+    let doc = service.upload_document(&pool, &blobs, upload(DocumentType::Invoice, &invoice("INV-2042", "$118.40"), None)).await.unwrap();
+
+    let result = service.process_document(&pool, &blobs, doc.base.id).await.unwrap();
+    assert!(matches!(result.status, OcrStatus::RequiresReview));
+    assert!(result.validation_errors.iter().any(|e| e == "Subtotal 105.00 plus tax 8.40 does not equal the total 118.40"), "{:?}", result.validation_errors);
+    assert!(result.vendor_bill_id.is_none());
+    let stored = service.get_document(&pool, doc.base.id).await.unwrap();
+    assert_eq!(stored.field_confidence["total_amount"], 0.5);
+
+    // A document left in Processing by a worker that stopped is claimed again after a while.
+    let claimed_at = |minutes: i64| (chrono::Utc::now() - chrono::Duration::minutes(minutes)).to_rfc3339();
+    sqlx::query("UPDATE ocr_documents SET status = 'Processing', processing_started_at = ? WHERE id = ?")
+        .bind(claimed_at(1)).bind(doc.base.id.to_string()).execute(&pool).await.unwrap();
+    let busy = service.process_document(&pool, &blobs, doc.base.id).await.unwrap_err();
+    assert!(matches!(busy.downcast::<Error>(), Ok(Error::Conflict(_))));
+    sqlx::query("UPDATE ocr_documents SET processing_started_at = ? WHERE id = ?")
+        .bind(claimed_at(60)).bind(doc.base.id.to_string()).execute(&pool).await.unwrap();
+    let result = service.process_document(&pool, &blobs, doc.base.id).await.unwrap();
+    assert!(matches!(result.status, OcrStatus::RequiresReview));
+}
+
+#[tokio::test]
+async fn test_invoices_in_other_currencies_go_to_review() {
+    let (pool, blobs) = setup().await;
+    create_vendor(&pool, "ACME", "Acme Industrial Supply", None).await;
+    let service = OcrService::with_engines(vec![Arc::new(PdfTextEngine)]);
+    let doc = service.upload_document(&pool, &blobs, upload(DocumentType::Invoice, &invoice("INV-2043", "113.40 EUR"), None)).await.unwrap();
+
+    let result = service.process_document(&pool, &blobs, doc.base.id).await.unwrap();
+    assert_eq!(result.extracted_data.as_ref().unwrap()["currency"], "EUR");
+    assert!(matches!(result.status, OcrStatus::RequiresReview), "{:?}", result.validation_errors);
+    assert!(result.validation_errors.iter().any(|e| e.contains("kept in USD")), "{:?}", result.validation_errors);
+    assert!(result.vendor_bill_id.is_none());
+}
+
+#[tokio::test]
+async fn test_low_confidence_fields_wait_for_review_and_review_creates_the_bill() {
+    let (pool, blobs) = setup().await;
+    let vendor_id = create_vendor(&pool, "GLX", "Globex", Some("ap@globex.example")).await;
+    let engine = ScannedEngine {
+        words: vec![
+            ("GLOBEX", 40.0, 30.0, 0.97),
+            ("ap@globex.example", 40.0, 50.0, 0.95),
+            ("Invoice", 300.0, 100.0, 0.98),
+            ("No.", 346.0, 100.0, 0.98),
+            ("G-77", 400.0, 100.0, 0.96),
+            ("Date", 300.0, 120.0, 0.97),
+            ("05/03/2026", 400.0, 120.0, 0.94),
+            ("Total", 300.0, 200.0, 0.95),
+            ("$49.00", 400.0, 200.0, 0.55),
+        ],
+    };
+    let service = OcrService::with_engines(vec![Arc::new(engine)]);
+    let doc = service.upload_document(&pool, &blobs, upload(DocumentType::Invoice, b"%PDF-1.4\n%scan", None)).await.unwrap();
+
+    let result = service.process_document(&pool, &blobs, doc.base.id).await.unwrap();
+    assert!(matches!(result.status, OcrStatus::RequiresReview), "{:?}", result.validation_errors);
+    assert_eq!(result.validation_errors, vec!["total_amount needs review: read with 55% confidence".to_string()]);
+    assert!(result.vendor_bill_id.is_none());
+    assert!((result.confidence - 0.55).abs() < 1e-9);
+    let data = result.extracted_data.unwrap();
+    assert_eq!(data["invoice_number"], "G-77");
+    assert_eq!(data["invoice_date"], "2026-05-03");
+    assert_eq!(data["vendor_id"], vendor_id.to_string());
+
+    let queue = service.list_documents(&pool, Some(OcrStatus::RequiresReview), 10, 0).await.unwrap();
+    assert_eq!(queue.len(), 1);
+
+    let invalid = service.review_document(&pool, doc.base.id, Uuid::new_v4(), Some(json!("49.00"))).await.unwrap_err();
+    assert!(matches!(invalid.downcast::<Error>(), Ok(Error::Validation(_))));
+
+    let reviewer = Uuid::new_v4();
+    let reviewed = service.review_document(&pool, doc.base.id, reviewer, Some(json!({ "total_amount": 4_900 }))).await.unwrap();
+    assert!(matches!(reviewed.status, OcrStatus::Validated));
+    assert_eq!(reviewed.reviewed_by, Some(reviewer));
+    assert_eq!(reviewed.field_confidence["total_amount"], 1.0);
+    assert!(reviewed.validation_errors.is_empty());
+    let bill = erp_vendor_bills::VendorBillService::get(&pool, reviewed.vendor_bill_id.unwrap()).await.unwrap();
+    assert_eq!(bill.total.amount, 4_900);
+    assert_eq!(bill.due_date.date_naive().to_string(), "2026-06-17");
+    assert_eq!(bill.lines[0].description, "Invoice G-77");
+
+    let again = service.process_document(&pool, &blobs, doc.base.id).await.unwrap_err();
+    assert!(matches!(again.downcast::<Error>(), Ok(Error::BusinessRule(_))));
+}
+
+#[tokio::test]
+async fn test_vendor_templates_read_fields_by_anchor_and_region() {
+    let (pool, blobs) = setup().await;
+    let vendor_id = create_vendor(&pool, "ACME", "Acme Industrial Supply", Some("billing@acme-industrial.com")).await;
+    let service = OcrService::with_engines(vec![Arc::new(PdfTextEngine)]);
+
+    let mappings: Vec<FieldMapping> = serde_json::from_value(json!([
+        { "source_field": "", "target_field": "customer_name", "anchor": "^bill to", "data_type": "String" },
+        { "source_field": "", "target_field": "order_reference", "data_type": "String", "required": true,
+          "region": { "x": 0.55, "y": 0.115, "width": 0.4, "height": 0.02 },
+          "extraction_pattern": "INV-(\\d+)", "transform_rules": [{ "transform_type": { "Replace": ["20", "PO-20"] } }] },
+        { "source_field": "", "target_field": "tax_rate", "anchor": "^tax", "data_type": "Percentage",
+          "extraction_pattern": "(\\d+)%", "validation_rules": [{ "rule_type": { "MaxValue": 5.0 }, "error_message": "Tax rate above 5%" }] }
+    ]))
+    .unwrap();
+    let invalid = service.create_template(&pool, CreateTemplateRequest {
+        name: "Broken".into(),
+        document_type: DocumentType::Invoice,
+        vendor_id: None,
+        field_mappings: serde_json::from_value(json!([{ "source_field": "", "target_field": "x", "anchor": "(", "data_type": "String" }])).unwrap(),
+    })
+    .await
+    .unwrap_err();
+    assert!(matches!(invalid.downcast::<Error>(), Ok(Error::Validation(_))));
+
+    let template = service.create_template(&pool, CreateTemplateRequest {
+        name: "Acme invoices".into(),
+        document_type: DocumentType::Invoice,
+        vendor_id: Some(vendor_id),
+        field_mappings: mappings,
+    })
+    .await
+    .unwrap();
+
+    let doc = service.upload_document(&pool, &blobs, upload(DocumentType::Invoice, &invoice("INV-2043", "$113.40"), None)).await.unwrap();
+    let result = service.process_document(&pool, &blobs, doc.base.id).await.unwrap();
+    let data = result.extracted_data.unwrap();
+    assert_eq!(data["customer_name"], "Northwind Traders");
+    assert_eq!(data["order_reference"], "PO-2043");
+    assert_eq!(data["tax_rate"], 8.0);
+    assert_eq!(result.validation_errors, vec!["Tax rate above 5%".to_string()]);
+    assert!(matches!(result.status, OcrStatus::RequiresReview));
+    assert_eq!(service.get_document(&pool, doc.base.id).await.unwrap().template_id, Some(template.base.id));
+
+    let job = service.create_batch_job(&pool, "March".into(), vec![doc.base.id], Some(template.base.id)).await.unwrap();
+    assert!(matches!(service.get_batch_job(&pool, job.base.id).await.unwrap().status, BatchJobStatus::Pending));
+    service.process_queued(&pool, &blobs, 10).await.unwrap();
+    let job = service.get_batch_job(&pool, job.base.id).await.unwrap();
+    assert!(matches!(job.status, BatchJobStatus::Completed));
+    assert_eq!((job.processed_documents, job.successful_documents, job.failed_documents), (1, 1, 0));
+}
+
+#[tokio::test]
+async fn test_unreadable_documents_fail_with_a_reason() {
+    let (pool, blobs) = setup().await;
+    let service = OcrService::with_engines(vec![Arc::new(PdfTextEngine)]);
+    let blank = pdf(&[]);
+    let doc = service.upload_document(&pool, &blobs, upload(DocumentType::Receipt, &blank, None)).await.unwrap();
+
+    let result = service.process_document(&pool, &blobs, doc.base.id).await.unwrap();
+    assert!(matches!(result.status, OcrStatus::Failed));
+    assert!(result.validation_errors[0].contains("No text could be read"), "{:?}", result.validation_errors);
+
+    let settings = OcrSettings { confidence_threshold: 1.5, ..OcrSettings::default() };
+    assert!(service.update_settings(&pool, settings).await.is_err());
+    let settings = OcrSettings { auto_create_entities: false, ..OcrSettings::default() };
+    service.update_settings(&pool, settings).await.unwrap();
+    assert!(!service.get_settings(&pool).await.unwrap().auto_create_entities);
+}
@@ -0,0 +1,108 @@
+use crate::models::PaymentStatus;
+use async_trait::async_trait;
+use erp_core::Result;
+use serde::{Deserialize, Serialize};
+
+/// Raw card data, only ever handed to a gateway to be exchanged for a token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardDetails {
+    pub number: String,
+    pub exp_month: u32,
+    pub exp_year: i32,
+    pub cvc: String,
+    pub holder_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentToken {
+    pub token: String,
+    pub card_last_four: Option<String>,
+    pub card_brand: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizeRequest {
+    pub amount: i64,
+    pub currency: String,
+    pub payment_method_token: String,
+    pub description: Option<String>,
+    /// Capture in the same call instead of holding the funds for a later `capture`.
+    pub capture: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GatewayStatus {
+    Authorized,
+    Captured,
+    /// Captured, but the funds have not settled yet.
+    Pending,
+    RequiresAction,
+    Declined,
+    Voided,
+    Refunded,
+}
+
+impl GatewayStatus {
+    pub fn payment_status(self) -> PaymentStatus {
+        match self {
+            GatewayStatus::Authorized => PaymentStatus::Authorized,
+            GatewayStatus::Captured => PaymentStatus::Completed,
+            GatewayStatus::Pending => PaymentStatus::Processing,
+            GatewayStatus::RequiresAction => PaymentStatus::RequiresAction,
+            GatewayStatus::Declined => PaymentStatus::Failed,
+            GatewayStatus::Voided => PaymentStatus::Cancelled,
+            GatewayStatus::Refunded => PaymentStatus::Refunded,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayResponse {
+    pub transaction_id: String,
+    pub status: GatewayStatus,
+    pub amount: i64,
+    pub fee: i64,
+    pub card_last_four: Option<String>,
+    pub card_brand: Option<String>,
+    pub decline_code: Option<String>,
+    pub message: Option<String>,
+    /// Where the payer completes a challenge when `status` is `RequiresAction`.
+    pub next_action_url: Option<String>,
+}
+
+/// A verified webhook notification about one transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayEvent {
+    pub event_id: String,
+    pub event_type: String,
+    pub transaction_id: String,
+    pub status: Option<GatewayStatus>,
+    pub fee: Option<i64>,
+    pub decline_code: Option<String>,
+}
+
+/// A payment provider. Declines come back as `Ok` with `GatewayStatus::Declined`; `Err` means the
+/// request itself could not be carried out.
+#[async_trait]
+pub trait PaymentGateway: Send + Sync {
+    /// Matches `payment_gateways.gateway_type`, case-insensitively.
+    fn gateway_type(&self) -> &'static str;
+
+    /// The request header carrying the webhook signature.
+    fn signature_header(&self) -> &'static str;
+
+    async fn tokenize(&self, card: CardDetails) -> Result<PaymentToken>;
+
+    async fn authorize(&self, req: AuthorizeRequest) -> Result<GatewayResponse>;
+
+    /// Captures an authorization, in full when `amount` is `None`.
+    async fn capture(&self, transaction_id: &str, amount: Option<i64>) -> Result<GatewayResponse>;
+
+    async fn void(&self, transaction_id: &str) -> Result<GatewayResponse>;
+
+    /// Returns the refund, whose `transaction_id` is the provider's refund id.
+    async fn refund(&self, transaction_id: &str, amount: i64) -> Result<GatewayResponse>;
+
+    /// Checks `signature` against `webhook_secret`, the secret on the gateway's row, and parses the event.
+    fn verify_webhook(&self, webhook_secret: &str, payload: &[u8], signature: &str) -> Result<GatewayEvent>;
+}
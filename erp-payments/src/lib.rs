@@ -1,8 +1,12 @@
+pub mod gateway;
 pub mod models;
 pub mod repository;
 pub mod service;
+pub mod simulator;
 pub mod stripe;
 
+pub use gateway::*;
 pub use models::*;
 pub use service::*;
+pub use simulator::*;
 pub use stripe::*;
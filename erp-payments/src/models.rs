@@ -7,6 +7,9 @@ use uuid::Uuid;
 #[sqlx(type_name = "TEXT")]
 pub enum PaymentStatus {
     Pending,
+    /// Waiting on the payer, e.g. a 3-D Secure challenge.
+    RequiresAction,
+    Authorized,
     Processing,
     Completed,
     Failed,
@@ -15,6 +18,24 @@ pub enum PaymentStatus {
     PartiallyRefunded,
 }
 
+impl std::str::FromStr for PaymentStatus {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "Pending" => Ok(PaymentStatus::Pending),
+            "RequiresAction" => Ok(PaymentStatus::RequiresAction),
+            "Authorized" => Ok(PaymentStatus::Authorized),
+            "Processing" => Ok(PaymentStatus::Processing),
+            "Completed" => Ok(PaymentStatus::Completed),
+            "Failed" => Ok(PaymentStatus::Failed),
+            "Cancelled" => Ok(PaymentStatus::Cancelled),
+            "Refunded" => Ok(PaymentStatus::Refunded),
+            "PartiallyRefunded" => Ok(PaymentStatus::PartiallyRefunded),
+            _ => Err(format!("Unknown payment status: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
 #[sqlx(type_name = "TEXT")]
 pub enum PaymentMethod {
@@ -31,7 +52,7 @@ pub enum PaymentMethod {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
-pub struct PaymentGateway {
+pub struct PaymentGatewayConfig {
     #[sqlx(flatten)]
     pub base: BaseEntity,
     pub code: String,
@@ -42,6 +63,7 @@ pub struct PaymentGateway {
     pub merchant_id: Option<String>,
     pub webhook_secret: Option<String>,
     pub is_live: bool,
+    pub is_default: bool,
     pub is_active: bool,
     pub supported_methods: String,
 }
@@ -153,6 +175,14 @@ pub struct ProcessPaymentRequest {
     pub invoice_id: Option<Uuid>,
     pub description: Option<String>,
     pub metadata: Option<String>,
+    /// Capture immediately; otherwise only authorize and capture later.
+    pub capture: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessedPayment {
+    pub payment: Payment,
+    pub gateway: crate::gateway::GatewayResponse,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,6 +1,7 @@
 use crate::models::*;
-use erp_core::{Error, Result};
-use sqlx::SqlitePool;
+use erp_core::{parse_datetime, parse_datetime_opt, parse_uuid, parse_uuid_opt, BaseEntity, Error, Result};
+use chrono::Utc;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use async_trait::async_trait;
 
@@ -171,10 +172,87 @@ impl StripeRepository for SqliteStripeRepository {
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct PaymentRow {
+    id: String,
+    payment_number: String,
+    gateway_id: Option<String>,
+    invoice_id: Option<String>,
+    customer_id: String,
+    amount: i64,
+    currency: Option<String>,
+    payment_method: String,
+    status: String,
+    gateway_transaction_id: Option<String>,
+    gateway_response: Option<String>,
+    card_last_four: Option<String>,
+    card_brand: Option<String>,
+    bank_name: Option<String>,
+    bank_account_last_four: Option<String>,
+    check_number: Option<String>,
+    refunded_amount: i64,
+    refund_reason: Option<String>,
+    processing_fee: i64,
+    notes: Option<String>,
+    paid_at: Option<String>,
+    created_at: String,
+    updated_at: String,
+    created_by: Option<String>,
+    updated_by: Option<String>,
+}
+
+const PAYMENT_COLUMNS: &str = "id, payment_number, gateway_id, invoice_id, customer_id, amount, currency, payment_method, status, gateway_transaction_id, gateway_response, card_last_four, card_brand, bank_name, bank_account_last_four, check_number, refunded_amount, refund_reason, processing_fee, notes, paid_at, created_at, updated_at, created_by, updated_by";
+
+impl PaymentRow {
+    fn into_payment(self) -> Result<Payment> {
+        Ok(Payment {
+            base: BaseEntity {
+                id: parse_uuid(&self.id, "id")?,
+                created_at: parse_datetime(&self.created_at, "created_at")?,
+                updated_at: parse_datetime(&self.updated_at, "updated_at")?,
+                created_by: parse_uuid_opt(self.created_by.as_deref(), "created_by")?,
+                updated_by: parse_uuid_opt(self.updated_by.as_deref(), "updated_by")?,
+            },
+            payment_number: self.payment_number,
+            gateway_id: parse_uuid_opt(self.gateway_id.as_deref(), "gateway_id")?,
+            invoice_id: parse_uuid_opt(self.invoice_id.as_deref(), "invoice_id")?,
+            customer_id: parse_uuid(&self.customer_id, "customer_id")?,
+            amount: self.amount,
+            currency: self.currency.ok_or_else(|| Error::validation(format!("Payment {} has no currency", self.id)))?,
+            payment_method: match self.payment_method.as_str() {
+                "CreditCard" => PaymentMethod::CreditCard,
+                "DebitCard" => PaymentMethod::DebitCard,
+                "BankTransfer" => PaymentMethod::BankTransfer,
+                "ACH" => PaymentMethod::ACH,
+                "WireTransfer" => PaymentMethod::WireTransfer,
+                "Check" => PaymentMethod::Check,
+                "Cash" => PaymentMethod::Cash,
+                "PayPal" => PaymentMethod::PayPal,
+                "Stripe" => PaymentMethod::Stripe,
+                _ => PaymentMethod::Other,
+            },
+            status: self.status.parse().map_err(Error::validation)?,
+            gateway_transaction_id: self.gateway_transaction_id,
+            gateway_response: self.gateway_response,
+            card_last_four: self.card_last_four,
+            card_brand: self.card_brand,
+            bank_name: self.bank_name,
+            bank_account_last_four: self.bank_account_last_four,
+            check_number: self.check_number,
+            refunded_amount: self.refunded_amount,
+            refund_reason: self.refund_reason,
+            processing_fee: self.processing_fee,
+            notes: self.notes,
+            paid_at: parse_datetime_opt(self.paid_at.as_deref(), "paid_at")?,
+        })
+    }
+}
+
 #[async_trait]
 pub trait PaymentRepository: Send + Sync {
     async fn create(&self, payment: Payment) -> Result<Payment>;
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Payment>>;
+    async fn find_by_gateway_transaction(&self, transaction_id: &str) -> Result<Option<Payment>>;
     async fn list_by_customer(&self, customer_id: Uuid) -> Result<Vec<Payment>>;
     async fn update_refunded_amount(&self, id: Uuid, refunded_amount: i64, status: PaymentStatus, reason: Option<String>) -> Result<()>;
 }
@@ -187,20 +265,18 @@ impl SqlitePaymentRepository {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
     }
-}
 
-#[async_trait]
-impl PaymentRepository for SqlitePaymentRepository {
-    async fn create(&self, payment: Payment) -> Result<Payment> {
+    pub async fn insert_in(conn: &mut SqliteConnection, payment: &Payment) -> Result<()> {
         sqlx::query(
-            r#"INSERT INTO payments (id, payment_number, gateway_id, invoice_id, customer_id, amount, currency, payment_method, status, gateway_transaction_id, gateway_response, card_last_four, card_brand, bank_name, bank_account_last_four, check_number, refunded_amount, refund_reason, processing_fee, notes, paid_at, created_at, updated_at, created_by, updated_by)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            r#"INSERT INTO payments (id, payment_number, gateway_id, invoice_id, customer_id, payment_date, amount, currency, payment_method, status, gateway_transaction_id, gateway_response, card_last_four, card_brand, bank_name, bank_account_last_four, check_number, refunded_amount, refund_reason, processing_fee, notes, paid_at, created_at, updated_at, created_by, updated_by)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         )
-        .bind(payment.base.id)
+        .bind(payment.base.id.to_string())
         .bind(&payment.payment_number)
-        .bind(payment.gateway_id)
-        .bind(payment.invoice_id)
-        .bind(payment.customer_id)
+        .bind(payment.gateway_id.map(|id| id.to_string()))
+        .bind(payment.invoice_id.map(|id| id.to_string()))
+        .bind(payment.customer_id.to_string())
+        .bind(payment.paid_at.unwrap_or(payment.base.created_at).to_rfc3339())
         .bind(payment.amount)
         .bind(&payment.currency)
         .bind(&payment.payment_method)
@@ -216,50 +292,140 @@ impl PaymentRepository for SqlitePaymentRepository {
         .bind(&payment.refund_reason)
         .bind(payment.processing_fee)
         .bind(&payment.notes)
-        .bind(payment.paid_at)
-        .bind(payment.base.created_at)
-        .bind(payment.base.updated_at)
-        .bind(payment.base.created_by)
-        .bind(payment.base.updated_by)
-        .execute(&self.pool).await?;
+        .bind(payment.paid_at.map(|at| at.to_rfc3339()))
+        .bind(payment.base.created_at.to_rfc3339())
+        .bind(payment.base.updated_at.to_rfc3339())
+        .bind(payment.base.created_by.map(|id| id.to_string()))
+        .bind(payment.base.updated_by.map(|id| id.to_string()))
+        .execute(&mut *conn).await?;
+        Ok(())
+    }
+
+    pub async fn get_by_id_in(conn: &mut SqliteConnection, id: Uuid) -> Result<Option<Payment>> {
+        sqlx::query_as::<_, PaymentRow>(&format!("SELECT {} FROM payments WHERE id = ?", PAYMENT_COLUMNS))
+            .bind(id.to_string())
+            .fetch_optional(&mut *conn).await?
+            .map(PaymentRow::into_payment)
+            .transpose()
+    }
+
+    pub async fn find_by_gateway_transaction_in(conn: &mut SqliteConnection, transaction_id: &str) -> Result<Option<Payment>> {
+        sqlx::query_as::<_, PaymentRow>(&format!("SELECT {} FROM payments WHERE gateway_transaction_id = ?", PAYMENT_COLUMNS))
+            .bind(transaction_id)
+            .fetch_optional(&mut *conn).await?
+            .map(PaymentRow::into_payment)
+            .transpose()
+    }
+
+    /// Records the gateway's latest word on a payment (status, amount, response, fee and settlement
+    /// time) if it is still in status `from`. Returns whether it was.
+    pub async fn update_gateway_status_in(conn: &mut SqliteConnection, payment: &Payment, from: &PaymentStatus) -> Result<bool> {
+        let result = sqlx::query(
+            r#"UPDATE payments SET status = ?, amount = ?, gateway_response = ?, processing_fee = ?, paid_at = ?, updated_at = ? WHERE id = ? AND status = ?"#
+        )
+        .bind(&payment.status)
+        .bind(payment.amount)
+        .bind(&payment.gateway_response)
+        .bind(payment.processing_fee)
+        .bind(payment.paid_at.map(|at| at.to_rfc3339()))
+        .bind(payment.base.updated_at.to_rfc3339())
+        .bind(payment.base.id.to_string())
+        .bind(from)
+        .execute(&mut *conn).await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[async_trait]
+impl PaymentRepository for SqlitePaymentRepository {
+    async fn create(&self, payment: Payment) -> Result<Payment> {
+        Self::insert_in(&mut *self.pool.acquire().await?, &payment).await?;
         Ok(payment)
     }
 
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Payment>> {
-        sqlx::query_as::<_, Payment>(
-            "SELECT * FROM payments WHERE id = ?"
-        )
-        .bind(id)
-        .fetch_optional(&self.pool).await
-        .map_err(Error::from)
+        Self::get_by_id_in(&mut *self.pool.acquire().await?, id).await
+    }
+
+    async fn find_by_gateway_transaction(&self, transaction_id: &str) -> Result<Option<Payment>> {
+        Self::find_by_gateway_transaction_in(&mut *self.pool.acquire().await?, transaction_id).await
     }
 
     async fn list_by_customer(&self, customer_id: Uuid) -> Result<Vec<Payment>> {
-        sqlx::query_as::<_, Payment>(
-            "SELECT * FROM payments WHERE customer_id = ?"
-        )
-        .bind(customer_id)
-        .fetch_all(&self.pool).await
-        .map_err(Error::from)
+        sqlx::query_as::<_, PaymentRow>(&format!("SELECT {} FROM payments WHERE customer_id = ? ORDER BY created_at", PAYMENT_COLUMNS))
+            .bind(customer_id.to_string())
+            .fetch_all(&self.pool).await?
+            .into_iter()
+            .map(PaymentRow::into_payment)
+            .collect()
     }
 
     async fn update_refunded_amount(&self, id: Uuid, refunded_amount: i64, status: PaymentStatus, reason: Option<String>) -> Result<()> {
         sqlx::query(
-            r#"UPDATE payments SET refunded_amount = ?, status = ?, refund_reason = ? WHERE id = ?"#
+            r#"UPDATE payments SET refunded_amount = ?, status = ?, refund_reason = ?, updated_at = ? WHERE id = ?"#
         )
         .bind(refunded_amount)
         .bind(status)
         .bind(reason)
-        .bind(id)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id.to_string())
         .execute(&self.pool).await?;
         Ok(())
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct GatewayRow {
+    id: String,
+    code: String,
+    name: String,
+    gateway_type: String,
+    api_key: Option<String>,
+    api_secret: Option<String>,
+    merchant_id: Option<String>,
+    webhook_secret: Option<String>,
+    is_sandbox: Option<bool>,
+    is_default: Option<bool>,
+    is_active: Option<bool>,
+    supported_methods: Option<String>,
+    created_at: String,
+    updated_at: String,
+    created_by: Option<String>,
+    updated_by: Option<String>,
+}
+
+const GATEWAY_COLUMNS: &str = "id, code, name, gateway_type, api_key, api_secret, merchant_id, webhook_secret, is_sandbox, is_default, is_active, supported_methods, created_at, updated_at, created_by, updated_by";
+
+impl GatewayRow {
+    fn into_gateway(self) -> Result<PaymentGatewayConfig> {
+        Ok(PaymentGatewayConfig {
+            base: BaseEntity {
+                id: parse_uuid(&self.id, "id")?,
+                created_at: parse_datetime(&self.created_at, "created_at")?,
+                updated_at: parse_datetime(&self.updated_at, "updated_at")?,
+                created_by: parse_uuid_opt(self.created_by.as_deref(), "created_by")?,
+                updated_by: parse_uuid_opt(self.updated_by.as_deref(), "updated_by")?,
+            },
+            code: self.code,
+            name: self.name,
+            gateway_type: self.gateway_type,
+            api_key: self.api_key,
+            api_secret: self.api_secret,
+            merchant_id: self.merchant_id,
+            webhook_secret: self.webhook_secret,
+            is_live: !self.is_sandbox.unwrap_or(false),
+            is_default: self.is_default.unwrap_or(false),
+            is_active: self.is_active.unwrap_or(true),
+            supported_methods: self.supported_methods.unwrap_or_else(|| "[]".to_string()),
+        })
+    }
+}
+
 #[async_trait]
 pub trait GatewayRepository: Send + Sync {
-    async fn create(&self, gateway: PaymentGateway) -> Result<PaymentGateway>;
-    async fn list_active(&self) -> Result<Vec<PaymentGateway>>;
+    async fn create(&self, gateway: PaymentGatewayConfig) -> Result<PaymentGatewayConfig>;
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<PaymentGatewayConfig>>;
+    async fn list_active(&self) -> Result<Vec<PaymentGatewayConfig>>;
 }
 
 pub struct SqliteGatewayRepository {
@@ -270,16 +436,30 @@ impl SqliteGatewayRepository {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
     }
+
+    /// Notes that a webhook event has been received. Returns false if it already had been.
+    pub async fn record_event_in(conn: &mut SqliteConnection, gateway_id: Uuid, event_id: &str, event_type: &str, transaction_id: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO payment_gateway_events (gateway_id, event_id, event_type, transaction_id, received_at) VALUES (?, ?, ?, ?, ?)"
+        )
+        .bind(gateway_id.to_string())
+        .bind(event_id)
+        .bind(event_type)
+        .bind(transaction_id)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *conn).await?;
+        Ok(result.rows_affected() == 1)
+    }
 }
 
 #[async_trait]
 impl GatewayRepository for SqliteGatewayRepository {
-    async fn create(&self, gateway: PaymentGateway) -> Result<PaymentGateway> {
+    async fn create(&self, gateway: PaymentGatewayConfig) -> Result<PaymentGatewayConfig> {
         sqlx::query(
-            r#"INSERT INTO payment_gateways (id, code, name, gateway_type, api_key, api_secret, merchant_id, webhook_secret, is_live, is_active, supported_methods, created_at, updated_at, created_by, updated_by)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            r#"INSERT INTO payment_gateways (id, code, name, gateway_type, api_key, api_secret, merchant_id, webhook_secret, is_sandbox, is_default, is_active, supported_methods, created_at, updated_at, created_by, updated_by)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         )
-        .bind(gateway.base.id)
+        .bind(gateway.base.id.to_string())
         .bind(&gateway.code)
         .bind(&gateway.name)
         .bind(&gateway.gateway_type)
@@ -287,23 +467,35 @@ impl GatewayRepository for SqliteGatewayRepository {
         .bind(&gateway.api_secret)
         .bind(&gateway.merchant_id)
         .bind(&gateway.webhook_secret)
-        .bind(gateway.is_live)
+        .bind(!gateway.is_live)
+        .bind(gateway.is_default)
         .bind(gateway.is_active)
         .bind(&gateway.supported_methods)
-        .bind(gateway.base.created_at)
-        .bind(gateway.base.updated_at)
-        .bind(gateway.base.created_by)
-        .bind(gateway.base.updated_by)
+        .bind(gateway.base.created_at.to_rfc3339())
+        .bind(gateway.base.updated_at.to_rfc3339())
+        .bind(gateway.base.created_by.map(|id| id.to_string()))
+        .bind(gateway.base.updated_by.map(|id| id.to_string()))
         .execute(&self.pool).await?;
         Ok(gateway)
     }
 
-    async fn list_active(&self) -> Result<Vec<PaymentGateway>> {
-        sqlx::query_as::<_, PaymentGateway>(
-            "SELECT * FROM payment_gateways WHERE is_active = 1"
-        )
-        .fetch_all(&self.pool).await
-        .map_err(|e| Error::Internal(e.into()))
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<PaymentGatewayConfig>> {
+        sqlx::query_as::<_, GatewayRow>(&format!("SELECT {} FROM payment_gateways WHERE id = ?", GATEWAY_COLUMNS))
+            .bind(id.to_string())
+            .fetch_optional(&self.pool).await?
+            .map(GatewayRow::into_gateway)
+            .transpose()
+    }
+
+    /// Active gateways, the default first.
+    async fn list_active(&self) -> Result<Vec<PaymentGatewayConfig>> {
+        sqlx::query_as::<_, GatewayRow>(&format!(
+            "SELECT {} FROM payment_gateways WHERE is_active = 1 ORDER BY is_default DESC, created_at", GATEWAY_COLUMNS
+        ))
+        .fetch_all(&self.pool).await?
+        .into_iter()
+        .map(GatewayRow::into_gateway)
+        .collect()
     }
 }
 
@@ -326,22 +518,24 @@ impl SqliteRefundRepository {
 impl RefundRepository for SqliteRefundRepository {
     async fn create(&self, refund: Refund) -> Result<Refund> {
         sqlx::query(
-            r#"INSERT INTO refunds (id, refund_number, payment_id, amount, currency, reason, status, gateway_refund_id, processed_at, created_at, updated_at, created_by, updated_by)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            r#"INSERT INTO refunds (id, refund_number, customer_id, refund_date, payment_id, amount, currency, reason, status, gateway_refund_id, processed_at, created_at, updated_at, created_by, updated_by)
+               VALUES (?, ?, (SELECT customer_id FROM payments WHERE id = ?), ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         )
-        .bind(refund.base.id)
+        .bind(refund.base.id.to_string())
         .bind(&refund.refund_number)
-        .bind(refund.payment_id)
+        .bind(refund.payment_id.to_string())
+        .bind(refund.base.created_at.to_rfc3339())
+        .bind(refund.payment_id.to_string())
         .bind(refund.amount)
         .bind(&refund.currency)
         .bind(&refund.reason)
         .bind(&refund.status)
         .bind(&refund.gateway_refund_id)
-        .bind(refund.processed_at)
-        .bind(refund.base.created_at)
-        .bind(refund.base.updated_at)
-        .bind(refund.base.created_by)
-        .bind(refund.base.updated_by)
+        .bind(refund.processed_at.map(|at| at.to_rfc3339()))
+        .bind(refund.base.created_at.to_rfc3339())
+        .bind(refund.base.updated_at.to_rfc3339())
+        .bind(refund.base.created_by.map(|id| id.to_string()))
+        .bind(refund.base.updated_by.map(|id| id.to_string()))
         .execute(&self.pool).await?;
         Ok(refund)
     }
@@ -360,24 +554,29 @@ impl SqlitePaymentAllocationRepository {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
     }
-}
 
-#[async_trait]
-impl PaymentAllocationRepository for SqlitePaymentAllocationRepository {
-    async fn create(&self, allocation: PaymentAllocation) -> Result<PaymentAllocation> {
+    pub async fn insert_in(conn: &mut SqliteConnection, allocation: &PaymentAllocation) -> Result<()> {
         sqlx::query(
             r#"INSERT INTO payment_allocations (id, payment_id, invoice_id, amount, created_at, updated_at, created_by, updated_by)
                VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#
         )
-        .bind(allocation.base.id)
-        .bind(allocation.payment_id)
-        .bind(allocation.invoice_id)
+        .bind(allocation.base.id.to_string())
+        .bind(allocation.payment_id.to_string())
+        .bind(allocation.invoice_id.to_string())
         .bind(allocation.amount)
-        .bind(allocation.base.created_at)
-        .bind(allocation.base.updated_at)
-        .bind(allocation.base.created_by)
-        .bind(allocation.base.updated_by)
-        .execute(&self.pool).await?;
+        .bind(allocation.base.created_at.to_rfc3339())
+        .bind(allocation.base.updated_at.to_rfc3339())
+        .bind(allocation.base.created_by.map(|id| id.to_string()))
+        .bind(allocation.base.updated_by.map(|id| id.to_string()))
+        .execute(&mut *conn).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PaymentAllocationRepository for SqlitePaymentAllocationRepository {
+    async fn create(&self, allocation: PaymentAllocation) -> Result<PaymentAllocation> {
+        Self::insert_in(&mut *self.pool.acquire().await?, &allocation).await?;
         Ok(allocation)
     }
 }
@@ -1,8 +1,13 @@
+use crate::gateway::*;
 use crate::models::*;
 use crate::repository::*;
-use erp_core::{BaseEntity, Error, Result};
+use crate::simulator::SimulatorGateway;
+use erp_core::{BaseEntity, Currency, Error, Result};
+use erp_finance::{PostingDocument, PostingEvent, PostingService};
 use chrono::Utc;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct PaymentService<
@@ -13,6 +18,7 @@ pub struct PaymentService<
     repo: P,
     refund_repo: R,
     allocation_repo: A,
+    ledger_pool: Option<SqlitePool>,
 }
 
 impl PaymentService<SqlitePaymentRepository, SqliteRefundRepository, SqlitePaymentAllocationRepository> {
@@ -20,7 +26,8 @@ impl PaymentService<SqlitePaymentRepository, SqliteRefundRepository, SqlitePayme
         Self {
             repo: SqlitePaymentRepository::new(pool.clone()),
             refund_repo: SqliteRefundRepository::new(pool.clone()),
-            allocation_repo: SqlitePaymentAllocationRepository::new(pool),
+            allocation_repo: SqlitePaymentAllocationRepository::new(pool.clone()),
+            ledger_pool: Some(pool),
         }
     }
 }
@@ -36,6 +43,7 @@ where
             repo,
             refund_repo,
             allocation_repo,
+            ledger_pool: None,
         }
     }
 
@@ -72,18 +80,41 @@ where
             notes: req.notes,
             paid_at: Some(now),
         };
-        self.repo.create(payment.clone()).await?;
+        let allocation = req.invoice_id.map(|invoice_id| Self::allocation(payment.base.id, invoice_id, req.amount));
         
-        if let Some(invoice_id) = req.invoice_id {
-            self.allocate_to_invoice(payment.base.id, invoice_id, req.amount).await?;
+        // With a ledger the payment, its allocation and its journal entry commit together.
+        let Some(pool) = &self.ledger_pool else {
+            self.repo.create(payment.clone()).await?;
+            if let Some(allocation) = allocation {
+                self.allocation_repo.create(allocation).await?;
+            }
+            return Ok(payment);
+        };
+        let currency = payment.currency.parse::<Currency>().map_err(Error::validation)?;
+        let mut tx = pool.begin().await?;
+        SqlitePaymentRepository::insert_in(&mut tx, &payment).await?;
+        if let Some(allocation) = &allocation {
+            SqlitePaymentAllocationRepository::insert_in(&mut tx, allocation).await?;
         }
+        PostingService::post_document(&mut tx, PostingDocument {
+            event: PostingEvent::CustomerPayment,
+            source_type: "Payment".to_string(),
+            source_id: payment.base.id,
+            source_number: payment.payment_number.clone(),
+            date: now,
+            amount: payment.amount,
+            tax_amount: 0,
+            currency,
+            description: Some(format!("Customer payment {}", payment.payment_number)),
+        }).await?;
+        tx.commit().await?;
         
         Ok(payment)
     }
 
-    async fn allocate_to_invoice(&self, payment_id: Uuid, invoice_id: Uuid, amount: i64) -> Result<()> {
+    fn allocation(payment_id: Uuid, invoice_id: Uuid, amount: i64) -> PaymentAllocation {
         let now = Utc::now();
-        let allocation = PaymentAllocation {
+        PaymentAllocation {
             base: BaseEntity {
                 id: Uuid::new_v4(),
                 created_at: now,
@@ -94,9 +125,7 @@ where
             payment_id,
             invoice_id,
             amount,
-        };
-        self.allocation_repo.create(allocation).await?;
-        Ok(())
+        }
     }
 
     pub async fn get(&self, id: Uuid) -> Result<Option<Payment>> {
@@ -153,40 +182,78 @@ where
 pub struct GatewayService<
     G: GatewayRepository = SqliteGatewayRepository,
     P: PaymentRepository = SqlitePaymentRepository,
-    A: PaymentAllocationRepository = SqlitePaymentAllocationRepository,
+    R: RefundRepository = SqliteRefundRepository,
 > {
+    pool: SqlitePool,
     repo: G,
     payment_repo: P,
-    allocation_repo: A,
+    refund_repo: R,
+    simulator: Option<Arc<SimulatorGateway>>,
+    gateways: HashMap<String, Arc<dyn PaymentGateway>>,
 }
 
-impl GatewayService<SqliteGatewayRepository, SqlitePaymentRepository, SqlitePaymentAllocationRepository> {
+impl GatewayService<SqliteGatewayRepository, SqlitePaymentRepository, SqliteRefundRepository> {
     pub fn new(pool: SqlitePool) -> Self {
-        Self {
-            repo: SqliteGatewayRepository::new(pool.clone()),
-            payment_repo: SqlitePaymentRepository::new(pool.clone()),
-            allocation_repo: SqlitePaymentAllocationRepository::new(pool),
-        }
+        Self::with_repos(
+            pool.clone(),
+            SqliteGatewayRepository::new(pool.clone()),
+            SqlitePaymentRepository::new(pool.clone()),
+            SqliteRefundRepository::new(pool),
+        )
     }
 }
 
-impl<G, P, A> GatewayService<G, P, A>
+impl<G, P, R> GatewayService<G, P, R>
 where
     G: GatewayRepository,
     P: PaymentRepository,
-    A: PaymentAllocationRepository,
+    R: RefundRepository,
 {
-    pub fn with_repos(repo: G, payment_repo: P, allocation_repo: A) -> Self {
+    /// Starts with no providers; add them with [`Self::with_gateway`] and [`Self::with_simulator`].
+    /// Status changes and their allocations are written through `pool` in one transaction.
+    pub fn with_repos(pool: SqlitePool, repo: G, payment_repo: P, refund_repo: R) -> Self {
         Self {
+            pool,
             repo,
             payment_repo,
-            allocation_repo,
+            refund_repo,
+            simulator: None,
+            gateways: HashMap::new(),
         }
     }
 
-    pub async fn create(&self, code: String, name: String, gateway_type: String, supported_methods: Vec<String>) -> Result<PaymentGateway> {
+    /// Serves every configured gateway whose `gateway_type` matches `gateway.gateway_type()`.
+    pub fn with_gateway(mut self, gateway: Arc<dyn PaymentGateway>) -> Self {
+        self.gateways.insert(gateway.gateway_type().to_string(), gateway);
+        self
+    }
+
+    /// Serves `Simulator` gateways. For development and testing only.
+    pub fn with_simulator(mut self, simulator: SimulatorGateway) -> Self {
+        let simulator = Arc::new(simulator);
+        self.simulator = Some(simulator.clone());
+        self.with_gateway(simulator)
+    }
+
+    /// The simulator, if registered, for driving challenges and settlement in tests.
+    pub fn simulator(&self) -> Option<Arc<SimulatorGateway>> {
+        self.simulator.clone()
+    }
+
+    pub fn gateway_for(&self, config: &PaymentGatewayConfig) -> Result<Arc<dyn PaymentGateway>> {
+        self.gateways.get(&config.gateway_type.to_lowercase()).cloned()
+            .ok_or_else(|| Error::business_rule(format!("No {} gateway is available", config.gateway_type)))
+    }
+
+    /// The default active gateway, or else the oldest active one, that this build can serve; for
+    /// flows such as POS and the customer portal that do not choose one.
+    pub async fn default_gateway(&self) -> Result<Option<Arc<dyn PaymentGateway>>> {
+        Ok(self.repo.list_active().await?.iter().find_map(|config| self.gateway_for(config).ok()))
+    }
+
+    pub async fn create(&self, code: String, name: String, gateway_type: String, supported_methods: Vec<String>, webhook_secret: Option<String>) -> Result<PaymentGatewayConfig> {
         let now = Utc::now();
-        let gateway = PaymentGateway {
+        let gateway = PaymentGatewayConfig {
             base: BaseEntity {
                 id: Uuid::new_v4(),
                 created_at: now,
@@ -200,29 +267,47 @@ where
             api_key: None,
             api_secret: None,
             merchant_id: None,
-            webhook_secret: None,
+            webhook_secret: webhook_secret.filter(|secret| !secret.is_empty()),
             is_live: false,
+            is_default: false,
             is_active: true,
             supported_methods: serde_json::to_string(&supported_methods)
                 .map_err(|e| Error::Internal(anyhow::anyhow!("Failed to serialize supported methods: {}", e)))?,
         };
+        self.gateway_for(&gateway)?;
         self.repo.create(gateway.clone()).await?;
         Ok(gateway)
     }
 
-    pub async fn list_active(&self) -> Result<Vec<PaymentGateway>> {
+    pub async fn list_active(&self) -> Result<Vec<PaymentGatewayConfig>> {
         self.repo.list_active().await
     }
 
-    pub async fn process_payment(&self, req: ProcessPaymentRequest) -> Result<Payment> {
-        let _gateway = self.repo.list_active().await?
-            .into_iter().find(|g| g.base.id == req.gateway_id)
-            .ok_or_else(|| Error::not_found("Gateway", &req.gateway_id.to_string()))?;
-        
+    pub async fn get_payment(&self, id: Uuid) -> Result<Option<Payment>> {
+        self.payment_repo.get_by_id(id).await
+    }
+
+    pub async fn tokenize(&self, gateway_id: Uuid, card: CardDetails) -> Result<PaymentToken> {
+        self.active_gateway(gateway_id).await?.tokenize(card).await
+    }
+
+    /// Authorizes, and unless `capture` is false captures, a card payment. The payment is recorded
+    /// whatever the outcome, so declines and pending challenges leave a trail.
+    pub async fn process_payment(&self, req: ProcessPaymentRequest) -> Result<ProcessedPayment> {
+        if req.amount <= 0 {
+            return Err(Error::validation("Amount must be greater than zero"));
+        }
+        let gateway = self.active_gateway(req.gateway_id).await?;
+        let response = gateway.authorize(AuthorizeRequest {
+            amount: req.amount,
+            currency: req.currency.clone(),
+            payment_method_token: req.payment_method_token,
+            description: req.description.clone(),
+            capture: req.capture,
+        }).await?;
+
         let now = Utc::now();
-        let payment_number = format!("PAY-{}-{}", now.format("%Y%m%d%H%M%S"), &Uuid::new_v4().to_string()[0..8]);
-        let processing_fee = (req.amount as f64 * 0.029 + 30.0) as i64;
-        
+        let status = response.status.payment_status();
         let payment = Payment {
             base: BaseEntity {
                 id: Uuid::new_v4(),
@@ -231,49 +316,216 @@ where
                 created_by: None,
                 updated_by: None,
             },
-            payment_number,
+            payment_number: format!("PAY-{}-{}", now.format("%Y%m%d%H%M%S"), &Uuid::new_v4().to_string()[0..8]),
             gateway_id: Some(req.gateway_id),
             invoice_id: req.invoice_id,
             customer_id: req.customer_id,
             amount: req.amount,
             currency: req.currency,
             payment_method: PaymentMethod::CreditCard,
-            status: PaymentStatus::Completed,
-            gateway_transaction_id: Some(format!("txn_{}", Uuid::new_v4())),
-            gateway_response: Some(r#"{"status": "succeeded"}"#.to_string()),
-            card_last_four: Some("4242".to_string()),
-            card_brand: Some("Visa".to_string()),
+            paid_at: (status == PaymentStatus::Completed).then_some(now),
+            status,
+            gateway_transaction_id: Some(response.transaction_id.clone()).filter(|id| !id.is_empty()),
+            gateway_response: Some(to_json(&response)?),
+            card_last_four: response.card_last_four.clone(),
+            card_brand: response.card_brand.clone(),
             bank_name: None,
             bank_account_last_four: None,
             check_number: None,
             refunded_amount: 0,
             refund_reason: None,
-            processing_fee,
+            processing_fee: response.fee,
             notes: req.description,
-            paid_at: Some(now),
         };
-        self.payment_repo.create(payment.clone()).await?;
-        
-        if let Some(invoice_id) = req.invoice_id {
-            let allocation = PaymentAllocation {
-                base: BaseEntity {
-                    id: Uuid::new_v4(),
-                    created_at: now,
-                    updated_at: now,
-                    created_by: None,
-                    updated_by: None,
-                },
-                payment_id: payment.base.id,
-                invoice_id,
-                amount: req.amount,
-            };
-            self.allocation_repo.create(allocation).await?;
+        let mut tx = self.pool.begin().await?;
+        SqlitePaymentRepository::insert_in(&mut tx, &payment).await?;
+        if payment.status == PaymentStatus::Completed {
+            allocate_in(&mut tx, &payment).await?;
         }
-        
+        tx.commit().await?;
+        Ok(ProcessedPayment { payment, gateway: response })
+    }
+
+    pub async fn capture_payment(&self, payment_id: Uuid, amount: Option<i64>) -> Result<ProcessedPayment> {
+        let (payment, gateway, transaction_id) = self.gateway_payment(payment_id).await?;
+        if payment.status != PaymentStatus::Authorized {
+            return Err(Error::business_rule("Only an authorized payment can be captured"));
+        }
+        let response = gateway.capture(&transaction_id, amount).await?;
+        let payment = self.transition(&payment, response.status, Some(response.fee), Some(response.amount), to_json(&response)?).await?;
+        Ok(ProcessedPayment { payment, gateway: response })
+    }
+
+    pub async fn void_payment(&self, payment_id: Uuid) -> Result<ProcessedPayment> {
+        let (payment, gateway, transaction_id) = self.gateway_payment(payment_id).await?;
+        if !matches!(payment.status, PaymentStatus::Authorized | PaymentStatus::RequiresAction) {
+            return Err(Error::business_rule("Only an uncaptured payment can be voided"));
+        }
+        let response = gateway.void(&transaction_id).await?;
+        let payment = self.transition(&payment, response.status, None, None, to_json(&response)?).await?;
+        Ok(ProcessedPayment { payment, gateway: response })
+    }
+
+    /// Refunds through the gateway that took the payment, then records the refund.
+    pub async fn refund_payment(&self, req: CreateRefundRequest, user_id: Option<Uuid>) -> Result<Refund> {
+        let (payment, gateway, transaction_id) = self.gateway_payment(req.payment_id).await?;
+        if !matches!(payment.status, PaymentStatus::Completed | PaymentStatus::PartiallyRefunded) {
+            return Err(Error::business_rule("Only a settled payment can be refunded"));
+        }
+        if req.amount <= 0 || req.amount > payment.amount - payment.refunded_amount {
+            return Err(Error::business_rule("Refund amount exceeds available balance"));
+        }
+
+        let response = gateway.refund(&transaction_id, req.amount).await?;
+        if response.status == GatewayStatus::Declined {
+            return Err(Error::business_rule(format!(
+                "Gateway declined the refund: {}", response.decline_code.as_deref().unwrap_or("unknown reason")
+            )));
+        }
+
+        let now = Utc::now();
+        let refund = Refund {
+            base: BaseEntity {
+                id: Uuid::new_v4(),
+                created_at: now,
+                updated_at: now,
+                created_by: user_id,
+                updated_by: None,
+            },
+            refund_number: format!("RFD-{}-{}", now.format("%Y%m%d%H%M%S"), &Uuid::new_v4().to_string()[0..8]),
+            payment_id: payment.base.id,
+            amount: req.amount,
+            currency: payment.currency.clone(),
+            reason: req.reason,
+            status: "Completed".to_string(),
+            gateway_refund_id: Some(response.transaction_id),
+            processed_at: Some(now),
+        };
+        self.refund_repo.create(refund.clone()).await?;
+
+        let refunded = payment.refunded_amount + req.amount;
+        let status = if refunded >= payment.amount { PaymentStatus::Refunded } else { PaymentStatus::PartiallyRefunded };
+        self.payment_repo.update_refunded_amount(payment.base.id, refunded, status, Some(refund.reason.clone())).await?;
+        Ok(refund)
+    }
+
+    /// Verifies a webhook from the given gateway against the gateway's `webhook_secret` and applies
+    /// it to the payment it concerns, once per event id. Returns `None` for events about
+    /// transactions this system did not start.
+    pub async fn handle_webhook(&self, gateway_id: Uuid, payload: &[u8], signature: &str) -> Result<Option<Payment>> {
+        let config = self.active_config(gateway_id).await?;
+        let secret = config.webhook_secret.as_deref()
+            .ok_or_else(|| Error::business_rule(format!("Gateway {} has no webhook secret", config.code)))?;
+        let event = self.gateway_for(&config)?.verify_webhook(secret, payload, signature)?;
+
+        let mut tx = self.pool.begin().await?;
+        let first_delivery = SqliteGatewayRepository::record_event_in(&mut tx, gateway_id, &event.event_id, &event.event_type, &event.transaction_id).await?;
+        let payment = SqlitePaymentRepository::find_by_gateway_transaction_in(&mut tx, &event.transaction_id).await?
+            .filter(|payment| payment.gateway_id == Some(gateway_id));
+        let (Some(payment), Some(status)) = (payment, event.status) else {
+            tx.commit().await?;
+            return Ok(None);
+        };
+        if !first_delivery
+            || matches!(payment.status, PaymentStatus::Refunded | PaymentStatus::PartiallyRefunded)
+            || payment.status == status.payment_status()
+        {
+            tx.commit().await?;
+            return Ok(Some(payment));
+        }
+        let payment = apply_in(&mut tx, payment, status, event.fee, None, to_json(&event)?).await?;
+        tx.commit().await?;
+        Ok(Some(payment))
+    }
+
+    /// The signature header the gateway behind `gateway_id` sends with its webhooks.
+    pub async fn signature_header(&self, gateway_id: Uuid) -> Result<&'static str> {
+        Ok(self.active_gateway(gateway_id).await?.signature_header())
+    }
+
+    async fn active_config(&self, gateway_id: Uuid) -> Result<PaymentGatewayConfig> {
+        self.repo.get_by_id(gateway_id).await?
+            .filter(|gateway| gateway.is_active)
+            .ok_or_else(|| Error::not_found("Gateway", &gateway_id.to_string()))
+    }
+
+    async fn active_gateway(&self, gateway_id: Uuid) -> Result<Arc<dyn PaymentGateway>> {
+        self.gateway_for(&self.active_config(gateway_id).await?)
+    }
+
+    async fn gateway_payment(&self, payment_id: Uuid) -> Result<(Payment, Arc<dyn PaymentGateway>, String)> {
+        let payment = self.payment_repo.get_by_id(payment_id).await?
+            .ok_or_else(|| Error::not_found("Payment", &payment_id.to_string()))?;
+        let (Some(gateway_id), Some(transaction_id)) = (payment.gateway_id, payment.gateway_transaction_id.clone()) else {
+            return Err(Error::business_rule("Payment was not taken through a gateway"));
+        };
+        let gateway = self.active_gateway(gateway_id).await?;
+        Ok((payment, gateway, transaction_id))
+    }
+
+    /// Applies a gateway response to a payment the caller read in status `seen.status`, failing if
+    /// the payment has moved on since.
+    async fn transition(&self, seen: &Payment, status: GatewayStatus, fee: Option<i64>, amount: Option<i64>, raw: String) -> Result<Payment> {
+        let mut tx = self.pool.begin().await?;
+        let payment = SqlitePaymentRepository::get_by_id_in(&mut tx, seen.base.id).await?
+            .ok_or_else(|| Error::not_found("Payment", &seen.base.id.to_string()))?;
+        if payment.status != seen.status {
+            return Err(Error::Conflict("Payment changed while the gateway was being called".to_string()));
+        }
+        let payment = apply_in(&mut tx, payment, status, fee, amount, raw).await?;
+        tx.commit().await?;
         Ok(payment)
     }
 }
 
+/// Moves a payment to the gateway's status, on the condition that it is still in the status it was
+/// read in, and allocates it to its invoice when it becomes completed.
+async fn apply_in(conn: &mut SqliteConnection, mut payment: Payment, status: GatewayStatus, fee: Option<i64>, amount: Option<i64>, raw: String) -> Result<Payment> {
+    let from = payment.status.clone();
+    let now = Utc::now();
+    payment.status = status.payment_status();
+    if let Some(fee) = fee.filter(|fee| *fee > 0) {
+        payment.processing_fee = fee;
+    }
+    if let Some(amount) = amount.filter(|amount| *amount > 0) {
+        payment.amount = amount;
+    }
+    if payment.status == PaymentStatus::Completed && payment.paid_at.is_none() {
+        payment.paid_at = Some(now);
+    }
+    payment.gateway_response = Some(raw);
+    payment.base.updated_at = now;
+    if !SqlitePaymentRepository::update_gateway_status_in(conn, &payment, &from).await? {
+        return Err(Error::Conflict("Payment changed while it was being updated".to_string()));
+    }
+    if from != PaymentStatus::Completed && payment.status == PaymentStatus::Completed {
+        allocate_in(conn, &payment).await?;
+    }
+    Ok(payment)
+}
+
+async fn allocate_in(conn: &mut SqliteConnection, payment: &Payment) -> Result<()> {
+    let Some(invoice_id) = payment.invoice_id else { return Ok(()) };
+    let now = Utc::now();
+    SqlitePaymentAllocationRepository::insert_in(conn, &PaymentAllocation {
+        base: BaseEntity {
+            id: Uuid::new_v4(),
+            created_at: now,
+            updated_at: now,
+            created_by: None,
+            updated_by: None,
+        },
+        payment_id: payment.base.id,
+        invoice_id,
+        amount: payment.amount,
+    }).await
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value)
+        .map_err(|e| Error::Internal(anyhow::anyhow!("Failed to serialize gateway response: {}", e)))
+}
+
 
 pub struct CustomerPaymentMethodService<
     R: CustomerPaymentMethodRepository = SqliteCustomerPaymentMethodRepository,
@@ -0,0 +1,391 @@
+use crate::gateway::*;
+use crate::stripe::constant_time_eq;
+use async_trait::async_trait;
+use chrono::{Datelike, Utc};
+use erp_core::{Error, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Approves.
+pub const SIMULATOR_CARD_APPROVED: &str = "4242424242424242";
+/// Declines with `card_declined`.
+pub const SIMULATOR_CARD_DECLINED: &str = "4000000000000002";
+/// Declines with `insufficient_funds`.
+pub const SIMULATOR_CARD_INSUFFICIENT_FUNDS: &str = "4000000000009995";
+/// Requires a 3-D Secure challenge before it is authorized.
+pub const SIMULATOR_CARD_REQUIRES_ACTION: &str = "4000000000003220";
+/// Approves, but captured funds stay pending until [`SimulatorGateway::settle`].
+pub const SIMULATOR_CARD_DELAYED_SETTLEMENT: &str = "4000000000000077";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulatedOutcome {
+    Approve,
+    Decline(String),
+    RequireAction,
+    DelaySettlement,
+}
+
+/// A webhook the simulator would have delivered, ready to post to the webhook endpoint.
+#[derive(Debug, Clone)]
+pub struct SignedWebhook {
+    pub payload: String,
+    pub signature: String,
+}
+
+struct SimulatedCard {
+    last_four: String,
+    brand: String,
+    outcome: SimulatedOutcome,
+}
+
+struct SimulatedTransaction {
+    status: GatewayStatus,
+    amount: i64,
+    captured: i64,
+    refunded: i64,
+    capture_after_challenge: bool,
+    settles_late: bool,
+    card_last_four: String,
+    card_brand: String,
+}
+
+#[derive(Default)]
+struct SimulatorState {
+    sequence: u64,
+    script: VecDeque<SimulatedOutcome>,
+    cards: HashMap<String, SimulatedCard>,
+    transactions: HashMap<String, SimulatedTransaction>,
+    unsettled: Vec<String>,
+}
+
+impl SimulatorState {
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.sequence += 1;
+        format!("{}_{:06}", prefix, self.sequence)
+    }
+}
+
+/// An in-process gateway for offline testing. Ids are sequential, card numbers pick the outcome
+/// (see the `SIMULATOR_CARD_*` constants) and [`SimulatorGateway::script`] overrides it for the
+/// next authorizations. Its webhooks are signed with the secret the caller passes, which should be
+/// the `webhook_secret` of the gateway row they will be delivered to.
+pub struct SimulatorGateway {
+    state: Mutex<SimulatorState>,
+    fee_basis_points: i64,
+    fee_fixed: i64,
+}
+
+impl Default for SimulatorGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatorGateway {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(SimulatorState::default()),
+            fee_basis_points: 290,
+            fee_fixed: 30,
+        }
+    }
+
+    pub fn with_fees(mut self, basis_points: i64, fixed: i64) -> Self {
+        self.fee_basis_points = basis_points;
+        self.fee_fixed = fixed;
+        self
+    }
+
+    /// Queues an outcome for the next authorization, whatever card it uses.
+    pub fn script(&self, outcome: SimulatedOutcome) {
+        self.state.lock().unwrap().script.push_back(outcome);
+    }
+
+    /// Finishes a 3-D Secure challenge, authorizing (and capturing, if the authorization asked
+    /// for it) or declining the transaction.
+    pub fn complete_challenge(&self, transaction_id: &str, approve: bool, webhook_secret: &str) -> Result<SignedWebhook> {
+        let mut state = self.state.lock().unwrap();
+        let txn = state.transactions.get_mut(transaction_id)
+            .ok_or_else(|| Error::not_found("GatewayTransaction", transaction_id))?;
+        if txn.status != GatewayStatus::RequiresAction {
+            return Err(Error::business_rule("Transaction is not awaiting a challenge"));
+        }
+
+        let (event_type, decline_code) = if !approve {
+            txn.status = GatewayStatus::Declined;
+            ("transaction.declined", Some("authentication_failed".to_string()))
+        } else if txn.capture_after_challenge {
+            txn.captured = txn.amount;
+            txn.status = if txn.settles_late { GatewayStatus::Pending } else { GatewayStatus::Captured };
+            ("transaction.captured", None)
+        } else {
+            txn.status = GatewayStatus::Authorized;
+            ("transaction.authorized", None)
+        };
+        let (status, captured) = (txn.status, txn.captured);
+        if status == GatewayStatus::Pending {
+            state.unsettled.push(transaction_id.to_string());
+        }
+        let event = GatewayEvent {
+            event_id: state.next_id("evt_sim"),
+            event_type: event_type.to_string(),
+            transaction_id: transaction_id.to_string(),
+            status: Some(status),
+            fee: (captured > 0).then(|| self.fee(captured)),
+            decline_code,
+        };
+        sign(&event, webhook_secret)
+    }
+
+    /// Settles every pending capture, returning a `transaction.settled` webhook for each.
+    pub fn settle(&self, webhook_secret: &str) -> Result<Vec<SignedWebhook>> {
+        let mut state = self.state.lock().unwrap();
+        let unsettled = std::mem::take(&mut state.unsettled);
+        let mut webhooks = Vec::new();
+        for transaction_id in unsettled {
+            let Some(txn) = state.transactions.get_mut(&transaction_id) else { continue };
+            if txn.status != GatewayStatus::Pending {
+                continue;
+            }
+            txn.status = GatewayStatus::Captured;
+            let captured = txn.captured;
+            let event = GatewayEvent {
+                event_id: state.next_id("evt_sim"),
+                event_type: "transaction.settled".to_string(),
+                transaction_id,
+                status: Some(GatewayStatus::Captured),
+                fee: Some(self.fee(captured)),
+                decline_code: None,
+            };
+            webhooks.push(sign(&event, webhook_secret)?);
+        }
+        Ok(webhooks)
+    }
+
+    fn fee(&self, amount: i64) -> i64 {
+        (amount * self.fee_basis_points + 5_000) / 10_000 + self.fee_fixed
+    }
+
+    fn response(&self, transaction_id: &str, txn: &SimulatedTransaction) -> GatewayResponse {
+        GatewayResponse {
+            transaction_id: transaction_id.to_string(),
+            status: txn.status,
+            amount: if txn.captured > 0 { txn.captured } else { txn.amount },
+            fee: if txn.captured > 0 { self.fee(txn.captured) } else { 0 },
+            card_last_four: Some(txn.card_last_four.clone()),
+            card_brand: Some(txn.card_brand.clone()),
+            decline_code: None,
+            message: None,
+            next_action_url: None,
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentGateway for SimulatorGateway {
+    fn gateway_type(&self) -> &'static str {
+        "simulator"
+    }
+
+    fn signature_header(&self) -> &'static str {
+        "x-simulator-signature"
+    }
+
+    async fn tokenize(&self, card: CardDetails) -> Result<PaymentToken> {
+        let number: String = card.number.chars().filter(|c| !c.is_whitespace() && *c != '-').collect();
+        if !(12..=19).contains(&number.len()) || !number.chars().all(|c| c.is_ascii_digit()) || !luhn_valid(&number) {
+            return Err(Error::validation("Invalid card number"));
+        }
+        if !(1..=12).contains(&card.exp_month) {
+            return Err(Error::validation("Invalid expiry month"));
+        }
+        let today = Utc::now();
+        if (card.exp_year, card.exp_month) < (today.year(), today.month()) {
+            return Err(Error::validation("Card has expired"));
+        }
+        if !(3..=4).contains(&card.cvc.len()) || !card.cvc.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error::validation("Invalid card security code"));
+        }
+
+        let outcome = match number.as_str() {
+            SIMULATOR_CARD_DECLINED => SimulatedOutcome::Decline("card_declined".to_string()),
+            SIMULATOR_CARD_INSUFFICIENT_FUNDS => SimulatedOutcome::Decline("insufficient_funds".to_string()),
+            SIMULATOR_CARD_REQUIRES_ACTION => SimulatedOutcome::RequireAction,
+            SIMULATOR_CARD_DELAYED_SETTLEMENT => SimulatedOutcome::DelaySettlement,
+            _ => SimulatedOutcome::Approve,
+        };
+        let brand = match number.as_bytes()[0] {
+            b'4' => "Visa",
+            b'5' | b'2' => "Mastercard",
+            b'3' => "Amex",
+            b'6' => "Discover",
+            _ => "Unknown",
+        };
+        let card = SimulatedCard {
+            last_four: number[number.len() - 4..].to_string(),
+            brand: brand.to_string(),
+            outcome,
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let token = state.next_id("tok_sim");
+        let result = PaymentToken {
+            token: token.clone(),
+            card_last_four: Some(card.last_four.clone()),
+            card_brand: Some(card.brand.clone()),
+        };
+        state.cards.insert(token, card);
+        Ok(result)
+    }
+
+    async fn authorize(&self, req: AuthorizeRequest) -> Result<GatewayResponse> {
+        if req.amount <= 0 {
+            return Err(Error::validation("Amount must be greater than zero"));
+        }
+        let mut state = self.state.lock().unwrap();
+        let card = state.cards.get(&req.payment_method_token)
+            .ok_or_else(|| Error::validation("Unknown payment method token"))?;
+        let (last_four, brand, card_outcome) = (card.last_four.clone(), card.brand.clone(), card.outcome.clone());
+        let outcome = state.script.pop_front().unwrap_or(card_outcome);
+        let transaction_id = state.next_id("txn_sim");
+
+        let mut txn = SimulatedTransaction {
+            status: GatewayStatus::Authorized,
+            amount: req.amount,
+            captured: 0,
+            refunded: 0,
+            capture_after_challenge: false,
+            settles_late: false,
+            card_last_four: last_four,
+            card_brand: brand,
+        };
+        let mut decline_code = None;
+        match outcome {
+            SimulatedOutcome::Decline(code) => {
+                txn.status = GatewayStatus::Declined;
+                decline_code = Some(code);
+            }
+            SimulatedOutcome::RequireAction => {
+                txn.status = GatewayStatus::RequiresAction;
+                txn.capture_after_challenge = req.capture;
+            }
+            SimulatedOutcome::DelaySettlement | SimulatedOutcome::Approve => {
+                txn.settles_late = outcome == SimulatedOutcome::DelaySettlement;
+                if req.capture {
+                    txn.captured = req.amount;
+                    txn.status = if txn.settles_late { GatewayStatus::Pending } else { GatewayStatus::Captured };
+                }
+            }
+        }
+
+        let mut response = self.response(&transaction_id, &txn);
+        match txn.status {
+            GatewayStatus::Declined => {
+                response.message = decline_code.as_ref().map(|code| format!("Card declined: {}", code));
+                response.decline_code = decline_code;
+            }
+            GatewayStatus::RequiresAction => {
+                response.next_action_url = Some(format!("https://simulator.invalid/3ds/{}", transaction_id));
+            }
+            GatewayStatus::Pending => state.unsettled.push(transaction_id.clone()),
+            _ => {}
+        }
+        state.transactions.insert(transaction_id, txn);
+        Ok(response)
+    }
+
+    async fn capture(&self, transaction_id: &str, amount: Option<i64>) -> Result<GatewayResponse> {
+        let mut state = self.state.lock().unwrap();
+        let txn = state.transactions.get_mut(transaction_id)
+            .ok_or_else(|| Error::not_found("GatewayTransaction", transaction_id))?;
+        if txn.status != GatewayStatus::Authorized {
+            return Err(Error::business_rule("Only an authorized transaction can be captured"));
+        }
+        let amount = amount.unwrap_or(txn.amount);
+        if amount <= 0 || amount > txn.amount {
+            return Err(Error::validation("Capture amount must be positive and no more than the authorized amount"));
+        }
+        txn.captured = amount;
+        txn.status = if txn.settles_late { GatewayStatus::Pending } else { GatewayStatus::Captured };
+        let response = self.response(transaction_id, txn);
+        if response.status == GatewayStatus::Pending {
+            state.unsettled.push(transaction_id.to_string());
+        }
+        Ok(response)
+    }
+
+    async fn void(&self, transaction_id: &str) -> Result<GatewayResponse> {
+        let mut state = self.state.lock().unwrap();
+        let txn = state.transactions.get_mut(transaction_id)
+            .ok_or_else(|| Error::not_found("GatewayTransaction", transaction_id))?;
+        if !matches!(txn.status, GatewayStatus::Authorized | GatewayStatus::RequiresAction) {
+            return Err(Error::business_rule("Only an uncaptured transaction can be voided"));
+        }
+        txn.status = GatewayStatus::Voided;
+        Ok(self.response(transaction_id, txn))
+    }
+
+    async fn refund(&self, transaction_id: &str, amount: i64) -> Result<GatewayResponse> {
+        let mut state = self.state.lock().unwrap();
+        let txn = state.transactions.get_mut(transaction_id)
+            .ok_or_else(|| Error::not_found("GatewayTransaction", transaction_id))?;
+        if txn.status != GatewayStatus::Captured {
+            return Err(Error::business_rule("Only a settled capture can be refunded"));
+        }
+        if amount <= 0 || amount > txn.captured - txn.refunded {
+            return Err(Error::business_rule("Refund amount exceeds the captured balance"));
+        }
+        txn.refunded += amount;
+        if txn.refunded == txn.captured {
+            txn.status = GatewayStatus::Refunded;
+        }
+        let (last_four, brand) = (txn.card_last_four.clone(), txn.card_brand.clone());
+        Ok(GatewayResponse {
+            transaction_id: state.next_id("re_sim"),
+            status: GatewayStatus::Refunded,
+            amount,
+            fee: 0,
+            card_last_four: Some(last_four),
+            card_brand: Some(brand),
+            decline_code: None,
+            message: None,
+            next_action_url: None,
+        })
+    }
+
+    fn verify_webhook(&self, webhook_secret: &str, payload: &[u8], signature: &str) -> Result<GatewayEvent> {
+        if !constant_time_eq(&signature_of(payload, webhook_secret)?, signature) {
+            return Err(Error::Validation("Webhook signature verification failed".to_string()));
+        }
+        serde_json::from_slice(payload)
+            .map_err(|e| Error::validation(format!("Invalid webhook payload: {}", e)))
+    }
+}
+
+fn signature_of(payload: &[u8], webhook_secret: &str) -> Result<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(webhook_secret.as_bytes())
+        .map_err(|_| Error::internal("Invalid webhook secret"))?;
+    mac.update(payload);
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+fn sign(event: &GatewayEvent, webhook_secret: &str) -> Result<SignedWebhook> {
+    let payload = serde_json::to_string(event)
+        .map_err(|e| Error::Internal(anyhow::anyhow!("Failed to serialize webhook: {}", e)))?;
+    let signature = signature_of(payload.as_bytes(), webhook_secret)?;
+    Ok(SignedWebhook { payload, signature })
+}
+
+fn luhn_valid(number: &str) -> bool {
+    let sum: u32 = number.chars().rev().filter_map(|c| c.to_digit(10)).enumerate().map(|(i, digit)| {
+        if i % 2 == 1 {
+            let doubled = digit * 2;
+            if doubled > 9 { doubled - 9 } else { doubled }
+        } else {
+            digit
+        }
+    }).sum();
+    sum.is_multiple_of(10)
+}
@@ -1,3 +1,4 @@
+use crate::gateway::*;
 use crate::models::*;
 use crate::repository::*;
 use erp_core::{BaseEntity, Error, Result};
@@ -318,56 +319,7 @@ where
     }
     
     pub fn verify_webhook_signature(&self, payload: &[u8], signature: &str) -> Result<StripeWebhookPayload> {
-        use hmac::{Hmac, Mac};
-        use sha2::Sha256;
-        use std::time::{SystemTime, UNIX_EPOCH};
-        
-        type HmacSha256 = Hmac<Sha256>;
-        
-        let signature_parts: Vec<&str> = signature.split(',').collect();
-        let mut signature_value = "";
-        let mut timestamp = "";
-        
-        for part in signature_parts {
-            if let Some(val) = part.strip_prefix("v1=") {
-                signature_value = val;
-            } else if let Some(val) = part.strip_prefix("t=") {
-                timestamp = val;
-            }
-        }
-        
-        if signature_value.is_empty() || timestamp.is_empty() {
-            return Err(Error::Validation("Invalid webhook signature format".to_string()));
-        }
-        
-        let timestamp_secs: u64 = timestamp.parse()
-            .map_err(|_| Error::Validation("Invalid timestamp in signature".to_string()))?;
-        
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| Error::Internal(anyhow::anyhow!("System time error: {}", e)))?
-            .as_secs();
-        
-        const TOLERANCE_SECS: u64 = 300;
-        if now.abs_diff(timestamp_secs) > TOLERANCE_SECS {
-            return Err(Error::Validation("Webhook timestamp outside tolerance window".to_string()));
-        }
-        
-        let signed_payload = format!("{}.{}", timestamp, String::from_utf8_lossy(payload));
-        let mut mac = HmacSha256::new_from_slice(self.config.webhook_secret.as_bytes())
-            .map_err(|_| Error::internal("Invalid webhook secret"))?;
-        mac.update(signed_payload.as_bytes());
-        let expected = hex::encode(mac.finalize().into_bytes());
-        
-        if !constant_time_eq(&expected, signature_value) {
-            return Err(Error::Validation("Webhook signature verification failed".to_string()));
-        }
-        
-        let payload_str = String::from_utf8_lossy(payload);
-        let webhook: StripeWebhookPayload = serde_json::from_str(&payload_str)
-            .map_err(|e| Error::Internal(anyhow::anyhow!("Failed to parse webhook payload: {}", e)))?;
-        
-        Ok(webhook)
+        verify_stripe_signature(&self.config.webhook_secret, payload, signature)
     }
     
     pub async fn process_webhook_event(
@@ -509,6 +461,173 @@ where
     }
 }
 
+/// [`PaymentGateway`] over Stripe PaymentIntents, with the payment method id as the token.
+pub struct StripeGateway {
+    client: Client,
+    config: StripeConfig,
+}
+
+impl StripeGateway {
+    pub fn new(config: StripeConfig) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(STRIPE_API_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| Error::Internal(anyhow::anyhow!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self { client, config })
+    }
+
+    /// Posts a form and returns the status with the decoded body; card declines arrive as 402.
+    async fn post(&self, path: &str, form: &[(String, String)]) -> Result<(reqwest::StatusCode, serde_json::Value)> {
+        let response = self.client
+            .post(format!("{}/{}", self.config.api_base_url(), path))
+            .basic_auth(&self.config.secret_key, Some(""))
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| Error::Internal(anyhow::anyhow!("Stripe request failed: {}", e)))?;
+        let status = response.status();
+        let body: serde_json::Value = response.json().await
+            .map_err(|e| Error::Internal(anyhow::anyhow!("Failed to parse Stripe response: {}", e)))?;
+        if !status.is_success() && status != reqwest::StatusCode::PAYMENT_REQUIRED {
+            return Err(Error::Internal(anyhow::anyhow!("Stripe API error: {}", body["error"]["message"])));
+        }
+        Ok((status, body))
+    }
+
+    async fn post_intent(&self, path: &str, mut form: Vec<(String, String)>) -> Result<GatewayResponse> {
+        form.push(("expand[]".to_string(), "latest_charge.balance_transaction".to_string()));
+        let (status, body) = self.post(path, &form).await?;
+        if status == reqwest::StatusCode::PAYMENT_REQUIRED {
+            let error = &body["error"];
+            let mut response = intent_response(&error["payment_intent"]);
+            response.status = GatewayStatus::Declined;
+            response.decline_code = error["decline_code"].as_str().or(error["code"].as_str()).map(str::to_string);
+            response.message = error["message"].as_str().map(str::to_string);
+            return Ok(response);
+        }
+        Ok(intent_response(&body))
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentGateway for StripeGateway {
+    fn gateway_type(&self) -> &'static str {
+        "stripe"
+    }
+
+    fn signature_header(&self) -> &'static str {
+        "stripe-signature"
+    }
+
+    async fn tokenize(&self, card: CardDetails) -> Result<PaymentToken> {
+        let form = vec![
+            ("type".to_string(), "card".to_string()),
+            ("card[number]".to_string(), card.number),
+            ("card[exp_month]".to_string(), card.exp_month.to_string()),
+            ("card[exp_year]".to_string(), card.exp_year.to_string()),
+            ("card[cvc]".to_string(), card.cvc),
+        ];
+        let (status, body) = self.post("payment_methods", &form).await?;
+        if !status.is_success() {
+            return Err(Error::validation(body["error"]["message"].as_str().unwrap_or("Card was rejected")));
+        }
+        Ok(PaymentToken {
+            token: body["id"].as_str().unwrap_or_default().to_string(),
+            card_last_four: body["card"]["last4"].as_str().map(str::to_string),
+            card_brand: body["card"]["brand"].as_str().map(str::to_string),
+        })
+    }
+
+    async fn authorize(&self, req: AuthorizeRequest) -> Result<GatewayResponse> {
+        let mut form = vec![
+            ("amount".to_string(), req.amount.to_string()),
+            ("currency".to_string(), req.currency.to_lowercase()),
+            ("payment_method".to_string(), req.payment_method_token),
+            ("payment_method_types[]".to_string(), "card".to_string()),
+            ("confirm".to_string(), "true".to_string()),
+            ("capture_method".to_string(), if req.capture { "automatic" } else { "manual" }.to_string()),
+        ];
+        if let Some(description) = req.description {
+            form.push(("description".to_string(), description));
+        }
+        self.post_intent("payment_intents", form).await
+    }
+
+    async fn capture(&self, transaction_id: &str, amount: Option<i64>) -> Result<GatewayResponse> {
+        let form = amount.map(|amount| vec![("amount_to_capture".to_string(), amount.to_string())]).unwrap_or_default();
+        self.post_intent(&format!("payment_intents/{}/capture", transaction_id), form).await
+    }
+
+    async fn void(&self, transaction_id: &str) -> Result<GatewayResponse> {
+        self.post_intent(&format!("payment_intents/{}/cancel", transaction_id), Vec::new()).await
+    }
+
+    async fn refund(&self, transaction_id: &str, amount: i64) -> Result<GatewayResponse> {
+        let form = vec![
+            ("payment_intent".to_string(), transaction_id.to_string()),
+            ("amount".to_string(), amount.to_string()),
+        ];
+        let (_, body) = self.post("refunds", &form).await?;
+        let failed = body["status"].as_str() == Some("failed");
+        Ok(GatewayResponse {
+            transaction_id: body["id"].as_str().unwrap_or_default().to_string(),
+            status: if failed { GatewayStatus::Declined } else { GatewayStatus::Refunded },
+            amount: body["amount"].as_i64().unwrap_or(amount),
+            fee: 0,
+            card_last_four: None,
+            card_brand: None,
+            decline_code: body["failure_reason"].as_str().map(str::to_string),
+            message: None,
+            next_action_url: None,
+        })
+    }
+
+    fn verify_webhook(&self, webhook_secret: &str, payload: &[u8], signature: &str) -> Result<GatewayEvent> {
+        let webhook = verify_stripe_signature(webhook_secret, payload, signature)?;
+        let object = webhook.data.object.unwrap_or_default();
+        let (transaction_id, status, fee, decline_code) = if object["object"] == "payment_intent" {
+            let response = intent_response(&object);
+            (response.transaction_id, Some(response.status), Some(response.fee), response.decline_code)
+        } else {
+            (object["payment_intent"].as_str().unwrap_or_default().to_string(), None, None, None)
+        };
+        Ok(GatewayEvent {
+            event_id: webhook.id,
+            event_type: webhook.event_type,
+            transaction_id,
+            status,
+            fee,
+            decline_code,
+        })
+    }
+}
+
+fn intent_response(intent: &serde_json::Value) -> GatewayResponse {
+    let charge = &intent["latest_charge"];
+    let card = &charge["payment_method_details"]["card"];
+    let error = &intent["last_payment_error"];
+    GatewayResponse {
+        transaction_id: intent["id"].as_str().unwrap_or_default().to_string(),
+        status: match intent["status"].as_str().unwrap_or_default() {
+            "requires_capture" => GatewayStatus::Authorized,
+            "succeeded" => GatewayStatus::Captured,
+            "processing" => GatewayStatus::Pending,
+            "requires_action" | "requires_confirmation" => GatewayStatus::RequiresAction,
+            "canceled" => GatewayStatus::Voided,
+            _ => GatewayStatus::Declined,
+        },
+        amount: intent["amount_received"].as_i64().filter(|received| *received > 0)
+            .or(intent["amount"].as_i64())
+            .unwrap_or(0),
+        fee: charge["balance_transaction"]["fee"].as_i64().unwrap_or(0),
+        card_last_four: card["last4"].as_str().map(str::to_string),
+        card_brand: card["brand"].as_str().map(str::to_string),
+        decline_code: error["decline_code"].as_str().or(error["code"].as_str()).map(str::to_string),
+        message: error["message"].as_str().map(str::to_string),
+        next_action_url: intent["next_action"]["redirect_to_url"]["url"].as_str().map(str::to_string),
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StripePaymentIntentResponse {
     pub id: String,
@@ -557,7 +676,60 @@ pub struct StripeWebhookData {
     pub object: Option<serde_json::Value>,
 }
 
-fn constant_time_eq(a: &str, b: &str) -> bool {
+pub fn verify_stripe_signature(webhook_secret: &str, payload: &[u8], signature: &str) -> Result<StripeWebhookPayload> {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    
+    type HmacSha256 = Hmac<Sha256>;
+    
+    let signature_parts: Vec<&str> = signature.split(',').collect();
+    let mut signature_value = "";
+    let mut timestamp = "";
+    
+    for part in signature_parts {
+        if let Some(val) = part.strip_prefix("v1=") {
+            signature_value = val;
+        } else if let Some(val) = part.strip_prefix("t=") {
+            timestamp = val;
+        }
+    }
+    
+    if signature_value.is_empty() || timestamp.is_empty() {
+        return Err(Error::Validation("Invalid webhook signature format".to_string()));
+    }
+    
+    let timestamp_secs: u64 = timestamp.parse()
+        .map_err(|_| Error::Validation("Invalid timestamp in signature".to_string()))?;
+    
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| Error::Internal(anyhow::anyhow!("System time error: {}", e)))?
+        .as_secs();
+    
+    const TOLERANCE_SECS: u64 = 300;
+    if now.abs_diff(timestamp_secs) > TOLERANCE_SECS {
+        return Err(Error::Validation("Webhook timestamp outside tolerance window".to_string()));
+    }
+    
+    let signed_payload = format!("{}.{}", timestamp, String::from_utf8_lossy(payload));
+    let mut mac = HmacSha256::new_from_slice(webhook_secret.as_bytes())
+        .map_err(|_| Error::internal("Invalid webhook secret"))?;
+    mac.update(signed_payload.as_bytes());
+    let expected = hex::encode(mac.finalize().into_bytes());
+    
+    if !constant_time_eq(&expected, signature_value) {
+        return Err(Error::Validation("Webhook signature verification failed".to_string()));
+    }
+    
+    let payload_str = String::from_utf8_lossy(payload);
+    let webhook: StripeWebhookPayload = serde_json::from_str(&payload_str)
+        .map_err(|e| Error::Internal(anyhow::anyhow!("Failed to parse webhook payload: {}", e)))?;
+    
+    Ok(webhook)
+}
+
+pub(crate) fn constant_time_eq(a: &str, b: &str) -> bool {
     if a.len() != b.len() {
         return false;
     }
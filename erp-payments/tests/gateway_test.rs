@@ -0,0 +1,235 @@
+use erp_payments::*;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+const WEBHOOK_SECRET: &str = "whsec_test";
+
+async fn setup() -> (SqlitePool, GatewayService, Uuid) {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    for ddl in [
+        r#"CREATE TABLE payment_gateways (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            code TEXT NOT NULL UNIQUE,
+            gateway_type TEXT NOT NULL,
+            api_key TEXT,
+            api_secret TEXT,
+            merchant_id TEXT,
+            webhook_secret TEXT,
+            supported_methods TEXT,
+            is_sandbox INTEGER DEFAULT 0,
+            is_default INTEGER DEFAULT 0,
+            is_active INTEGER DEFAULT 1,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            created_by TEXT,
+            updated_by TEXT
+        )"#,
+        r#"CREATE TABLE payments (
+            id TEXT PRIMARY KEY,
+            payment_number TEXT NOT NULL UNIQUE,
+            customer_id TEXT NOT NULL,
+            invoice_id TEXT,
+            payment_date TEXT NOT NULL,
+            amount INTEGER NOT NULL,
+            currency TEXT DEFAULT 'USD',
+            payment_method TEXT NOT NULL,
+            gateway_id TEXT,
+            status TEXT NOT NULL DEFAULT 'Completed',
+            gateway_transaction_id TEXT,
+            gateway_response TEXT,
+            card_last_four TEXT,
+            card_brand TEXT,
+            bank_name TEXT,
+            bank_account_last_four TEXT,
+            check_number TEXT,
+            refunded_amount INTEGER NOT NULL DEFAULT 0,
+            refund_reason TEXT,
+            processing_fee INTEGER NOT NULL DEFAULT 0,
+            notes TEXT,
+            paid_at TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            created_by TEXT,
+            updated_by TEXT
+        )"#,
+        r#"CREATE TABLE refunds (
+            id TEXT PRIMARY KEY,
+            refund_number TEXT NOT NULL UNIQUE,
+            customer_id TEXT NOT NULL,
+            refund_date TEXT NOT NULL,
+            payment_id TEXT,
+            amount INTEGER NOT NULL,
+            currency TEXT,
+            reason TEXT,
+            status TEXT NOT NULL DEFAULT 'Pending',
+            gateway_refund_id TEXT,
+            processed_at TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT,
+            created_by TEXT,
+            updated_by TEXT
+        )"#,
+        r#"CREATE TABLE payment_allocations (
+            id TEXT PRIMARY KEY,
+            payment_id TEXT NOT NULL,
+            invoice_id TEXT NOT NULL,
+            amount INTEGER NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            created_by TEXT,
+            updated_by TEXT
+        )"#,
+        r#"CREATE TABLE payment_gateway_events (
+            gateway_id TEXT NOT NULL,
+            event_id TEXT NOT NULL,
+            event_type TEXT NOT NULL,
+            transaction_id TEXT NOT NULL,
+            received_at TEXT NOT NULL,
+            PRIMARY KEY (gateway_id, event_id)
+        )"#,
+    ] {
+        sqlx::query(ddl).execute(&pool).await.unwrap();
+    }
+
+    let service = GatewayService::new(pool.clone()).with_simulator(SimulatorGateway::new());
+    let gateway = service.create("SIM".to_string(), "Simulator".to_string(), "Simulator".to_string(), vec!["CreditCard".to_string()], Some(WEBHOOK_SECRET.to_string())).await.unwrap();
+    (pool, service, gateway.base.id)
+}
+
+async fn pay(service: &GatewayService, gateway_id: Uuid, card: &str, capture: bool, invoice_id: Option<Uuid>) -> ProcessedPayment {
+    let token = service.tokenize(gateway_id, CardDetails {
+        number: card.to_string(),
+        exp_month: 12,
+        exp_year: 2099,
+        cvc: "123".to_string(),
+        holder_name: None,
+    }).await.unwrap();
+    service.process_payment(ProcessPaymentRequest {
+        gateway_id,
+        customer_id: Uuid::new_v4(),
+        amount: 10000,
+        currency: "USD".to_string(),
+        payment_method_token: token.token,
+        invoice_id,
+        description: None,
+        metadata: None,
+        capture,
+    }).await.unwrap()
+}
+
+async fn allocations(pool: &SqlitePool, payment_id: Uuid) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM payment_allocations WHERE payment_id = ?")
+        .bind(payment_id.to_string())
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_simulator_approves_declines_and_challenges() {
+    let (pool, service, gateway_id) = setup().await;
+
+    let approved = pay(&service, gateway_id, SIMULATOR_CARD_APPROVED, true, Some(Uuid::new_v4())).await;
+    assert_eq!(approved.payment.status, PaymentStatus::Completed);
+    assert_eq!(approved.payment.processing_fee, 320);
+    assert_eq!(approved.payment.card_last_four.as_deref(), Some("4242"));
+    assert_eq!(approved.payment.card_brand.as_deref(), Some("Visa"));
+    assert_eq!(allocations(&pool, approved.payment.base.id).await, 1);
+
+    let declined = pay(&service, gateway_id, SIMULATOR_CARD_INSUFFICIENT_FUNDS, true, Some(Uuid::new_v4())).await;
+    assert_eq!(declined.gateway.decline_code.as_deref(), Some("insufficient_funds"));
+    let stored = service.get_payment(declined.payment.base.id).await.unwrap().unwrap();
+    assert_eq!(stored.status, PaymentStatus::Failed);
+    assert_eq!(allocations(&pool, declined.payment.base.id).await, 0);
+
+    let invoice_id = Uuid::new_v4();
+    let challenged = pay(&service, gateway_id, SIMULATOR_CARD_REQUIRES_ACTION, true, Some(invoice_id)).await;
+    assert_eq!(challenged.payment.status, PaymentStatus::RequiresAction);
+    assert!(challenged.gateway.next_action_url.is_some());
+
+    let webhook = service.simulator().unwrap().complete_challenge(&challenged.gateway.transaction_id, true, WEBHOOK_SECRET).unwrap();
+    assert!(service.handle_webhook(gateway_id, webhook.payload.as_bytes(), "forged").await.is_err());
+    let unsigned = service.create("NOSECRET".to_string(), "No secret".to_string(), "Simulator".to_string(), vec![], None).await.unwrap();
+    assert!(service.handle_webhook(unsigned.base.id, webhook.payload.as_bytes(), &webhook.signature).await.is_err());
+    let settled = service.handle_webhook(gateway_id, webhook.payload.as_bytes(), &webhook.signature).await.unwrap().unwrap();
+    assert_eq!(settled.status, PaymentStatus::Completed);
+    assert!(settled.paid_at.is_some());
+    assert_eq!(allocations(&pool, settled.base.id).await, 1);
+
+    // A redelivered webhook changes nothing.
+    service.handle_webhook(gateway_id, webhook.payload.as_bytes(), &webhook.signature).await.unwrap();
+    assert_eq!(allocations(&pool, settled.base.id).await, 1);
+
+    sqlx::query("UPDATE payments SET status = 'Settled' WHERE id = ?")
+        .bind(settled.base.id.to_string())
+        .execute(&pool).await.unwrap();
+    assert!(service.get_payment(settled.base.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_delayed_settlement_and_scripted_outcomes() {
+    let (pool, service, gateway_id) = setup().await;
+
+    let delayed = pay(&service, gateway_id, SIMULATOR_CARD_DELAYED_SETTLEMENT, true, Some(Uuid::new_v4())).await;
+    assert_eq!(delayed.payment.status, PaymentStatus::Processing);
+    assert_eq!(allocations(&pool, delayed.payment.base.id).await, 0);
+
+    let simulator = service.simulator().unwrap();
+    let webhooks = simulator.settle(WEBHOOK_SECRET).unwrap();
+    assert_eq!(webhooks.len(), 1);
+    let settled = service.handle_webhook(gateway_id, webhooks[0].payload.as_bytes(), &webhooks[0].signature).await.unwrap().unwrap();
+    assert_eq!(settled.status, PaymentStatus::Completed);
+    assert_eq!(allocations(&pool, settled.base.id).await, 1);
+    assert!(simulator.settle(WEBHOOK_SECRET).unwrap().is_empty());
+
+    simulator.script(SimulatedOutcome::Decline("do_not_honor".to_string()));
+    let scripted = pay(&service, gateway_id, SIMULATOR_CARD_APPROVED, true, None).await;
+    assert_eq!(scripted.payment.status, PaymentStatus::Failed);
+    assert_eq!(scripted.gateway.decline_code.as_deref(), Some("do_not_honor"));
+
+    let unscripted = pay(&service, gateway_id, SIMULATOR_CARD_APPROVED, true, None).await;
+    assert_eq!(unscripted.payment.status, PaymentStatus::Completed);
+}
+
+#[tokio::test]
+async fn test_authorize_capture_refund_and_void() {
+    let (pool, service, gateway_id) = setup().await;
+
+    let authorized = pay(&service, gateway_id, SIMULATOR_CARD_APPROVED, false, Some(Uuid::new_v4())).await;
+    assert_eq!(authorized.payment.status, PaymentStatus::Authorized);
+    assert_eq!(allocations(&pool, authorized.payment.base.id).await, 0);
+
+    let captured = service.capture_payment(authorized.payment.base.id, Some(6000)).await.unwrap();
+    assert_eq!(captured.payment.status, PaymentStatus::Completed);
+    assert_eq!(captured.payment.amount, 6000);
+    assert_eq!(captured.payment.processing_fee, 204);
+    assert_eq!(allocations(&pool, captured.payment.base.id).await, 1);
+    assert!(service.capture_payment(authorized.payment.base.id, None).await.is_err());
+
+    let refund = service.refund_payment(CreateRefundRequest {
+        payment_id: captured.payment.base.id,
+        amount: 2000,
+        reason: "Damaged".to_string(),
+    }, None).await.unwrap();
+    assert!(refund.gateway_refund_id.is_some());
+    assert_eq!(service.get_payment(captured.payment.base.id).await.unwrap().unwrap().status, PaymentStatus::PartiallyRefunded);
+    assert!(service.refund_payment(CreateRefundRequest {
+        payment_id: captured.payment.base.id,
+        amount: 5000,
+        reason: "Too much".to_string(),
+    }, None).await.is_err());
+
+    let held = pay(&service, gateway_id, SIMULATOR_CARD_APPROVED, false, None).await;
+    let voided = service.void_payment(held.payment.base.id).await.unwrap();
+    assert_eq!(voided.payment.status, PaymentStatus::Cancelled);
+    assert!(service.refund_payment(CreateRefundRequest {
+        payment_id: held.payment.base.id,
+        amount: 100,
+        reason: "Voided".to_string(),
+    }, None).await.is_err());
+
+    assert!(service.create("BAD".to_string(), "Unknown".to_string(), "Acme".to_string(), vec![], None).await.is_err());
+    let no_simulator = GatewayService::new(pool);
+    assert!(no_simulator.create("SIM2".to_string(), "Simulator".to_string(), "Simulator".to_string(), vec![], None).await.is_err());
+}
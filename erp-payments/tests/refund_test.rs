@@ -15,6 +15,7 @@ async fn test_payment_refund() -> Result<()> {
             gateway_id TEXT,
             invoice_id TEXT,
             customer_id TEXT NOT NULL,
+            payment_date TEXT NOT NULL,
             amount INTEGER NOT NULL,
             currency TEXT NOT NULL,
             payment_method TEXT NOT NULL,
@@ -42,6 +43,8 @@ async fn test_payment_refund() -> Result<()> {
         r#"CREATE TABLE IF NOT EXISTS refunds (
             id TEXT PRIMARY KEY,
             refund_number TEXT NOT NULL UNIQUE,
+            customer_id TEXT NOT NULL,
+            refund_date TEXT NOT NULL,
             payment_id TEXT NOT NULL,
             amount INTEGER NOT NULL,
             currency TEXT NOT NULL,
@@ -69,6 +72,33 @@ async fn test_payment_refund() -> Result<()> {
         )"#,
     ).execute(&pool).await.unwrap();
 
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS posting_rules (
+            id TEXT PRIMARY KEY,
+            event TEXT NOT NULL UNIQUE,
+            debit_account_id TEXT NOT NULL,
+            credit_account_id TEXT NOT NULL,
+            tax_account_id TEXT,
+            description TEXT,
+            is_active INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )"#,
+    ).execute(&pool).await.unwrap();
+
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS journal_entry_sources (
+            id TEXT PRIMARY KEY,
+            journal_entry_id TEXT NOT NULL,
+            event TEXT NOT NULL,
+            source_type TEXT NOT NULL,
+            source_id TEXT NOT NULL,
+            source_number TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            UNIQUE(event, source_id)
+        )"#,
+    ).execute(&pool).await.unwrap();
+
     let service = PaymentService::new(pool.clone());
     let customer_id = Uuid::new_v4();
     
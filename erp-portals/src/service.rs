@@ -4,6 +4,8 @@ use chrono::Utc;
 use erp_core::{Error, Result, BaseEntity, Pagination, Paginated};
 use crate::models::*;
 use crate::repository::*;
+use erp_payments::{AuthorizeRequest, GatewayStatus, PaymentGateway};
+use std::sync::Arc;
 
 pub struct PortalUserService { repo: SqlitePortalUserRepository }
 impl Default for PortalUserService {
@@ -282,7 +284,9 @@ impl PortalInvoiceService {
     }
 }
 
-pub struct PortalPaymentService;
+pub struct PortalPaymentService {
+    gateway: Option<Arc<dyn PaymentGateway>>,
+}
 impl Default for PortalPaymentService {
     fn default() -> Self {
         Self::new()
@@ -290,11 +294,22 @@ impl Default for PortalPaymentService {
 }
 
 impl PortalPaymentService {
-    pub fn new() -> Self { Self }
+    pub fn new() -> Self { Self { gateway: None } }
+
+    /// Card payments are charged through `gateway`; without one they are refused.
+    pub fn with_gateway(mut self, gateway: Arc<dyn PaymentGateway>) -> Self {
+        self.gateway = Some(gateway);
+        self
+    }
     
-    pub async fn process_payment(&self, pool: &SqlitePool, user_id: Uuid, invoice_ids: Vec<Uuid>, amount_cents: i64, method: PaymentMethodType) -> Result<PortalPayment> {
+    /// Charges card payments through the gateway using `payment_token`. Other methods are recorded
+    /// as pending until the funds arrive.
+    pub async fn process_payment(&self, pool: &SqlitePool, user_id: Uuid, invoice_ids: Vec<Uuid>, amount_cents: i64, method: PaymentMethodType, payment_token: Option<String>) -> Result<PortalPayment> {
         let _ = pool;
-        Ok(PortalPayment {
+        if amount_cents <= 0 {
+            return Err(Error::validation("Amount must be greater than zero"));
+        }
+        let mut payment = PortalPayment {
             base: BaseEntity::new(),
             portal_user_id: user_id,
             payment_reference: format!("PAY-{}", Utc::now().format("%Y%m%d%H%M%S")),
@@ -304,21 +319,57 @@ impl PortalPaymentService {
             payment_method: method,
             amount_cents,
             currency: "USD".to_string(),
-            status: PaymentStatus::Completed,
-            payment_provider: Some("Stripe".to_string()),
-            provider_transaction_id: Some(format!("tx_{}", Uuid::new_v4())),
+            status: PaymentStatus::Pending,
+            payment_provider: None,
+            provider_transaction_id: None,
             provider_response: None,
             card_last_four: None,
             card_brand: None,
             bank_name: None,
             check_number: None,
-            processed_at: Some(Utc::now()),
+            processed_at: None,
             failed_at: None,
             failure_reason: None,
             refunded_at: None,
             refund_amount_cents: None,
             notes: None,
-        })
+        };
+        if !matches!(payment.payment_method, PaymentMethodType::CreditCard | PaymentMethodType::DebitCard | PaymentMethodType::Stripe) {
+            return Ok(payment);
+        }
+
+        let gateway = self.gateway.as_ref()
+            .ok_or_else(|| Error::business_rule("No payment gateway is configured for card payments"))?;
+        let token = payment_token.ok_or_else(|| Error::validation("A payment token is required for card payments"))?;
+        let response = gateway.authorize(AuthorizeRequest {
+            amount: amount_cents,
+            currency: payment.currency.clone(),
+            payment_method_token: token,
+            description: Some(payment.payment_reference.clone()),
+            capture: true,
+        }).await?;
+
+        let now = Utc::now();
+        payment.status = match response.status {
+            GatewayStatus::Captured => PaymentStatus::Completed,
+            GatewayStatus::RequiresAction => PaymentStatus::Pending,
+            GatewayStatus::Declined | GatewayStatus::Voided => PaymentStatus::Failed,
+            _ => PaymentStatus::Processing,
+        };
+        match payment.status {
+            PaymentStatus::Completed => payment.processed_at = Some(now),
+            PaymentStatus::Failed => {
+                payment.failed_at = Some(now);
+                payment.failure_reason = response.decline_code.clone().or(response.message.clone());
+            }
+            _ => {}
+        }
+        payment.payment_provider = Some(gateway.gateway_type().to_string());
+        payment.provider_transaction_id = Some(response.transaction_id.clone());
+        payment.card_last_four = response.card_last_four.clone();
+        payment.card_brand = response.card_brand.clone();
+        payment.provider_response = serde_json::to_string(&response).ok();
+        Ok(payment)
     }
 }
 
@@ -3,6 +3,8 @@ use uuid::Uuid;
 use chrono::Utc;
 use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Money, Currency};
 use serde::Serialize;
+use std::sync::Arc;
+use erp_payments::{AuthorizeRequest, GatewayStatus, PaymentGateway};
 use crate::models::*;
 use crate::repository::*;
 
@@ -39,7 +41,10 @@ impl POSStoreService {
     }
 }
 
-pub struct POSTransactionService { repo: SqlitePOSTransactionRepository }
+pub struct POSTransactionService {
+    repo: SqlitePOSTransactionRepository,
+    gateway: Option<Arc<dyn PaymentGateway>>,
+}
 impl Default for POSTransactionService {
     fn default() -> Self {
         Self::new()
@@ -47,7 +52,14 @@ impl Default for POSTransactionService {
 }
 
 impl POSTransactionService {
-    pub fn new() -> Self { Self { repo: SqlitePOSTransactionRepository } }
+    pub fn new() -> Self { Self { repo: SqlitePOSTransactionRepository, gateway: None } }
+
+    /// Charges card payments that carry a gateway token in `reference` and no `authorization_code`
+    /// through `gateway`. Without one, card payments are recorded as authorized at the terminal.
+    pub fn with_gateway(mut self, gateway: Arc<dyn PaymentGateway>) -> Self {
+        self.gateway = Some(gateway);
+        self
+    }
     
     pub async fn get(&self, pool: &SqlitePool, id: Uuid) -> Result<POSTransaction> {
         self.repo.find_by_id(pool, id).await
@@ -94,7 +106,106 @@ impl POSTransactionService {
         
         transaction.completed_at = Some(Utc::now());
         
-        self.repo.create(pool, transaction).await
+        // Card payments are charged before the sale is recorded, and given back if recording fails.
+        let charges = self.authorize_card_payments(&mut transaction).await?;
+        self.capture_charges(&charges).await?;
+        match self.repo.create(pool, transaction).await {
+            Ok(transaction) => Ok(transaction),
+            Err(e) => {
+                self.refund_charges(&charges).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Authorizes every gateway-charged card payment, or none: a decline voids the ones before it.
+    /// Returns each authorization's gateway transaction id and amount.
+    async fn authorize_card_payments(&self, transaction: &mut POSTransaction) -> Result<Vec<(String, i64)>> {
+        let Some(gateway) = &self.gateway else { return Ok(Vec::new()) };
+        let mut authorized: Vec<(String, i64)> = Vec::new();
+        for payment in &mut transaction.payments {
+            let is_card = matches!(payment.payment_method, PaymentMethod::CreditCard | PaymentMethod::DebitCard | PaymentMethod::MobilePayment);
+            let Some(token) = payment.reference.clone().filter(|_| is_card && payment.authorization_code.is_none()) else {
+                continue;
+            };
+            let response = match gateway.authorize(AuthorizeRequest {
+                amount: payment.amount.amount,
+                currency: payment.amount.currency.to_string(),
+                payment_method_token: token,
+                description: Some(transaction.transaction_number.clone()),
+                capture: false,
+            }).await {
+                Ok(response) => response,
+                Err(e) => {
+                    self.void_authorizations(&authorized).await?;
+                    return Err(e);
+                }
+            };
+            if response.status != GatewayStatus::Authorized {
+                let refusal = if response.status == GatewayStatus::RequiresAction {
+                    authorized.push((response.transaction_id, payment.amount.amount));
+                    Error::business_rule("Card requires cardholder authentication, which the register cannot complete")
+                } else {
+                    Error::business_rule(format!("Card declined: {}", response.decline_code.as_deref().unwrap_or("unknown reason")))
+                };
+                self.void_authorizations(&authorized).await?;
+                return Err(refusal);
+            }
+            payment.authorization_code = Some(response.transaction_id.clone());
+            payment.card_last_four = response.card_last_four.or(payment.card_last_four.take());
+            payment.card_type = response.card_brand.or(payment.card_type.take());
+            authorized.push((response.transaction_id, payment.amount.amount));
+        }
+        Ok(authorized)
+    }
+
+    /// Captures every authorization, or none: a failed capture refunds the ones before it and voids
+    /// the rest.
+    async fn capture_charges(&self, charges: &[(String, i64)]) -> Result<()> {
+        let Some(gateway) = &self.gateway else { return Ok(()) };
+        for (i, (transaction_id, _)) in charges.iter().enumerate() {
+            let failure = match gateway.capture(transaction_id, None).await {
+                Ok(response) if matches!(response.status, GatewayStatus::Captured | GatewayStatus::Pending) => continue,
+                Ok(response) => Error::business_rule(format!(
+                    "Card capture failed: {}", response.decline_code.as_deref().unwrap_or("unknown reason")
+                )),
+                Err(e) => e,
+            };
+            self.refund_charges(&charges[..i]).await?;
+            self.void_authorizations(&charges[i..]).await?;
+            return Err(failure);
+        }
+        Ok(())
+    }
+
+    /// Voids every authorization, returning the first failure after trying them all.
+    async fn void_authorizations(&self, authorizations: &[(String, i64)]) -> Result<()> {
+        let Some(gateway) = &self.gateway else { return Ok(()) };
+        let mut failure = None;
+        for (transaction_id, _) in authorizations {
+            if let Err(e) = gateway.void(transaction_id).await {
+                failure.get_or_insert(e);
+            }
+        }
+        failure.map_or(Ok(()), Err)
+    }
+
+    /// Refunds every captured charge in full, returning the first failure after trying them all.
+    async fn refund_charges(&self, charges: &[(String, i64)]) -> Result<()> {
+        let Some(gateway) = &self.gateway else { return Ok(()) };
+        let mut failure = None;
+        for (transaction_id, amount) in charges {
+            match gateway.refund(transaction_id, *amount).await {
+                Ok(response) if response.status == GatewayStatus::Declined => {
+                    failure.get_or_insert(Error::business_rule(format!("Gateway declined the refund of {}", transaction_id)));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    failure.get_or_insert(e);
+                }
+            }
+        }
+        failure.map_or(Ok(()), Err)
     }
     
     pub async fn void(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use chrono::{Utc, DateTime};
 use std::str::FromStr;
@@ -182,6 +182,16 @@ impl PurchaseOrderRepository for SqlitePurchaseOrderRepository {
     }
 }
 
+impl SqlitePurchaseOrderRepository {
+    /// Moves an approved order to Completed. Returns false when the order is no longer approved,
+    /// so two concurrent receipts cannot both book the goods.
+    pub async fn mark_received_in(&self, conn: &mut SqliteConnection, id: Uuid) -> Result<bool> {
+        let rows = sqlx::query("UPDATE purchase_orders SET status = 'Completed', updated_at = ? WHERE id = ? AND status = 'Approved'")
+            .bind(Utc::now().to_rfc3339()).bind(id.to_string()).execute(&mut *conn).await?;
+        Ok(rows.rows_affected() == 1)
+    }
+}
+
 pub struct SqliteLandedCostRepository;
 
 #[async_trait]
@@ -2,6 +2,8 @@ use sqlx::SqlitePool;
 use uuid::Uuid;
 use chrono::Utc;
 use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status, Money, Currency};
+use erp_finance::{PostingDocument, PostingEvent, PostingService};
+use erp_inventory::{CostingService, MovementType, StockMovement, StockService};
 use crate::models::*;
 use crate::repository::*;
 
@@ -50,7 +52,56 @@ impl PurchaseOrderService {
     
     pub async fn submit(&self, pool: &SqlitePool, id: Uuid) -> Result<()> { self.repo.update_status(pool, id, Status::Pending).await }
     pub async fn approve(&self, pool: &SqlitePool, id: Uuid) -> Result<()> { self.repo.update_status(pool, id, Status::Approved).await }
-    pub async fn receive(&self, pool: &SqlitePool, id: Uuid) -> Result<()> { self.repo.update_status(pool, id, Status::Completed).await }
+    
+    /// Receives every line of an approved order into a stock location, creating the stock movements
+    /// and cost layers and posting the goods receipt in one transaction.
+    pub async fn receive(&self, pool: &SqlitePool, id: Uuid, location_id: Uuid) -> Result<()> {
+        let order = self.repo.find_by_id(pool, id).await?;
+        if order.status != Status::Approved {
+            return Err(Error::business_rule("Only approved purchase orders can be received"));
+        }
+        
+        let mut tx = pool.begin().await?;
+        if !self.repo.mark_received_in(&mut tx, id).await? {
+            return Err(Error::business_rule("Only approved purchase orders can be received"));
+        }
+        let stock = StockService::new();
+        let (mut received_value, mut price_variance) = (0, 0);
+        for line in &order.lines {
+            let movement = stock.record_movement_in(&mut tx, StockMovement {
+                base: BaseEntity::new(),
+                movement_number: String::new(),
+                movement_type: MovementType::Receipt,
+                product_id: line.product_id,
+                from_location_id: None,
+                to_location_id: location_id,
+                quantity: line.quantity,
+                reference: Some(order.po_number.clone()),
+                date: Utc::now(),
+                unit_cost: Some(line.unit_price.amount),
+                total_cost: None,
+            }).await?;
+            received_value += movement.total_cost.unwrap_or(0);
+            price_variance += CostingService::purchase_price_variance_in(&mut tx, movement.base.id).await?;
+        }
+        // Stock comes in at its inventory value; a standard-cost difference to the order price
+        // goes to the purchase price variance so the clearing account still receives the order value.
+        for (event, amount) in [(PostingEvent::GoodsReceipt, received_value), (PostingEvent::PurchasePriceVariance, price_variance)] {
+            PostingService::post_document(&mut tx, PostingDocument {
+                event,
+                source_type: "PurchaseOrder".to_string(),
+                source_id: order.base.id,
+                source_number: order.po_number.clone(),
+                date: Utc::now(),
+                amount,
+                tax_amount: 0,
+                currency: order.total.currency.clone(),
+                description: Some(format!("Goods receipt for {}", order.po_number)),
+            }).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
 }
 
 pub struct SupplierScorecardService;
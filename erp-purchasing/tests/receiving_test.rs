@@ -0,0 +1,142 @@
+use chrono::{Duration, Utc};
+use erp_core::{BaseEntity, Currency, Money, Status};
+use erp_finance::{PostingEvent, PostingService};
+use erp_inventory::StockService;
+use erp_purchasing::models::*;
+use erp_purchasing::service::PurchaseOrderService;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+async fn setup() -> SqlitePool {
+    let options = SqliteConnectOptions::from_str("sqlite::memory:").unwrap().foreign_keys(false);
+    let pool = SqlitePoolOptions::new().max_connections(1).connect_with(options).await.unwrap();
+    for migration in [
+        include_str!("../../migrations/20240101000000_finance.sql"),
+        include_str!("../../migrations/20240101000001_inventory.sql"),
+        include_str!("../../migrations/20240101000003_purchasing.sql"),
+        include_str!("../../migrations/20240101000018_enterprise_additions.sql"),
+        include_str!("../../migrations/20260309000000_posting_rules.sql"),
+        include_str!("../../migrations/20260315000000_inventory_costing.sql"),
+        include_str!("../../migrations/20260329000500_journal_line_currency.sql"),
+    ] {
+        for statement in migration.split(';') {
+            let statement = statement.trim();
+            if !statement.is_empty() {
+                sqlx::query(statement).execute(&pool).await.unwrap();
+            }
+        }
+    }
+    pool
+}
+
+async fn insert(pool: &SqlitePool, sql: &str, binds: &[&str]) {
+    let mut query = sqlx::query(sql);
+    for value in binds {
+        query = query.bind(*value);
+    }
+    query.execute(pool).await.unwrap();
+}
+
+async fn account(pool: &SqlitePool, code: &str, account_type: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    let now = Utc::now().to_rfc3339();
+    insert(pool, "INSERT INTO accounts (id, code, name, account_type, status, created_at, updated_at) VALUES (?, ?, ?, ?, 'Active', ?, ?)", &[&id.to_string(), code, code, account_type, &now, &now]).await;
+    id
+}
+
+async fn location(pool: &SqlitePool) -> Uuid {
+    let (warehouse, location) = (Uuid::new_v4().to_string(), Uuid::new_v4());
+    let now = Utc::now().to_rfc3339();
+    insert(pool, "INSERT INTO warehouses (id, code, name, created_at, updated_at) VALUES (?, ?, 'Main', ?, ?)", &[&warehouse, &warehouse, &now, &now]).await;
+    insert(pool, "INSERT INTO stock_locations (id, warehouse_id, code, name, created_at, updated_at) VALUES (?, ?, 'A1', 'Aisle 1', ?, ?)", &[&location.to_string(), &warehouse, &now, &now]).await;
+    location
+}
+
+/// An approved order for 10 x 250 of a new product.
+async fn approved_order(pool: &SqlitePool) -> PurchaseOrder {
+    let product = Uuid::new_v4();
+    let now = Utc::now().to_rfc3339();
+    insert(pool, "INSERT INTO products (id, sku, name, product_type, unit_of_measure, created_at, updated_at) VALUES (?, ?, 'Bolt', 'Goods', 'PCS', ?, ?)", &[&product.to_string(), &product.to_string(), &now, &now]).await;
+
+    let service = PurchaseOrderService::new();
+    let order = service.create(pool, PurchaseOrder {
+        base: BaseEntity::new(),
+        po_number: String::new(),
+        vendor_id: Uuid::new_v4(),
+        order_date: Utc::now(),
+        expected_date: None,
+        lines: vec![PurchaseOrderLine {
+            id: Uuid::nil(),
+            product_id: product,
+            description: "Bolts".to_string(),
+            quantity: 10,
+            unit_price: Money::new(250, Currency::USD),
+            tax_rate: 0.0,
+            line_total: Money::new(2500, Currency::USD),
+        }],
+        subtotal: Money::zero(Currency::USD),
+        tax_amount: Money::zero(Currency::USD),
+        total: Money::zero(Currency::USD),
+        status: Status::Draft,
+    }).await.unwrap();
+    service.approve(pool, order.base.id).await.unwrap();
+    service.get(pool, order.base.id).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_receive_records_stock_and_posts_goods_receipt() {
+    let pool = setup().await;
+    let (inventory, clearing) = (account(&pool, "1300", "Asset").await, account(&pool, "2100", "Liability").await);
+    PostingService::upsert_rule(&pool, PostingEvent::GoodsReceipt, inventory, clearing, None, None).await.unwrap();
+    let order = approved_order(&pool).await;
+    let location = location(&pool).await;
+    let service = PurchaseOrderService::new();
+
+    service.receive(&pool, order.base.id, location).await.unwrap();
+
+    let level = StockService::new().get_stock_level(&pool, order.lines[0].product_id, location).await.unwrap();
+    assert_eq!(level.quantity, 10);
+    let sources = PostingService::list_sources(&pool, "PurchaseOrder", order.base.id).await.unwrap();
+    assert_eq!(sources.len(), 1);
+    let (debit,): (i64,) = sqlx::query_as("SELECT debit FROM journal_lines WHERE journal_entry_id = ? AND account_id = ?")
+        .bind(sources[0].journal_entry_id.to_string())
+        .bind(inventory.to_string())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(debit, 2500);
+    assert_eq!(service.get(&pool, order.base.id).await.unwrap().status, Status::Completed);
+
+    let again = service.receive(&pool, order.base.id, location).await;
+    assert!(matches!(again, Err(erp_core::Error::BusinessRule(_))));
+    let level = StockService::new().get_stock_level(&pool, order.lines[0].product_id, location).await.unwrap();
+    assert_eq!(level.quantity, 10);
+}
+
+#[tokio::test]
+async fn test_receive_into_closed_period_leaves_order_untouched() {
+    let pool = setup().await;
+    let (inventory, clearing) = (account(&pool, "1300", "Asset").await, account(&pool, "2100", "Liability").await);
+    PostingService::upsert_rule(&pool, PostingEvent::GoodsReceipt, inventory, clearing, None, None).await.unwrap();
+    let now = Utc::now();
+    insert(&pool, "INSERT INTO accounting_periods (id, fiscal_year_id, period_number, name, start_date, end_date, lock_type, created_at) VALUES (?, ?, 1, 'Closed', ?, ?, 'HardClose', ?)", &[
+        &Uuid::new_v4().to_string(),
+        &Uuid::new_v4().to_string(),
+        &(now - Duration::days(1)).to_rfc3339(),
+        &(now + Duration::days(1)).to_rfc3339(),
+        &now.to_rfc3339(),
+    ]).await;
+    let order = approved_order(&pool).await;
+    let location = location(&pool).await;
+    let service = PurchaseOrderService::new();
+
+    assert!(service.receive(&pool, order.base.id, location).await.is_err());
+
+    assert_eq!(service.get(&pool, order.base.id).await.unwrap().status, Status::Approved);
+    assert!(StockService::new().get_stock_level(&pool, order.lines[0].product_id, location).await.is_err());
+    assert!(PostingService::list_sources(&pool, "PurchaseOrder", order.base.id).await.unwrap().is_empty());
+    let (entries,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM journal_entries").fetch_one(&pool).await.unwrap();
+    assert_eq!(entries, 0);
+}
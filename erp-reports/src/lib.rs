@@ -1,6 +1,10 @@
 pub mod models;
+pub mod query;
+pub mod render;
 pub mod repository;
+pub mod semantic;
 pub mod service;
 pub use models::*;
+pub use query::*;
 pub use repository::*;
 pub use service::*;
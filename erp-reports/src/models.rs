@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
+use crate::query::ReportQuery;
 use erp_core::{BaseEntity, Status};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum ReportFormat {
     PDF,
@@ -14,6 +15,30 @@ pub enum ReportFormat {
     Word,
 }
 
+impl ReportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::PDF => "pdf",
+            ReportFormat::Excel => "xlsx",
+            ReportFormat::CSV => "csv",
+            ReportFormat::HTML => "html",
+            ReportFormat::JSON => "json",
+            ReportFormat::Word => "docx",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ReportFormat::PDF => "application/pdf",
+            ReportFormat::Excel => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            ReportFormat::CSV => "text/csv",
+            ReportFormat::HTML => "text/html",
+            ReportFormat::JSON => "application/json",
+            ReportFormat::Word => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum ScheduleFrequency {
@@ -56,7 +81,7 @@ pub struct ReportDefinition {
     pub category: ReportCategory,
     pub description: Option<String>,
     pub data_source: String,
-    pub query_template: String,
+    pub query: ReportQuery,
     pub parameters: Vec<ReportParameter>,
     pub columns: Vec<ReportColumn>,
     pub default_format: ReportFormat,
@@ -129,7 +154,7 @@ pub struct ReportColumn {
     pub alignment: ColumnAlignment,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum ColumnDataType {
     String,
@@ -142,7 +167,7 @@ pub enum ColumnDataType {
     Boolean,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum AggregationType {
     Sum,
@@ -201,12 +226,25 @@ pub struct ReportExecution {
     pub row_count: i64,
     pub file_path: Option<String>,
     pub file_size_bytes: Option<i64>,
+    /// SHA-256 of the output, which is also how its blob is found.
+    pub checksum: Option<String>,
     pub error_message: Option<String>,
     pub delivery_status: Option<String>,
     pub delivered_at: Option<DateTime<Utc>>,
     pub executed_by: Option<Uuid>,
 }
 
+/// What a completed execution produced.
+#[derive(Debug, Clone)]
+pub struct ReportOutput {
+    /// Key of the output's blob.
+    pub file_path: String,
+    pub file_size_bytes: i64,
+    pub checksum: String,
+    pub row_count: i64,
+    pub duration_ms: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportDashboard {
     pub base: BaseEntity,
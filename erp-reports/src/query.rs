@@ -0,0 +1,508 @@
+//! Compiles a [`ReportQuery`] against the semantic model into a parameterised SELECT.
+
+use crate::models::*;
+use crate::semantic::{self, Entity, Field};
+use erp_core::{Error, Result, RowScope};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportQuery {
+    pub entity: String,
+    pub columns: Vec<QueryColumn>,
+    #[serde(default)]
+    pub filters: Vec<QueryFilter>,
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    #[serde(default)]
+    pub sort: Vec<QuerySort>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryColumn {
+    /// A field of the entity, or `join.field` for a field of a joined entity.
+    pub field: String,
+    pub label: Option<String>,
+    pub aggregate: Option<AggregationType>,
+    /// Whether the column is summed in the report's totals row.
+    #[serde(default)]
+    pub total: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryFilter {
+    pub field: String,
+    pub op: FilterOp,
+    /// A fixed value. Currency values are in cents, like everywhere else in the API.
+    pub value: Option<serde_json::Value>,
+    /// The report parameter to take the value from instead. The filter is left out when an
+    /// optional parameter has no value.
+    pub param: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    Contains,
+    StartsWith,
+    In,
+    IsNull,
+    IsNotNull,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuerySort {
+    /// An output column, by field or by the name it has in the output.
+    pub field: String,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// What the person a report runs for may see, by entity name.
+#[derive(Debug, Clone, Default)]
+pub struct DataAccess {
+    denied: HashSet<String>,
+    scopes: HashMap<String, RowScope>,
+    hidden: HashSet<(String, String)>,
+}
+
+impl DataAccess {
+    pub fn unrestricted() -> Self {
+        Self::default()
+    }
+
+    /// Refuses reports that read the entity, directly or through a join.
+    pub fn deny(mut self, entity: &str) -> Self {
+        self.denied.insert(entity.to_string());
+        self
+    }
+
+    /// Limits the entity to the rows within `scope`, whose predicates name the entity's table.
+    pub fn scope(mut self, entity: &str, scope: RowScope) -> Self {
+        if !scope.is_unrestricted() {
+            self.scopes.insert(entity.to_string(), scope);
+        }
+        self
+    }
+
+    /// Refuses reports that show or filter on the field.
+    pub fn hide(mut self, entity: &str, field: &str) -> Self {
+        self.hidden.insert((entity.to_string(), field.to_string()));
+        self
+    }
+
+    pub fn allows(&self, entity: &str) -> bool {
+        !self.denied.contains(entity)
+    }
+
+    fn check(&self, entity: &Entity) -> Result<()> {
+        if !self.allows(entity.name) {
+            return Err(Error::forbidden(format!("Not allowed to report on {}", entity.label.to_lowercase())));
+        }
+        Ok(())
+    }
+
+    fn check_field(&self, entity: &Entity, field: &Field) -> Result<()> {
+        if self.hidden.contains(&(entity.name.to_string(), field.name.to_string())) {
+            return Err(Error::forbidden(format!("Not allowed to read {} of {}", field.label, entity.label.to_lowercase())));
+        }
+        Ok(())
+    }
+
+    /// ` AND (...)` predicates restricting `alias`, which reads the entity's table, to the visible rows.
+    fn restrict(&self, entity: &Entity, alias: &str, binds: &mut Vec<SqlValue>) -> String {
+        let mut sql = String::new();
+        if let Some(scope) = self.scopes.get(entity.name) {
+            sql.push_str(&format!(
+                " AND {}.\"id\" IN (SELECT id FROM {} WHERE 1 = 1{})", quote(alias), entity.name, scope.sql()
+            ));
+            binds.extend(scope.binds().iter().cloned().map(SqlValue::Text));
+        }
+        if let Some((parent, foreign_key)) = entity.parent {
+            if let Some(scope) = self.scopes.get(parent) {
+                sql.push_str(&format!(
+                    " AND {}.{} IN (SELECT id FROM {} WHERE 1 = 1{})", quote(alias), quote(foreign_key), parent, scope.sql()
+                ));
+                binds.extend(scope.binds().iter().cloned().map(SqlValue::Text));
+            }
+        }
+        sql
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct CompiledQuery {
+    pub sql: String,
+    pub binds: Vec<SqlValue>,
+    /// The output columns in order. `name` is the column's name in the result set, and an
+    /// `aggregation` of `Sum` marks the columns of the totals row.
+    pub columns: Vec<ReportColumn>,
+}
+
+impl CompiledQuery {
+    pub fn bind<'q>(
+        &'q self,
+        mut query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    ) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+        for value in &self.binds {
+            query = match value {
+                SqlValue::Null => query.bind(None::<String>),
+                SqlValue::Integer(v) => query.bind(*v),
+                SqlValue::Real(v) => query.bind(*v),
+                SqlValue::Text(v) => query.bind(v.as_str()),
+            };
+        }
+        query
+    }
+}
+
+/// A field reference resolved against the model.
+struct Resolved {
+    entity: &'static Entity,
+    field: &'static Field,
+    /// The join it is read through, if not the report's own entity.
+    join: Option<&'static semantic::Join>,
+}
+
+impl Resolved {
+    fn alias(&self, base: &Entity) -> &'static str {
+        self.join.map(|j| j.name).unwrap_or(base.name)
+    }
+
+    fn expression(&self, base: &Entity) -> String {
+        let column = format!("{}.{}", quote(self.alias(base)), quote(self.field.name));
+        match self.field.data_type {
+            // Dates are stored as RFC 3339 timestamps in some tables and plain dates in others.
+            ColumnDataType::Date => format!("substr({}, 1, 10)", column),
+            _ => column,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self.join {
+            Some(join) => format!("{} {}", join.label, self.field.label),
+            None => self.field.label.to_string(),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self.join {
+            Some(join) => format!("{}_{}", join.name, self.field.name),
+            None => self.field.name.to_string(),
+        }
+    }
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier)
+}
+
+fn resolve(base: &'static Entity, path: &str, access: &DataAccess) -> Result<Resolved> {
+    let resolved = match path.split_once('.') {
+        None => Resolved {
+            entity: base,
+            field: base.field(path).ok_or_else(|| unknown_field(base, path))?,
+            join: None,
+        },
+        Some((join_name, field_name)) => {
+            let join = base.join(join_name).ok_or_else(|| unknown_field(base, path))?;
+            let entity = semantic::entity(join.entity).ok_or_else(|| Error::internal(format!("Unknown entity {}", join.entity)))?;
+            Resolved {
+                entity,
+                field: entity.field(field_name).ok_or_else(|| unknown_field(base, path))?,
+                join: Some(join),
+            }
+        }
+    };
+    access.check(resolved.entity)?;
+    access.check_field(resolved.entity, resolved.field)?;
+    Ok(resolved)
+}
+
+fn unknown_field(entity: &Entity, path: &str) -> Error {
+    Error::validation(format!("{} has no field {}", entity.label, path))
+}
+
+/// Checks a query and its parameters without running it, so definitions can be rejected on save.
+pub fn validate(query: &ReportQuery, parameters: &[ReportParameter]) -> Result<Vec<ReportColumn>> {
+    let values = parameters.iter()
+        .map(|p| (p.name.clone(), p.default_value.clone().map(serde_json::Value::String).unwrap_or(serde_json::Value::Null)))
+        .collect();
+    let optional: Vec<ReportParameter> = parameters.iter().cloned().map(|p| ReportParameter { is_required: false, ..p }).collect();
+    Ok(compile(query, &optional, &values, &DataAccess::unrestricted())?.columns)
+}
+
+pub fn compile(
+    query: &ReportQuery,
+    parameters: &[ReportParameter],
+    values: &serde_json::Map<String, serde_json::Value>,
+    access: &DataAccess,
+) -> Result<CompiledQuery> {
+    let base = semantic::entity(&query.entity)
+        .ok_or_else(|| Error::validation(format!("Unknown report entity {}", query.entity)))?;
+    access.check(base)?;
+    if let Some((parent, _)) = base.parent {
+        access.check(semantic::entity(parent).ok_or_else(|| Error::internal(format!("Unknown entity {}", parent)))?)?;
+    }
+    if query.columns.is_empty() {
+        return Err(Error::validation("A report needs at least one column"));
+    }
+
+    let mut joins: Vec<&'static semantic::Join> = Vec::new();
+    let mut use_join = |resolved: &Resolved| {
+        if let Some(join) = resolved.join {
+            if !joins.iter().any(|j| j.name == join.name) {
+                joins.push(join);
+            }
+        }
+    };
+
+    let grouped = !query.group_by.is_empty()
+        || query.columns.iter().any(|c| matches!(c.aggregate, Some(a) if a != AggregationType::None));
+    let mut group_by = Vec::new();
+    for path in &query.group_by {
+        let resolved = resolve(base, path, access)?;
+        use_join(&resolved);
+        group_by.push((path.as_str(), resolved.expression(base)));
+    }
+
+    let mut select = Vec::new();
+    let mut columns: Vec<ReportColumn> = Vec::new();
+    for column in &query.columns {
+        let resolved = resolve(base, &column.field, access)?;
+        use_join(&resolved);
+        let expression = resolved.expression(base);
+        let aggregate = column.aggregate.filter(|a| *a != AggregationType::None);
+        let (sql, name, label, data_type) = match aggregate {
+            None => {
+                if grouped && !group_by.iter().any(|(path, _)| *path == column.field) {
+                    return Err(Error::validation(format!(
+                        "{} must be grouped by or aggregated", column.field
+                    )));
+                }
+                (expression, resolved.name(), resolved.label(), resolved.field.data_type)
+            }
+            Some(aggregate) => {
+                let (function, verb) = match aggregate {
+                    AggregationType::Sum => ("SUM", "Sum"),
+                    AggregationType::Average => ("AVG", "Average"),
+                    AggregationType::Count => ("COUNT", "Count"),
+                    AggregationType::Min => ("MIN", "Min"),
+                    AggregationType::Max => ("MAX", "Max"),
+                    AggregationType::None => unreachable!(),
+                };
+                let data_type = match aggregate {
+                    AggregationType::Count => ColumnDataType::Integer,
+                    AggregationType::Average if resolved.field.data_type == ColumnDataType::Integer => ColumnDataType::Decimal,
+                    _ => resolved.field.data_type,
+                };
+                if matches!(aggregate, AggregationType::Sum | AggregationType::Average) && !is_numeric(resolved.field.data_type) {
+                    return Err(Error::validation(format!("{} of {} is not a number", verb, column.field)));
+                }
+                (
+                    format!("{}({})", function, expression),
+                    format!("{}_{}", function.to_lowercase(), resolved.name()),
+                    format!("{} of {}", verb, resolved.label()),
+                    data_type,
+                )
+            }
+        };
+        if columns.iter().any(|c| c.name == name) {
+            return Err(Error::validation(format!("Column {} appears more than once", name)));
+        }
+        if column.total && !matches!(data_type, ColumnDataType::Integer | ColumnDataType::Decimal | ColumnDataType::Currency) {
+            return Err(Error::validation(format!("Only numeric columns can be totalled, not {}", name)));
+        }
+        select.push(format!("{} AS {}", sql, quote(&name)));
+        columns.push(ReportColumn {
+            alignment: if is_numeric(data_type) { ColumnAlignment::Right } else { ColumnAlignment::Left },
+            name,
+            label: column.label.clone().unwrap_or(label),
+            data_type,
+            format: None,
+            is_visible: true,
+            is_sortable: true,
+            is_filterable: aggregate.is_none(),
+            aggregation: column.total.then_some(AggregationType::Sum),
+            width: None,
+        });
+    }
+
+    let mut conditions = Vec::new();
+    let mut filter_binds = Vec::new();
+    for filter in &query.filters {
+        let resolved = resolve(base, &filter.field, access)?;
+        let value = match &filter.param {
+            Some(name) => {
+                let parameter = parameters.iter().find(|p| &p.name == name)
+                    .ok_or_else(|| Error::validation(format!("Filter on {} uses undeclared parameter {}", filter.field, name)))?;
+                match values.get(name).filter(|v| !v.is_null()).cloned()
+                    .or_else(|| parameter.default_value.clone().map(serde_json::Value::String))
+                {
+                    Some(value) => Some(value),
+                    None if parameter.is_required => {
+                        return Err(Error::validation(format!("Parameter {} is required", parameter.label)));
+                    }
+                    None => continue,
+                }
+            }
+            None => filter.value.clone(),
+        };
+        use_join(&resolved);
+        conditions.push(condition(&resolved, base, filter.op, value, &mut filter_binds)?);
+    }
+
+    let mut sort = Vec::new();
+    for order in &query.sort {
+        let position = query.columns.iter().position(|c| c.field == order.field)
+            .or_else(|| columns.iter().position(|c| c.name == order.field))
+            .ok_or_else(|| Error::validation(format!("Can only sort by an output column, not {}", order.field)))?;
+        sort.push(format!("{}{}", quote(&columns[position].name), if order.descending { " DESC" } else { "" }));
+    }
+
+    let mut binds = Vec::new();
+    let mut sql = format!("SELECT {} FROM {}", select.join(", "), quote(base.name));
+    for join in &joins {
+        let entity = semantic::entity(join.entity).ok_or_else(|| Error::internal(format!("Unknown entity {}", join.entity)))?;
+        sql.push_str(&format!(
+            " LEFT JOIN {} AS {} ON {}.\"id\" = {}.{}{}",
+            quote(entity.name), quote(join.name), quote(join.name), quote(base.name), quote(join.foreign_key),
+            access.restrict(entity, join.name, &mut binds)
+        ));
+    }
+    sql.push_str(&format!(" WHERE 1 = 1{}", access.restrict(base, base.name, &mut binds)));
+    for condition in conditions {
+        sql.push_str(&format!(" AND {}", condition));
+    }
+    binds.extend(filter_binds);
+    if !group_by.is_empty() {
+        sql.push_str(&format!(" GROUP BY {}", group_by.iter().map(|(_, e)| e.as_str()).collect::<Vec<_>>().join(", ")));
+    }
+    if !sort.is_empty() {
+        sql.push_str(&format!(" ORDER BY {}", sort.join(", ")));
+    }
+    if let Some(limit) = query.limit {
+        if limit <= 0 {
+            return Err(Error::validation("Limit must be positive"));
+        }
+        sql.push_str(" LIMIT ?");
+        binds.push(SqlValue::Integer(limit));
+    }
+
+    Ok(CompiledQuery { sql, binds, columns })
+}
+
+fn is_numeric(data_type: ColumnDataType) -> bool {
+    matches!(data_type, ColumnDataType::Integer | ColumnDataType::Decimal | ColumnDataType::Currency | ColumnDataType::Percentage)
+}
+
+fn condition(
+    resolved: &Resolved,
+    base: &Entity,
+    op: FilterOp,
+    value: Option<serde_json::Value>,
+    binds: &mut Vec<SqlValue>,
+) -> Result<String> {
+    let expression = resolved.expression(base);
+    let field = resolved.field;
+    let required = || value.clone().filter(|v| !v.is_null())
+        .ok_or_else(|| Error::validation(format!("Filter on {} needs a value", field.name)));
+    let sql = match op {
+        FilterOp::IsNull => format!("{} IS NULL", expression),
+        FilterOp::IsNotNull => format!("{} IS NOT NULL", expression),
+        FilterOp::Contains | FilterOp::StartsWith => {
+            let text = match required()? {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            };
+            let escaped = text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            binds.push(SqlValue::Text(if op == FilterOp::Contains { format!("%{}%", escaped) } else { format!("{}%", escaped) }));
+            format!("{} LIKE ? ESCAPE '\\'", expression)
+        }
+        FilterOp::In => {
+            let items = match required()? {
+                serde_json::Value::Array(items) => items,
+                serde_json::Value::String(s) => s.split(',').map(|v| serde_json::Value::String(v.trim().to_string())).collect(),
+                other => vec![other],
+            };
+            if items.is_empty() {
+                return Ok("0 = 1".to_string());
+            }
+            for item in &items {
+                binds.push(typed_value(field, item)?);
+            }
+            format!("{} IN ({})", expression, vec!["?"; items.len()].join(", "))
+        }
+        _ => {
+            binds.push(typed_value(field, &required()?)?);
+            let operator = match op {
+                FilterOp::Eq => "=",
+                FilterOp::Ne => "IS NOT",
+                FilterOp::Lt => "<",
+                FilterOp::Lte => "<=",
+                FilterOp::Gt => ">",
+                FilterOp::Gte => ">=",
+                _ => unreachable!(),
+            };
+            format!("{} {} ?", expression, operator)
+        }
+    };
+    Ok(sql)
+}
+
+/// Converts a filter value to the type of the field, so comparisons behave as they would
+/// against the stored column.
+fn typed_value(field: &Field, value: &serde_json::Value) -> Result<SqlValue> {
+    let invalid = || Error::validation(format!("{} is not a valid value for {}", value, field.label));
+    let text = match value {
+        serde_json::Value::String(s) => Some(s.trim()),
+        _ => None,
+    };
+    Ok(match field.data_type {
+        ColumnDataType::Integer | ColumnDataType::Currency => SqlValue::Integer(match (value.as_i64(), text) {
+            (Some(v), _) => v,
+            (None, Some(s)) => s.parse().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        }),
+        ColumnDataType::Decimal | ColumnDataType::Percentage => SqlValue::Real(match (value.as_f64(), text) {
+            (Some(v), _) => v,
+            (None, Some(s)) => s.parse().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        }),
+        ColumnDataType::Boolean => SqlValue::Integer(match (value.as_bool(), text) {
+            (Some(v), _) => v as i64,
+            (None, Some("true" | "1")) => 1,
+            (None, Some("false" | "0")) => 0,
+            _ => return Err(invalid()),
+        }),
+        ColumnDataType::Date => {
+            let date = text.ok_or_else(invalid)?;
+            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| invalid())?;
+            SqlValue::Text(date.to_string())
+        }
+        ColumnDataType::DateTime => {
+            let timestamp = text.ok_or_else(invalid)?;
+            chrono::DateTime::parse_from_rfc3339(timestamp).map_err(|_| invalid())?;
+            SqlValue::Text(timestamp.to_string())
+        }
+        ColumnDataType::String => SqlValue::Text(match text {
+            Some(s) => s.to_string(),
+            None => value.to_string(),
+        }),
+    })
+}
@@ -0,0 +1,47 @@
+use super::{Cell, Renderer, Totals};
+use crate::models::ReportColumn;
+use erp_core::Result;
+
+/// RFC 4180 CSV with a header row of column labels. Totals are left out so every line is data.
+pub struct CsvRenderer {
+    out: Vec<u8>,
+}
+
+impl CsvRenderer {
+    pub fn new(columns: Vec<ReportColumn>) -> Self {
+        let mut renderer = Self { out: Vec::new() };
+        let header: Vec<String> = columns.iter().map(|c| c.label.clone()).collect();
+        renderer.line(&header);
+        renderer
+    }
+
+    fn line(&mut self, values: &[String]) {
+        let fields: Vec<String> = values.iter().map(|v| escape(v)).collect();
+        self.out.extend_from_slice(fields.join(",").as_bytes());
+        self.out.extend_from_slice(b"\r\n");
+    }
+}
+
+fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl Renderer for CsvRenderer {
+    fn row(&mut self, cells: &[Cell]) -> Result<()> {
+        let values: Vec<String> = cells.iter().map(Cell::text).collect();
+        self.line(&values);
+        Ok(())
+    }
+
+    fn finish(&mut self, _totals: &Totals) -> Result<()> {
+        Ok(())
+    }
+
+    fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.out)
+    }
+}
@@ -0,0 +1,50 @@
+use super::{Cell, Renderer, Totals};
+use crate::models::ReportColumn;
+use erp_core::{Error, Result};
+use serde_json::{json, Map, Value};
+
+/// `{"columns": [...], "rows": [{...}], "totals": {...}}`, with one object per row keyed by
+/// column name. Amounts are in cents.
+pub struct JsonRenderer {
+    columns: Vec<ReportColumn>,
+    out: Vec<u8>,
+    rows: usize,
+}
+
+impl JsonRenderer {
+    pub fn new(columns: Vec<ReportColumn>) -> Self {
+        let described: Vec<Value> = columns.iter()
+            .map(|c| json!({ "name": c.name, "label": c.label, "data_type": c.data_type }))
+            .collect();
+        let out = format!("{{\"columns\":{},\"rows\":[", Value::Array(described)).into_bytes();
+        Self { columns, out, rows: 0 }
+    }
+}
+
+impl Renderer for JsonRenderer {
+    fn row(&mut self, cells: &[Cell]) -> Result<()> {
+        let row: Map<String, Value> = self.columns.iter().zip(cells)
+            .map(|(column, cell)| (column.name.clone(), cell.json()))
+            .collect();
+        if self.rows > 0 {
+            self.out.push(b',');
+        }
+        serde_json::to_writer(&mut self.out, &row).map_err(|e| Error::internal(e.to_string()))?;
+        self.rows += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self, totals: &Totals) -> Result<()> {
+        let totals: Map<String, Value> = self.columns.iter().zip(totals.cells())
+            .filter_map(|(column, total)| total.as_ref().map(|t| (column.name.clone(), t.json())))
+            .collect();
+        self.out.extend_from_slice(b"],\"totals\":");
+        serde_json::to_writer(&mut self.out, &totals).map_err(|e| Error::internal(e.to_string()))?;
+        self.out.push(b'}');
+        Ok(())
+    }
+
+    fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.out)
+    }
+}
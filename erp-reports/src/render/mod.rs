@@ -0,0 +1,185 @@
+//! Writers for report output. Rows are fed in one at a time and the bytes produced so far
+//! can be taken at any point, so a report never has to be held in memory whole.
+
+mod csv;
+mod json;
+mod pdf;
+mod xlsx;
+
+use crate::models::{AggregationType, ColumnDataType, ReportColumn, ReportFormat};
+use erp_core::{Error, Result};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, TypeInfo, ValueRef};
+
+pub use self::csv::CsvRenderer;
+pub use self::json::JsonRenderer;
+pub use self::pdf::PdfRenderer;
+pub use self::xlsx::XlsxRenderer;
+
+/// A value of a report cell, typed by its column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Null,
+    Text(String),
+    Integer(i64),
+    Decimal(f64),
+    /// An amount in cents.
+    Money(i64),
+    /// `YYYY-MM-DD`.
+    Date(String),
+    /// An RFC 3339 timestamp.
+    DateTime(String),
+    Bool(bool),
+}
+
+impl Cell {
+    /// Reads column `index` of `row` as a cell of `data_type`.
+    pub fn decode(row: &SqliteRow, index: usize, data_type: ColumnDataType) -> Result<Cell> {
+        enum Raw {
+            Integer(i64),
+            Real(f64),
+            Text(String),
+        }
+        let value = row.try_get_raw(index)?;
+        if value.is_null() {
+            return Ok(Cell::Null);
+        }
+        let raw = match value.type_info().name() {
+            "INTEGER" => Raw::Integer(row.try_get_unchecked(index)?),
+            "REAL" => Raw::Real(row.try_get_unchecked(index)?),
+            _ => Raw::Text(row.try_get_unchecked(index)?),
+        };
+        Ok(match (data_type, raw) {
+            (ColumnDataType::Currency, Raw::Integer(v)) => Cell::Money(v),
+            (ColumnDataType::Currency, Raw::Real(v)) => Cell::Money(v.round() as i64),
+            (ColumnDataType::Integer, Raw::Integer(v)) => Cell::Integer(v),
+            (ColumnDataType::Boolean, Raw::Integer(v)) => Cell::Bool(v != 0),
+            (ColumnDataType::Date, Raw::Text(v)) => Cell::Date(v),
+            (ColumnDataType::DateTime, Raw::Text(v)) => Cell::DateTime(v),
+            (ColumnDataType::String, Raw::Text(v)) => Cell::Text(v),
+            (_, Raw::Integer(v)) => Cell::Decimal(v as f64),
+            (_, Raw::Real(v)) => Cell::Decimal(v),
+            (_, Raw::Text(v)) => match v.parse::<f64>() {
+                Ok(number) if matches!(data_type, ColumnDataType::Decimal | ColumnDataType::Percentage) => Cell::Decimal(number),
+                _ => Cell::Text(v),
+            },
+        })
+    }
+
+    /// The value as plain text, the way CSV output carries it.
+    pub fn text(&self) -> String {
+        match self {
+            Cell::Null => String::new(),
+            Cell::Text(v) | Cell::Date(v) | Cell::DateTime(v) => v.clone(),
+            Cell::Integer(v) => v.to_string(),
+            Cell::Decimal(v) => v.to_string(),
+            Cell::Money(cents) => money(*cents, false),
+            Cell::Bool(v) => v.to_string(),
+        }
+    }
+
+    /// The value formatted for people to read.
+    pub fn display(&self) -> String {
+        match self {
+            Cell::Decimal(v) => format!("{:.2}", v),
+            Cell::Money(cents) => money(*cents, true),
+            Cell::DateTime(v) => chrono::DateTime::parse_from_rfc3339(v)
+                .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|_| v.clone()),
+            Cell::Bool(v) => if *v { "Yes" } else { "No" }.to_string(),
+            _ => self.text(),
+        }
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        match self {
+            Cell::Null => serde_json::Value::Null,
+            Cell::Text(v) | Cell::Date(v) | Cell::DateTime(v) => v.clone().into(),
+            Cell::Integer(v) | Cell::Money(v) => (*v).into(),
+            Cell::Decimal(v) => serde_json::Number::from_f64(*v).map(Into::into).unwrap_or(serde_json::Value::Null),
+            Cell::Bool(v) => (*v).into(),
+        }
+    }
+}
+
+/// Formats cents as an amount in major units, optionally with thousands separators.
+fn money(cents: i64, grouped: bool) -> String {
+    let units = (cents / 100).unsigned_abs().to_string();
+    let units = if grouped {
+        let digits: Vec<char> = units.chars().collect();
+        let mut out = String::new();
+        for (i, c) in digits.iter().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                out.push(',');
+            }
+            out.push(*c);
+        }
+        out
+    } else {
+        units
+    };
+    format!("{}{}.{:02}", if cents < 0 { "-" } else { "" }, units, (cents % 100).unsigned_abs())
+}
+
+/// Running sums of the columns marked for the totals row.
+#[derive(Debug, Clone)]
+pub struct Totals {
+    sums: Vec<Option<Cell>>,
+}
+
+impl Totals {
+    pub fn new(columns: &[ReportColumn]) -> Self {
+        let sums = columns.iter()
+            .map(|c| match (c.aggregation, c.data_type) {
+                (Some(AggregationType::Sum), ColumnDataType::Currency) => Some(Cell::Money(0)),
+                (Some(AggregationType::Sum), ColumnDataType::Integer) => Some(Cell::Integer(0)),
+                (Some(AggregationType::Sum), _) => Some(Cell::Decimal(0.0)),
+                _ => None,
+            })
+            .collect();
+        Self { sums }
+    }
+
+    pub fn add(&mut self, cells: &[Cell]) {
+        for (sum, cell) in self.sums.iter_mut().zip(cells) {
+            match (sum, cell) {
+                (Some(Cell::Money(total)), Cell::Money(v)) | (Some(Cell::Integer(total)), Cell::Integer(v)) => *total += v,
+                (Some(Cell::Decimal(total)), Cell::Decimal(v)) => *total += v,
+                (Some(Cell::Decimal(total)), Cell::Integer(v)) => *total += *v as f64,
+                _ => {}
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sums.iter().all(Option::is_none)
+    }
+
+    /// The total of each column, `None` for columns that are not totalled.
+    pub fn cells(&self) -> &[Option<Cell>] {
+        &self.sums
+    }
+}
+
+pub trait Renderer: Send {
+    fn row(&mut self, cells: &[Cell]) -> Result<()>;
+
+    /// Ends the output, adding a totals row if any column is totalled.
+    fn finish(&mut self, totals: &Totals) -> Result<()>;
+
+    /// The bytes produced since the last call.
+    fn take_output(&mut self) -> Vec<u8>;
+}
+
+pub fn renderer(format: ReportFormat, title: &str, columns: &[ReportColumn]) -> Result<Box<dyn Renderer>> {
+    let columns = columns.to_vec();
+    Ok(match format {
+        ReportFormat::CSV => Box::new(CsvRenderer::new(columns)),
+        ReportFormat::JSON => Box::new(JsonRenderer::new(columns)),
+        ReportFormat::Excel => Box::new(XlsxRenderer::new(title, columns)),
+        ReportFormat::PDF => Box::new(PdfRenderer::new(title, columns)),
+        ReportFormat::HTML | ReportFormat::Word => {
+            return Err(Error::validation(format!("Reports cannot be produced as {:?}", format)));
+        }
+    })
+}
@@ -0,0 +1,124 @@
+use super::{Cell, Renderer, Totals};
+use crate::models::{ColumnAlignment, ColumnDataType, ReportColumn};
+use erp_core::pdf::{fit_text, text_width, Font, PdfWriter, A4};
+use erp_core::Result;
+
+const MARGIN: f32 = 36.0;
+const TITLE_SIZE: f32 = 14.0;
+const HEADER_SIZE: f32 = 9.0;
+const BODY_SIZE: f32 = 8.0;
+const ROW_HEIGHT: f32 = 14.0;
+const PADDING: f32 = 3.0;
+
+/// A paginated table on A4, turned to landscape for wide reports. Every page repeats the
+/// title and column headers and is numbered in the footer.
+pub struct PdfRenderer {
+    pdf: PdfWriter,
+    title: String,
+    columns: Vec<ReportColumn>,
+    /// Left edge and width of each column.
+    layout: Vec<(f32, f32)>,
+    /// Baseline of the next row, `None` before the first page.
+    y: Option<f32>,
+}
+
+impl PdfRenderer {
+    pub fn new(title: &str, columns: Vec<ReportColumn>) -> Self {
+        let size = if columns.len() > 5 { (A4.1, A4.0) } else { A4 };
+        let weights: Vec<f32> = columns.iter()
+            .map(|c| match c.data_type {
+                ColumnDataType::String => 2.0,
+                ColumnDataType::Boolean => 0.8,
+                _ => 1.2,
+            })
+            .collect();
+        let total: f32 = weights.iter().sum::<f32>().max(1.0);
+        let available = size.0 - 2.0 * MARGIN;
+        let mut x = MARGIN;
+        let layout = weights.iter()
+            .map(|w| {
+                let width = available * w / total;
+                let column = (x, width);
+                x += width;
+                column
+            })
+            .collect();
+        Self { pdf: PdfWriter::new(size, title), title: title.to_string(), columns, layout, y: None }
+    }
+
+    fn start_page(&mut self) {
+        self.pdf.begin_page();
+        let (width, height) = self.pdf.page_size();
+        let number = self.pdf.page_count() + 1;
+        let mut y = height - MARGIN - TITLE_SIZE;
+        let title = fit_text(&self.title, Font::Bold, TITLE_SIZE, width - 2.0 * MARGIN);
+        self.pdf.text(MARGIN, y, Font::Bold, TITLE_SIZE, &title);
+        let footer = format!("Page {}", number);
+        let footer_x = width - MARGIN - text_width(&footer, Font::Regular, BODY_SIZE);
+        self.pdf.text(footer_x, MARGIN / 2.0, Font::Regular, BODY_SIZE, &footer);
+
+        y -= ROW_HEIGHT * 1.5;
+        self.pdf.fill_rect(MARGIN, y - 4.0, width - 2.0 * MARGIN, ROW_HEIGHT, 0.9);
+        let labels: Vec<String> = self.columns.iter().map(|c| c.label.clone()).collect();
+        self.draw_row(y, &labels, Font::Bold, HEADER_SIZE);
+        self.y = Some(y - ROW_HEIGHT);
+    }
+
+    /// The baseline for another row, starting a page when the current one is full.
+    fn next_line(&mut self) -> f32 {
+        match self.y {
+            Some(y) if y >= MARGIN + ROW_HEIGHT => y,
+            _ => {
+                self.start_page();
+                self.y.unwrap_or_default()
+            }
+        }
+    }
+
+    fn draw_row(&mut self, y: f32, values: &[String], font: Font, size: f32) {
+        for ((value, column), (x, width)) in values.iter().zip(&self.columns).zip(self.layout.clone()) {
+            let text = fit_text(value, font, size, width - 2.0 * PADDING);
+            let text_x = match column.alignment {
+                ColumnAlignment::Right => x + width - PADDING - text_width(&text, font, size),
+                ColumnAlignment::Center => x + (width - text_width(&text, font, size)) / 2.0,
+                ColumnAlignment::Left => x + PADDING,
+            };
+            self.pdf.text(text_x, y, font, size, &text);
+        }
+    }
+}
+
+impl Renderer for PdfRenderer {
+    fn row(&mut self, cells: &[Cell]) -> Result<()> {
+        let y = self.next_line();
+        let values: Vec<String> = cells.iter().map(Cell::display).collect();
+        self.draw_row(y, &values, Font::Regular, BODY_SIZE);
+        self.y = Some(y - ROW_HEIGHT);
+        Ok(())
+    }
+
+    fn finish(&mut self, totals: &Totals) -> Result<()> {
+        if self.y.is_none() {
+            self.start_page();
+        }
+        if !totals.is_empty() {
+            let y = self.next_line();
+            let (width, _) = self.pdf.page_size();
+            self.pdf.line((MARGIN, y + ROW_HEIGHT - 3.0), (width - MARGIN, y + ROW_HEIGHT - 3.0), 0.5);
+            let mut values: Vec<String> = totals.cells().iter()
+                .map(|t| t.as_ref().map(Cell::display).unwrap_or_default())
+                .collect();
+            if totals.cells()[0].is_none() {
+                values[0] = "Total".to_string();
+            }
+            self.draw_row(y, &values, Font::Bold, BODY_SIZE);
+            self.y = Some(y - ROW_HEIGHT);
+        }
+        self.pdf.finish();
+        Ok(())
+    }
+
+    fn take_output(&mut self) -> Vec<u8> {
+        self.pdf.take_output()
+    }
+}
@@ -0,0 +1,301 @@
+use super::{Cell, Renderer, Totals};
+use crate::models::ReportColumn;
+use erp_core::Result;
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use std::io::Write;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#;
+
+/// Cell formats, referenced by position: plain, bold, amount, date, date and time, bold
+/// amount, bold number.
+const STYLES: &str = r##"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><numFmts count="1"><numFmt numFmtId="164" formatCode="#,##0.00"/></numFmts><fonts count="2"><font><sz val="11"/><name val="Calibri"/></font><font><b/><sz val="11"/><name val="Calibri"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="7"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/><xf numFmtId="164" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="14" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="22" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="164" fontId="1" fillId="0" borderId="0" xfId="0" applyNumberFormat="1" applyFont="1"/><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/></cellXfs></styleSheet>"##;
+
+const BOLD: u8 = 1;
+const AMOUNT: u8 = 2;
+const DATE: u8 = 3;
+const DATE_TIME: u8 = 4;
+const BOLD_AMOUNT: u8 = 5;
+const BOLD_NUMBER: u8 = 6;
+
+const SHEET: &str = "xl/worksheets/sheet1.xml";
+
+/// An Office Open XML workbook with one sheet. Numbers, amounts, dates and booleans are
+/// written as typed cells so they sort and sum in a spreadsheet.
+pub struct XlsxRenderer {
+    zip: ZipWriter,
+    row: usize,
+}
+
+impl XlsxRenderer {
+    pub fn new(title: &str, columns: Vec<ReportColumn>) -> Self {
+        let mut zip = ZipWriter::default();
+        zip.add("[Content_Types].xml", CONTENT_TYPES.as_bytes());
+        zip.add("_rels/.rels", ROOT_RELS.as_bytes());
+        let workbook = format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+            escape(&sheet_name(title))
+        );
+        zip.add("xl/workbook.xml", workbook.as_bytes());
+        zip.add("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.as_bytes());
+        zip.add("xl/styles.xml", STYLES.as_bytes());
+
+        zip.start(SHEET);
+        let mut sheet = String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#, "\n",
+            r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+            r#"<sheetViews><sheetView workbookViewId="0"><pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/></sheetView></sheetViews>"#,
+        ));
+        sheet.push_str("<cols>");
+        for (i, column) in columns.iter().enumerate() {
+            let width = (column.label.chars().count() + 4).clamp(10, 40);
+            sheet.push_str(&format!(r#"<col min="{0}" max="{0}" width="{1}" customWidth="1"/>"#, i + 1, width));
+        }
+        sheet.push_str("</cols><sheetData><row r=\"1\">");
+        for (i, column) in columns.iter().enumerate() {
+            sheet.push_str(&text_cell(&reference(i, 1), &column.label, Some(BOLD)));
+        }
+        sheet.push_str("</row>");
+        zip.write(sheet.as_bytes());
+        Self { zip, row: 1 }
+    }
+
+    fn write_row(&mut self, cells: &[Option<Cell>], bold: bool) {
+        self.row += 1;
+        let mut xml = format!("<row r=\"{}\">", self.row);
+        for (i, cell) in cells.iter().enumerate() {
+            if let Some(cell) = cell {
+                xml.push_str(&cell_xml(&reference(i, self.row), cell, bold));
+            }
+        }
+        xml.push_str("</row>");
+        self.zip.write(xml.as_bytes());
+    }
+}
+
+impl Renderer for XlsxRenderer {
+    fn row(&mut self, cells: &[Cell]) -> Result<()> {
+        let cells: Vec<Option<Cell>> = cells.iter().cloned().map(Some).collect();
+        self.write_row(&cells, false);
+        Ok(())
+    }
+
+    fn finish(&mut self, totals: &Totals) -> Result<()> {
+        if !totals.is_empty() {
+            let mut cells = totals.cells().to_vec();
+            if cells[0].is_none() {
+                cells[0] = Some(Cell::Text("Total".to_string()));
+            }
+            self.write_row(&cells, true);
+        }
+        self.zip.write(b"</sheetData></worksheet>");
+        self.zip.finish();
+        Ok(())
+    }
+
+    fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.zip.out)
+    }
+}
+
+/// Excel limits sheet names to 31 characters, some of which are not allowed.
+fn sheet_name(title: &str) -> String {
+    let name: String = title.chars()
+        .filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\'))
+        .take(31)
+        .collect();
+    if name.trim().is_empty() { "Report".to_string() } else { name }
+}
+
+/// A cell reference such as `AB12` for a zero-based column and one-based row.
+fn reference(column: usize, row: usize) -> String {
+    let mut letters = Vec::new();
+    let mut n = column + 1;
+    while n > 0 {
+        letters.push(b'A' + ((n - 1) % 26) as u8);
+        n = (n - 1) / 26;
+    }
+    letters.reverse();
+    format!("{}{}", String::from_utf8(letters).unwrap_or_default(), row)
+}
+
+fn cell_xml(reference: &str, cell: &Cell, bold: bool) -> String {
+    let number = |value: String, style: Option<u8>| match style {
+        Some(s) => format!(r#"<c r="{}" s="{}"><v>{}</v></c>"#, reference, s, value),
+        None => format!(r#"<c r="{}"><v>{}</v></c>"#, reference, value),
+    };
+    match cell {
+        Cell::Null => String::new(),
+        Cell::Text(v) => text_cell(reference, v, bold.then_some(BOLD)),
+        Cell::Integer(v) => number(v.to_string(), bold.then_some(BOLD_NUMBER)),
+        Cell::Decimal(v) if v.is_finite() => number(v.to_string(), bold.then_some(BOLD_NUMBER)),
+        Cell::Decimal(_) => String::new(),
+        Cell::Money(_) => number(cell.text(), Some(if bold { BOLD_AMOUNT } else { AMOUNT })),
+        Cell::Date(v) => match chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d") {
+            Ok(date) => number(serial(date.and_hms_opt(0, 0, 0).unwrap_or_default()).to_string(), Some(DATE)),
+            Err(_) => text_cell(reference, v, None),
+        },
+        Cell::DateTime(v) => match chrono::DateTime::parse_from_rfc3339(v) {
+            Ok(at) => number(format!("{:.6}", serial(at.naive_utc())), Some(DATE_TIME)),
+            Err(_) => text_cell(reference, v, None),
+        },
+        Cell::Bool(v) => format!(r#"<c r="{}" t="b"><v>{}</v></c>"#, reference, *v as u8),
+    }
+}
+
+fn text_cell(reference: &str, text: &str, style: Option<u8>) -> String {
+    let style = style.map(|s| format!(r#" s="{}""#, s)).unwrap_or_default();
+    format!(r#"<c r="{}"{} t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#, reference, style, escape(text))
+}
+
+/// Days since the spreadsheet epoch of 30 December 1899, with the time as a fraction.
+fn serial(at: chrono::NaiveDateTime) -> f64 {
+    let epoch = chrono::NaiveDate::from_ymd_opt(1899, 12, 30).and_then(|d| d.and_hms_opt(0, 0, 0)).unwrap_or_default();
+    (at - epoch).num_seconds() as f64 / 86_400.0
+}
+
+/// Escapes markup and drops characters XML does not allow.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+struct Entry {
+    name: String,
+    crc: u32,
+    compressed: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// Writes a zip archive front to back. Each entry's checksum and sizes follow its data, so
+/// entries can be compressed as they are produced.
+#[derive(Default)]
+struct ZipWriter {
+    out: Vec<u8>,
+    written: u32,
+    entries: Vec<Entry>,
+    current: Option<(DeflateEncoder<Vec<u8>>, Crc)>,
+}
+
+impl ZipWriter {
+    /// Date and time fields of every entry: 1 January 1980, the earliest a zip can record.
+    const DOS_TIME: u16 = 0;
+    const DOS_DATE: u16 = (1 << 5) | 1;
+    /// Sizes and checksum are in a descriptor after the data.
+    const FLAGS: u16 = 0x0008;
+    const DEFLATE: u16 = 8;
+
+    fn add(&mut self, name: &str, data: &[u8]) {
+        self.start(name);
+        self.write(data);
+        self.end();
+    }
+
+    fn start(&mut self, name: &str) {
+        self.end();
+        self.entries.push(Entry { name: name.to_string(), crc: 0, compressed: 0, size: 0, offset: self.position() });
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        header.extend_from_slice(&20u16.to_le_bytes());
+        header.extend_from_slice(&Self::FLAGS.to_le_bytes());
+        header.extend_from_slice(&Self::DEFLATE.to_le_bytes());
+        header.extend_from_slice(&Self::DOS_TIME.to_le_bytes());
+        header.extend_from_slice(&Self::DOS_DATE.to_le_bytes());
+        header.extend_from_slice(&[0; 12]);
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(name.as_bytes());
+        self.emit(&header);
+        self.current = Some((DeflateEncoder::new(Vec::new(), Compression::default()), Crc::new()));
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        let Some((encoder, crc)) = self.current.as_mut() else { return };
+        crc.update(data);
+        encoder.write_all(data).expect("compressing into memory cannot fail");
+        let compressed = std::mem::take(encoder.get_mut());
+        self.emit(&compressed);
+        if let Some(entry) = self.entries.last_mut() {
+            entry.compressed += compressed.len() as u32;
+        }
+    }
+
+    fn end(&mut self) {
+        let Some((encoder, crc)) = self.current.take() else { return };
+        let rest = encoder.finish().expect("compressing into memory cannot fail");
+        self.emit(&rest);
+        let Some(entry) = self.entries.last_mut() else { return };
+        entry.compressed += rest.len() as u32;
+        entry.crc = crc.sum();
+        entry.size = crc.amount();
+        let mut descriptor = Vec::with_capacity(16);
+        descriptor.extend_from_slice(&0x0807_4b50u32.to_le_bytes());
+        descriptor.extend_from_slice(&entry.crc.to_le_bytes());
+        descriptor.extend_from_slice(&entry.compressed.to_le_bytes());
+        descriptor.extend_from_slice(&entry.size.to_le_bytes());
+        self.emit(&descriptor);
+    }
+
+    fn finish(&mut self) {
+        self.end();
+        let directory_offset = self.position();
+        let mut directory = Vec::new();
+        for entry in &self.entries {
+            directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            directory.extend_from_slice(&20u16.to_le_bytes());
+            directory.extend_from_slice(&20u16.to_le_bytes());
+            directory.extend_from_slice(&Self::FLAGS.to_le_bytes());
+            directory.extend_from_slice(&Self::DEFLATE.to_le_bytes());
+            directory.extend_from_slice(&Self::DOS_TIME.to_le_bytes());
+            directory.extend_from_slice(&Self::DOS_DATE.to_le_bytes());
+            directory.extend_from_slice(&entry.crc.to_le_bytes());
+            directory.extend_from_slice(&entry.compressed.to_le_bytes());
+            directory.extend_from_slice(&entry.size.to_le_bytes());
+            directory.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            directory.extend_from_slice(&[0; 12]);
+            directory.extend_from_slice(&entry.offset.to_le_bytes());
+            directory.extend_from_slice(entry.name.as_bytes());
+        }
+        let count = (self.entries.len() as u16).to_le_bytes();
+        let mut end = Vec::with_capacity(22);
+        end.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        end.extend_from_slice(&[0; 4]);
+        end.extend_from_slice(&count);
+        end.extend_from_slice(&count);
+        end.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        end.extend_from_slice(&directory_offset.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes());
+        self.emit(&directory);
+        self.emit(&end);
+    }
+
+    fn position(&self) -> u32 {
+        self.written
+    }
+
+    fn emit(&mut self, bytes: &[u8]) {
+        self.out.extend_from_slice(bytes);
+        self.written += bytes.len() as u32;
+    }
+}
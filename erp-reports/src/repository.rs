@@ -65,7 +65,7 @@ impl ReportDefinitionRepository for SqliteReportDefinitionRepository {
     
     async fn create(&self, pool: &SqlitePool, report: ReportDefinition) -> Result<ReportDefinition> {
         sqlx::query(
-            "INSERT INTO report_definitions (id, name, code, category, description, data_source, query_template, parameters, columns, default_format, allowed_formats, is_scheduled, status, created_by, version, created_at, updated_at)
+            "INSERT INTO report_definitions (id, name, code, category, description, data_source, query, parameters, columns, default_format, allowed_formats, is_scheduled, status, created_by, version, created_at, updated_at)
              VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(report.base.id.to_string())
@@ -74,7 +74,7 @@ impl ReportDefinitionRepository for SqliteReportDefinitionRepository {
         .bind(format!("{:?}", report.category))
         .bind(&report.description)
         .bind(&report.data_source)
-        .bind(&report.query_template)
+        .bind(serde_json::to_string(&report.query).unwrap_or_default())
         .bind(serde_json::to_string(&report.parameters).unwrap_or_default())
         .bind(serde_json::to_string(&report.columns).unwrap_or_default())
         .bind(format!("{:?}", report.default_format))
@@ -94,10 +94,11 @@ impl ReportDefinitionRepository for SqliteReportDefinitionRepository {
     
     async fn update(&self, pool: &SqlitePool, report: ReportDefinition) -> Result<ReportDefinition> {
         sqlx::query(
-            "UPDATE report_definitions SET name = ?, query_template = ?, parameters = ?, columns = ?, status = ?, version = version + 1, updated_at = ? WHERE id = ?"
+            "UPDATE report_definitions SET name = ?, data_source = ?, query = ?, parameters = ?, columns = ?, status = ?, version = version + 1, updated_at = ? WHERE id = ?"
         )
         .bind(&report.name)
-        .bind(&report.query_template)
+        .bind(&report.data_source)
+        .bind(serde_json::to_string(&report.query).unwrap_or_default())
         .bind(serde_json::to_string(&report.parameters).unwrap_or_default())
         .bind(serde_json::to_string(&report.columns).unwrap_or_default())
         .bind(format!("{:?}", report.status))
@@ -119,7 +120,7 @@ struct ReportDefinitionRow {
     category: String,
     description: Option<String>,
     data_source: String,
-    query_template: String,
+    query: String,
     parameters: String,
     columns: String,
     default_format: String,
@@ -161,7 +162,7 @@ impl From<ReportDefinitionRow> for ReportDefinition {
             },
             description: r.description,
             data_source: r.data_source,
-            query_template: r.query_template,
+            query: serde_json::from_str(&r.query).unwrap_or_default(),
             parameters: serde_json::from_str(&r.parameters).unwrap_or_default(),
             columns: serde_json::from_str(&r.columns).unwrap_or_default(),
             default_format: match r.default_format.as_str() {
@@ -369,6 +370,8 @@ pub trait ReportExecutionRepository: Send + Sync {
     async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<ReportExecution>;
     async fn create(&self, pool: &SqlitePool, execution: ReportExecution) -> Result<ReportExecution>;
     async fn update_status(&self, pool: &SqlitePool, id: Uuid, status: ReportStatus, file_path: Option<&str>, row_count: i64, error: Option<&str>) -> Result<()>;
+    async fn record_output(&self, pool: &SqlitePool, id: Uuid, output: &ReportOutput) -> Result<()>;
+    async fn find_by_executor(&self, pool: &SqlitePool, executed_by: Uuid, limit: i64) -> Result<Vec<ReportExecution>>;
 }
 
 pub struct SqliteReportExecutionRepository;
@@ -390,8 +393,8 @@ impl ReportExecutionRepository for SqliteReportExecutionRepository {
     
     async fn create(&self, pool: &SqlitePool, execution: ReportExecution) -> Result<ReportExecution> {
         sqlx::query(
-            "INSERT INTO report_executions (id, report_definition_id, schedule_id, parameters, format, status, started_at, completed_at, duration_ms, row_count, file_path, file_size_bytes, error_message, delivery_status, delivered_at, executed_by, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO report_executions (id, report_definition_id, schedule_id, parameters, format, status, started_at, completed_at, duration_ms, row_count, file_path, file_size_bytes, checksum, error_message, delivery_status, delivered_at, executed_by, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(execution.base.id.to_string())
         .bind(execution.report_definition_id.to_string())
@@ -405,6 +408,7 @@ impl ReportExecutionRepository for SqliteReportExecutionRepository {
         .bind(execution.row_count)
         .bind(&execution.file_path)
         .bind(execution.file_size_bytes)
+        .bind(&execution.checksum)
         .bind(&execution.error_message)
         .bind(&execution.delivery_status)
         .bind(execution.delivered_at.map(|d| d.to_rfc3339()))
@@ -438,6 +442,40 @@ impl ReportExecutionRepository for SqliteReportExecutionRepository {
         
         Ok(())
     }
+
+    async fn record_output(&self, pool: &SqlitePool, id: Uuid, output: &ReportOutput) -> Result<()> {
+        let now = chrono::Utc::now();
+        sqlx::query(
+            "UPDATE report_executions SET status = ?, file_path = ?, file_size_bytes = ?, checksum = ?, row_count = ?, duration_ms = ?, completed_at = ?, updated_at = ? WHERE id = ?"
+        )
+        .bind(format!("{:?}", ReportStatus::Completed))
+        .bind(&output.file_path)
+        .bind(output.file_size_bytes)
+        .bind(&output.checksum)
+        .bind(output.row_count)
+        .bind(output.duration_ms)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .bind(id.to_string())
+        .execute(pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(())
+    }
+
+    async fn find_by_executor(&self, pool: &SqlitePool, executed_by: Uuid, limit: i64) -> Result<Vec<ReportExecution>> {
+        let rows = sqlx::query_as::<_, ReportExecutionRow>(
+            "SELECT * FROM report_executions WHERE executed_by = ? ORDER BY created_at DESC LIMIT ?"
+        )
+        .bind(executed_by.to_string())
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
 }
 
 #[derive(sqlx::FromRow)]
@@ -454,6 +492,7 @@ struct ReportExecutionRow {
     row_count: i64,
     file_path: Option<String>,
     file_size_bytes: Option<i64>,
+    checksum: Option<String>,
     error_message: Option<String>,
     delivery_status: Option<String>,
     delivered_at: Option<String>,
@@ -501,6 +540,7 @@ impl From<ReportExecutionRow> for ReportExecution {
             row_count: r.row_count,
             file_path: r.file_path,
             file_size_bytes: r.file_size_bytes,
+            checksum: r.checksum,
             error_message: r.error_message,
             delivery_status: r.delivery_status,
             delivered_at: r.delivered_at.and_then(|d| chrono::DateTime::parse_from_rfc3339(&d).ok())
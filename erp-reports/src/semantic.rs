@@ -0,0 +1,238 @@
+//! The entities, fields and joins reports may be built from. Queries only ever reference names
+//! from this catalogue, so no identifier in a generated statement comes from user input.
+
+use crate::models::ColumnDataType;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct Entity {
+    /// Also the name of the table the entity is read from.
+    pub name: &'static str,
+    pub label: &'static str,
+    /// Permission needed to report on the entity, on its own or through a join.
+    pub permission: &'static str,
+    pub fields: &'static [Field],
+    pub joins: &'static [Join],
+    /// An entity whose rows are only visible through a parent's: the parent's name and the
+    /// column holding its id.
+    #[serde(skip)]
+    pub parent: Option<(&'static str, &'static str)>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Field {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub data_type: ColumnDataType,
+}
+
+/// A many-to-one relation. Its fields are referenced as `join.field`.
+#[derive(Debug, Serialize)]
+pub struct Join {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub entity: &'static str,
+    pub foreign_key: &'static str,
+}
+
+impl Entity {
+    pub fn field(&self, name: &str) -> Option<&'static Field> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    pub fn join(&self, name: &str) -> Option<&'static Join> {
+        self.joins.iter().find(|j| j.name == name)
+    }
+}
+
+pub fn entity(name: &str) -> Option<&'static Entity> {
+    ENTITIES.iter().find(|e| e.name == name)
+}
+
+const fn field(name: &'static str, label: &'static str, data_type: ColumnDataType) -> Field {
+    Field { name, label, data_type }
+}
+
+const fn join(name: &'static str, label: &'static str, entity: &'static str, foreign_key: &'static str) -> Join {
+    Join { name, label, entity, foreign_key }
+}
+
+use ColumnDataType::{Currency, Date, DateTime, Integer, Percentage, String as Text};
+
+pub static ENTITIES: [Entity; 10] = [
+    Entity {
+        name: "customers",
+        label: "Customers",
+        permission: "sales:customers:read",
+        fields: &[
+            field("code", "Code", Text),
+            field("name", "Name", Text),
+            field("email", "Email", Text),
+            field("phone", "Phone", Text),
+            field("billing_city", "City", Text),
+            field("billing_state", "State", Text),
+            field("billing_country", "Country", Text),
+            field("credit_limit", "Credit Limit", Currency),
+            field("payment_terms", "Payment Terms (Days)", Integer),
+            field("status", "Status", Text),
+            field("created_at", "Created", DateTime),
+        ],
+        joins: &[],
+        parent: None,
+    },
+    Entity {
+        name: "sales_orders",
+        label: "Sales Orders",
+        permission: "sales:orders:read",
+        fields: &[
+            field("order_number", "Order Number", Text),
+            field("order_date", "Order Date", Date),
+            field("required_date", "Required Date", Date),
+            field("subtotal", "Subtotal", Currency),
+            field("tax_amount", "Tax", Currency),
+            field("total", "Total", Currency),
+            field("status", "Status", Text),
+            field("created_at", "Created", DateTime),
+        ],
+        joins: &[join("customer", "Customer", "customers", "customer_id")],
+        parent: None,
+    },
+    Entity {
+        name: "sales_order_lines",
+        label: "Sales Order Lines",
+        permission: "sales:orders:read",
+        fields: &[
+            field("description", "Description", Text),
+            field("quantity", "Quantity", Integer),
+            field("unit_price", "Unit Price", Currency),
+            field("discount_percent", "Discount %", Percentage),
+            field("tax_rate", "Tax Rate %", Percentage),
+            field("line_total", "Line Total", Currency),
+        ],
+        joins: &[
+            join("order", "Order", "sales_orders", "sales_order_id"),
+            join("product", "Product", "products", "product_id"),
+        ],
+        parent: Some(("sales_orders", "sales_order_id")),
+    },
+    Entity {
+        name: "products",
+        label: "Products",
+        permission: "inventory:products:read",
+        fields: &[
+            field("sku", "SKU", Text),
+            field("name", "Name", Text),
+            field("description", "Description", Text),
+            field("product_type", "Type", Text),
+            field("unit_of_measure", "Unit", Text),
+            field("status", "Status", Text),
+        ],
+        joins: &[],
+        parent: None,
+    },
+    Entity {
+        name: "vendors",
+        label: "Vendors",
+        permission: "purchasing:vendors:read",
+        fields: &[
+            field("code", "Code", Text),
+            field("name", "Name", Text),
+            field("email", "Email", Text),
+            field("phone", "Phone", Text),
+            field("city", "City", Text),
+            field("state", "State", Text),
+            field("country", "Country", Text),
+            field("payment_terms", "Payment Terms (Days)", Integer),
+            field("status", "Status", Text),
+        ],
+        joins: &[],
+        parent: None,
+    },
+    Entity {
+        name: "purchase_orders",
+        label: "Purchase Orders",
+        permission: "purchasing:orders:read",
+        fields: &[
+            field("po_number", "PO Number", Text),
+            field("order_date", "Order Date", Date),
+            field("expected_date", "Expected Date", Date),
+            field("subtotal", "Subtotal", Currency),
+            field("tax_amount", "Tax", Currency),
+            field("total", "Total", Currency),
+            field("currency", "Currency", Text),
+            field("status", "Status", Text),
+        ],
+        joins: &[join("vendor", "Vendor", "vendors", "vendor_id")],
+        parent: None,
+    },
+    Entity {
+        name: "invoices",
+        label: "Invoices",
+        permission: "sales:invoices:read",
+        fields: &[
+            field("invoice_number", "Invoice Number", Text),
+            field("invoice_date", "Invoice Date", Date),
+            field("due_date", "Due Date", Date),
+            field("subtotal", "Subtotal", Currency),
+            field("tax_amount", "Tax", Currency),
+            field("total", "Total", Currency),
+            field("amount_paid", "Amount Paid", Currency),
+            field("currency", "Currency", Text),
+            field("status", "Status", Text),
+        ],
+        joins: &[
+            join("customer", "Customer", "customers", "customer_id"),
+            join("order", "Order", "sales_orders", "sales_order_id"),
+        ],
+        parent: None,
+    },
+    Entity {
+        name: "employees",
+        label: "Employees",
+        permission: "hr:employees:read",
+        fields: &[
+            field("employee_number", "Employee Number", Text),
+            field("first_name", "First Name", Text),
+            field("last_name", "Last Name", Text),
+            field("email", "Email", Text),
+            field("phone", "Phone", Text),
+            field("city", "City", Text),
+            field("country", "Country", Text),
+            field("hire_date", "Hire Date", Date),
+            field("termination_date", "Termination Date", Date),
+            field("status", "Status", Text),
+        ],
+        joins: &[
+            join("department", "Department", "departments", "department_id"),
+            join("manager", "Manager", "employees", "manager_id"),
+        ],
+        parent: None,
+    },
+    Entity {
+        name: "departments",
+        label: "Departments",
+        permission: "hr:employees:read",
+        fields: &[
+            field("code", "Code", Text),
+            field("name", "Name", Text),
+            field("status", "Status", Text),
+        ],
+        joins: &[],
+        parent: None,
+    },
+    Entity {
+        name: "expense_reports",
+        label: "Expense Reports",
+        permission: "hr:expenses:read",
+        fields: &[
+            field("report_number", "Report Number", Text),
+            field("title", "Title", Text),
+            field("total_amount", "Total", Currency),
+            field("status", "Status", Text),
+            field("submitted_at", "Submitted", DateTime),
+            field("approved_at", "Approved", DateTime),
+        ],
+        joins: &[join("employee", "Employee", "employees", "employee_id")],
+        parent: None,
+    },
+];
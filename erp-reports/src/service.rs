@@ -1,10 +1,21 @@
 use sqlx::SqlitePool;
 use uuid::Uuid;
 use chrono::Utc;
-use erp_core::{Error, Result, Pagination, Paginated, BaseEntity};
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use erp_core::blob::Blob;
+use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, BlobService, UploadPolicy};
 use crate::models::*;
+use crate::query::{self, CompiledQuery, DataAccess};
+use crate::render::{self, Cell, Totals};
 use crate::repository::*;
 
+/// Largest report output kept, in bytes.
+pub const MAX_REPORT_SIZE: u64 = 512 * 1024 * 1024;
+
+/// Rows rendered between writes to storage.
+const FLUSH_ROWS: i64 = 256;
+
 pub struct ReportDefinitionService { repo: SqliteReportDefinitionRepository }
 impl Default for ReportDefinitionService {
     fn default() -> Self {
@@ -31,9 +42,8 @@ impl ReportDefinitionService {
         if report.name.is_empty() || report.code.is_empty() {
             return Err(Error::validation("Report name and code are required"));
         }
-        if report.query_template.is_empty() {
-            return Err(Error::validation("Query template is required"));
-        }
+        report.columns = query::validate(&report.query, &report.parameters)?;
+        report.data_source = report.query.entity.clone();
         report.base = BaseEntity::new();
         report.status = erp_core::Status::Active;
         report.version = 1;
@@ -41,6 +51,8 @@ impl ReportDefinitionService {
     }
     
     pub async fn update(&self, pool: &SqlitePool, mut report: ReportDefinition) -> Result<ReportDefinition> {
+        report.columns = query::validate(&report.query, &report.parameters)?;
+        report.data_source = report.query.entity.clone();
         report.base.updated_at = Utc::now();
         self.repo.update(pool, report).await
     }
@@ -75,6 +87,39 @@ impl ReportScheduleService {
         self.repo.create(pool, schedule).await
     }
     
+    /// Runs every due schedule as the user who created it, so scheduled output never shows
+    /// more than its owner could see. A failed run is recorded on its execution and the
+    /// schedule still moves on to its next run.
+    pub async fn run_due(&self, pool: &SqlitePool, blobs: &BlobService, resolver: &dyn AccessResolver) -> Result<Vec<ReportExecution>> {
+        let executions = ReportExecutionService::new();
+        let mut completed = Vec::new();
+        for schedule in self.get_due(pool).await? {
+            match schedule.created_by {
+                None => tracing::warn!(schedule_id = %schedule.base.id, "Skipping report schedule without an owner"),
+                Some(owner) => {
+                    let result = async {
+                        let access = resolver.access_for(pool, owner).await?;
+                        let parameters = serde_json::from_str(&schedule.parameters)
+                            .map_err(|e| Error::validation(format!("Invalid schedule parameters: {}", e)))?;
+                        executions.run(pool, blobs, &access, RunReport {
+                            report_definition_id: schedule.report_definition_id,
+                            format: Some(schedule.output_format),
+                            parameters,
+                            schedule_id: Some(schedule.base.id),
+                            executed_by: Some(owner),
+                        }).await
+                    }.await;
+                    match result {
+                        Ok(execution) => completed.push(execution),
+                        Err(e) => tracing::warn!(schedule_id = %schedule.base.id, "Scheduled report failed: {}", e),
+                    }
+                }
+            }
+            self.mark_run(pool, schedule.base.id).await?;
+        }
+        Ok(completed)
+    }
+
     pub async fn mark_run(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
         let schedule = self.repo.find_by_id(pool, id).await?;
         let next_run = Self::calculate_next_run(&schedule.frequency, Utc::now());
@@ -111,7 +156,40 @@ impl ReportExecutionService {
         self.repo.find_by_id(pool, id).await
     }
     
-    pub async fn start(&self, pool: &SqlitePool, report_definition_id: Uuid, schedule_id: Option<Uuid>, parameters: &str, format: ReportFormat) -> Result<ReportExecution> {
+    pub async fn list_for_user(&self, pool: &SqlitePool, user_id: Uuid, limit: i64) -> Result<Vec<ReportExecution>> {
+        self.repo.find_by_executor(pool, user_id, limit).await
+    }
+
+    /// Runs a report for someone with the given access and stores its output. Queries that
+    /// the definition or the caller's access rule out are refused before anything is recorded.
+    pub async fn run(&self, pool: &SqlitePool, blobs: &BlobService, access: &DataAccess, request: RunReport) -> Result<ReportExecution> {
+        let report = ReportDefinitionService::new().get(pool, request.report_definition_id).await?;
+        if report.status != erp_core::Status::Active {
+            return Err(Error::business_rule("Report is not active"));
+        }
+        let format = request.format.unwrap_or(report.default_format);
+        if !report.allowed_formats.is_empty() && !report.allowed_formats.contains(&format) {
+            return Err(Error::validation(format!("Report {} cannot be produced as {:?}", report.code, format)));
+        }
+        let compiled = query::compile(&report.query, &report.parameters, &request.parameters, access)?;
+        render::renderer(format, &report.name, &compiled.columns)?;
+
+        let parameters = serde_json::Value::Object(request.parameters).to_string();
+        let execution = self.start(pool, report.base.id, request.schedule_id, &parameters, format, request.executed_by).await?;
+        let started = std::time::Instant::now();
+        match ReportGeneratorService::generate(pool, blobs, &report.name, &compiled, format).await {
+            Ok((blob, row_count)) => {
+                self.complete(pool, execution.base.id, &blob, row_count, started.elapsed().as_millis() as i64).await?;
+            }
+            Err(e) => {
+                self.fail(pool, execution.base.id, &e.to_string()).await?;
+                return Err(e);
+            }
+        }
+        self.get(pool, execution.base.id).await
+    }
+
+    pub async fn start(&self, pool: &SqlitePool, report_definition_id: Uuid, schedule_id: Option<Uuid>, parameters: &str, format: ReportFormat, executed_by: Option<Uuid>) -> Result<ReportExecution> {
         let execution = ReportExecution {
             base: BaseEntity::new(),
             report_definition_id,
@@ -125,17 +203,24 @@ impl ReportExecutionService {
             row_count: 0,
             file_path: None,
             file_size_bytes: None,
+            checksum: None,
             error_message: None,
             delivery_status: None,
             delivered_at: None,
-            executed_by: None,
+            executed_by,
         };
         
         self.repo.create(pool, execution).await
     }
     
-    pub async fn complete(&self, pool: &SqlitePool, id: Uuid, file_path: &str, row_count: i64) -> Result<()> {
-        self.repo.update_status(pool, id, ReportStatus::Completed, Some(file_path), row_count, None).await
+    pub async fn complete(&self, pool: &SqlitePool, id: Uuid, blob: &Blob, row_count: i64, duration_ms: i64) -> Result<()> {
+        self.repo.record_output(pool, id, &ReportOutput {
+            file_path: blob.key(),
+            file_size_bytes: blob.size,
+            checksum: blob.sha256.clone(),
+            row_count,
+            duration_ms,
+        }).await
     }
     
     pub async fn fail(&self, pool: &SqlitePool, id: Uuid, error: &str) -> Result<()> {
@@ -143,6 +228,21 @@ impl ReportExecutionService {
     }
 }
 
+pub struct RunReport {
+    pub report_definition_id: Uuid,
+    /// The definition's default format when not given.
+    pub format: Option<ReportFormat>,
+    pub parameters: serde_json::Map<String, serde_json::Value>,
+    pub schedule_id: Option<Uuid>,
+    pub executed_by: Option<Uuid>,
+}
+
+/// Works out what a user may see, for reports run on their behalf without a request.
+#[async_trait]
+pub trait AccessResolver: Send + Sync {
+    async fn access_for(&self, pool: &SqlitePool, user_id: Uuid) -> Result<DataAccess>;
+}
+
 pub struct ReportGeneratorService;
 impl Default for ReportGeneratorService {
     fn default() -> Self {
@@ -153,37 +253,40 @@ impl Default for ReportGeneratorService {
 impl ReportGeneratorService {
     pub fn new() -> Self { Self }
     
-    pub async fn generate_csv(
-        pool: &SqlitePool,
-        query: &str,
-        columns: &[ReportColumn],
-    ) -> Result<(String, i64)> {
-        let rows = sqlx::query(query)
-            .fetch_all(pool)
-            .await
-            .map_err(Error::Database)?;
-        
-        let row_count = rows.len() as i64;
-        
-        let mut csv = String::new();
-        csv.push_str(&columns.iter().filter(|c| c.is_visible).map(|c| c.label.as_str()).collect::<Vec<_>>().join(","));
-        csv.push('\n');
-        
-        Ok((csv, row_count))
-    }
-    
-    pub async fn generate_json(
+    /// Runs a compiled query and renders its rows into a stored blob, returning the blob and
+    /// the number of rows. Rows are streamed from the database and written out in batches.
+    pub async fn generate(
         pool: &SqlitePool,
-        query: &str,
-    ) -> Result<(String, i64)> {
-        let rows = sqlx::query(query)
-            .fetch_all(pool)
-            .await
-            .map_err(Error::Database)?;
-        
-        let row_count = rows.len() as i64;
-        
-        Ok((format!("{{\"row_count\": {}}}", row_count), row_count))
+        blobs: &BlobService,
+        title: &str,
+        compiled: &CompiledQuery,
+        format: ReportFormat,
+    ) -> Result<(Blob, i64)> {
+        let mut renderer = render::renderer(format, title, &compiled.columns)?;
+        let mut totals = Totals::new(&compiled.columns);
+        let mut writer = blobs.writer(MAX_REPORT_SIZE).await?;
+        let mut row_count = 0;
+
+        let mut rows = compiled.bind(sqlx::query(&compiled.sql)).fetch(pool);
+        while let Some(row) = rows.try_next().await? {
+            let cells = compiled.columns.iter().enumerate()
+                .map(|(i, column)| Cell::decode(&row, i, column.data_type))
+                .collect::<Result<Vec<_>>>()?;
+            totals.add(&cells);
+            renderer.row(&cells)?;
+            row_count += 1;
+            if row_count % FLUSH_ROWS == 0 {
+                writer.write(&renderer.take_output()).await?;
+            }
+        }
+        drop(rows);
+
+        renderer.finish(&totals)?;
+        writer.write(&renderer.take_output()).await?;
+        let staged = writer.finish().await?;
+        let policy = UploadPolicy { max_size: MAX_REPORT_SIZE, allowed_mime_types: vec![format.mime_type().to_string()] };
+        let blob = blobs.save(pool, staged, format.mime_type(), &policy, None).await?;
+        Ok((blob, row_count))
     }
 }
 
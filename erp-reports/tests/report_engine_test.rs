@@ -0,0 +1,331 @@
+use async_trait::async_trait;
+use erp_core::blob::LocalBlobStore;
+use erp_core::{BaseEntity, BlobService, Error, RowScope, Status};
+use erp_reports::*;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE blobs (sha256 TEXT PRIMARY KEY, size INTEGER NOT NULL, mime_type TEXT NOT NULL, backend TEXT NOT NULL, ref_count INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL)",
+    "CREATE TABLE customers (id TEXT PRIMARY KEY, code TEXT, name TEXT, email TEXT, phone TEXT, billing_city TEXT, billing_state TEXT, billing_country TEXT, credit_limit INTEGER, payment_terms INTEGER, status TEXT, created_at TEXT, created_by TEXT)",
+    "CREATE TABLE sales_orders (id TEXT PRIMARY KEY, order_number TEXT, customer_id TEXT, order_date TEXT, required_date TEXT, subtotal INTEGER, tax_amount INTEGER, total INTEGER, status TEXT, created_at TEXT, created_by TEXT)",
+    "CREATE TABLE products (id TEXT PRIMARY KEY, sku TEXT, name TEXT, description TEXT, product_type TEXT, unit_of_measure TEXT, status TEXT)",
+    "CREATE TABLE sales_order_lines (id TEXT PRIMARY KEY, sales_order_id TEXT, product_id TEXT, description TEXT, quantity INTEGER, unit_price INTEGER, discount_percent REAL, tax_rate REAL, line_total INTEGER)",
+    r#"CREATE TABLE report_definitions (id TEXT PRIMARY KEY, name TEXT NOT NULL, code TEXT NOT NULL UNIQUE, category TEXT NOT NULL, description TEXT,
+        data_source TEXT NOT NULL, query TEXT NOT NULL, parameters TEXT NOT NULL, columns TEXT NOT NULL, default_format TEXT NOT NULL,
+        allowed_formats TEXT NOT NULL, is_scheduled INTEGER NOT NULL, status TEXT NOT NULL, created_by TEXT, version INTEGER NOT NULL,
+        created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+    r#"CREATE TABLE report_executions (id TEXT PRIMARY KEY, report_definition_id TEXT NOT NULL, schedule_id TEXT, parameters TEXT NOT NULL,
+        format TEXT NOT NULL, status TEXT NOT NULL, started_at TEXT, completed_at TEXT, duration_ms INTEGER, row_count INTEGER NOT NULL,
+        file_path TEXT, file_size_bytes INTEGER, checksum TEXT, error_message TEXT, delivery_status TEXT, delivered_at TEXT,
+        executed_by TEXT, created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+    r#"CREATE TABLE report_schedules (id TEXT PRIMARY KEY, report_definition_id TEXT NOT NULL, name TEXT NOT NULL, frequency TEXT NOT NULL,
+        cron_expression TEXT, start_date TEXT NOT NULL, end_date TEXT, next_run_at TEXT, last_run_at TEXT, parameters TEXT NOT NULL,
+        output_format TEXT NOT NULL, delivery_methods TEXT NOT NULL, recipients TEXT NOT NULL, email_subject TEXT, email_body TEXT,
+        include_attachments INTEGER NOT NULL, ftp_host TEXT, ftp_path TEXT, webhook_url TEXT, is_active INTEGER NOT NULL,
+        status TEXT NOT NULL, created_by TEXT, created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+];
+
+async fn setup() -> (SqlitePool, BlobService) {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    for statement in SCHEMA {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    let customers = [("c1", "Acme", "alice"), ("c2", "Globex", "bob")];
+    for (id, name, owner) in customers {
+        sqlx::query("INSERT INTO customers (id, code, name, credit_limit, status, created_by) VALUES (?, ?, ?, 500000, 'Active', ?)")
+            .bind(id).bind(id.to_uppercase()).bind(name).bind(owner)
+            .execute(&pool).await.unwrap();
+    }
+    let orders = [
+        ("o1", "SO-1", "c1", "2026-01-05T10:00:00+00:00", 10_050, "Confirmed", "alice"),
+        ("o2", "SO-2", "c1", "2026-01-20", 20_000, "Confirmed", "alice"),
+        ("o3", "SO-3", "c2", "2026-02-01", 5_025, "Confirmed", "bob"),
+        ("o4", "SO-4", "c2", "2026-02-03", 99_900, "Draft", "bob"),
+    ];
+    for (id, number, customer, date, total, status, owner) in orders {
+        sqlx::query("INSERT INTO sales_orders (id, order_number, customer_id, order_date, subtotal, tax_amount, total, status, created_by) VALUES (?, ?, ?, ?, ?, 0, ?, ?, ?)")
+            .bind(id).bind(number).bind(customer).bind(date).bind(total).bind(total).bind(status).bind(owner)
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO sales_order_lines (id, sales_order_id, description, quantity, unit_price, discount_percent, line_total) VALUES (?, ?, 'Widget', 1, ?, 0, ?)")
+            .bind(format!("{}-l1", id)).bind(id).bind(total).bind(total)
+            .execute(&pool).await.unwrap();
+    }
+    let root = std::env::temp_dir().join(format!("erp-report-test-{}", Uuid::new_v4()));
+    let blobs = BlobService::new(Arc::new(LocalBlobStore::new(root.join("blobs"))), root.join("staging"));
+    (pool, blobs)
+}
+
+async fn read_blob(pool: &SqlitePool, blobs: &BlobService, sha256: &str) -> Vec<u8> {
+    let (_, mut stream) = blobs.open(pool, sha256).await.unwrap();
+    let mut out = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        out.extend_from_slice(&chunk.unwrap());
+    }
+    out
+}
+
+fn column(field: &str, aggregate: Option<AggregationType>, total: bool) -> QueryColumn {
+    QueryColumn { field: field.into(), label: None, aggregate, total }
+}
+
+fn sales_by_customer() -> ReportQuery {
+    ReportQuery {
+        entity: "sales_orders".into(),
+        columns: vec![
+            column("customer.name", None, false),
+            column("order_number", Some(AggregationType::Count), false),
+            column("total", Some(AggregationType::Sum), true),
+        ],
+        filters: vec![QueryFilter { field: "status".into(), op: FilterOp::Eq, value: None, param: Some("status".into()) }],
+        group_by: vec!["customer.name".into()],
+        sort: vec![QuerySort { field: "sum_total".into(), descending: true }],
+        limit: None,
+    }
+}
+
+fn status_parameter(required: bool) -> ReportParameter {
+    ReportParameter {
+        name: "status".into(),
+        label: "Status".into(),
+        param_type: ParameterType::String,
+        default_value: None,
+        is_required: required,
+        lookup_query: None,
+        validation_regex: None,
+        validation_message: None,
+    }
+}
+
+fn parameters(value: Value) -> serde_json::Map<String, Value> {
+    value.as_object().cloned().unwrap_or_default()
+}
+
+async fn render(pool: &SqlitePool, blobs: &BlobService, compiled: &CompiledQuery, format: ReportFormat) -> (Vec<u8>, i64) {
+    let (blob, rows) = ReportGeneratorService::generate(pool, blobs, "Sales by Customer", compiled, format).await.unwrap();
+    assert_eq!(blob.mime_type, format.mime_type());
+    (read_blob(pool, blobs, &blob.sha256).await, rows)
+}
+
+#[tokio::test]
+async fn groups_filters_and_totals_within_row_scope() {
+    let (pool, blobs) = setup().await;
+    let query = sales_by_customer();
+    let params = [status_parameter(true)];
+
+    let everyone = compile(&query, &params, &parameters(json!({ "status": "Confirmed" })), &DataAccess::unrestricted()).unwrap();
+    assert_eq!(
+        everyone.columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+        ["customer_name", "count_order_number", "sum_total"]
+    );
+    assert_eq!(everyone.columns[2].label, "Sum of Total");
+    let (json, rows) = render(&pool, &blobs, &everyone, ReportFormat::JSON).await;
+    let json: Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(rows, 2);
+    assert_eq!(json["rows"], json!([
+        { "customer_name": "Acme", "count_order_number": 2, "sum_total": 30050 },
+        { "customer_name": "Globex", "count_order_number": 1, "sum_total": 5025 },
+    ]));
+    assert_eq!(json["totals"], json!({ "sum_total": 35075 }));
+
+    // Bob only sees his own orders, and only customers he created through the join.
+    let bob = DataAccess::unrestricted()
+        .scope("sales_orders", RowScope::unrestricted().and("sales_orders.created_by = ?", vec!["bob".into()]))
+        .scope("customers", RowScope::unrestricted().and("customers.created_by = ?", vec!["bob".into()]));
+    let scoped = compile(&query, &params, &parameters(json!({ "status": "Confirmed" })), &bob).unwrap();
+    let (json, _) = render(&pool, &blobs, &scoped, ReportFormat::JSON).await;
+    let json: Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["rows"], json!([{ "customer_name": "Globex", "count_order_number": 1, "sum_total": 5025 }]));
+
+    // Order lines are visible only through orders the caller can see.
+    let lines = ReportQuery {
+        entity: "sales_order_lines".into(),
+        columns: vec![column("order.order_number", None, false), column("line_total", None, true)],
+        sort: vec![QuerySort { field: "order.order_number".into(), descending: false }],
+        ..Default::default()
+    };
+    let scoped = compile(&lines, &[], &Default::default(), &bob).unwrap();
+    let (csv, rows) = render(&pool, &blobs, &scoped, ReportFormat::CSV).await;
+    assert_eq!(rows, 2);
+    assert_eq!(String::from_utf8(csv).unwrap(), "Order Order Number,Line Total\r\nSO-3,50.25\r\nSO-4,999.00\r\n");
+}
+
+#[tokio::test]
+async fn rejects_queries_outside_the_model_or_the_callers_access() {
+    let query = sales_by_customer();
+    let params = [status_parameter(true)];
+    let confirmed = parameters(json!({ "status": "Confirmed" }));
+
+    let denied = DataAccess::unrestricted().deny("customers");
+    assert!(matches!(compile(&query, &params, &confirmed, &denied), Err(Error::Forbidden(_))));
+    let hidden = DataAccess::unrestricted().hide("sales_orders", "total");
+    assert!(matches!(compile(&query, &params, &confirmed, &hidden), Err(Error::Forbidden(_))));
+
+    let missing = compile(&query, &params, &Default::default(), &DataAccess::unrestricted());
+    assert!(matches!(missing, Err(Error::Validation(m)) if m.contains("Status is required")));
+    let optional = compile(&query, &[status_parameter(false)], &Default::default(), &DataAccess::unrestricted()).unwrap();
+    assert!(!optional.sql.contains("\"status\" ="), "optional parameters without a value drop their filter");
+
+    let mut injected = query.clone();
+    injected.columns[0].field = "name; DROP TABLE customers".into();
+    assert!(matches!(compile(&injected, &params, &confirmed, &DataAccess::unrestricted()), Err(Error::Validation(_))));
+
+    let mut ungrouped = query.clone();
+    ungrouped.columns.push(column("order_number", None, false));
+    assert!(matches!(compile(&ungrouped, &params, &confirmed, &DataAccess::unrestricted()), Err(Error::Validation(m)) if m.contains("grouped")));
+
+    let mut bad_value = query.clone();
+    bad_value.filters = vec![QueryFilter { field: "order_date".into(), op: FilterOp::Gte, value: Some(json!("last week")), param: None }];
+    assert!(matches!(compile(&bad_value, &params, &confirmed, &DataAccess::unrestricted()), Err(Error::Validation(_))));
+}
+
+#[tokio::test]
+async fn writes_typed_xlsx_and_paginated_pdf() {
+    let (pool, blobs) = setup().await;
+    for i in 0..120 {
+        sqlx::query("INSERT INTO sales_orders (id, order_number, customer_id, order_date, total, status, created_by) VALUES (?, ?, 'c1', '2026-03-01', 100, 'Confirmed', 'alice')")
+            .bind(format!("bulk-{}", i)).bind(format!("SO-B{:03}", i))
+            .execute(&pool).await.unwrap();
+    }
+    let query = ReportQuery {
+        entity: "sales_orders".into(),
+        columns: vec![
+            column("order_number", None, false),
+            column("order_date", None, false),
+            column("customer.name", None, false),
+            column("status", None, false),
+            column("tax_amount", None, false),
+            column("total", None, true),
+        ],
+        filters: vec![QueryFilter { field: "order_date".into(), op: FilterOp::Gte, value: Some(json!("2026-01-05")), param: None }],
+        sort: vec![QuerySort { field: "order_number".into(), descending: false }],
+        ..Default::default()
+    };
+    let compiled = compile(&query, &[], &Default::default(), &DataAccess::unrestricted()).unwrap();
+
+    let (xlsx, rows) = render(&pool, &blobs, &compiled, ReportFormat::Excel).await;
+    assert_eq!(rows, 124);
+    assert!(xlsx.starts_with(b"PK\x03\x04"));
+    let end = xlsx.len() - 22;
+    assert_eq!(&xlsx[end..end + 4], b"PK\x05\x06");
+    assert_eq!(u16::from_le_bytes([xlsx[end + 10], xlsx[end + 11]]), 6, "six parts in the package");
+    let sheet = String::from_utf8_lossy(&xlsx);
+    assert!(sheet.contains("xl/worksheets/sheet1.xml"));
+
+    let (pdf, _) = render(&pool, &blobs, &compiled, ReportFormat::PDF).await;
+    let text = String::from_utf8_lossy(&pdf);
+    assert!(text.starts_with("%PDF-1.4"));
+    assert!(text.trim_end().ends_with("%%EOF"));
+    assert!(text.contains("/MediaBox [0 0 842 595]"), "wide reports are landscape");
+    assert!(text.contains("/Count 4 "), "124 rows and a totals row fill four pages");
+}
+
+fn definition(query: ReportQuery) -> ReportDefinition {
+    ReportDefinition {
+        base: BaseEntity::new(),
+        name: "Sales by Customer".into(),
+        code: "SALES-BY-CUSTOMER".into(),
+        category: ReportCategory::Sales,
+        description: None,
+        data_source: String::new(),
+        query,
+        parameters: vec![status_parameter(true)],
+        columns: vec![],
+        default_format: ReportFormat::CSV,
+        allowed_formats: vec![ReportFormat::CSV, ReportFormat::JSON],
+        is_scheduled: true,
+        status: Status::Active,
+        created_by: None,
+        version: 1,
+    }
+}
+
+struct FixedAccess(DataAccess);
+
+#[async_trait]
+impl AccessResolver for FixedAccess {
+    async fn access_for(&self, _pool: &SqlitePool, _user_id: Uuid) -> erp_core::Result<DataAccess> {
+        Ok(self.0.clone())
+    }
+}
+
+#[tokio::test]
+async fn runs_definitions_on_demand_and_on_schedule() {
+    let (pool, blobs) = setup().await;
+    let definitions = ReportDefinitionService::new();
+    let mut invalid = definition(sales_by_customer());
+    invalid.query.entity = "users".into();
+    assert!(matches!(definitions.create(&pool, invalid).await, Err(Error::Validation(_))));
+    let report = definitions.create(&pool, definition(sales_by_customer())).await.unwrap();
+    assert_eq!(report.data_source, "sales_orders");
+    assert_eq!(report.columns.len(), 3);
+
+    let executions = ReportExecutionService::new();
+    let user = Uuid::new_v4();
+    let run = |format: Option<ReportFormat>, params: Value| RunReport {
+        report_definition_id: report.base.id,
+        format,
+        parameters: parameters(params),
+        schedule_id: None,
+        executed_by: Some(user),
+    };
+    let execution = executions.run(&pool, &blobs, &DataAccess::unrestricted(), run(None, json!({ "status": "Confirmed" }))).await.unwrap();
+    assert!(matches!(execution.status, ReportStatus::Completed));
+    assert_eq!(execution.row_count, 2);
+    assert_eq!(execution.executed_by, Some(user));
+    let checksum = execution.checksum.clone().unwrap();
+    let csv = read_blob(&pool, &blobs, &checksum).await;
+    assert_eq!(execution.file_size_bytes, Some(csv.len() as i64));
+    assert!(String::from_utf8(csv).unwrap().starts_with("Customer Name,Count of Order Number,Sum of Total\r\nAcme,2,300.50\r\n"));
+
+    assert!(matches!(
+        executions.run(&pool, &blobs, &DataAccess::unrestricted(), run(Some(ReportFormat::PDF), json!({ "status": "Confirmed" }))).await,
+        Err(Error::Validation(_))
+    ));
+    assert!(matches!(
+        executions.run(&pool, &blobs, &DataAccess::unrestricted().deny("sales_orders"), run(None, json!({ "status": "Confirmed" }))).await,
+        Err(Error::Forbidden(_))
+    ));
+    let recorded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM report_executions").fetch_one(&pool).await.unwrap();
+    assert_eq!(recorded, 1, "refused runs leave no execution behind");
+
+    let schedules = ReportScheduleService::new();
+    let schedule = schedules.create(&pool, ReportSchedule {
+        base: BaseEntity::new(),
+        report_definition_id: report.base.id,
+        name: "Nightly".into(),
+        frequency: ScheduleFrequency::Daily,
+        cron_expression: None,
+        start_date: chrono::Utc::now() - chrono::Duration::days(2),
+        end_date: None,
+        next_run_at: None,
+        last_run_at: None,
+        parameters: json!({ "status": "Confirmed" }).to_string(),
+        output_format: ReportFormat::JSON,
+        delivery_methods: vec![DeliveryMethod::Download],
+        recipients: vec![],
+        email_subject: None,
+        email_body: None,
+        include_attachments: false,
+        ftp_host: None,
+        ftp_path: None,
+        webhook_url: None,
+        is_active: true,
+        status: Status::Active,
+        created_by: Some(user),
+    }).await.unwrap();
+
+    let scoped = FixedAccess(DataAccess::unrestricted()
+        .scope("sales_orders", RowScope::unrestricted().and("sales_orders.created_by = ?", vec!["alice".into()])));
+    let ran = schedules.run_due(&pool, &blobs, &scoped).await.unwrap();
+    assert_eq!(ran.len(), 1);
+    assert_eq!(ran[0].schedule_id, Some(schedule.base.id));
+    assert_eq!(ran[0].executed_by, Some(user));
+    let json: Value = serde_json::from_slice(&read_blob(&pool, &blobs, ran[0].checksum.as_ref().unwrap()).await).unwrap();
+    assert_eq!(json["rows"], json!([{ "customer_name": "Acme", "count_order_number": 2, "sum_total": 30050 }]));
+
+    assert!(schedules.run_due(&pool, &blobs, &scoped).await.unwrap().is_empty(), "the schedule moved on to its next run");
+}
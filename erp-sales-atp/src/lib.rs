@@ -42,7 +42,7 @@ impl AtpEngine {
     pub fn new(events: Vec<InventoryEvent>) -> Self {
         let mut sorted_events = events;
         // Sort events by date to calculate cumulative availability
-        sorted_events.sort_by(|a, b| a.date.cmp(&b.date));
+        sorted_events.sort_by_key(|e| e.date);
         Self { events: sorted_events }
     }
 
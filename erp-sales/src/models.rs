@@ -86,6 +86,8 @@ pub struct Invoice {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InvoiceLine {
     pub id: Uuid,
+    #[serde(default)]
+    pub sales_order_line_id: Option<Uuid>,
     pub product_id: Uuid,
     pub description: String,
     pub quantity: i64,
@@ -114,6 +116,77 @@ pub enum PaymentMethod {
     BankTransfer,
 }
 
+/// Quantity of a sales order line to bill on an invoice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceLineRequest {
+    pub sales_order_line_id: Uuid,
+    pub quantity: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentAllocationRequest {
+    pub invoice_id: Uuid,
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentAllocation {
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    pub invoice_id: Uuid,
+    pub amount: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A receipt and how it was spread over the customer's invoices. Whatever could not be applied
+/// stays on account as `unapplied_amount`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CashApplication {
+    pub payment: Payment,
+    pub allocations: Vec<PaymentAllocation>,
+    pub unapplied_amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenItem {
+    pub invoice_id: Uuid,
+    pub invoice_number: String,
+    pub invoice_date: DateTime<Utc>,
+    pub due_date: DateTime<Utc>,
+    pub total: i64,
+    pub amount_paid: i64,
+    pub balance: i64,
+    pub days_overdue: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatementLine {
+    pub date: DateTime<Utc>,
+    pub document_type: String,
+    pub document_number: String,
+    pub debit: i64,
+    pub credit: i64,
+    pub balance: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerStatement {
+    pub customer_id: Uuid,
+    pub customer_name: String,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub opening_balance: i64,
+    pub lines: Vec<StatementLine>,
+    pub closing_balance: i64,
+    pub open_items: Vec<OpenItem>,
+    pub unapplied_credit: i64,
+    pub current: i64,
+    pub days_1_30: i64,
+    pub days_31_60: i64,
+    pub days_61_90: i64,
+    pub over_90: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Lead {
     pub id: Uuid,
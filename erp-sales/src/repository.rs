@@ -1,8 +1,8 @@
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use chrono::Utc;
-use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status, Money, Currency, Address, ContactInfo};
+use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status, Money, Currency, Address, ContactInfo, RowScope};
 use crate::models::*;
 
 #[derive(sqlx::FromRow)]
@@ -17,6 +17,7 @@ struct CustomerRow {
     status: String,
     created_at: String,
     updated_at: String,
+    created_by: Option<String>,
 }
 
 impl CustomerRow {
@@ -28,7 +29,7 @@ impl CustomerRow {
                     .map(|d| d.with_timezone(&Utc)).unwrap_or_else(|_| Utc::now()),
                 updated_at: chrono::DateTime::parse_from_rfc3339(&self.updated_at)
                     .map(|d| d.with_timezone(&Utc)).unwrap_or_else(|_| Utc::now()),
-                created_by: None,
+                created_by: self.created_by.and_then(|s| Uuid::parse_str(&s).ok()),
                 updated_by: None,
             },
             code: self.code,
@@ -48,20 +49,29 @@ pub struct SqliteCustomerRepository;
 #[async_trait]
 impl CustomerRepository for SqliteCustomerRepository {
     async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<Customer> {
-        let row = sqlx::query_as::<_, CustomerRow>(
-            "SELECT id, code, name, email, phone, credit_limit, payment_terms, status, created_at, updated_at
-             FROM customers WHERE id = ?"
-        ).bind(id.to_string()).fetch_optional(pool).await?
-        .ok_or_else(|| Error::not_found("Customer", &id.to_string()))?;
+        self.find_in_scope(pool, id, &RowScope::unrestricted()).await
+    }
+
+    async fn find_in_scope(&self, pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<Customer> {
+        let sql = format!(
+            "SELECT id, code, name, email, phone, credit_limit, payment_terms, status, created_at, updated_at, created_by
+             FROM customers WHERE id = ?{}", scope.sql()
+        );
+        let row = scope.bind_query_as(sqlx::query_as::<_, CustomerRow>(&sql).bind(id.to_string()))
+            .fetch_optional(pool).await?
+            .ok_or_else(|| Error::not_found("Customer", &id.to_string()))?;
         Ok(row.into_customer())
     }
 
-    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<Customer>> {
-        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM customers WHERE status != 'Deleted'").fetch_one(pool).await?;
-        let rows = sqlx::query_as::<_, CustomerRow>(
-            "SELECT id, code, name, email, phone, credit_limit, payment_terms, status, created_at, updated_at
-             FROM customers WHERE status != 'Deleted' ORDER BY code LIMIT ? OFFSET ?"
-        ).bind(pagination.limit() as i64).bind(pagination.offset() as i64).fetch_all(pool).await?;
+    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination, scope: &RowScope) -> Result<Paginated<Customer>> {
+        let count_sql = format!("SELECT COUNT(*) FROM customers WHERE status != 'Deleted'{}", scope.sql());
+        let count: (i64,) = scope.bind_query_as(sqlx::query_as(&count_sql)).fetch_one(pool).await?;
+        let sql = format!(
+            "SELECT id, code, name, email, phone, credit_limit, payment_terms, status, created_at, updated_at, created_by
+             FROM customers WHERE status != 'Deleted'{} ORDER BY code LIMIT ? OFFSET ?", scope.sql()
+        );
+        let rows = scope.bind_query_as(sqlx::query_as::<_, CustomerRow>(&sql))
+            .bind(pagination.limit() as i64).bind(pagination.offset() as i64).fetch_all(pool).await?;
         Ok(Paginated::new(rows.into_iter().map(|r| r.into_customer()).collect(), count.0 as u64, pagination))
     }
 
@@ -69,12 +79,13 @@ impl CustomerRepository for SqliteCustomerRepository {
         let now = Utc::now();
         let credit_limit = customer.credit_limit.as_ref().map(|m| m.amount);
         sqlx::query(
-            "INSERT INTO customers (id, code, name, email, phone, credit_limit, payment_terms, status, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO customers (id, code, name, email, phone, credit_limit, payment_terms, status, created_at, updated_at, created_by)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         ).bind(customer.base.id.to_string()).bind(&customer.code).bind(&customer.name)
         .bind(&customer.contact.email).bind(&customer.contact.phone)
         .bind(credit_limit).bind(customer.payment_terms as i64)
         .bind(format!("{:?}", customer.status)).bind(customer.base.created_at.to_rfc3339()).bind(now.to_rfc3339())
+        .bind(customer.base.created_by.map(|id| id.to_string()))
         .execute(pool).await?;
         Ok(customer)
     }
@@ -107,6 +118,7 @@ struct SalesOrderRow {
     status: String,
     created_at: String,
     updated_at: String,
+    created_by: Option<String>,
 }
 
 #[derive(sqlx::FromRow)]
@@ -122,16 +134,29 @@ struct SalesOrderLineRow {
     line_total: i64,
 }
 
+fn order_status(value: &str) -> Status {
+    match value {
+        "Confirmed" => Status::Approved,
+        other => other.parse().unwrap_or(Status::Draft),
+    }
+}
+
 pub struct SqliteSalesOrderRepository;
 
 #[async_trait]
 impl SalesOrderRepository for SqliteSalesOrderRepository {
     async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<SalesOrder> {
-        let row = sqlx::query_as::<_, SalesOrderRow>(
-            "SELECT id, order_number, customer_id, order_date, subtotal, tax_amount, total, status, created_at, updated_at
-             FROM sales_orders WHERE id = ?"
-        ).bind(id.to_string()).fetch_optional(pool).await?
-        .ok_or_else(|| Error::not_found("SalesOrder", &id.to_string()))?;
+        self.find_in_scope(pool, id, &RowScope::unrestricted()).await
+    }
+
+    async fn find_in_scope(&self, pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<SalesOrder> {
+        let sql = format!(
+            "SELECT id, order_number, customer_id, order_date, subtotal, tax_amount, total, status, created_at, updated_at, created_by
+             FROM sales_orders WHERE id = ?{}", scope.sql()
+        );
+        let row = scope.bind_query_as(sqlx::query_as::<_, SalesOrderRow>(&sql).bind(id.to_string()))
+            .fetch_optional(pool).await?
+            .ok_or_else(|| Error::not_found("SalesOrder", &id.to_string()))?;
         
         let lines = sqlx::query_as::<_, SalesOrderLineRow>(
             "SELECT id, sales_order_id, product_id, description, quantity, unit_price, discount_percent, line_total
@@ -143,7 +168,7 @@ impl SalesOrderRepository for SqliteSalesOrderRepository {
                 id: Uuid::parse_str(&row.id).unwrap_or_default(),
                 created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at).map(|d| d.with_timezone(&Utc)).unwrap_or_else(|_| Utc::now()),
                 updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at).map(|d| d.with_timezone(&Utc)).unwrap_or_else(|_| Utc::now()),
-                created_by: None, updated_by: None,
+                created_by: row.created_by.and_then(|s| Uuid::parse_str(&s).ok()), updated_by: None,
             },
             order_number: row.order_number,
             customer_id: Uuid::parse_str(&row.customer_id).unwrap_or_default(),
@@ -162,16 +187,19 @@ impl SalesOrderRepository for SqliteSalesOrderRepository {
             subtotal: Money::new(row.subtotal, Currency::USD),
             tax_amount: Money::new(row.tax_amount, Currency::USD),
             total: Money::new(row.total, Currency::USD),
-            status: match row.status.as_str() { "Confirmed" => Status::Approved, "Completed" => Status::Completed, _ => Status::Draft },
+            status: order_status(&row.status),
         })
     }
 
-    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<SalesOrder>> {
-        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sales_orders").fetch_one(pool).await?;
-        let rows = sqlx::query_as::<_, SalesOrderRow>(
-            "SELECT id, order_number, customer_id, order_date, subtotal, tax_amount, total, status, created_at, updated_at
-             FROM sales_orders ORDER BY order_date DESC LIMIT ? OFFSET ?"
-        ).bind(pagination.limit() as i64).bind(pagination.offset() as i64).fetch_all(pool).await?;
+    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination, scope: &RowScope) -> Result<Paginated<SalesOrder>> {
+        let count_sql = format!("SELECT COUNT(*) FROM sales_orders WHERE 1 = 1{}", scope.sql());
+        let count: (i64,) = scope.bind_query_as(sqlx::query_as(&count_sql)).fetch_one(pool).await?;
+        let sql = format!(
+            "SELECT id, order_number, customer_id, order_date, subtotal, tax_amount, total, status, created_at, updated_at, created_by
+             FROM sales_orders WHERE 1 = 1{} ORDER BY order_date DESC LIMIT ? OFFSET ?", scope.sql()
+        );
+        let rows = scope.bind_query_as(sqlx::query_as::<_, SalesOrderRow>(&sql))
+            .bind(pagination.limit() as i64).bind(pagination.offset() as i64).fetch_all(pool).await?;
         
         let mut orders = Vec::new();
         for row in rows {
@@ -185,7 +213,7 @@ impl SalesOrderRepository for SqliteSalesOrderRepository {
                 base: BaseEntity {
                     id, created_at: chrono::DateTime::parse_from_rfc3339(&row.created_at).map(|d| d.with_timezone(&Utc)).unwrap_or_else(|_| Utc::now()),
                     updated_at: chrono::DateTime::parse_from_rfc3339(&row.updated_at).map(|d| d.with_timezone(&Utc)).unwrap_or_else(|_| Utc::now()),
-                    created_by: None, updated_by: None,
+                    created_by: row.created_by.and_then(|s| Uuid::parse_str(&s).ok()), updated_by: None,
                 },
                 order_number: row.order_number,
                 customer_id: Uuid::parse_str(&row.customer_id).unwrap_or_default(),
@@ -202,24 +230,37 @@ impl SalesOrderRepository for SqliteSalesOrderRepository {
                 subtotal: Money::new(row.subtotal, Currency::USD),
                 tax_amount: Money::new(row.tax_amount, Currency::USD),
                 total: Money::new(row.total, Currency::USD),
-                status: match row.status.as_str() { "Confirmed" => Status::Approved, "Completed" => Status::Completed, _ => Status::Draft },
+                status: order_status(&row.status),
             });
         }
         Ok(Paginated::new(orders, count.0 as u64, pagination))
     }
 
     async fn create(&self, pool: &SqlitePool, order: SalesOrder) -> Result<SalesOrder> {
-        let now = Utc::now();
         let mut tx = pool.begin().await?;
+        self.create_in(&mut tx, &order).await?;
+        tx.commit().await?;
+        Ok(order)
+    }
+
+    async fn update_status(&self, pool: &SqlitePool, id: Uuid, status: Status) -> Result<()> {
+        self.update_status_in(&mut *pool.acquire().await?, id, status).await
+    }
+}
+
+impl SqliteSalesOrderRepository {
+    pub async fn create_in(&self, conn: &mut SqliteConnection, order: &SalesOrder) -> Result<()> {
+        let now = Utc::now();
         
         sqlx::query(
-            "INSERT INTO sales_orders (id, order_number, customer_id, order_date, subtotal, tax_amount, total, status, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO sales_orders (id, order_number, customer_id, order_date, subtotal, tax_amount, total, status, created_at, updated_at, created_by)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         ).bind(order.base.id.to_string()).bind(&order.order_number).bind(order.customer_id.to_string())
         .bind(order.order_date.to_rfc3339()).bind(order.subtotal.amount).bind(order.tax_amount.amount)
         .bind(order.total.amount).bind(format!("{:?}", order.status))
         .bind(order.base.created_at.to_rfc3339()).bind(now.to_rfc3339())
-        .execute(&mut *tx).await?;
+        .bind(order.base.created_by.map(|id| id.to_string()))
+        .execute(&mut *conn).await?;
         
         for line in &order.lines {
             sqlx::query(
@@ -228,27 +269,35 @@ impl SalesOrderRepository for SqliteSalesOrderRepository {
             ).bind(line.id.to_string()).bind(order.base.id.to_string()).bind(line.product_id.to_string())
             .bind(&line.description).bind(line.quantity).bind(line.unit_price.amount)
             .bind(line.discount_percent).bind(line.line_total.amount)
-            .execute(&mut *tx).await?;
+            .execute(&mut *conn).await?;
         }
         
-        tx.commit().await?;
-        Ok(order)
+        Ok(())
     }
 
-    async fn update_status(&self, pool: &SqlitePool, id: Uuid, status: Status) -> Result<()> {
+    pub async fn update_status_in(&self, conn: &mut SqliteConnection, id: Uuid, status: Status) -> Result<()> {
         let status_str = format!("{:?}", status);
         let rows = sqlx::query("UPDATE sales_orders SET status = ?, updated_at = ? WHERE id = ?")
             .bind(&status_str).bind(Utc::now().to_rfc3339()).bind(id.to_string())
-            .execute(pool).await?;
+            .execute(&mut *conn).await?;
         if rows.rows_affected() == 0 { return Err(Error::not_found("SalesOrder", &id.to_string())); }
         Ok(())
     }
+
+    /// Moves the order from `from` to `to`, returning false when it was no longer in `from`.
+    pub async fn transition_status_in(&self, conn: &mut SqliteConnection, id: Uuid, from: Status, to: Status) -> Result<bool> {
+        let rows = sqlx::query("UPDATE sales_orders SET status = ?, updated_at = ? WHERE id = ? AND status = ?")
+            .bind(format!("{:?}", to)).bind(Utc::now().to_rfc3339()).bind(id.to_string()).bind(format!("{:?}", from))
+            .execute(&mut *conn).await?;
+        Ok(rows.rows_affected() > 0)
+    }
 }
 
 #[async_trait]
 pub trait CustomerRepository: Send + Sync {
     async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<Customer>;
-    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<Customer>>;
+    async fn find_in_scope(&self, pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<Customer>;
+    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination, scope: &RowScope) -> Result<Paginated<Customer>>;
     async fn create(&self, pool: &SqlitePool, customer: Customer) -> Result<Customer>;
     async fn update(&self, pool: &SqlitePool, customer: Customer) -> Result<Customer>;
 }
@@ -256,7 +305,8 @@ pub trait CustomerRepository: Send + Sync {
 #[async_trait]
 pub trait SalesOrderRepository: Send + Sync {
     async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<SalesOrder>;
-    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<SalesOrder>>;
+    async fn find_in_scope(&self, pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<SalesOrder>;
+    async fn find_all(&self, pool: &SqlitePool, pagination: Pagination, scope: &RowScope) -> Result<Paginated<SalesOrder>>;
     async fn create(&self, pool: &SqlitePool, order: SalesOrder) -> Result<SalesOrder>;
     async fn update_status(&self, pool: &SqlitePool, id: Uuid, status: Status) -> Result<()>;
 }
@@ -1,8 +1,13 @@
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use chrono::{Utc, DateTime};
 use serde::{Serialize, Deserialize};
-use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status, Money, Currency};
+use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Status, Money, Currency, RowScope, parse_datetime};
+use erp_finance::{PostingDocument, PostingEvent, PostingService};
+use erp_inventory::{ReservationLine, ReservationService, StockReservation};
+use erp_config::ConfigService;
+use erp_credit::CreditService;
+use erp_payment_terms::{PaymentTerm, PaymentTermService};
 use crate::models::*;
 use crate::repository::*;
 
@@ -25,8 +30,14 @@ impl CustomerService {
     
     pub async fn get(&self, pool: &SqlitePool, id: Uuid) -> Result<Customer> { self.repo.find_by_id(pool, id).await }
     
+    pub async fn get_scoped(&self, pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<Customer> { self.repo.find_in_scope(pool, id, scope).await }
+    
     pub async fn list(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<Customer>> {
-        self.repo.find_all(pool, pagination).await
+        self.repo.find_all(pool, pagination, &RowScope::unrestricted()).await
+    }
+    
+    pub async fn list_scoped(&self, pool: &SqlitePool, pagination: Pagination, scope: &RowScope) -> Result<Paginated<Customer>> {
+        self.repo.find_all(pool, pagination, scope).await
     }
     
     pub async fn create(&self, pool: &SqlitePool, customer: Customer) -> Result<Customer> {
@@ -40,6 +51,9 @@ impl CustomerService {
     }
 }
 
+/// `source_type` of stock reservations held by sales orders.
+pub const SALES_ORDER_SOURCE: &str = "SalesOrder";
+
 pub struct SalesOrderService { repo: SqliteSalesOrderRepository }
 impl Default for SalesOrderService {
     fn default() -> Self {
@@ -52,30 +66,100 @@ impl SalesOrderService {
     
     pub async fn get(&self, pool: &SqlitePool, id: Uuid) -> Result<SalesOrder> { self.repo.find_by_id(pool, id).await }
     
+    pub async fn get_scoped(&self, pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<SalesOrder> { self.repo.find_in_scope(pool, id, scope).await }
+    
     pub async fn list(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<SalesOrder>> {
-        self.repo.find_all(pool, pagination).await
+        self.repo.find_all(pool, pagination, &RowScope::unrestricted()).await
     }
     
-    pub async fn create(&self, pool: &SqlitePool, mut order: SalesOrder) -> Result<SalesOrder> {
+    pub async fn list_scoped(&self, pool: &SqlitePool, pagination: Pagination, scope: &RowScope) -> Result<Paginated<SalesOrder>> {
+        self.repo.find_all(pool, pagination, scope).await
+    }
+    
+    pub async fn create(&self, pool: &SqlitePool, order: SalesOrder) -> Result<SalesOrder> {
+        let mut tx = pool.begin().await?;
+        let order = self.create_in(&mut tx, order).await?;
+        tx.commit().await?;
+        Ok(order)
+    }
+    
+    pub async fn create_in(&self, conn: &mut SqliteConnection, mut order: SalesOrder) -> Result<SalesOrder> {
         if order.lines.is_empty() { return Err(Error::validation("Order must have at least one line")); }
         
         let subtotal: i64 = order.lines.iter().map(|l| l.line_total.amount).sum();
         order.subtotal = Money::new(subtotal, Currency::USD);
         order.total = Money::new(subtotal + order.tax_amount.amount, Currency::USD);
         order.order_number = format!("SO-{}-{:04x}", Utc::now().format("%Y%m%d%H%M%S"), rand_digits());
+        let created_by = order.base.created_by;
         order.base = BaseEntity::new();
+        order.base.created_by = created_by;
         order.status = Status::Draft;
         
         for line in &mut order.lines { line.id = Uuid::new_v4(); }
-        self.repo.create(pool, order).await
+        self.repo.create_in(conn, &order).await?;
+        Ok(order)
     }
     
     pub async fn confirm(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
-        self.repo.update_status(pool, id, Status::Approved).await
+        self.confirm_from_warehouse(pool, id, None).await
+    }
+    
+    /// Confirms the order and reserves its stock, from `warehouse_id` only when one is given.
+    pub async fn confirm_from_warehouse(&self, pool: &SqlitePool, id: Uuid, warehouse_id: Option<Uuid>) -> Result<()> {
+        let order = self.repo.find_by_id(pool, id).await?;
+        if !matches!(order.status, Status::Draft | Status::Pending) {
+            return Err(Error::business_rule("Only draft or pending orders can be confirmed"));
+        }
+        let lines: Vec<ReservationLine> = order.lines.iter()
+            .map(|l| ReservationLine { product_id: l.product_id, quantity: l.quantity, source_line_id: Some(l.id) })
+            .collect();
+        ReservationService::reserve(pool, SALES_ORDER_SOURCE, id, &lines, warehouse_id).await?;
+        if let Err(e) = self.repo.update_status(pool, id, Status::Approved).await {
+            ReservationService::release(pool, SALES_ORDER_SOURCE, id).await?;
+            return Err(e);
+        }
+        Ok(())
+    }
+    
+    pub async fn cancel(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
+        let order = self.repo.find_by_id(pool, id).await?;
+        if matches!(order.status, Status::Completed | Status::Cancelled) {
+            return Err(Error::business_rule("Shipped or cancelled orders cannot be cancelled"));
+        }
+        ReservationService::release(pool, SALES_ORDER_SOURCE, id).await?;
+        self.repo.update_status(pool, id, Status::Cancelled).await
     }
     
+    pub async fn reservations(&self, pool: &SqlitePool, id: Uuid) -> Result<Vec<StockReservation>> {
+        ReservationService::list_for_source(pool, SALES_ORDER_SOURCE, id).await
+    }
+    
+    /// Issues the reserved stock of an approved order. The issue, its goods-issue posting at the
+    /// cost of the layers consumed and the status change commit together. Billing is separate: see [`ReceivablesService::invoice_order`].
     pub async fn ship(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
-        self.repo.update_status(pool, id, Status::Completed).await
+        let order = self.repo.find_by_id(pool, id).await?;
+        if order.status != Status::Approved {
+            return Err(Error::business_rule("Only confirmed orders can be shipped"));
+        }
+        let now = Utc::now();
+        let mut tx = pool.begin().await?;
+        if !self.repo.transition_status_in(&mut tx, id, Status::Approved, Status::Completed).await? {
+            return Err(Error::business_rule("Only confirmed orders can be shipped"));
+        }
+        let (_, cost) = ReservationService::consume_in(&mut tx, SALES_ORDER_SOURCE, id, &order.order_number).await?;
+        PostingService::post_document(&mut tx, PostingDocument {
+            event: PostingEvent::GoodsIssue,
+            source_type: "SalesOrder".to_string(),
+            source_id: order.base.id,
+            source_number: order.order_number.clone(),
+            date: now,
+            amount: cost,
+            tax_amount: 0,
+            currency: order.total.currency,
+            description: Some(format!("Goods issue for {}", order.order_number)),
+        }).await?;
+        tx.commit().await?;
+        Ok(())
     }
 }
 
@@ -1944,3 +2028,755 @@ pub struct CampaignROI {
     pub total_revenue: i64,
     pub roi_percent: f64,
 }
+
+/// `number_sequences` codes that number customer invoices and receipts.
+pub const INVOICE_SEQUENCE: &str = "INVOICE";
+pub const PAYMENT_SEQUENCE: &str = "PAYMENT";
+
+/// Customer invoicing and cash application. An issued invoice is `Posted` while it carries an
+/// open balance and `Completed` once it is paid in full.
+pub struct ReceivablesService;
+
+impl Default for ReceivablesService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReceivablesService {
+    pub fn new() -> Self { Self }
+
+    /// Invoices a shipped order. `lines` picks the quantity to bill from each order line; without
+    /// it, everything not yet invoiced is billed. The due date follows `payment_term_id`, or the
+    /// customer's payment terms when none is given.
+    pub async fn invoice_order(
+        pool: &SqlitePool,
+        order_id: Uuid,
+        lines: Option<&[InvoiceLineRequest]>,
+        payment_term_id: Option<Uuid>,
+        user_id: Option<Uuid>,
+    ) -> Result<Invoice> {
+        let order = SqliteSalesOrderRepository.find_by_id(pool, order_id).await?;
+        if order.status != Status::Completed {
+            return Err(Error::business_rule("Only shipped orders can be invoiced"));
+        }
+        let customer = SqliteCustomerRepository.find_by_id(pool, order.customer_id).await?;
+        let terms = PaymentTermService::new();
+        let term = match payment_term_id {
+            Some(id) => terms.get(pool, id).await?,
+            None => PaymentTerm {
+                base: BaseEntity::new(),
+                code: format!("NET{}", customer.payment_terms),
+                name: format!("Net {}", customer.payment_terms),
+                description: None,
+                due_days: customer.payment_terms as i32,
+                discount_days: None,
+                discount_percent: None,
+                is_default: false,
+                status: Status::Active,
+            },
+        };
+
+        let mut tx = pool.begin().await?;
+        // Writing to the order first takes the write lock, so no other invoice for it can land
+        // between reading what is already billed and inserting this one.
+        sqlx::query("UPDATE sales_orders SET updated_at = ? WHERE id = ?")
+            .bind(Utc::now().to_rfc3339())
+            .bind(order_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        let invoiced = Self::invoiced_quantities_in(&mut tx, order_id).await?;
+        let remaining = |line: &SalesOrderLine| line.quantity - invoiced.get(&line.id).copied().unwrap_or(0);
+
+        let mut billed: Vec<(&SalesOrderLine, i64)> = Vec::new();
+        match lines {
+            Some(requests) => {
+                for request in requests {
+                    let line = order.lines.iter()
+                        .find(|l| l.id == request.sales_order_line_id)
+                        .ok_or_else(|| Error::not_found("SalesOrderLine", &request.sales_order_line_id.to_string()))?;
+                    if request.quantity <= 0 {
+                        return Err(Error::validation("Invoiced quantity must be positive"));
+                    }
+                    if billed.iter().any(|(l, _)| l.id == line.id) {
+                        return Err(Error::validation(format!("Order line '{}' is listed more than once", line.description)));
+                    }
+                    if request.quantity > remaining(line) {
+                        return Err(Error::business_rule(format!(
+                            "Only {} of '{}' is left to invoice", remaining(line), line.description
+                        )));
+                    }
+                    billed.push((line, request.quantity));
+                }
+            }
+            None => billed.extend(order.lines.iter().map(|l| (l, remaining(l))).filter(|(_, q)| *q > 0)),
+        }
+        if billed.is_empty() {
+            return Err(Error::business_rule(format!("Order {} has nothing left to invoice", order.order_number)));
+        }
+
+        // Amounts are prorated on the cumulative quantity billed, so the invoice that completes a
+        // line (or the order) picks up any rounding.
+        let invoice_lines: Vec<InvoiceLine> = billed.iter().map(|(line, quantity)| {
+            let before = line.quantity - remaining(line);
+            let amount = line.line_total.amount;
+            InvoiceLine {
+                id: Uuid::new_v4(),
+                sales_order_line_id: Some(line.id),
+                product_id: line.product_id,
+                description: line.description.clone(),
+                quantity: *quantity,
+                unit_price: line.unit_price.clone(),
+                line_total: Money::new(share(amount, before + quantity, line.quantity) - share(amount, before, line.quantity), Currency::USD),
+            }
+        }).collect();
+        let subtotal: i64 = invoice_lines.iter().map(|l| l.line_total.amount).sum();
+        let (billed_before,): (i64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(subtotal), 0) FROM invoices WHERE sales_order_id = ? AND status != 'Cancelled'"
+        )
+        .bind(order_id.to_string())
+        .fetch_one(&mut *tx)
+        .await?;
+        let tax = share(order.tax_amount.amount, billed_before + subtotal, order.subtotal.amount)
+            - share(order.tax_amount.amount, billed_before, order.subtotal.amount);
+
+        let invoice_date = Utc::now();
+        let dates = terms.calculate_dates(&term, invoice_date, subtotal + tax);
+        let invoice = Invoice {
+            base: BaseEntity { created_by: user_id, ..BaseEntity::new() },
+            invoice_number: ConfigService::new().get_next_number_in(&mut tx, INVOICE_SEQUENCE).await?,
+            customer_id: customer.base.id,
+            sales_order_id: Some(order_id),
+            invoice_date,
+            due_date: dates.due_date,
+            lines: invoice_lines,
+            subtotal: Money::new(subtotal, Currency::USD),
+            tax_amount: Money::new(tax, Currency::USD),
+            total: Money::new(subtotal + tax, Currency::USD),
+            amount_paid: Money::zero(Currency::USD),
+            status: Status::Posted,
+        };
+
+        sqlx::query(
+            "INSERT INTO invoices (id, invoice_number, customer_id, sales_order_id, invoice_date, due_date, subtotal, tax_amount, total, currency, amount_paid, status, payment_term_id, created_at, updated_at, created_by)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 'USD', 0, 'Posted', ?, ?, ?, ?)"
+        )
+        .bind(invoice.base.id.to_string())
+        .bind(&invoice.invoice_number)
+        .bind(invoice.customer_id.to_string())
+        .bind(order_id.to_string())
+        .bind(invoice.invoice_date.to_rfc3339())
+        .bind(invoice.due_date.to_rfc3339())
+        .bind(subtotal)
+        .bind(tax)
+        .bind(invoice.total.amount)
+        .bind(payment_term_id.map(|id| id.to_string()))
+        .bind(invoice.base.created_at.to_rfc3339())
+        .bind(invoice.base.updated_at.to_rfc3339())
+        .bind(user_id.map(|id| id.to_string()))
+        .execute(&mut *tx)
+        .await?;
+        for line in &invoice.lines {
+            sqlx::query(
+                "INSERT INTO invoice_lines (id, invoice_id, sales_order_line_id, product_id, description, quantity, unit_price, line_total)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(line.id.to_string())
+            .bind(invoice.base.id.to_string())
+            .bind(line.sales_order_line_id.map(|id| id.to_string()))
+            .bind(line.product_id.to_string())
+            .bind(&line.description)
+            .bind(line.quantity)
+            .bind(line.unit_price.amount)
+            .bind(line.line_total.amount)
+            .execute(&mut *tx)
+            .await?;
+        }
+        PostingService::post_document(&mut tx, PostingDocument {
+            event: PostingEvent::CustomerInvoice,
+            source_type: "Invoice".to_string(),
+            source_id: invoice.base.id,
+            source_number: invoice.invoice_number.clone(),
+            date: invoice.invoice_date,
+            amount: subtotal,
+            tax_amount: tax,
+            currency: invoice.total.currency.clone(),
+            description: Some(format!("Customer invoice {} for {}", invoice.invoice_number, order.order_number)),
+        }).await?;
+        let credit = CreditService::new();
+        credit.get_or_create_profile_in(&mut tx, customer.base.id, customer.credit_limit.as_ref().map_or(0, |m| m.amount)).await?;
+        credit.record_invoice_in(&mut tx, customer.base.id, invoice.base.id, invoice.invoice_number.clone(), invoice.total.amount, user_id).await?;
+        tx.commit().await?;
+        Ok(invoice)
+    }
+
+    pub async fn get_invoice(pool: &SqlitePool, id: Uuid) -> Result<Invoice> {
+        Self::get_invoice_scoped(pool, id, &RowScope::unrestricted()).await
+    }
+
+    /// An invoice within `scope`; one outside it is reported as not found.
+    pub async fn get_invoice_scoped(pool: &SqlitePool, id: Uuid, scope: &RowScope) -> Result<Invoice> {
+        let sql = format!(
+            "SELECT id, invoice_number, customer_id, sales_order_id, invoice_date, due_date, subtotal, tax_amount, total, amount_paid, status, created_at, updated_at, created_by
+             FROM invoices WHERE id = ?{}", scope.sql()
+        );
+        let row = scope.bind_query_as(sqlx::query_as::<_, InvoiceRow>(&sql).bind(id.to_string()))
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| Error::not_found("Invoice", &id.to_string()))?;
+        Self::with_lines(pool, row).await
+    }
+
+    pub async fn list_invoices(pool: &SqlitePool, customer_id: Option<Uuid>, pagination: Pagination) -> Result<Paginated<Invoice>> {
+        Self::list_invoices_scoped(pool, customer_id, pagination, &RowScope::unrestricted()).await
+    }
+
+    pub async fn list_invoices_scoped(pool: &SqlitePool, customer_id: Option<Uuid>, pagination: Pagination, scope: &RowScope) -> Result<Paginated<Invoice>> {
+        let customer_id = customer_id.map(|id| id.to_string());
+        let count_sql = format!("SELECT COUNT(*) FROM invoices WHERE (? IS NULL OR customer_id = ?){}", scope.sql());
+        let (total,): (i64,) = scope.bind_query_as(sqlx::query_as(&count_sql).bind(&customer_id).bind(&customer_id))
+            .fetch_one(pool)
+            .await?;
+        let sql = format!(
+            "SELECT id, invoice_number, customer_id, sales_order_id, invoice_date, due_date, subtotal, tax_amount, total, amount_paid, status, created_at, updated_at, created_by
+             FROM invoices WHERE (? IS NULL OR customer_id = ?){} ORDER BY invoice_date DESC LIMIT ? OFFSET ?", scope.sql()
+        );
+        let rows = scope.bind_query_as(sqlx::query_as::<_, InvoiceRow>(&sql).bind(&customer_id).bind(&customer_id))
+            .bind(pagination.limit() as i64)
+            .bind(pagination.offset() as i64)
+            .fetch_all(pool)
+            .await?;
+        let mut invoices = Vec::with_capacity(rows.len());
+        for row in rows {
+            invoices.push(Self::with_lines(pool, row).await?);
+        }
+        Ok(Paginated::new(invoices, total as u64, pagination))
+    }
+
+    /// Records a receipt and applies it to the customer's open invoices, as directed by
+    /// `allocations` or oldest due first when none are given. Applying less than an invoice's
+    /// balance is a short payment that leaves the rest open; whatever is not applied stays on
+    /// account until [`Self::apply_unapplied`] uses it. `created_by` on the payment is the user.
+    pub async fn apply_payment(pool: &SqlitePool, mut payment: Payment, allocations: &[PaymentAllocationRequest]) -> Result<CashApplication> {
+        if payment.amount.amount <= 0 {
+            return Err(Error::validation("Payment amount must be positive"));
+        }
+        SqliteCustomerRepository.find_by_id(pool, payment.customer_id).await?;
+
+        let mut tx = pool.begin().await?;
+        let open = Self::open_items_in(&mut tx, payment.customer_id, Utc::now()).await?;
+        let plan = plan_allocations(&open, payment.amount.amount, allocations)?;
+        let unapplied = payment.amount.amount - plan.iter().map(|(_, amount)| amount).sum::<i64>();
+
+        let user_id = payment.base.created_by;
+        payment.base = BaseEntity { created_by: user_id, ..BaseEntity::new() };
+        payment.payment_number = ConfigService::new().get_next_number_in(&mut tx, PAYMENT_SEQUENCE).await?;
+        payment.invoice_id = match plan.as_slice() {
+            [(invoice_id, _)] => Some(*invoice_id),
+            _ => None,
+        };
+
+        sqlx::query(
+            "INSERT INTO payments (id, payment_number, customer_id, invoice_id, payment_date, amount, currency, payment_method, reference, unapplied_amount, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(payment.base.id.to_string())
+        .bind(&payment.payment_number)
+        .bind(payment.customer_id.to_string())
+        .bind(payment.invoice_id.map(|id| id.to_string()))
+        .bind(payment.payment_date.to_rfc3339())
+        .bind(payment.amount.amount)
+        .bind(format!("{:?}", payment.amount.currency))
+        .bind(format!("{:?}", payment.payment_method))
+        .bind(&payment.reference)
+        .bind(unapplied)
+        .bind(payment.base.created_at.to_rfc3339())
+        .bind(payment.base.updated_at.to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+        let allocations = Self::allocate_in(&mut tx, payment.base.id, &plan, user_id).await?;
+        PostingService::post_document(&mut tx, PostingDocument {
+            event: PostingEvent::CustomerPayment,
+            source_type: "Payment".to_string(),
+            source_id: payment.base.id,
+            source_number: payment.payment_number.clone(),
+            date: payment.payment_date,
+            amount: payment.amount.amount,
+            tax_amount: 0,
+            currency: payment.amount.currency.clone(),
+            description: Some(format!("Customer payment {}", payment.payment_number)),
+        }).await?;
+        Self::release_credit_in(&mut tx, payment.customer_id, &allocations, user_id).await?;
+        tx.commit().await?;
+        Ok(CashApplication { payment, allocations, unapplied_amount: unapplied })
+    }
+
+    /// Applies the unapplied part of an earlier receipt to open invoices.
+    pub async fn apply_unapplied(
+        pool: &SqlitePool,
+        payment_id: Uuid,
+        allocations: &[PaymentAllocationRequest],
+        user_id: Option<Uuid>,
+    ) -> Result<CashApplication> {
+        let mut tx = pool.begin().await?;
+        let row = Self::payment_row_in(&mut tx, payment_id).await?;
+        if row.unapplied_amount <= 0 {
+            return Err(Error::business_rule(format!("Payment {} has no unapplied amount", row.payment_number)));
+        }
+        let customer_id = Uuid::parse_str(&row.customer_id).unwrap_or_default();
+        let open = Self::open_items_in(&mut tx, customer_id, Utc::now()).await?;
+        let plan = plan_allocations(&open, row.unapplied_amount, allocations)?;
+        if plan.is_empty() {
+            return Err(Error::business_rule("The customer has no open invoices"));
+        }
+        let applied: i64 = plan.iter().map(|(_, amount)| amount).sum();
+
+        let updated = sqlx::query(
+            "UPDATE payments SET unapplied_amount = unapplied_amount - ?, updated_at = ? WHERE id = ? AND unapplied_amount >= ?"
+        )
+        .bind(applied)
+        .bind(Utc::now().to_rfc3339())
+        .bind(payment_id.to_string())
+        .bind(applied)
+        .execute(&mut *tx)
+        .await?;
+        if updated.rows_affected() == 0 {
+            return Err(Error::business_rule(format!("Payment {} no longer has {} unapplied", row.payment_number, applied)));
+        }
+        let allocations = Self::allocate_in(&mut tx, payment_id, &plan, user_id).await?;
+        Self::release_credit_in(&mut tx, customer_id, &allocations, user_id).await?;
+        tx.commit().await?;
+
+        Self::get_cash_application(pool, payment_id).await
+    }
+
+    pub async fn get_cash_application(pool: &SqlitePool, payment_id: Uuid) -> Result<CashApplication> {
+        let row = Self::payment_row_in(&mut *pool.acquire().await?, payment_id).await?;
+        let allocations = sqlx::query_as::<_, PaymentAllocationRow>(
+            "SELECT id, payment_id, invoice_id, amount, created_at FROM payment_allocations WHERE payment_id = ? ORDER BY created_at"
+        )
+        .bind(payment_id.to_string())
+        .fetch_all(pool)
+        .await?;
+        let unapplied_amount = row.unapplied_amount;
+        Ok(CashApplication {
+            payment: row.try_into()?,
+            allocations: allocations.into_iter().map(TryInto::try_into).collect::<Result<_>>()?,
+            unapplied_amount,
+        })
+    }
+
+    /// The customer's invoices with an open balance, oldest due first.
+    pub async fn open_items(pool: &SqlitePool, customer_id: Uuid) -> Result<Vec<OpenItem>> {
+        Self::open_items_in(&mut *pool.acquire().await?, customer_id, Utc::now()).await
+    }
+
+    /// Invoices and receipts dated within `from..=to` with a running balance, followed by the
+    /// open items aged at `to`.
+    pub async fn statement(pool: &SqlitePool, customer_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<CustomerStatement> {
+        if from > to {
+            return Err(Error::validation("Statement start must not be after its end"));
+        }
+        let customer = SqliteCustomerRepository.find_by_id(pool, customer_id).await?;
+        let documents = sqlx::query_as::<_, StatementRow>(
+            "SELECT invoice_date AS date, 'Invoice' AS document_type, invoice_number AS document_number, total AS debit, 0 AS credit
+             FROM invoices WHERE customer_id = ? AND status IN ('Posted', 'Completed')
+             UNION ALL
+             SELECT payment_date, 'Payment', payment_number, 0, amount FROM payments WHERE customer_id = ?
+             ORDER BY date, document_number"
+        )
+        .bind(customer_id.to_string())
+        .bind(customer_id.to_string())
+        .fetch_all(pool)
+        .await?;
+
+        let mut opening_balance = 0;
+        let mut lines = Vec::new();
+        for row in documents {
+            let date = parse_datetime(&row.date, "date")?;
+            if date < from {
+                opening_balance += row.debit - row.credit;
+            } else if date <= to {
+                lines.push(StatementLine {
+                    date,
+                    document_type: row.document_type,
+                    document_number: row.document_number,
+                    debit: row.debit,
+                    credit: row.credit,
+                    balance: 0,
+                });
+            }
+        }
+        let mut balance = opening_balance;
+        for line in &mut lines {
+            balance += line.debit - line.credit;
+            line.balance = balance;
+        }
+
+        // Receipts on hand at `to`, less only what had been applied by then.
+        let (unapplied_credit,): (i64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(p.unapplied_amount + COALESCE(
+                        (SELECT SUM(pa.amount) FROM payment_allocations pa WHERE pa.payment_id = p.id AND pa.created_at > ?), 0)), 0)
+             FROM payments p WHERE p.customer_id = ? AND p.payment_date <= ?"
+        )
+        .bind(to.to_rfc3339())
+        .bind(customer_id.to_string())
+        .bind(to.to_rfc3339())
+        .fetch_one(pool)
+        .await?;
+        let open_items = Self::open_items_in(&mut *pool.acquire().await?, customer_id, to).await?;
+        let bucket = |range: std::ops::RangeInclusive<i64>| -> i64 {
+            open_items.iter().filter(|i| range.contains(&i.days_overdue)).map(|i| i.balance).sum()
+        };
+        Ok(CustomerStatement {
+            customer_id,
+            customer_name: customer.name,
+            from,
+            to,
+            opening_balance,
+            closing_balance: balance,
+            current: bucket(0..=0),
+            days_1_30: bucket(1..=30),
+            days_31_60: bucket(31..=60),
+            days_61_90: bucket(61..=90),
+            over_90: bucket(91..=i64::MAX),
+            lines,
+            open_items,
+            unapplied_credit,
+        })
+    }
+
+    /// Invoices issued by `as_of` that were still open then: receipts applied after `as_of` are
+    /// taken back out of what was paid.
+    async fn open_items_in(conn: &mut SqliteConnection, customer_id: Uuid, as_of: DateTime<Utc>) -> Result<Vec<OpenItem>> {
+        let rows = sqlx::query_as::<_, OpenItemRow>(
+            "SELECT i.id, i.invoice_number, i.invoice_date, i.due_date, i.total,
+                    i.amount_paid - COALESCE((SELECT SUM(pa.amount) FROM payment_allocations pa
+                                              WHERE pa.invoice_id = i.id AND pa.created_at > ?
+                                                AND pa.payment_id IN (SELECT id FROM payments)), 0) AS amount_paid
+             FROM invoices i
+             WHERE i.customer_id = ? AND i.status IN ('Posted', 'Completed') AND i.invoice_date <= ?
+             ORDER BY i.due_date, i.invoice_date"
+        )
+        .bind(as_of.to_rfc3339())
+        .bind(customer_id.to_string())
+        .bind(as_of.to_rfc3339())
+        .fetch_all(&mut *conn)
+        .await?;
+        let mut items = Vec::new();
+        for r in rows.into_iter().filter(|r| r.total > r.amount_paid) {
+            let due_date = parse_datetime(&r.due_date, "due_date")?;
+            items.push(OpenItem {
+                invoice_id: Uuid::parse_str(&r.id).unwrap_or_default(),
+                invoice_number: r.invoice_number,
+                invoice_date: parse_datetime(&r.invoice_date, "invoice_date")?,
+                due_date,
+                total: r.total,
+                amount_paid: r.amount_paid,
+                balance: r.total - r.amount_paid,
+                days_overdue: (as_of - due_date).num_days().max(0),
+            });
+        }
+        Ok(items)
+    }
+
+    async fn invoiced_quantities_in(conn: &mut SqliteConnection, order_id: Uuid) -> Result<std::collections::HashMap<Uuid, i64>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT il.sales_order_line_id, SUM(il.quantity) FROM invoice_lines il
+             JOIN invoices i ON i.id = il.invoice_id
+             WHERE i.sales_order_id = ? AND i.status != 'Cancelled' AND il.sales_order_line_id IS NOT NULL
+             GROUP BY il.sales_order_line_id"
+        )
+        .bind(order_id.to_string())
+        .fetch_all(&mut *conn)
+        .await?;
+        Ok(rows.into_iter().filter_map(|(id, quantity)| Uuid::parse_str(&id).ok().map(|id| (id, quantity))).collect())
+    }
+
+    async fn with_lines(pool: &SqlitePool, row: InvoiceRow) -> Result<Invoice> {
+        let lines = sqlx::query_as::<_, InvoiceLineRow>(
+            "SELECT id, sales_order_line_id, product_id, description, quantity, unit_price, line_total FROM invoice_lines WHERE invoice_id = ?"
+        )
+        .bind(&row.id)
+        .fetch_all(pool)
+        .await?;
+        let mut invoice: Invoice = row.try_into()?;
+        invoice.lines = lines.into_iter().map(Into::into).collect();
+        Ok(invoice)
+    }
+
+    async fn payment_row_in(conn: &mut SqliteConnection, id: Uuid) -> Result<PaymentRow> {
+        sqlx::query_as::<_, PaymentRow>(
+            "SELECT id, payment_number, customer_id, invoice_id, payment_date, amount, payment_method, reference, unapplied_amount, created_at, updated_at
+             FROM payments WHERE id = ?"
+        )
+        .bind(id.to_string())
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| Error::not_found("Payment", &id.to_string()))
+    }
+
+    async fn allocate_in(
+        conn: &mut SqliteConnection,
+        payment_id: Uuid,
+        plan: &[(Uuid, i64)],
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<PaymentAllocation>> {
+        let now = Utc::now();
+        let mut allocations = Vec::with_capacity(plan.len());
+        for &(invoice_id, amount) in plan {
+            let updated = sqlx::query(
+                "UPDATE invoices SET amount_paid = amount_paid + ?,
+                        status = CASE WHEN amount_paid + ? >= total THEN 'Completed' ELSE status END,
+                        updated_at = ?
+                 WHERE id = ? AND status = 'Posted' AND total - amount_paid >= ?"
+            )
+            .bind(amount)
+            .bind(amount)
+            .bind(now.to_rfc3339())
+            .bind(invoice_id.to_string())
+            .bind(amount)
+            .execute(&mut *conn)
+            .await?;
+            if updated.rows_affected() == 0 {
+                return Err(Error::business_rule(format!("Invoice {} no longer has {} open", invoice_id, amount)));
+            }
+            let allocation = PaymentAllocation { id: Uuid::new_v4(), payment_id, invoice_id, amount, created_at: now };
+            sqlx::query(
+                "INSERT INTO payment_allocations (id, payment_id, invoice_id, amount, created_at, updated_at, created_by)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(allocation.id.to_string())
+            .bind(payment_id.to_string())
+            .bind(invoice_id.to_string())
+            .bind(amount)
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .bind(user_id.map(|id| id.to_string()))
+            .execute(&mut *conn)
+            .await?;
+            allocations.push(allocation);
+        }
+        Ok(allocations)
+    }
+
+    /// Gives back the credit the paid invoices were using.
+    async fn release_credit_in(conn: &mut SqliteConnection, customer_id: Uuid, allocations: &[PaymentAllocation], user_id: Option<Uuid>) -> Result<()> {
+        let credit = CreditService::new();
+        if allocations.is_empty() || credit.get_profile_in(conn, customer_id).await?.is_none() {
+            return Ok(());
+        }
+        for allocation in allocations {
+            credit.record_payment_in(conn, customer_id, Some(allocation.invoice_id), allocation.amount, user_id).await?;
+        }
+        Ok(())
+    }
+}
+
+/// `total * part / whole`, rounded down.
+fn share(total: i64, part: i64, whole: i64) -> i64 {
+    if whole == 0 {
+        return 0;
+    }
+    (total as i128 * part as i128 / whole as i128) as i64
+}
+
+/// Turns allocation requests into `(invoice, amount)` pairs against the customer's open items,
+/// or spreads `available` over them oldest due first when there are no requests.
+fn plan_allocations(open: &[OpenItem], available: i64, requests: &[PaymentAllocationRequest]) -> Result<Vec<(Uuid, i64)>> {
+    let mut plan: Vec<(Uuid, i64)> = Vec::new();
+    if requests.is_empty() {
+        let mut left = available;
+        for item in open {
+            if left == 0 {
+                break;
+            }
+            let amount = item.balance.min(left);
+            plan.push((item.invoice_id, amount));
+            left -= amount;
+        }
+        return Ok(plan);
+    }
+
+    for request in requests {
+        if request.amount <= 0 {
+            return Err(Error::validation("Allocated amounts must be positive"));
+        }
+        let item = open.iter()
+            .find(|i| i.invoice_id == request.invoice_id)
+            .ok_or_else(|| Error::business_rule(format!("Invoice {} is not open for this customer", request.invoice_id)))?;
+        let index = match plan.iter().position(|(id, _)| *id == item.invoice_id) {
+            Some(index) => index,
+            None => {
+                plan.push((item.invoice_id, 0));
+                plan.len() - 1
+            }
+        };
+        plan[index].1 += request.amount;
+        if plan[index].1 > item.balance {
+            return Err(Error::business_rule(format!(
+                "Cannot apply {} to invoice {}: its open balance is {}", plan[index].1, item.invoice_number, item.balance
+            )));
+        }
+    }
+    let applied: i64 = plan.iter().map(|(_, amount)| amount).sum();
+    if applied > available {
+        return Err(Error::business_rule(format!("Allocations of {} exceed the {} available", applied, available)));
+    }
+    Ok(plan)
+}
+
+#[derive(sqlx::FromRow)]
+struct InvoiceRow {
+    id: String,
+    invoice_number: String,
+    customer_id: String,
+    sales_order_id: Option<String>,
+    invoice_date: String,
+    due_date: String,
+    subtotal: i64,
+    tax_amount: i64,
+    total: i64,
+    amount_paid: i64,
+    status: String,
+    created_at: String,
+    updated_at: String,
+    created_by: Option<String>,
+}
+
+impl TryFrom<InvoiceRow> for Invoice {
+    type Error = Error;
+
+    fn try_from(r: InvoiceRow) -> Result<Self> {
+        Ok(Self {
+            base: BaseEntity {
+                id: Uuid::parse_str(&r.id).unwrap_or_default(),
+                created_at: parse_datetime(&r.created_at, "created_at")?,
+                updated_at: parse_datetime(&r.updated_at, "updated_at")?,
+                created_by: r.created_by.and_then(|id| Uuid::parse_str(&id).ok()),
+                updated_by: None,
+            },
+            invoice_number: r.invoice_number,
+            customer_id: Uuid::parse_str(&r.customer_id).unwrap_or_default(),
+            sales_order_id: r.sales_order_id.and_then(|id| Uuid::parse_str(&id).ok()),
+            invoice_date: parse_datetime(&r.invoice_date, "invoice_date")?,
+            due_date: parse_datetime(&r.due_date, "due_date")?,
+            lines: Vec::new(),
+            subtotal: Money::new(r.subtotal, Currency::USD),
+            tax_amount: Money::new(r.tax_amount, Currency::USD),
+            total: Money::new(r.total, Currency::USD),
+            amount_paid: Money::new(r.amount_paid, Currency::USD),
+            status: r.status.parse().unwrap_or(Status::Draft),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct InvoiceLineRow {
+    id: String,
+    sales_order_line_id: Option<String>,
+    product_id: String,
+    description: String,
+    quantity: i64,
+    unit_price: i64,
+    line_total: i64,
+}
+
+impl From<InvoiceLineRow> for InvoiceLine {
+    fn from(r: InvoiceLineRow) -> Self {
+        Self {
+            id: Uuid::parse_str(&r.id).unwrap_or_default(),
+            sales_order_line_id: r.sales_order_line_id.and_then(|id| Uuid::parse_str(&id).ok()),
+            product_id: Uuid::parse_str(&r.product_id).unwrap_or_default(),
+            description: r.description,
+            quantity: r.quantity,
+            unit_price: Money::new(r.unit_price, Currency::USD),
+            line_total: Money::new(r.line_total, Currency::USD),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PaymentRow {
+    id: String,
+    payment_number: String,
+    customer_id: String,
+    invoice_id: Option<String>,
+    payment_date: String,
+    amount: i64,
+    payment_method: String,
+    reference: Option<String>,
+    unapplied_amount: i64,
+    created_at: String,
+    updated_at: String,
+}
+
+impl TryFrom<PaymentRow> for Payment {
+    type Error = Error;
+
+    fn try_from(r: PaymentRow) -> Result<Self> {
+        Ok(Self {
+            base: BaseEntity {
+                id: Uuid::parse_str(&r.id).unwrap_or_default(),
+                created_at: parse_datetime(&r.created_at, "created_at")?,
+                updated_at: parse_datetime(&r.updated_at, "updated_at")?,
+                created_by: None,
+                updated_by: None,
+            },
+            payment_number: r.payment_number,
+            customer_id: Uuid::parse_str(&r.customer_id).unwrap_or_default(),
+            invoice_id: r.invoice_id.and_then(|id| Uuid::parse_str(&id).ok()),
+            payment_date: parse_datetime(&r.payment_date, "payment_date")?,
+            amount: Money::new(r.amount, Currency::USD),
+            payment_method: match r.payment_method.as_str() {
+                "Check" => PaymentMethod::Check,
+                "CreditCard" => PaymentMethod::CreditCard,
+                "BankTransfer" => PaymentMethod::BankTransfer,
+                _ => PaymentMethod::Cash,
+            },
+            reference: r.reference,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PaymentAllocationRow {
+    id: String,
+    payment_id: String,
+    invoice_id: String,
+    amount: i64,
+    created_at: String,
+}
+
+impl TryFrom<PaymentAllocationRow> for PaymentAllocation {
+    type Error = Error;
+
+    fn try_from(r: PaymentAllocationRow) -> Result<Self> {
+        Ok(Self {
+            id: Uuid::parse_str(&r.id).unwrap_or_default(),
+            payment_id: Uuid::parse_str(&r.payment_id).unwrap_or_default(),
+            invoice_id: Uuid::parse_str(&r.invoice_id).unwrap_or_default(),
+            amount: r.amount,
+            created_at: parse_datetime(&r.created_at, "created_at")?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct OpenItemRow {
+    id: String,
+    invoice_number: String,
+    invoice_date: String,
+    due_date: String,
+    total: i64,
+    amount_paid: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct StatementRow {
+    date: String,
+    document_type: String,
+    document_number: String,
+    debit: i64,
+    credit: i64,
+}
@@ -0,0 +1,189 @@
+use chrono::{Duration, Utc};
+use erp_core::{Address, BaseEntity, ContactInfo, Currency, Error, Money, Status};
+use erp_credit::CreditService;
+use erp_finance::{DunningLevel, DunningService, PostingEvent, PostingService};
+use erp_sales::*;
+use erp_core::testing::{insert, memory_pool};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+async fn setup() -> SqlitePool {
+    memory_pool(&[
+        include_str!("../../migrations/20240101000000_finance.sql"),
+        include_str!("../../migrations/20240101000001_inventory.sql"),
+        include_str!("../../migrations/20240101000002_sales.sql"),
+        include_str!("../../migrations/20240101000018_enterprise_additions.sql"),
+        include_str!("../../migrations/20240101000023_new_enterprise_features.sql"),
+        include_str!("../../migrations/20240303000000_credit_management.sql"),
+        include_str!("../../migrations/20260303100000_payment_terms.sql"),
+        include_str!("../../migrations/20260309000000_posting_rules.sql"),
+        include_str!("../../migrations/20260314000000_stock_reservations.sql"),
+        include_str!("../../migrations/20260315000000_inventory_costing.sql"),
+        include_str!("../../migrations/20260316000000_receivables.sql"),
+        include_str!("../../migrations/20260329000500_journal_line_currency.sql"),
+    ]).await
+}
+
+async fn account(pool: &SqlitePool, code: &str, account_type: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    let now = Utc::now().to_rfc3339();
+    insert(pool, "INSERT INTO accounts (id, code, name, account_type, status, created_at, updated_at) VALUES (?, ?, ?, ?, 'Active', ?, ?)", &[&id.to_string(), code, code, account_type, &now, &now]).await;
+    id
+}
+
+/// A shipped order for 10 x 100 and 3 x 333 with 200 of tax.
+async fn shipped_order(pool: &SqlitePool, customer_id: Uuid) -> SalesOrder {
+    let (warehouse, location, product) = (Uuid::new_v4().to_string(), Uuid::new_v4().to_string(), Uuid::new_v4());
+    let now = Utc::now().to_rfc3339();
+    insert(pool, "INSERT INTO warehouses (id, code, name, created_at, updated_at) VALUES (?, 'WH1', 'Main', ?, ?)", &[&warehouse, &now, &now]).await;
+    insert(pool, "INSERT INTO stock_locations (id, warehouse_id, code, name, created_at, updated_at) VALUES (?, ?, 'A1', 'Aisle 1', ?, ?)", &[&location, &warehouse, &now, &now]).await;
+    insert(pool, "INSERT INTO products (id, sku, name, product_type, unit_of_measure, created_at, updated_at) VALUES (?, 'WIDGET', 'Widget', 'Goods', 'PCS', ?, ?)", &[&product.to_string(), &now, &now]).await;
+    insert(pool, "INSERT INTO stock_levels (id, product_id, location_id, quantity, reserved_quantity, available_quantity) VALUES (?, ?, ?, 20, 0, 20)", &[&Uuid::new_v4().to_string(), &product.to_string(), &location]).await;
+
+    let line = |description: &str, quantity: i64, unit_price: i64| SalesOrderLine {
+        id: Uuid::nil(),
+        product_id: product,
+        description: description.to_string(),
+        quantity,
+        unit_price: Money::new(unit_price, Currency::USD),
+        discount_percent: 0.0,
+        tax_rate: 0.0,
+        line_total: Money::new(quantity * unit_price, Currency::USD),
+    };
+    let service = SalesOrderService::new();
+    let order = service.create(pool, SalesOrder {
+        base: BaseEntity::new(),
+        order_number: String::new(),
+        customer_id,
+        order_date: Utc::now(),
+        required_date: None,
+        lines: vec![line("Widgets", 10, 100), line("Widget kits", 3, 333)],
+        subtotal: Money::zero(Currency::USD),
+        tax_amount: Money::new(200, Currency::USD),
+        total: Money::zero(Currency::USD),
+        status: Status::Draft,
+    }).await.unwrap();
+    assert!(service.ship(pool, order.base.id).await.is_err(), "draft orders must be confirmed before shipping");
+    service.confirm(pool, order.base.id).await.unwrap();
+    service.ship(pool, order.base.id).await.unwrap();
+    assert!(service.ship(pool, order.base.id).await.is_err());
+    service.get(pool, order.base.id).await.unwrap()
+}
+
+fn receipt(customer_id: Uuid, amount: i64) -> Payment {
+    Payment {
+        base: BaseEntity::new(),
+        payment_number: String::new(),
+        customer_id,
+        invoice_id: None,
+        payment_date: Utc::now(),
+        amount: Money::new(amount, Currency::USD),
+        payment_method: PaymentMethod::BankTransfer,
+        reference: None,
+    }
+}
+
+#[tokio::test]
+async fn test_invoicing_cash_application_and_dunning() {
+    let pool = setup().await;
+    let (receivable, revenue, cash) = (account(&pool, "1200", "Asset").await, account(&pool, "4000", "Revenue").await, account(&pool, "1000", "Asset").await);
+    PostingService::upsert_rule(&pool, PostingEvent::CustomerInvoice, receivable, revenue, None, None).await.unwrap();
+    PostingService::upsert_rule(&pool, PostingEvent::CustomerPayment, cash, receivable, None, None).await.unwrap();
+
+    let customer = CustomerService::new().create(&pool, Customer {
+        base: BaseEntity::new(),
+        code: "ACME".to_string(),
+        name: "Acme".to_string(),
+        contact: ContactInfo { email: None, phone: None, fax: None, website: None },
+        billing_address: Address { street: String::new(), city: String::new(), state: None, postal_code: String::new(), country: String::new() },
+        shipping_address: None,
+        credit_limit: Some(Money::new(100_000, Currency::USD)),
+        payment_terms: 30,
+        status: Status::Active,
+    }).await.unwrap();
+    let order = shipped_order(&pool, customer.base.id).await;
+    assert_eq!(order.status, Status::Completed);
+    // Shipping alone no longer bills the customer.
+    assert!(PostingService::list_sources(&pool, "SalesOrder", order.base.id).await.unwrap().iter().all(|s| s.event != PostingEvent::CustomerInvoice));
+
+    // Partial invoice: 4 of the widgets and 1 kit, with its share of the order's tax.
+    let (widgets, kits) = (order.lines[0].id, order.lines[1].id);
+    let partial = [
+        InvoiceLineRequest { sales_order_line_id: widgets, quantity: 4 },
+        InvoiceLineRequest { sales_order_line_id: kits, quantity: 1 },
+    ];
+    let first = ReceivablesService::invoice_order(&pool, order.base.id, Some(&partial), None, None).await.unwrap();
+    assert_eq!(first.invoice_number, "INV-000001");
+    assert_eq!((first.subtotal.amount, first.tax_amount.amount, first.total.amount), (733, 73, 806));
+    assert_eq!((first.due_date - first.invoice_date).num_days(), 30);
+    assert_eq!(PostingService::list_sources(&pool, "Invoice", first.base.id).await.unwrap().len(), 1);
+
+    let too_many = [InvoiceLineRequest { sales_order_line_id: widgets, quantity: 7 }];
+    let err = ReceivablesService::invoice_order(&pool, order.base.id, Some(&too_many), None, None).await.unwrap_err();
+    assert!(err.to_string().contains("Only 6"));
+
+    // The rest of the order on Net 60 picks up the remaining amounts exactly.
+    let net60 = Uuid::parse_str("00000000-0000-0000-0000-000000000002").unwrap();
+    let second = ReceivablesService::invoice_order(&pool, order.base.id, None, Some(net60), None).await.unwrap();
+    assert_eq!(second.invoice_number, "INV-000002");
+    assert_eq!((second.subtotal.amount, second.tax_amount.amount, second.total.amount), (1266, 127, 1393));
+    assert_eq!((second.due_date - second.invoice_date).num_days(), 60);
+    assert!(ReceivablesService::invoice_order(&pool, order.base.id, None, None, None).await.is_err());
+
+    let credit = CreditService::new();
+    assert_eq!(credit.get_profile(&pool, customer.base.id).await.unwrap().unwrap().credit_used, 2199);
+
+    // Only the first invoice is overdue, and the level comes from the run's own policy.
+    let overdue = (Utc::now() - Duration::days(45)).to_rfc3339();
+    insert(&pool, "UPDATE invoices SET due_date = ? WHERE id = ?", &[&overdue, &first.base.id.to_string()]).await;
+    let policy = DunningService::create_policy(&pool, "Standard", None).await.unwrap();
+    DunningService::add_level(&pool, policy.id, DunningLevel::Reminder, 0, 0.0, 0, false, true).await.unwrap();
+    DunningService::add_level(&pool, policy.id, DunningLevel::FirstNotice, 30, 0.0, 0, false, true).await.unwrap();
+    let strict = DunningService::create_policy(&pool, "Strict", None).await.unwrap();
+    DunningService::add_level(&pool, strict.id, DunningLevel::Legal, 10, 0.0, 0, false, true).await.unwrap();
+    let run = DunningService::create_run(&pool, policy.id).await.unwrap();
+    let letters = DunningService::execute_run(&pool, run.id).await.unwrap();
+    assert_eq!(letters.len(), 1);
+    assert_eq!(letters[0].invoice_ids, vec![first.base.id]);
+    assert_eq!(letters[0].invoice_amount, 806);
+    assert!(matches!(letters[0].level, DunningLevel::FirstNotice));
+
+    // A short payment clears the oldest invoice and leaves part of the next one open.
+    let applied = ReceivablesService::apply_payment(&pool, receipt(customer.base.id, 1000), &[]).await.unwrap();
+    assert_eq!(applied.payment.payment_number, "RCPT-000001");
+    assert_eq!(applied.allocations.iter().map(|a| (a.invoice_id, a.amount)).collect::<Vec<_>>(), vec![(first.base.id, 806), (second.base.id, 194)]);
+    assert_eq!(applied.unapplied_amount, 0);
+    assert_eq!(ReceivablesService::get_invoice(&pool, first.base.id).await.unwrap().status, Status::Completed);
+    let open = ReceivablesService::open_items(&pool, customer.base.id).await.unwrap();
+    assert_eq!(open.iter().map(|i| (i.invoice_id, i.balance)).collect::<Vec<_>>(), vec![(second.base.id, 1199)]);
+
+    // An overpayment is held on account and applied later.
+    let too_much = [PaymentAllocationRequest { invoice_id: second.base.id, amount: 1200 }];
+    assert!(ReceivablesService::apply_payment(&pool, receipt(customer.base.id, 2000), &too_much).await.is_err());
+    let partial = [PaymentAllocationRequest { invoice_id: second.base.id, amount: 500 }];
+    let over = ReceivablesService::apply_payment(&pool, receipt(customer.base.id, 2000), &partial).await.unwrap();
+    assert_eq!(over.unapplied_amount, 1500);
+    let applied = ReceivablesService::apply_unapplied(&pool, over.payment.base.id, &[], None).await.unwrap();
+    assert_eq!((applied.allocations.len(), applied.unapplied_amount), (2, 801));
+    assert!(ReceivablesService::apply_unapplied(&pool, over.payment.base.id, &[], None).await.is_err());
+    assert!(ReceivablesService::open_items(&pool, customer.base.id).await.unwrap().is_empty());
+    assert_eq!(credit.get_profile(&pool, customer.base.id).await.unwrap().unwrap().credit_used, 0);
+
+    let statement = ReceivablesService::statement(&pool, customer.base.id, Utc::now() - Duration::days(1), Utc::now() + Duration::days(1)).await.unwrap();
+    assert_eq!(statement.opening_balance, 0);
+    assert_eq!(statement.lines.len(), 4);
+    assert_eq!((statement.closing_balance, statement.unapplied_credit), (-801, 801));
+    let later = ReceivablesService::statement(&pool, customer.base.id, Utc::now() + Duration::days(1), Utc::now() + Duration::days(2)).await.unwrap();
+    assert_eq!((later.opening_balance, later.lines.len(), later.closing_balance), (-801, 0, -801));
+
+    // Aged as of an earlier date, the first invoice is still open and the later receipts are not on hand.
+    let issued = (Utc::now() - Duration::days(20)).to_rfc3339();
+    insert(&pool, "UPDATE invoices SET invoice_date = ? WHERE id = ?", &[&issued, &first.base.id.to_string()]).await;
+    let past = ReceivablesService::statement(&pool, customer.base.id, Utc::now() - Duration::days(30), Utc::now() - Duration::days(10)).await.unwrap();
+    assert_eq!(past.open_items.iter().map(|i| (i.invoice_id, i.balance)).collect::<Vec<_>>(), vec![(first.base.id, 806)]);
+    assert_eq!((past.closing_balance, past.unapplied_credit, past.days_31_60), (806, 0, 806));
+
+    insert(&pool, "UPDATE invoices SET invoice_date = 'yesterday' WHERE id = ?", &[&second.base.id.to_string()]).await;
+    let err = ReceivablesService::get_invoice(&pool, second.base.id).await.unwrap_err();
+    assert!(matches!(err, Error::Validation(_)));
+}
@@ -12,6 +12,10 @@ pub trait TwoFactorRepository: Send + Sync {
     async fn create(&self, pool: &SqlitePool, setup: TwoFactorSetup) -> Result<TwoFactorSetup>;
     async fn update_enabled(&self, pool: &SqlitePool, id: Uuid, enabled: bool) -> Result<()>;
     async fn update_backup_codes(&self, pool: &SqlitePool, id: Uuid, codes: Vec<String>) -> Result<()>;
+    /// Removes `code` from the backup codes unless another request already did. Returns whether it did.
+    async fn consume_backup_code(&self, pool: &SqlitePool, id: Uuid, codes: &[String], code: &str) -> Result<bool>;
+    /// Records `step` as the last accepted TOTP step unless it is not later than the recorded one.
+    async fn accept_totp_step(&self, pool: &SqlitePool, id: Uuid, step: u64) -> Result<bool>;
     async fn delete(&self, pool: &SqlitePool, user_id: Uuid) -> Result<()>;
 }
 
@@ -67,6 +71,29 @@ impl TwoFactorRepository for SqliteTwoFactorRepository {
         Ok(())
     }
 
+    async fn consume_backup_code(&self, pool: &SqlitePool, id: Uuid, codes: &[String], code: &str) -> Result<bool> {
+        let remaining: Vec<&String> = codes.iter().filter(|c| *c != code).collect();
+        let result = sqlx::query("UPDATE user_two_factor SET backup_codes = ? WHERE id = ? AND backup_codes = ?")
+            .bind(serde_json::to_string(&remaining).unwrap_or_default())
+            .bind(id.to_string())
+            .bind(serde_json::to_string(codes).unwrap_or_default())
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn accept_totp_step(&self, pool: &SqlitePool, id: Uuid, step: u64) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE user_two_factor SET last_used_step = ? WHERE id = ? AND (last_used_step IS NULL OR last_used_step < ?)"
+        )
+        .bind(step as i64)
+        .bind(id.to_string())
+        .bind(step as i64)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
     async fn delete(&self, pool: &SqlitePool, user_id: Uuid) -> Result<()> {
         sqlx::query("DELETE FROM user_two_factor WHERE user_id = ?")
             .bind(user_id.to_string())
@@ -348,8 +375,10 @@ pub trait UserSessionRepository: Send + Sync {
     async fn create(&self, pool: &SqlitePool, session: UserSession) -> Result<UserSession>;
     async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<Option<UserSession>>;
     async fn find_by_token(&self, pool: &SqlitePool, token_hash: &str) -> Result<Option<UserSession>>;
+    async fn find_by_previous_token(&self, pool: &SqlitePool, token_hash: &str) -> Result<Option<UserSession>>;
     async fn find_by_user(&self, pool: &SqlitePool, user_id: Uuid) -> Result<Vec<UserSession>>;
     async fn update_activity(&self, pool: &SqlitePool, id: Uuid) -> Result<()>;
+    async fn rotate_token(&self, pool: &SqlitePool, id: Uuid, current_hash: &str, new_hash: &str) -> Result<bool>;
     async fn delete(&self, pool: &SqlitePool, id: Uuid) -> Result<()>;
     async fn delete_by_user(&self, pool: &SqlitePool, user_id: Uuid) -> Result<()>;
     async fn delete_expired(&self, pool: &SqlitePool) -> Result<u64>;
@@ -401,6 +430,17 @@ impl UserSessionRepository for SqliteUserSessionRepository {
         Ok(row.map(|r| r.into_model()))
     }
 
+    async fn find_by_previous_token(&self, pool: &SqlitePool, token_hash: &str) -> Result<Option<UserSession>> {
+        let row = sqlx::query_as::<_, UserSessionRow>(
+            "SELECT id, user_id, token_hash, ip_address, user_agent, device_type, is_current, last_activity, expires_at, created_at FROM user_sessions WHERE previous_token_hash = ?"
+        )
+        .bind(token_hash)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(|r| r.into_model()))
+    }
+
     async fn find_by_user(&self, pool: &SqlitePool, user_id: Uuid) -> Result<Vec<UserSession>> {
         let rows = sqlx::query_as::<_, UserSessionRow>(
             "SELECT id, user_id, token_hash, ip_address, user_agent, device_type, is_current, last_activity, expires_at, created_at FROM user_sessions WHERE user_id = ? ORDER BY last_activity DESC"
@@ -421,6 +461,19 @@ impl UserSessionRepository for SqliteUserSessionRepository {
         Ok(())
     }
 
+    async fn rotate_token(&self, pool: &SqlitePool, id: Uuid, current_hash: &str, new_hash: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE user_sessions SET previous_token_hash = token_hash, token_hash = ?, last_activity = ? WHERE id = ? AND token_hash = ?"
+        )
+        .bind(new_hash)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id.to_string())
+        .bind(current_hash)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
     async fn delete(&self, pool: &SqlitePool, id: Uuid) -> Result<()> {
         sqlx::query("DELETE FROM user_sessions WHERE id = ?")
             .bind(id.to_string())
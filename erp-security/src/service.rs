@@ -128,15 +128,12 @@ impl SecurityService {
             .map_err(|e| erp_core::Error::validation(&e))?;
         let totp = TOTP::new(&secret);
 
-        if totp.verify(code, 1) {
-            return Ok(true);
+        if let Some(step) = totp.verify_step(code, 1) {
+            return self.two_factor_repo.accept_totp_step(pool, setup.id, step).await;
         }
 
-        if let Some(pos) = setup.backup_codes.iter().position(|c| c == code) {
-            let mut remaining_codes = setup.backup_codes.clone();
-            remaining_codes.remove(pos);
-            self.two_factor_repo.update_backup_codes(pool, setup.id, remaining_codes).await?;
-            return Ok(true);
+        if setup.backup_codes.iter().any(|c| c == code) {
+            return self.two_factor_repo.consume_backup_code(pool, setup.id, &setup.backup_codes, code).await;
         }
 
         Ok(false)
@@ -327,6 +324,27 @@ impl SecurityService {
         self.session_repo.find_by_user(pool, user_id).await
     }
 
+    pub async fn get_session(&self, pool: &SqlitePool, session_id: Uuid) -> Result<Option<UserSession>> {
+        self.session_repo.find_by_id(pool, session_id).await
+    }
+
+    pub async fn find_session_by_token(&self, pool: &SqlitePool, token: &str) -> Result<Option<UserSession>> {
+        self.session_repo.find_by_token(pool, &Self::hash_token(token)).await
+    }
+
+    /// The session whose token was `token` before its latest rotation.
+    pub async fn find_session_by_previous_token(&self, pool: &SqlitePool, token: &str) -> Result<Option<UserSession>> {
+        self.session_repo.find_by_previous_token(pool, &Self::hash_token(token)).await
+    }
+
+    /// Replaces the session's token, keeping the old one's hash to recognise its reuse. Returns false
+    /// if `token` is no longer the session's current token.
+    pub async fn rotate_session_token(&self, pool: &SqlitePool, session_id: Uuid, token: &str, new_token: &str) -> Result<bool> {
+        self.session_repo
+            .rotate_token(pool, session_id, &Self::hash_token(token), &Self::hash_token(new_token))
+            .await
+    }
+
     pub async fn revoke_session(&self, pool: &SqlitePool, user_id: Uuid, session_id: Uuid) -> Result<()> {
         let session = self.session_repo.find_by_id(pool, session_id).await?
             .ok_or_else(|| erp_core::Error::not_found("Session", &session_id.to_string()))?;
@@ -64,19 +64,20 @@ impl TOTP {
     }
 
     pub fn verify(&self, code: &str, allowed_drift: i32) -> bool {
+        self.verify_step(code, allowed_drift).is_some()
+    }
+
+    /// The time step `code` belongs to, if it is valid within `allowed_drift` steps of now.
+    pub fn verify_step(&self, code: &str, allowed_drift: i32) -> Option<u64> {
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
         let counter = timestamp / self.time_step;
 
-        for drift in -allowed_drift..=allowed_drift {
-            let test_counter = (counter as i64 + drift as i64) as u64;
-            if self.generate_code_at_counter(test_counter) == code {
-                return true;
-            }
-        }
-        false
+        (-allowed_drift..=allowed_drift)
+            .map(|drift| (counter as i64 + drift as i64) as u64)
+            .find(|&test_counter| self.generate_code_at_counter(test_counter) == code)
     }
 
     pub fn generate_qr_code_url(secret: &[u8], email: &str, issuer: &str) -> String {
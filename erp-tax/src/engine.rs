@@ -0,0 +1,525 @@
+use chrono::{DateTime, Utc};
+use erp_core::{Address, Currency, Error, Money, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::models::*;
+
+const PPM: i128 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum JurisdictionLevel {
+    Country,
+    State,
+    County,
+    City,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TaxRounding {
+    /// Each line's tax is rounded to the minor unit.
+    #[default]
+    PerLine,
+    /// Tax is accumulated unrounded and rounded once per rate for the whole document.
+    PerInvoice,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxCalculationRequest {
+    pub ship_to: Address,
+    #[serde(default)]
+    pub currency: Currency,
+    pub customer_id: Option<Uuid>,
+    /// A business customer's VAT number; with `seller_country` it triggers reverse charge on
+    /// cross-border VAT.
+    pub customer_vat_id: Option<String>,
+    pub seller_country: Option<String>,
+    /// Overrides each rate's own calculation method when set.
+    pub prices_include_tax: Option<bool>,
+    #[serde(default)]
+    pub rounding: TaxRounding,
+    pub date: Option<DateTime<Utc>>,
+    pub lines: Vec<TaxLineRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxLineRequest {
+    /// Line amount in minor units, net or gross depending on the rates' calculation method.
+    pub amount: i64,
+    pub product_id: Option<Uuid>,
+    pub tax_class_id: Option<Uuid>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedJurisdiction {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub level: JurisdictionLevel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxCalculationResult {
+    pub currency: Currency,
+    pub jurisdictions: Vec<ResolvedJurisdiction>,
+    pub lines: Vec<TaxLineResult>,
+    pub subtotal: Money,
+    pub taxable_amount: Money,
+    pub exempt_amount: Money,
+    pub total_tax: Money,
+    pub total: Money,
+    pub reverse_charge: bool,
+    /// One entry per jurisdiction, rate and product tax class.
+    pub tax_breakdown: Vec<TaxBreakdown>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxLineResult {
+    pub line: usize,
+    pub tax_class_id: Option<Uuid>,
+    pub amount: Money,
+    pub net_amount: Money,
+    pub tax_amount: Money,
+    pub gross_amount: Money,
+    pub taxes: Vec<TaxBreakdown>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxBreakdown {
+    pub jurisdiction_id: Uuid,
+    pub tax_rate_id: Uuid,
+    pub tax_class_id: Option<Uuid>,
+    pub name: String,
+    pub tax_type: TaxType,
+    pub rate: f64,
+    pub compound: bool,
+    pub inclusive: bool,
+    pub taxable_amount: Money,
+    pub tax_amount: Money,
+    pub exempt_amount: Money,
+    pub exemption_id: Option<Uuid>,
+    pub reverse_charge: bool,
+}
+
+pub fn level(jurisdiction: &TaxJurisdiction) -> JurisdictionLevel {
+    if jurisdiction.city.is_some() {
+        JurisdictionLevel::City
+    } else if jurisdiction.county.is_some() {
+        JurisdictionLevel::County
+    } else if jurisdiction.state_code.is_some() {
+        JurisdictionLevel::State
+    } else {
+        JurisdictionLevel::Country
+    }
+}
+
+/// The active jurisdictions whose country, state, city and postal-code range all match the
+/// address, least specific first. Addresses carry no county, so county jurisdictions are
+/// matched through their postal-code range.
+pub fn resolve_jurisdictions<'a>(
+    address: &Address,
+    date: DateTime<Utc>,
+    candidates: &'a [TaxJurisdiction],
+) -> Vec<&'a TaxJurisdiction> {
+    let same = |a: &str, b: &str| a.trim().eq_ignore_ascii_case(b.trim());
+    let mut matched: Vec<&TaxJurisdiction> = candidates
+        .iter()
+        .filter(|j| j.status == erp_core::Status::Active && j.effective_from <= date)
+        .filter(|j| j.effective_to.is_none_or(|to| to > date))
+        .filter(|j| same(&j.country_code, &address.country))
+        .filter(|j| match (&j.state_code, &address.state) {
+            (None, _) => true,
+            (Some(code), Some(state)) => same(code, state),
+            (Some(_), None) => false,
+        })
+        .filter(|j| j.city.as_deref().is_none_or(|city| same(city, &address.city)))
+        .filter(|j| match (&j.postal_code_from, &j.postal_code_to) {
+            (Some(from), Some(to)) => postal_in_range(&address.postal_code, from, to),
+            (Some(from), None) => postal_in_range(&address.postal_code, from, from),
+            (None, _) => j.county.is_none(),
+        })
+        .collect();
+    matched.sort_by(|a, b| level(a).cmp(&level(b)).then_with(|| a.code.cmp(&b.code)));
+    matched
+}
+
+/// Compares the leading characters of the postal code against an inclusive range of the same
+/// length, so `94000`-`94199` covers `94103-1234`.
+fn postal_in_range(postal_code: &str, from: &str, to: &str) -> bool {
+    let code: String = postal_code.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_ascii_uppercase();
+    let width = from.len().max(to.len());
+    if code.len() < width {
+        return false;
+    }
+    let prefix = &code[..width];
+    prefix >= from.to_ascii_uppercase().as_str() && prefix <= to.to_ascii_uppercase().as_str()
+}
+
+/// Percent to parts per million, the only place a rate leaves floating point.
+pub fn rate_ppm(rate: f64) -> i64 {
+    (rate * 10_000.0).round() as i64
+}
+
+/// Divides rounding half away from zero.
+fn round_div(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder.abs() * 2 >= denominator.abs() {
+        quotient + numerator.signum() * denominator.signum()
+    } else {
+        quotient
+    }
+}
+
+fn overflow() -> Error {
+    Error::validation("Tax calculation overflowed; amounts or compound rates are too large")
+}
+
+/// The rates that apply to a line of `tax_class_id`: for each jurisdiction and tax type, the
+/// rates for that class if there are any, otherwise the rates without a class.
+fn applicable_rates<'a>(
+    jurisdictions: &[&TaxJurisdiction],
+    rates: &'a [TaxRate],
+    tax_class_id: Option<Uuid>,
+    date: DateTime<Utc>,
+) -> Vec<&'a TaxRate> {
+    let mut selected = Vec::new();
+    for jurisdiction in jurisdictions {
+        let current: Vec<&TaxRate> = rates
+            .iter()
+            .filter(|r| r.jurisdiction_id == jurisdiction.base.id && r.status == erp_core::Status::Active)
+            .filter(|r| r.effective_from <= date && r.effective_to.is_none_or(|to| to > date))
+            .collect();
+        let mut types: Vec<TaxType> = Vec::new();
+        for rate in &current {
+            if !types.contains(&rate.tax_type) {
+                types.push(rate.tax_type);
+            }
+        }
+        for tax_type in types {
+            let of_type = current.iter().filter(|r| r.tax_type == tax_type);
+            let for_class: Vec<&TaxRate> = of_type.clone().filter(|r| tax_class_id.is_some() && r.tax_class_id == tax_class_id).copied().collect();
+            if for_class.is_empty() {
+                selected.extend(of_type.filter(|r| r.tax_class_id.is_none()).copied());
+            } else {
+                selected.extend(for_class);
+            }
+        }
+    }
+    let position = |id: Uuid| jurisdictions.iter().position(|j| j.base.id == id).unwrap_or(usize::MAX);
+    // Compound rates go last so they are charged on every simple tax before them.
+    selected.sort_by(|a, b| {
+        a.is_compound
+            .cmp(&b.is_compound)
+            .then(position(a.jurisdiction_id).cmp(&position(b.jurisdiction_id)))
+            .then(a.priority.cmp(&b.priority))
+            .then_with(|| a.code.cmp(&b.code))
+    });
+    selected
+}
+
+/// The certificate, if any, that exempts the customer in `jurisdiction_id` or one of its parents.
+fn covering_exemption<'a>(
+    exemptions: &'a [TaxExemption],
+    jurisdiction_id: Uuid,
+    parents: &HashMap<Uuid, Uuid>,
+    date: DateTime<Utc>,
+) -> Option<&'a TaxExemption> {
+    let mut chain = vec![jurisdiction_id];
+    let mut current = jurisdiction_id;
+    while let Some(parent) = parents.get(&current) {
+        if chain.contains(parent) {
+            break;
+        }
+        chain.push(*parent);
+        current = *parent;
+    }
+    exemptions.iter().find(|e| {
+        e.status == erp_core::Status::Active
+            && e.issue_date <= date
+            && e.expiry_date.is_none_or(|expiry| expiry > date)
+            && e.jurisdiction_id.is_none_or(|id| chain.contains(&id))
+    })
+}
+
+struct RateOutcome<'a> {
+    rate: &'a TaxRate,
+    inclusive: bool,
+    base: i64,
+    /// Tax in millionths of a minor unit.
+    tax_micros: i128,
+    tax: i64,
+    exemption: Option<&'a TaxExemption>,
+    reverse_charge: bool,
+}
+
+struct LineOutcome<'a> {
+    amount: i64,
+    net: i64,
+    tax_class_id: Option<Uuid>,
+    rates: Vec<RateOutcome<'a>>,
+}
+
+/// Checks that a VAT number has the shape of one issued in `country`: that country's prefix
+/// (EL for Greece) followed by 2 to 12 letters and digits, at least one of them a digit.
+fn check_vat_id(vat_id: &str, country: &str) -> Result<()> {
+    let compact: String = vat_id.chars().filter(|c| !matches!(c, ' ' | '.' | '-')).collect::<String>().to_ascii_uppercase();
+    let country = country.trim().to_ascii_uppercase();
+    let prefix = if country == "GR" { "EL" } else { country.as_str() };
+    let valid = compact.strip_prefix(prefix).is_some_and(|number| {
+        (2..=12).contains(&number.len())
+            && number.chars().all(|c| c.is_ascii_alphanumeric())
+            && number.chars().any(|c| c.is_ascii_digit())
+    });
+    if !valid {
+        return Err(Error::validation(format!("{} is not a valid VAT number for {}", vat_id, country)));
+    }
+    Ok(())
+}
+
+/// Works out tax in integer minor units for every line of the request against the candidate
+/// jurisdictions, rates and the customer's exemption certificates.
+pub fn calculate(
+    request: &TaxCalculationRequest,
+    candidates: &[TaxJurisdiction],
+    rates: &[TaxRate],
+    exemptions: &[TaxExemption],
+) -> Result<TaxCalculationResult> {
+    let date = request.date.unwrap_or_else(Utc::now);
+    let jurisdictions = resolve_jurisdictions(&request.ship_to, date, candidates);
+    let parents: HashMap<Uuid, Uuid> = candidates
+        .iter()
+        .filter_map(|j| j.parent_jurisdiction_id.map(|parent| (j.base.id, parent)))
+        .collect();
+    let cross_border = request
+        .seller_country
+        .as_deref()
+        .is_some_and(|seller| !seller.trim().eq_ignore_ascii_case(request.ship_to.country.trim()));
+    let reverse_charge_vat = match request.customer_vat_id.as_deref().filter(|id| !id.trim().is_empty()) {
+        Some(vat_id) if cross_border => {
+            check_vat_id(vat_id, &request.ship_to.country)?;
+            true
+        }
+        _ => false,
+    };
+
+    let mut lines = Vec::with_capacity(request.lines.len());
+    for line in &request.lines {
+        let mut outcomes: Vec<RateOutcome> = applicable_rates(&jurisdictions, rates, line.tax_class_id, date)
+            .into_iter()
+            .map(|rate| {
+                let exemption = covering_exemption(exemptions, rate.jurisdiction_id, &parents, date);
+                RateOutcome {
+                    rate,
+                    inclusive: request
+                        .prices_include_tax
+                        .unwrap_or(matches!(rate.calculation_method, TaxCalculationMethod::Inclusive)),
+                    base: 0,
+                    tax_micros: 0,
+                    tax: 0,
+                    reverse_charge: exemption.is_none() && reverse_charge_vat && rate.tax_type == TaxType::VAT,
+                    exemption,
+                }
+            })
+            .collect();
+
+        // Each charged rate's tax as a fraction of the net amount, over a shared denominator,
+        // so inclusive prices can be split into net and tax exactly.
+        let mut denominator: i128 = PPM;
+        let mut fractions: Vec<i128> = Vec::with_capacity(outcomes.len());
+        for outcome in &outcomes {
+            let ppm = if outcome.exemption.is_some() || outcome.reverse_charge { 0 } else { rate_ppm(outcome.rate.rate) as i128 };
+            if outcome.rate.is_compound {
+                let carried = denominator + fractions.iter().sum::<i128>();
+                denominator = denominator.checked_mul(PPM).ok_or_else(overflow)?;
+                for fraction in fractions.iter_mut() {
+                    *fraction = fraction.checked_mul(PPM).ok_or_else(overflow)?;
+                }
+                fractions.push(ppm.checked_mul(carried).ok_or_else(overflow)?);
+            } else {
+                fractions.push(ppm * denominator / PPM);
+            }
+        }
+        let inclusive: i128 = outcomes.iter().zip(&fractions).filter(|(o, _)| o.inclusive).map(|(_, f)| *f).sum();
+        let net = if inclusive == 0 {
+            line.amount
+        } else {
+            let scaled = (line.amount as i128).checked_mul(denominator).ok_or_else(overflow)?;
+            round_div(scaled, denominator + inclusive) as i64
+        };
+
+        let mut prior_micros: i128 = 0;
+        let mut prior_rounded: i64 = 0;
+        for outcome in outcomes.iter_mut() {
+            let mut base = if outcome.rate.is_compound {
+                match request.rounding {
+                    TaxRounding::PerLine => net + prior_rounded,
+                    TaxRounding::PerInvoice => net + round_div(prior_micros, PPM) as i64,
+                }
+            } else {
+                net
+            };
+            if let Some(threshold) = [&outcome.rate.min_amount, &outcome.rate.max_amount]
+                .into_iter()
+                .flatten()
+                .find(|m| m.currency != request.currency)
+            {
+                return Err(Error::business_rule(format!(
+                    "Tax rate {} has thresholds in {} but the document is in {}",
+                    outcome.rate.code, threshold.currency, request.currency
+                )));
+            }
+            if let Some(max) = &outcome.rate.max_amount {
+                base = base.min(max.amount);
+            }
+            let below_minimum = outcome.rate.min_amount.as_ref().is_some_and(|min| base < min.amount);
+            outcome.base = base;
+            if outcome.exemption.is_some() || outcome.reverse_charge || below_minimum {
+                continue;
+            }
+            let base_micros = if outcome.rate.is_compound && request.rounding == TaxRounding::PerInvoice {
+                net as i128 * PPM + prior_micros
+            } else {
+                base as i128 * PPM
+            };
+            outcome.tax_micros = round_div(base_micros.checked_mul(rate_ppm(outcome.rate.rate) as i128).ok_or_else(overflow)?, PPM);
+            outcome.tax = round_div(outcome.tax_micros, PPM) as i64;
+            prior_micros += outcome.tax_micros;
+            prior_rounded += outcome.tax;
+        }
+
+        // Rounding must not move an inclusive price: net plus the taxes inside it is the amount.
+        if inclusive != 0 && request.rounding == TaxRounding::PerLine {
+            let contained: i64 = outcomes.iter().filter(|o| o.inclusive).map(|o| o.tax).sum();
+            if let Some(last) = outcomes.iter_mut().rev().find(|o| o.inclusive && o.tax_micros != 0) {
+                last.tax += line.amount - net - contained;
+            }
+        }
+        lines.push(LineOutcome { amount: line.amount, net, tax_class_id: line.tax_class_id, rates: outcomes });
+    }
+
+    if request.rounding == TaxRounding::PerInvoice {
+        settle_invoice_rounding(&mut lines);
+    }
+    Ok(summarize(request, &jurisdictions, lines))
+}
+
+/// Rounds each rate once over the whole document and moves the difference onto the line that
+/// carries most of that rate's tax, so lines still add up to the totals.
+fn settle_invoice_rounding(lines: &mut [LineOutcome]) {
+    let mut keys: Vec<(Uuid, Option<Uuid>)> = Vec::new();
+    for line in lines.iter() {
+        for outcome in &line.rates {
+            let key = (outcome.rate.base.id, line.tax_class_id);
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+    for (rate_id, class) in keys {
+        let mut micros: i128 = 0;
+        let mut rounded: i64 = 0;
+        let mut largest: Option<(usize, usize, i128)> = None;
+        for (l, line) in lines.iter().enumerate().filter(|(_, line)| line.tax_class_id == class) {
+            for (r, outcome) in line.rates.iter().enumerate().filter(|(_, o)| o.rate.base.id == rate_id) {
+                micros += outcome.tax_micros;
+                rounded += outcome.tax;
+                if largest.is_none_or(|(_, _, m)| outcome.tax_micros.abs() > m.abs()) {
+                    largest = Some((l, r, outcome.tax_micros));
+                }
+            }
+        }
+        if let Some((l, r, _)) = largest {
+            lines[l].rates[r].tax += round_div(micros, PPM) as i64 - rounded;
+        }
+    }
+
+    let gross: i64 = lines.iter().filter(|l| l.rates.iter().any(|o| o.inclusive)).map(|l| l.amount).sum();
+    let net: i64 = lines.iter().filter(|l| l.rates.iter().any(|o| o.inclusive)).map(|l| l.net).sum();
+    let contained: i64 = lines.iter().flat_map(|l| l.rates.iter()).filter(|o| o.inclusive).map(|o| o.tax).sum();
+    if gross - net != contained {
+        if let Some(outcome) = lines
+            .iter_mut()
+            .flat_map(|l| l.rates.iter_mut())
+            .filter(|o| o.inclusive && o.tax_micros != 0)
+            .max_by_key(|o| o.tax_micros.abs())
+        {
+            outcome.tax += gross - net - contained;
+        }
+    }
+}
+
+fn summarize(request: &TaxCalculationRequest, jurisdictions: &[&TaxJurisdiction], lines: Vec<LineOutcome>) -> TaxCalculationResult {
+    let currency = request.currency.clone();
+    let money = |amount: i64| Money::new(amount, currency.clone());
+    let breakdown = |outcome: &RateOutcome, class: Option<Uuid>| TaxBreakdown {
+        jurisdiction_id: outcome.rate.jurisdiction_id,
+        tax_rate_id: outcome.rate.base.id,
+        tax_class_id: class,
+        name: outcome.rate.name.clone(),
+        tax_type: outcome.rate.tax_type,
+        rate: outcome.rate.rate,
+        compound: outcome.rate.is_compound,
+        inclusive: outcome.inclusive,
+        taxable_amount: money(if outcome.exemption.is_some() { 0 } else { outcome.base }),
+        tax_amount: money(outcome.tax),
+        exempt_amount: money(if outcome.exemption.is_some() { outcome.base } else { 0 }),
+        exemption_id: outcome.exemption.map(|e| e.base.id),
+        reverse_charge: outcome.reverse_charge,
+    };
+
+    let mut summary: Vec<TaxBreakdown> = Vec::new();
+    let mut results = Vec::with_capacity(lines.len());
+    let (mut subtotal, mut taxable, mut exempt, mut total_tax) = (0i64, 0i64, 0i64, 0i64);
+    for (index, line) in lines.iter().enumerate() {
+        let taxes: Vec<TaxBreakdown> = line.rates.iter().map(|o| breakdown(o, line.tax_class_id)).collect();
+        for tax in &taxes {
+            match summary
+                .iter_mut()
+                .find(|s| s.tax_rate_id == tax.tax_rate_id && s.tax_class_id == tax.tax_class_id)
+            {
+                Some(entry) => {
+                    entry.taxable_amount.amount += tax.taxable_amount.amount;
+                    entry.tax_amount.amount += tax.tax_amount.amount;
+                    entry.exempt_amount.amount += tax.exempt_amount.amount;
+                }
+                None => summary.push(tax.clone()),
+            }
+        }
+        let line_tax: i64 = line.rates.iter().map(|o| o.tax).sum();
+        subtotal += line.net;
+        total_tax += line_tax;
+        if !line.rates.is_empty() && line.rates.iter().all(|o| o.exemption.is_some()) {
+            exempt += line.net;
+        } else if line.rates.iter().any(|o| o.exemption.is_none() && !o.reverse_charge) {
+            taxable += line.net;
+        }
+        results.push(TaxLineResult {
+            line: index + 1,
+            tax_class_id: line.tax_class_id,
+            amount: money(line.amount),
+            net_amount: money(line.net),
+            tax_amount: money(line_tax),
+            gross_amount: money(line.net + line_tax),
+            taxes,
+        });
+    }
+
+    TaxCalculationResult {
+        currency: currency.clone(),
+        jurisdictions: jurisdictions
+            .iter()
+            .map(|j| ResolvedJurisdiction { id: j.base.id, code: j.code.clone(), name: j.name.clone(), level: level(j) })
+            .collect(),
+        lines: results,
+        subtotal: money(subtotal),
+        taxable_amount: money(taxable),
+        exempt_amount: money(exempt),
+        total_tax: money(total_tax),
+        total: money(subtotal + total_tax),
+        reverse_charge: summary.iter().any(|s| s.reverse_charge),
+        tax_breakdown: summary,
+    }
+}
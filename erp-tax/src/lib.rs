@@ -1,6 +1,11 @@
+pub mod engine;
 pub mod models;
 pub mod repository;
 pub mod service;
+pub use engine::{
+    JurisdictionLevel, ResolvedJurisdiction, TaxBreakdown, TaxCalculationRequest, TaxCalculationResult, TaxLineRequest,
+    TaxLineResult, TaxRounding,
+};
 pub use models::*;
 pub use repository::*;
 pub use service::*;
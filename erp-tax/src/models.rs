@@ -3,7 +3,7 @@ use erp_core::{BaseEntity, Money, Status};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum TaxType {
     SalesTax,
@@ -58,6 +58,7 @@ pub struct TaxRate {
     pub priority: i32,
     pub min_amount: Option<Money>,
     pub max_amount: Option<Money>,
+    pub tax_class_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,6 +135,7 @@ pub struct TaxTransaction {
     pub tax_amount: Money,
     pub exemption_id: Option<Uuid>,
     pub exempt_amount: Money,
+    pub reverse_charge: bool,
     pub source: TaxTransactionSource,
     pub external_id: Option<String>,
 }
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Money, Currency};
 use crate::models::*;
 use uuid::Uuid;
@@ -8,6 +8,7 @@ use uuid::Uuid;
 pub trait TaxJurisdictionRepository: Send + Sync {
     async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<TaxJurisdiction>;
     async fn find_all(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<TaxJurisdiction>>;
+    async fn find_by_country(&self, pool: &SqlitePool, country_code: &str) -> Result<Vec<TaxJurisdiction>>;
     async fn create(&self, pool: &SqlitePool, jurisdiction: TaxJurisdiction) -> Result<TaxJurisdiction>;
 }
 
@@ -47,6 +48,18 @@ impl TaxJurisdictionRepository for SqliteTaxJurisdictionRepository {
         Ok(Paginated::new(rows.into_iter().map(|r| r.into()).collect(), count as u64, pagination))
     }
     
+    async fn find_by_country(&self, pool: &SqlitePool, country_code: &str) -> Result<Vec<TaxJurisdiction>> {
+        let rows = sqlx::query_as::<_, TaxJurisdictionRow>(
+            "SELECT * FROM tax_jurisdictions WHERE UPPER(country_code) = UPPER(?) AND status = 'Active'"
+        )
+        .bind(country_code.trim())
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+    
     async fn create(&self, pool: &SqlitePool, jurisdiction: TaxJurisdiction) -> Result<TaxJurisdiction> {
         sqlx::query(
             "INSERT INTO tax_jurisdictions (id, code, name, country_code, state_code, county, city, postal_code_from, postal_code_to, parent_jurisdiction_id, status, effective_from, effective_to, created_at, updated_at)
@@ -150,7 +163,7 @@ impl TaxRateRepository for SqliteTaxRateRepository {
         .map_err(Error::Database)?
         .ok_or_else(|| Error::not_found("TaxRate", &id.to_string()))?;
         
-        Ok(row.into())
+        row.try_into()
     }
     
     async fn find_by_jurisdiction(&self, pool: &SqlitePool, jurisdiction_id: Uuid) -> Result<Vec<TaxRate>> {
@@ -162,7 +175,7 @@ impl TaxRateRepository for SqliteTaxRateRepository {
         .await
         .map_err(Error::Database)?;
         
-        Ok(rows.into_iter().map(|r| r.into()).collect())
+        rows.into_iter().map(TryInto::try_into).collect()
     }
     
     async fn find_all(&self, pool: &SqlitePool, pagination: Pagination) -> Result<Paginated<TaxRate>> {
@@ -181,13 +194,14 @@ impl TaxRateRepository for SqliteTaxRateRepository {
         .await
         .map_err(Error::Database)?;
         
-        Ok(Paginated::new(rows.into_iter().map(|r| r.into()).collect(), count as u64, pagination))
+        let rates = rows.into_iter().map(TryInto::try_into).collect::<Result<_>>()?;
+        Ok(Paginated::new(rates, count as u64, pagination))
     }
     
     async fn create(&self, pool: &SqlitePool, rate: TaxRate) -> Result<TaxRate> {
         sqlx::query(
-            "INSERT INTO tax_rates (id, jurisdiction_id, tax_type, name, code, rate, is_compound, is_recoverable, calculation_method, status, effective_from, effective_to, priority, min_amount, max_amount, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO tax_rates (id, jurisdiction_id, tax_type, name, code, rate, is_compound, is_recoverable, calculation_method, status, effective_from, effective_to, priority, min_amount, max_amount, threshold_currency, tax_class_id, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(rate.base.id.to_string())
         .bind(rate.jurisdiction_id.to_string())
@@ -204,6 +218,8 @@ impl TaxRateRepository for SqliteTaxRateRepository {
         .bind(rate.priority)
         .bind(rate.min_amount.as_ref().map(|m| m.amount))
         .bind(rate.max_amount.as_ref().map(|m| m.amount))
+        .bind(rate.min_amount.as_ref().or(rate.max_amount.as_ref()).map(|m| m.currency.to_string()))
+        .bind(rate.tax_class_id.map(|id| id.to_string()))
         .bind(rate.base.created_at.to_rfc3339())
         .bind(rate.base.updated_at.to_rfc3339())
         .execute(pool)
@@ -246,13 +262,22 @@ struct TaxRateRow {
     priority: i32,
     min_amount: Option<i64>,
     max_amount: Option<i64>,
+    threshold_currency: Option<String>,
+    tax_class_id: Option<String>,
     created_at: String,
     updated_at: String,
 }
 
-impl From<TaxRateRow> for TaxRate {
-    fn from(r: TaxRateRow) -> Self {
-        Self {
+impl TryFrom<TaxRateRow> for TaxRate {
+    type Error = Error;
+
+    fn try_from(r: TaxRateRow) -> Result<Self> {
+        let currency: Currency = match (&r.threshold_currency, r.min_amount.or(r.max_amount)) {
+            (Some(currency), _) => currency.parse().map_err(Error::validation)?,
+            (None, Some(_)) => return Err(Error::validation(format!("Tax rate {} has thresholds but no currency", r.code))),
+            (None, None) => Currency::default(),
+        };
+        Ok(Self {
             base: BaseEntity {
                 id: Uuid::parse_str(&r.id).unwrap_or_default(),
                 created_at: chrono::DateTime::parse_from_rfc3339(&r.created_at)
@@ -292,9 +317,10 @@ impl From<TaxRateRow> for TaxRate {
             effective_to: r.effective_to.and_then(|d| chrono::DateTime::parse_from_rfc3339(&d).ok())
                 .map(|d| d.with_timezone(&chrono::Utc)),
             priority: r.priority,
-            min_amount: r.min_amount.map(|m| Money::new(m, Currency::USD)),
-            max_amount: r.max_amount.map(|m| Money::new(m, Currency::USD)),
-        }
+            min_amount: r.min_amount.map(|m| Money::new(m, currency.clone())),
+            max_amount: r.max_amount.map(|m| Money::new(m, currency)),
+            tax_class_id: r.tax_class_id.and_then(|id| Uuid::parse_str(&id).ok()),
+        })
     }
 }
 
@@ -303,6 +329,7 @@ pub trait TaxTransactionRepository: Send + Sync {
     async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<TaxTransaction>;
     async fn find_by_reference(&self, pool: &SqlitePool, transaction_type: &str, transaction_id: Uuid) -> Result<Vec<TaxTransaction>>;
     async fn create(&self, pool: &SqlitePool, transaction: TaxTransaction) -> Result<TaxTransaction>;
+    async fn delete_by_reference(&self, pool: &SqlitePool, transaction_type: &str, transaction_id: Uuid) -> Result<u64>;
 }
 
 pub struct SqliteTaxTransactionRepository;
@@ -336,9 +363,19 @@ impl TaxTransactionRepository for SqliteTaxTransactionRepository {
     }
     
     async fn create(&self, pool: &SqlitePool, transaction: TaxTransaction) -> Result<TaxTransaction> {
+        self.create_in(&mut *pool.acquire().await?, transaction).await
+    }
+    
+    async fn delete_by_reference(&self, pool: &SqlitePool, transaction_type: &str, transaction_id: Uuid) -> Result<u64> {
+        self.delete_by_reference_in(&mut *pool.acquire().await?, transaction_type, transaction_id).await
+    }
+}
+
+impl SqliteTaxTransactionRepository {
+    pub async fn create_in(&self, conn: &mut SqliteConnection, transaction: TaxTransaction) -> Result<TaxTransaction> {
         sqlx::query(
-            "INSERT INTO tax_transactions (id, transaction_type, transaction_id, transaction_date, customer_id, jurisdiction_id, tax_rate_id, tax_class_id, tax_type, taxable_amount, tax_rate, tax_amount, exemption_id, exempt_amount, source, external_id, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO tax_transactions (id, transaction_type, transaction_id, transaction_date, customer_id, jurisdiction_id, tax_rate_id, tax_class_id, tax_type, taxable_amount, tax_rate, tax_amount, exemption_id, exempt_amount, currency, reverse_charge, source, external_id, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(transaction.base.id.to_string())
         .bind(&transaction.transaction_type)
@@ -354,16 +391,29 @@ impl TaxTransactionRepository for SqliteTaxTransactionRepository {
         .bind(transaction.tax_amount.amount)
         .bind(transaction.exemption_id.map(|id| id.to_string()))
         .bind(transaction.exempt_amount.amount)
+        .bind(transaction.tax_amount.currency.to_string())
+        .bind(transaction.reverse_charge as i32)
         .bind(format!("{:?}", transaction.source))
         .bind(&transaction.external_id)
         .bind(transaction.base.created_at.to_rfc3339())
         .bind(transaction.base.updated_at.to_rfc3339())
-        .execute(pool)
+        .execute(&mut *conn)
         .await
         .map_err(Error::Database)?;
         
         Ok(transaction)
     }
+    
+    pub async fn delete_by_reference_in(&self, conn: &mut SqliteConnection, transaction_type: &str, transaction_id: Uuid) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM tax_transactions WHERE transaction_type = ? AND transaction_id = ?")
+            .bind(transaction_type)
+            .bind(transaction_id.to_string())
+            .execute(&mut *conn)
+            .await
+            .map_err(Error::Database)?;
+        
+        Ok(result.rows_affected())
+    }
 }
 
 #[derive(sqlx::FromRow)]
@@ -382,6 +432,8 @@ struct TaxTransactionRow {
     tax_amount: i64,
     exemption_id: Option<String>,
     exempt_amount: i64,
+    currency: String,
+    reverse_charge: i32,
     source: String,
     external_id: Option<String>,
     created_at: String,
@@ -390,6 +442,7 @@ struct TaxTransactionRow {
 
 impl From<TaxTransactionRow> for TaxTransaction {
     fn from(r: TaxTransactionRow) -> Self {
+        let currency: Currency = r.currency.parse().unwrap_or_default();
         Self {
             base: BaseEntity {
                 id: Uuid::parse_str(&r.id).unwrap_or_default(),
@@ -418,11 +471,12 @@ impl From<TaxTransactionRow> for TaxTransaction {
                 "Custom" => TaxType::Custom,
                 _ => TaxType::SalesTax,
             },
-            taxable_amount: Money::new(r.taxable_amount, Currency::USD),
+            taxable_amount: Money::new(r.taxable_amount, currency.clone()),
             tax_rate: r.tax_rate,
-            tax_amount: Money::new(r.tax_amount, Currency::USD),
+            tax_amount: Money::new(r.tax_amount, currency.clone()),
             exemption_id: r.exemption_id.and_then(|id| Uuid::parse_str(&id).ok()),
-            exempt_amount: Money::new(r.exempt_amount, Currency::USD),
+            exempt_amount: Money::new(r.exempt_amount, currency),
+            reverse_charge: r.reverse_charge != 0,
             source: match r.source.as_str() {
                 "SalesOrder" => TaxTransactionSource::SalesOrder,
                 "Invoice" => TaxTransactionSource::Invoice,
@@ -552,3 +606,129 @@ impl From<TaxExemptionRow> for TaxExemption {
         }
     }
 }
+
+#[async_trait]
+pub trait TaxClassRepository: Send + Sync {
+    async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<TaxClass>;
+    async fn find_all(&self, pool: &SqlitePool) -> Result<Vec<TaxClass>>;
+    async fn create(&self, pool: &SqlitePool, class: TaxClass) -> Result<TaxClass>;
+    async fn find_product_class(&self, pool: &SqlitePool, product_id: Uuid) -> Result<Option<Uuid>>;
+    async fn set_product_class(&self, pool: &SqlitePool, product_id: Uuid, class_id: Option<Uuid>) -> Result<()>;
+}
+
+pub struct SqliteTaxClassRepository;
+
+#[async_trait]
+impl TaxClassRepository for SqliteTaxClassRepository {
+    async fn find_by_id(&self, pool: &SqlitePool, id: Uuid) -> Result<TaxClass> {
+        let row = sqlx::query_as::<_, TaxClassRow>(
+            "SELECT * FROM tax_classes WHERE id = ?"
+        )
+        .bind(id.to_string())
+        .fetch_optional(pool)
+        .await
+        .map_err(Error::Database)?
+        .ok_or_else(|| Error::not_found("TaxClass", &id.to_string()))?;
+        
+        Ok(row.into())
+    }
+    
+    async fn find_all(&self, pool: &SqlitePool) -> Result<Vec<TaxClass>> {
+        let rows = sqlx::query_as::<_, TaxClassRow>(
+            "SELECT * FROM tax_classes ORDER BY code"
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+    
+    async fn create(&self, pool: &SqlitePool, class: TaxClass) -> Result<TaxClass> {
+        sqlx::query(
+            "INSERT INTO tax_classes (id, name, code, description, class_type, status, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(class.base.id.to_string())
+        .bind(&class.name)
+        .bind(&class.code)
+        .bind(&class.description)
+        .bind(format!("{:?}", class.class_type))
+        .bind(format!("{:?}", class.status))
+        .bind(class.base.created_at.to_rfc3339())
+        .bind(class.base.updated_at.to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(Error::Database)?;
+        
+        Ok(class)
+    }
+    
+    async fn find_product_class(&self, pool: &SqlitePool, product_id: Uuid) -> Result<Option<Uuid>> {
+        let class: Option<Option<String>> = sqlx::query_scalar("SELECT tax_class_id FROM products WHERE id = ?")
+            .bind(product_id.to_string())
+            .fetch_optional(pool)
+            .await
+            .map_err(Error::Database)?;
+        
+        match class {
+            Some(class) => Ok(class.and_then(|id| Uuid::parse_str(&id).ok())),
+            None => Err(Error::not_found("Product", &product_id.to_string())),
+        }
+    }
+    
+    async fn set_product_class(&self, pool: &SqlitePool, product_id: Uuid, class_id: Option<Uuid>) -> Result<()> {
+        let result = sqlx::query("UPDATE products SET tax_class_id = ?, updated_at = ? WHERE id = ?")
+            .bind(class_id.map(|id| id.to_string()))
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(product_id.to_string())
+            .execute(pool)
+            .await
+            .map_err(Error::Database)?;
+        
+        if result.rows_affected() == 0 {
+            return Err(Error::not_found("Product", &product_id.to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct TaxClassRow {
+    id: String,
+    name: String,
+    code: String,
+    description: Option<String>,
+    class_type: String,
+    status: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<TaxClassRow> for TaxClass {
+    fn from(r: TaxClassRow) -> Self {
+        Self {
+            base: BaseEntity {
+                id: Uuid::parse_str(&r.id).unwrap_or_default(),
+                created_at: chrono::DateTime::parse_from_rfc3339(&r.created_at)
+                    .map(|d| d.with_timezone(&chrono::Utc)).unwrap_or_else(|_| chrono::Utc::now()),
+                updated_at: chrono::DateTime::parse_from_rfc3339(&r.updated_at)
+                    .map(|d| d.with_timezone(&chrono::Utc)).unwrap_or_else(|_| chrono::Utc::now()),
+                created_by: None,
+                updated_by: None,
+            },
+            name: r.name,
+            code: r.code,
+            description: r.description,
+            class_type: match r.class_type.as_str() {
+                "Customer" => TaxClassType::Customer,
+                "Both" => TaxClassType::Both,
+                _ => TaxClassType::Product,
+            },
+            status: match r.status.as_str() {
+                "Inactive" => erp_core::Status::Inactive,
+                _ => erp_core::Status::Active,
+            },
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 use chrono::Utc;
 use erp_core::{Error, Result, Pagination, Paginated, BaseEntity, Money, Currency};
-use serde::Serialize;
+use crate::engine::{self, TaxBreakdown, TaxCalculationRequest, TaxCalculationResult};
 use crate::models::*;
 use crate::repository::*;
 
@@ -28,6 +28,19 @@ impl TaxJurisdictionService {
         if jurisdiction.code.is_empty() || jurisdiction.name.is_empty() {
             return Err(Error::validation("Jurisdiction code and name are required"));
         }
+        match (&jurisdiction.postal_code_from, &jurisdiction.postal_code_to) {
+            (Some(from), Some(to)) if from.len() != to.len() || from > to => {
+                return Err(Error::validation("Postal code range must run from low to high with equal lengths"));
+            }
+            (None, Some(_)) => return Err(Error::validation("Postal code range needs a start")),
+            (None, None) if jurisdiction.county.is_some() => {
+                return Err(Error::validation("County jurisdictions need a postal code range"));
+            }
+            _ => {}
+        }
+        if let Some(parent) = jurisdiction.parent_jurisdiction_id {
+            self.repo.find_by_id(pool, parent).await?;
+        }
         jurisdiction.base = BaseEntity::new();
         jurisdiction.status = erp_core::Status::Active;
         self.repo.create(pool, jurisdiction).await
@@ -63,6 +76,18 @@ impl TaxRateService {
         if rate.rate < 0.0 || rate.rate > 100.0 {
             return Err(Error::validation("Tax rate must be between 0 and 100"));
         }
+        if (rate.rate * 10_000.0 - engine::rate_ppm(rate.rate) as f64).abs() > 1e-6 {
+            return Err(Error::validation("Tax rate may have at most four decimal places"));
+        }
+        if let (Some(min), Some(max)) = (&rate.min_amount, &rate.max_amount) {
+            if min.currency != max.currency {
+                return Err(Error::validation("Minimum and maximum taxable amounts must be in the same currency"));
+            }
+        }
+        SqliteTaxJurisdictionRepository.find_by_id(pool, rate.jurisdiction_id).await?;
+        if let Some(class_id) = rate.tax_class_id {
+            SqliteTaxClassRepository.find_by_id(pool, class_id).await?;
+        }
         rate.base = BaseEntity::new();
         rate.status = erp_core::Status::Active;
         self.repo.create(pool, rate).await
@@ -74,6 +99,40 @@ impl TaxRateService {
     }
 }
 
+pub struct TaxClassService { repo: SqliteTaxClassRepository }
+impl Default for TaxClassService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaxClassService {
+    pub fn new() -> Self { Self { repo: SqliteTaxClassRepository } }
+    
+    pub async fn list(&self, pool: &SqlitePool) -> Result<Vec<TaxClass>> {
+        self.repo.find_all(pool).await
+    }
+    
+    pub async fn create(&self, pool: &SqlitePool, mut class: TaxClass) -> Result<TaxClass> {
+        if class.name.is_empty() || class.code.is_empty() {
+            return Err(Error::validation("Tax class name and code are required"));
+        }
+        class.base = BaseEntity::new();
+        class.status = erp_core::Status::Active;
+        self.repo.create(pool, class).await
+    }
+    
+    pub async fn assign_to_product(&self, pool: &SqlitePool, product_id: Uuid, class_id: Option<Uuid>) -> Result<()> {
+        if let Some(class_id) = class_id {
+            let class = self.repo.find_by_id(pool, class_id).await?;
+            if matches!(class.class_type, TaxClassType::Customer) {
+                return Err(Error::validation(format!("Tax class {} is a customer class", class.code)));
+            }
+        }
+        self.repo.set_product_class(pool, product_id, class_id).await
+    }
+}
+
 pub struct TaxCalculationService;
 impl Default for TaxCalculationService {
     fn default() -> Self {
@@ -84,90 +143,67 @@ impl Default for TaxCalculationService {
 impl TaxCalculationService {
     pub fn new() -> Self { Self }
     
-    pub async fn calculate(
-        pool: &SqlitePool,
-        jurisdiction_id: Uuid,
-        amount: i64,
-        customer_id: Option<Uuid>,
-        _product_tax_class_id: Option<Uuid>,
-    ) -> Result<TaxCalculationResult> {
-        let rates_repo = SqliteTaxRateRepository;
-        let rates = rates_repo.find_by_jurisdiction(pool, jurisdiction_id).await?;
-        
-        if rates.is_empty() {
-            return Ok(TaxCalculationResult {
-                taxable_amount: Money::new(amount, Currency::USD),
-                total_tax: Money::zero(Currency::USD),
-                tax_breakdown: vec![],
-            });
+    /// Resolves the jurisdictions for the ship-to address, fills in each line's tax class from
+    /// its product and runs the engine against their rates and the customer's certificates.
+    pub async fn calculate(pool: &SqlitePool, request: &TaxCalculationRequest) -> Result<TaxCalculationResult> {
+        if request.lines.is_empty() {
+            return Err(Error::validation("At least one line is required"));
+        }
+        if request.ship_to.country.trim().is_empty() {
+            return Err(Error::validation("Ship-to country is required"));
         }
         
-        let exemption_amount = if let Some(cid) = customer_id {
-            Self::check_exemption(pool, cid, jurisdiction_id).await?
-        } else {
-            0
-        };
-        
-        let taxable_amount = (amount - exemption_amount).max(0);
-        let mut total_tax = 0i64;
-        let mut tax_breakdown = Vec::new();
+        let classes = SqliteTaxClassRepository;
+        let mut request = request.clone();
+        for line in request.lines.iter_mut() {
+            if line.tax_class_id.is_none() {
+                if let Some(product_id) = line.product_id {
+                    line.tax_class_id = classes.find_product_class(pool, product_id).await?;
+                }
+            }
+        }
         
-        for rate in rates {
-            let tax = ((taxable_amount as f64 * rate.rate / 100.0) * 100.0) as i64;
-            total_tax += tax;
-            
-            tax_breakdown.push(TaxBreakdown {
-                tax_rate_id: rate.base.id,
-                name: rate.name.clone(),
-                rate: rate.rate,
-                taxable_amount: Money::new(taxable_amount, Currency::USD),
-                tax_amount: Money::new(tax, Currency::USD),
-            });
+        let jurisdictions = SqliteTaxJurisdictionRepository.find_by_country(pool, &request.ship_to.country).await?;
+        let date = *request.date.get_or_insert_with(Utc::now);
+        let mut rates = Vec::new();
+        for jurisdiction in engine::resolve_jurisdictions(&request.ship_to, date, &jurisdictions) {
+            rates.extend(SqliteTaxRateRepository.find_by_jurisdiction(pool, jurisdiction.base.id).await?);
         }
+        let exemptions = match request.customer_id {
+            Some(customer_id) => SqliteTaxExemptionRepository.find_by_customer(pool, customer_id).await?,
+            None => Vec::new(),
+        };
         
-        Ok(TaxCalculationResult {
-            taxable_amount: Money::new(taxable_amount, Currency::USD),
-            total_tax: Money::new(total_tax, Currency::USD),
-            tax_breakdown,
-        })
+        engine::calculate(&request, &jurisdictions, &rates, &exemptions)
     }
     
-    async fn check_exemption(pool: &SqlitePool, customer_id: Uuid, jurisdiction_id: Uuid) -> Result<i64> {
-        let exemption_repo = SqliteTaxExemptionRepository;
-        let exemptions = exemption_repo.find_by_customer(pool, customer_id).await?;
-        
-        for exemption in exemptions {
-            if exemption.jurisdiction_id.is_none() || exemption.jurisdiction_id == Some(jurisdiction_id) {
-                if let Some(expiry) = exemption.expiry_date {
-                    if expiry > Utc::now() {
-                        return Ok(i64::MAX);
-                    }
-                } else {
-                    return Ok(i64::MAX);
-                }
-            }
+    /// Calculates and records the result against a document, replacing whatever was recorded
+    /// for it before in the same transaction.
+    pub async fn commit(
+        pool: &SqlitePool,
+        request: &TaxCalculationRequest,
+        transaction_type: &str,
+        transaction_id: Uuid,
+        source: TaxTransactionSource,
+    ) -> Result<(TaxCalculationResult, Vec<TaxTransaction>)> {
+        let result = Self::calculate(pool, request).await?;
+        let transactions = TaxTransactionService::new();
+        let mut tx = pool.begin().await?;
+        transactions.repo.delete_by_reference_in(&mut tx, transaction_type, transaction_id).await?;
+        let date = request.date.unwrap_or_else(Utc::now);
+        let mut recorded = Vec::with_capacity(result.tax_breakdown.len());
+        for tax in &result.tax_breakdown {
+            recorded.push(
+                transactions
+                    .record_in(&mut tx, transaction_type, transaction_id, date, request.customer_id, source.clone(), tax)
+                    .await?,
+            );
         }
-        
-        Ok(0)
+        tx.commit().await?;
+        Ok((result, recorded))
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct TaxCalculationResult {
-    pub taxable_amount: Money,
-    pub total_tax: Money,
-    pub tax_breakdown: Vec<TaxBreakdown>,
-}
-
-#[derive(Debug, Clone, Serialize)]
-pub struct TaxBreakdown {
-    pub tax_rate_id: Uuid,
-    pub name: String,
-    pub rate: f64,
-    pub taxable_amount: Money,
-    pub tax_amount: Money,
-}
-
 pub struct TaxTransactionService { repo: SqliteTaxTransactionRepository }
 impl Default for TaxTransactionService {
     fn default() -> Self {
@@ -184,36 +220,46 @@ impl TaxTransactionService {
         pool: &SqlitePool,
         transaction_type: &str,
         transaction_id: Uuid,
-        jurisdiction_id: Uuid,
-        tax_rate_id: Uuid,
-        taxable_amount: i64,
-        tax_amount: i64,
+        transaction_date: chrono::DateTime<Utc>,
         customer_id: Option<Uuid>,
         source: TaxTransactionSource,
+        tax: &TaxBreakdown,
+    ) -> Result<TaxTransaction> {
+        self.record_in(&mut *pool.acquire().await?, transaction_type, transaction_id, transaction_date, customer_id, source, tax).await
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_in(
+        &self,
+        conn: &mut SqliteConnection,
+        transaction_type: &str,
+        transaction_id: Uuid,
+        transaction_date: chrono::DateTime<Utc>,
+        customer_id: Option<Uuid>,
+        source: TaxTransactionSource,
+        tax: &TaxBreakdown,
     ) -> Result<TaxTransaction> {
-        let rate_repo = SqliteTaxRateRepository;
-        let rate = rate_repo.find_by_id(pool, tax_rate_id).await?;
-        
         let transaction = TaxTransaction {
             base: BaseEntity::new(),
             transaction_type: transaction_type.to_string(),
             transaction_id,
-            transaction_date: Utc::now(),
+            transaction_date,
             customer_id,
-            jurisdiction_id,
-            tax_rate_id,
-            tax_class_id: None,
-            tax_type: rate.tax_type,
-            taxable_amount: Money::new(taxable_amount, Currency::USD),
-            tax_rate: rate.rate,
-            tax_amount: Money::new(tax_amount, Currency::USD),
-            exemption_id: None,
-            exempt_amount: Money::zero(Currency::USD),
+            jurisdiction_id: tax.jurisdiction_id,
+            tax_rate_id: tax.tax_rate_id,
+            tax_class_id: tax.tax_class_id,
+            tax_type: tax.tax_type,
+            taxable_amount: tax.taxable_amount.clone(),
+            tax_rate: tax.rate,
+            tax_amount: tax.tax_amount.clone(),
+            exemption_id: tax.exemption_id,
+            exempt_amount: tax.exempt_amount.clone(),
+            reverse_charge: tax.reverse_charge,
             source,
             external_id: None,
         };
         
-        self.repo.create(pool, transaction).await
+        self.repo.create_in(conn, transaction).await
     }
     
     pub async fn get_by_reference(&self, pool: &SqlitePool, transaction_type: &str, transaction_id: Uuid) -> Result<Vec<TaxTransaction>> {
@@ -266,7 +312,7 @@ impl TaxReportService {
         period_end: chrono::DateTime<Utc>,
     ) -> Result<TaxReport> {
         let total_sales: i64 = sqlx::query_scalar(
-            "SELECT COALESCE(SUM(taxable_amount), 0) FROM tax_transactions WHERE jurisdiction_id = ? AND transaction_date >= ? AND transaction_date <= ?"
+            "SELECT COALESCE(SUM(taxable_amount + exempt_amount), 0) FROM tax_transactions WHERE jurisdiction_id = ? AND transaction_date >= ? AND transaction_date <= ?"
         )
         .bind(jurisdiction_id.to_string())
         .bind(period_start.to_rfc3339())
@@ -276,7 +322,7 @@ impl TaxReportService {
         .map_err(Error::Database)?;
         
         let taxable_sales: i64 = sqlx::query_scalar(
-            "SELECT COALESCE(SUM(taxable_amount), 0) FROM tax_transactions WHERE jurisdiction_id = ? AND transaction_date >= ? AND transaction_date <= ? AND exempt_amount = 0"
+            "SELECT COALESCE(SUM(taxable_amount), 0) FROM tax_transactions WHERE jurisdiction_id = ? AND transaction_date >= ? AND transaction_date <= ?"
         )
         .bind(jurisdiction_id.to_string())
         .bind(period_start.to_rfc3339())
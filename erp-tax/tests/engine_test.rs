@@ -0,0 +1,248 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use erp_core::{Address, BaseEntity, Currency, Status};
+use erp_tax::engine::{calculate, resolve_jurisdictions};
+use erp_tax::*;
+use uuid::Uuid;
+
+fn date() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap()
+}
+
+fn jurisdiction(code: &str, country: &str, state: Option<&str>, county: Option<&str>, city: Option<&str>, postal: Option<(&str, &str)>) -> TaxJurisdiction {
+    TaxJurisdiction {
+        base: BaseEntity::new(),
+        code: code.to_string(),
+        name: code.to_string(),
+        country_code: country.to_string(),
+        state_code: state.map(String::from),
+        county: county.map(String::from),
+        city: city.map(String::from),
+        postal_code_from: postal.map(|p| p.0.to_string()),
+        postal_code_to: postal.map(|p| p.1.to_string()),
+        parent_jurisdiction_id: None,
+        status: Status::Active,
+        effective_from: date() - Duration::days(365),
+        effective_to: None,
+    }
+}
+
+fn rate(jurisdiction: &TaxJurisdiction, code: &str, percent: f64, tax_type: TaxType) -> TaxRate {
+    TaxRate {
+        base: BaseEntity::new(),
+        jurisdiction_id: jurisdiction.base.id,
+        tax_type,
+        name: code.to_string(),
+        code: code.to_string(),
+        rate: percent,
+        is_compound: false,
+        is_recoverable: false,
+        calculation_method: TaxCalculationMethod::Exclusive,
+        status: Status::Active,
+        effective_from: date() - Duration::days(30),
+        effective_to: None,
+        priority: 1,
+        min_amount: None,
+        max_amount: None,
+        tax_class_id: None,
+    }
+}
+
+fn address(city: &str, state: Option<&str>, postal: &str, country: &str) -> Address {
+    Address {
+        street: "1 Main St".to_string(),
+        city: city.to_string(),
+        state: state.map(String::from),
+        postal_code: postal.to_string(),
+        country: country.to_string(),
+    }
+}
+
+fn request(ship_to: Address, amounts: &[i64]) -> TaxCalculationRequest {
+    TaxCalculationRequest {
+        ship_to,
+        currency: Currency::USD,
+        customer_id: None,
+        customer_vat_id: None,
+        seller_country: None,
+        prices_include_tax: None,
+        rounding: TaxRounding::PerLine,
+        date: Some(date()),
+        lines: amounts
+            .iter()
+            .map(|amount| TaxLineRequest { amount: *amount, product_id: None, tax_class_id: None, description: None })
+            .collect(),
+    }
+}
+
+fn us_jurisdictions() -> Vec<TaxJurisdiction> {
+    let us = jurisdiction("US", "US", None, None, None, None);
+    let mut ca = jurisdiction("US-CA", "US", Some("CA"), None, None, None);
+    ca.parent_jurisdiction_id = Some(us.base.id);
+    let mut county = jurisdiction("US-CA-SF", "US", Some("CA"), Some("San Francisco"), None, Some(("94100", "94199")));
+    county.parent_jurisdiction_id = Some(ca.base.id);
+    let mut city = jurisdiction("US-CA-SF-CITY", "US", Some("CA"), None, Some("San Francisco"), None);
+    city.parent_jurisdiction_id = Some(county.base.id);
+    let oakland = jurisdiction("US-CA-OAK", "US", Some("CA"), None, Some("Oakland"), None);
+    let ny = jurisdiction("US-NY", "US", Some("NY"), None, None, None);
+    vec![us, ca, county, city, oakland, ny]
+}
+
+#[test]
+fn resolves_jurisdictions_from_the_ship_to_address() {
+    let all = us_jurisdictions();
+    let resolved = resolve_jurisdictions(&address("san francisco", Some("ca"), "94103-1234", "us"), date(), &all);
+    let codes: Vec<&str> = resolved.iter().map(|j| j.code.as_str()).collect();
+    assert_eq!(codes, ["US", "US-CA", "US-CA-SF", "US-CA-SF-CITY"]);
+
+    let outside = resolve_jurisdictions(&address("Fresno", Some("CA"), "93701", "US"), date(), &all);
+    let codes: Vec<&str> = outside.iter().map(|j| j.code.as_str()).collect();
+    assert_eq!(codes, ["US", "US-CA"]);
+}
+
+#[test]
+fn stacks_rates_and_prefers_the_product_class_rate() {
+    let all = us_jurisdictions();
+    let clothing = Uuid::new_v4();
+    let state = rate(&all[1], "CA-STATE", 6.25, TaxType::SalesTax);
+    let mut state_clothing = rate(&all[1], "CA-CLOTHING", 0.0, TaxType::SalesTax);
+    state_clothing.tax_class_id = Some(clothing);
+    let county = rate(&all[2], "SF-COUNTY", 1.375, TaxType::SalesTax);
+    let rates = vec![state, state_clothing, county];
+
+    let mut req = request(address("San Francisco", Some("CA"), "94103", "US"), &[10_000, 10_000]);
+    req.lines[1].tax_class_id = Some(clothing);
+    let result = calculate(&req, &all, &rates, &[]).unwrap();
+
+    assert_eq!(result.lines[0].tax_amount.amount, 625 + 138);
+    assert_eq!(result.lines[1].tax_amount.amount, 138);
+    assert_eq!(result.total_tax.amount, 901);
+    assert_eq!(result.total.amount, 20_901);
+    assert_eq!(result.tax_breakdown.len(), 4);
+}
+
+#[test]
+fn compound_and_inclusive_rates_use_integer_minor_units() {
+    let canada = jurisdiction("CA", "CA", None, None, None, None);
+    let quebec = jurisdiction("CA-QC", "CA", Some("QC"), None, None, None);
+    let all = vec![canada.clone(), quebec.clone()];
+    let gst = rate(&canada, "GST", 5.0, TaxType::GST);
+    let mut qst = rate(&quebec, "QST", 9.975, TaxType::PST);
+    qst.is_compound = true;
+    let rates = vec![qst, gst];
+
+    let mut req = request(address("Montreal", Some("QC"), "H2X 1Y4", "CA"), &[10_000]);
+    req.currency = Currency::CAD;
+    let result = calculate(&req, &all, &rates, &[]).unwrap();
+    let taxes: Vec<(&str, i64)> = result.lines[0].taxes.iter().map(|t| (t.name.as_str(), t.tax_amount.amount)).collect();
+    assert_eq!(taxes, [("GST", 500), ("QST", 1047)]);
+    assert_eq!(result.total_tax.currency, Currency::CAD);
+
+    req.prices_include_tax = Some(true);
+    req.lines[0].amount = 11_547;
+    let result = calculate(&req, &all, &rates, &[]).unwrap();
+    let line = &result.lines[0];
+    assert_eq!(line.net_amount.amount, 10_000);
+    assert_eq!(line.gross_amount.amount, 11_547);
+    assert_eq!(line.net_amount.amount + line.tax_amount.amount, line.amount.amount);
+}
+
+#[test]
+fn inclusive_rounding_never_changes_the_price() {
+    let de = jurisdiction("DE", "DE", None, None, None, None);
+    let mut vat = rate(&de, "DE-VAT", 19.0, TaxType::VAT);
+    vat.calculation_method = TaxCalculationMethod::Inclusive;
+    let all = vec![de];
+    for gross in [1, 99, 119, 1_000, 1_999, 12_345] {
+        let req = request(address("Berlin", None, "10115", "DE"), &[gross]);
+        let result = calculate(&req, &all, std::slice::from_ref(&vat), &[]).unwrap();
+        assert_eq!(result.total.amount, gross, "gross {}", gross);
+    }
+}
+
+#[test]
+fn cross_border_business_sales_reverse_charge_vat() {
+    let fr = jurisdiction("FR", "FR", None, None, None, None);
+    let vat = rate(&fr, "FR-VAT", 20.0, TaxType::VAT);
+    let all = vec![fr];
+    let mut req = request(address("Paris", None, "75001", "FR"), &[50_000]);
+    req.customer_vat_id = Some("FR12345678901".to_string());
+    req.seller_country = Some("DE".to_string());
+
+    let result = calculate(&req, &all, std::slice::from_ref(&vat), &[]).unwrap();
+    assert!(result.reverse_charge);
+    assert_eq!(result.total_tax.amount, 0);
+    assert_eq!(result.tax_breakdown[0].taxable_amount.amount, 50_000);
+
+    req.seller_country = Some("FR".to_string());
+    let result = calculate(&req, &all, std::slice::from_ref(&vat), &[]).unwrap();
+    assert!(!result.reverse_charge);
+    assert_eq!(result.total_tax.amount, 10_000);
+
+    req.seller_country = Some("DE".to_string());
+    for malformed in ["x", "DE123456789", "FRABCDEFGH"] {
+        req.customer_vat_id = Some(malformed.to_string());
+        assert!(matches!(calculate(&req, &all, std::slice::from_ref(&vat), &[]), Err(erp_core::Error::Validation(_))));
+    }
+}
+
+#[test]
+fn rate_thresholds_must_be_in_the_document_currency() {
+    let fr = jurisdiction("FR", "FR", None, None, None, None);
+    let mut vat = rate(&fr, "FR-VAT", 20.0, TaxType::VAT);
+    vat.max_amount = Some(erp_core::Money::new(10_000, Currency::EUR));
+    let all = vec![fr];
+    let mut req = request(address("Paris", None, "75001", "FR"), &[50_000]);
+    assert!(matches!(calculate(&req, &all, std::slice::from_ref(&vat), &[]), Err(erp_core::Error::BusinessRule(_))));
+
+    req.currency = Currency::EUR;
+    let result = calculate(&req, &all, std::slice::from_ref(&vat), &[]).unwrap();
+    assert_eq!(result.total_tax.amount, 2_000);
+}
+
+#[test]
+fn exemption_certificates_cover_child_jurisdictions_until_they_expire() {
+    let all = us_jurisdictions();
+    let rates = vec![rate(&all[1], "CA-STATE", 6.0, TaxType::SalesTax), rate(&all[2], "SF-COUNTY", 1.0, TaxType::SalesTax)];
+    let customer = Uuid::new_v4();
+    let mut certificate = TaxExemption {
+        base: BaseEntity::new(),
+        customer_id: customer,
+        exemption_type: ExemptionType::Resale,
+        certificate_number: "RESALE-1".to_string(),
+        jurisdiction_id: Some(all[1].base.id),
+        issue_date: date() - Duration::days(10),
+        expiry_date: Some(date() + Duration::days(10)),
+        status: Status::Active,
+        document_url: None,
+        notes: None,
+    };
+    let mut req = request(address("San Francisco", Some("CA"), "94103", "US"), &[10_000]);
+    req.customer_id = Some(customer);
+
+    let result = calculate(&req, &all, &rates, std::slice::from_ref(&certificate)).unwrap();
+    assert_eq!(result.total_tax.amount, 0);
+    assert_eq!(result.exempt_amount.amount, 10_000);
+    assert!(result.tax_breakdown.iter().all(|t| t.exemption_id == Some(certificate.base.id)));
+
+    certificate.expiry_date = Some(date() - Duration::days(1));
+    let result = calculate(&req, &all, &rates, std::slice::from_ref(&certificate)).unwrap();
+    assert_eq!(result.total_tax.amount, 700);
+    assert_eq!(result.exempt_amount.amount, 0);
+}
+
+#[test]
+fn invoice_rounding_rounds_once_per_rate() {
+    let us = jurisdiction("US", "US", None, None, None, None);
+    let rates = vec![rate(&us, "FLAT", 5.0, TaxType::SalesTax)];
+    let all = vec![us];
+    let mut req = request(address("Austin", Some("TX"), "78701", "US"), &[10, 10, 10]);
+
+    let per_line = calculate(&req, &all, &rates, &[]).unwrap();
+    assert_eq!(per_line.total_tax.amount, 3);
+
+    req.rounding = TaxRounding::PerInvoice;
+    let per_invoice = calculate(&req, &all, &rates, &[]).unwrap();
+    assert_eq!(per_invoice.total_tax.amount, 2);
+    let line_sum: i64 = per_invoice.lines.iter().map(|l| l.tax_amount.amount).sum();
+    assert_eq!(line_sum, 2);
+}
@@ -0,0 +1,720 @@
+//! Lays print markup out on pages and writes it as a PDF.
+//!
+//! Blocks are first laid out as bands, strips across the page that are never split. Bands are
+//! then dealt onto pages, repeating a table's header rows above its rows wherever it breaks,
+//! and once the page count is known each page gets its own header and footer.
+
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use erp_barcode::symbology::{self, Symbol};
+use erp_barcode::BarcodeType;
+use erp_core::pdf::{self, text_width, Font, Image, PdfWriter};
+use erp_core::{Error, Result};
+
+use crate::markup::{Element, Node};
+
+const LETTER: (f32, f32) = (612.0, 792.0);
+const LINE_HEIGHT: f32 = 1.25;
+const PARAGRAPH_GAP: f32 = 4.0;
+const CELL_PADDING: f32 = 4.0;
+/// Space between the body and the header or footer.
+const HEADER_GAP: f32 = 10.0;
+const RULE: f32 = 0.5;
+const RULE_GRAY: f32 = 0.7;
+const SHADE_GRAY: f32 = 0.9;
+const BARCODE_TEXT_SIZE: f32 = 8.0;
+
+const INLINE: [&str; 6] = ["b", "strong", "span", "br", "page-number", "page-count"];
+
+/// A parsed `<document>`, ready to render.
+#[derive(Debug, Clone)]
+pub struct Document {
+    root: Element,
+    header: Option<Element>,
+    footer: Option<Element>,
+}
+
+impl Document {
+    pub fn parse(markup: &str) -> Result<Self> {
+        let root = Element::parse(markup)?;
+        if root.name != "document" {
+            return Err(Error::validation(format!("Document markup must start with <document>, not <{}>", root.name)));
+        }
+        Ok(Self { header: root.child("header").cloned(), footer: root.child("footer").cloned(), root })
+    }
+
+    /// Uses the blocks in `markup` as the page header, unless the document has its own.
+    pub fn with_header(mut self, markup: &str) -> Result<Self> {
+        if self.header.is_none() {
+            self.header = Some(Element::parse(&format!("<header>{}</header>", markup))?);
+        }
+        Ok(self)
+    }
+
+    /// Uses the blocks in `markup` as the page footer, unless the document has its own.
+    pub fn with_footer(mut self, markup: &str) -> Result<Self> {
+        if self.footer.is_none() {
+            self.footer = Some(Element::parse(&format!("<footer>{}</footer>", markup))?);
+        }
+        Ok(self)
+    }
+
+    /// Checksums of the stored files the document's `blob:` images refer to.
+    pub fn blob_sources(&self) -> Vec<String> {
+        let mut sources: Vec<String> = [Some(&self.root), self.header.as_ref(), self.footer.as_ref()]
+            .into_iter()
+            .flatten()
+            .flat_map(|e| e.descendants())
+            .filter(|e| e.name == "image")
+            .filter_map(|e| e.attribute("src")?.strip_prefix("blob:").map(str::to_string))
+            .collect();
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+
+    /// Renders the document, `blobs` holding the bytes of the files from [`Self::blob_sources`].
+    pub fn render(&self, title: &str, blobs: &HashMap<String, Vec<u8>>) -> Result<Vec<u8>> {
+        let mut size = match self.root.attribute("size").unwrap_or("A4") {
+            "A4" => pdf::A4,
+            "Letter" => LETTER,
+            other => return Err(Error::validation(format!("Unknown page size {:?}, expected A4 or Letter", other))),
+        };
+        if self.root.attribute("orientation") == Some("landscape") {
+            size = (size.1, size.0);
+        }
+        let margin = self.root.number("margin", 40.0)?;
+        let width = size.0 - 2.0 * margin;
+        if width < 50.0 || size.1 - 2.0 * margin < 50.0 {
+            return Err(Error::validation("The page margins leave no room for content"));
+        }
+
+        let mut layout = Layout {
+            font_size: self.root.number("font-size", 10.0)?,
+            blobs,
+            images: Vec::new(),
+            image_sources: HashMap::new(),
+            table_headers: Vec::new(),
+            page: None,
+        };
+        let body = layout.blocks(&self.root, margin, width)?;
+
+        // Page numbers only change a few characters, so one layout measures every page.
+        layout.page = Some((1, 1));
+        let header_height = match &self.header {
+            Some(header) => height(&layout.blocks(header, margin, width)?) + HEADER_GAP,
+            None => 0.0,
+        };
+        let footer_height = match &self.footer {
+            Some(footer) => height(&layout.blocks(footer, margin, width)?) + HEADER_GAP,
+            None => 0.0,
+        };
+        let top = margin + header_height;
+        let bottom = size.1 - margin - footer_height;
+        if bottom - top < 50.0 {
+            return Err(Error::validation("The header and footer leave no room for content"));
+        }
+
+        let mut pages = layout.paginate(body, top, bottom);
+        let count = pages.len();
+        for (index, page) in pages.iter_mut().enumerate() {
+            layout.page = Some((index + 1, count));
+            if let Some(header) = &self.header {
+                place(page, layout.blocks(header, margin, width)?, margin);
+            }
+            if let Some(footer) = &self.footer {
+                let bands = layout.blocks(footer, margin, width)?;
+                let y = size.1 - margin - height(&bands);
+                place(page, bands, y);
+            }
+        }
+
+        let mut writer = PdfWriter::new(size, title);
+        let images: Vec<_> = layout.images.iter().map(|image| writer.add_image(image)).collect();
+        for page in pages {
+            writer.begin_page();
+            for op in page {
+                match op {
+                    Op::Text { x, y, font, size: text_size, text } => writer.text(x, size.1 - y, font, text_size, &text),
+                    Op::Fill { x, y, width, height, gray } => writer.fill_rect(x, size.1 - y - height, width, height, gray),
+                    Op::Image { image, x, y, width, height } => writer.image(images[image], x, size.1 - y - height, width, height),
+                }
+            }
+        }
+        writer.finish();
+        Ok(writer.take_output())
+    }
+}
+
+/// Something to draw, `y` measured down from the top of its band or, once placed, the page.
+#[derive(Debug, Clone)]
+enum Op {
+    /// `y` is the baseline.
+    Text { x: f32, y: f32, font: Font, size: f32, text: String },
+    Fill { x: f32, y: f32, width: f32, height: f32, gray: f32 },
+    Image { image: usize, x: f32, y: f32, width: f32, height: f32 },
+}
+
+impl Op {
+    fn shift(&mut self, dy: f32) {
+        match self {
+            Op::Text { y, .. } | Op::Fill { y, .. } | Op::Image { y, .. } => *y += dy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Band {
+    height: f32,
+    ops: Vec<Op>,
+    /// The table whose header rows go above this band when it starts a page.
+    table: Option<usize>,
+    /// Moves to the next page with the band after it.
+    keep_with_next: bool,
+    page_break: bool,
+}
+
+fn height(bands: &[Band]) -> f32 {
+    bands.iter().map(|b| b.height).sum()
+}
+
+/// Draws `bands` one under another from `y` down.
+fn place(page: &mut Vec<Op>, bands: Vec<Band>, mut y: f32) {
+    for band in bands {
+        page.extend(band.ops.into_iter().map(|mut op| {
+            op.shift(y);
+            op
+        }));
+        y += band.height;
+    }
+}
+
+/// Joins `bands` into one that cannot be split.
+fn merge(bands: Vec<Band>, padding: f32) -> Band {
+    let mut merged = Band { height: padding, ..Default::default() };
+    place(&mut merged.ops, bands.clone(), padding);
+    merged.height += height(&bands) + padding;
+    merged
+}
+
+enum Inline {
+    Text(String, Font),
+    Break,
+}
+
+struct Layout<'a> {
+    font_size: f32,
+    blobs: &'a HashMap<String, Vec<u8>>,
+    images: Vec<Image>,
+    image_sources: HashMap<String, usize>,
+    /// The header rows of each table laid out so far.
+    table_headers: Vec<Vec<Band>>,
+    /// The page number and page count, while laying out a header or footer.
+    page: Option<(usize, usize)>,
+}
+
+impl Layout<'_> {
+    /// Lays out the children of `parent` one under another. Text and inline elements between
+    /// blocks make up paragraphs of their own.
+    fn blocks(&mut self, parent: &Element, x: f32, width: f32) -> Result<Vec<Band>> {
+        let mut bands = Vec::new();
+        let mut inline = Vec::new();
+        for node in &parent.children {
+            let element = match node {
+                Node::Element(e) if !INLINE.contains(&e.name.as_str()) => e,
+                _ => {
+                    inline.push(node.clone());
+                    continue;
+                }
+            };
+            bands.extend(self.paragraph(&std::mem::take(&mut inline), x, width, self.font_size, Font::Regular, "left")?);
+            match element.name.as_str() {
+                "header" | "footer" => {}
+                "h1" | "h2" | "h3" => {
+                    let scale = match element.name.as_str() {
+                        "h1" => 1.8,
+                        "h2" => 1.4,
+                        _ => 1.2,
+                    };
+                    let size = element.number("size", self.font_size * scale)?;
+                    let mut lines = self.paragraph(&element.children, x, width, size, Font::Bold, align(element))?;
+                    if let Some(last) = lines.last_mut() {
+                        last.height += PARAGRAPH_GAP;
+                        last.keep_with_next = true;
+                    }
+                    bands.extend(lines);
+                }
+                "p" => {
+                    let size = element.number("size", self.font_size)?;
+                    let font = if element.flag("bold") { Font::Bold } else { Font::Regular };
+                    let mut lines = self.paragraph(&element.children, x, width, size, font, align(element))?;
+                    if let Some(last) = lines.last_mut() {
+                        last.height += PARAGRAPH_GAP;
+                    }
+                    bands.extend(lines);
+                }
+                "table" => bands.extend(self.table(element, x, width)?),
+                "row" => bands.push(self.row(element, x, width)?),
+                "image" => bands.push(self.image(element, x, width)?),
+                "barcode" => bands.push(self.barcode(element, x, width)?),
+                "spacer" => bands.push(Band { height: element.number("height", self.font_size)?, ..Default::default() }),
+                "hr" => bands.push(Band {
+                    height: PARAGRAPH_GAP * 2.0 + RULE,
+                    ops: vec![Op::Fill { x, y: PARAGRAPH_GAP, width, height: RULE, gray: RULE_GRAY }],
+                    ..Default::default()
+                }),
+                "page-break" => bands.push(Band { page_break: true, ..Default::default() }),
+                other => return Err(Error::validation(format!("Unknown element <{}> in <{}>", other, parent.name))),
+            }
+        }
+        bands.extend(self.paragraph(&inline, x, width, self.font_size, Font::Regular, "left")?);
+        Ok(bands)
+    }
+
+    /// One band per line of wrapped text. Nothing at all for blank text.
+    fn paragraph(&mut self, nodes: &[Node], x: f32, width: f32, size: f32, font: Font, align: &str) -> Result<Vec<Band>> {
+        let mut runs = Vec::new();
+        self.inline(nodes, font, &mut runs)?;
+        let line_height = size * LINE_HEIGHT;
+        Ok(wrap(&runs, size, width)
+            .into_iter()
+            .map(|line| {
+                let line_width: f32 = line.iter().map(|(text, font)| text_width(text, *font, size)).sum();
+                let mut left = match align {
+                    "center" => x + (width - line_width) / 2.0,
+                    "right" => x + width - line_width,
+                    _ => x,
+                };
+                let ops = line
+                    .into_iter()
+                    .map(|(text, font)| {
+                        let op = Op::Text { x: left, y: size, font, size, text: text.clone() };
+                        left += text_width(&text, font, size);
+                        op
+                    })
+                    .collect();
+                Band { height: line_height, ops, ..Default::default() }
+            })
+            .collect())
+    }
+
+    fn inline(&self, nodes: &[Node], font: Font, runs: &mut Vec<Inline>) -> Result<()> {
+        for node in nodes {
+            let element = match node {
+                Node::Text(text) => {
+                    runs.push(Inline::Text(text.clone(), font));
+                    continue;
+                }
+                Node::Element(element) => element,
+            };
+            match element.name.as_str() {
+                "b" | "strong" => self.inline(&element.children, Font::Bold, runs)?,
+                "span" => self.inline(&element.children, font, runs)?,
+                "br" => runs.push(Inline::Break),
+                "page-number" | "page-count" => {
+                    let (number, count) = self.page.ok_or_else(|| {
+                        Error::validation(format!("<{}> can only be used in a header or footer", element.name))
+                    })?;
+                    let value = if element.name == "page-number" { number } else { count };
+                    runs.push(Inline::Text(value.to_string(), font));
+                }
+                other => return Err(Error::validation(format!("<{}> cannot be used inside text", other))),
+            }
+        }
+        Ok(())
+    }
+
+    /// Rows of `<th>` and `<td>` cells, in `<thead>`, `<tbody>` and `<tfoot>` or directly in
+    /// the table. Header rows repeat on every page the table runs onto and footer rows are bold.
+    fn table(&mut self, element: &Element, x: f32, width: f32) -> Result<Vec<Band>> {
+        let mut head = Vec::new();
+        let mut rows = Vec::new();
+        for child in element.elements() {
+            match child.name.as_str() {
+                "thead" => head.extend(child.elements().map(|row| (row, true))),
+                "tbody" => rows.extend(child.elements().map(|row| (row, row.flag("bold")))),
+                "tfoot" => rows.extend(child.elements().map(|row| (row, true))),
+                "tr" => rows.push((child, child.flag("bold"))),
+                other => return Err(Error::validation(format!("Unknown element <{}> in <table>", other))),
+            }
+        }
+        let Some((first, _)) = head.first().or(rows.first()) else { return Ok(Vec::new()) };
+        let widths = first
+            .elements()
+            .map(|cell| Ok((cell.attribute("width"), span(cell)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let columns = columns(&widths, x, width)?;
+
+        let table = self.table_headers.len();
+        let mut header = Vec::new();
+        for (row, _) in head {
+            header.push(self.table_row(row, &columns, true, Some(SHADE_GRAY))?);
+        }
+        if let Some(last) = header.last_mut() {
+            last.keep_with_next = true;
+        }
+        self.table_headers.push(header.clone());
+
+        let mut bands = header;
+        for (row, bold) in rows {
+            let mut band = self.table_row(row, &columns, bold, None)?;
+            band.table = Some(table);
+            bands.push(band);
+        }
+        if let Some(last) = bands.last_mut() {
+            last.height += PARAGRAPH_GAP * 2.0;
+        }
+        Ok(bands)
+    }
+
+    fn table_row(&mut self, row: &Element, columns: &[(f32, f32)], bold: bool, shade: Option<f32>) -> Result<Band> {
+        if row.name != "tr" {
+            return Err(Error::validation(format!("Unknown element <{}> in a table section", row.name)));
+        }
+        let mut cells = Vec::new();
+        let mut column = 0;
+        for cell in row.elements() {
+            if !matches!(cell.name.as_str(), "th" | "td") {
+                return Err(Error::validation(format!("Unknown element <{}> in <tr>", cell.name)));
+            }
+            let span = span(cell)?;
+            let Some(&(left, _)) = columns.get(column) else {
+                return Err(Error::validation("A table row has more cells than the table has columns"));
+            };
+            let cell_width: f32 = columns[column..(column + span).min(columns.len())].iter().map(|c| c.1).sum();
+            column += span;
+            let font = if bold || cell.name == "th" { Font::Bold } else { Font::Regular };
+            let lines = if cell.elements().any(|e| !INLINE.contains(&e.name.as_str())) {
+                self.blocks(cell, left + CELL_PADDING, cell_width - 2.0 * CELL_PADDING)?
+            } else {
+                self.paragraph(&cell.children, left + CELL_PADDING, cell_width - 2.0 * CELL_PADDING, self.font_size, font, align(cell))?
+            };
+            cells.push(merge(lines, CELL_PADDING));
+        }
+
+        let Some(&(x, _)) = columns.first() else {
+            return Err(Error::validation("The first row of a table needs at least one cell"));
+        };
+        let width = columns.iter().map(|c| c.1).sum::<f32>();
+        let row_height = cells.iter().map(|c| c.height).fold(self.font_size * LINE_HEIGHT, f32::max);
+        let mut band = Band { height: row_height, ..Default::default() };
+        if let Some(gray) = shade {
+            band.ops.push(Op::Fill { x, y: 0.0, width, height: row_height, gray });
+        }
+        for cell in cells {
+            band.ops.extend(cell.ops);
+        }
+        band.ops.push(Op::Fill { x, y: row_height - RULE, width, height: RULE, gray: RULE_GRAY });
+        Ok(band)
+    }
+
+    /// `<cell>` blocks side by side, such as the two addresses on an invoice.
+    fn row(&mut self, element: &Element, x: f32, width: f32) -> Result<Band> {
+        let cells: Vec<&Element> = element.elements().collect();
+        if let Some(other) = cells.iter().find(|c| c.name != "cell") {
+            return Err(Error::validation(format!("Unknown element <{}> in <row>", other.name)));
+        }
+        let widths: Vec<_> = cells.iter().map(|c| (c.attribute("width"), 1)).collect();
+        let columns = columns(&widths, x, width)?;
+        let mut band = Band::default();
+        for (cell, (left, cell_width)) in cells.into_iter().zip(columns) {
+            let merged = merge(self.blocks(cell, left, cell_width - CELL_PADDING)?, 0.0);
+            band.height = band.height.max(merged.height);
+            band.ops.extend(merged.ops);
+        }
+        band.height += PARAGRAPH_GAP;
+        Ok(band)
+    }
+
+    fn image(&mut self, element: &Element, x: f32, width: f32) -> Result<Band> {
+        let source = element.attribute("src").filter(|s| !s.is_empty())
+            .ok_or_else(|| Error::validation("<image> needs a src"))?;
+        let index = self.load_image(source)?;
+        let (natural_width, natural_height) = (self.images[index].width as f32, self.images[index].height as f32);
+        let (mut image_width, mut image_height) = match (element.optional_number("width")?, element.optional_number("height")?) {
+            (None, None) => (natural_width, natural_height),
+            (Some(w), None) => (w, w * natural_height / natural_width),
+            (None, Some(h)) => (h * natural_width / natural_height, h),
+            (Some(w), Some(h)) => (w, h),
+        };
+        if image_width > width {
+            image_height *= width / image_width;
+            image_width = width;
+        }
+        let left = aligned(x, width, image_width, align(element));
+        Ok(Band {
+            height: image_height + PARAGRAPH_GAP,
+            ops: vec![Op::Image { image: index, x: left, y: 0.0, width: image_width, height: image_height }],
+            ..Default::default()
+        })
+    }
+
+    /// Decodes an image the first time it is used. Sources are `data:` URIs of base64 data or
+    /// `blob:` followed by the checksum of a stored file.
+    fn load_image(&mut self, source: &str) -> Result<usize> {
+        if let Some(&index) = self.image_sources.get(source) {
+            return Ok(index);
+        }
+        let bytes = if let Some(data) = source.strip_prefix("data:") {
+            let (_, encoded) = data.split_once(";base64,")
+                .ok_or_else(|| Error::validation("Image data URIs must be base64 encoded"))?;
+            BASE64.decode(encoded.trim()).map_err(|_| Error::validation("Image data URI is not valid base64"))?
+        } else if let Some(checksum) = source.strip_prefix("blob:") {
+            self.blobs.get(checksum).cloned().ok_or_else(|| Error::not_found("Blob", checksum))?
+        } else {
+            return Err(Error::validation(format!("Image source {:?} must be a data: or blob: URI", source)));
+        };
+        let image = Image::decode(&bytes)?;
+        if image.width == 0 || image.height == 0 {
+            return Err(Error::validation("Images must not be empty"));
+        }
+        self.images.push(image);
+        self.image_sources.insert(source.to_string(), self.images.len() - 1);
+        Ok(self.images.len() - 1)
+    }
+
+    fn barcode(&mut self, element: &Element, x: f32, width: f32) -> Result<Band> {
+        let name = element.attribute("type").unwrap_or("Code128");
+        let barcode_type: BarcodeType = serde_json::from_value(serde_json::Value::String(name.to_string()))
+            .map_err(|_| Error::validation(format!("Unknown barcode type {:?}", name)))?;
+        let value = element.attribute("value").filter(|v| !v.is_empty())
+            .ok_or_else(|| Error::validation("<barcode> needs a value"))?;
+        let symbol = symbology::encode(&barcode_type, value)?;
+        let quiet = symbol.quiet_zone() as f32;
+
+        let mut band = Band::default();
+        match symbol {
+            Symbol::Linear { modules, text } => {
+                let total = modules.len() as f32 + 2.0 * quiet;
+                let symbol_width = element.number("width", total)?.min(width);
+                let bar_height = element.number("height", 40.0)?;
+                let module = symbol_width / total;
+                let left = aligned(x, width, symbol_width, align(element));
+                for (start, length) in runs(&modules) {
+                    band.ops.push(Op::Fill {
+                        x: left + (quiet + start as f32) * module,
+                        y: 0.0,
+                        width: length as f32 * module,
+                        height: bar_height,
+                        gray: 0.0,
+                    });
+                }
+                band.height = bar_height;
+                if element.attribute("text") != Some("false") {
+                    let text_left = left + (symbol_width - text_width(&text, Font::Regular, BARCODE_TEXT_SIZE)) / 2.0;
+                    band.height += BARCODE_TEXT_SIZE * LINE_HEIGHT;
+                    band.ops.push(Op::Text { x: text_left, y: band.height - 2.0, font: Font::Regular, size: BARCODE_TEXT_SIZE, text });
+                }
+            }
+            Symbol::Matrix { size, modules } => {
+                let total = size as f32 + 2.0 * quiet;
+                let side = element.number("width", element.number("height", 80.0)?)?.min(width);
+                let module = side / total;
+                let left = aligned(x, width, side, align(element));
+                for (y, row) in modules.chunks(size).enumerate() {
+                    for (start, length) in runs(row) {
+                        band.ops.push(Op::Fill {
+                            x: left + (quiet + start as f32) * module,
+                            y: (quiet + y as f32) * module,
+                            width: length as f32 * module,
+                            height: module,
+                            gray: 0.0,
+                        });
+                    }
+                }
+                band.height = side;
+            }
+        }
+        band.height += PARAGRAPH_GAP;
+        Ok(band)
+    }
+
+    /// Deals bands onto pages between `top` and `bottom`.
+    fn paginate(&self, bands: Vec<Band>, top: f32, bottom: f32) -> Vec<Vec<Op>> {
+        let mut pages = vec![Vec::new()];
+        let mut y = top;
+        for (i, band) in bands.iter().enumerate() {
+            if band.page_break {
+                if y > top {
+                    pages.push(Vec::new());
+                    y = top;
+                }
+                continue;
+            }
+            let mut needed = band.height;
+            if band.keep_with_next {
+                needed += bands.get(i + 1).map_or(0.0, |b| b.height);
+            }
+            if y + needed > bottom && y > top {
+                pages.push(Vec::new());
+                y = top;
+                if let Some(table) = band.table {
+                    let header = self.table_headers[table].clone();
+                    let header_height = height(&header);
+                    place(pages.last_mut().expect("a page was just added"), header, y);
+                    y += header_height;
+                }
+            }
+            place(pages.last_mut().expect("there is always a page"), vec![band.clone()], y);
+            y += band.height;
+        }
+        pages
+    }
+}
+
+fn align(element: &Element) -> &str {
+    element.attribute("align").unwrap_or("left")
+}
+
+fn aligned(x: f32, width: f32, item_width: f32, align: &str) -> f32 {
+    match align {
+        "center" => x + (width - item_width) / 2.0,
+        "right" => x + width - item_width,
+        _ => x,
+    }
+}
+
+fn span(cell: &Element) -> Result<usize> {
+    match cell.attribute("colspan") {
+        None => Ok(1),
+        Some(value) => value.parse::<usize>().ok().filter(|n| (1..=50).contains(n))
+            .ok_or_else(|| Error::validation(format!("colspan must be a whole number from 1 to 50, not {:?}", value))),
+    }
+}
+
+/// The left edge and width of each column. Widths are points or percentages of `width`, and
+/// columns without one share what is left.
+fn columns(widths: &[(Option<&str>, usize)], x: f32, width: f32) -> Result<Vec<(f32, f32)>> {
+    let mut fixed = Vec::new();
+    for (value, span) in widths {
+        let points = match value.map(str::trim).filter(|v| !v.is_empty()) {
+            None => None,
+            Some(v) => {
+                let (number, scale) = match v.strip_suffix('%') {
+                    Some(percent) => (percent, width / 100.0),
+                    None => (v.trim_end_matches("pt"), 1.0),
+                };
+                let number = number.trim().parse::<f32>().ok().filter(|n| n.is_finite() && *n > 0.0)
+                    .ok_or_else(|| Error::validation(format!("Column width must be points or a percentage, not {:?}", v)))?;
+                Some(number * scale)
+            }
+        };
+        // A spanning cell's width is split evenly over the columns it covers.
+        fixed.extend(std::iter::repeat_n(points.map(|p| p / *span as f32), *span));
+    }
+    let taken: f32 = fixed.iter().flatten().sum();
+    let flexible = fixed.iter().filter(|w| w.is_none()).count();
+    let share = if flexible > 0 { ((width - taken) / flexible as f32).max(0.0) } else { 0.0 };
+    let scale = if taken + share * flexible as f32 > width { width / (taken + share * flexible as f32) } else { 1.0 };
+    let mut left = x;
+    Ok(fixed
+        .into_iter()
+        .map(|w| {
+            let column_width = w.unwrap_or(share) * scale;
+            left += column_width;
+            (left - column_width, column_width)
+        })
+        .collect())
+}
+
+/// Start and length of each run of dark modules.
+fn runs(modules: &[bool]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (i, &dark) in modules.iter().chain(std::iter::once(&false)).enumerate() {
+        match (dark, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                runs.push((s, i - s));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    runs
+}
+
+/// Breaks runs of text into lines no wider than `width`, at whitespace where possible.
+/// Whitespace is collapsed as in HTML. Each line is its pieces of text and their fonts.
+fn wrap(runs: &[Inline], size: f32, width: f32) -> Vec<Vec<(String, Font)>> {
+    // Words, each possibly in several fonts, with `None` for a forced break.
+    let mut words: Vec<Option<Vec<(char, Font)>>> = Vec::new();
+    let mut word: Vec<(char, Font)> = Vec::new();
+    for run in runs {
+        match run {
+            Inline::Break => {
+                if !word.is_empty() {
+                    words.push(Some(std::mem::take(&mut word)));
+                }
+                words.push(None);
+            }
+            Inline::Text(text, font) => {
+                for c in text.chars() {
+                    if c.is_whitespace() {
+                        if !word.is_empty() {
+                            words.push(Some(std::mem::take(&mut word)));
+                        }
+                    } else {
+                        word.push((c, *font));
+                    }
+                }
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(Some(word));
+    }
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = Vec::new();
+    let mut line: Vec<(char, Font)> = Vec::new();
+    let measure = |chars: &[(char, Font)]| -> f32 {
+        chars.iter().map(|(c, font)| text_width(c.encode_utf8(&mut [0; 4]), *font, size)).sum()
+    };
+    for word in words {
+        let Some(word) = word else {
+            lines.push(std::mem::take(&mut line));
+            continue;
+        };
+        let mut candidate = line.clone();
+        if !candidate.is_empty() {
+            candidate.push((' ', word[0].1));
+        }
+        candidate.extend(&word);
+        if measure(&candidate) <= width {
+            line = candidate;
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        // A word wider than the line is broken wherever it has to be.
+        for c in word {
+            line.push(c);
+            if line.len() > 1 && measure(&line) > width {
+                line.pop();
+                lines.push(std::mem::replace(&mut line, vec![c]));
+            }
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+        .into_iter()
+        .map(|chars| {
+            let mut pieces: Vec<(String, Font)> = Vec::new();
+            for (c, font) in chars {
+                match pieces.last_mut() {
+                    Some((text, last)) if *last == font => text.push(c),
+                    _ => pieces.push((c.to_string(), font)),
+                }
+            }
+            pieces
+        })
+        .collect()
+}
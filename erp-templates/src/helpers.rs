@@ -0,0 +1,96 @@
+//! Handlebars helpers that write numbers, amounts and dates for the reader's locale.
+//!
+//! - `{{format_money total currency}}` takes an amount in hundredths, as amounts are stored.
+//! - `{{format_number quantity 3}}` rounds to the given places, two by default.
+//! - `{{format_date issued_at}}` takes an ISO 8601 date or timestamp.
+//!
+//! The prefix keeps them clear of variables named `number` or `date`.
+
+use chrono::{DateTime, NaiveDate};
+use erp_i18n::LocaleFormat;
+use handlebars::{Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext, RenderErrorReason};
+use serde_json::Value;
+
+pub fn register(handlebars: &mut Handlebars, format: &LocaleFormat) {
+    handlebars.register_helper("format_money", Box::new(Money(format.clone())));
+    handlebars.register_helper("format_number", Box::new(Number(format.clone())));
+    handlebars.register_helper("format_date", Box::new(Date(format.clone())));
+}
+
+struct Money(LocaleFormat);
+struct Number(LocaleFormat);
+struct Date(LocaleFormat);
+
+impl HelperDef for Money {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let Some(amount) = number_param(h, 0, "format_money")? else { return Ok(()) };
+        let currency = h.param(1).and_then(|p| p.value().as_str())
+            .ok_or(RenderErrorReason::ParamNotFoundForIndex("format_money", 1))?;
+        out.write(&self.0.money(amount.round() as i64, currency))?;
+        Ok(())
+    }
+}
+
+impl HelperDef for Number {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let Some(value) = number_param(h, 0, "format_number")? else { return Ok(()) };
+        let decimals = match h.param(1) {
+            Some(p) => p.value().as_u64().filter(|d| *d <= 10)
+                .ok_or(RenderErrorReason::InvalidParamType("a number of decimal places from 0 to 10"))? as usize,
+            None => 2,
+        };
+        out.write(&self.0.number(value, decimals))?;
+        Ok(())
+    }
+}
+
+impl HelperDef for Date {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let value = h.param(0).ok_or(RenderErrorReason::ParamNotFoundForIndex("format_date", 0))?.value();
+        let text = match value {
+            Value::Null => return Ok(()),
+            Value::String(text) => text,
+            _ => return Err(RenderErrorReason::InvalidParamType("an ISO 8601 date").into()),
+        };
+        let date = DateTime::parse_from_rfc3339(text)
+            .map(|t| t.date_naive())
+            .or_else(|_| NaiveDate::parse_from_str(text.get(..10).unwrap_or(text), "%Y-%m-%d"))
+            .map_err(|_| RenderErrorReason::Other(format!("{:?} is not an ISO 8601 date", text)))?;
+        out.write(&self.0.date(date))?;
+        Ok(())
+    }
+}
+
+/// A numeric parameter, which may also be given as a string of digits. Missing values print
+/// nothing.
+fn number_param(h: &Helper, index: usize, helper: &'static str) -> Result<Option<f64>, RenderErrorReason> {
+    match h.param(index).map(|p| p.value()) {
+        None => Err(RenderErrorReason::ParamNotFoundForIndex(helper, index)),
+        Some(Value::Null) => Ok(None),
+        Some(Value::Number(n)) => Ok(n.as_f64()),
+        Some(Value::String(s)) => s.trim().parse().map(Some)
+            .map_err(|_| RenderErrorReason::Other(format!("{} expects a number, not {:?}", helper, s))),
+        Some(_) => Err(RenderErrorReason::InvalidParamType("a number")),
+    }
+}
@@ -1,6 +1,10 @@
+pub mod document;
+pub mod helpers;
+pub mod markup;
 pub mod models;
 pub mod repository;
 pub mod service;
 
+pub use document::Document;
 pub use models::*;
 pub use service::*;
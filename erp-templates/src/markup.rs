@@ -0,0 +1,129 @@
+//! The print markup PDF templates render to: XML with elements for the blocks of a page.
+//!
+//! ```xml
+//! <document size="A4" margin="40">
+//!   <header><p align="right">Page <page-number/> of <page-count/></p></header>
+//!   <h1>Invoice {{number}}</h1>
+//!   <table>
+//!     <thead><tr><th width="60%">Item</th><th align="right">Amount</th></tr></thead>
+//!     <tbody><tr><td>Widget</td><td align="right">{{format_money total currency}}</td></tr></tbody>
+//!   </table>
+//! </document>
+//! ```
+
+use erp_core::{Error, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    /// Parses markup with a single root element. Text is kept as written, whitespace included.
+    pub fn parse(markup: &str) -> Result<Self> {
+        let invalid = |e: quick_xml::Error| Error::validation(format!("Invalid document markup: {}", e));
+        let mut reader = Reader::from_str(markup);
+        let mut stack: Vec<Element> = Vec::new();
+        loop {
+            match reader.read_event().map_err(invalid)? {
+                Event::Start(start) => stack.push(Self::open(&start)?),
+                Event::Empty(start) => {
+                    let element = Self::open(&start)?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(Node::Element(element)),
+                        None => return Ok(element),
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some(current) = stack.last_mut() {
+                        current.children.push(Node::Text(text.unescape().map_err(invalid)?.into_owned()));
+                    }
+                }
+                Event::CData(data) => {
+                    if let Some(current) = stack.last_mut() {
+                        current.children.push(Node::Text(String::from_utf8_lossy(&data).into_owned()));
+                    }
+                }
+                Event::End(_) => {
+                    let element = stack.pop().ok_or_else(|| Error::validation("Invalid document markup: unbalanced end tag"))?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(Node::Element(element)),
+                        None => return Ok(element),
+                    }
+                }
+                Event::Eof => {
+                    return Err(Error::validation("Invalid document markup: it ends before its root element closes"))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn open(start: &BytesStart) -> Result<Self> {
+        let mut attributes = Vec::new();
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(|e| Error::validation(format!("Invalid document markup: {}", e)))?;
+            let value = attribute
+                .unescape_value()
+                .map_err(|e| Error::validation(format!("Invalid document markup: {}", e)))?;
+            attributes.push((String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned(), value.into_owned()));
+        }
+        Ok(Self {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+            attributes,
+            children: Vec::new(),
+        })
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(k, _)| k == name).map(|(_, v)| v.trim())
+    }
+
+    /// A numeric attribute, `default` when it is missing.
+    pub fn number(&self, name: &str, default: f32) -> Result<f32> {
+        Ok(self.optional_number(name)?.unwrap_or(default))
+    }
+
+    pub fn optional_number(&self, name: &str) -> Result<Option<f32>> {
+        match self.attribute(name) {
+            None | Some("") => Ok(None),
+            Some(value) => value.trim_end_matches("pt").parse::<f32>().ok().filter(|n| n.is_finite() && *n > 0.0).map(Some)
+                .ok_or_else(|| Error::validation(format!("<{}> {} must be a number of points, not {:?}", self.name, name, value))),
+        }
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        matches!(self.attribute(name), Some("true" | "yes" | "1"))
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|e| e.name == name)
+    }
+
+    /// Every element below this one, depth first.
+    pub fn descendants(&self) -> Vec<&Element> {
+        let mut found = Vec::new();
+        for child in self.elements() {
+            found.push(child);
+            found.extend(child.descendants());
+        }
+        found
+    }
+}
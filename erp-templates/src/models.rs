@@ -14,6 +14,9 @@ pub enum TemplateType {
     Quote,
     PurchaseOrder,
     PackingSlip,
+    PickList,
+    CreditNote,
+    Payslip,
     Contract,
     Letter,
     SMS,
@@ -1,6 +1,8 @@
 use async_trait::async_trait;
 use chrono::Utc;
-use sqlx::SqlitePool;
+use erp_core::{parse_datetime, parse_datetime_opt, parse_uuid, parse_uuid_opt, BaseEntity};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
 use uuid::Uuid;
 
 use crate::models::*;
@@ -17,21 +19,61 @@ pub trait TemplateRepository: Send + Sync {
 
 pub struct SqliteTemplateRepository;
 
+const TEMPLATE_COLUMNS: &str = "id, name, code, description, template_type, format, subject, body, html_body, variables, \
+    default_values, styles, header_template_id, footer_template_id, version, parent_id, status, created_by, created_at, updated_at";
+
+fn json_column(row: &SqliteRow, column: &str) -> anyhow::Result<Option<serde_json::Value>> {
+    Ok(row.get::<Option<String>, _>(column).map(|text| serde_json::from_str(&text)).transpose()?)
+}
+
+fn template_from_row(row: &SqliteRow) -> anyhow::Result<Template> {
+    let created_by = parse_uuid(&row.get::<String, _>("created_by"), "created_by")?;
+    let created_at = parse_datetime(&row.get::<String, _>("created_at"), "created_at")?;
+    let updated_at = parse_datetime(&row.get::<String, _>("updated_at"), "updated_at")?;
+    Ok(Template {
+        base: BaseEntity {
+            id: parse_uuid(&row.get::<String, _>("id"), "id")?,
+            created_at,
+            updated_at,
+            created_by: Some(created_by),
+            updated_by: None,
+        },
+        name: row.get("name"),
+        code: row.get("code"),
+        description: row.get("description"),
+        template_type: row.try_get("template_type")?,
+        format: row.try_get("format")?,
+        subject: row.get("subject"),
+        body: row.get("body"),
+        html_body: row.get("html_body"),
+        variables: json_column(row, "variables")?,
+        default_values: json_column(row, "default_values")?,
+        styles: row.get("styles"),
+        header_template_id: parse_uuid_opt(row.get::<Option<String>, _>("header_template_id").as_deref(), "header_template_id")?,
+        footer_template_id: parse_uuid_opt(row.get::<Option<String>, _>("footer_template_id").as_deref(), "footer_template_id")?,
+        version: row.get::<Option<i32>, _>("version").unwrap_or(1),
+        parent_id: parse_uuid_opt(row.get::<Option<String>, _>("parent_id").as_deref(), "parent_id")?,
+        status: row.try_get::<Option<erp_core::Status>, _>("status")?.unwrap_or_default(),
+        created_by,
+        created_at,
+        updated_at,
+    })
+}
+
 #[async_trait]
 impl TemplateRepository for SqliteTemplateRepository {
     async fn create(&self, pool: &SqlitePool, template: &Template) -> anyhow::Result<Template> {
         let now = Utc::now();
-        sqlx::query_as::<_, Template>(
+        sqlx::query(
             r#"
             INSERT INTO templates (
                 id, name, code, description, template_type, format, subject,
                 body, html_body, variables, default_values, styles, header_template_id,
                 footer_template_id, version, parent_id, status, created_by, created_at, updated_at
             ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            RETURNING *
             "#,
         )
-        .bind(template.base.id)
+        .bind(template.base.id.to_string())
         .bind(&template.name)
         .bind(&template.code)
         .bind(&template.description)
@@ -40,50 +82,54 @@ impl TemplateRepository for SqliteTemplateRepository {
         .bind(&template.subject)
         .bind(&template.body)
         .bind(&template.html_body)
-        .bind(&template.variables)
-        .bind(&template.default_values)
+        .bind(template.variables.as_ref().map(|v| v.to_string()))
+        .bind(template.default_values.as_ref().map(|v| v.to_string()))
         .bind(&template.styles)
-        .bind(template.header_template_id)
-        .bind(template.footer_template_id)
+        .bind(template.header_template_id.map(|id| id.to_string()))
+        .bind(template.footer_template_id.map(|id| id.to_string()))
         .bind(template.version)
-        .bind(template.parent_id)
+        .bind(template.parent_id.map(|id| id.to_string()))
         .bind(&template.status)
-        .bind(template.created_by)
-        .bind(now)
-        .bind(now)
-        .fetch_one(pool)
-        .await
-        .map_err(Into::into)
+        .bind(template.created_by.to_string())
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(pool)
+        .await?;
+        self.get_by_id(pool, template.base.id).await?
+            .ok_or_else(|| anyhow::anyhow!("Template {} was not saved", template.base.id))
     }
 
     async fn get_by_id(&self, pool: &SqlitePool, id: Uuid) -> anyhow::Result<Option<Template>> {
-        sqlx::query_as::<_, Template>("SELECT * FROM templates WHERE id = ?")
-            .bind(id)
+        sqlx::query(&format!("SELECT {} FROM templates WHERE id = ?", TEMPLATE_COLUMNS))
+            .bind(id.to_string())
             .fetch_optional(pool)
-            .await
-            .map_err(Into::into)
+            .await?
+            .as_ref()
+            .map(template_from_row)
+            .transpose()
     }
 
     async fn get_by_code(&self, pool: &SqlitePool, code: &str) -> anyhow::Result<Option<Template>> {
-        sqlx::query_as::<_, Template>("SELECT * FROM templates WHERE code = ?")
+        sqlx::query(&format!("SELECT {} FROM templates WHERE code = ?", TEMPLATE_COLUMNS))
             .bind(code)
             .fetch_optional(pool)
-            .await
-            .map_err(Into::into)
+            .await?
+            .as_ref()
+            .map(template_from_row)
+            .transpose()
     }
 
     async fn list(&self, pool: &SqlitePool, template_type: Option<TemplateType>) -> anyhow::Result<Vec<Template>> {
-        match template_type {
-            Some(t) => sqlx::query_as::<_, Template>("SELECT * FROM templates WHERE template_type = ? ORDER BY name")
+        let rows = match template_type {
+            Some(t) => sqlx::query(&format!("SELECT {} FROM templates WHERE template_type = ? ORDER BY name", TEMPLATE_COLUMNS))
                 .bind(&t)
                 .fetch_all(pool)
-                .await
-                .map_err(Into::into),
-            None => sqlx::query_as::<_, Template>("SELECT * FROM templates ORDER BY name")
+                .await?,
+            None => sqlx::query(&format!("SELECT {} FROM templates ORDER BY name", TEMPLATE_COLUMNS))
                 .fetch_all(pool)
-                .await
-                .map_err(Into::into),
-        }
+                .await?,
+        };
+        rows.iter().map(template_from_row).collect()
     }
 
     async fn update(&self, pool: &SqlitePool, template: &Template) -> anyhow::Result<()> {
@@ -102,15 +148,15 @@ impl TemplateRepository for SqliteTemplateRepository {
         .bind(&template.subject)
         .bind(&template.body)
         .bind(&template.html_body)
-        .bind(&template.variables)
-        .bind(&template.default_values)
+        .bind(template.variables.as_ref().map(|v| v.to_string()))
+        .bind(template.default_values.as_ref().map(|v| v.to_string()))
         .bind(&template.styles)
-        .bind(template.header_template_id)
-        .bind(template.footer_template_id)
+        .bind(template.header_template_id.map(|id| id.to_string()))
+        .bind(template.footer_template_id.map(|id| id.to_string()))
         .bind(template.version)
         .bind(&template.status)
-        .bind(now)
-        .bind(template.base.id)
+        .bind(now.to_rfc3339())
+        .bind(template.base.id.to_string())
         .execute(pool)
         .await?;
         Ok(())
@@ -118,7 +164,7 @@ impl TemplateRepository for SqliteTemplateRepository {
 
     async fn delete(&self, pool: &SqlitePool, id: Uuid) -> anyhow::Result<()> {
         sqlx::query("DELETE FROM templates WHERE id = ?")
-            .bind(id)
+            .bind(id.to_string())
             .execute(pool)
             .await?;
         Ok(())
@@ -238,62 +284,96 @@ pub trait GeneratedDocumentRepository: Send + Sync {
 
 pub struct SqliteGeneratedDocumentRepository;
 
+const DOCUMENT_COLUMNS: &str = "id, template_id, template_version, name, output_format, content, file_path, file_size, \
+    variables_used, related_entity_type, related_entity_id, generated_by, generated_at, expires_at, created_at";
+
+fn document_from_row(row: &SqliteRow) -> anyhow::Result<GeneratedDocument> {
+    let generated_by = parse_uuid(&row.get::<String, _>("generated_by"), "generated_by")?;
+    let created_at = parse_datetime(&row.get::<String, _>("created_at"), "created_at")?;
+    Ok(GeneratedDocument {
+        base: BaseEntity {
+            id: parse_uuid(&row.get::<String, _>("id"), "id")?,
+            created_at,
+            updated_at: created_at,
+            created_by: Some(generated_by),
+            updated_by: None,
+        },
+        template_id: parse_uuid(&row.get::<String, _>("template_id"), "template_id")?,
+        template_version: row.get("template_version"),
+        name: row.get("name"),
+        output_format: row.try_get("output_format")?,
+        content: row.get("content"),
+        file_path: row.get("file_path"),
+        file_size: row.get("file_size"),
+        variables_used: json_column(row, "variables_used")?,
+        related_entity_type: row.get("related_entity_type"),
+        related_entity_id: parse_uuid_opt(row.get::<Option<String>, _>("related_entity_id").as_deref(), "related_entity_id")?,
+        generated_by,
+        generated_at: parse_datetime(&row.get::<String, _>("generated_at"), "generated_at")?,
+        expires_at: parse_datetime_opt(row.get::<Option<String>, _>("expires_at").as_deref(), "expires_at")?,
+        created_at,
+    })
+}
+
 #[async_trait]
 impl GeneratedDocumentRepository for SqliteGeneratedDocumentRepository {
     async fn create(&self, pool: &SqlitePool, doc: &GeneratedDocument) -> anyhow::Result<GeneratedDocument> {
         let now = Utc::now();
-        sqlx::query_as::<_, GeneratedDocument>(
+        sqlx::query(
             r#"
             INSERT INTO generated_documents (
                 id, template_id, template_version, name, output_format, content,
                 file_path, file_size, variables_used, related_entity_type,
                 related_entity_id, generated_by, generated_at, expires_at, created_at
             ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            RETURNING *
             "#,
         )
-        .bind(doc.base.id)
-        .bind(doc.template_id)
+        .bind(doc.base.id.to_string())
+        .bind(doc.template_id.to_string())
         .bind(doc.template_version)
         .bind(&doc.name)
         .bind(&doc.output_format)
         .bind(&doc.content)
         .bind(&doc.file_path)
         .bind(doc.file_size)
-        .bind(&doc.variables_used)
+        .bind(doc.variables_used.as_ref().map(|v| v.to_string()))
         .bind(&doc.related_entity_type)
-        .bind(doc.related_entity_id)
-        .bind(doc.generated_by)
-        .bind(doc.generated_at)
-        .bind(doc.expires_at)
-        .bind(now)
-        .fetch_one(pool)
-        .await
-        .map_err(Into::into)
+        .bind(doc.related_entity_id.map(|id| id.to_string()))
+        .bind(doc.generated_by.to_string())
+        .bind(doc.generated_at.to_rfc3339())
+        .bind(doc.expires_at.map(|t| t.to_rfc3339()))
+        .bind(now.to_rfc3339())
+        .execute(pool)
+        .await?;
+        self.get_by_id(pool, doc.base.id).await?
+            .ok_or_else(|| anyhow::anyhow!("Generated document {} was not saved", doc.base.id))
     }
 
     async fn get_by_id(&self, pool: &SqlitePool, id: Uuid) -> anyhow::Result<Option<GeneratedDocument>> {
-        sqlx::query_as::<_, GeneratedDocument>("SELECT * FROM generated_documents WHERE id = ?")
-            .bind(id)
+        sqlx::query(&format!("SELECT {} FROM generated_documents WHERE id = ?", DOCUMENT_COLUMNS))
+            .bind(id.to_string())
             .fetch_optional(pool)
-            .await
-            .map_err(Into::into)
+            .await?
+            .as_ref()
+            .map(document_from_row)
+            .transpose()
     }
 
     async fn list_by_entity(&self, pool: &SqlitePool, entity_type: &str, entity_id: Uuid) -> anyhow::Result<Vec<GeneratedDocument>> {
-        sqlx::query_as::<_, GeneratedDocument>(
-            "SELECT * FROM generated_documents WHERE related_entity_type = ? AND related_entity_id = ? ORDER BY created_at DESC"
-        )
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM generated_documents WHERE related_entity_type = ? AND related_entity_id = ? ORDER BY created_at DESC",
+            DOCUMENT_COLUMNS
+        ))
         .bind(entity_type)
-        .bind(entity_id)
+        .bind(entity_id.to_string())
         .fetch_all(pool)
-        .await
-        .map_err(Into::into)
+        .await?;
+        rows.iter().map(document_from_row).collect()
     }
 
     async fn delete(&self, pool: &SqlitePool, id: Uuid) -> anyhow::Result<()> {
         sqlx::query("DELETE FROM generated_documents WHERE id = ?")
-            .bind(id)
+            .bind(id.to_string())
             .execute(pool)
             .await?;
         Ok(())
@@ -1,13 +1,22 @@
+use std::collections::HashMap;
+
 use chrono::Utc;
-use erp_core::BaseEntity;
+use erp_core::{BaseEntity, BlobService, Error, UploadPolicy};
+use erp_i18n::{I18nService, LocaleFormat};
+use futures::TryStreamExt;
 use handlebars::Handlebars;
 use regex::Regex;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
+use crate::document::Document;
+use crate::helpers;
 use crate::models::*;
 use crate::repository::*;
 
+pub const MAX_DOCUMENT_SIZE: u64 = 64 * 1024 * 1024;
+const PDF_MIME_TYPE: &str = "application/pdf";
+
 pub struct TemplateService {
     template_repo: SqliteTemplateRepository,
     document_repo: SqliteGeneratedDocumentRepository,
@@ -89,30 +98,24 @@ impl TemplateService {
     }
 
     pub fn render(&self, template: &Template, variables: &serde_json::Value) -> anyhow::Result<RenderedTemplate> {
-        let mut handlebars = Handlebars::new();
-        handlebars.register_escape_fn(handlebars::no_escape);
-        
-        let mut merged_vars = variables.clone();
-        if let Some(defaults) = &template.default_values {
-            if let (serde_json::Value::Object(vars), serde_json::Value::Object(defs)) = (&mut merged_vars, defaults) {
-                for (key, value) in defs {
-                    if !vars.contains_key(key) {
-                        vars.insert(key.clone(), value.clone());
-                    }
-                }
-            }
-        }
+        self.render_localized(template, variables, &LocaleFormat::default())
+    }
+
+    /// Renders with amounts and dates written for `locale`. Values are escaped for the
+    /// template's format, the HTML body as HTML, and the subject not at all.
+    pub fn render_localized(&self, template: &Template, variables: &serde_json::Value, locale: &LocaleFormat) -> anyhow::Result<RenderedTemplate> {
+        let merged_vars = merged_variables(template, variables);
         
         let subject = if let Some(subj_template) = &template.subject {
-            Some(render_string(subj_template, &merged_vars)?)
+            Some(render_string(subj_template, &merged_vars, handlebars::no_escape, locale)?)
         } else {
             None
         };
         
-        let body = render_string(&template.body, &merged_vars)?;
+        let body = render_string(&template.body, &merged_vars, escape_for(&template.format), locale)?;
         
         let html_body = if let Some(html_template) = &template.html_body {
-            Some(render_string(html_template, &merged_vars)?)
+            Some(render_string(html_template, &merged_vars, handlebars::html_escape, locale)?)
         } else {
             None
         };
@@ -124,35 +127,85 @@ impl TemplateService {
         })
     }
 
-    #[allow(clippy::too_many_arguments)]
+    /// Renders a PDF template, with its header and footer templates, into a finished PDF.
+    pub async fn render_pdf(
+        &self,
+        pool: &SqlitePool,
+        blobs: &BlobService,
+        template: &Template,
+        variables: &serde_json::Value,
+        locale: &LocaleFormat,
+    ) -> anyhow::Result<Vec<u8>> {
+        if !matches!(template.format, TemplateFormat::PDF) {
+            return Err(Error::validation(format!("Template {} does not produce a PDF", template.code)).into());
+        }
+        let rendered = self.render_localized(template, variables, locale)?;
+        let mut document = Document::parse(&rendered.body)?;
+        for (part_id, is_header) in [(template.header_template_id, true), (template.footer_template_id, false)] {
+            let Some(part_id) = part_id else { continue };
+            let part = self.template_repo.get_by_id(pool, part_id).await?
+                .ok_or_else(|| Error::not_found("Template", &part_id.to_string()))?;
+            let part_variables = merged_variables(&part, &merged_variables(template, variables));
+            let markup = render_string(&part.body, &part_variables, handlebars::html_escape, locale)?;
+            document = if is_header { document.with_header(&markup)? } else { document.with_footer(&markup)? };
+        }
+
+        let mut files = HashMap::new();
+        for checksum in document.blob_sources() {
+            let (_, mut stream) = blobs.open(pool, &checksum).await?;
+            let mut bytes = Vec::new();
+            while let Some(chunk) = stream.try_next().await
+                .map_err(|e| Error::internal(format!("Failed to read image {}: {}", checksum, e)))?
+            {
+                bytes.extend_from_slice(&chunk);
+            }
+            files.insert(checksum, bytes);
+        }
+        Ok(document.render(&template.name, &files)?)
+    }
+
+    /// Renders a template and keeps the result. PDFs are stored as files, other formats in
+    /// the document itself.
     pub async fn generate_document(
         &self,
         pool: &SqlitePool,
-        template_id: Uuid,
-        name: String,
-        variables: serde_json::Value,
-        related_entity_type: Option<String>,
-        related_entity_id: Option<Uuid>,
-        generated_by: Uuid,
+        blobs: &BlobService,
+        request: GenerateDocument,
     ) -> anyhow::Result<GeneratedDocument> {
-        let template = self.template_repo.get_by_id(pool, template_id).await?
-            .ok_or_else(|| anyhow::anyhow!("Template not found"))?;
-        
-        let rendered = self.render(&template, &variables)?;
+        let template = self.template_repo.get_by_id(pool, request.template_id).await?
+            .ok_or_else(|| Error::not_found("Template", &request.template_id.to_string()))?;
+        let locale = match &request.locale {
+            Some(code) => {
+                let locale = I18nService::new().get_locale(pool, code).await?
+                    .ok_or_else(|| Error::validation(format!("Unknown locale {}", code)))?;
+                LocaleFormat::from(&locale)
+            }
+            None => LocaleFormat::default(),
+        };
+
+        let (content, file_path, file_size) = if matches!(template.format, TemplateFormat::PDF) {
+            let pdf = self.render_pdf(pool, blobs, &template, &request.variables, &locale).await?;
+            let staged = blobs.stage_bytes(pdf, MAX_DOCUMENT_SIZE).await?;
+            let policy = UploadPolicy { max_size: MAX_DOCUMENT_SIZE, allowed_mime_types: vec![PDF_MIME_TYPE.to_string()] };
+            let blob = blobs.save(pool, staged, PDF_MIME_TYPE, &policy, None).await?;
+            (None, Some(blob.sha256), Some(blob.size))
+        } else {
+            (Some(self.render_localized(&template, &request.variables, &locale)?.body), None, None)
+        };
         
         let doc = GeneratedDocument {
             base: BaseEntity::new(),
-            template_id,
+            template_id: template.base.id,
             template_version: template.version,
-            name,
+            name: request.name,
             output_format: template.format,
-            content: Some(rendered.body),
-            file_path: None,
-            file_size: None,
-            variables_used: Some(variables),
-            related_entity_type,
-            related_entity_id,
-            generated_by,
+            content,
+            file_path,
+            file_size,
+            variables_used: Some(request.variables),
+            related_entity_type: request.related_entity_type,
+            related_entity_id: request.related_entity_id,
+            generated_by: request.generated_by,
             generated_at: Utc::now(),
             expires_at: None,
             created_at: Utc::now(),
@@ -177,18 +230,59 @@ impl TemplateService {
     }
 }
 
+pub struct GenerateDocument {
+    pub template_id: Uuid,
+    pub name: String,
+    pub variables: serde_json::Value,
+    /// Code of the locale to write amounts and dates for, else en-US conventions.
+    pub locale: Option<String>,
+    pub related_entity_type: Option<String>,
+    pub related_entity_id: Option<Uuid>,
+    pub generated_by: Uuid,
+}
+
+#[derive(Debug)]
 pub struct RenderedTemplate {
     pub subject: Option<String>,
     pub body: String,
     pub html_body: Option<String>,
 }
 
-fn render_string(template: &str, variables: &serde_json::Value) -> anyhow::Result<String> {
+/// `variables` with the template's default values filled in where they are missing.
+fn merged_variables(template: &Template, variables: &serde_json::Value) -> serde_json::Value {
+    let mut merged = variables.clone();
+    if let (serde_json::Value::Object(vars), Some(serde_json::Value::Object(defs))) = (&mut merged, &template.default_values) {
+        for (key, value) in defs {
+            if !vars.contains_key(key) {
+                vars.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    merged
+}
+
+fn render_string(template: &str, variables: &serde_json::Value, escape: fn(&str) -> String, locale: &LocaleFormat) -> anyhow::Result<String> {
     let mut handlebars = Handlebars::new();
-    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars.register_escape_fn(escape);
+    helpers::register(&mut handlebars, locale);
     
     handlebars.render_template(template, variables)
-        .map_err(|e| anyhow::anyhow!("Template rendering error: {}", e))
+        .map_err(|e| Error::validation(format!("Template rendering error: {}", e)).into())
+}
+
+/// How values are escaped in a body of the given format. PDFs are rendered from XML markup.
+fn escape_for(format: &TemplateFormat) -> fn(&str) -> String {
+    match format {
+        TemplateFormat::HTML | TemplateFormat::XML | TemplateFormat::PDF => handlebars::html_escape,
+        TemplateFormat::JSON => json_escape,
+        TemplateFormat::PlainText | TemplateFormat::Markdown | TemplateFormat::CSV => handlebars::no_escape,
+    }
+}
+
+/// Escapes a value for use inside a JSON string literal.
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
 }
 
 pub struct EmailTemplateService {
@@ -258,16 +352,17 @@ impl EmailTemplateService {
     }
 
     pub fn render(&self, template: &EmailTemplate, variables: &serde_json::Value) -> anyhow::Result<RenderedEmail> {
-        let subject = render_string(&template.subject_template, variables)?;
+        let locale = LocaleFormat::default();
+        let subject = render_string(&template.subject_template, variables, handlebars::no_escape, &locale)?;
         
         let body_text = if let Some(text) = &template.body_text {
-            Some(render_string(text, variables)?)
+            Some(render_string(text, variables, handlebars::no_escape, &locale)?)
         } else {
             None
         };
         
         let body_html = if let Some(html) = &template.body_html {
-            Some(render_string(html, variables)?)
+            Some(render_string(html, variables, handlebars::html_escape, &locale)?)
         } else {
             None
         };
@@ -0,0 +1,309 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use erp_core::blob::LocalBlobStore;
+use erp_core::{BlobService, Error};
+use erp_i18n::LocaleFormat;
+use erp_templates::*;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::{Compression, Crc};
+use futures::StreamExt;
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::io::{Read, Write};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE blobs (sha256 TEXT PRIMARY KEY, size INTEGER NOT NULL, mime_type TEXT NOT NULL, backend TEXT NOT NULL, ref_count INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL)",
+    r#"CREATE TABLE templates (id TEXT PRIMARY KEY, name TEXT NOT NULL, code TEXT NOT NULL UNIQUE, description TEXT, template_type TEXT NOT NULL,
+        format TEXT NOT NULL, subject TEXT, body TEXT NOT NULL, html_body TEXT, variables TEXT, default_values TEXT, styles TEXT,
+        header_template_id TEXT, footer_template_id TEXT, version INTEGER DEFAULT 1, parent_id TEXT, status TEXT DEFAULT 'Active',
+        created_by TEXT NOT NULL, created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+    r#"CREATE TABLE generated_documents (id TEXT PRIMARY KEY, template_id TEXT NOT NULL, template_version INTEGER NOT NULL, name TEXT NOT NULL,
+        output_format TEXT NOT NULL, content TEXT, file_path TEXT, file_size INTEGER, variables_used TEXT, related_entity_type TEXT,
+        related_entity_id TEXT, generated_by TEXT NOT NULL, generated_at TEXT NOT NULL, expires_at TEXT, created_at TEXT NOT NULL)"#,
+    r#"CREATE TABLE i18n_locales (id TEXT PRIMARY KEY, code TEXT NOT NULL UNIQUE, name TEXT NOT NULL, native_name TEXT NOT NULL,
+        language_code TEXT NOT NULL, country_code TEXT, is_rtl INTEGER NOT NULL DEFAULT 0, date_format TEXT NOT NULL DEFAULT 'YYYY-MM-DD',
+        time_format TEXT NOT NULL DEFAULT 'HH:mm:ss', number_format TEXT NOT NULL DEFAULT '#,##0.00', currency_symbol TEXT NOT NULL DEFAULT '$',
+        currency_position TEXT NOT NULL DEFAULT 'before', decimal_separator TEXT NOT NULL DEFAULT '.', thousand_separator TEXT NOT NULL DEFAULT ',',
+        status TEXT NOT NULL DEFAULT 'Active', is_default INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL)"#,
+    r#"INSERT INTO i18n_locales (id, code, name, native_name, language_code, date_format, currency_position, decimal_separator, thousand_separator, created_at, updated_at)
+        VALUES ('de', 'de-DE', 'German', 'Deutsch', 'de', 'DD.MM.YYYY', 'after', ',', '.', datetime('now'), datetime('now'))"#,
+];
+
+async fn setup() -> (SqlitePool, BlobService) {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    for statement in SCHEMA {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    let root = std::env::temp_dir().join(format!("erp-templates-test-{}", Uuid::new_v4()));
+    let blobs = BlobService::new(Arc::new(LocalBlobStore::new(root.join("blobs"))), root.join("staging"));
+    (pool, blobs)
+}
+
+async fn read_blob(pool: &SqlitePool, blobs: &BlobService, sha256: &str) -> Vec<u8> {
+    let (_, mut stream) = blobs.open(pool, sha256).await.unwrap();
+    let mut out = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        out.extend_from_slice(&chunk.unwrap());
+    }
+    out
+}
+
+async fn create(pool: &SqlitePool, code: &str, format: TemplateFormat, body: &str) -> Template {
+    TemplateService::new()
+        .create(pool, code.into(), code.into(), None, TemplateType::Document, format, Some("Re: {{name}}".into()), body.into(), None, None, Uuid::new_v4())
+        .await
+        .unwrap()
+}
+
+fn validation_message(error: anyhow::Error) -> String {
+    match error.downcast::<Error>() {
+        Ok(Error::Validation(message)) => message,
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+/// The decompressed content stream of each page, in page order. Image streams name their
+/// type first, so only page contents start with their length.
+fn page_contents(pdf: &[u8]) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut rest = pdf;
+    while let Some(start) = find(rest, b"<< /Length ") {
+        rest = &rest[start + 11..];
+        let digits = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+        let length: usize = std::str::from_utf8(&rest[..digits]).unwrap().parse().unwrap();
+        let Some(data) = rest[digits..].strip_prefix(b" /Filter /FlateDecode >>\nstream\n".as_slice()) else { continue };
+        let mut content = Vec::new();
+        ZlibDecoder::new(&data[..length]).read_to_end(&mut content).unwrap();
+        // Text is WinAnsi, so each byte stands for one character.
+        pages.push(content.iter().map(|&b| b as char).collect());
+    }
+    pages
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// A 2 by 2 RGBA image with one transparent pixel.
+fn png() -> Vec<u8> {
+    fn chunk(out: &mut Vec<u8>, kind: &[u8], data: &[u8]) {
+        out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        out.extend_from_slice(kind);
+        out.extend_from_slice(data);
+        let mut crc = Crc::new();
+        crc.update(kind);
+        crc.update(data);
+        out.extend_from_slice(&crc.sum().to_be_bytes());
+    }
+    let mut header = Vec::new();
+    header.extend_from_slice(&2u32.to_be_bytes());
+    header.extend_from_slice(&2u32.to_be_bytes());
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+    let rows = [0, 255, 0, 0, 255, 0, 0, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&rows).unwrap();
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut out, b"IHDR", &header);
+    chunk(&mut out, b"IDAT", &encoder.finish().unwrap());
+    chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+#[tokio::test]
+async fn values_are_escaped_for_the_output_format() {
+    let service = TemplateService::new();
+    let (pool, _) = setup().await;
+    let variables = json!({"name": "<b>\"Tom\" & Jerry</b>"});
+
+    let html = create(&pool, "HTML", TemplateFormat::HTML, "<p>{{name}}</p>").await;
+    let rendered = service.render(&html, &variables).unwrap();
+    assert_eq!(rendered.body, "<p>&lt;b&gt;&quot;Tom&quot; &amp; Jerry&lt;/b&gt;</p>");
+    assert_eq!(rendered.subject.as_deref(), Some("Re: <b>\"Tom\" & Jerry</b>"));
+
+    let text = create(&pool, "TEXT", TemplateFormat::PlainText, "Hello {{name}}").await;
+    assert_eq!(service.render(&text, &variables).unwrap().body, "Hello <b>\"Tom\" & Jerry</b>");
+
+    let json = create(&pool, "JSON", TemplateFormat::JSON, r#"{"name": "{{name}}"}"#).await;
+    let body = service.render(&json, &variables).unwrap().body;
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&body).unwrap()["name"], "<b>\"Tom\" & Jerry</b>");
+
+    // Markup in a value stays text in a PDF rather than becoming an element.
+    let pdf = create(&pool, "PDF", TemplateFormat::PDF, "<document><p>{{name}}</p></document>").await;
+    let bytes = service.render_pdf(&pool, &setup().await.1, &pdf, &variables, &LocaleFormat::default()).await.unwrap();
+    assert!(page_contents(&bytes)[0].contains("(<b>\"Tom\" & Jerry</b>)"));
+
+    let broken = create(&pool, "BROKEN", TemplateFormat::HTML, "{{#each items}}").await;
+    assert!(validation_message(service.render(&broken, &variables).unwrap_err()).contains("Template rendering error"));
+}
+
+#[tokio::test]
+async fn helpers_write_amounts_and_dates_for_the_locale() {
+    let service = TemplateService::new();
+    let (pool, _) = setup().await;
+    let template = create(
+        &pool,
+        "AMOUNTS",
+        TemplateFormat::PlainText,
+        "{{format_money total currency}}|{{format_money refund currency}}|{{format_money total \"JPY\"}}|{{format_money total \"CHF\"}}|{{format_number qty 3}}|{{format_number qty 0}}|{{format_date issued}}|{{format_date due}}|{{format_money missing currency}}",
+    )
+    .await;
+    let variables = json!({
+        "total": 123456789, "refund": -550, "currency": "EUR", "qty": "1234.5678",
+        "issued": "2026-03-01", "due": "2026-03-31T12:00:00Z",
+    });
+
+    let us = service.render(&template, &variables).unwrap().body;
+    assert_eq!(us, "€1,234,567.89|-€5.50|¥1,234,568|CHF 1,234,567.89|1,234.568|1,235|2026-03-01|2026-03-31|");
+
+    let german = LocaleFormat {
+        decimal_separator: ",".into(),
+        thousand_separator: ".".into(),
+        currency_position: "after".into(),
+        date_format: "DD.MM.YYYY".into(),
+    };
+    let de = service.render_localized(&template, &variables, &german).unwrap().body;
+    assert_eq!(de, "1.234.567,89 €|-5,50 €|1.234.568 ¥|1.234.567,89 CHF|1.234,568|1.235|01.03.2026|31.03.2026|");
+
+    let bad = create(&pool, "BAD_DATE", TemplateFormat::PlainText, "{{format_date issued}}").await;
+    let error = service.render(&bad, &json!({"issued": "next tuesday"})).unwrap_err();
+    assert!(validation_message(error).contains("not an ISO 8601 date"));
+}
+
+#[tokio::test]
+async fn long_tables_repeat_their_header_on_every_page() {
+    let (pool, blobs) = setup().await;
+    let rows: String = (1..=150)
+        .map(|i| format!("<tr><td>Item {}</td><td align=\"right\">{{{{format_money {} \"USD\"}}}}</td></tr>", i, i * 1000))
+        .collect();
+    let body = format!(
+        r#"<document size="Letter">
+            <header><p align="right">Page <page-number/> of <page-count/></p><hr/></header>
+            <footer><p align="center" size="8">Acme Corp</p></footer>
+            <h1>Statement</h1>
+            <table>
+              <thead><tr><th width="70%">Description</th><th align="right">Amount</th></tr></thead>
+              <tbody>{}</tbody>
+              <tfoot><tr><td>Total</td><td align="right">{{{{format_money 11325000 "USD"}}}}</td></tr></tfoot>
+            </table>
+            <page-break/>
+            <p>Terms and conditions</p>
+          </document>"#,
+        rows
+    );
+    let template = create(&pool, "STATEMENT", TemplateFormat::PDF, &body).await;
+    let pdf = TemplateService::new().render_pdf(&pool, &blobs, &template, &json!({}), &LocaleFormat::default()).await.unwrap();
+
+    let pages = page_contents(&pdf);
+    let count = pages.len();
+    assert!(count >= 4, "expected the table to run over several pages, got {}", count);
+    assert!(find(&pdf, format!("/Count {}", count).as_bytes()).is_some());
+    for (i, page) in pages.iter().enumerate() {
+        assert!(page.contains(&format!("(Page {} of {})", i + 1, count)), "page {} has no page number", i + 1);
+        assert!(page.contains("(Acme Corp)"));
+        if i + 1 < count {
+            assert!(page.contains("(Description)"), "page {} has no table header", i + 1);
+        }
+    }
+    assert!(pages[0].contains("(Statement)") && pages[0].contains("(Item 1)"));
+    assert!(pages[count - 2].contains("(Item 150)") && pages[count - 2].contains("($113,250.00)"));
+    assert!(pages[count - 1].contains("(Terms and conditions)") && !pages[count - 1].contains("(Description)"));
+    // Every row is drawn exactly once.
+    let drawn: usize = pages.iter().map(|p| p.matches("(Item ").count()).sum();
+    assert_eq!(drawn, 150);
+}
+
+#[tokio::test]
+async fn images_and_barcodes_are_drawn() {
+    let service = TemplateService::new();
+    let (pool, blobs) = setup().await;
+    let png = png();
+    let staged = blobs.stage_bytes(png.clone(), 1 << 20).await.unwrap();
+    let stored = blobs.save(&pool, staged, "image/png", blobs.default_policy(), None).await.unwrap();
+    let data_uri = format!("data:image/png;base64,{}", BASE64.encode(&png));
+
+    let template = create(&pool, "LABELS", TemplateFormat::PDF, r#"<document>
+        <row>
+          <cell><image src="{{logo}}" width="60"/></cell>
+          <cell><image src="blob:{{stamp}}" height="20" align="right"/></cell>
+        </row>
+        <image src="{{logo}}" width="30" align="center"/>
+        <barcode type="EAN13" value="{{ean}}" width="120" height="50"/>
+        <barcode type="QRCode" value="https://example.com/orders/{{order}}" width="60"/>
+        <table><tr><td><barcode value="{{order}}" text="false" height="20"/></td><td>{{order}}</td></tr></table>
+      </document>"#).await;
+    let variables = json!({"logo": data_uri, "stamp": stored.sha256, "ean": "400638133393", "order": "SO-1042"});
+    let pdf = service.render_pdf(&pool, &blobs, &template, &variables, &LocaleFormat::default()).await.unwrap();
+
+    // The logo is embedded once however often it is drawn, each copy with its soft mask.
+    assert_eq!(pdf.windows(17).filter(|w| w == b"/Subtype /Image /").count(), 4);
+    let page = &page_contents(&pdf)[0];
+    assert_eq!(page.matches("/Im1 Do").count(), 2);
+    assert_eq!(page.matches("/Im2 Do").count(), 1);
+    assert!(page.contains("(4006381333931)"), "EAN-13 text with its check digit");
+    assert!(!page.contains("(SO-1042)") || page.matches("(SO-1042)").count() == 1, "the Code 128 text is hidden");
+    assert!(page.matches(" re f").count() > 100);
+
+    let failures = [
+        (r#"<document><barcode type="EAN13" value="4006381333930"/></document>"#, "Invalid check digit"),
+        (r#"<document><barcode type="PDF417" value="x"/></document>"#, "cannot be printed"),
+        (r#"<document><barcode type="Aztec" value="x"/></document>"#, "Unknown barcode type"),
+        (r#"<document><image src="https://example.com/logo.png"/></document>"#, "must be a data: or blob: URI"),
+        (r#"<document><p>Page <page-number/></p></document>"#, "only be used in a header or footer"),
+        (r#"<document><marquee>Hi</marquee></document>"#, "Unknown element <marquee>"),
+        (r#"<document><table><tr></tr><tr><td>x</td></tr></table></document>"#, "needs at least one cell"),
+        (r#"<document><p>Unclosed</document>"#, "Invalid document markup"),
+        (r#"<invoice/>"#, "must start with <document>"),
+    ];
+    for (i, (body, message)) in failures.into_iter().enumerate() {
+        let template = create(&pool, &format!("BAD{}", i), TemplateFormat::PDF, body).await;
+        let error = service.render_pdf(&pool, &blobs, &template, &json!({}), &LocaleFormat::default()).await.unwrap_err();
+        let text = validation_message(error);
+        assert!(text.contains(message), "{:?} gave {:?}", body, text);
+    }
+}
+
+#[tokio::test]
+async fn generated_pdfs_are_stored_with_shared_headers_and_the_requested_locale() {
+    let service = TemplateService::new();
+    let (pool, blobs) = setup().await;
+    let header = create(&pool, "HEADER", TemplateFormat::PDF, "<p bold=\"true\">{{company}}</p><p>{{tagline}}</p>").await;
+    let footer = create(&pool, "FOOTER", TemplateFormat::PDF, "<p>Page <page-number/> of <page-count/></p>").await;
+    let mut invoice = create(&pool, "INVOICE", TemplateFormat::PDF, "<document><h1>Invoice {{number}}</h1><p>Total {{format_money total currency}} due {{format_date due}}</p></document>").await;
+    invoice.header_template_id = Some(header.base.id);
+    invoice.footer_template_id = Some(footer.base.id);
+    invoice.default_values = Some(json!({"tagline": "Quality since 1901"}));
+    service.update(&pool, &invoice).await.unwrap();
+
+    let request = |locale: Option<&str>| GenerateDocument {
+        template_id: invoice.base.id,
+        name: "Invoice INV-7".into(),
+        variables: json!({"company": "Acme GmbH", "number": "INV-7", "total": 250075, "currency": "EUR", "due": "2026-04-30"}),
+        locale: locale.map(str::to_string),
+        related_entity_type: Some("invoice".into()),
+        related_entity_id: Some(Uuid::nil()),
+        generated_by: Uuid::new_v4(),
+    };
+    let document = service.generate_document(&pool, &blobs, request(Some("de-DE"))).await.unwrap();
+    assert!(matches!(document.output_format, TemplateFormat::PDF));
+    assert!(document.content.is_none());
+    let pdf = read_blob(&pool, &blobs, document.file_path.as_deref().unwrap()).await;
+    assert_eq!(document.file_size, Some(pdf.len() as i64));
+    let page = &page_contents(&pdf)[0];
+    assert!(page.contains("(Acme GmbH)") && page.contains("(Page 1 of 1)"));
+    assert!(page.contains("(Quality since 1901)"), "the header sees the invoice's defaults");
+    assert!(page.contains("(Total 2.500,75 \u{80} due 30.04.2026)"), "{}", page);
+
+    let listed = service.list_documents_for_entity(&pool, "invoice", Uuid::nil()).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].file_path, document.file_path);
+
+    let error = service.generate_document(&pool, &blobs, request(Some("xx-XX"))).await.unwrap_err();
+    assert!(validation_message(error).contains("Unknown locale"));
+
+    let letter = create(&pool, "LETTER", TemplateFormat::Markdown, "# Dear {{company}}").await;
+    let document = service.generate_document(&pool, &blobs, GenerateDocument { template_id: letter.base.id, ..request(None) }).await.unwrap();
+    assert_eq!(document.content.as_deref(), Some("# Dear Acme GmbH"));
+    assert!(document.file_path.is_none());
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use chrono::Utc;
 use erp_core::{BaseEntity, Currency, Money, Paginated, Pagination};
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 
 use crate::models::{MatchStatus, VendorBill, VendorBillLine, VendorBillPayment, VendorBillStatus};
@@ -89,6 +89,13 @@ impl VendorBillRepository {
     }
 
     pub async fn create(pool: &SqlitePool, bill: VendorBill) -> Result<()> {
+        let mut tx = pool.begin().await?;
+        Self::create_in(&mut tx, bill).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn create_in(conn: &mut SqliteConnection, bill: VendorBill) -> Result<()> {
         sqlx::query(
             "INSERT INTO vendor_bills (id, bill_number, vendor_invoice_number, vendor_id,
              purchase_order_id, bill_date, due_date, subtotal, tax_amount, total, amount_paid,
@@ -111,17 +118,21 @@ impl VendorBillRepository {
         .bind(&bill.notes)
         .bind(bill.base.created_at.to_rfc3339())
         .bind(bill.base.updated_at.to_rfc3339())
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
 
         for line in &bill.lines {
-            Self::create_line(pool, line).await?;
+            Self::create_line_in(&mut *conn, line).await?;
         }
 
         Ok(())
     }
 
     pub async fn create_line(pool: &SqlitePool, line: &VendorBillLine) -> Result<()> {
+        Self::create_line_in(&mut *pool.acquire().await?, line).await
+    }
+
+    pub async fn create_line_in(conn: &mut SqliteConnection, line: &VendorBillLine) -> Result<()> {
         sqlx::query(
             "INSERT INTO vendor_bill_lines (id, bill_id, po_line_id, product_id, description,
              quantity, unit_price, tax_rate, line_total, match_quantity, match_status)
@@ -138,18 +149,22 @@ impl VendorBillRepository {
         .bind(line.line_total.amount)
         .bind(line.match_quantity)
         .bind(format!("{:?}", line.match_status))
-        .execute(pool)
+        .execute(conn)
         .await?;
 
         Ok(())
     }
 
     pub async fn update_status(pool: &SqlitePool, id: Uuid, status: VendorBillStatus) -> Result<()> {
+        Self::update_status_in(&mut *pool.acquire().await?, id, status).await
+    }
+
+    pub async fn update_status_in(conn: &mut SqliteConnection, id: Uuid, status: VendorBillStatus) -> Result<()> {
         sqlx::query("UPDATE vendor_bills SET status = ?, updated_at = ? WHERE id = ?")
             .bind(format!("{:?}", status))
             .bind(Utc::now().to_rfc3339())
             .bind(id.to_string())
-            .execute(pool)
+            .execute(&mut *conn)
             .await?;
         Ok(())
     }
@@ -176,7 +191,34 @@ impl VendorBillRepository {
         Ok(())
     }
 
-    pub async fn record_payment(pool: &SqlitePool, payment: VendorBillPayment) -> Result<()> {
+    pub async fn record_payment(pool: &SqlitePool, payment: VendorBillPayment) -> Result<bool> {
+        let mut tx = pool.begin().await?;
+        let applied = Self::record_payment_in(&mut tx, payment).await?;
+        tx.commit().await?;
+        Ok(applied)
+    }
+
+    /// Applies a payment and moves the bill to Paid or PartiallyPaid from its new balance.
+    /// Returns false, applying nothing, when the bill is not payable or the payment exceeds
+    /// what is still outstanding.
+    pub async fn record_payment_in(conn: &mut SqliteConnection, payment: VendorBillPayment) -> Result<bool> {
+        let updated = sqlx::query(
+            "UPDATE vendor_bills SET amount_paid = amount_paid + ?,
+                    status = CASE WHEN amount_paid + ? >= total THEN 'Paid' ELSE 'PartiallyPaid' END,
+                    updated_at = ?
+             WHERE id = ? AND status IN ('Approved', 'PartiallyPaid') AND amount_paid + ? <= total",
+        )
+        .bind(payment.amount.amount)
+        .bind(payment.amount.amount)
+        .bind(Utc::now().to_rfc3339())
+        .bind(payment.bill_id.to_string())
+        .bind(payment.amount.amount)
+        .execute(&mut *conn)
+        .await?;
+        if updated.rows_affected() == 0 {
+            return Ok(false);
+        }
+
         sqlx::query(
             "INSERT INTO vendor_bill_payments (id, bill_id, payment_id, amount, applied_at)
              VALUES (?, ?, ?, ?, ?)",
@@ -186,19 +228,10 @@ impl VendorBillRepository {
         .bind(payment.payment_id.to_string())
         .bind(payment.amount.amount)
         .bind(payment.applied_at.to_rfc3339())
-        .execute(pool)
+        .execute(&mut *conn)
         .await?;
 
-        sqlx::query(
-            "UPDATE vendor_bills SET amount_paid = amount_paid + ?, updated_at = ? WHERE id = ?",
-        )
-        .bind(payment.amount.amount)
-        .bind(Utc::now().to_rfc3339())
-        .bind(payment.bill_id.to_string())
-        .execute(pool)
-        .await?;
-
-        Ok(())
+        Ok(true)
     }
 }
 
@@ -1,6 +1,6 @@
 use chrono::Utc;
 use erp_core::{BaseEntity, Currency, Error, Money, Paginated, Pagination, Result};
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use uuid::Uuid;
 
 use crate::models::{
@@ -8,6 +8,7 @@ use crate::models::{
     VendorBillLine, VendorBillLineCreateRequest, VendorBillPayment, VendorBillStatus,
 };
 use crate::repository::VendorBillRepository;
+use erp_finance::{PostingDocument, PostingEvent, PostingService};
 
 pub struct VendorBillService;
 
@@ -43,6 +44,25 @@ impl VendorBillService {
         due_date: chrono::DateTime<Utc>,
         line_requests: Vec<VendorBillLineCreateRequest>,
         notes: Option<String>,
+    ) -> Result<VendorBill> {
+        let mut tx = pool.begin().await?;
+        let bill = Self::create_in(
+            &mut tx, vendor_id, vendor_invoice_number, purchase_order_id, bill_date, due_date, line_requests, notes,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(bill)
+    }
+
+    pub async fn create_in(
+        conn: &mut SqliteConnection,
+        vendor_id: Uuid,
+        vendor_invoice_number: String,
+        purchase_order_id: Option<Uuid>,
+        bill_date: chrono::DateTime<Utc>,
+        due_date: chrono::DateTime<Utc>,
+        line_requests: Vec<VendorBillLineCreateRequest>,
+        notes: Option<String>,
     ) -> Result<VendorBill> {
         if vendor_invoice_number.is_empty() {
             return Err(Error::validation("Vendor invoice number is required"));
@@ -55,7 +75,8 @@ impl VendorBillService {
         }
 
         let base = BaseEntity::new();
-        let bill_number = format!("VB-{}", Utc::now().format("%Y%m%d%H%M%S"));
+        // Bills captured in bulk are created many to the second, so the timestamp alone is not unique.
+        let bill_number = format!("VB-{}-{:04x}", Utc::now().format("%Y%m%d%H%M%S"), base.id.as_u128() as u16);
 
         let lines: Vec<VendorBillLine> = line_requests
             .into_iter()
@@ -104,7 +125,7 @@ impl VendorBillService {
             notes,
         };
 
-        VendorBillRepository::create(pool, bill.clone())
+        VendorBillRepository::create_in(conn, bill.clone())
             .await
             .map_err(Error::Internal)?;
         Ok(bill)
@@ -125,9 +146,23 @@ impl VendorBillService {
         if bill.status != VendorBillStatus::Pending {
             return Err(Error::business_rule("Only pending bills can be approved"));
         }
-        VendorBillRepository::update_status(pool, id, VendorBillStatus::Approved)
+        let mut tx = pool.begin().await?;
+        PostingService::post_document(&mut tx, PostingDocument {
+            event: PostingEvent::VendorBill,
+            source_type: "VendorBill".to_string(),
+            source_id: bill.base.id,
+            source_number: bill.bill_number.clone(),
+            date: bill.bill_date,
+            amount: bill.subtotal.amount,
+            tax_amount: bill.tax_amount.amount,
+            currency: bill.total.currency,
+            description: Some(format!("Vendor bill {} ({})", bill.bill_number, bill.vendor_invoice_number)),
+        }).await?;
+        VendorBillRepository::update_status_in(&mut tx, id, VendorBillStatus::Approved)
             .await
-            .map_err(Error::Internal)
+            .map_err(Error::Internal)?;
+        tx.commit().await?;
+        Ok(())
     }
 
     pub async fn void(pool: &SqlitePool, id: Uuid) -> Result<()> {
@@ -147,31 +182,58 @@ impl VendorBillService {
         amount: i64,
     ) -> Result<()> {
         let bill = Self::get(pool, bill_id).await?;
+        let mut tx = pool.begin().await?;
+        Self::record_payment_in(&mut tx, &bill, payment_id, amount).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Applies and posts a payment on `bill`. The bill's balance is checked again as the payment
+    /// is applied, so payments racing on the same bill cannot overpay it.
+    pub async fn record_payment_in(
+        conn: &mut SqliteConnection,
+        bill: &VendorBill,
+        payment_id: Uuid,
+        amount: i64,
+    ) -> Result<()> {
         if bill.status != VendorBillStatus::Approved && bill.status != VendorBillStatus::PartiallyPaid {
             return Err(Error::business_rule("Can only record payments on approved or partially paid bills"));
         }
+        let outstanding = bill.total.amount - bill.amount_paid.amount;
+        if amount <= 0 || amount > outstanding {
+            return Err(Error::validation(format!(
+                "Payment of {} on bill {} must be between 1 and the outstanding {}",
+                amount, bill.bill_number, outstanding
+            )));
+        }
 
         let payment = VendorBillPayment {
             id: Uuid::new_v4(),
-            bill_id,
+            bill_id: bill.base.id,
             payment_id,
-            amount: Money::new(amount, Currency::USD),
+            amount: Money::new(amount, bill.total.currency.clone()),
             applied_at: Utc::now(),
         };
 
-        VendorBillRepository::record_payment(pool, payment)
+        let application_id = payment.id;
+        let applied = VendorBillRepository::record_payment_in(&mut *conn, payment)
             .await
             .map_err(Error::Internal)?;
+        if !applied {
+            return Err(Error::Conflict(format!("Bill {} was paid or changed while this payment was recorded", bill.bill_number)));
+        }
 
-        let updated_bill = Self::get(pool, bill_id).await?;
-        let new_status = if updated_bill.amount_paid.amount >= updated_bill.total.amount {
-            VendorBillStatus::Paid
-        } else {
-            VendorBillStatus::PartiallyPaid
-        };
-        VendorBillRepository::update_status(pool, bill_id, new_status)
-            .await
-            .map_err(Error::Internal)?;
+        PostingService::post_document(&mut *conn, PostingDocument {
+            event: PostingEvent::VendorPayment,
+            source_type: "VendorBillPayment".to_string(),
+            source_id: application_id,
+            source_number: bill.bill_number.clone(),
+            date: Utc::now(),
+            amount,
+            tax_amount: 0,
+            currency: bill.total.currency.clone(),
+            description: Some(format!("Payment of vendor bill {}", bill.bill_number)),
+        }).await?;
 
         Ok(())
     }
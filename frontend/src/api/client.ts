@@ -1,6 +1,6 @@
-import axios from 'axios';
+import axios, { type InternalAxiosRequestConfig } from 'axios';
 import type { 
-  AuthResponse, User, Paginated, Account, JournalEntry, Product, 
+  AuthResponse, LoginResponse, User, Paginated, Account, JournalEntry, Product, 
   Warehouse, Customer, SalesOrder, Vendor, PurchaseOrder, Employee,
   Lead, Opportunity, SupplierScorecard, PerformanceCycle, PerformanceGoal,
   PerformanceReview
@@ -22,11 +22,44 @@ api.interceptors.request.use((config) => {
   return config;
 });
 
+// Access tokens are short-lived: on a 401, trade the refresh token for a new pair once and retry.
+let refreshing: Promise<string> | null = null;
+
+function refreshAccessToken(): Promise<string> {
+  const refreshToken = localStorage.getItem('refresh_token');
+  if (!refreshToken) {
+    return Promise.reject(new Error('No refresh token'));
+  }
+  refreshing ??= axios
+    .post<AuthResponse>(`${API_URL}/auth/refresh`, { refresh_token: refreshToken })
+    .then((res) => {
+      localStorage.setItem('token', res.data.token);
+      localStorage.setItem('refresh_token', res.data.refresh_token);
+      return res.data.token;
+    })
+    .finally(() => {
+      refreshing = null;
+    });
+  return refreshing;
+}
+
 api.interceptors.response.use(
   (response) => response,
-  (error) => {
+  async (error) => {
+    const original = error.config as (InternalAxiosRequestConfig & { _retried?: boolean }) | undefined;
+    if (error.response?.status === 401 && original && !original._retried && !original.url?.startsWith('/auth/')) {
+      original._retried = true;
+      try {
+        const token = await refreshAccessToken();
+        original.headers.Authorization = `Bearer ${token}`;
+        return api(original);
+      } catch {
+        // Fall through to signing out.
+      }
+    }
     if (error.response?.status === 401) {
       localStorage.removeItem('token');
+      localStorage.removeItem('refresh_token');
       window.location.href = '/login';
     }
     return Promise.reject(error);
@@ -40,6 +73,11 @@ export interface LoginRequest {
   password: string;
 }
 
+export interface TwoFactorLoginRequest {
+  mfa_token: string;
+  code: string;
+}
+
 export interface RegisterRequest {
   username: string;
   email: string;
@@ -204,9 +242,11 @@ export interface CreateLotRequest {
 
 // Auth
 export const auth = {
-  login: (data: LoginRequest) => api.post<AuthResponse>('/auth/login', data),
+  login: (data: LoginRequest) => api.post<LoginResponse>('/auth/login', data),
+  loginTwoFactor: (data: TwoFactorLoginRequest) => api.post<AuthResponse>('/auth/login/2fa', data),
   register: (data: RegisterRequest) => api.post<AuthResponse>('/auth/register', data),
   me: () => api.get<{ user: User }>('/auth/me'),
+  logout: (token: string) => api.post('/auth/logout', null, { headers: { Authorization: `Bearer ${token}` } }),
 };
 
 // Finance
@@ -325,10 +365,16 @@ export const expense = {
 // Import/Export
 export const data = {
   exportCsv: (entity: string) => api.get(`/api/v1/export?entity=${entity}`, { responseType: 'blob' }),
-  importCsv: (entity: string, csvContent: string) => 
-    api.post(`/api/v1/import?entity=${entity}`, csvContent, {
-      headers: { 'Content-Type': 'text/csv' }
-    }),
+  importCsv: (entity: string, csvContent: string, options: { dryRun?: boolean; upsert?: boolean } = {}) => {
+    const form = new FormData();
+    form.append('entity', entity);
+    form.append('format', 'csv');
+    form.append('dry_run', String(options.dryRun ?? false));
+    form.append('upsert', String(options.upsert ?? false));
+    form.append('file', new Blob([csvContent], { type: 'text/csv' }), `${entity}.csv`);
+    return api.post('/api/v1/imports', form, { headers: { 'Content-Type': 'multipart/form-data' } });
+  },
+  getImport: (id: string) => api.get(`/api/v1/imports/${id}`),
 };
 
 // Service Desk
@@ -1,11 +1,13 @@
 import { createContext, useContext, useEffect, useState, type ReactNode } from 'react';
 import { auth } from '../api/client';
-import type { AuthResponse, User } from '../types';
+import type { AuthResponse, TwoFactorChallenge, User } from '../types';
 
 interface AuthContextType {
   user: User | null;
   token: string | null;
-  login: (username: string, password: string) => Promise<void>;
+  /** Resolves to a challenge when the account has two-factor authentication enabled. */
+  login: (username: string, password: string) => Promise<TwoFactorChallenge | null>;
+  loginTwoFactor: (mfaToken: string, code: string) => Promise<void>;
   logout: () => void;
   isLoading: boolean;
 }
@@ -23,6 +25,7 @@ export function AuthProvider({ children }: { children: ReactNode }) {
         .then((res) => setUser(res.data.user))
         .catch(() => {
           localStorage.removeItem('token');
+          localStorage.removeItem('refresh_token');
           setToken(null);
         })
         .finally(() => setIsLoading(false));
@@ -31,22 +34,39 @@ export function AuthProvider({ children }: { children: ReactNode }) {
     }
   }, [token]);
 
-  const login = async (username: string, password: string) => {
-    const res = await auth.login({ username, password });
-    const data: AuthResponse = res.data;
+  const startSession = (data: AuthResponse) => {
     localStorage.setItem('token', data.token);
+    localStorage.setItem('refresh_token', data.refresh_token);
     setToken(data.token);
     setUser(data.user);
   };
 
+  const login = async (username: string, password: string) => {
+    const res = await auth.login({ username, password });
+    if ('mfa_required' in res.data) {
+      return res.data;
+    }
+    startSession(res.data);
+    return null;
+  };
+
+  const loginTwoFactor = async (mfaToken: string, code: string) => {
+    const res = await auth.loginTwoFactor({ mfa_token: mfaToken, code });
+    startSession(res.data);
+  };
+
   const logout = () => {
+    if (token) {
+      auth.logout(token).catch(() => undefined);
+    }
     localStorage.removeItem('token');
+    localStorage.removeItem('refresh_token');
     setToken(null);
     setUser(null);
   };
 
   return (
-    <AuthContext.Provider value={{ user, token, login, logout, isLoading }}>
+    <AuthContext.Provider value={{ user, token, login, loginTwoFactor, logout, isLoading }}>
       {children}
     </AuthContext.Provider>
   );
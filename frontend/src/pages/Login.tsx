@@ -9,10 +9,12 @@ export default function Login() {
   const [password, setPassword] = useState('');
   const [email, setEmail] = useState('');
   const [fullName, setFullName] = useState('');
+  const [mfaToken, setMfaToken] = useState<string | null>(null);
+  const [code, setCode] = useState('');
   const [error, setError] = useState('');
   const [loading, setLoading] = useState(false);
   
-  const { login } = useAuth();
+  const { login, loginTwoFactor } = useAuth();
   const navigate = useNavigate();
 
   const handleSubmit = async (e: React.FormEvent) => {
@@ -21,9 +23,16 @@ export default function Login() {
     setLoading(true);
 
     try {
-      if (isLogin) {
-        await login(username, password);
+      if (mfaToken) {
+        await loginTwoFactor(mfaToken, code);
         navigate('/');
+      } else if (isLogin) {
+        const challenge = await login(username, password);
+        if (challenge) {
+          setMfaToken(challenge.mfa_token);
+        } else {
+          navigate('/');
+        }
       } else {
         const { auth } = await import('../api/client');
         await auth.register({ username, email, password, full_name: fullName });
@@ -54,58 +63,73 @@ export default function Login() {
             </div>
           )}
           
-          <div className="space-y-4">
+          {mfaToken ? (
             <div>
-              <label className="label">Username</label>
+              <label className="label">Authentication Code</label>
               <input
                 type="text"
                 required
-                value={username}
-                onChange={(e) => setUsername(e.target.value)}
+                autoComplete="one-time-code"
+                value={code}
+                onChange={(e) => setCode(e.target.value.trim())}
                 className="input"
-                placeholder="Enter username"
+                placeholder="Enter the code from your authenticator app or a backup code"
               />
             </div>
+          ) : (
+            <div className="space-y-4">
+              <div>
+                <label className="label">Username</label>
+                <input
+                  type="text"
+                  required
+                  value={username}
+                  onChange={(e) => setUsername(e.target.value)}
+                  className="input"
+                  placeholder="Enter username"
+                />
+              </div>
 
-            {!isLogin && (
-              <>
-                <div>
-                  <label className="label">Email</label>
-                  <input
-                    type="email"
-                    required
-                    value={email}
-                    onChange={(e) => setEmail(e.target.value)}
-                    className="input"
-                    placeholder="Enter email"
-                  />
-                </div>
-                <div>
-                  <label className="label">Full Name</label>
-                  <input
-                    type="text"
-                    required
-                    value={fullName}
-                    onChange={(e) => setFullName(e.target.value)}
-                    className="input"
-                    placeholder="Enter full name"
-                  />
-                </div>
-              </>
-            )}
+              {!isLogin && (
+                <>
+                  <div>
+                    <label className="label">Email</label>
+                    <input
+                      type="email"
+                      required
+                      value={email}
+                      onChange={(e) => setEmail(e.target.value)}
+                      className="input"
+                      placeholder="Enter email"
+                    />
+                  </div>
+                  <div>
+                    <label className="label">Full Name</label>
+                    <input
+                      type="text"
+                      required
+                      value={fullName}
+                      onChange={(e) => setFullName(e.target.value)}
+                      className="input"
+                      placeholder="Enter full name"
+                    />
+                  </div>
+                </>
+              )}
 
-            <div>
-              <label className="label">Password</label>
-              <input
-                type="password"
-                required
-                value={password}
-                onChange={(e) => setPassword(e.target.value)}
-                className="input"
-                placeholder="Enter password"
-              />
+              <div>
+                <label className="label">Password</label>
+                <input
+                  type="password"
+                  required
+                  value={password}
+                  onChange={(e) => setPassword(e.target.value)}
+                  className="input"
+                  placeholder="Enter password"
+                />
+              </div>
             </div>
-          </div>
+          )}
 
           <div>
             <button
@@ -113,14 +137,18 @@ export default function Login() {
               disabled={loading}
               className="btn btn-primary w-full py-3"
             >
-              {loading ? 'Please wait...' : isLogin ? 'Sign in' : 'Register'}
+              {loading ? 'Please wait...' : mfaToken ? 'Verify' : isLogin ? 'Sign in' : 'Register'}
             </button>
           </div>
 
           <div className="text-center">
             <button
               type="button"
-              onClick={() => setIsLogin(!isLogin)}
+              onClick={() => {
+                setMfaToken(null);
+                setCode('');
+                setIsLogin(!isLogin);
+              }}
               className="text-blue-600 hover:text-blue-700 text-sm"
             >
               {isLogin ? "Don't have an account? Register" : 'Already have an account? Sign in'}
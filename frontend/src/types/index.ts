@@ -9,9 +9,19 @@ export interface User {
 export interface AuthResponse {
   token: string;
   expires_at: string;
+  refresh_token: string;
+  refresh_expires_at: string;
   user: User;
 }
 
+export interface TwoFactorChallenge {
+  mfa_required: true;
+  mfa_token: string;
+  expires_at: string;
+}
+
+export type LoginResponse = AuthResponse | TwoFactorChallenge;
+
 export interface Paginated<T> {
   items: T[];
   total: number;
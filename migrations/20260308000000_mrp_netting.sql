@@ -0,0 +1,25 @@
+-- MRP Netting Engine Tables
+CREATE TABLE IF NOT EXISTS mrp_item_settings (
+    product_id TEXT PRIMARY KEY,
+    lot_sizing_rule TEXT NOT NULL DEFAULT 'LotForLot',
+    fixed_lot_size INTEGER NOT NULL DEFAULT 0,
+    annual_demand INTEGER NOT NULL DEFAULT 0,
+    ordering_cost INTEGER NOT NULL DEFAULT 0,
+    holding_cost_per_unit INTEGER NOT NULL DEFAULT 0,
+    lead_time_days INTEGER,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mrp_pegging (
+    id TEXT PRIMARY KEY,
+    mrp_run_id TEXT NOT NULL REFERENCES mrp_runs(id),
+    planned_order_id TEXT NOT NULL REFERENCES mrp_planned_orders(id),
+    demand_type TEXT NOT NULL,
+    demand_id TEXT,
+    demand_date TEXT NOT NULL,
+    quantity INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mrp_pegging_run ON mrp_pegging(mrp_run_id);
+CREATE INDEX IF NOT EXISTS idx_mrp_pegging_planned_order ON mrp_pegging(planned_order_id);
+CREATE INDEX IF NOT EXISTS idx_mrp_pegging_demand ON mrp_pegging(demand_type, demand_id);
@@ -0,0 +1,26 @@
+-- Subledger to General Ledger Posting
+CREATE TABLE IF NOT EXISTS posting_rules (
+    id TEXT PRIMARY KEY,
+    event TEXT NOT NULL UNIQUE,
+    debit_account_id TEXT NOT NULL REFERENCES accounts(id),
+    credit_account_id TEXT NOT NULL REFERENCES accounts(id),
+    tax_account_id TEXT REFERENCES accounts(id),
+    description TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS journal_entry_sources (
+    id TEXT PRIMARY KEY,
+    journal_entry_id TEXT NOT NULL REFERENCES journal_entries(id),
+    event TEXT NOT NULL,
+    source_type TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    source_number TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE(event, source_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_journal_entry_sources_source ON journal_entry_sources(source_type, source_id);
+CREATE INDEX IF NOT EXISTS idx_journal_entry_sources_entry ON journal_entry_sources(journal_entry_id);
@@ -0,0 +1,15 @@
+-- Journal Entry Reversals
+CREATE TABLE IF NOT EXISTS journal_entry_reversals (
+    id TEXT PRIMARY KEY,
+    original_entry_id TEXT NOT NULL UNIQUE REFERENCES journal_entries(id),
+    reversal_entry_id TEXT REFERENCES journal_entries(id),
+    reversal_date TEXT NOT NULL,
+    reason TEXT,
+    status TEXT NOT NULL DEFAULT 'Scheduled',
+    created_at TEXT NOT NULL,
+    created_by TEXT,
+    completed_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_journal_entry_reversals_reversal ON journal_entry_reversals(reversal_entry_id);
+CREATE INDEX IF NOT EXISTS idx_journal_entry_reversals_due ON journal_entry_reversals(status, reversal_date);
@@ -0,0 +1,12 @@
+-- Backup chains: incremental/differential backups reference the backup they were diffed against
+ALTER TABLE backup_records ADD COLUMN parent_backup_id TEXT REFERENCES backup_records(id);
+ALTER TABLE backup_records ADD COLUMN database_checksum TEXT;
+ALTER TABLE backup_records ADD COLUMN created_by TEXT;
+ALTER TABLE backup_records ADD COLUMN updated_by TEXT;
+ALTER TABLE restore_operations ADD COLUMN created_by TEXT;
+ALTER TABLE restore_operations ADD COLUMN updated_by TEXT;
+ALTER TABLE backup_verifications ADD COLUMN created_by TEXT;
+ALTER TABLE backup_verifications ADD COLUMN updated_by TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_backup_records_schedule ON backup_records(schedule_id, started_at);
+CREATE INDEX IF NOT EXISTS idx_backup_records_parent ON backup_records(parent_backup_id);
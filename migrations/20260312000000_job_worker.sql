@@ -0,0 +1,84 @@
+-- Background job worker
+CREATE TABLE IF NOT EXISTS scheduled_jobs (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    job_type TEXT NOT NULL,
+    handler TEXT NOT NULL,
+    payload TEXT,
+    priority TEXT DEFAULT 'Normal',
+    cron_expression TEXT,
+    interval_seconds INTEGER,
+    scheduled_at TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    next_run_at TEXT,
+    last_run_at TEXT,
+    last_success_at TEXT,
+    last_failure_at TEXT,
+    status TEXT DEFAULT 'Pending',
+    run_count INTEGER DEFAULT 0,
+    success_count INTEGER DEFAULT 0,
+    failure_count INTEGER DEFAULT 0,
+    max_retries INTEGER DEFAULT 3,
+    retry_count INTEGER DEFAULT 0,
+    retry_delay_seconds INTEGER DEFAULT 60,
+    timeout_seconds INTEGER DEFAULT 300,
+    last_error TEXT,
+    last_duration_ms INTEGER,
+    avg_duration_ms INTEGER,
+    tags TEXT,
+    created_by TEXT,
+    updated_by TEXT,
+    locked_by TEXT,
+    locked_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS job_executions (
+    id TEXT PRIMARY KEY,
+    job_id TEXT NOT NULL,
+    execution_number INTEGER NOT NULL,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    duration_ms INTEGER,
+    status TEXT DEFAULT 'Running',
+    result TEXT,
+    error_message TEXT,
+    error_stack_trace TEXT,
+    retry_of_id TEXT,
+    retry_number INTEGER DEFAULT 0,
+    worker_id TEXT,
+    created_by TEXT,
+    updated_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS job_schedules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    job_template_id TEXT,
+    job_name TEXT NOT NULL,
+    handler TEXT NOT NULL,
+    default_payload TEXT,
+    schedule_type TEXT NOT NULL,
+    cron_expression TEXT,
+    interval_minutes INTEGER,
+    specific_times TEXT,
+    run_on_days TEXT,
+    timezone TEXT DEFAULT 'UTC',
+    start_date TEXT,
+    end_date TEXT,
+    next_scheduled_run TEXT,
+    last_run TEXT,
+    enabled INTEGER DEFAULT 1,
+    created_by TEXT,
+    updated_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_jobs_due ON scheduled_jobs(status, next_run_at);
+CREATE INDEX IF NOT EXISTS idx_scheduled_jobs_handler ON scheduled_jobs(handler);
+CREATE INDEX IF NOT EXISTS idx_job_executions_job ON job_executions(job_id, started_at);
@@ -0,0 +1,14 @@
+-- Row-level data permissions and field permissions. The role, assignment and permission tables
+-- come from 20240101000026_enterprise_infrastructure_features.
+
+-- One data permission per role and resource, so setting it again replaces the old one.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_data_permissions_role_resource ON data_permissions(role_id, resource);
+CREATE INDEX IF NOT EXISTS idx_user_role_assignments_user ON user_role_assignments(user_id);
+
+-- Links a login to the employee record that places it in a department and team.
+ALTER TABLE users ADD COLUMN employee_id TEXT REFERENCES employees(id);
+
+CREATE INDEX IF NOT EXISTS idx_users_employee ON users(employee_id);
+CREATE INDEX IF NOT EXISTS idx_customers_created_by ON customers(created_by);
+CREATE INDEX IF NOT EXISTS idx_sales_orders_created_by ON sales_orders(created_by);
+CREATE INDEX IF NOT EXISTS idx_employees_department ON employees(department_id);
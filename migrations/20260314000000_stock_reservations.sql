@@ -0,0 +1,18 @@
+-- Hard stock reservations held by sales orders until they ship or are cancelled
+CREATE TABLE IF NOT EXISTS stock_reservations (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    warehouse_id TEXT NOT NULL REFERENCES warehouses(id),
+    location_id TEXT NOT NULL REFERENCES stock_locations(id),
+    source_type TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    source_line_id TEXT,
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_stock_reservations_source ON stock_reservations(source_type, source_id);
+CREATE INDEX IF NOT EXISTS idx_stock_reservations_product ON stock_reservations(product_id, status);
+CREATE INDEX IF NOT EXISTS idx_stock_reservations_warehouse ON stock_reservations(warehouse_id, status);
@@ -0,0 +1,88 @@
+-- Perpetual inventory costing: per-product valuation method, cost layers and variances
+CREATE TABLE IF NOT EXISTS product_cost_settings (
+    product_id TEXT PRIMARY KEY REFERENCES products(id),
+    valuation_method TEXT NOT NULL DEFAULT 'FIFO',
+    standard_cost INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS product_valuations (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    valuation_method TEXT NOT NULL,
+    standard_cost INTEGER NOT NULL DEFAULT 0,
+    current_unit_cost INTEGER NOT NULL DEFAULT 0,
+    total_quantity INTEGER NOT NULL DEFAULT 0,
+    total_value INTEGER NOT NULL DEFAULT 0,
+    last_receipt_cost INTEGER NOT NULL DEFAULT 0,
+    last_receipt_date TEXT,
+    last_issue_cost INTEGER NOT NULL DEFAULT 0,
+    last_issue_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(product_id, warehouse_id)
+);
+
+CREATE TABLE IF NOT EXISTS inventory_cost_layers (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    layer_date TEXT NOT NULL,
+    receipt_reference TEXT NOT NULL,
+    receipt_id TEXT,
+    quantity INTEGER NOT NULL,
+    unit_cost INTEGER NOT NULL,
+    remaining_quantity INTEGER NOT NULL,
+    total_value INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS cost_adjustments (
+    id TEXT PRIMARY KEY,
+    adjustment_number TEXT NOT NULL UNIQUE,
+    adjustment_type TEXT NOT NULL,
+    adjustment_date TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    status TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT,
+    journal_entry_id TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS cost_adjustment_lines (
+    id TEXT PRIMARY KEY,
+    adjustment_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    old_unit_cost INTEGER NOT NULL,
+    new_unit_cost INTEGER NOT NULL,
+    old_total_value INTEGER NOT NULL,
+    new_total_value INTEGER NOT NULL,
+    value_change INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS inventory_cost_variances (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    variance_type TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    reference TEXT,
+    movement_id TEXT,
+    adjustment_id TEXT,
+    created_at TEXT NOT NULL
+);
+
+-- The cost each movement was valued at.
+ALTER TABLE stock_movements ADD COLUMN unit_cost INTEGER;
+ALTER TABLE stock_movements ADD COLUMN total_cost INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_inventory_cost_layers_product ON inventory_cost_layers(product_id, warehouse_id, remaining_quantity);
+CREATE INDEX IF NOT EXISTS idx_cost_adjustment_lines_adjustment ON cost_adjustment_lines(adjustment_id);
+CREATE INDEX IF NOT EXISTS idx_inventory_cost_variances_product ON inventory_cost_variances(product_id, created_at);
@@ -0,0 +1,13 @@
+-- payment_allocations and idx_payments_customer predate this migration and are kept.
+DELETE FROM number_sequences WHERE id IN ('00000000-0000-0000-0000-00000000a001', '00000000-0000-0000-0000-00000000a002');
+
+DROP INDEX IF EXISTS idx_payment_allocations_invoice;
+DROP INDEX IF EXISTS idx_payment_allocations_payment;
+DROP INDEX IF EXISTS idx_invoices_sales_order;
+DROP INDEX IF EXISTS idx_invoices_customer_status;
+DROP INDEX IF EXISTS idx_invoice_lines_order_line;
+
+ALTER TABLE payments DROP COLUMN unapplied_amount;
+ALTER TABLE invoices DROP COLUMN created_by;
+ALTER TABLE invoices DROP COLUMN payment_term_id;
+ALTER TABLE invoice_lines DROP COLUMN sales_order_line_id;
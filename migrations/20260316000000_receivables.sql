@@ -0,0 +1,30 @@
+-- Accounts receivable: invoicing from sales order lines and cash application
+ALTER TABLE invoice_lines ADD COLUMN sales_order_line_id TEXT REFERENCES sales_order_lines(id);
+ALTER TABLE invoices ADD COLUMN payment_term_id TEXT;
+ALTER TABLE invoices ADD COLUMN created_by TEXT;
+
+-- Part of a receipt not yet applied to any invoice (an overpayment held on account).
+ALTER TABLE payments ADD COLUMN unapplied_amount INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS payment_allocations (
+    id TEXT PRIMARY KEY,
+    payment_id TEXT NOT NULL,
+    invoice_id TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoice_lines_order_line ON invoice_lines(sales_order_line_id);
+CREATE INDEX IF NOT EXISTS idx_invoices_customer_status ON invoices(customer_id, status);
+CREATE INDEX IF NOT EXISTS idx_invoices_sales_order ON invoices(sales_order_id);
+CREATE INDEX IF NOT EXISTS idx_payments_customer ON payments(customer_id);
+CREATE INDEX IF NOT EXISTS idx_payment_allocations_payment ON payment_allocations(payment_id);
+CREATE INDEX IF NOT EXISTS idx_payment_allocations_invoice ON payment_allocations(invoice_id);
+
+INSERT OR IGNORE INTO number_sequences (id, name, code, prefix, current_value, increment, padding, is_active, created_at, updated_at)
+VALUES
+    ('00000000-0000-0000-0000-00000000a001', 'Customer invoices', 'INVOICE', 'INV-', 0, 1, 6, 1, datetime('now'), datetime('now')),
+    ('00000000-0000-0000-0000-00000000a002', 'Customer receipts', 'PAYMENT', 'RCPT-', 0, 1, 6, 1, datetime('now'), datetime('now'));
@@ -0,0 +1,103 @@
+DELETE FROM role_permissions WHERE role_id IN ('dddeeab3-a362-544b-8d6f-098f1a26f7cc', '42dd9921-7ae0-5caf-826b-fec92dafc130', '6c915aec-aa98-5ffc-a190-b75087a8a4fc', '41885a98-9aa9-5c1f-b915-00e2011f4a70', '00a17b51-c5d0-59d6-83f0-55ddeeea774e', '5f551956-ce5d-5cf9-9188-b8b68a43ef40');
+DELETE FROM user_role_assignments WHERE role_id IN ('dddeeab3-a362-544b-8d6f-098f1a26f7cc', '42dd9921-7ae0-5caf-826b-fec92dafc130', '6c915aec-aa98-5ffc-a190-b75087a8a4fc', '41885a98-9aa9-5c1f-b915-00e2011f4a70', '00a17b51-c5d0-59d6-83f0-55ddeeea774e', '5f551956-ce5d-5cf9-9188-b8b68a43ef40');
+DELETE FROM custom_roles WHERE id IN ('dddeeab3-a362-544b-8d6f-098f1a26f7cc', '42dd9921-7ae0-5caf-826b-fec92dafc130', '6c915aec-aa98-5ffc-a190-b75087a8a4fc', '41885a98-9aa9-5c1f-b915-00e2011f4a70', '00a17b51-c5d0-59d6-83f0-55ddeeea774e', '5f551956-ce5d-5cf9-9188-b8b68a43ef40');
+DELETE FROM role_permissions WHERE permission_id IN (
+    '4ca0f564-ff66-54ad-8962-7e4a50d747cb',
+    'f01c95be-2165-586b-a3ea-500ace2e0cf7',
+    '1554d022-cf3a-5f07-bebf-85cbde54813a',
+    '8a7cb4b4-f24c-5217-a7b6-2b9bf9a830c8',
+    'a5b6d1d6-f2e7-53fd-9a33-3c2e417dbcc0',
+    '7826ad7b-5689-5dd9-bf1d-561f785bfe88',
+    '294dea50-e93d-5a89-b344-268890bf7e2d',
+    '7f93596f-cf10-5d0b-9104-9e2cef26a324',
+    '9d34b37f-6884-5e0e-bff7-14538050cf61',
+    'b9102762-2f8d-5c80-9799-40927950b252',
+    '6d7d0aa4-2278-5287-b3af-49d773b83edd',
+    'd20cd5af-4555-5a52-b93d-b031eb80eb35',
+    '3743c6cb-d3ca-5ef2-abe8-518cc6148666',
+    '231a97ea-feac-5517-8f45-3ba711c1502b',
+    'c6764404-792a-5214-80ca-6d00d4c19313',
+    '44cea0cf-c1e7-53d7-b6ab-37ee7ab09080',
+    '15d39a41-e5af-5d6d-b708-e79e3d0e8c46',
+    'add9e874-2fc0-5dc8-9fea-9cb9fddc8a2f',
+    'bcf8c75f-94fb-59c5-b561-0ca513d82f2b',
+    'cf183c55-f1ff-5d6f-ac6d-2f664ce45ef3',
+    '9b21f492-72a5-5639-8cd0-43158c8fcc53',
+    '47455a7e-17af-5f65-8994-6f5a6d4ca460',
+    '9616278d-ed91-572d-bf33-2612fbeef625',
+    'a18ef493-324c-5b02-87b6-043a453563ba',
+    '08816bb8-e248-5124-97a3-d0a600a99876',
+    'd49e9442-d554-5a60-b7cf-66b3c711b131',
+    '4220ec69-7fa8-569c-aa7b-6f4fef06f2fb',
+    '2499bbf5-b10b-58fe-b594-5e8851206698',
+    'bc9532f6-a6dd-5d9d-982d-56ca77786685',
+    '6bbc9eef-183b-5401-85ff-34931601e94d',
+    'a4358dbd-d5a7-5901-8a91-8e18f7601711',
+    'b7605f92-e12d-57c5-93d7-ae9d79e8dd78',
+    'f7c6ff1b-26da-59b3-914a-0a9b22b5aef2',
+    'e15c212c-158f-5c6a-80de-978c1bcc84db',
+    '4f0be40d-9291-541a-8cd8-2a891c18927f',
+    '333f4d2b-7925-55ad-85b3-ce98caea1e6f',
+    '5b02b64a-508b-5331-84ad-0c177c69ef05',
+    '7534adbf-2eab-5226-ba55-dfa2ec70d5a9',
+    'c8064de2-ec72-5452-b112-651643cd9fe2',
+    'bc155d8b-697b-57e8-ad53-748d281aa67d',
+    'bad50f7c-3fac-54c4-b1ad-4a673c47d8a1',
+    '7f4fd52c-c51e-5930-8a68-a6f0588db326',
+    '83a781a0-4fb6-5521-a77a-cb6d2ff82b7a',
+    'fdf9b2ca-1200-5b76-94dd-9508a50b65f9',
+    'cf99cd1c-11d0-5c65-a1a9-a2c3cbc2dbde',
+    '68318ea1-daa1-51b7-b8b5-ef90af12349e',
+    '17e9cbf5-49dc-58ca-b4e4-2e3531ab4a39',
+    '7d730727-d9b4-59a6-8cde-c1fa338fab69'
+);
+DELETE FROM permissions WHERE id IN (
+    '4ca0f564-ff66-54ad-8962-7e4a50d747cb',
+    'f01c95be-2165-586b-a3ea-500ace2e0cf7',
+    '1554d022-cf3a-5f07-bebf-85cbde54813a',
+    '8a7cb4b4-f24c-5217-a7b6-2b9bf9a830c8',
+    'a5b6d1d6-f2e7-53fd-9a33-3c2e417dbcc0',
+    '7826ad7b-5689-5dd9-bf1d-561f785bfe88',
+    '294dea50-e93d-5a89-b344-268890bf7e2d',
+    '7f93596f-cf10-5d0b-9104-9e2cef26a324',
+    '9d34b37f-6884-5e0e-bff7-14538050cf61',
+    'b9102762-2f8d-5c80-9799-40927950b252',
+    '6d7d0aa4-2278-5287-b3af-49d773b83edd',
+    'd20cd5af-4555-5a52-b93d-b031eb80eb35',
+    '3743c6cb-d3ca-5ef2-abe8-518cc6148666',
+    '231a97ea-feac-5517-8f45-3ba711c1502b',
+    'c6764404-792a-5214-80ca-6d00d4c19313',
+    '44cea0cf-c1e7-53d7-b6ab-37ee7ab09080',
+    '15d39a41-e5af-5d6d-b708-e79e3d0e8c46',
+    'add9e874-2fc0-5dc8-9fea-9cb9fddc8a2f',
+    'bcf8c75f-94fb-59c5-b561-0ca513d82f2b',
+    'cf183c55-f1ff-5d6f-ac6d-2f664ce45ef3',
+    '9b21f492-72a5-5639-8cd0-43158c8fcc53',
+    '47455a7e-17af-5f65-8994-6f5a6d4ca460',
+    '9616278d-ed91-572d-bf33-2612fbeef625',
+    'a18ef493-324c-5b02-87b6-043a453563ba',
+    '08816bb8-e248-5124-97a3-d0a600a99876',
+    'd49e9442-d554-5a60-b7cf-66b3c711b131',
+    '4220ec69-7fa8-569c-aa7b-6f4fef06f2fb',
+    '2499bbf5-b10b-58fe-b594-5e8851206698',
+    'bc9532f6-a6dd-5d9d-982d-56ca77786685',
+    '6bbc9eef-183b-5401-85ff-34931601e94d',
+    'a4358dbd-d5a7-5901-8a91-8e18f7601711',
+    'b7605f92-e12d-57c5-93d7-ae9d79e8dd78',
+    'f7c6ff1b-26da-59b3-914a-0a9b22b5aef2',
+    'e15c212c-158f-5c6a-80de-978c1bcc84db',
+    '4f0be40d-9291-541a-8cd8-2a891c18927f',
+    '333f4d2b-7925-55ad-85b3-ce98caea1e6f',
+    '5b02b64a-508b-5331-84ad-0c177c69ef05',
+    '7534adbf-2eab-5226-ba55-dfa2ec70d5a9',
+    'c8064de2-ec72-5452-b112-651643cd9fe2',
+    'bc155d8b-697b-57e8-ad53-748d281aa67d',
+    'bad50f7c-3fac-54c4-b1ad-4a673c47d8a1',
+    '7f4fd52c-c51e-5930-8a68-a6f0588db326',
+    '83a781a0-4fb6-5521-a77a-cb6d2ff82b7a',
+    'fdf9b2ca-1200-5b76-94dd-9508a50b65f9',
+    'cf99cd1c-11d0-5c65-a1a9-a2c3cbc2dbde',
+    '68318ea1-daa1-51b7-b8b5-ef90af12349e',
+    '17e9cbf5-49dc-58ca-b4e4-2e3531ab4a39',
+    '7d730727-d9b4-59a6-8cde-c1fa338fab69'
+);
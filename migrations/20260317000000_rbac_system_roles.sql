@@ -0,0 +1,73 @@
+-- System roles backing users.role, with the grants the hardcoded role table used to give, plus
+-- the default permission catalog. Grants use * for any module, resource or action.
+
+INSERT OR IGNORE INTO permissions (id, code, name, description, module, resource, action, created_at) VALUES
+('4ca0f564-ff66-54ad-8962-7e4a50d747cb', '*:*:*', 'All Permissions', 'Every action on every module', '*', '*', '*', datetime('now')),
+('f01c95be-2165-586b-a3ea-500ace2e0cf7', '*:*:read', 'Read Everything', 'Read access to every module', '*', '*', 'read', datetime('now')),
+('1554d022-cf3a-5f07-bebf-85cbde54813a', 'finance:*:*', 'Finance', 'Every finance action', 'finance', '*', '*', datetime('now')),
+('8a7cb4b4-f24c-5217-a7b6-2b9bf9a830c8', 'sales:*:*', 'Sales', 'Every sales action', 'sales', '*', '*', datetime('now')),
+('a5b6d1d6-f2e7-53fd-9a33-3c2e417dbcc0', 'sales:*:read', 'Read Sales', 'Read access to sales', 'sales', '*', 'read', datetime('now')),
+('7826ad7b-5689-5dd9-bf1d-561f785bfe88', 'purchasing:*:*', 'Purchasing', 'Every purchasing action', 'purchasing', '*', '*', datetime('now')),
+('294dea50-e93d-5a89-b344-268890bf7e2d', 'purchasing:*:read', 'Read Purchasing', 'Read access to purchasing', 'purchasing', '*', 'read', datetime('now')),
+('7f93596f-cf10-5d0b-9104-9e2cef26a324', 'inventory:*:*', 'Inventory', 'Every inventory action', 'inventory', '*', '*', datetime('now')),
+('9d34b37f-6884-5e0e-bff7-14538050cf61', 'inventory:*:read', 'Read Inventory', 'Read access to inventory', 'inventory', '*', 'read', datetime('now')),
+('b9102762-2f8d-5c80-9799-40927950b252', 'manufacturing:*:read', 'Read Manufacturing', 'Read access to manufacturing', 'manufacturing', '*', 'read', datetime('now')),
+('6d7d0aa4-2278-5287-b3af-49d773b83edd', 'hr:*:*', 'HR', 'Every HR action', 'hr', '*', '*', datetime('now')),
+('d20cd5af-4555-5a52-b93d-b031eb80eb35', 'finance:accounts:read', 'View Accounts', 'View chart of accounts', 'finance', 'accounts', 'read', datetime('now')),
+('3743c6cb-d3ca-5ef2-abe8-518cc6148666', 'finance:accounts:write', 'Edit Accounts', 'Create and edit accounts', 'finance', 'accounts', 'write', datetime('now')),
+('231a97ea-feac-5517-8f45-3ba711c1502b', 'finance:accounts:delete', 'Delete Accounts', 'Delete accounts', 'finance', 'accounts', 'delete', datetime('now')),
+('c6764404-792a-5214-80ca-6d00d4c19313', 'finance:journals:read', 'View Journals', 'View journal entries', 'finance', 'journals', 'read', datetime('now')),
+('44cea0cf-c1e7-53d7-b6ab-37ee7ab09080', 'finance:journals:write', 'Create Journals', 'Create journal entries', 'finance', 'journals', 'write', datetime('now')),
+('15d39a41-e5af-5d6d-b708-e79e3d0e8c46', 'finance:journals:post', 'Post Journals', 'Post journal entries', 'finance', 'journals', 'post', datetime('now')),
+('add9e874-2fc0-5dc8-9fea-9cb9fddc8a2f', 'inventory:products:read', 'View Products', 'View product catalog', 'inventory', 'products', 'read', datetime('now')),
+('bcf8c75f-94fb-59c5-b561-0ca513d82f2b', 'inventory:products:write', 'Edit Products', 'Create and edit products', 'inventory', 'products', 'write', datetime('now')),
+('cf183c55-f1ff-5d6f-ac6d-2f664ce45ef3', 'inventory:products:delete', 'Delete Products', 'Delete products', 'inventory', 'products', 'delete', datetime('now')),
+('9b21f492-72a5-5639-8cd0-43158c8fcc53', 'inventory:stock:read', 'View Stock', 'View stock levels', 'inventory', 'stock', 'read', datetime('now')),
+('47455a7e-17af-5f65-8994-6f5a6d4ca460', 'inventory:stock:adjust', 'Adjust Stock', 'Adjust stock levels', 'inventory', 'stock', 'adjust', datetime('now')),
+('9616278d-ed91-572d-bf33-2612fbeef625', 'sales:customers:read', 'View Customers', 'View customer list', 'sales', 'customers', 'read', datetime('now')),
+('a18ef493-324c-5b02-87b6-043a453563ba', 'sales:customers:write', 'Edit Customers', 'Create and edit customers', 'sales', 'customers', 'write', datetime('now')),
+('08816bb8-e248-5124-97a3-d0a600a99876', 'sales:orders:read', 'View Orders', 'View sales orders', 'sales', 'orders', 'read', datetime('now')),
+('d49e9442-d554-5a60-b7cf-66b3c711b131', 'sales:orders:write', 'Create Orders', 'Create sales orders', 'sales', 'orders', 'write', datetime('now')),
+('4220ec69-7fa8-569c-aa7b-6f4fef06f2fb', 'sales:orders:approve', 'Approve Orders', 'Approve sales orders', 'sales', 'orders', 'approve', datetime('now')),
+('2499bbf5-b10b-58fe-b594-5e8851206698', 'purchasing:vendors:read', 'View Vendors', 'View vendor list', 'purchasing', 'vendors', 'read', datetime('now')),
+('bc9532f6-a6dd-5d9d-982d-56ca77786685', 'purchasing:vendors:write', 'Edit Vendors', 'Create and edit vendors', 'purchasing', 'vendors', 'write', datetime('now')),
+('6bbc9eef-183b-5401-85ff-34931601e94d', 'purchasing:orders:read', 'View POs', 'View purchase orders', 'purchasing', 'orders', 'read', datetime('now')),
+('a4358dbd-d5a7-5901-8a91-8e18f7601711', 'purchasing:orders:write', 'Create POs', 'Create purchase orders', 'purchasing', 'orders', 'write', datetime('now')),
+('b7605f92-e12d-57c5-93d7-ae9d79e8dd78', 'purchasing:orders:approve', 'Approve POs', 'Approve purchase orders', 'purchasing', 'orders', 'approve', datetime('now')),
+('f7c6ff1b-26da-59b3-914a-0a9b22b5aef2', 'hr:employees:read', 'View Employees', 'View employee list', 'hr', 'employees', 'read', datetime('now')),
+('e15c212c-158f-5c6a-80de-978c1bcc84db', 'hr:employees:write', 'Edit Employees', 'Create and edit employees', 'hr', 'employees', 'write', datetime('now')),
+('4f0be40d-9291-541a-8cd8-2a891c18927f', 'hr:payroll:read', 'View Payroll', 'View payroll data', 'hr', 'payroll', 'read', datetime('now')),
+('333f4d2b-7925-55ad-85b3-ce98caea1e6f', 'hr:payroll:write', 'Manage Payroll', 'Create and manage payroll', 'hr', 'payroll', 'write', datetime('now')),
+('5b02b64a-508b-5331-84ad-0c177c69ef05', 'manufacturing:boms:read', 'View BOMs', 'View bill of materials', 'manufacturing', 'boms', 'read', datetime('now')),
+('7534adbf-2eab-5226-ba55-dfa2ec70d5a9', 'manufacturing:boms:write', 'Edit BOMs', 'Create and edit BOMs', 'manufacturing', 'boms', 'write', datetime('now')),
+('c8064de2-ec72-5452-b112-651643cd9fe2', 'manufacturing:workorders:read', 'View Work Orders', 'View work orders', 'manufacturing', 'workorders', 'read', datetime('now')),
+('bc155d8b-697b-57e8-ad53-748d281aa67d', 'manufacturing:workorders:write', 'Manage Work Orders', 'Create and manage work orders', 'manufacturing', 'workorders', 'write', datetime('now')),
+('bad50f7c-3fac-54c4-b1ad-4a673c47d8a1', 'admin:users:read', 'View Users', 'View user list', 'admin', 'users', 'read', datetime('now')),
+('7f4fd52c-c51e-5930-8a68-a6f0588db326', 'admin:users:write', 'Manage Users', 'Create and manage users', 'admin', 'users', 'write', datetime('now')),
+('83a781a0-4fb6-5521-a77a-cb6d2ff82b7a', 'admin:roles:read', 'View Roles', 'View role list', 'admin', 'roles', 'read', datetime('now')),
+('fdf9b2ca-1200-5b76-94dd-9508a50b65f9', 'admin:roles:write', 'Manage Roles', 'Create and manage roles', 'admin', 'roles', 'write', datetime('now')),
+('cf99cd1c-11d0-5c65-a1a9-a2c3cbc2dbde', 'admin:settings:read', 'View Settings', 'View system settings', 'admin', 'settings', 'read', datetime('now')),
+('68318ea1-daa1-51b7-b8b5-ef90af12349e', 'admin:settings:write', 'Edit Settings', 'Edit system settings', 'admin', 'settings', 'write', datetime('now')),
+('17e9cbf5-49dc-58ca-b4e4-2e3531ab4a39', 'reports:all:read', 'View Reports', 'View all reports', 'reports', 'all', 'read', datetime('now')),
+('7d730727-d9b4-59a6-8cde-c1fa338fab69', 'reports:all:export', 'Export Reports', 'Export reports', 'reports', 'all', 'export', datetime('now'));
+
+INSERT OR IGNORE INTO custom_roles (id, name, code, description, parent_role_id, is_system, is_active, created_at, updated_at) VALUES
+('dddeeab3-a362-544b-8d6f-098f1a26f7cc', 'Administrator', 'Admin', 'Full system access', NULL, 1, 1, datetime('now'), datetime('now')),
+('42dd9921-7ae0-5caf-826b-fec92dafc130', 'Finance', 'Finance', 'Finance, with read access to sales and purchasing', NULL, 1, 1, datetime('now'), datetime('now')),
+('6c915aec-aa98-5ffc-a190-b75087a8a4fc', 'Warehouse', 'Warehouse', 'Inventory and purchasing, with read access to manufacturing', NULL, 1, 1, datetime('now'), datetime('now')),
+('41885a98-9aa9-5c1f-b915-00e2011f4a70', 'Sales', 'Sales', 'Sales, with read access to inventory', NULL, 1, 1, datetime('now'), datetime('now')),
+('00a17b51-c5d0-59d6-83f0-55ddeeea774e', 'Human Resources', 'HR', 'HR module access', NULL, 1, 1, datetime('now'), datetime('now')),
+('5f551956-ce5d-5cf9-9188-b8b68a43ef40', 'User', 'User', 'Read-only access', NULL, 1, 1, datetime('now'), datetime('now'));
+
+INSERT OR IGNORE INTO role_permissions (id, role_id, permission_id, granted_at) VALUES
+('901c4cfe-8b72-5fb6-b3df-372529a0d2d2', 'dddeeab3-a362-544b-8d6f-098f1a26f7cc', '4ca0f564-ff66-54ad-8962-7e4a50d747cb', datetime('now')),
+('c9aea8c0-ed2a-50fe-ad65-99b00c0c0143', '42dd9921-7ae0-5caf-826b-fec92dafc130', '1554d022-cf3a-5f07-bebf-85cbde54813a', datetime('now')),
+('1fc2a446-e335-5973-b2c8-d45d723a80b4', '42dd9921-7ae0-5caf-826b-fec92dafc130', 'a5b6d1d6-f2e7-53fd-9a33-3c2e417dbcc0', datetime('now')),
+('07fd38b1-6b67-595d-ae28-771dd7e0b7fc', '42dd9921-7ae0-5caf-826b-fec92dafc130', '294dea50-e93d-5a89-b344-268890bf7e2d', datetime('now')),
+('14762c21-f51d-560b-a42b-20cac1f0254d', '6c915aec-aa98-5ffc-a190-b75087a8a4fc', '7f93596f-cf10-5d0b-9104-9e2cef26a324', datetime('now')),
+('d5aed268-8c8a-5428-8c33-9d4ea7270244', '6c915aec-aa98-5ffc-a190-b75087a8a4fc', '7826ad7b-5689-5dd9-bf1d-561f785bfe88', datetime('now')),
+('b82065f6-dfe5-5b51-ae5f-ea90dbefbbe1', '6c915aec-aa98-5ffc-a190-b75087a8a4fc', 'b9102762-2f8d-5c80-9799-40927950b252', datetime('now')),
+('004bfdd8-0173-5ace-b0c7-29d24ac29de0', '41885a98-9aa9-5c1f-b915-00e2011f4a70', '8a7cb4b4-f24c-5217-a7b6-2b9bf9a830c8', datetime('now')),
+('091b8474-7511-5d00-a393-affab2e1f26c', '41885a98-9aa9-5c1f-b915-00e2011f4a70', '9d34b37f-6884-5e0e-bff7-14538050cf61', datetime('now')),
+('cd339d69-cd7b-5d41-a832-51f713bd06b6', '00a17b51-c5d0-59d6-83f0-55ddeeea774e', '6d7d0aa4-2278-5287-b3af-49d773b83edd', datetime('now')),
+('a22430da-eca9-565c-95c8-02285a8e4b65', '5f551956-ce5d-5cf9-9188-b8b68a43ef40', 'f01c95be-2165-586b-a3ea-500ace2e0cf7', datetime('now'));
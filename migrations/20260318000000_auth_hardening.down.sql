@@ -0,0 +1,21 @@
+DROP TABLE IF EXISTS password_reset_tokens;
+DROP TABLE IF EXISTS revoked_tokens;
+
+ALTER TABLE users DROP COLUMN locked_until;
+ALTER TABLE users DROP COLUMN failed_login_attempts;
+
+CREATE TABLE user_sessions_original (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+INSERT INTO user_sessions_original (id, user_id, token_hash, expires_at, created_at)
+SELECT id, user_id, token_hash, expires_at, created_at FROM user_sessions;
+
+DROP TABLE user_sessions;
+ALTER TABLE user_sessions_original RENAME TO user_sessions;
+
+CREATE INDEX idx_user_sessions_user ON user_sessions(user_id);
@@ -0,0 +1,49 @@
+-- The auth migration created user_sessions without the columns erp-security reads and writes, so the
+-- later CREATE TABLE IF NOT EXISTS never took effect. Rebuild it with those columns plus the hash of
+-- the refresh token it replaced, which is how reuse of a rotated-out token is detected.
+CREATE TABLE user_sessions_rebuilt (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    previous_token_hash TEXT,
+    ip_address TEXT,
+    user_agent TEXT,
+    device_type TEXT,
+    is_current INTEGER NOT NULL DEFAULT 0,
+    last_activity TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+INSERT OR IGNORE INTO user_sessions_rebuilt (id, user_id, token_hash, last_activity, expires_at, created_at)
+SELECT id, user_id, token_hash, created_at, expires_at, created_at FROM user_sessions;
+
+DROP TABLE user_sessions;
+ALTER TABLE user_sessions_rebuilt RENAME TO user_sessions;
+
+CREATE INDEX idx_user_sessions_user ON user_sessions(user_id);
+CREATE INDEX idx_user_sessions_previous_token ON user_sessions(previous_token_hash);
+
+ALTER TABLE users ADD COLUMN failed_login_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE users ADD COLUMN locked_until TEXT;
+
+-- Access tokens revoked before they expire; rows can be purged once expires_at has passed.
+CREATE TABLE revoked_tokens (
+    jti TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    revoked_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_revoked_tokens_expires ON revoked_tokens(expires_at);
+
+CREATE TABLE password_reset_tokens (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    expires_at TEXT NOT NULL,
+    used_at TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_password_reset_tokens_user ON password_reset_tokens(user_id);
@@ -0,0 +1,18 @@
+DROP INDEX IF EXISTS idx_payments_gateway_transaction;
+
+ALTER TABLE payments DROP COLUMN updated_by;
+ALTER TABLE payments DROP COLUMN created_by;
+ALTER TABLE payments DROP COLUMN paid_at;
+ALTER TABLE payments DROP COLUMN notes;
+ALTER TABLE payments DROP COLUMN processing_fee;
+ALTER TABLE payments DROP COLUMN refund_reason;
+ALTER TABLE payments DROP COLUMN refunded_amount;
+ALTER TABLE payments DROP COLUMN check_number;
+ALTER TABLE payments DROP COLUMN bank_account_last_four;
+ALTER TABLE payments DROP COLUMN bank_name;
+ALTER TABLE payments DROP COLUMN card_brand;
+ALTER TABLE payments DROP COLUMN card_last_four;
+ALTER TABLE payments DROP COLUMN gateway_response;
+ALTER TABLE payments DROP COLUMN gateway_transaction_id;
+ALTER TABLE payments DROP COLUMN status;
+ALTER TABLE payments DROP COLUMN gateway_id;
@@ -0,0 +1,19 @@
+-- Gateway payments share the payments table with receivables, and these columns hold what the gateway reports.
+ALTER TABLE payments ADD COLUMN gateway_id TEXT;
+ALTER TABLE payments ADD COLUMN status TEXT NOT NULL DEFAULT 'Completed';
+ALTER TABLE payments ADD COLUMN gateway_transaction_id TEXT;
+ALTER TABLE payments ADD COLUMN gateway_response TEXT;
+ALTER TABLE payments ADD COLUMN card_last_four TEXT;
+ALTER TABLE payments ADD COLUMN card_brand TEXT;
+ALTER TABLE payments ADD COLUMN bank_name TEXT;
+ALTER TABLE payments ADD COLUMN bank_account_last_four TEXT;
+ALTER TABLE payments ADD COLUMN check_number TEXT;
+ALTER TABLE payments ADD COLUMN refunded_amount INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE payments ADD COLUMN refund_reason TEXT;
+ALTER TABLE payments ADD COLUMN processing_fee INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE payments ADD COLUMN notes TEXT;
+ALTER TABLE payments ADD COLUMN paid_at TEXT;
+ALTER TABLE payments ADD COLUMN created_by TEXT;
+ALTER TABLE payments ADD COLUMN updated_by TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_payments_gateway_transaction ON payments(gateway_transaction_id);
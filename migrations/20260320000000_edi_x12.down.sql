@@ -0,0 +1,17 @@
+DROP INDEX IF EXISTS idx_edi_acknowledgments_transaction;
+DROP INDEX IF EXISTS idx_edi_850_orders_po;
+DROP INDEX IF EXISTS idx_edi_850_orders_sales_order;
+
+ALTER TABLE edi_850_orders DROP COLUMN sales_order_id;
+
+DROP INDEX IF EXISTS idx_edi_partners_interchange;
+
+ALTER TABLE edi_partners DROP COLUMN transaction_control_number;
+ALTER TABLE edi_partners DROP COLUMN group_control_number;
+ALTER TABLE edi_partners DROP COLUMN interchange_control_number;
+ALTER TABLE edi_partners DROP COLUMN encryption;
+ALTER TABLE edi_partners DROP COLUMN customer_id;
+ALTER TABLE edi_partners DROP COLUMN local_interchange_id;
+ALTER TABLE edi_partners DROP COLUMN local_qualifier;
+ALTER TABLE edi_partners DROP COLUMN interchange_id;
+ALTER TABLE edi_partners DROP COLUMN qualifier;
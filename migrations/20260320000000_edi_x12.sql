@@ -0,0 +1,19 @@
+-- X12 identities and control-number counters for each trading partner.
+ALTER TABLE edi_partners ADD COLUMN qualifier TEXT NOT NULL DEFAULT 'ZZ';
+ALTER TABLE edi_partners ADD COLUMN interchange_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE edi_partners ADD COLUMN local_qualifier TEXT NOT NULL DEFAULT 'ZZ';
+ALTER TABLE edi_partners ADD COLUMN local_interchange_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE edi_partners ADD COLUMN customer_id TEXT;
+ALTER TABLE edi_partners ADD COLUMN encryption TEXT;
+ALTER TABLE edi_partners ADD COLUMN interchange_control_number INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE edi_partners ADD COLUMN group_control_number INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE edi_partners ADD COLUMN transaction_control_number INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_edi_partners_interchange ON edi_partners(qualifier, interchange_id);
+
+-- Inbound 850s are booked as draft sales orders.
+ALTER TABLE edi_850_orders ADD COLUMN sales_order_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_edi_850_orders_sales_order ON edi_850_orders(sales_order_id);
+CREATE INDEX IF NOT EXISTS idx_edi_850_orders_po ON edi_850_orders(customer_id, po_number);
+CREATE INDEX IF NOT EXISTS idx_edi_acknowledgments_transaction ON edi_acknowledgments(transaction_id);
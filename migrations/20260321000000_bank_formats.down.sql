@@ -0,0 +1,12 @@
+DROP INDEX IF EXISTS idx_payment_file_items_bill;
+DROP INDEX IF EXISTS idx_payment_file_items_file;
+DROP TABLE IF EXISTS payment_file_items;
+DROP TABLE IF EXISTS vendor_bank_accounts;
+
+DROP INDEX IF EXISTS idx_bank_statements_account_date;
+
+ALTER TABLE bank_accounts DROP COLUMN bic;
+ALTER TABLE bank_accounts DROP COLUMN iban;
+ALTER TABLE bank_accounts DROP COLUMN ach_company_id;
+ALTER TABLE bank_accounts DROP COLUMN routing_number;
+ALTER TABLE bank_accounts DROP COLUMN company_name;
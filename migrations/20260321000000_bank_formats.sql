@@ -0,0 +1,34 @@
+-- Originator details for payment files.
+ALTER TABLE bank_accounts ADD COLUMN company_name TEXT;
+ALTER TABLE bank_accounts ADD COLUMN routing_number TEXT;
+ALTER TABLE bank_accounts ADD COLUMN ach_company_id TEXT;
+ALTER TABLE bank_accounts ADD COLUMN iban TEXT;
+ALTER TABLE bank_accounts ADD COLUMN bic TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_bank_statements_account_date ON bank_statements(bank_account_id, statement_date);
+
+-- Where each vendor is paid.
+CREATE TABLE IF NOT EXISTS vendor_bank_accounts (
+    vendor_id TEXT PRIMARY KEY,
+    account_name TEXT NOT NULL,
+    account_type TEXT NOT NULL DEFAULT 'Checking',
+    routing_number TEXT,
+    account_number TEXT,
+    iban TEXT,
+    bic TEXT,
+    updated_at TEXT NOT NULL
+);
+
+-- The bills each generated payment file pays.
+CREATE TABLE IF NOT EXISTS payment_file_items (
+    id TEXT PRIMARY KEY,
+    payment_file_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    bill_id TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    end_to_end_id TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_payment_file_items_file ON payment_file_items(payment_file_id);
+CREATE INDEX IF NOT EXISTS idx_payment_file_items_bill ON payment_file_items(bill_id);
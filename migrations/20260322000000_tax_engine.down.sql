@@ -0,0 +1,21 @@
+ALTER TABLE products DROP COLUMN tax_class_id;
+
+DROP INDEX IF EXISTS idx_tax_transactions_reference;
+
+ALTER TABLE tax_transactions DROP COLUMN reverse_charge;
+ALTER TABLE tax_transactions DROP COLUMN currency;
+
+DROP INDEX IF EXISTS idx_tax_jurisdictions_country;
+DROP INDEX IF EXISTS idx_tax_rates_jurisdiction;
+
+ALTER TABLE tax_rates DROP COLUMN tax_class_id;
+ALTER TABLE tax_rates DROP COLUMN max_amount;
+ALTER TABLE tax_rates DROP COLUMN min_amount;
+ALTER TABLE tax_rates DROP COLUMN priority;
+ALTER TABLE tax_rates DROP COLUMN effective_to;
+ALTER TABLE tax_rates DROP COLUMN effective_from;
+ALTER TABLE tax_rates DROP COLUMN calculation_method;
+ALTER TABLE tax_rates DROP COLUMN is_recoverable;
+ALTER TABLE tax_rates DROP COLUMN is_compound;
+ALTER TABLE tax_rates DROP COLUMN tax_type;
+ALTER TABLE tax_rates DROP COLUMN jurisdiction_id;
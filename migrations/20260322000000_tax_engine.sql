@@ -0,0 +1,23 @@
+-- The finance module created tax_rates first, so the jurisdiction-aware columns never landed.
+ALTER TABLE tax_rates ADD COLUMN jurisdiction_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE tax_rates ADD COLUMN tax_type TEXT NOT NULL DEFAULT 'SalesTax';
+ALTER TABLE tax_rates ADD COLUMN is_compound INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tax_rates ADD COLUMN is_recoverable INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tax_rates ADD COLUMN calculation_method TEXT NOT NULL DEFAULT 'Exclusive';
+ALTER TABLE tax_rates ADD COLUMN effective_from TEXT NOT NULL DEFAULT '1970-01-01T00:00:00+00:00';
+ALTER TABLE tax_rates ADD COLUMN effective_to TEXT;
+ALTER TABLE tax_rates ADD COLUMN priority INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE tax_rates ADD COLUMN min_amount INTEGER;
+ALTER TABLE tax_rates ADD COLUMN max_amount INTEGER;
+-- Rates scoped to one product tax class override the jurisdiction's general rate.
+ALTER TABLE tax_rates ADD COLUMN tax_class_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_tax_rates_jurisdiction ON tax_rates(jurisdiction_id);
+CREATE INDEX IF NOT EXISTS idx_tax_jurisdictions_country ON tax_jurisdictions(country_code, state_code);
+
+ALTER TABLE tax_transactions ADD COLUMN currency TEXT NOT NULL DEFAULT 'USD';
+ALTER TABLE tax_transactions ADD COLUMN reverse_charge INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_tax_transactions_reference ON tax_transactions(transaction_type, transaction_id);
+
+ALTER TABLE products ADD COLUMN tax_class_id TEXT;
@@ -0,0 +1,60 @@
+DROP TRIGGER IF EXISTS customers_search_ai;
+DROP TRIGGER IF EXISTS customers_search_au;
+DROP TRIGGER IF EXISTS customers_search_ad;
+DROP TRIGGER IF EXISTS products_search_ai;
+DROP TRIGGER IF EXISTS products_search_au;
+DROP TRIGGER IF EXISTS products_search_ad;
+DROP TRIGGER IF EXISTS vendors_search_ai;
+DROP TRIGGER IF EXISTS vendors_search_au;
+DROP TRIGGER IF EXISTS vendors_search_ad;
+DROP TRIGGER IF EXISTS sales_orders_search_ai;
+DROP TRIGGER IF EXISTS sales_orders_search_au;
+DROP TRIGGER IF EXISTS sales_orders_search_ad;
+DROP TRIGGER IF EXISTS purchase_orders_search_ai;
+DROP TRIGGER IF EXISTS purchase_orders_search_au;
+DROP TRIGGER IF EXISTS purchase_orders_search_ad;
+DROP TRIGGER IF EXISTS employees_search_ai;
+DROP TRIGGER IF EXISTS employees_search_au;
+DROP TRIGGER IF EXISTS employees_search_ad;
+DROP TRIGGER IF EXISTS documents_search_ai;
+DROP TRIGGER IF EXISTS documents_search_au;
+DROP TRIGGER IF EXISTS documents_search_ad;
+DROP TRIGGER IF EXISTS knowledge_articles_search_ai;
+DROP TRIGGER IF EXISTS knowledge_articles_search_au;
+DROP TRIGGER IF EXISTS knowledge_articles_search_ad;
+DROP TRIGGER IF EXISTS search_index_ai;
+DROP TRIGGER IF EXISTS search_index_ad;
+DROP TRIGGER IF EXISTS search_index_au;
+
+DROP VIEW IF EXISTS search_sources;
+DROP TABLE IF EXISTS search_index_vocab;
+DROP TABLE IF EXISTS search_index_fts;
+
+ALTER TABLE search_index RENAME TO search_index_new;
+
+CREATE TABLE search_index (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT,
+    keywords TEXT,
+    tenant_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, tenant_id, created_at, updated_at)
+SELECT id, entity_type, entity_id, title, content, keywords, tenant_id, created_at, updated_at FROM search_index_new;
+
+DROP TABLE search_index_new;
+
+CREATE VIRTUAL TABLE IF NOT EXISTS search_index_fts USING fts5(
+    title,
+    content,
+    keywords,
+    content='search_index',
+    content_rowid='rowid'
+);
+
+CREATE INDEX IF NOT EXISTS idx_search_entity ON search_index(entity_type, entity_id);
@@ -0,0 +1,280 @@
+-- Global search runs on FTS5. search_index gets an INTEGER PRIMARY KEY so the full-text index,
+-- which refers to rows by rowid, survives VACUUM, and one entry per entity so upserts can replace it.
+ALTER TABLE search_index RENAME TO search_index_old;
+DROP TABLE IF EXISTS search_index_fts;
+DROP INDEX IF EXISTS idx_search_entity;
+
+CREATE TABLE search_index (
+    doc_id INTEGER PRIMARY KEY,
+    id TEXT NOT NULL UNIQUE,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT,
+    keywords TEXT,
+    tenant_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (entity_type, entity_id)
+);
+
+INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, tenant_id, created_at, updated_at)
+SELECT id, entity_type, entity_id, title, content, keywords, tenant_id, created_at, updated_at
+FROM search_index_old
+WHERE rowid IN (SELECT MAX(rowid) FROM search_index_old GROUP BY entity_type, entity_id);
+
+DROP TABLE search_index_old;
+
+CREATE VIRTUAL TABLE search_index_fts USING fts5(
+    title,
+    content,
+    keywords,
+    content='search_index',
+    content_rowid='doc_id',
+    tokenize='unicode61 remove_diacritics 2',
+    prefix='2 3'
+);
+
+-- Every indexed word with its document count, for typo correction.
+CREATE VIRTUAL TABLE search_index_vocab USING fts5vocab(search_index_fts, 'row');
+
+CREATE TRIGGER search_index_ai AFTER INSERT ON search_index BEGIN
+    INSERT INTO search_index_fts (rowid, title, content, keywords) VALUES (new.doc_id, new.title, new.content, new.keywords);
+END;
+
+CREATE TRIGGER search_index_ad AFTER DELETE ON search_index BEGIN
+    INSERT INTO search_index_fts (search_index_fts, rowid, title, content, keywords) VALUES ('delete', old.doc_id, old.title, old.content, old.keywords);
+END;
+
+CREATE TRIGGER search_index_au AFTER UPDATE ON search_index BEGIN
+    INSERT INTO search_index_fts (search_index_fts, rowid, title, content, keywords) VALUES ('delete', old.doc_id, old.title, old.content, old.keywords);
+    INSERT INTO search_index_fts (rowid, title, content, keywords) VALUES (new.doc_id, new.title, new.content, new.keywords);
+END;
+
+-- What each searchable entity contributes to the index. The triggers below and the rebuild
+-- command both read from here, and a row that drops out of the view, like a deleted product,
+-- drops out of the index.
+CREATE VIEW search_sources AS
+SELECT 'customer' AS entity_type, id AS entity_id, name AS title,
+       trim(COALESCE(email, '') || ' ' || COALESCE(phone, '') || ' ' || COALESCE(billing_city, '') || ' ' || COALESCE(billing_country, '')) AS content,
+       code AS keywords, created_at, updated_at
+FROM customers
+UNION ALL
+SELECT 'product', id, name, description, sku, created_at, updated_at
+FROM products WHERE status <> 'Deleted'
+UNION ALL
+SELECT 'vendor', id, name,
+       trim(COALESCE(email, '') || ' ' || COALESCE(phone, '') || ' ' || COALESCE(city, '') || ' ' || COALESCE(country, '')),
+       code, created_at, updated_at
+FROM vendors
+UNION ALL
+SELECT 'sales_order', o.id, o.order_number, trim(COALESCE(c.name, '') || ' ' || o.status), c.code, o.created_at, o.updated_at
+FROM sales_orders o LEFT JOIN customers c ON c.id = o.customer_id
+UNION ALL
+SELECT 'purchase_order', p.id, p.po_number, trim(COALESCE(v.name, '') || ' ' || p.status), v.code, p.created_at, p.updated_at
+FROM purchase_orders p LEFT JOIN vendors v ON v.id = p.vendor_id
+UNION ALL
+SELECT 'employee', id, first_name || ' ' || last_name,
+       trim(email || ' ' || COALESCE(phone, '') || ' ' || COALESCE(city, '')),
+       employee_number, created_at, updated_at
+FROM employees
+UNION ALL
+SELECT 'document', id, title, description, trim(document_number || ' ' || file_name || ' ' || COALESCE(tags, '')), created_at, updated_at
+FROM documents
+UNION ALL
+SELECT 'knowledge_article', id, title, trim(COALESCE(summary, '') || ' ' || content), tags, created_at, updated_at
+FROM knowledge_articles;
+
+CREATE TRIGGER customers_search_ai AFTER INSERT ON customers BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'customer' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+END;
+
+CREATE TRIGGER customers_search_au AFTER UPDATE ON customers BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'customer' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+    DELETE FROM search_index WHERE entity_type = 'customer' AND entity_id = new.id
+        AND NOT EXISTS (SELECT 1 FROM search_sources WHERE entity_type = 'customer' AND entity_id = new.id);
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'sales_order' AND entity_id IN (SELECT id FROM sales_orders WHERE customer_id = new.id)
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+END;
+
+CREATE TRIGGER customers_search_ad AFTER DELETE ON customers BEGIN
+    DELETE FROM search_index WHERE entity_type = 'customer' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER products_search_ai AFTER INSERT ON products BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'product' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+END;
+
+CREATE TRIGGER products_search_au AFTER UPDATE ON products BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'product' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+    DELETE FROM search_index WHERE entity_type = 'product' AND entity_id = new.id
+        AND NOT EXISTS (SELECT 1 FROM search_sources WHERE entity_type = 'product' AND entity_id = new.id);
+END;
+
+CREATE TRIGGER products_search_ad AFTER DELETE ON products BEGIN
+    DELETE FROM search_index WHERE entity_type = 'product' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER vendors_search_ai AFTER INSERT ON vendors BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'vendor' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+END;
+
+CREATE TRIGGER vendors_search_au AFTER UPDATE ON vendors BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'vendor' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+    DELETE FROM search_index WHERE entity_type = 'vendor' AND entity_id = new.id
+        AND NOT EXISTS (SELECT 1 FROM search_sources WHERE entity_type = 'vendor' AND entity_id = new.id);
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'purchase_order' AND entity_id IN (SELECT id FROM purchase_orders WHERE vendor_id = new.id)
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+END;
+
+CREATE TRIGGER vendors_search_ad AFTER DELETE ON vendors BEGIN
+    DELETE FROM search_index WHERE entity_type = 'vendor' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER sales_orders_search_ai AFTER INSERT ON sales_orders BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'sales_order' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+END;
+
+CREATE TRIGGER sales_orders_search_au AFTER UPDATE ON sales_orders BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'sales_order' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+    DELETE FROM search_index WHERE entity_type = 'sales_order' AND entity_id = new.id
+        AND NOT EXISTS (SELECT 1 FROM search_sources WHERE entity_type = 'sales_order' AND entity_id = new.id);
+END;
+
+CREATE TRIGGER sales_orders_search_ad AFTER DELETE ON sales_orders BEGIN
+    DELETE FROM search_index WHERE entity_type = 'sales_order' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER purchase_orders_search_ai AFTER INSERT ON purchase_orders BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'purchase_order' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+END;
+
+CREATE TRIGGER purchase_orders_search_au AFTER UPDATE ON purchase_orders BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'purchase_order' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+    DELETE FROM search_index WHERE entity_type = 'purchase_order' AND entity_id = new.id
+        AND NOT EXISTS (SELECT 1 FROM search_sources WHERE entity_type = 'purchase_order' AND entity_id = new.id);
+END;
+
+CREATE TRIGGER purchase_orders_search_ad AFTER DELETE ON purchase_orders BEGIN
+    DELETE FROM search_index WHERE entity_type = 'purchase_order' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER employees_search_ai AFTER INSERT ON employees BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'employee' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+END;
+
+CREATE TRIGGER employees_search_au AFTER UPDATE ON employees BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'employee' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+    DELETE FROM search_index WHERE entity_type = 'employee' AND entity_id = new.id
+        AND NOT EXISTS (SELECT 1 FROM search_sources WHERE entity_type = 'employee' AND entity_id = new.id);
+END;
+
+CREATE TRIGGER employees_search_ad AFTER DELETE ON employees BEGIN
+    DELETE FROM search_index WHERE entity_type = 'employee' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER documents_search_ai AFTER INSERT ON documents BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'document' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+END;
+
+CREATE TRIGGER documents_search_au AFTER UPDATE ON documents BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'document' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+    DELETE FROM search_index WHERE entity_type = 'document' AND entity_id = new.id
+        AND NOT EXISTS (SELECT 1 FROM search_sources WHERE entity_type = 'document' AND entity_id = new.id);
+END;
+
+CREATE TRIGGER documents_search_ad AFTER DELETE ON documents BEGIN
+    DELETE FROM search_index WHERE entity_type = 'document' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER knowledge_articles_search_ai AFTER INSERT ON knowledge_articles BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'knowledge_article' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+END;
+
+CREATE TRIGGER knowledge_articles_search_au AFTER UPDATE ON knowledge_articles BEGIN
+    INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+    SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+    FROM search_sources WHERE entity_type = 'knowledge_article' AND entity_id = new.id
+    ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+        title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+    DELETE FROM search_index WHERE entity_type = 'knowledge_article' AND entity_id = new.id
+        AND NOT EXISTS (SELECT 1 FROM search_sources WHERE entity_type = 'knowledge_article' AND entity_id = new.id);
+END;
+
+CREATE TRIGGER knowledge_articles_search_ad AFTER DELETE ON knowledge_articles BEGIN
+    DELETE FROM search_index WHERE entity_type = 'knowledge_article' AND entity_id = old.id;
+END;
+
+INSERT INTO search_index (id, entity_type, entity_id, title, content, keywords, created_at, updated_at)
+SELECT entity_type || ':' || entity_id, entity_type, entity_id, title, content, keywords, created_at, updated_at
+FROM search_sources WHERE true
+ON CONFLICT (entity_type, entity_id) DO UPDATE SET
+    title = excluded.title, content = excluded.content, keywords = excluded.keywords, updated_at = excluded.updated_at;
+
+INSERT INTO search_index_fts (search_index_fts) VALUES ('rebuild');
@@ -0,0 +1,8 @@
+ALTER TABLE document_folders DROP COLUMN allowed_mime_types;
+ALTER TABLE document_folders DROP COLUMN max_file_size;
+
+DROP INDEX IF EXISTS idx_attachments_checksum;
+
+ALTER TABLE attachments DROP COLUMN checksum;
+
+DROP TABLE IF EXISTS blobs;
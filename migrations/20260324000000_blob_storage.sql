@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS blobs (
+    sha256 TEXT PRIMARY KEY,
+    size INTEGER NOT NULL,
+    mime_type TEXT NOT NULL,
+    backend TEXT NOT NULL,
+    ref_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+
+ALTER TABLE attachments ADD COLUMN checksum TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_attachments_checksum ON attachments(checksum);
+
+ALTER TABLE document_folders ADD COLUMN max_file_size INTEGER;
+ALTER TABLE document_folders ADD COLUMN allowed_mime_types TEXT;
@@ -0,0 +1,52 @@
+CREATE TABLE report_definitions_previous (
+    id TEXT PRIMARY KEY,
+    report_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    category TEXT,
+    data_source TEXT NOT NULL,
+    query_text TEXT NOT NULL,
+    parameters TEXT,
+    columns TEXT,
+    filters TEXT,
+    sorting TEXT,
+    grouping TEXT,
+    chart_type TEXT,
+    is_scheduled INTEGER DEFAULT 0,
+    schedule_cron TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL
+);
+
+INSERT INTO report_definitions_previous (id, report_code, name, description, category, data_source, query_text, parameters, columns, is_scheduled, created_by, created_at)
+SELECT id, code, name, description, category, data_source, query, parameters, columns, is_scheduled, created_by, created_at
+FROM report_definitions;
+
+CREATE TEMP TABLE report_executions_staged AS SELECT * FROM report_executions;
+
+DROP TABLE report_executions;
+DROP TABLE report_definitions;
+ALTER TABLE report_definitions_previous RENAME TO report_definitions;
+
+CREATE TABLE report_executions (
+    id TEXT PRIMARY KEY,
+    report_id TEXT NOT NULL,
+    parameters TEXT,
+    row_count INTEGER,
+    file_path TEXT,
+    file_format TEXT,
+    file_size INTEGER,
+    execution_time_ms INTEGER,
+    status TEXT DEFAULT 'Running',
+    error_message TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (report_id) REFERENCES report_definitions(id)
+);
+
+INSERT INTO report_executions (id, report_id, parameters, row_count, file_path, file_format, file_size, execution_time_ms, status, error_message, created_by, created_at)
+SELECT id, report_definition_id, parameters, row_count, file_path, format, file_size_bytes, duration_ms, status, error_message, executed_by, created_at
+FROM report_executions_staged;
+
+DROP TABLE report_executions_staged;
+
@@ -0,0 +1,66 @@
+-- report_definitions and report_executions still had the shape of the enterprise features migration,
+-- so the later CREATE TABLE IF NOT EXISTS for the reports module never took effect. Rebuild both in
+-- the shape erp-reports reads and writes. Definitions now hold a semantic query as JSON rather than
+-- SQL text, so carried-over definitions are kept inactive with an empty query until redefined.
+CREATE TABLE report_definitions_rebuilt (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    category TEXT NOT NULL DEFAULT 'Custom',
+    description TEXT,
+    data_source TEXT NOT NULL DEFAULT '',
+    query TEXT NOT NULL DEFAULT '{}',
+    parameters TEXT NOT NULL DEFAULT '[]',
+    columns TEXT NOT NULL DEFAULT '[]',
+    default_format TEXT NOT NULL DEFAULT 'PDF',
+    allowed_formats TEXT NOT NULL DEFAULT '[]',
+    is_scheduled INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_by TEXT,
+    version INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+INSERT INTO report_definitions_rebuilt (id, name, code, category, description, data_source, status, is_scheduled, created_by, created_at, updated_at)
+SELECT id, name, report_code, COALESCE(category, 'Custom'), description, data_source, 'Inactive', COALESCE(is_scheduled, 0), created_by, created_at, created_at
+FROM report_definitions;
+
+CREATE TEMP TABLE report_executions_staged AS SELECT * FROM report_executions;
+
+DROP TABLE report_executions;
+DROP TABLE report_definitions;
+ALTER TABLE report_definitions_rebuilt RENAME TO report_definitions;
+
+CREATE TABLE report_executions (
+    id TEXT PRIMARY KEY,
+    report_definition_id TEXT NOT NULL REFERENCES report_definitions(id),
+    schedule_id TEXT,
+    parameters TEXT NOT NULL DEFAULT '{}',
+    format TEXT NOT NULL DEFAULT 'PDF',
+    status TEXT NOT NULL DEFAULT 'Pending',
+    started_at TEXT,
+    completed_at TEXT,
+    duration_ms INTEGER,
+    row_count INTEGER NOT NULL DEFAULT 0,
+    file_path TEXT,
+    file_size_bytes INTEGER,
+    checksum TEXT,
+    error_message TEXT,
+    delivery_status TEXT,
+    delivered_at TEXT,
+    executed_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+INSERT INTO report_executions (id, report_definition_id, parameters, format, status, started_at, duration_ms, row_count, file_path, file_size_bytes, error_message, executed_by, created_at, updated_at)
+SELECT id, report_id, COALESCE(parameters, '{}'), COALESCE(file_format, 'PDF'), COALESCE(status, 'Completed'), created_at, execution_time_ms, COALESCE(row_count, 0), file_path, file_size, error_message, created_by, created_at, created_at
+FROM report_executions_staged;
+
+DROP TABLE report_executions_staged;
+
+CREATE INDEX idx_report_definitions_category ON report_definitions(category);
+CREATE INDEX idx_report_executions_definition ON report_executions(report_definition_id);
+CREATE INDEX idx_report_executions_schedule ON report_executions(schedule_id);
+CREATE INDEX idx_report_executions_executed_by ON report_executions(executed_by);
@@ -0,0 +1,2 @@
+DELETE FROM generated_documents WHERE template_id IN ('00000000-0000-4000-a000-000000000001', '00000000-0000-4000-a000-000000000002', '00000000-0000-4000-a000-000000000011', '00000000-0000-4000-a000-000000000012', '00000000-0000-4000-a000-000000000013', '00000000-0000-4000-a000-000000000014', '00000000-0000-4000-a000-000000000015', '00000000-0000-4000-a000-000000000016');
+DELETE FROM templates WHERE id IN ('00000000-0000-4000-a000-000000000001', '00000000-0000-4000-a000-000000000002', '00000000-0000-4000-a000-000000000011', '00000000-0000-4000-a000-000000000012', '00000000-0000-4000-a000-000000000013', '00000000-0000-4000-a000-000000000014', '00000000-0000-4000-a000-000000000015', '00000000-0000-4000-a000-000000000016');
@@ -0,0 +1,152 @@
+-- Standard print templates. They render to PDF from the document markup of erp-templates and share
+-- a header and footer template. Amounts are in hundredths of the document currency, dates ISO 8601.
+
+INSERT OR IGNORE INTO templates (id, name, code, description, template_type, format, body, variables, header_template_id, footer_template_id, version, status, created_by, created_at, updated_at)
+VALUES ('00000000-0000-4000-a000-000000000001', 'Standard document header', 'STD_DOCUMENT_HEADER', 'Company name, logo and address at the top of every page', 'Document', 'PDF',
+'<row>
+  <cell>{{#if company.logo}}<image src="{{company.logo}}" height="36"/>{{else}}<h2>{{company.name}}</h2>{{/if}}</cell>
+  <cell width="45%"><p align="right" bold="true">{{company.name}}</p><p align="right" size="8">{{company.address}}</p></cell>
+</row>
+<hr/>',
+'["company"]', NULL, NULL, 1, 'Active', '00000000-0000-0000-0000-000000000000', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+INSERT OR IGNORE INTO templates (id, name, code, description, template_type, format, body, variables, header_template_id, footer_template_id, version, status, created_by, created_at, updated_at)
+VALUES ('00000000-0000-4000-a000-000000000002', 'Standard document footer', 'STD_DOCUMENT_FOOTER', 'Company details and page numbers at the foot of every page', 'Document', 'PDF',
+'<hr/>
+<row>
+  <cell><p size="8">{{company.name}}{{#if company.tax_id}} - VAT {{company.tax_id}}{{/if}}</p></cell>
+  <cell width="30%"><p align="right" size="8">Page <page-number/> of <page-count/></p></cell>
+</row>',
+'["company"]', NULL, NULL, 1, 'Active', '00000000-0000-0000-0000-000000000000', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+INSERT OR IGNORE INTO templates (id, name, code, description, template_type, format, body, variables, header_template_id, footer_template_id, version, status, created_by, created_at, updated_at)
+VALUES ('00000000-0000-4000-a000-000000000011', 'Sales invoice', 'STD_SALES_INVOICE', 'Invoice with line items, tax and total due', 'Invoice', 'PDF',
+'<document size="A4">
+<h1>Invoice {{invoice_number}}</h1>
+<row>
+  <cell><p bold="true">Bill to</p><p>{{customer.name}}<br/>{{customer.address}}</p></cell>
+  <cell width="40%"><table>
+    <tr><td>Invoice date</td><td align="right">{{format_date invoice_date}}</td></tr>
+    <tr><td>Due date</td><td align="right">{{format_date due_date}}</td></tr>
+    {{#if order_number}}<tr><td>Order</td><td align="right">{{order_number}}</td></tr>{{/if}}
+  </table></cell>
+</row>
+<table>
+  <thead><tr><th>Description</th><th width="12%" align="right">Qty</th><th width="18%" align="right">Unit price</th><th width="18%" align="right">Amount</th></tr></thead>
+  <tbody>{{#each lines}}<tr><td>{{description}}</td><td align="right">{{format_number quantity}}</td><td align="right">{{format_money unit_price ../currency}}</td><td align="right">{{format_money amount ../currency}}</td></tr>{{/each}}</tbody>
+  <tfoot>
+    <tr><td colspan="3" align="right">Subtotal</td><td align="right">{{format_money subtotal currency}}</td></tr>
+    <tr><td colspan="3" align="right">Tax</td><td align="right">{{format_money tax currency}}</td></tr>
+    <tr><td colspan="3" align="right">Total due</td><td align="right">{{format_money total currency}}</td></tr>
+  </tfoot>
+</table>
+{{#if notes}}<p>{{notes}}</p>{{/if}}
+{{#if invoice_number}}<barcode type="Code128" value="{{invoice_number}}" height="30"/>{{/if}}
+</document>',
+'["company", "customer", "invoice_number", "invoice_date", "due_date", "order_number", "currency", "lines", "subtotal", "tax", "total", "notes"]', '00000000-0000-4000-a000-000000000001', '00000000-0000-4000-a000-000000000002', 1, 'Active', '00000000-0000-0000-0000-000000000000', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+INSERT OR IGNORE INTO templates (id, name, code, description, template_type, format, body, variables, header_template_id, footer_template_id, version, status, created_by, created_at, updated_at)
+VALUES ('00000000-0000-4000-a000-000000000012', 'Purchase order', 'STD_PURCHASE_ORDER', 'Purchase order to a supplier with delivery address', 'PurchaseOrder', 'PDF',
+'<document size="A4">
+<h1>Purchase order {{po_number}}</h1>
+<row>
+  <cell><p bold="true">Supplier</p><p>{{supplier.name}}<br/>{{supplier.address}}</p></cell>
+  <cell><p bold="true">Deliver to</p><p>{{ship_to.name}}<br/>{{ship_to.address}}</p></cell>
+  <cell width="30%"><table>
+    <tr><td>Order date</td><td align="right">{{format_date order_date}}</td></tr>
+    <tr><td>Expected</td><td align="right">{{format_date expected_date}}</td></tr>
+  </table></cell>
+</row>
+<table>
+  <thead><tr><th width="18%">SKU</th><th>Description</th><th width="10%" align="right">Qty</th><th width="16%" align="right">Unit price</th><th width="16%" align="right">Amount</th></tr></thead>
+  <tbody>{{#each lines}}<tr><td>{{sku}}</td><td>{{description}}</td><td align="right">{{format_number quantity}}</td><td align="right">{{format_money unit_price ../currency}}</td><td align="right">{{format_money amount ../currency}}</td></tr>{{/each}}</tbody>
+  <tfoot><tr><td colspan="4" align="right">Total</td><td align="right">{{format_money total currency}}</td></tr></tfoot>
+</table>
+{{#if terms}}<p size="8">{{terms}}</p>{{/if}}
+</document>',
+'["company", "supplier", "ship_to", "po_number", "order_date", "expected_date", "currency", "lines", "total", "terms"]', '00000000-0000-4000-a000-000000000001', '00000000-0000-4000-a000-000000000002', 1, 'Active', '00000000-0000-0000-0000-000000000000', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+INSERT OR IGNORE INTO templates (id, name, code, description, template_type, format, body, variables, header_template_id, footer_template_id, version, status, created_by, created_at, updated_at)
+VALUES ('00000000-0000-4000-a000-000000000013', 'Packing slip', 'STD_PACKING_SLIP', 'Items in a shipment, with a scannable shipment number', 'PackingSlip', 'PDF',
+'<document size="A4">
+<h1>Packing slip {{shipment_number}}</h1>
+<row>
+  <cell><p bold="true">Ship to</p><p>{{ship_to.name}}<br/>{{ship_to.address}}</p></cell>
+  <cell width="40%"><table>
+    <tr><td>Order</td><td align="right">{{order_number}}</td></tr>
+    <tr><td>Ship date</td><td align="right">{{format_date ship_date}}</td></tr>
+    <tr><td>Carrier</td><td align="right">{{carrier}}</td></tr>
+    <tr><td>Tracking</td><td align="right">{{tracking_number}}</td></tr>
+  </table></cell>
+</row>
+<table>
+  <thead><tr><th width="22%">SKU</th><th>Description</th><th width="14%" align="right">Ordered</th><th width="14%" align="right">Shipped</th></tr></thead>
+  <tbody>{{#each lines}}<tr><td>{{sku}}</td><td>{{description}}</td><td align="right">{{format_number ordered 0}}</td><td align="right">{{format_number shipped 0}}</td></tr>{{/each}}</tbody>
+</table>
+{{#if shipment_number}}<barcode type="Code128" value="{{shipment_number}}" height="40" align="right"/>{{/if}}
+</document>',
+'["company", "ship_to", "shipment_number", "order_number", "ship_date", "carrier", "tracking_number", "lines"]', '00000000-0000-4000-a000-000000000001', '00000000-0000-4000-a000-000000000002', 1, 'Active', '00000000-0000-0000-0000-000000000000', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+INSERT OR IGNORE INTO templates (id, name, code, description, template_type, format, body, variables, header_template_id, footer_template_id, version, status, created_by, created_at, updated_at)
+VALUES ('00000000-0000-4000-a000-000000000014', 'Pick list', 'STD_PICK_LIST', 'Warehouse pick list with a barcode for every item', 'PickList', 'PDF',
+'<document size="A4">
+<h1>Pick list {{pick_list_number}}</h1>
+<p>Warehouse {{warehouse}} - {{format_date pick_date}}{{#if picker}} - picker {{picker}}{{/if}}</p>
+{{#if pick_list_number}}<barcode type="Code128" value="{{pick_list_number}}" height="30"/>{{/if}}
+<table>
+  <thead><tr><th width="14%">Location</th><th width="28%">Item</th><th>Description</th><th width="12%">Lot</th><th width="9%" align="right">Qty</th><th width="8%">Done</th></tr></thead>
+  <tbody>{{#each lines}}<tr><td>{{location}}</td><td><barcode type="Code128" value="{{sku}}" height="18"/></td><td>{{description}}</td><td>{{lot}}</td><td align="right">{{format_number quantity 0}}</td><td>[   ]</td></tr>{{/each}}</tbody>
+</table>
+</document>',
+'["company", "pick_list_number", "warehouse", "pick_date", "picker", "lines"]', '00000000-0000-4000-a000-000000000001', '00000000-0000-4000-a000-000000000002', 1, 'Active', '00000000-0000-0000-0000-000000000000', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+INSERT OR IGNORE INTO templates (id, name, code, description, template_type, format, body, variables, header_template_id, footer_template_id, version, status, created_by, created_at, updated_at)
+VALUES ('00000000-0000-4000-a000-000000000015', 'Credit note', 'STD_CREDIT_NOTE', 'Credit against an earlier invoice', 'CreditNote', 'PDF',
+'<document size="A4">
+<h1>Credit note {{credit_note_number}}</h1>
+<row>
+  <cell><p bold="true">Customer</p><p>{{customer.name}}<br/>{{customer.address}}</p></cell>
+  <cell width="40%"><table>
+    <tr><td>Date</td><td align="right">{{format_date issue_date}}</td></tr>
+    <tr><td>Original invoice</td><td align="right">{{invoice_number}}</td></tr>
+  </table></cell>
+</row>
+{{#if reason}}<p>Reason: {{reason}}</p>{{/if}}
+<table>
+  <thead><tr><th>Description</th><th width="12%" align="right">Qty</th><th width="18%" align="right">Unit price</th><th width="18%" align="right">Amount</th></tr></thead>
+  <tbody>{{#each lines}}<tr><td>{{description}}</td><td align="right">{{format_number quantity}}</td><td align="right">{{format_money unit_price ../currency}}</td><td align="right">{{format_money amount ../currency}}</td></tr>{{/each}}</tbody>
+  <tfoot>
+    <tr><td colspan="3" align="right">Subtotal</td><td align="right">{{format_money subtotal currency}}</td></tr>
+    <tr><td colspan="3" align="right">Tax</td><td align="right">{{format_money tax currency}}</td></tr>
+    <tr><td colspan="3" align="right">Total credited</td><td align="right">{{format_money total currency}}</td></tr>
+  </tfoot>
+</table>
+</document>',
+'["company", "customer", "credit_note_number", "issue_date", "invoice_number", "reason", "currency", "lines", "subtotal", "tax", "total"]', '00000000-0000-4000-a000-000000000001', '00000000-0000-4000-a000-000000000002', 1, 'Active', '00000000-0000-0000-0000-000000000000', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+INSERT OR IGNORE INTO templates (id, name, code, description, template_type, format, body, variables, header_template_id, footer_template_id, version, status, created_by, created_at, updated_at)
+VALUES ('00000000-0000-4000-a000-000000000016', 'Payslip', 'STD_PAYSLIP', 'Earnings, deductions and net pay for a pay period', 'Payslip', 'PDF',
+'<document size="A4">
+<h1>Payslip</h1>
+<row>
+  <cell><p bold="true">{{employee.name}}</p><p>Employee {{employee.number}}<br/>{{employee.department}}</p></cell>
+  <cell width="45%"><table>
+    <tr><td>Period</td><td align="right">{{format_date period_start}} to {{format_date period_end}}</td></tr>
+    <tr><td>Pay date</td><td align="right">{{format_date pay_date}}</td></tr>
+  </table></cell>
+</row>
+<h3>Earnings</h3>
+<table>
+  <thead><tr><th>Description</th><th width="25%" align="right">Amount</th></tr></thead>
+  <tbody>{{#each earnings}}<tr><td>{{description}}</td><td align="right">{{format_money amount ../currency}}</td></tr>{{/each}}</tbody>
+  <tfoot><tr><td align="right">Gross pay</td><td align="right">{{format_money gross_pay currency}}</td></tr></tfoot>
+</table>
+<h3>Deductions</h3>
+<table>
+  <thead><tr><th>Description</th><th width="25%" align="right">Amount</th></tr></thead>
+  <tbody>{{#each deductions}}<tr><td>{{description}}</td><td align="right">{{format_money amount ../currency}}</td></tr>{{/each}}</tbody>
+  <tfoot><tr><td align="right">Total deductions</td><td align="right">{{format_money total_deductions currency}}</td></tr></tfoot>
+</table>
+<table><tr bold="true"><td>Net pay</td><td width="25%" align="right">{{format_money net_pay currency}}</td></tr></table>
+</document>',
+'["company", "employee", "period_start", "period_end", "pay_date", "currency", "earnings", "gross_pay", "deductions", "total_deductions", "net_pay"]', '00000000-0000-4000-a000-000000000001', NULL, 1, 'Active', '00000000-0000-0000-0000-000000000000', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
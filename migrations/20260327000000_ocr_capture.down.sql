@@ -0,0 +1,12 @@
+DROP TABLE IF EXISTS ocr_settings;
+
+DROP INDEX IF EXISTS idx_vendor_bills_vendor_invoice;
+DROP INDEX IF EXISTS idx_ocr_templates_vendor;
+DROP INDEX IF EXISTS idx_ocr_documents_vendor_bill;
+
+ALTER TABLE ocr_documents DROP COLUMN vendor_bill_id;
+ALTER TABLE ocr_documents DROP COLUMN vendor_id;
+ALTER TABLE ocr_documents DROP COLUMN field_confidence;
+ALTER TABLE ocr_documents DROP COLUMN engine;
+ALTER TABLE ocr_documents DROP COLUMN template_id;
+ALTER TABLE ocr_documents DROP COLUMN checksum;
@@ -0,0 +1,19 @@
+-- Captured documents keep the checksum of their stored file, the engine that read them, how sure
+-- extraction was of each field and the vendor and draft bill they were matched to.
+ALTER TABLE ocr_documents ADD COLUMN checksum TEXT;
+ALTER TABLE ocr_documents ADD COLUMN template_id TEXT;
+ALTER TABLE ocr_documents ADD COLUMN engine TEXT;
+ALTER TABLE ocr_documents ADD COLUMN field_confidence TEXT NOT NULL DEFAULT '{}';
+ALTER TABLE ocr_documents ADD COLUMN vendor_id TEXT;
+ALTER TABLE ocr_documents ADD COLUMN vendor_bill_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_ocr_documents_vendor_bill ON ocr_documents(vendor_bill_id);
+CREATE INDEX IF NOT EXISTS idx_ocr_templates_vendor ON ocr_templates(vendor_id, document_type);
+CREATE INDEX IF NOT EXISTS idx_vendor_bills_vendor_invoice ON vendor_bills(vendor_id, vendor_invoice_number);
+
+-- A single row of OCR settings, stored as JSON.
+CREATE TABLE IF NOT EXISTS ocr_settings (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    settings TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
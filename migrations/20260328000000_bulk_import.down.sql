@@ -0,0 +1,12 @@
+DROP INDEX IF EXISTS idx_mdm_import_errors_job;
+DROP TABLE IF EXISTS mdm_import_errors;
+DROP INDEX IF EXISTS idx_mdm_import_mappings_entity;
+DROP TABLE IF EXISTS mdm_import_mappings;
+DROP INDEX IF EXISTS idx_mdm_import_jobs_status;
+
+ALTER TABLE mdm_import_jobs DROP COLUMN created_by;
+ALTER TABLE mdm_import_jobs DROP COLUMN options;
+ALTER TABLE mdm_import_jobs DROP COLUMN file_checksum;
+ALTER TABLE mdm_import_jobs DROP COLUMN dry_run;
+ALTER TABLE mdm_import_jobs DROP COLUMN updated_records;
+ALTER TABLE mdm_import_jobs DROP COLUMN created_records;
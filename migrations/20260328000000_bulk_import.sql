@@ -0,0 +1,35 @@
+-- Import jobs count created and updated records, may be dry runs, and keep the uploaded file's
+-- checksum, how it is read and who started them.
+ALTER TABLE mdm_import_jobs ADD COLUMN created_records INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE mdm_import_jobs ADD COLUMN updated_records INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE mdm_import_jobs ADD COLUMN dry_run INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE mdm_import_jobs ADD COLUMN file_checksum TEXT;
+ALTER TABLE mdm_import_jobs ADD COLUMN options TEXT;
+ALTER TABLE mdm_import_jobs ADD COLUMN created_by TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_mdm_import_jobs_status ON mdm_import_jobs(status, created_at);
+
+-- Saved column mappings, stored as JSON.
+CREATE TABLE IF NOT EXISTS mdm_import_mappings (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    columns TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mdm_import_mappings_entity ON mdm_import_mappings(entity_type, name);
+
+-- Rows an import did not save, with the row's cells as a JSON array.
+CREATE TABLE IF NOT EXISTS mdm_import_errors (
+    id TEXT PRIMARY KEY,
+    job_id TEXT NOT NULL REFERENCES mdm_import_jobs(id) ON DELETE CASCADE,
+    row_number INTEGER NOT NULL,
+    field TEXT,
+    message TEXT NOT NULL,
+    row_values TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mdm_import_errors_job ON mdm_import_errors(job_id, row_number);
@@ -0,0 +1 @@
+ALTER TABLE journal_entry_reversals DROP COLUMN last_error;
@@ -0,0 +1,2 @@
+-- A scheduled reversal that fails stays scheduled and keeps the error from its last attempt.
+ALTER TABLE journal_entry_reversals ADD COLUMN last_error TEXT;
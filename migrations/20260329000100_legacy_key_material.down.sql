@@ -0,0 +1,2 @@
+ALTER TABLE encrypted_data DROP COLUMN legacy_format;
+ALTER TABLE encryption_keys DROP COLUMN legacy_material;
@@ -0,0 +1,8 @@
+-- Marks the key material and encrypted values written before envelope encryption. Only rows
+-- flagged here may be read through the legacy formats, and they are re-encrypted when next read.
+ALTER TABLE encryption_keys ADD COLUMN legacy_material INTEGER NOT NULL DEFAULT 0;
+UPDATE encryption_keys SET legacy_material = 1
+WHERE encrypted_private_key IS NOT NULL AND encrypted_private_key NOT LIKE 'v1:%';
+
+ALTER TABLE encrypted_data ADD COLUMN legacy_format INTEGER NOT NULL DEFAULT 0;
+UPDATE encrypted_data SET legacy_format = 1 WHERE auth_tag IS NULL;
@@ -0,0 +1,3 @@
+-- The most recent TOTP time step accepted for each user. A code is accepted only for a later
+-- step, so an intercepted code cannot be replayed within its validity window.
+ALTER TABLE user_two_factor ADD COLUMN last_used_step INTEGER;
@@ -0,0 +1,10 @@
+-- Webhook events already applied, by gateway and the gateway's event id, so a redelivered
+-- event is acknowledged without being applied twice.
+CREATE TABLE IF NOT EXISTS payment_gateway_events (
+    gateway_id TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    transaction_id TEXT NOT NULL,
+    received_at TEXT NOT NULL,
+    PRIMARY KEY (gateway_id, event_id)
+);
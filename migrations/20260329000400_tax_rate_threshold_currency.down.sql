@@ -0,0 +1 @@
+ALTER TABLE tax_rates DROP COLUMN threshold_currency;
@@ -0,0 +1,3 @@
+-- Minimum and maximum taxable amounts are only comparable with documents in the same currency.
+ALTER TABLE tax_rates ADD COLUMN threshold_currency TEXT;
+UPDATE tax_rates SET threshold_currency = 'USD' WHERE min_amount IS NOT NULL OR max_amount IS NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE journal_lines DROP COLUMN currency;
@@ -0,0 +1,2 @@
+-- Lines posted from documents keep the document's currency instead of reading back as USD.
+ALTER TABLE journal_lines ADD COLUMN currency TEXT NOT NULL DEFAULT 'USD';
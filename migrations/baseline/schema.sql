@@ -0,0 +1,17022 @@
+-- Schema and seed rows of every migration through 20260307200000_quality_capa_calibration, the files
+-- shipped before schema_migrations existed. A database with no schema_migrations rows loads this once
+-- and records those files as applied without running them. Every statement only creates what is
+-- missing. payment_gateways and refunds, which those files define more than once, get the columns of
+-- every definition.
+
+CREATE TABLE IF NOT EXISTS accounts (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    account_type TEXT NOT NULL,
+    parent_id TEXT REFERENCES accounts(id),
+    status TEXT NOT NULL DEFAULT 'Active',
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS journal_entries (
+    id TEXT PRIMARY KEY,
+    entry_number TEXT NOT NULL UNIQUE,
+    date TEXT NOT NULL,
+    description TEXT NOT NULL,
+    reference TEXT,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS journal_lines (
+    id TEXT PRIMARY KEY,
+    journal_entry_id TEXT NOT NULL REFERENCES journal_entries(id) ON DELETE CASCADE,
+    account_id TEXT NOT NULL REFERENCES accounts(id),
+    debit INTEGER NOT NULL DEFAULT 0,
+    credit INTEGER NOT NULL DEFAULT 0,
+    description TEXT
+);
+CREATE TABLE IF NOT EXISTS fiscal_years (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tax_rates (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    rate REAL NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS product_categories (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    parent_id TEXT REFERENCES product_categories(id),
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS products (
+    id TEXT PRIMARY KEY,
+    sku TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    product_type TEXT NOT NULL DEFAULT 'Goods',
+    category_id TEXT REFERENCES product_categories(id),
+    unit_of_measure TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS warehouses (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    address_street TEXT,
+    address_city TEXT,
+    address_state TEXT,
+    address_postal_code TEXT,
+    address_country TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS stock_locations (
+    id TEXT PRIMARY KEY,
+    warehouse_id TEXT NOT NULL REFERENCES warehouses(id),
+    code TEXT NOT NULL,
+    name TEXT NOT NULL,
+    location_type TEXT NOT NULL DEFAULT 'Storage',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(warehouse_id, code)
+);
+CREATE TABLE IF NOT EXISTS stock_levels (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    location_id TEXT NOT NULL REFERENCES stock_locations(id),
+    quantity INTEGER NOT NULL DEFAULT 0,
+    reserved_quantity INTEGER NOT NULL DEFAULT 0,
+    available_quantity INTEGER NOT NULL DEFAULT 0,
+    UNIQUE(product_id, location_id)
+);
+CREATE TABLE IF NOT EXISTS stock_movements (
+    id TEXT PRIMARY KEY,
+    movement_number TEXT NOT NULL UNIQUE,
+    movement_type TEXT NOT NULL,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    from_location_id TEXT REFERENCES stock_locations(id),
+    to_location_id TEXT REFERENCES stock_locations(id),
+    quantity INTEGER NOT NULL,
+    reference TEXT,
+    movement_date TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS price_lists (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS price_list_items (
+    id TEXT PRIMARY KEY,
+    price_list_id TEXT NOT NULL REFERENCES price_lists(id),
+    product_id TEXT NOT NULL REFERENCES products(id),
+    price INTEGER NOT NULL,
+    min_quantity INTEGER NOT NULL DEFAULT 0,
+    UNIQUE(price_list_id, product_id)
+);
+CREATE TABLE IF NOT EXISTS customers (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    email TEXT,
+    phone TEXT,
+    fax TEXT,
+    website TEXT,
+    billing_street TEXT,
+    billing_city TEXT,
+    billing_state TEXT,
+    billing_postal_code TEXT,
+    billing_country TEXT,
+    shipping_street TEXT,
+    shipping_city TEXT,
+    shipping_state TEXT,
+    shipping_postal_code TEXT,
+    shipping_country TEXT,
+    credit_limit INTEGER,
+    credit_limit_currency TEXT DEFAULT 'USD',
+    payment_terms INTEGER NOT NULL DEFAULT 30,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sales_orders (
+    id TEXT PRIMARY KEY,
+    order_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL REFERENCES customers(id),
+    order_date TEXT NOT NULL,
+    required_date TEXT,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    subtotal_currency TEXT DEFAULT 'USD',
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    tax_currency TEXT DEFAULT 'USD',
+    total INTEGER NOT NULL DEFAULT 0,
+    total_currency TEXT DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sales_order_lines (
+    id TEXT PRIMARY KEY,
+    sales_order_id TEXT NOT NULL REFERENCES sales_orders(id) ON DELETE CASCADE,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    unit_price_currency TEXT DEFAULT 'USD',
+    discount_percent REAL NOT NULL DEFAULT 0,
+    tax_rate REAL NOT NULL DEFAULT 0,
+    line_total INTEGER NOT NULL,
+    line_total_currency TEXT DEFAULT 'USD'
+);
+CREATE TABLE IF NOT EXISTS sales_quotes (
+    id TEXT PRIMARY KEY,
+    quote_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL REFERENCES customers(id),
+    quote_date TEXT NOT NULL,
+    valid_until TEXT NOT NULL,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    currency TEXT DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS sales_quote_lines (
+    id TEXT PRIMARY KEY,
+    quote_id TEXT NOT NULL REFERENCES sales_quotes(id) ON DELETE CASCADE,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    discount_percent REAL NOT NULL DEFAULT 0,
+    line_total INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS invoices (
+    id TEXT PRIMARY KEY,
+    invoice_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL REFERENCES customers(id),
+    sales_order_id TEXT REFERENCES sales_orders(id),
+    invoice_date TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    currency TEXT DEFAULT 'USD',
+    amount_paid INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS invoice_lines (
+    id TEXT PRIMARY KEY,
+    invoice_id TEXT NOT NULL REFERENCES invoices(id) ON DELETE CASCADE,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    line_total INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS payments (
+    id TEXT PRIMARY KEY,
+    payment_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL REFERENCES customers(id),
+    invoice_id TEXT REFERENCES invoices(id),
+    payment_date TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    currency TEXT DEFAULT 'USD',
+    payment_method TEXT NOT NULL,
+    reference TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS vendors (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    email TEXT,
+    phone TEXT,
+    fax TEXT,
+    website TEXT,
+    street TEXT,
+    city TEXT,
+    state TEXT,
+    postal_code TEXT,
+    country TEXT,
+    payment_terms INTEGER NOT NULL DEFAULT 30,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS purchase_requisitions (
+    id TEXT PRIMARY KEY,
+    requisition_number TEXT NOT NULL UNIQUE,
+    requested_by TEXT,
+    request_date TEXT NOT NULL,
+    required_date TEXT,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS requisition_lines (
+    id TEXT PRIMARY KEY,
+    requisition_id TEXT NOT NULL REFERENCES purchase_requisitions(id) ON DELETE CASCADE,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS purchase_orders (
+    id TEXT PRIMARY KEY,
+    po_number TEXT NOT NULL UNIQUE,
+    vendor_id TEXT NOT NULL REFERENCES vendors(id),
+    order_date TEXT NOT NULL,
+    expected_date TEXT,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    currency TEXT DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS purchase_order_lines (
+    id TEXT PRIMARY KEY,
+    purchase_order_id TEXT NOT NULL REFERENCES purchase_orders(id) ON DELETE CASCADE,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    tax_rate REAL NOT NULL DEFAULT 0,
+    line_total INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS goods_receipts (
+    id TEXT PRIMARY KEY,
+    receipt_number TEXT NOT NULL UNIQUE,
+    purchase_order_id TEXT NOT NULL REFERENCES purchase_orders(id),
+    warehouse_id TEXT NOT NULL REFERENCES warehouses(id),
+    receipt_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS goods_receipt_lines (
+    id TEXT PRIMARY KEY,
+    goods_receipt_id TEXT NOT NULL REFERENCES goods_receipts(id) ON DELETE CASCADE,
+    po_line_id TEXT NOT NULL REFERENCES purchase_order_lines(id),
+    product_id TEXT NOT NULL REFERENCES products(id),
+    quantity_ordered INTEGER NOT NULL,
+    quantity_received INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS work_centers (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    capacity INTEGER NOT NULL DEFAULT 0,
+    cost_per_hour INTEGER NOT NULL DEFAULT 0,
+    cost_currency TEXT DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS bills_of_material (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    name TEXT NOT NULL,
+    version TEXT NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(product_id, version)
+);
+CREATE TABLE IF NOT EXISTS bom_components (
+    id TEXT PRIMARY KEY,
+    bom_id TEXT NOT NULL REFERENCES bills_of_material(id) ON DELETE CASCADE,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    quantity INTEGER NOT NULL,
+    unit TEXT NOT NULL,
+    scrap_percent REAL NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS bom_operations (
+    id TEXT PRIMARY KEY,
+    bom_id TEXT NOT NULL REFERENCES bills_of_material(id) ON DELETE CASCADE,
+    sequence INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    work_center_id TEXT NOT NULL REFERENCES work_centers(id),
+    setup_time INTEGER NOT NULL DEFAULT 0,
+    run_time INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS routings (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS routing_operations (
+    id TEXT PRIMARY KEY,
+    routing_id TEXT NOT NULL REFERENCES routings(id) ON DELETE CASCADE,
+    sequence INTEGER NOT NULL,
+    work_center_id TEXT NOT NULL REFERENCES work_centers(id),
+    operation TEXT NOT NULL,
+    setup_time INTEGER NOT NULL DEFAULT 0,
+    run_time INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS work_orders (
+    id TEXT PRIMARY KEY,
+    order_number TEXT NOT NULL UNIQUE,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    bom_id TEXT NOT NULL REFERENCES bills_of_material(id),
+    quantity INTEGER NOT NULL,
+    planned_start TEXT NOT NULL,
+    planned_end TEXT NOT NULL,
+    actual_start TEXT,
+    actual_end TEXT,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+, work_order_type TEXT, priority TEXT, asset_id TEXT, location_id TEXT, failure_code_id TEXT, problem_description TEXT, cause_description TEXT, remedy_description TEXT, requested_by TEXT, requested_date TEXT, required_date TEXT, scheduled_start TEXT, scheduled_end TEXT, assigned_to TEXT, assigned_team_id TEXT, estimated_labor_hours REAL, actual_labor_hours REAL, estimated_cost INTEGER, actual_cost INTEGER, downtime_hours REAL, completion_notes TEXT, closed_by TEXT, closed_at TEXT);
+CREATE TABLE IF NOT EXISTS production_orders (
+    id TEXT PRIMARY KEY,
+    order_number TEXT NOT NULL UNIQUE,
+    planned_quantity INTEGER NOT NULL,
+    produced_quantity INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS production_order_work_orders (
+    production_order_id TEXT NOT NULL REFERENCES production_orders(id),
+    work_order_id TEXT NOT NULL REFERENCES work_orders(id),
+    PRIMARY KEY (production_order_id, work_order_id)
+);
+CREATE TABLE IF NOT EXISTS departments (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    parent_id TEXT REFERENCES departments(id),
+    manager_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS positions (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    department_id TEXT REFERENCES departments(id),
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS employees (
+    id TEXT PRIMARY KEY,
+    employee_number TEXT NOT NULL UNIQUE,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    phone TEXT,
+    fax TEXT,
+    website TEXT,
+    street TEXT,
+    city TEXT,
+    state TEXT,
+    postal_code TEXT,
+    country TEXT,
+    birth_date TEXT NOT NULL,
+    hire_date TEXT NOT NULL,
+    termination_date TEXT,
+    department_id TEXT REFERENCES departments(id),
+    position_id TEXT REFERENCES positions(id),
+    manager_id TEXT REFERENCES employees(id),
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS attendance (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL REFERENCES employees(id),
+    date TEXT NOT NULL,
+    check_in TEXT,
+    check_out TEXT,
+    work_hours REAL NOT NULL DEFAULT 0,
+    overtime_hours REAL NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Present',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(employee_id, date)
+);
+CREATE TABLE IF NOT EXISTS leave_types (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    code TEXT NOT NULL UNIQUE,
+    days_per_year INTEGER NOT NULL DEFAULT 0,
+    carry_over INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS leave_requests (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL REFERENCES employees(id),
+    leave_type TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    days REAL NOT NULL,
+    reason TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT
+);
+CREATE TABLE IF NOT EXISTS salary_structures (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL REFERENCES employees(id),
+    base_salary INTEGER NOT NULL,
+    currency TEXT DEFAULT 'USD',
+    effective_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS allowances (
+    id TEXT PRIMARY KEY,
+    salary_structure_id TEXT NOT NULL REFERENCES salary_structures(id),
+    name TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    taxable INTEGER NOT NULL DEFAULT 1
+);
+CREATE TABLE IF NOT EXISTS payroll (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL REFERENCES employees(id),
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    base_salary INTEGER NOT NULL,
+    overtime INTEGER NOT NULL DEFAULT 0,
+    bonuses INTEGER NOT NULL DEFAULT 0,
+    deductions INTEGER NOT NULL DEFAULT 0,
+    net_salary INTEGER NOT NULL,
+    currency TEXT DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT
+);
+CREATE TABLE IF NOT EXISTS users (
+    id TEXT PRIMARY KEY,
+    username TEXT NOT NULL UNIQUE,
+    email TEXT NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL,
+    full_name TEXT NOT NULL,
+    role TEXT NOT NULL DEFAULT 'User',
+    status TEXT NOT NULL DEFAULT 'Active',
+    last_login TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS roles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    permissions TEXT NOT NULL DEFAULT '{}',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+INSERT OR IGNORE INTO roles (id, name, description, permissions, created_at, updated_at, created_by, updated_by) VALUES ('role-admin', 'Admin', 'Full system access', '["*"]', '2026-10-18 16:36:13', '2026-10-18 16:36:13', NULL, NULL);
+INSERT OR IGNORE INTO roles (id, name, description, permissions, created_at, updated_at, created_by, updated_by) VALUES ('role-finance', 'Finance', 'Finance module access', '["finance:*","sales:read"]', '2026-10-18 16:36:13', '2026-10-18 16:36:13', NULL, NULL);
+INSERT OR IGNORE INTO roles (id, name, description, permissions, created_at, updated_at, created_by, updated_by) VALUES ('role-warehouse', 'Warehouse', 'Inventory module access', '["inventory:*","purchasing:*"]', '2026-10-18 16:36:13', '2026-10-18 16:36:13', NULL, NULL);
+INSERT OR IGNORE INTO roles (id, name, description, permissions, created_at, updated_at, created_by, updated_by) VALUES ('role-sales', 'Sales', 'Sales module access', '["sales:*","inventory:read"]', '2026-10-18 16:36:13', '2026-10-18 16:36:13', NULL, NULL);
+INSERT OR IGNORE INTO roles (id, name, description, permissions, created_at, updated_at, created_by, updated_by) VALUES ('role-hr', 'HR', 'HR module access', '["hr:*"]', '2026-10-18 16:36:13', '2026-10-18 16:36:13', NULL, NULL);
+INSERT OR IGNORE INTO roles (id, name, description, permissions, created_at, updated_at, created_by, updated_by) VALUES ('role-user', 'User', 'Basic read access', '["*:read"]', '2026-10-18 16:36:13', '2026-10-18 16:36:13', NULL, NULL);
+CREATE TABLE IF NOT EXISTS user_sessions (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS audit_logs (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    old_values TEXT,
+    new_values TEXT,
+    user_id TEXT,
+    username TEXT,
+    ip_address TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_audit_logs_entity ON audit_logs(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_audit_logs_user ON audit_logs(user_id);
+CREATE INDEX IF NOT EXISTS idx_audit_logs_created ON audit_logs(created_at);
+CREATE TABLE IF NOT EXISTS quotations (
+    id TEXT PRIMARY KEY,
+    quote_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    quote_date TEXT NOT NULL,
+    valid_until TEXT,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    notes TEXT,
+    terms TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS quotation_lines (
+    id TEXT PRIMARY KEY,
+    quotation_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    description TEXT,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    discount INTEGER DEFAULT 0,
+    line_total INTEGER NOT NULL,
+    FOREIGN KEY (quotation_id) REFERENCES quotations(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_quotations_customer ON quotations(customer_id);
+CREATE INDEX IF NOT EXISTS idx_quotations_status ON quotations(status);
+CREATE TABLE IF NOT EXISTS workflows (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    approval_levels INTEGER NOT NULL DEFAULT 1,
+    auto_approve_below INTEGER DEFAULT 0,
+    require_comment INTEGER DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS approval_requests (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    current_level INTEGER NOT NULL DEFAULT 1,
+    max_level INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    requested_by TEXT,
+    requested_at TEXT NOT NULL,
+    completed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS approvals (
+    id TEXT PRIMARY KEY,
+    approval_request_id TEXT NOT NULL,
+    level INTEGER NOT NULL,
+    approver_id TEXT,
+    approver_name TEXT,
+    status TEXT NOT NULL,
+    comment TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (approval_request_id) REFERENCES approval_requests(id)
+);
+CREATE TABLE IF NOT EXISTS notifications (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    entity_type TEXT,
+    entity_id TEXT,
+    notification_type TEXT NOT NULL,
+    read INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_approval_requests_entity ON approval_requests(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_approval_requests_status ON approval_requests(status);
+CREATE INDEX IF NOT EXISTS idx_notifications_user ON notifications(user_id);
+CREATE INDEX IF NOT EXISTS idx_notifications_read ON notifications(read);
+CREATE TABLE IF NOT EXISTS attachments (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    filename TEXT NOT NULL,
+    original_filename TEXT NOT NULL,
+    mime_type TEXT NOT NULL,
+    file_size INTEGER NOT NULL,
+    file_path TEXT NOT NULL,
+    uploaded_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_attachments_entity ON attachments(entity_type, entity_id);
+CREATE TABLE IF NOT EXISTS currencies (
+    code TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    is_base INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS exchange_rates (
+    id TEXT PRIMARY KEY,
+    from_currency TEXT NOT NULL,
+    to_currency TEXT NOT NULL,
+    rate REAL NOT NULL,
+    effective_date TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_exchange_rates_currencies ON exchange_rates(from_currency, to_currency);
+CREATE INDEX IF NOT EXISTS idx_exchange_rates_date ON exchange_rates(effective_date);
+CREATE TABLE IF NOT EXISTS budgets (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    fiscal_year_id TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    total_amount INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS budget_lines (
+    id TEXT PRIMARY KEY,
+    budget_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    period INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    actual INTEGER DEFAULT 0,
+    variance INTEGER DEFAULT 0,
+    FOREIGN KEY (budget_id) REFERENCES budgets(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_budget_lines_budget ON budget_lines(budget_id);
+CREATE INDEX IF NOT EXISTS idx_budget_lines_account ON budget_lines(account_id);
+CREATE TABLE IF NOT EXISTS lots (
+    id TEXT PRIMARY KEY,
+    lot_number TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    serial_number TEXT,
+    manufacture_date TEXT,
+    expiry_date TEXT,
+    quantity INTEGER NOT NULL DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lot_transactions (
+    id TEXT PRIMARY KEY,
+    lot_id TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    reference_type TEXT,
+    reference_id TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_lots_product ON lots(product_id);
+CREATE INDEX IF NOT EXISTS idx_lots_lot_number ON lots(lot_number);
+CREATE INDEX IF NOT EXISTS idx_lot_transactions_lot ON lot_transactions(lot_id);
+CREATE TABLE IF NOT EXISTS leave_balances (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    leave_type_id TEXT NOT NULL,
+    year INTEGER NOT NULL,
+    entitled INTEGER DEFAULT 0,
+    used INTEGER DEFAULT 0,
+    remaining INTEGER DEFAULT 0,
+    carried_over INTEGER DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_leave_balances_employee ON leave_balances(employee_id);
+CREATE INDEX IF NOT EXISTS idx_leave_requests_employee ON leave_requests(employee_id);
+CREATE INDEX IF NOT EXISTS idx_leave_requests_status ON leave_requests(status);
+CREATE TABLE IF NOT EXISTS expense_categories (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS expense_reports (
+    id TEXT PRIMARY KEY,
+    report_number TEXT NOT NULL UNIQUE,
+    employee_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    total_amount INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Draft',
+    submitted_at TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    rejected_at TEXT,
+    rejection_reason TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS expense_lines (
+    id TEXT PRIMARY KEY,
+    expense_report_id TEXT NOT NULL,
+    category_id TEXT NOT NULL,
+    expense_date TEXT NOT NULL,
+    description TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    currency TEXT DEFAULT 'USD',
+    receipt_path TEXT,
+    FOREIGN KEY (expense_report_id) REFERENCES expense_reports(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_expense_reports_employee ON expense_reports(employee_id);
+CREATE INDEX IF NOT EXISTS idx_expense_reports_status ON expense_reports(status);
+CREATE INDEX IF NOT EXISTS idx_expense_lines_report ON expense_lines(expense_report_id);
+CREATE TABLE IF NOT EXISTS fixed_assets (
+    id TEXT PRIMARY KEY,
+    asset_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    category TEXT NOT NULL,
+    location TEXT,
+    cost INTEGER NOT NULL,
+    salvage_value INTEGER DEFAULT 0,
+    useful_life_years INTEGER NOT NULL,
+    depreciation_method TEXT NOT NULL DEFAULT 'StraightLine',
+    acquisition_date TEXT NOT NULL,
+    depreciation_start_date TEXT,
+    accumulated_depreciation INTEGER DEFAULT 0,
+    net_book_value INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS asset_depreciation (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    period TEXT NOT NULL,
+    depreciation_amount INTEGER NOT NULL,
+    accumulated_depreciation INTEGER NOT NULL,
+    posted_at TEXT NOT NULL,
+    FOREIGN KEY (asset_id) REFERENCES fixed_assets(id)
+);
+CREATE INDEX IF NOT EXISTS idx_assets_code ON fixed_assets(asset_code);
+CREATE INDEX IF NOT EXISTS idx_assets_status ON fixed_assets(status);
+CREATE INDEX IF NOT EXISTS idx_depreciation_asset ON asset_depreciation(asset_id);
+CREATE TABLE IF NOT EXISTS quality_inspections (
+    id TEXT PRIMARY KEY,
+    inspection_number TEXT NOT NULL UNIQUE,
+    inspection_type TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    inspector_id TEXT,
+    inspection_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    result TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS inspection_items (
+    id TEXT PRIMARY KEY,
+    inspection_id TEXT NOT NULL,
+    criterion TEXT NOT NULL,
+    expected_value TEXT,
+    actual_value TEXT,
+    pass_fail TEXT,
+    notes TEXT,
+    FOREIGN KEY (inspection_id) REFERENCES quality_inspections(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS non_conformance_reports (
+    id TEXT PRIMARY KEY,
+    ncr_number TEXT NOT NULL UNIQUE,
+    source_type TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    description TEXT NOT NULL,
+    severity TEXT NOT NULL DEFAULT 'Minor',
+    status TEXT NOT NULL DEFAULT 'Open',
+    assigned_to TEXT,
+    root_cause TEXT,
+    corrective_action TEXT,
+    preventive_action TEXT,
+    resolution_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+, product_id TEXT);
+CREATE INDEX IF NOT EXISTS idx_inspections_entity ON quality_inspections(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_ncr_status ON non_conformance_reports(status);
+CREATE TABLE IF NOT EXISTS leads (
+    id TEXT PRIMARY KEY,
+    lead_number TEXT NOT NULL UNIQUE,
+    company_name TEXT NOT NULL,
+    contact_name TEXT,
+    email TEXT,
+    phone TEXT,
+    source TEXT,
+    industry TEXT,
+    estimated_value INTEGER DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'New',
+    assigned_to TEXT,
+    notes TEXT,
+    converted_to_customer TEXT,
+    converted_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS opportunities (
+    id TEXT PRIMARY KEY,
+    opportunity_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    customer_id TEXT,
+    lead_id TEXT,
+    stage TEXT NOT NULL DEFAULT 'Prospecting',
+    probability INTEGER DEFAULT 0,
+    expected_close_date TEXT,
+    amount INTEGER DEFAULT 0,
+    description TEXT,
+    assigned_to TEXT,
+    status TEXT NOT NULL DEFAULT 'Open',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS opportunity_activities (
+    id TEXT PRIMARY KEY,
+    opportunity_id TEXT NOT NULL,
+    activity_type TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    description TEXT,
+    due_date TEXT,
+    completed INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (opportunity_id) REFERENCES opportunities(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_leads_status ON leads(status);
+CREATE INDEX IF NOT EXISTS idx_opportunities_stage ON opportunities(stage);
+CREATE TABLE IF NOT EXISTS safety_stock (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    safety_stock INTEGER NOT NULL,
+    reorder_point INTEGER NOT NULL,
+    reorder_quantity INTEGER NOT NULL,
+    lead_time_days INTEGER DEFAULT 0,
+    service_level INTEGER DEFAULT 95,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS replenishment_orders (
+    id TEXT PRIMARY KEY,
+    order_number TEXT NOT NULL UNIQUE,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    order_type TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    source TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_safety_stock_product ON safety_stock(product_id);
+CREATE TABLE IF NOT EXISTS production_schedules (
+    id TEXT PRIMARY KEY,
+    schedule_number TEXT NOT NULL UNIQUE,
+    work_order_id TEXT NOT NULL,
+    work_center_id TEXT NOT NULL,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Planned',
+    actual_start TEXT,
+    actual_end TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_schedules_work_center ON production_schedules(work_center_id);
+CREATE INDEX IF NOT EXISTS idx_schedules_status ON production_schedules(status);
+CREATE TABLE IF NOT EXISTS supplier_scorecards (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    period TEXT NOT NULL,
+    on_time_delivery INTEGER DEFAULT 0,
+    quality_score INTEGER DEFAULT 0,
+    price_competitiveness INTEGER DEFAULT 0,
+    responsiveness INTEGER DEFAULT 0,
+    overall_score INTEGER DEFAULT 0,
+    total_orders INTEGER DEFAULT 0,
+    total_value INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS vendor_performance (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    order_id TEXT NOT NULL,
+    delivery_date TEXT,
+    expected_date TEXT,
+    on_time INTEGER DEFAULT 0,
+    quality_rating INTEGER DEFAULT 0,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_scorecards_vendor ON supplier_scorecards(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_performance_vendor ON vendor_performance(vendor_id);
+CREATE TABLE IF NOT EXISTS custom_field_definitions (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    field_name TEXT NOT NULL,
+    field_label TEXT NOT NULL,
+    field_type TEXT NOT NULL,
+    required INTEGER DEFAULT 0,
+    options TEXT,
+    sort_order INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS custom_field_values (
+    id TEXT PRIMARY KEY,
+    definition_id TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    value TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (definition_id) REFERENCES custom_field_definitions(id)
+);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_custom_field_unique ON custom_field_definitions(entity_type, field_name);
+CREATE INDEX IF NOT EXISTS idx_custom_values_entity ON custom_field_values(entity_id);
+CREATE TABLE IF NOT EXISTS bank_accounts (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL,
+    bank_name TEXT NOT NULL,
+    account_number TEXT NOT NULL,
+    account_type TEXT NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    gl_code TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (account_id) REFERENCES accounts(id)
+);
+CREATE TABLE IF NOT EXISTS bank_statements (
+    id TEXT PRIMARY KEY,
+    bank_account_id TEXT NOT NULL,
+    statement_date TEXT NOT NULL,
+    opening_balance INTEGER NOT NULL,
+    closing_balance INTEGER NOT NULL,
+    status TEXT DEFAULT 'Pending',
+    reconciled_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (bank_account_id) REFERENCES bank_accounts(id)
+);
+CREATE TABLE IF NOT EXISTS bank_transactions (
+    id TEXT PRIMARY KEY,
+    bank_account_id TEXT NOT NULL,
+    statement_id TEXT,
+    transaction_date TEXT NOT NULL,
+    value_date TEXT,
+    description TEXT,
+    reference TEXT,
+    debit INTEGER DEFAULT 0,
+    credit INTEGER DEFAULT 0,
+    balance INTEGER NOT NULL,
+    reconciled INTEGER DEFAULT 0,
+    journal_entry_id TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (bank_account_id) REFERENCES bank_accounts(id),
+    FOREIGN KEY (statement_id) REFERENCES bank_statements(id)
+);
+CREATE TABLE IF NOT EXISTS reconciliation_rules (
+    id TEXT PRIMARY KEY,
+    bank_account_id TEXT NOT NULL,
+    rule_name TEXT,
+    rule_type TEXT NOT NULL,
+    description TEXT,
+    match_field TEXT NOT NULL,
+    match_pattern TEXT,
+    match_criteria TEXT,
+    tolerance_days INTEGER DEFAULT 0,
+    tolerance_amount INTEGER DEFAULT 0,
+    tolerance_type TEXT,
+    tolerance_value REAL,
+    date_tolerance_days INTEGER,
+    reference_patterns TEXT,
+    auto_match INTEGER DEFAULT 0,
+    priority INTEGER,
+    status TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT
+);
+CREATE TABLE IF NOT EXISTS cash_flow_forecasts (
+    id TEXT PRIMARY KEY,
+    forecast_date TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    opening_balance INTEGER NOT NULL,
+    expected_inflows INTEGER DEFAULT 0,
+    expected_outflows INTEGER DEFAULT 0,
+    closing_balance INTEGER NOT NULL,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cash_flow_categories (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    category_type TEXT NOT NULL,
+    parent_id TEXT,
+    sort_order INTEGER DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS cash_flow_items (
+    id TEXT PRIMARY KEY,
+    forecast_id TEXT NOT NULL,
+    category_id TEXT NOT NULL,
+    description TEXT NOT NULL,
+    expected_date TEXT,
+    amount INTEGER NOT NULL,
+    probability INTEGER DEFAULT 100,
+    actual_amount INTEGER,
+    actual_date TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (forecast_id) REFERENCES cash_flow_forecasts(id)
+);
+CREATE TABLE IF NOT EXISTS cost_centers (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    department_id TEXT,
+    manager_id TEXT,
+    cost_center_type TEXT NOT NULL,
+    allocation_method TEXT DEFAULT 'Direct',
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cost_elements (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    element_type TEXT NOT NULL,
+    account_id TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cost_pools (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    cost_center_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_cost INTEGER DEFAULT 0,
+    allocation_base TEXT NOT NULL,
+    allocation_rate REAL DEFAULT 0,
+    status TEXT DEFAULT 'Open',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (cost_center_id) REFERENCES cost_centers(id)
+);
+CREATE TABLE IF NOT EXISTS cost_allocations (
+    id TEXT PRIMARY KEY,
+    pool_id TEXT NOT NULL,
+    from_cost_center_id TEXT NOT NULL,
+    to_cost_center_id TEXT NOT NULL,
+    allocation_base_value REAL NOT NULL,
+    allocated_amount INTEGER NOT NULL,
+    allocated_at TEXT NOT NULL,
+    FOREIGN KEY (pool_id) REFERENCES cost_pools(id),
+    FOREIGN KEY (from_cost_center_id) REFERENCES cost_centers(id),
+    FOREIGN KEY (to_cost_center_id) REFERENCES cost_centers(id)
+);
+CREATE TABLE IF NOT EXISTS activity_types (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    cost_driver TEXT NOT NULL,
+    unit_of_measure TEXT,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS activity_costs (
+    id TEXT PRIMARY KEY,
+    activity_type_id TEXT NOT NULL,
+    cost_pool_id TEXT NOT NULL,
+    total_activities INTEGER NOT NULL,
+    cost_per_activity INTEGER NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    FOREIGN KEY (activity_type_id) REFERENCES activity_types(id),
+    FOREIGN KEY (cost_pool_id) REFERENCES cost_pools(id)
+);
+CREATE TABLE IF NOT EXISTS companies (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    legal_name TEXT,
+    tax_id TEXT,
+    registration_number TEXT,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    address TEXT,
+    city TEXT,
+    country TEXT,
+    is_consolidation_entity INTEGER DEFAULT 0,
+    parent_company_id TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS intercompany_transactions (
+    id TEXT PRIMARY KEY,
+    transaction_number TEXT NOT NULL UNIQUE,
+    from_company_id TEXT NOT NULL,
+    to_company_id TEXT NOT NULL,
+    transaction_date TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT,
+    reference TEXT,
+    from_journal_entry_id TEXT,
+    to_journal_entry_id TEXT,
+    status TEXT DEFAULT 'Pending',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (from_company_id) REFERENCES companies(id),
+    FOREIGN KEY (to_company_id) REFERENCES companies(id)
+);
+CREATE TABLE IF NOT EXISTS intercompany_accounts (
+    id TEXT PRIMARY KEY,
+    company_id TEXT NOT NULL,
+    partner_company_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    due_to_account_id TEXT NOT NULL,
+    due_from_account_id TEXT NOT NULL,
+    FOREIGN KEY (company_id) REFERENCES companies(id),
+    FOREIGN KEY (partner_company_id) REFERENCES companies(id)
+);
+CREATE TABLE IF NOT EXISTS revenue_schedules (
+    id TEXT PRIMARY KEY,
+    schedule_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    recognition_method TEXT NOT NULL,
+    total_amount INTEGER NOT NULL,
+    recognized_amount INTEGER DEFAULT 0,
+    deferred_amount INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS revenue_schedule_lines (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT NOT NULL,
+    line_number INTEGER NOT NULL,
+    recognition_date TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    recognized INTEGER DEFAULT 0,
+    journal_entry_id TEXT,
+    recognized_at TEXT,
+    FOREIGN KEY (schedule_id) REFERENCES revenue_schedules(id)
+);
+CREATE TABLE IF NOT EXISTS revenue_recognition_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    recognition_type TEXT NOT NULL,
+    periods INTEGER NOT NULL,
+    recognition_rule TEXT NOT NULL,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS consolidation_schedules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    parent_company_id TEXT NOT NULL,
+    status TEXT DEFAULT 'Draft',
+    elimination_entries INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (parent_company_id) REFERENCES companies(id)
+);
+CREATE TABLE IF NOT EXISTS consolidation_companies (
+    id TEXT PRIMARY KEY,
+    consolidation_id TEXT NOT NULL,
+    company_id TEXT NOT NULL,
+    ownership_percent REAL DEFAULT 100,
+    consolidation_method TEXT DEFAULT 'Full',
+    exchange_rate REAL DEFAULT 1,
+    translation_method TEXT DEFAULT 'Current',
+    FOREIGN KEY (consolidation_id) REFERENCES consolidation_schedules(id),
+    FOREIGN KEY (company_id) REFERENCES companies(id)
+);
+CREATE TABLE IF NOT EXISTS elimination_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    from_account_pattern TEXT NOT NULL,
+    to_account_pattern TEXT NOT NULL,
+    elimination_account_id TEXT NOT NULL,
+    description TEXT,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS elimination_entries (
+    id TEXT PRIMARY KEY,
+    consolidation_id TEXT NOT NULL,
+    elimination_rule_id TEXT,
+    description TEXT NOT NULL,
+    debit_account_id TEXT NOT NULL,
+    credit_account_id TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    journal_entry_id TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (consolidation_id) REFERENCES consolidation_schedules(id)
+);
+CREATE TABLE IF NOT EXISTS warehouse_zones (
+    id TEXT PRIMARY KEY,
+    warehouse_id TEXT NOT NULL,
+    zone_code TEXT NOT NULL,
+    name TEXT NOT NULL,
+    zone_type TEXT NOT NULL,
+    temperature_controlled INTEGER DEFAULT 0,
+    max_capacity INTEGER,
+    current_utilization INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS warehouse_bins (
+    id TEXT PRIMARY KEY,
+    zone_id TEXT NOT NULL,
+    bin_code TEXT NOT NULL,
+    bin_type TEXT NOT NULL,
+    aisle TEXT,
+    row_number INTEGER,
+    level_number INTEGER,
+    capacity INTEGER,
+    current_quantity INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    FOREIGN KEY (zone_id) REFERENCES warehouse_zones(id)
+);
+CREATE TABLE IF NOT EXISTS pick_lists (
+    id TEXT PRIMARY KEY,
+    pick_number TEXT NOT NULL UNIQUE,
+    warehouse_id TEXT NOT NULL,
+    order_id TEXT,
+    assigned_to TEXT,
+    priority INTEGER DEFAULT 5,
+    status TEXT DEFAULT 'Pending',
+    total_items INTEGER DEFAULT 0,
+    picked_items INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    completed_at TEXT
+);
+CREATE TABLE IF NOT EXISTS pick_list_items (
+    id TEXT PRIMARY KEY,
+    pick_list_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    bin_id TEXT NOT NULL,
+    lot_id TEXT,
+    requested_qty INTEGER NOT NULL,
+    picked_qty INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Pending',
+    FOREIGN KEY (pick_list_id) REFERENCES pick_lists(id)
+);
+CREATE TABLE IF NOT EXISTS pack_lists (
+    id TEXT PRIMARY KEY,
+    pack_number TEXT NOT NULL UNIQUE,
+    pick_list_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    packed_by TEXT,
+    status TEXT DEFAULT 'Pending',
+    total_weight INTEGER,
+    tracking_number TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (pick_list_id) REFERENCES pick_lists(id)
+);
+CREATE TABLE IF NOT EXISTS pack_list_items (
+    id TEXT PRIMARY KEY,
+    pack_list_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    box_number INTEGER DEFAULT 1,
+    FOREIGN KEY (pack_list_id) REFERENCES pack_lists(id)
+);
+CREATE TABLE IF NOT EXISTS shipment_orders (
+    id TEXT PRIMARY KEY,
+    shipment_number TEXT NOT NULL UNIQUE,
+    warehouse_id TEXT NOT NULL,
+    carrier_id TEXT,
+    service_type TEXT,
+    ship_to_name TEXT NOT NULL,
+    ship_to_address TEXT NOT NULL,
+    ship_to_city TEXT NOT NULL,
+    ship_to_state TEXT,
+    ship_to_postal TEXT NOT NULL,
+    ship_to_country TEXT NOT NULL,
+    total_weight INTEGER,
+    tracking_number TEXT,
+    ship_date TEXT,
+    estimated_delivery TEXT,
+    actual_delivery TEXT,
+    status TEXT DEFAULT 'Draft',
+    freight_charge INTEGER DEFAULT 0,
+    insurance_charge INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS shipment_items (
+    id TEXT PRIMARY KEY,
+    shipment_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    weight INTEGER,
+    foreign_key TEXT,
+    FOREIGN KEY (shipment_id) REFERENCES shipment_orders(id)
+);
+CREATE TABLE IF NOT EXISTS shipping_carriers (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    api_endpoint TEXT,
+    api_key TEXT,
+    account_number TEXT,
+    supports_tracking INTEGER DEFAULT 1,
+    supports_label_generation INTEGER DEFAULT 1,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS carrier_services (
+    id TEXT PRIMARY KEY,
+    carrier_id TEXT NOT NULL,
+    service_code TEXT NOT NULL,
+    service_name TEXT NOT NULL,
+    delivery_days INTEGER,
+    status TEXT DEFAULT 'Active',
+    FOREIGN KEY (carrier_id) REFERENCES shipping_carriers(id)
+);
+CREATE TABLE IF NOT EXISTS shipping_rate_cards (
+    id TEXT PRIMARY KEY,
+    carrier_id TEXT NOT NULL,
+    service_id TEXT NOT NULL,
+    zone_from TEXT NOT NULL,
+    zone_to TEXT NOT NULL,
+    weight_from INTEGER NOT NULL,
+    weight_to INTEGER NOT NULL,
+    base_rate INTEGER NOT NULL,
+    per_kg_rate INTEGER DEFAULT 0,
+    effective_date TEXT NOT NULL,
+    expiry_date TEXT,
+    FOREIGN KEY (carrier_id) REFERENCES shipping_carriers(id),
+    FOREIGN KEY (service_id) REFERENCES carrier_services(id)
+);
+CREATE TABLE IF NOT EXISTS edi_partners (
+    id TEXT PRIMARY KEY,
+    partner_code TEXT NOT NULL UNIQUE,
+    partner_name TEXT NOT NULL,
+    partner_type TEXT NOT NULL,
+    edi_standard TEXT DEFAULT 'X12',
+    communication_method TEXT NOT NULL,
+    ftp_host TEXT,
+    ftp_username TEXT,
+    ftp_password TEXT,
+    api_endpoint TEXT,
+    api_key TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS edi_documents (
+    id TEXT PRIMARY KEY,
+    document_number TEXT NOT NULL UNIQUE,
+    partner_id TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    direction TEXT NOT NULL,
+    reference_number TEXT,
+    raw_content TEXT,
+    parsed_data TEXT,
+    status TEXT DEFAULT 'Pending',
+    processed_at TEXT,
+    error_message TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (partner_id) REFERENCES edi_partners(id)
+);
+CREATE TABLE IF NOT EXISTS edi_mappings (
+    id TEXT PRIMARY KEY,
+    partner_id TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    segment_id TEXT NOT NULL,
+    element_position INTEGER NOT NULL,
+    internal_field TEXT NOT NULL,
+    transformation_rule TEXT,
+    FOREIGN KEY (partner_id) REFERENCES edi_partners(id)
+);
+CREATE TABLE IF NOT EXISTS supplier_users (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    username TEXT NOT NULL UNIQUE,
+    email TEXT NOT NULL,
+    password_hash TEXT NOT NULL,
+    role TEXT DEFAULT 'Supplier',
+    last_login TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (vendor_id) REFERENCES vendors(id)
+);
+CREATE TABLE IF NOT EXISTS supplier_invitations (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    email TEXT NOT NULL,
+    invitation_token TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    accepted_at TEXT,
+    status TEXT DEFAULT 'Pending',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS supplier_documents (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    document_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    uploaded_by TEXT,
+    expiry_date TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS rfqs (
+    id TEXT PRIMARY KEY,
+    rfq_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    buyer_id TEXT NOT NULL,
+    currency TEXT DEFAULT 'USD',
+    submission_deadline TEXT NOT NULL,
+    valid_until TEXT,
+    status TEXT DEFAULT 'Draft',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS rfq_lines (
+    id TEXT PRIMARY KEY,
+    rfq_id TEXT NOT NULL,
+    line_number INTEGER NOT NULL,
+    product_id TEXT,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit TEXT NOT NULL,
+    delivery_date TEXT,
+    specifications TEXT,
+    FOREIGN KEY (rfq_id) REFERENCES rfqs(id)
+);
+CREATE TABLE IF NOT EXISTS rfq_vendors (
+    id TEXT PRIMARY KEY,
+    rfq_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    invited_at TEXT,
+    responded_at TEXT,
+    status TEXT DEFAULT 'Invited',
+    FOREIGN KEY (rfq_id) REFERENCES rfqs(id),
+    FOREIGN KEY (vendor_id) REFERENCES vendors(id)
+);
+CREATE TABLE IF NOT EXISTS rfq_responses (
+    id TEXT PRIMARY KEY,
+    rfq_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    response_number TEXT NOT NULL UNIQUE,
+    response_date TEXT NOT NULL,
+    valid_until TEXT,
+    payment_terms INTEGER,
+    delivery_terms TEXT,
+    notes TEXT,
+    status TEXT DEFAULT 'Submitted',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (rfq_id) REFERENCES rfqs(id),
+    FOREIGN KEY (vendor_id) REFERENCES vendors(id)
+);
+CREATE TABLE IF NOT EXISTS rfq_response_lines (
+    id TEXT PRIMARY KEY,
+    response_id TEXT NOT NULL,
+    rfq_line_id TEXT NOT NULL,
+    unit_price INTEGER NOT NULL,
+    lead_time_days INTEGER,
+    minimum_order_qty INTEGER,
+    notes TEXT,
+    FOREIGN KEY (response_id) REFERENCES rfq_responses(id)
+);
+CREATE TABLE IF NOT EXISTS mrp_runs (
+    id TEXT PRIMARY KEY,
+    run_number TEXT NOT NULL UNIQUE,
+    run_date TEXT NOT NULL,
+    planning_horizon_days INTEGER NOT NULL,
+    status TEXT DEFAULT 'Running',
+    created_at TEXT NOT NULL,
+    completed_at TEXT
+);
+CREATE TABLE IF NOT EXISTS mrp_planned_orders (
+    id TEXT PRIMARY KEY,
+    mrp_run_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    order_type TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    due_date TEXT NOT NULL,
+    release_date TEXT,
+    source_type TEXT,
+    source_id TEXT,
+    status TEXT DEFAULT 'Planned',
+    FOREIGN KEY (mrp_run_id) REFERENCES mrp_runs(id)
+);
+CREATE TABLE IF NOT EXISTS capacity_plans (
+    id TEXT PRIMARY KEY,
+    plan_number TEXT NOT NULL UNIQUE,
+    work_center_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    available_hours INTEGER NOT NULL,
+    planned_hours INTEGER DEFAULT 0,
+    actual_hours INTEGER DEFAULT 0,
+    utilization_percent REAL DEFAULT 0,
+    status TEXT DEFAULT 'Planned',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS capacity_requirements (
+    id TEXT PRIMARY KEY,
+    capacity_plan_id TEXT NOT NULL,
+    work_order_id TEXT NOT NULL,
+    operation_sequence INTEGER NOT NULL,
+    required_hours INTEGER NOT NULL,
+    scheduled_start TEXT,
+    scheduled_end TEXT,
+    status TEXT DEFAULT 'Planned',
+    FOREIGN KEY (capacity_plan_id) REFERENCES capacity_plans(id)
+);
+CREATE TABLE IF NOT EXISTS shop_floor_operations (
+    id TEXT PRIMARY KEY,
+    operation_number TEXT NOT NULL UNIQUE,
+    work_order_id TEXT NOT NULL,
+    work_center_id TEXT NOT NULL,
+    operation_code TEXT NOT NULL,
+    description TEXT,
+    setup_time INTEGER DEFAULT 0,
+    run_time INTEGER NOT NULL,
+    quantity INTEGER NOT NULL,
+    completed_qty INTEGER DEFAULT 0,
+    scrapped_qty INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Pending',
+    started_at TEXT,
+    completed_at TEXT,
+    operator_id TEXT,
+    FOREIGN KEY (work_order_id) REFERENCES work_orders(id)
+);
+CREATE TABLE IF NOT EXISTS shop_floor_logs (
+    id TEXT PRIMARY KEY,
+    operation_id TEXT NOT NULL,
+    log_type TEXT NOT NULL,
+    operator_id TEXT,
+    quantity INTEGER,
+    reason TEXT,
+    notes TEXT,
+    logged_at TEXT NOT NULL,
+    FOREIGN KEY (operation_id) REFERENCES shop_floor_operations(id)
+);
+CREATE TABLE IF NOT EXISTS downtime_events (
+    id TEXT PRIMARY KEY,
+    work_center_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    reason_code TEXT,
+    description TEXT,
+    started_at TEXT NOT NULL,
+    ended_at TEXT,
+    duration_minutes INTEGER,
+    FOREIGN KEY (work_center_id) REFERENCES work_centers(id)
+);
+CREATE TABLE IF NOT EXISTS equipment (
+    id TEXT PRIMARY KEY,
+    equipment_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    equipment_type TEXT NOT NULL,
+    manufacturer TEXT,
+    model TEXT,
+    serial_number TEXT,
+    installation_date TEXT,
+    warranty_expiry TEXT,
+    location TEXT,
+    work_center_id TEXT,
+    parent_equipment_id TEXT,
+    status TEXT DEFAULT 'Active',
+    criticality TEXT DEFAULT 'Medium',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS maintenance_schedules (
+    id TEXT PRIMARY KEY,
+    schedule_number TEXT NOT NULL UNIQUE,
+    equipment_id TEXT NOT NULL,
+    asset_id TEXT,
+    maintenance_type TEXT NOT NULL,
+    frequency_type TEXT NOT NULL,
+    frequency_value INTEGER NOT NULL,
+    last_maintenance TEXT,
+    next_maintenance TEXT NOT NULL,
+    scheduled_date TEXT,
+    estimated_duration INTEGER,
+    estimated_duration_hours REAL,
+    estimated_cost INTEGER,
+    currency TEXT,
+    priority TEXT,
+    prediction_id TEXT,
+    description TEXT,
+    tasks TEXT,
+    parts_required TEXT,
+    assigned_to TEXT,
+    status TEXT DEFAULT 'Active',
+    completed_date TEXT,
+    actual_cost INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT,
+    FOREIGN KEY (equipment_id) REFERENCES equipment(id)
+);
+CREATE TABLE IF NOT EXISTS maintenance_work_orders (
+    id TEXT PRIMARY KEY,
+    work_order_number TEXT NOT NULL UNIQUE,
+    equipment_id TEXT NOT NULL,
+    schedule_id TEXT,
+    maintenance_type TEXT NOT NULL,
+    priority TEXT DEFAULT 'Medium',
+    description TEXT NOT NULL,
+    requested_by TEXT,
+    assigned_to TEXT,
+    scheduled_date TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    downtime_hours REAL DEFAULT 0,
+    labor_hours REAL DEFAULT 0,
+    parts_cost INTEGER DEFAULT 0,
+    labor_cost INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Requested',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (equipment_id) REFERENCES equipment(id),
+    FOREIGN KEY (schedule_id) REFERENCES maintenance_schedules(id)
+);
+CREATE TABLE IF NOT EXISTS maintenance_parts (
+    id TEXT PRIMARY KEY,
+    work_order_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_cost INTEGER,
+    total_cost INTEGER,
+    FOREIGN KEY (work_order_id) REFERENCES maintenance_work_orders(id)
+);
+CREATE TABLE IF NOT EXISTS engineering_change_requests (
+    id TEXT PRIMARY KEY,
+    ecr_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    change_type TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    impact_assessment TEXT,
+    requested_by TEXT NOT NULL,
+    priority TEXT DEFAULT 'Medium',
+    status TEXT DEFAULT 'Draft',
+    submitted_at TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ecr_items (
+    id TEXT PRIMARY KEY,
+    ecr_id TEXT NOT NULL,
+    item_type TEXT NOT NULL,
+    item_id TEXT NOT NULL,
+    current_state TEXT,
+    proposed_state TEXT,
+    FOREIGN KEY (ecr_id) REFERENCES engineering_change_requests(id)
+);
+CREATE TABLE IF NOT EXISTS engineering_change_orders (
+    id TEXT PRIMARY KEY,
+    eco_number TEXT NOT NULL UNIQUE,
+    ecr_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    effective_date TEXT NOT NULL,
+    approved_by TEXT,
+    approval_date TEXT,
+    status TEXT DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (ecr_id) REFERENCES engineering_change_requests(id)
+);
+CREATE TABLE IF NOT EXISTS document_revisions (
+    id TEXT PRIMARY KEY,
+    document_number TEXT NOT NULL,
+    revision TEXT NOT NULL,
+    title TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    file_path TEXT,
+    product_id TEXT,
+    status TEXT DEFAULT 'Draft',
+    approved_by TEXT,
+    approved_at TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE(document_number, revision)
+);
+CREATE TABLE IF NOT EXISTS sales_territories (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    parent_territory_id TEXT,
+    manager_id TEXT,
+    geography_type TEXT,
+    geography_codes TEXT,
+    target_revenue INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS territory_assignments (
+    id TEXT PRIMARY KEY,
+    territory_id TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    sales_rep_id TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    is_primary INTEGER DEFAULT 1,
+    FOREIGN KEY (territory_id) REFERENCES sales_territories(id)
+);
+CREATE TABLE IF NOT EXISTS commission_plans (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    plan_type TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    expiry_date TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS commission_tiers (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL,
+    tier_number INTEGER NOT NULL,
+    min_amount INTEGER NOT NULL,
+    max_amount INTEGER,
+    rate_percent REAL NOT NULL,
+    FOREIGN KEY (plan_id) REFERENCES commission_plans(id)
+);
+CREATE TABLE IF NOT EXISTS sales_rep_commissions (
+    id TEXT PRIMARY KEY,
+    sales_rep_id TEXT NOT NULL,
+    plan_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    gross_sales INTEGER DEFAULT 0,
+    returns INTEGER DEFAULT 0,
+    net_sales INTEGER DEFAULT 0,
+    commission_rate REAL DEFAULT 0,
+    commission_amount INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Calculated',
+    paid_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (plan_id) REFERENCES commission_plans(id)
+);
+CREATE TABLE IF NOT EXISTS commission_transactions (
+    id TEXT PRIMARY KEY,
+    commission_id TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    reference_type TEXT,
+    reference_id TEXT,
+    amount INTEGER NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (commission_id) REFERENCES sales_rep_commissions(id)
+);
+CREATE TABLE IF NOT EXISTS contracts (
+    id TEXT PRIMARY KEY,
+    contract_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    contract_type TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    value INTEGER NOT NULL,
+    currency TEXT DEFAULT 'USD',
+    billing_cycle TEXT,
+    auto_renew INTEGER DEFAULT 0,
+    renewal_notice_days INTEGER DEFAULT 30,
+    terms TEXT,
+    status TEXT DEFAULT 'Draft',
+    signed_date TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS contract_lines (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    product_id TEXT,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    billing_type TEXT NOT NULL,
+    billing_frequency TEXT,
+    next_billing_date TEXT,
+    status TEXT DEFAULT 'Active',
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_renewals (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    renewal_date TEXT NOT NULL,
+    new_start_date TEXT NOT NULL,
+    new_end_date TEXT NOT NULL,
+    new_value INTEGER,
+    status TEXT DEFAULT 'Pending',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS subscription_plans (
+    id TEXT PRIMARY KEY,
+    plan_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    billing_cycle TEXT NOT NULL,
+    billing_interval INTEGER NOT NULL DEFAULT 1,
+    setup_fee INTEGER DEFAULT 0,
+    base_price INTEGER NOT NULL,
+    trial_days INTEGER DEFAULT 0,
+    features TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS subscriptions (
+    id TEXT PRIMARY KEY,
+    subscription_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    plan_id TEXT NOT NULL,
+    contract_id TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    current_period_start TEXT NOT NULL,
+    current_period_end TEXT NOT NULL,
+    quantity INTEGER DEFAULT 1,
+    price_override INTEGER,
+    status TEXT DEFAULT 'Active',
+    cancelled_at TEXT,
+    cancellation_reason TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (plan_id) REFERENCES subscription_plans(id),
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS subscription_usage (
+    id TEXT PRIMARY KEY,
+    subscription_id TEXT NOT NULL,
+    usage_date TEXT NOT NULL,
+    usage_type TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER,
+    total_amount INTEGER,
+    invoice_id TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (subscription_id) REFERENCES subscriptions(id)
+);
+CREATE TABLE IF NOT EXISTS subscription_invoices (
+    id TEXT PRIMARY KEY,
+    invoice_number TEXT NOT NULL UNIQUE,
+    subscription_id TEXT NOT NULL,
+    invoice_date TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    subtotal INTEGER NOT NULL,
+    tax_amount INTEGER DEFAULT 0,
+    total INTEGER NOT NULL,
+    amount_paid INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Draft',
+    paid_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (subscription_id) REFERENCES subscriptions(id)
+);
+CREATE TABLE IF NOT EXISTS marketing_campaigns (
+    id TEXT PRIMARY KEY,
+    campaign_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    campaign_type TEXT NOT NULL,
+    channel TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    budget INTEGER NOT NULL,
+    actual_spend INTEGER DEFAULT 0,
+    target_audience TEXT,
+    objectives TEXT,
+    status TEXT DEFAULT 'Draft',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS campaign_leads (
+    id TEXT PRIMARY KEY,
+    campaign_id TEXT NOT NULL,
+    lead_id TEXT NOT NULL,
+    responded_at TEXT,
+    response_type TEXT,
+    converted INTEGER DEFAULT 0,
+    conversion_value INTEGER,
+    FOREIGN KEY (campaign_id) REFERENCES marketing_campaigns(id),
+    FOREIGN KEY (lead_id) REFERENCES leads(id)
+);
+CREATE TABLE IF NOT EXISTS projects (
+    id TEXT PRIMARY KEY,
+    project_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    customer_id TEXT,
+    project_type TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    budget INTEGER DEFAULT 0,
+    billable INTEGER DEFAULT 1,
+    billing_method TEXT DEFAULT 'FixedPrice',
+    project_manager TEXT,
+    status TEXT DEFAULT 'Planning',
+    percent_complete INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS project_tasks (
+    id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    task_number INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    parent_task_id TEXT,
+    assigned_to TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    estimated_hours REAL,
+    actual_hours REAL DEFAULT 0,
+    percent_complete INTEGER DEFAULT 0,
+    priority TEXT DEFAULT 'Medium',
+    status TEXT DEFAULT 'NotStarted',
+    billable INTEGER DEFAULT 1,
+    FOREIGN KEY (project_id) REFERENCES projects(id)
+);
+CREATE TABLE IF NOT EXISTS project_milestones (
+    id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    planned_date TEXT NOT NULL,
+    actual_date TEXT,
+    billing_amount INTEGER DEFAULT 0,
+    billing_status TEXT DEFAULT 'NotBilled',
+    status TEXT DEFAULT 'Planned',
+    FOREIGN KEY (project_id) REFERENCES projects(id)
+);
+CREATE TABLE IF NOT EXISTS project_expenses (
+    id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    expense_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    expense_date TEXT NOT NULL,
+    billable INTEGER DEFAULT 1,
+    invoiced INTEGER DEFAULT 0,
+    invoice_id TEXT,
+    status TEXT DEFAULT 'Pending',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(id)
+);
+CREATE TABLE IF NOT EXISTS timesheets (
+    id TEXT PRIMARY KEY,
+    timesheet_number TEXT NOT NULL UNIQUE,
+    employee_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_hours REAL DEFAULT 0,
+    overtime_hours REAL DEFAULT 0,
+    status TEXT DEFAULT 'Draft',
+    submitted_at TEXT,
+    approved_at TEXT,
+    approved_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS timesheet_entries (
+    id TEXT PRIMARY KEY,
+    timesheet_id TEXT NOT NULL,
+    project_id TEXT,
+    task_id TEXT,
+    entry_date TEXT NOT NULL,
+    hours REAL NOT NULL,
+    description TEXT,
+    billable INTEGER DEFAULT 1,
+    hourly_rate INTEGER,
+    FOREIGN KEY (timesheet_id) REFERENCES timesheets(id),
+    FOREIGN KEY (project_id) REFERENCES projects(id)
+);
+CREATE TABLE IF NOT EXISTS project_billings (
+    id TEXT PRIMARY KEY,
+    billing_number TEXT NOT NULL UNIQUE,
+    project_id TEXT NOT NULL,
+    billing_type TEXT NOT NULL,
+    milestone_id TEXT,
+    period_start TEXT,
+    period_end TEXT,
+    amount INTEGER NOT NULL,
+    invoice_id TEXT,
+    status TEXT DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(id)
+);
+CREATE TABLE IF NOT EXISTS pay_grades (
+    id TEXT PRIMARY KEY,
+    grade_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    min_salary INTEGER NOT NULL,
+    max_salary INTEGER NOT NULL,
+    midpoint INTEGER,
+    currency TEXT DEFAULT 'USD',
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS pay_components (
+    id TEXT PRIMARY KEY,
+    component_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    component_type TEXT NOT NULL,
+    calculation_type TEXT NOT NULL,
+    default_value INTEGER,
+    taxable INTEGER DEFAULT 1,
+    affects_gross INTEGER DEFAULT 1,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS employee_salaries (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    pay_grade_id TEXT,
+    effective_date TEXT NOT NULL,
+    base_salary INTEGER NOT NULL,
+    salary_type TEXT DEFAULT 'Annual',
+    currency TEXT DEFAULT 'USD',
+    FOREIGN KEY (employee_id) REFERENCES employees(id)
+);
+CREATE TABLE IF NOT EXISTS employee_components (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    component_id TEXT NOT NULL,
+    value INTEGER NOT NULL,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    FOREIGN KEY (employee_id) REFERENCES employees(id),
+    FOREIGN KEY (component_id) REFERENCES pay_components(id)
+);
+CREATE TABLE IF NOT EXISTS payroll_runs (
+    id TEXT PRIMARY KEY,
+    run_number TEXT NOT NULL UNIQUE,
+    pay_period_start TEXT NOT NULL,
+    pay_period_end TEXT NOT NULL,
+    pay_date TEXT NOT NULL,
+    total_gross INTEGER DEFAULT 0,
+    total_deductions INTEGER DEFAULT 0,
+    total_net INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Draft',
+    processed_at TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS payroll_entries (
+    id TEXT PRIMARY KEY,
+    payroll_run_id TEXT NOT NULL,
+    employee_id TEXT NOT NULL,
+    gross_pay INTEGER NOT NULL,
+    total_deductions INTEGER NOT NULL,
+    net_pay INTEGER NOT NULL,
+    payment_method TEXT DEFAULT 'BankTransfer',
+    bank_account TEXT,
+    status TEXT DEFAULT 'Pending',
+    FOREIGN KEY (payroll_run_id) REFERENCES payroll_runs(id),
+    FOREIGN KEY (employee_id) REFERENCES employees(id)
+);
+CREATE TABLE IF NOT EXISTS payroll_line_items (
+    id TEXT PRIMARY KEY,
+    payroll_entry_id TEXT NOT NULL,
+    component_id TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    is_deduction INTEGER DEFAULT 0,
+    FOREIGN KEY (payroll_entry_id) REFERENCES payroll_entries(id)
+);
+CREATE TABLE IF NOT EXISTS tax_tables (
+    id TEXT PRIMARY KEY,
+    tax_name TEXT NOT NULL,
+    tax_type TEXT NOT NULL,
+    year INTEGER NOT NULL,
+    bracket_min INTEGER NOT NULL,
+    bracket_max INTEGER,
+    rate REAL NOT NULL,
+    base_amount INTEGER DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS benefit_plans (
+    id TEXT PRIMARY KEY,
+    plan_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    plan_type TEXT NOT NULL,
+    provider TEXT,
+    coverage_type TEXT,
+    employee_contribution INTEGER NOT NULL,
+    employer_contribution INTEGER NOT NULL,
+    max_dependents INTEGER DEFAULT 0,
+    waiting_period_days INTEGER DEFAULT 0,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS employee_benefits (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    plan_id TEXT NOT NULL,
+    coverage_level TEXT,
+    enrollment_date TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    termination_date TEXT,
+    employee_cost INTEGER NOT NULL,
+    employer_cost INTEGER NOT NULL,
+    status TEXT DEFAULT 'Active',
+    FOREIGN KEY (employee_id) REFERENCES employees(id),
+    FOREIGN KEY (plan_id) REFERENCES benefit_plans(id)
+);
+CREATE TABLE IF NOT EXISTS benefit_dependents (
+    id TEXT PRIMARY KEY,
+    employee_benefit_id TEXT NOT NULL,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    relationship TEXT NOT NULL,
+    birth_date TEXT,
+    gender TEXT,
+    FOREIGN KEY (employee_benefit_id) REFERENCES employee_benefits(id)
+);
+CREATE TABLE IF NOT EXISTS performance_cycles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    cycle_type TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    review_due_date TEXT NOT NULL,
+    status TEXT DEFAULT 'Draft',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS performance_goals (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    cycle_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    weight INTEGER DEFAULT 1,
+    target_value TEXT,
+    actual_value TEXT,
+    self_rating INTEGER,
+    manager_rating INTEGER,
+    final_rating INTEGER,
+    status TEXT DEFAULT 'Draft',
+    FOREIGN KEY (cycle_id) REFERENCES performance_cycles(id)
+);
+CREATE TABLE IF NOT EXISTS performance_reviews (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    reviewer_id TEXT NOT NULL,
+    cycle_id TEXT NOT NULL,
+    review_type TEXT NOT NULL,
+    overall_rating INTEGER,
+    strengths TEXT,
+    areas_for_improvement TEXT,
+    comments TEXT,
+    submitted_at TEXT,
+    status TEXT DEFAULT 'Draft',
+    FOREIGN KEY (cycle_id) REFERENCES performance_cycles(id)
+);
+CREATE TABLE IF NOT EXISTS competency_ratings (
+    id TEXT PRIMARY KEY,
+    review_id TEXT NOT NULL,
+    competency_name TEXT NOT NULL,
+    rating INTEGER NOT NULL,
+    comments TEXT,
+    FOREIGN KEY (review_id) REFERENCES performance_reviews(id)
+);
+CREATE TABLE IF NOT EXISTS training_courses (
+    id TEXT PRIMARY KEY,
+    course_code TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    category TEXT,
+    duration_hours REAL,
+    delivery_method TEXT NOT NULL,
+    provider TEXT,
+    cost INTEGER DEFAULT 0,
+    required_for TEXT,
+    certification_valid_days INTEGER,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS course_modules (
+    id TEXT PRIMARY KEY,
+    course_id TEXT NOT NULL,
+    module_number INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    content_path TEXT,
+    duration_minutes INTEGER,
+    FOREIGN KEY (course_id) REFERENCES training_courses(id)
+);
+CREATE TABLE IF NOT EXISTS employee_training (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    course_id TEXT NOT NULL,
+    enrollment_date TEXT NOT NULL,
+    start_date TEXT,
+    completion_date TEXT,
+    due_date TEXT,
+    score REAL,
+    passed INTEGER DEFAULT 0,
+    certificate_number TEXT,
+    certificate_expiry TEXT,
+    status TEXT DEFAULT 'Enrolled',
+    FOREIGN KEY (employee_id) REFERENCES employees(id),
+    FOREIGN KEY (course_id) REFERENCES training_courses(id)
+);
+CREATE TABLE IF NOT EXISTS training_sessions (
+    id TEXT PRIMARY KEY,
+    course_id TEXT NOT NULL,
+    session_date TEXT NOT NULL,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    location TEXT,
+    instructor TEXT,
+    max_attendees INTEGER,
+    current_attendees INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Scheduled',
+    FOREIGN KEY (course_id) REFERENCES training_courses(id)
+);
+CREATE TABLE IF NOT EXISTS job_postings (
+    id TEXT PRIMARY KEY,
+    job_code TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    department_id TEXT,
+    location TEXT,
+    employment_type TEXT NOT NULL,
+    min_salary INTEGER,
+    max_salary INTEGER,
+    description TEXT NOT NULL,
+    requirements TEXT,
+    posted_date TEXT,
+    closing_date TEXT,
+    openings INTEGER DEFAULT 1,
+    filled INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Draft',
+    hiring_manager TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS job_applications (
+    id TEXT PRIMARY KEY,
+    job_id TEXT NOT NULL,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    phone TEXT,
+    resume_path TEXT,
+    cover_letter TEXT,
+    source TEXT,
+    applied_at TEXT NOT NULL,
+    status TEXT DEFAULT 'New',
+    rating INTEGER,
+    FOREIGN KEY (job_id) REFERENCES job_postings(id)
+);
+CREATE TABLE IF NOT EXISTS application_stages (
+    id TEXT PRIMARY KEY,
+    application_id TEXT NOT NULL,
+    stage TEXT NOT NULL,
+    entered_at TEXT NOT NULL,
+    exited_at TEXT,
+    notes TEXT,
+    FOREIGN KEY (application_id) REFERENCES job_applications(id)
+);
+CREATE TABLE IF NOT EXISTS interviews (
+    id TEXT PRIMARY KEY,
+    application_id TEXT NOT NULL,
+    interview_type TEXT NOT NULL,
+    scheduled_at TEXT NOT NULL,
+    duration_minutes INTEGER DEFAULT 60,
+    interviewer TEXT NOT NULL,
+    location TEXT,
+    notes TEXT,
+    feedback TEXT,
+    rating INTEGER,
+    status TEXT DEFAULT 'Scheduled',
+    FOREIGN KEY (application_id) REFERENCES job_applications(id)
+);
+CREATE TABLE IF NOT EXISTS job_offers (
+    id TEXT PRIMARY KEY,
+    application_id TEXT NOT NULL,
+    offer_date TEXT NOT NULL,
+    salary INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    expiration_date TEXT,
+    terms TEXT,
+    status TEXT DEFAULT 'Pending',
+    responded_at TEXT,
+    FOREIGN KEY (application_id) REFERENCES job_applications(id)
+);
+CREATE TABLE IF NOT EXISTS dashboards (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    dashboard_type TEXT NOT NULL,
+    is_default INTEGER DEFAULT 0,
+    layout TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS dashboard_widgets (
+    id TEXT PRIMARY KEY,
+    dashboard_id TEXT NOT NULL,
+    widget_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    data_source TEXT NOT NULL,
+    query_text TEXT,
+    refresh_interval INTEGER DEFAULT 300,
+    position_x INTEGER DEFAULT 0,
+    position_y INTEGER DEFAULT 0,
+    width INTEGER DEFAULT 1,
+    height INTEGER DEFAULT 1,
+    config TEXT,
+    FOREIGN KEY (dashboard_id) REFERENCES dashboards(id)
+);
+CREATE TABLE IF NOT EXISTS kpi_definitions (
+    id TEXT PRIMARY KEY,
+    kpi_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    category TEXT NOT NULL,
+    unit TEXT,
+    target_value REAL,
+    warning_threshold REAL,
+    critical_threshold REAL,
+    calculation_formula TEXT,
+    data_source TEXT,
+    refresh_frequency TEXT DEFAULT 'Daily',
+    owner TEXT,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS kpi_values (
+    id TEXT PRIMARY KEY,
+    kpi_id TEXT NOT NULL,
+    period TEXT NOT NULL,
+    value REAL NOT NULL,
+    target REAL,
+    variance REAL,
+    variance_percent REAL,
+    trend TEXT,
+    calculated_at TEXT NOT NULL,
+    FOREIGN KEY (kpi_id) REFERENCES kpi_definitions(id)
+);
+CREATE TABLE IF NOT EXISTS alerts (
+    id TEXT PRIMARY KEY,
+    alert_type TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    source_entity TEXT,
+    source_id TEXT,
+    rule_id TEXT,
+    acknowledged INTEGER DEFAULT 0,
+    acknowledged_by TEXT,
+    acknowledged_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS alert_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    entity_type TEXT NOT NULL,
+    condition_field TEXT NOT NULL,
+    operator TEXT NOT NULL,
+    threshold_value TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    notification_channels TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS forecast_models (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    model_type TEXT NOT NULL,
+    target_entity TEXT NOT NULL,
+    features TEXT,
+    parameters TEXT,
+    accuracy_score REAL,
+    last_trained TEXT,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS predictions (
+    id TEXT PRIMARY KEY,
+    model_id TEXT NOT NULL,
+    entity_id TEXT,
+    prediction_date TEXT NOT NULL,
+    predicted_value REAL NOT NULL,
+    confidence_lower REAL,
+    confidence_upper REAL,
+    actual_value REAL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (model_id) REFERENCES forecast_models(id)
+);
+CREATE TABLE IF NOT EXISTS tenants (
+    id TEXT PRIMARY KEY,
+    tenant_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    plan_type TEXT NOT NULL,
+    max_users INTEGER DEFAULT 10,
+    max_storage_mb INTEGER DEFAULT 1000,
+    settings TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    expires_at TEXT
+);
+CREATE TABLE IF NOT EXISTS tenant_users (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    role TEXT DEFAULT 'User',
+    joined_at TEXT NOT NULL,
+    status TEXT DEFAULT 'Active',
+    UNIQUE(tenant_id, user_id),
+    FOREIGN KEY (tenant_id) REFERENCES tenants(id)
+);
+CREATE TABLE IF NOT EXISTS automation_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    entity_type TEXT NOT NULL,
+    trigger_event TEXT NOT NULL,
+    conditions TEXT,
+    actions TEXT NOT NULL,
+    priority INTEGER DEFAULT 5,
+    active INTEGER DEFAULT 1,
+    created_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS automation_logs (
+    id TEXT PRIMARY KEY,
+    rule_id TEXT NOT NULL,
+    entity_type TEXT,
+    entity_id TEXT,
+    trigger_data TEXT,
+    action_results TEXT,
+    success INTEGER DEFAULT 1,
+    error_message TEXT,
+    executed_at TEXT NOT NULL,
+    FOREIGN KEY (rule_id) REFERENCES automation_rules(id)
+);
+CREATE TABLE IF NOT EXISTS email_templates (
+    id TEXT PRIMARY KEY,
+    template_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    body_html TEXT NOT NULL,
+    body_text TEXT,
+    category TEXT,
+    variables TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS email_queues (
+    id TEXT PRIMARY KEY,
+    template_id TEXT,
+    to_address TEXT NOT NULL,
+    cc_addresses TEXT,
+    bcc_addresses TEXT,
+    subject TEXT NOT NULL,
+    body TEXT NOT NULL,
+    attachments TEXT,
+    priority INTEGER DEFAULT 5,
+    attempts INTEGER DEFAULT 0,
+    max_attempts INTEGER DEFAULT 3,
+    sent_at TEXT,
+    error_message TEXT,
+    status TEXT DEFAULT 'Pending',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (template_id) REFERENCES email_templates(id)
+);
+CREATE TABLE IF NOT EXISTS email_logs (
+    id TEXT PRIMARY KEY,
+    message_id TEXT,
+    direction TEXT NOT NULL,
+    from_address TEXT NOT NULL,
+    to_address TEXT NOT NULL,
+    subject TEXT,
+    body TEXT,
+    attachments TEXT,
+    related_entity_type TEXT,
+    related_entity_id TEXT,
+    sent_at TEXT NOT NULL,
+    status TEXT DEFAULT 'Sent'
+);
+CREATE TABLE IF NOT EXISTS report_definitions (
+    id TEXT PRIMARY KEY,
+    report_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    category TEXT,
+    data_source TEXT NOT NULL,
+    query_text TEXT NOT NULL,
+    parameters TEXT,
+    columns TEXT,
+    filters TEXT,
+    sorting TEXT,
+    grouping TEXT,
+    chart_type TEXT,
+    is_scheduled INTEGER DEFAULT 0,
+    schedule_cron TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS report_executions (
+    id TEXT PRIMARY KEY,
+    report_id TEXT NOT NULL,
+    parameters TEXT,
+    row_count INTEGER,
+    file_path TEXT,
+    file_format TEXT,
+    file_size INTEGER,
+    execution_time_ms INTEGER,
+    status TEXT DEFAULT 'Running',
+    error_message TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (report_id) REFERENCES report_definitions(id)
+);
+CREATE TABLE IF NOT EXISTS mobile_devices (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    device_type TEXT NOT NULL,
+    device_token TEXT NOT NULL,
+    device_name TEXT,
+    os_version TEXT,
+    app_version TEXT,
+    last_active TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mobile_sessions (
+    id TEXT PRIMARY KEY,
+    device_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    login_at TEXT NOT NULL,
+    logout_at TEXT,
+    ip_address TEXT,
+    status TEXT DEFAULT 'Active',
+    FOREIGN KEY (device_id) REFERENCES mobile_devices(id)
+);
+CREATE TABLE IF NOT EXISTS push_notifications (
+    id TEXT PRIMARY KEY,
+    device_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    data TEXT,
+    sent_at TEXT,
+    delivered_at TEXT,
+    read_at TEXT,
+    status TEXT DEFAULT 'Pending',
+    FOREIGN KEY (device_id) REFERENCES mobile_devices(id)
+);
+CREATE TABLE IF NOT EXISTS api_keys (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    key_hash TEXT NOT NULL,
+    name TEXT NOT NULL,
+    permissions TEXT,
+    rate_limit INTEGER DEFAULT 1000,
+    last_used TEXT,
+    expires_at TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS api_usage_logs (
+    id TEXT PRIMARY KEY,
+    api_key_id TEXT,
+    endpoint TEXT NOT NULL,
+    method TEXT NOT NULL,
+    request_size INTEGER,
+    response_size INTEGER,
+    response_code INTEGER,
+    response_time_ms INTEGER,
+    ip_address TEXT,
+    user_agent TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_bank_tx_account ON bank_transactions(bank_account_id);
+CREATE INDEX IF NOT EXISTS idx_bank_tx_date ON bank_transactions(transaction_date);
+CREATE INDEX IF NOT EXISTS idx_cash_flow_items_forecast ON cash_flow_items(forecast_id);
+CREATE INDEX IF NOT EXISTS idx_cost_allocations_pool ON cost_allocations(pool_id);
+CREATE INDEX IF NOT EXISTS idx_intercompany_from ON intercompany_transactions(from_company_id);
+CREATE INDEX IF NOT EXISTS idx_intercompany_to ON intercompany_transactions(to_company_id);
+CREATE INDEX IF NOT EXISTS idx_consolidation_companies ON consolidation_companies(consolidation_id);
+CREATE INDEX IF NOT EXISTS idx_pick_lists_warehouse ON pick_lists(warehouse_id);
+CREATE INDEX IF NOT EXISTS idx_shipments_carrier ON shipment_orders(carrier_id);
+CREATE INDEX IF NOT EXISTS idx_edi_docs_partner ON edi_documents(partner_id);
+CREATE INDEX IF NOT EXISTS idx_rfq_responses_rfq ON rfq_responses(rfq_id);
+CREATE INDEX IF NOT EXISTS idx_mrp_orders_run ON mrp_planned_orders(mrp_run_id);
+CREATE INDEX IF NOT EXISTS idx_shop_floor_wo ON shop_floor_operations(work_order_id);
+CREATE INDEX IF NOT EXISTS idx_maintenance_wo_equipment ON maintenance_work_orders(equipment_id);
+CREATE INDEX IF NOT EXISTS idx_equipment_work_center ON equipment(work_center_id);
+CREATE INDEX IF NOT EXISTS idx_territory_assign_terr ON territory_assignments(territory_id);
+CREATE INDEX IF NOT EXISTS idx_commissions_rep ON sales_rep_commissions(sales_rep_id);
+CREATE INDEX IF NOT EXISTS idx_contracts_customer ON contracts(customer_id);
+CREATE INDEX IF NOT EXISTS idx_subscriptions_customer ON subscriptions(customer_id);
+CREATE INDEX IF NOT EXISTS idx_project_tasks_project ON project_tasks(project_id);
+CREATE INDEX IF NOT EXISTS idx_timesheets_employee ON timesheets(employee_id);
+CREATE INDEX IF NOT EXISTS idx_timesheet_entries_ts ON timesheet_entries(timesheet_id);
+CREATE INDEX IF NOT EXISTS idx_payroll_entries_run ON payroll_entries(payroll_run_id);
+CREATE INDEX IF NOT EXISTS idx_employee_benefits_emp ON employee_benefits(employee_id);
+CREATE INDEX IF NOT EXISTS idx_performance_goals_cycle ON performance_goals(cycle_id);
+CREATE INDEX IF NOT EXISTS idx_employee_training_emp ON employee_training(employee_id);
+CREATE INDEX IF NOT EXISTS idx_job_apps_job ON job_applications(job_id);
+CREATE INDEX IF NOT EXISTS idx_kpi_values_kpi ON kpi_values(kpi_id);
+CREATE INDEX IF NOT EXISTS idx_alerts_created ON alerts(created_at);
+CREATE INDEX IF NOT EXISTS idx_predictions_model ON predictions(model_id);
+CREATE INDEX IF NOT EXISTS idx_tenant_users_tenant ON tenant_users(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_automation_rules_entity ON automation_rules(entity_type);
+CREATE INDEX IF NOT EXISTS idx_email_queues_status ON email_queues(status);
+CREATE INDEX IF NOT EXISTS idx_api_usage_key ON api_usage_logs(api_key_id);
+CREATE INDEX IF NOT EXISTS idx_journal_lines_account ON journal_lines(account_id);
+CREATE INDEX IF NOT EXISTS idx_journal_lines_entry ON journal_lines(journal_entry_id);
+CREATE INDEX IF NOT EXISTS idx_stock_levels_product ON stock_levels(product_id);
+CREATE INDEX IF NOT EXISTS idx_stock_levels_location ON stock_levels(location_id);
+CREATE INDEX IF NOT EXISTS idx_stock_movements_product ON stock_movements(product_id);
+CREATE INDEX IF NOT EXISTS idx_stock_movements_to_location ON stock_movements(to_location_id);
+CREATE INDEX IF NOT EXISTS idx_products_sku ON products(sku);
+CREATE INDEX IF NOT EXISTS idx_products_category ON products(category_id);
+CREATE INDEX IF NOT EXISTS idx_customers_code ON customers(code);
+CREATE INDEX IF NOT EXISTS idx_vendors_code ON vendors(code);
+CREATE INDEX IF NOT EXISTS idx_purchase_orders_vendor ON purchase_orders(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_po_lines_po ON purchase_order_lines(purchase_order_id);
+CREATE INDEX IF NOT EXISTS idx_po_lines_product ON purchase_order_lines(product_id);
+CREATE INDEX IF NOT EXISTS idx_employees_department ON employees(department_id);
+CREATE TABLE IF NOT EXISTS service_tickets (
+    id TEXT PRIMARY KEY,
+    ticket_number TEXT NOT NULL UNIQUE,
+    subject TEXT NOT NULL,
+    description TEXT NOT NULL,
+    customer_id TEXT,
+    contact_id TEXT,
+    assigned_to TEXT,
+    team_id TEXT,
+    priority TEXT NOT NULL DEFAULT '"Medium"',
+    status TEXT NOT NULL DEFAULT '"New"',
+    ticket_type TEXT NOT NULL DEFAULT '"Incident"',
+    source TEXT NOT NULL DEFAULT '"WebPortal"',
+    category_id TEXT,
+    sla_id TEXT,
+    due_date TEXT,
+    resolved_at TEXT,
+    closed_at TEXT,
+    first_response_at TEXT,
+    satisfaction_rating INTEGER,
+    satisfaction_comment TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ticket_comments (
+    id TEXT PRIMARY KEY,
+    ticket_id TEXT NOT NULL,
+    author_id TEXT NOT NULL,
+    author_type TEXT NOT NULL DEFAULT '"Agent"',
+    body TEXT NOT NULL,
+    is_internal INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (ticket_id) REFERENCES service_tickets(id)
+);
+CREATE TABLE IF NOT EXISTS ticket_attachments (
+    id TEXT PRIMARY KEY,
+    ticket_id TEXT NOT NULL,
+    comment_id TEXT,
+    filename TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_size INTEGER NOT NULL,
+    content_type TEXT NOT NULL,
+    uploaded_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (ticket_id) REFERENCES service_tickets(id)
+);
+CREATE TABLE IF NOT EXISTS slas (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    response_time_hours INTEGER NOT NULL,
+    resolution_time_hours INTEGER NOT NULL,
+    business_hours_only INTEGER NOT NULL DEFAULT 1,
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    escalation_rule_id TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS sla_policies (
+    id TEXT PRIMARY KEY,
+    sla_id TEXT NOT NULL,
+    priority TEXT NOT NULL,
+    response_time_hours INTEGER NOT NULL,
+    resolution_time_hours INTEGER NOT NULL,
+    FOREIGN KEY (sla_id) REFERENCES slas(id)
+);
+CREATE TABLE IF NOT EXISTS escalation_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    conditions TEXT NOT NULL,
+    actions TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ticket_categories (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    parent_id TEXT,
+    sla_id TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"'
+);
+CREATE TABLE IF NOT EXISTS service_teams (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    manager_id TEXT,
+    members TEXT NOT NULL DEFAULT '[]',
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS knowledge_articles (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    summary TEXT,
+    category_id TEXT,
+    author_id TEXT NOT NULL,
+    article_type TEXT NOT NULL DEFAULT '"KnowledgeBase"',
+    status TEXT NOT NULL DEFAULT '"Draft"',
+    view_count INTEGER NOT NULL DEFAULT 0,
+    helpful_count INTEGER NOT NULL DEFAULT 0,
+    not_helpful_count INTEGER NOT NULL DEFAULT 0,
+    tags TEXT NOT NULL DEFAULT '[]',
+    published_at TEXT,
+    expires_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS knowledge_categories (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    parent_id TEXT,
+    position INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT '"Active"'
+);
+CREATE TABLE IF NOT EXISTS service_catalogs (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    category_id TEXT,
+    owner_id TEXT,
+    approval_required INTEGER NOT NULL DEFAULT 0,
+    sla_id TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS service_catalog_items (
+    id TEXT PRIMARY KEY,
+    catalog_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    service_type TEXT NOT NULL DEFAULT '"Request"',
+    price INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    delivery_time_hours INTEGER NOT NULL DEFAULT 0,
+    form_schema TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    FOREIGN KEY (catalog_id) REFERENCES service_catalogs(id)
+);
+CREATE TABLE IF NOT EXISTS service_contracts (
+    id TEXT PRIMARY KEY,
+    contract_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    contract_type TEXT NOT NULL DEFAULT '"PerIncident"',
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    sla_id TEXT,
+    max_tickets INTEGER,
+    max_hours REAL,
+    used_tickets INTEGER NOT NULL DEFAULT 0,
+    used_hours REAL NOT NULL DEFAULT 0,
+    value INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS problems (
+    id TEXT PRIMARY KEY,
+    problem_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    root_cause TEXT,
+    workaround TEXT,
+    impact TEXT NOT NULL DEFAULT '"Moderate"',
+    urgency TEXT NOT NULL DEFAULT '"Medium"',
+    priority TEXT NOT NULL DEFAULT '"Medium"',
+    status TEXT NOT NULL DEFAULT '"New"',
+    assigned_to TEXT,
+    category_id TEXT,
+    related_incidents TEXT NOT NULL DEFAULT '[]',
+    resolution TEXT,
+    resolved_at TEXT,
+    closed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS change_requests (
+    id TEXT PRIMARY KEY,
+    change_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    change_type TEXT NOT NULL DEFAULT '"Normal"',
+    risk_level TEXT NOT NULL DEFAULT '"Medium"',
+    impact_assessment TEXT,
+    rollback_plan TEXT,
+    status TEXT NOT NULL DEFAULT '"Draft"',
+    requester_id TEXT NOT NULL,
+    approver_id TEXT,
+    assigned_to TEXT,
+    planned_start TEXT,
+    planned_end TEXT,
+    actual_start TEXT,
+    actual_end TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS change_tasks (
+    id TEXT PRIMARY KEY,
+    change_request_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    assigned_to TEXT,
+    sequence INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT '"Pending"',
+    started_at TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (change_request_id) REFERENCES change_requests(id)
+);
+CREATE TABLE IF NOT EXISTS canned_responses (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    subject TEXT,
+    body TEXT NOT NULL,
+    category TEXT,
+    tags TEXT NOT NULL DEFAULT '[]',
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS customer_contacts (
+    id TEXT PRIMARY KEY,
+    customer_id TEXT NOT NULL,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    phone TEXT,
+    title TEXT,
+    is_primary INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS service_metrics (
+    id TEXT PRIMARY KEY,
+    metric_date TEXT NOT NULL,
+    team_id TEXT,
+    agent_id TEXT,
+    tickets_created INTEGER NOT NULL DEFAULT 0,
+    tickets_resolved INTEGER NOT NULL DEFAULT 0,
+    tickets_open INTEGER NOT NULL DEFAULT 0,
+    avg_first_response_hours REAL NOT NULL DEFAULT 0,
+    avg_resolution_hours REAL NOT NULL DEFAULT 0,
+    sla_breached INTEGER NOT NULL DEFAULT 0,
+    customer_satisfaction REAL
+);
+CREATE INDEX IF NOT EXISTS idx_service_tickets_status ON service_tickets(status);
+CREATE INDEX IF NOT EXISTS idx_service_tickets_assigned ON service_tickets(assigned_to);
+CREATE INDEX IF NOT EXISTS idx_service_tickets_customer ON service_tickets(customer_id);
+CREATE INDEX IF NOT EXISTS idx_service_tickets_created ON service_tickets(created_at);
+CREATE INDEX IF NOT EXISTS idx_knowledge_articles_status ON knowledge_articles(status);
+CREATE TABLE IF NOT EXISTS it_assets (
+    id TEXT PRIMARY KEY,
+    asset_tag TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    asset_type TEXT NOT NULL DEFAULT '"Hardware"',
+    status TEXT NOT NULL DEFAULT '"Available"',
+    model TEXT,
+    manufacturer TEXT,
+    serial_number TEXT,
+    purchase_date TEXT,
+    purchase_cost INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    warranty_expiry TEXT,
+    location_id TEXT,
+    assigned_to TEXT,
+    assigned_date TEXT,
+    department_id TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS software_licenses (
+    id TEXT PRIMARY KEY,
+    license_key TEXT NOT NULL,
+    product_name TEXT NOT NULL,
+    vendor TEXT NOT NULL,
+    license_type TEXT NOT NULL DEFAULT '"Perpetual"',
+    seats_purchased INTEGER NOT NULL DEFAULT 1,
+    seats_used INTEGER NOT NULL DEFAULT 0,
+    purchase_date TEXT NOT NULL,
+    purchase_cost INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    start_date TEXT NOT NULL,
+    expiry_date TEXT,
+    auto_renew INTEGER NOT NULL DEFAULT 0,
+    support_expiry TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS software_installations (
+    id TEXT PRIMARY KEY,
+    license_id TEXT NOT NULL,
+    asset_id TEXT NOT NULL,
+    installed_by TEXT,
+    installed_at TEXT NOT NULL,
+    version TEXT,
+    status TEXT NOT NULL DEFAULT '"Installed"',
+    FOREIGN KEY (license_id) REFERENCES software_licenses(id),
+    FOREIGN KEY (asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS asset_assignments (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    assigned_to TEXT NOT NULL,
+    assigned_by TEXT NOT NULL,
+    assigned_at TEXT NOT NULL,
+    expected_return TEXT,
+    returned_at TEXT,
+    returned_by TEXT,
+    notes TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    FOREIGN KEY (asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS asset_maintenance (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    maintenance_type TEXT NOT NULL DEFAULT '"Preventive"',
+    description TEXT NOT NULL,
+    scheduled_date TEXT NOT NULL,
+    performed_date TEXT,
+    performed_by TEXT,
+    cost INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT '"Scheduled"',
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS asset_depreciations (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL UNIQUE,
+    depreciation_method TEXT NOT NULL DEFAULT '"StraightLine"',
+    useful_life_months INTEGER NOT NULL,
+    salvage_value INTEGER NOT NULL DEFAULT 0,
+    current_value INTEGER NOT NULL DEFAULT 0,
+    accumulated_depreciation INTEGER NOT NULL DEFAULT 0,
+    last_depreciation_date TEXT,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS asset_disposals (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    disposal_type TEXT NOT NULL DEFAULT '"Scrapped"',
+    disposal_date TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    proceeds INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    approved_by TEXT,
+    approved_at TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS asset_locations (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    building TEXT,
+    floor TEXT,
+    room TEXT,
+    address TEXT,
+    parent_id TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS asset_categories (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    parent_id TEXT,
+    default_depreciation_method TEXT,
+    default_useful_life_months INTEGER,
+    status TEXT NOT NULL DEFAULT '"Active"'
+);
+CREATE TABLE IF NOT EXISTS vendor_contracts (
+    id TEXT PRIMARY KEY,
+    vendor_name TEXT NOT NULL,
+    contract_number TEXT NOT NULL UNIQUE,
+    contract_type TEXT NOT NULL DEFAULT '"Support"',
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    value INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    contact_name TEXT,
+    contact_email TEXT,
+    contact_phone TEXT,
+    terms TEXT,
+    auto_renew INTEGER NOT NULL DEFAULT 0,
+    renewal_notice_days INTEGER NOT NULL DEFAULT 30,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS network_assets (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL UNIQUE,
+    ip_address TEXT,
+    mac_address TEXT,
+    hostname TEXT,
+    domain TEXT,
+    network_segment TEXT,
+    vlan INTEGER,
+    port TEXT,
+    switch_port TEXT,
+    dns_servers TEXT,
+    gateway TEXT,
+    subnet_mask TEXT,
+    FOREIGN KEY (asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS security_assets (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL UNIQUE,
+    security_level TEXT NOT NULL DEFAULT '"Internal"',
+    data_classification TEXT NOT NULL DEFAULT '"Internal"',
+    encryption_status INTEGER NOT NULL DEFAULT 0,
+    antivirus_installed INTEGER NOT NULL DEFAULT 0,
+    antivirus_updated TEXT,
+    last_security_scan TEXT,
+    vulnerabilities_found INTEGER NOT NULL DEFAULT 0,
+    vulnerabilities_fixed INTEGER NOT NULL DEFAULT 0,
+    compliance_status TEXT,
+    notes TEXT,
+    FOREIGN KEY (asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS asset_audits (
+    id TEXT PRIMARY KEY,
+    audit_date TEXT NOT NULL,
+    auditor TEXT,
+    location_id TEXT,
+    total_assets INTEGER NOT NULL DEFAULT 0,
+    verified_assets INTEGER NOT NULL DEFAULT 0,
+    missing_assets INTEGER NOT NULL DEFAULT 0,
+    extra_assets INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT '"InProgress"',
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    completed_at TEXT
+);
+CREATE TABLE IF NOT EXISTS asset_audit_items (
+    id TEXT PRIMARY KEY,
+    audit_id TEXT NOT NULL,
+    asset_id TEXT NOT NULL,
+    expected_location_id TEXT,
+    actual_location_id TEXT,
+    expected_assignee_id TEXT,
+    actual_assignee_id TEXT,
+    status TEXT NOT NULL DEFAULT '"Pending"',
+    notes TEXT,
+    verified_at TEXT,
+    verified_by TEXT,
+    FOREIGN KEY (audit_id) REFERENCES asset_audits(id),
+    FOREIGN KEY (asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS asset_relationships (
+    id TEXT PRIMARY KEY,
+    parent_asset_id TEXT NOT NULL,
+    child_asset_id TEXT NOT NULL,
+    relationship_type TEXT NOT NULL DEFAULT '"Contains"',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (parent_asset_id) REFERENCES it_assets(id),
+    FOREIGN KEY (child_asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS asset_checkouts (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    checked_out_to TEXT NOT NULL,
+    checked_out_by TEXT NOT NULL,
+    checked_out_at TEXT NOT NULL,
+    expected_return TEXT NOT NULL,
+    actual_return TEXT,
+    returned_to TEXT,
+    condition_on_checkout TEXT NOT NULL DEFAULT '"Good"',
+    condition_on_return TEXT,
+    notes TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    FOREIGN KEY (asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS software_meters (
+    id TEXT PRIMARY KEY,
+    license_id TEXT NOT NULL,
+    meter_date TEXT NOT NULL,
+    peak_usage INTEGER NOT NULL DEFAULT 0,
+    avg_usage REAL NOT NULL DEFAULT 0,
+    total_hours REAL NOT NULL DEFAULT 0,
+    FOREIGN KEY (license_id) REFERENCES software_licenses(id)
+);
+CREATE TABLE IF NOT EXISTS asset_documents (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    document_type TEXT NOT NULL DEFAULT '"Other"',
+    title TEXT NOT NULL,
+    description TEXT,
+    file_path TEXT NOT NULL,
+    file_size INTEGER NOT NULL DEFAULT 0,
+    uploaded_by TEXT NOT NULL,
+    uploaded_at TEXT NOT NULL,
+    FOREIGN KEY (asset_id) REFERENCES it_assets(id)
+);
+CREATE TABLE IF NOT EXISTS asset_metrics (
+    id TEXT PRIMARY KEY,
+    metric_date TEXT NOT NULL,
+    total_assets INTEGER NOT NULL DEFAULT 0,
+    assets_in_use INTEGER NOT NULL DEFAULT 0,
+    assets_available INTEGER NOT NULL DEFAULT 0,
+    assets_in_maintenance INTEGER NOT NULL DEFAULT 0,
+    assets_retired INTEGER NOT NULL DEFAULT 0,
+    total_value INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    total_depreciation INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_it_assets_status ON it_assets(status);
+CREATE INDEX IF NOT EXISTS idx_it_assets_assigned ON it_assets(assigned_to);
+CREATE INDEX IF NOT EXISTS idx_it_assets_type ON it_assets(asset_type);
+CREATE INDEX IF NOT EXISTS idx_software_licenses_expiry ON software_licenses(expiry_date);
+CREATE INDEX IF NOT EXISTS idx_asset_assignments_active ON asset_assignments(status);
+CREATE TABLE IF NOT EXISTS data_subjects (
+    id TEXT PRIMARY KEY,
+    email TEXT NOT NULL UNIQUE,
+    first_name TEXT,
+    last_name TEXT,
+    phone TEXT,
+    address TEXT,
+    identifier_type TEXT,
+    identifier_value TEXT,
+    verification_status TEXT NOT NULL DEFAULT '"Unverified"',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS consent_records (
+    id TEXT PRIMARY KEY,
+    data_subject_id TEXT NOT NULL,
+    consent_type TEXT NOT NULL,
+    purpose TEXT NOT NULL,
+    legal_basis TEXT NOT NULL DEFAULT '"Consent"',
+    granted_at TEXT,
+    withdrawn_at TEXT,
+    source TEXT NOT NULL,
+    ip_address TEXT,
+    user_agent TEXT,
+    evidence_path TEXT,
+    status TEXT NOT NULL DEFAULT '"Pending"',
+    expiry_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (data_subject_id) REFERENCES data_subjects(id)
+);
+CREATE TABLE IF NOT EXISTS data_processing_activities (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    purpose TEXT NOT NULL,
+    legal_basis TEXT NOT NULL DEFAULT '"Consent"',
+    data_categories TEXT NOT NULL DEFAULT '[]',
+    data_subjects TEXT NOT NULL DEFAULT '[]',
+    recipients TEXT NOT NULL DEFAULT '[]',
+    third_country_transfers TEXT NOT NULL DEFAULT '[]',
+    retention_period_days INTEGER NOT NULL DEFAULT 365,
+    security_measures TEXT NOT NULL DEFAULT '[]',
+    dpo_review_date TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    owner_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS data_breaches (
+    id TEXT PRIMARY KEY,
+    breach_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    breach_type TEXT NOT NULL DEFAULT '"Confidentiality"',
+    severity TEXT NOT NULL DEFAULT '"Medium"',
+    discovered_at TEXT NOT NULL,
+    occurred_at TEXT,
+    reported_at TEXT,
+    affected_records INTEGER NOT NULL DEFAULT 0,
+    affected_data_subjects INTEGER NOT NULL DEFAULT 0,
+    data_categories TEXT NOT NULL DEFAULT '[]',
+    containment_measures TEXT,
+    remediation_measures TEXT,
+    authority_notified INTEGER NOT NULL DEFAULT 0,
+    authority_notification_date TEXT,
+    subjects_notified INTEGER NOT NULL DEFAULT 0,
+    subject_notification_date TEXT,
+    status TEXT NOT NULL DEFAULT '"Detected"',
+    assigned_to TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS dsar_requests (
+    id TEXT PRIMARY KEY,
+    request_number TEXT NOT NULL UNIQUE,
+    data_subject_id TEXT NOT NULL,
+    request_type TEXT NOT NULL DEFAULT '"Access"',
+    description TEXT,
+    received_at TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    completed_at TEXT,
+    status TEXT NOT NULL DEFAULT '"Received"',
+    assigned_to TEXT,
+    response TEXT,
+    rejection_reason TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (data_subject_id) REFERENCES data_subjects(id)
+);
+CREATE TABLE IF NOT EXISTS data_retention_policies (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    data_category TEXT NOT NULL,
+    retention_period_days INTEGER NOT NULL,
+    legal_basis TEXT,
+    disposal_method TEXT NOT NULL DEFAULT '"SecureDeletion"',
+    review_frequency_days INTEGER NOT NULL DEFAULT 365,
+    last_review_date TEXT,
+    next_review_date TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS privacy_impact_assessments (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    project_name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    assessor_id TEXT,
+    assessment_date TEXT NOT NULL,
+    data_types TEXT NOT NULL DEFAULT '[]',
+    processing_purposes TEXT NOT NULL DEFAULT '[]',
+    data_subjects TEXT NOT NULL DEFAULT '[]',
+    risks TEXT NOT NULL DEFAULT '[]',
+    mitigation_measures TEXT NOT NULL DEFAULT '[]',
+    residual_risk_level TEXT NOT NULL DEFAULT '"Low"',
+    recommendation TEXT NOT NULL DEFAULT '"Proceed"',
+    dpo_approval INTEGER NOT NULL DEFAULT 0,
+    dpo_approved_at TEXT,
+    dpo_comments TEXT,
+    status TEXT NOT NULL DEFAULT '"Draft"',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS third_party_processors (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    contact_name TEXT,
+    contact_email TEXT,
+    contact_phone TEXT,
+    address TEXT,
+    country TEXT NOT NULL,
+    processing_activities TEXT NOT NULL DEFAULT '[]',
+    data_categories TEXT NOT NULL DEFAULT '[]',
+    contract_date TEXT,
+    contract_expiry TEXT,
+    dpa_signed INTEGER NOT NULL DEFAULT 0,
+    security_assessment_date TEXT,
+    security_assessment_result TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cookie_policies (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    cookie_type TEXT NOT NULL DEFAULT '"Essential"',
+    purpose TEXT NOT NULL,
+    provider TEXT,
+    expiry TEXT NOT NULL,
+    required INTEGER NOT NULL DEFAULT 0,
+    consent_required INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS compliance_frameworks (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    framework_type TEXT NOT NULL DEFAULT '"GDPR"',
+    version TEXT NOT NULL,
+    applicable_regions TEXT NOT NULL DEFAULT '[]',
+    requirements TEXT NOT NULL DEFAULT '[]',
+    assessment_frequency_days INTEGER NOT NULL DEFAULT 365,
+    last_assessment TEXT,
+    next_assessment TEXT,
+    compliance_status TEXT NOT NULL DEFAULT '"NotStarted"',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS data_inventory (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    data_type TEXT NOT NULL,
+    source_system TEXT NOT NULL,
+    location TEXT NOT NULL,
+    owner_id TEXT,
+    data_classification TEXT NOT NULL DEFAULT '"Internal"',
+    contains_pii INTEGER NOT NULL DEFAULT 0,
+    contains_phi INTEGER NOT NULL DEFAULT 0,
+    contains_pci INTEGER NOT NULL DEFAULT 0,
+    retention_policy_id TEXT,
+    last_accessed TEXT,
+    last_reviewed TEXT,
+    status TEXT NOT NULL DEFAULT '"Active"',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (retention_policy_id) REFERENCES data_retention_policies(id)
+);
+CREATE INDEX IF NOT EXISTS idx_consent_records_subject ON consent_records(data_subject_id);
+CREATE INDEX IF NOT EXISTS idx_consent_records_status ON consent_records(status);
+CREATE INDEX IF NOT EXISTS idx_dsar_requests_status ON dsar_requests(status);
+CREATE INDEX IF NOT EXISTS idx_dsar_requests_due ON dsar_requests(due_date);
+CREATE INDEX IF NOT EXISTS idx_data_breaches_status ON data_breaches(status);
+CREATE INDEX IF NOT EXISTS idx_data_inventory_classification ON data_inventory(data_classification);
+CREATE TABLE IF NOT EXISTS return_orders (
+    id TEXT PRIMARY KEY,
+    return_number TEXT NOT NULL UNIQUE,
+    return_type TEXT NOT NULL DEFAULT 'CustomerReturn',
+    customer_id TEXT,
+    vendor_id TEXT,
+    original_order_id TEXT,
+    original_invoice_id TEXT,
+    request_date TEXT NOT NULL,
+    received_date TEXT,
+    processed_date TEXT,
+    reason TEXT NOT NULL DEFAULT 'Other',
+    notes TEXT,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    total_credit INTEGER NOT NULL DEFAULT 0,
+    warehouse_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS return_lines (
+    id TEXT PRIMARY KEY,
+    return_order_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    description TEXT NOT NULL,
+    quantity_requested INTEGER NOT NULL,
+    quantity_received INTEGER NOT NULL DEFAULT 0,
+    quantity_approved INTEGER NOT NULL DEFAULT 0,
+    unit_price INTEGER NOT NULL,
+    reason TEXT NOT NULL DEFAULT 'Other',
+    disposition TEXT NOT NULL DEFAULT 'Restock',
+    condition_type TEXT NOT NULL DEFAULT 'New',
+    inspection_notes TEXT,
+    credit_amount INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (return_order_id) REFERENCES return_orders(id)
+);
+CREATE TABLE IF NOT EXISTS return_inspections (
+    id TEXT PRIMARY KEY,
+    return_order_id TEXT NOT NULL,
+    return_line_id TEXT NOT NULL,
+    inspector_id TEXT,
+    inspection_date TEXT NOT NULL,
+    condition TEXT NOT NULL DEFAULT 'New',
+    passes_quality INTEGER NOT NULL DEFAULT 0,
+    notes TEXT,
+    disposition TEXT NOT NULL DEFAULT 'Restock',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (return_order_id) REFERENCES return_orders(id),
+    FOREIGN KEY (return_line_id) REFERENCES return_lines(id)
+);
+CREATE TABLE IF NOT EXISTS credit_memos (
+    id TEXT PRIMARY KEY,
+    memo_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    return_order_id TEXT,
+    invoice_id TEXT,
+    memo_date TEXT NOT NULL,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    applied_amount INTEGER NOT NULL DEFAULT 0,
+    reason TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS credit_memo_lines (
+    id TEXT PRIMARY KEY,
+    credit_memo_id TEXT NOT NULL,
+    product_id TEXT,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    line_total INTEGER NOT NULL,
+    FOREIGN KEY (credit_memo_id) REFERENCES credit_memos(id)
+);
+CREATE TABLE IF NOT EXISTS refunds (
+    id TEXT PRIMARY KEY,
+    refund_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    credit_memo_id TEXT,
+    return_order_id TEXT,
+    refund_date TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    method TEXT NOT NULL DEFAULT 'OriginalPayment',
+    reference TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    processed_at TEXT,
+    created_at TEXT NOT NULL
+, payment_id TEXT, currency TEXT, reason TEXT, gateway_refund_id TEXT, updated_at TEXT, created_by TEXT, updated_by TEXT);
+CREATE TABLE IF NOT EXISTS return_policies (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    return_window_days INTEGER NOT NULL DEFAULT 30,
+    requires_receipt INTEGER NOT NULL DEFAULT 1,
+    requires_original_packaging INTEGER NOT NULL DEFAULT 0,
+    restocking_fee_percent REAL NOT NULL DEFAULT 0,
+    allows_exchange INTEGER NOT NULL DEFAULT 1,
+    allows_refund INTEGER NOT NULL DEFAULT 1,
+    allows_store_credit INTEGER NOT NULL DEFAULT 1,
+    excluded_categories TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS dunning_policies (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS dunning_level_configs (
+    id TEXT PRIMARY KEY,
+    policy_id TEXT NOT NULL,
+    level TEXT NOT NULL DEFAULT 'Reminder',
+    days_overdue INTEGER NOT NULL DEFAULT 0,
+    fee_percent REAL NOT NULL DEFAULT 0,
+    fee_fixed INTEGER NOT NULL DEFAULT 0,
+    template_id TEXT,
+    stop_services INTEGER NOT NULL DEFAULT 0,
+    send_email INTEGER NOT NULL DEFAULT 1,
+    FOREIGN KEY (policy_id) REFERENCES dunning_policies(id)
+);
+CREATE TABLE IF NOT EXISTS dunning_runs (
+    id TEXT PRIMARY KEY,
+    run_number TEXT NOT NULL UNIQUE,
+    policy_id TEXT NOT NULL,
+    run_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    customers_processed INTEGER NOT NULL DEFAULT 0,
+    total_amount INTEGER NOT NULL DEFAULT 0,
+    total_fees INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (policy_id) REFERENCES dunning_policies(id)
+);
+CREATE TABLE IF NOT EXISTS dunning_letters (
+    id TEXT PRIMARY KEY,
+    run_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    level TEXT NOT NULL DEFAULT 'Reminder',
+    letter_date TEXT NOT NULL,
+    invoice_amount INTEGER NOT NULL DEFAULT 0,
+    fee_amount INTEGER NOT NULL DEFAULT 0,
+    total_amount INTEGER NOT NULL DEFAULT 0,
+    sent_at TEXT,
+    acknowledged_at TEXT,
+    status TEXT NOT NULL DEFAULT 'Generated',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (run_id) REFERENCES dunning_runs(id)
+);
+CREATE TABLE IF NOT EXISTS dunning_letter_invoices (
+    letter_id TEXT NOT NULL,
+    invoice_id TEXT NOT NULL,
+    PRIMARY KEY (letter_id, invoice_id),
+    FOREIGN KEY (letter_id) REFERENCES dunning_letters(id)
+);
+CREATE TABLE IF NOT EXISTS collection_cases (
+    id TEXT PRIMARY KEY,
+    case_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    dunning_letter_id TEXT,
+    assigned_to TEXT,
+    open_date TEXT NOT NULL,
+    close_date TEXT,
+    total_amount INTEGER NOT NULL DEFAULT 0,
+    collected_amount INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Open',
+    priority TEXT NOT NULL DEFAULT 'Medium',
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (dunning_letter_id) REFERENCES dunning_letters(id)
+);
+CREATE TABLE IF NOT EXISTS collection_activities (
+    id TEXT PRIMARY KEY,
+    case_id TEXT NOT NULL,
+    activity_type TEXT NOT NULL DEFAULT 'Note',
+    description TEXT NOT NULL,
+    performed_by TEXT,
+    performed_at TEXT NOT NULL,
+    result TEXT,
+    next_action TEXT,
+    next_action_date TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (case_id) REFERENCES collection_cases(id)
+);
+CREATE TABLE IF NOT EXISTS accounting_periods (
+    id TEXT PRIMARY KEY,
+    fiscal_year_id TEXT NOT NULL,
+    period_number INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    lock_type TEXT NOT NULL DEFAULT 'Open',
+    locked_at TEXT,
+    locked_by TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (fiscal_year_id) REFERENCES fiscal_years(id)
+);
+CREATE TABLE IF NOT EXISTS period_close_checklists (
+    id TEXT PRIMARY KEY,
+    period_id TEXT NOT NULL,
+    task_name TEXT NOT NULL,
+    description TEXT,
+    task_order INTEGER NOT NULL DEFAULT 0,
+    is_required INTEGER NOT NULL DEFAULT 1,
+    completed INTEGER NOT NULL DEFAULT 0,
+    completed_at TEXT,
+    completed_by TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (period_id) REFERENCES accounting_periods(id)
+);
+CREATE TABLE IF NOT EXISTS recurring_journals (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    frequency TEXT NOT NULL DEFAULT 'Monthly',
+    interval_value INTEGER NOT NULL DEFAULT 1,
+    day_of_month INTEGER,
+    day_of_week INTEGER,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    next_run_date TEXT,
+    last_run_date TEXT,
+    auto_post INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS recurring_journal_lines (
+    id TEXT PRIMARY KEY,
+    recurring_journal_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    debit INTEGER NOT NULL DEFAULT 0,
+    credit INTEGER NOT NULL DEFAULT 0,
+    description TEXT,
+    FOREIGN KEY (recurring_journal_id) REFERENCES recurring_journals(id)
+);
+CREATE TABLE IF NOT EXISTS recurring_journal_runs (
+    id TEXT PRIMARY KEY,
+    recurring_journal_id TEXT NOT NULL,
+    run_date TEXT NOT NULL,
+    journal_entry_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (recurring_journal_id) REFERENCES recurring_journals(id),
+    FOREIGN KEY (journal_entry_id) REFERENCES journal_entries(id)
+);
+CREATE INDEX IF NOT EXISTS idx_return_orders_customer ON return_orders(customer_id);
+CREATE INDEX IF NOT EXISTS idx_return_orders_status ON return_orders(status);
+CREATE INDEX IF NOT EXISTS idx_credit_memos_customer ON credit_memos(customer_id);
+CREATE INDEX IF NOT EXISTS idx_dunning_letters_customer ON dunning_letters(customer_id);
+CREATE INDEX IF NOT EXISTS idx_collection_cases_customer ON collection_cases(customer_id);
+CREATE INDEX IF NOT EXISTS idx_accounting_periods_fy ON accounting_periods(fiscal_year_id);
+CREATE INDEX IF NOT EXISTS idx_recurring_journals_next_run ON recurring_journals(next_run_date);
+CREATE TABLE IF NOT EXISTS pos_stores (
+    id TEXT PRIMARY KEY,
+    store_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    address TEXT NOT NULL,
+    city TEXT NOT NULL,
+    state TEXT NOT NULL,
+    postal_code TEXT NOT NULL,
+    country TEXT NOT NULL,
+    phone TEXT,
+    email TEXT,
+    manager_id TEXT,
+    warehouse_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    opening_time TEXT NOT NULL DEFAULT '09:00',
+    closing_time TEXT NOT NULL DEFAULT '21:00',
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pos_terminals (
+    id TEXT PRIMARY KEY,
+    terminal_code TEXT NOT NULL UNIQUE,
+    store_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    location TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    printer_name TEXT,
+    receipt_printer TEXT,
+    cash_drawer INTEGER NOT NULL DEFAULT 1,
+    customer_display INTEGER NOT NULL DEFAULT 0,
+    barcode_scanner INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS registers (
+    id TEXT PRIMARY KEY,
+    register_number TEXT NOT NULL UNIQUE,
+    store_id TEXT NOT NULL,
+    terminal_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Closed',
+    opened_at TEXT,
+    opened_by TEXT,
+    closed_at TEXT,
+    closed_by TEXT,
+    opening_float INTEGER NOT NULL DEFAULT 0,
+    closing_float INTEGER,
+    expected_cash INTEGER,
+    cash_variance INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pos_transactions (
+    id TEXT PRIMARY KEY,
+    transaction_number TEXT NOT NULL UNIQUE,
+    store_id TEXT NOT NULL,
+    terminal_id TEXT NOT NULL,
+    register_id TEXT NOT NULL,
+    transaction_type TEXT NOT NULL DEFAULT 'Sale',
+    customer_id TEXT,
+    sales_rep_id TEXT,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    discount_amount INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    change_amount INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    original_transaction_id TEXT,
+    notes TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pos_transaction_lines (
+    id TEXT PRIMARY KEY,
+    transaction_id TEXT NOT NULL,
+    line_number INTEGER NOT NULL,
+    product_id TEXT NOT NULL,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    discount_percent REAL NOT NULL DEFAULT 0,
+    discount_amount INTEGER NOT NULL DEFAULT 0,
+    tax_rate_id TEXT,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    line_total INTEGER NOT NULL,
+    lot_number TEXT,
+    serial_number TEXT
+);
+CREATE TABLE IF NOT EXISTS pos_transaction_payments (
+    id TEXT PRIMARY KEY,
+    transaction_id TEXT NOT NULL,
+    payment_method TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    reference TEXT,
+    card_last_four TEXT,
+    card_type TEXT,
+    authorization_code TEXT,
+    gift_card_id TEXT
+);
+CREATE TABLE IF NOT EXISTS loyalty_accounts (
+    id TEXT PRIMARY KEY,
+    customer_id TEXT NOT NULL,
+    program_id TEXT,
+    points_balance INTEGER NOT NULL DEFAULT 0,
+    tier TEXT NOT NULL DEFAULT 'Bronze',
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ecommerce_platforms (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    platform_type TEXT NOT NULL,
+    base_url TEXT NOT NULL,
+    api_key TEXT,
+    api_secret TEXT,
+    access_token TEXT,
+    webhook_secret TEXT,
+    store_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    sync_direction TEXT NOT NULL DEFAULT 'Import',
+    last_sync_at TEXT,
+    sync_interval_minutes INTEGER NOT NULL DEFAULT 60,
+    auto_sync INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS product_listings (
+    id TEXT PRIMARY KEY,
+    platform_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    external_product_id TEXT NOT NULL,
+    external_variant_id TEXT,
+    title TEXT NOT NULL,
+    description TEXT,
+    price INTEGER NOT NULL,
+    compare_at_price INTEGER,
+    quantity INTEGER NOT NULL DEFAULT 0,
+    sku TEXT,
+    barcode TEXT,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    visibility TEXT NOT NULL DEFAULT 'Visible',
+    seo_title TEXT,
+    seo_description TEXT,
+    tags TEXT,
+    category TEXT,
+    images TEXT,
+    sync_status TEXT NOT NULL DEFAULT 'Pending',
+    last_sync_at TEXT,
+    sync_error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ecommerce_orders (
+    id TEXT PRIMARY KEY,
+    platform_id TEXT NOT NULL,
+    external_order_id TEXT NOT NULL,
+    order_number TEXT NOT NULL,
+    customer_id TEXT,
+    external_customer_id TEXT,
+    sales_order_id TEXT,
+    order_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    fulfillment_status TEXT NOT NULL DEFAULT 'Unfulfilled',
+    payment_status TEXT NOT NULL DEFAULT 'Pending',
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    shipping_amount INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    discount_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    billing_name TEXT NOT NULL,
+    billing_address TEXT NOT NULL,
+    billing_city TEXT NOT NULL,
+    billing_state TEXT NOT NULL,
+    billing_postal_code TEXT NOT NULL,
+    billing_country TEXT NOT NULL,
+    shipping_name TEXT NOT NULL,
+    shipping_address TEXT NOT NULL,
+    shipping_city TEXT NOT NULL,
+    shipping_state TEXT NOT NULL,
+    shipping_postal_code TEXT NOT NULL,
+    shipping_country TEXT NOT NULL,
+    shipping_method TEXT,
+    tracking_number TEXT,
+    customer_email TEXT,
+    customer_phone TEXT,
+    notes TEXT,
+    sync_status TEXT NOT NULL DEFAULT 'Pending',
+    imported_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ecommerce_order_lines (
+    id TEXT PRIMARY KEY,
+    order_id TEXT NOT NULL,
+    external_line_id TEXT,
+    product_id TEXT,
+    listing_id TEXT,
+    sku TEXT,
+    title TEXT NOT NULL,
+    variant_title TEXT,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    discount_amount INTEGER NOT NULL DEFAULT 0,
+    line_total INTEGER NOT NULL,
+    fulfillment_status TEXT NOT NULL DEFAULT 'Unfulfilled',
+    quantity_fulfilled INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS webhook_events (
+    id TEXT PRIMARY KEY,
+    platform_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    external_id TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    processed INTEGER NOT NULL DEFAULT 0,
+    processed_at TEXT,
+    error TEXT,
+    retry_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS inventory_syncs (
+    id TEXT PRIMARY KEY,
+    platform_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT,
+    external_product_id TEXT NOT NULL,
+    external_variant_id TEXT,
+    local_quantity INTEGER NOT NULL DEFAULT 0,
+    remote_quantity INTEGER NOT NULL DEFAULT 0,
+    reserved_quantity INTEGER NOT NULL DEFAULT 0,
+    sync_status TEXT NOT NULL DEFAULT 'Pending',
+    last_sync_at TEXT,
+    sync_error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tax_jurisdictions (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    country_code TEXT NOT NULL,
+    state_code TEXT,
+    county TEXT,
+    city TEXT,
+    postal_code_from TEXT,
+    postal_code_to TEXT,
+    parent_jurisdiction_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    effective_from TEXT NOT NULL,
+    effective_to TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tax_classes (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    class_type TEXT NOT NULL DEFAULT 'Product',
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tax_exemptions (
+    id TEXT PRIMARY KEY,
+    customer_id TEXT NOT NULL,
+    exemption_type TEXT NOT NULL DEFAULT 'Resale',
+    certificate_number TEXT NOT NULL,
+    jurisdiction_id TEXT,
+    issue_date TEXT NOT NULL,
+    expiry_date TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    document_url TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tax_transactions (
+    id TEXT PRIMARY KEY,
+    transaction_type TEXT NOT NULL,
+    transaction_id TEXT NOT NULL,
+    transaction_date TEXT NOT NULL,
+    customer_id TEXT,
+    jurisdiction_id TEXT NOT NULL,
+    tax_rate_id TEXT NOT NULL,
+    tax_class_id TEXT,
+    tax_type TEXT NOT NULL DEFAULT 'SalesTax',
+    taxable_amount INTEGER NOT NULL DEFAULT 0,
+    tax_rate REAL NOT NULL,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    exemption_id TEXT,
+    exempt_amount INTEGER NOT NULL DEFAULT 0,
+    source TEXT NOT NULL DEFAULT 'Manual',
+    external_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tax_reports (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    jurisdiction_id TEXT NOT NULL,
+    report_period TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_sales INTEGER NOT NULL DEFAULT 0,
+    taxable_sales INTEGER NOT NULL DEFAULT 0,
+    exempt_sales INTEGER NOT NULL DEFAULT 0,
+    tax_collected INTEGER NOT NULL DEFAULT 0,
+    tax_paid INTEGER NOT NULL DEFAULT 0,
+    tax_due INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    filed_at TEXT,
+    filed_by TEXT,
+    filing_reference TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS report_schedules (
+    id TEXT PRIMARY KEY,
+    report_definition_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    frequency TEXT NOT NULL DEFAULT 'Daily',
+    cron_expression TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    next_run_at TEXT,
+    last_run_at TEXT,
+    parameters TEXT NOT NULL DEFAULT '{}',
+    output_format TEXT NOT NULL DEFAULT 'PDF',
+    delivery_methods TEXT NOT NULL DEFAULT '["Email"]',
+    recipients TEXT NOT NULL DEFAULT '[]',
+    email_subject TEXT,
+    email_body TEXT,
+    include_attachments INTEGER NOT NULL DEFAULT 1,
+    ftp_host TEXT,
+    ftp_path TEXT,
+    webhook_url TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS report_dashboards (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    layout TEXT NOT NULL DEFAULT 'grid',
+    widgets TEXT NOT NULL DEFAULT '[]',
+    is_default INTEGER NOT NULL DEFAULT 0,
+    is_public INTEGER NOT NULL DEFAULT 1,
+    refresh_interval_seconds INTEGER,
+    status TEXT NOT NULL DEFAULT 'Active',
+    owner_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS barcodes (
+    id TEXT PRIMARY KEY,
+    barcode TEXT NOT NULL UNIQUE,
+    barcode_type TEXT NOT NULL DEFAULT 'EAN13',
+    entity_type TEXT NOT NULL DEFAULT 'Product',
+    entity_id TEXT NOT NULL,
+    definition_id TEXT,
+    is_primary INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS barcode_definitions (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    barcode_type TEXT NOT NULL DEFAULT 'Code128',
+    entity_type TEXT NOT NULL DEFAULT 'Product',
+    prefix TEXT,
+    suffix TEXT,
+    include_check_digit INTEGER NOT NULL DEFAULT 1,
+    auto_generate INTEGER NOT NULL DEFAULT 1,
+    sequence_start INTEGER NOT NULL DEFAULT 1,
+    sequence_current INTEGER NOT NULL DEFAULT 0,
+    padding_length INTEGER NOT NULL DEFAULT 6,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS barcode_printers (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    printer_type TEXT NOT NULL DEFAULT 'Thermal',
+    ip_address TEXT,
+    port INTEGER,
+    connection_type TEXT NOT NULL DEFAULT 'Network',
+    dpi INTEGER NOT NULL DEFAULT 203,
+    label_width_mm REAL NOT NULL DEFAULT 50.0,
+    label_height_mm REAL NOT NULL DEFAULT 25.0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    last_seen_at TEXT,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS barcode_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    width_mm REAL NOT NULL DEFAULT 50.0,
+    height_mm REAL NOT NULL DEFAULT 25.0,
+    margin_top_mm REAL NOT NULL DEFAULT 2.0,
+    margin_bottom_mm REAL NOT NULL DEFAULT 2.0,
+    margin_left_mm REAL NOT NULL DEFAULT 2.0,
+    margin_right_mm REAL NOT NULL DEFAULT 2.0,
+    barcode_type TEXT NOT NULL DEFAULT 'Code128',
+    barcode_width_mm REAL NOT NULL DEFAULT 40.0,
+    barcode_height_mm REAL NOT NULL DEFAULT 15.0,
+    barcode_position_x REAL NOT NULL DEFAULT 5.0,
+    barcode_position_y REAL NOT NULL DEFAULT 5.0,
+    include_text INTEGER NOT NULL DEFAULT 1,
+    text_font TEXT NOT NULL DEFAULT 'Arial',
+    text_size INTEGER NOT NULL DEFAULT 10,
+    text_position TEXT NOT NULL DEFAULT 'Below',
+    elements TEXT NOT NULL DEFAULT '[]',
+    zpl_template TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS barcode_print_jobs (
+    id TEXT PRIMARY KEY,
+    job_number TEXT NOT NULL UNIQUE,
+    printer_id TEXT NOT NULL,
+    template_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 0,
+    printed_count INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    created_by TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS barcode_print_items (
+    id TEXT PRIMARY KEY,
+    job_id TEXT NOT NULL,
+    barcode_id TEXT NOT NULL,
+    barcode TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    copies INTEGER NOT NULL DEFAULT 1,
+    printed_copies INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    error_message TEXT
+);
+CREATE TABLE IF NOT EXISTS scan_events (
+    id TEXT PRIMARY KEY,
+    barcode TEXT NOT NULL,
+    barcode_type TEXT NOT NULL DEFAULT 'EAN13',
+    scanner_id TEXT NOT NULL,
+    user_id TEXT,
+    location_id TEXT,
+    entity_type TEXT NOT NULL DEFAULT 'Product',
+    entity_id TEXT,
+    action TEXT NOT NULL DEFAULT 'Lookup',
+    quantity INTEGER NOT NULL DEFAULT 1,
+    reference_type TEXT,
+    reference_id TEXT,
+    scanned_at TEXT NOT NULL,
+    metadata TEXT
+);
+CREATE TABLE IF NOT EXISTS barcode_scanners (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    scanner_type TEXT NOT NULL DEFAULT 'Handheld',
+    connection_type TEXT NOT NULL DEFAULT 'USB',
+    device_id TEXT,
+    location_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    last_used_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_pos_transactions_store ON pos_transactions(store_id);
+CREATE INDEX IF NOT EXISTS idx_pos_transactions_date ON pos_transactions(created_at);
+CREATE INDEX IF NOT EXISTS idx_pos_transaction_lines ON pos_transaction_lines(transaction_id);
+CREATE INDEX IF NOT EXISTS idx_pos_transaction_payments ON pos_transaction_payments(transaction_id);
+CREATE INDEX IF NOT EXISTS idx_ecommerce_orders_platform ON ecommerce_orders(platform_id);
+CREATE INDEX IF NOT EXISTS idx_ecommerce_orders_status ON ecommerce_orders(status);
+CREATE INDEX IF NOT EXISTS idx_ecommerce_order_lines ON ecommerce_order_lines(order_id);
+CREATE INDEX IF NOT EXISTS idx_product_listings_product ON product_listings(product_id);
+CREATE INDEX IF NOT EXISTS idx_webhook_events ON webhook_events(platform_id, processed);
+CREATE INDEX IF NOT EXISTS idx_tax_transactions_date ON tax_transactions(transaction_date);
+CREATE INDEX IF NOT EXISTS idx_tax_exemptions_customer ON tax_exemptions(customer_id);
+CREATE INDEX IF NOT EXISTS idx_report_schedules_next ON report_schedules(next_run_at);
+CREATE INDEX IF NOT EXISTS idx_barcodes_barcode ON barcodes(barcode);
+CREATE INDEX IF NOT EXISTS idx_barcodes_entity ON barcodes(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_scan_events_barcode ON scan_events(barcode);
+CREATE INDEX IF NOT EXISTS idx_scan_events_date ON scan_events(scanned_at);
+CREATE TABLE IF NOT EXISTS ai_models (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    model_type TEXT NOT NULL,
+    algorithm TEXT NOT NULL,
+    description TEXT,
+    version TEXT NOT NULL,
+    parameters TEXT,
+    features TEXT,
+    target_variable TEXT,
+    training_data_source TEXT,
+    training_config TEXT,
+    validation_split REAL DEFAULT 0.2,
+    cross_validation_folds INTEGER DEFAULT 5,
+    hyperparameters TEXT,
+    training_status TEXT DEFAULT 'Draft',
+    training_started_at TEXT,
+    training_completed_at TEXT,
+    training_duration_seconds INTEGER,
+    training_samples INTEGER,
+    validation_metrics TEXT,
+    test_metrics TEXT,
+    accuracy_score REAL,
+    precision_score REAL,
+    recall_score REAL,
+    f1_score REAL,
+    rmse REAL,
+    mae REAL,
+    mape REAL,
+    r2_score REAL,
+    auc_roc REAL,
+    feature_importance TEXT,
+    model_artifact_path TEXT,
+    deployment_endpoint TEXT,
+    deployment_status TEXT DEFAULT 'Draft',
+    deployed_at TEXT,
+    inference_count INTEGER DEFAULT 0,
+    last_inference_at TEXT,
+    drift_detected INTEGER DEFAULT 0,
+    drift_score REAL,
+    retraining_required INTEGER DEFAULT 0,
+    auto_retrain INTEGER DEFAULT 0,
+    retrain_threshold REAL,
+    retrain_schedule_cron TEXT,
+    last_retrain_at TEXT,
+    owner_id TEXT,
+    tags TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS training_jobs (
+    id TEXT PRIMARY KEY,
+    model_id TEXT NOT NULL,
+    job_number TEXT NOT NULL,
+    data_source TEXT NOT NULL,
+    data_filters TEXT,
+    feature_config TEXT,
+    parameter_overrides TEXT,
+    training_config TEXT,
+    status TEXT DEFAULT 'Queued',
+    progress_percent INTEGER DEFAULT 0,
+    current_epoch INTEGER,
+    total_epochs INTEGER,
+    current_batch INTEGER,
+    total_batches INTEGER,
+    loss_value REAL,
+    metrics_history TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    duration_seconds INTEGER,
+    samples_processed INTEGER,
+    error_message TEXT,
+    compute_resource TEXT,
+    gpu_used INTEGER DEFAULT 0,
+    memory_used_mb INTEGER,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS prediction_requests (
+    id TEXT PRIMARY KEY,
+    model_id TEXT NOT NULL,
+    request_id TEXT NOT NULL UNIQUE,
+    input_data TEXT NOT NULL,
+    input_features TEXT,
+    batch_mode INTEGER DEFAULT 0,
+    batch_size INTEGER,
+    priority INTEGER DEFAULT 5,
+    status TEXT DEFAULT 'Pending',
+    result TEXT,
+    predictions TEXT,
+    confidence_scores TEXT,
+    explanation TEXT,
+    feature_contributions TEXT,
+    processing_time_ms INTEGER,
+    created_by TEXT,
+    processed_at TEXT,
+    error_message TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS anomaly_detections (
+    id TEXT PRIMARY KEY,
+    model_id TEXT,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    anomaly_type TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    detected_at TEXT NOT NULL,
+    metric_name TEXT NOT NULL,
+    expected_value REAL NOT NULL,
+    actual_value REAL NOT NULL,
+    deviation_percent REAL NOT NULL,
+    z_score REAL,
+    confidence_score REAL NOT NULL,
+    detection_method TEXT NOT NULL,
+    context_data TEXT,
+    root_cause_analysis TEXT,
+    related_anomalies TEXT,
+    status TEXT DEFAULT 'New',
+    acknowledged_at TEXT,
+    acknowledged_by TEXT,
+    resolution_notes TEXT,
+    resolved_at TEXT,
+    false_positive INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS customer_insights (
+    id TEXT PRIMARY KEY,
+    customer_id TEXT NOT NULL,
+    insight_type TEXT NOT NULL,
+    segment TEXT,
+    segment_score REAL,
+    lifetime_value REAL,
+    churn_probability REAL,
+    churn_risk_level TEXT,
+    next_best_action TEXT,
+    recommended_products TEXT,
+    cross_sell_opportunities TEXT,
+    upsell_opportunities TEXT,
+    purchase_propensity TEXT,
+    engagement_score REAL,
+    satisfaction_score REAL,
+    nps_score INTEGER,
+    sentiment TEXT,
+    behavior_patterns TEXT,
+    preferences TEXT,
+    risk_factors TEXT,
+    last_purchase_prediction TEXT,
+    predicted_order_value REAL,
+    model_version TEXT,
+    calculated_at TEXT NOT NULL,
+    valid_until TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS recommendation_engines (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    recommendation_type TEXT NOT NULL,
+    algorithm TEXT NOT NULL,
+    description TEXT,
+    model_id TEXT,
+    config TEXT,
+    fallback_strategy TEXT,
+    diversity_factor REAL DEFAULT 0.5,
+    novelty_factor REAL DEFAULT 0.3,
+    cold_start_strategy TEXT,
+    context_features TEXT,
+    ranking_strategy TEXT,
+    ab_test_enabled INTEGER DEFAULT 0,
+    ab_test_config TEXT,
+    metrics_window_days INTEGER DEFAULT 30,
+    click_through_rate REAL,
+    conversion_rate REAL,
+    average_rating REAL,
+    total_recommendations INTEGER DEFAULT 0,
+    total_clicks INTEGER DEFAULT 0,
+    total_conversions INTEGER DEFAULT 0,
+    last_optimized_at TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS recommendation_results (
+    id TEXT PRIMARY KEY,
+    engine_id TEXT NOT NULL,
+    user_id TEXT,
+    customer_id TEXT,
+    session_id TEXT,
+    context TEXT,
+    recommendations TEXT NOT NULL,
+    scores TEXT,
+    reasons TEXT,
+    diversity_score REAL,
+    novelty_score REAL,
+    displayed_at TEXT,
+    clicked_at TEXT,
+    clicked_item_id TEXT,
+    clicked_position INTEGER,
+    converted_at TEXT,
+    converted_item_id TEXT,
+    conversion_value REAL,
+    ab_test_variant TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ai_monitoring_metrics (
+    id TEXT PRIMARY KEY,
+    model_id TEXT NOT NULL,
+    metric_name TEXT NOT NULL,
+    metric_type TEXT NOT NULL,
+    value REAL NOT NULL,
+    threshold_warning REAL,
+    threshold_critical REAL,
+    trend TEXT,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    sample_size INTEGER,
+    details TEXT,
+    alert_triggered INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS feature_stores (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    entity_type TEXT NOT NULL,
+    features TEXT NOT NULL,
+    data_source TEXT NOT NULL,
+    refresh_schedule TEXT,
+    last_refresh_at TEXT,
+    feature_count INTEGER,
+    row_count INTEGER,
+    size_bytes INTEGER,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ml_experiments (
+    id TEXT PRIMARY KEY,
+    model_id TEXT NOT NULL,
+    experiment_name TEXT NOT NULL,
+    run_number INTEGER DEFAULT 1,
+    parameters TEXT NOT NULL,
+    metrics TEXT,
+    artifacts_path TEXT,
+    status TEXT DEFAULT 'Draft',
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    duration_seconds INTEGER,
+    notes TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS portal_users (
+    id TEXT PRIMARY KEY,
+    portal_type TEXT NOT NULL,
+    external_id TEXT,
+    username TEXT NOT NULL UNIQUE,
+    email TEXT NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL,
+    display_name TEXT,
+    company_name TEXT,
+    phone TEXT,
+    access_level TEXT DEFAULT 'Standard',
+    permissions TEXT,
+    preferences TEXT,
+    language TEXT DEFAULT 'en',
+    timezone TEXT DEFAULT 'UTC',
+    avatar_url TEXT,
+    last_login_at TEXT,
+    login_count INTEGER DEFAULT 0,
+    failed_login_count INTEGER DEFAULT 0,
+    locked_until TEXT,
+    password_changed_at TEXT,
+    must_change_password INTEGER DEFAULT 0,
+    two_factor_enabled INTEGER DEFAULT 0,
+    two_factor_secret TEXT,
+    api_key TEXT,
+    api_key_expires_at TEXT,
+    session_timeout_minutes INTEGER DEFAULT 30,
+    status TEXT DEFAULT 'Active',
+    invited_by TEXT,
+    invited_at TEXT,
+    accepted_at TEXT,
+    notification_preferences TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS customer_portal_access (
+    id TEXT PRIMARY KEY,
+    portal_user_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    role TEXT DEFAULT 'Viewer',
+    permissions TEXT,
+    can_view_orders INTEGER DEFAULT 1,
+    can_create_orders INTEGER DEFAULT 0,
+    can_view_invoices INTEGER DEFAULT 1,
+    can_pay_invoices INTEGER DEFAULT 0,
+    can_view_quotes INTEGER DEFAULT 1,
+    can_request_quotes INTEGER DEFAULT 0,
+    can_view_shipments INTEGER DEFAULT 1,
+    can_view_returns INTEGER DEFAULT 1,
+    can_create_returns INTEGER DEFAULT 0,
+    can_view_contracts INTEGER DEFAULT 0,
+    can_view_statements INTEGER DEFAULT 1,
+    can_download_documents INTEGER DEFAULT 1,
+    can_view_pricing INTEGER DEFAULT 1,
+    can_view_inventory INTEGER DEFAULT 0,
+    default_warehouse_id TEXT,
+    default_price_list_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS supplier_portal_access (
+    id TEXT PRIMARY KEY,
+    portal_user_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    role TEXT DEFAULT 'Viewer',
+    permissions TEXT,
+    can_view_pos INTEGER DEFAULT 1,
+    can_submit_quotes INTEGER DEFAULT 0,
+    can_update_orders INTEGER DEFAULT 0,
+    can_create_invoices INTEGER DEFAULT 0,
+    can_view_payments INTEGER DEFAULT 1,
+    can_view_forecasts INTEGER DEFAULT 0,
+    can_update_inventory INTEGER DEFAULT 0,
+    can_view_performance INTEGER DEFAULT 1,
+    can_upload_documents INTEGER DEFAULT 1,
+    can_manage_catalog INTEGER DEFAULT 0,
+    default_currency TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS portal_sessions (
+    id TEXT PRIMARY KEY,
+    portal_user_id TEXT NOT NULL,
+    session_token TEXT NOT NULL UNIQUE,
+    refresh_token TEXT NOT NULL UNIQUE,
+    ip_address TEXT,
+    user_agent TEXT,
+    device_type TEXT,
+    login_at TEXT NOT NULL,
+    last_activity_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    logout_at TEXT,
+    logout_reason TEXT,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS portal_orders (
+    id TEXT PRIMARY KEY,
+    portal_user_id TEXT NOT NULL,
+    portal_order_number TEXT NOT NULL UNIQUE,
+    erp_order_id TEXT,
+    erp_order_number TEXT,
+    customer_id TEXT NOT NULL,
+    order_type TEXT DEFAULT 'Standard',
+    status TEXT DEFAULT 'Draft',
+    billing_address TEXT,
+    shipping_address TEXT,
+    requested_delivery_date TEXT,
+    shipping_method TEXT,
+    payment_method TEXT,
+    notes TEXT,
+    internal_notes TEXT,
+    subtotal_cents INTEGER DEFAULT 0,
+    tax_cents INTEGER DEFAULT 0,
+    shipping_cents INTEGER DEFAULT 0,
+    discount_cents INTEGER DEFAULT 0,
+    total_cents INTEGER DEFAULT 0,
+    currency TEXT DEFAULT 'USD',
+    submitted_at TEXT,
+    confirmed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS portal_order_lines (
+    id TEXT PRIMARY KEY,
+    portal_order_id TEXT NOT NULL,
+    line_number INTEGER NOT NULL,
+    product_id TEXT NOT NULL,
+    product_code TEXT NOT NULL,
+    product_name TEXT NOT NULL,
+    description TEXT,
+    quantity INTEGER NOT NULL,
+    unit_of_measure TEXT NOT NULL,
+    unit_price_cents INTEGER NOT NULL,
+    discount_percent REAL DEFAULT 0,
+    discount_cents INTEGER DEFAULT 0,
+    tax_percent REAL DEFAULT 0,
+    tax_cents INTEGER DEFAULT 0,
+    line_total_cents INTEGER NOT NULL,
+    notes TEXT,
+    erp_line_id TEXT
+);
+CREATE TABLE IF NOT EXISTS portal_invoices (
+    id TEXT PRIMARY KEY,
+    portal_user_id TEXT NOT NULL,
+    erp_invoice_id TEXT NOT NULL,
+    erp_invoice_number TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    invoice_date TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    status TEXT DEFAULT 'Sent',
+    subtotal_cents INTEGER DEFAULT 0,
+    tax_cents INTEGER DEFAULT 0,
+    total_cents INTEGER DEFAULT 0,
+    amount_paid_cents INTEGER DEFAULT 0,
+    amount_due_cents INTEGER DEFAULT 0,
+    currency TEXT DEFAULT 'USD',
+    payment_url TEXT,
+    pdf_url TEXT,
+    payments TEXT,
+    viewed_at TEXT,
+    disputed INTEGER DEFAULT 0,
+    dispute_reason TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS portal_payments (
+    id TEXT PRIMARY KEY,
+    portal_user_id TEXT NOT NULL,
+    payment_reference TEXT NOT NULL UNIQUE,
+    erp_payment_id TEXT,
+    customer_id TEXT NOT NULL,
+    invoice_ids TEXT NOT NULL,
+    payment_method TEXT NOT NULL,
+    amount_cents INTEGER NOT NULL,
+    currency TEXT DEFAULT 'USD',
+    status TEXT DEFAULT 'Pending',
+    payment_provider TEXT,
+    provider_transaction_id TEXT,
+    provider_response TEXT,
+    card_last_four TEXT,
+    card_brand TEXT,
+    bank_name TEXT,
+    check_number TEXT,
+    processed_at TEXT,
+    failed_at TEXT,
+    failure_reason TEXT,
+    refunded_at TEXT,
+    refund_amount_cents INTEGER,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS supplier_quote_submissions (
+    id TEXT PRIMARY KEY,
+    portal_user_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    rfq_id TEXT NOT NULL,
+    quote_number TEXT NOT NULL UNIQUE,
+    erp_quote_id TEXT,
+    status TEXT DEFAULT 'Draft',
+    valid_until TEXT,
+    delivery_lead_time_days INTEGER DEFAULT 14,
+    payment_terms TEXT,
+    incoterms TEXT,
+    notes TEXT,
+    internal_notes TEXT,
+    subtotal_cents INTEGER DEFAULT 0,
+    tax_cents INTEGER DEFAULT 0,
+    total_cents INTEGER DEFAULT 0,
+    currency TEXT DEFAULT 'USD',
+    submitted_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS supplier_quote_lines (
+    id TEXT PRIMARY KEY,
+    submission_id TEXT NOT NULL,
+    rfq_line_id TEXT NOT NULL,
+    product_id TEXT,
+    product_code TEXT NOT NULL,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_of_measure TEXT NOT NULL,
+    unit_price_cents INTEGER NOT NULL,
+    discount_percent REAL DEFAULT 0,
+    tax_percent REAL DEFAULT 0,
+    line_total_cents INTEGER NOT NULL,
+    lead_time_days INTEGER DEFAULT 14,
+    minimum_order_quantity INTEGER DEFAULT 1,
+    notes TEXT
+);
+CREATE TABLE IF NOT EXISTS portal_notifications (
+    id TEXT PRIMARY KEY,
+    portal_user_id TEXT NOT NULL,
+    notification_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    entity_type TEXT,
+    entity_id TEXT,
+    action_url TEXT,
+    priority INTEGER DEFAULT 5,
+    read_at TEXT,
+    emailed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS portal_documents (
+    id TEXT PRIMARY KEY,
+    portal_user_id TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    document_number TEXT NOT NULL,
+    erp_entity_type TEXT NOT NULL,
+    erp_entity_id TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_size_bytes INTEGER NOT NULL,
+    mime_type TEXT NOT NULL,
+    download_count INTEGER DEFAULT 0,
+    last_downloaded_at TEXT,
+    expires_at TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS portal_activity_logs (
+    id TEXT PRIMARY KEY,
+    portal_user_id TEXT NOT NULL,
+    session_id TEXT,
+    activity_type TEXT NOT NULL,
+    entity_type TEXT,
+    entity_id TEXT,
+    description TEXT NOT NULL,
+    ip_address TEXT,
+    user_agent TEXT,
+    request_path TEXT,
+    request_method TEXT,
+    response_status INTEGER,
+    duration_ms INTEGER,
+    additional_data TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS iot_devices (
+    id TEXT PRIMARY KEY,
+    device_id TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    device_type TEXT NOT NULL,
+    manufacturer TEXT,
+    model TEXT,
+    serial_number TEXT,
+    firmware_version TEXT,
+    hardware_version TEXT,
+    status TEXT DEFAULT 'Provisioning',
+    connectivity_type TEXT NOT NULL,
+    ip_address TEXT,
+    mac_address TEXT,
+    port INTEGER,
+    protocol_config TEXT,
+    gateway_id TEXT,
+    location_id TEXT,
+    warehouse_id TEXT,
+    zone TEXT,
+    latitude REAL,
+    longitude REAL,
+    geofence_enabled INTEGER DEFAULT 0,
+    geofence_radius_meters INTEGER,
+    last_seen_at TEXT,
+    last_heartbeat_at TEXT,
+    heartbeat_interval_seconds INTEGER DEFAULT 60,
+    battery_level INTEGER,
+    signal_strength INTEGER,
+    temperature_celsius REAL,
+    humidity_percent REAL,
+    data_format TEXT,
+    data_schema TEXT,
+    transforms TEXT,
+    alert_rules TEXT,
+    metadata TEXT,
+    tags TEXT,
+    owner_id TEXT,
+    installed_at TEXT,
+    maintenance_due_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS iot_device_groups (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    group_type TEXT NOT NULL,
+    parent_id TEXT,
+    device_count INTEGER DEFAULT 0,
+    alert_rules TEXT,
+    metadata TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS device_group_memberships (
+    id TEXT PRIMARY KEY,
+    group_id TEXT NOT NULL,
+    device_id TEXT NOT NULL,
+    added_at TEXT NOT NULL,
+    added_by TEXT,
+    UNIQUE(group_id, device_id)
+);
+CREATE TABLE IF NOT EXISTS telemetry_data (
+    id TEXT PRIMARY KEY,
+    device_id TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    metric_name TEXT NOT NULL,
+    metric_type TEXT NOT NULL,
+    value_numeric REAL,
+    value_string TEXT,
+    value_boolean INTEGER,
+    unit TEXT,
+    quality TEXT DEFAULT 'Good',
+    raw_value TEXT,
+    transformation_applied TEXT,
+    metadata TEXT,
+    received_at TEXT NOT NULL,
+    processed_at TEXT
+);
+CREATE TABLE IF NOT EXISTS telemetry_aggregates (
+    id TEXT PRIMARY KEY,
+    device_id TEXT NOT NULL,
+    metric_name TEXT NOT NULL,
+    aggregation_type TEXT NOT NULL,
+    period TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    value_min REAL,
+    value_max REAL,
+    value_avg REAL,
+    value_sum REAL,
+    value_count INTEGER DEFAULT 0,
+    value_stddev REAL,
+    quality_score REAL DEFAULT 1.0,
+    computed_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS iot_alerts (
+    id TEXT PRIMARY KEY,
+    device_id TEXT NOT NULL,
+    alert_rule_id TEXT,
+    alert_type TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    metric_name TEXT,
+    threshold_value REAL,
+    actual_value REAL,
+    trigger_condition TEXT,
+    context_data TEXT,
+    status TEXT DEFAULT 'New',
+    triggered_at TEXT NOT NULL,
+    acknowledged_at TEXT,
+    acknowledged_by TEXT,
+    resolved_at TEXT,
+    resolution_notes TEXT,
+    auto_resolved INTEGER DEFAULT 0,
+    notification_sent INTEGER DEFAULT 0,
+    escalation_level INTEGER DEFAULT 0,
+    escalated_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS iot_alert_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    device_type TEXT,
+    device_group_id TEXT,
+    metric_name TEXT NOT NULL,
+    condition_type TEXT NOT NULL,
+    operator TEXT NOT NULL,
+    threshold_value REAL NOT NULL,
+    threshold_value_secondary REAL,
+    duration_seconds INTEGER DEFAULT 0,
+    aggregation_window_seconds INTEGER DEFAULT 60,
+    aggregation_type TEXT,
+    severity TEXT NOT NULL,
+    cooldown_seconds INTEGER DEFAULT 300,
+    auto_resolve INTEGER DEFAULT 0,
+    auto_resolve_after_seconds INTEGER,
+    notification_channels TEXT,
+    escalation_config TEXT,
+    suppress_duplicates INTEGER DEFAULT 1,
+    suppression_window_seconds INTEGER DEFAULT 300,
+    status TEXT DEFAULT 'Active',
+    last_triggered_at TEXT,
+    trigger_count INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS device_commands (
+    id TEXT PRIMARY KEY,
+    device_id TEXT NOT NULL,
+    command_type TEXT NOT NULL,
+    command_payload TEXT,
+    priority INTEGER DEFAULT 5,
+    status TEXT DEFAULT 'Pending',
+    retry_count INTEGER DEFAULT 0,
+    max_retries INTEGER DEFAULT 3,
+    timeout_seconds INTEGER DEFAULT 30,
+    response_payload TEXT,
+    error_message TEXT,
+    sent_at TEXT,
+    acknowledged_at TEXT,
+    completed_at TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS firmware_versions (
+    id TEXT PRIMARY KEY,
+    device_type TEXT NOT NULL,
+    manufacturer TEXT,
+    model TEXT,
+    version TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_size_bytes INTEGER NOT NULL,
+    checksum TEXT NOT NULL,
+    checksum_type TEXT DEFAULT 'SHA256',
+    release_notes TEXT,
+    is_critical INTEGER DEFAULT 0,
+    rollout_strategy TEXT DEFAULT 'Manual',
+    rollout_percentage INTEGER DEFAULT 100,
+    target_device_group_id TEXT,
+    status TEXT DEFAULT 'Draft',
+    scheduled_at TEXT,
+    released_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(device_type, manufacturer, model, version)
+);
+CREATE TABLE IF NOT EXISTS firmware_update_jobs (
+    id TEXT PRIMARY KEY,
+    firmware_id TEXT NOT NULL,
+    device_id TEXT NOT NULL,
+    status TEXT DEFAULT 'Pending',
+    previous_version TEXT,
+    target_version TEXT NOT NULL,
+    progress_percent INTEGER DEFAULT 0,
+    started_at TEXT,
+    completed_at TEXT,
+    error_message TEXT,
+    retry_count INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS iot_data_exports (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    export_type TEXT NOT NULL,
+    device_ids TEXT,
+    device_group_ids TEXT,
+    metric_names TEXT,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    format TEXT DEFAULT 'CSV',
+    compression TEXT DEFAULT 'Gzip',
+    file_path TEXT,
+    file_size_bytes INTEGER,
+    row_count INTEGER,
+    status TEXT DEFAULT 'Pending',
+    error_message TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    expires_at TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS digital_twins (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    twin_type TEXT NOT NULL,
+    physical_device_id TEXT,
+    model_reference TEXT,
+    properties TEXT,
+    relationships TEXT,
+    simulation_config TEXT,
+    last_synced_at TEXT,
+    sync_status TEXT DEFAULT 'Error',
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS automation_workflows (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    category TEXT NOT NULL,
+    automation_type TEXT NOT NULL,
+    trigger_config TEXT NOT NULL,
+    conditions TEXT,
+    actions TEXT NOT NULL,
+    error_handling TEXT,
+    retry_policy TEXT,
+    timeout_seconds INTEGER DEFAULT 3600,
+    max_concurrent_runs INTEGER DEFAULT 10,
+    priority INTEGER DEFAULT 5,
+    status TEXT DEFAULT 'Draft',
+    version INTEGER DEFAULT 1,
+    last_modified_by TEXT,
+    last_modified_at TEXT NOT NULL,
+    published_at TEXT,
+    published_by TEXT,
+    schedule_cron TEXT,
+    schedule_timezone TEXT DEFAULT 'UTC',
+    next_run_at TEXT,
+    last_run_at TEXT,
+    last_run_status TEXT,
+    total_runs INTEGER DEFAULT 0,
+    successful_runs INTEGER DEFAULT 0,
+    failed_runs INTEGER DEFAULT 0,
+    avg_duration_ms INTEGER,
+    tags TEXT,
+    owner_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS workflow_steps (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    step_number INTEGER NOT NULL,
+    step_name TEXT NOT NULL,
+    step_type TEXT NOT NULL,
+    config TEXT NOT NULL,
+    input_mapping TEXT,
+    output_mapping TEXT,
+    condition TEXT,
+    error_handler TEXT,
+    retry_count INTEGER DEFAULT 0,
+    timeout_seconds INTEGER DEFAULT 300,
+    on_success TEXT,
+    on_failure TEXT,
+    parallel_group INTEGER,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS workflow_executions (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    execution_number TEXT NOT NULL UNIQUE,
+    trigger_type TEXT NOT NULL,
+    trigger_data TEXT,
+    input_data TEXT,
+    output_data TEXT,
+    status TEXT DEFAULT 'Pending',
+    current_step INTEGER,
+    total_steps INTEGER DEFAULT 0,
+    completed_steps INTEGER DEFAULT 0,
+    progress_percent INTEGER DEFAULT 0,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    duration_ms INTEGER,
+    error_step INTEGER,
+    error_message TEXT,
+    error_stack TEXT,
+    retry_count INTEGER DEFAULT 0,
+    parent_execution_id TEXT,
+    correlation_id TEXT,
+    variables TEXT,
+    checkpoint_data TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS step_executions (
+    id TEXT PRIMARY KEY,
+    execution_id TEXT NOT NULL,
+    step_id TEXT NOT NULL,
+    step_number INTEGER NOT NULL,
+    status TEXT DEFAULT 'Pending',
+    input_data TEXT,
+    output_data TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    duration_ms INTEGER,
+    error_message TEXT,
+    retry_count INTEGER DEFAULT 0,
+    attempts INTEGER DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS automation_triggers (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    trigger_type TEXT NOT NULL,
+    entity_type TEXT,
+    event_name TEXT,
+    condition TEXT,
+    workflow_id TEXT NOT NULL,
+    is_active INTEGER DEFAULT 1,
+    priority INTEGER DEFAULT 5,
+    cooldown_seconds INTEGER DEFAULT 0,
+    last_triggered_at TEXT,
+    trigger_count INTEGER DEFAULT 0,
+    config TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS webhook_endpoints (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    endpoint_path TEXT NOT NULL UNIQUE,
+    workflow_id TEXT NOT NULL,
+    authentication_type TEXT DEFAULT 'None',
+    authentication_config TEXT,
+    allowed_ips TEXT,
+    rate_limit_per_minute INTEGER DEFAULT 60,
+    timeout_seconds INTEGER DEFAULT 30,
+    retry_policy TEXT,
+    is_active INTEGER DEFAULT 1,
+    verify_ssl INTEGER DEFAULT 1,
+    secret_key TEXT,
+    total_requests INTEGER DEFAULT 0,
+    successful_requests INTEGER DEFAULT 0,
+    failed_requests INTEGER DEFAULT 0,
+    last_request_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS webhook_requests (
+    id TEXT PRIMARY KEY,
+    endpoint_id TEXT NOT NULL,
+    request_id TEXT NOT NULL UNIQUE,
+    method TEXT NOT NULL,
+    headers TEXT,
+    query_params TEXT,
+    body TEXT,
+    content_type TEXT,
+    source_ip TEXT,
+    user_agent TEXT,
+    execution_id TEXT,
+    response_status INTEGER,
+    response_body TEXT,
+    processing_time_ms INTEGER,
+    error_message TEXT,
+    received_at TEXT NOT NULL,
+    processed_at TEXT
+);
+CREATE TABLE IF NOT EXISTS action_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    category TEXT NOT NULL,
+    description TEXT,
+    action_type TEXT NOT NULL,
+    icon TEXT,
+    input_schema TEXT,
+    output_schema TEXT,
+    config_schema TEXT,
+    default_config TEXT,
+    documentation_url TEXT,
+    is_builtin INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS automation_queues (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    max_workers INTEGER DEFAULT 10,
+    current_workers INTEGER DEFAULT 0,
+    pending_count INTEGER DEFAULT 0,
+    processing_count INTEGER DEFAULT 0,
+    completed_count INTEGER DEFAULT 0,
+    failed_count INTEGER DEFAULT 0,
+    avg_wait_time_ms INTEGER,
+    avg_process_time_ms INTEGER,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS queue_items (
+    id TEXT PRIMARY KEY,
+    queue_id TEXT NOT NULL,
+    execution_id TEXT NOT NULL,
+    priority INTEGER DEFAULT 5,
+    status TEXT DEFAULT 'Pending',
+    enqueued_at TEXT NOT NULL,
+    started_at TEXT,
+    completed_at TEXT,
+    wait_time_ms INTEGER,
+    process_time_ms INTEGER,
+    retry_count INTEGER DEFAULT 0,
+    max_retries INTEGER DEFAULT 3,
+    error_message TEXT,
+    worker_id TEXT
+);
+CREATE TABLE IF NOT EXISTS automation_variables (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    variable_type TEXT DEFAULT 'String',
+    scope TEXT DEFAULT 'Global',
+    value TEXT,
+    default_value TEXT,
+    description TEXT,
+    is_encrypted INTEGER DEFAULT 0,
+    is_required INTEGER DEFAULT 0,
+    validation_regex TEXT,
+    workflow_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS automation_metrics (
+    id TEXT PRIMARY KEY,
+    metric_name TEXT NOT NULL,
+    metric_type TEXT NOT NULL,
+    workflow_id TEXT,
+    value REAL NOT NULL,
+    unit TEXT,
+    tags TEXT,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    sample_count INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS rpa_recorders (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    script_type TEXT NOT NULL,
+    script_content TEXT NOT NULL,
+    selector_strategy TEXT,
+    variables TEXT,
+    recorded_at TEXT NOT NULL,
+    recorded_by TEXT,
+    status TEXT DEFAULT 'Active'
+);
+CREATE TABLE IF NOT EXISTS rpa_executions (
+    id TEXT PRIMARY KEY,
+    recorder_id TEXT NOT NULL,
+    workflow_execution_id TEXT,
+    status TEXT DEFAULT 'Pending',
+    screenshots TEXT,
+    logs TEXT,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    duration_ms INTEGER,
+    error_message TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_ai_models_type ON ai_models(model_type);
+CREATE INDEX IF NOT EXISTS idx_ai_models_status ON ai_models(status);
+CREATE INDEX IF NOT EXISTS idx_prediction_requests_model ON prediction_requests(model_id);
+CREATE INDEX IF NOT EXISTS idx_prediction_requests_status ON prediction_requests(status);
+CREATE INDEX IF NOT EXISTS idx_anomaly_detections_entity ON anomaly_detections(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_anomaly_detections_status ON anomaly_detections(status);
+CREATE INDEX IF NOT EXISTS idx_portal_users_email ON portal_users(email);
+CREATE INDEX IF NOT EXISTS idx_portal_users_portal_type ON portal_users(portal_type);
+CREATE INDEX IF NOT EXISTS idx_portal_sessions_token ON portal_sessions(session_token);
+CREATE INDEX IF NOT EXISTS idx_portal_orders_user ON portal_orders(portal_user_id);
+CREATE INDEX IF NOT EXISTS idx_portal_invoices_customer ON portal_invoices(customer_id);
+CREATE INDEX IF NOT EXISTS idx_iot_devices_device_id ON iot_devices(device_id);
+CREATE INDEX IF NOT EXISTS idx_iot_devices_status ON iot_devices(status);
+CREATE INDEX IF NOT EXISTS idx_telemetry_device_time ON telemetry_data(device_id, timestamp);
+CREATE INDEX IF NOT EXISTS idx_iot_alerts_device ON iot_alerts(device_id);
+CREATE INDEX IF NOT EXISTS idx_iot_alerts_status ON iot_alerts(status);
+CREATE INDEX IF NOT EXISTS idx_workflows_status ON automation_workflows(status);
+CREATE INDEX IF NOT EXISTS idx_workflow_executions_workflow ON workflow_executions(workflow_id);
+CREATE INDEX IF NOT EXISTS idx_workflow_executions_status ON workflow_executions(status);
+CREATE INDEX IF NOT EXISTS idx_webhook_endpoints_path ON webhook_endpoints(endpoint_path);
+CREATE TABLE IF NOT EXISTS capas (
+    id TEXT PRIMARY KEY,
+    capa_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    source TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    status TEXT NOT NULL,
+    current_phase TEXT NOT NULL,
+    product_id TEXT,
+    process_id TEXT,
+    supplier_id TEXT,
+    customer_id TEXT,
+    department_id TEXT,
+    detected_date TEXT NOT NULL,
+    detected_by TEXT,
+    assigned_to TEXT,
+    assigned_date TEXT,
+    target_completion_date TEXT,
+    actual_completion_date TEXT,
+    effectiveness_review_date TEXT,
+    is_effective INTEGER,
+    closure_date TEXT,
+    closed_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS capa_investigations (
+    id TEXT PRIMARY KEY,
+    capa_id TEXT NOT NULL,
+    investigation_date TEXT NOT NULL,
+    investigator_id TEXT,
+    what_happened TEXT NOT NULL,
+    when_it_happened TEXT,
+    where_it_happened TEXT,
+    who_was_involved TEXT,
+    immediate_action_taken TEXT,
+    evidence_collected TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (capa_id) REFERENCES capas(id)
+);
+CREATE TABLE IF NOT EXISTS root_cause_analyses (
+    id TEXT PRIMARY KEY,
+    capa_id TEXT NOT NULL,
+    analysis_method TEXT NOT NULL,
+    root_cause_category TEXT NOT NULL,
+    root_cause_description TEXT NOT NULL,
+    contributing_factors TEXT,
+    is_primary INTEGER NOT NULL,
+    verified INTEGER NOT NULL,
+    verified_by TEXT,
+    verified_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (capa_id) REFERENCES capas(id)
+);
+CREATE TABLE IF NOT EXISTS capa_actions (
+    id TEXT PRIMARY KEY,
+    capa_id TEXT NOT NULL,
+    action_type TEXT NOT NULL,
+    action_number INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    root_cause_id TEXT,
+    responsible_person_id TEXT,
+    planned_date TEXT NOT NULL,
+    completed_date TEXT,
+    status TEXT NOT NULL,
+    verification_method TEXT,
+    verification_date TEXT,
+    verified_by TEXT,
+    effectiveness_notes TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (capa_id) REFERENCES capas(id)
+);
+CREATE TABLE IF NOT EXISTS audit_programs (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    audit_type TEXT NOT NULL,
+    standard_reference TEXT,
+    frequency_months INTEGER NOT NULL,
+    next_audit_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS audit_schedules (
+    id TEXT PRIMARY KEY,
+    audit_number TEXT NOT NULL UNIQUE,
+    program_id TEXT,
+    title TEXT NOT NULL,
+    audit_type TEXT NOT NULL,
+    scope TEXT NOT NULL,
+    objectives TEXT,
+    criteria TEXT,
+    planned_start_date TEXT NOT NULL,
+    planned_end_date TEXT NOT NULL,
+    actual_start_date TEXT,
+    actual_end_date TEXT,
+    lead_auditor_id TEXT,
+    status TEXT NOT NULL,
+    overall_rating TEXT,
+    summary TEXT,
+    conclusions TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (program_id) REFERENCES audit_programs(id)
+);
+CREATE TABLE IF NOT EXISTS calibration_equipment (
+    id TEXT PRIMARY KEY,
+    equipment_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    serial_number TEXT,
+    model TEXT,
+    manufacturer TEXT,
+    equipment_type TEXT NOT NULL,
+    location_id TEXT,
+    department_id TEXT,
+    responsible_person_id TEXT,
+    calibration_frequency_months INTEGER NOT NULL,
+    last_calibration_date TEXT,
+    next_calibration_date TEXT,
+    calibration_status TEXT NOT NULL,
+    accuracy_class TEXT,
+    measurement_range TEXT,
+    resolution TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS control_plans (
+    id TEXT PRIMARY KEY,
+    plan_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    product_id TEXT,
+    process_id TEXT,
+    revision TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    approval_status TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS document_controls (
+    id TEXT PRIMARY KEY,
+    document_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    category TEXT,
+    department_id TEXT,
+    owner_id TEXT,
+    current_revision TEXT NOT NULL,
+    effective_date TEXT,
+    review_frequency_months INTEGER NOT NULL,
+    next_review_date TEXT,
+    approval_workflow_id TEXT,
+    distribution_list TEXT,
+    retention_years INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS spc_charts (
+    id TEXT PRIMARY KEY,
+    spc_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    product_id TEXT,
+    process_id TEXT,
+    characteristic TEXT NOT NULL,
+    specification_min REAL NOT NULL,
+    specification_max REAL NOT NULL,
+    target_value REAL NOT NULL,
+    unit TEXT NOT NULL,
+    sample_size INTEGER NOT NULL,
+    sampling_frequency TEXT NOT NULL,
+    subgroup_size INTEGER NOT NULL,
+    control_chart_type TEXT NOT NULL,
+    ucl REAL NOT NULL,
+    lcl REAL NOT NULL,
+    center_line REAL NOT NULL,
+    rule_set TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fmea_analyses (
+    id TEXT PRIMARY KEY,
+    fmea_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    fmea_type TEXT NOT NULL,
+    product_id TEXT,
+    process_id TEXT,
+    prepared_by TEXT,
+    prepared_date TEXT NOT NULL,
+    revision TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cash_pools (
+    id TEXT PRIMARY KEY,
+    pool_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    pool_type TEXT NOT NULL,
+    header_account_id TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    target_balance INTEGER NOT NULL,
+    min_balance INTEGER NOT NULL,
+    max_balance INTEGER,
+    interest_allocation_method TEXT,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cash_positions (
+    id TEXT PRIMARY KEY,
+    position_date TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    opening_balance INTEGER NOT NULL,
+    receipts INTEGER NOT NULL,
+    disbursements INTEGER NOT NULL,
+    transfers_in INTEGER NOT NULL,
+    transfers_out INTEGER NOT NULL,
+    fx_gains INTEGER NOT NULL,
+    fx_losses INTEGER NOT NULL,
+    closing_balance INTEGER NOT NULL,
+    available_balance INTEGER NOT NULL,
+    invested_balance INTEGER NOT NULL,
+    borrowed_balance INTEGER NOT NULL,
+    net_position INTEGER NOT NULL,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cash_forecasts (
+    id TEXT PRIMARY KEY,
+    forecast_number TEXT NOT NULL UNIQUE,
+    forecast_date TEXT NOT NULL,
+    horizon_days INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    company_id TEXT,
+    scenario TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS investments (
+    id TEXT PRIMARY KEY,
+    investment_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    investment_type TEXT NOT NULL,
+    issuer TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    principal_amount INTEGER NOT NULL,
+    current_value INTEGER NOT NULL,
+    purchase_date TEXT NOT NULL,
+    maturity_date TEXT NOT NULL,
+    purchase_price INTEGER NOT NULL,
+    coupon_rate REAL,
+    yield_to_maturity REAL,
+    yield_current REAL,
+    credit_rating TEXT,
+    cusip TEXT,
+    isin TEXT,
+    counterparty_id TEXT,
+    custodian TEXT,
+    account_id TEXT,
+    accrued_interest INTEGER NOT NULL,
+    unrealized_gain_loss INTEGER NOT NULL,
+    realized_gain_loss INTEGER NOT NULL,
+    day_count_convention TEXT NOT NULL,
+    payment_frequency TEXT NOT NULL,
+    next_payment_date TEXT,
+    call_date TEXT,
+    call_price INTEGER,
+    put_date TEXT,
+    put_price INTEGER,
+    status TEXT NOT NULL,
+    policy_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS credit_facilities (
+    id TEXT PRIMARY KEY,
+    facility_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    facility_type TEXT NOT NULL,
+    lender_id TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    committed_amount INTEGER NOT NULL,
+    available_amount INTEGER NOT NULL,
+    drawn_amount INTEGER NOT NULL,
+    undrawn_amount INTEGER NOT NULL,
+    interest_rate_type TEXT NOT NULL,
+    base_rate TEXT,
+    margin_rate REAL NOT NULL,
+    all_in_rate REAL,
+    commitment_fee REAL NOT NULL,
+    facility_fee INTEGER NOT NULL,
+    utilization_fee REAL,
+    effective_date TEXT NOT NULL,
+    maturity_date TEXT NOT NULL,
+    renewal_date TEXT,
+    financial_covenants TEXT,
+    borrowing_base_formula TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS hedge_instruments (
+    id TEXT PRIMARY KEY,
+    hedge_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    hedge_type TEXT NOT NULL,
+    hedge_purpose TEXT NOT NULL,
+    underlying TEXT NOT NULL,
+    notional_amount INTEGER NOT NULL,
+    notional_currency TEXT NOT NULL,
+    counter_currency TEXT,
+    strike_price REAL,
+    forward_rate REAL,
+    spot_rate REAL,
+    premium INTEGER,
+    premium_currency TEXT,
+    trade_date TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    maturity_date TEXT NOT NULL,
+    settlement_date TEXT,
+    counterparty_id TEXT,
+    hedge_accounting INTEGER NOT NULL,
+    effectiveness_method TEXT,
+    effectiveness_status TEXT,
+    designation_document TEXT,
+    fair_value INTEGER,
+    unrealized_gain_loss INTEGER,
+    realized_gain_loss INTEGER,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS carbon_accounts (
+    id TEXT PRIMARY KEY,
+    account_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    emission_scope TEXT NOT NULL,
+    emission_category TEXT NOT NULL,
+    location_id TEXT,
+    department_id TEXT,
+    responsible_person_id TEXT,
+    reporting_standard TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS carbon_emissions (
+    id TEXT PRIMARY KEY,
+    emission_number TEXT NOT NULL UNIQUE,
+    account_id TEXT NOT NULL,
+    emission_date TEXT NOT NULL,
+    reporting_period TEXT NOT NULL,
+    activity_data REAL NOT NULL,
+    activity_unit TEXT NOT NULL,
+    emission_factor REAL NOT NULL,
+    emission_factor_unit TEXT NOT NULL,
+    emission_factor_source TEXT,
+    co2_equivalent REAL NOT NULL,
+    co2_equivalent_unit TEXT NOT NULL,
+    uncertainty_percent REAL,
+    data_quality_score INTEGER,
+    source_type TEXT,
+    source_id TEXT,
+    verification_status TEXT NOT NULL,
+    verified_by TEXT,
+    verified_at TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (account_id) REFERENCES carbon_accounts(id)
+);
+CREATE TABLE IF NOT EXISTS emission_factors (
+    id TEXT PRIMARY KEY,
+    factor_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    emission_category TEXT NOT NULL,
+    fuel_type TEXT,
+    region TEXT,
+    year INTEGER NOT NULL,
+    factor_value REAL NOT NULL,
+    factor_unit TEXT NOT NULL,
+    co2_factor REAL,
+    ch4_factor REAL,
+    n2o_factor REAL,
+    gwp_co2 REAL,
+    gwp_ch4 REAL,
+    gwp_n2o REAL,
+    source TEXT NOT NULL,
+    source_url TEXT,
+    uncertainty_percent REAL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS carbon_offsets (
+    id TEXT PRIMARY KEY,
+    offset_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    project_name TEXT NOT NULL,
+    project_type TEXT NOT NULL,
+    standard TEXT NOT NULL,
+    registry TEXT NOT NULL,
+    registry_id TEXT,
+    vintage_year INTEGER NOT NULL,
+    quantity_tonnes INTEGER NOT NULL,
+    remaining_tonnes INTEGER NOT NULL,
+    price_per_tonne INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    purchase_date TEXT NOT NULL,
+    retirement_date TEXT,
+    project_location TEXT,
+    co_benefits TEXT,
+    sdg_contributions TEXT,
+    verification_body TEXT,
+    verification_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS energy_consumption (
+    id TEXT PRIMARY KEY,
+    consumption_number TEXT NOT NULL UNIQUE,
+    facility_id TEXT,
+    consumption_date TEXT NOT NULL,
+    reporting_period TEXT NOT NULL,
+    energy_type TEXT NOT NULL,
+    consumption_value REAL NOT NULL,
+    consumption_unit TEXT NOT NULL,
+    supplier TEXT,
+    meter_id TEXT,
+    cost INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    renewable_percent REAL NOT NULL,
+    renewable_source TEXT,
+    location_based_emissions REAL,
+    market_based_emissions REAL,
+    data_source TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS water_consumption (
+    id TEXT PRIMARY KEY,
+    consumption_number TEXT NOT NULL UNIQUE,
+    facility_id TEXT,
+    consumption_date TEXT NOT NULL,
+    reporting_period TEXT NOT NULL,
+    water_source TEXT NOT NULL,
+    consumption_cubic_meters REAL NOT NULL,
+    withdrawal_cubic_meters REAL NOT NULL,
+    discharge_cubic_meters REAL NOT NULL,
+    recycled_cubic_meters REAL NOT NULL,
+    supplier TEXT,
+    meter_id TEXT,
+    cost INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    water_stress_area INTEGER NOT NULL,
+    quality_treatment_required TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS waste_records (
+    id TEXT PRIMARY KEY,
+    waste_number TEXT NOT NULL UNIQUE,
+    facility_id TEXT,
+    waste_date TEXT NOT NULL,
+    reporting_period TEXT NOT NULL,
+    waste_type TEXT NOT NULL,
+    waste_category TEXT NOT NULL,
+    quantity_kg REAL NOT NULL,
+    disposal_method TEXT NOT NULL,
+    contractor TEXT,
+    manifest_number TEXT,
+    cost INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    hazardous INTEGER NOT NULL,
+    recycled_kg REAL,
+    recovery_rate_percent REAL,
+    destination_facility TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS sustainability_targets (
+    id TEXT PRIMARY KEY,
+    target_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    target_type TEXT NOT NULL,
+    baseline_year INTEGER NOT NULL,
+    target_year INTEGER NOT NULL,
+    baseline_value REAL NOT NULL,
+    target_value REAL NOT NULL,
+    current_value REAL,
+    progress_percent REAL,
+    unit TEXT NOT NULL,
+    scope TEXT,
+    alignment TEXT,
+    science_based INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS esg_reports (
+    id TEXT PRIMARY KEY,
+    report_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    reporting_period TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    report_type TEXT NOT NULL,
+    framework TEXT NOT NULL,
+    scope_1_emissions REAL,
+    scope_2_emissions_location REAL,
+    scope_2_emissions_market REAL,
+    scope_3_emissions REAL,
+    total_energy_consumed REAL,
+    renewable_energy_percent REAL,
+    total_water_withdrawn REAL,
+    total_waste_generated REAL,
+    waste_diverted_percent REAL,
+    employee_count INTEGER,
+    employee_turnover_percent REAL,
+    diversity_data TEXT,
+    safety_incidents INTEGER,
+    training_hours REAL,
+    community_investment INTEGER,
+    currency TEXT NOT NULL,
+    assurance_status TEXT,
+    assurance_provider TEXT,
+    status TEXT NOT NULL,
+    published_at TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS sop_cycles (
+    id TEXT PRIMARY KEY,
+    cycle_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    fiscal_year INTEGER NOT NULL,
+    planning_horizon_months INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    current_status TEXT NOT NULL,
+    demand_review_date TEXT,
+    supply_review_date TEXT,
+    pre_sop_date TEXT,
+    executive_sop_date TEXT,
+    total_demand INTEGER NOT NULL,
+    total_supply INTEGER NOT NULL,
+    gap INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    owner_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS supply_plans (
+    id TEXT PRIMARY KEY,
+    plan_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    sop_cycle_id TEXT NOT NULL,
+    planning_horizon_months INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    total_production_capacity INTEGER NOT NULL,
+    total_external_supply INTEGER NOT NULL,
+    total_available INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_by TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (sop_cycle_id) REFERENCES sop_cycles(id)
+);
+CREATE TABLE IF NOT EXISTS drp_plans (
+    id TEXT PRIMARY KEY,
+    plan_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    planning_horizon_days INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS distribution_networks (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    source_warehouse_id TEXT NOT NULL,
+    destination_warehouse_id TEXT NOT NULL,
+    lead_time_days INTEGER NOT NULL,
+    transportation_mode TEXT NOT NULL,
+    shipping_cost_per_unit INTEGER NOT NULL,
+    min_order_quantity INTEGER NOT NULL,
+    max_order_quantity INTEGER,
+    lot_size_multiple INTEGER,
+    priority INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS planning_parameters (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    parameter_type TEXT NOT NULL,
+    value REAL NOT NULL,
+    unit TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    source TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS promotion_events (
+    id TEXT PRIMARY KEY,
+    event_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    event_type TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS corporate_cards (
+    id TEXT PRIMARY KEY,
+    card_number_masked TEXT NOT NULL,
+    cardholder_id TEXT NOT NULL,
+    card_type TEXT NOT NULL,
+    issuer TEXT NOT NULL,
+    credit_limit INTEGER NOT NULL,
+    current_balance INTEGER NOT NULL,
+    available_credit INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    issue_date TEXT NOT NULL,
+    expiry_date TEXT NOT NULL,
+    billing_day INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS card_transactions (
+    id TEXT PRIMARY KEY,
+    card_id TEXT NOT NULL,
+    transaction_date TEXT NOT NULL,
+    posting_date TEXT,
+    merchant_name TEXT NOT NULL,
+    merchant_category TEXT,
+    merchant_category_code TEXT,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    original_amount INTEGER,
+    original_currency TEXT,
+    expense_type TEXT,
+    expense_report_id TEXT,
+    receipt_attached INTEGER NOT NULL,
+    receipt_required INTEGER NOT NULL,
+    verified INTEGER NOT NULL,
+    verified_by TEXT,
+    verified_at TEXT,
+    status TEXT NOT NULL,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (card_id) REFERENCES corporate_cards(id)
+);
+CREATE TABLE IF NOT EXISTS per_diem_rates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    country_code TEXT NOT NULL,
+    city TEXT,
+    rate_type TEXT NOT NULL,
+    lodging_rate INTEGER NOT NULL,
+    meals_rate INTEGER NOT NULL,
+    incidentals_rate INTEGER NOT NULL,
+    total_rate INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mileage_rates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    vehicle_type TEXT NOT NULL,
+    rate_per_mile INTEGER NOT NULL,
+    rate_per_km INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS travel_requests (
+    id TEXT PRIMARY KEY,
+    request_number TEXT NOT NULL UNIQUE,
+    employee_id TEXT NOT NULL,
+    purpose TEXT NOT NULL,
+    destination_city TEXT NOT NULL,
+    destination_country TEXT NOT NULL,
+    departure_date TEXT NOT NULL,
+    return_date TEXT NOT NULL,
+    estimated_airfare INTEGER NOT NULL,
+    estimated_lodging INTEGER NOT NULL,
+    estimated_meals INTEGER NOT NULL,
+    estimated_transportation INTEGER NOT NULL,
+    estimated_other INTEGER NOT NULL,
+    total_estimated INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    advance_required INTEGER NOT NULL,
+    advance_amount INTEGER,
+    expense_report_id TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS expense_policies (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    expense_type TEXT NOT NULL,
+    daily_limit INTEGER,
+    per_transaction_limit INTEGER,
+    monthly_limit INTEGER,
+    annual_limit INTEGER,
+    requires_receipt_above INTEGER,
+    requires_preapproval_above INTEGER,
+    currency TEXT NOT NULL,
+    approval_workflow_id TEXT,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS learning_courses (
+    id TEXT PRIMARY KEY,
+    course_code TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    category_id TEXT,
+    instructor_id TEXT,
+    difficulty_level TEXT NOT NULL,
+    estimated_duration_minutes INTEGER NOT NULL,
+    language TEXT NOT NULL,
+    keywords TEXT,
+    prerequisites TEXT,
+    learning_objectives TEXT,
+    passing_score_percent INTEGER NOT NULL,
+    max_attempts INTEGER NOT NULL,
+    certificate_template_id TEXT,
+    certificate_validity_days INTEGER,
+    is_mandatory INTEGER NOT NULL,
+    is_featured INTEGER NOT NULL,
+    enrollment_type TEXT NOT NULL,
+    price INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    thumbnail_url TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS learning_paths (
+    id TEXT PRIMARY KEY,
+    path_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    category_id TEXT,
+    total_courses INTEGER NOT NULL,
+    estimated_duration_hours INTEGER NOT NULL,
+    is_mandatory INTEGER NOT NULL,
+    target_roles TEXT,
+    target_departments TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS course_enrollments (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    course_id TEXT NOT NULL,
+    learning_path_id TEXT,
+    enrolled_by TEXT,
+    enrolled_at TEXT NOT NULL,
+    started_at TEXT,
+    completed_at TEXT,
+    due_date TEXT,
+    progress_percent INTEGER NOT NULL,
+    time_spent_minutes INTEGER NOT NULL,
+    score REAL,
+    passed INTEGER,
+    attempts INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    certificate_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS assessments (
+    id TEXT PRIMARY KEY,
+    course_id TEXT,
+    assessment_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    instructions TEXT,
+    time_limit_minutes INTEGER,
+    passing_score_percent INTEGER NOT NULL,
+    max_attempts INTEGER NOT NULL,
+    shuffle_questions INTEGER NOT NULL,
+    shuffle_answers INTEGER NOT NULL,
+    show_correct_answers INTEGER NOT NULL,
+    show_score_immediately INTEGER NOT NULL,
+    randomize_questions INTEGER NOT NULL,
+    questions_per_attempt INTEGER,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS question_banks (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    category_id TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS questions (
+    id TEXT PRIMARY KEY,
+    question_bank_id TEXT,
+    assessment_id TEXT,
+    question_type TEXT NOT NULL,
+    question_text TEXT NOT NULL,
+    explanation TEXT,
+    hint TEXT,
+    points INTEGER NOT NULL,
+    difficulty TEXT NOT NULL,
+    media_url TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS certificates (
+    id TEXT PRIMARY KEY,
+    certificate_number TEXT NOT NULL UNIQUE,
+    employee_id TEXT NOT NULL,
+    course_id TEXT,
+    learning_path_id TEXT,
+    certificate_template_id TEXT NOT NULL,
+    issue_date TEXT NOT NULL,
+    expiry_date TEXT,
+    verification_code TEXT NOT NULL,
+    pdf_path TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS competencies (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    category_id TEXT,
+    proficiency_levels TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS risks (
+    id TEXT PRIMARY KEY,
+    risk_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    category TEXT NOT NULL,
+    subcategory TEXT,
+    owner_id TEXT,
+    department_id TEXT,
+    process_id TEXT,
+    identified_date TEXT NOT NULL,
+    likelihood INTEGER NOT NULL,
+    impact INTEGER NOT NULL,
+    inherent_risk_score INTEGER NOT NULL,
+    inherent_risk_level TEXT NOT NULL,
+    control_effectiveness INTEGER,
+    residual_likelihood INTEGER,
+    residual_impact INTEGER,
+    residual_risk_score INTEGER,
+    residual_risk_level TEXT,
+    target_risk_score INTEGER,
+    risk_response TEXT,
+    status TEXT NOT NULL,
+    review_frequency_days INTEGER NOT NULL,
+    last_review_date TEXT,
+    next_review_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS risk_assessments (
+    id TEXT PRIMARY KEY,
+    risk_id TEXT NOT NULL,
+    assessment_date TEXT NOT NULL,
+    assessor_id TEXT,
+    likelihood_before INTEGER NOT NULL,
+    impact_before INTEGER NOT NULL,
+    score_before INTEGER NOT NULL,
+    likelihood_after INTEGER,
+    impact_after INTEGER,
+    score_after INTEGER,
+    assessment_method TEXT NOT NULL,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (risk_id) REFERENCES risks(id)
+);
+CREATE TABLE IF NOT EXISTS controls (
+    id TEXT PRIMARY KEY,
+    control_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    control_type TEXT NOT NULL,
+    control_nature TEXT NOT NULL,
+    control_frequency TEXT NOT NULL,
+    control_owner_id TEXT,
+    department_id TEXT,
+    process_id TEXT,
+    framework_reference TEXT,
+    key_control INTEGER NOT NULL,
+    automated INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS risk_control_mappings (
+    id TEXT PRIMARY KEY,
+    risk_id TEXT NOT NULL,
+    control_id TEXT NOT NULL,
+    mapping_type TEXT NOT NULL,
+    effectiveness INTEGER,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (risk_id) REFERENCES risks(id),
+    FOREIGN KEY (control_id) REFERENCES controls(id)
+);
+CREATE TABLE IF NOT EXISTS control_tests (
+    id TEXT PRIMARY KEY,
+    test_number TEXT NOT NULL UNIQUE,
+    control_id TEXT NOT NULL,
+    test_date TEXT NOT NULL,
+    tester_id TEXT,
+    test_type TEXT NOT NULL,
+    sample_size INTEGER,
+    population_size INTEGER,
+    exceptions_found INTEGER NOT NULL,
+    test_result TEXT NOT NULL,
+    effectiveness_rating INTEGER,
+    findings TEXT,
+    remediation_required INTEGER NOT NULL,
+    remediation_due_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (control_id) REFERENCES controls(id)
+);
+CREATE TABLE IF NOT EXISTS policies (
+    id TEXT PRIMARY KEY,
+    policy_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    category TEXT NOT NULL,
+    owner_id TEXT,
+    approver_id TEXT,
+    effective_date TEXT NOT NULL,
+    review_frequency_months INTEGER NOT NULL,
+    next_review_date TEXT,
+    version TEXT NOT NULL,
+    document_path TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS policy_acknowledgments (
+    id TEXT PRIMARY KEY,
+    policy_id TEXT NOT NULL,
+    employee_id TEXT NOT NULL,
+    acknowledged_at TEXT NOT NULL,
+    version TEXT NOT NULL,
+    notes TEXT,
+    FOREIGN KEY (policy_id) REFERENCES policies(id)
+);
+CREATE TABLE IF NOT EXISTS compliance_requirements (
+    id TEXT PRIMARY KEY,
+    framework_id TEXT NOT NULL,
+    requirement_code TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    parent_id TEXT,
+    control_owner_id TEXT,
+    evidence_required INTEGER NOT NULL,
+    testing_required INTEGER NOT NULL,
+    frequency TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (framework_id) REFERENCES compliance_frameworks(id)
+);
+CREATE TABLE IF NOT EXISTS incidents (
+    id TEXT PRIMARY KEY,
+    incident_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    incident_type TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    reported_by TEXT,
+    reported_date TEXT NOT NULL,
+    occurred_date TEXT,
+    discovered_date TEXT,
+    location TEXT,
+    department_id TEXT,
+    affected_systems TEXT,
+    affected_data TEXT,
+    affected_parties TEXT,
+    root_cause TEXT,
+    immediate_actions TEXT,
+    assigned_to TEXT,
+    status TEXT NOT NULL,
+    resolved_date TEXT,
+    closure_notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS third_party_risks (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    risk_tier TEXT NOT NULL,
+    assessment_date TEXT,
+    next_assessment_date TEXT,
+    inherent_risk_score INTEGER,
+    residual_risk_score INTEGER,
+    data_access_level TEXT NOT NULL,
+    business_impact TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS kri_definitions (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    category TEXT NOT NULL,
+    measurement_unit TEXT NOT NULL,
+    calculation_method TEXT,
+    data_source TEXT,
+    frequency TEXT NOT NULL,
+    threshold_green REAL NOT NULL,
+    threshold_yellow REAL NOT NULL,
+    threshold_red REAL NOT NULL,
+    direction TEXT NOT NULL,
+    owner_id TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_capas_status ON capas(status);
+CREATE INDEX IF NOT EXISTS idx_capas_source ON capas(source);
+CREATE INDEX IF NOT EXISTS idx_carbon_emissions_date ON carbon_emissions(emission_date);
+CREATE INDEX IF NOT EXISTS idx_carbon_emissions_account ON carbon_emissions(account_id);
+CREATE INDEX IF NOT EXISTS idx_investments_status ON investments(status);
+CREATE INDEX IF NOT EXISTS idx_risks_status ON risks(status);
+CREATE INDEX IF NOT EXISTS idx_risks_category ON risks(category);
+CREATE INDEX IF NOT EXISTS idx_controls_owner ON controls(control_owner_id);
+CREATE INDEX IF NOT EXISTS idx_policies_status ON policies(status);
+CREATE INDEX IF NOT EXISTS idx_course_enrollments_employee ON course_enrollments(employee_id);
+CREATE INDEX IF NOT EXISTS idx_course_enrollments_status ON course_enrollments(status);
+CREATE INDEX IF NOT EXISTS idx_sop_cycles_status ON sop_cycles(current_status);
+CREATE INDEX IF NOT EXISTS idx_incidents_status ON incidents(status);
+CREATE INDEX IF NOT EXISTS idx_incidents_severity ON incidents(severity);
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    notification_type TEXT NOT NULL,
+    channel TEXT NOT NULL,
+    enabled INTEGER DEFAULT 1,
+    priority_threshold TEXT DEFAULT 'Normal',
+    quiet_hours_start TEXT,
+    quiet_hours_end TEXT,
+    quiet_hours_timezone TEXT,
+    digest_enabled INTEGER DEFAULT 0,
+    digest_frequency TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(user_id, notification_type, channel)
+);
+CREATE TABLE IF NOT EXISTS notification_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    notification_type TEXT NOT NULL,
+    channel TEXT NOT NULL,
+    subject_template TEXT,
+    body_template TEXT NOT NULL,
+    html_template TEXT,
+    variables TEXT,
+    default_priority TEXT DEFAULT 'Normal',
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS notification_batches (
+    id TEXT PRIMARY KEY,
+    batch_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    channel TEXT NOT NULL,
+    template_id TEXT,
+    total_recipients INTEGER DEFAULT 0,
+    sent_count INTEGER DEFAULT 0,
+    delivered_count INTEGER DEFAULT 0,
+    failed_count INTEGER DEFAULT 0,
+    opened_count INTEGER DEFAULT 0,
+    clicked_count INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Draft',
+    scheduled_at TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS notification_providers (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    channel TEXT NOT NULL,
+    provider_type TEXT NOT NULL,
+    configuration TEXT,
+    credentials_encrypted TEXT,
+    webhook_url TEXT,
+    is_default INTEGER DEFAULT 0,
+    daily_limit INTEGER,
+    daily_used INTEGER DEFAULT 0,
+    monthly_limit INTEGER,
+    monthly_used INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS device_tokens (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    device_type TEXT NOT NULL,
+    token TEXT NOT NULL,
+    device_name TEXT,
+    os_version TEXT,
+    app_version TEXT,
+    last_used_at TEXT,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id TEXT PRIMARY KEY,
+    endpoint_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    headers TEXT,
+    response_status INTEGER,
+    response_body TEXT,
+    response_headers TEXT,
+    duration_ms INTEGER,
+    attempt_number INTEGER DEFAULT 0,
+    max_attempts INTEGER DEFAULT 5,
+    next_retry_at TEXT,
+    delivered_at TEXT,
+    status TEXT DEFAULT 'Pending',
+    error_message TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS job_queues (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    max_concurrent_jobs INTEGER DEFAULT 10,
+    current_jobs INTEGER DEFAULT 0,
+    total_processed INTEGER DEFAULT 0,
+    total_failed INTEGER DEFAULT 0,
+    avg_wait_time_ms INTEGER,
+    avg_process_time_ms INTEGER,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS api_key_usage (
+    id TEXT PRIMARY KEY,
+    api_key_id TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    endpoint TEXT NOT NULL,
+    method TEXT NOT NULL,
+    status_code INTEGER NOT NULL,
+    response_time_ms INTEGER NOT NULL,
+    request_size INTEGER DEFAULT 0,
+    response_size INTEGER DEFAULT 0,
+    ip_address TEXT,
+    user_agent TEXT,
+    error_message TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS external_connections (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    connection_type TEXT NOT NULL,
+    description TEXT,
+    endpoint_url TEXT,
+    configuration TEXT,
+    credentials_encrypted TEXT,
+    auth_type TEXT DEFAULT 'None',
+    auth_config TEXT,
+    status TEXT DEFAULT 'Pending',
+    last_sync_at TEXT,
+    last_sync_status TEXT,
+    last_error TEXT,
+    sync_interval_minutes INTEGER,
+    auto_sync INTEGER DEFAULT 0,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS connection_sync_logs (
+    id TEXT PRIMARY KEY,
+    connection_id TEXT NOT NULL,
+    sync_type TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    status TEXT DEFAULT 'Running',
+    records_processed INTEGER DEFAULT 0,
+    records_created INTEGER DEFAULT 0,
+    records_updated INTEGER DEFAULT 0,
+    records_failed INTEGER DEFAULT 0,
+    error_message TEXT,
+    details TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS integration_flows (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    trigger_type TEXT NOT NULL,
+    trigger_config TEXT,
+    steps TEXT NOT NULL,
+    error_handling TEXT DEFAULT 'StopOnError',
+    retry_policy TEXT,
+    enabled INTEGER DEFAULT 1,
+    execution_count INTEGER DEFAULT 0,
+    success_count INTEGER DEFAULT 0,
+    failure_count INTEGER DEFAULT 0,
+    last_execution_at TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS flow_executions (
+    id TEXT PRIMARY KEY,
+    flow_id TEXT NOT NULL,
+    trigger_type TEXT NOT NULL,
+    trigger_data TEXT,
+    status TEXT DEFAULT 'Pending',
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    current_step INTEGER,
+    total_steps INTEGER DEFAULT 0,
+    step_results TEXT,
+    error_message TEXT,
+    error_step INTEGER,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    template_type TEXT NOT NULL,
+    format TEXT NOT NULL,
+    subject TEXT,
+    body TEXT NOT NULL,
+    html_body TEXT,
+    variables TEXT,
+    default_values TEXT,
+    styles TEXT,
+    header_template_id TEXT,
+    footer_template_id TEXT,
+    version INTEGER DEFAULT 1,
+    parent_id TEXT,
+    status TEXT DEFAULT 'Active',
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS template_versions (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    subject TEXT,
+    body TEXT NOT NULL,
+    html_body TEXT,
+    variables TEXT,
+    change_summary TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE(template_id, version)
+);
+CREATE TABLE IF NOT EXISTS template_variables (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    description TEXT,
+    variable_type TEXT DEFAULT 'String',
+    default_value TEXT,
+    required INTEGER DEFAULT 0,
+    validation_regex TEXT,
+    options TEXT,
+    sort_order INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    UNIQUE(template_id, name)
+);
+CREATE TABLE IF NOT EXISTS generated_documents (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    template_version INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    output_format TEXT NOT NULL,
+    content TEXT,
+    file_path TEXT,
+    file_size INTEGER,
+    variables_used TEXT,
+    related_entity_type TEXT,
+    related_entity_id TEXT,
+    generated_by TEXT NOT NULL,
+    generated_at TEXT NOT NULL,
+    expires_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS email_campaigns (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    template_id TEXT NOT NULL,
+    recipient_list_id TEXT,
+    recipient_query TEXT,
+    variables TEXT,
+    total_recipients INTEGER DEFAULT 0,
+    sent_count INTEGER DEFAULT 0,
+    delivered_count INTEGER DEFAULT 0,
+    opened_count INTEGER DEFAULT 0,
+    clicked_count INTEGER DEFAULT 0,
+    bounced_count INTEGER DEFAULT 0,
+    unsubscribed_count INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Draft',
+    scheduled_at TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS print_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    paper_size TEXT DEFAULT 'A4',
+    orientation TEXT DEFAULT 'Portrait',
+    margin_top_mm REAL DEFAULT 25.4,
+    margin_bottom_mm REAL DEFAULT 25.4,
+    margin_left_mm REAL DEFAULT 25.4,
+    margin_right_mm REAL DEFAULT 25.4,
+    header_template TEXT,
+    footer_template TEXT,
+    body_template TEXT NOT NULL,
+    css_styles TEXT,
+    variables TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS report_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    report_type TEXT NOT NULL,
+    data_source TEXT NOT NULL,
+    query TEXT,
+    parameters TEXT,
+    columns TEXT,
+    groupings TEXT,
+    filters TEXT,
+    sort_order TEXT,
+    chart_config TEXT,
+    template_content TEXT,
+    default_format TEXT DEFAULT 'PDF',
+    schedule_id TEXT,
+    status TEXT DEFAULT 'Active',
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_notifications_user_id ON notifications(user_id);
+CREATE INDEX IF NOT EXISTS idx_notifications_created_at ON notifications(created_at);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_status ON webhook_deliveries(status);
+CREATE INDEX IF NOT EXISTS idx_api_key_usage_key_id ON api_key_usage(api_key_id);
+CREATE INDEX IF NOT EXISTS idx_templates_type ON templates(template_type);
+CREATE INDEX IF NOT EXISTS idx_templates_code ON templates(code);
+CREATE TABLE IF NOT EXISTS document_folders (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    parent_id TEXT REFERENCES document_folders(id),
+    path TEXT NOT NULL,
+    description TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS documents (
+    id TEXT PRIMARY KEY,
+    document_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    document_type TEXT DEFAULT 'Other',
+    folder_id TEXT REFERENCES document_folders(id),
+    status TEXT DEFAULT 'Draft',
+    version INTEGER DEFAULT 1,
+    revision TEXT DEFAULT 'A',
+    access_level TEXT DEFAULT 'Internal',
+    file_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_size INTEGER NOT NULL,
+    mime_type TEXT NOT NULL,
+    checksum TEXT NOT NULL,
+    author_id TEXT,
+    owner_id TEXT,
+    checked_out_by TEXT,
+    checked_out_at TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    published_at TEXT,
+    expires_at TEXT,
+    tags TEXT,
+    metadata TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS document_versions (
+    id TEXT PRIMARY KEY,
+    document_id TEXT NOT NULL REFERENCES documents(id),
+    version INTEGER NOT NULL,
+    revision TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_size INTEGER NOT NULL,
+    checksum TEXT NOT NULL,
+    change_summary TEXT,
+    changed_by TEXT,
+    status TEXT DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS document_checkouts (
+    id TEXT PRIMARY KEY,
+    document_id TEXT NOT NULL REFERENCES documents(id),
+    user_id TEXT NOT NULL,
+    checkout_at TEXT NOT NULL,
+    expected_return TEXT,
+    checkin_at TEXT,
+    notes TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS document_reviews (
+    id TEXT PRIMARY KEY,
+    document_id TEXT NOT NULL REFERENCES documents(id),
+    version INTEGER NOT NULL,
+    reviewer_id TEXT NOT NULL,
+    requested_at TEXT NOT NULL,
+    reviewed_at TEXT,
+    status TEXT DEFAULT 'Pending',
+    comments TEXT,
+    approved INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS document_permissions (
+    id TEXT PRIMARY KEY,
+    document_id TEXT REFERENCES documents(id),
+    folder_id TEXT REFERENCES document_folders(id),
+    user_id TEXT,
+    role_id TEXT,
+    can_read INTEGER DEFAULT 1,
+    can_write INTEGER DEFAULT 0,
+    can_delete INTEGER DEFAULT 0,
+    can_share INTEGER DEFAULT 0,
+    can_approve INTEGER DEFAULT 0,
+    can_checkout INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS document_relations (
+    id TEXT PRIMARY KEY,
+    source_document_id TEXT NOT NULL REFERENCES documents(id),
+    target_document_id TEXT NOT NULL REFERENCES documents(id),
+    relation_type TEXT DEFAULT 'Related',
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS document_workflows (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    document_type TEXT,
+    steps TEXT NOT NULL,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS document_workflow_instances (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL REFERENCES document_workflows(id),
+    document_id TEXT NOT NULL REFERENCES documents(id),
+    current_step INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS retention_policies (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    document_types TEXT,
+    retention_years INTEGER NOT NULL DEFAULT 7,
+    review_after_years INTEGER,
+    disposition TEXT DEFAULT 'Destroy',
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS price_books (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    is_default INTEGER DEFAULT 0,
+    is_active INTEGER DEFAULT 1,
+    valid_from TEXT,
+    valid_to TEXT,
+    parent_id TEXT REFERENCES price_books(id),
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS price_book_entries (
+    id TEXT PRIMARY KEY,
+    price_book_id TEXT NOT NULL REFERENCES price_books(id),
+    product_id TEXT NOT NULL,
+    unit_price INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    min_quantity INTEGER DEFAULT 1,
+    max_quantity INTEGER,
+    valid_from TEXT,
+    valid_to TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT,
+    UNIQUE(price_book_id, product_id, min_quantity)
+);
+CREATE TABLE IF NOT EXISTS price_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    rule_type TEXT DEFAULT 'Discount',
+    scope TEXT DEFAULT 'Global',
+    priority INTEGER DEFAULT 100,
+    value REAL NOT NULL,
+    currency TEXT,
+    conditions TEXT,
+    valid_from TEXT,
+    valid_to TEXT,
+    is_active INTEGER DEFAULT 1,
+    is_stackable INTEGER DEFAULT 0,
+    max_applications INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS price_rule_assignments (
+    id TEXT PRIMARY KEY,
+    rule_id TEXT NOT NULL REFERENCES price_rules(id),
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS discounts (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    discount_type TEXT DEFAULT 'Percentage',
+    value REAL NOT NULL,
+    max_discount INTEGER,
+    min_order_value INTEGER,
+    applicable_to TEXT,
+    customer_groups TEXT,
+    products TEXT,
+    categories TEXT,
+    valid_from TEXT,
+    valid_to TEXT,
+    usage_limit INTEGER,
+    usage_per_customer INTEGER,
+    current_usage INTEGER DEFAULT 0,
+    is_active INTEGER DEFAULT 1,
+    requires_code INTEGER DEFAULT 1,
+    auto_apply INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS discount_usages (
+    id TEXT PRIMARY KEY,
+    discount_id TEXT NOT NULL REFERENCES discounts(id),
+    order_id TEXT NOT NULL,
+    customer_id TEXT,
+    discount_amount INTEGER NOT NULL,
+    applied_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS promotions (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    promotion_type TEXT DEFAULT 'ProductDiscount',
+    status TEXT DEFAULT 'Draft',
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    rules TEXT NOT NULL,
+    rewards TEXT NOT NULL,
+    target_segments TEXT,
+    channels TEXT,
+    budget INTEGER,
+    spent INTEGER DEFAULT 0,
+    usage_limit INTEGER,
+    current_usage INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS coupons (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    discount_id TEXT NOT NULL REFERENCES discounts(id),
+    promotion_id TEXT REFERENCES promotions(id),
+    customer_id TEXT,
+    is_used INTEGER DEFAULT 0,
+    used_at TEXT,
+    order_id TEXT,
+    valid_from TEXT,
+    valid_to TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS price_tiers (
+    id TEXT PRIMARY KEY,
+    price_book_entry_id TEXT REFERENCES price_book_entries(id),
+    product_id TEXT,
+    min_quantity INTEGER NOT NULL,
+    max_quantity INTEGER,
+    unit_price INTEGER NOT NULL,
+    discount_percent REAL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS customer_price_groups (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    price_book_id TEXT REFERENCES price_books(id),
+    discount_id TEXT REFERENCES discounts(id),
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS customer_price_group_members (
+    id TEXT PRIMARY KEY,
+    group_id TEXT NOT NULL REFERENCES customer_price_groups(id),
+    customer_id TEXT NOT NULL,
+    joined_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT,
+    UNIQUE(group_id, customer_id)
+);
+CREATE TABLE IF NOT EXISTS sourcing_events (
+    id TEXT PRIMARY KEY,
+    event_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    event_type TEXT DEFAULT 'RFQ',
+    status TEXT DEFAULT 'Draft',
+    auction_type TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    estimated_value INTEGER,
+    budget INTEGER,
+    requirements TEXT,
+    evaluation_criteria TEXT,
+    terms_conditions TEXT,
+    buyer_id TEXT,
+    category_id TEXT,
+    is_public INTEGER DEFAULT 1,
+    allow_reverse_auction INTEGER DEFAULT 0,
+    min_bid_decrement INTEGER,
+    auto_extend INTEGER DEFAULT 0,
+    extension_minutes INTEGER,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by_user TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_items (
+    id TEXT PRIMARY KEY,
+    event_id TEXT NOT NULL REFERENCES sourcing_events(id),
+    product_id TEXT,
+    sku TEXT,
+    name TEXT NOT NULL,
+    description TEXT,
+    quantity INTEGER NOT NULL,
+    unit_of_measure TEXT NOT NULL,
+    target_price INTEGER,
+    max_price INTEGER,
+    specifications TEXT,
+    delivery_date TEXT,
+    delivery_location TEXT,
+    sort_order INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_suppliers (
+    id TEXT PRIMARY KEY,
+    event_id TEXT NOT NULL REFERENCES sourcing_events(id),
+    vendor_id TEXT NOT NULL,
+    invited_at TEXT NOT NULL,
+    accepted_at TEXT,
+    declined_at TEXT,
+    status TEXT DEFAULT 'Active',
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_bids (
+    id TEXT PRIMARY KEY,
+    event_id TEXT NOT NULL REFERENCES sourcing_events(id),
+    vendor_id TEXT NOT NULL,
+    bid_number TEXT NOT NULL UNIQUE,
+    status TEXT DEFAULT 'Draft',
+    submitted_at TEXT,
+    valid_until TEXT,
+    total_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    terms TEXT,
+    notes TEXT,
+    rank INTEGER,
+    score REAL,
+    is_winner INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_bid_lines (
+    id TEXT PRIMARY KEY,
+    bid_id TEXT NOT NULL REFERENCES sourcing_bids(id),
+    item_id TEXT NOT NULL REFERENCES sourcing_items(id),
+    unit_price INTEGER NOT NULL,
+    quantity INTEGER NOT NULL,
+    total_price INTEGER NOT NULL,
+    delivery_date TEXT,
+    lead_time_days INTEGER,
+    specifications_met INTEGER DEFAULT 1,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_bid_attachments (
+    id TEXT PRIMARY KEY,
+    bid_id TEXT NOT NULL REFERENCES sourcing_bids(id),
+    file_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_size INTEGER NOT NULL,
+    mime_type TEXT NOT NULL,
+    uploaded_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_auction_rounds (
+    id TEXT PRIMARY KEY,
+    event_id TEXT NOT NULL REFERENCES sourcing_events(id),
+    round_number INTEGER NOT NULL,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    status TEXT DEFAULT 'Active',
+    min_bid INTEGER,
+    max_bid INTEGER,
+    bid_count INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_auction_bids (
+    id TEXT PRIMARY KEY,
+    round_id TEXT NOT NULL REFERENCES sourcing_auction_rounds(id),
+    vendor_id TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    bid_time TEXT NOT NULL,
+    rank INTEGER,
+    is_winning INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_evaluation_criteria (
+    id TEXT PRIMARY KEY,
+    event_id TEXT NOT NULL REFERENCES sourcing_events(id),
+    name TEXT NOT NULL,
+    description TEXT,
+    weight REAL NOT NULL DEFAULT 1.0,
+    max_score INTEGER NOT NULL DEFAULT 100,
+    evaluation_method TEXT DEFAULT 'Score',
+    sort_order INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_bid_evaluations (
+    id TEXT PRIMARY KEY,
+    bid_id TEXT NOT NULL REFERENCES sourcing_bids(id),
+    criteria_id TEXT NOT NULL REFERENCES sourcing_evaluation_criteria(id),
+    score INTEGER NOT NULL,
+    comments TEXT,
+    evaluated_by TEXT,
+    evaluated_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_awards (
+    id TEXT PRIMARY KEY,
+    event_id TEXT NOT NULL REFERENCES sourcing_events(id),
+    bid_id TEXT NOT NULL REFERENCES sourcing_bids(id),
+    vendor_id TEXT NOT NULL,
+    item_id TEXT REFERENCES sourcing_items(id),
+    awarded_quantity INTEGER NOT NULL,
+    awarded_price INTEGER NOT NULL,
+    total_value INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    awarded_at TEXT NOT NULL,
+    award_type TEXT DEFAULT 'Full',
+    purchase_order_id TEXT,
+    contract_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS sourcing_contracts (
+    id TEXT PRIMARY KEY,
+    event_id TEXT REFERENCES sourcing_events(id),
+    vendor_id TEXT NOT NULL,
+    contract_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    total_value INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    terms TEXT,
+    status TEXT DEFAULT 'Active',
+    renewal_type TEXT,
+    auto_renew INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS supplier_qualifications (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    qualification_type TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    issued_by TEXT,
+    issued_date TEXT,
+    expiry_date TEXT,
+    certificate_number TEXT,
+    document_path TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS system_configs (
+    id TEXT PRIMARY KEY,
+    category TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    value_type TEXT DEFAULT 'String',
+    description TEXT,
+    is_encrypted INTEGER DEFAULT 0,
+    is_system INTEGER DEFAULT 0,
+    is_public INTEGER DEFAULT 0,
+    default_value TEXT,
+    validation_regex TEXT,
+    group_name TEXT,
+    sort_order INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT,
+    UNIQUE(category, key)
+);
+CREATE TABLE IF NOT EXISTS config_history (
+    id TEXT PRIMARY KEY,
+    config_id TEXT NOT NULL REFERENCES system_configs(id),
+    old_value TEXT,
+    new_value TEXT NOT NULL,
+    changed_by TEXT,
+    changed_at TEXT NOT NULL,
+    reason TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS company_settings (
+    id TEXT PRIMARY KEY,
+    company_name TEXT NOT NULL,
+    legal_name TEXT,
+    tax_id TEXT,
+    registration_number TEXT,
+    logo_url TEXT,
+    favicon_url TEXT,
+    primary_color TEXT,
+    secondary_color TEXT,
+    timezone TEXT DEFAULT 'UTC',
+    date_format TEXT DEFAULT 'YYYY-MM-DD',
+    time_format TEXT DEFAULT 'HH:mm',
+    currency TEXT DEFAULT 'USD',
+    language TEXT DEFAULT 'en',
+    fiscal_year_start INTEGER DEFAULT 1,
+    week_start INTEGER DEFAULT 1,
+    address TEXT,
+    city TEXT,
+    state TEXT,
+    country TEXT,
+    postal_code TEXT,
+    phone TEXT,
+    email TEXT,
+    website TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS number_sequences (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    prefix TEXT,
+    suffix TEXT,
+    current_value INTEGER DEFAULT 0,
+    increment INTEGER DEFAULT 1,
+    padding INTEGER DEFAULT 4,
+    reset_period TEXT,
+    last_reset TEXT,
+    format TEXT,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS email_configs (
+    id TEXT PRIMARY KEY,
+    smtp_host TEXT NOT NULL,
+    smtp_port INTEGER NOT NULL DEFAULT 587,
+    smtp_user TEXT NOT NULL,
+    smtp_password TEXT,
+    use_tls INTEGER DEFAULT 1,
+    use_ssl INTEGER DEFAULT 0,
+    from_address TEXT NOT NULL,
+    from_name TEXT,
+    reply_to TEXT,
+    is_default INTEGER DEFAULT 1,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS storage_configs (
+    id TEXT PRIMARY KEY,
+    storage_type TEXT DEFAULT 'Local',
+    name TEXT NOT NULL,
+    endpoint TEXT,
+    bucket TEXT,
+    region TEXT,
+    access_key TEXT,
+    secret_key TEXT,
+    base_path TEXT,
+    max_file_size INTEGER DEFAULT 10485760,
+    allowed_types TEXT,
+    is_default INTEGER DEFAULT 1,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS payment_gateways (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    gateway_type TEXT NOT NULL,
+    api_key TEXT,
+    api_secret TEXT,
+    merchant_id TEXT,
+    endpoint_url TEXT,
+    webhook_url TEXT,
+    supported_currencies TEXT,
+    supported_methods TEXT,
+    fee_percent REAL,
+    fee_fixed INTEGER,
+    is_sandbox INTEGER DEFAULT 0,
+    is_default INTEGER DEFAULT 0,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+, webhook_secret TEXT, is_live INTEGER NOT NULL DEFAULT 0);
+CREATE TABLE IF NOT EXISTS shipping_providers (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    api_key TEXT,
+    api_secret TEXT,
+    account_number TEXT,
+    endpoint_url TEXT,
+    tracking_url TEXT,
+    supported_services TEXT,
+    supported_countries TEXT,
+    is_default INTEGER DEFAULT 0,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS localizations (
+    id TEXT PRIMARY KEY,
+    language_code TEXT NOT NULL,
+    locale TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    native_name TEXT NOT NULL,
+    date_format TEXT NOT NULL,
+    time_format TEXT NOT NULL,
+    number_format TEXT NOT NULL,
+    currency_symbol TEXT,
+    currency_position TEXT DEFAULT 'before',
+    decimal_separator TEXT DEFAULT '.',
+    thousand_separator TEXT DEFAULT ',',
+    is_rtl INTEGER DEFAULT 0,
+    is_default INTEGER DEFAULT 0,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS audit_settings (
+    id TEXT PRIMARY KEY,
+    log_retention_days INTEGER DEFAULT 365,
+    log_sensitive_data INTEGER DEFAULT 0,
+    log_login_attempts INTEGER DEFAULT 1,
+    log_data_changes INTEGER DEFAULT 1,
+    log_api_requests INTEGER DEFAULT 1,
+    alert_on_suspicious INTEGER DEFAULT 1,
+    max_login_attempts INTEGER DEFAULT 5,
+    lockout_duration_minutes INTEGER DEFAULT 30,
+    password_expiry_days INTEGER,
+    require_mfa INTEGER DEFAULT 0,
+    session_timeout_minutes INTEGER DEFAULT 60,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS integration_configs (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    integration_type TEXT NOT NULL,
+    api_endpoint TEXT,
+    api_key TEXT,
+    api_secret TEXT,
+    config_json TEXT,
+    sync_enabled INTEGER DEFAULT 0,
+    sync_frequency TEXT,
+    last_sync TEXT,
+    sync_status TEXT,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS business_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    rule_type TEXT DEFAULT 'Validation',
+    entity_type TEXT NOT NULL,
+    status TEXT DEFAULT 'Active',
+    priority INTEGER DEFAULT 100,
+    version INTEGER DEFAULT 1,
+    effective_from TEXT,
+    effective_to TEXT,
+    conditions TEXT NOT NULL,
+    actions TEXT NOT NULL,
+    else_actions TEXT,
+    tags TEXT,
+    owner_id TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS rule_conditions (
+    id TEXT PRIMARY KEY,
+    rule_id TEXT NOT NULL REFERENCES business_rules(id),
+    group_id TEXT REFERENCES rule_condition_groups(id),
+    field TEXT NOT NULL,
+    operator TEXT NOT NULL,
+    value TEXT NOT NULL,
+    value_type TEXT DEFAULT 'String',
+    logical_operator TEXT DEFAULT 'And',
+    sort_order INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS rule_condition_groups (
+    id TEXT PRIMARY KEY,
+    rule_id TEXT NOT NULL REFERENCES business_rules(id),
+    parent_group_id TEXT REFERENCES rule_condition_groups(id),
+    logical_operator TEXT DEFAULT 'And',
+    sort_order INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS rule_actions (
+    id TEXT PRIMARY KEY,
+    rule_id TEXT NOT NULL REFERENCES business_rules(id),
+    action_type TEXT NOT NULL,
+    target TEXT NOT NULL,
+    parameters TEXT,
+    execution_order INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS rule_sets (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    entity_type TEXT NOT NULL,
+    status TEXT DEFAULT 'Active',
+    version INTEGER DEFAULT 1,
+    effective_from TEXT,
+    effective_to TEXT,
+    execution_mode TEXT DEFAULT 'Sequential',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS rule_set_members (
+    id TEXT PRIMARY KEY,
+    ruleset_id TEXT NOT NULL REFERENCES rule_sets(id),
+    rule_id TEXT NOT NULL REFERENCES business_rules(id),
+    sort_order INTEGER DEFAULT 0,
+    is_required INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS rule_executions (
+    id TEXT PRIMARY KEY,
+    rule_id TEXT NOT NULL REFERENCES business_rules(id),
+    ruleset_id TEXT REFERENCES rule_sets(id),
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    triggered_at TEXT NOT NULL,
+    conditions_evaluated TEXT,
+    matched INTEGER DEFAULT 0,
+    actions_executed TEXT,
+    result TEXT,
+    error TEXT,
+    execution_time_ms INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS rule_variables (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    data_type TEXT NOT NULL DEFAULT 'String',
+    default_value TEXT,
+    source_type TEXT DEFAULT 'Static',
+    source_config TEXT,
+    is_constant INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS rule_functions (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    return_type TEXT NOT NULL DEFAULT 'String',
+    parameters TEXT,
+    function_body TEXT,
+    is_builtin INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS rule_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    rule_type TEXT DEFAULT 'Validation',
+    entity_type TEXT NOT NULL,
+    template TEXT NOT NULL,
+    variables TEXT,
+    is_builtin INTEGER DEFAULT 0,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS decision_tables (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    entity_type TEXT NOT NULL,
+    input_columns TEXT NOT NULL,
+    output_columns TEXT NOT NULL,
+    hit_policy TEXT DEFAULT 'First',
+    status TEXT DEFAULT 'Active',
+    version INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS decision_table_rows (
+    id TEXT PRIMARY KEY,
+    table_id TEXT NOT NULL REFERENCES decision_tables(id),
+    row_number INTEGER NOT NULL,
+    inputs TEXT NOT NULL,
+    outputs TEXT NOT NULL,
+    description TEXT,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS rule_versions (
+    id TEXT PRIMARY KEY,
+    rule_id TEXT NOT NULL REFERENCES business_rules(id),
+    version INTEGER NOT NULL,
+    conditions TEXT NOT NULL,
+    actions TEXT NOT NULL,
+    changed_by TEXT,
+    changed_at TEXT NOT NULL,
+    change_reason TEXT,
+    status TEXT DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_documents_folder ON documents(folder_id);
+CREATE INDEX IF NOT EXISTS idx_documents_status ON documents(status);
+CREATE INDEX IF NOT EXISTS idx_price_book_entries_product ON price_book_entries(product_id);
+CREATE INDEX IF NOT EXISTS idx_discounts_code ON discounts(code);
+CREATE INDEX IF NOT EXISTS idx_coupons_code ON coupons(code);
+CREATE INDEX IF NOT EXISTS idx_sourcing_events_status ON sourcing_events(status);
+CREATE INDEX IF NOT EXISTS idx_sourcing_bids_event ON sourcing_bids(event_id);
+CREATE INDEX IF NOT EXISTS idx_business_rules_entity ON business_rules(entity_type);
+CREATE INDEX IF NOT EXISTS idx_business_rules_status ON business_rules(status);
+CREATE INDEX IF NOT EXISTS idx_rule_executions_entity ON rule_executions(entity_type, entity_id);
+CREATE TABLE IF NOT EXISTS business_units (
+    id TEXT PRIMARY KEY,
+    company_id TEXT NOT NULL REFERENCES companies(id),
+    code TEXT NOT NULL,
+    name TEXT NOT NULL,
+    manager_id TEXT,
+    budget INTEGER,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    UNIQUE(company_id, code)
+);
+CREATE TABLE IF NOT EXISTS consolidations (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    total_eliminations INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    created_by TEXT
+);
+CREATE TABLE IF NOT EXISTS consolidation_entries (
+    id TEXT PRIMARY KEY,
+    consolidation_id TEXT NOT NULL REFERENCES consolidations(id),
+    company_id TEXT NOT NULL REFERENCES companies(id),
+    account_code TEXT NOT NULL,
+    debit INTEGER NOT NULL DEFAULT 0,
+    credit INTEGER NOT NULL DEFAULT 0,
+    elimination_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS subscription_items (
+    id TEXT PRIMARY KEY,
+    subscription_id TEXT NOT NULL REFERENCES subscriptions(id),
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    unit_price INTEGER NOT NULL,
+    discount_percent REAL NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS metered_prices (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL REFERENCES subscription_plans(id),
+    meter_type TEXT NOT NULL,
+    unit_price INTEGER NOT NULL,
+    included_units INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS carriers (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    carrier_type TEXT NOT NULL DEFAULT 'Other',
+    api_key TEXT,
+    api_secret TEXT,
+    account_number TEXT,
+    tracking_url_template TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS shipments (
+    id TEXT PRIMARY KEY,
+    shipment_number TEXT NOT NULL UNIQUE,
+    order_id TEXT,
+    carrier_id TEXT NOT NULL REFERENCES carriers(id),
+    carrier_service_id TEXT NOT NULL REFERENCES carrier_services(id),
+    status TEXT NOT NULL DEFAULT 'Pending',
+    ship_from_name TEXT NOT NULL,
+    ship_from_street TEXT NOT NULL,
+    ship_from_city TEXT NOT NULL,
+    ship_from_state TEXT,
+    ship_from_postal_code TEXT NOT NULL,
+    ship_from_country TEXT NOT NULL,
+    ship_to_name TEXT NOT NULL,
+    ship_to_street TEXT NOT NULL,
+    ship_to_city TEXT NOT NULL,
+    ship_to_state TEXT,
+    ship_to_postal_code TEXT NOT NULL,
+    ship_to_country TEXT NOT NULL,
+    ship_to_phone TEXT,
+    weight REAL NOT NULL DEFAULT 0,
+    weight_unit TEXT NOT NULL DEFAULT 'lb',
+    length REAL NOT NULL DEFAULT 0,
+    width REAL NOT NULL DEFAULT 0,
+    height REAL NOT NULL DEFAULT 0,
+    dimension_unit TEXT NOT NULL DEFAULT 'in',
+    shipping_cost INTEGER NOT NULL DEFAULT 0,
+    insurance_cost INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    tracking_number TEXT,
+    tracking_url TEXT,
+    label_url TEXT,
+    shipped_at TEXT,
+    estimated_delivery TEXT,
+    delivered_at TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tracking_events (
+    id TEXT PRIMARY KEY,
+    shipment_id TEXT NOT NULL REFERENCES shipments(id),
+    event_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    location TEXT,
+    occurred_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS shipping_zones (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    countries TEXT NOT NULL,
+    states TEXT,
+    postal_codes TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS shipping_rates (
+    id TEXT PRIMARY KEY,
+    zone_id TEXT NOT NULL REFERENCES shipping_zones(id),
+    carrier_service_id TEXT NOT NULL REFERENCES carrier_services(id),
+    min_weight REAL NOT NULL DEFAULT 0,
+    max_weight REAL NOT NULL DEFAULT 999999,
+    min_value INTEGER NOT NULL DEFAULT 0,
+    max_value INTEGER NOT NULL DEFAULT 999999999,
+    rate INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS payment_batches (
+    id TEXT PRIMARY KEY,
+    batch_number TEXT NOT NULL UNIQUE,
+    gateway_id TEXT NOT NULL REFERENCES payment_gateways(id),
+    total_amount INTEGER NOT NULL DEFAULT 0,
+    total_count INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Open',
+    settled_at TEXT,
+    settlement_reference TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mitigation_plans (
+    id TEXT PRIMARY KEY,
+    risk_id TEXT NOT NULL REFERENCES risks(id),
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    strategy TEXT NOT NULL,
+    owner_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Planned',
+    priority TEXT NOT NULL DEFAULT 'Medium',
+    start_date TEXT NOT NULL,
+    target_date TEXT NOT NULL,
+    completion_date TEXT,
+    budget INTEGER NOT NULL DEFAULT 0,
+    actual_cost INTEGER NOT NULL DEFAULT 0,
+    effectiveness REAL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mitigation_tasks (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL REFERENCES mitigation_plans(id),
+    title TEXT NOT NULL,
+    description TEXT,
+    assigned_to TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    due_date TEXT NOT NULL,
+    completed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS risk_controls (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    control_type TEXT NOT NULL DEFAULT 'Preventive',
+    frequency TEXT NOT NULL DEFAULT 'Continuous',
+    owner_id TEXT,
+    effectiveness TEXT NOT NULL DEFAULT 'Effective',
+    last_test_date TEXT,
+    next_test_date TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS risk_incidents (
+    id TEXT PRIMARY KEY,
+    risk_id TEXT NOT NULL REFERENCES risks(id),
+    incident_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    incident_date TEXT NOT NULL,
+    detected_date TEXT NOT NULL,
+    reported_by TEXT,
+    impact_amount INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Open',
+    root_cause TEXT,
+    lessons_learned TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS risk_registers (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    department TEXT,
+    owner_id TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS warehouse_locations (
+    id TEXT PRIMARY KEY,
+    zone_id TEXT NOT NULL REFERENCES warehouse_zones(id),
+    code TEXT NOT NULL,
+    name TEXT NOT NULL,
+    aisle TEXT,
+    rack TEXT,
+    shelf TEXT,
+    bin TEXT,
+    location_type TEXT NOT NULL DEFAULT 'Standard',
+    capacity INTEGER NOT NULL DEFAULT 0,
+    current_quantity INTEGER NOT NULL DEFAULT 0,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(zone_id, code)
+);
+CREATE TABLE IF NOT EXISTS stock_location_quantities (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    location_id TEXT NOT NULL REFERENCES warehouse_locations(id),
+    quantity INTEGER NOT NULL DEFAULT 0,
+    reserved_quantity INTEGER NOT NULL DEFAULT 0,
+    lot_number TEXT,
+    expiry_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(product_id, location_id, lot_number)
+);
+CREATE TABLE IF NOT EXISTS putaway_tasks (
+    id TEXT PRIMARY KEY,
+    task_number TEXT NOT NULL UNIQUE,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    source_location TEXT,
+    suggested_location TEXT,
+    actual_location TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    priority INTEGER NOT NULL DEFAULT 0,
+    assigned_to TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pick_tasks (
+    id TEXT PRIMARY KEY,
+    task_number TEXT NOT NULL UNIQUE,
+    order_id TEXT,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    location_id TEXT REFERENCES warehouse_locations(id),
+    status TEXT NOT NULL DEFAULT 'Pending',
+    priority INTEGER NOT NULL DEFAULT 0,
+    assigned_to TEXT,
+    picked_quantity INTEGER NOT NULL DEFAULT 0,
+    completed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS replenishment_tasks (
+    id TEXT PRIMARY KEY,
+    task_number TEXT NOT NULL UNIQUE,
+    product_id TEXT NOT NULL,
+    from_location TEXT NOT NULL,
+    to_location TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    priority INTEGER NOT NULL DEFAULT 0,
+    assigned_to TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_subscriptions_plan ON subscriptions(plan_id);
+CREATE INDEX IF NOT EXISTS idx_subscriptions_status ON subscriptions(status);
+CREATE INDEX IF NOT EXISTS idx_shipments_status ON shipments(status);
+CREATE INDEX IF NOT EXISTS idx_tracking_shipment ON tracking_events(shipment_id);
+CREATE INDEX IF NOT EXISTS idx_payments_customer ON payments(customer_id);
+CREATE INDEX IF NOT EXISTS idx_warehouse_zones_wh ON warehouse_zones(warehouse_id);
+CREATE INDEX IF NOT EXISTS idx_stock_loc_product ON stock_location_quantities(product_id);
+CREATE INDEX IF NOT EXISTS idx_pick_tasks_status ON pick_tasks(status);
+CREATE TABLE IF NOT EXISTS user_two_factor (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    backup_codes TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 0,
+    verified_at TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE(user_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS oauth_providers (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    display_name TEXT NOT NULL,
+    client_id TEXT NOT NULL,
+    client_secret TEXT NOT NULL,
+    authorize_url TEXT NOT NULL,
+    token_url TEXT NOT NULL,
+    userinfo_url TEXT NOT NULL,
+    scope TEXT NOT NULL DEFAULT 'openid email profile',
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS user_oauth_connections (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    provider_id TEXT NOT NULL,
+    provider_user_id TEXT NOT NULL,
+    access_token TEXT,
+    refresh_token TEXT,
+    token_expires_at TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE(provider_id, provider_user_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (provider_id) REFERENCES oauth_providers(id)
+);
+CREATE TABLE IF NOT EXISTS search_index (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT,
+    keywords TEXT,
+    tenant_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE VIRTUAL TABLE IF NOT EXISTS search_index_fts USING fts5(
+    title,
+    content,
+    keywords,
+    content='search_index',
+    content_rowid='rowid'
+);
+CREATE TABLE IF NOT EXISTS email_queue (
+    id TEXT PRIMARY KEY,
+    template_id TEXT,
+    to_address TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    body TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    sent_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (template_id) REFERENCES email_templates(id)
+);
+CREATE TABLE IF NOT EXISTS bulk_operations (
+    id TEXT PRIMARY KEY,
+    operation_type TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    total_count INTEGER NOT NULL,
+    processed_count INTEGER NOT NULL DEFAULT 0,
+    success_count INTEGER NOT NULL DEFAULT 0,
+    error_count INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'pending',
+    errors TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    completed_at TEXT,
+    FOREIGN KEY (created_by) REFERENCES users(id)
+);
+CREATE TABLE IF NOT EXISTS archived_records (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    original_data TEXT NOT NULL,
+    archived_at TEXT NOT NULL,
+    archived_by TEXT NOT NULL,
+    retention_until TEXT,
+    restored_at TEXT,
+    restored_by TEXT,
+    FOREIGN KEY (archived_by) REFERENCES users(id),
+    FOREIGN KEY (restored_by) REFERENCES users(id)
+);
+CREATE TABLE IF NOT EXISTS api_rate_limits (
+    id TEXT PRIMARY KEY,
+    identifier TEXT NOT NULL,
+    identifier_type TEXT NOT NULL,
+    endpoint_pattern TEXT,
+    requests_per_minute INTEGER NOT NULL,
+    requests_per_hour INTEGER NOT NULL,
+    requests_per_day INTEGER NOT NULL,
+    current_minute_count INTEGER NOT NULL DEFAULT 0,
+    current_hour_count INTEGER NOT NULL DEFAULT 0,
+    current_day_count INTEGER NOT NULL DEFAULT 0,
+    minute_window_start TEXT,
+    hour_window_start TEXT,
+    day_window_start TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_search_entity ON search_index(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_email_queue_status ON email_queue(status);
+CREATE INDEX IF NOT EXISTS idx_bulk_operations_status ON bulk_operations(status);
+CREATE INDEX IF NOT EXISTS idx_archived_records_type ON archived_records(entity_type);
+CREATE INDEX IF NOT EXISTS idx_user_sessions_user ON user_sessions(user_id);
+CREATE INDEX IF NOT EXISTS idx_rate_limits_identifier ON api_rate_limits(identifier, identifier_type);
+CREATE TABLE IF NOT EXISTS feature_flags (
+    id TEXT PRIMARY KEY,
+    key TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    enabled INTEGER NOT NULL DEFAULT 0,
+    rollout_percentage INTEGER NOT NULL DEFAULT 100,
+    target_type TEXT NOT NULL DEFAULT 'All',
+    target_ids TEXT,
+    start_time TEXT,
+    end_time TEXT,
+    prerequisites TEXT,
+    variants TEXT,
+    default_variant TEXT,
+    is_system INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS feature_flag_overrides (
+    id TEXT PRIMARY KEY,
+    flag_id TEXT NOT NULL,
+    target_type TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 0,
+    variant TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (flag_id) REFERENCES feature_flags(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS feature_flag_usage (
+    id TEXT PRIMARY KEY,
+    flag_id TEXT NOT NULL,
+    user_id TEXT,
+    variant TEXT,
+    evaluated_at TEXT NOT NULL,
+    context TEXT,
+    FOREIGN KEY (flag_id) REFERENCES feature_flags(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS encryption_keys (
+    id TEXT PRIMARY KEY,
+    key_id TEXT NOT NULL UNIQUE,
+    key_type TEXT NOT NULL,
+    algorithm TEXT NOT NULL,
+    key_version INTEGER NOT NULL DEFAULT 1,
+    public_key TEXT,
+    encrypted_private_key TEXT,
+    key_derivation_info TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    is_primary INTEGER NOT NULL DEFAULT 0,
+    rotation_days INTEGER,
+    last_rotated TEXT,
+    expires_at TEXT,
+    max_usage_count INTEGER,
+    current_usage_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS key_rotations (
+    id TEXT PRIMARY KEY,
+    key_id TEXT NOT NULL,
+    from_version INTEGER NOT NULL,
+    to_version INTEGER NOT NULL,
+    rotation_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    re_encrypted_count INTEGER NOT NULL DEFAULT 0,
+    error_message TEXT,
+    initiated_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (key_id) REFERENCES encryption_keys(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS key_usage_logs (
+    id TEXT PRIMARY KEY,
+    key_id TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    entity_type TEXT,
+    entity_id TEXT,
+    success INTEGER NOT NULL DEFAULT 0,
+    error_message TEXT,
+    performed_at TEXT NOT NULL,
+    performed_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (key_id) REFERENCES encryption_keys(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS encrypted_data (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    field_name TEXT NOT NULL,
+    key_id TEXT NOT NULL,
+    key_version INTEGER NOT NULL,
+    iv TEXT NOT NULL,
+    auth_tag TEXT,
+    encrypted_value TEXT NOT NULL,
+    encryption_algorithm TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (key_id) REFERENCES encryption_keys(id)
+);
+CREATE TABLE IF NOT EXISTS key_policies (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    key_type TEXT NOT NULL,
+    algorithm TEXT NOT NULL,
+    key_size_bits INTEGER NOT NULL,
+    rotation_days INTEGER NOT NULL,
+    max_usage_count INTEGER,
+    require_hsm INTEGER NOT NULL DEFAULT 0,
+    allow_export INTEGER NOT NULL DEFAULT 0,
+    allowed_operations TEXT NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS backup_schedules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    backup_type TEXT NOT NULL DEFAULT 'Full',
+    schedule_cron TEXT NOT NULL,
+    retention_days INTEGER NOT NULL DEFAULT 30,
+    max_backups INTEGER NOT NULL DEFAULT 10,
+    compression INTEGER NOT NULL DEFAULT 1,
+    encryption_enabled INTEGER NOT NULL DEFAULT 0,
+    encryption_key_id TEXT,
+    storage_type TEXT NOT NULL DEFAULT 'Local',
+    storage_path TEXT NOT NULL,
+    include_attachments INTEGER NOT NULL DEFAULT 1,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    last_run TEXT,
+    next_run TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS backup_records (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT,
+    backup_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    file_path TEXT NOT NULL,
+    file_size_bytes INTEGER NOT NULL DEFAULT 0,
+    compressed_size_bytes INTEGER,
+    checksum TEXT,
+    checksum_algorithm TEXT,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    duration_seconds INTEGER,
+    tables_included TEXT,
+    records_count INTEGER,
+    error_message TEXT,
+    verification_status TEXT,
+    verified_at TEXT,
+    is_restorable INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (schedule_id) REFERENCES backup_schedules(id) ON DELETE SET NULL
+);
+CREATE TABLE IF NOT EXISTS restore_operations (
+    id TEXT PRIMARY KEY,
+    backup_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    restore_type TEXT NOT NULL DEFAULT 'Full',
+    target_tables TEXT,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    duration_seconds INTEGER,
+    records_restored INTEGER,
+    error_message TEXT,
+    initiated_by TEXT,
+    backup_before_restore TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (backup_id) REFERENCES backup_records(id)
+);
+CREATE TABLE IF NOT EXISTS backup_verifications (
+    id TEXT PRIMARY KEY,
+    backup_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    checked_at TEXT NOT NULL,
+    checksum_valid INTEGER NOT NULL DEFAULT 0,
+    file_readable INTEGER NOT NULL DEFAULT 0,
+    schema_valid INTEGER NOT NULL DEFAULT 0,
+    sample_data_valid INTEGER NOT NULL DEFAULT 0,
+    error_details TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (backup_id) REFERENCES backup_records(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS system_metrics (
+    id TEXT PRIMARY KEY,
+    metric_type TEXT NOT NULL,
+    metric_name TEXT NOT NULL,
+    value REAL NOT NULL,
+    unit TEXT NOT NULL,
+    tags TEXT,
+    recorded_at TEXT NOT NULL,
+    hostname TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS health_checks (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    check_type TEXT NOT NULL,
+    endpoint TEXT,
+    timeout_seconds INTEGER NOT NULL DEFAULT 30,
+    interval_seconds INTEGER NOT NULL DEFAULT 60,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    last_check TEXT,
+    last_status TEXT,
+    last_response_time_ms INTEGER,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS health_check_results (
+    id TEXT PRIMARY KEY,
+    check_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    response_time_ms INTEGER NOT NULL,
+    message TEXT,
+    details TEXT,
+    checked_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (check_id) REFERENCES health_checks(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS custom_roles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    parent_role_id TEXT,
+    is_system INTEGER NOT NULL DEFAULT 0,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    FOREIGN KEY (parent_role_id) REFERENCES custom_roles(id)
+);
+CREATE TABLE IF NOT EXISTS permissions (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    module TEXT NOT NULL,
+    resource TEXT NOT NULL,
+    action TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS role_permissions (
+    id TEXT PRIMARY KEY,
+    role_id TEXT NOT NULL,
+    permission_id TEXT NOT NULL,
+    granted_at TEXT NOT NULL,
+    granted_by TEXT,
+    UNIQUE(role_id, permission_id),
+    FOREIGN KEY (role_id) REFERENCES custom_roles(id) ON DELETE CASCADE,
+    FOREIGN KEY (permission_id) REFERENCES permissions(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS user_role_assignments (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    role_id TEXT NOT NULL,
+    assigned_at TEXT NOT NULL,
+    assigned_by TEXT,
+    expires_at TEXT,
+    UNIQUE(user_id, role_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (role_id) REFERENCES custom_roles(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS data_permissions (
+    id TEXT PRIMARY KEY,
+    role_id TEXT NOT NULL,
+    resource TEXT NOT NULL,
+    filter_type TEXT NOT NULL DEFAULT 'All',
+    filter_value TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (role_id) REFERENCES custom_roles(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS field_permissions (
+    id TEXT PRIMARY KEY,
+    role_id TEXT NOT NULL,
+    resource TEXT NOT NULL,
+    field_name TEXT NOT NULL,
+    can_read INTEGER NOT NULL DEFAULT 1,
+    can_write INTEGER NOT NULL DEFAULT 0,
+    can_create INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    UNIQUE(role_id, resource, field_name),
+    FOREIGN KEY (role_id) REFERENCES custom_roles(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS permission_audit_logs (
+    id TEXT PRIMARY KEY,
+    action TEXT NOT NULL,
+    actor_id TEXT NOT NULL,
+    target_type TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    performed_at TEXT NOT NULL,
+    ip_address TEXT,
+    FOREIGN KEY (actor_id) REFERENCES users(id)
+);
+CREATE INDEX IF NOT EXISTS idx_feature_flags_key ON feature_flags(key);
+CREATE INDEX IF NOT EXISTS idx_feature_flag_overrides_flag ON feature_flag_overrides(flag_id);
+CREATE INDEX IF NOT EXISTS idx_encryption_keys_active ON encryption_keys(is_active, is_primary);
+CREATE INDEX IF NOT EXISTS idx_backup_records_status ON backup_records(status);
+CREATE INDEX IF NOT EXISTS idx_system_metrics_type ON system_metrics(metric_type, recorded_at);
+CREATE INDEX IF NOT EXISTS idx_health_checks_active ON health_checks(is_active);
+CREATE INDEX IF NOT EXISTS idx_role_permissions_role ON role_permissions(role_id);
+CREATE INDEX IF NOT EXISTS idx_user_role_assignments_user ON user_role_assignments(user_id);
+CREATE TABLE IF NOT EXISTS configuration_templates (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    product_id TEXT,
+    base_price INTEGER NOT NULL DEFAULT 0,
+    min_margin_percent REAL NOT NULL DEFAULT 0,
+    max_discount_percent REAL NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    version INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS config_attributes (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    data_type TEXT NOT NULL DEFAULT 'Text',
+    required INTEGER NOT NULL DEFAULT 0,
+    default_value TEXT,
+    options TEXT,
+    validation_regex TEXT,
+    min_value REAL,
+    max_value REAL,
+    unit TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (template_id) REFERENCES configuration_templates(id)
+);
+CREATE TABLE IF NOT EXISTS pricing_rules (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    rule_type TEXT NOT NULL DEFAULT 'Fixed',
+    attribute_id TEXT,
+    attribute_value TEXT,
+    base_price_modifier REAL,
+    fixed_price INTEGER,
+    markup_percent REAL,
+    formula TEXT,
+    priority INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    FOREIGN KEY (template_id) REFERENCES configuration_templates(id)
+);
+CREATE TABLE IF NOT EXISTS pricing_matrices (
+    id TEXT PRIMARY KEY,
+    pricing_rule_id TEXT NOT NULL,
+    row_attribute_id TEXT NOT NULL,
+    column_attribute_id TEXT NOT NULL,
+    rows TEXT NOT NULL,
+    columns TEXT NOT NULL,
+    prices TEXT NOT NULL,
+    FOREIGN KEY (pricing_rule_id) REFERENCES pricing_rules(id)
+);
+CREATE TABLE IF NOT EXISTS product_configurations (
+    id TEXT PRIMARY KEY,
+    configuration_number TEXT NOT NULL UNIQUE,
+    template_id TEXT NOT NULL,
+    product_id TEXT,
+    name TEXT NOT NULL,
+    description TEXT,
+    base_price INTEGER NOT NULL DEFAULT 0,
+    configured_price INTEGER NOT NULL DEFAULT 0,
+    margin_percent REAL NOT NULL DEFAULT 0,
+    is_valid INTEGER NOT NULL DEFAULT 1,
+    validation_errors TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (template_id) REFERENCES configuration_templates(id)
+);
+CREATE TABLE IF NOT EXISTS configuration_values (
+    id TEXT PRIMARY KEY,
+    configuration_id TEXT NOT NULL,
+    attribute_id TEXT NOT NULL,
+    value TEXT NOT NULL,
+    display_value TEXT,
+    FOREIGN KEY (configuration_id) REFERENCES product_configurations(id),
+    FOREIGN KEY (attribute_id) REFERENCES config_attributes(id)
+);
+CREATE TABLE IF NOT EXISTS configuration_constraints (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    constraint_type TEXT NOT NULL DEFAULT 'Requires',
+    source_attribute_id TEXT NOT NULL,
+    source_values TEXT NOT NULL,
+    target_attribute_id TEXT NOT NULL,
+    target_values TEXT NOT NULL,
+    error_message TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    FOREIGN KEY (template_id) REFERENCES configuration_templates(id)
+);
+CREATE TABLE IF NOT EXISTS configurable_boms (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    parent_attribute_id TEXT,
+    parent_value TEXT,
+    component_product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    quantity_formula TEXT,
+    is_optional INTEGER NOT NULL DEFAULT 0,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (template_id) REFERENCES configuration_templates(id)
+);
+CREATE TABLE IF NOT EXISTS configurable_routings (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    work_center_id TEXT NOT NULL,
+    operation_name TEXT NOT NULL,
+    setup_time INTEGER NOT NULL DEFAULT 0,
+    run_time INTEGER NOT NULL DEFAULT 0,
+    run_time_formula TEXT,
+    condition_attribute_id TEXT,
+    condition_value TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (template_id) REFERENCES configuration_templates(id)
+);
+CREATE TABLE IF NOT EXISTS configured_quotes (
+    id TEXT PRIMARY KEY,
+    quote_number TEXT NOT NULL UNIQUE,
+    configuration_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    opportunity_id TEXT,
+    valid_until TEXT NOT NULL,
+    base_price INTEGER NOT NULL DEFAULT 0,
+    configured_price INTEGER NOT NULL DEFAULT 0,
+    discount_percent REAL NOT NULL DEFAULT 0,
+    discount_amount INTEGER NOT NULL DEFAULT 0,
+    margin_percent REAL NOT NULL DEFAULT 0,
+    total_price INTEGER NOT NULL DEFAULT 0,
+    terms TEXT,
+    internal_notes TEXT,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    sent_at TEXT,
+    responded_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (configuration_id) REFERENCES product_configurations(id)
+);
+CREATE TABLE IF NOT EXISTS configured_quote_lines (
+    id TEXT PRIMARY KEY,
+    quote_id TEXT NOT NULL,
+    line_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    unit_price INTEGER NOT NULL DEFAULT 0,
+    total_price INTEGER NOT NULL DEFAULT 0,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (quote_id) REFERENCES configured_quotes(id)
+);
+CREATE TABLE IF NOT EXISTS quote_approvals (
+    id TEXT PRIMARY KEY,
+    quote_id TEXT NOT NULL,
+    approver_id TEXT NOT NULL,
+    approval_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    comments TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (quote_id) REFERENCES configured_quotes(id)
+);
+CREATE TABLE IF NOT EXISTS quote_versions (
+    id TEXT PRIMARY KEY,
+    quote_id TEXT NOT NULL,
+    version_number INTEGER NOT NULL DEFAULT 1,
+    configuration_snapshot TEXT NOT NULL,
+    price_snapshot TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    notes TEXT,
+    FOREIGN KEY (quote_id) REFERENCES configured_quotes(id)
+);
+CREATE TABLE IF NOT EXISTS guided_selling_steps (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    step_number INTEGER NOT NULL DEFAULT 1,
+    title TEXT NOT NULL,
+    description TEXT,
+    help_text TEXT,
+    attribute_ids TEXT NOT NULL,
+    is_required INTEGER NOT NULL DEFAULT 1,
+    FOREIGN KEY (template_id) REFERENCES configuration_templates(id)
+);
+CREATE TABLE IF NOT EXISTS recommendation_rules (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    condition_logic TEXT NOT NULL,
+    recommendation_text TEXT NOT NULL,
+    recommended_values TEXT NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (template_id) REFERENCES configuration_templates(id)
+);
+CREATE TABLE IF NOT EXISTS bulk_discount_tiers (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    min_quantity INTEGER NOT NULL DEFAULT 1,
+    max_quantity INTEGER,
+    discount_percent REAL NOT NULL DEFAULT 0,
+    FOREIGN KEY (template_id) REFERENCES configuration_templates(id)
+);
+CREATE TABLE IF NOT EXISTS volume_price_breaks (
+    id TEXT PRIMARY KEY,
+    pricing_rule_id TEXT NOT NULL,
+    min_quantity INTEGER NOT NULL DEFAULT 1,
+    max_quantity INTEGER,
+    unit_price INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (pricing_rule_id) REFERENCES pricing_rules(id)
+);
+CREATE TABLE IF NOT EXISTS contract_types (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    default_term_months INTEGER NOT NULL DEFAULT 12,
+    auto_renew_default INTEGER NOT NULL DEFAULT 0,
+    required_clauses TEXT,
+    approval_workflow_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS contract_clauses (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    clause_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    section_number TEXT NOT NULL,
+    is_standard INTEGER NOT NULL DEFAULT 1,
+    is_negotiable INTEGER NOT NULL DEFAULT 1,
+    deviation_notes TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_clause_library (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    clause_type TEXT NOT NULL,
+    content TEXT NOT NULL,
+    description TEXT,
+    is_mandatory INTEGER NOT NULL DEFAULT 0,
+    risk_level TEXT NOT NULL DEFAULT 'Low',
+    version INTEGER NOT NULL DEFAULT 1,
+    effective_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS contract_milestones (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    due_date TEXT NOT NULL,
+    completed_date TEXT,
+    is_billing_event INTEGER NOT NULL DEFAULT 0,
+    billing_amount INTEGER,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_obligations (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    obligation_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    responsible_party TEXT NOT NULL,
+    frequency TEXT NOT NULL,
+    next_due_date TEXT NOT NULL,
+    last_completed TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_amendments (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    amendment_number TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    changes_summary TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    approved_at TEXT,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_documents (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    document_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_size INTEGER NOT NULL DEFAULT 0,
+    version INTEGER NOT NULL DEFAULT 1,
+    uploaded_by TEXT,
+    uploaded_at TEXT NOT NULL,
+    is_signed INTEGER NOT NULL DEFAULT 0,
+    signed_at TEXT,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS approval_workflow_levels (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    level_number INTEGER NOT NULL DEFAULT 1,
+    approver_type TEXT NOT NULL,
+    approver_id TEXT,
+    role_id TEXT,
+    is_parallel INTEGER NOT NULL DEFAULT 0,
+    timeout_days INTEGER NOT NULL DEFAULT 7,
+    FOREIGN KEY (workflow_id) REFERENCES approval_workflows(id)
+);
+CREATE TABLE IF NOT EXISTS contract_approvals (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    approver_id TEXT NOT NULL,
+    approval_level INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    comments TEXT,
+    due_date TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_signatures (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    signer_type TEXT NOT NULL,
+    signer_id TEXT,
+    signer_name TEXT NOT NULL,
+    signer_email TEXT NOT NULL,
+    signer_title TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    signed_at TEXT,
+    signature_data TEXT,
+    ip_address TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_alerts (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    alert_type TEXT NOT NULL,
+    alert_date TEXT NOT NULL,
+    message TEXT NOT NULL,
+    recipients TEXT NOT NULL,
+    is_sent INTEGER NOT NULL DEFAULT 0,
+    sent_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_spend (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    committed_amount INTEGER NOT NULL DEFAULT 0,
+    spent_amount INTEGER NOT NULL DEFAULT 0,
+    remaining_amount INTEGER NOT NULL DEFAULT 0,
+    utilization_percent REAL NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_vendor_performance (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    evaluation_period TEXT NOT NULL,
+    on_time_delivery_pct REAL NOT NULL DEFAULT 0,
+    quality_score REAL NOT NULL DEFAULT 0,
+    responsiveness_score REAL NOT NULL DEFAULT 0,
+    compliance_score REAL NOT NULL DEFAULT 0,
+    overall_score REAL NOT NULL DEFAULT 0,
+    notes TEXT,
+    evaluated_at TEXT NOT NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_risk_assessments (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    assessment_date TEXT NOT NULL,
+    financial_risk TEXT NOT NULL,
+    legal_risk TEXT NOT NULL,
+    operational_risk TEXT NOT NULL,
+    compliance_risk TEXT NOT NULL,
+    overall_risk TEXT NOT NULL DEFAULT 'Medium',
+    mitigation_notes TEXT,
+    assessed_by TEXT,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS contract_terms (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    term_name TEXT NOT NULL,
+    term_value TEXT NOT NULL,
+    term_unit TEXT NOT NULL,
+    is_custom INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS compliance_checks (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    check_type TEXT NOT NULL,
+    check_name TEXT NOT NULL,
+    result TEXT NOT NULL,
+    details TEXT,
+    checked_at TEXT NOT NULL,
+    checked_by TEXT,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS commission_splits (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    split_type TEXT NOT NULL DEFAULT 'Percentage',
+    FOREIGN KEY (plan_id) REFERENCES commission_plans(id)
+);
+CREATE TABLE IF NOT EXISTS commission_split_participants (
+    id TEXT PRIMARY KEY,
+    split_id TEXT NOT NULL,
+    sales_rep_id TEXT NOT NULL,
+    split_percent REAL NOT NULL DEFAULT 0,
+    role TEXT NOT NULL,
+    FOREIGN KEY (split_id) REFERENCES commission_splits(id)
+);
+CREATE TABLE IF NOT EXISTS sales_rep_assignments (
+    id TEXT PRIMARY KEY,
+    sales_rep_id TEXT NOT NULL,
+    plan_id TEXT NOT NULL,
+    territory_id TEXT,
+    product_id TEXT,
+    customer_id TEXT,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    is_primary INTEGER NOT NULL DEFAULT 1,
+    split_percent REAL NOT NULL DEFAULT 100,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS commission_calculations (
+    id TEXT PRIMARY KEY,
+    calculation_number TEXT NOT NULL UNIQUE,
+    sales_rep_id TEXT NOT NULL,
+    plan_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    gross_revenue INTEGER NOT NULL DEFAULT 0,
+    returns INTEGER NOT NULL DEFAULT 0,
+    net_revenue INTEGER NOT NULL DEFAULT 0,
+    cost_of_goods INTEGER NOT NULL DEFAULT 0,
+    gross_margin INTEGER NOT NULL DEFAULT 0,
+    base_commission INTEGER NOT NULL DEFAULT 0,
+    tier_bonus INTEGER NOT NULL DEFAULT 0,
+    override_commission INTEGER NOT NULL DEFAULT 0,
+    adjustments INTEGER NOT NULL DEFAULT 0,
+    clawbacks INTEGER NOT NULL DEFAULT 0,
+    total_commission INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    calculated_at TEXT,
+    approved_at TEXT,
+    approved_by TEXT,
+    paid_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS commission_lines (
+    id TEXT PRIMARY KEY,
+    calculation_id TEXT NOT NULL,
+    source_type TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    transaction_date TEXT NOT NULL,
+    customer_id TEXT,
+    product_id TEXT,
+    quantity INTEGER NOT NULL DEFAULT 0,
+    revenue INTEGER NOT NULL DEFAULT 0,
+    cost INTEGER NOT NULL DEFAULT 0,
+    margin INTEGER NOT NULL DEFAULT 0,
+    rate_applied REAL NOT NULL DEFAULT 0,
+    commission_amount INTEGER NOT NULL DEFAULT 0,
+    notes TEXT,
+    FOREIGN KEY (calculation_id) REFERENCES commission_calculations(id)
+);
+CREATE TABLE IF NOT EXISTS commission_adjustments (
+    id TEXT PRIMARY KEY,
+    calculation_id TEXT NOT NULL,
+    adjustment_type TEXT NOT NULL,
+    amount INTEGER NOT NULL DEFAULT 0,
+    reason TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (calculation_id) REFERENCES commission_calculations(id)
+);
+CREATE TABLE IF NOT EXISTS commission_payments (
+    id TEXT PRIMARY KEY,
+    payment_number TEXT NOT NULL UNIQUE,
+    calculation_id TEXT NOT NULL,
+    sales_rep_id TEXT NOT NULL,
+    payment_date TEXT NOT NULL,
+    amount INTEGER NOT NULL DEFAULT 0,
+    payment_method TEXT NOT NULL,
+    reference TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (calculation_id) REFERENCES commission_calculations(id)
+);
+CREATE TABLE IF NOT EXISTS commission_draws (
+    id TEXT PRIMARY KEY,
+    sales_rep_id TEXT NOT NULL,
+    plan_id TEXT NOT NULL,
+    draw_type TEXT NOT NULL DEFAULT 'Recoverable',
+    amount INTEGER NOT NULL DEFAULT 0,
+    frequency TEXT NOT NULL DEFAULT 'Monthly',
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    balance INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (plan_id) REFERENCES commission_plans(id)
+);
+CREATE TABLE IF NOT EXISTS draw_transactions (
+    id TEXT PRIMARY KEY,
+    draw_id TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    amount INTEGER NOT NULL DEFAULT 0,
+    balance_after INTEGER NOT NULL DEFAULT 0,
+    period TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (draw_id) REFERENCES commission_draws(id)
+);
+CREATE TABLE IF NOT EXISTS sales_quotas (
+    id TEXT PRIMARY KEY,
+    sales_rep_id TEXT NOT NULL,
+    quota_type TEXT NOT NULL DEFAULT 'Revenue',
+    period TEXT NOT NULL DEFAULT 'Annual',
+    year INTEGER NOT NULL,
+    quarter INTEGER,
+    month INTEGER,
+    target_amount INTEGER NOT NULL DEFAULT 0,
+    stretch_amount INTEGER,
+    territory_id TEXT,
+    product_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS quota_progress (
+    id TEXT PRIMARY KEY,
+    quota_id TEXT NOT NULL,
+    as_of_date TEXT NOT NULL,
+    achieved_amount INTEGER NOT NULL DEFAULT 0,
+    percent_achieved REAL NOT NULL DEFAULT 0,
+    forecast_amount INTEGER NOT NULL DEFAULT 0,
+    gap_to_quota INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (quota_id) REFERENCES sales_quotas(id)
+);
+CREATE TABLE IF NOT EXISTS commission_disputes (
+    id TEXT PRIMARY KEY,
+    calculation_id TEXT NOT NULL,
+    sales_rep_id TEXT NOT NULL,
+    dispute_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    requested_amount INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Open',
+    resolution TEXT,
+    resolved_amount INTEGER,
+    resolved_by TEXT,
+    resolved_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (calculation_id) REFERENCES commission_calculations(id)
+);
+CREATE TABLE IF NOT EXISTS sales_teams (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    manager_id TEXT,
+    parent_team_id TEXT,
+    description TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS sales_team_members (
+    id TEXT PRIMARY KEY,
+    team_id TEXT NOT NULL,
+    sales_rep_id TEXT NOT NULL,
+    role TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    FOREIGN KEY (team_id) REFERENCES sales_teams(id)
+);
+CREATE TABLE IF NOT EXISTS override_rules (
+    id TEXT PRIMARY KEY,
+    manager_id TEXT NOT NULL,
+    plan_id TEXT,
+    override_type TEXT NOT NULL DEFAULT 'Team',
+    rate REAL NOT NULL DEFAULT 0,
+    applies_to_team INTEGER NOT NULL DEFAULT 1,
+    team_id TEXT,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    FOREIGN KEY (plan_id) REFERENCES commission_plans(id)
+);
+CREATE TABLE IF NOT EXISTS commission_reports (
+    id TEXT PRIMARY KEY,
+    report_type TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    sales_rep_id TEXT,
+    team_id TEXT,
+    total_revenue INTEGER NOT NULL DEFAULT 0,
+    total_commission INTEGER NOT NULL DEFAULT 0,
+    avg_commission_rate REAL NOT NULL DEFAULT 0,
+    top_performer_id TEXT,
+    generated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS commission_forecasts (
+    id TEXT PRIMARY KEY,
+    sales_rep_id TEXT NOT NULL,
+    period TEXT NOT NULL,
+    pipeline_revenue INTEGER NOT NULL DEFAULT 0,
+    weighted_revenue INTEGER NOT NULL DEFAULT 0,
+    forecast_commission INTEGER NOT NULL DEFAULT 0,
+    confidence_level REAL NOT NULL DEFAULT 0,
+    calculated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS spiffs (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    product_id TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    reward_type TEXT NOT NULL DEFAULT 'Fixed',
+    reward_amount INTEGER NOT NULL DEFAULT 0,
+    qualification_criteria TEXT NOT NULL,
+    max_payouts INTEGER,
+    current_payouts INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS spiff_achievements (
+    id TEXT PRIMARY KEY,
+    spiff_id TEXT NOT NULL,
+    sales_rep_id TEXT NOT NULL,
+    achieved_date TEXT NOT NULL,
+    achievement_value INTEGER NOT NULL DEFAULT 0,
+    payout_amount INTEGER NOT NULL DEFAULT 0,
+    paid INTEGER NOT NULL DEFAULT 0,
+    paid_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (spiff_id) REFERENCES spiffs(id)
+);
+CREATE TABLE IF NOT EXISTS planning_calendars (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    working_days TEXT NOT NULL DEFAULT '1,2,3,4,5',
+    shift_pattern TEXT NOT NULL,
+    holidays TEXT,
+    capacity_per_day INTEGER NOT NULL DEFAULT 480,
+    effective_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS planning_shifts (
+    id TEXT PRIMARY KEY,
+    calendar_id TEXT NOT NULL,
+    shift_name TEXT NOT NULL,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    break_start TEXT,
+    break_end TEXT,
+    capacity_percent REAL NOT NULL DEFAULT 100,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    FOREIGN KEY (calendar_id) REFERENCES planning_calendars(id)
+);
+CREATE TABLE IF NOT EXISTS resource_capacities (
+    id TEXT PRIMARY KEY,
+    resource_id TEXT NOT NULL,
+    resource_type TEXT NOT NULL DEFAULT 'Machine',
+    resource_name TEXT NOT NULL,
+    work_center_id TEXT,
+    calendar_id TEXT,
+    daily_capacity INTEGER NOT NULL DEFAULT 480,
+    unit_of_measure TEXT NOT NULL DEFAULT 'Minutes',
+    efficiency_percent REAL NOT NULL DEFAULT 100,
+    utilization_percent REAL NOT NULL DEFAULT 85,
+    available_from TEXT NOT NULL,
+    available_to TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS resource_availabilities (
+    id TEXT PRIMARY KEY,
+    resource_id TEXT NOT NULL,
+    date TEXT NOT NULL,
+    shift_id TEXT,
+    available_capacity INTEGER NOT NULL DEFAULT 0,
+    planned_capacity INTEGER NOT NULL DEFAULT 0,
+    actual_capacity INTEGER NOT NULL DEFAULT 0,
+    downtime_minutes INTEGER NOT NULL DEFAULT 0,
+    notes TEXT,
+    FOREIGN KEY (resource_id) REFERENCES resource_capacities(id)
+);
+CREATE TABLE IF NOT EXISTS master_production_schedules (
+    id TEXT PRIMARY KEY,
+    schedule_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    planning_horizon_days INTEGER NOT NULL DEFAULT 90,
+    time_bucket TEXT NOT NULL DEFAULT 'Weekly',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    schedule_method TEXT NOT NULL DEFAULT 'Forward',
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    released_at TEXT
+);
+CREATE TABLE IF NOT EXISTS mps_items (
+    id TEXT PRIMARY KEY,
+    mps_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    planning_start TEXT NOT NULL,
+    planning_end TEXT NOT NULL,
+    total_planned INTEGER NOT NULL DEFAULT 0,
+    total_demand INTEGER NOT NULL DEFAULT 0,
+    total_supply INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    FOREIGN KEY (mps_id) REFERENCES master_production_schedules(id)
+);
+CREATE TABLE IF NOT EXISTS mps_time_buckets (
+    id TEXT PRIMARY KEY,
+    mps_item_id TEXT NOT NULL,
+    bucket_start TEXT NOT NULL,
+    bucket_end TEXT NOT NULL,
+    gross_requirement INTEGER NOT NULL DEFAULT 0,
+    scheduled_receipts INTEGER NOT NULL DEFAULT 0,
+    projected_on_hand INTEGER NOT NULL DEFAULT 0,
+    net_requirement INTEGER NOT NULL DEFAULT 0,
+    planned_order_receipt INTEGER NOT NULL DEFAULT 0,
+    planned_order_release INTEGER NOT NULL DEFAULT 0,
+    available_to_promise INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (mps_item_id) REFERENCES mps_items(id)
+);
+CREATE TABLE IF NOT EXISTS material_requirements_plans (
+    id TEXT PRIMARY KEY,
+    mrp_number TEXT NOT NULL UNIQUE,
+    mps_id TEXT,
+    planning_date TEXT NOT NULL,
+    planning_horizon_days INTEGER NOT NULL DEFAULT 90,
+    regenerate INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    run_started_at TEXT,
+    run_completed_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (mps_id) REFERENCES master_production_schedules(id)
+);
+CREATE TABLE IF NOT EXISTS mrp_items (
+    id TEXT PRIMARY KEY,
+    mrp_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    bom_id TEXT,
+    lead_time_days INTEGER NOT NULL DEFAULT 0,
+    safety_stock INTEGER NOT NULL DEFAULT 0,
+    lot_size INTEGER NOT NULL DEFAULT 1,
+    lot_size_method TEXT NOT NULL DEFAULT 'Discrete',
+    on_hand INTEGER NOT NULL DEFAULT 0,
+    allocated INTEGER NOT NULL DEFAULT 0,
+    on_order INTEGER NOT NULL DEFAULT 0,
+    net_requirement INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    FOREIGN KEY (mrp_id) REFERENCES material_requirements_plans(id)
+);
+CREATE TABLE IF NOT EXISTS mrp_suggestions (
+    id TEXT PRIMARY KEY,
+    mrp_item_id TEXT NOT NULL,
+    suggestion_type TEXT NOT NULL DEFAULT 'PlannedOrder',
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 0,
+    due_date TEXT NOT NULL,
+    release_date TEXT NOT NULL,
+    source_type TEXT,
+    source_id TEXT,
+    priority INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    processed_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (mrp_item_id) REFERENCES mrp_items(id)
+);
+CREATE TABLE IF NOT EXISTS detailed_schedules (
+    id TEXT PRIMARY KEY,
+    schedule_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    schedule_type TEXT NOT NULL DEFAULT 'Production',
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    optimization_method TEXT NOT NULL DEFAULT 'Priority',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS schedule_operations (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT NOT NULL,
+    work_order_id TEXT NOT NULL,
+    routing_operation_id TEXT,
+    resource_id TEXT NOT NULL,
+    resource_type TEXT NOT NULL DEFAULT 'Machine',
+    operation_name TEXT NOT NULL,
+    scheduled_start TEXT NOT NULL,
+    scheduled_end TEXT NOT NULL,
+    setup_time INTEGER NOT NULL DEFAULT 0,
+    run_time INTEGER NOT NULL DEFAULT 0,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Planned',
+    priority INTEGER NOT NULL DEFAULT 0,
+    sequence INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (schedule_id) REFERENCES detailed_schedules(id)
+);
+CREATE TABLE IF NOT EXISTS schedule_constraints (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT NOT NULL,
+    constraint_type TEXT NOT NULL DEFAULT 'Precedence',
+    operation_id TEXT,
+    related_operation_id TEXT,
+    offset_minutes INTEGER NOT NULL DEFAULT 0,
+    is_hard INTEGER NOT NULL DEFAULT 1,
+    description TEXT,
+    FOREIGN KEY (schedule_id) REFERENCES detailed_schedules(id)
+);
+CREATE TABLE IF NOT EXISTS capacity_plan_items (
+    id TEXT PRIMARY KEY,
+    capacity_plan_id TEXT NOT NULL,
+    resource_id TEXT NOT NULL,
+    bucket_start TEXT NOT NULL,
+    bucket_end TEXT NOT NULL,
+    available_capacity INTEGER NOT NULL DEFAULT 0,
+    required_capacity INTEGER NOT NULL DEFAULT 0,
+    overload_capacity INTEGER NOT NULL DEFAULT 0,
+    utilization_percent REAL NOT NULL DEFAULT 0,
+    FOREIGN KEY (capacity_plan_id) REFERENCES capacity_plans(id)
+);
+CREATE TABLE IF NOT EXISTS production_lines (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    work_center_id TEXT,
+    capacity_per_hour INTEGER NOT NULL DEFAULT 0,
+    efficiency REAL NOT NULL DEFAULT 100,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS production_line_stations (
+    id TEXT PRIMARY KEY,
+    production_line_id TEXT NOT NULL,
+    station_number INTEGER NOT NULL DEFAULT 1,
+    station_name TEXT NOT NULL,
+    work_center_id TEXT,
+    cycle_time INTEGER NOT NULL DEFAULT 0,
+    buffer_capacity INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (production_line_id) REFERENCES production_lines(id)
+);
+CREATE TABLE IF NOT EXISTS finite_schedules (
+    id TEXT PRIMARY KEY,
+    schedule_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    resource_id TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    optimization_goal TEXT NOT NULL DEFAULT 'MinimizeTardiness',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS finite_schedule_blocks (
+    id TEXT PRIMARY KEY,
+    finite_schedule_id TEXT NOT NULL,
+    work_order_id TEXT NOT NULL,
+    operation_id TEXT,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    setup_time INTEGER NOT NULL DEFAULT 0,
+    run_time INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Planned',
+    FOREIGN KEY (finite_schedule_id) REFERENCES finite_schedules(id)
+);
+CREATE TABLE IF NOT EXISTS what_if_scenarios (
+    id TEXT PRIMARY KEY,
+    scenario_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    base_date TEXT NOT NULL,
+    changes TEXT NOT NULL,
+    results TEXT,
+    comparison_baseline_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS planning_exceptions (
+    id TEXT PRIMARY KEY,
+    exception_type TEXT NOT NULL,
+    severity TEXT NOT NULL DEFAULT 'Warning',
+    product_id TEXT,
+    resource_id TEXT,
+    work_order_id TEXT,
+    message TEXT NOT NULL,
+    suggested_action TEXT,
+    is_resolved INTEGER NOT NULL DEFAULT 0,
+    resolved_at TEXT,
+    resolved_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS order_priorities (
+    id TEXT PRIMARY KEY,
+    order_type TEXT NOT NULL,
+    order_id TEXT NOT NULL,
+    customer_id TEXT,
+    priority_score INTEGER NOT NULL DEFAULT 0,
+    due_date TEXT NOT NULL,
+    value INTEGER NOT NULL DEFAULT 0,
+    customer_priority INTEGER NOT NULL DEFAULT 0,
+    strategic_value INTEGER NOT NULL DEFAULT 0,
+    calculated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS resource_maintenance_windows (
+    id TEXT PRIMARY KEY,
+    resource_id TEXT NOT NULL,
+    window_start TEXT NOT NULL,
+    window_end TEXT NOT NULL,
+    maintenance_type TEXT NOT NULL DEFAULT 'Planned',
+    description TEXT,
+    capacity_reduction_percent REAL NOT NULL DEFAULT 100,
+    status TEXT NOT NULL DEFAULT 'Scheduled',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (resource_id) REFERENCES resource_capacities(id)
+);
+CREATE TABLE IF NOT EXISTS sequencing_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    rule_type TEXT NOT NULL DEFAULT 'Priority',
+    priority_criteria TEXT NOT NULL,
+    constraints TEXT,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS schedule_performance (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT NOT NULL,
+    metric_date TEXT NOT NULL,
+    on_time_percent REAL NOT NULL DEFAULT 0,
+    utilization_percent REAL NOT NULL DEFAULT 0,
+    efficiency_percent REAL NOT NULL DEFAULT 0,
+    throughput INTEGER NOT NULL DEFAULT 0,
+    wip_value INTEGER NOT NULL DEFAULT 0,
+    tardy_orders INTEGER NOT NULL DEFAULT 0,
+    calculated_at TEXT NOT NULL,
+    FOREIGN KEY (schedule_id) REFERENCES detailed_schedules(id)
+);
+CREATE TABLE IF NOT EXISTS spend_category_trees (
+    id TEXT PRIMARY KEY,
+    parent_id TEXT,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    level INTEGER NOT NULL DEFAULT 0,
+    path TEXT NOT NULL,
+    is_leaf INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS spend_transactions (
+    id TEXT PRIMARY KEY,
+    transaction_number TEXT NOT NULL UNIQUE,
+    transaction_date TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    category_id TEXT NOT NULL,
+    cost_center_id TEXT,
+    department_id TEXT,
+    project_id TEXT,
+    gl_account_id TEXT,
+    amount INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    amount_base INTEGER NOT NULL DEFAULT 0,
+    quantity INTEGER,
+    unit_of_measure TEXT,
+    source_type TEXT NOT NULL,
+    source_id TEXT,
+    description TEXT,
+    is_contracted INTEGER NOT NULL DEFAULT 0,
+    contract_id TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS spend_summaries (
+    id TEXT PRIMARY KEY,
+    period_type TEXT NOT NULL DEFAULT 'Monthly',
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    category_id TEXT,
+    vendor_id TEXT,
+    department_id TEXT,
+    cost_center_id TEXT,
+    total_spend INTEGER NOT NULL DEFAULT 0,
+    transaction_count INTEGER NOT NULL DEFAULT 0,
+    avg_transaction INTEGER NOT NULL DEFAULT 0,
+    min_transaction INTEGER NOT NULL DEFAULT 0,
+    max_transaction INTEGER NOT NULL DEFAULT 0,
+    contracted_spend INTEGER NOT NULL DEFAULT 0,
+    uncontracted_spend INTEGER NOT NULL DEFAULT 0,
+    maverick_spend INTEGER NOT NULL DEFAULT 0,
+    savings_identified INTEGER NOT NULL DEFAULT 0,
+    savings_realized INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS vendor_spend_analyses (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    analysis_period TEXT NOT NULL DEFAULT 'Annual',
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_spend INTEGER NOT NULL DEFAULT 0,
+    invoice_count INTEGER NOT NULL DEFAULT 0,
+    po_count INTEGER NOT NULL DEFAULT 0,
+    contract_count INTEGER NOT NULL DEFAULT 0,
+    spend_under_contract INTEGER NOT NULL DEFAULT 0,
+    spend_off_contract INTEGER NOT NULL DEFAULT 0,
+    avg_invoice_value INTEGER NOT NULL DEFAULT 0,
+    payment_terms_avg INTEGER NOT NULL DEFAULT 0,
+    early_payment_savings INTEGER NOT NULL DEFAULT 0,
+    duplicate_spend INTEGER NOT NULL DEFAULT 0,
+    category_breakdown TEXT,
+    trend_percent REAL NOT NULL DEFAULT 0,
+    market_share REAL NOT NULL DEFAULT 0,
+    risk_score REAL NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS category_spend_analyses (
+    id TEXT PRIMARY KEY,
+    category_id TEXT NOT NULL,
+    analysis_period TEXT NOT NULL DEFAULT 'Annual',
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_spend INTEGER NOT NULL DEFAULT 0,
+    vendor_count INTEGER NOT NULL DEFAULT 0,
+    top_vendor_spend INTEGER NOT NULL DEFAULT 0,
+    top_vendor_share REAL NOT NULL DEFAULT 0,
+    avg_contract_value INTEGER NOT NULL DEFAULT 0,
+    contract_coverage REAL NOT NULL DEFAULT 0,
+    savings_opportunity INTEGER NOT NULL DEFAULT 0,
+    price_variance REAL NOT NULL DEFAULT 0,
+    volume_trend REAL NOT NULL DEFAULT 0,
+    supplier_concentration REAL NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS department_spend_analyses (
+    id TEXT PRIMARY KEY,
+    department_id TEXT NOT NULL,
+    analysis_period TEXT NOT NULL DEFAULT 'Annual',
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_spend INTEGER NOT NULL DEFAULT 0,
+    budget INTEGER NOT NULL DEFAULT 0,
+    variance INTEGER NOT NULL DEFAULT 0,
+    variance_percent REAL NOT NULL DEFAULT 0,
+    category_breakdown TEXT,
+    top_vendors TEXT,
+    maverick_spend INTEGER NOT NULL DEFAULT 0,
+    maverick_percent REAL NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS spend_trends (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    metric_type TEXT NOT NULL,
+    period TEXT NOT NULL,
+    value INTEGER NOT NULL DEFAULT 0,
+    previous_value INTEGER NOT NULL DEFAULT 0,
+    change_percent REAL NOT NULL DEFAULT 0,
+    moving_avg_3m INTEGER NOT NULL DEFAULT 0,
+    moving_avg_12m INTEGER NOT NULL DEFAULT 0,
+    trend_direction TEXT NOT NULL DEFAULT 'Stable',
+    calculated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS spend_forecasts (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT,
+    forecast_date TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    forecast_amount INTEGER NOT NULL DEFAULT 0,
+    confidence_low INTEGER NOT NULL DEFAULT 0,
+    confidence_high INTEGER NOT NULL DEFAULT 0,
+    confidence_level REAL NOT NULL DEFAULT 0,
+    method TEXT NOT NULL DEFAULT 'Linear',
+    assumptions TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS maverick_spends (
+    id TEXT PRIMARY KEY,
+    transaction_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    category_id TEXT NOT NULL,
+    amount INTEGER NOT NULL DEFAULT 0,
+    maverick_type TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    preferred_vendor_id TEXT,
+    preferred_contract_id TEXT,
+    potential_savings INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Identified',
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (transaction_id) REFERENCES spend_transactions(id)
+);
+CREATE TABLE IF NOT EXISTS duplicate_spends (
+    id TEXT PRIMARY KEY,
+    transaction_ids TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    category_id TEXT,
+    total_amount INTEGER NOT NULL DEFAULT 0,
+    duplicate_amount INTEGER NOT NULL DEFAULT 0,
+    match_type TEXT NOT NULL,
+    confidence REAL NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Identified',
+    resolved_at TEXT,
+    resolution TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS price_variances (
+    id TEXT PRIMARY KEY,
+    product_id TEXT,
+    category_id TEXT,
+    vendor_id TEXT NOT NULL,
+    standard_price INTEGER NOT NULL DEFAULT 0,
+    actual_price INTEGER NOT NULL DEFAULT 0,
+    variance_amount INTEGER NOT NULL DEFAULT 0,
+    variance_percent REAL NOT NULL DEFAULT 0,
+    quantity INTEGER NOT NULL DEFAULT 0,
+    total_variance INTEGER NOT NULL DEFAULT 0,
+    period TEXT NOT NULL,
+    analysis_date TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS contract_compliances (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    category_id TEXT,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_spend INTEGER NOT NULL DEFAULT 0,
+    contracted_spend INTEGER NOT NULL DEFAULT 0,
+    compliance_rate REAL NOT NULL DEFAULT 0,
+    off_contract_spend INTEGER NOT NULL DEFAULT 0,
+    off_contract_transactions INTEGER NOT NULL DEFAULT 0,
+    contract_utilization REAL NOT NULL DEFAULT 0,
+    savings_achieved INTEGER NOT NULL DEFAULT 0,
+    missed_savings INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id)
+);
+CREATE TABLE IF NOT EXISTS savings_opportunities (
+    id TEXT PRIMARY KEY,
+    opportunity_number TEXT NOT NULL UNIQUE,
+    category_id TEXT NOT NULL,
+    vendor_id TEXT,
+    opportunity_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    current_spend INTEGER NOT NULL DEFAULT 0,
+    potential_savings INTEGER NOT NULL DEFAULT 0,
+    savings_percent REAL NOT NULL DEFAULT 0,
+    effort_level TEXT NOT NULL DEFAULT 'Medium',
+    implementation_timeframe TEXT NOT NULL DEFAULT '3-6 months',
+    status TEXT NOT NULL DEFAULT 'Identified',
+    owner_id TEXT,
+    created_at TEXT NOT NULL,
+    realized_at TEXT,
+    realized_savings INTEGER
+);
+CREATE TABLE IF NOT EXISTS spend_kpis (
+    id TEXT PRIMARY KEY,
+    kpi_name TEXT NOT NULL,
+    kpi_type TEXT NOT NULL,
+    period TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    target_value REAL NOT NULL DEFAULT 0,
+    actual_value REAL NOT NULL DEFAULT 0,
+    variance REAL NOT NULL DEFAULT 0,
+    variance_percent REAL NOT NULL DEFAULT 0,
+    trend TEXT NOT NULL DEFAULT 'Stable',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS supplier_risk_scores (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    score_date TEXT NOT NULL,
+    financial_risk REAL NOT NULL DEFAULT 0,
+    operational_risk REAL NOT NULL DEFAULT 0,
+    compliance_risk REAL NOT NULL DEFAULT 0,
+    geographic_risk REAL NOT NULL DEFAULT 0,
+    concentration_risk REAL NOT NULL DEFAULT 0,
+    overall_risk REAL NOT NULL DEFAULT 0,
+    risk_category TEXT NOT NULL DEFAULT 'Medium',
+    mitigation_recommendations TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS spend_budgets (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    fiscal_year INTEGER NOT NULL,
+    category_id TEXT,
+    department_id TEXT,
+    cost_center_id TEXT,
+    annual_budget INTEGER NOT NULL DEFAULT 0,
+    q1_budget INTEGER NOT NULL DEFAULT 0,
+    q2_budget INTEGER NOT NULL DEFAULT 0,
+    q3_budget INTEGER NOT NULL DEFAULT 0,
+    q4_budget INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS spend_budget_actuals (
+    id TEXT PRIMARY KEY,
+    budget_id TEXT NOT NULL,
+    period TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    budget_amount INTEGER NOT NULL DEFAULT 0,
+    actual_amount INTEGER NOT NULL DEFAULT 0,
+    variance INTEGER NOT NULL DEFAULT 0,
+    variance_percent REAL NOT NULL DEFAULT 0,
+    forecast_amount INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (budget_id) REFERENCES spend_budgets(id)
+);
+CREATE TABLE IF NOT EXISTS tail_spend_analyses (
+    id TEXT PRIMARY KEY,
+    analysis_date TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_spend INTEGER NOT NULL DEFAULT 0,
+    tail_spend INTEGER NOT NULL DEFAULT 0,
+    tail_percent REAL NOT NULL DEFAULT 0,
+    tail_vendor_count INTEGER NOT NULL DEFAULT 0,
+    tail_transaction_count INTEGER NOT NULL DEFAULT 0,
+    avg_tail_transaction INTEGER NOT NULL DEFAULT 0,
+    consolidation_opportunity INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tail_vendors (
+    id TEXT PRIMARY KEY,
+    analysis_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    vendor_name TEXT NOT NULL,
+    total_spend INTEGER NOT NULL DEFAULT 0,
+    transaction_count INTEGER NOT NULL DEFAULT 0,
+    is_tail INTEGER NOT NULL DEFAULT 1,
+    consolidation_candidate INTEGER NOT NULL DEFAULT 0,
+    recommended_action TEXT,
+    FOREIGN KEY (analysis_id) REFERENCES tail_spend_analyses(id)
+);
+CREATE TABLE IF NOT EXISTS spend_dashboards (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    owner_id TEXT,
+    filters TEXT NOT NULL,
+    widgets TEXT NOT NULL,
+    refresh_interval INTEGER NOT NULL DEFAULT 300,
+    last_refreshed TEXT,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS spend_reports (
+    id TEXT PRIMARY KEY,
+    report_name TEXT NOT NULL,
+    report_type TEXT NOT NULL,
+    parameters TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    generated_by TEXT,
+    generated_at TEXT NOT NULL,
+    file_path TEXT,
+    file_format TEXT NOT NULL DEFAULT 'PDF',
+    status TEXT NOT NULL DEFAULT 'Pending'
+);
+CREATE TABLE IF NOT EXISTS payment_term_analyses (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT,
+    category_id TEXT,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_spend INTEGER NOT NULL DEFAULT 0,
+    avg_payment_terms REAL NOT NULL DEFAULT 0,
+    early_payment_count INTEGER NOT NULL DEFAULT 0,
+    on_time_count INTEGER NOT NULL DEFAULT 0,
+    late_payment_count INTEGER NOT NULL DEFAULT 0,
+    early_payment_discounts INTEGER NOT NULL DEFAULT 0,
+    late_payment_penalties INTEGER NOT NULL DEFAULT 0,
+    working_capital_impact INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS compensation_plans (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    plan_year INTEGER NOT NULL,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    budget_amount INTEGER NOT NULL DEFAULT 0,
+    allocated_amount INTEGER NOT NULL DEFAULT 0,
+    spent_amount INTEGER NOT NULL DEFAULT 0,
+    review_cycle TEXT NOT NULL DEFAULT 'Annual',
+    default_merit_budget_percent REAL NOT NULL DEFAULT 3,
+    max_merit_percent REAL NOT NULL DEFAULT 10,
+    promotion_budget_percent REAL NOT NULL DEFAULT 2,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS compensation_budgets (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL,
+    department_id TEXT,
+    cost_center_id TEXT,
+    budget_type TEXT NOT NULL DEFAULT 'Merit',
+    allocated_amount INTEGER NOT NULL DEFAULT 0,
+    committed_amount INTEGER NOT NULL DEFAULT 0,
+    spent_amount INTEGER NOT NULL DEFAULT 0,
+    remaining_amount INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (plan_id) REFERENCES compensation_plans(id)
+);
+CREATE TABLE IF NOT EXISTS salary_ranges (
+    id TEXT PRIMARY KEY,
+    grade_id TEXT,
+    job_family_id TEXT,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    min_salary INTEGER NOT NULL DEFAULT 0,
+    mid_salary INTEGER NOT NULL DEFAULT 0,
+    max_salary INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS job_grades (
+    id TEXT PRIMARY KEY,
+    grade_code TEXT NOT NULL UNIQUE,
+    grade_name TEXT NOT NULL,
+    description TEXT,
+    level INTEGER NOT NULL DEFAULT 1,
+    job_family_id TEXT,
+    salary_range_id TEXT,
+    min_experience_years INTEGER NOT NULL DEFAULT 0,
+    typical_title TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS job_families (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    department_id TEXT,
+    parent_family_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS employee_compensations (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    base_salary INTEGER NOT NULL DEFAULT 0,
+    hourly_rate INTEGER,
+    pay_frequency TEXT NOT NULL DEFAULT 'Monthly',
+    currency TEXT NOT NULL DEFAULT 'USD',
+    grade_id TEXT,
+    salary_range_id TEXT,
+    compa_ratio REAL NOT NULL DEFAULT 0,
+    position_in_range REAL NOT NULL DEFAULT 0,
+    is_current INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS compensation_components (
+    id TEXT PRIMARY KEY,
+    employee_compensation_id TEXT NOT NULL,
+    component_type TEXT NOT NULL DEFAULT 'BaseSalary',
+    name TEXT NOT NULL,
+    amount INTEGER NOT NULL DEFAULT 0,
+    frequency TEXT NOT NULL DEFAULT 'Monthly',
+    is_recurring INTEGER NOT NULL DEFAULT 1,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    FOREIGN KEY (employee_compensation_id) REFERENCES employee_compensations(id)
+);
+CREATE TABLE IF NOT EXISTS compensation_adjustments (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    plan_id TEXT,
+    adjustment_type TEXT NOT NULL DEFAULT 'Merit',
+    current_base INTEGER NOT NULL DEFAULT 0,
+    new_base INTEGER NOT NULL DEFAULT 0,
+    adjustment_amount INTEGER NOT NULL DEFAULT 0,
+    adjustment_percent REAL NOT NULL DEFAULT 0,
+    effective_date TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    justification TEXT,
+    old_grade_id TEXT,
+    new_grade_id TEXT,
+    old_position_id TEXT,
+    new_position_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    approved_by TEXT,
+    approved_at TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (plan_id) REFERENCES compensation_plans(id)
+);
+CREATE TABLE IF NOT EXISTS merit_matrices (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    performance_levels INTEGER NOT NULL DEFAULT 5,
+    compa_ratio_buckets INTEGER NOT NULL DEFAULT 3,
+    matrix_data TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (plan_id) REFERENCES compensation_plans(id)
+);
+CREATE TABLE IF NOT EXISTS merit_guidelines (
+    id TEXT PRIMARY KEY,
+    matrix_id TEXT NOT NULL,
+    performance_rating INTEGER NOT NULL DEFAULT 3,
+    compa_ratio_min REAL NOT NULL DEFAULT 0,
+    compa_ratio_max REAL NOT NULL DEFAULT 100,
+    recommended_increase_min REAL NOT NULL DEFAULT 0,
+    recommended_increase_mid REAL NOT NULL DEFAULT 0,
+    recommended_increase_max REAL NOT NULL DEFAULT 0,
+    FOREIGN KEY (matrix_id) REFERENCES merit_matrices(id)
+);
+CREATE TABLE IF NOT EXISTS bonus_plans (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    plan_type TEXT NOT NULL DEFAULT 'Annual',
+    target_percent REAL NOT NULL DEFAULT 10,
+    max_percent REAL NOT NULL DEFAULT 20,
+    funding_formula TEXT NOT NULL,
+    performance_weights TEXT NOT NULL,
+    fiscal_year INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS employee_bonuses (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    bonus_plan_id TEXT NOT NULL,
+    fiscal_year INTEGER NOT NULL,
+    target_amount INTEGER NOT NULL DEFAULT 0,
+    company_performance_factor REAL NOT NULL DEFAULT 1,
+    individual_performance_factor REAL NOT NULL DEFAULT 1,
+    calculated_amount INTEGER NOT NULL DEFAULT 0,
+    recommended_amount INTEGER NOT NULL DEFAULT 0,
+    approved_amount INTEGER,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    approved_by TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (bonus_plan_id) REFERENCES bonus_plans(id)
+);
+CREATE TABLE IF NOT EXISTS equity_grants (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    grant_number TEXT NOT NULL UNIQUE,
+    grant_type TEXT NOT NULL DEFAULT 'RSU',
+    shares INTEGER NOT NULL DEFAULT 0,
+    strike_price INTEGER NOT NULL DEFAULT 0,
+    grant_date TEXT NOT NULL,
+    vest_start_date TEXT NOT NULL,
+    vest_schedule TEXT NOT NULL DEFAULT '4-year cliff',
+    vesting_years INTEGER NOT NULL DEFAULT 4,
+    cliff_months INTEGER NOT NULL DEFAULT 12,
+    vested_shares INTEGER NOT NULL DEFAULT 0,
+    unvested_shares INTEGER NOT NULL DEFAULT 0,
+    forfeited_shares INTEGER NOT NULL DEFAULT 0,
+    expiration_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS equity_vesting_events (
+    id TEXT PRIMARY KEY,
+    grant_id TEXT NOT NULL,
+    vest_date TEXT NOT NULL,
+    shares INTEGER NOT NULL DEFAULT 0,
+    cumulative_shares INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    processed_at TEXT,
+    FOREIGN KEY (grant_id) REFERENCES equity_grants(id)
+);
+CREATE TABLE IF NOT EXISTS market_data (
+    id TEXT PRIMARY KEY,
+    source TEXT NOT NULL,
+    survey_date TEXT NOT NULL,
+    job_code TEXT NOT NULL,
+    job_title TEXT NOT NULL,
+    industry TEXT,
+    region TEXT,
+    company_size TEXT,
+    p10 INTEGER NOT NULL DEFAULT 0,
+    p25 INTEGER NOT NULL DEFAULT 0,
+    p50 INTEGER NOT NULL DEFAULT 0,
+    p75 INTEGER NOT NULL DEFAULT 0,
+    p90 INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS market_data_mappings (
+    id TEXT PRIMARY KEY,
+    position_id TEXT NOT NULL,
+    market_data_id TEXT NOT NULL,
+    match_quality REAL NOT NULL DEFAULT 0,
+    effective_date TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (market_data_id) REFERENCES market_data(id)
+);
+CREATE TABLE IF NOT EXISTS compensation_reviews (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL,
+    employee_id TEXT NOT NULL,
+    reviewer_id TEXT NOT NULL,
+    review_date TEXT NOT NULL,
+    current_salary INTEGER NOT NULL DEFAULT 0,
+    proposed_salary INTEGER NOT NULL DEFAULT 0,
+    proposed_increase_percent REAL NOT NULL DEFAULT 0,
+    performance_rating INTEGER,
+    potential_rating INTEGER,
+    compa_ratio_current REAL NOT NULL DEFAULT 0,
+    compa_ratio_proposed REAL NOT NULL DEFAULT 0,
+    merit_recommendation TEXT NOT NULL,
+    promotion_recommendation INTEGER NOT NULL DEFAULT 0,
+    retention_risk TEXT NOT NULL DEFAULT 'Low',
+    comments TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (plan_id) REFERENCES compensation_plans(id)
+);
+CREATE TABLE IF NOT EXISTS compensation_approvals (
+    id TEXT PRIMARY KEY,
+    review_id TEXT NOT NULL,
+    approver_id TEXT NOT NULL,
+    approval_level INTEGER NOT NULL DEFAULT 1,
+    original_amount INTEGER NOT NULL DEFAULT 0,
+    approved_amount INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    comments TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (review_id) REFERENCES compensation_reviews(id)
+);
+CREATE TABLE IF NOT EXISTS compensation_histories (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    change_type TEXT NOT NULL,
+    previous_value INTEGER NOT NULL DEFAULT 0,
+    new_value INTEGER NOT NULL DEFAULT 0,
+    change_amount INTEGER NOT NULL DEFAULT 0,
+    change_percent REAL NOT NULL DEFAULT 0,
+    reason TEXT NOT NULL,
+    approved_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS total_rewards_statements (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    statement_year INTEGER NOT NULL,
+    base_salary INTEGER NOT NULL DEFAULT 0,
+    variable_pay INTEGER NOT NULL DEFAULT 0,
+    benefits_value INTEGER NOT NULL DEFAULT 0,
+    equity_value INTEGER NOT NULL DEFAULT 0,
+    other_compensation INTEGER NOT NULL DEFAULT 0,
+    total_compensation INTEGER NOT NULL DEFAULT 0,
+    generated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pay_equity_analyses (
+    id TEXT PRIMARY KEY,
+    analysis_date TEXT NOT NULL,
+    analysis_type TEXT NOT NULL DEFAULT 'Gender',
+    group_type TEXT NOT NULL,
+    group_id TEXT,
+    employee_count INTEGER NOT NULL DEFAULT 0,
+    avg_salary_male INTEGER NOT NULL DEFAULT 0,
+    avg_salary_female INTEGER NOT NULL DEFAULT 0,
+    pay_gap_percent REAL NOT NULL DEFAULT 0,
+    adjusted_gap_percent REAL NOT NULL DEFAULT 0,
+    statistical_significance REAL NOT NULL DEFAULT 0,
+    findings TEXT,
+    recommendations TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS compensation_benchmarks (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT,
+    position_id TEXT,
+    current_salary INTEGER NOT NULL DEFAULT 0,
+    market_p50 INTEGER NOT NULL DEFAULT 0,
+    market_p75 INTEGER NOT NULL DEFAULT 0,
+    market_p90 INTEGER NOT NULL DEFAULT 0,
+    compa_ratio_p50 REAL NOT NULL DEFAULT 0,
+    percentile REAL NOT NULL DEFAULT 50,
+    benchmark_date TEXT NOT NULL,
+    market_data_source TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS retention_allowances (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    allowance_type TEXT NOT NULL DEFAULT 'Retention',
+    amount INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    clawback_period_months INTEGER NOT NULL DEFAULT 12,
+    payment_schedule TEXT NOT NULL DEFAULT 'LumpSum',
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS geographic_differentials (
+    id TEXT PRIMARY KEY,
+    location_id TEXT NOT NULL,
+    location_name TEXT NOT NULL,
+    differential_percent REAL NOT NULL DEFAULT 0,
+    effective_date TEXT NOT NULL,
+    end_date TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_configurations_template ON product_configurations(template_id);
+CREATE INDEX IF NOT EXISTS idx_configured_quotes_customer ON configured_quotes(customer_id);
+CREATE INDEX IF NOT EXISTS idx_contracts_status ON contracts(status);
+CREATE INDEX IF NOT EXISTS idx_contracts_end_date ON contracts(end_date);
+CREATE INDEX IF NOT EXISTS idx_commission_calcs_rep ON commission_calculations(sales_rep_id);
+CREATE INDEX IF NOT EXISTS idx_commission_calcs_status ON commission_calculations(status);
+CREATE INDEX IF NOT EXISTS idx_spend_transactions_vendor ON spend_transactions(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_spend_transactions_date ON spend_transactions(transaction_date);
+CREATE INDEX IF NOT EXISTS idx_mps_items_product ON mps_items(product_id);
+CREATE INDEX IF NOT EXISTS idx_mrp_items_product ON mrp_items(product_id);
+CREATE INDEX IF NOT EXISTS idx_schedule_ops_resource ON schedule_operations(resource_id);
+CREATE INDEX IF NOT EXISTS idx_employee_comp_employee ON employee_compensations(employee_id);
+CREATE INDEX IF NOT EXISTS idx_comp_adjustments_employee ON compensation_adjustments(employee_id);
+CREATE TABLE IF NOT EXISTS credit_notes (
+    id TEXT PRIMARY KEY,
+    credit_note_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL REFERENCES customers(id),
+    invoice_id TEXT REFERENCES invoices(id),
+    credit_note_date TEXT NOT NULL,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    subtotal_currency TEXT DEFAULT 'USD',
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    tax_currency TEXT DEFAULT 'USD',
+    total INTEGER NOT NULL DEFAULT 0,
+    total_currency TEXT DEFAULT 'USD',
+    reason TEXT NOT NULL DEFAULT '"Return"',
+    notes TEXT,
+    status TEXT NOT NULL DEFAULT '"Draft"',
+    applied_amount INTEGER NOT NULL DEFAULT 0,
+    applied_currency TEXT DEFAULT 'USD',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS credit_note_lines (
+    id TEXT PRIMARY KEY,
+    credit_note_id TEXT NOT NULL REFERENCES credit_notes(id) ON DELETE CASCADE,
+    product_id TEXT REFERENCES products(id),
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    unit_price_currency TEXT DEFAULT 'USD',
+    line_total INTEGER NOT NULL,
+    line_total_currency TEXT DEFAULT 'USD'
+);
+CREATE TABLE IF NOT EXISTS credit_note_applications (
+    id TEXT PRIMARY KEY,
+    credit_note_id TEXT NOT NULL REFERENCES credit_notes(id) ON DELETE CASCADE,
+    invoice_id TEXT NOT NULL REFERENCES invoices(id),
+    amount INTEGER NOT NULL,
+    currency TEXT DEFAULT 'USD',
+    applied_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_vehicles (
+    id TEXT PRIMARY KEY,
+    vehicle_number TEXT NOT NULL UNIQUE,
+    vehicle_type TEXT NOT NULL,
+    license_plate TEXT NOT NULL,
+    vin TEXT,
+    make TEXT,
+    model TEXT,
+    year INTEGER,
+    capacity_weight REAL NOT NULL,
+    capacity_volume REAL NOT NULL,
+    capacity_unit TEXT NOT NULL,
+    fuel_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Available',
+    current_location_lat REAL,
+    current_location_lng REAL,
+    odometer REAL DEFAULT 0,
+    last_maintenance TEXT,
+    next_maintenance TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_drivers (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT,
+    driver_code TEXT NOT NULL UNIQUE,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    phone TEXT NOT NULL,
+    email TEXT,
+    license_number TEXT NOT NULL,
+    license_class TEXT NOT NULL,
+    license_expiry TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Available',
+    current_vehicle_id TEXT,
+    current_location_lat REAL,
+    current_location_lng REAL,
+    hours_driven_today REAL DEFAULT 0,
+    hours_available REAL DEFAULT 11,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_carrier_contracts (
+    id TEXT PRIMARY KEY,
+    carrier_id TEXT NOT NULL,
+    contract_number TEXT NOT NULL UNIQUE,
+    contract_name TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    payment_terms TEXT NOT NULL,
+    fuel_surcharge_percent REAL DEFAULT 0,
+    accessorial_rates TEXT,
+    volume_commitment REAL,
+    volume_discount_tiers TEXT,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_freight_rates (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    origin_zone TEXT NOT NULL,
+    destination_zone TEXT NOT NULL,
+    service_type TEXT NOT NULL,
+    rate_type TEXT NOT NULL,
+    base_rate INTEGER NOT NULL,
+    per_mile_rate INTEGER DEFAULT 0,
+    per_kg_rate INTEGER DEFAULT 0,
+    per_pallet_rate INTEGER DEFAULT 0,
+    min_charge INTEGER DEFAULT 0,
+    max_charge INTEGER,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    effective_date TEXT NOT NULL,
+    expiry_date TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_loads (
+    id TEXT PRIMARY KEY,
+    load_number TEXT NOT NULL UNIQUE,
+    carrier_id TEXT,
+    driver_id TEXT,
+    vehicle_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Planned',
+    origin_name TEXT NOT NULL,
+    origin_street TEXT NOT NULL,
+    origin_city TEXT NOT NULL,
+    origin_state TEXT,
+    origin_postal_code TEXT NOT NULL,
+    origin_country TEXT NOT NULL,
+    origin_lat REAL,
+    origin_lng REAL,
+    destination_name TEXT NOT NULL,
+    destination_street TEXT NOT NULL,
+    destination_city TEXT NOT NULL,
+    destination_state TEXT,
+    destination_postal_code TEXT NOT NULL,
+    destination_country TEXT NOT NULL,
+    destination_lat REAL,
+    destination_lng REAL,
+    planned_pickup TEXT NOT NULL,
+    actual_pickup TEXT,
+    planned_delivery TEXT NOT NULL,
+    actual_delivery TEXT,
+    total_weight REAL DEFAULT 0,
+    total_volume REAL DEFAULT 0,
+    total_pallets INTEGER DEFAULT 0,
+    total_pieces INTEGER DEFAULT 0,
+    freight_charge INTEGER DEFAULT 0,
+    fuel_surcharge INTEGER DEFAULT 0,
+    accessorial_charges INTEGER DEFAULT 0,
+    total_charge INTEGER DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    distance_miles REAL,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_load_stops (
+    id TEXT PRIMARY KEY,
+    load_id TEXT NOT NULL,
+    stop_number INTEGER NOT NULL,
+    stop_type TEXT NOT NULL,
+    location_name TEXT NOT NULL,
+    street TEXT NOT NULL,
+    city TEXT NOT NULL,
+    state TEXT,
+    postal_code TEXT NOT NULL,
+    country TEXT NOT NULL,
+    lat REAL,
+    lng REAL,
+    planned_arrival TEXT NOT NULL,
+    actual_arrival TEXT,
+    planned_departure TEXT NOT NULL,
+    actual_departure TEXT,
+    appointment_number TEXT,
+    instructions TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_load_items (
+    id TEXT PRIMARY KEY,
+    load_id TEXT NOT NULL,
+    stop_id TEXT NOT NULL,
+    shipment_id TEXT,
+    product_id TEXT,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    weight REAL NOT NULL,
+    volume REAL NOT NULL,
+    pallets INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_routes (
+    id TEXT PRIMARY KEY,
+    route_number TEXT NOT NULL UNIQUE,
+    route_name TEXT NOT NULL,
+    driver_id TEXT,
+    vehicle_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Planned',
+    planned_start TEXT NOT NULL,
+    actual_start TEXT,
+    planned_end TEXT NOT NULL,
+    actual_end TEXT,
+    total_distance REAL DEFAULT 0,
+    total_duration_minutes INTEGER DEFAULT 0,
+    total_stops INTEGER DEFAULT 0,
+    completed_stops INTEGER DEFAULT 0,
+    total_weight REAL DEFAULT 0,
+    optimization_score REAL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_route_stops (
+    id TEXT PRIMARY KEY,
+    route_id TEXT NOT NULL,
+    stop_sequence INTEGER NOT NULL,
+    stop_type TEXT NOT NULL,
+    load_id TEXT,
+    location_name TEXT NOT NULL,
+    street TEXT NOT NULL,
+    city TEXT NOT NULL,
+    state TEXT,
+    postal_code TEXT NOT NULL,
+    country TEXT NOT NULL,
+    lat REAL NOT NULL,
+    lng REAL NOT NULL,
+    planned_arrival TEXT NOT NULL,
+    actual_arrival TEXT,
+    planned_duration_minutes INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Planned',
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_freight_invoices (
+    id TEXT PRIMARY KEY,
+    invoice_number TEXT NOT NULL UNIQUE,
+    carrier_id TEXT NOT NULL,
+    load_id TEXT,
+    invoice_date TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    base_freight INTEGER NOT NULL,
+    fuel_surcharge INTEGER DEFAULT 0,
+    accessorial_charges INTEGER DEFAULT 0,
+    tax INTEGER DEFAULT 0,
+    total_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    expected_amount INTEGER NOT NULL,
+    variance_amount INTEGER DEFAULT 0,
+    variance_reason TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    paid_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_freight_invoice_lines (
+    id TEXT PRIMARY KEY,
+    invoice_id TEXT NOT NULL,
+    line_number INTEGER NOT NULL,
+    charge_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    quantity REAL NOT NULL,
+    unit TEXT NOT NULL,
+    rate INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    expected_amount INTEGER NOT NULL,
+    variance INTEGER DEFAULT 0,
+    currency TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_fuel_purchases (
+    id TEXT PRIMARY KEY,
+    vehicle_id TEXT,
+    driver_id TEXT,
+    purchase_date TEXT NOT NULL,
+    vendor_name TEXT NOT NULL,
+    location TEXT NOT NULL,
+    fuel_type TEXT NOT NULL,
+    quantity REAL NOT NULL,
+    unit TEXT NOT NULL,
+    price_per_unit INTEGER NOT NULL,
+    total_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    odometer REAL NOT NULL,
+    receipt_number TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tms_accessorial_charges (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    default_rate INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_items (
+    id TEXT PRIMARY KEY,
+    item_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    category TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    version TEXT NOT NULL DEFAULT '1.0',
+    revision INTEGER NOT NULL DEFAULT 1,
+    lifecycle_phase TEXT NOT NULL DEFAULT 'Concept',
+    owner_id TEXT,
+    product_id TEXT,
+    parent_item_id TEXT,
+    effective_date TEXT,
+    obsolete_date TEXT,
+    security_classification TEXT NOT NULL DEFAULT 'Internal',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_documents (
+    id TEXT PRIMARY KEY,
+    document_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    document_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    version TEXT NOT NULL DEFAULT '1.0',
+    revision INTEGER NOT NULL DEFAULT 1,
+    file_path TEXT,
+    file_size INTEGER,
+    file_format TEXT,
+    checksum TEXT,
+    owner_id TEXT,
+    checked_out_by TEXT,
+    checked_out_at TEXT,
+    effective_date TEXT,
+    obsolete_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_boms (
+    id TEXT PRIMARY KEY,
+    bom_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    item_id TEXT NOT NULL,
+    version TEXT NOT NULL DEFAULT '1.0',
+    revision INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    bom_type TEXT NOT NULL,
+    quantity REAL NOT NULL,
+    unit_of_measure TEXT NOT NULL,
+    effective_date TEXT,
+    obsolete_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_bom_lines (
+    id TEXT PRIMARY KEY,
+    bom_id TEXT NOT NULL,
+    item_id TEXT NOT NULL,
+    line_number INTEGER NOT NULL,
+    quantity REAL NOT NULL,
+    unit_of_measure TEXT NOT NULL,
+    find_number INTEGER,
+    reference_designator TEXT,
+    substitute_item_id TEXT,
+    is_phantom INTEGER DEFAULT 0,
+    sort_order INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_ecrs (
+    id TEXT PRIMARY KEY,
+    ecr_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    priority TEXT NOT NULL DEFAULT 'Medium',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    change_type TEXT NOT NULL,
+    requested_by TEXT NOT NULL,
+    submitted_at TEXT,
+    target_date TEXT,
+    implemented_date TEXT,
+    impact_assessment TEXT,
+    cost_estimate INTEGER,
+    currency TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    rejected_reason TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_ecns (
+    id TEXT PRIMARY KEY,
+    ecn_number TEXT NOT NULL UNIQUE,
+    ecr_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    effective_date TEXT NOT NULL,
+    implementation_instructions TEXT,
+    created_by TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_ecn_affected_items (
+    id TEXT PRIMARY KEY,
+    ecn_id TEXT NOT NULL,
+    item_id TEXT NOT NULL,
+    old_revision TEXT NOT NULL,
+    new_revision TEXT NOT NULL,
+    old_version TEXT NOT NULL,
+    new_version TEXT NOT NULL,
+    change_description TEXT NOT NULL,
+    disposition TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_workflows (
+    id TEXT PRIMARY KEY,
+    workflow_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    workflow_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    initiated_by TEXT NOT NULL,
+    current_step INTEGER DEFAULT 0,
+    total_steps INTEGER NOT NULL,
+    started_at TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_workflow_steps (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    step_number INTEGER NOT NULL,
+    step_name TEXT NOT NULL,
+    step_type TEXT NOT NULL,
+    assignee_id TEXT,
+    role_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    due_date TEXT,
+    completed_at TEXT,
+    completed_by TEXT,
+    comments TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_cad_files (
+    id TEXT PRIMARY KEY,
+    document_id TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_size INTEGER NOT NULL,
+    cad_system TEXT NOT NULL,
+    format TEXT NOT NULL,
+    version TEXT NOT NULL,
+    thumbnail_path TEXT,
+    geometry_data TEXT,
+    metadata TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_specifications (
+    id TEXT PRIMARY KEY,
+    spec_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    item_id TEXT,
+    spec_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    version TEXT NOT NULL DEFAULT '1.0',
+    revision INTEGER NOT NULL DEFAULT 1,
+    parameters TEXT NOT NULL,
+    owner_id TEXT,
+    effective_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_spec_parameters (
+    id TEXT PRIMARY KEY,
+    spec_id TEXT NOT NULL,
+    parameter_name TEXT NOT NULL,
+    parameter_type TEXT NOT NULL,
+    target_value TEXT NOT NULL,
+    min_value TEXT,
+    max_value TEXT,
+    unit TEXT,
+    test_method TEXT,
+    is_critical INTEGER DEFAULT 0,
+    sort_order INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_design_reviews (
+    id TEXT PRIMARY KEY,
+    review_number TEXT NOT NULL UNIQUE,
+    item_id TEXT NOT NULL,
+    review_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Scheduled',
+    scheduled_date TEXT NOT NULL,
+    conducted_date TEXT,
+    facilitator_id TEXT,
+    location TEXT,
+    outcome TEXT,
+    action_items TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_design_review_attendees (
+    id TEXT PRIMARY KEY,
+    review_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    role TEXT NOT NULL,
+    attended INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_compliance_requirements (
+    id TEXT PRIMARY KEY,
+    requirement_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    regulation TEXT NOT NULL,
+    category TEXT NOT NULL,
+    mandatory INTEGER DEFAULT 1,
+    verification_method TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS plm_item_compliances (
+    id TEXT PRIMARY KEY,
+    item_id TEXT NOT NULL,
+    requirement_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    certified INTEGER DEFAULT 0,
+    certification_date TEXT,
+    certification_expiry TEXT,
+    certifying_body TEXT,
+    certificate_number TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_entities (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_code TEXT NOT NULL,
+    entity_name TEXT NOT NULL,
+    source_system TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    golden_record_id TEXT,
+    quality_score INTEGER DEFAULT 0,
+    completeness_score INTEGER DEFAULT 0,
+    accuracy_score INTEGER DEFAULT 0,
+    timeliness_score INTEGER DEFAULT 0,
+    consistency_score INTEGER DEFAULT 0,
+    last_verified TEXT,
+    next_verification TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(entity_type, source_system, source_id)
+);
+CREATE TABLE IF NOT EXISTS mdm_golden_records (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    golden_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    attributes TEXT NOT NULL,
+    source_count INTEGER DEFAULT 0,
+    confidence_score INTEGER DEFAULT 0,
+    steward_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    version INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_golden_record_sources (
+    id TEXT PRIMARY KEY,
+    golden_record_id TEXT NOT NULL,
+    source_entity_id TEXT NOT NULL,
+    match_score INTEGER NOT NULL,
+    is_primary INTEGER DEFAULT 0,
+    contributed_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_quality_rules (
+    id TEXT PRIMARY KEY,
+    rule_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    entity_type TEXT NOT NULL,
+    field_name TEXT NOT NULL,
+    rule_type TEXT NOT NULL,
+    rule_expression TEXT NOT NULL,
+    severity TEXT NOT NULL DEFAULT 'Medium',
+    is_active INTEGER DEFAULT 1,
+    auto_fix INTEGER DEFAULT 0,
+    fix_expression TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_quality_violations (
+    id TEXT PRIMARY KEY,
+    rule_id TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    field_name TEXT NOT NULL,
+    current_value TEXT,
+    expected_value TEXT,
+    severity TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Open',
+    detected_at TEXT NOT NULL,
+    resolved_at TEXT,
+    resolved_by TEXT,
+    resolution_notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_match_rules (
+    id TEXT PRIMARY KEY,
+    rule_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    entity_type TEXT NOT NULL,
+    match_type TEXT NOT NULL,
+    blocking_rules TEXT NOT NULL,
+    matching_rules TEXT NOT NULL,
+    threshold_score INTEGER NOT NULL DEFAULT 80,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_match_results (
+    id TEXT PRIMARY KEY,
+    rule_id TEXT NOT NULL,
+    entity1_id TEXT NOT NULL,
+    entity2_id TEXT NOT NULL,
+    match_score INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    matched_at TEXT NOT NULL,
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    decision_notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_data_domains (
+    id TEXT PRIMARY KEY,
+    domain_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    parent_domain_id TEXT,
+    owner_id TEXT,
+    steward_id TEXT,
+    data_classification TEXT NOT NULL DEFAULT 'Internal',
+    retention_policy TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_data_attributes (
+    id TEXT PRIMARY KEY,
+    domain_id TEXT NOT NULL,
+    attribute_code TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    data_type TEXT NOT NULL,
+    max_length INTEGER,
+    is_required INTEGER DEFAULT 0,
+    is_unique INTEGER DEFAULT 0,
+    default_value TEXT,
+    validation_regex TEXT,
+    allowed_values TEXT,
+    business_rules TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(domain_id, attribute_code)
+);
+CREATE TABLE IF NOT EXISTS mdm_data_stewards (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    domain_id TEXT,
+    entity_types TEXT NOT NULL,
+    responsibilities TEXT NOT NULL,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_data_lineage (
+    id TEXT PRIMARY KEY,
+    source_entity_id TEXT NOT NULL,
+    target_entity_id TEXT NOT NULL,
+    transformation_type TEXT NOT NULL,
+    transformation_logic TEXT,
+    flow_type TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_data_workflows (
+    id TEXT PRIMARY KEY,
+    workflow_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    entity_type TEXT NOT NULL,
+    workflow_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    initiated_by TEXT,
+    current_step INTEGER DEFAULT 0,
+    total_steps INTEGER NOT NULL,
+    started_at TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_data_workflow_steps (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    step_number INTEGER NOT NULL,
+    step_name TEXT NOT NULL,
+    action_type TEXT NOT NULL,
+    assignee_id TEXT,
+    role_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    due_date TEXT,
+    completed_at TEXT,
+    completed_by TEXT,
+    comments TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_duplicate_records (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    primary_entity_id TEXT NOT NULL,
+    duplicate_entity_id TEXT NOT NULL,
+    similarity_score INTEGER NOT NULL,
+    matched_fields TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    merge_initiated_by TEXT,
+    merge_initiated_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_import_jobs (
+    id TEXT PRIMARY KEY,
+    job_name TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    source_file TEXT NOT NULL,
+    total_records INTEGER DEFAULT 0,
+    processed_records INTEGER DEFAULT 0,
+    success_records INTEGER DEFAULT 0,
+    failed_records INTEGER DEFAULT 0,
+    duplicate_records INTEGER DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    started_at TEXT,
+    completed_at TEXT,
+    error_log TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_export_jobs (
+    id TEXT PRIMARY KEY,
+    job_name TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    filter_criteria TEXT,
+    export_format TEXT NOT NULL,
+    output_file TEXT,
+    total_records INTEGER DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    started_at TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mdm_reference_data (
+    id TEXT PRIMARY KEY,
+    category TEXT NOT NULL,
+    code TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    parent_code TEXT,
+    sort_order INTEGER DEFAULT 0,
+    is_active INTEGER DEFAULT 1,
+    effective_date TEXT,
+    expiry_date TEXT,
+    attributes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(category, code)
+);
+CREATE TABLE IF NOT EXISTS fsm_service_orders (
+    id TEXT PRIMARY KEY,
+    order_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    contact_name TEXT NOT NULL,
+    contact_phone TEXT NOT NULL,
+    contact_email TEXT,
+    service_address TEXT NOT NULL,
+    service_city TEXT NOT NULL,
+    service_state TEXT,
+    service_postal_code TEXT NOT NULL,
+    service_country TEXT NOT NULL,
+    service_lat REAL,
+    service_lng REAL,
+    work_type TEXT NOT NULL,
+    priority TEXT NOT NULL DEFAULT 'Medium',
+    status TEXT NOT NULL DEFAULT 'Scheduled',
+    description TEXT NOT NULL,
+    asset_id TEXT,
+    asset_serial TEXT,
+    contract_id TEXT,
+    sla_id TEXT,
+    assigned_technician_id TEXT,
+    scheduled_date TEXT,
+    scheduled_start TEXT,
+    scheduled_end TEXT,
+    actual_start TEXT,
+    actual_end TEXT,
+    travel_time_minutes INTEGER,
+    work_duration_minutes INTEGER,
+    resolution_notes TEXT,
+    customer_signature TEXT,
+    customer_rating INTEGER,
+    customer_feedback TEXT,
+    total_charges INTEGER DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_order_tasks (
+    id TEXT PRIMARY KEY,
+    service_order_id TEXT NOT NULL,
+    task_number INTEGER NOT NULL,
+    task_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    estimated_duration_minutes INTEGER NOT NULL,
+    actual_duration_minutes INTEGER,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    completed_at TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_technicians (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT,
+    technician_code TEXT NOT NULL UNIQUE,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    phone TEXT NOT NULL,
+    email TEXT,
+    status TEXT NOT NULL DEFAULT 'Available',
+    skills TEXT NOT NULL,
+    certifications TEXT NOT NULL,
+    home_location_lat REAL,
+    home_location_lng REAL,
+    current_location_lat REAL,
+    current_location_lng REAL,
+    current_order_id TEXT,
+    service_region TEXT,
+    hourly_rate INTEGER NOT NULL,
+    overtime_rate INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    work_start_time TEXT,
+    work_end_time TEXT,
+    working_days TEXT NOT NULL DEFAULT 'Mon,Tue,Wed,Thu,Fri',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_technician_availability (
+    id TEXT PRIMARY KEY,
+    technician_id TEXT NOT NULL,
+    date TEXT NOT NULL,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Available',
+    reason TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_territories (
+    id TEXT PRIMARY KEY,
+    territory_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    parent_territory_id TEXT,
+    boundary_type TEXT NOT NULL,
+    boundary_data TEXT NOT NULL,
+    manager_id TEXT,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_technician_territories (
+    id TEXT PRIMARY KEY,
+    technician_id TEXT NOT NULL,
+    territory_id TEXT NOT NULL,
+    is_primary INTEGER DEFAULT 0,
+    effective_date TEXT NOT NULL,
+    expiry_date TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_appointments (
+    id TEXT PRIMARY KEY,
+    appointment_number TEXT NOT NULL UNIQUE,
+    service_order_id TEXT NOT NULL,
+    technician_id TEXT NOT NULL,
+    scheduled_start TEXT NOT NULL,
+    scheduled_end TEXT NOT NULL,
+    actual_start TEXT,
+    actual_end TEXT,
+    status TEXT NOT NULL DEFAULT 'Scheduled',
+    confirmation_status TEXT NOT NULL DEFAULT 'Pending',
+    reminder_sent INTEGER DEFAULT 0,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_routes (
+    id TEXT PRIMARY KEY,
+    route_number TEXT NOT NULL UNIQUE,
+    technician_id TEXT NOT NULL,
+    route_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Planned',
+    total_appointments INTEGER DEFAULT 0,
+    completed_appointments INTEGER DEFAULT 0,
+    total_distance REAL DEFAULT 0,
+    total_duration_minutes INTEGER DEFAULT 0,
+    optimization_score REAL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_route_stops (
+    id TEXT PRIMARY KEY,
+    route_id TEXT NOT NULL,
+    appointment_id TEXT NOT NULL,
+    stop_sequence INTEGER NOT NULL,
+    planned_arrival TEXT NOT NULL,
+    actual_arrival TEXT,
+    planned_departure TEXT NOT NULL,
+    actual_departure TEXT,
+    travel_distance REAL NOT NULL,
+    travel_time_minutes INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Planned',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_parts (
+    id TEXT PRIMARY KEY,
+    service_order_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    total_price INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    disposition TEXT NOT NULL DEFAULT 'Used',
+    returned INTEGER DEFAULT 0,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_time_entries (
+    id TEXT PRIMARY KEY,
+    service_order_id TEXT NOT NULL,
+    technician_id TEXT NOT NULL,
+    entry_date TEXT NOT NULL,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    hours REAL NOT NULL,
+    overtime_hours REAL DEFAULT 0,
+    work_type TEXT NOT NULL,
+    billable INTEGER DEFAULT 1,
+    rate INTEGER NOT NULL,
+    total_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    notes TEXT,
+    approved INTEGER DEFAULT 0,
+    approved_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_expenses (
+    id TEXT PRIMARY KEY,
+    service_order_id TEXT NOT NULL,
+    technician_id TEXT NOT NULL,
+    expense_type TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    description TEXT,
+    receipt_url TEXT,
+    approved INTEGER DEFAULT 0,
+    approved_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_checklists (
+    id TEXT PRIMARY KEY,
+    service_order_id TEXT NOT NULL,
+    checklist_type TEXT NOT NULL,
+    name TEXT NOT NULL,
+    completed INTEGER DEFAULT 0,
+    completed_at TEXT,
+    completed_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_checklist_items (
+    id TEXT PRIMARY KEY,
+    checklist_id TEXT NOT NULL,
+    item_number INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    is_required INTEGER DEFAULT 1,
+    response_type TEXT NOT NULL DEFAULT 'Boolean',
+    response_value TEXT,
+    notes TEXT,
+    completed INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_service_contracts (
+    id TEXT PRIMARY KEY,
+    contract_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    contract_name TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    contract_type TEXT NOT NULL,
+    coverage_type TEXT NOT NULL,
+    response_time_hours INTEGER NOT NULL,
+    resolution_time_hours INTEGER NOT NULL,
+    visit_limit INTEGER,
+    visits_used INTEGER DEFAULT 0,
+    coverage_hours TEXT NOT NULL,
+    coverage_days TEXT NOT NULL,
+    annual_fee INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_dispatch_rules (
+    id TEXT PRIMARY KEY,
+    rule_name TEXT NOT NULL,
+    description TEXT,
+    priority INTEGER NOT NULL,
+    conditions TEXT NOT NULL,
+    actions TEXT NOT NULL,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_technician_skills (
+    id TEXT PRIMARY KEY,
+    skill_code TEXT NOT NULL UNIQUE,
+    skill_name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    description TEXT,
+    proficiency_levels TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fsm_technician_skill_assignments (
+    id TEXT PRIMARY KEY,
+    technician_id TEXT NOT NULL,
+    skill_id TEXT NOT NULL,
+    proficiency_level INTEGER NOT NULL,
+    certified INTEGER DEFAULT 0,
+    certified_date TEXT,
+    expiry_date TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_promotions (
+    id TEXT PRIMARY KEY,
+    promotion_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    promotion_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    customer_id TEXT,
+    customer_group_id TEXT,
+    product_id TEXT,
+    product_group_id TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    planned_budget INTEGER NOT NULL,
+    committed_budget INTEGER DEFAULT 0,
+    spent_budget INTEGER DEFAULT 0,
+    accrued_budget INTEGER DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    discount_percent REAL,
+    discount_amount INTEGER,
+    buy_quantity INTEGER,
+    get_quantity INTEGER,
+    max_redemptions INTEGER,
+    redemptions_count INTEGER DEFAULT 0,
+    forecasted_sales INTEGER,
+    actual_sales INTEGER,
+    roi REAL,
+    owner_id TEXT,
+    approval_status TEXT NOT NULL DEFAULT 'Pending',
+    approved_by TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_promotion_products (
+    id TEXT PRIMARY KEY,
+    promotion_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    discount_percent REAL,
+    discount_amount INTEGER,
+    buy_qty INTEGER,
+    get_qty INTEGER,
+    max_qty INTEGER,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_promotion_customers (
+    id TEXT PRIMARY KEY,
+    promotion_id TEXT NOT NULL,
+    customer_id TEXT,
+    customer_group_id TEXT,
+    territory_id TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_trade_funds (
+    id TEXT PRIMARY KEY,
+    fund_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    fund_type TEXT NOT NULL,
+    customer_id TEXT,
+    fiscal_year INTEGER NOT NULL,
+    total_budget INTEGER NOT NULL,
+    committed_amount INTEGER DEFAULT 0,
+    spent_amount INTEGER DEFAULT 0,
+    available_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_fund_transactions (
+    id TEXT PRIMARY KEY,
+    fund_id TEXT NOT NULL,
+    promotion_id TEXT,
+    transaction_type TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    reference_number TEXT,
+    description TEXT,
+    transaction_date TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_rebate_agreements (
+    id TEXT PRIMARY KEY,
+    agreement_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    agreement_type TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    basis TEXT NOT NULL,
+    calculation_method TEXT NOT NULL,
+    payment_terms TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    total_eligible_sales INTEGER DEFAULT 0,
+    total_rebate_earned INTEGER DEFAULT 0,
+    total_rebate_paid INTEGER DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_rebate_tiers (
+    id TEXT PRIMARY KEY,
+    agreement_id TEXT NOT NULL,
+    tier_number INTEGER NOT NULL,
+    min_quantity REAL NOT NULL,
+    max_quantity REAL,
+    min_value INTEGER NOT NULL,
+    max_value INTEGER,
+    rebate_percent REAL NOT NULL,
+    rebate_amount INTEGER,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_rebate_products (
+    id TEXT PRIMARY KEY,
+    agreement_id TEXT NOT NULL,
+    product_id TEXT,
+    product_group_id TEXT,
+    specific_rate REAL,
+    specific_amount INTEGER,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_rebate_accruals (
+    id TEXT PRIMARY KEY,
+    agreement_id TEXT NOT NULL,
+    sales_order_id TEXT,
+    invoice_id TEXT,
+    product_id TEXT,
+    sales_amount INTEGER NOT NULL,
+    rebate_rate REAL NOT NULL,
+    rebate_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    accrual_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Accrued',
+    paid_amount INTEGER DEFAULT 0,
+    remaining_amount INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_rebate_payments (
+    id TEXT PRIMARY KEY,
+    payment_number TEXT NOT NULL UNIQUE,
+    agreement_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    payment_date TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    payment_method TEXT NOT NULL,
+    reference_number TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_rebate_payment_lines (
+    id TEXT PRIMARY KEY,
+    payment_id TEXT NOT NULL,
+    accrual_id TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_chargebacks (
+    id TEXT PRIMARY KEY,
+    chargeback_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    invoice_id TEXT,
+    promotion_id TEXT,
+    chargeback_date TEXT NOT NULL,
+    amount_claimed INTEGER NOT NULL,
+    amount_approved INTEGER DEFAULT 0,
+    amount_rejected INTEGER DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Submitted',
+    claim_type TEXT NOT NULL,
+    description TEXT,
+    rejection_reason TEXT,
+    submitted_by TEXT,
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    paid_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_chargeback_lines (
+    id TEXT PRIMARY KEY,
+    chargeback_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    claimed_amount INTEGER NOT NULL,
+    approved_amount INTEGER DEFAULT 0,
+    rejected_amount INTEGER DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_chargeback_documents (
+    id TEXT PRIMARY KEY,
+    chargeback_id TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    uploaded_by TEXT,
+    uploaded_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_promotion_performance (
+    id TEXT PRIMARY KEY,
+    promotion_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    baseline_sales INTEGER NOT NULL,
+    incremental_sales INTEGER NOT NULL,
+    total_sales INTEGER NOT NULL,
+    units_sold INTEGER NOT NULL,
+    promotion_cost INTEGER NOT NULL,
+    roi_percent REAL NOT NULL,
+    lift_percent REAL NOT NULL,
+    cannibalization INTEGER,
+    forward_buy INTEGER,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_promotion_plans (
+    id TEXT PRIMARY KEY,
+    plan_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    fiscal_year INTEGER NOT NULL,
+    customer_id TEXT,
+    customer_group_id TEXT,
+    total_budget INTEGER NOT NULL,
+    allocated_budget INTEGER DEFAULT 0,
+    spent_budget INTEGER DEFAULT 0,
+    remaining_budget INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    owner_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_promotion_plan_lines (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL,
+    promotion_id TEXT NOT NULL,
+    quarter INTEGER NOT NULL,
+    planned_amount INTEGER NOT NULL,
+    actual_amount INTEGER DEFAULT 0,
+    variance INTEGER DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_customer_trade_profiles (
+    id TEXT PRIMARY KEY,
+    customer_id TEXT NOT NULL UNIQUE,
+    trade_class TEXT NOT NULL,
+    annual_volume INTEGER,
+    growth_rate REAL,
+    avg_promotion_response REAL,
+    preferred_promotion_type TEXT,
+    credit_limit INTEGER,
+    payment_terms TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_ship_and_debits (
+    id TEXT PRIMARY KEY,
+    sad_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    authorized_price INTEGER NOT NULL,
+    list_price INTEGER NOT NULL,
+    authorized_discount INTEGER NOT NULL,
+    quantity_authorized INTEGER NOT NULL,
+    quantity_shipped INTEGER DEFAULT 0,
+    quantity_debited INTEGER DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tpm_price_protections (
+    id TEXT PRIMARY KEY,
+    pp_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    product_id TEXT,
+    product_group_id TEXT,
+    old_price INTEGER NOT NULL,
+    new_price INTEGER NOT NULL,
+    price_reduction INTEGER NOT NULL,
+    effective_date TEXT NOT NULL,
+    inventory_on_hand INTEGER NOT NULL,
+    claim_amount INTEGER NOT NULL,
+    approved_amount INTEGER DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Submitted',
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wms_storage_locations (
+    id TEXT PRIMARY KEY,
+    warehouse_id TEXT NOT NULL,
+    zone TEXT NOT NULL,
+    aisle TEXT NOT NULL,
+    rack TEXT NOT NULL,
+    shelf TEXT NOT NULL,
+    bin TEXT NOT NULL,
+    location_type TEXT NOT NULL,
+    capacity INTEGER NOT NULL,
+    occupied INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_wms_locations_warehouse ON wms_storage_locations(warehouse_id);
+CREATE INDEX IF NOT EXISTS idx_wms_locations_zone ON wms_storage_locations(zone);
+CREATE TABLE IF NOT EXISTS wms_zones (
+    id TEXT PRIMARY KEY,
+    warehouse_id TEXT NOT NULL,
+    zone_code TEXT NOT NULL,
+    zone_name TEXT NOT NULL,
+    zone_type TEXT NOT NULL,
+    temperature_controlled INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wms_putaway_tasks (
+    id TEXT PRIMARY KEY,
+    receipt_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    source_location TEXT,
+    suggested_location TEXT,
+    actual_location TEXT,
+    status TEXT NOT NULL,
+    priority INTEGER NOT NULL,
+    assigned_to TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wms_pick_tasks (
+    id TEXT PRIMARY KEY,
+    wave_id TEXT,
+    order_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    location_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    picked_quantity INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    pick_type TEXT NOT NULL,
+    priority INTEGER NOT NULL,
+    assigned_to TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wms_waves (
+    id TEXT PRIMARY KEY,
+    wave_number TEXT NOT NULL UNIQUE,
+    warehouse_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    planned_date TEXT NOT NULL,
+    released_at TEXT,
+    completed_at TEXT,
+    total_picks INTEGER NOT NULL DEFAULT 0,
+    completed_picks INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wms_crossdock_orders (
+    id TEXT PRIMARY KEY,
+    inbound_shipment_id TEXT NOT NULL,
+    outbound_order_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    dock_location TEXT,
+    processed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wms_receiving_receipts (
+    id TEXT PRIMARY KEY,
+    receipt_number TEXT NOT NULL UNIQUE,
+    warehouse_id TEXT NOT NULL,
+    po_id TEXT,
+    carrier TEXT,
+    tracking_number TEXT,
+    status TEXT NOT NULL,
+    received_by TEXT,
+    received_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wms_receipt_lines (
+    id TEXT PRIMARY KEY,
+    receipt_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    expected_qty INTEGER NOT NULL,
+    received_qty INTEGER NOT NULL,
+    damaged_qty INTEGER NOT NULL DEFAULT 0,
+    lot_number TEXT,
+    expiry_date TEXT,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wms_shipping_manifests (
+    id TEXT PRIMARY KEY,
+    manifest_number TEXT NOT NULL UNIQUE,
+    warehouse_id TEXT NOT NULL,
+    carrier TEXT NOT NULL,
+    service_level TEXT NOT NULL,
+    status TEXT NOT NULL,
+    total_packages INTEGER NOT NULL,
+    total_weight REAL NOT NULL,
+    shipped_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wms_cycle_counts (
+    id TEXT PRIMARY KEY,
+    count_number TEXT NOT NULL UNIQUE,
+    warehouse_id TEXT NOT NULL,
+    count_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    scheduled_date TEXT NOT NULL,
+    completed_date TEXT,
+    variance_count INTEGER NOT NULL DEFAULT 0,
+    accuracy_rate REAL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS wms_count_lines (
+    id TEXT PRIMARY KEY,
+    cycle_count_id TEXT NOT NULL,
+    location_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    system_qty INTEGER NOT NULL,
+    counted_qty INTEGER NOT NULL,
+    variance INTEGER NOT NULL,
+    counted_by TEXT,
+    recounted INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS demand_forecasts (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT,
+    forecast_date TEXT NOT NULL,
+    period_type TEXT NOT NULL,
+    forecast_qty REAL NOT NULL,
+    lower_bound REAL NOT NULL,
+    upper_bound REAL NOT NULL,
+    confidence REAL NOT NULL,
+    method TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_demand_forecasts_product ON demand_forecasts(product_id);
+CREATE INDEX IF NOT EXISTS idx_demand_forecasts_date ON demand_forecasts(forecast_date);
+CREATE TABLE IF NOT EXISTS demand_forecast_models (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    method TEXT NOT NULL,
+    parameters TEXT NOT NULL,
+    accuracy_mape REAL,
+    accuracy_mse REAL,
+    last_trained TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS demand_plans (
+    id TEXT PRIMARY KEY,
+    plan_name TEXT NOT NULL,
+    plan_type TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    status TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    baseline_id TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS demand_plan_lines (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    location_id TEXT,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    forecast_qty REAL NOT NULL,
+    adjusted_qty REAL NOT NULL,
+    final_qty REAL NOT NULL,
+    notes TEXT
+);
+CREATE TABLE IF NOT EXISTS demand_safety_stock (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    safety_qty INTEGER NOT NULL,
+    reorder_point INTEGER NOT NULL,
+    service_level REAL NOT NULL,
+    lead_time_days INTEGER NOT NULL,
+    demand_variability REAL NOT NULL,
+    last_calculated TEXT NOT NULL,
+    UNIQUE(product_id, warehouse_id)
+);
+CREATE TABLE IF NOT EXISTS demand_history (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT,
+    date TEXT NOT NULL,
+    actual_qty INTEGER NOT NULL,
+    forecast_qty REAL,
+    variance REAL,
+    source TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS demand_promotion_impacts (
+    id TEXT PRIMARY KEY,
+    promotion_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    lift_factor REAL NOT NULL,
+    cannibalization TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS demand_sensing_signals (
+    id TEXT PRIMARY KEY,
+    signal_type TEXT NOT NULL,
+    source TEXT NOT NULL,
+    value REAL NOT NULL,
+    weight REAL NOT NULL,
+    timestamp TEXT NOT NULL,
+    product_ids TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS edi_transactions (
+    id TEXT PRIMARY KEY,
+    partner_id TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    direction TEXT NOT NULL,
+    control_number TEXT NOT NULL UNIQUE,
+    status TEXT NOT NULL,
+    raw_content TEXT,
+    parsed_data TEXT,
+    error_message TEXT,
+    processed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_edi_transactions_partner ON edi_transactions(partner_id);
+CREATE TABLE IF NOT EXISTS edi_acknowledgments (
+    id TEXT PRIMARY KEY,
+    transaction_id TEXT NOT NULL,
+    ack_type TEXT NOT NULL,
+    accepted INTEGER NOT NULL,
+    error_codes TEXT NOT NULL,
+    segment_errors TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS edi_850_orders (
+    id TEXT PRIMARY KEY,
+    transaction_id TEXT NOT NULL,
+    po_number TEXT NOT NULL,
+    po_date TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    ship_to TEXT NOT NULL,
+    bill_to TEXT NOT NULL,
+    lines TEXT NOT NULL,
+    total_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS edi_810_invoices (
+    id TEXT PRIMARY KEY,
+    transaction_id TEXT NOT NULL,
+    invoice_number TEXT NOT NULL,
+    invoice_date TEXT NOT NULL,
+    po_number TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    lines TEXT NOT NULL,
+    subtotal INTEGER NOT NULL,
+    tax INTEGER NOT NULL,
+    total INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS edi_856_asns (
+    id TEXT PRIMARY KEY,
+    transaction_id TEXT NOT NULL,
+    asn_number TEXT NOT NULL,
+    shipment_date TEXT NOT NULL,
+    expected_date TEXT NOT NULL,
+    po_number TEXT NOT NULL,
+    carrier TEXT NOT NULL,
+    tracking_number TEXT,
+    packages TEXT NOT NULL,
+    total_items INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tenant_invitations (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    email TEXT NOT NULL,
+    role TEXT NOT NULL,
+    invited_by TEXT NOT NULL,
+    token TEXT NOT NULL UNIQUE,
+    expires_at TEXT NOT NULL,
+    accepted_at TEXT,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tenant_usage (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    period TEXT NOT NULL,
+    users_count INTEGER NOT NULL,
+    products_count INTEGER NOT NULL,
+    orders_count INTEGER NOT NULL,
+    storage_used_mb INTEGER NOT NULL,
+    api_calls INTEGER NOT NULL,
+    computed_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tenant_features (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    feature_key TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    settings TEXT,
+    updated_at TEXT NOT NULL,
+    UNIQUE(tenant_id, feature_key)
+);
+CREATE TABLE IF NOT EXISTS tenant_audit_logs (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    user_id TEXT,
+    action TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT,
+    old_value TEXT,
+    new_value TEXT,
+    ip_address TEXT,
+    user_agent TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tenant_billing (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL UNIQUE,
+    billing_email TEXT NOT NULL,
+    billing_address TEXT NOT NULL,
+    payment_method TEXT,
+    subscription_id TEXT,
+    next_billing_date TEXT,
+    amount_due INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD'
+);
+CREATE TABLE IF NOT EXISTS revrec_contracts (
+    id TEXT PRIMARY KEY,
+    contract_number TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    contract_date TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    total_value INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    status TEXT NOT NULL,
+    transaction_price INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS revrec_performance_obligations (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    standalone_price INTEGER NOT NULL,
+    allocated_price INTEGER NOT NULL,
+    recognition_type TEXT NOT NULL,
+    recognition_method TEXT NOT NULL,
+    total_periods INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS revrec_schedules (
+    id TEXT PRIMARY KEY,
+    obligation_id TEXT NOT NULL,
+    period TEXT NOT NULL,
+    planned_revenue INTEGER NOT NULL,
+    recognized_revenue INTEGER NOT NULL DEFAULT 0,
+    deferred_revenue INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    recognized_at TEXT
+);
+CREATE TABLE IF NOT EXISTS revrec_modifications (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    modification_date TEXT NOT NULL,
+    modification_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    price_change INTEGER NOT NULL,
+    new_total_value INTEGER NOT NULL,
+    approved_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS revrec_deferred_revenue (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    obligation_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    original_amount INTEGER NOT NULL,
+    recognized_amount INTEGER NOT NULL DEFAULT 0,
+    remaining_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    recognition_start TEXT NOT NULL,
+    recognition_end TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS revrec_events (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    obligation_id TEXT,
+    event_type TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    event_date TEXT NOT NULL,
+    period TEXT NOT NULL,
+    description TEXT,
+    journal_entry_id TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS revrec_allocation_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    method TEXT NOT NULL,
+    basis TEXT NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS revrec_vatb (
+    id TEXT PRIMARY KEY,
+    contract_id TEXT NOT NULL,
+    calculation_date TEXT NOT NULL,
+    total_complete_percent REAL NOT NULL,
+    costs_incurred INTEGER NOT NULL,
+    total_estimated_costs INTEGER NOT NULL,
+    revenue_to_date INTEGER NOT NULL,
+    revenue_to_recognize INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS intercompany_entities (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    legal_entity_id TEXT,
+    currency TEXT NOT NULL,
+    timezone TEXT NOT NULL,
+    tax_id TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS intercompany_transfer_prices (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    source_entity_id TEXT NOT NULL,
+    target_entity_id TEXT NOT NULL,
+    price INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    method TEXT NOT NULL,
+    effective_from TEXT NOT NULL,
+    effective_to TEXT,
+    approved_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS intercompany_due_to_from (
+    id TEXT PRIMARY KEY,
+    source_entity_id TEXT NOT NULL,
+    target_entity_id TEXT NOT NULL,
+    account_type TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    as_of_date TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE(source_entity_id, target_entity_id, account_type)
+);
+CREATE TABLE IF NOT EXISTS intercompany_eliminations (
+    id TEXT PRIMARY KEY,
+    consolidation_id TEXT NOT NULL,
+    source_transaction_id TEXT NOT NULL,
+    debit_entity_id TEXT NOT NULL,
+    credit_entity_id TEXT NOT NULL,
+    account_code TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    description TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS intercompany_consolidations (
+    id TEXT PRIMARY KEY,
+    consolidation_number TEXT NOT NULL UNIQUE,
+    period TEXT NOT NULL,
+    status TEXT NOT NULL,
+    entities TEXT NOT NULL,
+    elimination_entries TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    created_by TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS intercompany_agreements (
+    id TEXT PRIMARY KEY,
+    agreement_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    source_entity_id TEXT NOT NULL,
+    target_entity_id TEXT NOT NULL,
+    agreement_type TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    terms TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lms_courses (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    category TEXT NOT NULL,
+    difficulty TEXT NOT NULL,
+    duration_hours REAL NOT NULL,
+    format TEXT NOT NULL,
+    instructor_id TEXT,
+    max_enrollments INTEGER,
+    credits INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lms_modules (
+    id TEXT PRIMARY KEY,
+    course_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    sequence INTEGER NOT NULL,
+    duration_minutes INTEGER NOT NULL,
+    content_type TEXT NOT NULL,
+    content_url TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lms_enrollments (
+    id TEXT PRIMARY KEY,
+    course_id TEXT NOT NULL,
+    employee_id TEXT NOT NULL,
+    enrolled_at TEXT NOT NULL,
+    status TEXT NOT NULL,
+    progress_percent REAL NOT NULL DEFAULT 0,
+    started_at TEXT,
+    completed_at TEXT,
+    due_date TEXT,
+    score REAL,
+    certificate_id TEXT,
+    UNIQUE(course_id, employee_id)
+);
+CREATE TABLE IF NOT EXISTS lms_learning_paths (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    target_role TEXT,
+    courses TEXT NOT NULL,
+    total_credits INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lms_assessments (
+    id TEXT PRIMARY KEY,
+    course_id TEXT,
+    title TEXT NOT NULL,
+    description TEXT,
+    assessment_type TEXT NOT NULL,
+    time_limit_minutes INTEGER,
+    passing_score REAL NOT NULL,
+    max_attempts INTEGER NOT NULL DEFAULT 1,
+    questions TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lms_questions (
+    id TEXT PRIMARY KEY,
+    assessment_id TEXT NOT NULL,
+    question_text TEXT NOT NULL,
+    question_type TEXT NOT NULL,
+    options TEXT,
+    correct_answer TEXT NOT NULL,
+    points INTEGER NOT NULL DEFAULT 1,
+    sequence INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lms_assessment_attempts (
+    id TEXT PRIMARY KEY,
+    assessment_id TEXT NOT NULL,
+    employee_id TEXT NOT NULL,
+    attempt_number INTEGER NOT NULL,
+    started_at TEXT NOT NULL,
+    submitted_at TEXT,
+    score REAL,
+    passed INTEGER,
+    answers TEXT NOT NULL,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lms_certificates (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    course_id TEXT NOT NULL,
+    certificate_number TEXT NOT NULL UNIQUE,
+    issued_at TEXT NOT NULL,
+    expires_at TEXT,
+    verification_code TEXT NOT NULL UNIQUE
+);
+CREATE TABLE IF NOT EXISTS lms_skills (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    description TEXT
+);
+CREATE TABLE IF NOT EXISTS lms_skill_matrix (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    skill_id TEXT NOT NULL,
+    proficiency_level INTEGER NOT NULL,
+    assessed_at TEXT NOT NULL,
+    assessed_by TEXT,
+    notes TEXT,
+    UNIQUE(employee_id, skill_id)
+);
+CREATE TABLE IF NOT EXISTS lms_training_records (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    training_type TEXT NOT NULL,
+    training_name TEXT NOT NULL,
+    provider TEXT,
+    completed_at TEXT NOT NULL,
+    hours REAL NOT NULL,
+    credits INTEGER NOT NULL DEFAULT 0,
+    certificate_number TEXT,
+    cost INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS schedule_executions (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT NOT NULL,
+    execution_number INTEGER NOT NULL,
+    started_at TEXT,
+    completed_at TEXT,
+    duration_seconds INTEGER,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    report_url TEXT,
+    file_path TEXT,
+    file_size_bytes INTEGER,
+    record_count INTEGER,
+    error_message TEXT,
+    error_stack TEXT,
+    retry_count INTEGER NOT NULL DEFAULT 0,
+    delivery_attempts INTEGER NOT NULL DEFAULT 0,
+    delivery_status TEXT,
+    delivery_error TEXT,
+    delivered_at TEXT,
+    delivery_details TEXT,
+    parameters TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS report_subscriptions (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    email TEXT NOT NULL,
+    delivery_method TEXT NOT NULL DEFAULT 'Email',
+    format TEXT,
+    active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS schedule_audit (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT NOT NULL,
+    execution_id TEXT,
+    action TEXT NOT NULL,
+    old_values TEXT,
+    new_values TEXT,
+    performed_by TEXT NOT NULL,
+    performed_at TEXT NOT NULL,
+    ip_address TEXT,
+    user_agent TEXT,
+    notes TEXT
+);
+CREATE TABLE IF NOT EXISTS chat_channels (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    channel_type TEXT NOT NULL DEFAULT 'Group',
+    avatar_url TEXT,
+    is_private INTEGER NOT NULL DEFAULT 0,
+    is_archived INTEGER NOT NULL DEFAULT 0,
+    owner_id TEXT NOT NULL,
+    parent_channel_id TEXT,
+    related_entity_type TEXT,
+    related_entity_id TEXT,
+    topic TEXT,
+    slow_mode INTEGER NOT NULL DEFAULT 0,
+    slow_mode_delay INTEGER,
+    allow_mentions INTEGER NOT NULL DEFAULT 1,
+    allow_reactions INTEGER NOT NULL DEFAULT 1,
+    allow_threads INTEGER NOT NULL DEFAULT 1,
+    auto_join INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS chat_messages (
+    id TEXT PRIMARY KEY,
+    channel_id TEXT NOT NULL,
+    sender_id TEXT NOT NULL,
+    parent_message_id TEXT,
+    thread_id TEXT,
+    message_type TEXT NOT NULL DEFAULT 'Text',
+    content TEXT NOT NULL,
+    formatted_content TEXT,
+    attachments TEXT,
+    mentions TEXT,
+    reactions TEXT,
+    reply_count INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Sent',
+    edited_at TEXT,
+    edited_by TEXT,
+    deleted_at TEXT,
+    deleted_by TEXT,
+    pinned_at TEXT,
+    pinned_by TEXT,
+    starred_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS chat_memberships (
+    id TEXT PRIMARY KEY,
+    channel_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    role TEXT NOT NULL DEFAULT 'Member',
+    nickname TEXT,
+    muted INTEGER NOT NULL DEFAULT 0,
+    muted_until TEXT,
+    notifications_enabled INTEGER NOT NULL DEFAULT 1,
+    last_read_at TEXT,
+    last_read_message_id TEXT,
+    unread_count INTEGER NOT NULL DEFAULT 0,
+    unread_mentions INTEGER NOT NULL DEFAULT 0,
+    starred INTEGER NOT NULL DEFAULT 0,
+    hidden INTEGER NOT NULL DEFAULT 0,
+    joined_at TEXT NOT NULL,
+    invited_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(channel_id, user_id)
+);
+CREATE TABLE IF NOT EXISTS direct_messages (
+    id TEXT PRIMARY KEY,
+    sender_id TEXT NOT NULL,
+    recipient_id TEXT NOT NULL,
+    message_type TEXT NOT NULL DEFAULT 'Text',
+    content TEXT NOT NULL,
+    formatted_content TEXT,
+    attachments TEXT,
+    status TEXT NOT NULL DEFAULT 'Sent',
+    read_at TEXT,
+    edited_at TEXT,
+    deleted_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS message_reactions (
+    id TEXT PRIMARY KEY,
+    message_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    emoji TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE(message_id, user_id, emoji)
+);
+CREATE TABLE IF NOT EXISTS user_presence (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL UNIQUE,
+    status TEXT NOT NULL DEFAULT 'Offline',
+    status_message TEXT,
+    last_seen_at TEXT,
+    online INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS calendars (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    color TEXT NOT NULL DEFAULT '#3B82F6',
+    owner_id TEXT NOT NULL,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    is_public INTEGER NOT NULL DEFAULT 0,
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    working_hours_start TEXT,
+    working_hours_end TEXT,
+    working_days TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS calendar_events (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    description TEXT,
+    location TEXT,
+    is_virtual INTEGER NOT NULL DEFAULT 0,
+    virtual_meeting_url TEXT,
+    virtual_meeting_provider TEXT,
+    event_type TEXT NOT NULL DEFAULT 'Meeting',
+    status TEXT NOT NULL DEFAULT 'Confirmed',
+    start_at TEXT NOT NULL,
+    end_at TEXT NOT NULL,
+    is_all_day INTEGER NOT NULL DEFAULT 0,
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    recurrence_pattern TEXT NOT NULL DEFAULT 'None',
+    recurrence_rule TEXT,
+    recurrence_end_date TEXT,
+    recurrence_count INTEGER,
+    parent_event_id TEXT,
+    organizer_id TEXT NOT NULL,
+    calendar_id TEXT,
+    color TEXT,
+    visibility TEXT NOT NULL DEFAULT 'Private',
+    priority INTEGER NOT NULL DEFAULT 5,
+    capacity INTEGER,
+    current_attendees INTEGER NOT NULL DEFAULT 0,
+    allow_registration INTEGER NOT NULL DEFAULT 0,
+    registration_url TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS event_attendees (
+    id TEXT PRIMARY KEY,
+    event_id TEXT NOT NULL,
+    user_id TEXT,
+    email TEXT NOT NULL,
+    name TEXT,
+    status TEXT NOT NULL DEFAULT 'NeedsAction',
+    role TEXT NOT NULL DEFAULT 'Required',
+    response_message TEXT,
+    responded_at TEXT,
+    reminder_sent INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS event_reminders (
+    id TEXT PRIMARY KEY,
+    event_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    reminder_type TEXT NOT NULL DEFAULT 'Notification',
+    minutes_before INTEGER NOT NULL DEFAULT 15,
+    sent_at TEXT,
+    snoozed_until TEXT,
+    active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS calendar_shares (
+    id TEXT PRIMARY KEY,
+    calendar_id TEXT NOT NULL,
+    shared_with_user_id TEXT,
+    shared_with_email TEXT,
+    permission TEXT NOT NULL DEFAULT 'ViewOnly',
+    share_token TEXT,
+    expires_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS event_resources (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    resource_type TEXT NOT NULL DEFAULT 'Room',
+    location TEXT,
+    capacity INTEGER NOT NULL DEFAULT 1,
+    email TEXT,
+    calendar_id TEXT,
+    available INTEGER NOT NULL DEFAULT 1,
+    booking_enabled INTEGER NOT NULL DEFAULT 1,
+    auto_accept INTEGER NOT NULL DEFAULT 0,
+    approval_required INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS resource_bookings (
+    id TEXT PRIMARY KEY,
+    resource_id TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    booked_by TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    approved_by TEXT,
+    approved_at TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS signing_documents (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    document_type TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    file_size INTEGER NOT NULL,
+    file_hash TEXT NOT NULL,
+    pages INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    envelope_id TEXT,
+    sender_id TEXT NOT NULL,
+    message TEXT,
+    expires_at TEXT,
+    completed_at TEXT,
+    sent_at TEXT,
+    viewed_at TEXT,
+    reminder_count INTEGER NOT NULL DEFAULT 0,
+    last_reminder_at TEXT,
+    auto_remind INTEGER NOT NULL DEFAULT 1,
+    remind_days INTEGER NOT NULL DEFAULT 3,
+    sequential_signing INTEGER NOT NULL DEFAULT 0,
+    current_signer_order INTEGER,
+    final_signed_file TEXT,
+    final_signed_at TEXT,
+    audit_trail_file TEXT,
+    certificate_of_completion TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS signers (
+    id TEXT PRIMARY KEY,
+    document_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    phone TEXT,
+    user_id TEXT,
+    order_index INTEGER NOT NULL DEFAULT 0,
+    role TEXT NOT NULL DEFAULT 'Signer',
+    status TEXT NOT NULL DEFAULT 'Pending',
+    authentication_method TEXT NOT NULL DEFAULT 'Email',
+    access_code TEXT,
+    viewed_at TEXT,
+    signed_at TEXT,
+    declined_at TEXT,
+    declined_reason TEXT,
+    delegated_to TEXT,
+    email_sent_at TEXT,
+    reminder_sent_at TEXT,
+    signature_ip TEXT,
+    signature_user_agent TEXT,
+    signature_location TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS signature_fields (
+    id TEXT PRIMARY KEY,
+    document_id TEXT NOT NULL,
+    signer_id TEXT NOT NULL,
+    field_type TEXT NOT NULL DEFAULT 'Signature',
+    page INTEGER NOT NULL DEFAULT 1,
+    x_position REAL NOT NULL,
+    y_position REAL NOT NULL,
+    width REAL NOT NULL DEFAULT 100,
+    height REAL NOT NULL DEFAULT 30,
+    required INTEGER NOT NULL DEFAULT 1,
+    value TEXT,
+    signature_data TEXT,
+    signature_type TEXT,
+    signed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS signatures (
+    id TEXT PRIMARY KEY,
+    signer_id TEXT NOT NULL,
+    signature_type TEXT NOT NULL,
+    signature_data TEXT NOT NULL,
+    initials_data TEXT,
+    signed_at TEXT NOT NULL,
+    ip_address TEXT,
+    user_agent TEXT,
+    geolocation TEXT,
+    device_fingerprint TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS signing_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    category TEXT,
+    template_type TEXT NOT NULL,
+    document_path TEXT,
+    field_config TEXT NOT NULL,
+    signer_config TEXT NOT NULL,
+    message_template TEXT,
+    auto_expire_days INTEGER NOT NULL DEFAULT 30,
+    remind_days INTEGER NOT NULL DEFAULT 3,
+    sequential_signing INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    usage_count INTEGER NOT NULL DEFAULT 0,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS signing_audit (
+    id TEXT PRIMARY KEY,
+    document_id TEXT NOT NULL,
+    signer_id TEXT,
+    action TEXT NOT NULL,
+    details TEXT,
+    ip_address TEXT,
+    user_agent TEXT,
+    geolocation TEXT,
+    timestamp TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS email_lists (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    list_type TEXT NOT NULL DEFAULT 'Static',
+    subscriber_count INTEGER NOT NULL DEFAULT 0,
+    active_count INTEGER NOT NULL DEFAULT 0,
+    bounced_count INTEGER NOT NULL DEFAULT 0,
+    unsubscribed_count INTEGER NOT NULL DEFAULT 0,
+    double_optin INTEGER NOT NULL DEFAULT 0,
+    welcome_email_id TEXT,
+    unsubscribe_page TEXT,
+    confirmation_page TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS email_subscribers (
+    id TEXT PRIMARY KEY,
+    email TEXT NOT NULL UNIQUE,
+    first_name TEXT,
+    last_name TEXT,
+    company TEXT,
+    phone TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    subscribed_at TEXT,
+    unsubscribed_at TEXT,
+    bounced_at TEXT,
+    bounce_reason TEXT,
+    confirmed_at TEXT,
+    confirmation_token TEXT,
+    preferences TEXT,
+    custom_fields TEXT,
+    source TEXT,
+    ip_address TEXT,
+    user_agent TEXT,
+    last_email_at TEXT,
+    open_count INTEGER NOT NULL DEFAULT 0,
+    click_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS list_memberships (
+    id TEXT PRIMARY KEY,
+    list_id TEXT NOT NULL,
+    subscriber_id TEXT NOT NULL,
+    added_at TEXT NOT NULL,
+    added_by TEXT,
+    source TEXT,
+    UNIQUE(list_id, subscriber_id)
+);
+CREATE TABLE IF NOT EXISTS email_events (
+    id TEXT PRIMARY KEY,
+    queue_id TEXT,
+    campaign_id TEXT,
+    subscriber_id TEXT,
+    event_type TEXT NOT NULL,
+    email TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    ip_address TEXT,
+    user_agent TEXT,
+    url TEXT,
+    details TEXT
+);
+CREATE TABLE IF NOT EXISTS email_suppressions (
+    id TEXT PRIMARY KEY,
+    email TEXT NOT NULL UNIQUE,
+    reason TEXT NOT NULL,
+    source TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_report_schedules_status ON report_schedules(status);
+CREATE INDEX IF NOT EXISTS idx_report_schedules_next_run ON report_schedules(next_run_at);
+CREATE INDEX IF NOT EXISTS idx_schedule_executions_schedule ON schedule_executions(schedule_id);
+CREATE INDEX IF NOT EXISTS idx_chat_channels_owner ON chat_channels(owner_id);
+CREATE INDEX IF NOT EXISTS idx_chat_messages_channel ON chat_messages(channel_id);
+CREATE INDEX IF NOT EXISTS idx_chat_memberships_user ON chat_memberships(user_id);
+CREATE INDEX IF NOT EXISTS idx_direct_messages_sender ON direct_messages(sender_id);
+CREATE INDEX IF NOT EXISTS idx_direct_messages_recipient ON direct_messages(recipient_id);
+CREATE INDEX IF NOT EXISTS idx_calendar_events_organizer ON calendar_events(organizer_id);
+CREATE INDEX IF NOT EXISTS idx_calendar_events_calendar ON calendar_events(calendar_id);
+CREATE INDEX IF NOT EXISTS idx_calendar_events_start ON calendar_events(start_at);
+CREATE INDEX IF NOT EXISTS idx_event_attendees_event ON event_attendees(event_id);
+CREATE INDEX IF NOT EXISTS idx_signing_documents_sender ON signing_documents(sender_id);
+CREATE INDEX IF NOT EXISTS idx_signing_documents_status ON signing_documents(status);
+CREATE INDEX IF NOT EXISTS idx_signers_document ON signers(document_id);
+CREATE INDEX IF NOT EXISTS idx_signers_email ON signers(email);
+CREATE INDEX IF NOT EXISTS idx_signature_fields_document ON signature_fields(document_id);
+CREATE INDEX IF NOT EXISTS idx_email_campaigns_status ON email_campaigns(status);
+CREATE INDEX IF NOT EXISTS idx_email_subscribers_email ON email_subscribers(email);
+CREATE INDEX IF NOT EXISTS idx_list_memberships_list ON list_memberships(list_id);
+CREATE INDEX IF NOT EXISTS idx_list_memberships_subscriber ON list_memberships(subscriber_id);
+CREATE TABLE IF NOT EXISTS assistant_conversations (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    context TEXT NOT NULL DEFAULT '{}',
+    status TEXT NOT NULL DEFAULT 'active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS assistant_messages (
+    id TEXT PRIMARY KEY,
+    conversation_id TEXT NOT NULL,
+    role TEXT NOT NULL,
+    content TEXT NOT NULL,
+    intent TEXT,
+    entities TEXT NOT NULL DEFAULT '{}',
+    action_taken TEXT,
+    action_result TEXT,
+    feedback_rating INTEGER,
+    feedback_comment TEXT,
+    feedback_created_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (conversation_id) REFERENCES assistant_conversations(id)
+);
+CREATE TABLE IF NOT EXISTS assistant_intents (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    training_phrases TEXT NOT NULL DEFAULT '[]',
+    action_template TEXT NOT NULL,
+    parameters TEXT NOT NULL DEFAULT '[]',
+    confidence_threshold REAL NOT NULL DEFAULT 0.8,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS assistant_skills (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    category TEXT NOT NULL,
+    trigger_phrases TEXT NOT NULL DEFAULT '[]',
+    handler_module TEXT NOT NULL,
+    handler_function TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS assistant_quick_actions (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    icon TEXT NOT NULL DEFAULT 'action',
+    action TEXT NOT NULL,
+    category TEXT NOT NULL,
+    shortcut TEXT,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ocr_documents (
+    id TEXT PRIMARY KEY,
+    document_type TEXT NOT NULL,
+    original_filename TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_size INTEGER NOT NULL,
+    mime_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    processing_started_at TEXT,
+    processing_completed_at TEXT,
+    confidence_score REAL,
+    raw_text TEXT,
+    extracted_data TEXT,
+    validation_errors TEXT NOT NULL DEFAULT '[]',
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ocr_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    vendor_id TEXT,
+    field_mappings TEXT NOT NULL DEFAULT '[]',
+    sample_images TEXT NOT NULL DEFAULT '[]',
+    accuracy_score REAL NOT NULL DEFAULT 0.0,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ocr_batch_jobs (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    document_ids TEXT NOT NULL DEFAULT '[]',
+    template_id TEXT,
+    status TEXT NOT NULL DEFAULT 'pending',
+    total_documents INTEGER NOT NULL DEFAULT 0,
+    processed_documents INTEGER NOT NULL DEFAULT 0,
+    successful_documents INTEGER NOT NULL DEFAULT 0,
+    failed_documents INTEGER NOT NULL DEFAULT 0,
+    started_at TEXT,
+    completed_at TEXT,
+    error_details TEXT NOT NULL DEFAULT '{}',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fraud_alerts (
+    id TEXT PRIMARY KEY,
+    alert_type TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'new',
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    rule_id TEXT,
+    score REAL NOT NULL DEFAULT 0.0,
+    risk_factors TEXT NOT NULL DEFAULT '[]',
+    description TEXT NOT NULL,
+    detected_at TEXT NOT NULL,
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    resolution_type TEXT,
+    resolution_notes TEXT,
+    resolution_actions TEXT,
+    resolution_resolved_by TEXT,
+    resolution_resolved_at TEXT,
+    assigned_to TEXT,
+    due_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fraud_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    category TEXT NOT NULL,
+    rule_type TEXT NOT NULL,
+    conditions TEXT NOT NULL DEFAULT '[]',
+    actions TEXT NOT NULL DEFAULT '[]',
+    enabled INTEGER NOT NULL DEFAULT 1,
+    priority INTEGER NOT NULL DEFAULT 0,
+    false_positive_rate REAL NOT NULL DEFAULT 0.0,
+    true_positive_rate REAL NOT NULL DEFAULT 0.0,
+    last_triggered TEXT,
+    trigger_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fraud_cases (
+    id TEXT PRIMARY KEY,
+    case_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    status TEXT NOT NULL DEFAULT 'open',
+    priority TEXT NOT NULL DEFAULT 'medium',
+    assigned_investigator TEXT,
+    alert_ids TEXT NOT NULL DEFAULT '[]',
+    related_entities TEXT NOT NULL DEFAULT '[]',
+    timeline TEXT NOT NULL DEFAULT '[]',
+    evidence TEXT NOT NULL DEFAULT '[]',
+    estimated_loss INTEGER NOT NULL DEFAULT 0,
+    actual_loss INTEGER,
+    recovery_amount INTEGER,
+    opened_at TEXT NOT NULL,
+    closed_at TEXT,
+    resolution_outcome TEXT,
+    resolution_summary TEXT,
+    resolution_actions TEXT NOT NULL DEFAULT '[]',
+    resolution_recommendations TEXT NOT NULL DEFAULT '[]',
+    resolution_resolved_by TEXT,
+    resolution_resolved_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS vendor_risk_profiles (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL UNIQUE,
+    risk_score REAL NOT NULL DEFAULT 0.0,
+    risk_level TEXT NOT NULL DEFAULT 'low',
+    risk_factors TEXT NOT NULL DEFAULT '[]',
+    historical_alerts INTEGER NOT NULL DEFAULT 0,
+    payment_anomalies INTEGER NOT NULL DEFAULT 0,
+    days_since_first_transaction INTEGER NOT NULL DEFAULT 0,
+    total_transaction_value INTEGER NOT NULL DEFAULT 0,
+    average_transaction_value INTEGER NOT NULL DEFAULT 0,
+    transaction_count INTEGER NOT NULL DEFAULT 0,
+    duplicate_invoice_attempts INTEGER NOT NULL DEFAULT 0,
+    address_changes INTEGER NOT NULL DEFAULT 0,
+    bank_account_changes INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS employee_risk_profiles (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL UNIQUE,
+    risk_score REAL NOT NULL DEFAULT 0.0,
+    risk_level TEXT NOT NULL DEFAULT 'low',
+    risk_factors TEXT NOT NULL DEFAULT '[]',
+    expense_anomalies INTEGER NOT NULL DEFAULT 0,
+    access_violations INTEGER NOT NULL DEFAULT 0,
+    policy_violations INTEGER NOT NULL DEFAULT 0,
+    total_expense_value INTEGER NOT NULL DEFAULT 0,
+    average_expense_value INTEGER NOT NULL DEFAULT 0,
+    expense_count INTEGER NOT NULL DEFAULT 0,
+    after_hours_access INTEGER NOT NULL DEFAULT 0,
+    data_export_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS process_definitions (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    category TEXT NOT NULL,
+    version TEXT NOT NULL DEFAULT '1.0',
+    status TEXT NOT NULL DEFAULT 'draft',
+    owner_id TEXT,
+    start_event TEXT NOT NULL,
+    end_events TEXT NOT NULL DEFAULT '[]',
+    expected_duration_hours INTEGER,
+    sla_hours INTEGER,
+    tags TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS process_instances (
+    id TEXT PRIMARY KEY,
+    process_id TEXT NOT NULL,
+    case_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'running',
+    start_time TEXT NOT NULL,
+    end_time TEXT,
+    duration_hours REAL,
+    initiator_id TEXT,
+    entity_type TEXT,
+    entity_id TEXT,
+    variant_id TEXT,
+    is_compliant INTEGER,
+    deviation_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_id) REFERENCES process_definitions(id)
+);
+CREATE TABLE IF NOT EXISTS process_events (
+    id TEXT PRIMARY KEY,
+    instance_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    activity_name TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    user_id TEXT,
+    role_id TEXT,
+    department_id TEXT,
+    resource TEXT,
+    previous_state TEXT,
+    new_state TEXT,
+    duration_ms INTEGER,
+    metadata TEXT NOT NULL DEFAULT '{}',
+    cost_cents INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (instance_id) REFERENCES process_instances(id)
+);
+CREATE TABLE IF NOT EXISTS process_variants (
+    id TEXT PRIMARY KEY,
+    process_id TEXT NOT NULL,
+    variant_hash TEXT NOT NULL,
+    activity_sequence TEXT NOT NULL DEFAULT '[]',
+    frequency INTEGER NOT NULL DEFAULT 0,
+    percentage REAL NOT NULL DEFAULT 0.0,
+    avg_duration_hours REAL NOT NULL DEFAULT 0.0,
+    min_duration_hours REAL NOT NULL DEFAULT 0.0,
+    max_duration_hours REAL NOT NULL DEFAULT 0.0,
+    is_happy_path INTEGER NOT NULL DEFAULT 0,
+    deviation_from_standard REAL NOT NULL DEFAULT 0.0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_id) REFERENCES process_definitions(id)
+);
+CREATE TABLE IF NOT EXISTS process_discoveries (
+    id TEXT PRIMARY KEY,
+    process_id TEXT NOT NULL,
+    discovery_date TEXT NOT NULL,
+    total_cases INTEGER NOT NULL DEFAULT 0,
+    total_events INTEGER NOT NULL DEFAULT 0,
+    unique_activities INTEGER NOT NULL DEFAULT 0,
+    unique_variants INTEGER NOT NULL DEFAULT 0,
+    avg_case_duration_hours REAL NOT NULL DEFAULT 0.0,
+    median_case_duration_hours REAL NOT NULL DEFAULT 0.0,
+    activity_frequencies TEXT NOT NULL DEFAULT '{}',
+    transition_frequencies TEXT NOT NULL DEFAULT '{}',
+    start_activities TEXT NOT NULL DEFAULT '[]',
+    end_activities TEXT NOT NULL DEFAULT '[]',
+    self_loops TEXT NOT NULL DEFAULT '[]',
+    rework_loops TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_id) REFERENCES process_definitions(id)
+);
+CREATE TABLE IF NOT EXISTS bottleneck_analyses (
+    id TEXT PRIMARY KEY,
+    process_id TEXT NOT NULL,
+    analysis_date TEXT NOT NULL,
+    bottlenecks TEXT NOT NULL DEFAULT '[]',
+    waiting_time_analysis TEXT NOT NULL DEFAULT '[]',
+    resource_utilization TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_id) REFERENCES process_definitions(id)
+);
+CREATE TABLE IF NOT EXISTS conformance_checks (
+    id TEXT PRIMARY KEY,
+    process_id TEXT NOT NULL,
+    check_date TEXT NOT NULL,
+    total_cases INTEGER NOT NULL DEFAULT 0,
+    conformant_cases INTEGER NOT NULL DEFAULT 0,
+    conformance_rate REAL NOT NULL DEFAULT 0.0,
+    deviations TEXT NOT NULL DEFAULT '[]',
+    deviating_variants TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_id) REFERENCES process_definitions(id)
+);
+CREATE TABLE IF NOT EXISTS process_simulations (
+    id TEXT PRIMARY KEY,
+    process_id TEXT NOT NULL,
+    simulation_name TEXT NOT NULL,
+    scenario_name TEXT NOT NULL,
+    scenario_modifications TEXT NOT NULL DEFAULT '[]',
+    scenario_duration_days INTEGER NOT NULL DEFAULT 30,
+    scenario_case_arrival_rate REAL NOT NULL DEFAULT 0.0,
+    scenario_resource_allocation TEXT NOT NULL DEFAULT '{}',
+    start_time TEXT NOT NULL,
+    end_time TEXT,
+    status TEXT NOT NULL DEFAULT 'pending',
+    results_avg_cycle_time_hours REAL,
+    results_throughput_per_day REAL,
+    results_resource_utilization TEXT,
+    results_bottleneck_activities TEXT,
+    results_improvement_percentage REAL,
+    results_projected_cost_savings INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_id) REFERENCES process_definitions(id)
+);
+CREATE INDEX IF NOT EXISTS idx_assistant_messages_conversation ON assistant_messages(conversation_id);
+CREATE INDEX IF NOT EXISTS idx_ocr_documents_status ON ocr_documents(status);
+CREATE INDEX IF NOT EXISTS idx_fraud_alerts_status ON fraud_alerts(status);
+CREATE INDEX IF NOT EXISTS idx_fraud_alerts_severity ON fraud_alerts(severity);
+CREATE INDEX IF NOT EXISTS idx_fraud_cases_status ON fraud_cases(status);
+CREATE INDEX IF NOT EXISTS idx_process_instances_process ON process_instances(process_id);
+CREATE INDEX IF NOT EXISTS idx_process_events_instance ON process_events(instance_id);
+CREATE INDEX IF NOT EXISTS idx_process_events_timestamp ON process_events(timestamp);
+CREATE TABLE IF NOT EXISTS leases (
+    id TEXT PRIMARY KEY,
+    lease_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    lease_type TEXT NOT NULL,
+    lessor_id TEXT NOT NULL,
+    lessee_id TEXT NOT NULL,
+    asset_id TEXT,
+    commencement_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    lease_term_months INTEGER NOT NULL,
+    renewal_option INTEGER NOT NULL,
+    renewal_term_months INTEGER,
+    termination_option INTEGER NOT NULL,
+    termination_notice_days INTEGER,
+    purchase_option INTEGER NOT NULL,
+    purchase_option_price INTEGER,
+    fair_value_at_commencement INTEGER NOT NULL,
+    residual_value_guarantee INTEGER,
+    currency TEXT NOT NULL,
+    discount_rate REAL NOT NULL,
+    implicit_rate REAL,
+    incremental_borrowing_rate REAL,
+    initial_direct_costs INTEGER NOT NULL,
+    lease_incentives INTEGER NOT NULL,
+    decommissioning_provision INTEGER,
+    status TEXT NOT NULL,
+    classification_date TEXT,
+    classification_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lease_payments (
+    id TEXT PRIMARY KEY,
+    lease_id TEXT NOT NULL,
+    payment_number TEXT NOT NULL UNIQUE,
+    payment_date TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    period_number INTEGER NOT NULL,
+    fixed_payment INTEGER NOT NULL,
+    variable_payment INTEGER NOT NULL,
+    escalation_amount INTEGER NOT NULL,
+    total_payment INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    payment_status TEXT NOT NULL,
+    paid_date TEXT,
+    paid_amount INTEGER,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS right_of_use_assets (
+    id TEXT PRIMARY KEY,
+    lease_id TEXT NOT NULL UNIQUE,
+    asset_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    initial_cost INTEGER NOT NULL,
+    accumulated_depreciation INTEGER NOT NULL,
+    impairment_loss INTEGER NOT NULL,
+    net_book_value INTEGER NOT NULL,
+    depreciation_method TEXT NOT NULL,
+    useful_life_months INTEGER NOT NULL,
+    residual_value INTEGER NOT NULL,
+    depreciation_start_date TEXT NOT NULL,
+    depreciation_end_date TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lease_liabilities (
+    id TEXT PRIMARY KEY,
+    lease_id TEXT NOT NULL UNIQUE,
+    liability_number TEXT NOT NULL UNIQUE,
+    initial_liability INTEGER NOT NULL,
+    outstanding_balance INTEGER NOT NULL,
+    interest_accrued INTEGER NOT NULL,
+    principal_paid INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    calculation_date TEXT NOT NULL,
+    amortization_method TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lease_amortization_schedule (
+    id TEXT PRIMARY KEY,
+    lease_id TEXT NOT NULL,
+    period_number INTEGER NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    opening_liability INTEGER NOT NULL,
+    payment INTEGER NOT NULL,
+    interest_expense INTEGER NOT NULL,
+    principal_reduction INTEGER NOT NULL,
+    closing_liability INTEGER NOT NULL,
+    opening_rou_asset INTEGER NOT NULL,
+    depreciation_expense INTEGER NOT NULL,
+    closing_rou_asset INTEGER NOT NULL,
+    total_expense INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lease_modifications (
+    id TEXT PRIMARY KEY,
+    modification_number TEXT NOT NULL UNIQUE,
+    lease_id TEXT NOT NULL,
+    modification_date TEXT NOT NULL,
+    effective_date TEXT NOT NULL,
+    modification_type TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    original_term_months INTEGER NOT NULL,
+    new_term_months INTEGER NOT NULL,
+    original_payment INTEGER NOT NULL,
+    new_payment INTEGER NOT NULL,
+    original_discount_rate REAL NOT NULL,
+    new_discount_rate REAL,
+    remeasurement_gain_loss INTEGER NOT NULL,
+    rou_adjustment INTEGER NOT NULL,
+    liability_adjustment INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS lease_disclosures (
+    id TEXT PRIMARY KEY,
+    reporting_period TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_finance_leases INTEGER NOT NULL,
+    total_operating_leases INTEGER NOT NULL,
+    total_rou_assets INTEGER NOT NULL,
+    total_lease_liabilities INTEGER NOT NULL,
+    total_depreciation INTEGER NOT NULL,
+    total_interest INTEGER NOT NULL,
+    total_lease_payments INTEGER NOT NULL,
+    maturities_within_1_year INTEGER NOT NULL,
+    maturities_1_to_5_years INTEGER NOT NULL,
+    maturities_after_5_years INTEGER NOT NULL,
+    total_undiscounted_payments INTEGER NOT NULL,
+    weighted_avg_lease_term REAL NOT NULL,
+    weighted_avg_discount_rate REAL NOT NULL,
+    currency TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS bank_connections (
+    id TEXT PRIMARY KEY,
+    connection_number TEXT NOT NULL UNIQUE,
+    bank_name TEXT NOT NULL,
+    bank_code TEXT,
+    swift_code TEXT,
+    api_endpoint TEXT,
+    api_key TEXT,
+    api_secret TEXT,
+    certificate_path TEXT,
+    authentication_type TEXT NOT NULL,
+    statement_format TEXT NOT NULL,
+    polling_enabled INTEGER NOT NULL,
+    polling_interval_minutes INTEGER NOT NULL,
+    last_poll_at TEXT,
+    last_successful_at TEXT,
+    last_error TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS reconciliation_sessions (
+    id TEXT PRIMARY KEY,
+    session_number TEXT NOT NULL UNIQUE,
+    bank_account_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_transactions INTEGER NOT NULL,
+    matched_count INTEGER NOT NULL,
+    unmatched_count INTEGER NOT NULL,
+    exception_count INTEGER NOT NULL,
+    auto_matched_count INTEGER NOT NULL,
+    manual_matched_count INTEGER NOT NULL,
+    opening_balance INTEGER NOT NULL,
+    closing_balance INTEGER NOT NULL,
+    calculated_balance INTEGER NOT NULL,
+    variance INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    completed_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS reconciliation_matches (
+    id TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    bank_transaction_id TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    entity_reference TEXT NOT NULL,
+    transaction_amount INTEGER NOT NULL,
+    entity_amount INTEGER NOT NULL,
+    match_difference INTEGER NOT NULL,
+    match_type TEXT NOT NULL,
+    match_rule TEXT,
+    match_confidence REAL NOT NULL,
+    matched_at TEXT NOT NULL,
+    matched_by TEXT,
+    status TEXT NOT NULL,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS payment_file_generations (
+    id TEXT PRIMARY KEY,
+    file_number TEXT NOT NULL UNIQUE,
+    bank_account_id TEXT NOT NULL,
+    file_type TEXT NOT NULL,
+    file_date TEXT NOT NULL,
+    value_date TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    total_amount INTEGER NOT NULL,
+    payment_count INTEGER NOT NULL,
+    file_content TEXT,
+    file_path TEXT,
+    status TEXT NOT NULL,
+    generated_at TEXT NOT NULL,
+    transmitted_at TEXT,
+    acknowledged_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS loyalty_programs (
+    id TEXT PRIMARY KEY,
+    program_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    program_type TEXT NOT NULL,
+    points_name TEXT NOT NULL,
+    earn_rate REAL NOT NULL,
+    earn_currency TEXT NOT NULL,
+    minimum_points_redemption INTEGER NOT NULL,
+    points_expiry_months INTEGER,
+    enrollment_bonus INTEGER NOT NULL,
+    referral_bonus INTEGER NOT NULL,
+    birthday_bonus INTEGER NOT NULL,
+    max_points_per_day INTEGER,
+    max_points_per_year INTEGER,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS loyalty_tiers (
+    id TEXT PRIMARY KEY,
+    program_id TEXT NOT NULL,
+    tier_type TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    minimum_points INTEGER NOT NULL,
+    maximum_points INTEGER,
+    earn_multiplier REAL NOT NULL,
+    benefits TEXT,
+    upgrade_bonus INTEGER NOT NULL,
+    color_code TEXT,
+    priority INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS loyalty_members (
+    id TEXT PRIMARY KEY,
+    member_number TEXT NOT NULL UNIQUE,
+    program_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    tier_id TEXT,
+    enrollment_date TEXT NOT NULL,
+    enrollment_source TEXT,
+    total_points_earned INTEGER NOT NULL,
+    total_points_redeemed INTEGER NOT NULL,
+    available_points INTEGER NOT NULL,
+    lifetime_points INTEGER NOT NULL,
+    points_expiring INTEGER NOT NULL,
+    points_expiring_date TEXT,
+    total_rewards_redeemed INTEGER NOT NULL,
+    tier_upgrade_date TEXT,
+    tier_downgrade_date TEXT,
+    referral_code TEXT NOT NULL,
+    referred_by TEXT,
+    referral_count INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    last_activity_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS loyalty_transactions (
+    id TEXT PRIMARY KEY,
+    transaction_number TEXT NOT NULL UNIQUE,
+    member_id TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    point_type TEXT NOT NULL,
+    points INTEGER NOT NULL,
+    running_balance INTEGER NOT NULL,
+    source_type TEXT,
+    source_id TEXT,
+    order_id TEXT,
+    amount INTEGER,
+    currency TEXT,
+    description TEXT,
+    expiry_date TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS loyalty_rewards (
+    id TEXT PRIMARY KEY,
+    reward_number TEXT NOT NULL UNIQUE,
+    program_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    reward_type TEXT NOT NULL,
+    points_cost INTEGER NOT NULL,
+    cash_value INTEGER,
+    currency TEXT NOT NULL,
+    product_id TEXT,
+    discount_percent REAL,
+    discount_amount INTEGER,
+    min_purchase_amount INTEGER,
+    max_redemptions INTEGER,
+    redemptions_count INTEGER NOT NULL,
+    valid_from TEXT,
+    valid_until TEXT,
+    tier_requirement TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS loyalty_redemptions (
+    id TEXT PRIMARY KEY,
+    redemption_number TEXT NOT NULL UNIQUE,
+    member_id TEXT NOT NULL,
+    reward_id TEXT NOT NULL,
+    points_used INTEGER NOT NULL,
+    cash_value INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    order_id TEXT,
+    redemption_date TEXT NOT NULL,
+    status TEXT NOT NULL,
+    voucher_code TEXT,
+    voucher_expires TEXT,
+    used_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS loyalty_promotions (
+    id TEXT PRIMARY KEY,
+    promotion_number TEXT NOT NULL UNIQUE,
+    program_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    promotion_type TEXT NOT NULL,
+    earn_multiplier REAL NOT NULL,
+    bonus_points INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    product_ids TEXT,
+    category_ids TEXT,
+    tier_restriction TEXT,
+    max_points_per_member INTEGER,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS partners (
+    id TEXT PRIMARY KEY,
+    partner_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    legal_name TEXT,
+    partner_type TEXT NOT NULL,
+    tier TEXT NOT NULL,
+    parent_partner_id TEXT,
+    primary_contact_id TEXT,
+    website TEXT,
+    email TEXT,
+    phone TEXT,
+    address TEXT,
+    city TEXT,
+    state TEXT,
+    country TEXT,
+    postal_code TEXT,
+    tax_id TEXT,
+    registration_date TEXT,
+    agreement_date TEXT,
+    agreement_expiry TEXT,
+    contract_value INTEGER,
+    currency TEXT NOT NULL,
+    commission_rate REAL NOT NULL,
+    discount_rate REAL NOT NULL,
+    credit_limit INTEGER,
+    payment_terms_days INTEGER NOT NULL,
+    certification_level TEXT,
+    certifications TEXT,
+    specializations TEXT,
+    regions_served TEXT,
+    industries_served TEXT,
+    annual_revenue INTEGER,
+    employee_count INTEGER,
+    notes TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS partner_contacts (
+    id TEXT PRIMARY KEY,
+    partner_id TEXT NOT NULL,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    phone TEXT,
+    mobile TEXT,
+    title TEXT,
+    department TEXT,
+    is_primary INTEGER NOT NULL,
+    receive_notifications INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS partner_agreements (
+    id TEXT PRIMARY KEY,
+    agreement_number TEXT NOT NULL UNIQUE,
+    partner_id TEXT NOT NULL,
+    agreement_type TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    auto_renew INTEGER NOT NULL,
+    renewal_term_months INTEGER NOT NULL,
+    notice_period_days INTEGER NOT NULL,
+    commission_rate REAL NOT NULL,
+    discount_rate REAL NOT NULL,
+    min_sales_target INTEGER,
+    max_sales_limit INTEGER,
+    territory TEXT,
+    exclusivity INTEGER NOT NULL,
+    document_path TEXT,
+    signed_date TEXT,
+    signed_by TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS partner_deals (
+    id TEXT PRIMARY KEY,
+    deal_number TEXT NOT NULL UNIQUE,
+    partner_id TEXT NOT NULL,
+    customer_id TEXT,
+    customer_name TEXT NOT NULL,
+    deal_name TEXT NOT NULL,
+    description TEXT,
+    deal_type TEXT NOT NULL,
+    stage TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    expected_close_date TEXT NOT NULL,
+    probability INTEGER NOT NULL,
+    lead_source TEXT,
+    products TEXT,
+    partner_commission INTEGER NOT NULL,
+    internal_sales_rep_id TEXT,
+    partner_contact_id TEXT,
+    notes TEXT,
+    won_date TEXT,
+    lost_date TEXT,
+    lost_reason TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS partner_deal_registrations (
+    id TEXT PRIMARY KEY,
+    registration_number TEXT NOT NULL UNIQUE,
+    partner_id TEXT NOT NULL,
+    deal_id TEXT,
+    customer_name TEXT NOT NULL,
+    opportunity_name TEXT NOT NULL,
+    estimated_value INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    expected_close_date TEXT NOT NULL,
+    products TEXT,
+    registration_date TEXT NOT NULL,
+    expiry_date TEXT NOT NULL,
+    status TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT,
+    rejection_reason TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS partner_commissions (
+    id TEXT PRIMARY KEY,
+    commission_number TEXT NOT NULL UNIQUE,
+    partner_id TEXT NOT NULL,
+    deal_id TEXT,
+    invoice_id TEXT,
+    commission_date TEXT NOT NULL,
+    revenue_amount INTEGER NOT NULL,
+    commission_rate REAL NOT NULL,
+    commission_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    status TEXT NOT NULL,
+    paid_date TEXT,
+    payment_reference TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS partner_performances (
+    id TEXT PRIMARY KEY,
+    partner_id TEXT NOT NULL,
+    period_type TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    deals_opened INTEGER NOT NULL,
+    deals_won INTEGER NOT NULL,
+    deals_lost INTEGER NOT NULL,
+    total_pipeline INTEGER NOT NULL,
+    total_revenue INTEGER NOT NULL,
+    total_commission INTEGER NOT NULL,
+    win_rate_percent REAL NOT NULL,
+    avg_deal_size INTEGER NOT NULL,
+    avg_sales_cycle_days REAL NOT NULL,
+    customer_satisfaction REAL,
+    target_revenue INTEGER,
+    attainment_percent REAL,
+    currency TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS card_statements (
+    id TEXT PRIMARY KEY,
+    statement_number TEXT NOT NULL UNIQUE,
+    card_id TEXT NOT NULL,
+    statement_date TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    opening_balance INTEGER NOT NULL,
+    payments INTEGER NOT NULL,
+    credits INTEGER NOT NULL,
+    purchases INTEGER NOT NULL,
+    fees INTEGER NOT NULL,
+    interest INTEGER NOT NULL,
+    closing_balance INTEGER NOT NULL,
+    minimum_payment INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    paid_amount INTEGER,
+    paid_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS card_policies (
+    id TEXT PRIMARY KEY,
+    policy_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    card_type TEXT NOT NULL,
+    default_limit INTEGER NOT NULL,
+    daily_limit INTEGER,
+    transaction_limit INTEGER,
+    mcc_allowed TEXT,
+    mcc_blocked TEXT,
+    merchant_blocked TEXT,
+    requires_approval_over INTEGER,
+    approval_workflow_id TEXT,
+    requires_receipt_over INTEGER,
+    auto_reconcile INTEGER NOT NULL,
+    international_allowed INTEGER NOT NULL,
+    atm_allowed INTEGER NOT NULL,
+    online_allowed INTEGER NOT NULL,
+    contactless_allowed INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS card_disputes (
+    id TEXT PRIMARY KEY,
+    dispute_number TEXT NOT NULL UNIQUE,
+    card_id TEXT NOT NULL,
+    transaction_id TEXT NOT NULL,
+    dispute_type TEXT NOT NULL,
+    dispute_reason TEXT NOT NULL,
+    disputed_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    filed_date TEXT NOT NULL,
+    resolution_date TEXT,
+    resolution TEXT,
+    provisional_credit INTEGER,
+    provisional_credit_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS virtual_cards (
+    id TEXT PRIMARY KEY,
+    parent_card_id TEXT,
+    cardholder_id TEXT NOT NULL,
+    masked_number TEXT NOT NULL,
+    credit_limit INTEGER NOT NULL,
+    available_credit INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    valid_from TEXT NOT NULL,
+    valid_until TEXT NOT NULL,
+    single_use INTEGER NOT NULL,
+    merchant_lock TEXT,
+    usage_limit INTEGER,
+    usage_count INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS quota_attainments (
+    id TEXT PRIMARY KEY,
+    quota_id TEXT NOT NULL,
+    period_type TEXT NOT NULL,
+    period_number INTEGER NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    target INTEGER NOT NULL,
+    actual INTEGER NOT NULL,
+    attainment_percent REAL NOT NULL,
+    pipeline INTEGER NOT NULL,
+    pipeline_coverage REAL NOT NULL,
+    gap_to_quota INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    calculated_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS territory_performances (
+    id TEXT PRIMARY KEY,
+    territory_id TEXT NOT NULL,
+    period_type TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    quota INTEGER NOT NULL,
+    revenue INTEGER NOT NULL,
+    attainment_percent REAL NOT NULL,
+    new_accounts INTEGER NOT NULL,
+    total_accounts INTEGER NOT NULL,
+    opportunities_created INTEGER NOT NULL,
+    opportunities_won INTEGER NOT NULL,
+    win_rate_percent REAL NOT NULL,
+    avg_deal_size INTEGER NOT NULL,
+    pipeline_value INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS asset_sensors (
+    id TEXT PRIMARY KEY,
+    sensor_number TEXT NOT NULL UNIQUE,
+    asset_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    sensor_type TEXT NOT NULL,
+    manufacturer TEXT,
+    model TEXT,
+    serial_number TEXT,
+    location TEXT,
+    measurement_unit TEXT NOT NULL,
+    sampling_interval_seconds INTEGER NOT NULL,
+    data_source TEXT NOT NULL,
+    connection_type TEXT NOT NULL,
+    last_reading REAL,
+    last_reading_at TEXT,
+    min_threshold REAL,
+    max_threshold REAL,
+    alert_threshold_low REAL,
+    alert_threshold_high REAL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS sensor_readings (
+    id TEXT PRIMARY KEY,
+    sensor_id TEXT NOT NULL,
+    reading_timestamp TEXT NOT NULL,
+    value REAL NOT NULL,
+    unit TEXT NOT NULL,
+    quality TEXT NOT NULL,
+    raw_value REAL,
+    is_anomaly INTEGER NOT NULL,
+    anomaly_score REAL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS predictive_models (
+    id TEXT PRIMARY KEY,
+    model_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    model_type TEXT NOT NULL,
+    algorithm TEXT NOT NULL,
+    version TEXT NOT NULL,
+    asset_type_id TEXT,
+    target_variable TEXT NOT NULL,
+    features TEXT NOT NULL,
+    training_data_start TEXT,
+    training_data_end TEXT,
+    training_samples INTEGER NOT NULL,
+    accuracy REAL NOT NULL,
+    precision REAL NOT NULL,
+    recall REAL NOT NULL,
+    f1_score REAL NOT NULL,
+    auc_roc REAL,
+    confusion_matrix TEXT,
+    feature_importance TEXT,
+    model_path TEXT,
+    hyperparameters TEXT,
+    retraining_frequency_days INTEGER NOT NULL,
+    last_trained_at TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS asset_health_scores (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    score_date TEXT NOT NULL,
+    overall_score REAL NOT NULL,
+    previous_score REAL,
+    score_change REAL,
+    trend TEXT NOT NULL,
+    reliability_score REAL NOT NULL,
+    performance_score REAL NOT NULL,
+    maintenance_score REAL NOT NULL,
+    component_scores TEXT,
+    risk_level TEXT NOT NULL,
+    days_to_failure INTEGER,
+    recommended_action TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS failure_predictions (
+    id TEXT PRIMARY KEY,
+    prediction_number TEXT NOT NULL UNIQUE,
+    asset_id TEXT NOT NULL,
+    model_id TEXT,
+    prediction_type TEXT NOT NULL,
+    prediction_date TEXT NOT NULL,
+    predicted_failure_date TEXT NOT NULL,
+    confidence REAL NOT NULL,
+    failure_mode TEXT,
+    failure_probability REAL NOT NULL,
+    remaining_useful_life_days INTEGER,
+    health_score_at_prediction REAL NOT NULL,
+    contributing_factors TEXT,
+    recommended_actions TEXT,
+    priority INTEGER NOT NULL,
+    estimated_repair_cost INTEGER,
+    currency TEXT NOT NULL,
+    status TEXT NOT NULL,
+    actual_failure_date TEXT,
+    work_order_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS maintenance_cost_analyses (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_maintenance_cost INTEGER NOT NULL,
+    preventive_cost INTEGER NOT NULL,
+    predictive_cost INTEGER NOT NULL,
+    corrective_cost INTEGER NOT NULL,
+    downtime_cost INTEGER NOT NULL,
+    total_downtime_hours REAL NOT NULL,
+    unplanned_downtime_hours REAL NOT NULL,
+    mtbf_hours REAL,
+    mttr_hours REAL,
+    availability_percent REAL NOT NULL,
+    oee_percent REAL,
+    savings_from_predictions INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_leases_status ON leases(status);
+CREATE INDEX IF NOT EXISTS idx_lease_payments_lease ON lease_payments(lease_id);
+CREATE INDEX IF NOT EXISTS idx_bank_transactions_account ON bank_transactions(bank_account_id);
+CREATE INDEX IF NOT EXISTS idx_loyalty_members_customer ON loyalty_members(customer_id);
+CREATE INDEX IF NOT EXISTS idx_loyalty_transactions_member ON loyalty_transactions(member_id);
+CREATE INDEX IF NOT EXISTS idx_partners_status ON partners(status);
+CREATE INDEX IF NOT EXISTS idx_partner_deals_partner ON partner_deals(partner_id);
+CREATE INDEX IF NOT EXISTS idx_corporate_cards_cardholder ON corporate_cards(cardholder_id);
+CREATE INDEX IF NOT EXISTS idx_card_transactions_card ON card_transactions(card_id);
+CREATE INDEX IF NOT EXISTS idx_sales_territories_manager ON sales_territories(manager_id);
+CREATE INDEX IF NOT EXISTS idx_asset_sensors_asset ON asset_sensors(asset_id);
+CREATE INDEX IF NOT EXISTS idx_sensor_readings_sensor ON sensor_readings(sensor_id);
+CREATE INDEX IF NOT EXISTS idx_failure_predictions_asset ON failure_predictions(asset_id);
+CREATE TABLE IF NOT EXISTS mrp_item_plans (
+    id TEXT PRIMARY KEY,
+    run_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    starting_inventory INTEGER NOT NULL,
+    safety_stock INTEGER NOT NULL,
+    total_demand INTEGER NOT NULL,
+    total_supply INTEGER NOT NULL,
+    ending_inventory INTEGER NOT NULL,
+    shortage_quantity INTEGER NOT NULL,
+    suggested_actions INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mrp_demands (
+    id TEXT PRIMARY KEY,
+    run_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    demand_type TEXT NOT NULL,
+    source_type TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    source_line_id TEXT,
+    required_date TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    allocated_quantity INTEGER NOT NULL DEFAULT 0,
+    remaining_quantity INTEGER NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mrp_supplies (
+    id TEXT PRIMARY KEY,
+    run_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    supply_type TEXT NOT NULL,
+    source_type TEXT NOT NULL,
+    source_id TEXT NOT NULL,
+    available_date TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    allocated_quantity INTEGER NOT NULL DEFAULT 0,
+    remaining_quantity INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mrp_parameters (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    planning_method TEXT NOT NULL,
+    lot_size_method TEXT NOT NULL,
+    fixed_lot_size INTEGER NOT NULL DEFAULT 0,
+    min_lot_size INTEGER NOT NULL DEFAULT 1,
+    max_lot_size INTEGER NOT NULL DEFAULT 0,
+    multiple_lot_size INTEGER NOT NULL DEFAULT 1,
+    safety_stock INTEGER NOT NULL DEFAULT 0,
+    safety_time_days INTEGER NOT NULL DEFAULT 0,
+    lead_time_days INTEGER NOT NULL DEFAULT 0,
+    planning_time_fence_days INTEGER NOT NULL DEFAULT 7,
+    order_policy TEXT NOT NULL,
+    min_order_days INTEGER NOT NULL DEFAULT 1,
+    max_order_days INTEGER NOT NULL DEFAULT 365,
+    days_of_supply INTEGER NOT NULL DEFAULT 14,
+    service_level_percent INTEGER NOT NULL DEFAULT 95,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(product_id, warehouse_id)
+);
+CREATE TABLE IF NOT EXISTS demand_forecast_lines (
+    id TEXT PRIMARY KEY,
+    forecast_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    forecast_quantity INTEGER NOT NULL,
+    actual_quantity INTEGER,
+    variance INTEGER,
+    confidence_level INTEGER,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS planned_orders (
+    id TEXT PRIMARY KEY,
+    order_number TEXT NOT NULL UNIQUE,
+    run_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    order_type TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    bom_id TEXT,
+    routing_id TEXT,
+    source_demand_ids TEXT NOT NULL,
+    status TEXT NOT NULL,
+    firmed INTEGER NOT NULL DEFAULT 0,
+    converted_type TEXT,
+    converted_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS planned_order_components (
+    id TEXT PRIMARY KEY,
+    planned_order_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    required_quantity INTEGER NOT NULL,
+    issued_quantity INTEGER NOT NULL DEFAULT 0,
+    required_date TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mrp_exceptions (
+    id TEXT PRIMARY KEY,
+    run_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    exception_type TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    message TEXT NOT NULL,
+    details TEXT,
+    suggested_action TEXT,
+    acknowledged INTEGER NOT NULL DEFAULT 0,
+    acknowledged_by TEXT,
+    acknowledged_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS equipment_assets (
+    id TEXT PRIMARY KEY,
+    asset_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    asset_type TEXT NOT NULL,
+    category TEXT,
+    manufacturer TEXT,
+    model TEXT,
+    serial_number TEXT,
+    location_id TEXT,
+    department_id TEXT,
+    parent_asset_id TEXT,
+    installation_date TEXT,
+    warranty_end_date TEXT,
+    criticality TEXT NOT NULL,
+    status TEXT NOT NULL,
+    acquisition_cost INTEGER NOT NULL,
+    depreciation_method TEXT,
+    useful_life_years INTEGER,
+    current_book_value INTEGER NOT NULL,
+    meter_type TEXT,
+    meter_unit TEXT,
+    current_meter_reading INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS asset_meter_readings (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    reading_date TEXT NOT NULL,
+    reading_value INTEGER NOT NULL,
+    reading_type TEXT NOT NULL,
+    entered_by TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS work_order_tasks (
+    id TEXT PRIMARY KEY,
+    work_order_id TEXT NOT NULL,
+    task_number INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    estimated_hours REAL NOT NULL DEFAULT 0,
+    actual_hours REAL NOT NULL DEFAULT 0,
+    assigned_to TEXT,
+    completed INTEGER NOT NULL DEFAULT 0,
+    completed_at TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS work_order_labor (
+    id TEXT PRIMARY KEY,
+    work_order_id TEXT NOT NULL,
+    employee_id TEXT NOT NULL,
+    labor_type TEXT NOT NULL,
+    start_time TEXT NOT NULL,
+    end_time TEXT,
+    hours REAL NOT NULL DEFAULT 0,
+    hourly_rate INTEGER NOT NULL DEFAULT 0,
+    total_cost INTEGER NOT NULL DEFAULT 0,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS work_order_parts (
+    id TEXT PRIMARY KEY,
+    work_order_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    quantity_required INTEGER NOT NULL,
+    quantity_issued INTEGER NOT NULL DEFAULT 0,
+    unit_cost INTEGER NOT NULL DEFAULT 0,
+    total_cost INTEGER NOT NULL DEFAULT 0,
+    issued_at TEXT,
+    issued_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pm_schedules (
+    id TEXT PRIMARY KEY,
+    pm_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    asset_id TEXT NOT NULL,
+    maintenance_strategy TEXT NOT NULL,
+    frequency_type TEXT NOT NULL,
+    frequency_value INTEGER NOT NULL,
+    last_performed_date TEXT,
+    next_due_date TEXT NOT NULL,
+    meter_based INTEGER NOT NULL DEFAULT 0,
+    last_meter_reading INTEGER,
+    next_meter_due INTEGER,
+    estimated_duration_hours REAL NOT NULL DEFAULT 0,
+    estimated_cost INTEGER NOT NULL DEFAULT 0,
+    auto_generate_wo INTEGER NOT NULL DEFAULT 1,
+    lead_time_days INTEGER NOT NULL DEFAULT 7,
+    checklist_id TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pm_tasks (
+    id TEXT PRIMARY KEY,
+    pm_schedule_id TEXT NOT NULL,
+    task_number INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    estimated_minutes INTEGER NOT NULL DEFAULT 0,
+    required_skills TEXT,
+    safety_notes TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS failure_codes (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT NOT NULL,
+    problem_type TEXT NOT NULL,
+    cause_type TEXT,
+    remedy_type TEXT,
+    parent_id TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS asset_failure_history (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    failure_date TEXT NOT NULL,
+    failure_code_id TEXT,
+    problem_description TEXT NOT NULL,
+    cause_description TEXT,
+    remedy_description TEXT,
+    downtime_hours REAL NOT NULL DEFAULT 0,
+    repair_cost INTEGER NOT NULL DEFAULT 0,
+    work_order_id TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS maintenance_calendars (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    working_days TEXT NOT NULL,
+    shift_start TEXT NOT NULL,
+    shift_end TEXT NOT NULL,
+    holidays TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS maintenance_shifts (
+    id TEXT PRIMARY KEY,
+    calendar_id TEXT NOT NULL,
+    shift_name TEXT NOT NULL,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    days_of_week TEXT NOT NULL,
+    crew_size INTEGER NOT NULL DEFAULT 1
+);
+CREATE TABLE IF NOT EXISTS spare_parts (
+    id TEXT PRIMARY KEY,
+    part_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    category TEXT,
+    manufacturer TEXT,
+    unit_of_measure TEXT NOT NULL,
+    unit_cost INTEGER NOT NULL DEFAULT 0,
+    min_stock_level INTEGER NOT NULL DEFAULT 0,
+    max_stock_level INTEGER NOT NULL DEFAULT 0,
+    reorder_point INTEGER NOT NULL DEFAULT 0,
+    current_stock INTEGER NOT NULL DEFAULT 0,
+    warehouse_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS asset_spare_parts (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    spare_part_id TEXT NOT NULL,
+    quantity_required INTEGER NOT NULL,
+    installation_date TEXT,
+    notes TEXT
+);
+CREATE TABLE IF NOT EXISTS maintenance_budgets (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    fiscal_year INTEGER NOT NULL,
+    department_id TEXT,
+    total_budget INTEGER NOT NULL DEFAULT 0,
+    labor_budget INTEGER NOT NULL DEFAULT 0,
+    parts_budget INTEGER NOT NULL DEFAULT 0,
+    contractor_budget INTEGER NOT NULL DEFAULT 0,
+    spent_to_date INTEGER NOT NULL DEFAULT 0,
+    committed_amount INTEGER NOT NULL DEFAULT 0,
+    remaining_budget INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS maintenance_kpis (
+    id TEXT PRIMARY KEY,
+    kpi_type TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    asset_id TEXT,
+    department_id TEXT,
+    value REAL NOT NULL,
+    target REAL NOT NULL,
+    variance REAL NOT NULL,
+    trend TEXT,
+    calculated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS asset_down_events (
+    id TEXT PRIMARY KEY,
+    asset_id TEXT NOT NULL,
+    down_start TEXT NOT NULL,
+    down_end TEXT,
+    downtime_hours REAL,
+    reason TEXT NOT NULL,
+    work_order_id TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS product_valuations (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    valuation_method TEXT NOT NULL,
+    standard_cost INTEGER NOT NULL DEFAULT 0,
+    current_unit_cost INTEGER NOT NULL DEFAULT 0,
+    total_quantity INTEGER NOT NULL DEFAULT 0,
+    total_value INTEGER NOT NULL DEFAULT 0,
+    last_receipt_cost INTEGER NOT NULL DEFAULT 0,
+    last_receipt_date TEXT,
+    last_issue_cost INTEGER NOT NULL DEFAULT 0,
+    last_issue_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(product_id, warehouse_id)
+);
+CREATE TABLE IF NOT EXISTS inventory_cost_layers (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    layer_date TEXT NOT NULL,
+    receipt_reference TEXT NOT NULL,
+    receipt_id TEXT,
+    quantity INTEGER NOT NULL,
+    unit_cost INTEGER NOT NULL,
+    remaining_quantity INTEGER NOT NULL,
+    total_value INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS inventory_valuation_summaries (
+    id TEXT PRIMARY KEY,
+    valuation_date TEXT NOT NULL,
+    warehouse_id TEXT,
+    category_id TEXT,
+    total_products INTEGER NOT NULL DEFAULT 0,
+    total_quantity INTEGER NOT NULL DEFAULT 0,
+    total_value INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS inventory_valuation_lines (
+    id TEXT PRIMARY KEY,
+    summary_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    quantity_on_hand INTEGER NOT NULL,
+    unit_cost INTEGER NOT NULL,
+    total_value INTEGER NOT NULL,
+    valuation_method TEXT NOT NULL,
+    previous_value INTEGER NOT NULL DEFAULT 0,
+    value_change INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS cost_adjustments (
+    id TEXT PRIMARY KEY,
+    adjustment_number TEXT NOT NULL UNIQUE,
+    adjustment_type TEXT NOT NULL,
+    adjustment_date TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    status TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT,
+    journal_entry_id TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cost_adjustment_lines (
+    id TEXT PRIMARY KEY,
+    adjustment_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    old_unit_cost INTEGER NOT NULL,
+    new_unit_cost INTEGER NOT NULL,
+    old_total_value INTEGER NOT NULL,
+    new_total_value INTEGER NOT NULL,
+    value_change INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS abc_classifications (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    classification TEXT NOT NULL,
+    annual_value INTEGER NOT NULL DEFAULT 0,
+    annual_quantity INTEGER NOT NULL DEFAULT 0,
+    cumulative_value_percent REAL NOT NULL DEFAULT 0,
+    classification_date TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS inventory_turnovers (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    beginning_inventory INTEGER NOT NULL DEFAULT 0,
+    ending_inventory INTEGER NOT NULL DEFAULT 0,
+    average_inventory INTEGER NOT NULL DEFAULT 0,
+    cost_of_goods_sold INTEGER NOT NULL DEFAULT 0,
+    turnover_ratio REAL NOT NULL DEFAULT 0,
+    days_of_inventory REAL NOT NULL DEFAULT 0,
+    calculated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS consignment_stocks (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    agreement_id TEXT,
+    quantity INTEGER NOT NULL,
+    unit_cost INTEGER NOT NULL DEFAULT 0,
+    total_value INTEGER NOT NULL DEFAULT 0,
+    ownership_status TEXT NOT NULL,
+    received_date TEXT NOT NULL,
+    consumption_start_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS consignment_consumptions (
+    id TEXT PRIMARY KEY,
+    consignment_id TEXT NOT NULL,
+    consumption_date TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_cost INTEGER NOT NULL DEFAULT 0,
+    total_cost INTEGER NOT NULL DEFAULT 0,
+    purchase_order_id TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS blanket_purchase_orders (
+    id TEXT PRIMARY KEY,
+    bpo_number TEXT NOT NULL UNIQUE,
+    vendor_id TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    total_amount_limit INTEGER NOT NULL DEFAULT 0,
+    total_quantity_limit INTEGER,
+    total_released INTEGER NOT NULL DEFAULT 0,
+    total_invoiced INTEGER NOT NULL DEFAULT 0,
+    payment_terms INTEGER NOT NULL DEFAULT 0,
+    terms_conditions TEXT,
+    status TEXT NOT NULL,
+    created_by TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS blanket_purchase_order_lines (
+    id TEXT PRIMARY KEY,
+    bpo_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    unit_price INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    min_release_qty INTEGER NOT NULL DEFAULT 1,
+    max_quantity INTEGER NOT NULL DEFAULT 0,
+    released_quantity INTEGER NOT NULL DEFAULT 0,
+    remaining_quantity INTEGER NOT NULL DEFAULT 0,
+    uom TEXT NOT NULL,
+    lead_time_days INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS release_orders (
+    id TEXT PRIMARY KEY,
+    release_number TEXT NOT NULL UNIQUE,
+    bpo_id TEXT NOT NULL,
+    bpo_line_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL DEFAULT 0,
+    total_amount INTEGER NOT NULL DEFAULT 0,
+    required_date TEXT NOT NULL,
+    ship_to_warehouse_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    purchase_order_id TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cash_pool_members (
+    id TEXT PRIMARY KEY,
+    pool_id TEXT NOT NULL,
+    bank_account_id TEXT NOT NULL,
+    company_id TEXT NOT NULL,
+    member_type TEXT NOT NULL,
+    participation_percent REAL NOT NULL DEFAULT 100,
+    target_balance INTEGER NOT NULL DEFAULT 0,
+    min_balance INTEGER NOT NULL DEFAULT 0,
+    max_balance INTEGER NOT NULL DEFAULT 0,
+    interest_rate_override REAL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cash_sweeps (
+    id TEXT PRIMARY KEY,
+    sweep_number TEXT NOT NULL UNIQUE,
+    pool_id TEXT NOT NULL,
+    sweep_date TEXT NOT NULL,
+    sweep_type TEXT NOT NULL,
+    total_amount INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    processed_at TEXT,
+    journal_entry_id TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cash_sweep_lines (
+    id TEXT PRIMARY KEY,
+    sweep_id TEXT NOT NULL,
+    member_id TEXT NOT NULL,
+    bank_account_id TEXT NOT NULL,
+    opening_balance INTEGER NOT NULL DEFAULT 0,
+    target_balance INTEGER NOT NULL DEFAULT 0,
+    sweep_amount INTEGER NOT NULL DEFAULT 0,
+    closing_balance INTEGER NOT NULL DEFAULT 0,
+    direction TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cash_position_details (
+    id TEXT PRIMARY KEY,
+    position_id TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    reference_type TEXT,
+    reference_id TEXT,
+    description TEXT,
+    amount INTEGER NOT NULL,
+    value_date TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS intercompany_loans (
+    id TEXT PRIMARY KEY,
+    loan_number TEXT NOT NULL UNIQUE,
+    from_company_id TEXT NOT NULL,
+    to_company_id TEXT NOT NULL,
+    from_account_id TEXT NOT NULL,
+    to_account_id TEXT NOT NULL,
+    principal_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    interest_rate REAL NOT NULL DEFAULT 0,
+    interest_type TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    maturity_date TEXT,
+    repayment_schedule TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS intercompany_loan_payments (
+    id TEXT PRIMARY KEY,
+    loan_id TEXT NOT NULL,
+    payment_date TEXT NOT NULL,
+    principal_amount INTEGER NOT NULL,
+    interest_amount INTEGER NOT NULL DEFAULT 0,
+    total_amount INTEGER NOT NULL,
+    from_journal_entry_id TEXT,
+    to_journal_entry_id TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS supplier_qualification_documents (
+    id TEXT PRIMARY KEY,
+    qualification_id TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    document_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    expiry_date TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS supplier_evaluations (
+    id TEXT PRIMARY KEY,
+    evaluation_number TEXT NOT NULL UNIQUE,
+    vendor_id TEXT NOT NULL,
+    evaluation_period_start TEXT NOT NULL,
+    evaluation_period_end TEXT NOT NULL,
+    quality_score INTEGER NOT NULL DEFAULT 0,
+    delivery_score INTEGER NOT NULL DEFAULT 0,
+    price_score INTEGER NOT NULL DEFAULT 0,
+    service_score INTEGER NOT NULL DEFAULT 0,
+    overall_score REAL NOT NULL DEFAULT 0,
+    grade TEXT NOT NULL,
+    evaluator_id TEXT,
+    evaluated_at TEXT NOT NULL,
+    comments TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS supplier_evaluation_criteria (
+    id TEXT PRIMARY KEY,
+    criteria_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    category TEXT NOT NULL,
+    weight INTEGER NOT NULL DEFAULT 1,
+    max_score INTEGER NOT NULL DEFAULT 100,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS supplier_evaluation_lines (
+    id TEXT PRIMARY KEY,
+    evaluation_id TEXT NOT NULL,
+    criteria_id TEXT NOT NULL,
+    score INTEGER NOT NULL DEFAULT 0,
+    weighted_score REAL NOT NULL DEFAULT 0,
+    comments TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_mrp_runs_date ON mrp_runs(run_date);
+CREATE INDEX IF NOT EXISTS idx_mrp_suggestions_product ON mrp_suggestions(product_id);
+CREATE INDEX IF NOT EXISTS idx_equipment_assets_type ON equipment_assets(asset_type);
+CREATE INDEX IF NOT EXISTS idx_work_orders_status ON work_orders(status);
+CREATE INDEX IF NOT EXISTS idx_pm_schedules_next_due ON pm_schedules(next_due_date);
+CREATE INDEX IF NOT EXISTS idx_inventory_cost_layers_product ON inventory_cost_layers(product_id, warehouse_id);
+CREATE INDEX IF NOT EXISTS idx_cash_pools_status ON cash_pools(status);
+CREATE INDEX IF NOT EXISTS idx_cash_positions_date ON cash_positions(position_date);
+CREATE INDEX IF NOT EXISTS idx_blanket_pos_vendor ON blanket_purchase_orders(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_supplier_qual_vendor ON supplier_qualifications(vendor_id);
+CREATE TABLE IF NOT EXISTS budget_periods (
+    id TEXT PRIMARY KEY,
+    budget_id TEXT NOT NULL,
+    period_type TEXT NOT NULL,
+    period_number INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    planned_amount_cents INTEGER NOT NULL DEFAULT 0,
+    actual_amount_cents INTEGER NOT NULL DEFAULT 0,
+    is_locked INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS forecasts (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    forecast_type TEXT NOT NULL,
+    method TEXT NOT NULL,
+    fiscal_year INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    total_forecast_cents INTEGER NOT NULL DEFAULT 0,
+    confidence_level REAL NOT NULL DEFAULT 0,
+    created_by TEXT NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS forecast_lines (
+    id TEXT PRIMARY KEY,
+    forecast_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    period_date TEXT NOT NULL,
+    forecasted_amount_cents INTEGER NOT NULL DEFAULT 0,
+    actual_amount_cents,
+    accuracy_score REAL,
+    factors TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS budget_transfers (
+    id TEXT PRIMARY KEY,
+    from_budget_line_id TEXT NOT NULL,
+    to_budget_line_id TEXT NOT NULL,
+    amount_cents INTEGER NOT NULL,
+    reason TEXT NOT NULL,
+    requested_by TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS budget_scenarios (
+    id TEXT PRIMARY KEY,
+    budget_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    scenario_type TEXT NOT NULL,
+    adjustment_factor REAL NOT NULL DEFAULT 1.0,
+    description TEXT,
+    total_amount_cents INTEGER NOT NULL DEFAULT 0,
+    is_baseline INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS budget_alerts (
+    id TEXT PRIMARY KEY,
+    budget_id TEXT NOT NULL,
+    budget_line_id_id TEXT,
+    alert_type TEXT NOT NULL,
+    threshold_percent REAL NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    last_triggered TEXT,
+    notify_users TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS budget_approvals (
+    id TEXT PRIMARY KEY,
+    budget_id TEXT NOT NULL,
+    approver_id TEXT NOT NULL,
+    approval_level INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL,
+    comments TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS budget_actuals (
+    id TEXT PRIMARY KEY,
+    budget_line_id TEXT NOT NULL,
+    transaction_date TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    reference_id TEXT,
+    amount_cents INTEGER NOT NULL,
+    description TEXT,
+    source_module TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS grants (
+    id TEXT PRIMARY KEY,
+    grant_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    grant_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    funding_source TEXT NOT NULL,
+    funder_name TEXT NOT NULL,
+    funder_contact TEXT,
+    total_award_amount_cents INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    indirect_cost_rate REAL NOT NULL DEFAULT 0,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    principal_investigator_id TEXT NOT NULL,
+    department_id TEXT,
+    program_id TEXT,
+    cfda_number TEXT,
+    award_number TEXT,
+    is_cost_sharing INTEGER NOT NULL DEFAULT 0,
+    cost_sharing_amount_cents INTEGER,
+    reporting_frequency TEXT NOT NULL DEFAULT 'Quarterly',
+    next_report_due TEXT,
+    compliance_requirements TEXT,
+    special_conditions TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS grant_budgets (
+    id TEXT PRIMARY KEY,
+    grant_id TEXT NOT NULL,
+    budget_category TEXT NOT NULL,
+    description TEXT NOT NULL,
+    approved_amount_cents INTEGER NOT NULL DEFAULT 0,
+    budgeted_amount_cents INTEGER NOT NULL DEFAULT 0,
+    expended_amount_cents INTEGER NOT NULL DEFAULT 0,
+    encumbered_amount_cents INTEGER NOT NULL DEFAULT 0,
+    available_balance_cents INTEGER NOT NULL DEFAULT 0,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS grant_personnel (
+    id TEXT PRIMARY KEY,
+    grant_id TEXT NOT NULL,
+    employee_id TEXT NOT NULL,
+    role TEXT NOT NULL,
+    effort_percent REAL NOT NULL DEFAULT 0,
+    hourly_rate_cents INTEGER NOT NULL DEFAULT 0,
+    total_budgeted_cents INTEGER NOT NULL DEFAULT 0,
+    total_charged_cents INTEGER NOT NULL DEFAULT 0,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    is_key_personnel INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS grant_transactions (
+    id TEXT PRIMARY KEY,
+    grant_id TEXT NOT NULL,
+    budget_category TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    transaction_date TEXT NOT NULL,
+    amount_cents INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    reference_number TEXT,
+    invoice_id TEXT,
+    journal_entry_id TEXT,
+    approved_by TEXT,
+    cost_sharing_flag INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS grant_milestones (
+    id TEXT PRIMARY KEY,
+    grant_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    due_date TEXT NOT NULL,
+    completed_date TEXT,
+    status TEXT NOT NULL DEFAULT 'NotStarted',
+    deliverables TEXT,
+    is_payment_trigger INTEGER NOT NULL DEFAULT 0,
+    payment_amount_cents INTEGER,
+    completed_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS grant_reports (
+    id TEXT PRIMARY KEY,
+    grant_id TEXT NOT NULL,
+    report_type TEXT NOT NULL,
+    reporting_period_start TEXT NOT NULL,
+    reporting_period_end TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    submitted_date TEXT,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    prepared_by TEXT,
+    approved_by TEXT,
+    notes TEXT,
+    attachment_ids TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS grant_compliance (
+    id TEXT PRIMARY KEY,
+    grant_id TEXT NOT NULL,
+    requirement_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    is_mandatory INTEGER NOT NULL DEFAULT 1,
+    due_date TEXT,
+    completed_date TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    responsible_party TEXT,
+    documentation_ids TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS grant_subawards (
+    id TEXT PRIMARY KEY,
+    grant_id TEXT NOT NULL,
+    subrecipient_id TEXT NOT NULL,
+    subrecipient_name TEXT NOT NULL,
+    subaward_number TEXT NOT NULL,
+    total_amount_cents INTEGER NOT NULL DEFAULT 0,
+    disbursed_amount_cents INTEGER NOT NULL DEFAULT 0,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    contact_person TEXT,
+    contact_email TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS grant_closeouts (
+    id TEXT PRIMARY KEY,
+    grant_id TEXT NOT NULL,
+    closeout_date TEXT NOT NULL,
+    final_expenditure_cents INTEGER NOT NULL DEFAULT 0,
+    unexpended_balance_cents INTEGER NOT NULL DEFAULT 0,
+    final_report_submitted INTEGER NOT NULL DEFAULT 0,
+    equipment_inventory_complete INTEGER NOT NULL DEFAULT 0,
+    inventions_reported INTEGER NOT NULL DEFAULT 0,
+    subawards_closed INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Initiated',
+    closed_by TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS activities (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    activity_type TEXT NOT NULL,
+    cost_pool_id TEXT NOT NULL,
+    total_cost_cents INTEGER NOT NULL DEFAULT 0,
+    cost_driver_id TEXT,
+    driver_quantity REAL NOT NULL DEFAULT 0,
+    cost_per_driver_cents INTEGER NOT NULL DEFAULT 0,
+    department_id TEXT,
+    process_id TEXT,
+    is_value_added INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cost_drivers (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    driver_type TEXT NOT NULL,
+    unit_of_measure TEXT NOT NULL,
+    total_capacity REAL NOT NULL DEFAULT 0,
+    used_capacity REAL NOT NULL DEFAULT 0,
+    unused_capacity REAL NOT NULL DEFAULT 0,
+    utilization_percent REAL NOT NULL DEFAULT 0,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cost_objects (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    object_type TEXT NOT NULL,
+    parent_id TEXT,
+    direct_cost_cents INTEGER NOT NULL DEFAULT 0,
+    indirect_cost_cents INTEGER NOT NULL DEFAULT 0,
+    total_cost_cents INTEGER NOT NULL DEFAULT 0,
+    revenue_cents INTEGER NOT NULL DEFAULT 0,
+    profit_margin_cents INTEGER NOT NULL DEFAULT 0,
+    profit_margin_percent REAL NOT NULL DEFAULT 0,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS activity_allocations (
+    id TEXT PRIMARY KEY,
+    activity_id TEXT NOT NULL,
+    cost_object_id TEXT NOT NULL,
+    cost_pool_id TEXT NOT NULL,
+    driver_quantity REAL NOT NULL DEFAULT 0,
+    allocation_rate_cents INTEGER NOT NULL DEFAULT 0,
+    allocated_amount_cents INTEGER NOT NULL DEFAULT 0,
+    allocation_date TEXT NOT NULL,
+    fiscal_period TEXT NOT NULL,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS processes (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    owner_id TEXT,
+    department_id TEXT,
+    total_activities INTEGER NOT NULL DEFAULT 0,
+    total_cost_cents INTEGER NOT NULL DEFAULT 0,
+    cycle_time_hours REAL NOT NULL DEFAULT 0,
+    is_core_process INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS article_versions (
+    id TEXT PRIMARY KEY,
+    article_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    change_summary TEXT,
+    author_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    is_current INTEGER NOT NULL DEFAULT 1
+);
+CREATE TABLE IF NOT EXISTS article_feedback (
+    id TEXT PRIMARY KEY,
+    article_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    is_helpful INTEGER NOT NULL,
+    rating INTEGER,
+    comment TEXT,
+    submitted_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tickets (
+    id TEXT PRIMARY KEY,
+    ticket_number TEXT NOT NULL UNIQUE,
+    subject TEXT NOT NULL,
+    description TEXT NOT NULL,
+    ticket_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'New',
+    priority TEXT NOT NULL DEFAULT 'Medium',
+    source TEXT NOT NULL DEFAULT 'Web',
+    requester_id TEXT NOT NULL,
+    requester_email TEXT NOT NULL,
+    requester_name TEXT NOT NULL,
+    assignee_id TEXT,
+    team_id TEXT,
+    department_id TEXT,
+    category_id TEXT,
+    subcategory_id TEXT,
+    due_date TEXT,
+    resolution_date TEXT,
+    first_response_at TEXT,
+    closed_at TEXT,
+    sla_id TEXT,
+    sla_breached INTEGER NOT NULL DEFAULT 0,
+    satisfaction_rating INTEGER,
+    satisfaction_comment TEXT,
+    tags TEXT,
+    custom_fields TEXT,
+    related_tickets TEXT,
+    parent_ticket_id TEXT,
+    knowledge_article_id TEXT,
+    asset_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS support_teams (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    email TEXT NOT NULL,
+    leader_id TEXT,
+    members TEXT,
+    category_ids TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    working_hours TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS data_quality_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    rule_type TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    target_entity TEXT NOT NULL,
+    target_field TEXT NOT NULL,
+    condition TEXT NOT NULL,
+    threshold REAL,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    schedule TEXT,
+    last_run TEXT,
+    last_result TEXT,
+    tags TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS data_quality_executions (
+    id TEXT PRIMARY KEY,
+    rule_id TEXT NOT NULL,
+    executed_at TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    score TEXT NOT NULL,
+    errors TEXT,
+    warnings TEXT,
+    records_processed INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS data_quality_profiles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    entity TEXT NOT NULL,
+    profile_date TEXT NOT NULL,
+    total_records INTEGER NOT NULL DEFAULT 0,
+    field_profiles TEXT,
+    overall_quality_score REAL NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS data_cleansing_jobs (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    source_entity TEXT NOT NULL,
+    target_entity TEXT,
+    transformations TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    created_by TEXT NOT NULL,
+    started_at TEXT,
+    completed_at TEXT,
+    records_processed INTEGER NOT NULL DEFAULT 0,
+    records_modified INTEGER NOT NULL DEFAULT 0,
+    records_failed INTEGER NOT NULL DEFAULT 0,
+    error_log TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS data_matching_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    entity TEXT NOT NULL,
+    match_fields TEXT,
+    blocking_keys TEXT,
+    match_threshold REAL NOT NULL DEFAULT 0.8,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS duplicate_groups (
+    id TEXT PRIMARY KEY,
+    entity TEXT NOT NULL,
+    canonical_id TEXT NOT NULL,
+    duplicate_ids TEXT,
+    match_score REAL NOT NULL,
+    detected_at TEXT NOT NULL,
+    resolved_at TEXT,
+    resolved_by TEXT,
+    resolution_type TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_promotions_code ON promotions(code);
+CREATE INDEX IF NOT EXISTS idx_promotions_status ON promotions(status);
+CREATE INDEX IF NOT EXISTS idx_promotions_dates ON promotions(start_date, end_date);
+CREATE TABLE IF NOT EXISTS promotion_products (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    promotion_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    include INTEGER NOT NULL DEFAULT 1,
+    FOREIGN KEY (promotion_id) REFERENCES promotions(id) ON DELETE CASCADE,
+    UNIQUE(promotion_id, product_id)
+);
+CREATE INDEX IF NOT EXISTS idx_promotion_products_promotion ON promotion_products(promotion_id);
+CREATE INDEX IF NOT EXISTS idx_promotion_products_product ON promotion_products(product_id);
+CREATE TABLE IF NOT EXISTS promotion_categories (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    promotion_id TEXT NOT NULL,
+    category_id TEXT NOT NULL,
+    include INTEGER NOT NULL DEFAULT 1,
+    FOREIGN KEY (promotion_id) REFERENCES promotions(id) ON DELETE CASCADE,
+    UNIQUE(promotion_id, category_id)
+);
+CREATE INDEX IF NOT EXISTS idx_promotion_categories_promotion ON promotion_categories(promotion_id);
+CREATE INDEX IF NOT EXISTS idx_promotion_categories_category ON promotion_categories(category_id);
+CREATE TABLE IF NOT EXISTS promotion_customer_groups (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    promotion_id TEXT NOT NULL,
+    customer_group_id TEXT NOT NULL,
+    FOREIGN KEY (promotion_id) REFERENCES promotions(id) ON DELETE CASCADE,
+    UNIQUE(promotion_id, customer_group_id)
+);
+CREATE INDEX IF NOT EXISTS idx_promotion_customer_groups_promotion ON promotion_customer_groups(promotion_id);
+CREATE INDEX IF NOT EXISTS idx_coupons_promotion ON coupons(promotion_id);
+CREATE INDEX IF NOT EXISTS idx_coupons_status ON coupons(status);
+CREATE TABLE IF NOT EXISTS coupon_batches (
+    id TEXT PRIMARY KEY,
+    promotion_id TEXT NOT NULL,
+    prefix TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    length INTEGER NOT NULL DEFAULT 8,
+    created_count INTEGER NOT NULL DEFAULT 0,
+    used_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (promotion_id) REFERENCES promotions(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS promotion_rules (
+    id TEXT PRIMARY KEY,
+    promotion_id TEXT NOT NULL,
+    rule_type TEXT NOT NULL DEFAULT 'Eligibility',
+    condition_type TEXT NOT NULL DEFAULT 'Order',
+    condition_field TEXT NOT NULL,
+    operator TEXT NOT NULL DEFAULT 'Equals',
+    value TEXT NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (promotion_id) REFERENCES promotions(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_promotion_rules_promotion ON promotion_rules(promotion_id);
+CREATE INDEX IF NOT EXISTS idx_promotion_rules_type ON promotion_rules(rule_type);
+CREATE TABLE IF NOT EXISTS buy_x_get_y_rules (
+    id TEXT PRIMARY KEY,
+    promotion_id TEXT NOT NULL,
+    buy_quantity INTEGER NOT NULL DEFAULT 1,
+    get_quantity INTEGER NOT NULL DEFAULT 1,
+    buy_product_ids TEXT NOT NULL DEFAULT '[]',
+    get_product_ids TEXT NOT NULL DEFAULT '[]',
+    discount_percent INTEGER NOT NULL DEFAULT 100,
+    max_free_items INTEGER,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (promotion_id) REFERENCES promotions(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS promotion_usages (
+    id TEXT PRIMARY KEY,
+    promotion_id TEXT,
+    coupon_id TEXT,
+    order_id TEXT NOT NULL,
+    customer_id TEXT,
+    customer_email TEXT,
+    discount_amount INTEGER NOT NULL DEFAULT 0,
+    original_amount INTEGER NOT NULL DEFAULT 0,
+    final_amount INTEGER NOT NULL DEFAULT 0,
+    used_at TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Applied',
+    FOREIGN KEY (promotion_id) REFERENCES promotions(id) ON DELETE SET NULL,
+    FOREIGN KEY (coupon_id) REFERENCES coupons(id) ON DELETE SET NULL
+);
+CREATE INDEX IF NOT EXISTS idx_promotion_usages_promotion ON promotion_usages(promotion_id);
+CREATE INDEX IF NOT EXISTS idx_promotion_usages_coupon ON promotion_usages(coupon_id);
+CREATE INDEX IF NOT EXISTS idx_promotion_usages_order ON promotion_usages(order_id);
+CREATE INDEX IF NOT EXISTS idx_promotion_usages_customer ON promotion_usages(customer_id);
+CREATE INDEX IF NOT EXISTS idx_promotion_usages_date ON promotion_usages(used_at);
+CREATE TABLE IF NOT EXISTS referral_programs (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    referrer_reward_type TEXT NOT NULL DEFAULT 'FixedAmount',
+    referrer_reward_value INTEGER NOT NULL DEFAULT 0,
+    referee_reward_type TEXT NOT NULL DEFAULT 'FixedAmount',
+    referee_reward_value INTEGER NOT NULL DEFAULT 0,
+    min_referee_purchase INTEGER NOT NULL DEFAULT 0,
+    max_referrals_per_user INTEGER,
+    total_referrals INTEGER NOT NULL DEFAULT 0,
+    total_rewards_given INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_referral_programs_status ON referral_programs(status);
+CREATE TABLE IF NOT EXISTS referrals (
+    id TEXT PRIMARY KEY,
+    program_id TEXT NOT NULL,
+    referrer_customer_id TEXT NOT NULL,
+    referrer_email TEXT NOT NULL,
+    referee_email TEXT NOT NULL,
+    referral_code TEXT NOT NULL UNIQUE,
+    coupon_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    referred_at TEXT NOT NULL,
+    converted_at TEXT,
+    reward_given_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (program_id) REFERENCES referral_programs(id) ON DELETE CASCADE,
+    FOREIGN KEY (coupon_id) REFERENCES coupons(id) ON DELETE SET NULL
+);
+CREATE INDEX IF NOT EXISTS idx_referrals_program ON referrals(program_id);
+CREATE INDEX IF NOT EXISTS idx_referrals_referrer ON referrals(referrer_customer_id);
+CREATE INDEX IF NOT EXISTS idx_referrals_code ON referrals(referral_code);
+CREATE INDEX IF NOT EXISTS idx_referrals_status ON referrals(status);
+CREATE TABLE IF NOT EXISTS bi_kpis (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    category TEXT NOT NULL,
+    kpi_type TEXT NOT NULL DEFAULT 'Counter',
+    aggregation TEXT NOT NULL DEFAULT 'Sum',
+    data_source TEXT NOT NULL,
+    query TEXT,
+    target_value REAL,
+    warning_threshold REAL,
+    critical_threshold REAL,
+    unit TEXT,
+    refresh_interval_seconds INTEGER NOT NULL DEFAULT 300,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS bi_kpi_values (
+    id TEXT PRIMARY KEY,
+    kpi_id TEXT NOT NULL,
+    value REAL NOT NULL,
+    previous_value REAL,
+    change_percent REAL,
+    trend TEXT,
+    recorded_at TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (kpi_id) REFERENCES bi_kpis(id)
+);
+CREATE INDEX IF NOT EXISTS idx_bi_kpi_values_kpi_id ON bi_kpi_values(kpi_id);
+CREATE INDEX IF NOT EXISTS idx_bi_kpi_values_recorded_at ON bi_kpi_values(recorded_at);
+CREATE TABLE IF NOT EXISTS bi_dashboards (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    owner_id TEXT NOT NULL,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    is_public INTEGER NOT NULL DEFAULT 0,
+    layout_config TEXT NOT NULL,
+    refresh_interval_seconds INTEGER NOT NULL DEFAULT 300,
+    filters TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS bi_dashboard_widgets (
+    id TEXT PRIMARY KEY,
+    dashboard_id TEXT NOT NULL,
+    kpi_id TEXT,
+    widget_type TEXT NOT NULL DEFAULT 'Number',
+    title TEXT NOT NULL,
+    position_x INTEGER NOT NULL DEFAULT 0,
+    position_y INTEGER NOT NULL DEFAULT 0,
+    width INTEGER NOT NULL DEFAULT 4,
+    height INTEGER NOT NULL DEFAULT 3,
+    config TEXT NOT NULL,
+    data_source TEXT,
+    custom_query TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (dashboard_id) REFERENCES bi_dashboards(id),
+    FOREIGN KEY (kpi_id) REFERENCES bi_kpis(id)
+);
+CREATE INDEX IF NOT EXISTS idx_bi_widgets_dashboard_id ON bi_dashboard_widgets(dashboard_id);
+CREATE TABLE IF NOT EXISTS bi_reports (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    category TEXT NOT NULL,
+    query TEXT NOT NULL,
+    parameters TEXT,
+    columns TEXT NOT NULL,
+    chart_config TEXT,
+    is_scheduled INTEGER NOT NULL DEFAULT 0,
+    schedule_cron TEXT,
+    last_run_at TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS bi_report_executions (
+    id TEXT PRIMARY KEY,
+    report_id TEXT NOT NULL,
+    executed_by TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    status TEXT NOT NULL DEFAULT 'running',
+    parameters_used TEXT,
+    row_count INTEGER,
+    result_data TEXT,
+    export_url TEXT,
+    error_message TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (report_id) REFERENCES bi_reports(id)
+);
+CREATE INDEX IF NOT EXISTS idx_bi_report_executions_report_id ON bi_report_executions(report_id);
+CREATE TABLE IF NOT EXISTS bi_data_cubes (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    source_table TEXT NOT NULL,
+    dimensions TEXT NOT NULL,
+    measures TEXT NOT NULL,
+    aggregations TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    last_refreshed TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS bi_alert_rules (
+    id TEXT PRIMARY KEY,
+    kpi_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    condition TEXT NOT NULL,
+    threshold REAL NOT NULL,
+    severity TEXT NOT NULL DEFAULT 'warning',
+    notification_channels TEXT NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    cooldown_minutes INTEGER NOT NULL DEFAULT 60,
+    last_triggered TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (kpi_id) REFERENCES bi_kpis(id)
+);
+CREATE TABLE IF NOT EXISTS bi_scorecards (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    perspective TEXT NOT NULL,
+    objectives TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    overall_score REAL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS i18n_locales (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    native_name TEXT NOT NULL,
+    language_code TEXT NOT NULL,
+    country_code TEXT,
+    is_rtl INTEGER NOT NULL DEFAULT 0,
+    date_format TEXT NOT NULL DEFAULT 'YYYY-MM-DD',
+    time_format TEXT NOT NULL DEFAULT 'HH:mm:ss',
+    number_format TEXT NOT NULL DEFAULT '#,##0.00',
+    currency_symbol TEXT NOT NULL DEFAULT '$',
+    currency_position TEXT NOT NULL DEFAULT 'before',
+    decimal_separator TEXT NOT NULL DEFAULT '.',
+    thousand_separator TEXT NOT NULL DEFAULT ',',
+    status TEXT NOT NULL DEFAULT 'Active',
+    is_default INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+INSERT OR IGNORE INTO i18n_locales (id, code, name, native_name, language_code, country_code, is_rtl, date_format, time_format, number_format, currency_symbol, currency_position, decimal_separator, thousand_separator, status, is_default, created_at, updated_at) VALUES ('en-default', 'en-US', 'English (US)', 'English (US)', 'en', NULL, 0, 'YYYY-MM-DD', 'HH:mm:ss', '#,##0.00', '$', 'before', '.', ',', 'Active', 1, '2026-10-18 16:36:14', '2026-10-18 16:36:14');
+CREATE TABLE IF NOT EXISTS i18n_translations (
+    id TEXT PRIMARY KEY,
+    locale_code TEXT NOT NULL,
+    namespace TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    plural_form TEXT,
+    context TEXT,
+    is_approved INTEGER NOT NULL DEFAULT 0,
+    translated_by TEXT,
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_i18n_translations_unique ON i18n_translations(locale_code, namespace, key, COALESCE(plural_form, ''), COALESCE(context, ''));
+CREATE INDEX IF NOT EXISTS idx_i18n_translations_locale ON i18n_translations(locale_code);
+CREATE INDEX IF NOT EXISTS idx_i18n_translations_namespace ON i18n_translations(namespace);
+CREATE TABLE IF NOT EXISTS i18n_namespaces (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    module TEXT NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS i18n_user_preferences (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL UNIQUE,
+    locale_code TEXT NOT NULL,
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    date_format_override TEXT,
+    time_format_override TEXT,
+    number_format_override TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS i18n_import_jobs (
+    id TEXT PRIMARY KEY,
+    locale_code TEXT NOT NULL,
+    namespace TEXT NOT NULL,
+    file_url TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    total_keys INTEGER NOT NULL DEFAULT 0,
+    imported_keys INTEGER NOT NULL DEFAULT 0,
+    skipped_keys INTEGER NOT NULL DEFAULT 0,
+    error_count INTEGER NOT NULL DEFAULT 0,
+    errors TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS i18n_export_jobs (
+    id TEXT PRIMARY KEY,
+    locale_code TEXT,
+    namespace TEXT,
+    format TEXT NOT NULL DEFAULT 'json',
+    file_url TEXT,
+    status TEXT NOT NULL DEFAULT 'pending',
+    total_keys INTEGER NOT NULL DEFAULT 0,
+    started_at TEXT,
+    completed_at TEXT,
+    expires_at TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS i18n_missing_translations (
+    id TEXT PRIMARY KEY,
+    locale_code TEXT NOT NULL,
+    namespace TEXT NOT NULL,
+    key TEXT NOT NULL,
+    first_seen_at TEXT NOT NULL,
+    usage_count INTEGER NOT NULL DEFAULT 1,
+    last_used_at TEXT,
+    priority TEXT NOT NULL DEFAULT 'medium',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(locale_code, namespace, key)
+);
+CREATE TABLE IF NOT EXISTS push_devices (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    device_token TEXT NOT NULL,
+    platform TEXT NOT NULL DEFAULT 'Web',
+    device_name TEXT,
+    device_model TEXT,
+    os_version TEXT,
+    app_version TEXT,
+    language TEXT,
+    timezone TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    last_used_at TEXT,
+    push_enabled INTEGER NOT NULL DEFAULT 1,
+    badge_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(user_id, device_token)
+);
+CREATE INDEX IF NOT EXISTS idx_push_devices_user ON push_devices(user_id);
+CREATE INDEX IF NOT EXISTS idx_push_notifications_status ON push_notifications(status);
+CREATE TABLE IF NOT EXISTS push_notification_recipients (
+    id TEXT PRIMARY KEY,
+    notification_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    device_id TEXT,
+    status TEXT NOT NULL DEFAULT 'pending',
+    sent_at TEXT,
+    delivered_at TEXT,
+    opened_at TEXT,
+    error_message TEXT,
+    external_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (notification_id) REFERENCES push_notifications(id)
+);
+CREATE INDEX IF NOT EXISTS idx_push_recipients_notification ON push_notification_recipients(notification_id);
+CREATE INDEX IF NOT EXISTS idx_push_recipients_user ON push_notification_recipients(user_id);
+CREATE TABLE IF NOT EXISTS push_templates (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    title_template TEXT NOT NULL,
+    body_template TEXT NOT NULL,
+    default_data TEXT,
+    category TEXT NOT NULL,
+    priority TEXT NOT NULL DEFAULT 'Normal',
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS push_campaigns (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    template_id TEXT,
+    title_override TEXT,
+    body_override TEXT,
+    data TEXT,
+    target_audience TEXT NOT NULL,
+    scheduled_at TEXT,
+    started_at TEXT,
+    completed_at TEXT,
+    status TEXT NOT NULL DEFAULT 'draft',
+    total_recipients INTEGER NOT NULL DEFAULT 0,
+    sent_count INTEGER NOT NULL DEFAULT 0,
+    delivered_count INTEGER NOT NULL DEFAULT 0,
+    opened_count INTEGER NOT NULL DEFAULT 0,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (template_id) REFERENCES push_templates(id)
+);
+CREATE TABLE IF NOT EXISTS push_preferences (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    category TEXT NOT NULL,
+    push_enabled INTEGER NOT NULL DEFAULT 1,
+    email_enabled INTEGER NOT NULL DEFAULT 1,
+    sms_enabled INTEGER NOT NULL DEFAULT 0,
+    in_app_enabled INTEGER NOT NULL DEFAULT 1,
+    quiet_hours_start TEXT,
+    quiet_hours_end TEXT,
+    quiet_hours_timezone TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(user_id, category)
+);
+CREATE TABLE IF NOT EXISTS push_providers (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    provider_type TEXT NOT NULL,
+    config TEXT NOT NULL,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    daily_limit INTEGER,
+    daily_sent INTEGER NOT NULL DEFAULT 0,
+    last_reset TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS bpm_process_definitions (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    code TEXT NOT NULL UNIQUE,
+    description TEXT,
+    category TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    bpmn_xml TEXT,
+    diagram_data TEXT,
+    variables TEXT,
+    forms TEXT,
+    owner_id TEXT NOT NULL,
+    published_at TEXT,
+    published_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_bpm_definitions_status ON bpm_process_definitions(status);
+CREATE INDEX IF NOT EXISTS idx_bpm_definitions_category ON bpm_process_definitions(category);
+CREATE TABLE IF NOT EXISTS bpm_process_nodes (
+    id TEXT PRIMARY KEY,
+    process_definition_id TEXT NOT NULL,
+    node_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    task_type TEXT NOT NULL DEFAULT 'UserTask',
+    gateway_type TEXT,
+    config TEXT,
+    assignee_expression TEXT,
+    candidate_groups TEXT,
+    form_key TEXT,
+    script TEXT,
+    service_name TEXT,
+    position_x INTEGER NOT NULL DEFAULT 0,
+    position_y INTEGER NOT NULL DEFAULT 0,
+    incoming_flows TEXT,
+    outgoing_flows TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_definition_id) REFERENCES bpm_process_definitions(id),
+    UNIQUE(process_definition_id, node_id)
+);
+CREATE INDEX IF NOT EXISTS idx_bpm_nodes_definition ON bpm_process_nodes(process_definition_id);
+CREATE TABLE IF NOT EXISTS bpm_process_flows (
+    id TEXT PRIMARY KEY,
+    process_definition_id TEXT NOT NULL,
+    flow_id TEXT NOT NULL,
+    name TEXT,
+    source_node_id TEXT NOT NULL,
+    target_node_id TEXT NOT NULL,
+    condition_expression TEXT,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_definition_id) REFERENCES bpm_process_definitions(id),
+    UNIQUE(process_definition_id, flow_id)
+);
+CREATE INDEX IF NOT EXISTS idx_bpm_flows_definition ON bpm_process_flows(process_definition_id);
+CREATE TABLE IF NOT EXISTS bpm_process_instances (
+    id TEXT PRIMARY KEY,
+    process_definition_id TEXT NOT NULL,
+    process_definition_version INTEGER NOT NULL,
+    business_key TEXT,
+    status TEXT NOT NULL DEFAULT 'Running',
+    variables TEXT,
+    started_by TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    completed_at TEXT,
+    current_node_id TEXT,
+    parent_instance_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_definition_id) REFERENCES bpm_process_definitions(id)
+);
+CREATE INDEX IF NOT EXISTS idx_bpm_instances_status ON bpm_process_instances(status);
+CREATE INDEX IF NOT EXISTS idx_bpm_instances_definition ON bpm_process_instances(process_definition_id);
+CREATE INDEX IF NOT EXISTS idx_bpm_instances_business_key ON bpm_process_instances(business_key);
+CREATE TABLE IF NOT EXISTS bpm_process_tasks (
+    id TEXT PRIMARY KEY,
+    process_instance_id TEXT NOT NULL,
+    process_definition_id TEXT NOT NULL,
+    node_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    task_type TEXT NOT NULL DEFAULT 'UserTask',
+    status TEXT NOT NULL DEFAULT 'Created',
+    assignee_id TEXT,
+    candidate_users TEXT,
+    candidate_groups TEXT,
+    form_key TEXT,
+    form_data TEXT,
+    variables TEXT,
+    priority INTEGER NOT NULL DEFAULT 0,
+    due_date TEXT,
+    created_at TEXT NOT NULL,
+    claimed_at TEXT,
+    completed_at TEXT,
+    completed_by TEXT,
+    outcome TEXT,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_instance_id) REFERENCES bpm_process_instances(id),
+    FOREIGN KEY (process_definition_id) REFERENCES bpm_process_definitions(id)
+);
+CREATE INDEX IF NOT EXISTS idx_bpm_tasks_instance ON bpm_process_tasks(process_instance_id);
+CREATE INDEX IF NOT EXISTS idx_bpm_tasks_assignee ON bpm_process_tasks(assignee_id);
+CREATE INDEX IF NOT EXISTS idx_bpm_tasks_status ON bpm_process_tasks(status);
+CREATE TABLE IF NOT EXISTS bpm_process_events (
+    id TEXT PRIMARY KEY,
+    process_instance_id TEXT NOT NULL,
+    task_id TEXT,
+    event_type TEXT NOT NULL,
+    event_data TEXT,
+    occurred_at TEXT NOT NULL,
+    user_id TEXT,
+    details TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_instance_id) REFERENCES bpm_process_instances(id)
+);
+CREATE INDEX IF NOT EXISTS idx_bpm_events_instance ON bpm_process_events(process_instance_id);
+CREATE TABLE IF NOT EXISTS bpm_sla_definitions (
+    id TEXT PRIMARY KEY,
+    process_definition_id TEXT,
+    node_id TEXT,
+    name TEXT NOT NULL,
+    target_duration_minutes INTEGER NOT NULL,
+    warning_threshold_minutes INTEGER,
+    escalation_actions TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_definition_id) REFERENCES bpm_process_definitions(id)
+);
+CREATE TABLE IF NOT EXISTS bpm_process_metrics (
+    id TEXT PRIMARY KEY,
+    process_definition_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    instances_started INTEGER NOT NULL DEFAULT 0,
+    instances_completed INTEGER NOT NULL DEFAULT 0,
+    instances_cancelled INTEGER NOT NULL DEFAULT 0,
+    avg_duration_minutes REAL,
+    min_duration_minutes INTEGER,
+    max_duration_minutes INTEGER,
+    tasks_completed INTEGER NOT NULL DEFAULT 0,
+    avg_task_duration_minutes REAL,
+    sla_breaches INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (process_definition_id) REFERENCES bpm_process_definitions(id)
+);
+CREATE TABLE IF NOT EXISTS kanban_boards (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    board_type TEXT NOT NULL DEFAULT 'Generic',
+    team_id TEXT,
+    project_id TEXT,
+    swimlane_type TEXT NOT NULL DEFAULT 'None',
+    default_wip_limit INTEGER,
+    allow_card_reordering INTEGER NOT NULL DEFAULT 1,
+    show_card_count INTEGER NOT NULL DEFAULT 1,
+    show_wip_limits INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS kanban_columns (
+    id TEXT PRIMARY KEY,
+    board_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    wip_limit INTEGER,
+    is_done_column INTEGER NOT NULL DEFAULT 0,
+    is_backlog INTEGER NOT NULL DEFAULT 0,
+    color TEXT,
+    auto_assign_on_move TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES kanban_boards(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS kanban_swimlanes (
+    id TEXT PRIMARY KEY,
+    board_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    color TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES kanban_boards(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS kanban_cards (
+    id TEXT PRIMARY KEY,
+    board_id TEXT NOT NULL,
+    column_id TEXT NOT NULL,
+    swimlane_id TEXT,
+    card_type TEXT NOT NULL DEFAULT 'Task',
+    title TEXT NOT NULL,
+    description TEXT,
+    priority TEXT NOT NULL DEFAULT 'Medium',
+    position INTEGER NOT NULL DEFAULT 0,
+    assignee_ids TEXT NOT NULL DEFAULT '[]',
+    reporter_id TEXT,
+    due_date TEXT,
+    start_date TEXT,
+    completed_date TEXT,
+    estimated_hours REAL,
+    actual_hours REAL,
+    story_points INTEGER,
+    tags TEXT NOT NULL DEFAULT '[]',
+    external_ref_type TEXT,
+    external_ref_id TEXT,
+    blocked INTEGER NOT NULL DEFAULT 0,
+    blocked_reason TEXT,
+    parent_card_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES kanban_boards(id) ON DELETE CASCADE,
+    FOREIGN KEY (column_id) REFERENCES kanban_columns(id) ON DELETE CASCADE,
+    FOREIGN KEY (swimlane_id) REFERENCES kanban_swimlanes(id) ON DELETE SET NULL,
+    FOREIGN KEY (parent_card_id) REFERENCES kanban_cards(id) ON DELETE SET NULL
+);
+CREATE TABLE IF NOT EXISTS kanban_card_comments (
+    id TEXT PRIMARY KEY,
+    card_id TEXT NOT NULL,
+    author_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT,
+    FOREIGN KEY (card_id) REFERENCES kanban_cards(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS kanban_card_attachments (
+    id TEXT PRIMARY KEY,
+    card_id TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    file_size INTEGER NOT NULL DEFAULT 0,
+    content_type TEXT NOT NULL,
+    uploaded_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (card_id) REFERENCES kanban_cards(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS kanban_card_checklists (
+    id TEXT PRIMARY KEY,
+    card_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (card_id) REFERENCES kanban_cards(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS kanban_card_checklist_items (
+    id TEXT PRIMARY KEY,
+    checklist_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    completed INTEGER NOT NULL DEFAULT 0,
+    completed_by TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (checklist_id) REFERENCES kanban_card_checklists(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS kanban_card_moves (
+    id TEXT PRIMARY KEY,
+    card_id TEXT NOT NULL,
+    from_column_id TEXT NOT NULL,
+    to_column_id TEXT NOT NULL,
+    from_position INTEGER NOT NULL,
+    to_position INTEGER NOT NULL,
+    moved_by TEXT NOT NULL,
+    moved_at TEXT NOT NULL,
+    time_in_from_column_seconds INTEGER,
+    FOREIGN KEY (card_id) REFERENCES kanban_cards(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS kanban_card_labels (
+    id TEXT PRIMARY KEY,
+    board_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    color TEXT NOT NULL DEFAULT '#6B7280',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES kanban_boards(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS kanban_card_label_assignments (
+    id TEXT PRIMARY KEY,
+    card_id TEXT NOT NULL,
+    label_id TEXT NOT NULL,
+    assigned_at TEXT NOT NULL,
+    FOREIGN KEY (card_id) REFERENCES kanban_cards(id) ON DELETE CASCADE,
+    FOREIGN KEY (label_id) REFERENCES kanban_card_labels(id) ON DELETE CASCADE,
+    UNIQUE(card_id, label_id)
+);
+CREATE TABLE IF NOT EXISTS kanban_activity_logs (
+    id TEXT PRIMARY KEY,
+    board_id TEXT NOT NULL,
+    card_id TEXT,
+    action_type TEXT NOT NULL,
+    actor_id TEXT NOT NULL,
+    description TEXT NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES kanban_boards(id) ON DELETE CASCADE,
+    FOREIGN KEY (card_id) REFERENCES kanban_cards(id) ON DELETE SET NULL
+);
+CREATE TABLE IF NOT EXISTS kanban_wip_violations (
+    id TEXT PRIMARY KEY,
+    board_id TEXT NOT NULL,
+    column_id TEXT NOT NULL,
+    current_count INTEGER NOT NULL,
+    wip_limit INTEGER NOT NULL,
+    violated_at TEXT NOT NULL,
+    resolved_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES kanban_boards(id) ON DELETE CASCADE,
+    FOREIGN KEY (column_id) REFERENCES kanban_columns(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_kanban_boards_status ON kanban_boards(status);
+CREATE INDEX IF NOT EXISTS idx_kanban_columns_board ON kanban_columns(board_id);
+CREATE INDEX IF NOT EXISTS idx_kanban_cards_board ON kanban_cards(board_id);
+CREATE INDEX IF NOT EXISTS idx_kanban_cards_column ON kanban_cards(column_id);
+CREATE INDEX IF NOT EXISTS idx_kanban_cards_status ON kanban_cards(status);
+CREATE INDEX IF NOT EXISTS idx_kanban_cards_blocked ON kanban_cards(blocked);
+CREATE INDEX IF NOT EXISTS idx_kanban_cards_due_date ON kanban_cards(due_date);
+CREATE INDEX IF NOT EXISTS idx_kanban_card_comments_card ON kanban_card_comments(card_id);
+CREATE INDEX IF NOT EXISTS idx_kanban_card_moves_card ON kanban_card_moves(card_id);
+CREATE INDEX IF NOT EXISTS idx_kanban_card_moves_moved_at ON kanban_card_moves(moved_at);
+CREATE INDEX IF NOT EXISTS idx_kanban_activity_logs_board ON kanban_activity_logs(board_id);
+CREATE INDEX IF NOT EXISTS idx_kanban_activity_logs_created ON kanban_activity_logs(created_at);
+CREATE INDEX IF NOT EXISTS idx_kanban_wip_violations_board ON kanban_wip_violations(board_id);
+CREATE INDEX IF NOT EXISTS idx_kanban_wip_violations_resolved ON kanban_wip_violations(resolved_at);
+CREATE TABLE IF NOT EXISTS currency_revaluations (
+    id TEXT PRIMARY KEY,
+    revaluation_number TEXT NOT NULL UNIQUE,
+    revaluation_date TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    base_currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    total_unrealized_gain INTEGER NOT NULL DEFAULT 0,
+    total_unrealized_loss INTEGER NOT NULL DEFAULT 0,
+    net_unrealized INTEGER NOT NULL DEFAULT 0,
+    journal_entry_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    FOREIGN KEY (journal_entry_id) REFERENCES journal_entries(id)
+);
+CREATE INDEX IF NOT EXISTS idx_currency_revaluations_date ON currency_revaluations(revaluation_date);
+CREATE INDEX IF NOT EXISTS idx_currency_revaluations_status ON currency_revaluations(status);
+CREATE INDEX IF NOT EXISTS idx_currency_revaluations_period ON currency_revaluations(period_start, period_end);
+CREATE TABLE IF NOT EXISTS currency_revaluation_lines (
+    id TEXT PRIMARY KEY,
+    revaluation_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    account_code TEXT NOT NULL,
+    account_name TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    original_balance INTEGER NOT NULL,
+    original_rate REAL NOT NULL,
+    revaluation_rate REAL NOT NULL,
+    base_currency_balance INTEGER NOT NULL,
+    revalued_balance INTEGER NOT NULL,
+    unrealized_gain INTEGER NOT NULL DEFAULT 0,
+    unrealized_loss INTEGER NOT NULL DEFAULT 0,
+    gain_account_id TEXT,
+    loss_account_id TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (revaluation_id) REFERENCES currency_revaluations(id) ON DELETE CASCADE,
+    FOREIGN KEY (account_id) REFERENCES accounts(id),
+    FOREIGN KEY (gain_account_id) REFERENCES accounts(id),
+    FOREIGN KEY (loss_account_id) REFERENCES accounts(id)
+);
+CREATE INDEX IF NOT EXISTS idx_currency_reval_lines_reval ON currency_revaluation_lines(revaluation_id);
+CREATE INDEX IF NOT EXISTS idx_currency_reval_lines_account ON currency_revaluation_lines(account_id);
+CREATE INDEX IF NOT EXISTS idx_currency_reval_lines_currency ON currency_revaluation_lines(currency);
+CREATE TABLE IF NOT EXISTS approval_workflows (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    document_type TEXT NOT NULL,
+    approval_type TEXT NOT NULL DEFAULT 'Sequential',
+    min_amount INTEGER,
+    max_amount INTEGER,
+    auto_approve_below INTEGER,
+    escalation_hours INTEGER,
+    notify_requester INTEGER NOT NULL DEFAULT 1,
+    notify_approver INTEGER NOT NULL DEFAULT 1,
+    allow_delegation INTEGER NOT NULL DEFAULT 1,
+    allow_reassignment INTEGER NOT NULL DEFAULT 0,
+    require_comments INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS approval_records (
+    id TEXT PRIMARY KEY,
+    request_id TEXT NOT NULL,
+    level_number INTEGER NOT NULL,
+    approver_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    comments TEXT,
+    delegated_to TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (request_id) REFERENCES approval_requests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS approval_delegations (
+    id TEXT PRIMARY KEY,
+    from_user_id TEXT NOT NULL,
+    to_user_id TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    document_types TEXT NOT NULL DEFAULT '[]',
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_approval_workflows_document_type ON approval_workflows(document_type);
+CREATE INDEX IF NOT EXISTS idx_approval_workflows_status ON approval_workflows(status);
+CREATE INDEX IF NOT EXISTS idx_approval_workflow_levels_workflow ON approval_workflow_levels(workflow_id);
+CREATE INDEX IF NOT EXISTS idx_approval_requests_requested_by ON approval_requests(requested_by);
+CREATE INDEX IF NOT EXISTS idx_approval_records_request ON approval_records(request_id);
+CREATE INDEX IF NOT EXISTS idx_approval_delegations_from ON approval_delegations(from_user_id);
+CREATE INDEX IF NOT EXISTS idx_approval_delegations_to ON approval_delegations(to_user_id);
+CREATE TABLE IF NOT EXISTS customer_credit_profiles (
+    id TEXT PRIMARY KEY,
+    customer_id TEXT NOT NULL UNIQUE,
+    credit_limit INTEGER NOT NULL DEFAULT 0,
+    credit_used INTEGER NOT NULL DEFAULT 0,
+    available_credit INTEGER NOT NULL DEFAULT 0,
+    outstanding_invoices INTEGER NOT NULL DEFAULT 0,
+    pending_orders INTEGER NOT NULL DEFAULT 0,
+    overdue_amount INTEGER NOT NULL DEFAULT 0,
+    overdue_days_avg INTEGER NOT NULL DEFAULT 0,
+    credit_score INTEGER,
+    risk_level TEXT NOT NULL DEFAULT 'Low',
+    payment_history_score REAL,
+    last_credit_review TEXT,
+    next_review_date TEXT,
+    auto_hold_enabled INTEGER NOT NULL DEFAULT 1,
+    hold_threshold_percent INTEGER NOT NULL DEFAULT 90,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_credit_profiles_customer ON customer_credit_profiles(customer_id);
+CREATE INDEX IF NOT EXISTS idx_credit_profiles_risk ON customer_credit_profiles(risk_level);
+CREATE INDEX IF NOT EXISTS idx_credit_profiles_status ON customer_credit_profiles(status);
+CREATE TABLE IF NOT EXISTS credit_transactions (
+    id TEXT PRIMARY KEY,
+    profile_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    previous_credit_used INTEGER NOT NULL,
+    new_credit_used INTEGER NOT NULL,
+    reference_type TEXT,
+    reference_id TEXT,
+    reference_number TEXT,
+    description TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (profile_id) REFERENCES customer_credit_profiles(id),
+    FOREIGN KEY (customer_id) REFERENCES customers(id)
+);
+CREATE INDEX IF NOT EXISTS idx_credit_transactions_customer ON credit_transactions(customer_id);
+CREATE INDEX IF NOT EXISTS idx_credit_transactions_type ON credit_transactions(transaction_type);
+CREATE INDEX IF NOT EXISTS idx_credit_transactions_date ON credit_transactions(created_at);
+CREATE TABLE IF NOT EXISTS credit_holds (
+    id TEXT PRIMARY KEY,
+    profile_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    hold_type TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    amount_over_limit INTEGER NOT NULL DEFAULT 0,
+    related_order_id TEXT,
+    related_invoice_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    placed_by TEXT,
+    placed_at TEXT NOT NULL,
+    released_by TEXT,
+    released_at TEXT,
+    override_reason TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (profile_id) REFERENCES customer_credit_profiles(id),
+    FOREIGN KEY (customer_id) REFERENCES customers(id)
+);
+CREATE INDEX IF NOT EXISTS idx_credit_holds_customer ON credit_holds(customer_id);
+CREATE INDEX IF NOT EXISTS idx_credit_holds_status ON credit_holds(status);
+CREATE INDEX IF NOT EXISTS idx_credit_holds_active ON credit_holds(customer_id, status);
+CREATE TABLE IF NOT EXISTS credit_limit_changes (
+    id TEXT PRIMARY KEY,
+    profile_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    previous_limit INTEGER NOT NULL,
+    new_limit INTEGER NOT NULL,
+    change_reason TEXT NOT NULL,
+    approved_by TEXT,
+    approved_at TEXT,
+    effective_date TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (profile_id) REFERENCES customer_credit_profiles(id),
+    FOREIGN KEY (customer_id) REFERENCES customers(id)
+);
+CREATE INDEX IF NOT EXISTS idx_credit_limit_changes_customer ON credit_limit_changes(customer_id);
+CREATE TABLE IF NOT EXISTS credit_alerts (
+    id TEXT PRIMARY KEY,
+    profile_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    alert_type TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    message TEXT NOT NULL,
+    threshold_value INTEGER NOT NULL,
+    actual_value INTEGER NOT NULL,
+    is_read INTEGER NOT NULL DEFAULT 0,
+    acknowledged_by TEXT,
+    acknowledged_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (profile_id) REFERENCES customer_credit_profiles(id),
+    FOREIGN KEY (customer_id) REFERENCES customers(id)
+);
+CREATE INDEX IF NOT EXISTS idx_credit_alerts_customer ON credit_alerts(customer_id);
+CREATE INDEX IF NOT EXISTS idx_credit_alerts_unread ON credit_alerts(is_read);
+CREATE INDEX IF NOT EXISTS idx_credit_alerts_severity ON credit_alerts(severity);
+CREATE TABLE IF NOT EXISTS payment_allocations (
+    id TEXT PRIMARY KEY,
+    payment_id TEXT NOT NULL,
+    invoice_id TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS customer_payment_methods (
+    id TEXT PRIMARY KEY,
+    customer_id TEXT NOT NULL,
+    payment_method TEXT NOT NULL,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    card_last_four TEXT,
+    card_brand TEXT,
+    card_expiry_month INTEGER,
+    card_expiry_year INTEGER,
+    card_holder_name TEXT,
+    bank_name TEXT,
+    bank_account_type TEXT,
+    gateway_token TEXT,
+    nickname TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS stripe_payment_intents (
+    id TEXT PRIMARY KEY,
+    stripe_intent_id TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    invoice_id TEXT,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    status TEXT NOT NULL,
+    client_secret TEXT,
+    description TEXT,
+    metadata TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS stripe_checkout_sessions (
+    id TEXT PRIMARY KEY,
+    stripe_session_id TEXT NOT NULL UNIQUE,
+    customer_id TEXT NOT NULL,
+    invoice_id TEXT,
+    amount INTEGER NOT NULL,
+    currency TEXT NOT NULL,
+    status TEXT NOT NULL,
+    checkout_url TEXT,
+    success_url TEXT NOT NULL,
+    cancel_url TEXT NOT NULL,
+    payment_intent_id TEXT,
+    expires_at TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS stripe_webhook_events (
+    id TEXT PRIMARY KEY,
+    stripe_event_id TEXT NOT NULL UNIQUE,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    processed INTEGER NOT NULL DEFAULT 0,
+    processed_at TEXT,
+    error_message TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_payments_invoice ON payments(invoice_id);
+CREATE INDEX IF NOT EXISTS idx_stripe_intents_customer ON stripe_payment_intents(customer_id);
+CREATE INDEX IF NOT EXISTS idx_stripe_intents_status ON stripe_payment_intents(status);
+CREATE INDEX IF NOT EXISTS idx_stripe_sessions_customer ON stripe_checkout_sessions(customer_id);
+CREATE INDEX IF NOT EXISTS idx_stripe_webhook_type ON stripe_webhook_events(event_type);
+CREATE INDEX IF NOT EXISTS idx_stripe_webhook_processed ON stripe_webhook_events(processed);
+CREATE TABLE IF NOT EXISTS vendor_dropship_settings (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL UNIQUE,
+    enabled INTEGER NOT NULL DEFAULT 0,
+    tier TEXT NOT NULL DEFAULT 'Standard',
+    auto_confirm INTEGER NOT NULL DEFAULT 0,
+    require_approval INTEGER NOT NULL DEFAULT 0,
+    min_order_value INTEGER NOT NULL DEFAULT 0,
+    max_order_value INTEGER,
+    processing_time_days INTEGER NOT NULL DEFAULT 2,
+    shipping_carrier TEXT,
+    shipping_method TEXT,
+    free_shipping_threshold INTEGER,
+    handling_fee INTEGER NOT NULL DEFAULT 0,
+    allow_partial_shipment INTEGER NOT NULL DEFAULT 1,
+    return_policy_days INTEGER NOT NULL DEFAULT 30,
+    notification_email TEXT,
+    api_endpoint TEXT,
+    api_key TEXT,
+    sync_inventory INTEGER NOT NULL DEFAULT 0,
+    inventory_sync_hours INTEGER NOT NULL DEFAULT 24,
+    product_feed_url TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS drop_ship_orders (
+    id TEXT PRIMARY KEY,
+    order_number TEXT NOT NULL UNIQUE,
+    sales_order_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    purchase_order_id TEXT,
+    ship_to_name TEXT NOT NULL,
+    ship_to_company TEXT,
+    ship_to_address TEXT NOT NULL,
+    ship_to_city TEXT NOT NULL,
+    ship_to_state TEXT,
+    ship_to_postal_code TEXT NOT NULL,
+    ship_to_country TEXT NOT NULL,
+    ship_to_phone TEXT,
+    ship_to_email TEXT,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    shipping_cost INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Pending',
+    vendor_confirmation_number TEXT,
+    expected_ship_date TEXT,
+    actual_ship_date TEXT,
+    expected_delivery_date TEXT,
+    actual_delivery_date TEXT,
+    notes TEXT,
+    internal_notes TEXT,
+    priority INTEGER NOT NULL DEFAULT 0,
+    created_by TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    sent_to_vendor_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS drop_ship_order_lines (
+    id TEXT PRIMARY KEY,
+    drop_ship_order_id TEXT NOT NULL,
+    sales_order_line_id TEXT,
+    product_id TEXT NOT NULL,
+    vendor_sku TEXT,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    quantity_shipped INTEGER NOT NULL DEFAULT 0,
+    quantity_cancelled INTEGER NOT NULL DEFAULT 0,
+    unit_price INTEGER NOT NULL,
+    line_total INTEGER NOT NULL,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    FOREIGN KEY (drop_ship_order_id) REFERENCES drop_ship_orders(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_purchase_orders (
+    id TEXT PRIMARY KEY,
+    po_number TEXT NOT NULL UNIQUE,
+    vendor_id TEXT NOT NULL,
+    drop_ship_order_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    ship_to_name TEXT NOT NULL,
+    ship_to_address TEXT NOT NULL,
+    ship_to_city TEXT NOT NULL,
+    ship_to_state TEXT,
+    ship_to_postal_code TEXT NOT NULL,
+    ship_to_country TEXT NOT NULL,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    shipping_cost INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    vendor_acknowledgment TEXT,
+    vendor_acknowledged_at TEXT,
+    expected_delivery TEXT,
+    terms TEXT,
+    notes TEXT,
+    created_by TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (drop_ship_order_id) REFERENCES drop_ship_orders(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_po_lines (
+    id TEXT PRIMARY KEY,
+    po_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    vendor_sku TEXT,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    quantity_received INTEGER NOT NULL DEFAULT 0,
+    unit_cost INTEGER NOT NULL,
+    line_total INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    FOREIGN KEY (po_id) REFERENCES drop_ship_purchase_orders(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_shipments (
+    id TEXT PRIMARY KEY,
+    shipment_number TEXT NOT NULL UNIQUE,
+    drop_ship_order_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    carrier TEXT NOT NULL,
+    carrier_service TEXT,
+    tracking_number TEXT,
+    tracking_url TEXT,
+    shipping_label_url TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    ship_date TEXT,
+    estimated_delivery TEXT,
+    actual_delivery TEXT,
+    weight REAL,
+    weight_unit TEXT,
+    dimensions TEXT,
+    shipped_from_address TEXT,
+    shipped_from_city TEXT,
+    shipped_from_state TEXT,
+    shipped_from_postal TEXT,
+    shipped_from_country TEXT,
+    signature_required INTEGER NOT NULL DEFAULT 0,
+    signed_by TEXT,
+    signed_at TEXT,
+    delivery_notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (drop_ship_order_id) REFERENCES drop_ship_orders(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_shipment_lines (
+    id TEXT PRIMARY KEY,
+    shipment_id TEXT NOT NULL,
+    drop_ship_order_line_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    condition TEXT,
+    notes TEXT,
+    FOREIGN KEY (shipment_id) REFERENCES drop_ship_shipments(id),
+    FOREIGN KEY (drop_ship_order_line_id) REFERENCES drop_ship_order_lines(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_tracking_events (
+    id TEXT PRIMARY KEY,
+    shipment_id TEXT NOT NULL,
+    event_timestamp TEXT NOT NULL,
+    status_code TEXT NOT NULL,
+    status_description TEXT NOT NULL,
+    location_city TEXT,
+    location_state TEXT,
+    location_country TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (shipment_id) REFERENCES drop_ship_shipments(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_inventory_feeds (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    feed_url TEXT,
+    feed_format TEXT NOT NULL DEFAULT 'CSV',
+    last_sync_at TEXT,
+    last_sync_status TEXT,
+    products_count INTEGER NOT NULL DEFAULT 0,
+    in_stock_count INTEGER NOT NULL DEFAULT 0,
+    out_of_stock_count INTEGER NOT NULL DEFAULT 0,
+    sync_enabled INTEGER NOT NULL DEFAULT 0,
+    sync_interval_hours INTEGER NOT NULL DEFAULT 24,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS drop_ship_inventory_items (
+    id TEXT PRIMARY KEY,
+    feed_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    vendor_sku TEXT NOT NULL,
+    quantity_available INTEGER NOT NULL DEFAULT 0,
+    quantity_reserved INTEGER NOT NULL DEFAULT 0,
+    reorder_point INTEGER,
+    expected_restock_date TEXT,
+    cost INTEGER NOT NULL DEFAULT 0,
+    msrp INTEGER,
+    map_price INTEGER,
+    weight REAL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    last_updated TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (feed_id) REFERENCES drop_ship_inventory_feeds(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_invoices (
+    id TEXT PRIMARY KEY,
+    invoice_number TEXT NOT NULL UNIQUE,
+    vendor_id TEXT NOT NULL,
+    drop_ship_order_id TEXT NOT NULL,
+    purchase_order_id TEXT,
+    invoice_date TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    shipping_cost INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    vendor_invoice_number TEXT,
+    paid_amount INTEGER NOT NULL DEFAULT 0,
+    paid_at TEXT,
+    payment_reference TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (drop_ship_order_id) REFERENCES drop_ship_orders(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_invoice_lines (
+    id TEXT PRIMARY KEY,
+    invoice_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_cost INTEGER NOT NULL,
+    line_total INTEGER NOT NULL,
+    FOREIGN KEY (invoice_id) REFERENCES drop_ship_invoices(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_returns (
+    id TEXT PRIMARY KEY,
+    return_number TEXT NOT NULL UNIQUE,
+    drop_ship_order_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    return_type TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    request_date TEXT NOT NULL,
+    authorized_date TEXT,
+    received_date TEXT,
+    processed_date TEXT,
+    total_amount INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Requested',
+    vendor_rma_number TEXT,
+    refund_method TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (drop_ship_order_id) REFERENCES drop_ship_orders(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_return_lines (
+    id TEXT PRIMARY KEY,
+    return_id TEXT NOT NULL,
+    drop_ship_order_line_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    condition TEXT,
+    disposition TEXT,
+    refund_amount INTEGER NOT NULL DEFAULT 0,
+    notes TEXT,
+    FOREIGN KEY (return_id) REFERENCES drop_ship_returns(id),
+    FOREIGN KEY (drop_ship_order_line_id) REFERENCES drop_ship_order_lines(id)
+);
+CREATE TABLE IF NOT EXISTS drop_ship_performance (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    total_orders INTEGER NOT NULL DEFAULT 0,
+    orders_on_time INTEGER NOT NULL DEFAULT 0,
+    orders_late INTEGER NOT NULL DEFAULT 0,
+    orders_cancelled INTEGER NOT NULL DEFAULT 0,
+    total_items INTEGER NOT NULL DEFAULT 0,
+    items_shipped INTEGER NOT NULL DEFAULT 0,
+    items_backordered INTEGER NOT NULL DEFAULT 0,
+    avg_processing_time_hours REAL NOT NULL DEFAULT 0,
+    avg_shipping_time_hours REAL NOT NULL DEFAULT 0,
+    on_time_delivery_rate REAL NOT NULL DEFAULT 0,
+    fill_rate REAL NOT NULL DEFAULT 0,
+    cancellation_rate REAL NOT NULL DEFAULT 0,
+    return_rate REAL NOT NULL DEFAULT 0,
+    customer_complaints INTEGER NOT NULL DEFAULT 0,
+    quality_score REAL NOT NULL DEFAULT 0,
+    overall_score REAL NOT NULL DEFAULT 0,
+    tier TEXT NOT NULL DEFAULT 'Standard',
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS drop_ship_product_mappings (
+    id TEXT PRIMARY KEY,
+    vendor_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    vendor_sku TEXT NOT NULL,
+    vendor_product_name TEXT,
+    vendor_cost INTEGER NOT NULL DEFAULT 0,
+    vendor_msrp INTEGER,
+    vendor_map INTEGER,
+    min_order_qty INTEGER NOT NULL DEFAULT 1,
+    lead_time_days INTEGER NOT NULL DEFAULT 2,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE(vendor_id, product_id)
+);
+CREATE INDEX IF NOT EXISTS idx_dropship_orders_vendor ON drop_ship_orders(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_orders_customer ON drop_ship_orders(customer_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_orders_status ON drop_ship_orders(status);
+CREATE INDEX IF NOT EXISTS idx_dropship_orders_sales_order ON drop_ship_orders(sales_order_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_order_lines_order ON drop_ship_order_lines(drop_ship_order_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_order_lines_product ON drop_ship_order_lines(product_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_po_vendor ON drop_ship_purchase_orders(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_po_lines_product ON drop_ship_po_lines(product_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_shipments_order ON drop_ship_shipments(drop_ship_order_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_shipments_tracking ON drop_ship_shipments(tracking_number);
+CREATE INDEX IF NOT EXISTS idx_dropship_shipments_vendor ON drop_ship_shipments(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_inventory_vendor ON drop_ship_inventory_items(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_inventory_product ON drop_ship_inventory_items(product_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_invoices_vendor ON drop_ship_invoices(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_invoices_order ON drop_ship_invoices(drop_ship_order_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_invoice_lines_product ON drop_ship_invoice_lines(product_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_returns_vendor ON drop_ship_returns(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_returns_order ON drop_ship_returns(drop_ship_order_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_performance_vendor ON drop_ship_performance(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_performance_period ON drop_ship_performance(period_start, period_end);
+CREATE INDEX IF NOT EXISTS idx_dropship_mappings_vendor ON drop_ship_product_mappings(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_dropship_mappings_product ON drop_ship_product_mappings(product_id);
+CREATE TABLE IF NOT EXISTS product_bundles (
+    id TEXT PRIMARY KEY,
+    bundle_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    bundle_type TEXT NOT NULL DEFAULT 'SalesKit',
+    pricing_method TEXT NOT NULL DEFAULT 'FixedPrice',
+    list_price_cents INTEGER NOT NULL DEFAULT 0,
+    list_price_currency TEXT NOT NULL DEFAULT 'USD',
+    calculated_price_cents INTEGER NOT NULL DEFAULT 0,
+    calculated_price_currency TEXT NOT NULL DEFAULT 'USD',
+    discount_percent REAL,
+    auto_explode INTEGER NOT NULL DEFAULT 0,
+    track_inventory INTEGER NOT NULL DEFAULT 1,
+    availability_date TEXT,
+    expiry_date TEXT,
+    max_quantity_per_order INTEGER,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_bundles_code ON product_bundles(bundle_code);
+CREATE INDEX IF NOT EXISTS idx_bundles_status ON product_bundles(status);
+CREATE INDEX IF NOT EXISTS idx_bundles_type ON product_bundles(bundle_type);
+CREATE TABLE IF NOT EXISTS bundle_components (
+    id TEXT PRIMARY KEY,
+    bundle_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    unit_of_measure TEXT NOT NULL DEFAULT 'PCS',
+    is_mandatory INTEGER NOT NULL DEFAULT 1,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    component_price_cents INTEGER NOT NULL DEFAULT 0,
+    component_price_currency TEXT NOT NULL DEFAULT 'USD',
+    discount_percent REAL,
+    can_substitute INTEGER NOT NULL DEFAULT 0,
+    substitute_group_id TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (bundle_id) REFERENCES product_bundles(id) ON DELETE CASCADE,
+    FOREIGN KEY (product_id) REFERENCES products(id)
+);
+CREATE INDEX IF NOT EXISTS idx_bundle_components_bundle ON bundle_components(bundle_id);
+CREATE INDEX IF NOT EXISTS idx_bundle_components_product ON bundle_components(product_id);
+CREATE TABLE IF NOT EXISTS bundle_substitute_groups (
+    id TEXT PRIMARY KEY,
+    bundle_id TEXT NOT NULL,
+    group_name TEXT NOT NULL,
+    min_select INTEGER NOT NULL DEFAULT 1,
+    max_select INTEGER NOT NULL DEFAULT 1,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (bundle_id) REFERENCES product_bundles(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS bundle_price_rules (
+    id TEXT PRIMARY KEY,
+    bundle_id TEXT NOT NULL,
+    rule_name TEXT NOT NULL,
+    rule_type TEXT NOT NULL DEFAULT 'QuantityBreak',
+    min_quantity INTEGER NOT NULL DEFAULT 1,
+    max_quantity INTEGER,
+    discount_percent REAL,
+    fixed_price INTEGER,
+    start_date TEXT,
+    end_date TEXT,
+    customer_group_id TEXT,
+    priority INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (bundle_id) REFERENCES product_bundles(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_bundle_price_rules_bundle ON bundle_price_rules(bundle_id);
+CREATE INDEX IF NOT EXISTS idx_bundle_price_rules_type ON bundle_price_rules(rule_type);
+CREATE TABLE IF NOT EXISTS bundle_inventory (
+    id TEXT PRIMARY KEY,
+    bundle_id TEXT NOT NULL,
+    warehouse_id TEXT NOT NULL,
+    available_quantity INTEGER NOT NULL DEFAULT 0,
+    allocated_quantity INTEGER NOT NULL DEFAULT 0,
+    backorder_quantity INTEGER NOT NULL DEFAULT 0,
+    last_calculated TEXT NOT NULL,
+    FOREIGN KEY (bundle_id) REFERENCES product_bundles(id) ON DELETE CASCADE,
+    FOREIGN KEY (warehouse_id) REFERENCES warehouses(id),
+    UNIQUE(bundle_id, warehouse_id)
+);
+CREATE TABLE IF NOT EXISTS bundle_usage (
+    id TEXT PRIMARY KEY,
+    bundle_id TEXT NOT NULL,
+    order_id TEXT,
+    order_line_id TEXT,
+    invoice_id TEXT,
+    customer_id TEXT,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    unit_price INTEGER NOT NULL DEFAULT 0,
+    total_price INTEGER NOT NULL DEFAULT 0,
+    margin_amount INTEGER NOT NULL DEFAULT 0,
+    margin_percent REAL NOT NULL DEFAULT 0,
+    usage_date TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (bundle_id) REFERENCES product_bundles(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_bundle_usage_bundle ON bundle_usage(bundle_id);
+CREATE INDEX IF NOT EXISTS idx_bundle_usage_date ON bundle_usage(usage_date);
+CREATE INDEX IF NOT EXISTS idx_bundle_usage_customer ON bundle_usage(customer_id);
+CREATE TABLE IF NOT EXISTS bundle_templates (
+    id TEXT PRIMARY KEY,
+    template_code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    default_pricing_method TEXT NOT NULL DEFAULT 'FixedPrice',
+    default_markup_percent REAL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS bundle_template_components (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL,
+    product_id TEXT,
+    product_category_id TEXT,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    is_mandatory INTEGER NOT NULL DEFAULT 1,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (template_id) REFERENCES bundle_templates(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_template_components_template ON bundle_template_components(template_id);
+CREATE TABLE IF NOT EXISTS warranty_policies (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    warranty_type TEXT NOT NULL DEFAULT 'Standard',
+    duration_value INTEGER NOT NULL DEFAULT 12,
+    duration_unit TEXT NOT NULL DEFAULT 'Months',
+    coverage_percentage REAL NOT NULL DEFAULT 100.0,
+    labor_covered INTEGER NOT NULL DEFAULT 1,
+    parts_covered INTEGER NOT NULL DEFAULT 1,
+    on_site_service INTEGER NOT NULL DEFAULT 0,
+    max_claims INTEGER,
+    deductible_amount INTEGER NOT NULL DEFAULT 0,
+    terms_and_conditions TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_warranty_policies_code ON warranty_policies(code);
+CREATE INDEX IF NOT EXISTS idx_warranty_policies_status ON warranty_policies(status);
+CREATE TABLE IF NOT EXISTS product_warranties (
+    id TEXT PRIMARY KEY,
+    warranty_number TEXT NOT NULL UNIQUE,
+    policy_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    sales_order_id TEXT,
+    sales_order_line_id TEXT,
+    serial_number TEXT,
+    lot_number TEXT,
+    purchase_date TEXT NOT NULL,
+    activation_date TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    transferred_to_customer_id TEXT,
+    transferred_at TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT,
+    FOREIGN KEY (policy_id) REFERENCES warranty_policies(id)
+);
+CREATE INDEX IF NOT EXISTS idx_product_warranties_number ON product_warranties(warranty_number);
+CREATE INDEX IF NOT EXISTS idx_product_warranties_customer ON product_warranties(customer_id);
+CREATE INDEX IF NOT EXISTS idx_product_warranties_product ON product_warranties(product_id);
+CREATE INDEX IF NOT EXISTS idx_product_warranties_status ON product_warranties(status);
+CREATE INDEX IF NOT EXISTS idx_product_warranties_end_date ON product_warranties(end_date);
+CREATE TABLE IF NOT EXISTS warranty_claims (
+    id TEXT PRIMARY KEY,
+    claim_number TEXT NOT NULL UNIQUE,
+    product_warranty_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    reported_date TEXT NOT NULL,
+    issue_description TEXT NOT NULL,
+    issue_category TEXT,
+    symptom_codes TEXT,
+    status TEXT NOT NULL DEFAULT 'Submitted',
+    priority INTEGER NOT NULL DEFAULT 3,
+    assigned_to TEXT,
+    assigned_at TEXT,
+    resolution_type TEXT,
+    resolution_notes TEXT,
+    resolved_at TEXT,
+    resolved_by TEXT,
+    customer_notified INTEGER NOT NULL DEFAULT 0,
+    notification_date TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT,
+    FOREIGN KEY (product_warranty_id) REFERENCES product_warranties(id)
+);
+CREATE INDEX IF NOT EXISTS idx_warranty_claims_number ON warranty_claims(claim_number);
+CREATE INDEX IF NOT EXISTS idx_warranty_claims_warranty ON warranty_claims(product_warranty_id);
+CREATE INDEX IF NOT EXISTS idx_warranty_claims_customer ON warranty_claims(customer_id);
+CREATE INDEX IF NOT EXISTS idx_warranty_claims_status ON warranty_claims(status);
+CREATE INDEX IF NOT EXISTS idx_warranty_claims_assigned ON warranty_claims(assigned_to);
+CREATE TABLE IF NOT EXISTS warranty_claim_lines (
+    id TEXT PRIMARY KEY,
+    claim_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_cost INTEGER NOT NULL,
+    total_cost INTEGER NOT NULL,
+    coverage_percentage REAL NOT NULL DEFAULT 100.0,
+    covered_amount INTEGER NOT NULL DEFAULT 0,
+    customer_amount INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (claim_id) REFERENCES warranty_claims(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_warranty_claim_lines_claim ON warranty_claim_lines(claim_id);
+CREATE TABLE IF NOT EXISTS warranty_claim_labor (
+    id TEXT PRIMARY KEY,
+    claim_id TEXT NOT NULL,
+    technician_id TEXT,
+    work_description TEXT NOT NULL,
+    labor_hours REAL NOT NULL,
+    hourly_rate INTEGER NOT NULL,
+    total_cost INTEGER NOT NULL,
+    covered_amount INTEGER NOT NULL DEFAULT 0,
+    customer_amount INTEGER NOT NULL DEFAULT 0,
+    work_date TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (claim_id) REFERENCES warranty_claims(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_warranty_claim_labor_claim ON warranty_claim_labor(claim_id);
+CREATE TABLE IF NOT EXISTS warranty_registrations (
+    id TEXT PRIMARY KEY,
+    product_warranty_id TEXT NOT NULL,
+    customer_id TEXT NOT NULL,
+    registration_date TEXT NOT NULL,
+    registration_source TEXT NOT NULL,
+    verified INTEGER NOT NULL DEFAULT 0,
+    verified_at TEXT,
+    verified_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT,
+    FOREIGN KEY (product_warranty_id) REFERENCES product_warranties(id)
+);
+CREATE INDEX IF NOT EXISTS idx_warranty_registrations_warranty ON warranty_registrations(product_warranty_id);
+CREATE INDEX IF NOT EXISTS idx_warranty_registrations_customer ON warranty_registrations(customer_id);
+CREATE TABLE IF NOT EXISTS warranty_extensions (
+    id TEXT PRIMARY KEY,
+    product_warranty_id TEXT NOT NULL,
+    policy_id TEXT NOT NULL,
+    extension_date TEXT NOT NULL,
+    additional_duration_value INTEGER NOT NULL,
+    additional_duration_unit TEXT NOT NULL DEFAULT 'Months',
+    new_end_date TEXT NOT NULL,
+    cost INTEGER NOT NULL DEFAULT 0,
+    invoice_id TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT,
+    FOREIGN KEY (product_warranty_id) REFERENCES product_warranties(id),
+    FOREIGN KEY (policy_id) REFERENCES warranty_policies(id)
+);
+CREATE INDEX IF NOT EXISTS idx_warranty_extensions_warranty ON warranty_extensions(product_warranty_id);
+CREATE INDEX IF NOT EXISTS idx_warranty_extensions_status ON warranty_extensions(status);
+CREATE TABLE IF NOT EXISTS vendor_bills (
+    id TEXT PRIMARY KEY,
+    bill_number TEXT NOT NULL UNIQUE,
+    vendor_invoice_number TEXT NOT NULL,
+    vendor_id TEXT NOT NULL,
+    purchase_order_id TEXT,
+    bill_date TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    tax_amount INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    amount_paid INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    match_status TEXT NOT NULL DEFAULT 'Unmatched',
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS vendor_bill_lines (
+    id TEXT PRIMARY KEY,
+    bill_id TEXT NOT NULL,
+    po_line_id TEXT,
+    product_id TEXT,
+    description TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    tax_rate REAL NOT NULL DEFAULT 0,
+    line_total INTEGER NOT NULL DEFAULT 0,
+    match_quantity INTEGER NOT NULL DEFAULT 0,
+    match_status TEXT NOT NULL DEFAULT 'Unmatched',
+    FOREIGN KEY (bill_id) REFERENCES vendor_bills(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS vendor_bill_payments (
+    id TEXT PRIMARY KEY,
+    bill_id TEXT NOT NULL,
+    payment_id TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    applied_at TEXT NOT NULL,
+    FOREIGN KEY (bill_id) REFERENCES vendor_bills(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_vendor_bills_vendor_id ON vendor_bills(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_vendor_bills_status ON vendor_bills(status);
+CREATE INDEX IF NOT EXISTS idx_vendor_bills_bill_date ON vendor_bills(bill_date);
+CREATE INDEX IF NOT EXISTS idx_vendor_bills_due_date ON vendor_bills(due_date);
+CREATE INDEX IF NOT EXISTS idx_vendor_bills_po_id ON vendor_bills(purchase_order_id);
+CREATE INDEX IF NOT EXISTS idx_vendor_bill_lines_bill_id ON vendor_bill_lines(bill_id);
+CREATE INDEX IF NOT EXISTS idx_vendor_bill_payments_bill_id ON vendor_bill_payments(bill_id);
+CREATE TABLE IF NOT EXISTS inventory_adjustments (
+    id TEXT PRIMARY KEY,
+    adjustment_number TEXT NOT NULL UNIQUE,
+    warehouse_id TEXT NOT NULL,
+    adjustment_type TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    total_value_change INTEGER NOT NULL DEFAULT 0,
+    approved_by TEXT,
+    approved_at TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT
+);
+CREATE TABLE IF NOT EXISTS inventory_adjustment_lines (
+    id TEXT PRIMARY KEY,
+    adjustment_id TEXT NOT NULL REFERENCES inventory_adjustments(id) ON DELETE CASCADE,
+    product_id TEXT NOT NULL,
+    location_id TEXT NOT NULL,
+    system_quantity INTEGER NOT NULL,
+    counted_quantity INTEGER NOT NULL,
+    adjustment_quantity INTEGER NOT NULL,
+    unit_cost INTEGER NOT NULL DEFAULT 0,
+    total_value_change INTEGER NOT NULL DEFAULT 0,
+    lot_number TEXT,
+    serial_number TEXT,
+    reason_code TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_inventory_adjustments_warehouse ON inventory_adjustments(warehouse_id);
+CREATE INDEX IF NOT EXISTS idx_inventory_adjustments_status ON inventory_adjustments(status);
+CREATE INDEX IF NOT EXISTS idx_inventory_adjustment_lines_adjustment ON inventory_adjustment_lines(adjustment_id);
+CREATE INDEX IF NOT EXISTS idx_inventory_adjustment_lines_product ON inventory_adjustment_lines(product_id);
+CREATE TABLE IF NOT EXISTS stock_transfers (
+    id TEXT PRIMARY KEY,
+    transfer_number TEXT NOT NULL UNIQUE,
+    from_warehouse_id TEXT NOT NULL,
+    to_warehouse_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    priority TEXT NOT NULL DEFAULT 'Normal',
+    requested_date TEXT,
+    expected_date TEXT,
+    shipped_date TEXT,
+    received_date TEXT,
+    approved_by TEXT,
+    approved_at TEXT,
+    shipped_by TEXT,
+    received_by TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT
+);
+CREATE TABLE IF NOT EXISTS stock_transfer_lines (
+    id TEXT PRIMARY KEY,
+    transfer_id TEXT NOT NULL REFERENCES stock_transfers(id) ON DELETE CASCADE,
+    product_id TEXT NOT NULL,
+    requested_quantity INTEGER NOT NULL,
+    shipped_quantity INTEGER NOT NULL DEFAULT 0,
+    received_quantity INTEGER NOT NULL DEFAULT 0,
+    unit_cost INTEGER NOT NULL DEFAULT 0,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_stock_transfers_from_warehouse ON stock_transfers(from_warehouse_id);
+CREATE INDEX IF NOT EXISTS idx_stock_transfers_to_warehouse ON stock_transfers(to_warehouse_id);
+CREATE INDEX IF NOT EXISTS idx_stock_transfers_status ON stock_transfers(status);
+CREATE INDEX IF NOT EXISTS idx_stock_transfer_lines_transfer ON stock_transfer_lines(transfer_id);
+CREATE INDEX IF NOT EXISTS idx_stock_transfer_lines_product ON stock_transfer_lines(product_id);
+CREATE TABLE IF NOT EXISTS payment_terms (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    due_days INTEGER NOT NULL DEFAULT 30,
+    discount_days INTEGER,
+    discount_percent REAL,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+INSERT OR IGNORE INTO payment_terms (id, code, name, description, due_days, discount_days, discount_percent, is_default, status, created_at, updated_at) VALUES ('00000000-0000-0000-0000-000000000001', 'NET30', 'Net 30', 'Payment due within 30 days', 30, NULL, NULL, 1, 'Active', '2026-10-18 16:36:14', '2026-10-18 16:36:14');
+INSERT OR IGNORE INTO payment_terms (id, code, name, description, due_days, discount_days, discount_percent, is_default, status, created_at, updated_at) VALUES ('00000000-0000-0000-0000-000000000002', 'NET60', 'Net 60', 'Payment due within 60 days', 60, NULL, NULL, 0, 'Active', '2026-10-18 16:36:14', '2026-10-18 16:36:14');
+INSERT OR IGNORE INTO payment_terms (id, code, name, description, due_days, discount_days, discount_percent, is_default, status, created_at, updated_at) VALUES ('00000000-0000-0000-0000-000000000003', 'NET90', 'Net 90', 'Payment due within 90 days', 90, NULL, NULL, 0, 'Active', '2026-10-18 16:36:14', '2026-10-18 16:36:14');
+INSERT OR IGNORE INTO payment_terms (id, code, name, description, due_days, discount_days, discount_percent, is_default, status, created_at, updated_at) VALUES ('00000000-0000-0000-0000-000000000004', '2_10_NET30', '2/10 Net 30', '2% discount if paid within 10 days, otherwise due in 30 days', 30, 10, 2.0, 0, 'Active', '2026-10-18 16:36:14', '2026-10-18 16:36:14');
+INSERT OR IGNORE INTO payment_terms (id, code, name, description, due_days, discount_days, discount_percent, is_default, status, created_at, updated_at) VALUES ('00000000-0000-0000-0000-000000000005', 'COD', 'Cash on Delivery', 'Payment required upon delivery', 0, NULL, NULL, 0, 'Active', '2026-10-18 16:36:14', '2026-10-18 16:36:14');
+INSERT OR IGNORE INTO payment_terms (id, code, name, description, due_days, discount_days, discount_percent, is_default, status, created_at, updated_at) VALUES ('00000000-0000-0000-0000-000000000006', 'PREPAID', 'Prepaid', 'Payment required before shipment', 0, NULL, NULL, 0, 'Active', '2026-10-18 16:36:14', '2026-10-18 16:36:14');
+INSERT OR IGNORE INTO payment_terms (id, code, name, description, due_days, discount_days, discount_percent, is_default, status, created_at, updated_at) VALUES ('00000000-0000-0000-0000-000000000007', 'EOM', 'End of Month', 'Payment due at the end of the month following invoice', 30, NULL, NULL, 0, 'Active', '2026-10-18 16:36:14', '2026-10-18 16:36:14');
+INSERT OR IGNORE INTO payment_terms (id, code, name, description, due_days, discount_days, discount_percent, is_default, status, created_at, updated_at) VALUES ('00000000-0000-0000-0000-000000000008', '1_10_NET30', '1/10 Net 30', '1% discount if paid within 10 days, otherwise due in 30 days', 30, 10, 1.0, 0, 'Active', '2026-10-18 16:36:14', '2026-10-18 16:36:14');
+CREATE INDEX IF NOT EXISTS idx_payment_terms_code ON payment_terms(code);
+CREATE INDEX IF NOT EXISTS idx_payment_terms_is_default ON payment_terms(is_default);
+CREATE INDEX IF NOT EXISTS idx_payment_terms_status ON payment_terms(status);
+CREATE TABLE IF NOT EXISTS shifts (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    break_minutes INTEGER NOT NULL DEFAULT 30,
+    grace_period_minutes INTEGER NOT NULL DEFAULT 15,
+    color_code TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS schedules (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    department_id TEXT,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (department_id) REFERENCES departments(id)
+);
+CREATE TABLE IF NOT EXISTS shift_assignments (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT NOT NULL,
+    shift_id TEXT NOT NULL,
+    employee_id TEXT NOT NULL,
+    assignment_date TEXT NOT NULL,
+    actual_start_time TEXT,
+    actual_end_time TEXT,
+    overtime_minutes INTEGER NOT NULL DEFAULT 0,
+    notes TEXT,
+    status TEXT NOT NULL DEFAULT 'Scheduled',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (schedule_id) REFERENCES schedules(id) ON DELETE CASCADE,
+    FOREIGN KEY (shift_id) REFERENCES shifts(id),
+    FOREIGN KEY (employee_id) REFERENCES employees(id)
+);
+CREATE TABLE IF NOT EXISTS schedule_entries (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT NOT NULL,
+    day_of_week INTEGER NOT NULL,
+    shift_id TEXT NOT NULL,
+    employee_id TEXT NOT NULL,
+    is_recurring INTEGER NOT NULL DEFAULT 1,
+    effective_from TEXT,
+    effective_to TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (schedule_id) REFERENCES schedules(id) ON DELETE CASCADE,
+    FOREIGN KEY (shift_id) REFERENCES shifts(id),
+    FOREIGN KEY (employee_id) REFERENCES employees(id)
+);
+CREATE TABLE IF NOT EXISTS shift_swap_requests (
+    id TEXT PRIMARY KEY,
+    schedule_id TEXT NOT NULL,
+    requesting_employee_id TEXT NOT NULL,
+    target_employee_id TEXT NOT NULL,
+    request_date TEXT NOT NULL,
+    reason TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    approved_by TEXT,
+    approved_at TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (schedule_id) REFERENCES schedules(id) ON DELETE CASCADE,
+    FOREIGN KEY (requesting_employee_id) REFERENCES employees(id),
+    FOREIGN KEY (target_employee_id) REFERENCES employees(id),
+    FOREIGN KEY (approved_by) REFERENCES users(id)
+);
+CREATE INDEX IF NOT EXISTS idx_shift_assignments_schedule ON shift_assignments(schedule_id);
+CREATE INDEX IF NOT EXISTS idx_shift_assignments_employee ON shift_assignments(employee_id);
+CREATE INDEX IF NOT EXISTS idx_shift_assignments_date ON shift_assignments(assignment_date);
+CREATE INDEX IF NOT EXISTS idx_schedules_department ON schedules(department_id);
+CREATE INDEX IF NOT EXISTS idx_shifts_status ON shifts(status);
+CREATE INDEX IF NOT EXISTS idx_ncr_product ON non_conformance_reports(product_id);
+CREATE TABLE IF NOT EXISTS notes (
+    id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    note_type TEXT NOT NULL DEFAULT 'General',
+    title TEXT,
+    content TEXT NOT NULL,
+    is_private INTEGER NOT NULL DEFAULT 0,
+    is_pinned INTEGER NOT NULL DEFAULT 0,
+    reminder_at TEXT,
+    reminded_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_notes_entity ON notes(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_notes_created_by ON notes(created_by);
+CREATE INDEX IF NOT EXISTS idx_notes_reminder ON notes(reminder_at) WHERE reminder_at IS NOT NULL AND reminded_at IS NULL;
+CREATE TABLE IF NOT EXISTS product_attributes (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    attribute_type TEXT NOT NULL DEFAULT 'Select',
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS product_attribute_values (
+    id TEXT PRIMARY KEY,
+    attribute_id TEXT NOT NULL,
+    value TEXT NOT NULL,
+    display_value TEXT NOT NULL,
+    color_code TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (attribute_id) REFERENCES product_attributes(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS product_variants (
+    id TEXT PRIMARY KEY,
+    product_id TEXT NOT NULL,
+    sku TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    price_adjustment INTEGER NOT NULL DEFAULT 0,
+    cost_adjustment INTEGER NOT NULL DEFAULT 0,
+    barcode TEXT,
+    weight_kg REAL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS product_variant_attribute_values (
+    variant_id TEXT NOT NULL,
+    attribute_id TEXT NOT NULL,
+    attribute_name TEXT NOT NULL,
+    value_id TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (variant_id, attribute_id),
+    FOREIGN KEY (variant_id) REFERENCES product_variants(id) ON DELETE CASCADE,
+    FOREIGN KEY (attribute_id) REFERENCES product_attributes(id),
+    FOREIGN KEY (value_id) REFERENCES product_attribute_values(id)
+);
+CREATE INDEX IF NOT EXISTS idx_product_attributes_name ON product_attributes(name);
+CREATE INDEX IF NOT EXISTS idx_product_attribute_values_attribute ON product_attribute_values(attribute_id);
+CREATE INDEX IF NOT EXISTS idx_product_variants_product ON product_variants(product_id);
+CREATE INDEX IF NOT EXISTS idx_product_variants_sku ON product_variants(sku);
+CREATE INDEX IF NOT EXISTS idx_product_variant_attr_values_variant ON product_variant_attribute_values(variant_id);
+CREATE TABLE IF NOT EXISTS gift_cards (
+    id TEXT PRIMARY KEY,
+    card_number TEXT NOT NULL UNIQUE,
+    pin TEXT,
+    barcode TEXT,
+    gift_card_type TEXT NOT NULL DEFAULT 'Digital',
+    initial_balance INTEGER NOT NULL,
+    current_balance INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    customer_id TEXT,
+    order_id TEXT,
+    purchased_by TEXT,
+    recipient_email TEXT,
+    recipient_name TEXT,
+    message TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    issued_date TEXT NOT NULL,
+    expiry_date TEXT,
+    last_used_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS gift_card_transactions (
+    id TEXT PRIMARY KEY,
+    transaction_number TEXT NOT NULL UNIQUE,
+    gift_card_id TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    balance_before INTEGER NOT NULL,
+    balance_after INTEGER NOT NULL,
+    order_id TEXT,
+    reference TEXT,
+    notes TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (gift_card_id) REFERENCES gift_cards(id)
+);
+CREATE TABLE IF NOT EXISTS gift_card_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    initial_amount INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    is_reloadable INTEGER NOT NULL DEFAULT 1,
+    validity_months INTEGER NOT NULL DEFAULT 12,
+    design_url TEXT,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_gift_cards_card_number ON gift_cards(card_number);
+CREATE INDEX IF NOT EXISTS idx_gift_cards_customer_id ON gift_cards(customer_id);
+CREATE INDEX IF NOT EXISTS idx_gift_cards_status ON gift_cards(status);
+CREATE INDEX IF NOT EXISTS idx_gift_card_transactions_gift_card_id ON gift_card_transactions(gift_card_id);
+CREATE TABLE IF NOT EXISTS favorites (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    favorite_type TEXT NOT NULL,
+    entity_id TEXT,
+    entity_name TEXT NOT NULL,
+    entity_code TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_favorites_user_id ON favorites(user_id);
+CREATE INDEX IF NOT EXISTS idx_favorites_user_type ON favorites(user_id, favorite_type);
+CREATE INDEX IF NOT EXISTS idx_favorites_entity ON favorites(user_id, favorite_type, entity_id);
+CREATE TABLE IF NOT EXISTS cycle_counts (
+    id TEXT PRIMARY KEY,
+    count_number TEXT NOT NULL UNIQUE,
+    warehouse_id TEXT NOT NULL REFERENCES warehouses(id),
+    name TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    planned_date TEXT NOT NULL,
+    completed_at TEXT,
+    created_by TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cycle_count_lines (
+    id TEXT PRIMARY KEY,
+    cycle_count_id TEXT NOT NULL REFERENCES cycle_counts(id) ON DELETE CASCADE,
+    product_id TEXT NOT NULL REFERENCES products(id),
+    location_id TEXT NOT NULL REFERENCES stock_locations(id),
+    expected_quantity INTEGER NOT NULL,
+    actual_quantity INTEGER,
+    adjustment_qty INTEGER,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    notes TEXT,
+    UNIQUE(cycle_count_id, product_id, location_id)
+);
+CREATE INDEX IF NOT EXISTS idx_cycle_counts_warehouse ON cycle_counts(warehouse_id);
+CREATE INDEX IF NOT EXISTS idx_cycle_count_lines_count ON cycle_count_lines(cycle_count_id);
+CREATE TABLE IF NOT EXISTS landed_cost_categories (
+    id TEXT PRIMARY KEY,
+    code TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    allocation_method TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Active',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS landed_cost_vouchers (
+    id TEXT PRIMARY KEY,
+    voucher_number TEXT NOT NULL UNIQUE,
+    voucher_date TEXT NOT NULL,
+    reference_type TEXT NOT NULL,
+    reference_id TEXT NOT NULL,
+    total_landed_cost INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'USD',
+    status TEXT NOT NULL DEFAULT 'Draft',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS landed_cost_lines (
+    id TEXT PRIMARY KEY,
+    voucher_id TEXT NOT NULL REFERENCES landed_cost_vouchers(id) ON DELETE CASCADE,
+    category_id TEXT NOT NULL REFERENCES landed_cost_categories(id),
+    description TEXT NOT NULL,
+    amount INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS landed_cost_allocations (
+    id TEXT PRIMARY KEY,
+    voucher_id TEXT NOT NULL REFERENCES landed_cost_vouchers(id) ON DELETE CASCADE,
+    item_id TEXT NOT NULL,
+    allocated_amount INTEGER NOT NULL,
+    allocation_factor REAL NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_landed_cost_vouchers_ref ON landed_cost_vouchers(reference_id);
+CREATE INDEX IF NOT EXISTS idx_landed_cost_allocations_voucher ON landed_cost_allocations(voucher_id);
+CREATE INDEX IF NOT EXISTS idx_landed_cost_allocations_item ON landed_cost_allocations(item_id);
+CREATE TABLE IF NOT EXISTS subcontract_orders (
+    id TEXT PRIMARY KEY,
+    order_number TEXT NOT NULL UNIQUE,
+    vendor_id TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    service_cost_amount INTEGER NOT NULL,
+    service_cost_currency TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    warehouse_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT,
+    updated_by TEXT
+);
+CREATE TABLE IF NOT EXISTS subcontract_components (
+    id TEXT PRIMARY KEY,
+    order_id TEXT NOT NULL REFERENCES subcontract_orders(id) ON DELETE CASCADE,
+    product_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    sent_quantity INTEGER NOT NULL DEFAULT 0,
+    consumed_quantity INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_subcontract_orders_vendor ON subcontract_orders(vendor_id);
+CREATE INDEX IF NOT EXISTS idx_subcontract_orders_product ON subcontract_orders(product_id);
+CREATE INDEX IF NOT EXISTS idx_subcontract_components_order ON subcontract_components(order_id);
+CREATE TABLE IF NOT EXISTS quality_capas (
+    id TEXT PRIMARY KEY,
+    capa_number TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    source_type TEXT NOT NULL,
+    source_id TEXT,
+    description TEXT NOT NULL,
+    priority TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Draft',
+    initiator_id TEXT NOT NULL,
+    owner_id TEXT,
+    root_cause_analysis TEXT,
+    action_plan TEXT,
+    verification_plan TEXT,
+    effectiveness_criteria TEXT,
+    target_completion_date TEXT,
+    effectiveness_result INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS quality_capa_actions (
+    id TEXT PRIMARY KEY,
+    capa_id TEXT NOT NULL,
+    action_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    assigned_to TEXT,
+    due_date TEXT NOT NULL,
+    completed_at TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    evidence TEXT,
+    FOREIGN KEY (capa_id) REFERENCES quality_capas(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_capa_status ON quality_capas(status);
+CREATE INDEX IF NOT EXISTS idx_capa_priority ON quality_capas(priority);
+CREATE INDEX IF NOT EXISTS idx_capa_actions_capa ON quality_capa_actions(capa_id);
+CREATE TABLE IF NOT EXISTS calibration_devices (
+    id TEXT PRIMARY KEY,
+    device_number TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    manufacturer TEXT,
+    model TEXT,
+    serial_number TEXT,
+    location TEXT,
+    calibration_frequency_days INTEGER NOT NULL,
+    last_calibration_date TEXT,
+    next_calibration_date TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS calibration_records (
+    id TEXT PRIMARY KEY,
+    record_number TEXT NOT NULL UNIQUE,
+    device_id TEXT NOT NULL,
+    calibration_date TEXT NOT NULL,
+    calibrated_by TEXT,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    certificate_number TEXT,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (device_id) REFERENCES calibration_devices(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS calibration_readings (
+    id TEXT PRIMARY KEY,
+    record_id TEXT NOT NULL,
+    parameter TEXT NOT NULL,
+    reference_value REAL NOT NULL,
+    actual_value REAL NOT NULL,
+    tolerance_min REAL NOT NULL,
+    tolerance_max REAL NOT NULL,
+    pass_fail INTEGER NOT NULL,
+    uom TEXT NOT NULL,
+    FOREIGN KEY (record_id) REFERENCES calibration_records(id) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_calibration_devices_status ON calibration_devices(status);
+CREATE INDEX IF NOT EXISTS idx_calibration_records_device ON calibration_records(device_id);
+CREATE INDEX IF NOT EXISTS idx_calibration_readings_record ON calibration_readings(record_id);